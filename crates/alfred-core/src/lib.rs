@@ -61,6 +61,11 @@ impl Item {
         }
     }
 
+    /// Non-actionable row used to label a group of results; Enter and Tab do nothing.
+    pub fn section_header(title: impl Into<String>) -> Self {
+        Self::new(title).with_valid(false)
+    }
+
     pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
         self.subtitle = Some(subtitle.into());
         self
@@ -216,6 +221,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn section_header_is_not_actionable() {
+        let header = Item::section_header("Recently used").with_subtitle("2 projects");
+        let json = serde_json::to_string(&header).expect("serialize header");
+        assert_eq!(
+            json,
+            r#"{"title":"Recently used","subtitle":"2 projects","valid":false}"#
+        );
+    }
+
     #[test]
     fn modifier_and_variables_are_serialized() {
         let item = Item::new("project")
@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::model::{MarketOutput, ValidationError, decimal_to_string, parse_amount};
+use crate::providers::ProviderApi;
+use crate::service::resolve_symbol_output;
+use crate::store::{data_file_path, load_data_file, save_data_file};
+use crate::watchlist::parse_pair;
+
+const ALERTS_FILE_NAME: &str = "alerts.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+impl AlertDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Above => "above",
+            Self::Below => "below",
+        }
+    }
+
+    /// Thresholds are inclusive: `above 70000` is met at exactly 70000.
+    pub fn is_met(self, price: Decimal, threshold: Decimal) -> bool {
+        match self {
+            Self::Above => price >= threshold,
+            Self::Below => price <= threshold,
+        }
+    }
+}
+
+impl fmt::Display for AlertDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One price alert. `triggered` is set when a check notifies and cleared once the condition stops holding, so a
+/// scheduled check notifies once per crossing instead of on every run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: u32,
+    pub base: String,
+    pub quote: String,
+    pub direction: AlertDirection,
+    pub threshold: String,
+    #[serde(default)]
+    pub triggered: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_triggered_at: Option<String>,
+}
+
+impl Alert {
+    pub fn pair(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{} {} {}", self.pair(), self.direction, self.threshold)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertBook {
+    #[serde(default)]
+    pub next_id: u32,
+    #[serde(default)]
+    pub alerts: Vec<Alert>,
+}
+
+impl AlertBook {
+    pub fn add(
+        &mut self,
+        pair: &str,
+        direction: AlertDirection,
+        threshold: &str,
+    ) -> Result<Alert, ValidationError> {
+        let (base, quote) = parse_pair(pair)?;
+        let threshold = decimal_to_string(&parse_amount(threshold)?);
+        let id = self.next_id.max(1);
+        self.next_id = id + 1;
+
+        let alert = Alert {
+            id,
+            base,
+            quote,
+            direction,
+            threshold,
+            triggered: false,
+            last_triggered_at: None,
+        };
+        self.alerts.push(alert.clone());
+        Ok(alert)
+    }
+
+    pub fn remove(&mut self, id: u32) -> Option<Alert> {
+        let index = self.alerts.iter().position(|alert| alert.id == id)?;
+        Some(self.alerts.remove(index))
+    }
+}
+
+pub fn alerts_path(config: &RuntimeConfig) -> PathBuf {
+    data_file_path(config, ALERTS_FILE_NAME)
+}
+
+pub fn load_alerts(path: &Path) -> Result<AlertBook, AppError> {
+    load_data_file(path, "alerts")
+}
+
+pub fn save_alerts(path: &Path, book: &AlertBook) -> Result<(), AppError> {
+    save_data_file(path, "alerts", book)
+}
+
+/// Result of `alert add` / `alert remove`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlertUpdateOutput {
+    pub action: &'static str,
+    pub alert: Alert,
+    pub alerts: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertState {
+    /// Condition newly met on this check; a notification is due.
+    Triggered,
+    /// Condition still met and already notified by an earlier check.
+    Active,
+    /// Condition not met.
+    Armed,
+    /// The quote could not be resolved; the alert keeps its previous state.
+    Unavailable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlertCheck {
+    pub id: u32,
+    pub base: String,
+    pub quote: String,
+    pub direction: AlertDirection,
+    pub threshold: String,
+    pub state: AlertState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AlertCheck {
+    pub fn pair(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+
+    pub fn notification_title(&self) -> String {
+        format!("Market alert: {}", self.pair())
+    }
+
+    pub fn notification_message(&self) -> String {
+        format!(
+            "{} is {} {} ({} {})",
+            self.base,
+            self.price.as_deref().unwrap_or("?"),
+            self.quote,
+            self.direction,
+            self.threshold
+        )
+    }
+}
+
+/// Report of one `alert check` run. `notified` is false for dry runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlertReport {
+    pub checked: usize,
+    pub triggered: Vec<AlertCheck>,
+    pub failed: Vec<AlertCheck>,
+    pub notified: bool,
+}
+
+/// Evaluates every alert against a live quote (one concurrent lookup per distinct pair, through the per-pair quote
+/// cache) and updates each alert's trigger state in `book`.
+pub fn check_alerts<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    book: &mut AlertBook,
+) -> AlertReport
+where
+    P: ProviderApi + Clone + Send,
+    N: Fn() -> DateTime<Utc> + Copy + Send,
+{
+    let quotes = quote_pairs(config, providers, now_fn, &book.alerts);
+    let now = now_fn().to_rfc3339_opts(SecondsFormat::Secs, true);
+    let mut report = AlertReport {
+        checked: book.alerts.len(),
+        triggered: Vec::new(),
+        failed: Vec::new(),
+        notified: false,
+    };
+
+    for alert in &mut book.alerts {
+        let check = evaluate_alert(
+            alert,
+            quotes.get(&(alert.base.clone(), alert.quote.clone())),
+        );
+        match check.state {
+            AlertState::Triggered => {
+                alert.triggered = true;
+                alert.last_triggered_at = Some(now.clone());
+                report.triggered.push(check);
+            }
+            AlertState::Active => {}
+            AlertState::Armed => alert.triggered = false,
+            AlertState::Unavailable => report.failed.push(check),
+        }
+    }
+
+    report
+}
+
+fn quote_pairs<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    alerts: &[Alert],
+) -> HashMap<(String, String), Result<MarketOutput, String>>
+where
+    P: ProviderApi + Clone + Send,
+    N: Fn() -> DateTime<Utc> + Copy + Send,
+{
+    let mut pairs = alerts
+        .iter()
+        .map(|alert| (alert.base.clone(), alert.quote.clone()))
+        .collect::<Vec<_>>();
+    pairs.sort();
+    pairs.dedup();
+
+    std::thread::scope(|scope| {
+        let handles = pairs
+            .into_iter()
+            .map(|pair| {
+                let providers = providers.clone();
+                scope.spawn(move || {
+                    let output =
+                        resolve_symbol_output(config, &providers, now_fn, &pair.0, &pair.1)
+                            .map_err(|error| error.message);
+                    (pair, output)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("alert worker panicked"))
+            .collect()
+    })
+}
+
+fn evaluate_alert(alert: &Alert, quote: Option<&Result<MarketOutput, String>>) -> AlertCheck {
+    let mut check = AlertCheck {
+        id: alert.id,
+        base: alert.base.clone(),
+        quote: alert.quote.clone(),
+        direction: alert.direction,
+        threshold: alert.threshold.clone(),
+        state: AlertState::Unavailable,
+        price: None,
+        provider: None,
+        error: None,
+    };
+
+    let output = match quote {
+        Some(Ok(output)) => output,
+        Some(Err(error)) => {
+            check.error = Some(error.clone());
+            return check;
+        }
+        None => {
+            check.error = Some("quote was not requested".to_string());
+            return check;
+        }
+    };
+    check.price = Some(output.unit_price.clone());
+    check.provider = Some(output.provider.clone());
+
+    let (Ok(price), Ok(threshold)) = (
+        output.unit_price.parse::<Decimal>(),
+        alert.threshold.parse::<Decimal>(),
+    ) else {
+        check.error = Some(format!("invalid price or threshold for {}", alert.pair()));
+        return check;
+    };
+
+    check.state = match (alert.direction.is_met(price, threshold), alert.triggered) {
+        (true, false) => AlertState::Triggered,
+        (true, true) => AlertState::Active,
+        (false, _) => AlertState::Armed,
+    };
+    check
+}
+
+pub fn post_notification(title: &str, message: &str) -> Result<(), AppError> {
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(notification_script(title, message))
+        .output()
+        .map_err(|error| AppError::runtime(format!("failed to launch osascript: {error}")))?;
+    if !output.status.success() {
+        return Err(AppError::runtime(format!(
+            "osascript exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+fn notification_script(title: &str, message: &str) -> String {
+    format!(
+        "display notification {} with title {}",
+        applescript_string(message),
+        applescript_string(title)
+    )
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{CacheMetadata, CacheStatus, MarketKind};
+
+    use super::*;
+
+    fn quote(price: &str) -> Result<MarketOutput, String> {
+        Ok(MarketOutput {
+            kind: MarketKind::Crypto,
+            base: "BTC".to_string(),
+            quote: "USD".to_string(),
+            amount: "1".to_string(),
+            unit: None,
+            unit_price: price.to_string(),
+            converted: price.to_string(),
+            fee_pct: None,
+            converted_after_fee: None,
+            provider: "coinbase".to_string(),
+            fetched_at: "2026-02-10T12:00:00Z".to_string(),
+            cache: CacheMetadata {
+                status: CacheStatus::Live,
+                key: "crypto-btc-usd".to_string(),
+                ttl_secs: 300,
+                age_secs: 0,
+            },
+            provider_failures: Vec::new(),
+            derivation: None,
+        })
+    }
+
+    #[test]
+    fn alerts_book_assigns_ids_and_validates_input() {
+        let mut book = AlertBook::default();
+        let first = book
+            .add("btc/usd", AlertDirection::Above, "70000.00")
+            .expect("add");
+        assert_eq!(first.id, 1);
+        assert_eq!(first.describe(), "BTC/USD above 70000");
+        assert_eq!(
+            book.add("ETH/USD", AlertDirection::Below, "1800")
+                .expect("add")
+                .id,
+            2
+        );
+
+        assert!(book.add("BTCUSD", AlertDirection::Above, "1").is_err());
+        assert!(book.add("BTC/USD", AlertDirection::Above, "-1").is_err());
+
+        assert_eq!(book.remove(1).map(|alert| alert.id), Some(1));
+        assert_eq!(book.remove(1), None);
+        assert_eq!(
+            book.add("BTC/USD", AlertDirection::Above, "1")
+                .expect("add")
+                .id,
+            3
+        );
+    }
+
+    #[test]
+    fn alerts_trigger_once_per_crossing() {
+        let mut alert = AlertBook::default()
+            .add("BTC/USD", AlertDirection::Above, "70000")
+            .expect("add");
+
+        assert_eq!(
+            evaluate_alert(&alert, Some(&quote("69999.99"))).state,
+            AlertState::Armed
+        );
+        let fired = evaluate_alert(&alert, Some(&quote("70000")));
+        assert_eq!(fired.state, AlertState::Triggered);
+        assert_eq!(
+            fired.notification_message(),
+            "BTC is 70000 USD (above 70000)"
+        );
+
+        alert.triggered = true;
+        assert_eq!(
+            evaluate_alert(&alert, Some(&quote("71000"))).state,
+            AlertState::Active
+        );
+        let failed = evaluate_alert(&alert, Some(&Err("timeout".to_string())));
+        assert_eq!(failed.state, AlertState::Unavailable);
+        assert_eq!(failed.error.as_deref(), Some("timeout"));
+    }
+
+    #[test]
+    fn alerts_notification_script_escapes_quotes() {
+        assert_eq!(
+            notification_script("Market \"alert\"", "a\\b"),
+            "display notification \"a\\\\b\" with title \"Market \\\"alert\\\"\""
+        );
+    }
+}
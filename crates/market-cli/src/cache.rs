@@ -3,10 +3,11 @@ use std::io;
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::config::{CRYPTO_TTL_SECS, FX_TTL_SECS, RuntimeConfig};
-use crate::model::MarketKind;
+use crate::config::{CRYPTO_TTL_SECS, FX_TTL_SECS, METAL_TTL_SECS, RuntimeConfig};
+use crate::model::{HistoryPointOutput, MarketKind};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CacheRecord {
@@ -17,6 +18,29 @@ pub struct CacheRecord {
     pub fetched_at: String,
 }
 
+/// Cached stock quote; decimals are stored as strings like `CacheRecord::unit_price`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockCacheRecord {
+    pub symbol: String,
+    pub provider: String,
+    pub price: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_close: Option<String>,
+    pub fetched_at: String,
+}
+
+/// Cached daily series for one pair and lookback window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryCacheRecord {
+    pub base: String,
+    pub quote: String,
+    pub provider: String,
+    pub points: Vec<HistoryPointOutput>,
+    pub fetched_at: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Freshness {
     pub age_secs: u64,
@@ -39,31 +63,93 @@ pub fn cache_path(config: &RuntimeConfig, kind: MarketKind, base: &str, quote: &
         .join(format!("{}.json", cache_key(kind, base, quote)))
 }
 
+pub fn stock_cache_key(symbol: &str) -> String {
+    format!("stock-{}", symbol.to_ascii_lowercase())
+}
+
+pub fn stock_cache_path(config: &RuntimeConfig, symbol: &str) -> PathBuf {
+    config
+        .cache_dir
+        .join("market-cli")
+        .join(format!("{}.json", stock_cache_key(symbol)))
+}
+
+pub fn history_cache_key(kind: MarketKind, base: &str, quote: &str, days: u32) -> String {
+    format!("history-{}-{days}d", cache_key(kind, base, quote))
+}
+
+pub fn history_cache_path(
+    config: &RuntimeConfig,
+    kind: MarketKind,
+    base: &str,
+    quote: &str,
+    days: u32,
+) -> PathBuf {
+    config.cache_dir.join("market-cli").join(format!(
+        "{}.json",
+        history_cache_key(kind, base, quote, days)
+    ))
+}
+
 pub fn ttl_for_kind(kind: MarketKind) -> u64 {
     match kind {
         MarketKind::Fx => FX_TTL_SECS,
         MarketKind::Crypto => CRYPTO_TTL_SECS,
+        MarketKind::Metal => METAL_TTL_SECS,
     }
 }
 
 pub fn read_cache(path: &Path) -> io::Result<Option<CacheRecord>> {
+    read_json(path)
+}
+
+pub fn write_cache(path: &Path, record: &CacheRecord) -> io::Result<()> {
+    write_json(path, record)
+}
+
+pub fn read_stock_cache(path: &Path) -> io::Result<Option<StockCacheRecord>> {
+    read_json(path)
+}
+
+pub fn write_stock_cache(path: &Path, record: &StockCacheRecord) -> io::Result<()> {
+    write_json(path, record)
+}
+
+pub fn read_history_cache(path: &Path) -> io::Result<Option<HistoryCacheRecord>> {
+    read_json(path)
+}
+
+pub fn write_history_cache(path: &Path, record: &HistoryCacheRecord) -> io::Result<()> {
+    write_json(path, record)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<Option<T>> {
     if !path.exists() {
         return Ok(None);
     }
 
     let payload = fs::read_to_string(path)?;
-    let parsed = serde_json::from_str::<CacheRecord>(&payload).ok();
+    let parsed = serde_json::from_str::<T>(&payload).ok();
     Ok(parsed)
 }
 
-pub fn write_cache(path: &Path, record: &CacheRecord) -> io::Result<()> {
+fn write_json<T: Serialize>(path: &Path, record: &T) -> io::Result<()> {
     let payload = serde_json::to_vec(record)
         .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
     write_atomic(path, &payload)
 }
 
 pub fn evaluate_freshness(record: &CacheRecord, now: DateTime<Utc>, ttl_secs: u64) -> Freshness {
-    let fetched_at = parse_fetched_at(record)
+    evaluate_freshness_at(parse_fetched_at(record), now, ttl_secs)
+}
+
+/// Freshness of an entry fetched at `fetched_at`; an unparseable timestamp counts as just past the TTL.
+pub fn evaluate_freshness_at(
+    fetched_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    ttl_secs: u64,
+) -> Freshness {
+    let fetched_at = fetched_at
         .unwrap_or(now - chrono::Duration::seconds((ttl_secs + 1).try_into().unwrap_or(0)));
     let age_secs = now
         .signed_duration_since(fetched_at)
@@ -84,7 +170,7 @@ pub fn parse_fetched_at(record: &CacheRecord) -> Option<DateTime<Utc>> {
         .map(|value| value.with_timezone(&Utc))
 }
 
-fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
     let parent = path.parent().ok_or_else(|| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -124,6 +210,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cache_stock_key_and_record_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = RuntimeConfig {
+            cache_dir: dir.path().to_path_buf(),
+            data_dir: dir.path().to_path_buf(),
+            fx_cache_ttl_secs: FX_TTL_SECS,
+            crypto_cache_ttl_secs: CRYPTO_TTL_SECS,
+            stock_cache_ttl_secs: crate::config::STOCK_TTL_SECS,
+            metal_cache_ttl_secs: crate::config::METAL_TTL_SECS,
+            crypto_providers: crate::config::DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+            fees: crate::config::FeeSchedule::default(),
+        };
+        let path = stock_cache_path(&config, "BRK-B");
+        assert!(path.ends_with("market-cli/stock-brk-b.json"));
+
+        let record = StockCacheRecord {
+            symbol: "BRK-B".to_string(),
+            provider: "stooq".to_string(),
+            price: "480.1".to_string(),
+            currency: None,
+            previous_close: None,
+            fetched_at: "2026-02-10T12:00:00Z".to_string(),
+        };
+        write_stock_cache(&path, &record).expect("write");
+        assert_eq!(read_stock_cache(&path).expect("read"), Some(record));
+    }
+
     #[test]
     fn cache_read_write_roundtrip() {
         let dir = tempfile::tempdir().expect("tempdir");
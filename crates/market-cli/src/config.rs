@@ -1,11 +1,22 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use rust_decimal::Decimal;
+use workflow_common::split_ordered_list;
+
 use crate::icon_asset_filename;
-use crate::model::{MarketKind, ValidationError};
+use crate::model::{CryptoProvider, MarketKind, ValidationError};
 
 pub const FX_TTL_SECS: u64 = 24 * 60 * 60;
 pub const CRYPTO_TTL_SECS: u64 = 5 * 60;
+pub const STOCK_TTL_SECS: u64 = 15 * 60;
+pub const METAL_TTL_SECS: u64 = 15 * 60;
+pub const DEFAULT_CRYPTO_PROVIDERS: [CryptoProvider; 4] = [
+    CryptoProvider::Coinbase,
+    CryptoProvider::Kraken,
+    CryptoProvider::Binance,
+    CryptoProvider::Coingecko,
+];
 pub const ICON_SOURCE_PACKAGE: &str = "cryptocurrency-icons";
 pub const ICON_SOURCE_VERSION: &str = "0.18.1";
 pub const ICON_SOURCE_CDN_BASE_URL: &str =
@@ -14,8 +25,13 @@ pub const ICON_PNG_VARIANT_DIR: &str = "32/color";
 pub const ICON_GENERIC_BASENAME: &str = "generic.png";
 
 pub const MARKET_CACHE_DIR_ENV: &str = "MARKET_CACHE_DIR";
+pub const MARKET_DATA_DIR_ENV: &str = "MARKET_DATA_DIR";
 pub const MARKET_FX_CACHE_TTL_ENV: &str = "MARKET_FX_CACHE_TTL";
 pub const MARKET_CRYPTO_CACHE_TTL_ENV: &str = "MARKET_CRYPTO_CACHE_TTL";
+pub const MARKET_STOCK_CACHE_TTL_ENV: &str = "MARKET_STOCK_CACHE_TTL";
+pub const MARKET_METAL_CACHE_TTL_ENV: &str = "MARKET_METAL_CACHE_TTL";
+pub const MARKET_CRYPTO_PROVIDERS_ENV: &str = "MARKET_CRYPTO_PROVIDERS";
+pub const MARKET_FEE_PCT_ENV: &str = "MARKET_FEE_PCT";
 const ALFRED_WORKFLOW_CACHE_ENV: &str = "ALFRED_WORKFLOW_CACHE";
 const ALFRED_WORKFLOW_DATA_ENV: &str = "ALFRED_WORKFLOW_DATA";
 const HOME_ENV: &str = "HOME";
@@ -27,8 +43,15 @@ pub const PROVIDER_RETRY_BASE_BACKOFF_MS: u64 = 200;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeConfig {
     pub cache_dir: PathBuf,
+    /// Persistent user data such as the watchlist; unlike `cache_dir` it must survive cache cleanup.
+    pub data_dir: PathBuf,
     pub fx_cache_ttl_secs: u64,
     pub crypto_cache_ttl_secs: u64,
+    pub stock_cache_ttl_secs: u64,
+    pub metal_cache_ttl_secs: u64,
+    /// Crypto spot providers in fallback order; never empty.
+    pub crypto_providers: Vec<CryptoProvider>,
+    pub fees: FeeSchedule,
 }
 
 impl RuntimeConfig {
@@ -46,14 +69,28 @@ impl RuntimeConfig {
             .into_iter()
             .map(|(k, v)| (k.into(), v.into()))
             .collect();
+        let cache_dir = resolve_cache_dir(&map);
         Self {
-            cache_dir: resolve_cache_dir(&map),
+            data_dir: resolve_data_dir(&map).unwrap_or_else(|| cache_dir.clone()),
+            cache_dir,
             fx_cache_ttl_secs: resolve_cache_ttl_secs(&map, MARKET_FX_CACHE_TTL_ENV, FX_TTL_SECS),
             crypto_cache_ttl_secs: resolve_cache_ttl_secs(
                 &map,
                 MARKET_CRYPTO_CACHE_TTL_ENV,
                 CRYPTO_TTL_SECS,
             ),
+            stock_cache_ttl_secs: resolve_cache_ttl_secs(
+                &map,
+                MARKET_STOCK_CACHE_TTL_ENV,
+                STOCK_TTL_SECS,
+            ),
+            metal_cache_ttl_secs: resolve_cache_ttl_secs(
+                &map,
+                MARKET_METAL_CACHE_TTL_ENV,
+                METAL_TTL_SECS,
+            ),
+            crypto_providers: resolve_crypto_providers(&map),
+            fees: resolve_fee_schedule(&map),
         }
     }
 
@@ -61,6 +98,7 @@ impl RuntimeConfig {
         match kind {
             MarketKind::Fx => self.fx_cache_ttl_secs,
             MarketKind::Crypto => self.crypto_cache_ttl_secs,
+            MarketKind::Metal => self.metal_cache_ttl_secs,
         }
     }
 
@@ -103,6 +141,105 @@ fn resolve_cache_dir(env_map: &HashMap<String, String>) -> PathBuf {
         .unwrap_or_else(|| std::env::temp_dir().join("nils-market-cli"))
 }
 
+fn resolve_data_dir(env_map: &HashMap<String, String>) -> Option<PathBuf> {
+    let home = env_map.get(HOME_ENV).map(String::as_str);
+    env_map
+        .get(MARKET_DATA_DIR_ENV)
+        .or_else(|| env_map.get(ALFRED_WORKFLOW_DATA_ENV))
+        .map(String::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| PathBuf::from(expand_home_path(value, home)))
+}
+
+/// Comma/newline separated provider names; unknown names and repeats are skipped, and a list without any known
+/// provider keeps the default chain.
+fn resolve_crypto_providers(env_map: &HashMap<String, String>) -> Vec<CryptoProvider> {
+    let mut providers = Vec::new();
+    for provider in env_map
+        .get(MARKET_CRYPTO_PROVIDERS_ENV)
+        .map(|raw| split_ordered_list(raw))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|token| CryptoProvider::parse(token))
+    {
+        if !providers.contains(&provider) {
+            providers.push(provider);
+        }
+    }
+
+    if providers.is_empty() {
+        DEFAULT_CRYPTO_PROVIDERS.to_vec()
+    } else {
+        providers
+    }
+}
+
+/// Exchange fee or spread, in percent, taken off converted amounts. Mid-market prices and caches are unaffected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub default_pct: Option<Decimal>,
+    /// Overrides keyed by lowercase provider label (`coinbase`, `frankfurter`, ...), in env order.
+    pub provider_pct: Vec<(String, Decimal)>,
+}
+
+impl FeeSchedule {
+    /// Fee for a result `provider` label. Combined labels (`gold-api+frankfurter`, cross rates) use the first named
+    /// provider with an override, then the default.
+    pub fn pct_for(&self, provider: &str) -> Option<Decimal> {
+        provider
+            .split('+')
+            .find_map(|name| {
+                self.provider_pct
+                    .iter()
+                    .find(|(label, _)| label.eq_ignore_ascii_case(name.trim()))
+                    .map(|(_, pct)| *pct)
+            })
+            .or(self.default_pct)
+    }
+}
+
+/// Comma/newline separated `<pct>` (default) and `<provider>=<pct>` entries; an optional trailing `%` is allowed.
+/// Entries outside `0 <= pct < 100` or that fail to parse are skipped, and later entries win.
+fn resolve_fee_schedule(env_map: &HashMap<String, String>) -> FeeSchedule {
+    let mut schedule = FeeSchedule::default();
+    for token in env_map
+        .get(MARKET_FEE_PCT_ENV)
+        .map(|raw| split_ordered_list(raw))
+        .unwrap_or_default()
+    {
+        let (provider, raw_pct) = match token.split_once('=') {
+            Some((provider, pct)) => (Some(provider.trim().to_ascii_lowercase()), pct),
+            None => (None, token.as_str()),
+        };
+        let Some(pct) = parse_fee_pct(raw_pct) else {
+            continue;
+        };
+        match provider {
+            Some(provider) if provider.is_empty() => {}
+            Some(provider) => {
+                schedule
+                    .provider_pct
+                    .retain(|(label, _)| *label != provider);
+                schedule.provider_pct.push((provider, pct));
+            }
+            None => schedule.default_pct = Some(pct),
+        }
+    }
+    schedule
+}
+
+fn parse_fee_pct(raw: &str) -> Option<Decimal> {
+    let raw = raw.trim();
+    raw.strip_suffix('%')
+        .unwrap_or(raw)
+        .trim()
+        .parse::<Decimal>()
+        .ok()
+        .filter(|pct| *pct >= Decimal::ZERO && *pct < Decimal::ONE_HUNDRED)
+        .map(|pct| pct.normalize())
+}
+
 fn expand_home_path(raw: &str, home: Option<&str>) -> String {
     let trimmed = raw.trim();
     let Some(home) = home.map(str::trim).filter(|value| !value.is_empty()) else {
@@ -202,6 +339,7 @@ mod tests {
         assert!(config.cache_dir.ends_with("nils-market-cli"));
         assert_eq!(config.fx_cache_ttl_secs, FX_TTL_SECS);
         assert_eq!(config.crypto_cache_ttl_secs, CRYPTO_TTL_SECS);
+        assert_eq!(config.stock_cache_ttl_secs, STOCK_TTL_SECS);
         assert_eq!(config.cache_ttl_secs_for_kind(MarketKind::Fx), FX_TTL_SECS);
         assert_eq!(
             config.cache_ttl_secs_for_kind(MarketKind::Crypto),
@@ -218,6 +356,20 @@ mod tests {
         ]);
 
         assert_eq!(config.cache_dir, PathBuf::from("/tmp/market-cache"));
+        assert_eq!(config.data_dir, PathBuf::from("/tmp/alfred-data"));
+    }
+
+    #[test]
+    fn config_data_dir_prefers_market_data_dir_then_falls_back_to_cache_dir() {
+        let config = RuntimeConfig::from_pairs(vec![
+            (ALFRED_WORKFLOW_DATA_ENV, "/tmp/alfred-data"),
+            (MARKET_DATA_DIR_ENV, "~/market-data"),
+            ("HOME", "/Users/tester"),
+        ]);
+        assert_eq!(config.data_dir, PathBuf::from("/Users/tester/market-data"));
+
+        let config = RuntimeConfig::from_pairs(vec![(MARKET_CACHE_DIR_ENV, "/tmp/market-cache")]);
+        assert_eq!(config.data_dir, PathBuf::from("/tmp/market-cache"));
     }
 
     #[test]
@@ -304,6 +456,24 @@ mod tests {
         assert_eq!(config.crypto_cache_ttl_secs, 3600);
     }
 
+    #[test]
+    fn config_supports_stock_cache_ttl_duration_override() {
+        let config = RuntimeConfig::from_pairs(vec![(MARKET_STOCK_CACHE_TTL_ENV, "1m")]);
+        assert_eq!(config.stock_cache_ttl_secs, 60);
+        assert_eq!(config.crypto_cache_ttl_secs, CRYPTO_TTL_SECS);
+    }
+
+    #[test]
+    fn config_supports_metal_cache_ttl_duration_override() {
+        let config = RuntimeConfig::from_pairs(vec![(MARKET_METAL_CACHE_TTL_ENV, "2h")]);
+        assert_eq!(config.metal_cache_ttl_secs, 7200);
+        assert_eq!(config.cache_ttl_secs_for_kind(MarketKind::Metal), 7200);
+        assert_eq!(
+            RuntimeConfig::from_pairs(Vec::<(String, String)>::new()).metal_cache_ttl_secs,
+            METAL_TTL_SECS
+        );
+    }
+
     #[test]
     fn config_supports_seconds_and_uppercase_duration_suffixes() {
         let config = RuntimeConfig::from_pairs(vec![
@@ -331,6 +501,47 @@ mod tests {
         assert_eq!(zero.crypto_cache_ttl_secs, CRYPTO_TTL_SECS);
     }
 
+    #[test]
+    fn config_crypto_providers_follow_env_order() {
+        let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
+        assert_eq!(config.crypto_providers, DEFAULT_CRYPTO_PROVIDERS.to_vec());
+
+        let config = RuntimeConfig::from_pairs(vec![(
+            MARKET_CRYPTO_PROVIDERS_ENV,
+            " Binance, coinbase,nope,binance\nCoinGecko ",
+        )]);
+        assert_eq!(
+            config.crypto_providers,
+            vec![
+                CryptoProvider::Binance,
+                CryptoProvider::Coinbase,
+                CryptoProvider::Coingecko,
+            ]
+        );
+
+        let config = RuntimeConfig::from_pairs(vec![(MARKET_CRYPTO_PROVIDERS_ENV, "nope, ,")]);
+        assert_eq!(config.crypto_providers, DEFAULT_CRYPTO_PROVIDERS.to_vec());
+    }
+
+    #[test]
+    fn config_fee_schedule_parses_default_and_provider_overrides() {
+        let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
+        assert_eq!(config.fees, FeeSchedule::default());
+        assert_eq!(config.fees.pct_for("coinbase"), None);
+
+        let config = RuntimeConfig::from_pairs(vec![(
+            MARKET_FEE_PCT_ENV,
+            "0.5, Coinbase=1.49%, kraken=abc, binance=100\nfrankfurter = 0.30, coinbase=1.2",
+        )]);
+        let pct = |provider: &str| config.fees.pct_for(provider).map(|pct| pct.to_string());
+        assert_eq!(pct("coinbase").as_deref(), Some("1.2"));
+        assert_eq!(pct("kraken").as_deref(), Some("0.5"));
+        assert_eq!(pct("binance").as_deref(), Some("0.5"));
+        assert_eq!(pct("gold-api+frankfurter").as_deref(), Some("0.3"));
+        assert_eq!(pct("frankfurter+coinbase").as_deref(), Some("0.3"));
+        assert_eq!(config.fees.provider_pct.len(), 2);
+    }
+
     #[test]
     fn config_retry_policy_backoff_is_deterministic() {
         let policy = RetryPolicy::default();
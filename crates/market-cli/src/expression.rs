@@ -8,29 +8,30 @@ use crate::config::RuntimeConfig;
 use crate::error::AppError;
 use crate::icons;
 use crate::model::{
-    CacheStatus, MarketKind, MarketOutput, MarketRequest, decimal_to_string, normalize_fx_symbol,
+    CacheStatus, MarketOutput, MetalUnit, decimal_to_string, is_metal_symbol, normalize_fx_symbol,
 };
 use crate::providers::ProviderApi;
 use crate::service;
 
+/// Parsed expression tree. Binding is loosest for `+ -`, then `* /`, then unary `-`; parentheses group explicitly.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum ParsedTerm {
-    Numeric(Decimal),
-    Asset { amount: Decimal, symbol: String },
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ExpressionMode {
-    Numeric,
-    Asset,
+enum Expr {
+    Number(Decimal),
+    /// `5%`, stored as percentage points (`5`).
+    Percent(Decimal),
+    Asset(AssetTerm),
+    Negate(Box<Expr>),
+    Binary {
+        operator: char,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ParsedExpression {
-    terms: Vec<ParsedTerm>,
-    operators: Vec<char>,
+    expr: Expr,
     target_fiat: String,
-    mode: ExpressionMode,
 }
 
 #[derive(Debug, Clone)]
@@ -38,14 +39,41 @@ struct ResolvedAssetQuote {
     unit_price: Decimal,
     provider: String,
     cache_status: CacheStatus,
+    /// `MARKET_FEE_PCT` percent for this quote's provider, if any.
+    fee_pct: Option<Decimal>,
 }
 
-#[derive(Debug, Clone)]
+/// `unit` is only set for metals written with an explicit unit (`10g xau`); metals default to troy ounces.
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct AssetTerm {
     amount: Decimal,
     symbol: String,
+    unit: Option<MetalUnit>,
 }
 
+/// Value of an evaluated (sub)expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Value {
+    Scalar(Decimal),
+    /// Asset amount already converted into the target fiat.
+    Money(Decimal),
+    /// Percentage points. `x + 5%` / `x - 5%` scale `x`; anywhere else a percentage is the ratio `0.05`.
+    Percent(Decimal),
+}
+
+impl Value {
+    fn ratio(self) -> Decimal {
+        match self {
+            Self::Scalar(value) | Self::Money(value) => value,
+            Self::Percent(points) => points / Decimal::ONE_HUNDRED,
+        }
+    }
+}
+
+const ADDITIVE_PRECEDENCE: u8 = 1;
+const MULTIPLICATIVE_PRECEDENCE: u8 = 2;
+const UNARY_PRECEDENCE: u8 = 3;
+
 pub fn evaluate_query<P, N>(
     config: &RuntimeConfig,
     providers: &P,
@@ -58,9 +86,13 @@ where
     N: Fn() -> DateTime<Utc> + Copy,
 {
     let parsed = parse_expression(query, default_fiat)?;
-    match parsed.mode {
-        ExpressionMode::Numeric => evaluate_numeric_feedback(&parsed),
-        ExpressionMode::Asset => evaluate_asset_feedback(config, providers, now_fn, &parsed),
+    let mut asset_terms = Vec::new();
+    collect_asset_terms(&parsed.expr, &mut asset_terms);
+
+    if asset_terms.is_empty() {
+        evaluate_numeric_feedback(&parsed)
+    } else {
+        evaluate_asset_feedback(config, providers, now_fn, &parsed, &asset_terms)
     }
 }
 
@@ -71,46 +103,99 @@ fn parse_expression(query: &str, default_fiat: &str) -> Result<ParsedExpression,
     }
 
     let (expression_source, target_fiat) = split_target_clause(trimmed, default_fiat)?;
+    let expr = ExpressionParser::new(&expression_source).parse()?;
 
-    let parser = ExpressionParser::new(&expression_source);
-    let (terms, operators) = parser.parse()?;
+    // Type errors (a plain number added to an amount, two amounts multiplied) and division by zero never depend on
+    // prices, so a dry run with unit prices rejects them before any provider call.
+    evaluate(&expr, &|_| Decimal::ONE)?;
 
-    let has_numeric = terms
-        .iter()
-        .any(|term| matches!(term, ParsedTerm::Numeric(_)));
-    let has_asset = terms
-        .iter()
-        .any(|term| matches!(term, ParsedTerm::Asset { .. }));
+    Ok(ParsedExpression { expr, target_fiat })
+}
 
-    let mode = match (has_numeric, has_asset) {
-        (true, true) => {
-            return Err(AppError::user(
-                "mixed numeric and asset terms are not supported",
-            ));
+fn collect_asset_terms<'a>(expr: &'a Expr, terms: &mut Vec<&'a AssetTerm>) {
+    match expr {
+        Expr::Asset(term) => terms.push(term),
+        Expr::Negate(inner) => collect_asset_terms(inner, terms),
+        Expr::Binary { lhs, rhs, .. } => {
+            collect_asset_terms(lhs, terms);
+            collect_asset_terms(rhs, terms);
         }
-        (true, false) => ExpressionMode::Numeric,
-        (false, true) => ExpressionMode::Asset,
-        (false, false) => return Err(AppError::user("expression must contain at least one term")),
-    };
+        Expr::Number(_) | Expr::Percent(_) => {}
+    }
+}
 
-    if mode == ExpressionMode::Asset
-        && operators
-            .iter()
-            .any(|operator| !matches!(operator, '+' | '-'))
-    {
-        return Err(AppError::user(
-            "unsupported operator in asset expression: only + and - are supported",
-        ));
+fn evaluate(expr: &Expr, price: &dyn Fn(&AssetTerm) -> Decimal) -> Result<Value, AppError> {
+    match expr {
+        Expr::Number(value) => Ok(Value::Scalar(*value)),
+        Expr::Percent(points) => Ok(Value::Percent(*points)),
+        Expr::Asset(term) => in_range(term.amount.checked_mul(price(term))).map(Value::Money),
+        Expr::Negate(inner) => Ok(match evaluate(inner, price)? {
+            Value::Scalar(value) => Value::Scalar(-value),
+            Value::Money(value) => Value::Money(-value),
+            Value::Percent(points) => Value::Percent(-points),
+        }),
+        Expr::Binary { operator, lhs, rhs } => {
+            apply_operator(*operator, evaluate(lhs, price)?, evaluate(rhs, price)?)
+        }
+    }
+}
+
+fn apply_operator(operator: char, lhs: Value, rhs: Value) -> Result<Value, AppError> {
+    use Value::{Money, Percent, Scalar};
+
+    match (operator, lhs, rhs) {
+        ('+' | '-', Percent(lhs), Percent(rhs)) => add_or_subtract(operator, lhs, rhs).map(Percent),
+        ('+' | '-', Money(value), Percent(points)) => {
+            apply_percent(operator, value, points).map(Money)
+        }
+        ('+' | '-', Scalar(value), Percent(points)) => {
+            apply_percent(operator, value, points).map(Scalar)
+        }
+        ('+' | '-', Money(lhs), Money(rhs)) => add_or_subtract(operator, lhs, rhs).map(Money),
+        ('+' | '-', Money(_), _) | ('+' | '-', _, Money(_)) => Err(AppError::user(
+            "mixed numeric and asset terms are not supported: plain numbers can only scale amounts (`* 0.9`, `+ 5%`)",
+        )),
+        ('+' | '-', lhs, rhs) => add_or_subtract(operator, lhs.ratio(), rhs.ratio()).map(Scalar),
+        ('*', Money(_), Money(_)) => Err(AppError::user(
+            "unsupported operator: cannot multiply two asset amounts",
+        )),
+        ('*', Money(value), factor) | ('*', factor, Money(value)) => {
+            in_range(value.checked_mul(factor.ratio())).map(Money)
+        }
+        ('*', lhs, rhs) => in_range(lhs.ratio().checked_mul(rhs.ratio())).map(Scalar),
+        ('/', _, Money(_)) => Err(AppError::user(
+            "unsupported operator: cannot divide by an asset amount",
+        )),
+        ('/', Money(value), divisor) => divide(value, divisor.ratio()).map(Money),
+        ('/', lhs, rhs) => divide(lhs.ratio(), rhs.ratio()).map(Scalar),
+        _ => unreachable!("parser only permits +, -, * and /"),
     }
+}
 
-    Ok(ParsedExpression {
-        terms,
-        operators,
-        target_fiat,
-        mode,
+fn add_or_subtract(operator: char, lhs: Decimal, rhs: Decimal) -> Result<Decimal, AppError> {
+    in_range(match operator {
+        '+' => lhs.checked_add(rhs),
+        _ => lhs.checked_sub(rhs),
     })
 }
 
+/// `value + points%` or `value - points%`.
+fn apply_percent(operator: char, value: Decimal, points: Decimal) -> Result<Decimal, AppError> {
+    let factor = add_or_subtract(operator, Decimal::ONE, points / Decimal::ONE_HUNDRED)?;
+    in_range(value.checked_mul(factor))
+}
+
+fn divide(lhs: Decimal, rhs: Decimal) -> Result<Decimal, AppError> {
+    if rhs.is_zero() {
+        return Err(AppError::user("division by zero is not allowed"));
+    }
+    in_range(lhs.checked_div(rhs))
+}
+
+fn in_range(value: Option<Decimal>) -> Result<Decimal, AppError> {
+    value.ok_or_else(|| AppError::user("result is out of range"))
+}
+
 fn split_target_clause(
     trimmed_query: &str,
     default_fiat: &str,
@@ -147,35 +232,13 @@ fn split_target_clause(
 }
 
 fn evaluate_numeric_feedback(parsed: &ParsedExpression) -> Result<Feedback, AppError> {
-    let mut values = parsed.terms.iter();
-    let first = values
-        .next()
-        .expect("numeric expression always has at least one term");
-    let mut total = match first {
-        ParsedTerm::Numeric(value) => *value,
-        ParsedTerm::Asset { .. } => unreachable!("numeric mode cannot include asset term"),
+    let no_assets =
+        |_: &AssetTerm| -> Decimal { unreachable!("numeric expression has no asset terms") };
+    let rendered = match evaluate(&parsed.expr, &no_assets)? {
+        Value::Percent(points) => format!("{}%", format_plain_decimal(points)),
+        value => format_plain_decimal(value.ratio()),
     };
 
-    for (operator, term) in parsed.operators.iter().zip(values) {
-        let value = match term {
-            ParsedTerm::Numeric(value) => *value,
-            ParsedTerm::Asset { .. } => unreachable!("numeric mode cannot include asset term"),
-        };
-
-        match operator {
-            '+' => total += value,
-            '-' => total -= value,
-            '*' => total *= value,
-            '/' => {
-                total = total
-                    .checked_div(value)
-                    .ok_or_else(|| AppError::user("division by zero is not allowed"))?;
-            }
-            _ => unreachable!("parser only permits +, -, * and /"),
-        }
-    }
-
-    let rendered = format_plain_decimal(total);
     let item = Item::new(rendered.clone())
         .with_subtitle("Numeric result")
         .with_arg(rendered)
@@ -188,46 +251,53 @@ fn evaluate_asset_feedback<P, N>(
     providers: &P,
     now_fn: N,
     parsed: &ParsedExpression,
+    asset_terms: &[&AssetTerm],
 ) -> Result<Feedback, AppError>
 where
     P: ProviderApi,
     N: Fn() -> DateTime<Utc> + Copy,
 {
-    let asset_terms: Vec<AssetTerm> = parsed
-        .terms
-        .iter()
-        .map(|term| match term {
-            ParsedTerm::Asset { amount, symbol } => Ok(AssetTerm {
-                amount: *amount,
-                symbol: symbol.clone(),
-            }),
-            ParsedTerm::Numeric(_) => Err(AppError::user(
-                "mixed numeric and asset terms are not supported",
-            )),
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let mut ordered_symbols = Vec::<String>::new();
-    let mut seen = HashSet::<String>::new();
-    for term in &asset_terms {
-        if seen.insert(term.symbol.clone()) {
-            ordered_symbols.push(term.symbol.clone());
+    // One price row per symbol and unit (`1 oz XAU` and `1 g XAU` are separate rows); one quote per symbol.
+    let mut ordered_rows = Vec::<(String, Option<MetalUnit>)>::new();
+    let mut seen = HashSet::<(String, Option<MetalUnit>)>::new();
+    for term in asset_terms {
+        let row = (term.symbol.clone(), term.unit);
+        if seen.insert(row.clone()) {
+            ordered_rows.push(row);
         }
     }
 
     let mut quotes = HashMap::<String, ResolvedAssetQuote>::new();
-    for symbol in &ordered_symbols {
+    for (symbol, _) in &ordered_rows {
+        if quotes.contains_key(symbol) {
+            continue;
+        }
         let quote = resolve_asset_quote(config, providers, now_fn, symbol, &parsed.target_fiat)?;
         quotes.insert(symbol.clone(), quote);
     }
 
     let mut items = Vec::new();
-    for symbol in ordered_symbols {
+    for (symbol, unit) in ordered_rows {
         let quote = quotes
             .get(&symbol)
             .expect("quote lookup for resolved symbol must exist");
-        let rendered_price = format_market_decimal(quote.unit_price);
-        let title = format!("1 {symbol} = {rendered_price} {}", parsed.target_fiat);
+        let unit_price = term_unit_price(quote, unit);
+        let rendered_price = format_market_decimal(unit_price);
+        let fee_suffix = quote
+            .fee_pct
+            .map(|fee_pct| {
+                fee_suffix(
+                    after_fee(unit_price, fee_pct),
+                    &parsed.target_fiat,
+                    &format!("{}% fee", decimal_to_string(&fee_pct)),
+                )
+            })
+            .unwrap_or_default();
+        let title = format!(
+            "1 {} = {rendered_price} {}{fee_suffix}",
+            term_label(&symbol, unit),
+            parsed.target_fiat
+        );
         let subtitle = format!(
             "provider: {} · freshness: {}",
             quote.provider,
@@ -244,22 +314,83 @@ where
         ));
     }
 
-    let (total, formula) = evaluate_asset_total(&asset_terms, &parsed.operators, &quotes);
+    let price = |term: &AssetTerm| {
+        let quote = quotes
+            .get(&term.symbol)
+            .expect("asset quote must exist for all parsed terms");
+        term_unit_price(quote, term.unit)
+    };
+    let total = match evaluate(&parsed.expr, &price)? {
+        Value::Money(total) => total,
+        _ => unreachable!("an expression with asset terms evaluates to an amount"),
+    };
+    let formula = render_formula(&parsed.expr, &price, 0, false);
     let rendered_total = format_market_decimal(total);
+    let total_fee_suffix = match total_fee_label(asset_terms, &quotes) {
+        Some(label) => {
+            // Each asset converts at what its provider's fee leaves; fee-free assets keep mid-market prices.
+            let price_after_fee = |term: &AssetTerm| {
+                let quote = &quotes[&term.symbol];
+                let unit_price = term_unit_price(quote, term.unit);
+                quote
+                    .fee_pct
+                    .map_or(unit_price, |fee_pct| after_fee(unit_price, fee_pct))
+            };
+            let Value::Money(total_after_fee) = evaluate(&parsed.expr, &price_after_fee)? else {
+                unreachable!("an expression with asset terms evaluates to an amount");
+            };
+            fee_suffix(total_after_fee, &parsed.target_fiat, &label)
+        }
+        None => String::new(),
+    };
 
     items.push(
-        Item::new(format!("Total = {rendered_total} {}", parsed.target_fiat))
-            .with_subtitle(format!(
-                "{formula} = {rendered_total} {}",
-                parsed.target_fiat
-            ))
-            .with_arg(format!("{rendered_total} {}", parsed.target_fiat))
-            .with_valid(true),
+        Item::new(format!(
+            "Total = {rendered_total} {}{total_fee_suffix}",
+            parsed.target_fiat
+        ))
+        .with_subtitle(format!(
+            "Formula: {formula} = {rendered_total} {}",
+            parsed.target_fiat
+        ))
+        .with_arg(format!("{rendered_total} {}", parsed.target_fiat))
+        .with_valid(true),
     );
 
     Ok(Feedback::new(items))
 }
 
+/// Amount left after a `fee_pct` percent fee.
+fn after_fee(value: Decimal, fee_pct: Decimal) -> Decimal {
+    value * (Decimal::ONE_HUNDRED - fee_pct) / Decimal::ONE_HUNDRED
+}
+
+/// ` (66665 USD after 0.5% fee)`.
+fn fee_suffix(after_fee: Decimal, target_fiat: &str, label: &str) -> String {
+    format!(
+        " ({} {target_fiat} after {label})",
+        format_market_decimal(after_fee)
+    )
+}
+
+/// `0.5% fee` when every asset shares one fee, `fees` for mixed fees; `None` without any fee.
+fn total_fee_label(
+    asset_terms: &[&AssetTerm],
+    quotes: &HashMap<String, ResolvedAssetQuote>,
+) -> Option<String> {
+    let mut fees = asset_terms
+        .iter()
+        .map(|term| quotes[&term.symbol].fee_pct)
+        .collect::<Vec<_>>();
+    fees.sort();
+    fees.dedup();
+    match fees.as_slice() {
+        [None] => None,
+        [Some(fee_pct)] => Some(format!("{}% fee", decimal_to_string(fee_pct))),
+        _ => Some("fees".to_string()),
+    }
+}
+
 fn with_symbol_icon(item: Item, config: &RuntimeConfig, symbol: &str) -> Item {
     if let Some(path) = icons::resolve_icon_path(config, symbol) {
         return item.with_icon(ItemIcon::new(path.to_string_lossy().into_owned()));
@@ -268,50 +399,63 @@ fn with_symbol_icon(item: Item, config: &RuntimeConfig, symbol: &str) -> Item {
     item
 }
 
-fn evaluate_asset_total(
-    terms: &[AssetTerm],
-    operators: &[char],
-    quotes: &HashMap<String, ResolvedAssetQuote>,
-) -> (Decimal, String) {
-    let first = terms
-        .first()
-        .expect("asset expression has at least one term");
-    let first_quote = quotes
-        .get(&first.symbol)
-        .expect("first asset quote must exist");
-    let mut total = first.amount * first_quote.unit_price;
-    let mut formula = format!(
-        "{}*{}({})",
-        format_plain_decimal(first.amount),
-        format_market_decimal(first_quote.unit_price),
-        first.symbol
-    );
+/// Quote price for one `unit` of the asset; metal quotes are per troy ounce.
+fn term_unit_price(quote: &ResolvedAssetQuote, unit: Option<MetalUnit>) -> Decimal {
+    match unit {
+        Some(unit) => unit.price_from_troy_ounce(quote.unit_price),
+        None => quote.unit_price,
+    }
+}
 
-    for (operator, term) in operators.iter().zip(terms.iter().skip(1)) {
-        let quote = quotes
-            .get(&term.symbol)
-            .expect("asset quote must exist for all parsed terms");
-        let piece = format!(
+/// `BTC`, or `g XAU` for a metal written with a unit.
+fn term_label(symbol: &str, unit: Option<MetalUnit>) -> String {
+    match unit {
+        Some(unit) => format!("{} {symbol}", unit.as_str()),
+        None => symbol.to_string(),
+    }
+}
+
+/// Renders the priced expression (`2*60000(BTC) + 3*3000(ETH)`), adding parentheses only where binding requires them.
+fn render_formula(
+    expr: &Expr,
+    price: &dyn Fn(&AssetTerm) -> Decimal,
+    parent_precedence: u8,
+    right_operand: bool,
+) -> String {
+    match expr {
+        Expr::Number(value) => format_plain_decimal(*value),
+        Expr::Percent(points) => format!("{}%", format_plain_decimal(*points)),
+        Expr::Asset(term) => format!(
             "{}*{}({})",
             format_plain_decimal(term.amount),
-            format_market_decimal(quote.unit_price),
-            term.symbol
-        );
-
-        let amount = term.amount * quote.unit_price;
-        match operator {
-            '+' => total += amount,
-            '-' => total -= amount,
-            _ => unreachable!("parser only permits + and -"),
+            format_market_decimal(price(term)),
+            term_label(&term.symbol, term.unit)
+        ),
+        Expr::Negate(inner) => {
+            format!("-{}", render_formula(inner, price, UNARY_PRECEDENCE, false))
+        }
+        Expr::Binary { operator, lhs, rhs } => {
+            let precedence = operator_precedence(*operator);
+            let rendered = format!(
+                "{} {operator} {}",
+                render_formula(lhs, price, precedence, false),
+                render_formula(rhs, price, precedence, true)
+            );
+            if precedence < parent_precedence || (right_operand && precedence == parent_precedence)
+            {
+                format!("({rendered})")
+            } else {
+                rendered
+            }
         }
-
-        formula.push(' ');
-        formula.push(*operator);
-        formula.push(' ');
-        formula.push_str(&piece);
     }
+}
 
-    (total, format!("Formula: {formula}"))
+fn operator_precedence(operator: char) -> u8 {
+    match operator {
+        '*' | '/' => MULTIPLICATIVE_PRECEDENCE,
+        _ => ADDITIVE_PRECEDENCE,
+    }
 }
 
 fn resolve_asset_quote<P, N>(
@@ -325,11 +469,16 @@ where
     P: ProviderApi,
     N: Fn() -> DateTime<Utc> + Copy,
 {
-    let output = resolve_symbol_output(config, providers, now_fn, symbol, target_fiat)?;
-    convert_output(symbol, output)
+    let output = service::resolve_symbol_output(config, providers, now_fn, symbol, target_fiat)?;
+    let fee_pct = config.fees.pct_for(&output.provider);
+    convert_output(symbol, output, fee_pct)
 }
 
-fn convert_output(symbol: &str, output: MarketOutput) -> Result<ResolvedAssetQuote, AppError> {
+fn convert_output(
+    symbol: &str,
+    output: MarketOutput,
+    fee_pct: Option<Decimal>,
+) -> Result<ResolvedAssetQuote, AppError> {
     let unit_price = output.unit_price.parse::<Decimal>().map_err(|_| {
         AppError::runtime(format!(
             "provider returned invalid unit price for {symbol}: {}",
@@ -341,49 +490,10 @@ fn convert_output(symbol: &str, output: MarketOutput) -> Result<ResolvedAssetQuo
         unit_price,
         provider: output.provider,
         cache_status: output.cache.status,
+        fee_pct,
     })
 }
 
-pub fn resolve_symbol_output<P, N>(
-    config: &RuntimeConfig,
-    providers: &P,
-    now_fn: N,
-    symbol: &str,
-    target_fiat: &str,
-) -> Result<MarketOutput, AppError>
-where
-    P: ProviderApi,
-    N: Fn() -> DateTime<Utc> + Copy,
-{
-    let mut trace = Vec::<String>::new();
-
-    if looks_like_fiat_symbol(symbol) {
-        let fx_request =
-            MarketRequest::new(MarketKind::Fx, symbol, target_fiat, "1").map_err(AppError::from)?;
-        match service::resolve_market(config, providers, now_fn, &fx_request) {
-            Ok(output) => return Ok(output),
-            Err(error) => trace.push(format!("fx: {}", error.message)),
-        }
-    }
-
-    let crypto_request =
-        MarketRequest::new(MarketKind::Crypto, symbol, target_fiat, "1").map_err(AppError::from)?;
-    match service::resolve_market(config, providers, now_fn, &crypto_request) {
-        Ok(output) => Ok(output),
-        Err(error) => {
-            trace.push(format!("crypto: {}", error.message));
-            Err(AppError::runtime_with_trace(
-                &format!("failed to resolve quote for {symbol}/{target_fiat}"),
-                &trace,
-            ))
-        }
-    }
-}
-
-fn looks_like_fiat_symbol(symbol: &str) -> bool {
-    symbol.len() == 3 && symbol.chars().all(|ch| ch.is_ascii_alphabetic())
-}
-
 fn cache_status_label(status: CacheStatus) -> &'static str {
     match status {
         CacheStatus::Live => "live",
@@ -431,59 +541,116 @@ impl<'a> ExpressionParser<'a> {
         }
     }
 
-    fn parse(mut self) -> Result<(Vec<ParsedTerm>, Vec<char>), AppError> {
+    fn parse(mut self) -> Result<Expr, AppError> {
         self.skip_whitespace();
         if self.peek().is_none() {
             return Err(AppError::user("expression must not be empty"));
         }
 
-        let mut terms = Vec::new();
-        let mut operators = Vec::new();
-        terms.push(self.parse_term()?);
+        let expr = self.parse_additive()?;
+        self.skip_whitespace();
+        match self.peek() {
+            None => Ok(expr),
+            Some(b')') => Err(AppError::user("invalid expression: unmatched `)`")),
+            Some(_) => Err(AppError::user(format!(
+                "invalid token near `{}`",
+                self.remaining_fragment()
+            ))),
+        }
+    }
 
-        loop {
-            self.skip_whitespace();
-            let Some(token) = self.peek() else {
-                break;
+    fn parse_additive(&mut self) -> Result<Expr, AppError> {
+        let mut expr = self.parse_multiplicative()?;
+        while let Some(operator) = self.next_operator(b"+-") {
+            let rhs = self.parse_multiplicative()?;
+            expr = Expr::Binary {
+                operator,
+                lhs: Box::new(expr),
+                rhs: Box::new(rhs),
             };
+        }
+        Ok(expr)
+    }
 
-            let operator = match token {
-                b'+' => '+',
-                b'-' => '-',
-                b'*' => '*',
-                b'/' => '/',
-                _ => {
-                    return Err(AppError::user(format!(
-                        "invalid token near `{}`",
-                        self.remaining_fragment()
-                    )));
-                }
+    fn parse_multiplicative(&mut self) -> Result<Expr, AppError> {
+        let mut expr = self.parse_unary()?;
+        while let Some(operator) = self.next_operator(b"*/") {
+            let rhs = self.parse_unary()?;
+            expr = Expr::Binary {
+                operator,
+                lhs: Box::new(expr),
+                rhs: Box::new(rhs),
             };
+        }
+        Ok(expr)
+    }
 
-            self.cursor += 1;
-            operators.push(operator);
-            self.skip_whitespace();
-            if self.peek().is_none() {
-                return Err(AppError::user("expression cannot end with an operator"));
+    fn parse_unary(&mut self) -> Result<Expr, AppError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(AppError::user("expression cannot end with an operator")),
+            Some(b'-') => {
+                self.cursor += 1;
+                Ok(Expr::Negate(Box::new(self.parse_unary()?)))
             }
-
-            terms.push(self.parse_term()?);
+            Some(b'+') => {
+                self.cursor += 1;
+                self.parse_unary()
+            }
+            Some(b'(') => {
+                self.cursor += 1;
+                let expr = self.parse_additive()?;
+                self.skip_whitespace();
+                if self.peek() != Some(b')') {
+                    return Err(AppError::user("invalid expression: missing closing `)`"));
+                }
+                self.cursor += 1;
+                Ok(expr)
+            }
+            Some(_) => self.parse_term(),
         }
+    }
 
-        Ok((terms, operators))
+    fn next_operator(&mut self, operators: &[u8]) -> Option<char> {
+        self.skip_whitespace();
+        let token = self.peek().filter(|token| operators.contains(token))?;
+        self.cursor += 1;
+        Some(char::from(token))
     }
 
-    fn parse_term(&mut self) -> Result<ParsedTerm, AppError> {
+    fn parse_term(&mut self) -> Result<Expr, AppError> {
         let amount = self.parse_decimal()?;
-        let spaces = self.skip_whitespace();
+        if self.parse_percent_sign() {
+            return Ok(Expr::Percent(amount));
+        }
+        if let Some(unit) = self.parse_metal_unit() {
+            self.skip_whitespace();
+            let symbol = self.parse_asset_symbol(true)?;
+            if !is_metal_symbol(&symbol) {
+                return Err(AppError::user(format!(
+                    "unit `{}` only applies to metals (XAU, XAG, XPT), got {symbol}",
+                    unit.as_str()
+                )));
+            }
+            return Ok(Expr::Asset(AssetTerm {
+                amount,
+                symbol,
+                unit: Some(unit),
+            }));
+        }
 
+        let spaces = self.skip_whitespace();
         if spaces > 0
             && self
                 .peek()
                 .is_some_and(|token| token.is_ascii_alphanumeric())
         {
             let symbol = self.parse_asset_symbol(true)?;
-            return Ok(ParsedTerm::Asset { amount, symbol });
+            return Ok(Expr::Asset(AssetTerm {
+                amount,
+                symbol,
+                unit: None,
+            }));
         }
 
         // Relaxed compact form: allow "1btc", "3eth" without whitespace.
@@ -491,21 +658,61 @@ impl<'a> ExpressionParser<'a> {
         // (for example 1e2), compact suffix must be letters only.
         if spaces == 0 && self.peek().is_some_and(|token| token.is_ascii_alphabetic()) {
             let symbol = self.parse_asset_symbol(false)?;
-            return Ok(ParsedTerm::Asset { amount, symbol });
+            return Ok(Expr::Asset(AssetTerm {
+                amount,
+                symbol,
+                unit: None,
+            }));
         }
 
-        Ok(ParsedTerm::Numeric(amount))
+        Ok(Expr::Number(amount))
     }
 
-    fn parse_decimal(&mut self) -> Result<Decimal, AppError> {
+    /// Consumes `%` after a number (`5%`, `5 %`).
+    fn parse_percent_sign(&mut self) -> bool {
         let start = self.cursor;
+        self.skip_whitespace();
+        if self.peek() == Some(b'%') {
+            self.cursor += 1;
+            return true;
+        }
+        self.cursor = start;
+        false
+    }
 
-        if self
-            .peek()
-            .is_some_and(|token| token == b'+' || token == b'-')
-        {
+    /// Consumes a metal unit word (`10g xau`, `2 oz xag`) when it is followed by whitespace and a symbol; otherwise
+    /// leaves the cursor untouched so the word is parsed as an asset symbol.
+    fn parse_metal_unit(&mut self) -> Option<MetalUnit> {
+        let start = self.cursor;
+        self.skip_whitespace();
+        let word_start = self.cursor;
+        while self.peek().is_some_and(|token| token.is_ascii_alphabetic()) {
             self.cursor += 1;
         }
+        let word_end = self.cursor;
+        let unit = self
+            .slice(word_start, word_end)
+            .ok()
+            .and_then(MetalUnit::parse);
+        let followed_by_symbol = self.skip_whitespace() > 0
+            && self
+                .peek()
+                .is_some_and(|token| token.is_ascii_alphanumeric());
+
+        match unit {
+            Some(unit) if followed_by_symbol => {
+                self.cursor = word_end;
+                Some(unit)
+            }
+            _ => {
+                self.cursor = start;
+                None
+            }
+        }
+    }
+
+    fn parse_decimal(&mut self) -> Result<Decimal, AppError> {
+        let start = self.cursor;
 
         let mut integer_digits = 0usize;
         while self.peek().is_some_and(|token| token.is_ascii_digit()) {
@@ -609,6 +816,7 @@ mod tests {
         fx_calls: Cell<usize>,
         coinbase_calls: Cell<usize>,
         kraken_calls: Cell<usize>,
+        metal_calls: Cell<usize>,
     }
 
     impl FakeProviders {
@@ -617,6 +825,7 @@ mod tests {
                 fx_calls: Cell::new(0),
                 coinbase_calls: Cell::new(0),
                 kraken_calls: Cell::new(0),
+                metal_calls: Cell::new(0),
             }
         }
     }
@@ -696,6 +905,79 @@ mod tests {
                 "kraken disabled in tests".to_string(),
             ))
         }
+
+        fn fetch_crypto_binance(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<crate::model::MarketQuote, ProviderError> {
+            Err(ProviderError::Transport(
+                "binance disabled in tests".to_string(),
+            ))
+        }
+
+        fn fetch_crypto_coingecko(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<crate::model::MarketQuote, ProviderError> {
+            Err(ProviderError::Transport(
+                "coingecko disabled in tests".to_string(),
+            ))
+        }
+
+        fn fetch_metal_spot(
+            &self,
+            metal: &str,
+        ) -> Result<crate::model::MarketQuote, ProviderError> {
+            self.metal_calls.set(self.metal_calls.get() + 1);
+            let now = Utc
+                .with_ymd_and_hms(2026, 2, 10, 12, 0, 0)
+                .single()
+                .expect("time");
+            match metal {
+                "XAU" => Ok(crate::model::MarketQuote::new(
+                    "gold-api",
+                    Decimal::new(2400, 0),
+                    now,
+                )),
+                _ => Err(ProviderError::UnsupportedPair(format!("{metal}/USD"))),
+            }
+        }
+
+        fn fetch_stock_yahoo(
+            &self,
+            symbol: &str,
+        ) -> Result<crate::model::StockQuote, ProviderError> {
+            Err(ProviderError::UnknownSymbol(symbol.to_string()))
+        }
+
+        fn fetch_stock_stooq(
+            &self,
+            symbol: &str,
+        ) -> Result<crate::model::StockQuote, ProviderError> {
+            Err(ProviderError::UnknownSymbol(symbol.to_string()))
+        }
+
+        fn fetch_fx_history(
+            &self,
+            base: &str,
+            quote: &str,
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<crate::model::PriceSeries, ProviderError> {
+            Err(ProviderError::UnsupportedPair(format!("{base}/{quote}")))
+        }
+
+        fn fetch_crypto_history(
+            &self,
+            base: &str,
+            quote: &str,
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<crate::model::PriceSeries, ProviderError> {
+            Err(ProviderError::UnsupportedPair(format!("{base}/{quote}")))
+        }
     }
 
     fn fixed_now() -> DateTime<Utc> {
@@ -710,9 +992,14 @@ mod tests {
         std::mem::forget(dir);
 
         let config = RuntimeConfig {
-            cache_dir,
+            cache_dir: cache_dir.clone(),
+            data_dir: cache_dir,
             fx_cache_ttl_secs: crate::config::FX_TTL_SECS,
             crypto_cache_ttl_secs: crate::config::CRYPTO_TTL_SECS,
+            stock_cache_ttl_secs: crate::config::STOCK_TTL_SECS,
+            metal_cache_ttl_secs: crate::config::METAL_TTL_SECS,
+            crypto_providers: crate::config::DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+            fees: crate::config::FeeSchedule::default(),
         };
         seed_icon_files(&config, &["BTC", "ETH", "USD", "JPY"]);
         config
@@ -814,6 +1101,51 @@ mod tests {
         assert_eq!(icon_path(&feedback.items[2]), None);
     }
 
+    #[test]
+    fn expression_asset_rows_and_total_show_fee_adjusted_amounts() {
+        let providers = FakeProviders::new();
+        let config = RuntimeConfig {
+            fees: crate::config::FeeSchedule {
+                default_pct: Some(Decimal::new(5, 1)),
+                provider_pct: vec![("coinbase".to_string(), Decimal::ONE)],
+            },
+            ..config_in_tempdir()
+        };
+
+        let feedback = evaluate_query(&config, &providers, fixed_now, "1 btc + 3 btc", "USD")
+            .expect("must pass");
+        assert_eq!(
+            feedback.items[0].title,
+            "1 BTC = 60000 USD (59400 USD after 1% fee)"
+        );
+        assert_eq!(
+            feedback.items[1].title,
+            "Total = 240000 USD (237600 USD after 1% fee)"
+        );
+        assert_eq!(feedback.items[1].arg.as_deref(), Some("240000 USD"));
+
+        let feedback = evaluate_query(
+            &config,
+            &providers,
+            fixed_now,
+            "1 btc + 100 usd to jpy",
+            "USD",
+        )
+        .expect("must pass");
+        assert_eq!(
+            feedback.items[0].title,
+            "1 BTC = 10000000 JPY (9900000 JPY after 1% fee)"
+        );
+        assert_eq!(
+            feedback.items[1].title,
+            "1 USD = 150.0 JPY (149.3 JPY after 0.5% fee)"
+        );
+        assert_eq!(
+            feedback.items[2].title,
+            "Total = 10015000 JPY (9914925 JPY after fees)"
+        );
+    }
+
     #[test]
     fn expression_repeated_asset_deduplicates_unit_price_rows() {
         let providers = FakeProviders::new();
@@ -834,6 +1166,146 @@ mod tests {
         assert_eq!(providers.coinbase_calls.get(), 1);
     }
 
+    #[test]
+    fn expression_metal_terms_convert_grams_and_troy_ounces() {
+        let providers = FakeProviders::new();
+        let feedback = evaluate_query(
+            &config_in_tempdir(),
+            &providers,
+            fixed_now,
+            "10g xau + 2 oz XAU to jpy",
+            "USD",
+        )
+        .expect("must pass");
+
+        assert_eq!(feedback.items.len(), 3);
+        assert_eq!(feedback.items[0].title, "1 g XAU = 11574 JPY");
+        assert_eq!(feedback.items[1].title, "1 oz XAU = 360000 JPY");
+        assert_eq!(feedback.items[2].title, "Total = 835743 JPY");
+        assert_eq!(
+            feedback.items[2].subtitle.as_deref(),
+            Some("Formula: 10*11574(g XAU) + 2*360000(oz XAU) = 835743 JPY")
+        );
+        assert_eq!(providers.metal_calls.get(), 1);
+        assert_eq!(providers.coinbase_calls.get(), 0);
+    }
+
+    #[test]
+    fn expression_metal_unit_requires_metal_symbol() {
+        let providers = FakeProviders::new();
+        let err = evaluate_query(
+            &config_in_tempdir(),
+            &providers,
+            fixed_now,
+            "10g btc",
+            "USD",
+        )
+        .expect_err("must fail");
+        assert_eq!(err.kind, crate::error::ErrorKind::User);
+        assert!(err.message.contains("only applies to metals"));
+
+        // Without a following symbol the unit word is an ordinary (too short) asset token.
+        let err = evaluate_query(&config_in_tempdir(), &providers, fixed_now, "10 g", "USD")
+            .expect_err("must fail");
+        assert!(err.message.contains("invalid asset token: g"));
+    }
+
+    #[test]
+    fn expression_numeric_mode_follows_precedence_parentheses_and_percentages() {
+        let providers = FakeProviders::new();
+        for (query, expected) in [
+            ("1 + 2 * 3", "7"),
+            ("(1 + 2) * 3", "9"),
+            ("10 - 4 - 3", "3"),
+            ("2 * -(1 + 2)", "-6"),
+            ("100 + 5%", "105"),
+            ("100 - 5 %", "95"),
+            ("200 * 5%", "10"),
+            ("10% + 5%", "15%"),
+        ] {
+            let feedback =
+                evaluate_query(&config_in_tempdir(), &providers, fixed_now, query, "USD")
+                    .unwrap_or_else(|error| panic!("{query}: {}", error.message));
+            assert_eq!(feedback.items[0].title, expected, "{query}");
+        }
+    }
+
+    #[test]
+    fn expression_asset_mode_scales_amounts_by_numbers_and_percentages() {
+        let providers = FakeProviders::new();
+        let feedback = evaluate_query(
+            &config_in_tempdir(),
+            &providers,
+            fixed_now,
+            "(2 btc + 3 eth) * 0.9 to jpy",
+            "USD",
+        )
+        .expect("must pass");
+
+        assert_eq!(feedback.items.len(), 3);
+        assert_eq!(feedback.items[2].title, "Total = 18945000 JPY");
+        assert_eq!(
+            feedback.items[2].subtitle.as_deref(),
+            Some("Formula: (2*10000000(BTC) + 3*350000(ETH)) * 0.9 = 18945000 JPY")
+        );
+
+        for (query, total, formula) in [
+            (
+                "100 usd * 1.05 to jpy",
+                "Total = 15750 JPY",
+                "Formula: 100*150.0(USD) * 1.05 = 15750 JPY",
+            ),
+            (
+                "100 usd + 5% to jpy",
+                "Total = 15750 JPY",
+                "Formula: 100*150.0(USD) + 5% = 15750 JPY",
+            ),
+            (
+                "1 btc - 10%",
+                "Total = 54000 USD",
+                "Formula: 1*60000(BTC) - 10% = 54000 USD",
+            ),
+            (
+                "1 btc / 4 - 2 * 1 eth",
+                "Total = 9000 USD",
+                "Formula: 1*60000(BTC) / 4 - 2 * 1*3000(ETH) = 9000 USD",
+            ),
+        ] {
+            let feedback =
+                evaluate_query(&config_in_tempdir(), &providers, fixed_now, query, "USD")
+                    .unwrap_or_else(|error| panic!("{query}: {}", error.message));
+            let total_row = feedback.items.last().expect("total row");
+            assert_eq!(total_row.title, total, "{query}");
+            assert_eq!(total_row.subtitle.as_deref(), Some(formula), "{query}");
+        }
+    }
+
+    #[test]
+    fn expression_errors_are_user_errors_raised_before_any_quote() {
+        let providers = FakeProviders::new();
+        for (query, message) in [
+            ("2 btc + 5", "mixed numeric and asset terms"),
+            ("5% - 1 eth", "mixed numeric and asset terms"),
+            ("1 btc * 2 eth", "unsupported operator"),
+            ("10 / 1 btc", "cannot divide by an asset amount"),
+            ("1 btc / (2 - 2)", "division by zero"),
+            ("(1 btc + 2 eth", "missing closing `)`"),
+            ("1 btc + 2 eth)", "unmatched `)`"),
+            ("()", "invalid number token"),
+            ("1 btc 2 eth", "invalid token near `2 eth`"),
+            ("5% btc", "invalid token near `btc`"),
+            ("1 btc *", "cannot end with an operator"),
+            ("79228162514264337593543950335 * 2", "out of range"),
+        ] {
+            let err = evaluate_query(&config_in_tempdir(), &providers, fixed_now, query, "USD")
+                .expect_err(query);
+            assert_eq!(err.kind, crate::error::ErrorKind::User, "{query}");
+            assert!(err.message.contains(message), "{query}: {}", err.message);
+        }
+        assert_eq!(providers.fx_calls.get(), 0);
+        assert_eq!(providers.coinbase_calls.get(), 0);
+    }
+
     #[test]
     fn expression_mixed_numeric_and_asset_terms_fail_as_user_error() {
         let providers = FakeProviders::new();
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::model::{
+    HistoryOutput, HistoryRange, HistoryRequest, MarketKind, looks_like_fiat_symbol,
+};
+use crate::providers::ProviderApi;
+use crate::service;
+
+const SPARKLINE_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Widest sparkline rendered in human and Alfred output; longer series keep the last point of each bucket.
+pub const SPARKLINE_MAX_WIDTH: usize = 30;
+
+/// Unicode block sparkline scaled between the series minimum and maximum. A flat series renders mid-height.
+pub fn sparkline(values: &[Decimal], max_width: usize) -> String {
+    let width = values.len().min(max_width.max(1));
+    let sampled = (1..=width)
+        .map(|bucket| values[bucket * values.len() / width - 1])
+        .collect::<Vec<_>>();
+    let (Some(min), Some(max)) = (sampled.iter().min(), sampled.iter().max()) else {
+        return String::new();
+    };
+    let spread = *max - *min;
+    let top = Decimal::from(SPARKLINE_BLOCKS.len() - 1);
+
+    sampled
+        .iter()
+        .map(|value| {
+            if spread.is_zero() {
+                return SPARKLINE_BLOCKS[SPARKLINE_BLOCKS.len() / 2 - 1];
+            }
+            let level = ((*value - *min) / spread * top)
+                .round()
+                .to_usize()
+                .unwrap_or(0);
+            SPARKLINE_BLOCKS[level.min(SPARKLINE_BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// History for an explicit kind, or, when `kind` is `None`, the same FX-then-crypto resolution `expr` uses for
+/// bare symbols: FX first when both sides look like fiat codes, then crypto.
+pub fn resolve_history<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    kind: Option<MarketKind>,
+    base: &str,
+    quote: &str,
+    range: HistoryRange,
+) -> Result<HistoryOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    if let Some(kind) = kind {
+        let request = HistoryRequest::new(kind, base, quote, range)?;
+        return service::resolve_history(config, providers, now_fn, &request);
+    }
+
+    let mut trace = Vec::new();
+    if looks_like_fiat_symbol(base.trim()) && looks_like_fiat_symbol(quote.trim()) {
+        let request = HistoryRequest::new(MarketKind::Fx, base, quote, range)?;
+        match service::resolve_history(config, providers, now_fn, &request) {
+            Ok(output) => return Ok(output),
+            Err(error) => trace.push(format!("fx: {}", error.message)),
+        }
+    }
+
+    let request = HistoryRequest::new(MarketKind::Crypto, base, quote, range)?;
+    match service::resolve_history(config, providers, now_fn, &request) {
+        Ok(output) => Ok(output),
+        Err(error) if trace.is_empty() => Err(error),
+        Err(error) => {
+            trace.push(format!("crypto: {}", error.message));
+            Err(AppError::runtime_with_trace(
+                &format!(
+                    "failed to resolve history for {}/{}",
+                    request.base, request.quote
+                ),
+                &trace,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimals(values: &[i64]) -> Vec<Decimal> {
+        values.iter().map(|value| Decimal::from(*value)).collect()
+    }
+
+    #[test]
+    fn sparkline_scales_between_min_and_max() {
+        assert_eq!(
+            sparkline(&decimals(&[1, 2, 3, 4, 5, 6, 7, 8]), 30),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(sparkline(&decimals(&[10, 0, 10]), 30), "█▁█");
+        assert_eq!(sparkline(&decimals(&[5, 5, 5]), 30), "▄▄▄");
+        assert_eq!(sparkline(&[], 30), "");
+    }
+
+    #[test]
+    fn sparkline_downsamples_to_the_last_point_of_each_bucket() {
+        let values = decimals(&(1..=90).collect::<Vec<_>>());
+        let line = sparkline(&values, SPARKLINE_MAX_WIDTH);
+        assert_eq!(line.chars().count(), SPARKLINE_MAX_WIDTH);
+        assert!(line.starts_with('▁'));
+        assert!(line.ends_with('█'));
+    }
+}
@@ -29,6 +29,16 @@ pub fn resolve_icon_path(config: &RuntimeConfig, symbol: &str) -> Option<PathBuf
     resolve_icon_path_with(config, symbol, &mut |url| fetch_icon_bytes(&client, url))
 }
 
+/// Generic market icon only, for symbols such as stock tickers that have no per-symbol asset on the icon CDN.
+pub fn resolve_generic_icon(config: &RuntimeConfig) -> Option<PathBuf> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(ICON_FETCH_TIMEOUT_SECS))
+        .build()
+        .ok()?;
+
+    resolve_generic_icon_path(config, &mut |url| fetch_icon_bytes(&client, url))
+}
+
 fn resolve_icon_path_with<F>(config: &RuntimeConfig, symbol: &str, fetch: &mut F) -> Option<PathBuf>
 where
     F: FnMut(&str) -> io::Result<FetchOutcome>,
@@ -138,8 +148,8 @@ fn fetch_icon_bytes(client: &Client, url: &str) -> io::Result<FetchOutcome> {
 mod tests {
     use super::*;
     use crate::config::{
-        CRYPTO_TTL_SECS, FX_TTL_SECS, ICON_GENERIC_BASENAME, ICON_SOURCE_CDN_BASE_URL,
-        RuntimeConfig,
+        CRYPTO_TTL_SECS, DEFAULT_CRYPTO_PROVIDERS, FX_TTL_SECS, FeeSchedule, ICON_GENERIC_BASENAME,
+        ICON_SOURCE_CDN_BASE_URL, METAL_TTL_SECS, RuntimeConfig, STOCK_TTL_SECS,
     };
 
     fn config_in_tempdir() -> RuntimeConfig {
@@ -148,9 +158,14 @@ mod tests {
         std::mem::forget(dir);
 
         RuntimeConfig {
-            cache_dir,
+            cache_dir: cache_dir.clone(),
+            data_dir: cache_dir,
             fx_cache_ttl_secs: FX_TTL_SECS,
             crypto_cache_ttl_secs: CRYPTO_TTL_SECS,
+            stock_cache_ttl_secs: STOCK_TTL_SECS,
+            metal_cache_ttl_secs: METAL_TTL_SECS,
+            crypto_providers: DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+            fees: FeeSchedule::default(),
         }
     }
 
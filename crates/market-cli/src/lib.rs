@@ -2,14 +2,18 @@ use std::collections::HashSet;
 
 use workflow_common::parse_ordered_list_with;
 
+pub mod alerts;
 pub mod cache;
 pub mod config;
 pub mod error;
 pub mod expression;
+pub mod history;
 pub mod icons;
 pub mod model;
 pub mod providers;
 pub mod service;
+pub mod store;
+pub mod watchlist;
 
 use crate::model::{ValidationError, normalize_crypto_symbol, normalize_fx_symbol};
 
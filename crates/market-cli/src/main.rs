@@ -1,4 +1,4 @@
-use alfred_core::{Feedback, Item, ItemIcon};
+use alfred_core::{Feedback, Item, ItemIcon, ItemModifier};
 use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand, ValueEnum};
 use rust_decimal::Decimal;
@@ -9,17 +9,28 @@ use workflow_common::{
 
 use market_cli::{
     FavoriteTarget,
+    alerts::{
+        self, Alert, AlertBook, AlertCheck, AlertDirection, AlertReport, AlertUpdateOutput,
+        alerts_path, load_alerts, save_alerts,
+    },
     config::RuntimeConfig,
     error::AppError,
-    expression, icons,
-    model::{MarketKind, MarketRequest, normalize_fx_symbol},
+    expression, history, icons,
+    model::{
+        HistoryOutput, HistoryRange, MarketKind, MarketRequest, MetalUnit, StockOutput,
+        decimal_to_string, normalize_fx_symbol, normalize_stock_symbol,
+    },
     parse_favorites_list,
-    providers::{HttpProviders, ProviderApi},
+    providers::{self, HttpProviders, ProviderApi},
     service,
+    watchlist::{
+        self, WatchEntry, WatchQuote, WatchUpdateOutput, WatchlistOutput, load_watchlist,
+        save_watchlist, watchlist_path,
+    },
 };
 
 #[derive(Debug, Parser)]
-#[command(author, version, about = "FX + crypto market data CLI")]
+#[command(author, version, about = "FX + crypto + metal + stock market data CLI")]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
@@ -38,7 +49,7 @@ enum Commands {
         #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
         output: OutputModeArg,
     },
-    /// Query crypto spot price (Coinbase with Kraken fallback).
+    /// Query crypto spot price through the `MARKET_CRYPTO_PROVIDERS` chain (Coinbase, Kraken, Binance, CoinGecko).
     Crypto {
         #[arg(long)]
         base: String,
@@ -49,6 +60,52 @@ enum Commands {
         #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
         output: OutputModeArg,
     },
+    /// Query precious metal spot price (gold-api.com, converted from USD through Frankfurter).
+    Metal {
+        /// XAU, XAG, or XPT.
+        #[arg(long)]
+        base: String,
+        #[arg(long)]
+        quote: String,
+        #[arg(long)]
+        amount: String,
+        #[arg(long, value_enum, default_value_t = MetalUnitArg::Oz)]
+        unit: MetalUnitArg,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Query stock quote (Yahoo Finance with Stooq fallback).
+    Stock {
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Query daily price history with a sparkline (Frankfurter for FX, Coinbase candles for crypto).
+    History {
+        #[arg(long)]
+        base: String,
+        #[arg(long)]
+        quote: String,
+        #[arg(long, default_value = "30d")]
+        range: String,
+        /// Market kind; omitted means FX when both symbols look like fiat codes, falling back to crypto.
+        #[arg(long, value_enum)]
+        kind: Option<MarketKindArg>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Manage the persistent watchlist and render it with live quotes.
+    Watch {
+        #[command(subcommand)]
+        action: WatchAction,
+    },
+    /// Manage price alerts and check them against live quotes.
+    #[command(visible_alias = "alerts")]
+    Alert {
+        #[command(subcommand)]
+        action: AlertAction,
+    },
     /// Evaluate market expressions and return Alfred Script Filter JSON.
     Expr {
         #[arg(long)]
@@ -69,14 +126,74 @@ enum Commands {
     },
 }
 
+#[derive(Debug, Subcommand)]
+enum WatchAction {
+    /// Add a BASE/QUOTE pair with an optional held amount; re-adding a pair replaces its amount.
+    Add {
+        pair: String,
+        amount: Option<String>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Remove a BASE/QUOTE pair.
+    Remove {
+        pair: String,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Render every entry with a live quote plus portfolio totals per quote currency.
+    List {
+        #[arg(long, value_enum, default_value_t = OutputModeArg::AlfredJson)]
+        output: OutputModeArg,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum AlertAction {
+    /// Alert when a BASE/QUOTE price goes above or below a threshold (inclusive).
+    Add {
+        pair: String,
+        #[arg(value_enum)]
+        direction: AlertDirectionArg,
+        threshold: String,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Remove an alert by id.
+    Remove {
+        id: u32,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Render every alert with its trigger state.
+    List {
+        #[arg(long, value_enum, default_value_t = OutputModeArg::AlfredJson)]
+        output: OutputModeArg,
+    },
+    /// Evaluate alerts against live quotes and post a macOS notification for each newly triggered one.
+    Check {
+        /// Report without posting notifications or saving trigger state.
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Json)]
+        output: OutputModeArg,
+    },
+}
+
 const ERROR_CODE_USER_INVALID_INPUT: &str = "NILS_MARKET_001";
 const ERROR_CODE_RUNTIME_PROVIDER_INIT: &str = "NILS_MARKET_002";
 const ERROR_CODE_RUNTIME_PROVIDER_FAILED: &str = "NILS_MARKET_002";
+const ERROR_CODE_RUNTIME_NOTIFY: &str = "NILS_MARKET_003";
 const ERROR_CODE_RUNTIME_SERIALIZE: &str = "NILS_COMMON_005";
 const FAVORITES_PROMPT_TITLE: &str = "Enter a market expression";
 const FAVORITES_PROMPT_EXAMPLE: &str = "Example: 1 BTC + 3 ETH to JPY";
 const FAVORITES_QUOTE_UNAVAILABLE_SUBTITLE: &str =
     "Favorite quote. Type an expression to convert. Quote unavailable.";
+const WATCHLIST_EMPTY_TITLE: &str = "Watchlist is empty";
+const WATCHLIST_EMPTY_SUBTITLE: &str = "Add a pair with: market-cli watch add BTC/USD 0.5";
+const ALERTS_EMPTY_TITLE: &str = "No price alerts";
+const ALERTS_EMPTY_SUBTITLE: &str = "Add one with: market-cli alert add BTC/USD above 70000";
+const ALERTS_QUIET_TITLE: &str = "No alerts triggered";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum OutputModeArg {
@@ -85,6 +202,53 @@ enum OutputModeArg {
     AlfredJson,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MarketKindArg {
+    Fx,
+    Crypto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MetalUnitArg {
+    /// Troy ounce.
+    Oz,
+    /// Gram.
+    G,
+}
+
+impl From<MetalUnitArg> for MetalUnit {
+    fn from(value: MetalUnitArg) -> Self {
+        match value {
+            MetalUnitArg::Oz => MetalUnit::TroyOunce,
+            MetalUnitArg::G => MetalUnit::Gram,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AlertDirectionArg {
+    Above,
+    Below,
+}
+
+impl From<AlertDirectionArg> for AlertDirection {
+    fn from(value: AlertDirectionArg) -> Self {
+        match value {
+            AlertDirectionArg::Above => AlertDirection::Above,
+            AlertDirectionArg::Below => AlertDirection::Below,
+        }
+    }
+}
+
+impl From<MarketKindArg> for MarketKind {
+    fn from(value: MarketKindArg) -> Self {
+        match value {
+            MarketKindArg::Fx => MarketKind::Fx,
+            MarketKindArg::Crypto => MarketKind::Crypto,
+        }
+    }
+}
+
 impl From<OutputModeArg> for OutputMode {
     fn from(value: OutputModeArg) -> Self {
         match value {
@@ -132,6 +296,20 @@ impl Cli {
         match &self.command {
             Commands::Fx { .. } => "market.fx",
             Commands::Crypto { .. } => "market.crypto",
+            Commands::Metal { .. } => "market.metal",
+            Commands::Stock { .. } => "market.stock",
+            Commands::History { .. } => "market.history",
+            Commands::Watch { action } => match action {
+                WatchAction::Add { .. } => "market.watch.add",
+                WatchAction::Remove { .. } => "market.watch.remove",
+                WatchAction::List { .. } => "market.watch.list",
+            },
+            Commands::Alert { action } => match action {
+                AlertAction::Add { .. } => "market.alert.add",
+                AlertAction::Remove { .. } => "market.alert.remove",
+                AlertAction::List { .. } => "market.alert.list",
+                AlertAction::Check { .. } => "market.alert.check",
+            },
             Commands::Expr { .. } => "market.expr",
             Commands::Favorites { .. } => "market.favorites",
         }
@@ -141,8 +319,22 @@ impl Cli {
         match &self.command {
             Commands::Fx { output, .. }
             | Commands::Crypto { output, .. }
+            | Commands::Metal { output, .. }
+            | Commands::Stock { output, .. }
+            | Commands::History { output, .. }
             | Commands::Expr { output, .. }
             | Commands::Favorites { output, .. } => (*output).into(),
+            Commands::Watch { action } => match action {
+                WatchAction::Add { output, .. }
+                | WatchAction::Remove { output, .. }
+                | WatchAction::List { output } => (*output).into(),
+            },
+            Commands::Alert { action } => match action {
+                AlertAction::Add { output, .. }
+                | AlertAction::Remove { output, .. }
+                | AlertAction::List { output }
+                | AlertAction::Check { output, .. } => (*output).into(),
+            },
         }
     }
 }
@@ -195,6 +387,7 @@ where
                 base: &base,
                 quote: &quote,
                 amount: &amount,
+                unit: None,
                 output,
             },
         ),
@@ -213,9 +406,53 @@ where
                 base: &base,
                 quote: &quote,
                 amount: &amount,
+                unit: None,
+                output,
+            },
+        ),
+        Commands::Metal {
+            base,
+            quote,
+            amount,
+            unit,
+            output,
+        } => run_market_command(
+            config,
+            providers,
+            now_fn,
+            MarketCommandArgs {
+                command: "market.metal",
+                kind: MarketKind::Metal,
+                base: &base,
+                quote: &quote,
+                amount: &amount,
+                unit: Some(unit.into()),
                 output,
             },
         ),
+        Commands::Stock { symbol, output } => {
+            run_stock_command(config, providers, now_fn, &symbol, output)
+        }
+        Commands::History {
+            base,
+            quote,
+            range,
+            kind,
+            output,
+        } => run_history_command(
+            config,
+            providers,
+            now_fn,
+            HistoryCommandArgs {
+                base: &base,
+                quote: &quote,
+                range: &range,
+                kind: kind.map(MarketKind::from),
+                output,
+            },
+        ),
+        Commands::Watch { action } => run_watch_command(config, providers, now_fn, action),
+        Commands::Alert { action } => run_alert_command(config, providers, now_fn, action),
         Commands::Expr {
             query,
             default_fiat,
@@ -286,6 +523,7 @@ struct MarketCommandArgs<'a> {
     base: &'a str,
     quote: &'a str,
     amount: &'a str,
+    unit: Option<MetalUnit>,
     output: OutputModeArg,
 }
 
@@ -300,10 +538,15 @@ where
     N: Fn() -> DateTime<Utc>,
 {
     let output_mode: OutputMode = args.output.into();
-    let request = MarketRequest::new(args.kind, args.base, args.quote, args.amount)
+    let mut request = MarketRequest::new(args.kind, args.base, args.quote, args.amount)
         .map_err(|error| user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string()))?;
+    if let Some(unit) = args.unit {
+        request = request.with_unit(unit);
+    }
     let result =
         service::resolve_market(config, providers, now_fn, &request).map_err(map_app_error)?;
+    let fee_pct = config.fees.pct_for(&result.provider);
+    let result = result.with_fee(fee_pct);
 
     match output_mode {
         OutputMode::Json => {
@@ -326,33 +569,307 @@ where
 
 fn format_market_human_output(output: &market_cli::model::MarketOutput) -> String {
     format!(
-        "{} {} {} -> {} {} (price={} provider={} cache={})",
+        "{} {} -> {} {}{} (price={} provider={} cache={})",
         output.kind.as_str().to_ascii_uppercase(),
-        output.amount,
-        output.base,
+        market_amount_label(output),
         output.converted,
         output.quote,
-        output.unit_price,
+        market_fee_suffix(output),
+        market_price_label(output),
         output.provider,
         cache_status_label(output.cache.status),
     )
 }
 
+/// `10 BTC`, or `10 g XAU` for metals.
+fn market_amount_label(output: &market_cli::model::MarketOutput) -> String {
+    match output.unit {
+        Some(unit) => format!("{} {} {}", output.amount, unit.as_str(), output.base),
+        None => format!("{} {}", output.amount, output.base),
+    }
+}
+
+/// ` (3193.95 TWD after 0.5% fee)` when `MARKET_FEE_PCT` applies, otherwise empty.
+fn market_fee_suffix(output: &market_cli::model::MarketOutput) -> String {
+    match (&output.fee_pct, &output.converted_after_fee) {
+        (Some(fee_pct), Some(after_fee)) => {
+            format!(" ({after_fee} {} after {fee_pct}% fee)", output.quote)
+        }
+        _ => String::new(),
+    }
+}
+
+/// Unit price, suffixed with the metal unit it is quoted per (`2345.6/oz`).
+fn market_price_label(output: &market_cli::model::MarketOutput) -> String {
+    match output.unit {
+        Some(unit) => format!("{}/{}", output.unit_price, unit.as_str()),
+        None => output.unit_price.clone(),
+    }
+}
+
+/// `1 / unit_price`: how much `base` (per metal unit for metals) one `quote` buys.
+fn inverse_unit_price(output: &market_cli::model::MarketOutput) -> Option<String> {
+    let unit_price = output.unit_price.parse::<Decimal>().ok()?;
+    let inverse = Decimal::ONE.checked_div(unit_price)?;
+    Some(decimal_to_string(&inverse.round_sf(12).unwrap_or(inverse)))
+}
+
 fn render_market_alfred_output(
     config: &RuntimeConfig,
     output: &market_cli::model::MarketOutput,
 ) -> Result<String, CliError> {
-    let item = Item::new(format!(
-        "{} {} = {} {}",
-        output.amount, output.base, output.converted, output.quote
+    let mut item = Item::new(format!(
+        "{} = {} {}{}",
+        market_amount_label(output),
+        output.converted,
+        output.quote,
+        market_fee_suffix(output)
     ))
     .with_subtitle(format!(
         "price={} provider={} cache={}",
-        output.unit_price,
+        market_price_label(output),
         output.provider,
         cache_status_label(output.cache.status)
     ))
     .with_arg(output.converted.clone())
+    .with_valid(false)
+    .with_mod(
+        "alt",
+        ItemModifier::new()
+            .with_subtitle(format!("Copy unit price: {}", output.unit_price))
+            .with_arg(output.unit_price.clone())
+            .with_valid(true),
+    );
+    if let Some(inverse) = inverse_unit_price(output) {
+        let base_label = match output.unit {
+            Some(unit) => format!("{} {}", unit.as_str(), output.base),
+            None => output.base.clone(),
+        };
+        item = item.with_mod(
+            "cmd",
+            ItemModifier::new()
+                .with_subtitle(format!(
+                    "Copy inverse rate: 1 {} = {inverse} {base_label}",
+                    output.quote
+                ))
+                .with_arg(inverse)
+                .with_valid(true),
+        );
+    }
+    if let Some(url) = providers::chart_url(&output.provider, &output.base, &output.quote) {
+        item = item.with_mod(
+            "shift",
+            ItemModifier::new()
+                .with_subtitle(format!(
+                    "Open {} chart for {}/{}",
+                    output.provider, output.base, output.quote
+                ))
+                .with_arg(url)
+                .with_valid(true),
+        );
+    }
+    let item = with_symbol_icon(item, config, &output.base);
+
+    Feedback::new(vec![item]).to_json().map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+fn run_stock_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    symbol: &str,
+    output: OutputModeArg,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let output_mode: OutputMode = output.into();
+    let symbol = normalize_stock_symbol(symbol, "symbol")
+        .map_err(|error| user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string()))?;
+    let result =
+        service::resolve_stock(config, providers, now_fn, &symbol).map_err(map_app_error)?;
+
+    match output_mode {
+        OutputMode::Json => {
+            let raw = serde_json::to_string(&result).map_err(|error| {
+                runtime_error(
+                    ERROR_CODE_RUNTIME_SERIALIZE,
+                    format!("failed to serialize output: {error}"),
+                )
+            })?;
+            Ok(build_success_envelope(
+                "market.stock",
+                EnvelopePayloadKind::Result,
+                &raw,
+            ))
+        }
+        OutputMode::Human => Ok(format_stock_human_output(&result)),
+        OutputMode::AlfredJson => render_stock_alfred_output(config, &result),
+    }
+}
+
+fn stock_price_label(output: &StockOutput) -> String {
+    match &output.currency {
+        Some(currency) => format!("{} {}", output.price, currency),
+        None => output.price.clone(),
+    }
+}
+
+fn stock_change_label(output: &StockOutput) -> Option<String> {
+    let change = output.change.as_deref()?;
+    let change_pct = output.change_pct.as_deref()?;
+    let sign = if change.starts_with('-') { "" } else { "+" };
+    Some(format!("{sign}{change} ({sign}{change_pct}%)"))
+}
+
+fn format_stock_human_output(output: &StockOutput) -> String {
+    let change = stock_change_label(output)
+        .map(|change| format!(" {change}"))
+        .unwrap_or_default();
+    format!(
+        "STOCK {} {}{} (provider={} cache={})",
+        output.symbol,
+        stock_price_label(output),
+        change,
+        output.provider,
+        cache_status_label(output.cache.status),
+    )
+}
+
+fn render_stock_alfred_output(
+    config: &RuntimeConfig,
+    output: &StockOutput,
+) -> Result<String, CliError> {
+    let mut subtitle = format!(
+        "provider={} cache={}",
+        output.provider,
+        cache_status_label(output.cache.status)
+    );
+    if let Some(change) = stock_change_label(output) {
+        subtitle = format!("{change} {subtitle}");
+    }
+
+    let mut item = Item::new(format!("{} {}", output.symbol, stock_price_label(output)))
+        .with_subtitle(subtitle)
+        .with_arg(output.price.clone())
+        .with_valid(false);
+    if let Some(path) = icons::resolve_generic_icon(config) {
+        item = item.with_icon(ItemIcon::new(path.to_string_lossy().into_owned()));
+    }
+
+    Feedback::new(vec![item]).to_json().map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct HistoryCommandArgs<'a> {
+    base: &'a str,
+    quote: &'a str,
+    range: &'a str,
+    kind: Option<MarketKind>,
+    output: OutputModeArg,
+}
+
+fn run_history_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: HistoryCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let output_mode: OutputMode = args.output.into();
+    let range = HistoryRange::parse(args.range)
+        .map_err(|error| user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string()))?;
+    let result = history::resolve_history(
+        config, providers, now_fn, args.kind, args.base, args.quote, range,
+    )
+    .map_err(map_app_error)?;
+
+    match output_mode {
+        OutputMode::Json => {
+            let raw = serde_json::to_string(&result).map_err(|error| {
+                runtime_error(
+                    ERROR_CODE_RUNTIME_SERIALIZE,
+                    format!("failed to serialize output: {error}"),
+                )
+            })?;
+            Ok(build_success_envelope(
+                "market.history",
+                EnvelopePayloadKind::Result,
+                &raw,
+            ))
+        }
+        OutputMode::Human => Ok(format_history_human_output(&result)),
+        OutputMode::AlfredJson => render_history_alfred_output(config, &result),
+    }
+}
+
+fn history_sparkline(output: &HistoryOutput) -> String {
+    let prices = output
+        .points
+        .iter()
+        .filter_map(|point| point.price.parse::<Decimal>().ok())
+        .collect::<Vec<_>>();
+    history::sparkline(&prices, history::SPARKLINE_MAX_WIDTH)
+}
+
+fn history_summary_label(output: &HistoryOutput) -> String {
+    let change = output
+        .change_pct
+        .as_deref()
+        .map(|change_pct| {
+            let sign = if change_pct.starts_with('-') { "" } else { "+" };
+            format!(" change={sign}{change_pct}%")
+        })
+        .unwrap_or_default();
+    format!(
+        "latest={} min={} max={}{} (provider={} cache={})",
+        output.latest,
+        output.min,
+        output.max,
+        change,
+        output.provider,
+        cache_status_label(output.cache.status),
+    )
+}
+
+fn format_history_human_output(output: &HistoryOutput) -> String {
+    format!(
+        "HISTORY {}/{} {} {} {}",
+        output.base,
+        output.quote,
+        output.range,
+        history_sparkline(output),
+        history_summary_label(output),
+    )
+}
+
+fn render_history_alfred_output(
+    config: &RuntimeConfig,
+    output: &HistoryOutput,
+) -> Result<String, CliError> {
+    let item = Item::new(format!(
+        "{}/{} {} {}",
+        output.base,
+        output.quote,
+        output.range,
+        history_sparkline(output)
+    ))
+    .with_subtitle(history_summary_label(output))
+    .with_arg(output.latest.clone())
     .with_valid(false);
     let item = with_symbol_icon(item, config, &output.base);
 
@@ -364,6 +881,523 @@ fn render_market_alfred_output(
     })
 }
 
+fn run_watch_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    action: WatchAction,
+) -> Result<String, CliError>
+where
+    P: ProviderApi + Clone + Send,
+    N: Fn() -> DateTime<Utc> + Copy + Send,
+{
+    let path = watchlist_path(config);
+    let mut list = load_watchlist(&path).map_err(map_app_error)?;
+
+    let (command, output, result) = match action {
+        WatchAction::List { output } => {
+            let result = watchlist::quote_watchlist(config, providers, now_fn, &list);
+            return match output.into() {
+                OutputMode::Json => serialize_envelope("market.watch.list", &result),
+                OutputMode::Human => Ok(format_watchlist_human_output(&result)),
+                OutputMode::AlfredJson => render_watchlist_alfred_output(config, &result),
+            };
+        }
+        WatchAction::Add {
+            pair,
+            amount,
+            output,
+        } => {
+            let entry = WatchEntry::new(&pair, amount.as_deref())
+                .map_err(|error| user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string()))?;
+            let replaced = list.upsert(entry.clone());
+            let result = WatchUpdateOutput {
+                action: "add",
+                entry,
+                replaced,
+                entries: list.entries.len(),
+            };
+            ("market.watch.add", output, result)
+        }
+        WatchAction::Remove { pair, output } => {
+            let entry = WatchEntry::new(&pair, None)
+                .map_err(|error| user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string()))?;
+            if !list.remove(&entry.base, &entry.quote) {
+                return Err(user_error(
+                    ERROR_CODE_USER_INVALID_INPUT,
+                    format!("{} is not in the watchlist", entry.pair()),
+                ));
+            }
+            let result = WatchUpdateOutput {
+                action: "remove",
+                entry,
+                replaced: false,
+                entries: list.entries.len(),
+            };
+            ("market.watch.remove", output, result)
+        }
+    };
+
+    save_watchlist(&path, &list).map_err(map_app_error)?;
+
+    match output.into() {
+        OutputMode::Json => serialize_envelope(command, &result),
+        OutputMode::Human => Ok(format_watch_update_human_output(&result)),
+        OutputMode::AlfredJson => Feedback::new(vec![
+            Item::new(format_watch_update_human_output(&result)).with_valid(false),
+        ])
+        .to_json()
+        .map_err(|error| {
+            runtime_error(
+                ERROR_CODE_RUNTIME_SERIALIZE,
+                format!("failed to serialize Alfred output: {error}"),
+            )
+        }),
+    }
+}
+
+fn run_alert_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    action: AlertAction,
+) -> Result<String, CliError>
+where
+    P: ProviderApi + Clone + Send,
+    N: Fn() -> DateTime<Utc> + Copy + Send,
+{
+    let path = alerts_path(config);
+    let mut book = load_alerts(&path).map_err(map_app_error)?;
+
+    let (command, output, result) = match action {
+        AlertAction::List { output } => {
+            return match output.into() {
+                OutputMode::Json => serialize_envelope("market.alert.list", &book),
+                OutputMode::Human => Ok(format_alert_list_human_output(&book)),
+                OutputMode::AlfredJson => render_alert_list_alfred_output(config, &book),
+            };
+        }
+        AlertAction::Check { dry_run, output } => {
+            let before = book.clone();
+            let mut report = alerts::check_alerts(config, providers, now_fn, &mut book);
+            if !dry_run {
+                for check in &report.triggered {
+                    alerts::post_notification(
+                        &check.notification_title(),
+                        &check.notification_message(),
+                    )
+                    .map_err(|error| runtime_error(ERROR_CODE_RUNTIME_NOTIFY, error.message))?;
+                }
+                report.notified = !report.triggered.is_empty();
+                if book != before {
+                    save_alerts(&path, &book).map_err(map_app_error)?;
+                }
+            }
+            return match output.into() {
+                OutputMode::Json => serialize_envelope("market.alert.check", &report),
+                OutputMode::Human => Ok(format_alert_report_human_output(&report)),
+                OutputMode::AlfredJson => render_alert_report_alfred_output(config, &report),
+            };
+        }
+        AlertAction::Add {
+            pair,
+            direction,
+            threshold,
+            output,
+        } => {
+            let alert = book
+                .add(&pair, direction.into(), &threshold)
+                .map_err(|error| user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string()))?;
+            let result = AlertUpdateOutput {
+                action: "add",
+                alert,
+                alerts: book.alerts.len(),
+            };
+            ("market.alert.add", output, result)
+        }
+        AlertAction::Remove { id, output } => {
+            let alert = book.remove(id).ok_or_else(|| {
+                user_error(
+                    ERROR_CODE_USER_INVALID_INPUT,
+                    format!("alert #{id} does not exist"),
+                )
+            })?;
+            let result = AlertUpdateOutput {
+                action: "remove",
+                alert,
+                alerts: book.alerts.len(),
+            };
+            ("market.alert.remove", output, result)
+        }
+    };
+
+    save_alerts(&path, &book).map_err(map_app_error)?;
+
+    match output.into() {
+        OutputMode::Json => serialize_envelope(command, &result),
+        OutputMode::Human => Ok(format_alert_update_human_output(&result)),
+        OutputMode::AlfredJson => Feedback::new(vec![
+            Item::new(format_alert_update_human_output(&result)).with_valid(false),
+        ])
+        .to_json()
+        .map_err(|error| {
+            runtime_error(
+                ERROR_CODE_RUNTIME_SERIALIZE,
+                format!("failed to serialize Alfred output: {error}"),
+            )
+        }),
+    }
+}
+
+fn format_alert_update_human_output(result: &AlertUpdateOutput) -> String {
+    let verb = if result.action == "remove" {
+        "removed alert"
+    } else {
+        "alert"
+    };
+    format!(
+        "{verb} #{}: {} (alerts={})",
+        result.alert.id,
+        result.alert.describe(),
+        result.alerts
+    )
+}
+
+fn alert_state_label(alert: &Alert) -> String {
+    match (&alert.triggered, &alert.last_triggered_at) {
+        (true, Some(at)) => format!("triggered at {at}"),
+        (true, None) => "triggered".to_string(),
+        (false, _) => "armed".to_string(),
+    }
+}
+
+fn format_alert_list_human_output(book: &AlertBook) -> String {
+    if book.alerts.is_empty() {
+        return ALERTS_EMPTY_TITLE.to_ascii_lowercase();
+    }
+
+    book.alerts
+        .iter()
+        .map(|alert| {
+            format!(
+                "#{} {} ({})",
+                alert.id,
+                alert.describe(),
+                alert_state_label(alert)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_alert_list_alfred_output(
+    config: &RuntimeConfig,
+    book: &AlertBook,
+) -> Result<String, CliError> {
+    let items = if book.alerts.is_empty() {
+        vec![
+            Item::new(ALERTS_EMPTY_TITLE)
+                .with_uid("market-alert-empty")
+                .with_subtitle(ALERTS_EMPTY_SUBTITLE)
+                .with_valid(false),
+        ]
+    } else {
+        book.alerts
+            .iter()
+            .map(|alert| {
+                let item = Item::new(alert.describe())
+                    .with_uid(format!("market-alert-{}", alert.id))
+                    .with_subtitle(format!(
+                        "Alert #{} · {}",
+                        alert.id,
+                        alert_state_label(alert)
+                    ))
+                    .with_arg(alert.id.to_string())
+                    .with_valid(false);
+                with_symbol_icon(item, config, &alert.base)
+            })
+            .collect()
+    };
+
+    Feedback::new(items).to_json().map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize alerts Alfred output: {error}"),
+        )
+    })
+}
+
+fn format_alert_check_price(check: &AlertCheck) -> String {
+    check
+        .price
+        .as_deref()
+        .map(format_watch_price)
+        .unwrap_or_else(|| "?".to_string())
+}
+
+fn format_alert_report_human_output(report: &AlertReport) -> String {
+    if report.triggered.is_empty() && report.failed.is_empty() {
+        return format!("no alerts triggered (checked={})", report.checked);
+    }
+
+    report
+        .triggered
+        .iter()
+        .map(|check| {
+            format!(
+                "ALERT #{} {} {} {} {} {}",
+                check.id,
+                check.pair(),
+                format_alert_check_price(check),
+                check.quote,
+                check.direction,
+                check.threshold
+            )
+        })
+        .chain(report.failed.iter().map(|check| {
+            format!(
+                "FAILED #{} {}: {}",
+                check.id,
+                check.pair(),
+                check.error.as_deref().unwrap_or("no quote")
+            )
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_alert_report_alfred_output(
+    config: &RuntimeConfig,
+    report: &AlertReport,
+) -> Result<String, CliError> {
+    let mut items = report
+        .triggered
+        .iter()
+        .map(|check| {
+            let item = Item::new(format!(
+                "{} {} {} ({} {})",
+                check.pair(),
+                format_alert_check_price(check),
+                check.quote,
+                check.direction,
+                check.threshold
+            ))
+            .with_uid(format!("market-alert-{}", check.id))
+            .with_subtitle(format!("Alert #{} triggered", check.id))
+            .with_arg(check.price.clone().unwrap_or_default())
+            .with_valid(false);
+            with_symbol_icon(item, config, &check.base)
+        })
+        .chain(report.failed.iter().map(|check| {
+            let item = Item::new(check.pair())
+                .with_uid(format!("market-alert-{}", check.id))
+                .with_subtitle(format!(
+                    "Alert #{} unchecked: {}",
+                    check.id,
+                    check.error.as_deref().unwrap_or("no quote")
+                ))
+                .with_valid(false);
+            with_symbol_icon(item, config, &check.base)
+        }))
+        .collect::<Vec<_>>();
+
+    if items.is_empty() {
+        items.push(
+            Item::new(ALERTS_QUIET_TITLE)
+                .with_uid("market-alert-quiet")
+                .with_subtitle(format!("{} alerts checked", report.checked))
+                .with_valid(false),
+        );
+    }
+
+    Feedback::new(items).to_json().map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize alert check Alfred output: {error}"),
+        )
+    })
+}
+
+fn serialize_envelope<T: serde::Serialize>(command: &str, result: &T) -> Result<String, CliError> {
+    let raw = serde_json::to_string(result).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &raw,
+    ))
+}
+
+fn format_watch_update_human_output(result: &WatchUpdateOutput) -> String {
+    let verb = match (result.action, result.replaced) {
+        ("remove", _) => "removed",
+        (_, true) => "updated",
+        _ => "watching",
+    };
+    let amount = result
+        .entry
+        .amount
+        .as_deref()
+        .filter(|_| result.action != "remove")
+        .map(|amount| format!(" amount={amount}"))
+        .unwrap_or_default();
+    format!(
+        "{verb} {}{amount} (entries={})",
+        result.entry.pair(),
+        result.entries
+    )
+}
+
+fn format_watch_price(raw: &str) -> String {
+    raw.parse::<Decimal>()
+        .map(expression::format_market_decimal)
+        .unwrap_or_else(|_| raw.to_string())
+}
+
+fn format_watchlist_human_output(result: &WatchlistOutput) -> String {
+    if result.entries.is_empty() {
+        return WATCHLIST_EMPTY_TITLE.to_ascii_lowercase();
+    }
+
+    let mut lines = result
+        .entries
+        .iter()
+        .map(|quote| {
+            let holding = quote
+                .entry
+                .amount
+                .as_deref()
+                .map(|amount| format!(" x{amount}"))
+                .unwrap_or_default();
+            match (&quote.price, &quote.provider, quote.cache_status) {
+                (Some(price), Some(provider), Some(status)) => {
+                    let value = quote
+                        .value
+                        .as_deref()
+                        .map(|value| format!(" = {}", format_watch_price(value)))
+                        .unwrap_or_default();
+                    format!(
+                        "{} {}{holding}{value} (provider={provider} cache={})",
+                        quote.entry.pair(),
+                        format_watch_price(price),
+                        cache_status_label(status),
+                    )
+                }
+                _ => format!(
+                    "{}{holding} unavailable: {}",
+                    quote.entry.pair(),
+                    quote.error.as_deref().unwrap_or("no quote")
+                ),
+            }
+        })
+        .collect::<Vec<_>>();
+    lines.extend(result.totals.iter().map(|total| {
+        let unavailable = if total.unavailable > 0 {
+            format!(" ({} unavailable)", total.unavailable)
+        } else {
+            String::new()
+        };
+        format!(
+            "TOTAL {} {}{unavailable}",
+            format_watch_price(&total.value),
+            total.quote
+        )
+    }));
+    lines.join("\n")
+}
+
+fn render_watchlist_alfred_output(
+    config: &RuntimeConfig,
+    result: &WatchlistOutput,
+) -> Result<String, CliError> {
+    let mut items = Vec::with_capacity(result.entries.len() + result.totals.len());
+
+    if result.entries.is_empty() {
+        items.push(
+            Item::new(WATCHLIST_EMPTY_TITLE)
+                .with_uid("market-watch-empty")
+                .with_subtitle(WATCHLIST_EMPTY_SUBTITLE)
+                .with_valid(false),
+        );
+    }
+
+    items.extend(result.totals.iter().map(|total| {
+        let mut subtitle = format!("Portfolio total · {} holdings", total.holdings);
+        if total.unavailable > 0 {
+            subtitle.push_str(&format!(" · {} unavailable", total.unavailable));
+        }
+        Item::new(format!(
+            "Total = {} {}",
+            format_watch_price(&total.value),
+            total.quote
+        ))
+        .with_uid(format!(
+            "market-watch-total-{}",
+            total.quote.to_ascii_lowercase()
+        ))
+        .with_subtitle(subtitle)
+        .with_arg(total.value.clone())
+        .with_valid(false)
+    }));
+
+    items.extend(
+        result
+            .entries
+            .iter()
+            .map(|quote| watch_quote_item(config, quote)),
+    );
+
+    Feedback::new(items).to_json().map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize watchlist Alfred output: {error}"),
+        )
+    })
+}
+
+fn watch_quote_item(config: &RuntimeConfig, quote: &WatchQuote) -> Item {
+    let entry = &quote.entry;
+    let uid = format!(
+        "market-watch-{}-{}",
+        entry.base.to_ascii_lowercase(),
+        entry.quote.to_ascii_lowercase()
+    );
+    let item = match (&quote.price, &quote.provider, quote.cache_status) {
+        (Some(price), Some(provider), Some(status)) => {
+            let title = match (&entry.amount, &quote.value) {
+                (Some(amount), Some(value)) => format!(
+                    "{amount} {} = {} {}",
+                    entry.base,
+                    format_watch_price(value),
+                    entry.quote
+                ),
+                _ => format!(
+                    "1 {} = {} {}",
+                    entry.base,
+                    format_watch_price(price),
+                    entry.quote
+                ),
+            };
+            Item::new(title)
+                .with_subtitle(format!(
+                    "provider: {provider} · freshness: {}",
+                    cache_status_label(status)
+                ))
+                .with_arg(quote.value.clone().unwrap_or_else(|| price.clone()))
+        }
+        _ => Item::new(entry.pair()).with_subtitle(format!(
+            "Quote unavailable: {}",
+            quote.error.as_deref().unwrap_or("no quote")
+        )),
+    };
+
+    with_symbol_icon(item.with_uid(uid).with_valid(false), config, &entry.base)
+}
+
 fn format_favorites_human_output(favorites: &[FavoriteTarget]) -> String {
     format!(
         "favorites: {}",
@@ -501,7 +1535,7 @@ where
 {
     match favorite {
         FavoriteTarget::Symbol { symbol, quote } => {
-            expression::resolve_symbol_output(config, providers, now_fn, symbol, quote)
+            service::resolve_symbol_output(config, providers, now_fn, symbol, quote)
         }
         FavoriteTarget::FxPair { base, quote } => {
             let request =
@@ -624,7 +1658,7 @@ mod tests {
     use market_cli::{
         cache::{CacheRecord, cache_path, write_cache},
         icon_asset_filename,
-        model::{MarketKind, MarketQuote},
+        model::{MarketKind, MarketQuote, PricePoint, PriceSeries, StockQuote},
         providers::ProviderError,
     };
     use serde_json::Value;
@@ -636,6 +1670,11 @@ mod tests {
         fx_result: Result<MarketQuote, ProviderError>,
         crypto_coinbase_result: Result<MarketQuote, ProviderError>,
         crypto_kraken_result: Result<MarketQuote, ProviderError>,
+        metal_result: Result<MarketQuote, ProviderError>,
+        stock_yahoo_result: Result<StockQuote, ProviderError>,
+        stock_stooq_result: Result<StockQuote, ProviderError>,
+        fx_history_result: Result<PriceSeries, ProviderError>,
+        crypto_history_result: Result<PriceSeries, ProviderError>,
     }
 
     impl FakeProviders {
@@ -660,29 +1699,115 @@ mod tests {
                     rust_decimal::Decimal::new(670000, 1),
                     now,
                 )),
+                metal_result: Ok(MarketQuote::new(
+                    "gold-api",
+                    rust_decimal::Decimal::new(2400, 0),
+                    now,
+                )),
+                stock_yahoo_result: Ok(StockQuote {
+                    provider: "yahoo".to_string(),
+                    price: rust_decimal::Decimal::new(22752, 2),
+                    currency: Some("USD".to_string()),
+                    previous_close: Some(rust_decimal::Decimal::new(22500, 2)),
+                    fetched_at: now,
+                }),
+                stock_stooq_result: Err(ProviderError::Transport(
+                    "stooq disabled in tests".to_string(),
+                )),
+                fx_history_result: Ok(fixture_series("frankfurter", &[3210, 3190, 3250, 3280])),
+                crypto_history_result: Ok(fixture_series("coinbase", &[66000, 68000, 67000])),
             }
         }
     }
 
-    impl ProviderApi for FakeProviders {
-        fn fetch_fx_rate(&self, _base: &str, _quote: &str) -> Result<MarketQuote, ProviderError> {
-            self.fx_result.clone()
+    impl ProviderApi for FakeProviders {
+        fn fetch_fx_rate(&self, _base: &str, _quote: &str) -> Result<MarketQuote, ProviderError> {
+            self.fx_result.clone()
+        }
+
+        fn fetch_crypto_coinbase(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            self.crypto_coinbase_result.clone()
+        }
+
+        fn fetch_crypto_kraken(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            self.crypto_kraken_result.clone()
+        }
+
+        fn fetch_crypto_binance(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            Err(ProviderError::Transport(
+                "binance disabled in tests".to_string(),
+            ))
+        }
+
+        fn fetch_crypto_coingecko(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            Err(ProviderError::Transport(
+                "coingecko disabled in tests".to_string(),
+            ))
+        }
+
+        fn fetch_metal_spot(&self, _metal: &str) -> Result<MarketQuote, ProviderError> {
+            self.metal_result.clone()
+        }
+
+        fn fetch_stock_yahoo(&self, _symbol: &str) -> Result<StockQuote, ProviderError> {
+            self.stock_yahoo_result.clone()
+        }
+
+        fn fetch_stock_stooq(&self, _symbol: &str) -> Result<StockQuote, ProviderError> {
+            self.stock_stooq_result.clone()
         }
 
-        fn fetch_crypto_coinbase(
+        fn fetch_fx_history(
             &self,
             _base: &str,
             _quote: &str,
-        ) -> Result<MarketQuote, ProviderError> {
-            self.crypto_coinbase_result.clone()
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<PriceSeries, ProviderError> {
+            self.fx_history_result.clone()
         }
 
-        fn fetch_crypto_kraken(
+        fn fetch_crypto_history(
             &self,
             _base: &str,
             _quote: &str,
-        ) -> Result<MarketQuote, ProviderError> {
-            self.crypto_kraken_result.clone()
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<PriceSeries, ProviderError> {
+            self.crypto_history_result.clone()
+        }
+    }
+
+    /// Consecutive daily closes ending 2026-02-10; prices are in hundredths.
+    fn fixture_series(provider: &str, prices: &[i64]) -> PriceSeries {
+        let end = chrono::NaiveDate::from_ymd_opt(2026, 2, 10).expect("date");
+        PriceSeries {
+            provider: provider.to_string(),
+            points: prices
+                .iter()
+                .enumerate()
+                .map(|(index, price)| PricePoint {
+                    date: end - chrono::Duration::days((prices.len() - 1 - index) as i64),
+                    price: rust_decimal::Decimal::new(*price, 2),
+                })
+                .collect(),
+            fetched_at: fixed_now(),
         }
     }
 
@@ -750,6 +1875,58 @@ mod tests {
                 "kraken disabled in tests".to_string(),
             ))
         }
+
+        fn fetch_crypto_binance(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            Err(ProviderError::Transport(
+                "binance disabled in tests".to_string(),
+            ))
+        }
+
+        fn fetch_crypto_coingecko(
+            &self,
+            _base: &str,
+            _quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            Err(ProviderError::Transport(
+                "coingecko disabled in tests".to_string(),
+            ))
+        }
+
+        fn fetch_metal_spot(&self, metal: &str) -> Result<MarketQuote, ProviderError> {
+            Err(ProviderError::UnsupportedPair(format!("{metal}/USD")))
+        }
+
+        fn fetch_stock_yahoo(&self, symbol: &str) -> Result<StockQuote, ProviderError> {
+            Err(ProviderError::UnknownSymbol(symbol.to_string()))
+        }
+
+        fn fetch_stock_stooq(&self, symbol: &str) -> Result<StockQuote, ProviderError> {
+            Err(ProviderError::UnknownSymbol(symbol.to_string()))
+        }
+
+        fn fetch_fx_history(
+            &self,
+            base: &str,
+            quote: &str,
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<PriceSeries, ProviderError> {
+            Err(ProviderError::UnsupportedPair(format!("{base}/{quote}")))
+        }
+
+        fn fetch_crypto_history(
+            &self,
+            base: &str,
+            quote: &str,
+            _start: chrono::NaiveDate,
+            _end: chrono::NaiveDate,
+        ) -> Result<PriceSeries, ProviderError> {
+            Err(ProviderError::UnsupportedPair(format!("{base}/{quote}")))
+        }
     }
 
     fn config_in_tempdir() -> RuntimeConfig {
@@ -758,9 +1935,14 @@ mod tests {
         std::mem::forget(dir);
 
         let config = RuntimeConfig {
-            cache_dir,
+            cache_dir: cache_dir.clone(),
+            data_dir: cache_dir,
             fx_cache_ttl_secs: market_cli::config::FX_TTL_SECS,
             crypto_cache_ttl_secs: market_cli::config::CRYPTO_TTL_SECS,
+            stock_cache_ttl_secs: market_cli::config::STOCK_TTL_SECS,
+            metal_cache_ttl_secs: market_cli::config::METAL_TTL_SECS,
+            crypto_providers: market_cli::config::DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+            fees: market_cli::config::FeeSchedule::default(),
         };
         seed_icon_files(&config, &["BTC", "ETH", "USD", "JPY"]);
         config
@@ -869,6 +2051,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn main_market_outputs_show_raw_and_fee_adjusted_amounts() {
+        let config = RuntimeConfig {
+            fees: market_cli::config::FeeSchedule {
+                default_pct: Some(Decimal::new(5, 1)),
+                provider_pct: vec![("coinbase".to_string(), Decimal::new(15, 1))],
+            },
+            ..config_in_tempdir()
+        };
+        let run = |args: &[&str]| {
+            run_with(
+                Cli::parse_from(args),
+                &config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+            .expect("quote")
+        };
+
+        let json: Value = serde_json::from_str(&run(&[
+            "market-cli",
+            "fx",
+            "--base",
+            "USD",
+            "--quote",
+            "TWD",
+            "--amount",
+            "100",
+            "--output",
+            "json",
+        ]))
+        .expect("json");
+        assert_eq!(json["result"]["converted"], "3210");
+        assert_eq!(json["result"]["fee_pct"], "0.5");
+        assert_eq!(json["result"]["converted_after_fee"], "3193.95");
+
+        let human = run(&[
+            "market-cli",
+            "crypto",
+            "--base",
+            "BTC",
+            "--quote",
+            "USD",
+            "--amount",
+            "1",
+        ]);
+        assert!(
+            human.contains("-> 67000.1 USD (65995.0985 USD after 1.5% fee) (price=67000.1"),
+            "{human}"
+        );
+
+        let json: Value = serde_json::from_str(&run(&[
+            "market-cli",
+            "crypto",
+            "--base",
+            "BTC",
+            "--quote",
+            "USD",
+            "--amount",
+            "1",
+            "--output",
+            "alfred-json",
+        ]))
+        .expect("json");
+        assert_eq!(
+            json["items"][0]["title"],
+            "1 BTC = 67000.1 USD (65995.0985 USD after 1.5% fee)"
+        );
+        assert_eq!(json["items"][0]["arg"], "67000.1");
+    }
+
     #[test]
     fn main_outputs_crypto_json_contract() {
         let cli = Cli::parse_from([
@@ -907,6 +2160,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn main_outputs_metal_quote_per_unit() {
+        let config = config_in_tempdir();
+        let cli = Cli::parse_from([
+            "market-cli",
+            "metal",
+            "--base",
+            "xau",
+            "--quote",
+            "TWD",
+            "--amount",
+            "10",
+            "--unit",
+            "g",
+        ]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("metal");
+        assert!(output.starts_with("METAL 10 g XAU -> 24768.93"));
+        assert!(output.contains("price=2476.8935"));
+        assert!(output.contains("/g provider=gold-api+frankfurter"));
+
+        let cli = Cli::parse_from([
+            "market-cli",
+            "metal",
+            "--base",
+            "XAU",
+            "--quote",
+            "USD",
+            "--amount",
+            "2",
+            "--output",
+            "json",
+        ]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("metal");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(json["command"], "market.metal");
+        assert_eq!(json["result"]["kind"], "metal");
+        assert_eq!(json["result"]["unit"], "oz");
+        assert_eq!(json["result"]["unit_price"], "2400");
+        assert_eq!(json["result"]["converted"], "4800");
+        assert_eq!(json["result"]["provider"], "gold-api");
+
+        let cli = Cli::parse_from([
+            "market-cli",
+            "metal",
+            "--base",
+            "XPD",
+            "--quote",
+            "USD",
+            "--amount",
+            "1",
+        ]);
+        let error = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect_err("XPD");
+        assert_eq!(error.code, ERROR_CODE_USER_INVALID_INPUT);
+    }
+
     #[test]
     fn main_maps_invalid_symbols_to_user_error() {
         let cli = Cli::parse_from([
@@ -956,6 +2264,326 @@ mod tests {
         assert_eq!(err.exit_code(), 1);
     }
 
+    #[test]
+    fn main_outputs_stock_quote_in_each_mode() {
+        let config = config_in_tempdir();
+        let human = run_with(
+            Cli::parse_from(["market-cli", "stock", "--symbol", "aapl"]),
+            &config,
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("stock should pass");
+        assert_eq!(
+            human,
+            "STOCK AAPL 227.52 USD +2.52 (+1.12%) (provider=yahoo cache=live)"
+        );
+
+        let json: Value = serde_json::from_str(
+            &run_with(
+                Cli::parse_from([
+                    "market-cli",
+                    "stock",
+                    "--symbol",
+                    "AAPL",
+                    "--output",
+                    "json",
+                ]),
+                &config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+            .expect("stock json should pass"),
+        )
+        .expect("json");
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("market.stock")
+        );
+        assert_eq!(json["result"]["symbol"], "AAPL");
+        assert_eq!(json["result"]["change_pct"], "1.12");
+        assert_eq!(json["result"]["cache"]["status"], "cache_fresh");
+
+        let alfred: Value = serde_json::from_str(
+            &run_with(
+                Cli::parse_from([
+                    "market-cli",
+                    "stock",
+                    "--symbol",
+                    "AAPL",
+                    "--output",
+                    "alfred-json",
+                ]),
+                &config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+            .expect("stock alfred should pass"),
+        )
+        .expect("json");
+        let item = &alfred["items"][0];
+        assert_eq!(item["title"], "AAPL 227.52 USD");
+        assert_eq!(item["arg"], "227.52");
+        assert_eq!(
+            item_icon_path(item),
+            Some(
+                config
+                    .icon_cache_dir()
+                    .join(market_cli::config::ICON_GENERIC_BASENAME)
+                    .to_string_lossy()
+                    .as_ref()
+            )
+        );
+    }
+
+    #[test]
+    fn main_maps_stock_symbol_errors_to_user_error() {
+        let invalid = run_with(
+            Cli::parse_from(["market-cli", "stock", "--symbol", "AA PL"]),
+            &config_in_tempdir(),
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect_err("must fail");
+        assert_eq!(invalid.code, ERROR_CODE_USER_INVALID_INPUT);
+
+        let unknown = run_with(
+            Cli::parse_from(["market-cli", "stock", "--symbol", "NOPE"]),
+            &config_in_tempdir(),
+            &FavoritesProviders,
+            fixed_now,
+        )
+        .expect_err("must fail");
+        assert_eq!(unknown.exit_code(), 2);
+        assert_eq!(unknown.message, "unknown stock symbol: NOPE");
+    }
+
+    #[test]
+    fn main_outputs_history_summary_with_sparkline() {
+        let config = config_in_tempdir();
+        let human = run_with(
+            Cli::parse_from(["market-cli", "history", "--base", "USD", "--quote", "TWD"]),
+            &config,
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("history should pass");
+        assert_eq!(
+            human,
+            "HISTORY USD/TWD 30d ▃▁▆█ latest=32.8 min=31.9 max=32.8 change=+2.18% (provider=frankfurter cache=live)"
+        );
+
+        let json: Value = serde_json::from_str(
+            &run_with(
+                Cli::parse_from([
+                    "market-cli",
+                    "history",
+                    "--base",
+                    "USD",
+                    "--quote",
+                    "TWD",
+                    "--output",
+                    "json",
+                ]),
+                &config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+            .expect("history json should pass"),
+        )
+        .expect("json");
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("market.history")
+        );
+        assert_eq!(json["result"]["points"].as_array().map(Vec::len), Some(4));
+        assert_eq!(json["result"]["points"][0]["date"], "2026-02-07");
+        assert_eq!(json["result"]["cache"]["key"], "history-fx-usd-twd-30d");
+    }
+
+    #[test]
+    fn main_history_falls_back_to_crypto_and_validates_range() {
+        let providers = FakeProviders {
+            fx_history_result: Err(ProviderError::Http {
+                status: 404,
+                message: "not found".to_string(),
+            }),
+            ..FakeProviders::ok()
+        };
+        let alfred: Value = serde_json::from_str(
+            &run_with(
+                Cli::parse_from([
+                    "market-cli",
+                    "history",
+                    "--base",
+                    "btc",
+                    "--quote",
+                    "usd",
+                    "--range",
+                    "1w",
+                    "--output",
+                    "alfred-json",
+                ]),
+                &config_in_tempdir(),
+                &providers,
+                fixed_now,
+            )
+            .expect("history should pass"),
+        )
+        .expect("json");
+        let item = &alfred["items"][0];
+        assert_eq!(item["title"], "BTC/USD 7d ▁█▅");
+        assert_eq!(item["arg"], "670");
+        assert_eq!(item["valid"], false);
+
+        let err = run_with(
+            Cli::parse_from([
+                "market-cli",
+                "history",
+                "--base",
+                "USD",
+                "--quote",
+                "TWD",
+                "--range",
+                "2y",
+            ]),
+            &config_in_tempdir(),
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect_err("must fail");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_INPUT);
+    }
+
+    #[test]
+    fn main_watch_add_list_and_remove_roundtrip() {
+        let config = config_in_tempdir();
+        let run = |args: &[&str]| {
+            let mut argv = vec!["market-cli", "watch"];
+            argv.extend_from_slice(args);
+            run_with(
+                Cli::parse_from(argv),
+                &config,
+                &FavoritesProviders,
+                fixed_now,
+            )
+        };
+
+        let empty: Value =
+            serde_json::from_str(&run(&["list"]).expect("list empty")).expect("json");
+        assert_eq!(empty["items"][0]["title"], WATCHLIST_EMPTY_TITLE);
+
+        assert_eq!(
+            run(&["add", "btc/usd", "0.5"]).expect("add"),
+            "watching BTC/USD amount=0.5 (entries=1)"
+        );
+        assert_eq!(
+            run(&["add", "ETH/USD", "2"]).expect("add"),
+            "watching ETH/USD amount=2 (entries=2)"
+        );
+        run(&["add", "JPY/TWD"]).expect("add");
+        run(&["add", "DOGE/USD", "100"]).expect("add");
+        assert_eq!(
+            run(&["add", "BTC/USD", "1"]).expect("update"),
+            "updated BTC/USD amount=1 (entries=4)"
+        );
+
+        let alfred: Value = serde_json::from_str(&run(&["list"]).expect("list")).expect("json");
+        let items = alfred["items"].as_array().expect("items");
+        let titles = items
+            .iter()
+            .map(|item| item["title"].as_str().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            vec![
+                "Total = 72154 USD",
+                "1 BTC = 68194 USD",
+                "2 ETH = 3960 USD",
+                "1 JPY = 2.150 TWD",
+                "DOGE/USD",
+            ]
+        );
+        assert_eq!(
+            items[0]["subtitle"],
+            "Portfolio total · 3 holdings · 1 unavailable"
+        );
+        assert!(items.iter().all(|item| item["valid"] == false));
+
+        let json: Value =
+            serde_json::from_str(&run(&["list", "--output", "json"]).expect("json")).expect("json");
+        assert_eq!(json["command"], "market.watch.list");
+        assert_eq!(json["result"]["totals"][0]["value"], "72154");
+        assert_eq!(json["result"]["entries"][0]["amount"], "1");
+
+        assert_eq!(
+            run(&["remove", "doge/usd"]).expect("remove"),
+            "removed DOGE/USD (entries=3)"
+        );
+        let err = run(&["remove", "DOGE/USD"]).expect_err("already removed");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_INPUT);
+        let err = run(&["add", "BTCUSD"]).expect_err("invalid pair");
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn main_alert_add_check_and_remove_roundtrip() {
+        let config = config_in_tempdir();
+        let run = |args: &[&str]| {
+            let mut argv = vec!["market-cli", "alerts"];
+            argv.extend_from_slice(args);
+            run_with(
+                Cli::parse_from(argv),
+                &config,
+                &FavoritesProviders,
+                fixed_now,
+            )
+        };
+
+        let empty: Value =
+            serde_json::from_str(&run(&["list"]).expect("list empty")).expect("json");
+        assert_eq!(empty["items"][0]["title"], ALERTS_EMPTY_TITLE);
+
+        assert_eq!(
+            run(&["add", "btc/usd", "above", "70000"]).expect("add"),
+            "alert #1: BTC/USD above 70000 (alerts=1)"
+        );
+        run(&["add", "ETH/USD", "below", "2000"]).expect("add");
+        run(&["add", "DOGE/USD", "above", "1"]).expect("add");
+
+        let report: Value =
+            serde_json::from_str(&run(&["check", "--dry-run"]).expect("check")).expect("json");
+        assert_eq!(report["command"], "market.alert.check");
+        assert_eq!(report["result"]["checked"], 3);
+        assert_eq!(report["result"]["notified"], false);
+        assert_eq!(report["result"]["triggered"][0]["id"], 2);
+        assert_eq!(report["result"]["triggered"][0]["state"], "triggered");
+        assert_eq!(report["result"]["triggered"][0]["price"], "1980");
+        assert_eq!(report["result"]["failed"][0]["id"], 3);
+
+        let human = run(&["check", "--dry-run", "--output", "human"]).expect("check");
+        assert!(human.starts_with("ALERT #2 ETH/USD 1980 USD below 2000\nFAILED #3 DOGE/USD: "));
+        assert_eq!(
+            run(&["list", "--output", "human"]).expect("list"),
+            "#1 BTC/USD above 70000 (armed)\n#2 ETH/USD below 2000 (armed)\n#3 DOGE/USD above 1 (armed)"
+        );
+
+        assert_eq!(
+            run(&["remove", "2"]).expect("remove"),
+            "removed alert #2: ETH/USD below 2000 (alerts=2)"
+        );
+        let err = run(&["remove", "2"]).expect_err("already removed");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_INPUT);
+        run(&["remove", "3"]).expect("remove");
+        assert_eq!(
+            run(&["check", "--output", "human"]).expect("check"),
+            "no alerts triggered (checked=1)"
+        );
+
+        let err = run(&["add", "BTC/USD", "above", "0"]).expect_err("invalid threshold");
+        assert_eq!(err.exit_code(), 2);
+    }
+
     #[test]
     fn main_outputs_expr_alfred_json_contract() {
         let cli = Cli::parse_from(["market-cli", "expr", "--query", "1+5"]);
@@ -971,6 +2599,30 @@ mod tests {
         assert_eq!(items[0].get("title").and_then(Value::as_str), Some("6"));
     }
 
+    #[test]
+    fn main_expr_applies_market_fee_pct_to_rows_and_total() {
+        let config = RuntimeConfig {
+            fees: market_cli::config::FeeSchedule {
+                default_pct: Some(Decimal::new(5, 1)),
+                provider_pct: Vec::new(),
+            },
+            ..config_in_tempdir()
+        };
+        let cli = Cli::parse_from(["market-cli", "expr", "--query", "2 btc"]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("expr");
+        let json: Value = serde_json::from_str(&output).expect("json");
+
+        assert_eq!(
+            json["items"][0]["title"],
+            "1 BTC = 32.10 USD (31.94 USD after 0.5% fee)"
+        );
+        assert_eq!(
+            json["items"][1]["title"],
+            "Total = 64.20 USD (63.88 USD after 0.5% fee)"
+        );
+        assert_eq!(json["items"][1]["arg"], "64.20 USD");
+    }
+
     #[test]
     fn main_maps_expr_syntax_error_to_user_error() {
         let cli = Cli::parse_from(["market-cli", "expr", "--query", "2 btc + 5"]);
@@ -1031,6 +2683,57 @@ mod tests {
         assert!(icon_path.ends_with(expected_file.as_str()));
     }
 
+    #[test]
+    fn main_market_alfred_row_carries_inverse_unit_price_and_chart_modifiers() {
+        let config = config_in_tempdir();
+        let run = |args: &[&str]| {
+            let cli = Cli::parse_from(args);
+            let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("quote");
+            let json: Value = serde_json::from_str(&output).expect("json");
+            json["items"][0]["mods"].clone()
+        };
+
+        let mods = run(&[
+            "market-cli",
+            "crypto",
+            "--base",
+            "BTC",
+            "--quote",
+            "USD",
+            "--amount",
+            "2",
+            "--output",
+            "alfred-json",
+        ]);
+        assert_eq!(mods["alt"]["arg"], "67000.1");
+        assert_eq!(mods["cmd"]["arg"], "0.0000149253508577");
+        assert_eq!(
+            mods["cmd"]["subtitle"],
+            "Copy inverse rate: 1 USD = 0.0000149253508577 BTC"
+        );
+        assert_eq!(
+            mods["shift"]["arg"],
+            "https://www.coinbase.com/advanced-trade/spot/BTC-USD"
+        );
+        assert_eq!(mods["shift"]["valid"], true);
+
+        let mods = run(&[
+            "market-cli",
+            "fx",
+            "--base",
+            "USD",
+            "--quote",
+            "TWD",
+            "--amount",
+            "1",
+            "--output",
+            "alfred-json",
+        ]);
+        assert_eq!(mods["alt"]["arg"], "32.1");
+        assert_eq!(mods["cmd"]["arg"], "0.0311526479751");
+        assert!(mods.get("shift").is_none());
+    }
+
     #[test]
     fn main_outputs_fx_alfred_json_with_generic_fallback_icon() {
         let config = config_in_tempdir();
@@ -1122,6 +2825,7 @@ mod tests {
             fx_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_coinbase_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_kraken_result: Err(ProviderError::Transport("offline".to_string())),
+            ..FakeProviders::ok()
         };
 
         let output = run_with(cli, &config_in_tempdir(), &failing_providers, fixed_now)
@@ -1146,6 +2850,7 @@ mod tests {
             fx_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_coinbase_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_kraken_result: Err(ProviderError::Transport("offline".to_string())),
+            ..FakeProviders::ok()
         };
 
         let output = run_with(cli, &config_in_tempdir(), &failing_providers, fixed_now)
@@ -1280,6 +2985,7 @@ mod tests {
             fx_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_coinbase_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_kraken_result: Err(ProviderError::Transport("offline".to_string())),
+            ..FakeProviders::ok()
         };
 
         let output = run_with(cli, &config_in_tempdir(), &failing_providers, fixed_now)
@@ -1316,6 +3022,7 @@ mod tests {
             fx_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_coinbase_result: Err(ProviderError::Transport("offline".to_string())),
             crypto_kraken_result: Err(ProviderError::Transport("offline".to_string())),
+            ..FakeProviders::ok()
         };
 
         let output = run_with(cli, &config_in_tempdir(), &failing_providers, fixed_now)
@@ -1,4 +1,4 @@
-use chrono::{DateTime, SecondsFormat, Utc};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -8,6 +8,7 @@ use thiserror::Error;
 pub enum MarketKind {
     Fx,
     Crypto,
+    Metal,
 }
 
 impl MarketKind {
@@ -15,6 +16,86 @@ impl MarketKind {
         match self {
             Self::Fx => "fx",
             Self::Crypto => "crypto",
+            Self::Metal => "metal",
+        }
+    }
+}
+
+/// Precious metals quoted by the metals provider, in USD per troy ounce.
+pub const METAL_SYMBOLS: [&str; 3] = ["XAU", "XAG", "XPT"];
+
+/// Grams in one troy ounce (31.1034768).
+pub const TROY_OUNCE_GRAMS: Decimal = Decimal::from_parts(311_034_768, 0, 0, false, 7);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MetalUnit {
+    #[serde(rename = "oz")]
+    TroyOunce,
+    #[serde(rename = "g")]
+    Gram,
+}
+
+impl MetalUnit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TroyOunce => "oz",
+            Self::Gram => "g",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "oz" | "ozt" => Some(Self::TroyOunce),
+            "g" | "gram" | "grams" => Some(Self::Gram),
+            _ => None,
+        }
+    }
+
+    /// Price of one unit given the price of one troy ounce.
+    pub fn price_from_troy_ounce(self, price: Decimal) -> Decimal {
+        match self {
+            Self::TroyOunce => price,
+            Self::Gram => (price / TROY_OUNCE_GRAMS).round_dp(8),
+        }
+    }
+}
+
+pub fn is_metal_symbol(symbol: &str) -> bool {
+    METAL_SYMBOLS.contains(&symbol)
+}
+
+/// Three ASCII letters, the shape of an ISO 4217 code. Crypto tickers can share it, so callers still fall back.
+pub fn looks_like_fiat_symbol(symbol: &str) -> bool {
+    symbol.len() == 3 && symbol.chars().all(|ch| ch.is_ascii_alphabetic())
+}
+
+/// Crypto spot providers. The fallback order comes from `MARKET_CRYPTO_PROVIDERS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CryptoProvider {
+    Coinbase,
+    Kraken,
+    Binance,
+    Coingecko,
+}
+
+impl CryptoProvider {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Coinbase => "coinbase",
+            Self::Kraken => "kraken",
+            Self::Binance => "binance",
+            Self::Coingecko => "coingecko",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "coinbase" => Some(Self::Coinbase),
+            "kraken" => Some(Self::Kraken),
+            "binance" => Some(Self::Binance),
+            "coingecko" => Some(Self::Coingecko),
+            _ => None,
         }
     }
 }
@@ -41,11 +122,82 @@ pub struct MarketOutput {
     pub base: String,
     pub quote: String,
     pub amount: String,
+    /// Metal quantity unit; `amount` and `unit_price` are expressed in it. Omitted for FX and crypto.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<MetalUnit>,
     pub unit_price: String,
     pub converted: String,
+    /// Fee in percent from `MARKET_FEE_PCT` for `provider`; omitted when no fee is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_pct: Option<String>,
+    /// `converted` less `fee_pct` percent; omitted when no fee is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted_after_fee: Option<String>,
     pub provider: String,
     pub fetched_at: String,
     pub cache: CacheMetadata,
+    /// Providers tried before `provider` answered (or, on a stale fallback, every provider) with their errors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_failures: Vec<ProviderFailure>,
+    /// Set when no provider quotes the pair directly and `unit_price` was crossed through a pivot currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation: Option<CrossRateDerivation>,
+}
+
+impl MarketOutput {
+    /// Adds the amount left after a `fee_pct` percent fee; `None` keeps the mid-market output unchanged.
+    pub fn with_fee(self, fee_pct: Option<Decimal>) -> Self {
+        let Some((fee_pct, converted)) = fee_pct.zip(self.converted.parse::<Decimal>().ok()) else {
+            return self;
+        };
+        let after_fee =
+            (converted * (Decimal::ONE_HUNDRED - fee_pct) / Decimal::ONE_HUNDRED).round_dp(8);
+
+        Self {
+            fee_pct: Some(decimal_to_string(&fee_pct)),
+            converted_after_fee: Some(decimal_to_string(&after_fee)),
+            ..self
+        }
+    }
+}
+
+/// `unit_price = legs[0].unit_price / legs[1].unit_price`, both legs quoted in `pivot`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossRateDerivation {
+    pub pivot: String,
+    pub legs: Vec<CrossRateLeg>,
+}
+
+/// One leg of a cross rate, as resolved (and cached) on its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossRateLeg {
+    pub kind: MarketKind,
+    pub base: String,
+    pub quote: String,
+    pub unit_price: String,
+    pub provider: String,
+    pub fetched_at: String,
+    pub cache: CacheMetadata,
+}
+
+impl From<MarketOutput> for CrossRateLeg {
+    fn from(output: MarketOutput) -> Self {
+        Self {
+            kind: output.kind,
+            base: output.base,
+            quote: output.quote,
+            unit_price: output.unit_price,
+            provider: output.provider,
+            fetched_at: output.fetched_at,
+            cache: output.cache,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderFailure {
+    pub provider: String,
+    pub error: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -54,6 +206,8 @@ pub struct MarketRequest {
     pub base: String,
     pub quote: String,
     pub amount: Decimal,
+    /// Set for metals only; defaults to troy ounces.
+    pub unit: Option<MetalUnit>,
 }
 
 impl MarketRequest {
@@ -66,9 +220,10 @@ impl MarketRequest {
         let normalized_base = match kind {
             MarketKind::Fx => normalize_fx_symbol(base, "base")?,
             MarketKind::Crypto => normalize_crypto_symbol(base, "base")?,
+            MarketKind::Metal => normalize_metal_symbol(base, "base")?,
         };
         let normalized_quote = match kind {
-            MarketKind::Fx => normalize_fx_symbol(quote, "quote")?,
+            MarketKind::Fx | MarketKind::Metal => normalize_fx_symbol(quote, "quote")?,
             MarketKind::Crypto => normalize_crypto_symbol(quote, "quote")?,
         };
 
@@ -79,8 +234,17 @@ impl MarketRequest {
             base: normalized_base,
             quote: normalized_quote,
             amount: parsed_amount,
+            unit: (kind == MarketKind::Metal).then_some(MetalUnit::TroyOunce),
         })
     }
+
+    /// Quantity unit for metal requests; ignored for FX and crypto.
+    pub fn with_unit(mut self, unit: MetalUnit) -> Self {
+        if self.kind == MarketKind::Metal {
+            self.unit = Some(unit);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -109,21 +273,237 @@ pub fn build_output(
     quote: &MarketQuote,
     cache: CacheMetadata,
 ) -> MarketOutput {
-    let converted = (request.amount * quote.unit_price).round_dp(8);
+    // Metal quotes (and their cache records) are per troy ounce; the output is per requested unit.
+    let unit_price = match request.unit {
+        Some(unit) => unit.price_from_troy_ounce(quote.unit_price),
+        None => quote.unit_price,
+    };
+    let converted = (request.amount * unit_price).round_dp(8);
 
     MarketOutput {
         kind: request.kind,
         base: request.base.clone(),
         quote: request.quote.clone(),
         amount: decimal_to_string(&request.amount),
-        unit_price: decimal_to_string(&quote.unit_price),
+        unit: request.unit,
+        unit_price: decimal_to_string(&unit_price),
         converted: decimal_to_string(&converted),
+        fee_pct: None,
+        converted_after_fee: None,
+        provider: quote.provider.clone(),
+        fetched_at: quote.fetched_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        cache,
+        provider_failures: Vec::new(),
+        derivation: None,
+    }
+}
+
+/// Last traded price for one ticker. Providers that do not report the trading currency or previous close leave them
+/// unset, and the output then omits the daily change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StockQuote {
+    pub provider: String,
+    pub price: Decimal,
+    pub currency: Option<String>,
+    pub previous_close: Option<Decimal>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StockOutput {
+    pub symbol: String,
+    pub price: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_close: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_pct: Option<String>,
+    pub provider: String,
+    pub fetched_at: String,
+    pub cache: CacheMetadata,
+}
+
+pub fn build_stock_output(symbol: &str, quote: &StockQuote, cache: CacheMetadata) -> StockOutput {
+    let previous_close = quote
+        .previous_close
+        .filter(|previous| *previous > Decimal::ZERO);
+    let change = previous_close.map(|previous| (quote.price - previous).round_dp(4));
+    let change_pct = previous_close
+        .zip(change)
+        .map(|(previous, change)| (change / previous * Decimal::ONE_HUNDRED).round_dp(2));
+
+    StockOutput {
+        symbol: symbol.to_string(),
+        price: decimal_to_string(&quote.price),
+        currency: quote.currency.clone(),
+        previous_close: previous_close.as_ref().map(decimal_to_string),
+        change: change.as_ref().map(decimal_to_string),
+        change_pct: change_pct.as_ref().map(decimal_to_string),
         provider: quote.provider.clone(),
         fetched_at: quote.fetched_at.to_rfc3339_opts(SecondsFormat::Secs, true),
         cache,
     }
 }
 
+/// Lookback window for `history`, written as `<n>d`, `<n>w`, `<n>m` (30 days), or `<n>y` (365 days).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryRange {
+    pub days: u32,
+}
+
+impl HistoryRange {
+    pub const MIN_DAYS: u32 = 2;
+    pub const MAX_DAYS: u32 = 365;
+
+    pub fn parse(raw: &str) -> Result<Self, ValidationError> {
+        let invalid = || ValidationError::InvalidRange(raw.to_string());
+        let value = raw.trim().to_ascii_lowercase();
+        let split = value
+            .find(|ch: char| !ch.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (count, unit) = value.split_at(split);
+        let count = count.parse::<u32>().map_err(|_| invalid())?;
+        let unit_days = match unit {
+            "d" => 1,
+            "w" => 7,
+            "m" => 30,
+            "y" => 365,
+            _ => return Err(invalid()),
+        };
+        let days = count.checked_mul(unit_days).ok_or_else(invalid)?;
+        if !(Self::MIN_DAYS..=Self::MAX_DAYS).contains(&days) {
+            return Err(invalid());
+        }
+        Ok(Self { days })
+    }
+
+    pub fn label(self) -> String {
+        format!("{}d", self.days)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryRequest {
+    pub kind: MarketKind,
+    pub base: String,
+    pub quote: String,
+    pub range: HistoryRange,
+}
+
+impl HistoryRequest {
+    pub fn new(
+        kind: MarketKind,
+        base: &str,
+        quote: &str,
+        range: HistoryRange,
+    ) -> Result<Self, ValidationError> {
+        let (base, quote) = match kind {
+            MarketKind::Fx => (
+                normalize_fx_symbol(base, "base")?,
+                normalize_fx_symbol(quote, "quote")?,
+            ),
+            MarketKind::Crypto => (
+                normalize_crypto_symbol(base, "base")?,
+                normalize_crypto_symbol(quote, "quote")?,
+            ),
+            MarketKind::Metal => (
+                normalize_metal_symbol(base, "base")?,
+                normalize_fx_symbol(quote, "quote")?,
+            ),
+        };
+
+        Ok(Self {
+            kind,
+            base,
+            quote,
+            range,
+        })
+    }
+}
+
+/// One daily close; providers return points in any order and the output sorts them by date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PricePoint {
+    pub date: NaiveDate,
+    pub price: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceSeries {
+    pub provider: String,
+    pub points: Vec<PricePoint>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryPointOutput {
+    pub date: String,
+    pub price: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryOutput {
+    pub kind: MarketKind,
+    pub base: String,
+    pub quote: String,
+    pub range: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub latest: String,
+    pub min: String,
+    pub max: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_pct: Option<String>,
+    pub points: Vec<HistoryPointOutput>,
+    pub provider: String,
+    pub fetched_at: String,
+    pub cache: CacheMetadata,
+}
+
+/// Summary plus the raw series, or `None` when the series has no points.
+pub fn build_history_output(
+    request: &HistoryRequest,
+    series: &PriceSeries,
+    cache: CacheMetadata,
+) -> Option<HistoryOutput> {
+    let mut points = series.points.clone();
+    points.sort_by_key(|point| point.date);
+    points.dedup_by_key(|point| point.date);
+
+    let first = points.first()?;
+    let last = points.last()?;
+    let min = points.iter().map(|point| point.price).min()?;
+    let max = points.iter().map(|point| point.price).max()?;
+    let change_pct = (first.price > Decimal::ZERO)
+        .then(|| ((last.price - first.price) / first.price * Decimal::ONE_HUNDRED).round_dp(2));
+
+    Some(HistoryOutput {
+        kind: request.kind,
+        base: request.base.clone(),
+        quote: request.quote.clone(),
+        range: request.range.label(),
+        start_date: first.date.to_string(),
+        end_date: last.date.to_string(),
+        latest: decimal_to_string(&last.price),
+        min: decimal_to_string(&min),
+        max: decimal_to_string(&max),
+        change_pct: change_pct.as_ref().map(decimal_to_string),
+        points: points
+            .iter()
+            .map(|point| HistoryPointOutput {
+                date: point.date.to_string(),
+                price: decimal_to_string(&point.price),
+            })
+            .collect(),
+        provider: series.provider.clone(),
+        fetched_at: series.fetched_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        cache,
+    })
+}
+
 pub fn decimal_to_string(value: &Decimal) -> String {
     value.normalize().to_string()
 }
@@ -157,6 +537,40 @@ pub fn normalize_crypto_symbol(raw: &str, field: &'static str) -> Result<String,
     Ok(value)
 }
 
+pub fn normalize_metal_symbol(raw: &str, field: &'static str) -> Result<String, ValidationError> {
+    let value = raw.trim().to_ascii_uppercase();
+    if !is_metal_symbol(&value) {
+        return Err(ValidationError::InvalidSymbol {
+            field,
+            value: raw.to_string(),
+            expected: "metal code XAU, XAG, or XPT",
+        });
+    }
+    Ok(value)
+}
+
+/// Ticker such as `AAPL`, `BRK-B`, or an exchange-suffixed `7203.T`.
+pub fn normalize_stock_symbol(raw: &str, field: &'static str) -> Result<String, ValidationError> {
+    let value = raw.trim().to_ascii_uppercase();
+    let starts_alphanumeric = value
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphanumeric());
+    if value.len() > 15
+        || !starts_alphanumeric
+        || !value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-')
+    {
+        return Err(ValidationError::InvalidSymbol {
+            field,
+            value: raw.to_string(),
+            expected: "1-15 character ticker of letters, digits, '.' or '-'",
+        });
+    }
+    Ok(value)
+}
+
 pub fn parse_amount(raw: &str) -> Result<Decimal, ValidationError> {
     let value = raw.trim();
     let parsed = value
@@ -180,6 +594,8 @@ pub enum ValidationError {
     InvalidAmount(String),
     #[error("amount must be positive: {0}")]
     AmountMustBePositive(String),
+    #[error("invalid range: {0} (expected <n>d, <n>w, <n>m, or <n>y between 2d and 365d)")]
+    InvalidRange(String),
 }
 
 #[cfg(test)]
@@ -244,9 +660,167 @@ mod tests {
         );
     }
 
+    #[test]
+    fn model_normalize_stock_symbol_accepts_class_and_exchange_suffixes() {
+        assert_eq!(
+            normalize_stock_symbol(" aapl ", "symbol").as_deref(),
+            Ok("AAPL")
+        );
+        assert_eq!(
+            normalize_stock_symbol("brk-b", "symbol").as_deref(),
+            Ok("BRK-B")
+        );
+        assert_eq!(
+            normalize_stock_symbol("7203.t", "symbol").as_deref(),
+            Ok("7203.T")
+        );
+
+        for invalid in ["", ".AAPL", "AAPL$", "TOOLONGTICKERNAME"] {
+            assert!(
+                normalize_stock_symbol(invalid, "symbol").is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn stock_build_output_derives_change_from_previous_close() {
+        let fetched_at = Utc
+            .with_ymd_and_hms(2026, 2, 10, 21, 0, 0)
+            .single()
+            .expect("time");
+        let cache = CacheMetadata {
+            status: CacheStatus::Live,
+            key: "stock-aapl".to_string(),
+            ttl_secs: 900,
+            age_secs: 0,
+        };
+        let quote = StockQuote {
+            provider: "yahoo".to_string(),
+            price: Decimal::new(22752, 2),
+            currency: Some("USD".to_string()),
+            previous_close: Some(Decimal::new(22500, 2)),
+            fetched_at,
+        };
+
+        let output = build_stock_output("AAPL", &quote, cache.clone());
+        assert_eq!(output.price, "227.52");
+        assert_eq!(output.change.as_deref(), Some("2.52"));
+        assert_eq!(output.change_pct.as_deref(), Some("1.12"));
+        assert_eq!(output.fetched_at, "2026-02-10T21:00:00Z");
+
+        let without_close = build_stock_output(
+            "AAPL",
+            &StockQuote {
+                previous_close: None,
+                ..quote
+            },
+            cache,
+        );
+        assert_eq!(without_close.change, None);
+        assert_eq!(without_close.change_pct, None);
+    }
+
+    #[test]
+    fn history_range_parses_units_and_enforces_bounds() {
+        assert_eq!(HistoryRange::parse("30d").map(|range| range.days), Ok(30));
+        assert_eq!(HistoryRange::parse(" 2W ").map(|range| range.days), Ok(14));
+        assert_eq!(HistoryRange::parse("6m").map(|range| range.days), Ok(180));
+        assert_eq!(
+            HistoryRange::parse("1y").map(|range| range.label()),
+            Ok("365d".to_string())
+        );
+
+        for invalid in ["", "30", "d", "1d", "2y", "10x", "-5d"] {
+            assert_eq!(
+                HistoryRange::parse(invalid),
+                Err(ValidationError::InvalidRange(invalid.to_string())),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn history_build_output_sorts_points_and_summarizes() {
+        let request = HistoryRequest::new(
+            MarketKind::Fx,
+            "usd",
+            "twd",
+            HistoryRange::parse("7d").expect("range"),
+        )
+        .expect("request");
+        let date = |day| NaiveDate::from_ymd_opt(2026, 2, day).expect("date");
+        let series = PriceSeries {
+            provider: "frankfurter".to_string(),
+            points: vec![
+                PricePoint {
+                    date: date(5),
+                    price: Decimal::new(3250, 2),
+                },
+                PricePoint {
+                    date: date(3),
+                    price: Decimal::new(3200, 2),
+                },
+                PricePoint {
+                    date: date(4),
+                    price: Decimal::new(3150, 2),
+                },
+            ],
+            fetched_at: Utc
+                .with_ymd_and_hms(2026, 2, 10, 12, 0, 0)
+                .single()
+                .expect("time"),
+        };
+        let cache = CacheMetadata {
+            status: CacheStatus::Live,
+            key: "history-fx-usd-twd-7d".to_string(),
+            ttl_secs: 86400,
+            age_secs: 0,
+        };
+
+        let output = build_history_output(&request, &series, cache.clone()).expect("output");
+        assert_eq!(output.range, "7d");
+        assert_eq!(output.start_date, "2026-02-03");
+        assert_eq!(output.end_date, "2026-02-05");
+        assert_eq!(output.latest, "32.5");
+        assert_eq!(output.min, "31.5");
+        assert_eq!(output.max, "32.5");
+        assert_eq!(output.change_pct.as_deref(), Some("1.56"));
+        assert_eq!(output.points[1].date, "2026-02-04");
+
+        let empty = PriceSeries {
+            points: Vec::new(),
+            ..series
+        };
+        assert_eq!(build_history_output(&request, &empty, cache), None);
+    }
+
     #[test]
     fn model_normalize_crypto_symbol_accepts_letters_and_digits() {
         let parsed = normalize_crypto_symbol(" usdt ", "base").expect("should parse");
         assert_eq!(parsed, "USDT");
     }
+
+    #[test]
+    fn metal_request_defaults_to_troy_ounce_and_converts_to_grams() {
+        let request = MarketRequest::new(MarketKind::Metal, " xag ", "twd", "1").expect("request");
+        assert_eq!(request.base, "XAG");
+        assert_eq!(request.unit, Some(MetalUnit::TroyOunce));
+        assert!(MarketRequest::new(MarketKind::Metal, "BTC", "USD", "1").is_err());
+        assert_eq!(
+            MarketRequest::new(MarketKind::Fx, "USD", "TWD", "1")
+                .expect("request")
+                .with_unit(MetalUnit::Gram)
+                .unit,
+            None
+        );
+
+        assert_eq!(MetalUnit::parse("OZT"), Some(MetalUnit::TroyOunce));
+        assert_eq!(MetalUnit::parse("grams"), Some(MetalUnit::Gram));
+        assert_eq!(MetalUnit::parse("kg"), None);
+        assert_eq!(
+            MetalUnit::Gram.price_from_troy_ounce(TROY_OUNCE_GRAMS * Decimal::from(2)),
+            Decimal::from(2)
+        );
+    }
 }
@@ -0,0 +1,154 @@
+use chrono::Utc;
+use reqwest::blocking::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::config::RetryPolicy;
+use crate::model::MarketQuote;
+
+use super::{ProviderError, execute_with_retry};
+
+const ENDPOINT: &str = "https://api.binance.com/api/v3/ticker/price";
+const CHART_URL_PREFIX: &str = "https://www.binance.com/en/trade";
+// Binance reports an unknown market as HTTP 400 with this error code.
+const INVALID_SYMBOL_CODE: i64 = -1121;
+
+pub fn fetch_crypto_spot(
+    client: &Client,
+    base: &str,
+    quote: &str,
+    retry_policy: RetryPolicy,
+) -> Result<MarketQuote, ProviderError> {
+    execute_with_retry(
+        "binance",
+        retry_policy,
+        || fetch_once(client, base, quote),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_once(client: &Client, base: &str, quote: &str) -> Result<MarketQuote, ProviderError> {
+    let symbol = normalize_symbol(base, quote)?;
+    let response = client
+        .get(ENDPOINT)
+        .query(&[("symbol", symbol.as_str())])
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let unit_price = parse_ticker_body(status, &body)?;
+
+    Ok(MarketQuote::new("binance", unit_price, Utc::now()))
+}
+
+/// Binance has no USD spot markets, so USD quotes are served from the USDT market.
+pub fn normalize_symbol(base: &str, quote: &str) -> Result<String, ProviderError> {
+    if !is_valid_symbol(base) || !is_valid_symbol(quote) {
+        return Err(ProviderError::UnsupportedPair(format!("{base}/{quote}")));
+    }
+
+    Ok(format!("{base}{}", market_quote(quote)))
+}
+
+/// Spot trading page of the market [`normalize_symbol`] quotes from.
+pub fn chart_url(base: &str, quote: &str) -> String {
+    format!("{CHART_URL_PREFIX}/{base}_{}", market_quote(quote))
+}
+
+fn market_quote(quote: &str) -> &str {
+    match quote {
+        "USD" => "USDT",
+        other => other,
+    }
+}
+
+pub fn parse_ticker_body(status: u16, body: &str) -> Result<Decimal, ProviderError> {
+    if !(200..=299).contains(&status) {
+        let error = serde_json::from_str::<BinanceErrorResponse>(body).ok();
+        if let Some(error) = &error
+            && error.code == Some(INVALID_SYMBOL_CODE)
+        {
+            return Err(ProviderError::UnsupportedPair(error.msg.clone()));
+        }
+
+        return Err(ProviderError::Http {
+            status,
+            message: error
+                .map(|error| error.msg.trim().to_string())
+                .filter(|message| !message.is_empty())
+                .unwrap_or_else(|| format!("HTTP {status}")),
+        });
+    }
+
+    let payload: BinanceTickerResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(error.to_string()))?;
+    payload
+        .price
+        .trim()
+        .parse::<Decimal>()
+        .map_err(|_| ProviderError::InvalidResponse("invalid binance price".to_string()))
+}
+
+fn is_valid_symbol(value: &str) -> bool {
+    let len = value.len();
+    (2..=10).contains(&len) && value.chars().all(|ch| ch.is_ascii_alphanumeric())
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceTickerResponse {
+    price: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BinanceErrorResponse {
+    #[serde(default)]
+    code: Option<i64>,
+    #[serde(default)]
+    msg: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binance_parse_ticker_body_extracts_price() {
+        let body = r#"{"symbol":"BTCUSDT","price":"67321.10000000"}"#;
+
+        let price = parse_ticker_body(200, body).expect("must parse");
+        assert_eq!(price.normalize().to_string(), "67321.1");
+    }
+
+    #[test]
+    fn binance_parse_ticker_body_maps_invalid_symbol_to_unsupported_pair() {
+        let body = r#"{"code":-1121,"msg":"Invalid symbol."}"#;
+
+        let err = parse_ticker_body(400, body).expect_err("must fail");
+        assert_eq!(
+            err,
+            ProviderError::UnsupportedPair("Invalid symbol.".to_string())
+        );
+    }
+
+    #[test]
+    fn binance_parse_ticker_body_surfaces_http_errors() {
+        let err = parse_ticker_body(451, "").expect_err("must fail");
+        assert_eq!(
+            err,
+            ProviderError::Http {
+                status: 451,
+                message: "HTTP 451".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn binance_symbol_maps_usd_quote_to_usdt() {
+        assert_eq!(normalize_symbol("BTC", "USD").expect("map"), "BTCUSDT");
+        assert_eq!(normalize_symbol("ETH", "BTC").expect("map"), "ETHBTC");
+        assert!(normalize_symbol("BTC-", "USD").is_err());
+    }
+}
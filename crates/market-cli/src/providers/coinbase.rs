@@ -1,14 +1,21 @@
-use chrono::Utc;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
 use crate::config::RetryPolicy;
-use crate::model::MarketQuote;
+use crate::model::{MarketQuote, PricePoint, PriceSeries};
 
 use super::{ProviderError, execute_with_retry};
 
 const ENDPOINT_PREFIX: &str = "https://api.coinbase.com/v2/prices";
+const CANDLES_ENDPOINT_PREFIX: &str = "https://api.exchange.coinbase.com/products";
+const CANDLE_GRANULARITY_SECS: &str = "86400";
+// The exchange API returns at most 300 candles per request and rejects requests without a user agent.
+const MAX_CANDLES_PER_REQUEST: i64 = 300;
+const CANDLES_USER_AGENT: &str = "nils-market-cli";
+const CHART_URL_PREFIX: &str = "https://www.coinbase.com/advanced-trade/spot";
 
 pub fn fetch_crypto_spot(
     client: &Client,
@@ -41,6 +48,106 @@ fn fetch_once(client: &Client, base: &str, quote: &str) -> Result<MarketQuote, P
     Ok(MarketQuote::new("coinbase", unit_price, Utc::now()))
 }
 
+pub fn chart_url(base: &str, quote: &str) -> String {
+    format!("{CHART_URL_PREFIX}/{base}-{quote}")
+}
+
+pub fn fetch_crypto_history(
+    client: &Client,
+    base: &str,
+    quote: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+    retry_policy: RetryPolicy,
+) -> Result<PriceSeries, ProviderError> {
+    let mut points = Vec::new();
+    let mut chunk_start = start;
+
+    while chunk_start <= end {
+        let chunk_end = (chunk_start + Duration::days(MAX_CANDLES_PER_REQUEST - 1)).min(end);
+        points.extend(execute_with_retry(
+            "coinbase",
+            retry_policy,
+            || fetch_candles_once(client, base, quote, chunk_start, chunk_end),
+            std::thread::sleep,
+        )?);
+        chunk_start = chunk_end + Duration::days(1);
+    }
+
+    Ok(PriceSeries {
+        provider: "coinbase".to_string(),
+        points,
+        fetched_at: Utc::now(),
+    })
+}
+
+fn fetch_candles_once(
+    client: &Client,
+    base: &str,
+    quote: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<PricePoint>, ProviderError> {
+    let endpoint = format!("{CANDLES_ENDPOINT_PREFIX}/{base}-{quote}/candles");
+    let start = start.to_string();
+    let end = end.to_string();
+
+    let response = client
+        .get(endpoint)
+        .header(USER_AGENT, CANDLES_USER_AGENT)
+        .query(&[
+            ("granularity", CANDLE_GRANULARITY_SECS),
+            ("start", start.as_str()),
+            ("end", end.as_str()),
+        ])
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    parse_candles_body(status, &body, &format!("{base}-{quote}"))
+}
+
+/// Daily closes from exchange candles (`[time, low, high, open, close, volume]`, newest first).
+pub fn parse_candles_body(
+    status: u16,
+    body: &str,
+    pair: &str,
+) -> Result<Vec<PricePoint>, ProviderError> {
+    if status == 404 {
+        return Err(ProviderError::UnsupportedPair(pair.to_string()));
+    }
+    if !(200..=299).contains(&status) {
+        return Err(ProviderError::Http {
+            status,
+            message: extract_error_message(body).unwrap_or_else(|| format!("HTTP {status}")),
+        });
+    }
+
+    let candles: Vec<Vec<serde_json::Value>> = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(error.to_string()))?;
+    candles
+        .iter()
+        .map(|candle| {
+            let date = candle
+                .first()
+                .and_then(serde_json::Value::as_i64)
+                .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+                .map(|time| time.date_naive());
+            let price = candle
+                .get(4)
+                .and_then(|close| close.to_string().parse::<Decimal>().ok());
+            date.zip(price)
+                .map(|(date, price)| PricePoint { date, price })
+                .ok_or_else(|| {
+                    ProviderError::InvalidResponse("invalid coinbase candle".to_string())
+                })
+        })
+        .collect()
+}
+
 pub fn parse_spot_body(status: u16, body: &str) -> Result<Decimal, ProviderError> {
     if !(200..=299).contains(&status) {
         return Err(ProviderError::Http {
@@ -117,6 +224,20 @@ mod tests {
         assert!(matches!(err, ProviderError::InvalidResponse(_)));
     }
 
+    #[test]
+    fn coinbase_parse_candles_body_extracts_daily_closes() {
+        let body = "[[1770681600,67000.5,69000,68000,68194.25,1200.5],[1770595200,66000,68500,67500,68000,900]]";
+
+        let points = parse_candles_body(200, body, "BTC-USD").expect("must parse");
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].date.to_string(), "2026-02-10");
+        assert_eq!(points[0].price.to_string(), "68194.25");
+
+        let err =
+            parse_candles_body(404, r#"{"message":"NotFound"}"#, "ABC-USD").expect_err("must fail");
+        assert_eq!(err, ProviderError::UnsupportedPair("ABC-USD".to_string()));
+    }
+
     #[test]
     fn coinbase_parse_spot_body_surfaces_http_errors() {
         let body = r#"{"message":"Too many requests"}"#;
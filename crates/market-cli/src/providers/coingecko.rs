@@ -0,0 +1,170 @@
+use chrono::Utc;
+use reqwest::blocking::Client;
+use rust_decimal::Decimal;
+
+use crate::config::RetryPolicy;
+use crate::model::MarketQuote;
+
+use super::{ProviderError, execute_with_retry};
+
+const ENDPOINT: &str = "https://api.coingecko.com/api/v3/simple/price";
+const CHART_URL_PREFIX: &str = "https://www.coingecko.com/en/coins";
+
+pub fn fetch_crypto_spot(
+    client: &Client,
+    base: &str,
+    quote: &str,
+    retry_policy: RetryPolicy,
+) -> Result<MarketQuote, ProviderError> {
+    execute_with_retry(
+        "coingecko",
+        retry_policy,
+        || fetch_once(client, base, quote),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_once(client: &Client, base: &str, quote: &str) -> Result<MarketQuote, ProviderError> {
+    let id =
+        coin_id(base).ok_or_else(|| ProviderError::UnsupportedPair(format!("{base}/{quote}")))?;
+    let vs_currency = quote.to_ascii_lowercase();
+    let response = client
+        .get(ENDPOINT)
+        .query(&[("ids", id), ("vs_currencies", vs_currency.as_str())])
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let unit_price = parse_simple_price_body(status, &body, id, &vs_currency)?;
+
+    Ok(MarketQuote::new("coingecko", unit_price, Utc::now()))
+}
+
+/// CoinGecko keys prices by coin id rather than ticker; only well-known tickers are mapped.
+pub fn coin_id(symbol: &str) -> Option<&'static str> {
+    let id = match symbol {
+        "BTC" => "bitcoin",
+        "ETH" => "ethereum",
+        "USDT" => "tether",
+        "USDC" => "usd-coin",
+        "BNB" => "binancecoin",
+        "SOL" => "solana",
+        "XRP" => "ripple",
+        "ADA" => "cardano",
+        "DOGE" => "dogecoin",
+        "TRX" => "tron",
+        "TON" => "the-open-network",
+        "AVAX" => "avalanche-2",
+        "DOT" => "polkadot",
+        "LINK" => "chainlink",
+        "LTC" => "litecoin",
+        "BCH" => "bitcoin-cash",
+        "XLM" => "stellar",
+        "ATOM" => "cosmos",
+        "SHIB" => "shiba-inu",
+        "MATIC" | "POL" => "polygon-ecosystem-token",
+        _ => return None,
+    };
+    Some(id)
+}
+
+/// CoinGecko coin pages chart every quote currency, so only the base selects the page.
+pub fn chart_url(base: &str) -> Option<String> {
+    coin_id(base).map(|id| format!("{CHART_URL_PREFIX}/{id}"))
+}
+
+pub fn parse_simple_price_body(
+    status: u16,
+    body: &str,
+    id: &str,
+    vs_currency: &str,
+) -> Result<Decimal, ProviderError> {
+    if !(200..=299).contains(&status) {
+        return Err(ProviderError::Http {
+            status,
+            message: extract_error_message(body).unwrap_or_else(|| format!("HTTP {status}")),
+        });
+    }
+
+    let payload: serde_json::Value = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(error.to_string()))?;
+    // Unknown quote currencies are answered with an empty object instead of an error.
+    let price = payload
+        .get(id)
+        .and_then(|prices| prices.get(vs_currency))
+        .ok_or_else(|| ProviderError::UnsupportedPair(format!("{id}/{vs_currency}")))?;
+
+    let raw = price.to_string();
+    raw.parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(&raw))
+        .map_err(|_| ProviderError::InvalidResponse("invalid coingecko price".to_string()))
+}
+
+fn extract_error_message(body: &str) -> Option<String> {
+    let value = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    let candidates = [
+        value.get("error").and_then(serde_json::Value::as_str),
+        value
+            .get("status")
+            .and_then(|status| status.get("error_message"))
+            .and_then(serde_json::Value::as_str),
+    ];
+
+    candidates
+        .iter()
+        .flatten()
+        .map(|item| item.trim())
+        .find(|item| !item.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coingecko_parse_simple_price_body_extracts_price() {
+        let body = r#"{"bitcoin":{"usd":67321.12}}"#;
+        let price = parse_simple_price_body(200, body, "bitcoin", "usd").expect("must parse");
+        assert_eq!(price.to_string(), "67321.12");
+
+        let body = r#"{"shiba-inu":{"usd":1.2e-5}}"#;
+        let price = parse_simple_price_body(200, body, "shiba-inu", "usd").expect("must parse");
+        assert_eq!(price.normalize().to_string(), "0.000012");
+    }
+
+    #[test]
+    fn coingecko_parse_simple_price_body_maps_missing_quote_to_unsupported_pair() {
+        let err = parse_simple_price_body(200, r#"{"bitcoin":{}}"#, "bitcoin", "abc")
+            .expect_err("must fail");
+        assert_eq!(
+            err,
+            ProviderError::UnsupportedPair("bitcoin/abc".to_string())
+        );
+    }
+
+    #[test]
+    fn coingecko_parse_simple_price_body_surfaces_rate_limit() {
+        let body =
+            r#"{"status":{"error_code":429,"error_message":"You've exceeded the Rate Limit."}}"#;
+        let err = parse_simple_price_body(429, body, "bitcoin", "usd").expect_err("must fail");
+        assert_eq!(
+            err,
+            ProviderError::Http {
+                status: 429,
+                message: "You've exceeded the Rate Limit.".to_string(),
+            }
+        );
+        assert!(err.retryable());
+    }
+
+    #[test]
+    fn coingecko_coin_id_maps_known_tickers_only() {
+        assert_eq!(coin_id("BTC"), Some("bitcoin"));
+        assert_eq!(coin_id("AVAX"), Some("avalanche-2"));
+        assert_eq!(coin_id("NOPE"), None);
+    }
+}
@@ -1,16 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use reqwest::blocking::Client;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
 use crate::config::RetryPolicy;
-use crate::model::MarketQuote;
+use crate::model::{MarketQuote, PricePoint, PriceSeries};
 
 use super::{ProviderError, execute_with_retry};
 
 const ENDPOINT: &str = "https://api.frankfurter.dev/v1/latest";
+const TIMESERIES_ENDPOINT_PREFIX: &str = "https://api.frankfurter.dev/v1";
 
 pub fn fetch_fx_rate(
     client: &Client,
@@ -42,6 +43,81 @@ fn fetch_once(client: &Client, base: &str, quote: &str) -> Result<MarketQuote, P
     Ok(MarketQuote::new("frankfurter", unit_price, Utc::now()))
 }
 
+pub fn fetch_fx_history(
+    client: &Client,
+    base: &str,
+    quote: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+    retry_policy: RetryPolicy,
+) -> Result<PriceSeries, ProviderError> {
+    execute_with_retry(
+        "frankfurter",
+        retry_policy,
+        || fetch_history_once(client, base, quote, start, end),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_history_once(
+    client: &Client,
+    base: &str,
+    quote: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<PriceSeries, ProviderError> {
+    let response = client
+        .get(format!("{TIMESERIES_ENDPOINT_PREFIX}/{start}..{end}"))
+        .query(&[("base", base), ("symbols", quote)])
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let points = parse_timeseries_body(status, &body, quote)?;
+
+    Ok(PriceSeries {
+        provider: "frankfurter".to_string(),
+        points,
+        fetched_at: Utc::now(),
+    })
+}
+
+/// Daily rates from the time-series endpoint. Frankfurter publishes working days only, so weekends are absent.
+pub fn parse_timeseries_body(
+    status: u16,
+    body: &str,
+    quote: &str,
+) -> Result<Vec<PricePoint>, ProviderError> {
+    if !(200..=299).contains(&status) {
+        return Err(ProviderError::Http {
+            status,
+            message: extract_error_message(body).unwrap_or_else(|| format!("HTTP {status}")),
+        });
+    }
+
+    let payload: FrankfurterTimeseriesResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(error.to_string()))?;
+    let points = payload
+        .rates
+        .iter()
+        .filter_map(|(date, rates)| {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+            let price = rates.get(quote).and_then(parse_decimal_value)?;
+            Some(PricePoint { date, price })
+        })
+        .collect::<Vec<_>>();
+
+    if points.is_empty() {
+        return Err(ProviderError::InvalidResponse(format!(
+            "no daily rates for {quote}"
+        )));
+    }
+    Ok(points)
+}
+
 pub fn parse_fx_body(status: u16, body: &str, quote: &str) -> Result<Decimal, ProviderError> {
     if !(200..=299).contains(&status) {
         return Err(ProviderError::Http {
@@ -95,6 +171,12 @@ struct FrankfurterResponse {
     rates: HashMap<String, serde_json::Value>,
 }
 
+#[derive(Debug, Deserialize)]
+struct FrankfurterTimeseriesResponse {
+    #[serde(default)]
+    rates: BTreeMap<String, HashMap<String, serde_json::Value>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +201,29 @@ mod tests {
         assert!(matches!(err, ProviderError::InvalidResponse(_)));
     }
 
+    #[test]
+    fn frankfurter_parse_timeseries_body_extracts_daily_rates() {
+        let body = r#"{
+            "amount": 1.0,
+            "base": "USD",
+            "start_date": "2026-02-02",
+            "end_date": "2026-02-04",
+            "rates": {
+                "2026-02-02": { "TWD": 32.1 },
+                "2026-02-03": { "TWD": 32.25 },
+                "2026-02-04": { "JPY": 150.1 }
+            }
+        }"#;
+
+        let points = parse_timeseries_body(200, body, "TWD").expect("must parse");
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].date.to_string(), "2026-02-03");
+        assert_eq!(points[1].price.to_string(), "32.25");
+
+        let err = parse_timeseries_body(200, r#"{"rates":{}}"#, "TWD").expect_err("must fail");
+        assert!(matches!(err, ProviderError::InvalidResponse(_)));
+    }
+
     #[test]
     fn frankfurter_parse_surfaces_http_error_message() {
         let body = r#"{"message":"rate limit"}"#;
@@ -0,0 +1,122 @@
+use chrono::Utc;
+use reqwest::blocking::Client;
+use rust_decimal::Decimal;
+
+use crate::config::RetryPolicy;
+use crate::model::{MarketQuote, is_metal_symbol};
+
+use super::{ProviderError, execute_with_retry};
+
+const ENDPOINT_BASE: &str = "https://api.gold-api.com/price";
+
+/// Spot price of one troy ounce of `metal` (XAU/XAG/XPT) in USD.
+pub fn fetch_metal_spot(
+    client: &Client,
+    metal: &str,
+    retry_policy: RetryPolicy,
+) -> Result<MarketQuote, ProviderError> {
+    execute_with_retry(
+        "gold-api",
+        retry_policy,
+        || fetch_once(client, metal),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_once(client: &Client, metal: &str) -> Result<MarketQuote, ProviderError> {
+    if !is_metal_symbol(metal) {
+        return Err(ProviderError::UnsupportedPair(format!("{metal}/USD")));
+    }
+
+    let response = client
+        .get(format!("{ENDPOINT_BASE}/{metal}"))
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let unit_price = parse_price_body(status, &body, metal)?;
+
+    Ok(MarketQuote::new("gold-api", unit_price, Utc::now()))
+}
+
+pub fn parse_price_body(status: u16, body: &str, metal: &str) -> Result<Decimal, ProviderError> {
+    if status == 404 {
+        return Err(ProviderError::UnsupportedPair(format!("{metal}/USD")));
+    }
+    if !(200..=299).contains(&status) {
+        return Err(ProviderError::Http {
+            status,
+            message: extract_error_message(body).unwrap_or_else(|| format!("HTTP {status}")),
+        });
+    }
+
+    let payload: serde_json::Value = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(error.to_string()))?;
+    let price = payload
+        .get("price")
+        .filter(|price| price.is_number())
+        .ok_or_else(|| ProviderError::InvalidResponse("missing gold-api price".to_string()))?;
+
+    let raw = price.to_string();
+    let unit_price = raw
+        .parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(&raw))
+        .map_err(|_| ProviderError::InvalidResponse("invalid gold-api price".to_string()))?;
+    if unit_price <= Decimal::ZERO {
+        return Err(ProviderError::InvalidResponse(
+            "non-positive gold-api price".to_string(),
+        ));
+    }
+    Ok(unit_price)
+}
+
+fn extract_error_message(body: &str) -> Option<String> {
+    let value = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    value
+        .get("error")
+        .or_else(|| value.get("message"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goldapi_parse_price_body_extracts_usd_per_ounce() {
+        let body = r#"{"name":"Gold","price":2345.6001,"symbol":"XAU","updatedAt":"2026-10-15T08:00:00Z"}"#;
+
+        let price = parse_price_body(200, body, "XAU").expect("must parse");
+        assert_eq!(price.to_string(), "2345.6001");
+    }
+
+    #[test]
+    fn goldapi_parse_price_body_rejects_missing_or_invalid_price() {
+        let err = parse_price_body(200, r#"{"symbol":"XAU"}"#, "XAU").expect_err("must fail");
+        assert!(matches!(err, ProviderError::InvalidResponse(_)));
+
+        let err = parse_price_body(200, r#"{"price":0}"#, "XAU").expect_err("must fail");
+        assert!(matches!(err, ProviderError::InvalidResponse(_)));
+    }
+
+    #[test]
+    fn goldapi_parse_price_body_maps_errors() {
+        assert_eq!(
+            parse_price_body(404, "", "XPD").expect_err("must fail"),
+            ProviderError::UnsupportedPair("XPD/USD".to_string())
+        );
+        assert_eq!(
+            parse_price_body(429, r#"{"error":"rate limited"}"#, "XAU").expect_err("must fail"),
+            ProviderError::Http {
+                status: 429,
+                message: "rate limited".to_string(),
+            }
+        );
+    }
+}
@@ -11,6 +11,7 @@ use crate::model::MarketQuote;
 use super::{ProviderError, execute_with_retry};
 
 const ENDPOINT: &str = "https://api.kraken.com/0/public/Ticker";
+const CHART_URL_PREFIX: &str = "https://pro.kraken.com/app/trade";
 
 pub fn fetch_crypto_spot(
     client: &Client,
@@ -43,6 +44,14 @@ fn fetch_once(client: &Client, base: &str, quote: &str) -> Result<MarketQuote, P
     Ok(MarketQuote::new("kraken", unit_price, Utc::now()))
 }
 
+pub fn chart_url(base: &str, quote: &str) -> String {
+    format!(
+        "{CHART_URL_PREFIX}/{}-{}",
+        base.to_ascii_lowercase(),
+        quote.to_ascii_lowercase()
+    )
+}
+
 pub fn normalize_pair(base: &str, quote: &str) -> Result<String, ProviderError> {
     if !is_valid_symbol(base) || !is_valid_symbol(quote) {
         return Err(ProviderError::UnsupportedPair(format!("{base}/{quote}")));
@@ -1,20 +1,47 @@
 use std::time::Duration;
 
+use chrono::NaiveDate;
 use reqwest::blocking::Client;
 use thiserror::Error;
 
 use crate::config::{PROVIDER_TIMEOUT_SECS, RetryPolicy};
-use crate::model::MarketQuote;
+use crate::model::{CryptoProvider, MarketQuote, PriceSeries, StockQuote};
 
+pub mod binance;
 pub mod coinbase;
+pub mod coingecko;
 pub mod floatrates;
 pub mod frankfurter;
+pub mod goldapi;
 pub mod kraken;
+pub mod stooq;
+pub mod yahoo;
 
 pub trait ProviderApi {
     fn fetch_fx_rate(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError>;
     fn fetch_crypto_coinbase(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError>;
     fn fetch_crypto_kraken(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError>;
+    fn fetch_crypto_binance(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError>;
+    fn fetch_crypto_coingecko(&self, base: &str, quote: &str)
+    -> Result<MarketQuote, ProviderError>;
+    /// USD price of one troy ounce of `metal`.
+    fn fetch_metal_spot(&self, metal: &str) -> Result<MarketQuote, ProviderError>;
+    fn fetch_stock_yahoo(&self, symbol: &str) -> Result<StockQuote, ProviderError>;
+    fn fetch_stock_stooq(&self, symbol: &str) -> Result<StockQuote, ProviderError>;
+    fn fetch_fx_history(
+        &self,
+        base: &str,
+        quote: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<PriceSeries, ProviderError>;
+    fn fetch_crypto_history(
+        &self,
+        base: &str,
+        quote: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<PriceSeries, ProviderError>;
 }
 
 #[derive(Debug, Clone)]
@@ -58,6 +85,50 @@ impl ProviderApi for HttpProviders {
     fn fetch_crypto_kraken(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError> {
         kraken::fetch_crypto_spot(&self.client, base, quote, self.retry_policy)
     }
+
+    fn fetch_crypto_binance(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError> {
+        binance::fetch_crypto_spot(&self.client, base, quote, self.retry_policy)
+    }
+
+    fn fetch_crypto_coingecko(
+        &self,
+        base: &str,
+        quote: &str,
+    ) -> Result<MarketQuote, ProviderError> {
+        coingecko::fetch_crypto_spot(&self.client, base, quote, self.retry_policy)
+    }
+
+    fn fetch_metal_spot(&self, metal: &str) -> Result<MarketQuote, ProviderError> {
+        goldapi::fetch_metal_spot(&self.client, metal, self.retry_policy)
+    }
+
+    fn fetch_stock_yahoo(&self, symbol: &str) -> Result<StockQuote, ProviderError> {
+        yahoo::fetch_stock_quote(&self.client, symbol, self.retry_policy)
+    }
+
+    fn fetch_stock_stooq(&self, symbol: &str) -> Result<StockQuote, ProviderError> {
+        stooq::fetch_stock_quote(&self.client, symbol, self.retry_policy)
+    }
+
+    fn fetch_fx_history(
+        &self,
+        base: &str,
+        quote: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<PriceSeries, ProviderError> {
+        frankfurter::fetch_fx_history(&self.client, base, quote, start, end, self.retry_policy)
+    }
+
+    fn fetch_crypto_history(
+        &self,
+        base: &str,
+        quote: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<PriceSeries, ProviderError> {
+        coinbase::fetch_crypto_history(&self.client, base, quote, start, end, self.retry_policy)
+    }
 }
 
 fn resolve_fx_with_fallback<F>(
@@ -78,6 +149,17 @@ where
     }
 }
 
+/// Web chart for `base/quote` on the crypto provider named by `provider`. FX and metal providers have no chart pages,
+/// and combined labels (metal conversions, cross rates) have no single pair to chart.
+pub fn chart_url(provider: &str, base: &str, quote: &str) -> Option<String> {
+    match CryptoProvider::parse(provider)? {
+        CryptoProvider::Coinbase => Some(coinbase::chart_url(base, quote)),
+        CryptoProvider::Kraken => Some(kraken::chart_url(base, quote)),
+        CryptoProvider::Binance => Some(binance::chart_url(base, quote)),
+        CryptoProvider::Coingecko => coingecko::chart_url(base),
+    }
+}
+
 pub fn execute_with_retry<T, F, S>(
     provider_name: &'static str,
     policy: RetryPolicy,
@@ -121,6 +203,8 @@ pub enum ProviderError {
     InvalidResponse(String),
     #[error("unsupported trading pair: {0}")]
     UnsupportedPair(String),
+    #[error("unknown symbol: {0}")]
+    UnknownSymbol(String),
 }
 
 impl ProviderError {
@@ -130,6 +214,7 @@ impl ProviderError {
             ProviderError::Http { status, .. } => *status == 429 || (500..=599).contains(status),
             ProviderError::InvalidResponse(_) => false,
             ProviderError::UnsupportedPair(_) => false,
+            ProviderError::UnknownSymbol(_) => false,
         }
     }
 
@@ -148,6 +233,9 @@ impl ProviderError {
             ProviderError::UnsupportedPair(message) => {
                 ProviderError::UnsupportedPair(format!("{provider}: {message}"))
             }
+            ProviderError::UnknownSymbol(message) => {
+                ProviderError::UnknownSymbol(format!("{provider}: {message}"))
+            }
         }
     }
 }
@@ -159,6 +247,29 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn provider_chart_url_links_crypto_pairs_only() {
+        assert_eq!(
+            chart_url("coinbase", "BTC", "USD").as_deref(),
+            Some("https://www.coinbase.com/advanced-trade/spot/BTC-USD")
+        );
+        assert_eq!(
+            chart_url("kraken", "ETH", "EUR").as_deref(),
+            Some("https://pro.kraken.com/app/trade/eth-eur")
+        );
+        assert_eq!(
+            chart_url("binance", "SOL", "USD").as_deref(),
+            Some("https://www.binance.com/en/trade/SOL_USDT")
+        );
+        assert_eq!(
+            chart_url("coingecko", "BTC", "TWD").as_deref(),
+            Some("https://www.coingecko.com/en/coins/bitcoin")
+        );
+        assert_eq!(chart_url("coingecko", "PEPE", "USD"), None);
+        assert_eq!(chart_url("frankfurter", "USD", "TWD"), None);
+        assert_eq!(chart_url("frankfurter+coinbase", "TWD", "BTC"), None);
+    }
+
     #[test]
     fn provider_retries_transient_failures_with_backoff() {
         let attempts = Rc::new(RefCell::new(0usize));
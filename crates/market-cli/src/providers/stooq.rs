@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use rust_decimal::Decimal;
+
+use crate::config::RetryPolicy;
+use crate::model::StockQuote;
+
+use super::{ProviderError, execute_with_retry};
+
+const ENDPOINT: &str = "https://stooq.com/q/l/";
+const MISSING_VALUE: &str = "N/D";
+
+pub fn fetch_stock_quote(
+    client: &Client,
+    symbol: &str,
+    retry_policy: RetryPolicy,
+) -> Result<StockQuote, ProviderError> {
+    execute_with_retry(
+        "stooq",
+        retry_policy,
+        || fetch_once(client, symbol),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_once(client: &Client, symbol: &str) -> Result<StockQuote, ProviderError> {
+    let stooq_symbol = normalize_symbol(symbol);
+    let response = client
+        .get(ENDPOINT)
+        .query(&[
+            ("s", stooq_symbol.as_str()),
+            ("f", "sd2t2ohlcv"),
+            ("h", ""),
+            ("e", "csv"),
+        ])
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    parse_quote_body(status, &body, &stooq_symbol, Utc::now())
+}
+
+/// Stooq lists US tickers with a `.us` suffix; symbols that already carry an exchange suffix pass through.
+pub fn normalize_symbol(symbol: &str) -> String {
+    let lowered = symbol.to_ascii_lowercase();
+    if lowered.contains('.') {
+        lowered
+    } else {
+        format!("{lowered}.us")
+    }
+}
+
+/// Parse the headed CSV quote (`Symbol,Date,Time,Open,High,Low,Close,Volume`). Stooq reports no currency or
+/// previous close; only the `.us` market currency is known.
+pub fn parse_quote_body(
+    status: u16,
+    body: &str,
+    stooq_symbol: &str,
+    fetched_at: DateTime<Utc>,
+) -> Result<StockQuote, ProviderError> {
+    if !(200..=299).contains(&status) {
+        return Err(ProviderError::Http {
+            status,
+            message: format!("HTTP {status}"),
+        });
+    }
+
+    let mut lines = body.lines().map(str::trim).filter(|line| !line.is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| ProviderError::InvalidResponse("empty stooq response".to_string()))?;
+    let row = lines
+        .next()
+        .ok_or_else(|| ProviderError::InvalidResponse("missing stooq quote row".to_string()))?;
+    let close_index = header
+        .split(',')
+        .position(|column| column.trim().eq_ignore_ascii_case("close"))
+        .ok_or_else(|| ProviderError::InvalidResponse("missing stooq close column".to_string()))?;
+    let close = row
+        .split(',')
+        .nth(close_index)
+        .map(str::trim)
+        .ok_or_else(|| ProviderError::InvalidResponse("short stooq quote row".to_string()))?;
+    if close == MISSING_VALUE {
+        return Err(ProviderError::UnknownSymbol(
+            stooq_symbol.to_ascii_uppercase(),
+        ));
+    }
+    let price = close
+        .parse::<Decimal>()
+        .map_err(|_| ProviderError::InvalidResponse(format!("invalid stooq close: {close}")))?;
+
+    Ok(StockQuote {
+        provider: "stooq".to_string(),
+        price,
+        currency: stooq_symbol.ends_with(".us").then(|| "USD".to_string()),
+        previous_close: None,
+        fetched_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn fetched_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 10, 21, 0, 0)
+            .single()
+            .expect("time")
+    }
+
+    #[test]
+    fn stooq_normalize_symbol_defaults_to_us_market() {
+        assert_eq!(normalize_symbol("AAPL"), "aapl.us");
+        assert_eq!(normalize_symbol("BRK-B"), "brk-b.us");
+        assert_eq!(normalize_symbol("7203.JP"), "7203.jp");
+    }
+
+    #[test]
+    fn stooq_parse_quote_body_reads_close_column() {
+        let body = "Symbol,Date,Time,Open,High,Low,Close,Volume\r\n\
+                    AAPL.US,2026-02-10,22:00:09,225.1,228.3,224.9,227.52,51234567\r\n";
+
+        let quote = parse_quote_body(200, body, "aapl.us", fetched_at()).expect("must parse");
+        assert_eq!(quote.provider, "stooq");
+        assert_eq!(quote.price.to_string(), "227.52");
+        assert_eq!(quote.currency.as_deref(), Some("USD"));
+        assert_eq!(quote.previous_close, None);
+
+        let tokyo = "Symbol,Date,Time,Open,High,Low,Close,Volume\n7203.JP,2026-02-10,07:00:00,1,1,1,2890,1\n";
+        let quote = parse_quote_body(200, tokyo, "7203.jp", fetched_at()).expect("must parse");
+        assert_eq!(quote.currency, None);
+    }
+
+    #[test]
+    fn stooq_parse_quote_body_maps_missing_data_to_unknown_symbol() {
+        let body =
+            "Symbol,Date,Time,Open,High,Low,Close,Volume\nNOPE.US,N/D,N/D,N/D,N/D,N/D,N/D,N/D\n";
+        assert_eq!(
+            parse_quote_body(200, body, "nope.us", fetched_at()),
+            Err(ProviderError::UnknownSymbol("NOPE.US".to_string()))
+        );
+        assert!(matches!(
+            parse_quote_body(200, "", "aapl.us", fetched_at()),
+            Err(ProviderError::InvalidResponse(_))
+        ));
+    }
+}
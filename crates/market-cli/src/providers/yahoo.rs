@@ -0,0 +1,177 @@
+use chrono::{DateTime, Utc};
+use reqwest::blocking::Client;
+use reqwest::header::USER_AGENT;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::config::RetryPolicy;
+use crate::model::StockQuote;
+
+use super::{ProviderError, execute_with_retry};
+
+const ENDPOINT_PREFIX: &str = "https://query1.finance.yahoo.com/v8/finance/chart";
+// The chart endpoint rate-limits requests without a browser-like user agent.
+const BROWSER_USER_AGENT: &str = "Mozilla/5.0";
+
+pub fn fetch_stock_quote(
+    client: &Client,
+    symbol: &str,
+    retry_policy: RetryPolicy,
+) -> Result<StockQuote, ProviderError> {
+    execute_with_retry(
+        "yahoo",
+        retry_policy,
+        || fetch_once(client, symbol),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_once(client: &Client, symbol: &str) -> Result<StockQuote, ProviderError> {
+    let response = client
+        .get(format!("{ENDPOINT_PREFIX}/{symbol}"))
+        .query(&[("range", "1d"), ("interval", "1d")])
+        .header(USER_AGENT, BROWSER_USER_AGENT)
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    parse_chart_body(status, &body, symbol, Utc::now())
+}
+
+pub fn parse_chart_body(
+    status: u16,
+    body: &str,
+    symbol: &str,
+    fetched_at: DateTime<Utc>,
+) -> Result<StockQuote, ProviderError> {
+    let payload = serde_json::from_str::<YahooChartResponse>(body);
+    if !(200..=299).contains(&status) {
+        if status == 404 {
+            return Err(ProviderError::UnknownSymbol(symbol.to_string()));
+        }
+        let message = payload
+            .ok()
+            .and_then(|payload| payload.chart.error)
+            .map(|error| error.description)
+            .filter(|message| !message.trim().is_empty())
+            .unwrap_or_else(|| format!("HTTP {status}"));
+        return Err(ProviderError::Http { status, message });
+    }
+
+    let payload = payload.map_err(|error| ProviderError::InvalidResponse(error.to_string()))?;
+    let meta = payload
+        .chart
+        .result
+        .and_then(|results| results.into_iter().next())
+        .map(|result| result.meta)
+        .ok_or_else(|| ProviderError::UnknownSymbol(symbol.to_string()))?;
+    let price = meta
+        .regular_market_price
+        .as_ref()
+        .and_then(number_to_decimal)
+        .ok_or_else(|| ProviderError::InvalidResponse("missing regularMarketPrice".to_string()))?;
+    let previous_close = meta
+        .previous_close
+        .as_ref()
+        .or(meta.chart_previous_close.as_ref())
+        .and_then(number_to_decimal);
+
+    Ok(StockQuote {
+        provider: "yahoo".to_string(),
+        price,
+        currency: meta.currency.map(|currency| currency.to_ascii_uppercase()),
+        previous_close,
+        fetched_at,
+    })
+}
+
+fn number_to_decimal(value: &serde_json::Number) -> Option<Decimal> {
+    value.to_string().parse::<Decimal>().ok()
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartResponse {
+    chart: YahooChart,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChart {
+    result: Option<Vec<YahooChartResult>>,
+    error: Option<YahooChartError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartResult {
+    meta: YahooChartMeta,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YahooChartMeta {
+    currency: Option<String>,
+    regular_market_price: Option<serde_json::Number>,
+    previous_close: Option<serde_json::Number>,
+    chart_previous_close: Option<serde_json::Number>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartError {
+    description: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn fetched_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 10, 21, 0, 0)
+            .single()
+            .expect("time")
+    }
+
+    #[test]
+    fn yahoo_parse_chart_body_extracts_price_currency_and_previous_close() {
+        let body = r#"{"chart":{"result":[{"meta":{
+            "currency":"USD","symbol":"AAPL","regularMarketPrice":227.52,"chartPreviousClose":225.0
+        }}],"error":null}}"#;
+
+        let quote = parse_chart_body(200, body, "AAPL", fetched_at()).expect("must parse");
+        assert_eq!(quote.provider, "yahoo");
+        assert_eq!(quote.price.to_string(), "227.52");
+        assert_eq!(quote.currency.as_deref(), Some("USD"));
+        assert_eq!(
+            quote.previous_close.map(|close| close.to_string()),
+            Some("225.0".to_string())
+        );
+    }
+
+    #[test]
+    fn yahoo_parse_chart_body_maps_missing_symbols_and_http_errors() {
+        let not_found = r#"{"chart":{"result":null,"error":{
+            "code":"Not Found","description":"No data found, symbol may be delisted"
+        }}}"#;
+        assert_eq!(
+            parse_chart_body(404, not_found, "NOPE", fetched_at()),
+            Err(ProviderError::UnknownSymbol("NOPE".to_string()))
+        );
+
+        let throttled = r#"{"chart":{"result":null,"error":{"code":"Too Many Requests","description":"Rate limited"}}}"#;
+        assert_eq!(
+            parse_chart_body(429, throttled, "AAPL", fetched_at()),
+            Err(ProviderError::Http {
+                status: 429,
+                message: "Rate limited".to_string(),
+            })
+        );
+
+        assert!(matches!(
+            parse_chart_body(200, "not-json", "AAPL", fetched_at()),
+            Err(ProviderError::InvalidResponse(_))
+        ));
+    }
+}
@@ -2,22 +2,184 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 
 use crate::cache::{
-    CacheRecord, cache_key, cache_path, evaluate_freshness, parse_fetched_at, read_cache,
-    write_cache,
+    CacheRecord, HistoryCacheRecord, StockCacheRecord, cache_key, cache_path, evaluate_freshness,
+    evaluate_freshness_at, history_cache_key, history_cache_path, parse_fetched_at, read_cache,
+    read_history_cache, read_stock_cache, stock_cache_key, stock_cache_path, write_cache,
+    write_history_cache, write_stock_cache,
 };
 use crate::config::RuntimeConfig;
 use crate::error::AppError;
 use crate::model::{
-    CacheMetadata, CacheStatus, MarketOutput, MarketQuote, MarketRequest, build_output,
+    CacheMetadata, CacheStatus, CrossRateDerivation, CrossRateLeg, CryptoProvider, HistoryOutput,
+    HistoryPointOutput, HistoryRequest, MarketKind, MarketOutput, MarketQuote, MarketRequest,
+    PricePoint, PriceSeries, ProviderFailure, StockOutput, StockQuote, build_history_output,
+    build_output, build_stock_output, is_metal_symbol, looks_like_fiat_symbol,
 };
-use crate::providers::ProviderApi;
+use crate::providers::{ProviderApi, ProviderError};
 
+/// Currency both legs of a cross rate are quoted in.
+pub const CROSS_RATE_PIVOT: &str = "USD";
+
+/// Quote for `request`: fresh cache, then the live providers, then a stale cache entry. When none of those has the
+/// pair (typically exotic fiat against crypto), the rate is crossed through [`CROSS_RATE_PIVOT`] and the output
+/// records both legs in `derivation`.
 pub fn resolve_market<P, N>(
     config: &RuntimeConfig,
     providers: &P,
     now_fn: N,
     request: &MarketRequest,
 ) -> Result<MarketOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let direct_error = match resolve_direct_market(config, providers, &now_fn, request) {
+        Ok(output) => return Ok(output),
+        Err(error) => error,
+    };
+    if request.kind == MarketKind::Metal
+        || request.base == CROSS_RATE_PIVOT
+        || request.quote == CROSS_RATE_PIVOT
+        || request.base == request.quote
+    {
+        return Err(direct_error);
+    }
+
+    resolve_cross_market(config, providers, &now_fn, request).map_err(|pivot_error| AppError {
+        kind: direct_error.kind,
+        message: format!(
+            "{}; cross rate via {CROSS_RATE_PIVOT} failed: {}",
+            direct_error.message, pivot_error.message
+        ),
+    })
+}
+
+/// Resolves `symbol` priced in `target_fiat`, trying metal, then FX (for fiat-looking codes), then crypto.
+pub fn resolve_symbol_output<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    symbol: &str,
+    target_fiat: &str,
+) -> Result<MarketOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let failure_prefix = format!("failed to resolve quote for {symbol}/{target_fiat}");
+
+    // Metal codes look like fiat codes, so they are routed before the FX attempt.
+    if is_metal_symbol(symbol) {
+        let metal_request = MarketRequest::new(MarketKind::Metal, symbol, target_fiat, "1")
+            .map_err(AppError::from)?;
+        return resolve_market(config, providers, now_fn, &metal_request).map_err(|error| {
+            AppError::runtime_with_trace(&failure_prefix, &[format!("metal: {}", error.message)])
+        });
+    }
+
+    let mut trace = Vec::<String>::new();
+
+    if looks_like_fiat_symbol(symbol) {
+        let fx_request =
+            MarketRequest::new(MarketKind::Fx, symbol, target_fiat, "1").map_err(AppError::from)?;
+        match resolve_market(config, providers, now_fn, &fx_request) {
+            Ok(output) => return Ok(output),
+            Err(error) => trace.push(format!("fx: {}", error.message)),
+        }
+    }
+
+    let crypto_request =
+        MarketRequest::new(MarketKind::Crypto, symbol, target_fiat, "1").map_err(AppError::from)?;
+    match resolve_market(config, providers, now_fn, &crypto_request) {
+        Ok(output) => Ok(output),
+        Err(error) => {
+            trace.push(format!("crypto: {}", error.message));
+            Err(AppError::runtime_with_trace(&failure_prefix, &trace))
+        }
+    }
+}
+
+/// Crosses `request.base/USD` with `request.quote/USD`. Each leg goes through [`resolve_symbol_output`], so it is
+/// cached under its own key and reused by later direct lookups of that leg.
+/// Takes `now_fn` as a trait object: the legs recurse into [`resolve_market`], and a generic clock would need a new
+/// instantiation per level.
+fn resolve_cross_market<P: ProviderApi>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: &dyn Fn() -> DateTime<Utc>,
+    request: &MarketRequest,
+) -> Result<MarketOutput, AppError> {
+    let base_leg =
+        resolve_symbol_output(config, providers, now_fn, &request.base, CROSS_RATE_PIVOT)?;
+    let quote_leg =
+        resolve_symbol_output(config, providers, now_fn, &request.quote, CROSS_RATE_PIVOT)?;
+    let invalid_leg = |leg: &MarketOutput| {
+        AppError::runtime(format!(
+            "invalid cross rate leg {}/{}: {}",
+            leg.base, leg.quote, leg.unit_price
+        ))
+    };
+    let base_price = base_leg
+        .unit_price
+        .parse::<Decimal>()
+        .map_err(|_| invalid_leg(&base_leg))?;
+    let quote_price = quote_leg
+        .unit_price
+        .parse::<Decimal>()
+        .ok()
+        .filter(|price| *price > Decimal::ZERO)
+        .ok_or_else(|| invalid_leg(&quote_leg))?;
+    let cross = base_price
+        .checked_div(quote_price)
+        .ok_or_else(|| AppError::runtime("cross rate is out of range"))?;
+    let unit_price = cross.round_sf(12).unwrap_or(cross);
+
+    let fetched_at = [&base_leg, &quote_leg]
+        .into_iter()
+        .filter_map(|leg| DateTime::parse_from_rfc3339(&leg.fetched_at).ok())
+        .map(|fetched_at| fetched_at.with_timezone(&Utc))
+        .min()
+        .unwrap_or_else(now_fn);
+    let status = if [&base_leg, &quote_leg]
+        .iter()
+        .any(|leg| leg.cache.status == CacheStatus::CacheStaleFallback)
+    {
+        CacheStatus::CacheStaleFallback
+    } else if [&base_leg, &quote_leg]
+        .iter()
+        .any(|leg| leg.cache.status == CacheStatus::Live)
+    {
+        CacheStatus::Live
+    } else {
+        CacheStatus::CacheFresh
+    };
+    let cache = CacheMetadata {
+        status,
+        key: format!("{}+{}", base_leg.cache.key, quote_leg.cache.key),
+        ttl_secs: base_leg.cache.ttl_secs.min(quote_leg.cache.ttl_secs),
+        age_secs: base_leg.cache.age_secs.max(quote_leg.cache.age_secs),
+    };
+
+    let quote = MarketQuote::new(
+        format!("{}+{}", base_leg.provider, quote_leg.provider),
+        unit_price,
+        fetched_at,
+    );
+    Ok(MarketOutput {
+        derivation: Some(CrossRateDerivation {
+            pivot: CROSS_RATE_PIVOT.to_string(),
+            legs: vec![CrossRateLeg::from(base_leg), CrossRateLeg::from(quote_leg)],
+        }),
+        ..build_output(request, &quote, cache)
+    })
+}
+
+fn resolve_direct_market<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    request: &MarketRequest,
+) -> Result<MarketOutput, AppError>
 where
     P: ProviderApi,
     N: Fn() -> DateTime<Utc>,
@@ -50,13 +212,60 @@ where
     }
 
     match request.kind {
-        crate::model::MarketKind::Fx => {
-            resolve_fx(request, providers, now, &path, ttl_secs, cached_state, key)
-        }
-        crate::model::MarketKind::Crypto => {
-            resolve_crypto(request, providers, now, &path, ttl_secs, cached_state, key)
+        MarketKind::Fx => resolve_fx(request, providers, now, &path, ttl_secs, cached_state, key),
+        MarketKind::Crypto => {
+            let (quote, failures) =
+                fetch_crypto_chain(providers, &config.crypto_providers, request);
+            let output = match quote {
+                Some(quote) => build_live_output(request, quote, now, &path, ttl_secs, key),
+                None => fallback_or_error(
+                    "failed to fetch crypto spot price",
+                    failures
+                        .iter()
+                        .map(|failure| format!("{}: {}", failure.provider, failure.error))
+                        .collect(),
+                    request,
+                    cached_state,
+                    ttl_secs,
+                    key,
+                ),
+            }?;
+            Ok(MarketOutput {
+                provider_failures: failures,
+                ..output
+            })
         }
+        MarketKind::Metal => match fetch_metal_quote(providers, request) {
+            Ok(quote) => build_live_output(request, quote, now, &path, ttl_secs, key),
+            Err(error) => fallback_or_error(
+                "failed to fetch metal spot price",
+                vec![format!("metal provider: {error}")],
+                request,
+                cached_state,
+                ttl_secs,
+                key,
+            ),
+        },
+    }
+}
+
+/// Troy-ounce spot price in `request.quote`. The metals provider only quotes USD, so other currencies are converted
+/// through the FX provider and both providers are named in the quote.
+fn fetch_metal_quote<P: ProviderApi>(
+    providers: &P,
+    request: &MarketRequest,
+) -> Result<MarketQuote, ProviderError> {
+    let spot = providers.fetch_metal_spot(&request.base)?;
+    if request.quote == "USD" {
+        return Ok(spot);
     }
+
+    let fx = providers.fetch_fx_rate("USD", &request.quote)?;
+    Ok(MarketQuote::new(
+        format!("{}+{}", spot.provider, fx.provider),
+        (spot.unit_price * fx.unit_price).round_dp(8),
+        spot.fetched_at,
+    ))
 }
 
 fn resolve_fx<P: ProviderApi>(
@@ -81,35 +290,38 @@ fn resolve_fx<P: ProviderApi>(
     }
 }
 
-fn resolve_crypto<P: ProviderApi>(
-    request: &MarketRequest,
+/// Tries each configured crypto provider in order and returns the first quote plus the failures seen before it.
+fn fetch_crypto_chain<P: ProviderApi>(
     providers: &P,
-    now: DateTime<Utc>,
-    cache_path: &std::path::Path,
-    ttl_secs: u64,
-    cached_state: Option<(MarketQuote, u64, bool)>,
-    key: String,
-) -> Result<MarketOutput, AppError> {
-    let mut trace = Vec::new();
+    chain: &[CryptoProvider],
+    request: &MarketRequest,
+) -> (Option<MarketQuote>, Vec<ProviderFailure>) {
+    let mut failures = Vec::new();
 
-    match providers.fetch_crypto_coinbase(&request.base, &request.quote) {
-        Ok(quote) => return build_live_output(request, quote, now, cache_path, ttl_secs, key),
-        Err(error) => trace.push(format!("coinbase: {error}")),
+    for provider in chain {
+        match fetch_crypto_spot(providers, *provider, &request.base, &request.quote) {
+            Ok(quote) => return (Some(quote), failures),
+            Err(error) => failures.push(ProviderFailure {
+                provider: provider.as_str().to_string(),
+                error: error.to_string(),
+            }),
+        }
     }
 
-    match providers.fetch_crypto_kraken(&request.base, &request.quote) {
-        Ok(quote) => build_live_output(request, quote, now, cache_path, ttl_secs, key),
-        Err(error) => {
-            trace.push(format!("kraken: {error}"));
-            fallback_or_error(
-                "failed to fetch crypto spot price",
-                trace,
-                request,
-                cached_state,
-                ttl_secs,
-                key,
-            )
-        }
+    (None, failures)
+}
+
+fn fetch_crypto_spot<P: ProviderApi>(
+    providers: &P,
+    provider: CryptoProvider,
+    base: &str,
+    quote: &str,
+) -> Result<MarketQuote, ProviderError> {
+    match provider {
+        CryptoProvider::Coinbase => providers.fetch_crypto_coinbase(base, quote),
+        CryptoProvider::Kraken => providers.fetch_crypto_kraken(base, quote),
+        CryptoProvider::Binance => providers.fetch_crypto_binance(base, quote),
+        CryptoProvider::Coingecko => providers.fetch_crypto_coingecko(base, quote),
     }
 }
 
@@ -168,6 +380,270 @@ fn fallback_or_error(
     Err(AppError::runtime_with_trace(prefix, &trace))
 }
 
+/// Quote for an already normalized ticker: fresh cache, then Yahoo, then Stooq, then a stale cache entry. When every
+/// provider reports the symbol as unknown (and nothing is cached) the failure is a user error.
+pub fn resolve_stock<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    symbol: &str,
+) -> Result<StockOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let now = now_fn();
+    let path = stock_cache_path(config, symbol);
+    let key = stock_cache_key(symbol);
+    let ttl_secs = config.stock_cache_ttl_secs;
+
+    let cached = read_stock_cache(&path)
+        .map_err(|error| AppError::runtime(error.to_string()))?
+        .and_then(|record| parse_stock_cache_quote(&record))
+        .map(|quote| {
+            let freshness = evaluate_freshness_at(Some(quote.fetched_at), now, ttl_secs);
+            (quote, freshness.age_secs, freshness.is_fresh)
+        });
+
+    if let Some((cached_quote, age_secs, true)) = &cached {
+        return Ok(build_stock_output(
+            symbol,
+            cached_quote,
+            CacheMetadata {
+                status: CacheStatus::CacheFresh,
+                key,
+                ttl_secs,
+                age_secs: *age_secs,
+            },
+        ));
+    }
+
+    let mut trace = Vec::new();
+    let mut unknown_symbol = true;
+
+    match providers.fetch_stock_yahoo(symbol) {
+        Ok(quote) => return build_live_stock_output(symbol, quote, now, &path, ttl_secs, key),
+        Err(error) => {
+            unknown_symbol &= matches!(error, ProviderError::UnknownSymbol(_));
+            trace.push(format!("yahoo: {error}"));
+        }
+    }
+
+    match providers.fetch_stock_stooq(symbol) {
+        Ok(quote) => return build_live_stock_output(symbol, quote, now, &path, ttl_secs, key),
+        Err(error) => {
+            unknown_symbol &= matches!(error, ProviderError::UnknownSymbol(_));
+            trace.push(format!("stooq: {error}"));
+        }
+    }
+
+    if let Some((quote, age_secs, false)) = cached {
+        return Ok(build_stock_output(
+            symbol,
+            &quote,
+            CacheMetadata {
+                status: CacheStatus::CacheStaleFallback,
+                key,
+                ttl_secs,
+                age_secs,
+            },
+        ));
+    }
+
+    if unknown_symbol {
+        return Err(AppError::user(format!("unknown stock symbol: {symbol}")));
+    }
+    Err(AppError::runtime_with_trace(
+        "failed to fetch stock quote",
+        &trace,
+    ))
+}
+
+fn build_live_stock_output(
+    symbol: &str,
+    quote: StockQuote,
+    now: DateTime<Utc>,
+    path: &std::path::Path,
+    ttl_secs: u64,
+    key: String,
+) -> Result<StockOutput, AppError> {
+    let record = StockCacheRecord {
+        symbol: symbol.to_string(),
+        provider: quote.provider.clone(),
+        price: quote.price.normalize().to_string(),
+        currency: quote.currency.clone(),
+        previous_close: quote
+            .previous_close
+            .map(|previous| previous.normalize().to_string()),
+        fetched_at: quote.fetched_at.to_rfc3339(),
+    };
+
+    write_stock_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
+
+    Ok(build_stock_output(
+        symbol,
+        &StockQuote {
+            fetched_at: now,
+            ..quote
+        },
+        CacheMetadata {
+            status: CacheStatus::Live,
+            key,
+            ttl_secs,
+            age_secs: 0,
+        },
+    ))
+}
+
+fn parse_stock_cache_quote(record: &StockCacheRecord) -> Option<StockQuote> {
+    let fetched_at = DateTime::parse_from_rfc3339(&record.fetched_at)
+        .ok()?
+        .with_timezone(&Utc);
+    Some(StockQuote {
+        provider: record.provider.clone(),
+        price: record.price.parse::<Decimal>().ok()?,
+        currency: record.currency.clone(),
+        previous_close: record
+            .previous_close
+            .as_deref()
+            .and_then(|previous| previous.parse::<Decimal>().ok()),
+        fetched_at,
+    })
+}
+
+/// Daily series for `request.range` calendar days ending today (UTC). Uses the same TTL and stale-fallback policy as
+/// spot quotes of the same kind; crypto history comes from Coinbase exchange candles only.
+pub fn resolve_history<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    request: &HistoryRequest,
+) -> Result<HistoryOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let now = now_fn();
+    let days = request.range.days;
+    let path = history_cache_path(config, request.kind, &request.base, &request.quote, days);
+    let key = history_cache_key(request.kind, &request.base, &request.quote, days);
+    let ttl_secs = config.cache_ttl_secs_for_kind(request.kind);
+    let cache_metadata = |status, age_secs| CacheMetadata {
+        status,
+        key: key.clone(),
+        ttl_secs,
+        age_secs,
+    };
+    let no_points = || {
+        AppError::runtime(format!(
+            "no price history for {}/{}",
+            request.base, request.quote
+        ))
+    };
+
+    let cached = read_history_cache(&path)
+        .map_err(|error| AppError::runtime(error.to_string()))?
+        .and_then(|record| parse_history_cache_series(&record))
+        .map(|series| {
+            let freshness = evaluate_freshness_at(Some(series.fetched_at), now, ttl_secs);
+            (series, freshness.age_secs, freshness.is_fresh)
+        });
+
+    if let Some((series, age_secs, true)) = &cached {
+        return build_history_output(
+            request,
+            series,
+            cache_metadata(CacheStatus::CacheFresh, *age_secs),
+        )
+        .ok_or_else(no_points);
+    }
+
+    let end = now.date_naive();
+    let start = end - chrono::Duration::days(i64::from(days) - 1);
+    let (provider_label, fetched) = match request.kind {
+        MarketKind::Fx => (
+            "fx provider",
+            providers.fetch_fx_history(&request.base, &request.quote, start, end),
+        ),
+        MarketKind::Crypto => (
+            "coinbase",
+            providers.fetch_crypto_history(&request.base, &request.quote, start, end),
+        ),
+        MarketKind::Metal => (
+            "metal provider",
+            Err(ProviderError::UnsupportedPair(format!(
+                "no price history for metal {}/{}",
+                request.base, request.quote
+            ))),
+        ),
+    };
+
+    match fetched {
+        Ok(series) => {
+            let record = HistoryCacheRecord {
+                base: request.base.clone(),
+                quote: request.quote.clone(),
+                provider: series.provider.clone(),
+                points: series
+                    .points
+                    .iter()
+                    .map(|point| HistoryPointOutput {
+                        date: point.date.to_string(),
+                        price: point.price.normalize().to_string(),
+                    })
+                    .collect(),
+                fetched_at: series.fetched_at.to_rfc3339(),
+            };
+            write_history_cache(&path, &record)
+                .map_err(|error| AppError::runtime(error.to_string()))?;
+
+            build_history_output(
+                request,
+                &PriceSeries {
+                    fetched_at: now,
+                    ..series
+                },
+                cache_metadata(CacheStatus::Live, 0),
+            )
+            .ok_or_else(no_points)
+        }
+        Err(error) => match cached {
+            Some((series, age_secs, false)) => build_history_output(
+                request,
+                &series,
+                cache_metadata(CacheStatus::CacheStaleFallback, age_secs),
+            )
+            .ok_or_else(no_points),
+            _ => Err(AppError::runtime_with_trace(
+                "failed to fetch price history",
+                &[format!("{provider_label}: {error}")],
+            )),
+        },
+    }
+}
+
+fn parse_history_cache_series(record: &HistoryCacheRecord) -> Option<PriceSeries> {
+    let fetched_at = DateTime::parse_from_rfc3339(&record.fetched_at)
+        .ok()?
+        .with_timezone(&Utc);
+    let points = record
+        .points
+        .iter()
+        .map(|point| {
+            Some(PricePoint {
+                date: point.date.parse().ok()?,
+                price: point.price.parse::<Decimal>().ok()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(PriceSeries {
+        provider: record.provider.clone(),
+        points,
+        fetched_at,
+    })
+}
+
 fn parse_cache_quote(record: &CacheRecord) -> Option<MarketQuote> {
     let fetched_at = parse_fetched_at(record)?;
     let unit_price = record.unit_price.parse::<Decimal>().ok()?;
@@ -186,16 +662,28 @@ mod tests {
     use chrono::{TimeZone, Utc};
 
     use super::*;
-    use crate::model::{MarketKind, MarketRequest};
-    use crate::providers::ProviderError;
+    use crate::model::{MarketKind, MarketRequest, MetalUnit};
 
     struct FakeProviders {
         fx_result: Result<MarketQuote, ProviderError>,
         coinbase_result: Result<MarketQuote, ProviderError>,
         kraken_result: Result<MarketQuote, ProviderError>,
+        binance_result: Result<MarketQuote, ProviderError>,
+        coingecko_result: Result<MarketQuote, ProviderError>,
+        metal_result: Result<MarketQuote, ProviderError>,
+        yahoo_result: Result<StockQuote, ProviderError>,
+        stooq_result: Result<StockQuote, ProviderError>,
+        history_result: Result<PriceSeries, ProviderError>,
+        /// `BASE/QUOTE` pairs the FX / every crypto provider rejects as unsupported.
+        fx_unsupported: &'static [&'static str],
+        crypto_unsupported: &'static [&'static str],
         fx_calls: Cell<usize>,
         coinbase_calls: Cell<usize>,
         kraken_calls: Cell<usize>,
+        binance_calls: Cell<usize>,
+        yahoo_calls: Cell<usize>,
+        stooq_calls: Cell<usize>,
+        history_calls: Cell<usize>,
     }
 
     impl FakeProviders {
@@ -208,43 +696,157 @@ mod tests {
                 fx_result: Ok(MarketQuote::new("frankfurter", Decimal::new(321, 1), now)),
                 coinbase_result: Ok(MarketQuote::new("coinbase", Decimal::new(670001, 1), now)),
                 kraken_result: Ok(MarketQuote::new("kraken", Decimal::new(669999, 1), now)),
+                binance_result: Err(ProviderError::Transport(
+                    "binance disabled in tests".to_string(),
+                )),
+                coingecko_result: Ok(MarketQuote::new("coingecko", Decimal::new(67000, 0), now)),
+                metal_result: Ok(MarketQuote::new("gold-api", Decimal::new(24000, 1), now)),
+                yahoo_result: Ok(StockQuote {
+                    provider: "yahoo".to_string(),
+                    price: Decimal::new(22752, 2),
+                    currency: Some("USD".to_string()),
+                    previous_close: Some(Decimal::new(22500, 2)),
+                    fetched_at: now,
+                }),
+                stooq_result: Ok(StockQuote {
+                    provider: "stooq".to_string(),
+                    price: Decimal::new(22750, 2),
+                    currency: Some("USD".to_string()),
+                    previous_close: None,
+                    fetched_at: now,
+                }),
+                history_result: Ok(PriceSeries {
+                    provider: "frankfurter".to_string(),
+                    points: vec![
+                        PricePoint {
+                            date: chrono::NaiveDate::from_ymd_opt(2026, 2, 9).expect("date"),
+                            price: Decimal::new(320, 1),
+                        },
+                        PricePoint {
+                            date: chrono::NaiveDate::from_ymd_opt(2026, 2, 10).expect("date"),
+                            price: Decimal::new(328, 1),
+                        },
+                    ],
+                    fetched_at: now,
+                }),
+                fx_unsupported: &[],
+                crypto_unsupported: &[],
                 fx_calls: Cell::new(0),
                 coinbase_calls: Cell::new(0),
                 kraken_calls: Cell::new(0),
+                binance_calls: Cell::new(0),
+                yahoo_calls: Cell::new(0),
+                stooq_calls: Cell::new(0),
+                history_calls: Cell::new(0),
             }
         }
     }
 
+    fn reject_unsupported(
+        unsupported: &[&str],
+        base: &str,
+        quote: &str,
+    ) -> Result<(), ProviderError> {
+        let pair = format!("{base}/{quote}");
+        match unsupported.contains(&pair.as_str()) {
+            true => Err(ProviderError::UnsupportedPair(pair)),
+            false => Ok(()),
+        }
+    }
+
     impl ProviderApi for FakeProviders {
-        fn fetch_fx_rate(&self, _base: &str, _quote: &str) -> Result<MarketQuote, ProviderError> {
+        fn fetch_fx_rate(&self, base: &str, quote: &str) -> Result<MarketQuote, ProviderError> {
             self.fx_calls.set(self.fx_calls.get() + 1);
+            reject_unsupported(self.fx_unsupported, base, quote)?;
             self.fx_result.clone()
         }
 
         fn fetch_crypto_coinbase(
             &self,
-            _base: &str,
-            _quote: &str,
+            base: &str,
+            quote: &str,
         ) -> Result<MarketQuote, ProviderError> {
             self.coinbase_calls.set(self.coinbase_calls.get() + 1);
+            reject_unsupported(self.crypto_unsupported, base, quote)?;
             self.coinbase_result.clone()
         }
 
         fn fetch_crypto_kraken(
             &self,
-            _base: &str,
-            _quote: &str,
+            base: &str,
+            quote: &str,
         ) -> Result<MarketQuote, ProviderError> {
             self.kraken_calls.set(self.kraken_calls.get() + 1);
+            reject_unsupported(self.crypto_unsupported, base, quote)?;
             self.kraken_result.clone()
         }
+
+        fn fetch_crypto_binance(
+            &self,
+            base: &str,
+            quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            self.binance_calls.set(self.binance_calls.get() + 1);
+            reject_unsupported(self.crypto_unsupported, base, quote)?;
+            self.binance_result.clone()
+        }
+
+        fn fetch_crypto_coingecko(
+            &self,
+            base: &str,
+            quote: &str,
+        ) -> Result<MarketQuote, ProviderError> {
+            reject_unsupported(self.crypto_unsupported, base, quote)?;
+            self.coingecko_result.clone()
+        }
+
+        fn fetch_metal_spot(&self, _metal: &str) -> Result<MarketQuote, ProviderError> {
+            self.metal_result.clone()
+        }
+
+        fn fetch_stock_yahoo(&self, _symbol: &str) -> Result<StockQuote, ProviderError> {
+            self.yahoo_calls.set(self.yahoo_calls.get() + 1);
+            self.yahoo_result.clone()
+        }
+
+        fn fetch_stock_stooq(&self, _symbol: &str) -> Result<StockQuote, ProviderError> {
+            self.stooq_calls.set(self.stooq_calls.get() + 1);
+            self.stooq_result.clone()
+        }
+
+        fn fetch_fx_history(
+            &self,
+            _base: &str,
+            _quote: &str,
+            start: chrono::NaiveDate,
+            end: chrono::NaiveDate,
+        ) -> Result<PriceSeries, ProviderError> {
+            assert!(start < end);
+            self.history_calls.set(self.history_calls.get() + 1);
+            self.history_result.clone()
+        }
+
+        fn fetch_crypto_history(
+            &self,
+            base: &str,
+            quote: &str,
+            start: chrono::NaiveDate,
+            end: chrono::NaiveDate,
+        ) -> Result<PriceSeries, ProviderError> {
+            self.fetch_fx_history(base, quote, start, end)
+        }
     }
 
     fn fixture_config(cache_dir: PathBuf) -> RuntimeConfig {
         RuntimeConfig {
-            cache_dir,
+            cache_dir: cache_dir.clone(),
+            data_dir: cache_dir,
             fx_cache_ttl_secs: crate::config::FX_TTL_SECS,
             crypto_cache_ttl_secs: crate::config::CRYPTO_TTL_SECS,
+            stock_cache_ttl_secs: crate::config::STOCK_TTL_SECS,
+            metal_cache_ttl_secs: crate::config::METAL_TTL_SECS,
+            crypto_providers: crate::config::DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+            fees: crate::config::FeeSchedule::default(),
         }
     }
 
@@ -274,6 +876,87 @@ mod tests {
         assert_eq!(output.provider, "kraken");
         assert_eq!(providers.coinbase_calls.get(), 1);
         assert_eq!(providers.kraken_calls.get(), 1);
+        assert_eq!(providers.binance_calls.get(), 0);
+        assert_eq!(
+            output.provider_failures,
+            vec![ProviderFailure {
+                provider: "coinbase".to_string(),
+                error: "transport error: timeout".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn service_crypto_follows_configured_provider_order() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = RuntimeConfig {
+            crypto_providers: vec![
+                CryptoProvider::Binance,
+                CryptoProvider::Coingecko,
+                CryptoProvider::Coinbase,
+            ],
+            ..fixture_config(dir.path().to_path_buf())
+        };
+        let providers = FakeProviders::new();
+        let request = MarketRequest::new(MarketKind::Crypto, "BTC", "USD", "1").expect("request");
+
+        let output = resolve_market(&config, &providers, fixed_now, &request).expect("must pass");
+
+        assert_eq!(output.provider, "coingecko");
+        assert_eq!(output.unit_price, "67000");
+        assert_eq!(providers.binance_calls.get(), 1);
+        assert_eq!(providers.coinbase_calls.get(), 0);
+        assert_eq!(output.provider_failures.len(), 1);
+        assert_eq!(output.provider_failures[0].provider, "binance");
+    }
+
+    #[test]
+    fn service_metal_converts_usd_spot_and_caches_per_ounce() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = fixture_config(dir.path().to_path_buf());
+        let providers = FakeProviders::new();
+        let request = MarketRequest::new(MarketKind::Metal, "xau", "TWD", "10")
+            .expect("request")
+            .with_unit(MetalUnit::Gram);
+
+        let output = resolve_market(&config, &providers, fixed_now, &request).expect("must pass");
+
+        assert_eq!(output.provider, "gold-api+frankfurter");
+        assert_eq!(output.unit, Some(MetalUnit::Gram));
+        assert_eq!(output.unit_price, "2476.89351565");
+        assert_eq!(output.converted, "24768.9351565");
+        assert_eq!(providers.fx_calls.get(), 1);
+
+        let path = cache_path(&config, request.kind, &request.base, &request.quote);
+        let cached = read_cache(&path).expect("read").expect("cache exists");
+        assert_eq!(cached.unit_price, "77040");
+
+        let ounces = MarketRequest::new(MarketKind::Metal, "XAU", "TWD", "1").expect("request");
+        let output = resolve_market(&config, &providers, fixed_now, &ounces).expect("cached");
+        assert_eq!(output.cache.status, CacheStatus::CacheFresh);
+        assert_eq!(output.unit_price, "77040");
+    }
+
+    #[test]
+    fn service_metal_reports_provider_failure() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = FakeProviders {
+            metal_result: Err(ProviderError::Transport("timeout".to_string())),
+            ..FakeProviders::new()
+        };
+        let request = MarketRequest::new(MarketKind::Metal, "XAG", "USD", "1").expect("request");
+
+        let err = resolve_market(
+            &fixture_config(dir.path().to_path_buf()),
+            &providers,
+            fixed_now,
+            &request,
+        )
+        .expect_err("must fail");
+
+        assert_eq!(err.kind, crate::error::ErrorKind::Runtime);
+        assert!(err.message.contains("failed to fetch metal spot price"));
+        assert_eq!(providers.fx_calls.get(), 0);
     }
 
     #[test]
@@ -332,12 +1015,21 @@ mod tests {
         let providers = FakeProviders {
             coinbase_result: Err(ProviderError::Transport("timeout".to_string())),
             kraken_result: Err(ProviderError::Transport("unavailable".to_string())),
+            coingecko_result: Err(ProviderError::Transport("unavailable".to_string())),
             ..FakeProviders::new()
         };
         let output = resolve_market(&config, &providers, fixed_now, &request).expect("fallback");
 
         assert_eq!(output.cache.status, CacheStatus::CacheStaleFallback);
         assert_eq!(output.provider, "coinbase");
+        assert_eq!(
+            output
+                .provider_failures
+                .iter()
+                .map(|failure| failure.provider.as_str())
+                .collect::<Vec<_>>(),
+            vec!["coinbase", "kraken", "binance", "coingecko"]
+        );
     }
 
     #[test]
@@ -351,12 +1043,84 @@ mod tests {
                 status: 503,
                 message: "service unavailable".to_string(),
             }),
+            coingecko_result: Err(ProviderError::Transport("timeout".to_string())),
             ..FakeProviders::new()
         };
 
         let err = resolve_market(&config, &providers, fixed_now, &request).expect_err("must fail");
         assert_eq!(err.kind, crate::error::ErrorKind::Runtime);
         assert!(err.message.contains("provider trace"));
+        assert!(err.message.contains("binance: transport error"));
+    }
+
+    #[test]
+    fn service_crosses_unsupported_pair_through_usd_and_caches_each_leg() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = fixture_config(dir.path().to_path_buf());
+        let providers = FakeProviders {
+            fx_unsupported: &["BTC/USD"],
+            crypto_unsupported: &["TWD/BTC"],
+            ..FakeProviders::new()
+        };
+        let request =
+            MarketRequest::new(MarketKind::Crypto, "TWD", "BTC", "1000").expect("request");
+
+        let output = resolve_market(&config, &providers, fixed_now, &request).expect("cross rate");
+
+        assert_eq!(output.unit_price, "0.000479103762532");
+        assert_eq!(output.converted, "0.47910376");
+        assert_eq!(output.provider, "frankfurter+coinbase");
+        assert_eq!(output.cache.status, CacheStatus::Live);
+        assert_eq!(output.cache.key, "fx-twd-usd+crypto-btc-usd");
+        let derivation = output.derivation.expect("derivation");
+        assert_eq!(derivation.pivot, "USD");
+        assert_eq!(
+            derivation
+                .legs
+                .iter()
+                .map(|leg| (leg.kind, leg.base.as_str(), leg.unit_price.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (MarketKind::Fx, "TWD", "32.1"),
+                (MarketKind::Crypto, "BTC", "67000.1"),
+            ]
+        );
+        assert!(
+            read_cache(&cache_path(&config, MarketKind::Crypto, "TWD", "BTC"))
+                .expect("read")
+                .is_none()
+        );
+
+        // Both legs are cached on their own, so the cross rate survives a full provider outage.
+        let offline = FakeProviders {
+            fx_result: Err(ProviderError::Transport("offline".to_string())),
+            coinbase_result: Err(ProviderError::Transport("offline".to_string())),
+            kraken_result: Err(ProviderError::Transport("offline".to_string())),
+            coingecko_result: Err(ProviderError::Transport("offline".to_string())),
+            ..FakeProviders::new()
+        };
+        let cached = resolve_market(&config, &offline, fixed_now, &request).expect("cached legs");
+        assert_eq!(cached.unit_price, "0.000479103762532");
+        assert_eq!(cached.cache.status, CacheStatus::CacheFresh);
+        assert_eq!(offline.coinbase_calls.get(), 1);
+    }
+
+    #[test]
+    fn service_reports_direct_and_cross_failures_together() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = fixture_config(dir.path().to_path_buf());
+        let providers = FakeProviders {
+            fx_result: Err(ProviderError::Transport("offline".to_string())),
+            crypto_unsupported: &["TWD/USD", "JPY/USD"],
+            ..FakeProviders::new()
+        };
+        let request = MarketRequest::new(MarketKind::Fx, "TWD", "JPY", "1").expect("request");
+
+        let err = resolve_market(&config, &providers, fixed_now, &request).expect_err("must fail");
+
+        assert!(err.message.starts_with("failed to fetch fx rate"));
+        assert!(err.message.contains("cross rate via USD failed"));
+        assert!(err.message.contains("failed to resolve quote for TWD/USD"));
     }
 
     #[test]
@@ -387,4 +1151,122 @@ mod tests {
         assert_eq!(output.provider, "frankfurter");
         assert_eq!(output.unit_price, "149.5");
     }
+
+    #[test]
+    fn service_stock_falls_back_to_stooq_and_caches_the_quote() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = fixture_config(dir.path().to_path_buf());
+        let providers = FakeProviders {
+            yahoo_result: Err(ProviderError::Http {
+                status: 429,
+                message: "rate limited".to_string(),
+            }),
+            ..FakeProviders::new()
+        };
+
+        let output = resolve_stock(&config, &providers, fixed_now, "AAPL").expect("must pass");
+        assert_eq!(output.provider, "stooq");
+        assert_eq!(output.price, "227.5");
+        assert_eq!(output.change, None);
+        assert_eq!(output.cache.status, CacheStatus::Live);
+        assert_eq!(output.cache.key, "stock-aapl");
+
+        let cached =
+            resolve_stock(&config, &FakeProviders::new(), fixed_now, "AAPL").expect("cache hit");
+        assert_eq!(cached.cache.status, CacheStatus::CacheFresh);
+        assert_eq!(cached.provider, "stooq");
+    }
+
+    #[test]
+    fn service_stock_unknown_everywhere_is_a_user_error() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = FakeProviders {
+            yahoo_result: Err(ProviderError::UnknownSymbol("yahoo: NOPE".to_string())),
+            stooq_result: Err(ProviderError::UnknownSymbol("stooq: NOPE.US".to_string())),
+            ..FakeProviders::new()
+        };
+
+        let err = resolve_stock(
+            &fixture_config(dir.path().to_path_buf()),
+            &providers,
+            fixed_now,
+            "NOPE",
+        )
+        .expect_err("must fail");
+        assert_eq!(err.kind, crate::error::ErrorKind::User);
+        assert_eq!(err.message, "unknown stock symbol: NOPE");
+        assert_eq!(providers.stooq_calls.get(), 1);
+    }
+
+    #[test]
+    fn service_stock_uses_stale_cache_when_providers_fail() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = fixture_config(dir.path().to_path_buf());
+        write_stock_cache(
+            &stock_cache_path(&config, "AAPL"),
+            &StockCacheRecord {
+                symbol: "AAPL".to_string(),
+                provider: "yahoo".to_string(),
+                price: "220".to_string(),
+                currency: Some("USD".to_string()),
+                previous_close: Some("200".to_string()),
+                fetched_at: "2026-02-10T10:00:00Z".to_string(),
+            },
+        )
+        .expect("write");
+        let providers = FakeProviders {
+            yahoo_result: Err(ProviderError::Transport("timeout".to_string())),
+            stooq_result: Err(ProviderError::UnknownSymbol("stooq: AAPL.US".to_string())),
+            ..FakeProviders::new()
+        };
+
+        let output = resolve_stock(&config, &providers, fixed_now, "AAPL").expect("fallback");
+        assert_eq!(output.cache.status, CacheStatus::CacheStaleFallback);
+        assert_eq!(output.change_pct.as_deref(), Some("10"));
+        assert_eq!(providers.yahoo_calls.get(), 1);
+    }
+
+    #[test]
+    fn service_history_caches_series_and_serves_stale_on_failure() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = fixture_config(dir.path().to_path_buf());
+        let request = HistoryRequest::new(
+            MarketKind::Fx,
+            "USD",
+            "TWD",
+            crate::model::HistoryRange::parse("30d").expect("range"),
+        )
+        .expect("request");
+        let providers = FakeProviders::new();
+
+        let live = resolve_history(&config, &providers, fixed_now, &request).expect("live");
+        assert_eq!(live.cache.status, CacheStatus::Live);
+        assert_eq!(live.cache.key, "history-fx-usd-twd-30d");
+        assert_eq!(live.latest, "32.8");
+        assert_eq!(live.change_pct.as_deref(), Some("2.5"));
+
+        let cached = resolve_history(&config, &providers, fixed_now, &request).expect("cached");
+        assert_eq!(cached.cache.status, CacheStatus::CacheFresh);
+        assert_eq!(cached.points, live.points);
+        assert_eq!(providers.history_calls.get(), 1);
+
+        let failing = FakeProviders {
+            history_result: Err(ProviderError::Transport("offline".to_string())),
+            ..FakeProviders::new()
+        };
+        let later = || fixed_now() + chrono::Duration::days(2);
+        let stale = resolve_history(&config, &failing, later, &request).expect("stale");
+        assert_eq!(stale.cache.status, CacheStatus::CacheStaleFallback);
+
+        let empty_dir = tempfile::tempdir().expect("tempdir");
+        let err = resolve_history(
+            &fixture_config(empty_dir.path().to_path_buf()),
+            &failing,
+            fixed_now,
+            &request,
+        )
+        .expect_err("must fail");
+        assert_eq!(err.kind, crate::error::ErrorKind::Runtime);
+        assert!(err.message.starts_with("failed to fetch price history"));
+    }
 }
@@ -0,0 +1,45 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::cache::write_atomic;
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+
+/// Path of a persistent file under `<data_dir>/market-cli/`.
+pub fn data_file_path(config: &RuntimeConfig, file_name: &str) -> PathBuf {
+    config.data_dir.join("market-cli").join(file_name)
+}
+
+/// A missing file is the default value. An unreadable one is an error rather than a silent reset, so a later save
+/// never overwrites entries the user still expects.
+pub fn load_data_file<T>(path: &Path, label: &str) -> Result<T, AppError>
+where
+    T: DeserializeOwned + Default,
+{
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let payload = fs::read_to_string(path).map_err(|error| {
+        AppError::runtime(format!(
+            "failed to read {label} {}: {error}",
+            path.display()
+        ))
+    })?;
+    serde_json::from_str(&payload)
+        .map_err(|error| AppError::runtime(format!("invalid {label} {}: {error}", path.display())))
+}
+
+pub fn save_data_file<T: Serialize>(path: &Path, label: &str, value: &T) -> Result<(), AppError> {
+    let payload = serde_json::to_vec_pretty(value)
+        .map_err(|error| AppError::runtime(format!("failed to serialize {label}: {error}")))?;
+    write_atomic(path, &payload).map_err(|error| {
+        AppError::runtime(format!(
+            "failed to write {label} {}: {error}",
+            path.display()
+        ))
+    })
+}
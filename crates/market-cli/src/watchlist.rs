@@ -0,0 +1,335 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::model::{
+    CacheStatus, ValidationError, decimal_to_string, normalize_crypto_symbol, parse_amount,
+};
+use crate::providers::ProviderApi;
+use crate::service::resolve_symbol_output;
+use crate::store::{data_file_path, load_data_file, save_data_file};
+
+const WATCHLIST_FILE_NAME: &str = "watchlist.json";
+const PAIR_EXPECTED_FORMAT: &str = "BASE/QUOTE pair of 2-10 character symbols";
+
+/// One watched pair. `amount` is the held quantity of `base`; entries without it are price-only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub base: String,
+    pub quote: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+}
+
+impl WatchEntry {
+    pub fn new(pair: &str, amount: Option<&str>) -> Result<Self, ValidationError> {
+        let (base, quote) = parse_pair(pair)?;
+        let amount = amount
+            .map(parse_amount)
+            .transpose()?
+            .map(|amount| decimal_to_string(&amount));
+        Ok(Self {
+            base,
+            quote,
+            amount,
+        })
+    }
+
+    pub fn pair(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+}
+
+pub fn parse_pair(raw: &str) -> Result<(String, String), ValidationError> {
+    let invalid = || ValidationError::InvalidSymbol {
+        field: "pair",
+        value: raw.to_string(),
+        expected: PAIR_EXPECTED_FORMAT,
+    };
+    let (base, quote) = raw.trim().split_once('/').ok_or_else(invalid)?;
+    let base = normalize_crypto_symbol(base, "pair").map_err(|_| invalid())?;
+    let quote = normalize_crypto_symbol(quote, "pair").map_err(|_| invalid())?;
+    Ok((base, quote))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watchlist {
+    #[serde(default)]
+    pub entries: Vec<WatchEntry>,
+}
+
+impl Watchlist {
+    /// Adds `entry`, or replaces the entry for the same pair in place. Returns whether an entry was replaced.
+    pub fn upsert(&mut self, entry: WatchEntry) -> bool {
+        match self
+            .entries
+            .iter_mut()
+            .find(|existing| existing.base == entry.base && existing.quote == entry.quote)
+        {
+            Some(existing) => {
+                *existing = entry;
+                true
+            }
+            None => {
+                self.entries.push(entry);
+                false
+            }
+        }
+    }
+
+    pub fn remove(&mut self, base: &str, quote: &str) -> bool {
+        let before = self.entries.len();
+        self.entries
+            .retain(|entry| entry.base != base || entry.quote != quote);
+        self.entries.len() != before
+    }
+}
+
+pub fn watchlist_path(config: &RuntimeConfig) -> PathBuf {
+    data_file_path(config, WATCHLIST_FILE_NAME)
+}
+
+pub fn load_watchlist(path: &Path) -> Result<Watchlist, AppError> {
+    load_data_file(path, "watchlist")
+}
+
+pub fn save_watchlist(path: &Path, watchlist: &Watchlist) -> Result<(), AppError> {
+    save_data_file(path, "watchlist", watchlist)
+}
+
+/// Result of `watch add` / `watch remove`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchUpdateOutput {
+    pub action: &'static str,
+    pub entry: WatchEntry,
+    pub replaced: bool,
+    pub entries: usize,
+}
+
+/// One watchlist entry with its live quote. Quote fields are unset and `error` is set when resolution failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchQuote {
+    #[serde(flatten)]
+    pub entry: WatchEntry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_status: Option<CacheStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Portfolio value of every holding quoted in one currency. Holdings whose quote failed are counted in
+/// `unavailable` and left out of `value`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PortfolioTotal {
+    pub quote: String,
+    pub value: String,
+    pub holdings: usize,
+    pub unavailable: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WatchlistOutput {
+    pub entries: Vec<WatchQuote>,
+    pub totals: Vec<PortfolioTotal>,
+}
+
+/// Quotes every entry concurrently (each pair goes through the regular per-pair quote cache) and totals holdings
+/// per quote currency in first-seen order.
+pub fn quote_watchlist<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    watchlist: &Watchlist,
+) -> WatchlistOutput
+where
+    P: ProviderApi + Clone + Send,
+    N: Fn() -> DateTime<Utc> + Copy + Send,
+{
+    let entries = std::thread::scope(|scope| {
+        let handles = watchlist
+            .entries
+            .iter()
+            .map(|entry| {
+                let providers = providers.clone();
+                scope.spawn(move || quote_entry(config, &providers, now_fn, entry))
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("watchlist worker panicked"))
+            .collect::<Vec<_>>()
+    });
+    let totals = portfolio_totals(&entries);
+
+    WatchlistOutput { entries, totals }
+}
+
+fn quote_entry<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    entry: &WatchEntry,
+) -> WatchQuote
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    match resolve_symbol_output(config, providers, now_fn, &entry.base, &entry.quote) {
+        Ok(output) => {
+            let value = entry
+                .amount
+                .as_deref()
+                .and_then(|amount| amount.parse::<Decimal>().ok())
+                .zip(output.unit_price.parse::<Decimal>().ok())
+                .map(|(amount, price)| decimal_to_string(&(amount * price).round_dp(8)));
+            WatchQuote {
+                entry: entry.clone(),
+                price: Some(output.unit_price),
+                value,
+                provider: Some(output.provider),
+                cache_status: Some(output.cache.status),
+                error: None,
+            }
+        }
+        Err(error) => WatchQuote {
+            entry: entry.clone(),
+            price: None,
+            value: None,
+            provider: None,
+            cache_status: None,
+            error: Some(error.message),
+        },
+    }
+}
+
+fn portfolio_totals(quotes: &[WatchQuote]) -> Vec<PortfolioTotal> {
+    let mut totals: Vec<(String, Decimal, usize, usize)> = Vec::new();
+
+    for quote in quotes.iter().filter(|quote| quote.entry.amount.is_some()) {
+        let index = match totals
+            .iter()
+            .position(|(currency, ..)| *currency == quote.entry.quote)
+        {
+            Some(index) => index,
+            None => {
+                totals.push((quote.entry.quote.clone(), Decimal::ZERO, 0, 0));
+                totals.len() - 1
+            }
+        };
+        let total = &mut totals[index];
+        total.2 += 1;
+        match quote
+            .value
+            .as_deref()
+            .and_then(|value| value.parse::<Decimal>().ok())
+        {
+            Some(value) => total.1 += value,
+            None => total.3 += 1,
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(quote, value, holdings, unavailable)| PortfolioTotal {
+            quote,
+            value: decimal_to_string(&value),
+            holdings,
+            unavailable,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn watchlist_entries_validate_pairs_and_amounts() {
+        let entry = WatchEntry::new(" btc/usd ", Some("0.50")).expect("entry");
+        assert_eq!(entry.pair(), "BTC/USD");
+        assert_eq!(entry.amount.as_deref(), Some("0.5"));
+        assert_eq!(
+            WatchEntry::new("JPY/TWD", None).expect("entry").amount,
+            None
+        );
+
+        assert!(WatchEntry::new("BTC", None).is_err());
+        assert!(WatchEntry::new("BTC/USD/EUR", None).is_err());
+        assert!(WatchEntry::new("BTC/USD", Some("0")).is_err());
+    }
+
+    #[test]
+    fn watchlist_upsert_replaces_in_place_and_persists() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("market-cli").join(WATCHLIST_FILE_NAME);
+        assert_eq!(
+            load_watchlist(&path).expect("missing"),
+            Watchlist::default()
+        );
+
+        let mut watchlist = Watchlist::default();
+        assert!(!watchlist.upsert(WatchEntry::new("BTC/USD", Some("0.5")).expect("entry")));
+        assert!(!watchlist.upsert(WatchEntry::new("ETH/USD", None).expect("entry")));
+        assert!(watchlist.upsert(WatchEntry::new("BTC/USD", Some("1")).expect("entry")));
+        assert_eq!(watchlist.entries[0].amount.as_deref(), Some("1"));
+        assert!(watchlist.remove("ETH", "USD"));
+        assert!(!watchlist.remove("ETH", "USD"));
+
+        save_watchlist(&path, &watchlist).expect("save");
+        assert_eq!(load_watchlist(&path).expect("load"), watchlist);
+
+        fs::write(&path, "{not-json").expect("corrupt");
+        assert!(load_watchlist(&path).is_err());
+    }
+
+    #[test]
+    fn watchlist_totals_group_holdings_by_quote_currency() {
+        let quote = |pair: &str, amount: Option<&str>, value: Option<&str>| WatchQuote {
+            entry: WatchEntry::new(pair, amount).expect("entry"),
+            price: None,
+            value: value.map(ToOwned::to_owned),
+            provider: None,
+            cache_status: None,
+            error: None,
+        };
+
+        let totals = portfolio_totals(&[
+            quote("BTC/USD", Some("0.5"), Some("34097")),
+            quote("ETH/USD", Some("2"), None),
+            quote("JPY/TWD", None, None),
+            quote("ETH/JPY", Some("1"), Some("300000")),
+            quote("SOL/USD", Some("10"), Some("1500.5")),
+        ]);
+
+        assert_eq!(
+            totals,
+            vec![
+                PortfolioTotal {
+                    quote: "USD".to_string(),
+                    value: "35597.5".to_string(),
+                    holdings: 3,
+                    unavailable: 1,
+                },
+                PortfolioTotal {
+                    quote: "JPY".to_string(),
+                    value: "300000".to_string(),
+                    holdings: 1,
+                    unavailable: 0,
+                },
+            ]
+        );
+    }
+}
@@ -4,8 +4,9 @@ use std::process::{Command, Output};
 use chrono::{TimeZone, Utc};
 use market_cli::cache::{CacheRecord, cache_path, write_cache};
 use market_cli::config::{
-    CRYPTO_TTL_SECS, FX_TTL_SECS, MARKET_CACHE_DIR_ENV, MARKET_CRYPTO_CACHE_TTL_ENV,
-    MARKET_FX_CACHE_TTL_ENV, RuntimeConfig,
+    CRYPTO_TTL_SECS, DEFAULT_CRYPTO_PROVIDERS, FX_TTL_SECS, FeeSchedule, MARKET_CACHE_DIR_ENV,
+    MARKET_CRYPTO_CACHE_TTL_ENV, MARKET_FX_CACHE_TTL_ENV, METAL_TTL_SECS, RuntimeConfig,
+    STOCK_TTL_SECS,
 };
 use market_cli::model::{
     CacheMetadata, CacheStatus, MarketKind, MarketQuote, MarketRequest, build_output,
@@ -28,8 +29,13 @@ fn run_cli(args: &[&str], envs: &[(&str, &str)]) -> Output {
 fn config_icon_cache_dir_is_versioned() {
     let config = RuntimeConfig {
         cache_dir: PathBuf::from("/tmp/market-cache"),
+        data_dir: PathBuf::from("/tmp/market-cache"),
         fx_cache_ttl_secs: FX_TTL_SECS,
         crypto_cache_ttl_secs: CRYPTO_TTL_SECS,
+        stock_cache_ttl_secs: STOCK_TTL_SECS,
+        metal_cache_ttl_secs: METAL_TTL_SECS,
+        crypto_providers: DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+        fees: FeeSchedule::default(),
     };
 
     assert_eq!(
@@ -936,8 +942,13 @@ fn seed_cache_record(
 ) {
     let config = RuntimeConfig {
         cache_dir: cache_dir.to_path_buf(),
+        data_dir: cache_dir.to_path_buf(),
         fx_cache_ttl_secs: FX_TTL_SECS,
         crypto_cache_ttl_secs: CRYPTO_TTL_SECS,
+        stock_cache_ttl_secs: STOCK_TTL_SECS,
+        metal_cache_ttl_secs: METAL_TTL_SECS,
+        crypto_providers: DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+        fees: FeeSchedule::default(),
     };
     let path = cache_path(&config, kind, base, quote);
     let record = CacheRecord {
@@ -954,8 +965,13 @@ fn seed_cache_record(
 fn seed_icon_cache(cache_dir: &Path, filename: &str, bytes: &[u8]) {
     let config = RuntimeConfig {
         cache_dir: cache_dir.to_path_buf(),
+        data_dir: cache_dir.to_path_buf(),
         fx_cache_ttl_secs: FX_TTL_SECS,
         crypto_cache_ttl_secs: CRYPTO_TTL_SECS,
+        stock_cache_ttl_secs: STOCK_TTL_SECS,
+        metal_cache_ttl_secs: METAL_TTL_SECS,
+        crypto_providers: DEFAULT_CRYPTO_PROVIDERS.to_vec(),
+        fees: FeeSchedule::default(),
     };
     let path = config.icon_cache_dir().join(filename);
     let parent = path.parent().expect("icon cache path parent");
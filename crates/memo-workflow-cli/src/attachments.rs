@@ -0,0 +1,175 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::{AppError, AttachmentResult, expand_home_path};
+
+pub const ATTACHMENT_KIND_FILE: &str = "file";
+pub const ATTACHMENT_KIND_URL: &str = "url";
+
+/// SQL expression counting attachments of the `inbox_items` row aliased as `i`.
+pub(crate) const ATTACHMENT_COUNT_SQL: &str =
+    "(select count(*) from workflow_item_attachments a where a.item_id = i.item_id)";
+
+/// Resolve a raw attachment location into `(kind, canonical location)`.
+///
+/// `scheme://...` values other than `file://` are stored verbatim as URLs. Everything else is treated
+/// as a local path: `~` is expanded, the path must exist, and it is stored canonicalized.
+pub fn normalize_location(raw: &str) -> Result<(&'static str, String), AppError> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::User(
+            "attachment location must be non-empty".to_string(),
+        ));
+    }
+
+    let path = match url_scheme(trimmed) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("file") => &trimmed[scheme.len() + 3..],
+        Some(scheme) => {
+            let rest = &trimmed[scheme.len() + 3..];
+            if rest.is_empty() || trimmed.chars().any(char::is_whitespace) {
+                return Err(AppError::User(format!("invalid attachment url: {trimmed}")));
+            }
+            return Ok((ATTACHMENT_KIND_URL, trimmed.to_string()));
+        }
+        None => trimmed,
+    };
+
+    let expanded = expand_home_path(path, env::var("HOME").ok().as_deref());
+    let canonical = fs::canonicalize(&expanded)
+        .map_err(|_| AppError::User(format!("attachment path does not exist: {expanded}")))?;
+    Ok((
+        ATTACHMENT_KIND_FILE,
+        canonical.to_string_lossy().into_owned(),
+    ))
+}
+
+/// `scheme` of a `scheme://rest` location (RFC 3986 scheme characters, letter first).
+fn url_scheme(raw: &str) -> Option<&str> {
+    let (scheme, _) = raw.split_once("://")?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'));
+    valid.then_some(scheme)
+}
+
+/// Short label for menu rows: the file name for paths, the full location for URLs.
+pub(crate) fn display_name(attachment: &AttachmentResult) -> String {
+    if attachment.kind == ATTACHMENT_KIND_FILE
+        && let Some(name) = Path::new(&attachment.location).file_name()
+    {
+        return name.to_string_lossy().into_owned();
+    }
+    attachment.location.clone()
+}
+
+/// Attach one location to an item, returning the attachment id and whether a row was inserted.
+pub(crate) fn add_attachment(
+    conn: &Connection,
+    item_id: i64,
+    kind: &str,
+    location: &str,
+) -> Result<(i64, bool), MemoCliError> {
+    let inserted = conn
+        .execute(
+            "insert or ignore into workflow_item_attachments(item_id, kind, location)
+             values (?1, ?2, ?3)",
+            (item_id, kind, location),
+        )
+        .map_err(MemoCliError::db_write)?;
+    let attachment_id = conn
+        .query_row(
+            "select attachment_id from workflow_item_attachments
+             where item_id = ?1 and location = ?2",
+            (item_id, location),
+            |row| row.get(0),
+        )
+        .map_err(MemoCliError::db_query)?;
+    Ok((attachment_id, inserted > 0))
+}
+
+/// Attachments of one item in the order they were added.
+pub(crate) fn list_attachments(
+    conn: &Connection,
+    item_id: i64,
+) -> Result<Vec<AttachmentResult>, MemoCliError> {
+    let mut stmt = conn
+        .prepare(
+            "select attachment_id, item_id, kind, location, created_at
+            from workflow_item_attachments
+            where item_id = ?1
+            order by attachment_id asc",
+        )
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([item_id], map_attachment)
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+pub(crate) fn find_attachment(
+    conn: &Connection,
+    attachment_id: i64,
+) -> Result<Option<(i64, AttachmentResult)>, MemoCliError> {
+    conn.query_row(
+        "select attachment_id, item_id, kind, location, created_at
+        from workflow_item_attachments
+        where attachment_id = ?1",
+        [attachment_id],
+        |row| Ok((row.get::<_, i64>(1)?, map_attachment(row)?)),
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+fn map_attachment(row: &rusqlite::Row<'_>) -> rusqlite::Result<AttachmentResult> {
+    Ok(AttachmentResult {
+        attachment_id: row.get(0)?,
+        item_id: format_item_id(row.get::<_, i64>(1)?),
+        kind: row.get(2)?,
+        location: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn normalize_location_canonicalizes_paths_and_keeps_urls() {
+        let dir = tempdir().expect("temp dir");
+        let file = dir.path().join("notes.txt");
+        fs::write(&file, "hello").expect("write file");
+        let canonical = fs::canonicalize(&file).expect("canonical path");
+
+        let dotted = format!("{}/./notes.txt", dir.path().display());
+        let (kind, location) = normalize_location(&dotted).expect("file path");
+        assert_eq!(kind, ATTACHMENT_KIND_FILE);
+        assert_eq!(location, canonical.to_string_lossy());
+
+        let file_url = format!("file://{}", file.display());
+        assert_eq!(
+            normalize_location(&file_url).expect("file url").1,
+            canonical.to_string_lossy()
+        );
+        assert_eq!(
+            normalize_location(" https://example.com/a?b=1 ").expect("url"),
+            (ATTACHMENT_KIND_URL, "https://example.com/a?b=1".to_string())
+        );
+
+        let missing = dir.path().join("missing.txt");
+        assert!(matches!(
+            normalize_location(&missing.to_string_lossy()),
+            Err(AppError::User(_))
+        ));
+        assert!(normalize_location("https://").is_err());
+        assert!(normalize_location("   ").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use rusqlite::{Connection, OpenFlags};
+
+use crate::AppError;
+
+/// Snapshot directory created next to the memo database.
+pub(crate) const BACKUP_DIR_NAME: &str = "backups";
+/// Automatic snapshots kept per database; older ones are deleted after each new one.
+pub(crate) const AUTO_BACKUP_KEEP: usize = 5;
+const SNAPSHOT_PREFIX: &str = "memo-";
+const SNAPSHOT_SUFFIX: &str = ".db";
+/// Marks automatic snapshots (`memo-<stamp>-auto-<reason>.db`); manual snapshots are never rotated.
+const AUTO_MARKER: &str = "-auto-";
+
+/// `backups/` next to `db_path`.
+pub(crate) fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(BACKUP_DIR_NAME)
+}
+
+/// Write a consistent copy of the open database into `dir` and verify it with `PRAGMA integrity_check`.
+///
+/// `VACUUM INTO` includes committed WAL content, so the snapshot is a single self-contained file. A snapshot
+/// that fails the check is deleted and reported as a runtime error.
+pub(crate) fn create_snapshot(
+    conn: &Connection,
+    dir: &Path,
+    reason: Option<&str>,
+) -> Result<PathBuf, AppError> {
+    fs::create_dir_all(dir).map_err(|error| {
+        AppError::Runtime(format!(
+            "failed to create backup dir {}: {error}",
+            dir.display()
+        ))
+    })?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let label = reason.map_or(String::new(), |reason| format!("{AUTO_MARKER}{reason}"));
+    let mut path = dir.join(format!("{SNAPSHOT_PREFIX}{stamp}{label}{SNAPSHOT_SUFFIX}"));
+    let mut attempt = 2;
+    while path.exists() {
+        path = dir.join(format!(
+            "{SNAPSHOT_PREFIX}{stamp}{label}-{attempt}{SNAPSHOT_SUFFIX}"
+        ));
+        attempt += 1;
+    }
+
+    conn.execute("vacuum into ?1", [path.to_string_lossy()])
+        .map_err(|error| AppError::Runtime(format!("database backup failed: {error}")))?;
+    if let Some(problem) = integrity_problem(&path)? {
+        let _ = fs::remove_file(&path);
+        return Err(AppError::Runtime(format!(
+            "backup failed integrity check: {problem}"
+        )));
+    }
+    Ok(path)
+}
+
+/// First `PRAGMA integrity_check` finding for the database at `path`, or `None` when it reports `ok`.
+pub(crate) fn integrity_problem(path: &Path) -> Result<Option<String>, AppError> {
+    let conn =
+        Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|error| {
+            AppError::Runtime(format!("failed to open {}: {error}", path.display()))
+        })?;
+    let finding: String = conn
+        .query_row("pragma integrity_check", [], |row| row.get(0))
+        .map_err(|error| {
+            AppError::Runtime(format!(
+                "integrity check failed for {}: {error}",
+                path.display()
+            ))
+        })?;
+    Ok((finding != "ok").then_some(finding))
+}
+
+/// Whether the database at `path` holds the memo schema (an `inbox_items` table).
+pub(crate) fn has_memo_schema(path: &Path) -> Result<bool, AppError> {
+    let conn =
+        Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(|error| {
+            AppError::Runtime(format!("failed to open {}: {error}", path.display()))
+        })?;
+    conn.query_row(
+        "select exists(select 1 from sqlite_master where type = 'table' and name = 'inbox_items')",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|error| AppError::User(format!("not a memo database: {} ({error})", path.display())))
+}
+
+/// Snapshots in `dir`, oldest first (by modification time, then name).
+fn snapshot_paths(dir: &Path) -> Vec<(PathBuf, DateTime<Local>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(SNAPSHOT_PREFIX) && name.ends_with(SNAPSHOT_SUFFIX)
+                })
+        })
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+            Some((path, DateTime::<Local>::from(modified)))
+        })
+        .collect::<Vec<_>>();
+    paths.sort_by(|(left, left_at), (right, right_at)| {
+        left_at.cmp(right_at).then_with(|| left.cmp(right))
+    });
+    paths
+}
+
+/// Delete automatic snapshots beyond the newest `keep`, returning the removed paths.
+pub(crate) fn rotate_auto_snapshots(dir: &Path, keep: usize) -> Vec<PathBuf> {
+    let automatic = snapshot_paths(dir)
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| path.to_string_lossy().contains(AUTO_MARKER))
+        .collect::<Vec<_>>();
+    let excess = automatic.len().saturating_sub(keep);
+    automatic
+        .into_iter()
+        .take(excess)
+        .filter(|path| fs::remove_file(path).is_ok())
+        .collect()
+}
+
+/// Newest snapshot in `dir` with its modification time.
+pub(crate) fn latest_snapshot(dir: &Path) -> Option<(PathBuf, DateTime<Local>)> {
+    snapshot_paths(dir).pop()
+}
+
+/// Snapshot path for a `db-restore` argument: a bare file name is looked up in `dir`, anything else is a path.
+pub(crate) fn resolve_snapshot(raw: &str, dir: &Path) -> PathBuf {
+    let path = Path::new(raw);
+    if path.components().count() == 1 && !path.exists() {
+        dir.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Replace the database file at `db_path` with a copy of `snapshot`, dropping its WAL and shared-memory files.
+///
+/// The copy is written next to the database first and renamed over it, so a failed copy leaves the
+/// current database untouched.
+pub(crate) fn replace_database(snapshot: &Path, db_path: &Path) -> Result<(), AppError> {
+    let staging = db_path.with_extension("restore-tmp");
+    fs::copy(snapshot, &staging).map_err(|error| {
+        AppError::Runtime(format!(
+            "failed to copy snapshot {}: {error}",
+            snapshot.display()
+        ))
+    })?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{suffix}", db_path.display()));
+        if sidecar.exists() {
+            fs::remove_file(&sidecar).map_err(|error| {
+                AppError::Runtime(format!("failed to remove {}: {error}", sidecar.display()))
+            })?;
+        }
+    }
+    fs::rename(&staging, db_path).map_err(|error| {
+        AppError::Runtime(format!("failed to replace {}: {error}", db_path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn auto_snapshots_rotate_while_manual_snapshots_stay() {
+        let dir = tempdir().expect("temp dir");
+        let conn = Connection::open(dir.path().join("memo.db")).expect("open db");
+        conn.execute_batch("create table inbox_items (item_id integer primary key);")
+            .expect("schema");
+        let backups = dir.path().join(BACKUP_DIR_NAME);
+
+        let manual = create_snapshot(&conn, &backups, None).expect("manual snapshot");
+        let automatic = (0..3)
+            .map(|_| create_snapshot(&conn, &backups, Some("import")).expect("auto snapshot"))
+            .collect::<Vec<_>>();
+        assert!(automatic[0].to_string_lossy().contains("-auto-import"));
+        assert!(has_memo_schema(&manual).expect("schema check"));
+        assert_eq!(integrity_problem(&manual).expect("integrity"), None);
+
+        let removed = rotate_auto_snapshots(&backups, 2);
+        assert_eq!(removed, vec![automatic[0].clone()]);
+        assert!(manual.exists() && automatic[2].exists());
+        assert_eq!(
+            latest_snapshot(&backups).map(|(path, _)| path),
+            Some(automatic[2].clone())
+        );
+        assert_eq!(
+            resolve_snapshot("memo-x.db", &backups),
+            backups.join("memo-x.db")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use memo::storage::repository;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::{BatchAction, BatchItemResult, pins, trash};
+
+/// Current state of one batch target: `None` when the memo does not exist, else whether it is trashed/pinned.
+struct TargetState {
+    trashed: bool,
+    pinned: bool,
+}
+
+fn target_state(conn: &Connection, item_id: i64) -> Result<Option<TargetState>, MemoCliError> {
+    conn.query_row(
+        "select
+            exists(select 1 from workflow_item_trash t where t.item_id = i.item_id),
+            exists(select 1 from workflow_item_pins p where p.item_id = i.item_id)
+        from inbox_items i
+        where i.item_id = ?1",
+        [item_id],
+        |row| {
+            Ok(TargetState {
+                trashed: row.get(0)?,
+                pinned: row.get(1)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+/// Why `action` cannot apply to `item_id` right now, or `Ok(())` when it can.
+pub(crate) fn check_target(
+    conn: &Connection,
+    action: BatchAction,
+    item_id: i64,
+) -> Result<Result<(), &'static str>, MemoCliError> {
+    let Some(state) = target_state(conn, item_id)? else {
+        return Ok(Err("item not found"));
+    };
+    Ok(match action {
+        BatchAction::Delete if state.trashed => Err("already in trash"),
+        BatchAction::Restore if !state.trashed => Err("not in trash"),
+        BatchAction::Pin | BatchAction::Unpin if state.trashed => Err("item is in trash"),
+        BatchAction::Pin if state.pinned => Err("already pinned"),
+        BatchAction::Unpin if !state.pinned => Err("not pinned"),
+        _ => Ok(()),
+    })
+}
+
+/// Memos carrying `tag` that a tag-scoped batch of `action` targets, newest first: trashed memos for `restore` and
+/// `purge`, live memos otherwise.
+fn tag_item_ids(
+    conn: &Connection,
+    action: BatchAction,
+    tag: &str,
+    limit: usize,
+) -> Result<Vec<i64>, MemoCliError> {
+    let trashed = if matches!(action, BatchAction::Restore | BatchAction::Purge) {
+        ""
+    } else {
+        "not "
+    };
+    let sql = format!(
+        "select i.item_id
+        from inbox_items i
+        where exists(select 1 from workflow_item_tags g where g.item_id = i.item_id and g.tag_name = ?1)
+          and {trashed}exists(select 1 from workflow_item_trash t where t.item_id = i.item_id)
+        order by i.created_at desc, i.item_id desc
+        limit ?2"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map((tag, limit as i64), |row| row.get(0))
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+/// Explicit ids in order, then each tag's memos (up to `tag_limit` per tag), without repeats.
+pub(crate) fn resolve_targets(
+    conn: &Connection,
+    action: BatchAction,
+    explicit: &[i64],
+    tags: &[String],
+    tag_limit: usize,
+) -> Result<Vec<i64>, MemoCliError> {
+    let mut item_ids = Vec::with_capacity(explicit.len());
+    for &item_id in explicit {
+        if !item_ids.contains(&item_id) {
+            item_ids.push(item_id);
+        }
+    }
+    for tag in tags {
+        for item_id in tag_item_ids(conn, action, tag, tag_limit)? {
+            if !item_ids.contains(&item_id) {
+                item_ids.push(item_id);
+            }
+        }
+    }
+    Ok(item_ids)
+}
+
+/// Text preview of one memo for batch confirmation rows; `None` when it does not exist.
+pub(crate) fn target_preview(
+    conn: &Connection,
+    codec: &TextCodec,
+    item_id: i64,
+) -> Result<Option<String>, MemoCliError> {
+    let stored: Option<String> = conn
+        .query_row(
+            &format!("select {PREVIEW_TEXT_SQL} from inbox_items i where i.item_id = ?1"),
+            [item_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(MemoCliError::db_query)?;
+    stored.map(|stored| codec.preview(&stored)).transpose()
+}
+
+/// Apply `action` to every item in order, skipping (and reporting) items it cannot apply to.
+///
+/// Runs on the caller's transaction, so a storage failure rolls back the whole batch.
+pub(crate) fn apply_batch(
+    conn: &Connection,
+    action: BatchAction,
+    item_ids: &[i64],
+    retention_days: u32,
+) -> Result<Vec<BatchItemResult>, MemoCliError> {
+    let mut results = Vec::with_capacity(item_ids.len());
+    for &item_id in item_ids {
+        let result = match check_target(conn, action, item_id)? {
+            Err(reason) => Some(reason.to_string()),
+            Ok(()) => {
+                match action {
+                    BatchAction::Delete => {
+                        trash::move_to_trash(conn, item_id, retention_days)?;
+                    }
+                    BatchAction::Restore => {
+                        trash::restore_from_trash(conn, item_id)?;
+                    }
+                    BatchAction::Purge => {
+                        repository::delete_item_hard(conn, item_id)?;
+                    }
+                    BatchAction::Pin => {
+                        pins::pin_item(conn, item_id)?;
+                    }
+                    BatchAction::Unpin => {
+                        pins::unpin_item(conn, item_id)?;
+                    }
+                }
+                None
+            }
+        };
+        results.push(BatchItemResult {
+            item_id: format_item_id(item_id),
+            ok: result.is_none(),
+            error: result,
+        });
+    }
+    Ok(results)
+}
@@ -0,0 +1,69 @@
+use std::process::Command;
+
+/// What the system clipboard currently holds, as far as memo capture is concerned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClipboardContent {
+    /// UTF-8 text with trailing line breaks removed; never blank.
+    Text(String),
+    /// Nothing, or only whitespace.
+    Empty,
+    /// Bytes that are not UTF-8 text or carry control characters (NUL, escape sequences, ...).
+    Binary,
+    /// `pbpaste` is missing or failed, e.g. outside macOS.
+    Unavailable,
+}
+
+/// Read the clipboard through `pbpaste`.
+///
+/// `pbpaste` only emits the plain-text flavor, so an image or file on the clipboard reads as empty.
+pub(crate) fn read_clipboard() -> ClipboardContent {
+    match Command::new("pbpaste").output() {
+        Ok(output) if output.status.success() => classify(&output.stdout),
+        _ => ClipboardContent::Unavailable,
+    }
+}
+
+/// Clipboard text for `{clipboard}` template placeholders; empty unless the clipboard holds text.
+pub(crate) fn read_clipboard_text() -> String {
+    match read_clipboard() {
+        ClipboardContent::Text(text) => text,
+        _ => String::new(),
+    }
+}
+
+fn classify(bytes: &[u8]) -> ClipboardContent {
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return ClipboardContent::Binary;
+    };
+    if text
+        .chars()
+        .any(|ch| ch.is_control() && !matches!(ch, '\n' | '\r' | '\t'))
+    {
+        return ClipboardContent::Binary;
+    }
+    if text.trim().is_empty() {
+        return ClipboardContent::Empty;
+    }
+    ClipboardContent::Text(text.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_accepts_text_and_guards_empty_and_binary_clipboards() {
+        assert_eq!(
+            classify("line one\n\tline two\r\n".as_bytes()),
+            ClipboardContent::Text("line one\n\tline two".to_string())
+        );
+        assert_eq!(classify(b""), ClipboardContent::Empty);
+        assert_eq!(classify(b" \n\t"), ClipboardContent::Empty);
+        assert_eq!(
+            classify(&[0x89, b'P', b'N', b'G']),
+            ClipboardContent::Binary
+        );
+        assert_eq!(classify(b"abc\0def"), ClipboardContent::Binary);
+        assert_eq!(classify(b"\x1b[31mred"), ClipboardContent::Binary);
+    }
+}
@@ -0,0 +1,431 @@
+use std::sync::Mutex;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use keyring_core::{Entry, Error as KeyringError};
+use memo::errors::AppError as MemoCliError;
+use memo::storage::Storage;
+use rusqlite::{Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::{AppError, RuntimeConfig};
+
+pub const PASSPHRASE_ENV: &str = "MEMO_ENCRYPTION_PASSPHRASE";
+pub const KEYCHAIN_SERVICE: &str = "nils.memo-workflow.encryption";
+pub const KEYCHAIN_ACCOUNT: &str = "passphrase";
+const CIPHERTEXT_PREFIX: &str = "memoenc:v1:";
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+const VERIFIER_PLAINTEXT: &str = "nils-memo-workflow encryption check";
+const HASH_KEY_CONTEXT: &[u8] = b"nils-memo-workflow content hash";
+/// Characters kept by list/search previews (matches the upstream `substr(raw_text, 1, 80)`).
+const PREVIEW_CHARS: usize = 80;
+
+/// SQL expression for preview columns of the `inbox_items` row aliased as `i`: the first 80 characters of
+/// plaintext, or the whole ciphertext so `TextCodec::preview` can decrypt it before truncating.
+pub(crate) const PREVIEW_TEXT_SQL: &str =
+    "case when i.raw_text like 'memoenc:v1:%' then i.raw_text else substr(i.raw_text, 1, 80) end";
+
+/// Argon2id cost parameters recorded per database so they can be raised later without breaking old files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KdfParams {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+const DEFAULT_KDF_PARAMS: KdfParams = KdfParams {
+    memory_kib: 19 * 1024,
+    iterations: 2,
+    parallelism: 1,
+};
+
+/// Keys derived in this process as `(salt, passphrase, key)`, so one script-filter run derives at most once.
+static DERIVED_KEYS: Mutex<Vec<(String, String, [u8; 32])>> = Mutex::new(Vec::new());
+
+/// How memo text is stored in one database: verbatim, or sealed with XChaCha20-Poly1305.
+pub(crate) enum TextCodec {
+    Plain,
+    Encrypted(Box<SealingKey>),
+}
+
+pub(crate) struct SealingKey {
+    cipher: XChaCha20Poly1305,
+    hash_key: [u8; 32],
+}
+
+impl TextCodec {
+    fn encrypted(key: [u8; 32]) -> Self {
+        let hash_key = Sha256::new()
+            .chain_update(HASH_KEY_CONTEXT)
+            .chain_update(key)
+            .finalize()
+            .into();
+        Self::Encrypted(Box::new(SealingKey {
+            cipher: XChaCha20Poly1305::new(&key.into()),
+            hash_key,
+        }))
+    }
+
+    pub(crate) fn is_encrypted(&self) -> bool {
+        matches!(self, Self::Encrypted(_))
+    }
+
+    /// Text as it should be written to `inbox_items.raw_text`.
+    pub(crate) fn seal(&self, text: &str) -> Result<String, MemoCliError> {
+        match self {
+            Self::Plain => Ok(text.to_string()),
+            Self::Encrypted(key) => encrypt_text(&key.cipher, text),
+        }
+    }
+
+    /// Plaintext of a stored `raw_text` value.
+    pub(crate) fn open(&self, stored: &str) -> Result<String, MemoCliError> {
+        let Some(payload) = stored.strip_prefix(CIPHERTEXT_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        match self {
+            Self::Plain => Err(MemoCliError::runtime(
+                "memo text is encrypted but the database has no encryption settings",
+            )),
+            Self::Encrypted(key) => decrypt_payload(&key.cipher, payload),
+        }
+    }
+
+    /// Decrypted preview of a `PREVIEW_TEXT_SQL` column.
+    pub(crate) fn preview(&self, stored: &str) -> Result<String, MemoCliError> {
+        if !stored.starts_with(CIPHERTEXT_PREFIX) {
+            return Ok(stored.to_string());
+        }
+        Ok(self.open(stored)?.chars().take(PREVIEW_CHARS).collect())
+    }
+
+    /// SHA-256 of memo text with line endings normalized and surrounding whitespace trimmed.
+    ///
+    /// Encrypted databases prefix a secret derived from the key, so stored hashes cannot be matched against guessed
+    /// plaintext without the passphrase.
+    pub(crate) fn content_hash(&self, text: &str) -> String {
+        let normalized = text.replace("\r\n", "\n");
+        let mut hasher = Sha256::new();
+        if let Self::Encrypted(key) = self {
+            hasher.update(key.hash_key);
+        }
+        hasher.update(normalized.trim().as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+fn encrypt_text(cipher: &XChaCha20Poly1305, text: &str) -> Result<String, MemoCliError> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, text.as_bytes())
+        .map_err(|_| MemoCliError::runtime("failed to encrypt memo text"))?;
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&sealed);
+    Ok(format!("{CIPHERTEXT_PREFIX}{}", BASE64.encode(payload)))
+}
+
+fn decrypt_payload(cipher: &XChaCha20Poly1305, payload: &str) -> Result<String, MemoCliError> {
+    let invalid = || MemoCliError::runtime("failed to decrypt memo text (corrupted ciphertext)");
+    let bytes = BASE64.decode(payload).map_err(|_| invalid())?;
+    if bytes.len() < NONCE_LEN {
+        return Err(invalid());
+    }
+    let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+    let plain = cipher
+        .decrypt(XNonce::from_slice(nonce), sealed)
+        .map_err(|_| invalid())?;
+    String::from_utf8(plain).map_err(|_| invalid())
+}
+
+pub(crate) struct EncryptionSettings {
+    salt: String,
+    verifier: String,
+    params: KdfParams,
+}
+
+fn read_settings(conn: &Connection) -> Result<Option<EncryptionSettings>, MemoCliError> {
+    conn.query_row(
+        "select salt, verifier, kdf_memory_kib, kdf_iterations, kdf_parallelism
+        from workflow_encryption
+        where id = 1",
+        [],
+        |row| {
+            Ok(EncryptionSettings {
+                salt: row.get(0)?,
+                verifier: row.get(1)?,
+                params: KdfParams {
+                    memory_kib: row.get(2)?,
+                    iterations: row.get(3)?,
+                    parallelism: row.get(4)?,
+                },
+            })
+        },
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+/// Codec for one opened database; encrypted databases resolve the passphrase and check it.
+pub(crate) fn text_codec(storage: &Storage, config: &RuntimeConfig) -> Result<TextCodec, AppError> {
+    let settings = storage
+        .with_connection(read_settings)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    let Some(settings) = settings else {
+        return Ok(TextCodec::Plain);
+    };
+
+    let passphrase = resolve_passphrase(config.encryption_passphrase.as_deref())?;
+    let cached = DERIVED_KEYS.lock().ok().and_then(|keys| {
+        keys.iter()
+            .find(|(salt, cached, _)| *salt == settings.salt && *cached == passphrase)
+            .map(|(_, _, key)| *key)
+    });
+    let key = match cached {
+        Some(key) => key,
+        None => {
+            let key = derive_key(&passphrase, &settings.salt, settings.params)?;
+            let cipher = XChaCha20Poly1305::new(&key.into());
+            if decrypt_verifier(&cipher, &settings.verifier).is_none() {
+                return Err(AppError::User(
+                    "encryption passphrase does not match this memo database".to_string(),
+                ));
+            }
+            remember_key(&settings.salt, &passphrase, key);
+            key
+        }
+    };
+    Ok(TextCodec::encrypted(key))
+}
+
+fn remember_key(salt: &str, passphrase: &str, key: [u8; 32]) {
+    if let Ok(mut keys) = DERIVED_KEYS.lock() {
+        keys.push((salt.to_string(), passphrase.to_string(), key));
+    }
+}
+
+fn decrypt_verifier(cipher: &XChaCha20Poly1305, verifier: &str) -> Option<()> {
+    let payload = verifier.strip_prefix(CIPHERTEXT_PREFIX)?;
+    let plain = decrypt_payload(cipher, payload).ok()?;
+    (plain == VERIFIER_PLAINTEXT).then_some(())
+}
+
+fn derive_key(passphrase: &str, salt: &str, params: KdfParams) -> Result<[u8; 32], AppError> {
+    let salt = BASE64
+        .decode(salt)
+        .map_err(|_| AppError::Runtime("invalid encryption salt in memo database".to_string()))?;
+    let params = Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(32),
+    )
+    .map_err(|error| AppError::Runtime(format!("invalid encryption kdf parameters: {error}")))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|error| AppError::Runtime(format!("failed to derive encryption key: {error}")))?;
+    Ok(key)
+}
+
+/// The configured passphrase (`MEMO_ENCRYPTION_PASSPHRASE`), then the system keychain entry
+/// (`KEYCHAIN_SERVICE` / `KEYCHAIN_ACCOUNT`).
+pub(crate) fn resolve_passphrase(configured: Option<&str>) -> Result<String, AppError> {
+    if let Some(passphrase) = configured {
+        return Ok(passphrase.to_string());
+    }
+
+    keyring::use_native_store(false)
+        .map_err(|error| AppError::Runtime(format!("keychain unavailable: {error}")))?;
+    let entry = Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .map_err(|error| AppError::Runtime(format!("keychain unavailable: {error}")))?;
+    match entry.get_password() {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(passphrase),
+        Ok(_) | Err(KeyringError::NoEntry) => Err(AppError::User(format!(
+            "no encryption passphrase: add a keychain item (service {KEYCHAIN_SERVICE}, account \
+             {KEYCHAIN_ACCOUNT}) or set {PASSPHRASE_ENV}"
+        ))),
+        Err(error) => Err(AppError::Runtime(format!(
+            "failed to read encryption passphrase from keychain: {error}"
+        ))),
+    }
+}
+
+pub(crate) fn encryption_enabled(conn: &Connection) -> Result<bool, MemoCliError> {
+    Ok(read_settings(conn)?.is_some())
+}
+
+/// Fresh settings (random salt, default KDF cost) and the codec they unlock for `passphrase`.
+pub(crate) fn new_encryption(
+    passphrase: &str,
+) -> Result<(EncryptionSettings, TextCodec), AppError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let salt = BASE64.encode(salt);
+    let params = DEFAULT_KDF_PARAMS;
+    let key = derive_key(passphrase, &salt, params)?;
+    remember_key(&salt, passphrase, key);
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let verifier = encrypt_text(&cipher, VERIFIER_PLAINTEXT)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    Ok((
+        EncryptionSettings {
+            salt,
+            verifier,
+            params,
+        },
+        TextCodec::encrypted(key),
+    ))
+}
+
+/// Encrypt every memo with `codec` and record `settings`, returning the number of memos rewritten.
+pub(crate) fn encrypt_items(
+    conn: &Connection,
+    settings: &EncryptionSettings,
+    codec: &TextCodec,
+) -> Result<i64, MemoCliError> {
+    conn.pragma_update(None, "secure_delete", "ON")
+        .map_err(MemoCliError::db_write)?;
+    conn.execute(
+        "insert into workflow_encryption(id, salt, verifier, kdf_memory_kib, kdf_iterations, kdf_parallelism)
+         values (1, ?1, ?2, ?3, ?4, ?5)",
+        (
+            &settings.salt,
+            &settings.verifier,
+            settings.params.memory_kib,
+            settings.params.iterations,
+            settings.params.parallelism,
+        ),
+    )
+    .map_err(MemoCliError::db_write)?;
+    recode_items(conn, &TextCodec::Plain, codec)
+}
+
+/// Store every memo as plaintext again and drop the encryption settings.
+pub(crate) fn decrypt_items(conn: &Connection, codec: &TextCodec) -> Result<i64, MemoCliError> {
+    conn.pragma_update(None, "secure_delete", "ON")
+        .map_err(MemoCliError::db_write)?;
+    let rewritten = recode_items(conn, codec, &TextCodec::Plain)?;
+    conn.execute("delete from workflow_encryption", [])
+        .map_err(MemoCliError::db_write)?;
+    Ok(rewritten)
+}
+
+/// Rewrite every memo's `raw_text` (and its stored versions) from one codec to another, returning the number of
+/// memos rewritten.
+///
+/// Upstream triggers refresh `item_search_documents`, which in turn refreshes both FTS indexes.
+fn recode_items(conn: &Connection, from: &TextCodec, to: &TextCodec) -> Result<i64, MemoCliError> {
+    recode_versions(conn, from, to)?;
+    let rows = {
+        let mut stmt = conn
+            .prepare("select item_id, raw_text from inbox_items order by item_id")
+            .map_err(MemoCliError::db_query)?;
+        stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?
+    };
+
+    for (item_id, stored) in &rows {
+        let rewritten = to.seal(&from.open(stored)?)?;
+        conn.execute(
+            "update inbox_items set raw_text = ?1 where item_id = ?2",
+            (&rewritten, item_id),
+        )
+        .map_err(MemoCliError::db_write)?;
+    }
+    Ok(rows.len() as i64)
+}
+
+fn recode_versions(
+    conn: &Connection,
+    from: &TextCodec,
+    to: &TextCodec,
+) -> Result<(), MemoCliError> {
+    let rows = {
+        let mut stmt = conn
+            .prepare("select version_id, text from workflow_item_versions order by version_id")
+            .map_err(MemoCliError::db_query)?;
+        stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?
+    };
+
+    for (version_id, stored) in &rows {
+        conn.execute(
+            "update workflow_item_versions set text = ?1 where version_id = ?2",
+            (to.seal(&from.open(stored)?)?, version_id),
+        )
+        .map_err(MemoCliError::db_write)?;
+    }
+    Ok(())
+}
+
+/// Drop stale text left in FTS segments, freed pages, and the WAL after re-encoding memos.
+pub(crate) fn compact_after_recode(conn: &Connection) -> Result<(), MemoCliError> {
+    conn.execute_batch("insert into item_search_fts(item_search_fts) values('rebuild');")
+        .map_err(MemoCliError::db_write)?;
+    if crate::fts::index_available(conn)? {
+        conn.execute_batch(
+            "insert into workflow_search_fts(workflow_search_fts) values('rebuild');",
+        )
+        .map_err(MemoCliError::db_write)?;
+    }
+    conn.execute_batch("vacuum; pragma wal_checkpoint(truncate);")
+        .map_err(MemoCliError::db_write)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_codec_round_trips_and_rejects_tampered_ciphertext() {
+        let codec = TextCodec::encrypted([7u8; 32]);
+
+        let sealed = codec.seal("buy oat milk 🥛").expect("seal");
+        assert!(sealed.starts_with(CIPHERTEXT_PREFIX));
+        assert_ne!(
+            sealed,
+            codec.seal("buy oat milk 🥛").expect("seal"),
+            "fresh nonce per seal"
+        );
+        assert_eq!(codec.open(&sealed).expect("open"), "buy oat milk 🥛");
+        assert_eq!(
+            codec.open("legacy plaintext").expect("plain passthrough"),
+            "legacy plaintext"
+        );
+
+        let long = "x".repeat(200);
+        assert_eq!(
+            codec
+                .preview(&codec.seal(&long).expect("seal"))
+                .expect("preview")
+                .len(),
+            80
+        );
+
+        let mut tampered = sealed.clone();
+        tampered.pop();
+        tampered.push(if sealed.ends_with('A') { 'B' } else { 'A' });
+        assert!(codec.open(&tampered).is_err());
+        assert!(TextCodec::Plain.open(&sealed).is_err());
+
+        let wrong = XChaCha20Poly1305::new(&[8u8; 32].into());
+        assert!(decrypt_verifier(&wrong, &sealed).is_none());
+    }
+}
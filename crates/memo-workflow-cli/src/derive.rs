@@ -0,0 +1,280 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use chrono::DateTime;
+use memo::output::parse_item_id;
+use memo::preprocess::{ContentType, ValidationStatus};
+use memo::storage::derivations::{ApplyInputItem, IncomingStatus};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::AppError;
+use crate::editor::split_command_line;
+
+/// `agent_run_id` recorded on derivations stored by `derive`.
+pub(crate) const DERIVE_AGENT_RUN_ID: &str = "memo-workflow-derive";
+/// Environment variable carrying the memo id to the enrichment command.
+const ITEM_ID_ENV: &str = "MEMO_ITEM_ID";
+const PRIORITIES: [&str; 4] = ["low", "medium", "high", "urgent"];
+/// A payload must set at least one of these to count as an enrichment.
+const ENRICHMENT_FIELDS: [&str; 6] = [
+    "summary",
+    "category",
+    "priority",
+    "due_at",
+    "normalized_text",
+    "tags",
+];
+
+/// Run the enrichment `command` with the memo text on stdin and `MEMO_ITEM_ID` set, returning its stdout.
+///
+/// The command is split like `MEMO_EDITOR` (whitespace, honoring quotes); launch failures and non-zero exits are
+/// runtime errors carrying the first stderr line.
+pub(crate) fn run_enrichment(command: &str, item_id: &str, text: &str) -> Result<String, AppError> {
+    let mut args = split_command_line(command).into_iter();
+    let program = args
+        .next()
+        .ok_or_else(|| AppError::User("MEMO_DERIVE_COMMAND must be non-empty".to_string()))?;
+
+    let mut child = Command::new(&program)
+        .args(args)
+        .env(ITEM_ID_ENV, item_id)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| {
+            AppError::Runtime(format!(
+                "failed to launch enrichment command {program}: {error}"
+            ))
+        })?;
+    // Feed stdin from a separate thread so a command that writes before reading cannot block on a full pipe.
+    let writer = child.stdin.take().map(|mut stdin| {
+        let text = text.to_string();
+        std::thread::spawn(move || stdin.write_all(text.as_bytes()))
+    });
+    let output = child.wait_with_output().map_err(|error| {
+        AppError::Runtime(format!("enrichment command {program} failed: {error}"))
+    })?;
+    if let Some(writer) = writer {
+        // A command may exit without reading its input; only its exit status matters.
+        let _ = writer.join();
+    }
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .map_or(String::new(), |line| format!(": {}", line.trim()));
+        return Err(AppError::Runtime(format!(
+            "enrichment command {program} exited with {}{detail}",
+            output.status
+        )));
+    }
+    String::from_utf8(output.stdout).map_err(|_| {
+        AppError::Runtime(format!(
+            "enrichment command {program} printed non-UTF-8 output"
+        ))
+    })
+}
+
+/// Validate enrichment output against the derivation schema and build the accepted derivation for `item_id`.
+///
+/// The output must be one JSON object. Optional fields follow `item_derivations`: `summary`, `category`,
+/// `normalized_text` (strings), `priority` (`low|medium|high|urgent`), `due_at` (RFC 3339), `confidence` (`0..=1`),
+/// `content_type`, `validation_status`, and `tags` (strings, leading `#` dropped); `item_id` and `status`, when
+/// present, must name this memo and `accepted`. The whole object (or its `payload` member) is stored as the
+/// payload, and its SHA-256 is the derivation hash, so re-running with identical output stores nothing new.
+pub(crate) fn parse_derivation(item_id: i64, output: &str) -> Result<ApplyInputItem, String> {
+    let value: Value = serde_json::from_str(output.trim())
+        .map_err(|error| format!("output is not JSON: {error}"))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| "output must be a JSON object".to_string())?;
+
+    match object.get("item_id") {
+        None | Some(Value::Null) => {}
+        Some(Value::Number(number)) if number.as_i64() == Some(item_id) => {}
+        Some(Value::String(raw)) if parse_item_id(raw) == Some(item_id) => {}
+        Some(_) => return Err("item_id does not match the derived memo".to_string()),
+    }
+    match object.get("status") {
+        None | Some(Value::Null) => {}
+        Some(Value::String(status)) if status.trim() == "accepted" => {}
+        Some(_) => return Err("status must be accepted".to_string()),
+    }
+
+    let priority = optional_string(object, "priority")?;
+    if let Some(priority) = &priority
+        && !PRIORITIES.contains(&priority.as_str())
+    {
+        return Err("priority must be low|medium|high|urgent".to_string());
+    }
+    let due_at = optional_string(object, "due_at")?;
+    if let Some(due_at) = &due_at
+        && DateTime::parse_from_rfc3339(due_at).is_err()
+    {
+        return Err("due_at must be an RFC 3339 timestamp".to_string());
+    }
+    let confidence = match object.get("confidence") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(
+            value
+                .as_f64()
+                .filter(|confidence| (0.0..=1.0).contains(confidence))
+                .ok_or_else(|| "confidence must be a number between 0.0 and 1.0".to_string())?,
+        ),
+    };
+    let content_type = optional_string(object, "content_type")?
+        .map(|raw| {
+            ContentType::parse(&raw)
+                .map(|content_type| content_type.as_str().to_string())
+                .ok_or_else(|| {
+                    "content_type must be url|json|yaml|xml|markdown|text|unknown".to_string()
+                })
+        })
+        .transpose()?;
+    let validation_status = optional_string(object, "validation_status")?
+        .map(|raw| {
+            ValidationStatus::parse(&raw)
+                .map(|status| status.as_str().to_string())
+                .ok_or_else(|| {
+                    "validation_status must be valid|invalid|unknown|skipped".to_string()
+                })
+        })
+        .transpose()?;
+    let tags = match object.get("tags") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|tag| {
+                tag.as_str()
+                    .map(|tag| tag.trim().trim_start_matches('#').to_string())
+                    .ok_or_else(|| "tags must be an array of strings".to_string())
+            })
+            .filter(|tag| tag.as_ref().map_or(true, |tag| !tag.is_empty()))
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(_) => return Err("tags must be an array of strings".to_string()),
+    };
+
+    let item = ApplyInputItem {
+        item_id,
+        status: IncomingStatus::Accepted,
+        derivation_hash: payload_hash(&value),
+        base_derivation_id: None,
+        summary: optional_string(object, "summary")?,
+        category: optional_string(object, "category")?,
+        priority,
+        due_at,
+        normalized_text: optional_string(object, "normalized_text")?,
+        confidence,
+        content_type,
+        validation_status,
+        validation_errors: None,
+        payload_json: object.get("payload").cloned().unwrap_or(value.clone()),
+        conflict_reason: None,
+        tags,
+        agent_run_id: None,
+    };
+    let enriched = item.summary.is_some()
+        || item.category.is_some()
+        || item.priority.is_some()
+        || item.due_at.is_some()
+        || item.normalized_text.is_some()
+        || !item.tags.is_empty();
+    if !enriched {
+        return Err(format!(
+            "output sets none of {}",
+            ENRICHMENT_FIELDS.join(", ")
+        ));
+    }
+    Ok(item)
+}
+
+/// Trimmed string member; absent, `null`, and blank strings are `None`.
+fn optional_string(object: &Map<String, Value>, key: &str) -> Result<Option<String>, String> {
+    match object.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(raw)) => Ok(Some(raw.trim().to_string()).filter(|raw| !raw.is_empty())),
+        Some(_) => Err(format!("{key} must be a string")),
+    }
+}
+
+fn payload_hash(value: &Value) -> String {
+    let canonical = serde_json::to_string(value).unwrap_or_default();
+    let digest = Sha256::digest(canonical.as_bytes());
+    let hex = digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256:{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_derivation_accepts_schema_fields_and_hashes_the_payload() {
+        let output = r##"{
+            "item_id": "itm_00000007",
+            "summary": "  Buy milk ",
+            "category": "errands",
+            "priority": "high",
+            "due_at": "2026-03-10T09:00:00Z",
+            "confidence": 0.8,
+            "content_type": "text",
+            "tags": ["#home", " ", "shopping"],
+            "model": "local-llm"
+        }"##;
+        let item = parse_derivation(7, output).expect("valid derivation");
+        assert_eq!(item.summary.as_deref(), Some("Buy milk"));
+        assert_eq!(item.priority.as_deref(), Some("high"));
+        assert_eq!(item.confidence, Some(0.8));
+        assert_eq!(item.tags, vec!["home", "shopping"]);
+        assert_eq!(item.payload_json["model"], Value::from("local-llm"));
+        assert!(item.derivation_hash.starts_with("sha256:"));
+        assert_eq!(
+            parse_derivation(7, output)
+                .expect("same output")
+                .derivation_hash,
+            item.derivation_hash
+        );
+    }
+
+    #[test]
+    fn parse_derivation_rejects_payloads_outside_the_schema() {
+        let error = |output: &str| parse_derivation(7, output).expect_err(output);
+        assert!(error("not json").starts_with("output is not JSON"));
+        assert_eq!(error("[1]"), "output must be a JSON object");
+        assert_eq!(
+            error(r#"{"item_id": 8, "summary": "x"}"#),
+            "item_id does not match the derived memo"
+        );
+        assert_eq!(
+            error(r#"{"status": "rejected", "summary": "x"}"#),
+            "status must be accepted"
+        );
+        assert_eq!(
+            error(r#"{"priority": "p1"}"#),
+            "priority must be low|medium|high|urgent"
+        );
+        assert_eq!(
+            error(r#"{"due_at": "tomorrow"}"#),
+            "due_at must be an RFC 3339 timestamp"
+        );
+        assert_eq!(
+            error(r#"{"summary": "x", "confidence": 1.5}"#),
+            "confidence must be a number between 0.0 and 1.0"
+        );
+        assert_eq!(
+            error(r#"{"tags": [1]}"#),
+            "tags must be an array of strings"
+        );
+        assert_eq!(
+            error(r#"{"summary": " ", "model": "x"}"#),
+            "output sets none of summary, category, priority, due_at, normalized_text, tags"
+        );
+    }
+}
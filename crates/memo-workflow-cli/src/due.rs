@@ -0,0 +1,361 @@
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::trash::NOT_TRASHED_SQL;
+
+pub const DUE_STATUS_OVERDUE: &str = "overdue";
+pub const DUE_STATUS_TODAY: &str = "today";
+pub const DUE_STATUS_UPCOMING: &str = "upcoming";
+/// Phrases accepted by `remind` to remove a due timestamp.
+pub const DUE_CLEAR_WORDS: [&str; 3] = ["clear", "none", "off"];
+const DEFAULT_DUE_HOUR: u32 = 9;
+const STORED_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+
+/// Parse a due phrase relative to `now` (whose time zone is used for wall-clock phrases).
+///
+/// Accepted shapes: RFC 3339 timestamps, `YYYY-MM-DD[ HH:MM]`, `today` / `tomorrow` / weekday names
+/// (optionally prefixed by `next`), `in <n> <minutes|hours|days|weeks>`, each optionally followed by
+/// a time (`9am`, `9:30pm`, `21:00`, `noon`, with optional `at`). A bare time means its next occurrence;
+/// a date without a time defaults to 09:00.
+pub(crate) fn parse_due_phrase<Tz: TimeZone>(
+    raw: &str,
+    now: &DateTime<Tz>,
+) -> Option<DateTime<Utc>> {
+    let phrase = raw.trim().to_lowercase();
+    if phrase.is_empty() {
+        return None;
+    }
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(raw.trim()) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+
+    let words = phrase.split_whitespace().collect::<Vec<_>>();
+    if let ["in", amount, unit] = words.as_slice() {
+        return now
+            .with_timezone(&Utc)
+            .checked_add_signed(parse_offset(amount, unit)?);
+    }
+
+    let today = now.date_naive();
+    let (date, rest) = match words.as_slice() {
+        ["today", rest @ ..] => (Some(today), rest),
+        ["tomorrow", rest @ ..] => (today.checked_add_days(Days::new(1)), rest),
+        ["next", day, rest @ ..] => (next_weekday(today, parse_weekday(day)?), rest),
+        [first, rest @ ..] if parse_weekday(first).is_some() => {
+            (next_weekday(today, parse_weekday(first)?), rest)
+        }
+        [first, rest @ ..] if NaiveDate::parse_from_str(first, "%Y-%m-%d").is_ok() => {
+            (NaiveDate::parse_from_str(first, "%Y-%m-%d").ok(), rest)
+        }
+        [first, rest @ ..]
+            if first.starts_with(|ch: char| ch.is_ascii_digit()) && first.contains('t') =>
+        {
+            let (date_raw, time_raw) = first.split_once('t')?;
+            let date = NaiveDate::parse_from_str(date_raw, "%Y-%m-%d").ok()?;
+            if !rest.is_empty() {
+                return None;
+            }
+            let time = parse_time(&[time_raw])?;
+            return localize(now, date.and_time(time));
+        }
+        rest => (None, rest),
+    };
+    let rest = match rest {
+        ["at", rest @ ..] => rest,
+        rest => rest,
+    };
+
+    match (date, rest.is_empty()) {
+        (Some(date), true) => localize(now, date.and_time(default_time())),
+        (Some(date), false) => localize(now, date.and_time(parse_time(rest)?)),
+        (None, false) => {
+            let time = parse_time(rest)?;
+            let candidate = localize(now, today.and_time(time))?;
+            if candidate > now.with_timezone(&Utc) {
+                Some(candidate)
+            } else {
+                localize(now, today.checked_add_days(Days::new(1))?.and_time(time))
+            }
+        }
+        (None, true) => None,
+    }
+}
+
+fn default_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(DEFAULT_DUE_HOUR, 0, 0).unwrap_or(NaiveTime::MIN)
+}
+
+fn localize<Tz: TimeZone>(
+    now: &DateTime<Tz>,
+    naive: chrono::NaiveDateTime,
+) -> Option<DateTime<Utc>> {
+    now.timezone()
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+}
+
+fn parse_offset(amount: &str, unit: &str) -> Option<Duration> {
+    let amount = amount.parse::<i64>().ok().filter(|value| *value > 0)?;
+    match unit.trim_end_matches('s') {
+        "m" | "min" | "minute" => Duration::try_minutes(amount),
+        "h" | "hr" | "hour" => Duration::try_hours(amount),
+        "d" | "day" => Duration::try_days(amount),
+        "w" | "week" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+fn parse_weekday(raw: &str) -> Option<Weekday> {
+    let weekday = match raw {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Next date strictly after `today` falling on `weekday`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> Option<NaiveDate> {
+    let ahead = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    today.checked_add_days(Days::new(u64::from(ahead)))
+}
+
+/// Parse `9am`, `9 am`, `9:30pm`, `21:00`, `noon`, or `midnight`.
+fn parse_time(words: &[&str]) -> Option<NaiveTime> {
+    let joined = words.concat();
+    match joined.as_str() {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return Some(NaiveTime::MIN),
+        _ => {}
+    }
+
+    let (clock, meridiem) = if let Some(clock) = joined.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = joined.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (joined.as_str(), None)
+    };
+    let (hour_raw, minute_raw) = clock.split_once(':').unwrap_or((clock, "0"));
+    let minute_raw = minute_raw.split(':').next().unwrap_or("0");
+    let mut hour = hour_raw.parse::<u32>().ok()?;
+    let minute = minute_raw.parse::<u32>().ok()?;
+
+    match meridiem {
+        Some(is_pm) => {
+            if !(1..=12).contains(&hour) {
+                return None;
+            }
+            hour %= 12;
+            if is_pm {
+                hour += 12;
+            }
+        }
+        None if !clock.contains(':') => return None,
+        None => {}
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+pub(crate) fn format_due(due: &DateTime<Utc>) -> String {
+    due.format(STORED_FORMAT).to_string()
+}
+
+/// `overdue`, `today` (before the end of the local day), or `upcoming`.
+pub(crate) fn due_status<Tz: TimeZone>(due: &DateTime<Utc>, now: &DateTime<Tz>) -> &'static str {
+    if *due <= now.with_timezone(&Utc) {
+        return DUE_STATUS_OVERDUE;
+    }
+    match end_of_day(now) {
+        Some(end) if *due < end => DUE_STATUS_TODAY,
+        _ => DUE_STATUS_UPCOMING,
+    }
+}
+
+/// Start of the local day after `now`, in UTC.
+pub(crate) fn end_of_day<Tz: TimeZone>(now: &DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let tomorrow = now.date_naive().checked_add_days(Days::new(1))?;
+    localize(now, tomorrow.and_time(NaiveTime::MIN))
+}
+
+/// Relative description such as `Overdue by 2h` or `Due in 15m`.
+pub(crate) fn describe_due(due: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
+    let delta = *due - *now;
+    let span = delta.abs();
+    let amount = if span < Duration::minutes(1) {
+        return if delta <= Duration::zero() {
+            "Due now".to_string()
+        } else {
+            "Due in <1m".to_string()
+        };
+    } else if span < Duration::hours(1) {
+        format!("{}m", span.num_minutes())
+    } else if span < Duration::days(2) {
+        format!("{}h", span.num_hours())
+    } else {
+        format!("{}d", span.num_days())
+    };
+
+    if delta < Duration::zero() {
+        format!("Overdue by {amount}")
+    } else {
+        format!("Due in {amount}")
+    }
+}
+
+pub(crate) fn set_due(conn: &Connection, item_id: i64, due_at: &str) -> Result<(), MemoCliError> {
+    conn.execute(
+        "insert into workflow_item_due(item_id, due_at) values (?1, ?2)
+         on conflict(item_id) do update set due_at = excluded.due_at,
+             set_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        (item_id, due_at),
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(())
+}
+
+/// Remove one due timestamp, returning whether one was set.
+pub(crate) fn clear_due(conn: &Connection, item_id: i64) -> Result<bool, MemoCliError> {
+    let removed = conn
+        .execute(
+            "delete from workflow_item_due where item_id = ?1",
+            [item_id],
+        )
+        .map_err(MemoCliError::db_write)?;
+    Ok(removed > 0)
+}
+
+/// Live memos with a due timestamp before `until` (all when `None`), soonest first.
+///
+/// Rows are `(item_id, due_at, text_preview)`.
+pub(crate) fn list_due(
+    conn: &Connection,
+    until: Option<&str>,
+    limit: usize,
+    codec: &TextCodec,
+) -> Result<Vec<(String, String, String)>, MemoCliError> {
+    let sql = format!(
+        "select i.item_id, du.due_at, {PREVIEW_TEXT_SQL}
+        from workflow_item_due du
+        join inbox_items i on i.item_id = du.item_id
+        where (?1 is null or du.due_at < ?1)
+          and {NOT_TRASHED_SQL}
+        order by du.due_at asc, i.item_id asc
+        limit ?2"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map((until, limit as i64), |row| {
+            Ok((
+                format_item_id(row.get::<_, i64>(0)?),
+                row.get(1)?,
+                row.get(2)?,
+            ))
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.map(|row| {
+        let (item_id, due_at, text_preview): (String, String, String) =
+            row.map_err(MemoCliError::db_query)?;
+        Ok((item_id, due_at, codec.preview(&text_preview)?))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn now() -> DateTime<FixedOffset> {
+        // Thursday 2026-02-12 14:30 at UTC+8.
+        DateTime::parse_from_rfc3339("2026-02-12T14:30:00+08:00").expect("fixed now")
+    }
+
+    fn parsed(raw: &str) -> Option<String> {
+        parse_due_phrase(raw, &now()).map(|due| format_due(&due))
+    }
+
+    #[test]
+    fn parse_due_phrase_supports_relative_words_times_and_iso_dates() {
+        assert_eq!(
+            parsed("tomorrow 9am").as_deref(),
+            Some("2026-02-13T01:00:00.000Z")
+        );
+        assert_eq!(
+            parsed("today at 5:30pm").as_deref(),
+            Some("2026-02-12T09:30:00.000Z")
+        );
+        assert_eq!(
+            parsed("tomorrow").as_deref(),
+            Some("2026-02-13T01:00:00.000Z")
+        );
+        assert_eq!(parsed("9am").as_deref(), Some("2026-02-13T01:00:00.000Z"));
+        assert_eq!(parsed("21:00").as_deref(), Some("2026-02-12T13:00:00.000Z"));
+        assert_eq!(
+            parsed("mon noon").as_deref(),
+            Some("2026-02-16T04:00:00.000Z")
+        );
+        assert_eq!(
+            parsed("next thu").as_deref(),
+            Some("2026-02-19T01:00:00.000Z")
+        );
+        assert_eq!(
+            parsed("in 2 hours").as_deref(),
+            Some("2026-02-12T08:30:00.000Z")
+        );
+        assert_eq!(
+            parsed("2026-03-01").as_deref(),
+            Some("2026-03-01T01:00:00.000Z")
+        );
+        assert_eq!(
+            parsed("2026-03-01 18:15").as_deref(),
+            Some("2026-03-01T10:15:00.000Z")
+        );
+        assert_eq!(
+            parsed("2026-03-01T18:15").as_deref(),
+            Some("2026-03-01T10:15:00.000Z")
+        );
+        assert_eq!(
+            parsed("2026-03-01T18:15:00Z").as_deref(),
+            Some("2026-03-01T18:15:00.000Z")
+        );
+
+        for invalid in [
+            "",
+            "someday",
+            "tomorrow 25:00",
+            "13pm",
+            "in 0 days",
+            "in 99999999999999 weeks",
+            "2026-13-01",
+        ] {
+            assert_eq!(parsed(invalid), None, "{invalid} should not parse");
+        }
+    }
+
+    #[test]
+    fn due_status_and_description_are_relative_to_now() {
+        let now = now();
+        let now_utc = now.with_timezone(&Utc);
+        let overdue = now_utc - Duration::hours(3);
+        let later_today = now_utc + Duration::minutes(45);
+        let next_week = now_utc + Duration::days(7);
+
+        assert_eq!(due_status(&overdue, &now), DUE_STATUS_OVERDUE);
+        assert_eq!(due_status(&later_today, &now), DUE_STATUS_TODAY);
+        assert_eq!(due_status(&next_week, &now), DUE_STATUS_UPCOMING);
+        assert_eq!(describe_due(&overdue, &now_utc), "Overdue by 3h");
+        assert_eq!(describe_due(&later_today, &now_utc), "Due in 45m");
+        assert_eq!(describe_due(&next_week, &now_utc), "Due in 7d");
+    }
+}
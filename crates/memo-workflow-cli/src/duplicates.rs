@@ -0,0 +1,129 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::crypto::TextCodec;
+use crate::fuzzy::{self, FUZZY_SCAN_LIMIT};
+use crate::trash::{self, LiveText};
+
+/// Minimum similarity (both directions) for two memos to count as near-identical.
+pub(crate) const NEAR_DUPLICATE_THRESHOLD: f64 = 0.9;
+/// Candidates whose length differs from the new text by more than this fraction are never near-identical.
+const LENGTH_TOLERANCE: f64 = 0.2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DuplicateMatch {
+    pub(crate) item_id: String,
+    pub(crate) created_at: String,
+    pub(crate) text: String,
+    /// `1.0` for identical text (same content hash), otherwise the similarity score.
+    pub(crate) score: f64,
+}
+
+impl DuplicateMatch {
+    pub(crate) fn is_exact(&self) -> bool {
+        self.score >= 1.0
+    }
+}
+
+/// Hash every memo that has no `workflow_item_hashes` row yet (new, imported, or edited since the last check).
+pub(crate) fn ensure_content_hashes(
+    conn: &Connection,
+    codec: &TextCodec,
+) -> Result<usize, MemoCliError> {
+    let missing = {
+        let mut stmt = conn
+            .prepare(
+                "select i.item_id, i.raw_text
+                from inbox_items i
+                where not exists (select 1 from workflow_item_hashes h where h.item_id = i.item_id)",
+            )
+            .map_err(MemoCliError::db_query)?;
+        stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?
+    };
+
+    for (item_id, stored) in &missing {
+        let hash = codec.content_hash(&codec.open(stored)?);
+        conn.execute(
+            "insert into workflow_item_hashes(item_id, content_hash) values (?1, ?2)",
+            (item_id, hash),
+        )
+        .map_err(MemoCliError::db_write)?;
+    }
+    Ok(missing.len())
+}
+
+/// The newest live memo with identical text, else the closest near-identical one among the newest live memos.
+///
+/// Expects `ensure_content_hashes` to have run in the same transaction.
+pub(crate) fn find_duplicate(
+    conn: &Connection,
+    text: &str,
+    codec: &TextCodec,
+) -> Result<Option<DuplicateMatch>, MemoCliError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    let exact = conn
+        .query_row(
+            "select i.item_id, i.created_at, i.raw_text
+            from workflow_item_hashes h
+            join inbox_items i on i.item_id = h.item_id
+            where h.content_hash = ?1
+              and not exists (select 1 from workflow_item_trash t where t.item_id = i.item_id)
+            order by i.created_at desc, i.item_id desc
+            limit 1",
+            [codec.content_hash(text)],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(MemoCliError::db_query)?;
+    if let Some((item_id, created_at, stored)) = exact {
+        return Ok(Some(DuplicateMatch {
+            item_id: format_item_id(item_id),
+            created_at,
+            text: codec.open(&stored)?,
+            score: 1.0,
+        }));
+    }
+
+    let length = text.chars().count() as f64;
+    let mut best: Option<DuplicateMatch> = None;
+    for LiveText {
+        item_id,
+        created_at,
+        text: candidate,
+        ..
+    } in trash::list_live_texts(conn, codec, Some(FUZZY_SCAN_LIMIT))?
+    {
+        let candidate_length = candidate.trim().chars().count() as f64;
+        if (candidate_length - length).abs() > length.max(candidate_length) * LENGTH_TOLERANCE {
+            continue;
+        }
+        // `similarity` averages over the first argument's words, so check both ways to reject subsets.
+        let score = fuzzy::similarity(text, &candidate).min(fuzzy::similarity(&candidate, text));
+        if score >= NEAR_DUPLICATE_THRESHOLD && best.as_ref().is_none_or(|best| score > best.score)
+        {
+            best = Some(DuplicateMatch {
+                item_id: format_item_id(item_id),
+                created_at,
+                text: candidate,
+                score,
+            });
+        }
+    }
+    Ok(best)
+}
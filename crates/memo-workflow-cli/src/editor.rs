@@ -0,0 +1,114 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::AppError;
+
+/// Fallback when neither `MEMO_EDITOR`, `VISUAL`, nor `EDITOR` is set.
+pub(crate) const DEFAULT_EDITOR: &str = if cfg!(target_os = "macos") {
+    "open -W -n -t"
+} else {
+    "vi"
+};
+
+/// Open `text` in `editor` and return the saved text when it differs from the original.
+///
+/// The editor command must block until editing is done (`code --wait`, `open -W ...`).
+pub(crate) fn edit_text(
+    editor: &str,
+    item_id: &str,
+    text: &str,
+) -> Result<Option<String>, AppError> {
+    let mut args = split_command_line(editor).into_iter();
+    let program = args
+        .next()
+        .ok_or_else(|| AppError::User("MEMO_EDITOR must be non-empty".to_string()))?;
+
+    let path = temp_file_path(item_id);
+    fs::write(&path, text).map_err(|error| {
+        AppError::Runtime(format!(
+            "failed to write edit file {}: {error}",
+            path.display()
+        ))
+    })?;
+
+    let status = Command::new(&program).args(args).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    let status = status.map_err(|error| {
+        AppError::Runtime(format!("failed to launch editor {program}: {error}"))
+    })?;
+    if !status.success() {
+        return Err(AppError::Runtime(format!(
+            "editor {program} exited with {status}"
+        )));
+    }
+    let edited = edited.map_err(|error| {
+        AppError::Runtime(format!(
+            "failed to read edit file {}: {error}",
+            path.display()
+        ))
+    })?;
+
+    if edited.trim() == text.trim() {
+        return Ok(None);
+    }
+    Ok(Some(edited.trim().to_string()))
+}
+
+fn temp_file_path(item_id: &str) -> PathBuf {
+    env::temp_dir().join(format!("memo-edit-{item_id}-{}.md", std::process::id()))
+}
+
+/// Split an editor command on whitespace, honoring single and double quotes.
+pub(crate) fn split_command_line(raw: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut has_part = false;
+
+    for ch in raw.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                has_part = true;
+            }
+            None if ch.is_whitespace() => {
+                if has_part {
+                    parts.push(std::mem::take(&mut current));
+                    has_part = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                has_part = true;
+            }
+        }
+    }
+    if has_part {
+        parts.push(current);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_line_honors_quotes() {
+        assert_eq!(
+            split_command_line("open -W -a 'Sublime Text'"),
+            vec!["open", "-W", "-a", "Sublime Text"]
+        );
+        assert_eq!(
+            split_command_line(r#"  "/Applications/My Editor/bin/ed" --wait ''"#),
+            vec!["/Applications/My Editor/bin/ed", "--wait", ""]
+        );
+        assert!(split_command_line("   ").is_empty());
+    }
+}
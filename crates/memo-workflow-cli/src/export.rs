@@ -0,0 +1,254 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::crypto::TextCodec;
+use crate::pins::IS_PINNED_SQL;
+use crate::tags::list_item_tags;
+use crate::trash::NOT_TRASHED_SQL;
+use crate::{ExportFilter, ExportFormat};
+
+pub(crate) const EXPORT_SCHEMA_VERSION: &str = "memo-export@v1";
+pub(crate) const CSV_COLUMNS: [&str; 10] = [
+    "item_id",
+    "created_at",
+    "source",
+    "state",
+    "tags",
+    "is_pinned",
+    "deleted_at",
+    "summary",
+    "category",
+    "text",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ExportRecord {
+    pub item_id: String,
+    pub created_at: String,
+    pub source: String,
+    pub state: String,
+    pub tags: Vec<String>,
+    pub is_pinned: bool,
+    pub deleted_at: Option<String>,
+    pub summary: Option<String>,
+    pub category: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ExportDocument<'a> {
+    schema_version: &'static str,
+    exported_at: &'a str,
+    items: &'a [ExportRecord],
+}
+
+/// Oldest-first memo rows matching `filter`, with tags and active derivation fields.
+pub(crate) fn load_records(
+    conn: &Connection,
+    filter: &ExportFilter,
+    codec: &TextCodec,
+) -> Result<Vec<ExportRecord>, MemoCliError> {
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            i.source,
+            case when d.derivation_id is null then 'pending' else 'enriched' end as state,
+            {IS_PINNED_SQL} as is_pinned,
+            (select tr.deleted_at from workflow_item_trash tr where tr.item_id = i.item_id)
+                as deleted_at,
+            d.summary,
+            d.category,
+            i.raw_text
+        from inbox_items i
+        left join item_derivations d
+            on d.item_id = i.item_id and d.is_active = 1 and d.status = 'accepted'
+        where (?1 is null or exists (
+                select 1 from workflow_item_tags t where t.item_id = i.item_id and t.tag_name = ?1
+            ))
+          and (?2 = 1 or {NOT_TRASHED_SQL})
+        order by i.created_at asc, i.item_id asc"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map((filter.tag.as_deref(), filter.include_trash), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                ExportRecord {
+                    item_id: format_item_id(row.get::<_, i64>(0)?),
+                    created_at: row.get(1)?,
+                    source: row.get(2)?,
+                    state: row.get(3)?,
+                    tags: Vec::new(),
+                    is_pinned: row.get(4)?,
+                    deleted_at: row.get(5)?,
+                    summary: row.get(6)?,
+                    category: row.get(7)?,
+                    text: row.get(8)?,
+                },
+            ))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+
+    let mut records = Vec::with_capacity(rows.len());
+    for (item_id, mut record) in rows {
+        record.tags = list_item_tags(conn, item_id)?;
+        record.text = codec.open(&record.text)?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Compact UTC stamp used in default export file names.
+pub(crate) fn file_stamp(conn: &Connection) -> Result<String, MemoCliError> {
+    conn.query_row("select strftime('%Y%m%d-%H%M%S', 'now')", [], |row| {
+        row.get(0)
+    })
+    .map_err(MemoCliError::db_query)
+}
+
+pub(crate) fn render(format: ExportFormat, exported_at: &str, records: &[ExportRecord]) -> String {
+    match format {
+        ExportFormat::Json => render_json(exported_at, records),
+        ExportFormat::Markdown => render_markdown(exported_at, records),
+        ExportFormat::Csv => render_csv(records),
+    }
+}
+
+fn render_json(exported_at: &str, records: &[ExportRecord]) -> String {
+    let document = ExportDocument {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        exported_at,
+        items: records,
+    };
+    let mut output =
+        serde_json::to_string_pretty(&document).unwrap_or_else(|_| r#"{"items":[]}"#.to_string());
+    output.push('\n');
+    output
+}
+
+fn render_markdown(exported_at: &str, records: &[ExportRecord]) -> String {
+    let mut output = format!(
+        "# Memo export\n\nExported at {exported_at} ({} memos).\n",
+        records.len()
+    );
+    for record in records {
+        output.push_str(&format!("\n## {}\n\n", record.item_id));
+        output.push_str(&format!("- created_at: {}\n", record.created_at));
+        output.push_str(&format!("- source: {}\n", record.source));
+        output.push_str(&format!("- state: {}\n", record.state));
+        if !record.tags.is_empty() {
+            let tags = record
+                .tags
+                .iter()
+                .map(|tag| format!("#{tag}"))
+                .collect::<Vec<_>>();
+            output.push_str(&format!("- tags: {}\n", tags.join(" ")));
+        }
+        if record.is_pinned {
+            output.push_str("- pinned: true\n");
+        }
+        if let Some(deleted_at) = &record.deleted_at {
+            output.push_str(&format!("- deleted_at: {deleted_at}\n"));
+        }
+        if let Some(summary) = &record.summary {
+            output.push_str(&format!("- summary: {}\n", single_line(summary)));
+        }
+        if let Some(category) = &record.category {
+            output.push_str(&format!("- category: {}\n", single_line(category)));
+        }
+        output.push('\n');
+        output.push_str(record.text.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+fn render_csv(records: &[ExportRecord]) -> String {
+    let mut output = CSV_COLUMNS.join(",");
+    output.push('\n');
+    for record in records {
+        let fields = [
+            record.item_id.as_str(),
+            record.created_at.as_str(),
+            record.source.as_str(),
+            record.state.as_str(),
+            &record.tags.join(","),
+            if record.is_pinned { "true" } else { "false" },
+            record.deleted_at.as_deref().unwrap_or(""),
+            record.summary.as_deref().unwrap_or(""),
+            record.category.as_deref().unwrap_or(""),
+            record.text.as_str(),
+        ];
+        let line = fields
+            .iter()
+            .map(|field| csv_field(field))
+            .collect::<Vec<_>>()
+            .join(",");
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+/// Quote one CSV field per RFC 4180 when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(text: &str, tags: &[&str]) -> ExportRecord {
+        ExportRecord {
+            item_id: "itm_00000001".to_string(),
+            created_at: "2026-02-12T10:00:00.000Z".to_string(),
+            source: "alfred".to_string(),
+            state: "pending".to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            is_pinned: false,
+            deleted_at: None,
+            summary: None,
+            category: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn csv_quotes_delimiters_quotes_and_newlines() {
+        let output = render_csv(&[record("say \"hi\",\nthen leave", &["work", "home"])]);
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some(CSV_COLUMNS.join(",").as_str()));
+        assert_eq!(
+            output.split_once('\n').map(|(_, rows)| rows),
+            Some(
+                "itm_00000001,2026-02-12T10:00:00.000Z,alfred,pending,\"work,home\",false,,,,\"say \"\"hi\"\",\nthen leave\"\n"
+            )
+        );
+    }
+
+    #[test]
+    fn markdown_lists_metadata_before_memo_text() {
+        let output = render_markdown("2026-02-12T11:00:00.000Z", &[record("buy milk", &["home"])]);
+        assert!(
+            output
+                .starts_with("# Memo export\n\nExported at 2026-02-12T11:00:00.000Z (1 memos).\n")
+        );
+        assert!(output.contains(
+            "## itm_00000001\n\n- created_at: 2026-02-12T10:00:00.000Z\n- source: alfred\n- state: pending\n- tags: #home\n\nbuy milk\n"
+        ));
+    }
+}
@@ -0,0 +1,383 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::types::Value;
+use rusqlite::{Connection, params_from_iter};
+
+use crate::SearchResult;
+use crate::crypto::TextCodec;
+use crate::search_filters::{self, SearchFilters};
+use crate::trash::{self, NOT_TRASHED_SQL};
+
+/// Ledger version recorded in `workflow_schema_migrations` once the search index exists.
+pub(crate) const SEARCH_INDEX_VERSION: i64 = 6;
+const MATCHED_FIELDS: [&str; 3] = ["raw_text", "derived_text", "tags_text"];
+/// BM25 column weights for `raw_text`, `derived_text`, `tags_text`; tag hits rank highest.
+const BM25_WEIGHTS: &str = "1.0, 0.5, 2.0";
+const PREVIEW_CHARS: usize = 120;
+
+/// Statements re-deriving the index row of one item from upstream search documents plus workflow tags.
+fn refresh_row_sql(item_expr: &str) -> String {
+    format!(
+        "delete from workflow_search_fts where rowid = {item_expr};
+        insert into workflow_search_fts(rowid, raw_text, derived_text, tags_text)
+        select d.item_id, d.raw_text, d.derived_text,
+            trim(d.tags_text || ' ' || coalesce((
+                select group_concat(t.tag_name, ' ') from workflow_item_tags t
+                where t.item_id = d.item_id
+            ), ''))
+        from item_search_documents d
+        where d.item_id = {item_expr};"
+    )
+}
+
+/// Workflow-owned FTS5 index: created, wired to triggers, and backfilled in one batch.
+fn search_index_sql() -> String {
+    format!(
+        "create virtual table if not exists workflow_search_fts using fts5(
+            raw_text,
+            derived_text,
+            tags_text,
+            tokenize='unicode61 remove_diacritics 2 tokenchars ''-_'''
+        );
+        create trigger if not exists trg_workflow_search_documents_ai
+        after insert on item_search_documents
+        begin
+            {insert_new}
+        end;
+        create trigger if not exists trg_workflow_search_documents_au
+        after update on item_search_documents
+        begin
+            {insert_new}
+        end;
+        create trigger if not exists trg_workflow_search_documents_ad
+        after delete on item_search_documents
+        begin
+            delete from workflow_search_fts where rowid = old.item_id;
+        end;
+        create trigger if not exists trg_workflow_search_tags_ai
+        after insert on workflow_item_tags
+        begin
+            {insert_new}
+        end;
+        create trigger if not exists trg_workflow_search_tags_ad
+        after delete on workflow_item_tags
+        begin
+            {refresh_old}
+        end;
+        delete from workflow_search_fts;
+        insert into workflow_search_fts(rowid, raw_text, derived_text, tags_text)
+        select d.item_id, d.raw_text, d.derived_text,
+            trim(d.tags_text || ' ' || coalesce((
+                select group_concat(t.tag_name, ' ') from workflow_item_tags t
+                where t.item_id = d.item_id
+            ), ''))
+        from item_search_documents d;",
+        insert_new = refresh_row_sql("new.item_id"),
+        refresh_old = refresh_row_sql("old.item_id"),
+    )
+}
+
+/// Create and backfill the search index once, returning `false` when SQLite lacks FTS5.
+pub(crate) fn apply_search_index(conn: &Connection) -> Result<bool, MemoCliError> {
+    let applied: i64 = conn
+        .query_row(
+            "select count(*) from workflow_schema_migrations where version = ?1",
+            [SEARCH_INDEX_VERSION],
+            |row| row.get(0),
+        )
+        .map_err(MemoCliError::db_query)?;
+    if applied > 0 {
+        return Ok(true);
+    }
+
+    match conn.execute_batch(&search_index_sql()) {
+        Ok(()) => {}
+        Err(error) if error.to_string().contains("no such module: fts5") => return Ok(false),
+        Err(error) => return Err(MemoCliError::db_write(error)),
+    }
+    conn.execute(
+        "insert into workflow_schema_migrations(version) values(?1)",
+        [SEARCH_INDEX_VERSION],
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(true)
+}
+
+pub(crate) fn index_available(conn: &Connection) -> Result<bool, MemoCliError> {
+    conn.query_row(
+        "select exists(select 1 from sqlite_master where name = 'workflow_search_fts')",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(MemoCliError::db_query)
+}
+
+/// One parsed search term: a word or phrase, optionally a prefix match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QueryTerm {
+    text: String,
+    prefix: bool,
+}
+
+/// Parse user input into OR-separated groups of AND-ed terms.
+///
+/// `"quoted phrases"` stay phrases, a trailing `*` makes a term (or phrase) a prefix match, a bare
+/// uppercase `OR` between terms separates groups, and a leading `#` is dropped so tag markers match
+/// stored tags. `prefix_all` turns every term into a prefix match.
+fn parse_query(raw: &str, prefix_all: bool) -> Vec<Vec<QueryTerm>> {
+    let mut groups: Vec<Vec<QueryTerm>> = vec![Vec::new()];
+    let mut chars = raw.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let (text, quoted) = if ch == '"' {
+            chars.next();
+            let phrase = chars
+                .by_ref()
+                .take_while(|next| *next != '"')
+                .collect::<String>();
+            (phrase, true)
+        } else {
+            let mut word = String::new();
+            while let Some(&next) = chars.peek() {
+                if next.is_whitespace() || next == '"' {
+                    break;
+                }
+                word.push(next);
+                chars.next();
+            }
+            (word, false)
+        };
+
+        let mut prefix = prefix_all;
+        let term = if quoted {
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                prefix = true;
+            }
+            text.trim().to_string()
+        } else {
+            if text == "OR" {
+                if groups.last().is_some_and(|group| !group.is_empty()) {
+                    groups.push(Vec::new());
+                }
+                continue;
+            }
+            let stripped = text.trim_start_matches('#');
+            if stripped.ends_with('*') {
+                prefix = true;
+            }
+            stripped.trim_end_matches('*').to_string()
+        };
+        if term.is_empty() {
+            continue;
+        }
+        if let Some(group) = groups.last_mut() {
+            group.push(QueryTerm { text: term, prefix });
+        }
+    }
+
+    groups.retain(|group| !group.is_empty());
+    groups
+}
+
+/// Compile user input into a safe FTS5 match expression.
+///
+/// Terms follow `parse_query`; every term is quoted so FTS5 syntax characters are matched literally,
+/// terms are implicitly AND-ed, and groups are joined with `OR`.
+/// Returns `None` when no searchable term remains.
+pub(crate) fn compile_query(raw: &str, prefix_all: bool) -> Option<String> {
+    let groups = parse_query(raw, prefix_all);
+    let parts = groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|term| {
+                    let escaped = term.text.replace('"', "\"\"");
+                    if term.prefix {
+                        format!("\"{escaped}\"*")
+                    } else {
+                        format!("\"{escaped}\"")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+
+    (!parts.is_empty()).then(|| parts.join(" OR "))
+}
+
+/// Lowercased words split like the index tokenizer (letters, digits, `-`, `_`).
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|ch: char| !(ch.is_alphanumeric() || ch == '-' || ch == '_'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether `words` contain the term's words consecutively (the last one as a prefix when requested).
+fn term_matches(term: &QueryTerm, words: &[String]) -> bool {
+    let needle = tokenize(&term.text);
+    let Some((last, leading)) = needle.split_last() else {
+        return false;
+    };
+    words.windows(needle.len()).any(|window| {
+        window
+            .split_last()
+            .is_some_and(|(window_last, window_leading)| {
+                window_leading == leading
+                    && if term.prefix {
+                        window_last.starts_with(last.as_str())
+                    } else {
+                        window_last == last
+                    }
+            })
+    })
+}
+
+/// Search for encrypted databases, where the FTS5 index only holds ciphertext.
+///
+/// Every live memo is decrypted and tokenized in-process, then matched with the same query syntax as
+/// the index (terms, phrases, `*` prefixes, `OR`) against memo text and tags. Rows carry no rank
+/// (`score` 0) and stay newest first. Memos rejected by `filters` are skipped before matching.
+pub(crate) fn scan_decrypted(
+    conn: &Connection,
+    codec: &TextCodec,
+    raw_query: &str,
+    prefix_all: bool,
+    filters: &SearchFilters,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<SearchResult>, MemoCliError> {
+    let groups = parse_query(raw_query, prefix_all);
+    if groups.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let excluded = search_filters::excluded_item_ids(conn, filters)?;
+    let rows = trash::list_live_texts(conn, codec, None)?;
+    Ok(rows
+        .into_iter()
+        .filter(|row| !excluded.contains(&row.item_id))
+        .filter_map(|row| {
+            let text_words = tokenize(&row.text);
+            let tag_words = tokenize(&row.tags_text);
+            let group = groups.iter().find(|group| {
+                group
+                    .iter()
+                    .all(|term| term_matches(term, &text_words) || term_matches(term, &tag_words))
+            })?;
+
+            let mut matched_fields = Vec::new();
+            if group.iter().any(|term| term_matches(term, &text_words)) {
+                matched_fields.push("raw_text".to_string());
+            }
+            if group.iter().any(|term| term_matches(term, &tag_words)) {
+                matched_fields.push("tags_text".to_string());
+            }
+            Some(SearchResult {
+                item_id: format_item_id(row.item_id),
+                created_at: row.created_at,
+                score: 0.0,
+                matched_fields,
+                text_preview: row.text.chars().take(PREVIEW_CHARS).collect(),
+                content_type: None,
+                validation_status: None,
+                approximate: false,
+            })
+        })
+        .skip(offset)
+        .take(limit)
+        .collect())
+}
+
+/// BM25-ranked live memos matching a compiled expression and `filters` (best first).
+pub(crate) fn search(
+    conn: &Connection,
+    match_expr: &str,
+    filters: &SearchFilters,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<SearchResult>, MemoCliError> {
+    let (predicate, params) = filters.predicate_sql(4);
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            bm25(workflow_search_fts, {BM25_WEIGHTS}) as score,
+            substr(coalesce(nullif(f.derived_text, ''), i.raw_text), 1, 120) as preview,
+            json_extract(ad.payload_json, '$.content_type') as content_type,
+            json_extract(ad.payload_json, '$.validation_status') as validation_status
+        from workflow_search_fts f
+        join inbox_items i on i.item_id = f.rowid
+        left join item_derivations ad
+          on ad.derivation_id = (
+            select d.derivation_id
+            from item_derivations d
+            where d.item_id = i.item_id
+              and d.is_active = 1
+              and d.status = 'accepted'
+            order by d.derivation_version desc, d.derivation_id desc
+            limit 1
+          )
+        where workflow_search_fts match ?1
+          and {NOT_TRASHED_SQL}
+          and {predicate}
+        order by score asc, i.created_at desc, i.item_id desc
+        limit ?2 offset ?3"
+    );
+    let mut values = vec![
+        Value::Text(match_expr.to_string()),
+        Value::Integer(limit as i64),
+        Value::Integer(offset as i64),
+    ];
+    values.extend(params.into_iter().map(Value::Text));
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok(SearchResult {
+                item_id: format_item_id(row.get::<_, i64>(0)?),
+                created_at: row.get(1)?,
+                score: row.get(2)?,
+                matched_fields: MATCHED_FIELDS
+                    .iter()
+                    .map(|field| field.to_string())
+                    .collect(),
+                text_preview: row.get(3)?,
+                content_type: row.get(4)?,
+                validation_status: row.get(5)?,
+                approximate: false,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_query_quotes_terms_and_keeps_phrases_prefixes_and_or() {
+        assert_eq!(
+            compile_query(r#"buy "oat milk" groc* OR #home"#, false).as_deref(),
+            Some(r#""buy" "oat milk" "groc"* OR "home""#)
+        );
+        assert_eq!(
+            compile_query("a:b (c) NEAR", true).as_deref(),
+            Some(r#""a:b"* "(c)"* "NEAR"*"#)
+        );
+        assert_eq!(
+            compile_query(r#""half open"* OR"#, false).as_deref(),
+            Some(r#""half open"*"#)
+        );
+        assert_eq!(compile_query(r#"  "" # * "#, false), None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+
+use crate::SearchResult;
+use crate::crypto::TextCodec;
+use crate::trash::{self, LiveText};
+
+/// Number of newest live memos scanned by the fuzzy pass.
+pub(crate) const FUZZY_SCAN_LIMIT: usize = 500;
+const PREVIEW_CHARS: usize = 120;
+
+/// Similarity of `query` to `text` in `0.0..=1.0`.
+///
+/// Every query word is paired with its closest text word, and the per-word scores are averaged. Two
+/// words score the higher of their trigram Jaccard overlap and their normalized edit similarity
+/// (optimal string alignment, so `mlik` vs `milk` counts as one transposition).
+pub(crate) fn similarity(query: &str, text: &str) -> f64 {
+    let query_words = words(query);
+    let text_words = words(text);
+    if query_words.is_empty() || text_words.is_empty() {
+        return 0.0;
+    }
+
+    let total: f64 = query_words
+        .iter()
+        .map(|query_word| {
+            text_words
+                .iter()
+                .map(|text_word| word_similarity(query_word, text_word))
+                .fold(0.0, f64::max)
+        })
+        .sum();
+    total / query_words.len() as f64
+}
+
+fn words(text: &str) -> Vec<Vec<char>> {
+    text.split(|ch: char| !(ch.is_alphanumeric() || ch == '-' || ch == '_'))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase().chars().collect())
+        .collect()
+}
+
+fn word_similarity(a: &[char], b: &[char]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    trigram_similarity(a, b).max(edit_similarity(a, b))
+}
+
+/// Trigrams of one word padded like `pg_trgm` (two leading blanks, one trailing blank).
+fn trigrams(word: &[char]) -> HashSet<[char; 3]> {
+    let padded: Vec<char> = [' ', ' ']
+        .into_iter()
+        .chain(word.iter().copied())
+        .chain([' '])
+        .collect();
+    padded
+        .windows(3)
+        .map(|window| [window[0], window[1], window[2]])
+        .collect()
+}
+
+fn trigram_similarity(a: &[char], b: &[char]) -> f64 {
+    let left = trigrams(a);
+    let right = trigrams(b);
+    let shared = left.intersection(&right).count();
+    let union = left.len() + right.len() - shared;
+    if union == 0 {
+        return 0.0;
+    }
+    shared as f64 / union as f64
+}
+
+fn edit_similarity(a: &[char], b: &[char]) -> f64 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - osa_distance(a, b) as f64 / longest as f64
+}
+
+/// Levenshtein distance that also counts one adjacent transposition as a single edit.
+fn osa_distance(a: &[char], b: &[char]) -> usize {
+    let width = b.len() + 1;
+    let mut table = vec![0usize; (a.len() + 1) * width];
+    for (i, cell) in table.iter_mut().step_by(width).enumerate() {
+        *cell = i;
+    }
+    for (j, cell) in table.iter_mut().take(width).enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (table[(i - 1) * width + j] + 1)
+                .min(table[i * width + j - 1] + 1)
+                .min(table[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(table[(i - 2) * width + j - 2] + 1);
+            }
+            table[i * width + j] = best;
+        }
+    }
+    table[a.len() * width + b.len()]
+}
+
+/// Approximate matches among the newest live memos, closest first, flagged `approximate`.
+///
+/// `threshold` is the minimum similarity percentage (`1..=100`) a memo needs to be returned.
+pub(crate) fn fuzzy_search(
+    conn: &Connection,
+    query: &str,
+    threshold: u32,
+    limit: usize,
+    codec: &TextCodec,
+) -> Result<Vec<SearchResult>, MemoCliError> {
+    let rows = trash::list_live_texts(conn, codec, Some(FUZZY_SCAN_LIMIT))?;
+
+    let minimum = f64::from(threshold) / 100.0;
+    let mut matches: Vec<SearchResult> = rows
+        .into_iter()
+        .filter_map(
+            |LiveText {
+                 item_id,
+                 created_at,
+                 text,
+                 tags_text,
+             }| {
+                let score = similarity(query, &format!("{text} {tags_text}"));
+                (score >= minimum).then(|| SearchResult {
+                    item_id: format_item_id(item_id),
+                    created_at,
+                    score,
+                    matched_fields: vec!["raw_text".to_string()],
+                    text_preview: text.chars().take(PREVIEW_CHARS).collect(),
+                    content_type: None,
+                    validation_status: None,
+                    approximate: true,
+                })
+            },
+        )
+        .collect();
+    // Stable sort keeps newest-first order among equal scores.
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches.truncate(limit);
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similarity_tolerates_typos_and_rejects_unrelated_text() {
+        assert_eq!(similarity("milk", "buy oat milk"), 1.0);
+        assert!(similarity("mlik", "buy oat milk") >= 0.75);
+        assert!(similarity("plumbr apointment", "call plumber, appointment at 3") > 0.8);
+        assert!(similarity("Grocery", "groceries list") > 0.6);
+        assert!(similarity("zebra", "buy oat milk") < 0.3);
+        assert_eq!(similarity("  ", "buy oat milk"), 0.0);
+        assert_eq!(osa_distance(&['a', 'b'], &['b', 'a']), 1);
+        assert_eq!(osa_distance(&[], &['x', 'y']), 2);
+    }
+}
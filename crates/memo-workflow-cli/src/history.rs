@@ -0,0 +1,162 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::VersionResult;
+use crate::crypto::TextCodec;
+
+/// Versions kept per memo; older ones are dropped as new versions are recorded.
+pub(crate) const MAX_VERSIONS_PER_ITEM: i64 = 50;
+
+/// SQL expression counting stored versions of the `inbox_items` row aliased as `i`.
+pub(crate) const VERSION_COUNT_SQL: &str =
+    "(select count(*) from workflow_item_versions v where v.item_id = i.item_id)";
+
+/// Store the stored (possibly sealed) text an update is about to replace, keeping the newest versions only.
+pub(crate) fn record_version(
+    conn: &Connection,
+    item_id: i64,
+    stored_text: &str,
+) -> Result<(), MemoCliError> {
+    conn.execute(
+        "insert into workflow_item_versions(item_id, text) values (?1, ?2)",
+        (item_id, stored_text),
+    )
+    .map_err(MemoCliError::db_write)?;
+    conn.execute(
+        "delete from workflow_item_versions
+        where item_id = ?1
+          and version_id not in (
+            select version_id from workflow_item_versions
+            where item_id = ?1
+            order by version_id desc
+            limit ?2
+          )",
+        (item_id, MAX_VERSIONS_PER_ITEM),
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(())
+}
+
+/// Versions of one memo, newest first, numbered from `1` for the oldest kept version.
+pub(crate) fn list_versions(
+    conn: &Connection,
+    codec: &TextCodec,
+    item_id: i64,
+) -> Result<Vec<VersionResult>, MemoCliError> {
+    let mut stmt = conn
+        .prepare(
+            "select version_id, item_id, text, replaced_at
+            from workflow_item_versions
+            where item_id = ?1
+            order by version_id desc",
+        )
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([item_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+
+    let total = rows.len();
+    rows.into_iter()
+        .enumerate()
+        .map(|(index, (version_id, item_id, text, replaced_at))| {
+            Ok(VersionResult {
+                version_id,
+                item_id: format_item_id(item_id),
+                version: total - index,
+                text: codec.open(&text)?,
+                replaced_at,
+            })
+        })
+        .collect()
+}
+
+/// Item id and stored text of one version.
+pub(crate) fn find_version(
+    conn: &Connection,
+    version_id: i64,
+) -> Result<Option<(i64, String)>, MemoCliError> {
+    conn.query_row(
+        "select item_id, text from workflow_item_versions where version_id = ?1",
+        [version_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+/// Word-level diff from `old` to `new` rendered as `-removed +added` runs, unchanged words elided.
+///
+/// An empty string means the texts hold the same words.
+pub(crate) fn diff_preview(old: &str, new: &str) -> String {
+    let old_words = old.split_whitespace().collect::<Vec<_>>();
+    let new_words = new.split_whitespace().collect::<Vec<_>>();
+
+    // Longest-common-subsequence table over word suffixes.
+    let mut lcs = vec![vec![0usize; new_words.len() + 1]; old_words.len() + 1];
+    for i in (0..old_words.len()).rev() {
+        for j in (0..new_words.len()).rev() {
+            lcs[i][j] = if old_words[i] == new_words[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // Runs of consecutive edits with the same sign; an unchanged word closes the current run.
+    let mut runs: Vec<(char, Vec<&str>)> = Vec::new();
+    let mut open_run = false;
+    let (mut i, mut j) = (0, 0);
+    while i < old_words.len() || j < new_words.len() {
+        let (sign, word) =
+            if i < old_words.len() && j < new_words.len() && old_words[i] == new_words[j] {
+                i += 1;
+                j += 1;
+                open_run = false;
+                continue;
+            } else if j < new_words.len() && (i == old_words.len() || lcs[i][j + 1] > lcs[i + 1][j])
+            {
+                j += 1;
+                ('+', new_words[j - 1])
+            } else {
+                i += 1;
+                ('-', old_words[i - 1])
+            };
+        match runs.last_mut() {
+            Some((last_sign, words)) if open_run && *last_sign == sign => words.push(word),
+            _ => runs.push((sign, vec![word])),
+        }
+        open_run = true;
+    }
+
+    runs.into_iter()
+        .map(|(sign, words)| format!("{sign}{}", words.join(" ")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_preview_groups_removed_and_added_words() {
+        assert_eq!(
+            diff_preview("buy milk and eggs", "buy oat milk and bread"),
+            "+oat -eggs +bread"
+        );
+        assert_eq!(diff_preview("call mom", "call  mom"), "");
+        assert_eq!(diff_preview("", "new text"), "+new text");
+        assert_eq!(diff_preview("old words here", "here"), "-old words");
+    }
+}
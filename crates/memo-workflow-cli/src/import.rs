@@ -0,0 +1,428 @@
+use std::collections::HashSet;
+
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use memo::storage::repository;
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::crypto::TextCodec;
+use crate::duplicates;
+use crate::tags::{self, extract_tags, normalize_tag};
+use crate::{AppError, ExportFormat, pins};
+
+const TEXT_KEYS: [&str; 5] = ["text", "content", "body", "raw_text", "note"];
+const CREATED_AT_KEYS: [&str; 6] = [
+    "created_at",
+    "createdAt",
+    "creation_date",
+    "creationDate",
+    "created",
+    "date",
+];
+const MEMO_EXPORT_HEADING: &str = "# Memo export";
+const MARKDOWN_SEPARATOR: &str = "---";
+
+/// One memo parsed from an import file, before validation and dedup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ImportRecord {
+    pub text: String,
+    pub created_at: Option<String>,
+    pub source: Option<String>,
+    pub tags: Vec<String>,
+    pub is_pinned: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ImportOutcome {
+    pub item_ids: Vec<String>,
+    pub skipped: usize,
+    pub invalid: usize,
+}
+
+pub(crate) fn parse_records(
+    format: ExportFormat,
+    contents: &str,
+) -> Result<Vec<ImportRecord>, AppError> {
+    let contents = contents.strip_prefix('\u{feff}').unwrap_or(contents);
+    match format {
+        ExportFormat::Json => parse_json(contents),
+        ExportFormat::Markdown => Ok(parse_markdown(contents)),
+        ExportFormat::Csv => parse_csv_records(contents),
+    }
+}
+
+/// Insert records that are valid and not already stored (by content hash), in file order.
+pub(crate) fn apply_records(
+    conn: &Connection,
+    records: &[ImportRecord],
+    default_source: &str,
+    max_input_bytes: usize,
+    codec: &TextCodec,
+) -> Result<ImportOutcome, MemoCliError> {
+    let mut seen = existing_hashes(conn, codec)?;
+    let mut outcome = ImportOutcome::default();
+
+    for record in records {
+        let text = record.text.trim();
+        if text.is_empty() || text.len() > max_input_bytes {
+            outcome.invalid += 1;
+            continue;
+        }
+        if !seen.insert(codec.content_hash(text)) {
+            outcome.skipped += 1;
+            continue;
+        }
+
+        let source = record
+            .source
+            .as_deref()
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+            .unwrap_or(default_source);
+        let created_at = match record.created_at.as_deref() {
+            Some(raw) => normalize_timestamp(conn, raw)?,
+            None => None,
+        };
+
+        let added = repository::add_item(conn, &codec.seal(text)?, source, created_at.as_deref())?;
+        let flag_tags = record
+            .tags
+            .iter()
+            .filter_map(|tag| normalize_tag(tag))
+            .collect::<Vec<_>>();
+        tags::replace_item_tags(
+            conn,
+            added.item_id,
+            tags::TAG_ORIGIN_TEXT,
+            &extract_tags(text),
+        )?;
+        tags::replace_item_tags(conn, added.item_id, tags::TAG_ORIGIN_FLAG, &flag_tags)?;
+        if record.is_pinned {
+            pins::pin_item(conn, added.item_id)?;
+        }
+        outcome.item_ids.push(format_item_id(added.item_id));
+    }
+
+    Ok(outcome)
+}
+
+/// Content hashes of every stored memo, trashed ones included.
+fn existing_hashes(conn: &Connection, codec: &TextCodec) -> Result<HashSet<String>, MemoCliError> {
+    duplicates::ensure_content_hashes(conn, codec)?;
+    let mut stmt = conn
+        .prepare("select content_hash from workflow_item_hashes")
+        .map_err(MemoCliError::db_query)?;
+    stmt.query_map([], |row| row.get::<_, String>(0))
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+/// Canonical `created_at` for a parsable timestamp (ISO 8601, optional offset); `None` otherwise.
+fn normalize_timestamp(conn: &Connection, raw: &str) -> Result<Option<String>, MemoCliError> {
+    conn.query_row(
+        "select strftime('%Y-%m-%dT%H:%M:%fZ', ?1)",
+        [raw.trim()],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .map_err(MemoCliError::db_query)
+}
+
+fn parse_json(contents: &str) -> Result<Vec<ImportRecord>, AppError> {
+    let value: Value = serde_json::from_str(contents)
+        .map_err(|error| AppError::User(format!("invalid JSON import file: {error}")))?;
+    let entries = match &value {
+        Value::Array(entries) => entries,
+        Value::Object(object) => match object.get("items").or_else(|| object.get("drafts")) {
+            Some(Value::Array(entries)) => entries,
+            _ => {
+                return Err(AppError::User(
+                    "invalid JSON import file: expected an array or an `items` array".to_string(),
+                ));
+            }
+        },
+        _ => {
+            return Err(AppError::User(
+                "invalid JSON import file: expected an array or an `items` array".to_string(),
+            ));
+        }
+    };
+
+    Ok(entries.iter().map(json_record).collect())
+}
+
+fn json_record(entry: &Value) -> ImportRecord {
+    let Some(object) = entry.as_object() else {
+        return ImportRecord {
+            text: entry.as_str().unwrap_or_default().to_string(),
+            ..ImportRecord::default()
+        };
+    };
+    let string_field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| object.get(*key).and_then(Value::as_str))
+            .map(str::to_string)
+    };
+
+    let text = string_field(&TEXT_KEYS)
+        .or_else(|| string_field(&["title"]))
+        .unwrap_or_default();
+    let tags = match object.get("tags") {
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Some(Value::String(raw)) => split_tag_list(raw),
+        _ => Vec::new(),
+    };
+    let is_pinned = ["is_pinned", "pinned", "flagged"]
+        .iter()
+        .any(|key| object.get(*key).and_then(Value::as_bool) == Some(true));
+
+    ImportRecord {
+        text,
+        created_at: string_field(&CREATED_AT_KEYS),
+        source: string_field(&["source"]),
+        tags,
+        is_pinned,
+    }
+}
+
+/// Memo-export Markdown (`## <item_id>` sections), or generic notes separated by `---` lines.
+fn parse_markdown(contents: &str) -> Vec<ImportRecord> {
+    let contents = contents.replace("\r\n", "\n");
+    if contents.trim_start().starts_with(MEMO_EXPORT_HEADING) {
+        return parse_memo_export_markdown(&contents);
+    }
+
+    let mut records = Vec::new();
+    let mut current = Vec::new();
+    for line in contents.lines() {
+        if line.trim() == MARKDOWN_SEPARATOR {
+            records.push(markdown_note(&current));
+            current.clear();
+        } else {
+            current.push(line);
+        }
+    }
+    records.push(markdown_note(&current));
+    records.retain(|record| !record.text.trim().is_empty());
+    records
+}
+
+fn markdown_note(lines: &[&str]) -> ImportRecord {
+    ImportRecord {
+        text: lines.join("\n").trim().to_string(),
+        ..ImportRecord::default()
+    }
+}
+
+fn parse_memo_export_markdown(contents: &str) -> Vec<ImportRecord> {
+    let mut records = Vec::new();
+    let mut current: Option<(ImportRecord, Vec<&str>, bool)> = None;
+
+    for line in contents.lines() {
+        if line.starts_with("## ") {
+            if let Some((record, body, _)) = current.take() {
+                records.push(finish_markdown_record(record, &body));
+            }
+            current = Some((ImportRecord::default(), Vec::new(), true));
+            continue;
+        }
+
+        let Some((record, body, in_metadata)) = current.as_mut() else {
+            continue;
+        };
+        if *in_metadata {
+            if let Some((key, value)) = line
+                .strip_prefix("- ")
+                .and_then(|meta| meta.split_once(": "))
+            {
+                apply_markdown_metadata(record, key.trim(), value.trim());
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            *in_metadata = false;
+        }
+        body.push(line);
+    }
+
+    if let Some((record, body, _)) = current {
+        records.push(finish_markdown_record(record, &body));
+    }
+    records
+}
+
+fn apply_markdown_metadata(record: &mut ImportRecord, key: &str, value: &str) {
+    match key {
+        "created_at" => record.created_at = Some(value.to_string()),
+        "source" => record.source = Some(value.to_string()),
+        "tags" => record.tags = split_tag_list(value),
+        "pinned" => record.is_pinned = value == "true",
+        _ => {}
+    }
+}
+
+fn finish_markdown_record(mut record: ImportRecord, body: &[&str]) -> ImportRecord {
+    record.text = body.join("\n").trim().to_string();
+    record
+}
+
+fn parse_csv_records(contents: &str) -> Result<Vec<ImportRecord>, AppError> {
+    let rows = parse_csv(contents)?;
+    let Some((header, rows)) = rows.split_first() else {
+        return Ok(Vec::new());
+    };
+    let column = |keys: &[&str]| {
+        header
+            .iter()
+            .position(|name| keys.iter().any(|key| name.trim().eq_ignore_ascii_case(key)))
+    };
+    let Some(text_column) = column(&TEXT_KEYS) else {
+        return Err(AppError::User(
+            "invalid CSV import file: header needs a text/content/body column".to_string(),
+        ));
+    };
+    let created_column = column(&CREATED_AT_KEYS);
+    let source_column = column(&["source"]);
+    let tags_column = column(&["tags"]);
+    let pinned_column = column(&["is_pinned", "pinned"]);
+
+    let cell = |row: &[String], index: Option<usize>| {
+        index
+            .and_then(|index| row.get(index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    Ok(rows
+        .iter()
+        .map(|row| ImportRecord {
+            text: row.get(text_column).cloned().unwrap_or_default(),
+            created_at: cell(row, created_column),
+            source: cell(row, source_column),
+            tags: cell(row, tags_column)
+                .map(|raw| split_tag_list(&raw))
+                .unwrap_or_default(),
+            is_pinned: cell(row, pinned_column).as_deref() == Some("true"),
+        })
+        .collect())
+}
+
+/// Minimal RFC 4180 reader: quoted fields may contain delimiters, doubled quotes, and line breaks.
+fn parse_csv(contents: &str) -> Result<Vec<Vec<String>>, AppError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(ch),
+            }
+            continue;
+        }
+
+        match ch {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(ch),
+        }
+    }
+
+    if in_quotes {
+        return Err(AppError::User(
+            "invalid CSV import file: unterminated quoted field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    Ok(rows)
+}
+
+fn split_tag_list(raw: &str) -> Vec<String> {
+    raw.split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_reader_handles_quotes_and_embedded_newlines() {
+        let rows =
+            parse_csv("text,tags\r\n\"say \"\"hi\"\",\nbye\",\"a,b\"\nplain,\n").expect("csv");
+        assert_eq!(
+            rows,
+            vec![
+                vec!["text".to_string(), "tags".to_string()],
+                vec!["say \"hi\",\nbye".to_string(), "a,b".to_string()],
+                vec!["plain".to_string(), String::new()],
+            ]
+        );
+        assert!(parse_csv("text\n\"open").is_err());
+    }
+
+    #[test]
+    fn markdown_reads_memo_export_sections_and_generic_notes() {
+        let export = "# Memo export\n\nExported at x (2 memos).\n\n## itm_00000001\n\n- created_at: 2026-01-02T03:04:05.000Z\n- tags: #work #home\n- pinned: true\n\nfirst line\n\n- not metadata\n\n## itm_00000002\n\n- source: cli\n\nsecond\n";
+        let records = parse_markdown(export);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].text, "first line\n\n- not metadata");
+        assert_eq!(
+            records[0].created_at.as_deref(),
+            Some("2026-01-02T03:04:05.000Z")
+        );
+        assert_eq!(records[0].tags, vec!["#work", "#home"]);
+        assert!(records[0].is_pinned);
+        assert_eq!(records[1].source.as_deref(), Some("cli"));
+
+        let drafts = parse_markdown("Groceries\nmilk\n---\n\n---\nCall mom\n");
+        let texts = drafts
+            .iter()
+            .map(|record| record.text.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, vec!["Groceries\nmilk", "Call mom"]);
+    }
+
+    #[test]
+    fn json_accepts_export_document_and_drafts_style_arrays() {
+        let drafts = parse_json(
+            r#"[{"content":"draft one","created_at":"2025-05-01T08:00:00Z","tags":["a"],"flagged":true},"bare text"]"#,
+        )
+        .expect("drafts json");
+        assert_eq!(drafts[0].text, "draft one");
+        assert!(drafts[0].is_pinned);
+        assert_eq!(drafts[1].text, "bare text");
+
+        let export = parse_json(
+            r#"{"schema_version":"memo-export@v1","items":[{"text":"x","tags":"b,c"}]}"#,
+        )
+        .expect("export json");
+        assert_eq!(export[0].tags, vec!["b", "c"]);
+        assert!(parse_json(r#"{"nope":1}"#).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use memo::errors::AppError as MemoCliError;
+use memo::storage::repository;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::crypto::TextCodec;
+use crate::{extract_tags, tags, trash};
+
+/// Operation recorded in `workflow_operation_journal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JournalOperation {
+    Add,
+    Update,
+    Delete,
+    Merge,
+}
+
+impl JournalOperation {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "add" => Some(Self::Add),
+            "update" => Some(Self::Update),
+            "delete" => Some(Self::Delete),
+            "merge" => Some(Self::Merge),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Merge => "merge",
+        }
+    }
+}
+
+/// One journal row; `before_text` / `after_text` hold the stored (possibly sealed) text around an update or merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JournalEntry {
+    pub(crate) entry_id: i64,
+    pub(crate) operation: JournalOperation,
+    pub(crate) item_id: i64,
+    /// Memo a merge moved to trash; `None` for other operations.
+    pub(crate) merged_item_id: Option<i64>,
+    pub(crate) before_text: Option<String>,
+    pub(crate) after_text: Option<String>,
+    pub(crate) recorded_at: String,
+}
+
+/// `recorded_at` cutoff for a window of `window_minutes`.
+fn cutoff_sql(window_minutes: u32) -> String {
+    format!("strftime('%Y-%m-%dT%H:%M:%fZ', 'now', '-{window_minutes} minutes')")
+}
+
+/// Journal one operation and drop entries that fell out of the undo window; a zero window records nothing.
+pub(crate) fn record(
+    conn: &Connection,
+    operation: JournalOperation,
+    item_id: i64,
+    before_text: Option<&str>,
+    after_text: Option<&str>,
+    window_minutes: u32,
+) -> Result<(), MemoCliError> {
+    insert_entry(
+        conn,
+        operation,
+        item_id,
+        None,
+        before_text,
+        after_text,
+        window_minutes,
+    )
+}
+
+/// Journal a merge of `merged_item_id` into `item_id`, with the kept memo's stored text around the merge.
+pub(crate) fn record_merge(
+    conn: &Connection,
+    item_id: i64,
+    merged_item_id: i64,
+    before_text: &str,
+    after_text: &str,
+    window_minutes: u32,
+) -> Result<(), MemoCliError> {
+    insert_entry(
+        conn,
+        JournalOperation::Merge,
+        item_id,
+        Some(merged_item_id),
+        Some(before_text),
+        Some(after_text),
+        window_minutes,
+    )
+}
+
+fn insert_entry(
+    conn: &Connection,
+    operation: JournalOperation,
+    item_id: i64,
+    merged_item_id: Option<i64>,
+    before_text: Option<&str>,
+    after_text: Option<&str>,
+    window_minutes: u32,
+) -> Result<(), MemoCliError> {
+    if window_minutes == 0 {
+        return Ok(());
+    }
+    conn.execute(
+        &format!(
+            "delete from workflow_operation_journal where recorded_at < {}",
+            cutoff_sql(window_minutes)
+        ),
+        [],
+    )
+    .map_err(MemoCliError::db_write)?;
+    conn.execute(
+        "insert into workflow_operation_journal(
+            operation, item_id, merged_item_id, before_text, after_text
+         )
+         values (?1, ?2, ?3, ?4, ?5)",
+        (
+            operation.as_str(),
+            item_id,
+            merged_item_id,
+            before_text,
+            after_text,
+        ),
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(())
+}
+
+/// Newest journal entry still inside the undo window.
+pub(crate) fn latest_entry(
+    conn: &Connection,
+    window_minutes: u32,
+) -> Result<Option<JournalEntry>, MemoCliError> {
+    if window_minutes == 0 {
+        return Ok(None);
+    }
+    let sql = format!(
+        "select entry_id, operation, item_id, merged_item_id, before_text, after_text, recorded_at
+        from workflow_operation_journal
+        where recorded_at >= {}
+        order by entry_id desc
+        limit 1",
+        cutoff_sql(window_minutes)
+    );
+    let row = conn
+        .query_row(&sql, [], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })
+        .optional()
+        .map_err(MemoCliError::db_query)?;
+    row.map(
+        |(entry_id, operation, item_id, merged_item_id, before_text, after_text, recorded_at)| {
+            let operation = JournalOperation::parse(&operation).ok_or_else(|| {
+                MemoCliError::runtime(format!("unknown journal operation: {operation}"))
+            })?;
+            Ok(JournalEntry {
+                entry_id,
+                operation,
+                item_id,
+                merged_item_id,
+                before_text,
+                after_text,
+                recorded_at,
+            })
+        },
+    )
+    .transpose()
+}
+
+/// Drop every journal entry; used when memo texts are re-encoded and before-images no longer match.
+pub(crate) fn clear(conn: &Connection) -> Result<(), MemoCliError> {
+    conn.execute("delete from workflow_operation_journal", [])
+        .map_err(MemoCliError::db_write)?;
+    Ok(())
+}
+
+/// Stored (possibly sealed) `raw_text` of one memo, used as the before-image of an update.
+pub(crate) fn stored_text(conn: &Connection, item_id: i64) -> Result<Option<String>, MemoCliError> {
+    conn.query_row(
+        "select raw_text from inbox_items where item_id = ?1",
+        [item_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+/// Why `entry` cannot be undone against the current memo state, or `Ok(())` when it can.
+///
+/// Purged memos never reach this point: their journal rows are removed with them.
+pub(crate) fn check_undo(
+    conn: &Connection,
+    entry: &JournalEntry,
+) -> Result<Result<(), &'static str>, MemoCliError> {
+    let trashed = trash::trashed_at(conn, entry.item_id)?.is_some();
+    Ok(match entry.operation {
+        JournalOperation::Add => Ok(()),
+        JournalOperation::Update if trashed => Err("memo is in trash; restore it first"),
+        JournalOperation::Update => {
+            if stored_text(conn, entry.item_id)? == entry.after_text {
+                Ok(())
+            } else {
+                Err("memo text changed since the update")
+            }
+        }
+        JournalOperation::Delete if !trashed => Err("memo is no longer in trash"),
+        JournalOperation::Delete => Ok(()),
+        JournalOperation::Merge if trashed => Err("memo is in trash; restore it first"),
+        JournalOperation::Merge => {
+            let merged_trashed = match entry.merged_item_id {
+                Some(merged_item_id) => trash::trashed_at(conn, merged_item_id)?.is_some(),
+                None => false,
+            };
+            if stored_text(conn, entry.item_id)? != entry.after_text {
+                Err("memo text changed since the merge")
+            } else if !merged_trashed {
+                Err("merged memo is no longer in trash")
+            } else {
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Revert `entry` and remove it from the journal, returning the memo state after the undo.
+///
+/// An add is undone by purging the memo, an update by writing back the previous text (text tags follow it), a
+/// delete by restoring the memo from trash, and a merge by doing both of the last two. Runs on the caller's
+/// transaction.
+pub(crate) fn apply_undo(
+    conn: &Connection,
+    codec: &TextCodec,
+    entry: &JournalEntry,
+) -> Result<&'static str, MemoCliError> {
+    conn.execute(
+        "delete from workflow_operation_journal where entry_id = ?1",
+        [entry.entry_id],
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(match entry.operation {
+        JournalOperation::Add => {
+            repository::delete_item_hard(conn, entry.item_id)?;
+            "purged"
+        }
+        JournalOperation::Update => {
+            revert_text(conn, codec, entry)?;
+            "reverted"
+        }
+        JournalOperation::Delete => {
+            trash::restore_from_trash(conn, entry.item_id)?;
+            "restored"
+        }
+        JournalOperation::Merge => {
+            revert_text(conn, codec, entry)?;
+            let merged_item_id = entry
+                .merged_item_id
+                .ok_or_else(|| MemoCliError::runtime("journal merge entry has no merged item"))?;
+            trash::restore_from_trash(conn, merged_item_id)?;
+            "unmerged"
+        }
+    })
+}
+
+/// Write back the before-image of an update or merge; text tags follow the restored text.
+fn revert_text(
+    conn: &Connection,
+    codec: &TextCodec,
+    entry: &JournalEntry,
+) -> Result<(), MemoCliError> {
+    let before = entry.before_text.as_deref().ok_or_else(|| {
+        MemoCliError::runtime(format!(
+            "journal {} entry has no before-image",
+            entry.operation.as_str()
+        ))
+    })?;
+    repository::update_item(conn, entry.item_id, before)?;
+    let text_tags = extract_tags(&codec.open(before)?);
+    tags::replace_item_tags(conn, entry.item_id, tags::TAG_ORIGIN_TEXT, &text_tags)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::open_storage;
+    use tempfile::tempdir;
+
+    #[test]
+    fn journal_keeps_newest_entry_and_zero_window_records_nothing() {
+        let dir = tempdir().expect("temp dir");
+        let storage = open_storage(dir.path().join("memo.db")).expect("open storage");
+        storage
+            .with_transaction(|tx| {
+                let added = repository::add_item(tx, "first", "test", None)?;
+                record(tx, JournalOperation::Add, added.item_id, None, None, 0)?;
+                assert_eq!(latest_entry(tx, 10)?, None);
+
+                record(tx, JournalOperation::Add, added.item_id, None, None, 10)?;
+                record(
+                    tx,
+                    JournalOperation::Update,
+                    added.item_id,
+                    Some("first"),
+                    Some("second"),
+                    10,
+                )?;
+                let latest = latest_entry(tx, 10)?.expect("latest entry");
+                assert_eq!(latest.operation, JournalOperation::Update);
+                assert_eq!(latest.before_text.as_deref(), Some("first"));
+                assert_eq!(latest_entry(tx, 0)?, None);
+                // Stored text is still "first", so the journaled update no longer matches.
+                assert_eq!(
+                    check_undo(tx, &latest)?,
+                    Err("memo text changed since the update")
+                );
+
+                repository::delete_item_hard(tx, added.item_id)?;
+                assert_eq!(latest_entry(tx, 10)?, None);
+                Ok(())
+            })
+            .expect("journal round trip");
+    }
+}
@@ -1,32 +1,136 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use alfred_core::{Feedback, Item, ItemModifier};
+use alfred_core::{Feedback, Item, ItemIcon, ItemModifier};
+use chrono::{DateTime, Local, Utc};
 use memo::errors::AppError as MemoCliError;
 use memo::output::{format_item_id, parse_item_id};
-use memo::storage::{Storage, repository, search};
+use memo::storage::{Storage, derivations, repository, search};
 use serde::Serialize;
 use thiserror::Error;
 
+mod attachments;
+mod backup;
+mod batch;
+mod clipboard;
+mod crypto;
+mod derive;
+mod due;
+mod duplicates;
+mod editor;
+mod export;
+mod fts;
+mod fuzzy;
+mod history;
+mod import;
+mod journal;
+mod links;
+mod locale;
+mod markdown;
+mod merge;
+mod pins;
+mod relations;
+mod schema;
+mod search_filters;
+mod stats;
+mod sync;
+mod tags;
+mod templates;
+mod trash;
+
+use attachments::ATTACHMENT_COUNT_SQL;
+use crypto::text_codec;
+use history::VERSION_COUNT_SQL;
+pub use links::extract_links;
+pub use locale::Language;
+use locale::Msg;
+use pins::IS_PINNED_SQL;
+use relations::LINKED_COUNT_SQL;
+use schema::open_storage;
+use search_filters::ParsedSearch;
+pub use tags::{extract_tags, normalize_tag, parse_tag_list};
+pub use templates::normalize_template_name;
+
 pub const DB_INIT_TOKEN: &str = "db-init";
+pub const SYNC_TOKEN: &str = "sync";
 pub const ADD_TOKEN_PREFIX: &str = "add::";
+pub const CLIP_TOKEN_PREFIX: &str = "clip::";
 pub const COPY_TOKEN_PREFIX: &str = "copy::";
 pub const COPY_JSON_TOKEN_PREFIX: &str = "copy-json::";
+pub const COPY_MD_TOKEN_PREFIX: &str = "copy-md::";
+pub const COPY_HTML_TOKEN_PREFIX: &str = "copy-html::";
 pub const UPDATE_TOKEN_PREFIX: &str = "update::";
 pub const DELETE_TOKEN_PREFIX: &str = "delete::";
+pub const RESTORE_TOKEN_PREFIX: &str = "restore::";
+pub const PURGE_TOKEN_PREFIX: &str = "purge::";
+pub const PIN_TOKEN_PREFIX: &str = "pin::";
+pub const UNPIN_TOKEN_PREFIX: &str = "unpin::";
+pub const EXPORT_TOKEN_PREFIX: &str = "export::";
+pub const EDIT_TOKEN_PREFIX: &str = "edit::";
+pub const ATTACH_TOKEN_PREFIX: &str = "attach::";
+pub const OPEN_ATTACHMENT_TOKEN_PREFIX: &str = "open-attachment::";
+pub const OPEN_LINK_TOKEN_PREFIX: &str = "open-link::";
+pub const LINK_TOKEN_PREFIX: &str = "link::";
+pub const MERGE_TOKEN_PREFIX: &str = "merge::";
+pub const HISTORY_RESTORE_TOKEN_PREFIX: &str = "history-restore::";
+pub const REMIND_TOKEN_PREFIX: &str = "remind::";
+pub const TEMPLATE_ADD_TOKEN_PREFIX: &str = "template-add::";
+pub const BATCH_TOKEN_PREFIX: &str = "batch::";
+pub const UNDO_TOKEN_PREFIX: &str = "undo::";
+const BATCH_ID_DELIMITER: char = ',';
 const UPDATE_TOKEN_DELIMITER: &str = "::";
 pub const DEFAULT_SOURCE: &str = "alfred";
+/// Source label stored for memos captured with the `clip` intent or command.
+pub const CLIPBOARD_SOURCE: &str = "clipboard";
 pub const DEFAULT_MAX_INPUT_BYTES: usize = 4096;
 pub const DEFAULT_RECENT_LIMIT: usize = 8;
 pub const DEFAULT_SEARCH_MATCH_MODE: SearchMatchMode = SearchMatchMode::Fts;
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+pub const DEFAULT_FUZZY_THRESHOLD: u32 = 60;
+pub const DEFAULT_UNDO_WINDOW_MINUTES: u32 = 10;
+pub const DEFAULT_PREVIEW_CHARS: usize = 56;
 const MAX_INPUT_BYTES_LIMIT: usize = 1024 * 1024;
 const MAX_RECENT_LIMIT: usize = 50;
+const MAX_TRASH_RETENTION_DAYS: u32 = 3650;
+const MAX_FUZZY_THRESHOLD: u32 = 100;
+const MAX_UNDO_WINDOW_MINUTES: u32 = 1440;
+const MIN_PREVIEW_CHARS: usize = 16;
+const MAX_PREVIEW_CHARS: usize = 200;
 const MAX_LIST_LIMIT: usize = 200;
 const MAX_SEARCH_LIMIT: usize = 200;
 const MAX_SEARCH_FETCH_LIMIT: usize = 500;
-const SEARCH_INTENT_USAGE: &str = "Use: search <query> (optional: --match fts|prefix|contains)";
+const SEARCH_INTENT_USAGE: &str =
+    "Use: search <query> (optional: --match fts|prefix|contains, source: before: after: state:)";
 const SEARCH_MATCH_USAGE: &str = "Use: search --match <fts|prefix|contains> <query>";
 const ACTION_TITLE_MAX_CHARS: usize = 84;
+const PINNED_ICON_PATH: &str = "assets/icons/pinned.png";
+const MAX_TAG_LIST_LIMIT: usize = 200;
+pub const DEFAULT_STATS_WEEKS: usize = 8;
+const MAX_BATCH_ITEMS: usize = 200;
+const BATCH_INTENT_USAGE: &str = "Use: batch <delete|restore|purge|pin|unpin> <item_id...|#tag>";
+const MAX_STATS_WEEKS: usize = 52;
+/// Sources and tags listed by `stats`.
+const STATS_TOP_LIMIT: usize = 10;
+const TAG_INTENT_USAGE: &str = "Use: tag <name> (or `tag` alone to browse tags)";
+const EXPORT_INTENT_USAGE: &str = "Use: export [json|md|csv] [#tag]";
+const SYNC_INTENT_USAGE: &str = "Use: sync";
+const STATS_INTENT_USAGE: &str = "Use: stats";
+const ATTACH_INTENT_USAGE: &str = "Use: attach <item_id> <path|url>";
+const MAX_ATTACHMENT_MENU_ROWS: usize = 10;
+const MAX_LINK_MENU_ROWS: usize = 5;
+const LINKS_INTENT_USAGE: &str = "Use: links";
+const LINK_INTENT_USAGE: &str = "Use: link <item_id> <other_item_id>";
+const MERGE_INTENT_USAGE: &str = "Use: merge <keep_item_id> <merge_item_id>";
+const LINKED_INTENT_USAGE: &str = "Use: linked <item_id>";
+const HISTORY_INTENT_USAGE: &str = "Use: history <item_id>";
+const UNDO_INTENT_USAGE: &str = "Use: undo";
+const CLIP_INTENT_USAGE: &str = "Use: clip";
+const REMIND_INTENT_USAGE: &str = "Use: remind <item_id> <tomorrow 9am|YYYY-MM-DD [HH:MM]|clear>";
+const TEMPLATE_INTENT_USAGE: &str = "Use: tpl <name> (or `tpl add <name> <text>` to save one)";
+const TEMPLATE_ADD_USAGE: &str = "Use: tpl add <name> <text with {date} {clipboard} ...>";
+/// Name reserved for the `tpl add` sub-intent.
+const TEMPLATE_ADD_KEYWORD: &str = "add";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeConfig {
@@ -36,6 +140,22 @@ pub struct RuntimeConfig {
     pub max_input_bytes: usize,
     pub recent_limit: usize,
     pub search_match_mode: SearchMatchMode,
+    pub search_fts: bool,
+    pub fuzzy_threshold: u32,
+    pub trash_retention_days: u32,
+    /// `MEMO_UNDO_WINDOW_MINUTES`; `0` disables the undo journal.
+    pub undo_window_minutes: u32,
+    pub export_dir: PathBuf,
+    pub editor: String,
+    pub sync_dir: Option<PathBuf>,
+    /// `MEMO_DERIVE_COMMAND`; `derive` requires it unless `--command` is given.
+    pub derive_command: Option<String>,
+    /// `MEMO_ENCRYPTION_PASSPHRASE`; encrypted databases fall back to the keychain entry when unset.
+    pub encryption_passphrase: Option<String>,
+    /// `MEMO_PREVIEW_CHARS`; memo previews in row titles, with wider previews derived from it.
+    pub preview_chars: usize,
+    /// `MEMO_LANG`; language of script-filter row strings.
+    pub language: Language,
 }
 
 impl RuntimeConfig {
@@ -46,6 +166,19 @@ impl RuntimeConfig {
         let max_input_bytes = resolve_max_input_bytes()?;
         let recent_limit = resolve_recent_limit()?;
         let search_match_mode = resolve_search_match_mode()?;
+        let search_fts = resolve_search_fts()?;
+        let fuzzy_threshold = resolve_fuzzy_threshold()?;
+        let trash_retention_days = resolve_trash_retention_days()?;
+        let undo_window_minutes = resolve_undo_window_minutes()?;
+        let export_dir = resolve_export_dir();
+        let editor = resolve_editor();
+        let sync_dir = resolve_sync_dir();
+        let derive_command = non_empty_env("MEMO_DERIVE_COMMAND");
+        let encryption_passphrase = env::var(crypto::PASSPHRASE_ENV)
+            .ok()
+            .filter(|passphrase| !passphrase.is_empty());
+        let preview_chars = resolve_preview_chars()?;
+        let language = resolve_language()?;
 
         Ok(Self {
             db_path,
@@ -54,8 +187,29 @@ impl RuntimeConfig {
             max_input_bytes,
             recent_limit,
             search_match_mode,
+            search_fts,
+            fuzzy_threshold,
+            trash_retention_days,
+            undo_window_minutes,
+            export_dir,
+            editor,
+            sync_dir,
+            derive_command,
+            encryption_passphrase,
+            preview_chars,
+            language,
         })
     }
+
+    /// Width for single-memo previews such as the add row, 8 characters wider than row titles.
+    fn wide_preview_chars(&self) -> usize {
+        self.preview_chars + 8
+    }
+
+    /// Width for detail subtitles such as diffs, URLs, and raw JSON, 16 characters wider than row titles.
+    fn detail_preview_chars(&self) -> usize {
+        self.preview_chars + 16
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -76,6 +230,102 @@ impl SearchMatchMode {
     }
 }
 
+/// Action applied to every memo of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchAction {
+    Delete,
+    Restore,
+    Purge,
+    Pin,
+    Unpin,
+}
+
+impl BatchAction {
+    pub fn parse_token(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "delete" => Some(Self::Delete),
+            "restore" => Some(Self::Restore),
+            "purge" => Some(Self::Purge),
+            "pin" => Some(Self::Pin),
+            "unpin" => Some(Self::Unpin),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Restore => "restore",
+            Self::Purge => "purge",
+            Self::Pin => "pin",
+            Self::Unpin => "unpin",
+        }
+    }
+}
+
+/// File format shared by `execute_export` and `execute_import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+    Csv,
+}
+
+impl ExportFormat {
+    pub const ALL: [Self; 3] = [Self::Json, Self::Markdown, Self::Csv];
+
+    pub fn parse_token(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "md" | "markdown" => Some(Self::Markdown),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Infer the format from a file extension (`.txt` reads as Markdown notes).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "md" | "markdown" | "txt" => Some(Self::Markdown),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "markdown",
+            Self::Csv => "csv",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "md",
+            Self::Csv => "csv",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Markdown => "Markdown",
+            Self::Csv => "CSV",
+        }
+    }
+}
+
+/// Row selection for `execute_export`; the default exports every live memo.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportFilter {
+    pub tag: Option<String>,
+    pub include_trash: bool,
+}
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("{0}")]
@@ -105,6 +355,7 @@ pub struct AddResult {
     pub created_at: String,
     pub source: String,
     pub text: String,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -115,6 +366,7 @@ pub struct UpdateResult {
     pub state: String,
     pub cleared_derivations: i64,
     pub cleared_workflow_anchors: i64,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -122,13 +374,104 @@ pub struct DeleteResult {
     pub item_id: String,
     pub deleted: bool,
     pub deleted_at: String,
+    pub state: String,
+    pub purge_after: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RestoreResult {
+    pub item_id: String,
+    pub restored: bool,
+    pub restored_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PurgeResult {
+    pub item_id: String,
+    pub purged: bool,
+    pub purged_at: String,
     pub removed_derivations: i64,
     pub removed_workflow_anchors: i64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrashResult {
+    pub item_id: String,
+    pub created_at: String,
+    pub deleted_at: String,
+    pub purge_after: Option<String>,
+    pub text_preview: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UndoResult {
+    pub operation: String,
+    pub item_id: String,
+    pub recorded_at: String,
+    pub undone_at: String,
+    /// Memo state after the undo: `purged` (add), `reverted` (update), or `restored` (delete).
+    pub state: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct InitResult {
     pub db_path: String,
+    pub purged_expired: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchItemResult {
+    pub item_id: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchResult {
+    pub action: String,
+    pub requested: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub items: Vec<BatchItemResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncResult {
+    pub repo: String,
+    pub written: usize,
+    pub removed: usize,
+    pub committed: bool,
+    pub pulled: bool,
+    pub conflicts: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub purged: usize,
+    pub invalid: usize,
+    pub pushed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupResult {
+    pub db_path: String,
+    pub snapshot: String,
+    pub size_bytes: u64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotRestoreResult {
+    pub db_path: String,
+    pub snapshot: String,
+    /// Automatic snapshot of the replaced database, when one existed.
+    pub safety_backup: Option<String>,
+    pub items: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EncryptionResult {
+    pub db_path: String,
+    pub encrypted: bool,
+    pub items: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -137,6 +480,38 @@ pub struct ListResult {
     pub created_at: String,
     pub state: String,
     pub text_preview: String,
+    pub is_pinned: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagCountResult {
+    pub tag: String,
+    pub item_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceCountResult {
+    pub source: String,
+    pub item_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WeekCountResult {
+    pub week_start: String,
+    pub item_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatsResult {
+    pub db_path: String,
+    pub db_size_bytes: u64,
+    pub total: i64,
+    pub live: i64,
+    pub trashed: i64,
+    pub pinned: i64,
+    pub sources: Vec<SourceCountResult>,
+    pub weeks: Vec<WeekCountResult>,
+    pub tags: Vec<TagCountResult>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -148,6 +523,8 @@ pub struct SearchResult {
     pub text_preview: String,
     pub content_type: Option<String>,
     pub validation_status: Option<String>,
+    /// Set on "did you mean" rows from the fuzzy fallback, whose `score` is a similarity in `0..=1`.
+    pub approximate: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -159,6 +536,189 @@ pub struct ItemDetailResult {
     pub state: String,
     pub content_type: Option<String>,
     pub validation_status: Option<String>,
+    pub is_pinned: bool,
+    pub attachment_count: i64,
+    /// Live memos linked to this one with `link`.
+    pub linked_count: i64,
+    /// Earlier texts kept by `update` (see `history`).
+    pub version_count: i64,
+    pub due_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RenderedItemResult {
+    pub item_id: String,
+    /// Sanitized HTML rendered from the memo's Markdown text.
+    pub html: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PinResult {
+    pub item_id: String,
+    pub is_pinned: bool,
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AttachmentResult {
+    pub attachment_id: i64,
+    pub item_id: String,
+    pub kind: String,
+    pub location: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkedMemoResult {
+    pub item_id: String,
+    pub created_at: String,
+    pub text_preview: String,
+    pub links: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkResult {
+    pub item_id: String,
+    pub linked_item_id: String,
+    pub changed: bool,
+    pub linked_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergeResult {
+    pub item_id: String,
+    /// Memo whose text was appended; it is moved to trash.
+    pub merged_item_id: String,
+    pub merged_at: String,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub attachments_added: i64,
+    pub links_added: i64,
+    pub purge_after: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkedItemResult {
+    pub item_id: String,
+    pub created_at: String,
+    pub text_preview: String,
+    pub linked_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionResult {
+    pub version_id: i64,
+    pub item_id: String,
+    /// 1-based position among the kept versions of the memo, oldest first.
+    pub version: usize,
+    pub text: String,
+    /// When an update replaced this text.
+    pub replaced_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HistoryRestoreResult {
+    pub item_id: String,
+    pub version_id: i64,
+    /// `false` when the version already matches the current text.
+    pub changed: bool,
+    pub updated_at: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OpenLinkResult {
+    pub item_id: String,
+    /// 1-based position of the link in the memo text.
+    pub index: usize,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AttachResult {
+    pub item_id: String,
+    pub attachment_id: i64,
+    pub kind: String,
+    pub location: String,
+    pub changed: bool,
+    pub attachment_count: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RemindResult {
+    pub item_id: String,
+    pub due_at: Option<String>,
+    pub status: Option<String>,
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DueResult {
+    pub item_id: String,
+    pub due_at: String,
+    pub status: String,
+    pub text_preview: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateResult {
+    pub name: String,
+    pub body: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateSaveResult {
+    pub name: String,
+    pub body: String,
+    pub created: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateUseResult {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EditResult {
+    pub item_id: String,
+    pub changed: bool,
+    pub updated_at: Option<String>,
+    pub editor: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeriveResult {
+    pub item_id: String,
+    /// `false` when the command returned a payload already stored for this memo.
+    pub changed: bool,
+    pub derivation_version: i64,
+    pub state: String,
+    pub summary: Option<String>,
+    pub category: Option<String>,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub agent_run_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportResult {
+    pub path: String,
+    pub format: String,
+    pub imported: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub item_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub format: String,
+    pub exported: usize,
+    pub exported_at: String,
 }
 
 pub fn build_script_filter(query: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
@@ -179,6 +739,18 @@ pub fn build_script_filter(query: &str, config: &RuntimeConfig) -> Result<Feedba
         return build_delete_feedback(rest, config);
     }
 
+    if let Some(rest) = strip_intent(normalized, "edit") {
+        return build_edit_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "remind") {
+        return build_remind_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "attach") {
+        return build_attach_feedback(rest, config);
+    }
+
     if let Some(rest) = strip_intent(normalized, "copy") {
         return build_copy_feedback(rest, config);
     }
@@ -187,80 +759,428 @@ pub fn build_script_filter(query: &str, config: &RuntimeConfig) -> Result<Feedba
         return build_search_feedback(rest, config);
     }
 
-    if normalized.len() > config.max_input_bytes {
-        return Ok(Feedback::new(vec![
-            Item::new("Input exceeds MEMO_MAX_INPUT_BYTES")
-                .with_subtitle(format!(
-                    "Current {} bytes, limit {} bytes.",
-                    normalized.len(),
-                    config.max_input_bytes
-                ))
-                .with_valid(false),
-        ]));
+    if let Some(rest) = strip_intent(normalized, "tag") {
+        return build_tag_feedback(rest, config);
     }
 
-    let preview = truncate_title(normalized, 64);
-    let add_token = build_add_token(normalized);
+    if let Some(rest) = strip_intent(normalized, "trash") {
+        return build_trash_feedback(rest, config);
+    }
 
-    if config.require_confirm {
-        return Ok(Feedback::new(vec![
-            Item::new(format!("Preview: {preview}"))
-                .with_subtitle("Confirmation required. Choose the row below to save.")
-                .with_valid(false),
-            Item::new("Confirm add memo")
-                .with_subtitle(format!(
-                    "Source: {} | DB: {}",
-                    config.source,
-                    config.db_path.display()
-                ))
-                .with_arg(add_token)
-                .with_valid(true),
-        ]));
+    if let Some(rest) = strip_intent(normalized, "export") {
+        return build_export_feedback(rest, config);
     }
 
-    Ok(Feedback::new(vec![
-        Item::new(format!("Add memo: {preview}"))
-            .with_subtitle(format!(
-                "Press Enter to save ({}/{} bytes).",
-                normalized.len(),
-                config.max_input_bytes
-            ))
-            .with_arg(add_token)
-            .with_valid(true),
-    ]))
-}
+    if let Some(rest) = strip_intent(normalized, "tpl") {
+        return build_template_feedback(rest, config);
+    }
 
-pub fn execute_db_init(
-    db_override: Option<PathBuf>,
-    config: &RuntimeConfig,
-) -> Result<InitResult, AppError> {
-    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path.clone());
-    storage
-        .init()
-        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if let Some(rest) = strip_intent(normalized, "sync") {
+        return Ok(build_sync_feedback(rest, config));
+    }
 
-    Ok(InitResult {
-        db_path: db_path.display().to_string(),
-    })
-}
+    if let Some(rest) = strip_intent(normalized, "stats") {
+        return build_stats_feedback(rest, config);
+    }
 
-pub fn execute_add(
-    text: &str,
-    source_override: Option<&str>,
-    db_override: Option<PathBuf>,
-    config: &RuntimeConfig,
-) -> Result<AddResult, AppError> {
-    let normalized_text = text.trim();
-    if normalized_text.is_empty() {
-        return Err(AppError::User(
-            "add requires a non-empty memo text".to_string(),
-        ));
+    if let Some(rest) = strip_intent(normalized, "batch") {
+        return build_batch_feedback(rest, config);
     }
 
-    if normalized_text.len() > config.max_input_bytes {
-        return Err(AppError::User(format!(
-            "memo text exceeds MEMO_MAX_INPUT_BYTES: {} > {}",
+    if let Some(rest) = strip_intent(normalized, "links") {
+        return build_links_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "merge") {
+        return build_merge_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "link") {
+        return build_link_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "linked") {
+        return build_linked_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "history") {
+        return build_history_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "undo") {
+        return build_undo_feedback(rest, config);
+    }
+
+    if let Some(rest) = strip_intent(normalized, "clip") {
+        return build_clip_feedback(rest, config);
+    }
+
+    build_add_feedback(normalized, config)
+}
+
+fn build_add_feedback(normalized: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let lang = config.language;
+    if normalized.len() > config.max_input_bytes {
+        return Ok(Feedback::new(vec![build_input_too_large_item(
+            normalized.len(),
+            config,
+        )]));
+    }
+
+    let preview = truncate_title(normalized, config.wide_preview_chars());
+    let add_token = build_add_token(normalized);
+    let tag_hint = render_tag_hint(&extract_tags(normalized), lang);
+    let duplicate = find_add_duplicate(normalized, config)?;
+    let warning = duplicate
+        .as_ref()
+        .map(|duplicate| build_duplicate_item(duplicate, config));
+
+    if config.require_confirm {
+        let mut items = vec![
+            Item::new(lang.text(Msg::PreviewTitle, &[&preview]))
+                .with_subtitle(lang.text(Msg::ConfirmRequired, &[]))
+                .with_valid(false),
+        ];
+        items.extend(warning);
+        items.push(
+            Item::new(lang.text(
+                if duplicate.is_some() {
+                    Msg::AddAnyway
+                } else {
+                    Msg::ConfirmAdd
+                },
+                &[],
+            ))
+            .with_subtitle(lang.text(
+                Msg::SourceAndDb,
+                &[&config.source, &config.db_path.display()],
+            ))
+            .with_arg(add_token)
+            .with_valid(true),
+        );
+        return Ok(Feedback::new(items));
+    }
+
+    let title = if duplicate.is_some() {
+        lang.text(Msg::AddAnywayTitle, &[&preview])
+    } else {
+        lang.text(Msg::AddMemo, &[&preview])
+    };
+    let mut items = warning.into_iter().collect::<Vec<_>>();
+    items.push(
+        Item::new(title)
+            .with_subtitle(lang.text(
+                Msg::PressEnterToSave,
+                &[&normalized.len(), &config.max_input_bytes, &tag_hint],
+            ))
+            .with_arg(add_token)
+            .with_valid(true),
+    );
+    Ok(Feedback::new(items))
+}
+
+fn build_input_too_large_item(input_bytes: usize, config: &RuntimeConfig) -> Item {
+    let lang = config.language;
+    Item::new(lang.text(Msg::InputTooLarge, &[]))
+        .with_subtitle(lang.text(
+            Msg::InputTooLargeDetail,
+            &[&input_bytes, &config.max_input_bytes],
+        ))
+        .with_valid(false)
+}
+
+/// Identical or near-identical live memo for add-preview text; `None` when the DB does not exist yet.
+fn find_add_duplicate(
+    text: &str,
+    config: &RuntimeConfig,
+) -> Result<Option<duplicates::DuplicateMatch>, AppError> {
+    if !config.db_path.exists() {
+        return Ok(None);
+    }
+    let storage = open_storage(config.db_path.clone())?;
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_transaction(|tx| {
+            duplicates::ensure_content_hashes(tx, &codec)?;
+            duplicates::find_duplicate(tx, text, &codec)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
+
+fn build_duplicate_item(duplicate: &duplicates::DuplicateMatch, config: &RuntimeConfig) -> Item {
+    let lang = config.language;
+    let likeness = if duplicate.is_exact() {
+        lang.text(Msg::Identical, &[])
+    } else {
+        lang.text(
+            Msg::PercentSimilar,
+            &[&format!("{:.0}", duplicate.score * 100.0)],
+        )
+    };
+    Item::new(lang.text(Msg::SimilarMemoExists, &[&duplicate.item_id]))
+        .with_uid(format!("duplicate-{}", duplicate.item_id))
+        .with_subtitle(lang.text(
+            Msg::DuplicateDetail,
+            &[
+                &likeness,
+                &duplicate.created_at,
+                &truncate_title(duplicate.text.trim(), config.wide_preview_chars()),
+            ],
+        ))
+        .with_autocomplete(format!("item {}", item_route_id(&duplicate.item_id)))
+        .with_valid(false)
+}
+
+pub fn execute_db_init(
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<InitResult, AppError> {
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_storage(db_path.clone())?;
+    let purged_expired = purge_expired_trash(&storage, config)?;
+
+    Ok(InitResult {
+        db_path: db_path.display().to_string(),
+        purged_expired,
+    })
+}
+
+/// Sync memos through a git repo: export the per-memo tree, commit, pull (last-write-wins on conflicts), re-import
+/// the merged tree, then push.
+pub fn execute_sync(
+    repo_override: Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<SyncResult, AppError> {
+    let repo = repo_override
+        .or_else(|| config.sync_dir.clone())
+        .ok_or_else(|| AppError::User("sync requires --repo or MEMO_SYNC_DIR".to_string()))?;
+    sync::ensure_repo(&repo)?;
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+    let (records, tombstones) = storage
+        .with_transaction(|tx| {
+            sync::ensure_sync_rows(tx)?;
+            Ok((
+                sync::load_local_records(tx, &codec)?,
+                sync::load_tombstones(tx)?,
+            ))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    let changes = sync::write_tree(&repo, &records, &tombstones)?;
+    let stamp = current_timestamp(&storage)?;
+    let committed = sync::commit_tree(&repo, &format!("memo sync {stamp}"))?;
+    let upstream = sync::fetch_upstream(&repo)?;
+    let conflicts = if upstream { sync::pull(&repo)? } else { 0 };
+
+    let (remote, unreadable) = sync::read_tree(&repo)?;
+    let applied = storage
+        .with_transaction(|tx| sync::apply_tree(tx, &remote, &unreadable, &codec))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    let pushed = sync::push(&repo, upstream)?;
+
+    Ok(SyncResult {
+        repo: repo.display().to_string(),
+        written: changes.written,
+        removed: changes.removed,
+        committed,
+        pulled: upstream,
+        conflicts,
+        created: applied.created,
+        updated: applied.updated,
+        purged: applied.purged,
+        invalid: unreadable.len(),
+        pushed,
+    })
+}
+
+/// Encrypt every memo text at rest with a key derived from the configured or keychain passphrase.
+/// Write a verified snapshot of the memo database (default dir: `backups/` next to the DB).
+pub fn execute_db_backup(
+    db_override: Option<PathBuf>,
+    dir_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<BackupResult, AppError> {
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    if !db_path.exists() {
+        return Err(AppError::User(format!(
+            "memo database does not exist: {}",
+            db_path.display()
+        )));
+    }
+    let dir = dir_override.unwrap_or_else(|| backup::backup_dir(&db_path));
+    let storage = open_storage(db_path.clone())?;
+    let snapshot = storage
+        .with_connection(|conn| Ok(backup::create_snapshot(conn, &dir, None)))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))??;
+    let size_bytes = fs::metadata(&snapshot).map_or(0, |meta| meta.len());
+
+    Ok(BackupResult {
+        db_path: db_path.display().to_string(),
+        snapshot: snapshot.display().to_string(),
+        size_bytes,
+        created_at: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    })
+}
+
+/// Replace the memo database with a verified snapshot, taking an automatic snapshot of the current one first.
+pub fn execute_db_restore(
+    snapshot_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<SnapshotRestoreResult, AppError> {
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let raw = snapshot_raw.trim();
+    if raw.is_empty() {
+        return Err(AppError::User("db-restore requires a snapshot".to_string()));
+    }
+    let expanded = expand_home_path(raw, env::var("HOME").ok().as_deref());
+    let snapshot = backup::resolve_snapshot(&expanded, &backup::backup_dir(&db_path));
+    if !snapshot.is_file() {
+        return Err(AppError::User(format!(
+            "snapshot does not exist: {}",
+            snapshot.display()
+        )));
+    }
+    // An unreadable snapshot is a bad argument, not a storage failure of the memo DB.
+    let problem =
+        backup::integrity_problem(&snapshot).map_err(|error| AppError::User(error.to_string()))?;
+    if let Some(problem) = problem {
+        return Err(AppError::User(format!(
+            "snapshot failed integrity check: {problem}"
+        )));
+    }
+    if !backup::has_memo_schema(&snapshot)? {
+        return Err(AppError::User(format!(
+            "not a memo database: {}",
+            snapshot.display()
+        )));
+    }
+
+    let safety_backup = if db_path.exists() {
+        let storage = open_storage(db_path.clone())?;
+        Some(auto_backup(&storage, "restore")?.display().to_string())
+    } else {
+        None
+    };
+    backup::replace_database(&snapshot, &db_path)?;
+    let storage = open_storage(db_path.clone())?;
+    let (items, _, _, _) = storage
+        .with_connection(stats::memo_counts)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    Ok(SnapshotRestoreResult {
+        db_path: db_path.display().to_string(),
+        snapshot: snapshot.display().to_string(),
+        safety_backup,
+        items,
+    })
+}
+
+/// Snapshot the database before a destructive operation, keeping the newest `AUTO_BACKUP_KEEP` automatic snapshots.
+fn auto_backup(storage: &Storage, reason: &str) -> Result<PathBuf, AppError> {
+    let dir = backup::backup_dir(storage.db_path());
+    let snapshot = storage
+        .with_connection(|conn| Ok(backup::create_snapshot(conn, &dir, Some(reason))))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))??;
+    backup::rotate_auto_snapshots(&dir, backup::AUTO_BACKUP_KEEP);
+    Ok(snapshot)
+}
+
+pub fn execute_db_encrypt(
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<EncryptionResult, AppError> {
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path.clone(), config)?;
+    let enabled = storage
+        .with_connection(crypto::encryption_enabled)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if enabled {
+        return Err(AppError::User(
+            "memo database is already encrypted".to_string(),
+        ));
+    }
+
+    let passphrase = crypto::resolve_passphrase(config.encryption_passphrase.as_deref())?;
+    let (settings, codec) = crypto::new_encryption(&passphrase)?;
+    let items = storage
+        .with_transaction(|tx| {
+            // Journal before-images hold the old encoding (plaintext here), so they are dropped.
+            journal::clear(tx)?;
+            crypto::encrypt_items(tx, &settings, &codec)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    storage
+        .with_connection(crypto::compact_after_recode)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    Ok(EncryptionResult {
+        db_path: db_path.display().to_string(),
+        encrypted: true,
+        items,
+    })
+}
+
+/// Store every memo text as plaintext again and remove the encryption settings.
+pub fn execute_db_decrypt(
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<EncryptionResult, AppError> {
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path.clone(), config)?;
+    let codec = text_codec(&storage, config)?;
+    if !codec.is_encrypted() {
+        return Err(AppError::User("memo database is not encrypted".to_string()));
+    }
+
+    let items = storage
+        .with_transaction(|tx| {
+            journal::clear(tx)?;
+            crypto::decrypt_items(tx, &codec)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    storage
+        .with_connection(crypto::compact_after_recode)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    Ok(EncryptionResult {
+        db_path: db_path.display().to_string(),
+        encrypted: false,
+        items,
+    })
+}
+
+pub fn execute_add(
+    text: &str,
+    source_override: Option<&str>,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<AddResult, AppError> {
+    execute_add_with_tags(text, &[], source_override, db_override, config)
+}
+
+/// Add one memo, storing `#tags` found in the text plus any explicitly supplied tags.
+pub fn execute_add_with_tags(
+    text: &str,
+    explicit_tags: &[String],
+    source_override: Option<&str>,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<AddResult, AppError> {
+    let normalized_text = text.trim();
+    if normalized_text.is_empty() {
+        return Err(AppError::User(
+            "add requires a non-empty memo text".to_string(),
+        ));
+    }
+
+    if normalized_text.len() > config.max_input_bytes {
+        return Err(AppError::User(format!(
+            "memo text exceeds MEMO_MAX_INPUT_BYTES: {} > {}",
             normalized_text.len(),
             config.max_input_bytes
         )));
@@ -275,23 +1195,60 @@ pub fn execute_add(
     }
 
     let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path);
-    storage
-        .init()
-        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
-
-    let added = storage
-        .with_transaction(|tx| repository::add_item(tx, normalized_text, &source, None))
+    let storage = open_memo_storage(db_path, config)?;
+
+    let codec = text_codec(&storage, config)?;
+    let text_tags = extract_tags(normalized_text);
+    let (added, tags) = storage
+        .with_transaction(|tx| {
+            let added = repository::add_item(tx, &codec.seal(normalized_text)?, &source, None)?;
+            tags::replace_item_tags(tx, added.item_id, tags::TAG_ORIGIN_TEXT, &text_tags)?;
+            tags::replace_item_tags(tx, added.item_id, tags::TAG_ORIGIN_FLAG, explicit_tags)?;
+            journal::record(
+                tx,
+                journal::JournalOperation::Add,
+                added.item_id,
+                None,
+                None,
+                config.undo_window_minutes,
+            )?;
+            let tags = tags::list_item_tags(tx, added.item_id)?;
+            Ok((added, tags))
+        })
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
     Ok(AddResult {
         item_id: format_item_id(added.item_id),
         created_at: added.created_at,
         source: added.source,
-        text: added.text,
+        text: normalized_text.to_string(),
+        tags,
     })
 }
 
+/// Save the current clipboard text as one memo with source `clipboard`.
+pub fn execute_clip(
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<AddResult, AppError> {
+    let text = clipboard_text()?;
+    execute_add(&text, Some(CLIPBOARD_SOURCE), db_override, config)
+}
+
+/// Clipboard text for memo capture, or a user error naming why the clipboard cannot be saved.
+fn clipboard_text() -> Result<String, AppError> {
+    match clipboard::read_clipboard() {
+        clipboard::ClipboardContent::Text(text) => Ok(text),
+        clipboard::ClipboardContent::Empty => Err(AppError::User("clipboard is empty".to_string())),
+        clipboard::ClipboardContent::Binary => Err(AppError::User(
+            "clipboard does not hold plain text".to_string(),
+        )),
+        clipboard::ClipboardContent::Unavailable => Err(AppError::User(
+            "clipboard is unavailable (pbpaste not found or failed)".to_string(),
+        )),
+    }
+}
+
 pub fn execute_update(
     item_id_raw: &str,
     text: &str,
@@ -316,25 +1273,55 @@ pub fn execute_update(
     }
 
     let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path);
-    storage
-        .init()
+    let storage = open_memo_storage(db_path, config)?;
+
+    let trashed = storage
+        .with_connection(|conn| trash::trashed_at(conn, item_id))
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if trashed.is_some() {
+        return Err(AppError::User(
+            "item_id is in trash; restore it before updating".to_string(),
+        ));
+    }
 
-    let updated = storage
-        .with_transaction(|tx| repository::update_item(tx, item_id, normalized_text))
+    let codec = text_codec(&storage, config)?;
+    let text_tags = extract_tags(normalized_text);
+    let (updated, tags) = storage
+        .with_transaction(|tx| {
+            let before = journal::stored_text(tx, item_id)?;
+            if let Some(before) = before.as_deref()
+                && codec.open(before)? != normalized_text
+            {
+                history::record_version(tx, item_id, before)?;
+            }
+            let sealed = codec.seal(normalized_text)?;
+            let updated = repository::update_item(tx, item_id, &sealed)?;
+            tags::replace_item_tags(tx, item_id, tags::TAG_ORIGIN_TEXT, &text_tags)?;
+            journal::record(
+                tx,
+                journal::JournalOperation::Update,
+                item_id,
+                before.as_deref(),
+                Some(&sealed),
+                config.undo_window_minutes,
+            )?;
+            let tags = tags::list_item_tags(tx, item_id)?;
+            Ok((updated, tags))
+        })
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
     Ok(UpdateResult {
         item_id: format_item_id(updated.item_id),
         updated_at: updated.updated_at,
-        text: updated.text,
+        text: normalized_text.to_string(),
         state: "pending".to_string(),
         cleared_derivations: updated.cleared_derivations,
         cleared_workflow_anchors: updated.cleared_workflow_anchors,
+        tags,
     })
 }
 
+/// Move one memo to trash; `execute_purge` performs the permanent delete.
 pub fn execute_delete(
     item_id_raw: &str,
     db_override: Option<PathBuf>,
@@ -343,1076 +1330,5587 @@ pub fn execute_delete(
     let item_id = parse_item_id(item_id_raw)
         .ok_or_else(|| AppError::User("delete requires a valid item_id".to_string()))?;
     let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path);
-    storage
-        .init()
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_item_exists(&storage, item_id)?;
+
+    let trashed = storage
+        .with_connection(|conn| trash::trashed_at(conn, item_id))
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if trashed.is_some() {
+        return Err(AppError::User("item_id is already in trash".to_string()));
+    }
 
-    let deleted = storage
-        .with_transaction(|tx| repository::delete_item_hard(tx, item_id))
+    let (deleted_at, purge_after) = storage
+        .with_transaction(|tx| {
+            let trashed = trash::move_to_trash(tx, item_id, config.trash_retention_days)?;
+            journal::record(
+                tx,
+                journal::JournalOperation::Delete,
+                item_id,
+                None,
+                None,
+                config.undo_window_minutes,
+            )?;
+            Ok(trashed)
+        })
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
     Ok(DeleteResult {
-        item_id: format_item_id(deleted.item_id),
+        item_id: format_item_id(item_id),
         deleted: true,
-        deleted_at: deleted.deleted_at,
-        removed_derivations: deleted.removed_derivations,
-        removed_workflow_anchors: deleted.removed_workflow_anchors,
+        deleted_at,
+        state: "deleted".to_string(),
+        purge_after,
     })
 }
 
-pub fn execute_list(
+pub fn execute_restore(
+    item_id_raw: &str,
     db_override: Option<PathBuf>,
-    limit: usize,
-    offset: usize,
     config: &RuntimeConfig,
-) -> Result<Vec<ListResult>, AppError> {
-    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
-        return Err(AppError::User(format!(
-            "invalid list limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
-        )));
+) -> Result<RestoreResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("restore requires a valid item_id".to_string()))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_item_exists(&storage, item_id)?;
+
+    let restored = storage
+        .with_transaction(|tx| trash::restore_from_trash(tx, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if !restored {
+        return Err(AppError::User("item_id is not in trash".to_string()));
     }
 
+    Ok(RestoreResult {
+        item_id: format_item_id(item_id),
+        restored: true,
+        restored_at: current_timestamp(&storage)?,
+    })
+}
+
+/// Permanently remove one memo (trashed or not), including derivations and workflow rows.
+pub fn execute_purge(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<PurgeResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("purge requires a valid item_id".to_string()))?;
     let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path);
-    storage
-        .init()
-        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_item_exists(&storage, item_id)?;
 
-    let rows = storage
-        .with_connection(|conn| {
-            repository::list_items(conn, repository::QueryState::All, limit, offset)
-        })
+    let purged = storage
+        .with_transaction(|tx| repository::delete_item_hard(tx, item_id))
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-    Ok(rows
-        .into_iter()
-        .map(|row| ListResult {
-            item_id: format_item_id(row.item_id),
-            created_at: row.created_at,
-            state: row.state,
-            text_preview: row.text_preview,
-        })
-        .collect())
+    Ok(PurgeResult {
+        item_id: format_item_id(purged.item_id),
+        purged: true,
+        purged_at: purged.deleted_at,
+        removed_derivations: purged.removed_derivations,
+        removed_workflow_anchors: purged.removed_workflow_anchors,
+    })
 }
 
-pub fn execute_search(
+/// Revert the newest journaled add/update/delete/merge inside the undo window.
+///
+/// `entry_id` comes from an `undo::` token; it must still be the newest entry, so a stale Alfred row never undoes a
+/// different operation.
+pub fn execute_undo(
+    entry_id: Option<i64>,
     db_override: Option<PathBuf>,
-    query: &str,
-    match_mode: SearchMatchMode,
-    limit: usize,
-    offset: usize,
     config: &RuntimeConfig,
-) -> Result<Vec<SearchResult>, AppError> {
-    let normalized_query = query.trim();
-    if normalized_query.is_empty() {
+) -> Result<UndoResult, AppError> {
+    if config.undo_window_minutes == 0 {
         return Err(AppError::User(
-            "search requires a non-empty query".to_string(),
+            "undo is disabled (MEMO_UNDO_WINDOW_MINUTES=0)".to_string(),
         ));
     }
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+
+    let outcome = storage
+        .with_transaction(|tx| {
+            let Some(entry) = journal::latest_entry(tx, config.undo_window_minutes)? else {
+                return Ok(Err(format!(
+                    "nothing to undo in the last {} minutes",
+                    config.undo_window_minutes
+                )));
+            };
+            if entry_id.is_some_and(|entry_id| entry_id != entry.entry_id) {
+                return Ok(Err(
+                    "undo entry is no longer the latest operation".to_string()
+                ));
+            }
+            if let Err(reason) = journal::check_undo(tx, &entry)? {
+                return Ok(Err(format!(
+                    "cannot undo {} of {}: {reason}",
+                    entry.operation.as_str(),
+                    format_item_id(entry.item_id)
+                )));
+            }
+            let state = journal::apply_undo(tx, &codec, &entry)?;
+            Ok(Ok((entry, state)))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    let (entry, state) = outcome.map_err(AppError::User)?;
+
+    Ok(UndoResult {
+        operation: entry.operation.as_str().to_string(),
+        item_id: format_item_id(entry.item_id),
+        recorded_at: entry.recorded_at,
+        undone_at: current_timestamp(&storage)?,
+        state: state.to_string(),
+    })
+}
 
-    if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
-        return Err(AppError::User(format!(
-            "invalid search limit: {limit} (must be integer in range 1..={MAX_SEARCH_LIMIT})"
-        )));
+/// Open one memo in the configured editor and apply the saved text via `execute_update`.
+pub fn execute_edit(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<EditResult, AppError> {
+    if parse_item_id(item_id_raw).is_none() {
+        return Err(AppError::User("edit requires a valid item_id".to_string()));
     }
+    let detail = execute_fetch_item(item_id_raw, db_override.clone(), config)?;
+    let edited = editor::edit_text(&config.editor, &detail.item_id, &detail.text)?;
 
-    let fetch_limit = limit.checked_add(offset).ok_or_else(|| {
-        AppError::User("invalid search window: limit + offset overflow".to_string())
-    })?;
-    if fetch_limit > MAX_SEARCH_FETCH_LIMIT {
-        return Err(AppError::User(format!(
-            "invalid search window: limit + offset must be <= {MAX_SEARCH_FETCH_LIMIT}"
+    let updated_at = match edited {
+        Some(text) => Some(execute_update(&detail.item_id, &text, db_override, config)?.updated_at),
+        None => None,
+    };
+
+    Ok(EditResult {
+        item_id: detail.item_id,
+        changed: updated_at.is_some(),
+        updated_at,
+        editor: config.editor.clone(),
+    })
+}
+
+/// Enrich one memo with the configured command and store its output as the active accepted derivation.
+///
+/// `command_override` replaces `MEMO_DERIVE_COMMAND`. Encrypted databases are refused because derivations (and the
+/// search documents built from them) are stored in plaintext.
+pub fn execute_derive(
+    item_id_raw: &str,
+    command_override: Option<String>,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<DeriveResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("derive requires a valid item_id".to_string()))?;
+    let command = command_override
+        .filter(|command| !command.trim().is_empty())
+        .or_else(|| config.derive_command.clone())
+        .ok_or_else(|| {
+            AppError::User("derive requires MEMO_DERIVE_COMMAND (or --command)".to_string())
+        })?;
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path.clone(), config)?;
+    if text_codec(&storage, config)?.is_encrypted() {
+        return Err(AppError::User(
+            "derive is not available on encrypted databases (derivations are stored in plaintext)"
+                .to_string(),
+        ));
+    }
+    let detail = execute_fetch_item(item_id_raw, Some(db_path), config)?;
+
+    let output = derive::run_enrichment(&command, &detail.item_id, &detail.text)?;
+    let item = derive::parse_derivation(item_id, &output)
+        .map_err(|message| AppError::User(format!("invalid derivation payload: {message}")))?;
+    let summary = storage
+        .with_transaction(|tx| {
+            derivations::apply_items(
+                tx,
+                std::slice::from_ref(&item),
+                false,
+                derive::DERIVE_AGENT_RUN_ID,
+            )
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    let outcome = summary
+        .items
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Runtime("derive stored no derivation".to_string()))?;
+    if let Some(error) = outcome.error {
+        return Err(AppError::Runtime(format!(
+            "failed to store derivation: {}",
+            error.message
         )));
     }
+    let derivation_version = outcome
+        .derivation_version
+        .ok_or_else(|| AppError::Runtime("derive stored no derivation version".to_string()))?;
+
+    Ok(DeriveResult {
+        item_id: detail.item_id,
+        changed: outcome.status == "accepted",
+        derivation_version,
+        state: "enriched".to_string(),
+        summary: item.summary,
+        category: item.category,
+        priority: item.priority,
+        tags: item.tags,
+        agent_run_id: derive::DERIVE_AGENT_RUN_ID.to_string(),
+    })
+}
 
+/// Set (or clear with `clear` / `none` / `off`) the due timestamp of one memo from a due phrase.
+pub fn execute_remind(
+    item_id_raw: &str,
+    when_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<RemindResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("remind requires a valid item_id".to_string()))?;
+    let now = Local::now();
+    let due = if is_due_clear_word(when_raw) {
+        None
+    } else {
+        Some(due::parse_due_phrase(when_raw, &now).ok_or_else(|| {
+            AppError::User(format!("could not parse due date: {}", when_raw.trim()))
+        })?)
+    };
     let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path);
-    storage
-        .init()
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
+
+    let due_at = due.as_ref().map(due::format_due);
+    let changed = storage
+        .with_transaction(|tx| match &due_at {
+            Some(due_at) => due::set_due(tx, item_id, due_at).map(|()| true),
+            None => due::clear_due(tx, item_id),
+        })
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-    let search_fields = [
-        search::SearchField::Raw,
-        search::SearchField::Derived,
-        search::SearchField::Tags,
-    ];
-    let upstream_match_mode = match match_mode {
-        SearchMatchMode::Fts => search::SearchMatchMode::Fts,
-        SearchMatchMode::Prefix => search::SearchMatchMode::Prefix,
-        SearchMatchMode::Contains => search::SearchMatchMode::Contains,
-    };
+    Ok(RemindResult {
+        item_id: format_item_id(item_id),
+        status: due
+            .as_ref()
+            .map(|due| due::due_status(due, &now).to_string()),
+        due_at,
+        changed,
+    })
+}
+
+/// Live memos with a due timestamp, soonest first: overdue and due today, plus later ones when
+/// `include_upcoming` is set.
+pub fn execute_due_list(
+    db_override: Option<PathBuf>,
+    include_upcoming: bool,
+    limit: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<DueResult>, AppError> {
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid due limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
+        )));
+    }
 
+    let now = Local::now();
+    let until = if include_upcoming {
+        None
+    } else {
+        due::end_of_day(&now).map(|end| due::format_due(&end))
+    };
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
     let rows = storage
-        .with_connection(|conn| {
-            search::search_items(
-                conn,
-                normalized_query,
-                repository::QueryState::All,
-                &search_fields,
-                upstream_match_mode,
-                fetch_limit,
-            )
-        })
+        .with_connection(|conn| due::list_due(conn, until.as_deref(), limit, &codec))
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
     Ok(rows
         .into_iter()
-        .skip(offset)
-        .take(limit)
-        .map(|row| SearchResult {
-            item_id: format_item_id(row.item_id),
-            created_at: row.created_at,
-            score: row.score,
-            matched_fields: row.matched_fields,
-            text_preview: row.preview,
-            content_type: row.content_type,
-            validation_status: row.validation_status,
+        .map(|(item_id, due_at, text_preview)| {
+            let status = parse_stored_due(&due_at)
+                .map(|due| due::due_status(&due, &now))
+                .unwrap_or(due::DUE_STATUS_UPCOMING);
+            DueResult {
+                item_id,
+                due_at,
+                status: status.to_string(),
+                text_preview,
+            }
         })
         .collect())
 }
 
-pub fn execute_fetch_item(
-    item_id_raw: &str,
+fn is_due_clear_word(raw: &str) -> bool {
+    let trimmed = raw.trim();
+    due::DUE_CLEAR_WORDS
+        .iter()
+        .any(|word| trimmed.eq_ignore_ascii_case(word))
+}
+
+fn parse_stored_due(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|due| due.with_timezone(&Utc))
+}
+
+/// Attach a file path or URL to one memo; attaching the same location twice is a no-op.
+/// Save (or overwrite) one memo template.
+pub fn execute_template_save(
+    name_raw: &str,
+    body: &str,
     db_override: Option<PathBuf>,
     config: &RuntimeConfig,
-) -> Result<ItemDetailResult, AppError> {
-    let item_id = parse_item_id(item_id_raw)
-        .ok_or_else(|| AppError::User("copy requires a valid item_id".to_string()))?;
+) -> Result<TemplateSaveResult, AppError> {
+    let name = parse_template_name(name_raw)?;
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(AppError::User(
+            "template text must be non-empty".to_string(),
+        ));
+    }
+    if body.len() > config.max_input_bytes {
+        return Err(AppError::User(format!(
+            "template text exceeds MEMO_MAX_INPUT_BYTES ({} > {})",
+            body.len(),
+            config.max_input_bytes
+        )));
+    }
+
     let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
-    let storage = Storage::new(db_path);
-    storage
-        .init()
+    let storage = open_memo_storage(db_path, config)?;
+    let created = storage
+        .with_transaction(|tx| templates::save_template(tx, &name, body))
         .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-    let cursor = storage
-        .with_connection(|conn| repository::lookup_fetch_cursor(conn, item_id))
-        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
-    if cursor.is_none() {
-        return Err(AppError::User("item_id does not exist".to_string()));
-    }
+    Ok(TemplateSaveResult {
+        name,
+        body: body.to_string(),
+        created,
+    })
+}
 
+pub fn execute_template_list(
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<Vec<TemplateResult>, AppError> {
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
     storage
-        .with_connection(|conn| {
-            conn.query_row(
-                "select
-                    i.item_id,
-                    i.created_at,
-                    i.source,
-                    i.raw_text,
-                    case
-                        when ad.derivation_id is not null then 'enriched'
-                        else 'pending'
-                    end as state,
-                    json_extract(ad.payload_json, '$.content_type') as content_type,
-                    json_extract(ad.payload_json, '$.validation_status') as validation_status
-                from inbox_items i
-                left join item_derivations ad
-                  on ad.derivation_id = (
-                    select d.derivation_id
-                    from item_derivations d
-                    where d.item_id = i.item_id
-                      and d.is_active = 1
-                      and d.status = 'accepted'
-                    order by d.derivation_version desc, d.derivation_id desc
-                    limit 1
-                  )
-                where i.item_id = ?1",
-                [item_id],
-                |row| {
-                    Ok(ItemDetailResult {
-                        item_id: format_item_id(row.get::<_, i64>(0)?),
-                        created_at: row.get(1)?,
-                        source: row.get(2)?,
-                        text: row.get(3)?,
-                        state: row.get(4)?,
-                        content_type: row.get(5)?,
-                        validation_status: row.get(6)?,
-                    })
-                },
-            )
-            .map_err(MemoCliError::db_query)
-        })
+        .with_connection(templates::list_templates)
         .map_err(|error| AppError::Runtime(error.message().to_string()))
 }
 
-pub fn parse_add_token(arg: &str) -> Option<String> {
-    arg.strip_prefix(ADD_TOKEN_PREFIX).map(str::to_string)
+/// Expand one saved template's placeholders (`{date}`, `{clipboard}`, ...) into memo text.
+pub fn execute_template_use(
+    name_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<TemplateUseResult, AppError> {
+    let name = parse_template_name(name_raw)?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let template = storage
+        .with_connection(|conn| templates::find_template(conn, &name))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?
+        .ok_or_else(|| AppError::User(format!("template not found: {name}")))?;
+
+    Ok(TemplateUseResult {
+        text: templates::expand_placeholders(
+            &template.body,
+            &Local::now(),
+            clipboard::read_clipboard_text,
+        ),
+        name: template.name,
+    })
 }
 
-pub fn build_add_token(text: &str) -> String {
-    format!("{ADD_TOKEN_PREFIX}{text}")
+fn parse_template_name(raw: &str) -> Result<String, AppError> {
+    normalize_template_name(raw)
+        .filter(|name| name != TEMPLATE_ADD_KEYWORD)
+        .ok_or_else(|| {
+            AppError::User(format!(
+                "invalid template name: {} (use letters, digits, `-`, `_`; `add` is reserved)",
+                raw.trim()
+            ))
+        })
 }
 
-pub fn parse_copy_token(arg: &str) -> Option<String> {
-    let payload = arg.strip_prefix(COPY_TOKEN_PREFIX)?;
-    let item_id = parse_item_id(payload.trim())?;
-    Some(format_item_id(item_id))
-}
+pub fn execute_attach(
+    item_id_raw: &str,
+    location_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<AttachResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("attach requires a valid item_id".to_string()))?;
+    let (kind, location) = attachments::normalize_location(location_raw)?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
+
+    let (attachment_id, changed, attachment_count) = storage
+        .with_transaction(|tx| {
+            let (attachment_id, changed) =
+                attachments::add_attachment(tx, item_id, kind, &location)?;
+            let count = attachments::list_attachments(tx, item_id)?.len() as i64;
+            Ok((attachment_id, changed, count))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-pub fn build_copy_token(item_id: &str) -> String {
-    format!("{COPY_TOKEN_PREFIX}{item_id}")
+    Ok(AttachResult {
+        item_id: format_item_id(item_id),
+        attachment_id,
+        kind: kind.to_string(),
+        location,
+        changed,
+        attachment_count,
+    })
 }
 
-pub fn parse_copy_json_token(arg: &str) -> Option<String> {
-    let payload = arg.strip_prefix(COPY_JSON_TOKEN_PREFIX)?;
-    let item_id = parse_item_id(payload.trim())?;
-    Some(format_item_id(item_id))
-}
+pub fn execute_attachments(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<Vec<AttachmentResult>, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("attachments requires a valid item_id".to_string()))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
 
-pub fn build_copy_json_token(item_id: &str) -> String {
-    format!("{COPY_JSON_TOKEN_PREFIX}{item_id}")
+    storage
+        .with_connection(|conn| attachments::list_attachments(conn, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
 }
 
-pub fn parse_update_token(arg: &str) -> Option<(String, String)> {
-    let payload = arg.strip_prefix(UPDATE_TOKEN_PREFIX)?;
-    let (item_id_raw, text_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
-    let item_id = parse_item_id(item_id_raw)?;
-    let text = text_raw.trim();
-    if text.is_empty() {
-        return None;
+/// Link two live memos in both directions; linking an already linked pair is a no-op.
+pub fn execute_link(
+    item_id_raw: &str,
+    other_item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<LinkResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("link requires a valid item_id".to_string()))?;
+    let other_item_id = parse_item_id(other_item_id_raw)
+        .ok_or_else(|| AppError::User("link requires a valid other item_id".to_string()))?;
+    if item_id == other_item_id {
+        return Err(AppError::User("cannot link a memo to itself".to_string()));
     }
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
+    ensure_live_item(&storage, other_item_id)?;
+
+    let codec = text_codec(&storage, config)?;
+    let (changed, linked_count) = storage
+        .with_transaction(|tx| {
+            let changed = relations::add_link(tx, item_id, other_item_id)?;
+            let count = relations::list_linked_items(tx, &codec, item_id)?.len() as i64;
+            Ok((changed, count))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-    Some((format_item_id(item_id), text.to_string()))
+    Ok(LinkResult {
+        item_id: format_item_id(item_id),
+        linked_item_id: format_item_id(other_item_id),
+        changed,
+        linked_count,
+    })
 }
 
-pub fn build_update_token(item_id: &str, text: &str) -> String {
-    format!("{UPDATE_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{text}")
-}
+/// Append the text of `merge_item_id` to `keep_item_id`, copy its flag tags, attachments, and memo links over, and
+/// move it to trash; the merge is journaled so `undo` can split the memos again.
+pub fn execute_merge(
+    keep_item_id_raw: &str,
+    merge_item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<MergeResult, AppError> {
+    let item_id = parse_item_id(keep_item_id_raw)
+        .ok_or_else(|| AppError::User("merge requires a valid keep item_id".to_string()))?;
+    let merged_item_id = parse_item_id(merge_item_id_raw)
+        .ok_or_else(|| AppError::User("merge requires a valid merge item_id".to_string()))?;
+    if item_id == merged_item_id {
+        return Err(AppError::User(
+            "cannot merge a memo into itself".to_string(),
+        ));
+    }
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
+    ensure_live_item(&storage, merged_item_id)?;
+
+    let codec = text_codec(&storage, config)?;
+    let outcome = storage
+        .with_transaction(|tx| {
+            let before = journal::stored_text(tx, item_id)?.unwrap_or_default();
+            let merged_stored = journal::stored_text(tx, merged_item_id)?.unwrap_or_default();
+            let text = merge::merged_text(&codec.open(&before)?, &codec.open(&merged_stored)?);
+            if text.len() > config.max_input_bytes {
+                return Ok(Err(format!(
+                    "merged text exceeds MEMO_MAX_INPUT_BYTES: {} > {}",
+                    text.len(),
+                    config.max_input_bytes
+                )));
+            }
 
-pub fn parse_delete_token(arg: &str) -> Option<String> {
-    let payload = arg.strip_prefix(DELETE_TOKEN_PREFIX)?;
-    let item_id = parse_item_id(payload.trim())?;
-    Some(format_item_id(item_id))
+            history::record_version(tx, item_id, &before)?;
+            let sealed = codec.seal(&text)?;
+            let updated = repository::update_item(tx, item_id, &sealed)?;
+            tags::replace_item_tags(tx, item_id, tags::TAG_ORIGIN_TEXT, &extract_tags(&text))?;
+            let union = merge::union_item_rows(tx, item_id, merged_item_id)?;
+            let (_, purge_after) =
+                trash::move_to_trash(tx, merged_item_id, config.trash_retention_days)?;
+            journal::record_merge(
+                tx,
+                item_id,
+                merged_item_id,
+                &before,
+                &sealed,
+                config.undo_window_minutes,
+            )?;
+            let tags = tags::list_item_tags(tx, item_id)?;
+            Ok(Ok(MergeResult {
+                item_id: format_item_id(item_id),
+                merged_item_id: format_item_id(merged_item_id),
+                merged_at: updated.updated_at,
+                text,
+                tags,
+                attachments_added: union.attachments_added,
+                links_added: union.links_added,
+                purge_after,
+            }))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    outcome.map_err(AppError::User)
 }
 
-pub fn build_delete_token(item_id: &str) -> String {
-    format!("{DELETE_TOKEN_PREFIX}{item_id}")
-}
+/// Live memos linked to one memo, most recently linked first.
+pub fn execute_linked(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<Vec<LinkedItemResult>, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("linked requires a valid item_id".to_string()))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
 
-fn strip_intent<'a>(query: &'a str, intent: &str) -> Option<&'a str> {
-    let mut parts = query.splitn(2, char::is_whitespace);
-    let first = parts.next()?;
-    if !first.eq_ignore_ascii_case(intent) {
-        return None;
-    }
-    Some(parts.next().unwrap_or("").trim())
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_connection(|conn| relations::list_linked_items(conn, &codec, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
 }
 
-fn item_route_id(item_id: &str) -> String {
-    parse_item_id(item_id)
-        .map(|id| id.to_string())
-        .unwrap_or_else(|| item_id.to_string())
+/// Earlier texts of one live memo, newest first.
+pub fn execute_history(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<Vec<VersionResult>, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("history requires a valid item_id".to_string()))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_live_item(&storage, item_id)?;
+
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_connection(|conn| history::list_versions(conn, &codec, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
 }
 
-fn item_display_id(item_id: &str) -> String {
-    parse_item_id(item_id)
-        .map(|id| format!("#{id}"))
-        .unwrap_or_else(|| item_id.to_string())
+/// Copy one stored version back as the memo's current text via `execute_update`, which keeps the replaced text as
+/// a new version.
+pub fn execute_history_restore(
+    version_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<HistoryRestoreResult, AppError> {
+    let version_id = version_id_raw
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|id| *id > 0)
+        .ok_or_else(|| AppError::User("history-restore requires a valid version_id".to_string()))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path.clone(), config)?;
+    let (item_id, stored) = storage
+        .with_connection(|conn| history::find_version(conn, version_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?
+        .ok_or_else(|| AppError::User("version_id does not exist".to_string()))?;
+    let text = text_codec(&storage, config)?
+        .open(&stored)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    let item_id = format_item_id(item_id);
+    let current = execute_fetch_item(&item_id, Some(db_path.clone()), config)?;
+    let updated_at = if current.text.trim() == text.trim() {
+        None
+    } else {
+        Some(execute_update(&item_id, &text, Some(db_path), config)?.updated_at)
+    };
+
+    Ok(HistoryRestoreResult {
+        item_id,
+        version_id,
+        changed: updated_at.is_some(),
+        updated_at,
+        text,
+    })
 }
 
-fn build_item_action_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
-    let mut parts = rest.split_whitespace();
-    let item_id_raw = parts.next().unwrap_or("").trim();
-    if item_id_raw.is_empty() || parts.next().is_some() {
-        return Ok(Feedback::new(vec![
-            Item::new("Invalid item selection syntax")
-                .with_subtitle("Use: item <item_id>")
-                .with_valid(false),
-        ]));
-    }
-
-    let item_id = match parse_item_id(item_id_raw) {
-        Some(item_id) => format_item_id(item_id),
-        None => {
-            return Ok(Feedback::new(vec![
-                Item::new("Invalid item_id for selection")
-                    .with_subtitle("Expected itm_XXXXXXXX or positive integer item id.")
-                    .with_valid(false),
-            ]));
-        }
-    };
-    let item_route = item_route_id(&item_id);
-    let item_display = item_display_id(&item_id);
+/// Resolve one attachment for opening; file attachments must still exist on disk.
+pub fn execute_open_attachment(
+    attachment_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<AttachmentResult, AppError> {
+    let attachment_id = attachment_id_raw
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|id| *id > 0)
+        .ok_or_else(|| {
+            AppError::User("open-attachment requires a valid attachment_id".to_string())
+        })?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
 
-    let detail = match execute_fetch_item(&item_id, None, config) {
-        Ok(detail) => detail,
-        Err(AppError::User(message)) => {
-            return Ok(Feedback::new(vec![
-                Item::new("Memo item not found")
-                    .with_subtitle(format!("{message}: {item_display}"))
-                    .with_valid(false),
-            ]));
-        }
-        Err(error) => return Err(error),
-    };
-    let raw_json_preview = render_item_detail_json(&detail);
-    let copy_item = build_copy_item(
-        &item_id,
-        &item_display,
-        Some(&detail.text),
-        &raw_json_preview,
-    );
-    let update_item = build_update_item(
-        &item_id,
-        &item_display,
-        &item_route,
-        Some(&detail.text),
-        None,
-        false,
-    );
-    let delete_item = build_delete_item(&item_id, &item_display, Some(&detail.text));
+    let (item_id, attachment) = storage
+        .with_connection(|conn| attachments::find_attachment(conn, attachment_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?
+        .ok_or_else(|| AppError::User("attachment_id does not exist".to_string()))?;
+    ensure_live_item(&storage, item_id)?;
 
-    Ok(Feedback::new(vec![copy_item, update_item, delete_item]))
+    if attachment.kind == attachments::ATTACHMENT_KIND_FILE
+        && !Path::new(&attachment.location).exists()
+    {
+        return Err(AppError::User(format!(
+            "attachment file no longer exists: {}",
+            attachment.location
+        )));
+    }
+    Ok(attachment)
 }
 
-fn build_update_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
-    let mut parts = rest.splitn(2, char::is_whitespace);
-    let item_id_raw = parts.next().unwrap_or("").trim();
-    let text = parts.next().unwrap_or("").trim();
-
-    if item_id_raw.is_empty() {
-        return Ok(Feedback::new(vec![
-            Item::new("Invalid update syntax")
-                .with_subtitle("Use: update <item_id> <new text>")
-                .with_valid(false),
-        ]));
+/// Apply one action to explicit item ids plus every memo of the given tags, in one transaction.
+///
+/// Items the action cannot apply to (missing, already trashed, ...) are reported per item and skipped; a storage
+/// failure rolls back the whole batch.
+pub fn execute_batch(
+    action: BatchAction,
+    item_ids_raw: &[String],
+    tags_raw: &[String],
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<BatchResult, AppError> {
+    let explicit = item_ids_raw
+        .iter()
+        .map(|raw| {
+            parse_item_id(raw.trim())
+                .ok_or_else(|| AppError::User(format!("invalid item_id in batch: {}", raw.trim())))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let tags = tags_raw
+        .iter()
+        .map(|raw| {
+            normalize_tag(raw)
+                .ok_or_else(|| AppError::User(format!("invalid tag in batch: {}", raw.trim())))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if explicit.is_empty() && tags.is_empty() {
+        return Err(AppError::User(
+            "batch requires at least one item_id or tag".to_string(),
+        ));
     }
 
-    let item_id = match parse_item_id(item_id_raw) {
-        Some(item_id) => format_item_id(item_id),
-        None => {
-            return Ok(Feedback::new(vec![
-                Item::new("Invalid item_id for update")
-                    .with_subtitle("Expected itm_XXXXXXXX or positive integer item id.")
-                    .with_valid(false),
-            ]));
-        }
-    };
-    let item_route = item_route_id(&item_id);
-    let item_display = item_display_id(&item_id);
-    let detail_text = execute_fetch_item(&item_id, None, config)
-        .ok()
-        .map(|detail| detail.text);
-
-    if text.is_empty() {
-        let update_item = build_update_item(
-            &item_id,
-            &item_display,
-            &item_route,
-            detail_text.as_deref(),
-            None,
-            false,
-        );
-        return Ok(Feedback::new(vec![update_item]));
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    if matches!(action, BatchAction::Delete | BatchAction::Purge) {
+        auto_backup(&storage, &format!("batch-{}", action.as_str()))?;
     }
-
-    if text.len() > config.max_input_bytes {
-        return Ok(Feedback::new(vec![
-            Item::new("Input exceeds MEMO_MAX_INPUT_BYTES")
-                .with_subtitle(format!(
-                    "Current {} bytes, limit {} bytes.",
-                    text.len(),
-                    config.max_input_bytes
-                ))
-                .with_valid(false),
-        ]));
+    let items = storage
+        .with_transaction(|tx| {
+            let item_ids =
+                batch::resolve_targets(tx, action, &explicit, &tags, MAX_BATCH_ITEMS + 1)?;
+            if item_ids.len() > MAX_BATCH_ITEMS {
+                return Ok(Err(item_ids.len()));
+            }
+            batch::apply_batch(tx, action, &item_ids, config.trash_retention_days).map(Ok)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?
+        .map_err(|count| {
+            AppError::User(format!(
+                "batch has {count} items (limit {MAX_BATCH_ITEMS}); narrow the selection"
+            ))
+        })?;
+    if items.is_empty() {
+        return Err(AppError::User(format!(
+            "no memos to {} for the given tags",
+            action.as_str()
+        )));
     }
 
-    let update_item = build_update_item(
-        &item_id,
-        &item_display,
-        &item_route,
-        detail_text.as_deref(),
-        Some(text),
-        true,
-    );
+    let succeeded = items.iter().filter(|item| item.ok).count();
+    Ok(BatchResult {
+        action: action.as_str().to_string(),
+        requested: items.len(),
+        succeeded,
+        failed: items.len() - succeeded,
+        items,
+    })
+}
 
-    Ok(Feedback::new(vec![update_item]))
+pub fn execute_pin(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<PinResult, AppError> {
+    set_pinned(item_id_raw, true, db_override, config)
 }
 
-fn build_delete_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
-    let mut parts = rest.split_whitespace();
-    let item_id_raw = parts.next().unwrap_or("").trim();
-    if item_id_raw.is_empty() || parts.next().is_some() {
-        return Ok(Feedback::new(vec![
-            Item::new("Invalid delete syntax")
-                .with_subtitle("Use: delete <item_id>")
-                .with_valid(false),
-        ]));
+pub fn execute_unpin(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<PinResult, AppError> {
+    set_pinned(item_id_raw, false, db_override, config)
+}
+
+fn set_pinned(
+    item_id_raw: &str,
+    pinned: bool,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<PinResult, AppError> {
+    let action = if pinned { "pin" } else { "unpin" };
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User(format!("{action} requires a valid item_id")))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    ensure_item_exists(&storage, item_id)?;
+
+    let trashed = storage
+        .with_connection(|conn| trash::trashed_at(conn, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if trashed.is_some() {
+        return Err(AppError::User("item_id is in trash".to_string()));
     }
 
-    let item_id = match parse_item_id(item_id_raw) {
-        Some(item_id) => format_item_id(item_id),
-        None => {
-            return Ok(Feedback::new(vec![
-                Item::new("Invalid item_id for delete")
-                    .with_subtitle("Expected itm_XXXXXXXX or positive integer item id.")
-                    .with_valid(false),
-            ]));
-        }
-    };
-    let item_display = item_display_id(&item_id);
-    let detail_text = execute_fetch_item(&item_id, None, config)
-        .ok()
-        .map(|detail| detail.text);
-    let delete_item = build_delete_item(&item_id, &item_display, detail_text.as_deref());
+    let changed = storage
+        .with_transaction(|tx| {
+            if pinned {
+                pins::pin_item(tx, item_id)
+            } else {
+                pins::unpin_item(tx, item_id)
+            }
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-    Ok(Feedback::new(vec![delete_item]))
+    Ok(PinResult {
+        item_id: format_item_id(item_id),
+        is_pinned: pinned,
+        changed,
+    })
 }
 
-fn build_copy_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
-    let mut parts = rest.split_whitespace();
-    let item_id_raw = parts.next().unwrap_or("").trim();
-    if item_id_raw.is_empty() || parts.next().is_some() {
-        return Ok(Feedback::new(vec![
-            Item::new("Invalid copy syntax")
-                .with_subtitle("Use: copy <item_id>")
-                .with_valid(false),
-        ]));
+pub fn execute_pinned_list(
+    db_override: Option<PathBuf>,
+    limit: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<ListResult>, AppError> {
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid pinned limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
+        )));
     }
 
-    let item_id = match parse_item_id(item_id_raw) {
-        Some(item_id) => format_item_id(item_id),
-        None => {
-            return Ok(Feedback::new(vec![
-                Item::new("Invalid item_id for copy")
-                    .with_subtitle("Expected itm_XXXXXXXX or positive integer item id.")
-                    .with_valid(false),
-            ]));
-        }
-    };
-    let item_display = item_display_id(&item_id);
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_connection(|conn| pins::list_pinned(conn, limit, &codec))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
 
-    let detail = match execute_fetch_item(&item_id, None, config) {
-        Ok(detail) => detail,
-        Err(AppError::User(message)) => {
-            return Ok(Feedback::new(vec![
-                Item::new("Memo item not found")
-                    .with_subtitle(format!("{message}: {item_display}"))
-                    .with_valid(false),
-            ]));
-        }
-        Err(error) => return Err(error),
+/// Write memos matching `filter` to `path` (default: a timestamped file under `export_dir`).
+pub fn execute_export(
+    format: ExportFormat,
+    path: Option<PathBuf>,
+    filter: &ExportFilter,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<ExportResult, AppError> {
+    let filter = ExportFilter {
+        tag: match filter.tag.as_deref() {
+            Some(raw) => Some(
+                normalize_tag(raw).ok_or_else(|| AppError::User(format!("invalid tag: {raw}")))?,
+            ),
+            None => None,
+        },
+        include_trash: filter.include_trash,
     };
-    let raw_json_preview = render_item_detail_json(&detail);
-    let copy_item = build_copy_item(
-        &item_id,
-        &item_display,
-        Some(&detail.text),
-        &raw_json_preview,
-    );
 
-    Ok(Feedback::new(vec![copy_item]))
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let exported_at = current_timestamp(&storage)?;
+    let codec = text_codec(&storage, config)?;
+    let (records, stamp) = storage
+        .with_connection(|conn| {
+            Ok((
+                export::load_records(conn, &filter, &codec)?,
+                export::file_stamp(conn)?,
+            ))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    let path = path.unwrap_or_else(|| {
+        config
+            .export_dir
+            .join(format!("memo-export-{stamp}.{}", format.extension()))
+    });
+    write_export_file(&path, &export::render(format, &exported_at, &records))?;
+
+    Ok(ExportResult {
+        path: path.display().to_string(),
+        format: format.as_str().to_string(),
+        exported: records.len(),
+        exported_at,
+    })
 }
 
-fn build_search_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
-    let (match_mode, query) = match parse_search_intent(rest, config.search_match_mode) {
-        Ok(parsed) => parsed,
-        Err(feedback) => return Ok(feedback),
+/// Import memos from an export-style file; duplicates (by content hash) are skipped.
+pub fn execute_import(
+    path: &Path,
+    format: Option<ExportFormat>,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<ImportResult, AppError> {
+    let format = match format {
+        Some(format) => format,
+        None => ExportFormat::from_path(path).ok_or_else(|| {
+            AppError::User(format!(
+                "cannot infer import format from {} (use --format json|markdown|csv)",
+                path.display()
+            ))
+        })?,
     };
+    let contents = fs::read_to_string(path).map_err(|error| {
+        AppError::User(format!(
+            "failed to read import file {}: {error}",
+            path.display()
+        ))
+    })?;
+    let records = import::parse_records(format, &contents)?;
 
-    if query.is_empty() {
-        return Ok(Feedback::new(vec![
-            Item::new("Type search text after keyword")
-                .with_subtitle(SEARCH_INTENT_USAGE)
-                .with_valid(false),
-        ]));
-    }
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    auto_backup(&storage, "import")?;
+    let codec = text_codec(&storage, config)?;
+    let outcome = storage
+        .with_transaction(|tx| {
+            import::apply_records(tx, &records, &config.source, config.max_input_bytes, &codec)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
 
-    if query.len() > config.max_input_bytes {
-        return Ok(Feedback::new(vec![
-            Item::new("Input exceeds MEMO_MAX_INPUT_BYTES")
-                .with_subtitle(format!(
-                    "Current {} bytes, limit {} bytes.",
-                    query.len(),
-                    config.max_input_bytes
-                ))
-                .with_valid(false),
-        ]));
-    }
+    Ok(ImportResult {
+        path: path.display().to_string(),
+        format: format.as_str().to_string(),
+        imported: outcome.item_ids.len(),
+        skipped: outcome.skipped,
+        invalid: outcome.invalid,
+        item_ids: outcome.item_ids,
+    })
+}
 
-    let rows = execute_search(None, query, match_mode, config.recent_limit, 0, config)?;
-    if rows.is_empty() {
-        return Ok(Feedback::new(vec![
-            Item::new("No matching memo records")
-                .with_subtitle(format!("No results for: {}", truncate_title(query, 64)))
-                .with_valid(false),
-        ]));
+fn write_export_file(path: &Path, contents: &str) -> Result<(), AppError> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(|error| {
+            AppError::Runtime(format!(
+                "failed to create export directory {}: {error}",
+                parent.display()
+            ))
+        })?;
     }
+    fs::write(path, contents).map_err(|error| {
+        AppError::Runtime(format!(
+            "failed to write export file {}: {error}",
+            path.display()
+        ))
+    })
+}
 
-    let mut items = Vec::with_capacity(rows.len());
-    for row in rows {
-        let item_display = item_display_id(&row.item_id);
-        let item_route = item_route_id(&row.item_id);
-        let preview = row.text_preview.trim();
-        let title = if preview.is_empty() {
-            format!("Search {}: (empty memo)", item_display)
-        } else {
-            format!("Search {}: {}", item_display, truncate_title(preview, 56))
-        };
+pub fn execute_trash_list(
+    db_override: Option<PathBuf>,
+    limit: usize,
+    offset: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<TrashResult>, AppError> {
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid trash limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
+        )));
+    }
 
-        let matched_fields = if row.matched_fields.is_empty() {
-            "n/a".to_string()
-        } else {
-            row.matched_fields.join(",")
-        };
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_connection(|conn| {
+            trash::list_trash(conn, config.trash_retention_days, limit, offset, &codec)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
 
-        items.push(
-            Item::new(title)
-                .with_uid(format!("search-{}", row.item_id))
-                .with_subtitle(format!(
-                    "{} | fields {} | score {:.3} | Press Enter to manage",
-                    row.created_at, matched_fields, row.score
-                ))
-                .with_autocomplete(format!("item {}", item_route))
-                .with_valid(false),
-        );
+pub fn execute_list(
+    db_override: Option<PathBuf>,
+    limit: usize,
+    offset: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<ListResult>, AppError> {
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid list limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
+        )));
     }
 
-    Ok(Feedback::new(items))
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+
+    storage
+        .with_connection(|conn| trash::list_live_items(conn, limit, offset, &codec))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
 }
 
-fn parse_search_intent(
-    rest: &str,
-    default_match_mode: SearchMatchMode,
-) -> Result<(SearchMatchMode, &str), Feedback> {
-    let trimmed = rest.trim();
-    if trimmed.is_empty() {
-        return Err(Feedback::new(vec![
-            Item::new("Type search text after keyword")
-                .with_subtitle("Use: search <query>")
-                .with_valid(false),
-        ]));
+pub fn execute_search(
+    db_override: Option<PathBuf>,
+    query: &str,
+    match_mode: SearchMatchMode,
+    limit: usize,
+    offset: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<SearchResult>, AppError> {
+    let normalized_query = query.trim();
+    if normalized_query.is_empty() {
+        return Err(AppError::User(
+            "search requires a non-empty query".to_string(),
+        ));
     }
 
-    if let Some(after_flag) = trimmed.strip_prefix("--match") {
-        let is_match_flag = after_flag
-            .chars()
-            .next()
-            .map(|ch| ch.is_whitespace() || ch == '=')
-            .unwrap_or(true);
-        if is_match_flag {
-            let mut rest = after_flag.trim_start();
-            if let Some(remaining) = rest.strip_prefix('=') {
-                rest = remaining.trim_start();
-            }
-            if rest.is_empty() {
-                return Err(Feedback::new(vec![
-                    Item::new("Missing search match mode")
-                        .with_subtitle(SEARCH_MATCH_USAGE)
-                        .with_valid(false),
-                ]));
-            }
+    if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid search limit: {limit} (must be integer in range 1..={MAX_SEARCH_LIMIT})"
+        )));
+    }
 
-            let mut parts = rest.splitn(2, char::is_whitespace);
-            let mode_raw = parts.next().unwrap_or("").trim();
-            let Some(match_mode) = SearchMatchMode::parse_token(mode_raw) else {
-                return Err(Feedback::new(vec![
-                    Item::new(format!(
-                        "Invalid search match mode: {}",
-                        truncate_title(mode_raw, 32)
-                    ))
-                    .with_subtitle(SEARCH_MATCH_USAGE)
-                    .with_valid(false),
-                ]));
-            };
+    let fetch_limit = limit.checked_add(offset).ok_or_else(|| {
+        AppError::User("invalid search window: limit + offset overflow".to_string())
+    })?;
+    if fetch_limit > MAX_SEARCH_FETCH_LIMIT {
+        return Err(AppError::User(format!(
+            "invalid search window: limit + offset must be <= {MAX_SEARCH_FETCH_LIMIT}"
+        )));
+    }
 
-            let query = parts.next().unwrap_or("").trim();
-            if query.is_empty() {
-                return Err(Feedback::new(vec![
-                    Item::new("Type search text after keyword")
-                        .with_subtitle(SEARCH_MATCH_USAGE)
-                        .with_valid(false),
-                ]));
-            }
-            return Ok((match_mode, query));
-        }
+    let parsed =
+        search_filters::parse_search(normalized_query, &Local::now()).map_err(AppError::User)?;
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+
+    // Operator-only queries list the filtered memos, newest first.
+    if parsed.text.is_empty() {
+        return storage
+            .with_connection(|conn| {
+                search_filters::search_filtered(conn, &codec, &parsed.filters, limit, offset)
+            })
+            .map_err(|error| AppError::Runtime(error.message().to_string()));
     }
 
-    Ok((default_match_mode, trimmed))
+    // Encrypted databases cannot use either index (both hold ciphertext), so memos are decrypted and
+    // matched in-process with the fts query syntax.
+    let rows = if codec.is_encrypted() {
+        storage
+            .with_connection(|conn| {
+                fts::scan_decrypted(
+                    conn,
+                    &codec,
+                    &parsed.text,
+                    match_mode == SearchMatchMode::Prefix,
+                    &parsed.filters,
+                    limit,
+                    offset,
+                )
+            })
+            .map_err(|error| AppError::Runtime(error.message().to_string()))?
+    } else {
+        search_exact(
+            &storage,
+            &parsed,
+            match_mode,
+            limit,
+            offset,
+            fetch_limit,
+            config,
+        )?
+    };
+    // Approximate rows cannot honour operators, so filtered searches skip the fuzzy fallback.
+    if !rows.is_empty() || offset > 0 || config.fuzzy_threshold == 0 || !parsed.filters.is_empty() {
+        return Ok(rows);
+    }
+
+    // Nothing matched exactly: offer approximate "did you mean" rows from recent memos.
+    storage
+        .with_connection(|conn| {
+            fuzzy::fuzzy_search(conn, &parsed.text, config.fuzzy_threshold, limit, &codec)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
 }
 
-fn build_empty_query_feedback(config: &RuntimeConfig) -> Result<Feedback, AppError> {
-    let db_exists = config.db_path.exists();
+fn search_exact(
+    storage: &Storage,
+    query: &ParsedSearch,
+    match_mode: SearchMatchMode,
+    limit: usize,
+    offset: usize,
+    fetch_limit: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<SearchResult>, AppError> {
+    // Fts and prefix modes run against the workflow FTS5 index (phrases, `*` prefixes, BM25);
+    // without it (`MEMO_SEARCH_FTS=0` or SQLite lacking FTS5) every mode falls back to contains.
+    let use_index = config.search_fts
+        && match_mode != SearchMatchMode::Contains
+        && storage
+            .with_connection(fts::index_available)
+            .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if use_index {
+        let Some(match_expr) =
+            fts::compile_query(&query.text, match_mode == SearchMatchMode::Prefix)
+        else {
+            return Ok(Vec::new());
+        };
+        return storage
+            .with_connection(|conn| fts::search(conn, &match_expr, &query.filters, limit, offset))
+            .map_err(|error| AppError::Runtime(error.message().to_string()));
+    }
 
-    let mut items = vec![
-        Item::new("Type memo text after keyword")
-            .with_subtitle(format!(
-                "Max {} bytes. Current source: {}.",
-                config.max_input_bytes, config.source
-            ))
-            .with_valid(false),
+    let search_fields = [
+        search::SearchField::Raw,
+        search::SearchField::Derived,
+        search::SearchField::Tags,
     ];
 
-    if !db_exists {
-        items.push(
-            Item::new("Initialize memo database")
-                .with_subtitle(format!(
-                    "Create/open SQLite at {}",
-                    config.db_path.display()
-                ))
-                .with_arg(DB_INIT_TOKEN)
-                .with_valid(true),
-        );
-        items.push(
-            Item::new("No memo records yet")
-                .with_subtitle("Run `db-init`, then use `mm <text>` to add your first memo.")
-                .with_valid(false),
+    // Upstream search cannot take workflow predicates, so trashed and filtered-out rows are
+    // over-fetched and dropped here.
+    let (excluded, rows) = storage
+        .with_connection(|conn| {
+            let mut excluded = search_filters::excluded_item_ids(conn, &query.filters)?;
+            excluded.extend(trash::trashed_item_ids(conn)?);
+            let rows = search::search_items(
+                conn,
+                &query.text,
+                repository::QueryState::All,
+                &search_fields,
+                search::SearchMatchMode::Contains,
+                fetch_limit + excluded.len(),
+            )?;
+            Ok((excluded, rows))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| !excluded.contains(&row.item_id))
+        .skip(offset)
+        .take(limit)
+        .map(|row| SearchResult {
+            item_id: format_item_id(row.item_id),
+            created_at: row.created_at,
+            score: row.score,
+            matched_fields: row.matched_fields,
+            text_preview: row.preview,
+            content_type: row.content_type,
+            validation_status: row.validation_status,
+            approximate: false,
+        })
+        .collect())
+}
+
+pub fn execute_tag_items(
+    db_override: Option<PathBuf>,
+    tag: &str,
+    limit: usize,
+    offset: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<ListResult>, AppError> {
+    let tag =
+        normalize_tag(tag).ok_or_else(|| AppError::User(format!("invalid tag: {}", tag.trim())))?;
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid tag limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
+        )));
+    }
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_connection(|conn| tags::list_items_for_tag(conn, &tag, limit, offset, &codec))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
+
+pub fn execute_tag_counts(
+    db_override: Option<PathBuf>,
+    limit: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<TagCountResult>, AppError> {
+    if !(1..=MAX_TAG_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid tag limit: {limit} (must be integer in range 1..={MAX_TAG_LIST_LIMIT})"
+        )));
+    }
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    storage
+        .with_connection(|conn| tags::list_tag_counts(conn, limit))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
+
+/// Newest-first live memos whose text contains links, with the extracted links.
+pub fn execute_links(
+    db_override: Option<PathBuf>,
+    limit: usize,
+    config: &RuntimeConfig,
+) -> Result<Vec<LinkedMemoResult>, AppError> {
+    if !(1..=MAX_LIST_LIMIT).contains(&limit) {
+        return Err(AppError::User(format!(
+            "invalid links limit: {limit} (must be integer in range 1..={MAX_LIST_LIMIT})"
+        )));
+    }
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+    let codec = text_codec(&storage, config)?;
+    storage
+        .with_connection(|conn| links::list_linked_items(conn, &codec, limit))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
+
+/// Resolve the `index`-th (1-based) link of one live memo for opening.
+pub fn execute_open_link(
+    item_id_raw: &str,
+    index: usize,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<OpenLinkResult, AppError> {
+    if parse_item_id(item_id_raw).is_none() {
+        return Err(AppError::User(
+            "open-link requires a valid item_id".to_string(),
+        ));
+    }
+    let detail = execute_fetch_item(item_id_raw, db_override, config)?;
+    let url = index
+        .checked_sub(1)
+        .and_then(|position| extract_links(&detail.text).into_iter().nth(position))
+        .ok_or_else(|| {
+            AppError::User(format!("link {index} does not exist in {}", detail.item_id))
+        })?;
+    Ok(OpenLinkResult {
+        item_id: detail.item_id,
+        index,
+        url,
+    })
+}
+
+/// Memo totals, per-source counts, memos per week (last `weeks` weeks), top tags, and database file size.
+pub fn execute_stats(
+    db_override: Option<PathBuf>,
+    weeks: usize,
+    config: &RuntimeConfig,
+) -> Result<StatsResult, AppError> {
+    if !(1..=MAX_STATS_WEEKS).contains(&weeks) {
+        return Err(AppError::User(format!(
+            "invalid stats weeks: {weeks} (must be integer in range 1..={MAX_STATS_WEEKS})"
+        )));
+    }
+
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path.clone(), config)?;
+    let ((total, live, trashed, pinned), sources, week_counts, tags) = storage
+        .with_connection(|conn| {
+            Ok((
+                stats::memo_counts(conn)?,
+                stats::source_counts(conn, STATS_TOP_LIMIT)?,
+                stats::weekly_counts(conn, weeks)?,
+                tags::list_tag_counts(conn, STATS_TOP_LIMIT)?,
+            ))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    Ok(StatsResult {
+        db_size_bytes: db_file_size(&db_path),
+        db_path: db_path.display().to_string(),
+        total,
+        live,
+        trashed,
+        pinned,
+        sources,
+        weeks: week_counts,
+        tags,
+    })
+}
+
+/// Size of the database file plus its write-ahead log, in bytes.
+fn db_file_size(db_path: &Path) -> u64 {
+    let mut wal_path = db_path.as_os_str().to_owned();
+    wal_path.push("-wal");
+    [db_path.to_path_buf(), PathBuf::from(wal_path)]
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Fetch one live memo and render its Markdown text to sanitized HTML for rich-text copy.
+pub fn execute_render_item(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<RenderedItemResult, AppError> {
+    let detail = execute_fetch_item(item_id_raw, db_override, config)?;
+    Ok(RenderedItemResult {
+        html: markdown::render_markdown_html(&detail.text),
+        item_id: detail.item_id,
+    })
+}
+
+pub fn execute_fetch_item(
+    item_id_raw: &str,
+    db_override: Option<PathBuf>,
+    config: &RuntimeConfig,
+) -> Result<ItemDetailResult, AppError> {
+    let item_id = parse_item_id(item_id_raw)
+        .ok_or_else(|| AppError::User("copy requires a valid item_id".to_string()))?;
+    let db_path = db_override.unwrap_or_else(|| config.db_path.clone());
+    let storage = open_memo_storage(db_path, config)?;
+
+    let cursor = storage
+        .with_connection(|conn| repository::lookup_fetch_cursor(conn, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if cursor.is_none() {
+        return Err(AppError::User("item_id does not exist".to_string()));
+    }
+    let trashed = storage
+        .with_connection(|conn| trash::trashed_at(conn, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if trashed.is_some() {
+        return Err(AppError::User("item_id is in trash".to_string()));
+    }
+
+    let codec = text_codec(&storage, config)?;
+    let mut detail = storage
+        .with_connection(|conn| {
+            conn.query_row(
+                &format!(
+                    "select
+                    i.item_id,
+                    i.created_at,
+                    i.source,
+                    i.raw_text,
+                    case
+                        when ad.derivation_id is not null then 'enriched'
+                        else 'pending'
+                    end as state,
+                    json_extract(ad.payload_json, '$.content_type') as content_type,
+                    json_extract(ad.payload_json, '$.validation_status') as validation_status,
+                    {IS_PINNED_SQL} as is_pinned,
+                    {ATTACHMENT_COUNT_SQL} as attachment_count,
+                    {LINKED_COUNT_SQL} as linked_count,
+                    {VERSION_COUNT_SQL} as version_count,
+                    (select du.due_at from workflow_item_due du where du.item_id = i.item_id)
+                        as due_at
+                from inbox_items i
+                left join item_derivations ad
+                  on ad.derivation_id = (
+                    select d.derivation_id
+                    from item_derivations d
+                    where d.item_id = i.item_id
+                      and d.is_active = 1
+                      and d.status = 'accepted'
+                    order by d.derivation_version desc, d.derivation_id desc
+                    limit 1
+                  )
+                where i.item_id = ?1"
+                ),
+                [item_id],
+                |row| {
+                    Ok(ItemDetailResult {
+                        item_id: format_item_id(row.get::<_, i64>(0)?),
+                        created_at: row.get(1)?,
+                        source: row.get(2)?,
+                        text: row.get(3)?,
+                        state: row.get(4)?,
+                        content_type: row.get(5)?,
+                        validation_status: row.get(6)?,
+                        is_pinned: row.get(7)?,
+                        attachment_count: row.get(8)?,
+                        linked_count: row.get(9)?,
+                        version_count: row.get(10)?,
+                        due_at: row.get(11)?,
+                    })
+                },
+            )
+            .map_err(MemoCliError::db_query)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    detail.text = codec
+        .open(&detail.text)
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    Ok(detail)
+}
+
+/// Open storage and apply the `MEMO_TRASH_RETENTION_DAYS` auto-purge.
+fn open_memo_storage(db_path: PathBuf, config: &RuntimeConfig) -> Result<Storage, AppError> {
+    let storage = open_storage(db_path)?;
+    purge_expired_trash(&storage, config)?;
+    Ok(storage)
+}
+
+fn purge_expired_trash(storage: &Storage, config: &RuntimeConfig) -> Result<i64, AppError> {
+    if config.trash_retention_days == 0 {
+        return Ok(0);
+    }
+
+    storage
+        .with_transaction(|tx| trash::purge_expired(tx, config.trash_retention_days))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
+
+fn ensure_item_exists(storage: &Storage, item_id: i64) -> Result<(), AppError> {
+    let cursor = storage
+        .with_connection(|conn| repository::lookup_fetch_cursor(conn, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if cursor.is_none() {
+        return Err(AppError::User("item_id does not exist".to_string()));
+    }
+    Ok(())
+}
+
+/// Reject missing and trashed items.
+fn ensure_live_item(storage: &Storage, item_id: i64) -> Result<(), AppError> {
+    ensure_item_exists(storage, item_id)?;
+    let trashed = storage
+        .with_connection(|conn| trash::trashed_at(conn, item_id))
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    if trashed.is_some() {
+        return Err(AppError::User("item_id is in trash".to_string()));
+    }
+    Ok(())
+}
+
+fn current_timestamp(storage: &Storage) -> Result<String, AppError> {
+    storage
+        .with_connection(|conn| {
+            conn.query_row("select strftime('%Y-%m-%dT%H:%M:%fZ', 'now')", [], |row| {
+                row.get(0)
+            })
+            .map_err(MemoCliError::db_query)
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))
+}
+
+pub fn parse_add_token(arg: &str) -> Option<String> {
+    arg.strip_prefix(ADD_TOKEN_PREFIX).map(str::to_string)
+}
+
+pub fn build_add_token(text: &str) -> String {
+    format!("{ADD_TOKEN_PREFIX}{text}")
+}
+
+pub fn parse_clip_token(arg: &str) -> Option<String> {
+    arg.strip_prefix(CLIP_TOKEN_PREFIX).map(str::to_string)
+}
+
+pub fn build_clip_token(text: &str) -> String {
+    format!("{CLIP_TOKEN_PREFIX}{text}")
+}
+
+pub fn parse_copy_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(COPY_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_copy_token(item_id: &str) -> String {
+    format!("{COPY_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_copy_json_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(COPY_JSON_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_copy_json_token(item_id: &str) -> String {
+    format!("{COPY_JSON_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_copy_md_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(COPY_MD_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_copy_md_token(item_id: &str) -> String {
+    format!("{COPY_MD_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_copy_html_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(COPY_HTML_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_copy_html_token(item_id: &str) -> String {
+    format!("{COPY_HTML_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_update_token(arg: &str) -> Option<(String, String)> {
+    let payload = arg.strip_prefix(UPDATE_TOKEN_PREFIX)?;
+    let (item_id_raw, text_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let item_id = parse_item_id(item_id_raw)?;
+    let text = text_raw.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    Some((format_item_id(item_id), text.to_string()))
+}
+
+pub fn build_update_token(item_id: &str, text: &str) -> String {
+    format!("{UPDATE_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{text}")
+}
+
+pub fn parse_delete_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(DELETE_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_delete_token(item_id: &str) -> String {
+    format!("{DELETE_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_restore_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(RESTORE_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_restore_token(item_id: &str) -> String {
+    format!("{RESTORE_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_purge_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(PURGE_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_purge_token(item_id: &str) -> String {
+    format!("{PURGE_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_pin_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(PIN_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_pin_token(item_id: &str) -> String {
+    format!("{PIN_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_unpin_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(UNPIN_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_unpin_token(item_id: &str) -> String {
+    format!("{UNPIN_TOKEN_PREFIX}{item_id}")
+}
+
+pub fn parse_edit_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(EDIT_TOKEN_PREFIX)?;
+    let item_id = parse_item_id(payload.trim())?;
+    Some(format_item_id(item_id))
+}
+
+pub fn build_edit_token(item_id: &str) -> String {
+    format!("{EDIT_TOKEN_PREFIX}{item_id}")
+}
+
+/// Parse `remind::<item_id>::<due>`; `<due>` is an RFC 3339 timestamp or a clear word.
+pub fn parse_remind_token(arg: &str) -> Option<(String, String)> {
+    let payload = arg.strip_prefix(REMIND_TOKEN_PREFIX)?;
+    let (item_id_raw, due_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let item_id = parse_item_id(item_id_raw.trim())?;
+    if due_raw.trim().is_empty() {
+        return None;
+    }
+    Some((format_item_id(item_id), due_raw.trim().to_string()))
+}
+
+pub fn build_remind_token(item_id: &str, due: &str) -> String {
+    format!("{REMIND_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{due}")
+}
+
+/// Parse `template-add::<name>::<text>`; the text keeps any later `::` bytes.
+pub fn parse_template_add_token(arg: &str) -> Option<(String, String)> {
+    let payload = arg.strip_prefix(TEMPLATE_ADD_TOKEN_PREFIX)?;
+    let (name_raw, body) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let name = normalize_template_name(name_raw)?;
+    if body.trim().is_empty() {
+        return None;
+    }
+    Some((name, body.to_string()))
+}
+
+pub fn build_template_add_token(name: &str, body: &str) -> String {
+    format!("{TEMPLATE_ADD_TOKEN_PREFIX}{name}{UPDATE_TOKEN_DELIMITER}{body}")
+}
+
+/// Parse `attach::<item_id>::<location>`; the location keeps any later `::` bytes.
+pub fn parse_attach_token(arg: &str) -> Option<(String, String)> {
+    let payload = arg.strip_prefix(ATTACH_TOKEN_PREFIX)?;
+    let (item_id_raw, location) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let item_id = parse_item_id(item_id_raw.trim())?;
+    if location.trim().is_empty() {
+        return None;
+    }
+    Some((format_item_id(item_id), location.to_string()))
+}
+
+pub fn build_attach_token(item_id: &str, location: &str) -> String {
+    format!("{ATTACH_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{location}")
+}
+
+/// Parse `link::<item_id>::<other_item_id>` into two distinct canonical item ids.
+pub fn parse_link_token(arg: &str) -> Option<(String, String)> {
+    let payload = arg.strip_prefix(LINK_TOKEN_PREFIX)?;
+    let (item_id_raw, other_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let item_id = parse_item_id(item_id_raw.trim())?;
+    let other_item_id = parse_item_id(other_raw.trim())?;
+    if item_id == other_item_id {
+        return None;
+    }
+    Some((format_item_id(item_id), format_item_id(other_item_id)))
+}
+
+pub fn build_link_token(item_id: &str, other_item_id: &str) -> String {
+    format!("{LINK_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{other_item_id}")
+}
+
+/// Parse `merge::<keep_item_id>::<merge_item_id>` into two distinct canonical item ids.
+pub fn parse_merge_token(arg: &str) -> Option<(String, String)> {
+    let payload = arg.strip_prefix(MERGE_TOKEN_PREFIX)?;
+    let (keep_raw, merge_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let item_id = parse_item_id(keep_raw.trim())?;
+    let merged_item_id = parse_item_id(merge_raw.trim())?;
+    if item_id == merged_item_id {
+        return None;
+    }
+    Some((format_item_id(item_id), format_item_id(merged_item_id)))
+}
+
+pub fn build_merge_token(item_id: &str, merged_item_id: &str) -> String {
+    format!("{MERGE_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{merged_item_id}")
+}
+
+pub fn parse_history_restore_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(HISTORY_RESTORE_TOKEN_PREFIX)?.trim();
+    let version_id = payload.parse::<i64>().ok().filter(|id| *id > 0)?;
+    Some(version_id.to_string())
+}
+
+pub fn build_history_restore_token(version_id: i64) -> String {
+    format!("{HISTORY_RESTORE_TOKEN_PREFIX}{version_id}")
+}
+
+pub fn parse_open_attachment_token(arg: &str) -> Option<String> {
+    let payload = arg.strip_prefix(OPEN_ATTACHMENT_TOKEN_PREFIX)?.trim();
+    let attachment_id = payload.parse::<i64>().ok().filter(|id| *id > 0)?;
+    Some(attachment_id.to_string())
+}
+
+pub fn build_open_attachment_token(attachment_id: i64) -> String {
+    format!("{OPEN_ATTACHMENT_TOKEN_PREFIX}{attachment_id}")
+}
+
+/// Parse `open-link::<item_id>::<index>` into the canonical item id and 1-based link index.
+pub fn parse_open_link_token(arg: &str) -> Option<(String, usize)> {
+    let payload = arg.strip_prefix(OPEN_LINK_TOKEN_PREFIX)?;
+    let (item_id_raw, index_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let item_id = parse_item_id(item_id_raw.trim())?;
+    let index = index_raw
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index > 0)?;
+    Some((format_item_id(item_id), index))
+}
+
+pub fn build_open_link_token(item_id: &str, index: usize) -> String {
+    format!("{OPEN_LINK_TOKEN_PREFIX}{item_id}{UPDATE_TOKEN_DELIMITER}{index}")
+}
+
+/// Parse `export::<format>[::<tag>]` into the format and optional tag filter.
+pub fn parse_export_token(arg: &str) -> Option<(ExportFormat, Option<String>)> {
+    let payload = arg.strip_prefix(EXPORT_TOKEN_PREFIX)?;
+    let (format_raw, tag_raw) = match payload.split_once(UPDATE_TOKEN_DELIMITER) {
+        Some((format_raw, tag_raw)) => (format_raw, Some(tag_raw)),
+        None => (payload, None),
+    };
+    let format = ExportFormat::parse_token(format_raw)?;
+    let tag = match tag_raw {
+        Some(raw) => Some(normalize_tag(raw)?),
+        None => None,
+    };
+    Some((format, tag))
+}
+
+pub fn parse_batch_token(arg: &str) -> Option<(BatchAction, Vec<String>)> {
+    let payload = arg.strip_prefix(BATCH_TOKEN_PREFIX)?;
+    let (action_raw, ids_raw) = payload.split_once(UPDATE_TOKEN_DELIMITER)?;
+    let action = BatchAction::parse_token(action_raw)?;
+    let item_ids = ids_raw
+        .split(BATCH_ID_DELIMITER)
+        .map(|raw| parse_item_id(raw.trim()).map(format_item_id))
+        .collect::<Option<Vec<_>>>()?;
+    (!item_ids.is_empty()).then_some((action, item_ids))
+}
+
+pub fn build_batch_token(action: BatchAction, item_ids: &[String]) -> String {
+    format!(
+        "{BATCH_TOKEN_PREFIX}{}::{}",
+        action.as_str(),
+        item_ids.join(&BATCH_ID_DELIMITER.to_string())
+    )
+}
+
+pub fn parse_undo_token(arg: &str) -> Option<i64> {
+    let entry_id = arg.strip_prefix(UNDO_TOKEN_PREFIX)?.trim();
+    entry_id
+        .parse::<i64>()
+        .ok()
+        .filter(|entry_id| *entry_id > 0)
+}
+
+pub fn build_undo_token(entry_id: i64) -> String {
+    format!("{UNDO_TOKEN_PREFIX}{entry_id}")
+}
+
+pub fn build_export_token(format: ExportFormat, tag: Option<&str>) -> String {
+    match tag {
+        Some(tag) => format!("{EXPORT_TOKEN_PREFIX}{}::{tag}", format.as_str()),
+        None => format!("{EXPORT_TOKEN_PREFIX}{}", format.as_str()),
+    }
+}
+
+fn strip_intent<'a>(query: &'a str, intent: &str) -> Option<&'a str> {
+    let mut parts = query.splitn(2, char::is_whitespace);
+    let first = parts.next()?;
+    if !first.eq_ignore_ascii_case(intent) {
+        return None;
+    }
+    Some(parts.next().unwrap_or("").trim())
+}
+
+fn item_route_id(item_id: &str) -> String {
+    parse_item_id(item_id)
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| item_id.to_string())
+}
+
+fn item_display_id(item_id: &str) -> String {
+    parse_item_id(item_id)
+        .map(|id| format!("#{id}"))
+        .unwrap_or_else(|| item_id.to_string())
+}
+
+fn build_item_action_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.split_whitespace();
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    if item_id_raw.is_empty() || parts.next().is_some() {
+        return Ok(Feedback::new(vec![
+            Item::new(
+                config
+                    .language
+                    .text(Msg::InvalidSyntax, &[&"item selection"]),
+            )
+            .with_subtitle("Use: item <item_id>")
+            .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "selection",
+                config.language,
+            )]));
+        }
+    };
+    let item_route = item_route_id(&item_id);
+    let item_display = item_display_id(&item_id);
+
+    let detail = match execute_fetch_item(&item_id, None, config) {
+        Ok(detail) => detail,
+        Err(AppError::User(message)) => {
+            return Ok(Feedback::new(vec![build_item_not_found_item(
+                &message,
+                &item_display,
+                config.language,
+            )]));
+        }
+        Err(error) => return Err(error),
+    };
+    let raw_json_preview = render_item_detail_json(&detail);
+    let copy_item = build_copy_item(
+        &item_id,
+        &item_display,
+        Some(&detail.text),
+        &raw_json_preview,
+        config,
+    );
+    let update_item = build_update_item(
+        &item_id,
+        &item_display,
+        &item_route,
+        Some(&detail.text),
+        None,
+        false,
+        config.language,
+    );
+    let delete_item =
+        build_delete_item(&item_id, &item_display, Some(&detail.text), config.language);
+    let pin_item =
+        build_pin_toggle_item(&item_id, &item_display, detail.is_pinned, config.language);
+    let edit_item = build_edit_item(&item_id, &item_display, config);
+
+    let mut items = vec![copy_item, update_item, delete_item, pin_item, edit_item];
+    if detail.version_count > 0 {
+        items.push(build_history_summary_item(
+            &item_route,
+            &item_display,
+            detail.version_count,
+            config.language,
+        ));
+    }
+    if detail.linked_count > 0 {
+        items.push(build_linked_summary_item(
+            &item_route,
+            &item_display,
+            detail.linked_count,
+            config.language,
+        ));
+    }
+    if detail.attachment_count > 0 {
+        let attachments = execute_attachments(&item_id, None, config)?;
+        let total = attachments.len();
+        items.extend(
+            attachments
+                .iter()
+                .take(MAX_ATTACHMENT_MENU_ROWS)
+                .enumerate()
+                .map(|(index, attachment)| {
+                    build_open_attachment_item(attachment, index, total, config)
+                }),
+        );
+    }
+    let links = extract_links(&detail.text);
+    items.extend(
+        links
+            .iter()
+            .take(MAX_LINK_MENU_ROWS)
+            .enumerate()
+            .map(|(index, url)| build_open_link_item(&item_id, url, index, links.len(), config)),
+    );
+
+    Ok(Feedback::new(items))
+}
+
+fn build_invalid_item_id_item(intent: &str, lang: Language) -> Item {
+    Item::new(lang.text(Msg::InvalidItemId, &[&intent]))
+        .with_subtitle(lang.text(Msg::InvalidItemIdHint, &[]))
+        .with_valid(false)
+}
+
+fn build_item_not_found_item(message: &str, item_display: &str, lang: Language) -> Item {
+    Item::new(lang.text(Msg::ItemNotFound, &[]))
+        .with_subtitle(format!("{message}: {item_display}"))
+        .with_valid(false)
+}
+
+fn build_update_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    let text = parts.next().unwrap_or("").trim();
+
+    if item_id_raw.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(config.language.text(Msg::InvalidSyntax, &[&"update"]))
+                .with_subtitle("Use: update <item_id> <new text>")
+                .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "update",
+                config.language,
+            )]));
+        }
+    };
+    let item_route = item_route_id(&item_id);
+    let item_display = item_display_id(&item_id);
+    let detail_text = execute_fetch_item(&item_id, None, config)
+        .ok()
+        .map(|detail| detail.text);
+
+    if text.is_empty() {
+        let update_item = build_update_item(
+            &item_id,
+            &item_display,
+            &item_route,
+            detail_text.as_deref(),
+            None,
+            false,
+            config.language,
+        );
+        return Ok(Feedback::new(vec![update_item]));
+    }
+
+    if text.len() > config.max_input_bytes {
+        return Ok(Feedback::new(vec![build_input_too_large_item(
+            text.len(),
+            config,
+        )]));
+    }
+
+    let update_item = build_update_item(
+        &item_id,
+        &item_display,
+        &item_route,
+        detail_text.as_deref(),
+        Some(text),
+        true,
+        config.language,
+    );
+
+    Ok(Feedback::new(vec![update_item]))
+}
+
+fn build_delete_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.split_whitespace();
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    if item_id_raw.is_empty() || parts.next().is_some() {
+        return Ok(Feedback::new(vec![
+            Item::new(config.language.text(Msg::InvalidSyntax, &[&"delete"]))
+                .with_subtitle("Use: delete <item_id>")
+                .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "delete",
+                config.language,
+            )]));
+        }
+    };
+    let item_display = item_display_id(&item_id);
+    let detail_text = execute_fetch_item(&item_id, None, config)
+        .ok()
+        .map(|detail| detail.text);
+    let delete_item = build_delete_item(
+        &item_id,
+        &item_display,
+        detail_text.as_deref(),
+        config.language,
+    );
+
+    Ok(Feedback::new(vec![delete_item]))
+}
+
+fn build_edit_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.split_whitespace();
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    if item_id_raw.is_empty() || parts.next().is_some() {
+        return Ok(Feedback::new(vec![
+            Item::new(config.language.text(Msg::InvalidSyntax, &[&"edit"]))
+                .with_subtitle("Use: edit <item_id>")
+                .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "edit",
+                config.language,
+            )]));
+        }
+    };
+    let item_display = item_display_id(&item_id);
+
+    Ok(Feedback::new(vec![build_edit_item(
+        &item_id,
+        &item_display,
+        config,
+    )]))
+}
+
+fn build_remind_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    let when_raw = parts.next().unwrap_or("").trim();
+    if item_id_raw.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid remind syntax")
+                .with_subtitle(REMIND_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "remind",
+                config.language,
+            )]));
+        }
+    };
+    let item_display = item_display_id(&item_id);
+
+    if when_raw.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Set reminder: {item_display}"))
+                .with_subtitle(REMIND_INTENT_USAGE)
+                .with_autocomplete(format!("remind {} ", item_route_id(&item_id)))
+                .with_valid(false),
+        ]));
+    }
+
+    let detail = match execute_fetch_item(&item_id, None, config) {
+        Ok(detail) => detail,
+        Err(AppError::User(message)) => {
+            return Ok(Feedback::new(vec![build_item_not_found_item(
+                &message,
+                &item_display,
+                config.language,
+            )]));
+        }
+        Err(error) => return Err(error),
+    };
+
+    if is_due_clear_word(when_raw) {
+        let subtitle = match &detail.due_at {
+            Some(due_at) => format!("Currently due {due_at}. Press Enter to clear."),
+            None => "No reminder is set for this memo.".to_string(),
+        };
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Clear reminder: {item_display}"))
+                .with_subtitle(subtitle)
+                .with_arg(build_remind_token(&item_id, due::DUE_CLEAR_WORDS[0]))
+                .with_valid(true),
+        ]));
+    }
+
+    let now = Local::now();
+    let Some(due) = due::parse_due_phrase(when_raw, &now) else {
+        return Ok(Feedback::new(vec![
+            Item::new(format!(
+                "Invalid due date: {}",
+                truncate_title(when_raw, 48)
+            ))
+            .with_subtitle(REMIND_INTENT_USAGE)
+            .with_valid(false),
+        ]));
+    };
+
+    Ok(Feedback::new(vec![
+        Item::new(format!(
+            "Remind {item_display}: {}",
+            due.with_timezone(&Local).format("%a %Y-%m-%d %H:%M")
+        ))
+        .with_subtitle(format!(
+            "{} | Press Enter to set the due date.",
+            due::describe_due(&due, &now.with_timezone(&Utc))
+        ))
+        .with_arg(build_remind_token(&item_id, &due::format_due(&due)))
+        .with_valid(true),
+    ]))
+}
+
+fn build_attach_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    let location_raw = parts.next().unwrap_or("").trim();
+    if item_id_raw.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid attach syntax")
+                .with_subtitle(ATTACH_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "attach",
+                config.language,
+            )]));
+        }
+    };
+    let item_display = item_display_id(&item_id);
+
+    if location_raw.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Attach to memo: {item_display}"))
+                .with_subtitle("Type a file path or URL to attach.")
+                .with_autocomplete(format!("attach {} ", item_route_id(&item_id)))
+                .with_valid(false),
+        ]));
+    }
+
+    let (kind, location) = match attachments::normalize_location(location_raw) {
+        Ok(resolved) => resolved,
+        Err(AppError::User(message)) => {
+            return Ok(Feedback::new(vec![
+                Item::new("Invalid attachment location")
+                    .with_subtitle(message)
+                    .with_valid(false),
+            ]));
+        }
+        Err(error) => return Err(error),
+    };
+    if let Err(AppError::User(message)) = execute_fetch_item(&item_id, None, config) {
+        return Ok(Feedback::new(vec![build_item_not_found_item(
+            &message,
+            &item_display,
+            config.language,
+        )]));
+    }
+
+    let mut item = Item::new(format!(
+        "Attach {kind} to memo {item_display}: {}",
+        truncate_title(&location, config.preview_chars)
+    ))
+    .with_subtitle(format!("Press Enter to attach {location}"))
+    .with_arg(build_attach_token(&item_id, &location))
+    .with_valid(true);
+    if kind == attachments::ATTACHMENT_KIND_FILE {
+        item = item.with_icon(ItemIcon::new(location.as_str()).with_type("fileicon"));
+    }
+    Ok(Feedback::new(vec![item]))
+}
+
+fn build_copy_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.split_whitespace();
+    let item_id_raw = parts.next().unwrap_or("").trim();
+    if item_id_raw.is_empty() || parts.next().is_some() {
+        return Ok(Feedback::new(vec![
+            Item::new(config.language.text(Msg::InvalidSyntax, &[&"copy"]))
+                .with_subtitle("Use: copy <item_id>")
+                .with_valid(false),
+        ]));
+    }
+
+    let item_id = match parse_item_id(item_id_raw) {
+        Some(item_id) => format_item_id(item_id),
+        None => {
+            return Ok(Feedback::new(vec![build_invalid_item_id_item(
+                "copy",
+                config.language,
+            )]));
+        }
+    };
+    let item_display = item_display_id(&item_id);
+
+    let detail = match execute_fetch_item(&item_id, None, config) {
+        Ok(detail) => detail,
+        Err(AppError::User(message)) => {
+            return Ok(Feedback::new(vec![build_item_not_found_item(
+                &message,
+                &item_display,
+                config.language,
+            )]));
+        }
+        Err(error) => return Err(error),
+    };
+    let raw_json_preview = render_item_detail_json(&detail);
+    let copy_item = build_copy_item(
+        &item_id,
+        &item_display,
+        Some(&detail.text),
+        &raw_json_preview,
+        config,
+    );
+
+    Ok(Feedback::new(vec![copy_item]))
+}
+
+fn build_search_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let (match_mode, query) = match parse_search_intent(rest, config.search_match_mode) {
+        Ok(parsed) => parsed,
+        Err(feedback) => return Ok(feedback),
+    };
+
+    if query.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(config.language.text(Msg::TypeSearchText, &[]))
+                .with_subtitle(SEARCH_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+
+    if query.len() > config.max_input_bytes {
+        return Ok(Feedback::new(vec![build_input_too_large_item(
+            query.len(),
+            config,
+        )]));
+    }
+
+    if let Err(message) = search_filters::parse_search(query, &Local::now()) {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid search filter")
+                .with_subtitle(message)
+                .with_valid(false),
+        ]));
+    }
+
+    let rows = execute_search(None, query, match_mode, config.recent_limit, 0, config)?;
+    if rows.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(config.language.text(Msg::NoMatchingRecords, &[]))
+                .with_subtitle(config.language.text(
+                    Msg::NoResultsFor,
+                    &[&truncate_title(query, config.wide_preview_chars())],
+                ))
+                .with_valid(false),
+        ]));
+    }
+
+    let lang = config.language;
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let item_display = item_display_id(&row.item_id);
+        let item_route = item_route_id(&row.item_id);
+        let label = if row.approximate {
+            Msg::DidYouMeanRow
+        } else {
+            Msg::SearchRow
+        };
+        let title = lang.text(
+            label,
+            &[&item_display, &row_preview(&row.text_preview, config)],
+        );
+        let subtitle = if row.approximate {
+            lang.text(
+                Msg::ApproximateMatchManage,
+                &[&row.created_at, &format!("{:.0}", row.score * 100.0)],
+            )
+        } else {
+            let matched_fields = if row.matched_fields.is_empty() {
+                lang.text(Msg::NotAvailable, &[])
+            } else {
+                row.matched_fields.join(",")
+            };
+            lang.text(
+                Msg::SearchFieldsManage,
+                &[
+                    &row.created_at,
+                    &matched_fields,
+                    &format!("{:.3}", row.score),
+                ],
+            )
+        };
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("search-{}", row.item_id))
+                .with_subtitle(subtitle)
+                .with_autocomplete(format!("item {}", item_route))
+                .with_valid(false),
+        );
+    }
+
+    Ok(Feedback::new(items))
+}
+
+fn build_tag_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    if rest.is_empty() {
+        return build_tag_browse_feedback(config);
+    }
+
+    let Some(tag) = normalize_tag(rest) else {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Invalid tag: {}", truncate_title(rest, 32)))
+                .with_subtitle(TAG_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    };
+
+    if !config.db_path.exists() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("No memos tagged #{tag}"))
+                .with_subtitle("Memo database does not exist yet.")
+                .with_valid(false),
+        ]));
+    }
+
+    let rows = execute_tag_items(None, &tag, config.recent_limit, 0, config)?;
+    if rows.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("No memos tagged #{tag}"))
+                .with_subtitle(TAG_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let item_display = item_display_id(&row.item_id);
+        let item_route = item_route_id(&row.item_id);
+        let preview = row.text_preview.trim();
+        let title = if preview.is_empty() {
+            format!("#{tag} {}: (empty memo)", item_display)
+        } else {
+            format!(
+                "#{tag} {}: {}",
+                item_display,
+                truncate_title(preview, config.preview_chars)
+            )
+        };
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("tag-{tag}-{}", row.item_id))
+                .with_subtitle(format!(
+                    "{} | {} | Press Enter to manage",
+                    row.created_at, row.state
+                ))
+                .with_autocomplete(format!("item {}", item_route))
+                .with_valid(false),
+        );
+    }
+
+    Ok(Feedback::new(items))
+}
+
+fn build_trash_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    if !rest.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid trash syntax")
+                .with_subtitle("Use: trash")
+                .with_valid(false),
+        ]));
+    }
+
+    let rows = if config.db_path.exists() {
+        execute_trash_list(None, MAX_RECENT_LIMIT, 0, config)?
+    } else {
+        Vec::new()
+    };
+    if rows.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Trash is empty")
+                .with_subtitle("Deleted memos appear here until restored or purged.")
+                .with_valid(false),
+        ]));
+    }
+
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let item_display = item_display_id(&row.item_id);
+        let preview = row.text_preview.trim();
+        let title = if preview.is_empty() {
+            format!("Trash {}: (empty memo)", item_display)
+        } else {
+            format!(
+                "Trash {}: {}",
+                item_display,
+                truncate_title(preview, config.preview_chars)
+            )
+        };
+        let expiry = match &row.purge_after {
+            Some(purge_after) => format!(" | auto-purge {purge_after}"),
+            None => String::new(),
+        };
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("trash-{}", row.item_id))
+                .with_subtitle(format!(
+                    "Deleted {}{expiry} | Enter restore, Cmd purge",
+                    row.deleted_at
+                ))
+                .with_arg(build_restore_token(&row.item_id))
+                .with_mod(
+                    "cmd",
+                    ItemModifier::new()
+                        .with_subtitle(format!("Permanently delete memo {item_display}"))
+                        .with_arg(build_purge_token(&row.item_id))
+                        .with_valid(true),
+                )
+                .with_valid(true),
+        );
+    }
+
+    Ok(Feedback::new(items))
+}
+
+fn build_export_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut format = None;
+    let mut tag = None;
+    for word in rest.split_whitespace() {
+        if format.is_none()
+            && tag.is_none()
+            && let Some(parsed) = ExportFormat::parse_token(word)
+        {
+            format = Some(parsed);
+        } else if tag.is_none()
+            && let Some(parsed) = normalize_tag(word)
+        {
+            tag = Some(parsed);
+        } else {
+            return Ok(Feedback::new(vec![
+                Item::new("Invalid export syntax")
+                    .with_subtitle(EXPORT_INTENT_USAGE)
+                    .with_valid(false),
+            ]));
+        }
+    }
+
+    let formats = match format {
+        Some(format) => vec![format],
+        None => ExportFormat::ALL.to_vec(),
+    };
+    let scope = match &tag {
+        Some(tag) => format!(" tagged #{tag}"),
+        None => String::new(),
+    };
+
+    let items = formats
+        .into_iter()
+        .map(|format| {
+            Item::new(format!("Export memos{scope} as {}", format.label()))
+                .with_uid(format!("export-{}", format.as_str()))
+                .with_subtitle(format!(
+                    "Save memo-export-<timestamp>.{} to {}",
+                    format.extension(),
+                    config.export_dir.display()
+                ))
+                .with_arg(build_export_token(format, tag.as_deref()))
+                .with_valid(true)
+        })
+        .collect();
+
+    Ok(Feedback::new(items))
+}
+
+fn build_batch_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let guidance = |title: String| {
+        Ok(Feedback::new(vec![
+            Item::new(title)
+                .with_subtitle(BATCH_INTENT_USAGE)
+                .with_valid(false),
+        ]))
+    };
+    let mut parts = rest.split_whitespace();
+    let Some(action_raw) = parts.next() else {
+        return guidance("Select a batch action".to_string());
+    };
+    let Some(action) = BatchAction::parse_token(action_raw) else {
+        return guidance(format!(
+            "Unknown batch action: {}",
+            truncate_title(action_raw, 32)
+        ));
+    };
+
+    let mut explicit = Vec::new();
+    let mut tags = Vec::new();
+    for target in parts {
+        if target.starts_with('#') {
+            match normalize_tag(target) {
+                Some(tag) => tags.push(tag),
+                None => {
+                    return guidance(format!(
+                        "Invalid tag in batch: {}",
+                        truncate_title(target, 32)
+                    ));
+                }
+            }
+        } else {
+            match parse_item_id(target) {
+                Some(item_id) => explicit.push(item_id),
+                None => {
+                    return guidance(format!(
+                        "Invalid item_id in batch: {}",
+                        truncate_title(target, 32)
+                    ));
+                }
+            }
+        }
+    }
+    if explicit.is_empty() && tags.is_empty() {
+        return guidance(format!("Select memos to batch {}", action.as_str()));
+    }
+    if !config.db_path.exists() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("No memos to batch {}", action.as_str()))
+                .with_subtitle("Memo database does not exist yet.")
+                .with_valid(false),
+        ]));
+    }
+
+    let storage = open_storage(config.db_path.clone())?;
+    let codec = text_codec(&storage, config)?;
+    let plan = storage
+        .with_connection(|conn| {
+            let item_ids =
+                batch::resolve_targets(conn, action, &explicit, &tags, MAX_BATCH_ITEMS + 1)?;
+            item_ids
+                .into_iter()
+                .map(|item_id| {
+                    Ok((
+                        format_item_id(item_id),
+                        batch::check_target(conn, action, item_id)?,
+                        batch::target_preview(conn, &codec, item_id)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>, MemoCliError>>()
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+
+    let ready = plan
+        .iter()
+        .filter(|(_, check, _)| check.is_ok())
+        .map(|(item_id, _, _)| item_id.clone())
+        .collect::<Vec<_>>();
+    let skipped = plan.len() - ready.len();
+    let mut items = Vec::with_capacity(plan.len() + 1);
+    if ready.len() > MAX_BATCH_ITEMS {
+        items.push(
+            Item::new(format!("Too many memos for one batch ({})", ready.len()))
+                .with_subtitle(format!("Limit {MAX_BATCH_ITEMS}; narrow the selection."))
+                .with_valid(false),
+        );
+        return Ok(Feedback::new(items));
+    }
+    if ready.is_empty() {
+        items.push(
+            Item::new(format!("Nothing to batch {}", action.as_str()))
+                .with_subtitle(format!("All {skipped} selected memos would be skipped."))
+                .with_valid(false),
+        );
+    } else {
+        let refs = ready
+            .iter()
+            .map(|item_id| item_display_id(item_id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let skipped_note = if skipped == 0 {
+            String::new()
+        } else {
+            format!(" | {skipped} skipped")
+        };
+        items.push(
+            Item::new(format!(
+                "Confirm batch {}: {} {}",
+                action.as_str(),
+                ready.len(),
+                if ready.len() == 1 { "memo" } else { "memos" }
+            ))
+            .with_uid(format!("batch-{}", action.as_str()))
+            .with_subtitle(truncate_title(
+                &format!("{refs}{skipped_note}"),
+                ACTION_TITLE_MAX_CHARS,
+            ))
+            .with_arg(build_batch_token(action, &ready))
+            .with_valid(true),
+        );
+    }
+
+    for (item_id, check, preview) in &plan {
+        let preview = preview.as_deref().map(str::trim).unwrap_or_default();
+        let title = if preview.is_empty() {
+            format!("{}: (no text)", item_display_id(item_id))
+        } else {
+            format!(
+                "{}: {}",
+                item_display_id(item_id),
+                truncate_title(preview, config.preview_chars)
+            )
+        };
+        let subtitle = match check {
+            Ok(()) => format!("Will {}", action.as_str()),
+            Err(reason) => format!("Skipped: {reason}"),
+        };
+        items.push(
+            Item::new(title)
+                .with_uid(format!("batch-{}-{item_id}", action.as_str()))
+                .with_subtitle(subtitle)
+                .with_valid(false),
+        );
+    }
+    Ok(Feedback::new(items))
+}
+
+fn build_links_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    if !rest.trim().is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid links syntax")
+                .with_subtitle(LINKS_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+
+    let rows = if config.db_path.exists() {
+        execute_links(None, config.recent_limit, config)?
+    } else {
+        Vec::new()
+    };
+    if rows.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("No memos with links")
+                .with_subtitle("Memos containing http(s):// or www. links are listed here.")
+                .with_valid(false),
+        ]));
+    }
+
+    let mut items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let item_display = item_display_id(&row.item_id);
+        let item_route = item_route_id(&row.item_id);
+        let preview = row.text_preview.trim();
+        let title = if preview.is_empty() {
+            format!("{}: (empty memo)", item_display)
+        } else {
+            format!(
+                "{}: {}",
+                item_display,
+                truncate_title(preview, config.preview_chars)
+            )
+        };
+        let count = row.links.len();
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("links-{}", row.item_id))
+                .with_subtitle(format!(
+                    "{count} {} | {} | Press Enter to manage",
+                    if count == 1 { "link" } else { "links" },
+                    truncate_title(&row.links[0], config.preview_chars)
+                ))
+                .with_autocomplete(format!("item {}", item_route))
+                .with_valid(false),
+        );
+    }
+
+    Ok(Feedback::new(items))
+}
+
+fn build_link_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let guard = |title: &str, subtitle: String| {
+        Ok(Feedback::new(vec![
+            Item::new(title).with_subtitle(subtitle).with_valid(false),
+        ]))
+    };
+    let ids = rest.split_whitespace().collect::<Vec<_>>();
+    if ids.is_empty() || ids.len() > 2 {
+        return guard("Invalid link syntax", LINK_INTENT_USAGE.to_string());
+    }
+
+    let Some(item_id) = parse_item_id(ids[0]).map(format_item_id) else {
+        return Ok(Feedback::new(vec![build_invalid_item_id_item(
+            "link",
+            config.language,
+        )]));
+    };
+    let item_display = item_display_id(&item_id);
+    let Some(other_raw) = ids.get(1) else {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Link memo {item_display} to ..."))
+                .with_subtitle("Type the item_id of the memo to link.")
+                .with_autocomplete(format!("link {} ", item_route_id(&item_id)))
+                .with_valid(false),
+        ]));
+    };
+    let Some(other_item_id) = parse_item_id(other_raw).map(format_item_id) else {
+        return Ok(Feedback::new(vec![build_invalid_item_id_item(
+            "link",
+            config.language,
+        )]));
+    };
+    let other_display = item_display_id(&other_item_id);
+    if other_item_id == item_id {
+        return guard(
+            "Cannot link a memo to itself",
+            format!("Choose a memo other than {item_display}."),
+        );
+    }
+
+    let mut previews = Vec::with_capacity(2);
+    for (id, display) in [(&item_id, &item_display), (&other_item_id, &other_display)] {
+        match execute_fetch_item(id, None, config) {
+            Ok(detail) => previews.push(normalize_text_preview(&detail.text, config.language)),
+            Err(AppError::User(message)) => {
+                return Ok(Feedback::new(vec![build_item_not_found_item(
+                    &message,
+                    display,
+                    config.language,
+                )]));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok(Feedback::new(vec![
+        Item::new(format!("Link memos {item_display} <-> {other_display}"))
+            .with_subtitle(format!(
+                "{} <-> {} | Press Enter to link",
+                truncate_title(&previews[0], 36),
+                truncate_title(&previews[1], 36)
+            ))
+            .with_arg(build_link_token(&item_id, &other_item_id))
+            .with_valid(true),
+    ]))
+}
+
+fn build_merge_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let guard = |title: &str, subtitle: String| {
+        Ok(Feedback::new(vec![
+            Item::new(title).with_subtitle(subtitle).with_valid(false),
+        ]))
+    };
+    let ids = rest.split_whitespace().collect::<Vec<_>>();
+    if ids.is_empty() || ids.len() > 2 {
+        return guard("Invalid merge syntax", MERGE_INTENT_USAGE.to_string());
+    }
+
+    let Some(item_id) = parse_item_id(ids[0]).map(format_item_id) else {
+        return Ok(Feedback::new(vec![build_invalid_item_id_item(
+            "merge",
+            config.language,
+        )]));
+    };
+    let item_display = item_display_id(&item_id);
+    let Some(merge_raw) = ids.get(1) else {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Merge a memo into {item_display} ..."))
+                .with_subtitle("Type the item_id of the memo to merge and move to trash.")
+                .with_autocomplete(format!("merge {} ", item_route_id(&item_id)))
+                .with_valid(false),
+        ]));
+    };
+    let Some(merged_item_id) = parse_item_id(merge_raw).map(format_item_id) else {
+        return Ok(Feedback::new(vec![build_invalid_item_id_item(
+            "merge",
+            config.language,
+        )]));
+    };
+    let merged_display = item_display_id(&merged_item_id);
+    if merged_item_id == item_id {
+        return guard(
+            "Cannot merge a memo into itself",
+            format!("Choose a memo other than {item_display}."),
+        );
+    }
+
+    let mut texts = Vec::with_capacity(2);
+    for (id, display) in [
+        (&item_id, &item_display),
+        (&merged_item_id, &merged_display),
+    ] {
+        match execute_fetch_item(id, None, config) {
+            Ok(detail) => texts.push(detail.text),
+            Err(AppError::User(message)) => {
+                return Ok(Feedback::new(vec![build_item_not_found_item(
+                    &message,
+                    display,
+                    config.language,
+                )]));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    let combined = merge::merged_text(&texts[0], &texts[1]);
+    if combined.len() > config.max_input_bytes {
+        return guard(
+            "Merged memo would be too large",
+            format!(
+                "Combined {} bytes, limit {} bytes (MEMO_MAX_INPUT_BYTES).",
+                combined.len(),
+                config.max_input_bytes
+            ),
+        );
+    }
+
+    let wide = config.wide_preview_chars();
+    Ok(Feedback::new(vec![
+        Item::new(format!("Merge {merged_display} into {item_display}"))
+            .with_uid(format!("merge-{item_id}-{merged_item_id}"))
+            .with_subtitle(format!(
+                "Press Enter to merge; {merged_display} moves to trash, its tags, attachments, and links move to {item_display}"
+            ))
+            .with_arg(build_merge_token(&item_id, &merged_item_id))
+            .with_valid(true),
+        Item::new(format!(
+            "Keep {item_display}: {}",
+            truncate_title(&normalize_text_preview(&texts[0], config.language), wide)
+        ))
+        .with_subtitle("Text stays first in the merged memo.")
+        .with_valid(false),
+        Item::new(format!(
+            "Merge {merged_display}: {}",
+            truncate_title(&normalize_text_preview(&texts[1], config.language), wide)
+        ))
+        .with_subtitle("Text is appended after a blank line.")
+        .with_valid(false),
+        Item::new(format!(
+            "Result: {}",
+            truncate_title(&normalize_text_preview(&combined, config.language), wide)
+        ))
+        .with_subtitle(if config.undo_window_minutes == 0 {
+            format!("{} bytes | Undo is disabled.", combined.len())
+        } else {
+            format!(
+                "{} bytes | Undo with `undo` within {} minutes.",
+                combined.len(),
+                config.undo_window_minutes
+            )
+        })
+        .with_valid(false),
+    ]))
+}
+
+fn build_linked_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.split_whitespace();
+    let item_id_raw = parts.next().unwrap_or("");
+    if item_id_raw.is_empty() || parts.next().is_some() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid linked syntax")
+                .with_subtitle(LINKED_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+    let Some(item_id) = parse_item_id(item_id_raw).map(format_item_id) else {
+        return Ok(Feedback::new(vec![build_invalid_item_id_item(
+            "linked",
+            config.language,
+        )]));
+    };
+    let item_display = item_display_id(&item_id);
+
+    let rows = match execute_linked(&item_id, None, config) {
+        Ok(rows) => rows,
+        Err(AppError::User(message)) => {
+            return Ok(Feedback::new(vec![build_item_not_found_item(
+                &message,
+                &item_display,
+                config.language,
+            )]));
+        }
+        Err(error) => return Err(error),
+    };
+    if rows.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("No memos linked to {item_display}"))
+                .with_subtitle(format!(
+                    "Use: link {} <other_item_id>",
+                    item_route_id(&item_id)
+                ))
+                .with_valid(false),
+        ]));
+    }
+
+    let items = rows
+        .iter()
+        .map(|row| {
+            let linked_display = item_display_id(&row.item_id);
+            Item::new(format!(
+                "{linked_display}: {}",
+                truncate_title(
+                    &normalize_text_preview(&row.text_preview, config.language),
+                    config.preview_chars
+                )
+            ))
+            .with_uid(format!("linked-{item_id}-{}", row.item_id))
+            .with_subtitle(format!(
+                "Linked to {item_display} at {} | Press Enter to manage",
+                row.linked_at
+            ))
+            .with_autocomplete(format!("item {}", item_route_id(&row.item_id)))
+            .with_valid(false)
+        })
+        .collect();
+    Ok(Feedback::new(items))
+}
+
+fn build_history_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.split_whitespace();
+    let item_id_raw = parts.next().unwrap_or("");
+    if item_id_raw.is_empty() || parts.next().is_some() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid history syntax")
+                .with_subtitle(HISTORY_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+    let Some(item_id) = parse_item_id(item_id_raw).map(format_item_id) else {
+        return Ok(Feedback::new(vec![build_invalid_item_id_item(
+            "history",
+            config.language,
+        )]));
+    };
+    let item_display = item_display_id(&item_id);
+
+    let (detail, versions) = match execute_fetch_item(&item_id, None, config)
+        .and_then(|detail| Ok((detail, execute_history(&item_id, None, config)?)))
+    {
+        Ok(found) => found,
+        Err(AppError::User(message)) => {
+            return Ok(Feedback::new(vec![build_item_not_found_item(
+                &message,
+                &item_display,
+                config.language,
+            )]));
+        }
+        Err(error) => return Err(error),
+    };
+    if versions.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("No earlier versions of {item_display}"))
+                .with_subtitle("Updating a memo keeps the replaced text here.")
+                .with_valid(false),
+        ]));
+    }
+
+    let items = versions
+        .iter()
+        .map(|version| {
+            let diff = history::diff_preview(&detail.text, &version.text);
+            let change = if diff.is_empty() {
+                "same words as current text".to_string()
+            } else {
+                truncate_title(&diff, config.detail_preview_chars())
+            };
+            Item::new(format!(
+                "v{} {item_display}: {}",
+                version.version,
+                truncate_title(
+                    &normalize_text_preview(&version.text, config.language),
+                    config.preview_chars
+                )
+            ))
+            .with_uid(format!("history-{}", version.version_id))
+            .with_subtitle(format!("{} | Restore: {change}", version.replaced_at))
+            .with_arg(build_history_restore_token(version.version_id))
+            .with_valid(true)
+        })
+        .collect();
+    Ok(Feedback::new(items))
+}
+
+fn build_clip_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let guard = |title: &str, subtitle: String| {
+        Ok(Feedback::new(vec![
+            Item::new(title).with_subtitle(subtitle).with_valid(false),
+        ]))
+    };
+    if !rest.trim().is_empty() {
+        return guard("Invalid clip syntax", CLIP_INTENT_USAGE.to_string());
+    }
+    let text = match clipboard::read_clipboard() {
+        clipboard::ClipboardContent::Text(text) => text,
+        clipboard::ClipboardContent::Empty => {
+            return guard(
+                "Clipboard is empty",
+                "Copy some text and retry.".to_string(),
+            );
+        }
+        clipboard::ClipboardContent::Binary => {
+            return guard(
+                "Clipboard does not hold text",
+                "Images, files, and binary data cannot be saved as memos.".to_string(),
+            );
+        }
+        clipboard::ClipboardContent::Unavailable => {
+            return guard(
+                "Clipboard is unavailable",
+                "pbpaste was not found or failed; clip needs macOS.".to_string(),
+            );
+        }
+    };
+    let normalized = text.trim();
+    if normalized.len() > config.max_input_bytes {
+        return guard(
+            "Clipboard text is too long",
+            format!(
+                "{} > {} bytes (MEMO_MAX_INPUT_BYTES); copy a shorter selection.",
+                normalized.len(),
+                config.max_input_bytes
+            ),
+        );
+    }
+
+    let preview = truncate_title(
+        &normalize_text_preview(normalized, config.language),
+        config.wide_preview_chars(),
+    );
+    let lines = normalized.lines().count();
+    let size = format!(
+        "{lines} {}, {}/{} bytes",
+        if lines == 1 { "line" } else { "lines" },
+        normalized.len(),
+        config.max_input_bytes
+    );
+    let tag_hint = render_tag_hint(&extract_tags(normalized), config.language);
+    let duplicate = find_add_duplicate(normalized, config)?;
+    let mut items = Vec::with_capacity(3);
+    if config.require_confirm {
+        items.push(
+            Item::new(format!("Preview: {preview}"))
+                .with_subtitle(format!(
+                    "Clipboard ({size}). Confirmation required. Choose the row below to save."
+                ))
+                .with_valid(false),
+        );
+    }
+    items.extend(
+        duplicate
+            .as_ref()
+            .map(|duplicate| build_duplicate_item(duplicate, config)),
+    );
+    let title = match (config.require_confirm, duplicate.is_some()) {
+        (_, true) => format!("Add clipboard anyway: {preview}"),
+        (true, false) => "Confirm add clipboard memo".to_string(),
+        (false, false) => format!("Add clipboard: {preview}"),
+    };
+    items.push(
+        Item::new(title)
+            .with_uid("clip")
+            .with_subtitle(format!(
+                "Source: {CLIPBOARD_SOURCE} | Press Enter to save ({size}).{tag_hint}"
+            ))
+            .with_arg(build_clip_token(normalized))
+            .with_valid(true),
+    );
+    Ok(Feedback::new(items))
+}
+
+fn build_undo_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    if !rest.trim().is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid undo syntax")
+                .with_subtitle(UNDO_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+    if config.undo_window_minutes == 0 {
+        return Ok(Feedback::new(vec![
+            Item::new("Undo is disabled")
+                .with_subtitle(
+                    "Set MEMO_UNDO_WINDOW_MINUTES above 0 to record undoable operations.",
+                )
+                .with_valid(false),
+        ]));
+    }
+    let nothing = || {
+        Ok(Feedback::new(vec![
+            Item::new("Nothing to undo")
+                .with_subtitle(format!(
+                    "Adds, updates, deletes, and merges from the last {} minutes can be undone.",
+                    config.undo_window_minutes
+                ))
+                .with_valid(false),
+        ]))
+    };
+    if !config.db_path.exists() {
+        return nothing();
+    }
+
+    let storage = open_storage(config.db_path.clone())?;
+    let codec = text_codec(&storage, config)?;
+    let candidate = storage
+        .with_connection(|conn| {
+            let Some(entry) = journal::latest_entry(conn, config.undo_window_minutes)? else {
+                return Ok(None);
+            };
+            let check = journal::check_undo(conn, &entry)?;
+            // Updates show the text the undo brings back; adds and deletes show the memo itself.
+            let preview = match entry.before_text.as_deref() {
+                Some(before) => Some(codec.preview(before)?),
+                None => batch::target_preview(conn, &codec, entry.item_id)?,
+            };
+            Ok(Some((entry, check, preview)))
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    let Some((entry, check, preview)) = candidate else {
+        return nothing();
+    };
+
+    let item_id = format_item_id(entry.item_id);
+    let preview = preview.as_deref().map(str::trim).unwrap_or_default();
+    let title = format!(
+        "Undo {} {}: {}",
+        entry.operation.as_str(),
+        item_display_id(&item_id),
+        if preview.is_empty() {
+            "(no text)".to_string()
+        } else {
+            truncate_title(preview, config.preview_chars)
+        }
+    );
+    let effect = match entry.operation {
+        journal::JournalOperation::Add => "Permanently removes the added memo",
+        journal::JournalOperation::Update => "Restores the previous text",
+        journal::JournalOperation::Delete => "Restores the memo from trash",
+        journal::JournalOperation::Merge => {
+            "Restores the previous text and the merged memo from trash"
+        }
+    };
+    let item = Item::new(title).with_uid("undo");
+    let item = match check {
+        Ok(()) => item
+            .with_subtitle(format!("{effect} | recorded {}", entry.recorded_at))
+            .with_arg(build_undo_token(entry.entry_id))
+            .with_valid(true),
+        Err(reason) => item
+            .with_subtitle(format!("Cannot undo: {reason}"))
+            .with_autocomplete(format!("item {}", item_route_id(&item_id)))
+            .with_valid(false),
+    };
+    Ok(Feedback::new(vec![item]))
+}
+
+fn build_stats_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    if !rest.trim().is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Invalid stats syntax")
+                .with_subtitle(STATS_INTENT_USAGE)
+                .with_valid(false),
+        ]));
+    }
+    if !config.db_path.exists() {
+        return Ok(Feedback::new(vec![
+            Item::new("No memo stats yet")
+                .with_subtitle("Memo database does not exist yet.")
+                .with_valid(false),
+        ]));
+    }
+
+    let stats = execute_stats(None, DEFAULT_STATS_WEEKS, config)?;
+    let this_week = stats.weeks.first().map_or(0, |week| week.item_count);
+    let weekly = stats
+        .weeks
+        .iter()
+        .map(|week| week.item_count.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sources = if stats.sources.is_empty() {
+        "none".to_string()
+    } else {
+        stats
+            .sources
+            .iter()
+            .map(|row| format!("{} {}", row.source, row.item_count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let tags = if stats.tags.is_empty() {
+        "none".to_string()
+    } else {
+        stats
+            .tags
+            .iter()
+            .map(|row| format!("#{} {}", row.tag, row.item_count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    Ok(Feedback::new(vec![
+        Item::new(format!(
+            "{} memos ({} pinned, {} in trash)",
+            stats.live, stats.pinned, stats.trashed
+        ))
+        .with_uid("stats-totals")
+        .with_subtitle(format!(
+            "DB {} | {}",
+            format_byte_size(stats.db_size_bytes),
+            stats.db_path
+        ))
+        .with_valid(false),
+        Item::new(format!("This week: {this_week} memos"))
+            .with_uid("stats-weeks")
+            .with_subtitle(format!(
+                "Last {} weeks, newest first: {weekly}",
+                stats.weeks.len()
+            ))
+            .with_valid(false),
+        Item::new(truncate_title(
+            &format!("Sources: {sources}"),
+            ACTION_TITLE_MAX_CHARS,
+        ))
+        .with_uid("stats-sources")
+        .with_subtitle("Live memos per source label")
+        .with_valid(false),
+        Item::new(truncate_title(
+            &format!("Top tags: {tags}"),
+            ACTION_TITLE_MAX_CHARS,
+        ))
+        .with_uid("stats-tags")
+        .with_subtitle("Press Tab to browse tags")
+        .with_autocomplete("tag ")
+        .with_valid(false),
+    ]))
+}
+
+/// Human-readable byte count (`512 B`, `1.5 KB`, `2.3 MB`) using 1024-byte units.
+fn format_byte_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn build_sync_feedback(rest: &str, config: &RuntimeConfig) -> Feedback {
+    if !rest.trim().is_empty() {
+        return Feedback::new(vec![
+            Item::new("Invalid sync syntax")
+                .with_subtitle(SYNC_INTENT_USAGE)
+                .with_valid(false),
+        ]);
+    }
+    let Some(repo) = &config.sync_dir else {
+        return Feedback::new(vec![
+            Item::new("Memo sync is not configured")
+                .with_subtitle(
+                    "Set MEMO_SYNC_DIR to a git repository to sync memos across machines.",
+                )
+                .with_valid(false),
+        ]);
+    };
+
+    Feedback::new(vec![
+        Item::new("Sync memos with git")
+            .with_uid("sync")
+            .with_subtitle(format!(
+                "Export, commit, pull, re-import, and push {}",
+                repo.display()
+            ))
+            .with_arg(SYNC_TOKEN)
+            .with_valid(true),
+    ])
+}
+
+fn build_template_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    if let Some(save_rest) = strip_intent(rest, TEMPLATE_ADD_KEYWORD) {
+        return build_template_save_feedback(save_rest, config);
+    }
+
+    let templates = if config.db_path.exists() {
+        execute_template_list(None, config)?
+    } else {
+        Vec::new()
+    };
+    let name = rest.trim().to_ascii_lowercase();
+    if let Some(template) = templates.iter().find(|template| template.name == name) {
+        let expanded = templates::expand_placeholders(
+            &template.body,
+            &Local::now(),
+            clipboard::read_clipboard_text,
+        );
+        if expanded.trim().is_empty() {
+            return Ok(Feedback::new(vec![
+                Item::new(format!("Template {name} expands to empty text"))
+                    .with_subtitle("The clipboard may be empty; copy text and retry.")
+                    .with_valid(false),
+            ]));
+        }
+        return build_add_feedback(expanded.trim(), config);
+    }
+
+    let matches = templates
+        .iter()
+        .filter(|template| template.name.starts_with(&name))
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        let placeholders = templates::TEMPLATE_PLACEHOLDERS
+            .iter()
+            .map(|(placeholder, _)| *placeholder)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (title, hint) = if templates.is_empty() {
+            (
+                "No templates yet".to_string(),
+                "Save one: tpl add <name> <text>",
+            )
+        } else {
+            (
+                format!("Unknown template: {}", truncate_title(&name, 48)),
+                TEMPLATE_INTENT_USAGE,
+            )
+        };
+        return Ok(Feedback::new(vec![
+            Item::new(title)
+                .with_subtitle(format!("{hint}. Placeholders: {placeholders}"))
+                .with_autocomplete("tpl add ")
+                .with_valid(false),
+        ]));
+    }
+
+    Ok(Feedback::new(
+        matches
+            .into_iter()
+            .map(|template| {
+                let preview = template
+                    .body
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                Item::new(format!("Template: {}", template.name))
+                    .with_uid(format!("template-{}", template.name))
+                    .with_subtitle(format!(
+                        "{} | Press Enter to fill",
+                        truncate_title(&preview, config.wide_preview_chars())
+                    ))
+                    .with_autocomplete(format!("tpl {}", template.name))
+                    .with_valid(false)
+            })
+            .collect(),
+    ))
+}
+
+fn build_template_save_feedback(rest: &str, config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let mut parts = rest.splitn(2, char::is_whitespace);
+    let name_raw = parts.next().unwrap_or("").trim();
+    let body = parts.next().unwrap_or("").trim();
+    if name_raw.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("Type a template name and text")
+                .with_subtitle(TEMPLATE_ADD_USAGE)
+                .with_valid(false),
+        ]));
+    }
+
+    let name = match parse_template_name(name_raw) {
+        Ok(name) => name,
+        Err(error) => {
+            return Ok(Feedback::new(vec![
+                Item::new("Invalid template name")
+                    .with_subtitle(error.message().to_string())
+                    .with_valid(false),
+            ]));
+        }
+    };
+    if body.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new(format!("Save template: {name}"))
+                .with_subtitle("Type the template text after the name.")
+                .with_valid(false),
+        ]));
+    }
+    if body.len() > config.max_input_bytes {
+        return Ok(Feedback::new(vec![
+            Item::new("Input exceeds MEMO_MAX_INPUT_BYTES")
+                .with_subtitle(format!(
+                    "Current {} bytes, limit {} bytes.",
+                    body.len(),
+                    config.max_input_bytes
+                ))
+                .with_valid(false),
+        ]));
+    }
+
+    Ok(Feedback::new(vec![
+        Item::new(format!("Save template: {name}"))
+            .with_subtitle(format!(
+                "{} | Press Enter to save",
+                truncate_title(body, config.wide_preview_chars())
+            ))
+            .with_arg(build_template_add_token(&name, body))
+            .with_valid(true),
+    ]))
+}
+
+fn build_tag_browse_feedback(config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let counts = if config.db_path.exists() {
+        execute_tag_counts(None, MAX_RECENT_LIMIT, config)?
+    } else {
+        Vec::new()
+    };
+    if counts.is_empty() {
+        return Ok(Feedback::new(vec![
+            Item::new("No tags yet")
+                .with_subtitle("Add `#tag` to memo text to group memos by tag.")
+                .with_valid(false),
+        ]));
+    }
+
+    Ok(Feedback::new(
+        counts
+            .into_iter()
+            .map(|row| {
+                let noun = if row.item_count == 1 { "memo" } else { "memos" };
+                Item::new(format!("#{}", row.tag))
+                    .with_uid(format!("tag-{}", row.tag))
+                    .with_subtitle(format!("{} {noun} | Press Enter to list", row.item_count))
+                    .with_autocomplete(format!("tag {}", row.tag))
+                    .with_valid(false)
+            })
+            .collect(),
+    ))
+}
+
+fn render_tag_hint(tags: &[String], lang: Language) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+
+    let rendered = tags
+        .iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    lang.text(Msg::TagsHint, &[&rendered])
+}
+
+fn parse_search_intent(
+    rest: &str,
+    default_match_mode: SearchMatchMode,
+) -> Result<(SearchMatchMode, &str), Feedback> {
+    let trimmed = rest.trim();
+    if trimmed.is_empty() {
+        return Err(Feedback::new(vec![
+            Item::new("Type search text after keyword")
+                .with_subtitle("Use: search <query>")
+                .with_valid(false),
+        ]));
+    }
+
+    if let Some(after_flag) = trimmed.strip_prefix("--match") {
+        let is_match_flag = after_flag
+            .chars()
+            .next()
+            .map(|ch| ch.is_whitespace() || ch == '=')
+            .unwrap_or(true);
+        if is_match_flag {
+            let mut rest = after_flag.trim_start();
+            if let Some(remaining) = rest.strip_prefix('=') {
+                rest = remaining.trim_start();
+            }
+            if rest.is_empty() {
+                return Err(Feedback::new(vec![
+                    Item::new("Missing search match mode")
+                        .with_subtitle(SEARCH_MATCH_USAGE)
+                        .with_valid(false),
+                ]));
+            }
+
+            let mut parts = rest.splitn(2, char::is_whitespace);
+            let mode_raw = parts.next().unwrap_or("").trim();
+            let Some(match_mode) = SearchMatchMode::parse_token(mode_raw) else {
+                return Err(Feedback::new(vec![
+                    Item::new(format!(
+                        "Invalid search match mode: {}",
+                        truncate_title(mode_raw, 32)
+                    ))
+                    .with_subtitle(SEARCH_MATCH_USAGE)
+                    .with_valid(false),
+                ]));
+            };
+
+            let query = parts.next().unwrap_or("").trim();
+            if query.is_empty() {
+                return Err(Feedback::new(vec![
+                    Item::new("Type search text after keyword")
+                        .with_subtitle(SEARCH_MATCH_USAGE)
+                        .with_valid(false),
+                ]));
+            }
+            return Ok((match_mode, query));
+        }
+    }
+
+    Ok((default_match_mode, trimmed))
+}
+
+fn build_empty_query_feedback(config: &RuntimeConfig) -> Result<Feedback, AppError> {
+    let lang = config.language;
+    let db_exists = config.db_path.exists();
+
+    let mut items = vec![
+        Item::new(lang.text(Msg::TypeMemoText, &[]))
+            .with_subtitle(lang.text(
+                Msg::MaxBytesAndSource,
+                &[&config.max_input_bytes, &config.source],
+            ))
+            .with_valid(false),
+    ];
+
+    if !db_exists {
+        items.push(
+            Item::new(lang.text(Msg::InitializeDatabase, &[]))
+                .with_subtitle(lang.text(Msg::CreateDatabaseAt, &[&config.db_path.display()]))
+                .with_arg(DB_INIT_TOKEN)
+                .with_valid(true),
+        );
+        items.push(
+            Item::new(lang.text(Msg::NoRecordsYet, &[]))
+                .with_subtitle(lang.text(Msg::NoRecordsInitHint, &[]))
+                .with_valid(false),
+        );
+        return Ok(Feedback::new(items));
+    }
+
+    let last_backup = match backup::latest_snapshot(&backup::backup_dir(&config.db_path)) {
+        Some((_, modified)) => lang.text(Msg::LastBackup, &[&modified.format("%Y-%m-%d %H:%M")]),
+        None => lang.text(Msg::NoBackupsYet, &[]),
+    };
+    items.push(
+        Item::new(lang.text(Msg::DatabasePath, &[]))
+            .with_subtitle(lang.text(
+                Msg::UsingDatabaseAt,
+                &[&config.db_path.display(), &last_backup],
+            ))
+            .with_valid(false),
+    );
+
+    let now = Utc::now();
+    for row in execute_due_list(None, false, MAX_RECENT_LIMIT, config)? {
+        let item_display = item_display_id(&row.item_id);
+        let item_route = item_route_id(&row.item_id);
+        let title = lang.text(
+            Msg::DueRow,
+            &[&item_display, &row_preview(&row.text_preview, config)],
+        );
+        let relative = parse_stored_due(&row.due_at)
+            .map(|due| due::describe_due(&due, &now))
+            .unwrap_or_else(|| format!("Due {}", row.due_at));
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("due-{}", row.item_id))
+                .with_subtitle(lang.text(Msg::ManageDetail, &[&relative, &row.due_at]))
+                .with_autocomplete(format!("item {}", item_route))
+                .with_valid(false),
+        );
+    }
+
+    let pinned = execute_pinned_list(None, MAX_RECENT_LIMIT, config)?;
+    for row in &pinned {
+        let item_display = item_display_id(&row.item_id);
+        let item_route = item_route_id(&row.item_id);
+        let title = lang.text(
+            Msg::PinnedRow,
+            &[&item_display, &row_preview(&row.text_preview, config)],
+        );
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("pinned-{}", row.item_id))
+                .with_subtitle(lang.text(Msg::ManageDetail, &[&row.created_at, &row.state]))
+                .with_icon(ItemIcon::new(PINNED_ICON_PATH))
+                .with_autocomplete(format!("item {}", item_route))
+                .with_valid(false),
+        );
+    }
+
+    let recent_window = (config.recent_limit + pinned.len()).min(MAX_LIST_LIMIT);
+    let recent = execute_list(None, recent_window, 0, config)?
+        .into_iter()
+        .filter(|row| !row.is_pinned)
+        .take(config.recent_limit)
+        .collect::<Vec<_>>();
+    if recent.is_empty() && pinned.is_empty() {
+        items.push(
+            Item::new(lang.text(Msg::NoRecordsYet, &[]))
+                .with_subtitle(lang.text(Msg::NoRecordsAddHint, &[]))
+                .with_valid(false),
+        );
+        return Ok(Feedback::new(items));
+    }
+
+    for row in recent {
+        let item_display = item_display_id(&row.item_id);
+        let item_route = item_route_id(&row.item_id);
+        let title = lang.text(
+            Msg::RecentRow,
+            &[&item_display, &row_preview(&row.text_preview, config)],
+        );
+
+        items.push(
+            Item::new(title)
+                .with_uid(format!("recent-{}", row.item_id))
+                .with_subtitle(lang.text(Msg::ManageDetail, &[&row.created_at, &row.state]))
+                .with_autocomplete(format!("item {}", item_route))
+                .with_valid(false),
+        );
+    }
+
+    Ok(Feedback::new(items))
+}
+
+fn render_item_detail_json(detail: &ItemDetailResult) -> String {
+    serde_json::to_string(detail).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn normalize_text_preview(text: &str, lang: Language) -> String {
+    let normalized = text
+        .chars()
+        .map(|ch| {
+            if matches!(ch, '\n' | '\r' | '\t') {
+                ' '
+            } else {
+                ch
+            }
+        })
+        .collect::<String>();
+    let collapsed = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        lang.text(Msg::EmptyMemo, &[])
+    } else {
+        collapsed
+    }
+}
+
+fn split_action_title(prefix: &str, text: &str, lang: Language) -> (String, Option<String>) {
+    let preview = normalize_text_preview(text, lang);
+    let prefix_len = prefix.chars().count();
+    if prefix_len >= ACTION_TITLE_MAX_CHARS {
+        return (
+            truncate_title(&format!("{prefix}{preview}"), ACTION_TITLE_MAX_CHARS),
+            None,
+        );
+    }
+
+    let available = ACTION_TITLE_MAX_CHARS - prefix_len;
+    let preview_len = preview.chars().count();
+    if preview_len <= available {
+        return (format!("{prefix}{preview}"), None);
+    }
+
+    let title_preview = preview.chars().take(available).collect::<String>();
+    let overflow = preview.chars().skip(available).collect::<String>();
+    (format!("{prefix}{title_preview}…"), Some(overflow))
+}
+
+/// Title and optional overflow subtitle for an item-menu action row, previewing the memo text when known.
+fn action_title(title: String, text: Option<&str>, lang: Language) -> (String, Option<String>) {
+    match text {
+        Some(value) => split_action_title(&format!("{title} | "), value, lang),
+        None => (title, None),
+    }
+}
+
+fn build_open_attachment_item(
+    attachment: &AttachmentResult,
+    index: usize,
+    total: usize,
+    config: &RuntimeConfig,
+) -> Item {
+    let lang = config.language;
+    let mut item = Item::new(lang.text(
+        Msg::OpenAttachment,
+        &[&truncate_title(
+            &attachments::display_name(attachment),
+            config.wide_preview_chars(),
+        )],
+    ))
+    .with_subtitle(lang.text(
+        Msg::AttachmentPosition,
+        &[
+            &(index + 1),
+            &total,
+            &truncate_title(&attachment.location, config.detail_preview_chars()),
+        ],
+    ))
+    .with_arg(build_open_attachment_token(attachment.attachment_id))
+    .with_valid(true);
+    if attachment.kind == attachments::ATTACHMENT_KIND_FILE {
+        item = item.with_icon(ItemIcon::new(attachment.location.as_str()).with_type("fileicon"));
+    }
+    item
+}
+
+fn build_open_link_item(
+    item_id: &str,
+    url: &str,
+    index: usize,
+    total: usize,
+    config: &RuntimeConfig,
+) -> Item {
+    let lang = config.language;
+    let label = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .trim_end_matches('/');
+    Item::new(lang.text(
+        Msg::OpenUrl,
+        &[&truncate_title(label, config.wide_preview_chars())],
+    ))
+    .with_uid(format!("link-{item_id}-{}", index + 1))
+    .with_subtitle(lang.text(
+        Msg::LinkPosition,
+        &[
+            &(index + 1),
+            &total,
+            &truncate_title(url, config.detail_preview_chars()),
+        ],
+    ))
+    .with_arg(build_open_link_token(item_id, index + 1))
+    .with_valid(true)
+}
+
+fn build_history_summary_item(
+    item_route: &str,
+    item_display: &str,
+    version_count: i64,
+    lang: Language,
+) -> Item {
+    let title = if version_count == 1 {
+        Msg::HistoryOne
+    } else {
+        Msg::HistoryMany
+    };
+    Item::new(lang.text(title, &[&version_count]))
+        .with_subtitle(lang.text(Msg::HistoryHint, &[&item_display]))
+        .with_autocomplete(format!("history {item_route}"))
+        .with_valid(false)
+}
+
+fn build_linked_summary_item(
+    item_route: &str,
+    item_display: &str,
+    linked_count: i64,
+    lang: Language,
+) -> Item {
+    let title = if linked_count == 1 {
+        Msg::LinkedOne
+    } else {
+        Msg::LinkedMany
+    };
+    Item::new(lang.text(title, &[&linked_count]))
+        .with_subtitle(lang.text(Msg::LinkedHint, &[&item_display]))
+        .with_autocomplete(format!("linked {item_route}"))
+        .with_valid(false)
+}
+
+fn build_edit_item(item_id: &str, item_display: &str, config: &RuntimeConfig) -> Item {
+    let lang = config.language;
+    Item::new(lang.text(Msg::EditInEditor, &[&item_display]))
+        .with_subtitle(lang.text(Msg::EditHint, &[&truncate_title(&config.editor, 48)]))
+        .with_arg(build_edit_token(item_id))
+        .with_valid(true)
+}
+
+fn build_delete_item(
+    item_id: &str,
+    item_display: &str,
+    text: Option<&str>,
+    lang: Language,
+) -> Item {
+    let (title, subtitle) = action_title(lang.text(Msg::DeleteMemo, &[&item_display]), text, lang);
+
+    let mut item = Item::new(title)
+        .with_arg(build_delete_token(item_id))
+        .with_valid(true);
+    if let Some(value) = subtitle {
+        item = item.with_subtitle(value);
+    }
+
+    item
+}
+
+fn build_copy_item(
+    item_id: &str,
+    item_display: &str,
+    text: Option<&str>,
+    raw_json: &str,
+    config: &RuntimeConfig,
+) -> Item {
+    let lang = config.language;
+    let (title, subtitle) = action_title(lang.text(Msg::CopyMemo, &[&item_display]), text, lang);
+
+    let mut item = Item::new(title)
+        .with_arg(build_copy_token(item_id))
+        .with_mod(
+            "cmd",
+            ItemModifier::new()
+                .with_subtitle(lang.text(
+                    Msg::PreviewJson,
+                    &[&truncate_title(raw_json, config.detail_preview_chars())],
+                ))
+                .with_arg(build_copy_json_token(item_id))
+                .with_valid(true),
+        )
+        .with_mod(
+            "alt",
+            ItemModifier::new()
+                .with_subtitle(lang.text(Msg::CopyRichText, &[]))
+                .with_arg(build_copy_md_token(item_id))
+                .with_valid(true),
+        )
+        .with_mod(
+            "ctrl",
+            ItemModifier::new()
+                .with_subtitle(lang.text(Msg::CopyHtmlSource, &[]))
+                .with_arg(build_copy_html_token(item_id))
+                .with_valid(true),
+        )
+        .with_valid(true);
+    if let Some(value) = subtitle {
+        item = item.with_subtitle(value);
+    }
+
+    item
+}
+
+fn build_pin_toggle_item(
+    item_id: &str,
+    item_display: &str,
+    is_pinned: bool,
+    lang: Language,
+) -> Item {
+    if is_pinned {
+        Item::new(lang.text(Msg::UnpinMemo, &[&item_display]))
+            .with_subtitle(lang.text(Msg::UnpinHint, &[]))
+            .with_arg(build_unpin_token(item_id))
+            .with_valid(true)
+    } else {
+        Item::new(lang.text(Msg::PinMemo, &[&item_display]))
+            .with_subtitle(lang.text(Msg::PinHint, &[]))
+            .with_icon(ItemIcon::new(PINNED_ICON_PATH))
+            .with_arg(build_pin_token(item_id))
+            .with_valid(true)
+    }
+}
+
+fn build_update_item(
+    item_id: &str,
+    item_display: &str,
+    item_route: &str,
+    existing_text: Option<&str>,
+    update_text: Option<&str>,
+    valid: bool,
+    lang: Language,
+) -> Item {
+    let preview_text = existing_text.or(update_text);
+    let (title, subtitle) = action_title(
+        lang.text(Msg::UpdateMemo, &[&item_display]),
+        preview_text,
+        lang,
+    );
+
+    let mut item = Item::new(title);
+    if valid {
+        if let Some(text) = update_text {
+            item = item.with_arg(build_update_token(item_id, text));
+        }
+    } else {
+        item = item.with_autocomplete(format!("update {item_route} "));
+    }
+    if let Some(value) = subtitle {
+        item = item.with_subtitle(value);
+    }
+
+    item.with_valid(valid)
+}
+
+/// Row-title preview of stored memo text, or the localized empty-memo marker.
+fn row_preview(text_preview: &str, config: &RuntimeConfig) -> String {
+    let preview = text_preview.trim();
+    if preview.is_empty() {
+        config.language.text(Msg::EmptyMemo, &[])
+    } else {
+        truncate_title(preview, config.preview_chars)
+    }
+}
+
+fn truncate_title(input: &str, max_chars: usize) -> String {
+    let count = input.chars().count();
+    if count <= max_chars {
+        return input.to_string();
+    }
+
+    let mut value = input.chars().take(max_chars).collect::<String>();
+    value.push('…');
+    value
+}
+
+fn resolve_db_path() -> PathBuf {
+    let home = env::var("HOME").ok();
+
+    if let Some(path) = non_empty_env("MEMO_DB_PATH") {
+        return PathBuf::from(expand_home_path(&path, home.as_deref()));
+    }
+
+    for env_key in ["ALFRED_WORKFLOW_DATA", "ALFRED_WORKFLOW_CACHE"] {
+        if let Some(path) = non_empty_env(env_key) {
+            let expanded = expand_home_path(&path, home.as_deref());
+            return PathBuf::from(expanded).join("memo.db");
+        }
+    }
+
+    default_memo_db_path()
+}
+
+fn default_memo_db_path() -> PathBuf {
+    if let Some(data_home) = env::var_os("XDG_DATA_HOME") {
+        return PathBuf::from(data_home).join("nils-cli").join("memo.db");
+    }
+
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join("nils-cli")
+            .join("memo.db");
+    }
+
+    PathBuf::from("memo.db")
+}
+
+fn resolve_source() -> Result<String, AppError> {
+    let source = non_empty_env("MEMO_SOURCE").unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+    let source = source.trim();
+
+    if source.is_empty() {
+        return Err(AppError::User("MEMO_SOURCE must be non-empty".to_string()));
+    }
+
+    Ok(source.to_string())
+}
+
+fn resolve_require_confirm() -> Result<bool, AppError> {
+    let raw = non_empty_env("MEMO_REQUIRE_CONFIRM").unwrap_or_else(|| "0".to_string());
+    parse_bool(&raw).ok_or_else(|| {
+        AppError::User(
+            "invalid MEMO_REQUIRE_CONFIRM: expected one of 1/0/true/false/yes/no/on/off"
+                .to_string(),
+        )
+    })
+}
+
+fn resolve_max_input_bytes() -> Result<usize, AppError> {
+    let raw = non_empty_env("MEMO_MAX_INPUT_BYTES")
+        .unwrap_or_else(|| DEFAULT_MAX_INPUT_BYTES.to_string());
+
+    let parsed = raw.parse::<usize>().map_err(|_| {
+        AppError::User(format!(
+            "invalid MEMO_MAX_INPUT_BYTES: {raw} (must be integer in range 1..={MAX_INPUT_BYTES_LIMIT})"
+        ))
+    })?;
+
+    if !(1..=MAX_INPUT_BYTES_LIMIT).contains(&parsed) {
+        return Err(AppError::User(format!(
+            "invalid MEMO_MAX_INPUT_BYTES: {parsed} (must be integer in range 1..={MAX_INPUT_BYTES_LIMIT})"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+fn resolve_recent_limit() -> Result<usize, AppError> {
+    let raw =
+        non_empty_env("MEMO_RECENT_LIMIT").unwrap_or_else(|| DEFAULT_RECENT_LIMIT.to_string());
+
+    let parsed = raw.parse::<usize>().map_err(|_| {
+        AppError::User(format!(
+            "invalid MEMO_RECENT_LIMIT: {raw} (must be integer in range 1..={MAX_RECENT_LIMIT})"
+        ))
+    })?;
+
+    if !(1..=MAX_RECENT_LIMIT).contains(&parsed) {
+        return Err(AppError::User(format!(
+            "invalid MEMO_RECENT_LIMIT: {parsed} (must be integer in range 1..={MAX_RECENT_LIMIT})"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+fn resolve_preview_chars() -> Result<usize, AppError> {
+    let raw =
+        non_empty_env("MEMO_PREVIEW_CHARS").unwrap_or_else(|| DEFAULT_PREVIEW_CHARS.to_string());
+
+    let parsed = raw.parse::<usize>().map_err(|_| {
+        AppError::User(format!(
+            "invalid MEMO_PREVIEW_CHARS: {raw} (must be integer in range {MIN_PREVIEW_CHARS}..={MAX_PREVIEW_CHARS})"
+        ))
+    })?;
+
+    if !(MIN_PREVIEW_CHARS..=MAX_PREVIEW_CHARS).contains(&parsed) {
+        return Err(AppError::User(format!(
+            "invalid MEMO_PREVIEW_CHARS: {parsed} (must be integer in range {MIN_PREVIEW_CHARS}..={MAX_PREVIEW_CHARS})"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+fn resolve_language() -> Result<Language, AppError> {
+    match non_empty_env("MEMO_LANG") {
+        None => Ok(Language::default()),
+        Some(raw) => Language::parse(&raw)
+            .ok_or_else(|| AppError::User(format!("invalid MEMO_LANG: {raw} (expected en or zh)"))),
+    }
+}
+
+fn resolve_search_match_mode() -> Result<SearchMatchMode, AppError> {
+    let raw = non_empty_env("MEMO_SEARCH_MATCH").unwrap_or_else(|| "fts".to_string());
+    SearchMatchMode::parse_token(&raw).ok_or_else(|| {
+        AppError::User(format!(
+            "invalid MEMO_SEARCH_MATCH: {raw} (must be one of fts/prefix/contains)"
+        ))
+    })
+}
+
+fn resolve_search_fts() -> Result<bool, AppError> {
+    let raw = non_empty_env("MEMO_SEARCH_FTS").unwrap_or_else(|| "1".to_string());
+    parse_bool(&raw).ok_or_else(|| {
+        AppError::User(
+            "invalid MEMO_SEARCH_FTS: expected one of 1/0/true/false/yes/no/on/off".to_string(),
+        )
+    })
+}
+
+fn resolve_fuzzy_threshold() -> Result<u32, AppError> {
+    let raw = non_empty_env("MEMO_FUZZY_THRESHOLD")
+        .unwrap_or_else(|| DEFAULT_FUZZY_THRESHOLD.to_string());
+
+    let parsed = raw.parse::<u32>().map_err(|_| {
+        AppError::User(format!(
+            "invalid MEMO_FUZZY_THRESHOLD: {raw} (must be integer in range 0..={MAX_FUZZY_THRESHOLD})"
+        ))
+    })?;
+
+    if parsed > MAX_FUZZY_THRESHOLD {
+        return Err(AppError::User(format!(
+            "invalid MEMO_FUZZY_THRESHOLD: {parsed} (must be integer in range 0..={MAX_FUZZY_THRESHOLD})"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+fn resolve_trash_retention_days() -> Result<u32, AppError> {
+    let raw = non_empty_env("MEMO_TRASH_RETENTION_DAYS")
+        .unwrap_or_else(|| DEFAULT_TRASH_RETENTION_DAYS.to_string());
+
+    let parsed = raw.parse::<u32>().map_err(|_| {
+        AppError::User(format!(
+            "invalid MEMO_TRASH_RETENTION_DAYS: {raw} (must be integer in range 0..={MAX_TRASH_RETENTION_DAYS})"
+        ))
+    })?;
+
+    if parsed > MAX_TRASH_RETENTION_DAYS {
+        return Err(AppError::User(format!(
+            "invalid MEMO_TRASH_RETENTION_DAYS: {parsed} (must be integer in range 0..={MAX_TRASH_RETENTION_DAYS})"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+fn resolve_undo_window_minutes() -> Result<u32, AppError> {
+    let raw = non_empty_env("MEMO_UNDO_WINDOW_MINUTES")
+        .unwrap_or_else(|| DEFAULT_UNDO_WINDOW_MINUTES.to_string());
+
+    let parsed = raw.parse::<u32>().map_err(|_| {
+        AppError::User(format!(
+            "invalid MEMO_UNDO_WINDOW_MINUTES: {raw} (must be integer in range 0..={MAX_UNDO_WINDOW_MINUTES})"
+        ))
+    })?;
+
+    if parsed > MAX_UNDO_WINDOW_MINUTES {
+        return Err(AppError::User(format!(
+            "invalid MEMO_UNDO_WINDOW_MINUTES: {parsed} (must be integer in range 0..={MAX_UNDO_WINDOW_MINUTES})"
+        )));
+    }
+
+    Ok(parsed)
+}
+
+/// `MEMO_EDITOR`, then `VISUAL`, then `EDITOR`, then the platform default.
+fn resolve_editor() -> String {
+    ["MEMO_EDITOR", "VISUAL", "EDITOR"]
+        .iter()
+        .find_map(|key| non_empty_env(key))
+        .unwrap_or_else(|| editor::DEFAULT_EDITOR.to_string())
+}
+
+/// `MEMO_SYNC_DIR` (git work tree used by `sync`); unset disables sync.
+fn resolve_sync_dir() -> Option<PathBuf> {
+    let home = env::var("HOME").ok();
+    non_empty_env("MEMO_SYNC_DIR")
+        .map(|path| PathBuf::from(expand_home_path(&path, home.as_deref())))
+}
+
+/// `MEMO_EXPORT_DIR`, then `~/Downloads`, then the current directory.
+fn resolve_export_dir() -> PathBuf {
+    let home = env::var("HOME").ok();
+
+    if let Some(path) = non_empty_env("MEMO_EXPORT_DIR") {
+        return PathBuf::from(expand_home_path(&path, home.as_deref()));
+    }
+
+    match home
+        .as_deref()
+        .map(str::trim)
+        .filter(|home| !home.is_empty())
+    {
+        Some(home) => PathBuf::from(home).join("Downloads"),
+        None => PathBuf::from("."),
+    }
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    let value = env::var(key).ok()?;
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some(value.to_string())
+}
+
+fn expand_home_path(raw: &str, home: Option<&str>) -> String {
+    let trimmed = raw.trim();
+    let Some(home) = home.map(str::trim).filter(|value| !value.is_empty()) else {
+        return trimmed.to_string();
+    };
+
+    let home = home.trim_end_matches('/');
+    let mut expanded = trimmed.replace("$HOME", home);
+
+    if expanded == "~" {
+        expanded = home.to_string();
+    } else if let Some(rest) = expanded.strip_prefix("~/") {
+        expanded = format!("{home}/{rest}");
+    }
+
+    expanded
+}
+
+fn parse_bool(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_config() -> RuntimeConfig {
+        RuntimeConfig {
+            db_path: PathBuf::from("/tmp/memo-test.db"),
+            source: "alfred".to_string(),
+            require_confirm: false,
+            max_input_bytes: 4096,
+            recent_limit: DEFAULT_RECENT_LIMIT,
+            search_match_mode: DEFAULT_SEARCH_MATCH_MODE,
+            search_fts: true,
+            fuzzy_threshold: DEFAULT_FUZZY_THRESHOLD,
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+            undo_window_minutes: DEFAULT_UNDO_WINDOW_MINUTES,
+            export_dir: PathBuf::from("/tmp/memo-test-exports"),
+            editor: "true".to_string(),
+            sync_dir: None,
+            derive_command: None,
+            encryption_passphrase: None,
+            preview_chars: DEFAULT_PREVIEW_CHARS,
+            language: Language::En,
+        }
+    }
+
+    #[test]
+    fn add_token_roundtrip() {
+        let token = build_add_token("buy milk");
+        assert_eq!(parse_add_token(&token).as_deref(), Some("buy milk"));
+    }
+
+    #[test]
+    fn copy_token_roundtrip() {
+        let token = build_copy_token("itm_00000042");
+        let parsed = parse_copy_token(&token).expect("copy token should parse");
+        assert_eq!(parsed, "itm_00000042");
+    }
+
+    #[test]
+    fn copy_json_token_roundtrip() {
+        let token = build_copy_json_token("itm_00000042");
+        let parsed = parse_copy_json_token(&token).expect("copy json token should parse");
+        assert_eq!(parsed, "itm_00000042");
+    }
+
+    #[test]
+    fn update_token_roundtrip() {
+        let token = build_update_token("itm_00000042", "buy milk::after work");
+        let parsed = parse_update_token(&token).expect("update token should parse");
+        assert_eq!(parsed.0, "itm_00000042");
+        assert_eq!(parsed.1, "buy milk::after work");
+    }
+
+    #[test]
+    fn delete_token_roundtrip() {
+        let token = build_delete_token("itm_00000042");
+        let parsed = parse_delete_token(&token).expect("delete token should parse");
+        assert_eq!(parsed, "itm_00000042");
+    }
+
+    #[test]
+    fn update_token_rejects_missing_text() {
+        let token = format!("{UPDATE_TOKEN_PREFIX}itm_00000042{UPDATE_TOKEN_DELIMITER}");
+        assert!(parse_update_token(&token).is_none());
+    }
+
+    #[test]
+    fn script_filter_returns_db_init_on_empty_query() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("missing.db");
+
+        let feedback = build_script_filter("", &config).expect("script filter should build");
+
+        assert!(feedback.items.len() >= 2);
+        let db_init_item = feedback
+            .items
+            .iter()
+            .find(|item| item.arg.as_deref() == Some(DB_INIT_TOKEN))
+            .expect("db init row should exist");
+        assert_eq!(db_init_item.valid, Some(true));
+    }
+
+    #[test]
+    fn script_filter_existing_db_shows_db_path_info_without_db_init() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        std::fs::File::create(&config.db_path).expect("create db file");
+
+        let feedback = build_script_filter("", &config).expect("script filter should build");
+
+        let has_db_init = feedback
+            .items
+            .iter()
+            .any(|item| item.arg.as_deref() == Some(DB_INIT_TOKEN));
+        assert!(
+            !has_db_init,
+            "existing db should not show db-init action row"
+        );
+
+        let expected_subtitle = format!(
+            "Using SQLite at {} | No backups yet",
+            config.db_path.display()
+        );
+        let has_db_path_info = feedback.items.iter().any(|item| {
+            item.title == "Memo database path"
+                && item.subtitle.as_deref() == Some(expected_subtitle.as_str())
+        });
+        assert!(has_db_path_info, "existing db should show db path info row");
+    }
+
+    #[test]
+    fn script_filter_returns_add_action_for_non_empty_query() {
+        let feedback =
+            build_script_filter("buy milk", &test_config()).expect("script filter should build");
+
+        assert_eq!(feedback.items.len(), 1);
+        assert!(
+            feedback.items[0]
+                .arg
+                .as_deref()
+                .expect("arg")
+                .starts_with(ADD_TOKEN_PREFIX)
+        );
+        assert_eq!(feedback.items[0].valid, Some(true));
+    }
+
+    #[test]
+    fn script_filter_returns_update_action_for_update_intent() {
+        let feedback = build_script_filter("update itm_00000002 buy almond milk", &test_config())
+            .expect("script filter should build");
+
+        assert_eq!(feedback.items.len(), 1);
+        let arg = feedback.items[0].arg.as_deref().expect("arg should exist");
+        assert!(arg.starts_with(UPDATE_TOKEN_PREFIX));
+        assert_eq!(feedback.items[0].valid, Some(true));
+    }
+
+    #[test]
+    fn script_filter_guides_update_without_text() {
+        let feedback =
+            build_script_filter("update itm_00000002", &test_config()).expect("script filter");
+
+        assert_eq!(feedback.items.len(), 1);
+        assert_eq!(feedback.items[0].valid, Some(false));
+        assert_eq!(feedback.items[0].title, "Update memo: #2");
+        assert_eq!(feedback.items[0].autocomplete.as_deref(), Some("update 2 "));
+    }
+
+    #[test]
+    fn update_row_overflow_moves_remaining_text_to_subtitle() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let long_text =
+            "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod tempor";
+        let add = execute_add(long_text, None, None, &config).expect("seed add");
+        let query = format!("update {}", add.item_id);
+
+        let feedback = build_script_filter(&query, &config).expect("script filter");
+
+        assert_eq!(feedback.items.len(), 1);
+        assert!(
+            feedback.items[0].title.ends_with('…'),
+            "overflow title should end with ellipsis"
+        );
+        assert!(
+            feedback.items[0]
+                .subtitle
+                .as_deref()
+                .map(|value| !value.is_empty())
+                .unwrap_or(false),
+            "overflow title should move remaining preview into subtitle"
+        );
+    }
+
+    #[test]
+    fn script_filter_item_intent_returns_copy_update_delete_pin_edit_choices() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let add = execute_add("seed memo", None, None, &config).expect("seed add");
+        let query = format!("item {}", add.item_id);
+
+        let feedback = build_script_filter(&query, &config).expect("script filter");
+
+        assert_eq!(feedback.items.len(), 5);
+        let expected_item_display = item_display_id(&add.item_id);
+        let expected_item_route = item_route_id(&add.item_id);
+        let expected_copy_title_prefix =
+            format!("Copy memo: {} | seed memo", expected_item_display);
+        assert!(
+            feedback.items[0]
+                .title
+                .starts_with(expected_copy_title_prefix.as_str()),
+            "copy row title should include memo text preview"
+        );
+        let expected_copy_arg = format!("copy::{}", add.item_id);
+        assert_eq!(
+            feedback.items[0].arg.as_deref(),
+            Some(expected_copy_arg.as_str())
+        );
+        assert!(
+            feedback.items[0].subtitle.is_none(),
+            "copy row should not use subtitle for short preview"
+        );
+        let cmd_mod = feedback.items[0]
+            .mods
+            .as_ref()
+            .and_then(|mods| mods.get("cmd"))
+            .expect("copy row should include cmd modifier");
+        let expected_copy_json_arg = format!("copy-json::{}", add.item_id);
+        assert_eq!(
+            cmd_mod.arg.as_deref(),
+            Some(expected_copy_json_arg.as_str())
+        );
+        let cmd_subtitle = cmd_mod
+            .subtitle
+            .as_deref()
+            .expect("cmd modifier subtitle should exist");
+        assert!(
+            cmd_subtitle.contains("Preview JSON:"),
+            "cmd modifier subtitle should keep JSON preview"
+        );
+
+        let expected_update_title_prefix =
+            format!("Update memo: {} | seed memo", expected_item_display);
+        assert!(
+            feedback.items[1]
+                .title
+                .starts_with(expected_update_title_prefix.as_str()),
+            "update row title should include memo text preview"
+        );
+        let expected_update_autocomplete = format!("update {} ", expected_item_route);
+        assert_eq!(
+            feedback.items[1].autocomplete.as_deref(),
+            Some(expected_update_autocomplete.as_str())
+        );
+        assert_eq!(feedback.items[1].valid, Some(false));
+        assert!(
+            feedback.items[1].subtitle.is_none(),
+            "update row should not use subtitle for short preview"
+        );
+
+        let expected_delete_title_prefix =
+            format!("Delete memo: {} | seed memo", expected_item_display);
+        assert!(
+            feedback.items[2]
+                .title
+                .starts_with(expected_delete_title_prefix.as_str()),
+            "delete row title should include memo text preview"
+        );
+        let expected_delete_arg = format!("delete::{}", add.item_id);
+        assert_eq!(
+            feedback.items[2].arg.as_deref(),
+            Some(expected_delete_arg.as_str())
+        );
+        assert_eq!(feedback.items[2].valid, Some(true));
+        assert!(
+            feedback.items[2].subtitle.is_none(),
+            "delete row should not use subtitle for short preview"
+        );
+
+        let expected_pin_arg = format!("pin::{}", add.item_id);
+        assert_eq!(
+            feedback.items[3].arg.as_deref(),
+            Some(expected_pin_arg.as_str())
+        );
+        assert_eq!(feedback.items[3].valid, Some(true));
+
+        let expected_edit_title = format!("Edit in editor: {}", expected_item_display);
+        assert_eq!(feedback.items[4].title, expected_edit_title);
+        let expected_edit_arg = format!("edit::{}", add.item_id);
+        assert_eq!(
+            feedback.items[4].arg.as_deref(),
+            Some(expected_edit_arg.as_str())
+        );
+    }
+
+    #[test]
+    fn script_filter_returns_delete_action_for_delete_intent() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let add = execute_add("delete me", None, None, &config).expect("seed add");
+        let query = format!("delete {}", add.item_id);
+
+        let feedback = build_script_filter(&query, &config).expect("script filter");
+
+        assert_eq!(feedback.items.len(), 1);
+        let arg = feedback.items[0].arg.as_deref().expect("arg should exist");
+        assert!(arg.starts_with(DELETE_TOKEN_PREFIX));
+        assert!(
+            feedback.items[0].title.starts_with(
+                format!("Delete memo: {} | delete me", item_display_id(&add.item_id)).as_str()
+            ),
+            "delete intent title should include memo text preview"
+        );
+        assert!(
+            feedback.items[0].subtitle.is_none(),
+            "delete intent should not use subtitle for short preview"
+        );
+        assert_eq!(feedback.items[0].valid, Some(true));
+    }
+
+    #[test]
+    fn delete_row_overflow_moves_remaining_text_to_subtitle() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let long_text = "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod";
+        let add = execute_add(long_text, None, None, &config).expect("seed add");
+        let query = format!("delete {}", add.item_id);
+
+        let feedback = build_script_filter(&query, &config).expect("script filter");
+
+        assert_eq!(feedback.items.len(), 1);
+        assert!(
+            feedback.items[0].title.ends_with('…'),
+            "overflow title should end with ellipsis"
+        );
+        assert!(
+            feedback.items[0]
+                .subtitle
+                .as_deref()
+                .map(|value| !value.is_empty())
+                .unwrap_or(false),
+            "overflow title should move remaining preview into subtitle"
+        );
+    }
+
+    #[test]
+    fn script_filter_returns_copy_action_for_copy_intent() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let add = execute_add("seed memo", None, None, &config).expect("seed add");
+        let query = format!("copy {}", add.item_id);
+
+        let feedback = build_script_filter(&query, &config).expect("script filter");
+
+        assert_eq!(feedback.items.len(), 1);
+        let expected_item_display = item_display_id(&add.item_id);
+        let expected_copy_title_prefix =
+            format!("Copy memo: {} | seed memo", expected_item_display);
+        assert!(
+            feedback.items[0]
+                .title
+                .starts_with(expected_copy_title_prefix.as_str()),
+            "copy intent title should include memo text preview"
+        );
+        let expected_copy_arg = format!("copy::{}", add.item_id);
+        assert_eq!(
+            feedback.items[0].arg.as_deref(),
+            Some(expected_copy_arg.as_str())
+        );
+        assert!(
+            feedback.items[0].subtitle.is_none(),
+            "copy intent should not use subtitle for short preview"
+        );
+        let cmd_mod = feedback.items[0]
+            .mods
+            .as_ref()
+            .and_then(|mods| mods.get("cmd"))
+            .expect("copy row should include cmd modifier");
+        let expected_copy_json_arg = format!("copy-json::{}", add.item_id);
+        assert_eq!(
+            cmd_mod.arg.as_deref(),
+            Some(expected_copy_json_arg.as_str())
+        );
+    }
+
+    #[test]
+    fn script_filter_search_intent_returns_manage_rows() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let add = execute_add("buy milk", None, None, &config).expect("seed add");
+
+        let feedback = build_script_filter("search milk", &config).expect("script filter");
+
+        assert_eq!(
+            feedback.items.len(),
+            1,
+            "single search hit should keep selectable search row"
+        );
+        assert_eq!(feedback.items[0].valid, Some(false));
+        let expected_autocomplete = format!("item {}", item_route_id(&add.item_id));
+        assert_eq!(
+            feedback.items[0].autocomplete.as_deref(),
+            Some(expected_autocomplete.as_str())
+        );
+    }
+
+    #[test]
+    fn script_filter_search_intent_multiple_hits_keep_search_rows() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        execute_add("milk tea", None, None, &config).expect("seed add one");
+        execute_add("milk coffee", None, None, &config).expect("seed add two");
+
+        let feedback = build_script_filter("search milk", &config).expect("script filter");
+
+        assert!(
+            feedback.items.len() >= 2,
+            "multiple search hits should keep search result rows"
+        );
+        assert_eq!(feedback.items[0].valid, Some(false));
+        let autocomplete = feedback.items[0]
+            .autocomplete
+            .as_deref()
+            .expect("search row should include autocomplete");
+        assert!(
+            autocomplete.starts_with("item "),
+            "multi-hit search rows should route to item intent"
         );
-        return Ok(Feedback::new(items));
     }
 
-    items.push(
-        Item::new("Memo database path")
-            .with_subtitle(format!("Using SQLite at {}", config.db_path.display()))
-            .with_valid(false),
-    );
+    #[test]
+    fn script_filter_search_intent_requires_query_text() {
+        let feedback = build_script_filter("search", &test_config()).expect("script filter");
 
-    let recent = execute_list(None, config.recent_limit, 0, config)?;
-    if recent.is_empty() {
-        items.push(
-            Item::new("No memo records yet")
-                .with_subtitle("Use `mm <text>` then press Enter to add your first memo.")
-                .with_valid(false),
-        );
-        return Ok(Feedback::new(items));
+        assert_eq!(feedback.items.len(), 1);
+        assert_eq!(feedback.items[0].valid, Some(false));
+        assert_eq!(feedback.items[0].title, "Type search text after keyword");
     }
 
-    for row in recent {
-        let item_display = item_display_id(&row.item_id);
-        let item_route = item_route_id(&row.item_id);
-        let preview = row.text_preview.trim();
-        let title = if preview.is_empty() {
-            format!("Recent {}: (empty memo)", item_display)
-        } else {
-            format!("Recent {}: {}", item_display, truncate_title(preview, 56))
-        };
+    #[test]
+    fn script_filter_search_intent_supports_explicit_match_modes() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let add = execute_add("123", None, None, &config).expect("seed add");
 
-        items.push(
-            Item::new(title)
-                .with_uid(format!("recent-{}", row.item_id))
-                .with_subtitle(format!(
-                    "{} | {} | Press Enter to manage",
-                    row.created_at, row.state
-                ))
-                .with_autocomplete(format!("item {}", item_route))
-                .with_valid(false),
+        let prefix_feedback =
+            build_script_filter("search --match prefix 12", &config).expect("prefix search");
+        assert_eq!(prefix_feedback.items.len(), 1);
+        let expected_autocomplete = format!("item {}", item_route_id(&add.item_id));
+        assert_eq!(
+            prefix_feedback.items[0].autocomplete.as_deref(),
+            Some(expected_autocomplete.as_str())
         );
-    }
-
-    Ok(Feedback::new(items))
-}
-
-fn render_item_detail_json(detail: &ItemDetailResult) -> String {
-    serde_json::to_string(detail).unwrap_or_else(|_| "{}".to_string())
-}
 
-fn normalize_text_preview(text: &str) -> String {
-    let normalized = text
-        .chars()
-        .map(|ch| {
-            if matches!(ch, '\n' | '\r' | '\t') {
-                ' '
-            } else {
-                ch
-            }
-        })
-        .collect::<String>();
-    let collapsed = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
-    if collapsed.is_empty() {
-        "(empty memo)".to_string()
-    } else {
-        collapsed
+        let contains_feedback =
+            build_script_filter("search --match contains 23", &config).expect("contains search");
+        assert_eq!(contains_feedback.items.len(), 1);
+        assert_eq!(
+            contains_feedback.items[0].autocomplete.as_deref(),
+            Some(expected_autocomplete.as_str())
+        );
     }
-}
 
-fn split_action_title(prefix: &str, text: &str) -> (String, Option<String>) {
-    let preview = normalize_text_preview(text);
-    let prefix_len = prefix.chars().count();
-    if prefix_len >= ACTION_TITLE_MAX_CHARS {
-        return (
-            truncate_title(&format!("{prefix}{preview}"), ACTION_TITLE_MAX_CHARS),
-            None,
+    #[test]
+    fn script_filter_search_intent_uses_configured_default_match_mode() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        config.search_match_mode = SearchMatchMode::Prefix;
+        let add = execute_add("123", None, None, &config).expect("seed add");
+
+        let feedback = build_script_filter("search 12", &config).expect("prefix-by-default search");
+        assert_eq!(feedback.items.len(), 1);
+        let expected_autocomplete = format!("item {}", item_route_id(&add.item_id));
+        assert_eq!(
+            feedback.items[0].autocomplete.as_deref(),
+            Some(expected_autocomplete.as_str())
         );
     }
 
-    let available = ACTION_TITLE_MAX_CHARS - prefix_len;
-    let preview_len = preview.chars().count();
-    if preview_len <= available {
-        return (format!("{prefix}{preview}"), None);
+    #[test]
+    fn script_filter_search_intent_rejects_invalid_match_mode() {
+        let feedback = build_script_filter("search --match fuzzy milk", &test_config())
+            .expect("script filter");
+
+        assert_eq!(feedback.items.len(), 1);
+        assert_eq!(feedback.items[0].valid, Some(false));
+        assert_eq!(feedback.items[0].title, "Invalid search match mode: fuzzy");
     }
 
-    let title_preview = preview.chars().take(available).collect::<String>();
-    let overflow = preview.chars().skip(available).collect::<String>();
-    (format!("{prefix}{title_preview}…"), Some(overflow))
-}
+    #[test]
+    fn execute_search_rejects_invalid_limit_or_window() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        execute_add("seed memo", None, None, &config).expect("seed add");
 
-fn build_delete_item(item_id: &str, item_display: &str, text: Option<&str>) -> Item {
-    let prefix = format!("Delete memo: {item_display} | ");
-    let (title, subtitle) = match text {
-        Some(value) => split_action_title(&prefix, value),
-        None => (format!("Delete memo: {item_display}"), None),
-    };
+        let invalid_limit = execute_search(None, "seed", SearchMatchMode::Fts, 0, 0, &config);
+        assert!(invalid_limit.is_err(), "search should reject limit=0");
 
-    let mut item = Item::new(title)
-        .with_arg(build_delete_token(item_id))
-        .with_valid(true);
-    if let Some(value) = subtitle {
-        item = item.with_subtitle(value);
+        let invalid_window = execute_search(None, "seed", SearchMatchMode::Fts, 200, 400, &config);
+        assert!(
+            invalid_window.is_err(),
+            "search should reject oversized window"
+        );
     }
 
-    item
-}
-
-fn build_copy_item(item_id: &str, item_display: &str, text: Option<&str>, raw_json: &str) -> Item {
-    let prefix = format!("Copy memo: {item_display} | ");
-    let (title, subtitle) = match text {
-        Some(value) => split_action_title(&prefix, value),
-        None => (format!("Copy memo: {item_display}"), None),
-    };
-
-    let mut item = Item::new(title)
-        .with_arg(build_copy_token(item_id))
-        .with_mod(
-            "cmd",
-            ItemModifier::new()
-                .with_subtitle(format!("Preview JSON: {}", truncate_title(raw_json, 72)))
-                .with_arg(build_copy_json_token(item_id))
-                .with_valid(true),
+    #[test]
+    fn execute_search_uses_fts_index_for_phrases_prefixes_and_tags() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let milk = execute_add("buy oat milk today", None, None, &config).expect("seed add");
+        let oats = execute_add("oat bars and milk", None, None, &config).expect("seed add");
+        let tagged = execute_add("call plumber #home", None, None, &config).expect("seed add");
+        let ids =
+            |rows: Vec<SearchResult>| rows.into_iter().map(|row| row.item_id).collect::<Vec<_>>();
+
+        let phrase = execute_search(None, "\"oat milk\"", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("phrase search");
+        assert_eq!(ids(phrase), vec![milk.item_id.clone()]);
+
+        let prefix = execute_search(None, "mil* oat", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("prefix search");
+        assert_eq!(prefix.len(), 2);
+        assert!(prefix.iter().all(|row| row.score <= 0.0));
+
+        let tag = execute_search(None, "#home", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("tag search");
+        assert_eq!(ids(tag), vec![tagged.item_id.clone()]);
+
+        let either = execute_search(
+            None,
+            "plumber OR bars",
+            SearchMatchMode::Fts,
+            10,
+            0,
+            &config,
         )
-        .with_valid(true);
-    if let Some(value) = subtitle {
-        item = item.with_subtitle(value);
+        .expect("or search");
+        assert_eq!(either.len(), 2);
+
+        let syntax = execute_search(None, "milk (AND", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("fts syntax characters are quoted");
+        assert_eq!(ids(syntax), vec![oats.item_id.clone()]);
+
+        execute_update(&oats.item_id, "granola bars", None, &config).expect("update");
+        let stale = execute_search(None, "oat", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("search after update");
+        assert_eq!(ids(stale), vec![milk.item_id.clone()]);
+
+        execute_delete(&milk.item_id, None, &config).expect("delete");
+        let trashed = execute_search(None, "milk", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("search after delete");
+        assert!(trashed.is_empty());
+
+        config.search_fts = false;
+        let fallback = execute_search(None, "lumb", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("contains fallback");
+        assert_eq!(ids(fallback), vec![tagged.item_id]);
     }
 
-    item
-}
-
-fn build_update_item(
-    item_id: &str,
-    item_display: &str,
-    item_route: &str,
-    existing_text: Option<&str>,
-    update_text: Option<&str>,
-    valid: bool,
-) -> Item {
-    let preview_text = existing_text.or(update_text);
-    let prefix = format!("Update memo: {item_display} | ");
-    let (title, subtitle) = match preview_text {
-        Some(value) => split_action_title(&prefix, value),
-        None => (format!("Update memo: {item_display}"), None),
-    };
+    #[test]
+    fn execute_search_applies_source_date_and_state_operators_on_every_path() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        config.encryption_passphrase = Some("correct horse".to_string());
+        let old = execute_add("milk receipt", None, None, &config).expect("seed add");
+        let clip = execute_add("milk from clipboard", Some(CLIPBOARD_SOURCE), None, &config)
+            .expect("seed add");
+        let recent = execute_add("oat milk", None, None, &config).expect("seed add");
+        open_storage(config.db_path.clone())
+            .expect("open storage")
+            .with_connection(|conn| {
+                conn.execute(
+                    "update inbox_items set created_at = '2024-06-01T12:00:00.000Z' where item_id = ?1",
+                    [parse_item_id(&old.item_id).expect("item id")],
+                )
+                .map_err(MemoCliError::db_write)
+            })
+            .expect("backdate memo");
+        let search = |query: &str, mode: SearchMatchMode, config: &RuntimeConfig| {
+            execute_search(None, query, mode, 10, 0, config)
+                .expect(query)
+                .into_iter()
+                .map(|row| row.item_id)
+                .collect::<Vec<_>>()
+        };
 
-    let mut item = Item::new(title);
-    if valid {
-        if let Some(text) = update_text {
-            item = item.with_arg(build_update_token(item_id, text));
+        for mode in [SearchMatchMode::Fts, SearchMatchMode::Contains] {
+            assert_eq!(
+                search("milk source:clipboard", mode, &config),
+                vec![clip.item_id.clone()]
+            );
+            assert_eq!(
+                search("before:2025-01-01 milk", mode, &config),
+                vec![old.item_id.clone()]
+            );
+            assert_eq!(search("milk after:today", mode, &config).len(), 2);
+            assert_eq!(
+                search("milk state:enriched", mode, &config),
+                Vec::<String>::new()
+            );
         }
-    } else {
-        item = item.with_autocomplete(format!("update {item_route} "));
-    }
-    if let Some(value) = subtitle {
-        item = item.with_subtitle(value);
-    }
-
-    item.with_valid(valid)
-}
+        assert_eq!(
+            search(
+                "after:2025-01-01 source:alfred",
+                SearchMatchMode::Fts,
+                &config
+            ),
+            vec![recent.item_id.clone()]
+        );
+        assert!(matches!(
+            execute_search(None, "milk state:done", SearchMatchMode::Fts, 10, 0, &config),
+            Err(AppError::User(message)) if message.contains("state:done")
+        ));
+        assert!(
+            build_script_filter("search milk before:soon", &config)
+                .expect("script filter")
+                .items[0]
+                .title
+                .contains("Invalid search filter")
+        );
 
-fn truncate_title(input: &str, max_chars: usize) -> String {
-    let count = input.chars().count();
-    if count <= max_chars {
-        return input.to_string();
+        execute_db_encrypt(None, &config).expect("encrypt db");
+        assert_eq!(
+            search("milk source:clipboard", SearchMatchMode::Fts, &config),
+            vec![clip.item_id.clone()]
+        );
+        assert_eq!(
+            search(
+                "state:pending before:2025-01-01",
+                SearchMatchMode::Fts,
+                &config
+            ),
+            vec![old.item_id]
+        );
     }
 
-    let mut value = input.chars().take(max_chars).collect::<String>();
-    value.push('…');
-    value
-}
+    #[test]
+    fn search_falls_back_to_approximate_rows_when_nothing_matches() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let milk = execute_add("buy oat milk", None, None, &config).expect("seed add");
+        execute_add("call plumber", None, None, &config).expect("seed add");
+
+        let exact = execute_search(None, "milk", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("exact search");
+        assert_eq!(exact.len(), 1);
+        assert!(!exact[0].approximate);
+
+        let fuzzy = execute_search(None, "mlik", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("fuzzy search");
+        assert_eq!(fuzzy.len(), 1);
+        assert_eq!(fuzzy[0].item_id, milk.item_id);
+        assert!(fuzzy[0].approximate);
+        assert!(fuzzy[0].score >= 0.6 && fuzzy[0].score < 1.0);
+
+        let feedback = build_script_filter("search mlik", &config).expect("script filter");
+        assert_eq!(feedback.items.len(), 1);
+        assert!(feedback.items[0].title.starts_with("Did you mean #"));
+        assert!(
+            feedback.items[0]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.contains("approximate match 75%"))
+        );
 
-fn resolve_db_path() -> PathBuf {
-    let home = env::var("HOME").ok();
+        let paged = execute_search(None, "mlik", SearchMatchMode::Fts, 10, 5, &config)
+            .expect("paged search");
+        assert!(paged.is_empty(), "fuzzy fallback only fills the first page");
 
-    if let Some(path) = non_empty_env("MEMO_DB_PATH") {
-        return PathBuf::from(expand_home_path(&path, home.as_deref()));
-    }
+        config.fuzzy_threshold = 80;
+        let strict = execute_search(None, "mlik", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("strict search");
+        assert!(strict.is_empty());
 
-    for env_key in ["ALFRED_WORKFLOW_DATA", "ALFRED_WORKFLOW_CACHE"] {
-        if let Some(path) = non_empty_env(env_key) {
-            let expanded = expand_home_path(&path, home.as_deref());
-            return PathBuf::from(expanded).join("memo.db");
-        }
+        config.fuzzy_threshold = 0;
+        let disabled = execute_search(None, "mlik", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("disabled fuzzy search");
+        assert!(disabled.is_empty());
     }
 
-    default_memo_db_path()
-}
-
-fn default_memo_db_path() -> PathBuf {
-    if let Some(data_home) = env::var_os("XDG_DATA_HOME") {
-        return PathBuf::from(data_home).join("nils-cli").join("memo.db");
-    }
+    #[test]
+    fn template_intent_saves_previews_and_expands_into_add_rows() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
 
-    if let Some(home) = env::var_os("HOME") {
-        return PathBuf::from(home)
-            .join(".local")
-            .join("share")
-            .join("nils-cli")
-            .join("memo.db");
-    }
+        let empty = build_script_filter("tpl", &config).expect("empty template browse");
+        assert_eq!(empty.items[0].title, "No templates yet");
 
-    PathBuf::from("memo.db")
-}
+        let save =
+            build_script_filter("tpl add Standup Standup {date}: yesterday / today", &config)
+                .expect("save intent");
+        let token = save.items[0].arg.clone().expect("save token");
+        assert_eq!(
+            token,
+            "template-add::standup::Standup {date}: yesterday / today"
+        );
+        let (name, body) = parse_template_add_token(&token).expect("token should parse");
+        let saved = execute_template_save(&name, &body, None, &config).expect("save template");
+        assert!(saved.created);
+        execute_template_save("retro", "Retro {weekday}", None, &config).expect("save template");
+        let replaced = execute_template_save("retro", "Retro notes {weekday}", None, &config)
+            .expect("replace template");
+        assert!(!replaced.created);
+
+        let browse = build_script_filter("tpl", &config).expect("template browse");
+        assert_eq!(browse.items.len(), 2);
+        assert_eq!(browse.items[0].title, "Template: retro");
+        assert_eq!(browse.items[0].autocomplete.as_deref(), Some("tpl retro"));
+        let prefix = build_script_filter("tpl st", &config).expect("template prefix");
+        assert_eq!(prefix.items.len(), 1);
+        assert_eq!(prefix.items[0].title, "Template: standup");
+
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        let filled = build_script_filter("tpl standup", &config).expect("template fill");
+        let expected = format!("Standup {today}: yesterday / today");
+        assert_eq!(filled.items.len(), 1);
+        assert_eq!(
+            filled.items[0]
+                .arg
+                .as_deref()
+                .and_then(parse_add_token)
+                .as_deref(),
+            Some(expected.as_str())
+        );
+        assert_eq!(
+            execute_template_use("STANDUP", None, &config)
+                .expect("use template")
+                .text,
+            expected
+        );
 
-fn resolve_source() -> Result<String, AppError> {
-    let source = non_empty_env("MEMO_SOURCE").unwrap_or_else(|| DEFAULT_SOURCE.to_string());
-    let source = source.trim();
+        config.require_confirm = true;
+        let confirm = build_script_filter("tpl standup", &config).expect("template confirm");
+        assert_eq!(confirm.items.len(), 2);
+        assert_eq!(confirm.items[1].title, "Confirm add memo");
 
-    if source.is_empty() {
-        return Err(AppError::User("MEMO_SOURCE must be non-empty".to_string()));
+        assert!(matches!(
+            execute_template_use("missing", None, &config),
+            Err(AppError::User(_))
+        ));
+        assert!(matches!(
+            execute_template_save("add", "reserved", None, &config),
+            Err(AppError::User(_))
+        ));
+        let unknown = build_script_filter("tpl zzz", &config).expect("unknown template");
+        assert_eq!(unknown.items[0].title, "Unknown template: zzz");
     }
 
-    Ok(source.to_string())
-}
+    #[test]
+    fn db_encrypt_stores_ciphertext_and_reads_decrypt_transparently() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        config.encryption_passphrase = Some("correct horse".to_string());
 
-fn resolve_require_confirm() -> Result<bool, AppError> {
-    let raw = non_empty_env("MEMO_REQUIRE_CONFIRM").unwrap_or_else(|| "0".to_string());
-    parse_bool(&raw).ok_or_else(|| {
-        AppError::User(
-            "invalid MEMO_REQUIRE_CONFIRM: expected one of 1/0/true/false/yes/no/on/off"
-                .to_string(),
-        )
-    })
-}
+        let milk = execute_add("buy oat milk #home", None, None, &config).expect("add milk");
+        execute_add("call plumber", None, None, &config).expect("add plumber");
+        assert!(matches!(
+            execute_db_decrypt(None, &config),
+            Err(AppError::User(_))
+        ));
 
-fn resolve_max_input_bytes() -> Result<usize, AppError> {
-    let raw = non_empty_env("MEMO_MAX_INPUT_BYTES")
-        .unwrap_or_else(|| DEFAULT_MAX_INPUT_BYTES.to_string());
+        let encrypted = execute_db_encrypt(None, &config).expect("encrypt db");
+        assert!(encrypted.encrypted);
+        assert_eq!(encrypted.items, 2);
+        assert!(matches!(
+            execute_db_encrypt(None, &config),
+            Err(AppError::User(_))
+        ));
 
-    let parsed = raw.parse::<usize>().map_err(|_| {
-        AppError::User(format!(
-            "invalid MEMO_MAX_INPUT_BYTES: {raw} (must be integer in range 1..={MAX_INPUT_BYTES_LIMIT})"
-        ))
-    })?;
+        let added = execute_add("renew passport", None, None, &config).expect("add encrypted");
+        assert_eq!(added.text, "renew passport");
+        let stored: Vec<String> = open_storage(config.db_path.clone())
+            .expect("open storage")
+            .with_connection(|conn| {
+                let mut stmt = conn
+                    .prepare("select raw_text from inbox_items order by item_id")
+                    .map_err(MemoCliError::db_query)?;
+                stmt.query_map([], |row| row.get(0))
+                    .map_err(MemoCliError::db_query)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(MemoCliError::db_query)
+            })
+            .expect("raw rows");
+        assert_eq!(stored.len(), 3);
+        assert!(stored.iter().all(|text| text.starts_with("memoenc:v1:")));
+
+        let listed = execute_list(None, 8, 0, &config).expect("list encrypted");
+        assert_eq!(listed[0].text_preview, "renew passport");
+        assert_eq!(
+            execute_fetch_item(&milk.item_id, None, &config)
+                .expect("fetch encrypted")
+                .text,
+            "buy oat milk #home"
+        );
+        let phrase = execute_search(None, "\"oat milk\"", SearchMatchMode::Fts, 8, 0, &config)
+            .expect("phrase search");
+        assert_eq!(phrase.len(), 1);
+        assert_eq!(phrase[0].item_id, milk.item_id);
+        assert!(!phrase[0].approximate);
+        let prefix = execute_search(None, "pass", SearchMatchMode::Prefix, 8, 0, &config)
+            .expect("prefix search");
+        assert_eq!(prefix[0].text_preview, "renew passport");
+        let tagged =
+            execute_search(None, "#home", SearchMatchMode::Fts, 8, 0, &config).expect("tag search");
+        assert_eq!(tagged[0].matched_fields, vec!["raw_text", "tags_text"]);
+        let fuzzy = execute_search(None, "plumbr", SearchMatchMode::Fts, 8, 0, &config)
+            .expect("fuzzy search");
+        assert!(fuzzy[0].approximate);
+        assert_eq!(fuzzy[0].text_preview, "call plumber");
+
+        let mut wrong = config.clone();
+        wrong.encryption_passphrase = Some("wrong horse".to_string());
+        wrong.db_path = dir.path().join("other.db");
+        execute_add("other memo", None, None, &wrong).expect("add other");
+        execute_db_encrypt(None, &wrong).expect("encrypt other");
+        wrong.encryption_passphrase = Some("correct horse".to_string());
+        assert!(matches!(
+            execute_list(None, 8, 0, &wrong),
+            Err(AppError::User(_))
+        ));
 
-    if !(1..=MAX_INPUT_BYTES_LIMIT).contains(&parsed) {
-        return Err(AppError::User(format!(
-            "invalid MEMO_MAX_INPUT_BYTES: {parsed} (must be integer in range 1..={MAX_INPUT_BYTES_LIMIT})"
-        )));
+        let decrypted = execute_db_decrypt(None, &config).expect("decrypt db");
+        assert!(!decrypted.encrypted);
+        assert_eq!(decrypted.items, 3);
+        let restored = execute_search(None, "oat", SearchMatchMode::Fts, 8, 0, &config)
+            .expect("search after decrypt");
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].text_preview, "buy oat milk #home");
     }
 
-    Ok(parsed)
-}
+    #[test]
+    fn sync_round_trips_edits_and_deletions_between_clones() {
+        let dir = tempdir().expect("temp dir");
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(dir.path())
+                .args(args)
+                .output()
+                .expect("run git")
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q", "--bare", "remote.git"]);
+        for clone in ["a", "b"] {
+            git(&["clone", "-q", "remote.git", clone]);
+            git(&["-C", clone, "config", "user.name", "memo"]);
+            git(&["-C", clone, "config", "user.email", "memo@example.com"]);
+        }
+        let machine = |name: &str| {
+            let mut config = test_config();
+            config.db_path = dir.path().join(format!("{name}.db"));
+            config.sync_dir = Some(dir.path().join(name));
+            config
+        };
+        let (a, b) = (machine("a"), machine("b"));
 
-fn resolve_recent_limit() -> Result<usize, AppError> {
-    let raw =
-        non_empty_env("MEMO_RECENT_LIMIT").unwrap_or_else(|| DEFAULT_RECENT_LIMIT.to_string());
+        let milk = execute_add("buy milk #home", None, None, &a).expect("add on a");
+        let first = execute_sync(None, None, &a).expect("sync a");
+        assert_eq!(
+            (first.written, first.committed, first.pushed),
+            (1, true, true)
+        );
+        assert_eq!(
+            dir.path().join("a/memos").read_dir().expect("tree").count(),
+            1
+        );
 
-    let parsed = raw.parse::<usize>().map_err(|_| {
-        AppError::User(format!(
-            "invalid MEMO_RECENT_LIMIT: {raw} (must be integer in range 1..={MAX_RECENT_LIMIT})"
-        ))
-    })?;
+        let joined = execute_sync(None, None, &b).expect("sync b");
+        assert_eq!(joined.created, 1);
+        let on_b = execute_list(None, 8, 0, &b).expect("list b");
+        assert_eq!(on_b[0].text_preview, "buy milk #home");
+        let b_milk = on_b[0].item_id.clone();
+
+        execute_update(&milk.item_id, "buy oat milk #home", None, &a).expect("edit on a");
+        execute_sync(None, None, &a).expect("push edit");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        execute_update(&b_milk, "buy soy milk #home", None, &b).expect("later edit on b");
+        execute_pin(&b_milk, None, &b).expect("pin on b");
+        let merged = execute_sync(None, None, &b).expect("sync conflicting edit");
+        assert_eq!((merged.conflicts, merged.updated), (1, 0));
+        execute_sync(None, None, &a).expect("pull winner");
+        let on_a = execute_list(None, 8, 0, &a).expect("list a");
+        assert_eq!(on_a[0].text_preview, "buy soy milk #home");
+        assert!(on_a[0].is_pinned);
+
+        execute_purge(&milk.item_id, None, &a).expect("purge on a");
+        let removed = execute_sync(None, None, &a).expect("sync purge");
+        assert_eq!(removed.removed, 1);
+        let purged = execute_sync(None, None, &b).expect("sync purge to b");
+        assert_eq!(purged.purged, 1);
+        assert!(execute_list(None, 8, 0, &b).expect("list b").is_empty());
+        let idle = execute_sync(None, None, &b).expect("idle sync");
+        assert_eq!(
+            (idle.written, idle.committed, idle.pushed),
+            (0, false, false)
+        );
 
-    if !(1..=MAX_RECENT_LIMIT).contains(&parsed) {
-        return Err(AppError::User(format!(
-            "invalid MEMO_RECENT_LIMIT: {parsed} (must be integer in range 1..={MAX_RECENT_LIMIT})"
-        )));
+        assert!(matches!(
+            execute_sync(None, None, &test_config()),
+            Err(AppError::User(_))
+        ));
     }
 
-    Ok(parsed)
-}
+    #[test]
+    fn script_filter_sync_intent_requires_configured_repo() {
+        let mut config = test_config();
+        let unset = build_script_filter("sync", &config).expect("unset sync");
+        assert_eq!(unset.items[0].title, "Memo sync is not configured");
+        assert_eq!(unset.items[0].valid, Some(false));
+
+        config.sync_dir = Some(PathBuf::from("/tmp/memo-sync"));
+        let ready = build_script_filter("sync", &config).expect("sync row");
+        assert_eq!(ready.items[0].arg.as_deref(), Some(SYNC_TOKEN));
+        assert_eq!(ready.items[0].valid, Some(true));
+    }
 
-fn resolve_search_match_mode() -> Result<SearchMatchMode, AppError> {
-    let raw = non_empty_env("MEMO_SEARCH_MATCH").unwrap_or_else(|| "fts".to_string());
-    SearchMatchMode::parse_token(&raw).ok_or_else(|| {
-        AppError::User(format!(
-            "invalid MEMO_SEARCH_MATCH: {raw} (must be one of fts/prefix/contains)"
-        ))
-    })
-}
+    #[test]
+    fn script_filter_add_warns_about_identical_and_near_identical_memos() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
 
-fn non_empty_env(key: &str) -> Option<String> {
-    let value = env::var(key).ok()?;
-    let value = value.trim();
-    if value.is_empty() {
-        return None;
-    }
-    Some(value.to_string())
-}
+        let fresh = build_script_filter("buy oat milk #home", &config).expect("no db yet");
+        assert_eq!(fresh.items[0].title, "Add memo: buy oat milk #home");
 
-fn expand_home_path(raw: &str, home: Option<&str>) -> String {
-    let trimmed = raw.trim();
-    let Some(home) = home.map(str::trim).filter(|value| !value.is_empty()) else {
-        return trimmed.to_string();
-    };
+        let milk = execute_add("buy oat milk #home", None, None, &config).expect("add milk");
+        let plumber =
+            execute_add("call plumber tomorrow morning", None, None, &config).expect("add plumber");
 
-    let home = home.trim_end_matches('/');
-    let mut expanded = trimmed.replace("$HOME", home);
+        let identical = build_script_filter("buy oat milk #home", &config).expect("identical");
+        assert_eq!(
+            identical.items[0].title,
+            format!("Similar memo exists: {}", milk.item_id)
+        );
+        assert!(
+            identical.items[0]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.starts_with("Identical, saved"))
+        );
+        assert_eq!(identical.items[0].valid, Some(false));
+        assert_eq!(identical.items[0].autocomplete.as_deref(), Some("item 1"));
+        assert_eq!(identical.items[1].title, "Add anyway: buy oat milk #home");
+        assert_eq!(
+            identical.items[1].arg.as_deref(),
+            Some("add::buy oat milk #home")
+        );
 
-    if expanded == "~" {
-        expanded = home.to_string();
-    } else if let Some(rest) = expanded.strip_prefix("~/") {
-        expanded = format!("{home}/{rest}");
+        let near = build_script_filter("call plumber tomorow morning", &config).expect("near");
+        assert_eq!(
+            near.items[0].title,
+            format!("Similar memo exists: {}", plumber.item_id)
+        );
+        assert!(
+            near.items[0]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.contains("% similar"))
+        );
+        let subset = build_script_filter("call plumber", &config).expect("subset");
+        assert_eq!(subset.items[0].title, "Add memo: call plumber");
+
+        execute_update(&plumber.item_id, "call electrician", None, &config).expect("edit");
+        let edited = build_script_filter("call plumber tomorrow morning", &config).expect("edited");
+        assert_eq!(
+            edited.items[0].title,
+            "Add memo: call plumber tomorrow morning"
+        );
+        execute_delete(&milk.item_id, None, &config).expect("trash milk");
+        let trashed = build_script_filter("buy oat milk #home", &config).expect("trashed");
+        assert_eq!(trashed.items[0].title, "Add memo: buy oat milk #home");
+
+        config.require_confirm = true;
+        config.encryption_passphrase = Some("correct horse".to_string());
+        execute_db_encrypt(None, &config).expect("encrypt db");
+        let sealed = build_script_filter("call electrician", &config).expect("encrypted");
+        assert_eq!(
+            sealed.items[1].title,
+            format!("Similar memo exists: {}", plumber.item_id)
+        );
+        assert_eq!(sealed.items[2].title, "Add anyway");
     }
 
-    expanded
-}
+    #[test]
+    fn execute_stats_counts_live_memos_by_source_week_and_tag() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
 
-fn parse_bool(raw: &str) -> Option<bool> {
-    match raw.trim().to_ascii_lowercase().as_str() {
-        "1" | "true" | "yes" | "on" => Some(true),
-        "0" | "false" | "no" | "off" => Some(false),
-        _ => None,
-    }
-}
+        let milk = execute_add("buy milk #home", None, None, &config).expect("add milk");
+        execute_add("standup notes #work", Some("cli"), None, &config).expect("add notes");
+        let old = execute_add("old idea #work", None, None, &config).expect("add idea");
+        execute_pin(&milk.item_id, None, &config).expect("pin milk");
+        execute_delete(&old.item_id, None, &config).expect("trash idea");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::tempdir;
+        let stats = execute_stats(None, 4, &config).expect("stats");
+        assert_eq!(
+            (stats.total, stats.live, stats.trashed, stats.pinned),
+            (3, 2, 1, 1)
+        );
+        assert!(stats.db_size_bytes > 0);
+        assert_eq!(
+            stats.sources,
+            vec![
+                SourceCountResult {
+                    source: "alfred".to_string(),
+                    item_count: 1,
+                },
+                SourceCountResult {
+                    source: "cli".to_string(),
+                    item_count: 1,
+                },
+            ]
+        );
+        assert_eq!(stats.weeks.len(), 4);
+        assert_eq!(stats.weeks[0].item_count, 2);
+        assert!(stats.weeks[1..].iter().all(|week| week.item_count == 0));
+        assert!(stats.weeks[0].week_start > stats.weeks[1].week_start);
+        assert_eq!(stats.tags.len(), 2);
+        assert!(matches!(
+            execute_stats(None, 0, &config),
+            Err(AppError::User(_))
+        ));
 
-    fn test_config() -> RuntimeConfig {
-        RuntimeConfig {
-            db_path: PathBuf::from("/tmp/memo-test.db"),
-            source: "alfred".to_string(),
-            require_confirm: false,
-            max_input_bytes: 4096,
-            recent_limit: DEFAULT_RECENT_LIMIT,
-            search_match_mode: DEFAULT_SEARCH_MATCH_MODE,
-        }
+        let rows = build_script_filter("stats", &config).expect("stats rows");
+        assert_eq!(rows.items[0].title, "2 memos (1 pinned, 1 in trash)");
+        assert_eq!(rows.items[1].title, "This week: 2 memos");
+        assert_eq!(rows.items[2].title, "Sources: alfred 1, cli 1");
+        assert_eq!(rows.items[3].title, "Top tags: #home 1, #work 1");
+        assert!(rows.items.iter().all(|item| item.valid == Some(false)));
+        assert_eq!(format_byte_size(1536), "1.5 KB");
     }
 
     #[test]
-    fn add_token_roundtrip() {
-        let token = build_add_token("buy milk");
-        assert_eq!(parse_add_token(&token).as_deref(), Some("buy milk"));
-    }
+    fn batch_applies_per_item_and_previews_tag_scopes() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
 
-    #[test]
-    fn copy_token_roundtrip() {
-        let token = build_copy_token("itm_00000042");
-        let parsed = parse_copy_token(&token).expect("copy token should parse");
-        assert_eq!(parsed, "itm_00000042");
+        let milk = execute_add("buy milk #home", None, None, &config).expect("add milk");
+        let bread = execute_add("buy bread #home", None, None, &config).expect("add bread");
+        let notes = execute_add("standup notes #work", None, None, &config).expect("add notes");
+        execute_pin(&milk.item_id, None, &config).expect("pin milk");
+
+        let rows = build_script_filter("batch pin #home", &config).expect("batch rows");
+        assert_eq!(rows.items.len(), 3);
+        assert_eq!(rows.items[0].title, "Confirm batch pin: 1 memo");
+        assert_eq!(
+            rows.items[0].arg.as_deref(),
+            Some(
+                build_batch_token(BatchAction::Pin, std::slice::from_ref(&bread.item_id)).as_str()
+            )
+        );
+        assert_eq!(rows.items[1].subtitle.as_deref(), Some("Will pin"));
+        assert_eq!(
+            rows.items[2].subtitle.as_deref(),
+            Some("Skipped: already pinned")
+        );
+        assert!(rows.items[1..].iter().all(|item| item.valid == Some(false)));
+        let unknown = build_script_filter("batch frob 1", &config).expect("unknown action");
+        assert_eq!(unknown.items[0].title, "Unknown batch action: frob");
+
+        let token = build_batch_token(
+            BatchAction::Delete,
+            &[milk.item_id.clone(), notes.item_id.clone()],
+        );
+        assert_eq!(
+            parse_batch_token(&token),
+            Some((
+                BatchAction::Delete,
+                vec![milk.item_id.clone(), notes.item_id.clone()]
+            ))
+        );
+        assert_eq!(parse_batch_token("batch::delete::abc"), None);
+
+        let deleted = execute_batch(
+            BatchAction::Delete,
+            &[milk.item_id.clone(), "999".to_string()],
+            &["work".to_string()],
+            None,
+            &config,
+        )
+        .expect("batch delete");
+        assert_eq!(
+            (deleted.requested, deleted.succeeded, deleted.failed),
+            (3, 2, 1)
+        );
+        assert_eq!(deleted.items[1].error.as_deref(), Some("item not found"));
+        assert_eq!(deleted.items[2].item_id, notes.item_id);
+
+        let restored = execute_batch(
+            BatchAction::Restore,
+            &[],
+            &["#home".to_string()],
+            None,
+            &config,
+        )
+        .expect("batch restore");
+        assert_eq!(restored.items.len(), 1);
+        assert_eq!(restored.items[0].item_id, milk.item_id);
+        assert!(matches!(
+            execute_batch(
+                BatchAction::Purge,
+                &[],
+                &["nope".to_string()],
+                None,
+                &config
+            ),
+            Err(AppError::User(_))
+        ));
     }
 
     #[test]
-    fn copy_json_token_roundtrip() {
-        let token = build_copy_json_token("itm_00000042");
-        let parsed = parse_copy_json_token(&token).expect("copy json token should parse");
-        assert_eq!(parsed, "itm_00000042");
+    fn item_menu_opens_memo_links_and_links_intent_lists_linked_memos() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+
+        let docs = execute_add(
+            "read [docs](https://docs.rs/memo) and www.example.com/guide.",
+            None,
+            None,
+            &config,
+        )
+        .expect("add docs");
+        execute_add("no links here", None, None, &config).expect("add plain");
+        let many = (1..=7)
+            .map(|n| format!("https://site{n}.dev"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let capped = execute_add(&many, None, None, &config).expect("add many");
+
+        let menu = build_script_filter(&format!("item {}", docs.item_id), &config).expect("menu");
+        assert_eq!(menu.items.len(), 7);
+        assert_eq!(menu.items[5].title, "Open URL: docs.rs/memo");
+        assert_eq!(
+            menu.items[5].arg.as_deref(),
+            Some(build_open_link_token(&docs.item_id, 1).as_str())
+        );
+        assert_eq!(menu.items[6].title, "Open URL: www.example.com/guide");
+        let capped_menu =
+            build_script_filter(&format!("item {}", capped.item_id), &config).expect("menu");
+        assert_eq!(capped_menu.items.len(), 5 + MAX_LINK_MENU_ROWS);
+
+        let token = build_open_link_token(&docs.item_id, 2);
+        let (item_id, index) = parse_open_link_token(&token).expect("open-link token");
+        let opened = execute_open_link(&item_id, index, None, &config).expect("open link");
+        assert_eq!(opened.url, "https://www.example.com/guide");
+        assert!(matches!(
+            execute_open_link(&item_id, 3, None, &config),
+            Err(AppError::User(_))
+        ));
+        assert_eq!(parse_open_link_token("open-link::itm_00000001::0"), None);
+
+        let rows = build_script_filter("links", &config).expect("links rows");
+        assert_eq!(rows.items.len(), 2);
+        assert_eq!(rows.items[0].autocomplete.as_deref(), Some("item 3"));
+        assert!(
+            rows.items[1]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.starts_with("2 links | https://docs.rs/memo"))
+        );
+        execute_delete(&capped.item_id, None, &config).expect("trash many");
+        assert_eq!(execute_links(None, 8, &config).expect("links").len(), 1);
     }
 
     #[test]
-    fn update_token_roundtrip() {
-        let token = build_update_token("itm_00000042", "buy milk::after work");
-        let parsed = parse_update_token(&token).expect("update token should parse");
-        assert_eq!(parsed.0, "itm_00000042");
-        assert_eq!(parsed.1, "buy milk::after work");
+    fn copy_row_offers_rendered_markdown_and_html_modifiers() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let add = execute_add("**ship** it <i>now</i>", None, None, &config).expect("add");
+
+        let feedback =
+            build_script_filter(&format!("copy {}", add.item_id), &config).expect("copy");
+        let mods = feedback.items[0].mods.as_ref().expect("copy row mods");
+        let expected_md_arg = build_copy_md_token(&add.item_id);
+        assert_eq!(
+            mods.get("alt").and_then(|modifier| modifier.arg.as_deref()),
+            Some(expected_md_arg.as_str())
+        );
+        let expected_html_arg = build_copy_html_token(&add.item_id);
+        assert_eq!(
+            mods.get("ctrl")
+                .and_then(|modifier| modifier.arg.as_deref()),
+            Some(expected_html_arg.as_str())
+        );
+        assert_eq!(
+            parse_copy_md_token(&expected_md_arg).as_deref(),
+            Some(add.item_id.as_str())
+        );
+        assert_eq!(parse_copy_html_token("copy-html::abc"), None);
+
+        let rendered = execute_render_item(&add.item_id, None, &config).expect("render");
+        assert_eq!(
+            rendered.html,
+            "<p><strong>ship</strong> it &lt;i&gt;now&lt;/i&gt;</p>\n"
+        );
     }
 
     #[test]
-    fn delete_token_roundtrip() {
-        let token = build_delete_token("itm_00000042");
-        let parsed = parse_delete_token(&token).expect("delete token should parse");
-        assert_eq!(parsed, "itm_00000042");
+    fn db_restore_rolls_back_to_snapshot_and_destructive_ops_snapshot_first() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let backups = dir.path().join(backup::BACKUP_DIR_NAME);
+
+        let keep = execute_add("keep me", None, None, &config).expect("add keep");
+        let manual = execute_db_backup(None, None, &config).expect("backup");
+        assert!(manual.snapshot.starts_with(&backups.display().to_string()));
+        assert!(manual.size_bytes > 0);
+        let empty_query = build_script_filter("", &config).expect("empty query");
+        assert!(
+            empty_query.items[1]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.contains("| Last backup "))
+        );
+
+        let extra = execute_add("extra", None, None, &config).expect("add extra");
+        execute_batch(
+            BatchAction::Delete,
+            &[keep.item_id.clone(), extra.item_id.clone()],
+            &[],
+            None,
+            &config,
+        )
+        .expect("batch delete");
+        let auto = std::fs::read_dir(&backups)
+            .expect("backups dir")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .contains("-auto-batch-delete")
+            })
+            .count();
+        assert_eq!(auto, 1);
+
+        let snapshot_name = Path::new(&manual.snapshot)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("snapshot name");
+        let restored = execute_db_restore(snapshot_name, None, &config).expect("restore");
+        assert_eq!(restored.items, 1);
+        assert!(
+            restored
+                .safety_backup
+                .as_deref()
+                .is_some_and(|path| path.contains("-auto-restore"))
+        );
+        let detail = execute_fetch_item(&keep.item_id, None, &config).expect("restored memo");
+        assert_eq!(detail.text, "keep me");
+
+        let junk = dir.path().join("junk.db");
+        std::fs::write(&junk, "not sqlite").expect("write junk");
+        assert!(matches!(
+            execute_db_restore(&junk.display().to_string(), None, &config),
+            Err(AppError::User(_))
+        ));
+        let mut missing = config.clone();
+        missing.db_path = dir.path().join("missing.db");
+        assert!(matches!(
+            execute_db_backup(None, None, &missing),
+            Err(AppError::User(_))
+        ));
     }
 
     #[test]
-    fn update_token_rejects_missing_text() {
-        let token = format!("{UPDATE_TOKEN_PREFIX}itm_00000042{UPDATE_TOKEN_DELIMITER}");
-        assert!(parse_update_token(&token).is_none());
-    }
+    fn undo_walks_back_journaled_operations_and_respects_trash_state() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+
+        let added = execute_add("draft #old", None, None, &config).expect("add");
+        execute_update(&added.item_id, "final #new", None, &config).expect("update");
+        execute_delete(&added.item_id, None, &config).expect("delete");
+
+        let feedback = build_script_filter("undo", &config).expect("undo intent");
+        assert_eq!(feedback.items.len(), 1);
+        assert!(feedback.items[0].title.starts_with("Undo delete #1: final"));
+        let token = feedback.items[0].arg.clone().expect("undo token");
+        let entry_id = parse_undo_token(&token).expect("entry id");
+
+        let undone = execute_undo(Some(entry_id), None, &config).expect("undo delete");
+        assert_eq!(
+            (undone.operation.as_str(), undone.state.as_str()),
+            ("delete", "restored")
+        );
+        // The row rendered before the undo is stale now.
+        assert!(matches!(
+            execute_undo(Some(entry_id), None, &config),
+            Err(AppError::User(message)) if message.contains("no longer the latest")
+        ));
+
+        assert_eq!(
+            execute_undo(None, None, &config)
+                .expect("undo update")
+                .state,
+            "reverted"
+        );
+        let detail = execute_fetch_item(&added.item_id, None, &config).expect("fetch");
+        assert_eq!(detail.text, "draft #old");
+        assert_eq!(
+            execute_tag_items(None, "old", 10, 0, &config)
+                .expect("old tag")
+                .len(),
+            1
+        );
+        assert!(
+            execute_tag_items(None, "new", 10, 0, &config)
+                .expect("new tag")
+                .is_empty()
+        );
 
-    #[test]
-    fn script_filter_returns_db_init_on_empty_query() {
-        let dir = tempdir().expect("temp dir");
-        let mut config = test_config();
-        config.db_path = dir.path().join("missing.db");
+        // A memo trashed outside the journal (batch) blocks undoing its add only while the add is newest.
+        execute_update(&added.item_id, "again", None, &config).expect("update again");
+        execute_batch(
+            BatchAction::Delete,
+            std::slice::from_ref(&added.item_id),
+            &[],
+            None,
+            &config,
+        )
+        .expect("batch delete");
+        assert!(matches!(
+            execute_undo(None, None, &config),
+            Err(AppError::User(message)) if message.contains("memo is in trash")
+        ));
+        let blocked = build_script_filter("undo", &config).expect("blocked undo intent");
+        assert_eq!(blocked.items[0].valid, Some(false));
+        assert_eq!(
+            blocked.items[0].subtitle.as_deref(),
+            Some("Cannot undo: memo is in trash; restore it first")
+        );
 
-        let feedback = build_script_filter("", &config).expect("script filter should build");
+        execute_restore(&added.item_id, None, &config).expect("restore");
+        execute_undo(None, None, &config).expect("undo second update");
+        assert_eq!(
+            execute_undo(None, None, &config).expect("undo add").state,
+            "purged"
+        );
+        assert!(execute_fetch_item(&added.item_id, None, &config).is_err());
+        assert_eq!(
+            build_script_filter("undo", &config)
+                .expect("empty journal")
+                .items[0]
+                .title,
+            "Nothing to undo"
+        );
 
-        assert!(feedback.items.len() >= 2);
-        let db_init_item = feedback
-            .items
-            .iter()
-            .find(|item| item.arg.as_deref() == Some(DB_INIT_TOKEN))
-            .expect("db init row should exist");
-        assert_eq!(db_init_item.valid, Some(true));
+        let mut disabled = config.clone();
+        disabled.undo_window_minutes = 0;
+        execute_add("not journaled", None, None, &disabled).expect("add without journal");
+        assert!(matches!(
+            execute_undo(None, None, &disabled),
+            Err(AppError::User(_))
+        ));
+        assert!(matches!(
+            execute_undo(None, None, &config),
+            Err(AppError::User(message)) if message.starts_with("nothing to undo")
+        ));
     }
 
     #[test]
-    fn script_filter_existing_db_shows_db_path_info_without_db_init() {
+    fn derive_stores_command_output_as_active_derivation() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        std::fs::File::create(&config.db_path).expect("create db file");
+        let added = execute_add("buy milk tomorrow", None, None, &config).expect("seed add");
+        let script = dir.path().join("enrich.sh");
+        std::fs::write(
+            &script,
+            "text=$(cat)\nprintf '{\"item_id\":\"%s\",\"summary\":\"%s\",\"priority\":\"high\",\"tags\":[\"#errands\"]}' \"$MEMO_ITEM_ID\" \"$text\"\n",
+        )
+        .expect("write script");
+        let command = format!("sh '{}'", script.display());
 
-        let feedback = build_script_filter("", &config).expect("script filter should build");
+        assert!(matches!(
+            execute_derive(&added.item_id, None, None, &config),
+            Err(AppError::User(message)) if message.contains("MEMO_DERIVE_COMMAND")
+        ));
 
-        let has_db_init = feedback
-            .items
-            .iter()
-            .any(|item| item.arg.as_deref() == Some(DB_INIT_TOKEN));
-        assert!(
-            !has_db_init,
-            "existing db should not show db-init action row"
-        );
+        config.derive_command = Some(command.clone());
+        let derived = execute_derive(&added.item_id, None, None, &config).expect("derive");
+        assert!(derived.changed);
+        assert_eq!(derived.derivation_version, 1);
+        assert_eq!(derived.summary.as_deref(), Some("buy milk tomorrow"));
+        assert_eq!(derived.tags, vec!["errands"]);
+        let detail = execute_fetch_item(&added.item_id, None, &config).expect("fetch");
+        assert_eq!(detail.state, "enriched");
+        let enriched = execute_search(None, "state:enriched", SearchMatchMode::Fts, 10, 0, &config)
+            .expect("state search");
+        assert_eq!(enriched.len(), 1);
+
+        let rerun = execute_derive(&added.item_id, Some(command), None, &config).expect("rerun");
+        assert!(!rerun.changed);
+        assert_eq!(rerun.derivation_version, 1);
+
+        assert!(matches!(
+            execute_derive(&added.item_id, Some("echo {}".to_string()), None, &config),
+            Err(AppError::User(message)) if message.starts_with("invalid derivation payload: output sets none")
+        ));
+        assert!(matches!(
+            execute_derive(&added.item_id, Some("false".to_string()), None, &config),
+            Err(AppError::Runtime(message)) if message.contains("enrichment command false exited")
+        ));
 
-        let expected_subtitle = format!("Using SQLite at {}", config.db_path.display());
-        let has_db_path_info = feedback.items.iter().any(|item| {
-            item.title == "Memo database path"
-                && item.subtitle.as_deref() == Some(expected_subtitle.as_str())
-        });
-        assert!(has_db_path_info, "existing db should show db path info row");
+        config.encryption_passphrase = Some("correct horse".to_string());
+        execute_db_encrypt(None, &config).expect("encrypt db");
+        assert!(matches!(
+            execute_derive(&added.item_id, None, None, &config),
+            Err(AppError::User(message)) if message.contains("encrypted")
+        ));
     }
 
     #[test]
-    fn script_filter_returns_add_action_for_non_empty_query() {
+    fn script_filter_rejects_delete_with_invalid_item_id() {
         let feedback =
-            build_script_filter("buy milk", &test_config()).expect("script filter should build");
+            build_script_filter("delete abc", &test_config()).expect("script filter should build");
 
         assert_eq!(feedback.items.len(), 1);
-        assert!(
-            feedback.items[0]
-                .arg
-                .as_deref()
-                .expect("arg")
-                .starts_with(ADD_TOKEN_PREFIX)
-        );
-        assert_eq!(feedback.items[0].valid, Some(true));
+        assert_eq!(feedback.items[0].valid, Some(false));
+        assert_eq!(feedback.items[0].title, "Invalid item_id for delete");
     }
 
     #[test]
-    fn script_filter_returns_update_action_for_update_intent() {
-        let feedback = build_script_filter("update itm_00000002 buy almond milk", &test_config())
-            .expect("script filter should build");
+    fn script_filter_enforces_max_input_bytes() {
+        let mut config = test_config();
+        config.max_input_bytes = 4;
 
+        let feedback = build_script_filter("12345", &config).expect("script filter should build");
         assert_eq!(feedback.items.len(), 1);
-        let arg = feedback.items[0].arg.as_deref().expect("arg should exist");
-        assert!(arg.starts_with(UPDATE_TOKEN_PREFIX));
-        assert_eq!(feedback.items[0].valid, Some(true));
+        assert_eq!(feedback.items[0].valid, Some(false));
     }
 
     #[test]
-    fn script_filter_guides_update_without_text() {
-        let feedback =
-            build_script_filter("update itm_00000002", &test_config()).expect("script filter");
+    fn parse_bool_supports_expected_values() {
+        assert_eq!(parse_bool("true"), Some(true));
+        assert_eq!(parse_bool("1"), Some(true));
+        assert_eq!(parse_bool("false"), Some(false));
+        assert_eq!(parse_bool("0"), Some(false));
+        assert_eq!(parse_bool("unknown"), None);
+    }
 
-        assert_eq!(feedback.items.len(), 1);
-        assert_eq!(feedback.items[0].valid, Some(false));
-        assert_eq!(feedback.items[0].title, "Update memo: #2");
-        assert_eq!(feedback.items[0].autocomplete.as_deref(), Some("update 2 "));
+    #[test]
+    fn expand_home_path_supports_tilde_prefix() {
+        assert_eq!(
+            expand_home_path("~/memo.db", Some("/tmp/home")),
+            "/tmp/home/memo.db"
+        );
+        assert_eq!(
+            expand_home_path("$HOME/memo.db", Some("/tmp/home")),
+            "/tmp/home/memo.db"
+        );
     }
 
     #[test]
-    fn update_row_overflow_moves_remaining_text_to_subtitle() {
+    fn copy_row_overflow_moves_remaining_text_to_subtitle() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
         let long_text =
-            "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod tempor";
+            "copy overflow lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod";
         let add = execute_add(long_text, None, None, &config).expect("seed add");
-        let query = format!("update {}", add.item_id);
+        let query = format!("copy {}", add.item_id);
 
         let feedback = build_script_filter(&query, &config).expect("script filter");
 
@@ -1432,376 +6930,878 @@ mod tests {
     }
 
     #[test]
-    fn script_filter_item_intent_returns_copy_update_delete_choices() {
+    fn add_captures_hash_tags_and_tag_intent_lists_matching_memos() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        let add = execute_add("seed memo", None, None, &config).expect("seed add");
-        let query = format!("item {}", add.item_id);
+        let first = execute_add("call bob #Work", None, None, &config).expect("seed add one");
+        execute_add("buy milk #home", None, None, &config).expect("seed add two");
+        let flagged = execute_add_with_tags(
+            "plan offsite",
+            &["work".to_string(), "travel".to_string()],
+            None,
+            None,
+            &config,
+        )
+        .expect("seed add three");
 
-        let feedback = build_script_filter(&query, &config).expect("script filter");
+        assert_eq!(first.tags, vec!["work".to_string()]);
+        assert_eq!(flagged.tags, vec!["travel".to_string(), "work".to_string()]);
 
-        assert_eq!(feedback.items.len(), 3);
-        let expected_item_display = item_display_id(&add.item_id);
-        let expected_item_route = item_route_id(&add.item_id);
-        let expected_copy_title_prefix =
-            format!("Copy memo: {} | seed memo", expected_item_display);
-        assert!(
-            feedback.items[0]
-                .title
-                .starts_with(expected_copy_title_prefix.as_str()),
-            "copy row title should include memo text preview"
-        );
-        let expected_copy_arg = format!("copy::{}", add.item_id);
-        assert_eq!(
-            feedback.items[0].arg.as_deref(),
-            Some(expected_copy_arg.as_str())
-        );
-        assert!(
-            feedback.items[0].subtitle.is_none(),
-            "copy row should not use subtitle for short preview"
-        );
-        let cmd_mod = feedback.items[0]
-            .mods
-            .as_ref()
-            .and_then(|mods| mods.get("cmd"))
-            .expect("copy row should include cmd modifier");
-        let expected_copy_json_arg = format!("copy-json::{}", add.item_id);
+        let feedback = build_script_filter("tag #WORK", &config).expect("script filter");
+        let autocompletes = feedback
+            .items
+            .iter()
+            .map(|item| item.autocomplete.clone().unwrap_or_default())
+            .collect::<Vec<_>>();
         assert_eq!(
-            cmd_mod.arg.as_deref(),
-            Some(expected_copy_json_arg.as_str())
+            autocompletes,
+            vec![
+                format!("item {}", item_route_id(&flagged.item_id)),
+                format!("item {}", item_route_id(&first.item_id)),
+            ]
         );
-        let cmd_subtitle = cmd_mod
-            .subtitle
-            .as_deref()
-            .expect("cmd modifier subtitle should exist");
+        assert!(feedback.items.iter().all(|item| item.valid == Some(false)));
+
+        let browse = build_script_filter("tag", &config).expect("tag browse");
+        assert_eq!(browse.items[0].title, "#work");
+        assert_eq!(browse.items[0].autocomplete.as_deref(), Some("tag work"));
+        assert_eq!(browse.items.len(), 3);
+    }
+
+    #[test]
+    fn update_resyncs_text_tags_but_keeps_flag_tags() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let added =
+            execute_add_with_tags("draft #idea", &["keep".to_string()], None, None, &config)
+                .expect("seed add");
+
+        let updated = execute_update(&added.item_id, "final #done", None, &config).expect("update");
+        assert_eq!(updated.tags, vec!["done".to_string(), "keep".to_string()]);
         assert!(
-            cmd_subtitle.contains("Preview JSON:"),
-            "cmd modifier subtitle should keep JSON preview"
+            execute_tag_items(None, "idea", 10, 0, &config)
+                .expect("tag items")
+                .is_empty()
         );
 
-        let expected_update_title_prefix =
-            format!("Update memo: {} | seed memo", expected_item_display);
+        execute_delete(&added.item_id, None, &config).expect("delete");
         assert!(
-            feedback.items[1]
-                .title
-                .starts_with(expected_update_title_prefix.as_str()),
-            "update row title should include memo text preview"
+            execute_tag_counts(None, 10, &config)
+                .expect("tag counts")
+                .is_empty(),
+            "trashed memos should drop out of tag counts"
         );
-        let expected_update_autocomplete = format!("update {} ", expected_item_route);
+    }
+
+    #[test]
+    fn script_filter_add_row_previews_detected_tags() {
+        let feedback =
+            build_script_filter("buy milk #home #Errands", &test_config()).expect("script filter");
+        let subtitle = feedback.items[0].subtitle.as_deref().unwrap_or_default();
+        assert!(subtitle.ends_with("Tags: #home #errands"), "{subtitle}");
+    }
+
+    #[test]
+    fn script_filter_tag_intent_rejects_invalid_names() {
+        let feedback = build_script_filter("tag bad!name", &test_config()).expect("script filter");
+        assert_eq!(feedback.items.len(), 1);
+        assert_eq!(feedback.items[0].valid, Some(false));
+        assert!(feedback.items[0].title.starts_with("Invalid tag"));
+    }
+
+    #[test]
+    fn delete_moves_item_to_trash_and_restore_brings_it_back() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let kept = execute_add("keep milk", None, None, &config).expect("seed add one");
+        let trashed = execute_add("trash milk", None, None, &config).expect("seed add two");
+
+        let deleted = execute_delete(&trashed.item_id, None, &config).expect("delete");
+        assert_eq!(deleted.state, "deleted");
+        assert!(deleted.purge_after.is_some());
+        assert!(matches!(
+            execute_delete(&trashed.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
+
+        let listed = execute_list(None, 10, 0, &config).expect("list");
         assert_eq!(
-            feedback.items[1].autocomplete.as_deref(),
-            Some(expected_update_autocomplete.as_str())
-        );
-        assert_eq!(feedback.items[1].valid, Some(false));
-        assert!(
-            feedback.items[1].subtitle.is_none(),
-            "update row should not use subtitle for short preview"
+            listed
+                .iter()
+                .map(|row| row.item_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![kept.item_id.as_str()]
         );
+        let searched =
+            execute_search(None, "milk", SearchMatchMode::Fts, 10, 0, &config).expect("search");
+        assert!(searched.iter().all(|row| row.item_id != trashed.item_id));
+        assert!(matches!(
+            execute_fetch_item(&trashed.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
 
-        let expected_delete_title_prefix =
-            format!("Delete memo: {} | seed memo", expected_item_display);
-        assert!(
-            feedback.items[2]
-                .title
-                .starts_with(expected_delete_title_prefix.as_str()),
-            "delete row title should include memo text preview"
-        );
-        let expected_delete_arg = format!("delete::{}", add.item_id);
+        let feedback = build_script_filter("trash", &config).expect("trash intent");
+        assert_eq!(feedback.items.len(), 1);
+        let expected_restore = build_restore_token(&trashed.item_id);
         assert_eq!(
-            feedback.items[2].arg.as_deref(),
-            Some(expected_delete_arg.as_str())
+            feedback.items[0].arg.as_deref(),
+            Some(expected_restore.as_str())
         );
-        assert_eq!(feedback.items[2].valid, Some(true));
+        let expected_purge = build_purge_token(&trashed.item_id);
+        let cmd_mod = feedback.items[0]
+            .mods
+            .as_ref()
+            .and_then(|mods| mods.get("cmd"))
+            .expect("trash row should include cmd purge modifier");
+        assert_eq!(cmd_mod.arg.as_deref(), Some(expected_purge.as_str()));
+
+        execute_restore(&trashed.item_id, None, &config).expect("restore");
+        assert!(matches!(
+            execute_restore(&trashed.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
+        assert_eq!(execute_list(None, 10, 0, &config).expect("list").len(), 2);
         assert!(
-            feedback.items[2].subtitle.is_none(),
-            "delete row should not use subtitle for short preview"
+            execute_trash_list(None, 10, 0, &config)
+                .expect("trash list")
+                .is_empty()
         );
     }
 
     #[test]
-    fn script_filter_returns_delete_action_for_delete_intent() {
+    fn expired_trash_is_purged_on_open_unless_retention_is_disabled() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        let add = execute_add("delete me", None, None, &config).expect("seed add");
-        let query = format!("delete {}", add.item_id);
-
-        let feedback = build_script_filter(&query, &config).expect("script filter");
-
-        assert_eq!(feedback.items.len(), 1);
-        let arg = feedback.items[0].arg.as_deref().expect("arg should exist");
-        assert!(arg.starts_with(DELETE_TOKEN_PREFIX));
-        assert!(
-            feedback.items[0].title.starts_with(
-                format!("Delete memo: {} | delete me", item_display_id(&add.item_id)).as_str()
-            ),
-            "delete intent title should include memo text preview"
-        );
-        assert!(
-            feedback.items[0].subtitle.is_none(),
-            "delete intent should not use subtitle for short preview"
+        let added = execute_add("old memo", None, None, &config).expect("seed add");
+        execute_delete(&added.item_id, None, &config).expect("delete");
+        open_storage(config.db_path.clone())
+            .expect("open storage")
+            .with_connection(|conn| {
+                conn.execute(
+                    "update workflow_item_trash set deleted_at = '2000-01-01T00:00:00.000Z'",
+                    [],
+                )
+                .map_err(MemoCliError::db_write)
+            })
+            .expect("backdate trash row");
+
+        config.trash_retention_days = 0;
+        assert_eq!(
+            execute_trash_list(None, 10, 0, &config)
+                .expect("trash list")
+                .len(),
+            1
         );
-        assert_eq!(feedback.items[0].valid, Some(true));
+
+        config.trash_retention_days = DEFAULT_TRASH_RETENTION_DAYS;
+        let init = execute_db_init(None, &config).expect("db init");
+        assert_eq!(init.purged_expired, 1);
+        assert!(matches!(
+            execute_purge(&added.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
     }
 
     #[test]
-    fn delete_row_overflow_moves_remaining_text_to_subtitle() {
+    fn empty_query_lists_pinned_section_above_recent_without_duplicates() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        let long_text = "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod";
-        let add = execute_add(long_text, None, None, &config).expect("seed add");
-        let query = format!("delete {}", add.item_id);
+        let older = execute_add("older pinned memo", None, None, &config).expect("seed add one");
+        let newer = execute_add("newer memo", None, None, &config).expect("seed add two");
+        let first = execute_pin(&older.item_id, None, &config).expect("pin");
+        assert!(first.changed && first.is_pinned);
+        assert!(
+            !execute_pin(&older.item_id, None, &config)
+                .expect("repeat pin")
+                .changed
+        );
 
-        let feedback = build_script_filter(&query, &config).expect("script filter");
+        let feedback = build_script_filter("", &config).expect("script filter");
+        let rows = feedback
+            .items
+            .iter()
+            .filter(|item| item.autocomplete.is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].title.starts_with("Pinned "));
+        assert_eq!(
+            rows[0].autocomplete.as_deref(),
+            Some(format!("item {}", item_route_id(&older.item_id)).as_str())
+        );
+        assert_eq!(
+            rows[0].icon.as_ref().map(|icon| icon.path.as_str()),
+            Some(PINNED_ICON_PATH)
+        );
+        assert!(rows[1].title.starts_with("Recent "));
+        assert_eq!(
+            rows[1].autocomplete.as_deref(),
+            Some(format!("item {}", item_route_id(&newer.item_id)).as_str())
+        );
+
+        let menu =
+            build_script_filter(&format!("item {}", older.item_id), &config).expect("item menu");
+        let expected_unpin_arg = format!("unpin::{}", older.item_id);
+        assert_eq!(
+            menu.items[3].arg.as_deref(),
+            Some(expected_unpin_arg.as_str())
+        );
 
-        assert_eq!(feedback.items.len(), 1);
         assert!(
-            feedback.items[0].title.ends_with('…'),
-            "overflow title should end with ellipsis"
+            execute_unpin(&older.item_id, None, &config)
+                .expect("unpin")
+                .changed
+        );
+        let feedback = build_script_filter("", &config).expect("script filter");
+        assert!(
+            feedback
+                .items
+                .iter()
+                .all(|item| !item.title.starts_with("Pinned "))
+        );
+    }
+
+    #[test]
+    fn zh_language_and_preview_chars_shape_recent_rows_and_item_menu() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        config.language = Language::Zh;
+        config.preview_chars = 16;
+        let added = execute_add("a fairly long memo text for previews", None, None, &config)
+            .expect("seed add");
+
+        let feedback = build_script_filter("", &config).expect("script filter");
+        assert_eq!(feedback.items[0].title, "在關鍵字後輸入備忘內容");
+        let recent = feedback
+            .items
+            .iter()
+            .find(|item| item.autocomplete.is_some())
+            .expect("recent row");
+        assert_eq!(
+            recent.title,
+            format!(
+                "最近 {}：a fairly long me…",
+                item_display_id(&added.item_id)
+            )
         );
         assert!(
-            feedback.items[0]
+            recent
                 .subtitle
                 .as_deref()
-                .map(|value| !value.is_empty())
-                .unwrap_or(false),
-            "overflow title should move remaining preview into subtitle"
+                .is_some_and(|subtitle| subtitle.ends_with("按 Enter 管理"))
         );
+
+        let menu =
+            build_script_filter(&format!("item {}", added.item_id), &config).expect("item menu");
+        assert!(menu.items[0].title.starts_with("複製備忘："));
+        assert!(menu.items[2].title.starts_with("刪除備忘："));
+
+        config.language = Language::En;
+        let search = build_script_filter("search nothing-like-this", &config).expect("search");
+        assert_eq!(search.items[0].title, "No matching memo records");
     }
 
     #[test]
-    fn script_filter_returns_copy_action_for_copy_intent() {
+    fn pin_rejects_trashed_items_and_trash_hides_pinned_rows() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        let add = execute_add("seed memo", None, None, &config).expect("seed add");
-        let query = format!("copy {}", add.item_id);
+        let added = execute_add("pin then trash", None, None, &config).expect("seed add");
+        execute_pin(&added.item_id, None, &config).expect("pin");
+        execute_delete(&added.item_id, None, &config).expect("delete");
 
-        let feedback = build_script_filter(&query, &config).expect("script filter");
-
-        assert_eq!(feedback.items.len(), 1);
-        let expected_item_display = item_display_id(&add.item_id);
-        let expected_copy_title_prefix =
-            format!("Copy memo: {} | seed memo", expected_item_display);
         assert!(
-            feedback.items[0]
-                .title
-                .starts_with(expected_copy_title_prefix.as_str()),
-            "copy intent title should include memo text preview"
+            execute_pinned_list(None, 10, &config)
+                .expect("pinned list")
+                .is_empty()
         );
-        let expected_copy_arg = format!("copy::{}", add.item_id);
+        assert!(matches!(
+            execute_pin(&added.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
+
+        execute_restore(&added.item_id, None, &config).expect("restore");
+        let pinned = execute_pinned_list(None, 10, &config).expect("pinned list");
+        assert_eq!(pinned.len(), 1);
+        assert!(pinned[0].is_pinned);
+    }
+
+    #[test]
+    fn export_token_roundtrip_and_intent_rows() {
+        let token = build_export_token(ExportFormat::Markdown, Some("work"));
+        assert_eq!(token, "export::markdown::work");
         assert_eq!(
-            feedback.items[0].arg.as_deref(),
-            Some(expected_copy_arg.as_str())
-        );
-        assert!(
-            feedback.items[0].subtitle.is_none(),
-            "copy intent should not use subtitle for short preview"
+            parse_export_token(&token),
+            Some((ExportFormat::Markdown, Some("work".to_string())))
         );
-        let cmd_mod = feedback.items[0]
-            .mods
-            .as_ref()
-            .and_then(|mods| mods.get("cmd"))
-            .expect("copy row should include cmd modifier");
-        let expected_copy_json_arg = format!("copy-json::{}", add.item_id);
         assert_eq!(
-            cmd_mod.arg.as_deref(),
-            Some(expected_copy_json_arg.as_str())
+            parse_export_token("export::csv"),
+            Some((ExportFormat::Csv, None))
         );
-    }
-
-    #[test]
-    fn script_filter_search_intent_returns_manage_rows() {
-        let dir = tempdir().expect("temp dir");
-        let mut config = test_config();
-        config.db_path = dir.path().join("memo.db");
-        let add = execute_add("buy milk", None, None, &config).expect("seed add");
+        assert!(parse_export_token("export::xml").is_none());
 
-        let feedback = build_script_filter("search milk", &config).expect("script filter");
+        let all = build_script_filter("export", &test_config()).expect("export intent");
+        let args = all
+            .items
+            .iter()
+            .map(|item| item.arg.clone().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            args,
+            vec!["export::json", "export::markdown", "export::csv"]
+        );
 
+        let tagged = build_script_filter("export md #Work", &test_config()).expect("export intent");
+        assert_eq!(tagged.items.len(), 1);
         assert_eq!(
-            feedback.items.len(),
-            1,
-            "single search hit should keep selectable search row"
+            tagged.items[0].title,
+            "Export memos tagged #work as Markdown"
         );
-        assert_eq!(feedback.items[0].valid, Some(false));
-        let expected_autocomplete = format!("item {}", item_route_id(&add.item_id));
         assert_eq!(
-            feedback.items[0].autocomplete.as_deref(),
-            Some(expected_autocomplete.as_str())
+            tagged.items[0].arg.as_deref(),
+            Some("export::markdown::work")
         );
+
+        let invalid =
+            build_script_filter("export csv #work #home", &test_config()).expect("export intent");
+        assert_eq!(invalid.items[0].title, "Invalid export syntax");
+        assert_eq!(invalid.items[0].valid, Some(false));
     }
 
     #[test]
-    fn script_filter_search_intent_multiple_hits_keep_search_rows() {
+    fn export_writes_filtered_json_and_skips_trash_unless_requested() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        execute_add("milk tea", None, None, &config).expect("seed add one");
-        execute_add("milk coffee", None, None, &config).expect("seed add two");
-
-        let feedback = build_script_filter("search milk", &config).expect("script filter");
-
+        config.export_dir = dir.path().join("exports");
+        let work = execute_add("#work ship release", None, None, &config).expect("seed add one");
+        execute_add("buy milk", None, None, &config).expect("seed add two");
+        let trashed = execute_add("#work old draft", None, None, &config).expect("seed add three");
+        execute_pin(&work.item_id, None, &config).expect("pin");
+        execute_delete(&trashed.item_id, None, &config).expect("delete");
+
+        let filter = ExportFilter {
+            tag: Some("#Work".to_string()),
+            include_trash: false,
+        };
+        let result =
+            execute_export(ExportFormat::Json, None, &filter, None, &config).expect("export");
+        assert_eq!(result.exported, 1);
+        assert_eq!(result.format, "json");
         assert!(
-            feedback.items.len() >= 2,
-            "multiple search hits should keep search result rows"
+            result
+                .path
+                .starts_with(&config.export_dir.display().to_string())
         );
-        assert_eq!(feedback.items[0].valid, Some(false));
-        let autocomplete = feedback.items[0]
-            .autocomplete
-            .as_deref()
-            .expect("search row should include autocomplete");
+        assert!(result.path.ends_with(".json"));
+
+        let payload: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&result.path).expect("read export"))
+                .expect("export json");
+        assert_eq!(payload["schema_version"], "memo-export@v1");
+        let items = payload["items"].as_array().expect("items");
+        assert_eq!(items[0]["item_id"], work.item_id.as_str());
+        assert_eq!(items[0]["text"], "#work ship release");
+        assert_eq!(items[0]["tags"], serde_json::json!(["work"]));
+        assert_eq!(items[0]["state"], "pending");
+        assert_eq!(items[0]["is_pinned"], true);
+
+        let csv_path = dir.path().join("all.csv");
+        let filter = ExportFilter {
+            tag: None,
+            include_trash: true,
+        };
+        let result = execute_export(
+            ExportFormat::Csv,
+            Some(csv_path.clone()),
+            &filter,
+            None,
+            &config,
+        )
+        .expect("csv export");
+        assert_eq!(result.exported, 3);
+        let csv = std::fs::read_to_string(&csv_path).expect("read csv");
+        assert_eq!(csv.lines().count(), 4);
         assert!(
-            autocomplete.starts_with("item "),
-            "multi-hit search rows should route to item intent"
+            csv.lines()
+                .nth(3)
+                .expect("trashed row")
+                .contains("old draft")
         );
+
+        let invalid = ExportFilter {
+            tag: Some("bad!tag".to_string()),
+            include_trash: false,
+        };
+        assert!(matches!(
+            execute_export(ExportFormat::Csv, None, &invalid, None, &config),
+            Err(AppError::User(_))
+        ));
     }
 
     #[test]
-    fn script_filter_search_intent_requires_query_text() {
-        let feedback = build_script_filter("search", &test_config()).expect("script filter");
+    fn import_roundtrips_export_and_skips_duplicates_by_content() {
+        let dir = tempdir().expect("temp dir");
+        let mut source = test_config();
+        source.db_path = dir.path().join("source.db");
+        let first = execute_add("#work ship release", None, None, &source).expect("seed add one");
+        execute_add_with_tags("buy milk", &["home".to_string()], None, None, &source)
+            .expect("seed add two");
+        execute_pin(&first.item_id, None, &source).expect("pin");
+        let export_path = dir.path().join("backup.md");
+        execute_export(
+            ExportFormat::Markdown,
+            Some(export_path.clone()),
+            &ExportFilter::default(),
+            None,
+            &source,
+        )
+        .expect("export");
 
-        assert_eq!(feedback.items.len(), 1);
-        assert_eq!(feedback.items[0].valid, Some(false));
-        assert_eq!(feedback.items[0].title, "Type search text after keyword");
+        let mut target = test_config();
+        target.db_path = dir.path().join("target.db");
+        execute_add("buy milk", None, None, &target).expect("seed duplicate");
+        let imported = execute_import(&export_path, None, None, &target).expect("import");
+        assert_eq!(imported.format, "markdown");
+        assert_eq!(
+            (imported.imported, imported.skipped, imported.invalid),
+            (1, 1, 0)
+        );
+
+        let detail = execute_fetch_item(&imported.item_ids[0], None, &target).expect("fetch");
+        assert_eq!(detail.text, "#work ship release");
+        assert_eq!(detail.created_at, first.created_at);
+        assert!(detail.is_pinned);
+
+        let again = execute_import(&export_path, None, None, &target).expect("re-import");
+        assert_eq!((again.imported, again.skipped), (0, 2));
     }
 
     #[test]
-    fn script_filter_search_intent_supports_explicit_match_modes() {
+    fn import_csv_normalizes_timestamps_and_counts_invalid_rows() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        let add = execute_add("123", None, None, &config).expect("seed add");
+        config.max_input_bytes = 32;
+        let csv_path = dir.path().join("notes.csv");
+        std::fs::write(
+            &csv_path,
+            "Content,Created,Tags\n\"line one\nline two\",2025-03-04 05:06:07+02:00,\"Travel, #Work\"\n,2025-01-01,\nthis memo text is far too long to import,,\nsecond,not a date,\n",
+        )
+        .expect("write csv");
 
-        let prefix_feedback =
-            build_script_filter("search --match prefix 12", &config).expect("prefix search");
-        assert_eq!(prefix_feedback.items.len(), 1);
-        let expected_autocomplete = format!("item {}", item_route_id(&add.item_id));
-        assert_eq!(
-            prefix_feedback.items[0].autocomplete.as_deref(),
-            Some(expected_autocomplete.as_str())
-        );
+        let result = execute_import(&csv_path, None, None, &config).expect("import");
+        assert_eq!((result.imported, result.invalid), (2, 2));
 
-        let contains_feedback =
-            build_script_filter("search --match contains 23", &config).expect("contains search");
-        assert_eq!(contains_feedback.items.len(), 1);
+        let rows = execute_tag_items(None, "travel", 10, 0, &config).expect("tag items");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].created_at, "2025-03-04T03:06:07.000Z");
         assert_eq!(
-            contains_feedback.items[0].autocomplete.as_deref(),
-            Some(expected_autocomplete.as_str())
+            execute_tag_items(None, "work", 10, 0, &config)
+                .expect("tag items")
+                .len(),
+            1
         );
+
+        let missing = execute_import(&dir.path().join("notes.xml"), None, None, &config);
+        assert!(matches!(missing, Err(AppError::User(_))));
     }
 
     #[test]
-    fn script_filter_search_intent_uses_configured_default_match_mode() {
+    fn edit_applies_saved_editor_text_and_skips_unchanged_files() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        config.search_match_mode = SearchMatchMode::Prefix;
-        let add = execute_add("123", None, None, &config).expect("seed add");
-
-        let feedback = build_script_filter("search 12", &config).expect("prefix-by-default search");
-        assert_eq!(feedback.items.len(), 1);
-        let expected_autocomplete = format!("item {}", item_route_id(&add.item_id));
+        let added = execute_add("draft #todo", None, None, &config).expect("seed add");
+
+        let script = dir.path().join("editor.sh");
+        std::fs::write(&script, "printf 'final text #done\\n' > \"$1\"\n").expect("write editor");
+        config.editor = format!("sh '{}'", script.display());
+        let edited = execute_edit(&added.item_id, None, &config).expect("edit");
+        assert!(edited.changed);
+        assert!(edited.updated_at.is_some());
+        let detail = execute_fetch_item(&added.item_id, None, &config).expect("fetch");
+        assert_eq!(detail.text, "final text #done");
         assert_eq!(
-            feedback.items[0].autocomplete.as_deref(),
-            Some(expected_autocomplete.as_str())
+            execute_tag_items(None, "done", 10, 0, &config)
+                .expect("tag items")
+                .len(),
+            1
         );
-    }
 
-    #[test]
-    fn script_filter_search_intent_rejects_invalid_match_mode() {
-        let feedback = build_script_filter("search --match fuzzy milk", &test_config())
-            .expect("script filter");
+        config.editor = "true".to_string();
+        let unchanged = execute_edit(&added.item_id, None, &config).expect("noop edit");
+        assert!(!unchanged.changed && unchanged.updated_at.is_none());
 
-        assert_eq!(feedback.items.len(), 1);
-        assert_eq!(feedback.items[0].valid, Some(false));
-        assert_eq!(feedback.items[0].title, "Invalid search match mode: fuzzy");
+        config.editor = "false".to_string();
+        assert!(matches!(
+            execute_edit(&added.item_id, None, &config),
+            Err(AppError::Runtime(_))
+        ));
+
+        execute_delete(&added.item_id, None, &config).expect("delete");
+        assert!(matches!(
+            execute_edit(&added.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
+        assert_eq!(
+            parse_edit_token(&build_edit_token(&added.item_id)).as_deref(),
+            Some(added.item_id.as_str())
+        );
     }
 
     #[test]
-    fn execute_search_rejects_invalid_limit_or_window() {
+    fn attach_stores_canonical_locations_and_lists_open_rows_in_item_menu() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        execute_add("seed memo", None, None, &config).expect("seed add");
+        let added = execute_add("receipt scan", None, None, &config).expect("seed add");
+        let file = dir.path().join("receipt.pdf");
+        std::fs::write(&file, "pdf").expect("write attachment");
+        let canonical = std::fs::canonicalize(&file).expect("canonical path");
+
+        let dotted = format!("{}/./receipt.pdf", dir.path().display());
+        let attached = execute_attach(&added.item_id, &dotted, None, &config).expect("attach");
+        assert!(attached.changed);
+        assert_eq!(attached.location, canonical.to_string_lossy());
+        let again = execute_attach(&added.item_id, &file.to_string_lossy(), None, &config)
+            .expect("attach again");
+        assert!(!again.changed && again.attachment_id == attached.attachment_id);
+        execute_attach(&added.item_id, "https://example.com/r", None, &config).expect("url");
+        assert!(matches!(
+            execute_attach(&added.item_id, "/no/such/file", None, &config),
+            Err(AppError::User(_))
+        ));
 
-        let invalid_limit = execute_search(None, "seed", SearchMatchMode::Fts, 0, 0, &config);
-        assert!(invalid_limit.is_err(), "search should reject limit=0");
+        let detail = execute_fetch_item(&added.item_id, None, &config).expect("fetch");
+        assert_eq!(detail.attachment_count, 2);
+        let menu =
+            build_script_filter(&format!("item {}", added.item_id), &config).expect("item menu");
+        assert_eq!(menu.items.len(), 7);
+        assert_eq!(menu.items[5].title, "Open attachment: receipt.pdf");
+        let open_arg = build_open_attachment_token(attached.attachment_id);
+        assert_eq!(menu.items[5].arg.as_deref(), Some(open_arg.as_str()));
+        assert_eq!(
+            menu.items[6].title,
+            "Open attachment: https://example.com/r"
+        );
 
-        let invalid_window = execute_search(None, "seed", SearchMatchMode::Fts, 200, 400, &config);
-        assert!(
-            invalid_window.is_err(),
-            "search should reject oversized window"
+        let intent = build_script_filter(&format!("attach {} {dotted}", added.item_id), &config)
+            .expect("attach intent");
+        let attach_arg = build_attach_token(&added.item_id, &canonical.to_string_lossy());
+        assert_eq!(intent.items[0].arg.as_deref(), Some(attach_arg.as_str()));
+        assert_eq!(
+            parse_attach_token(&attach_arg),
+            Some((
+                added.item_id.clone(),
+                canonical.to_string_lossy().into_owned()
+            ))
         );
+
+        std::fs::remove_file(&file).expect("remove attachment");
+        assert!(matches!(
+            execute_open_attachment(&attached.attachment_id.to_string(), None, &config),
+            Err(AppError::User(_))
+        ));
+        execute_delete(&added.item_id, None, &config).expect("delete");
+        assert!(matches!(
+            execute_attachments(&added.item_id, None, &config),
+            Err(AppError::User(_))
+        ));
     }
 
     #[test]
-    fn script_filter_rejects_delete_with_invalid_item_id() {
-        let feedback =
-            build_script_filter("delete abc", &test_config()).expect("script filter should build");
-
-        assert_eq!(feedback.items.len(), 1);
-        assert_eq!(feedback.items[0].valid, Some(false));
-        assert_eq!(feedback.items[0].title, "Invalid item_id for delete");
+    fn merge_appends_text_unions_rows_trashes_merged_memo_and_undoes() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let keep = execute_add("trip plan #travel", None, None, &config).expect("seed keep");
+        let merged = execute_add_with_tags(
+            "hotel booking",
+            &["booking".to_string()],
+            None,
+            None,
+            &config,
+        )
+        .expect("seed merged");
+        let other = execute_add("packing list", None, None, &config).expect("seed other");
+        execute_attach(&merged.item_id, "https://example.com/hotel", None, &config)
+            .expect("attach");
+        execute_link(&merged.item_id, &other.item_id, None, &config).expect("link");
+
+        let keep_route = item_route_id(&keep.item_id);
+        let merged_route = item_route_id(&merged.item_id);
+        let preview = build_script_filter(&format!("merge {keep_route} {merged_route}"), &config)
+            .expect("merge intent");
+        let merge_arg = build_merge_token(&keep.item_id, &merged.item_id);
+        assert_eq!(preview.items.len(), 4);
+        assert_eq!(preview.items[0].arg.as_deref(), Some(merge_arg.as_str()));
+        assert!(
+            preview.items[1..]
+                .iter()
+                .all(|item| item.valid == Some(false))
+        );
+        assert_eq!(
+            preview.items[3].title,
+            "Result: trip plan #travel hotel booking"
+        );
+        assert_eq!(
+            parse_merge_token(&merge_arg),
+            Some((keep.item_id.clone(), merged.item_id.clone()))
+        );
+        assert_eq!(
+            parse_merge_token(&format!("merge::{keep_route}::{keep_route}")),
+            None
+        );
+        let itself = build_script_filter(&format!("merge {keep_route} {keep_route}"), &config)
+            .expect("self merge intent");
+        assert_eq!(itself.items[0].title, "Cannot merge a memo into itself");
+
+        let result = execute_merge(&keep.item_id, &merged.item_id, None, &config).expect("merge");
+        assert_eq!(result.text, "trip plan #travel\n\nhotel booking");
+        assert_eq!(result.tags, vec!["booking", "travel"]);
+        assert_eq!(result.attachments_added, 1);
+        assert_eq!(result.links_added, 1);
+        assert!(matches!(
+            execute_merge(&keep.item_id, &merged.item_id, None, &config),
+            Err(AppError::User(message)) if message.contains("trash")
+        ));
+        let detail = execute_fetch_item(&keep.item_id, None, &config).expect("fetch keep");
+        assert_eq!(detail.attachment_count, 1);
+        assert_eq!(detail.linked_count, 1);
+        assert_eq!(detail.version_count, 1);
+
+        let undo_row = build_script_filter("undo", &config).expect("undo intent");
+        assert!(undo_row.items[0].title.starts_with("Undo merge"));
+        let undone = execute_undo(None, None, &config).expect("undo merge");
+        assert_eq!(undone.operation, "merge");
+        assert_eq!(undone.state, "unmerged");
+        assert_eq!(
+            execute_fetch_item(&keep.item_id, None, &config)
+                .expect("fetch keep after undo")
+                .text,
+            "trip plan #travel"
+        );
+        execute_pin(&merged.item_id, None, &config).expect("merged memo is live again");
     }
 
     #[test]
-    fn script_filter_enforces_max_input_bytes() {
+    fn link_connects_memos_both_ways_and_item_menu_lists_linked_count() {
+        let dir = tempdir().expect("temp dir");
         let mut config = test_config();
-        config.max_input_bytes = 4;
+        config.db_path = dir.path().join("memo.db");
+        let first = execute_add("design notes", None, None, &config).expect("seed add one");
+        let second = execute_add("meeting minutes", None, None, &config).expect("seed add two");
+        let third = execute_add("follow-ups", None, None, &config).expect("seed add three");
+
+        let linked = execute_link(&first.item_id, &second.item_id, None, &config).expect("link");
+        assert!(linked.changed);
+        assert_eq!(linked.linked_count, 1);
+        let again = execute_link(&second.item_id, &first.item_id, None, &config).expect("relink");
+        assert!(!again.changed);
+        execute_link(&third.item_id, &first.item_id, None, &config).expect("link third");
+        assert!(matches!(
+            execute_link(&first.item_id, &first.item_id, None, &config),
+            Err(AppError::User(message)) if message.contains("itself")
+        ));
 
-        let feedback = build_script_filter("12345", &config).expect("script filter should build");
-        assert_eq!(feedback.items.len(), 1);
-        assert_eq!(feedback.items[0].valid, Some(false));
-    }
+        let backlinks = execute_linked(&second.item_id, None, &config).expect("backlinks");
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].item_id, first.item_id);
+        assert_eq!(backlinks[0].text_preview, "design notes");
+        assert_eq!(
+            execute_fetch_item(&first.item_id, None, &config)
+                .expect("fetch")
+                .linked_count,
+            2
+        );
 
-    #[test]
-    fn parse_bool_supports_expected_values() {
-        assert_eq!(parse_bool("true"), Some(true));
-        assert_eq!(parse_bool("1"), Some(true));
-        assert_eq!(parse_bool("false"), Some(false));
-        assert_eq!(parse_bool("0"), Some(false));
-        assert_eq!(parse_bool("unknown"), None);
-    }
+        let route = item_route_id(&first.item_id);
+        let menu = build_script_filter(&format!("item {route}"), &config).expect("item menu");
+        assert_eq!(menu.items[5].title, "Linked: 2 memos");
+        let list_route = format!("linked {route}");
+        assert_eq!(
+            menu.items[5].autocomplete.as_deref(),
+            Some(list_route.as_str())
+        );
+        let listed = build_script_filter(&list_route, &config).expect("linked intent");
+        assert_eq!(listed.items.len(), 2);
+        assert!(listed.items.iter().all(|item| item.valid == Some(false)));
 
-    #[test]
-    fn expand_home_path_supports_tilde_prefix() {
+        let intent = build_script_filter(
+            &format!("link {route} {}", item_route_id(&third.item_id)),
+            &config,
+        )
+        .expect("link intent");
+        let link_arg = build_link_token(&first.item_id, &third.item_id);
+        assert_eq!(intent.items[0].arg.as_deref(), Some(link_arg.as_str()));
         assert_eq!(
-            expand_home_path("~/memo.db", Some("/tmp/home")),
-            "/tmp/home/memo.db"
+            parse_link_token(&link_arg),
+            Some((first.item_id.clone(), third.item_id.clone()))
         );
+        assert_eq!(parse_link_token(&format!("link::{route}::{route}")), None);
+        let itself = build_script_filter(&format!("link {route} {route}"), &config)
+            .expect("self link intent");
+        assert_eq!(itself.items[0].title, "Cannot link a memo to itself");
+
+        execute_delete(&third.item_id, None, &config).expect("delete");
         assert_eq!(
-            expand_home_path("$HOME/memo.db", Some("/tmp/home")),
-            "/tmp/home/memo.db"
+            execute_linked(&first.item_id, None, &config)
+                .expect("linked after delete")
+                .len(),
+            1
+        );
+        let menu = build_script_filter(&format!("item {route}"), &config).expect("item menu");
+        assert_eq!(menu.items[5].title, "Linked: 1 memo");
+        execute_purge(&second.item_id, None, &config).expect("purge");
+        let menu = build_script_filter(&format!("item {route}"), &config).expect("item menu");
+        assert!(
+            menu.items
+                .iter()
+                .all(|item| !item.title.starts_with("Linked:"))
         );
     }
 
     #[test]
-    fn copy_row_overflow_moves_remaining_text_to_subtitle() {
+    fn update_keeps_versions_and_history_restore_copies_one_back() {
         let dir = tempdir().expect("temp dir");
         let mut config = test_config();
         config.db_path = dir.path().join("memo.db");
-        let long_text =
-            "copy overflow lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod";
-        let add = execute_add(long_text, None, None, &config).expect("seed add");
-        let query = format!("copy {}", add.item_id);
+        let added = execute_add("draft one", None, None, &config).expect("seed add");
+        execute_update(&added.item_id, "draft two", None, &config).expect("update one");
+        execute_update(&added.item_id, "  draft two ", None, &config).expect("same text");
+        execute_update(&added.item_id, "final text", None, &config).expect("update two");
 
-        let feedback = build_script_filter(&query, &config).expect("script filter");
+        let versions = execute_history(&added.item_id, None, &config).expect("history");
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| (version.version, version.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(2, "draft two"), (1, "draft one")]
+        );
+        let route = item_route_id(&added.item_id);
+        let menu = build_script_filter(&format!("item {route}"), &config).expect("item menu");
+        assert_eq!(menu.items[5].title, "History: 2 earlier versions");
 
-        assert_eq!(feedback.items.len(), 1);
+        let intent = build_script_filter(&format!("history {route}"), &config).expect("history");
+        assert_eq!(intent.items.len(), 2);
+        assert_eq!(intent.items[1].title, "v1 #1: draft one");
         assert!(
-            feedback.items[0].title.ends_with('…'),
-            "overflow title should end with ellipsis"
+            intent.items[1]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.ends_with("Restore: -final text +draft one"))
         );
+        let restore_arg = build_history_restore_token(versions[1].version_id);
+        assert_eq!(intent.items[1].arg.as_deref(), Some(restore_arg.as_str()));
+        assert_eq!(
+            parse_history_restore_token(&restore_arg),
+            Some(versions[1].version_id.to_string())
+        );
+
+        let restored = execute_history_restore(&versions[1].version_id.to_string(), None, &config)
+            .expect("restore");
+        assert!(restored.changed);
+        let detail = execute_fetch_item(&added.item_id, None, &config).expect("fetch");
+        assert_eq!(detail.text, "draft one");
+        assert_eq!(detail.version_count, 3);
+        let again = execute_history_restore(&versions[1].version_id.to_string(), None, &config)
+            .expect("restore again");
+        assert!(!again.changed);
+        assert!(matches!(
+            execute_history_restore("999", None, &config),
+            Err(AppError::User(message)) if message.contains("does not exist")
+        ));
+
+        config.encryption_passphrase = Some("correct horse".to_string());
+        execute_db_encrypt(None, &config).expect("encrypt db");
+        let stored_plaintext: i64 = open_storage(config.db_path.clone())
+            .expect("open storage")
+            .with_connection(|conn| {
+                conn.query_row(
+                    "select count(*) from workflow_item_versions where text not like 'memoenc:v1:%'",
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(MemoCliError::db_query)
+            })
+            .expect("count plaintext versions");
+        assert_eq!(stored_plaintext, 0);
+        assert_eq!(
+            execute_history(&added.item_id, None, &config).expect("encrypted history")[0].text,
+            "final text"
+        );
+    }
+
+    #[test]
+    fn remind_sets_due_dates_and_surfaces_overdue_memos_first() {
+        let dir = tempdir().expect("temp dir");
+        let mut config = test_config();
+        config.db_path = dir.path().join("memo.db");
+        let overdue = execute_add("file taxes", None, None, &config).expect("seed overdue");
+        let later = execute_add("renew passport", None, None, &config).expect("seed later");
+
+        let set = execute_remind(&overdue.item_id, "2000-01-01T09:00:00Z", None, &config)
+            .expect("remind overdue");
+        assert_eq!(set.due_at.as_deref(), Some("2000-01-01T09:00:00.000Z"));
+        assert_eq!(set.status.as_deref(), Some("overdue"));
+        execute_remind(&later.item_id, "2999-01-01", None, &config).expect("remind later");
+        assert!(matches!(
+            execute_remind(&later.item_id, "someday", None, &config),
+            Err(AppError::User(_))
+        ));
+
+        let due = execute_due_list(None, false, 10, &config).expect("due list");
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].item_id, overdue.item_id);
+        assert_eq!(due[0].status, "overdue");
+        assert_eq!(
+            execute_due_list(None, true, 10, &config)
+                .expect("all due")
+                .len(),
+            2
+        );
+
+        let feedback = build_script_filter("", &config).expect("empty query");
+        let due_row = &feedback.items[2];
+        assert_eq!(due_row.title, "Due #1: file taxes");
         assert!(
-            feedback.items[0]
+            due_row
                 .subtitle
                 .as_deref()
-                .map(|value| !value.is_empty())
-                .unwrap_or(false),
-            "overflow title should move remaining preview into subtitle"
+                .is_some_and(|subtitle| subtitle.starts_with("Overdue by "))
+        );
+
+        let intent = build_script_filter("remind 2 2999-01-01T00:00:00Z", &config).expect("intent");
+        let token = build_remind_token(&later.item_id, "2999-01-01T00:00:00.000Z");
+        assert_eq!(intent.items[0].arg.as_deref(), Some(token.as_str()));
+        assert_eq!(
+            parse_remind_token(&token),
+            Some((
+                later.item_id.clone(),
+                "2999-01-01T00:00:00.000Z".to_string()
+            ))
+        );
+
+        let cleared = execute_remind(&overdue.item_id, "clear", None, &config).expect("clear");
+        assert!(cleared.changed && cleared.due_at.is_none());
+        assert!(
+            execute_due_list(None, false, 10, &config)
+                .expect("due after clear")
+                .is_empty()
+        );
+
+        execute_delete(&later.item_id, None, &config).expect("delete");
+        assert!(
+            execute_due_list(None, true, 10, &config)
+                .expect("due after delete")
+                .is_empty()
         );
     }
 }
@@ -0,0 +1,152 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+
+use crate::LinkedMemoResult;
+use crate::crypto::TextCodec;
+use crate::trash;
+
+const MAX_LINK_CHARS: usize = 2048;
+/// Preview length matching `PREVIEW_TEXT_SQL` list rows.
+const PREVIEW_CHARS: usize = 80;
+
+/// Extract `http(s)://` and `www.` links from memo text in first-seen order, without repeats.
+///
+/// A link must start the text or follow whitespace or one of `(<["'*_`, which covers bare URLs,
+/// `<https://...>` autolinks, and markdown `[label](https://...)` targets. It ends at whitespace or
+/// a closing delimiter, then trailing sentence punctuation and unbalanced `)`/`]` are dropped.
+/// `www.` links are returned with an `https://` scheme so they can be opened directly.
+pub fn extract_links(text: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut previous: Option<char> = None;
+
+    for (index, ch) in text.char_indices() {
+        let at_boundary = previous.is_none_or(|prev| {
+            prev.is_whitespace() || matches!(prev, '(' | '<' | '[' | '"' | '\'' | '*' | '_')
+        });
+        previous = Some(ch);
+        if !at_boundary || !matches!(ch, 'h' | 'H' | 'w' | 'W') {
+            continue;
+        }
+
+        let rest = &text[index..];
+        let Some(scheme_len) = link_prefix_len(rest) else {
+            continue;
+        };
+        let end = rest
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '<' | '>' | '"' | '`'))
+            .unwrap_or(rest.len());
+        let candidate = trim_link_end(&rest[..end]);
+        if candidate.len() <= scheme_len || candidate.chars().count() > MAX_LINK_CHARS {
+            continue;
+        }
+
+        let link = if candidate[..4].eq_ignore_ascii_case("www.") {
+            format!("https://{candidate}")
+        } else {
+            candidate.to_string()
+        };
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+
+    links
+}
+
+/// Length of the `http://` / `https://` / `www.` prefix `rest` starts with (case-insensitive).
+fn link_prefix_len(rest: &str) -> Option<usize> {
+    ["https://", "http://", "www."]
+        .into_iter()
+        .find_map(|prefix| {
+            rest.get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+                .then_some(prefix.len())
+        })
+}
+
+/// Drop trailing punctuation that belongs to the surrounding sentence or markdown syntax.
+fn trim_link_end(candidate: &str) -> &str {
+    let mut link = candidate;
+    loop {
+        let trimmed = link.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '*', '_']);
+        let trimmed = match trimmed.chars().last() {
+            Some(')') if trimmed.matches('(').count() < trimmed.matches(')').count() => {
+                &trimmed[..trimmed.len() - 1]
+            }
+            Some(']') if trimmed.matches('[').count() < trimmed.matches(']').count() => {
+                &trimmed[..trimmed.len() - 1]
+            }
+            _ => trimmed,
+        };
+        if trimmed.len() == link.len() {
+            return link;
+        }
+        link = trimmed;
+    }
+}
+
+/// Newest-first live memos whose text contains at least one link.
+pub(crate) fn list_linked_items(
+    conn: &Connection,
+    codec: &TextCodec,
+    limit: usize,
+) -> Result<Vec<LinkedMemoResult>, MemoCliError> {
+    Ok(trash::list_live_texts(conn, codec, None)?
+        .into_iter()
+        .filter_map(|row| {
+            let links = extract_links(&row.text);
+            (!links.is_empty()).then(|| LinkedMemoResult {
+                item_id: format_item_id(row.item_id),
+                created_at: row.created_at,
+                text_preview: row.text.chars().take(PREVIEW_CHARS).collect(),
+                links,
+            })
+        })
+        .take(limit)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_links_finds_bare_and_www_urls_without_sentence_punctuation() {
+        assert_eq!(
+            extract_links(
+                "see https://example.com/a?b=1, then (http://x.io/docs). and www.Rust-lang.org!"
+            ),
+            vec![
+                "https://example.com/a?b=1".to_string(),
+                "http://x.io/docs".to_string(),
+                "https://www.Rust-lang.org".to_string(),
+            ]
+        );
+        assert_eq!(
+            extract_links(
+                "https://en.wikipedia.org/wiki/Rust_(language) twice https://en.wikipedia.org/wiki/Rust_(language)"
+            ),
+            vec!["https://en.wikipedia.org/wiki/Rust_(language)".to_string()]
+        );
+        assert!(extract_links("no link: https:// or foo.https://a.b or ftp://x.y").is_empty());
+    }
+
+    #[test]
+    fn extract_links_reads_markdown_targets_and_autolinks() {
+        assert_eq!(
+            extract_links(
+                "[docs](https://docs.rs/memo) and <https://example.com/x> **https://b.dev**"
+            ),
+            vec![
+                "https://docs.rs/memo".to_string(),
+                "https://example.com/x".to_string(),
+                "https://b.dev".to_string(),
+            ]
+        );
+        assert_eq!(
+            extract_links("![img](https://cdn.example.com/a.png \"title\")"),
+            vec!["https://cdn.example.com/a.png".to_string()]
+        );
+    }
+}
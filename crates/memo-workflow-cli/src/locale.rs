@@ -0,0 +1,270 @@
+use std::fmt::Display;
+
+/// Script-filter row language selected by `MEMO_LANG` (`en` default, `zh` optional).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    En,
+    Zh,
+}
+
+impl Language {
+    /// Parse a `MEMO_LANG` value, accepting the same aliases as the weather workflow locale.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "en" | "english" => Some(Self::En),
+            "zh" | "zh-tw" | "zh-cn" | "zh-hant" | "zh-hans" | "chinese" => Some(Self::Zh),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Zh => "zh",
+        }
+    }
+
+    /// Row string for `msg`, filling each `{}` placeholder from `args` in order.
+    pub(crate) fn text(self, msg: Msg, args: &[&dyn Display]) -> String {
+        let (en, zh) = msg.templates();
+        let template = match self {
+            Self::En => en,
+            Self::Zh => zh,
+        };
+
+        let mut args = args.iter();
+        let mut output = String::with_capacity(template.len());
+        let mut pieces = template.split("{}");
+        if let Some(first) = pieces.next() {
+            output.push_str(first);
+        }
+        for piece in pieces {
+            if let Some(arg) = args.next() {
+                output.push_str(&arg.to_string());
+            }
+            output.push_str(piece);
+        }
+        output
+    }
+}
+
+/// User-facing row strings shared by the capture, recent-list, search, and item-menu rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Msg {
+    AddMemo,
+    AddAnyway,
+    AddAnywayTitle,
+    ConfirmAdd,
+    ConfirmRequired,
+    PreviewTitle,
+    PressEnterToSave,
+    SourceAndDb,
+    TagsHint,
+    InputTooLarge,
+    InputTooLargeDetail,
+    SimilarMemoExists,
+    Identical,
+    PercentSimilar,
+    DuplicateDetail,
+    TypeMemoText,
+    MaxBytesAndSource,
+    InitializeDatabase,
+    CreateDatabaseAt,
+    NoRecordsYet,
+    NoRecordsInitHint,
+    NoRecordsAddHint,
+    DatabasePath,
+    UsingDatabaseAt,
+    LastBackup,
+    NoBackupsYet,
+    DueRow,
+    PinnedRow,
+    RecentRow,
+    SearchRow,
+    DidYouMeanRow,
+    EmptyMemo,
+    ManageDetail,
+    ApproximateMatchManage,
+    SearchFieldsManage,
+    NotAvailable,
+    TypeSearchText,
+    NoMatchingRecords,
+    NoResultsFor,
+    InvalidSyntax,
+    InvalidItemId,
+    InvalidItemIdHint,
+    ItemNotFound,
+    CopyMemo,
+    PreviewJson,
+    CopyRichText,
+    CopyHtmlSource,
+    UpdateMemo,
+    DeleteMemo,
+    PinMemo,
+    PinHint,
+    UnpinMemo,
+    UnpinHint,
+    EditInEditor,
+    EditHint,
+    HistoryOne,
+    HistoryMany,
+    HistoryHint,
+    LinkedOne,
+    LinkedMany,
+    LinkedHint,
+    OpenAttachment,
+    AttachmentPosition,
+    OpenUrl,
+    LinkPosition,
+}
+
+impl Msg {
+    /// `(en, zh)` templates; `{}` marks one argument.
+    fn templates(self) -> (&'static str, &'static str) {
+        match self {
+            Self::AddMemo => ("Add memo: {}", "新增備忘：{}"),
+            Self::AddAnyway => ("Add anyway", "仍要新增"),
+            Self::AddAnywayTitle => ("Add anyway: {}", "仍要新增：{}"),
+            Self::ConfirmAdd => ("Confirm add memo", "確認新增備忘"),
+            Self::ConfirmRequired => (
+                "Confirmation required. Choose the row below to save.",
+                "需要確認，選擇下一列以儲存。",
+            ),
+            Self::PreviewTitle => ("Preview: {}", "預覽：{}"),
+            Self::PressEnterToSave => (
+                "Press Enter to save ({}/{} bytes).{}",
+                "按 Enter 儲存（{}/{} bytes）。{}",
+            ),
+            Self::SourceAndDb => ("Source: {} | DB: {}", "來源：{} | DB：{}"),
+            Self::TagsHint => (" Tags: {}", " 標籤：{}"),
+            Self::InputTooLarge => (
+                "Input exceeds MEMO_MAX_INPUT_BYTES",
+                "輸入超過 MEMO_MAX_INPUT_BYTES",
+            ),
+            Self::InputTooLargeDetail => (
+                "Current {} bytes, limit {} bytes.",
+                "目前 {} bytes，上限 {} bytes。",
+            ),
+            Self::SimilarMemoExists => ("Similar memo exists: {}", "已有相似備忘：{}"),
+            Self::Identical => ("Identical", "完全相同"),
+            Self::PercentSimilar => ("{}% similar", "相似度 {}%"),
+            Self::DuplicateDetail => (
+                "{}, saved {}: {} | Press Enter to manage",
+                "{}，儲存於 {}：{} | 按 Enter 管理",
+            ),
+            Self::TypeMemoText => ("Type memo text after keyword", "在關鍵字後輸入備忘內容"),
+            Self::MaxBytesAndSource => (
+                "Max {} bytes. Current source: {}.",
+                "上限 {} bytes，目前來源：{}。",
+            ),
+            Self::InitializeDatabase => ("Initialize memo database", "初始化備忘資料庫"),
+            Self::CreateDatabaseAt => ("Create/open SQLite at {}", "在 {} 建立或開啟 SQLite"),
+            Self::NoRecordsYet => ("No memo records yet", "尚無備忘紀錄"),
+            Self::NoRecordsInitHint => (
+                "Run `db-init`, then use `mm <text>` to add your first memo.",
+                "先執行 `db-init`，再用 `mm <text>` 新增第一則備忘。",
+            ),
+            Self::NoRecordsAddHint => (
+                "Use `mm <text>` then press Enter to add your first memo.",
+                "輸入 `mm <text>` 後按 Enter 新增第一則備忘。",
+            ),
+            Self::DatabasePath => ("Memo database path", "備忘資料庫路徑"),
+            Self::UsingDatabaseAt => ("Using SQLite at {} | {}", "使用 SQLite：{} | {}"),
+            Self::LastBackup => ("Last backup {}", "上次備份 {}"),
+            Self::NoBackupsYet => ("No backups yet", "尚無備份"),
+            Self::DueRow => ("Due {}: {}", "到期 {}：{}"),
+            Self::PinnedRow => ("Pinned {}: {}", "置頂 {}：{}"),
+            Self::RecentRow => ("Recent {}: {}", "最近 {}：{}"),
+            Self::SearchRow => ("Search {}: {}", "搜尋 {}：{}"),
+            Self::DidYouMeanRow => ("Did you mean {}: {}", "您是要找 {}：{}"),
+            Self::EmptyMemo => ("(empty memo)", "（空白備忘）"),
+            Self::ManageDetail => ("{} | {} | Press Enter to manage", "{} | {} | 按 Enter 管理"),
+            Self::ApproximateMatchManage => (
+                "{} | approximate match {}% | Press Enter to manage",
+                "{} | 近似符合 {}% | 按 Enter 管理",
+            ),
+            Self::SearchFieldsManage => (
+                "{} | fields {} | score {} | Press Enter to manage",
+                "{} | 欄位 {} | 分數 {} | 按 Enter 管理",
+            ),
+            Self::NotAvailable => ("n/a", "無"),
+            Self::TypeSearchText => ("Type search text after keyword", "在關鍵字後輸入搜尋文字"),
+            Self::NoMatchingRecords => ("No matching memo records", "沒有符合的備忘紀錄"),
+            Self::NoResultsFor => ("No results for: {}", "找不到結果：{}"),
+            Self::InvalidSyntax => ("Invalid {} syntax", "{} 語法無效"),
+            Self::InvalidItemId => ("Invalid item_id for {}", "{} 的 item_id 無效"),
+            Self::InvalidItemIdHint => (
+                "Expected itm_XXXXXXXX or positive integer item id.",
+                "請輸入 itm_XXXXXXXX 或正整數 item id。",
+            ),
+            Self::ItemNotFound => ("Memo item not found", "找不到備忘"),
+            Self::CopyMemo => ("Copy memo: {}", "複製備忘：{}"),
+            Self::PreviewJson => ("Preview JSON: {}", "預覽 JSON：{}"),
+            Self::CopyRichText => (
+                "Copy as rich text (Markdown rendered to HTML)",
+                "複製為格式化文字（Markdown 轉為 HTML）",
+            ),
+            Self::CopyHtmlSource => ("Copy rendered HTML source", "複製轉換後的 HTML 原始碼"),
+            Self::UpdateMemo => ("Update memo: {}", "更新備忘：{}"),
+            Self::DeleteMemo => ("Delete memo: {}", "刪除備忘：{}"),
+            Self::PinMemo => ("Pin memo: {}", "置頂備忘：{}"),
+            Self::PinHint => (
+                "Show above recent memos in the empty query view.",
+                "在空白查詢畫面中顯示於最近備忘之上。",
+            ),
+            Self::UnpinMemo => ("Unpin memo: {}", "取消置頂：{}"),
+            Self::UnpinHint => (
+                "Remove from the pinned section of the empty query view.",
+                "從空白查詢畫面的置頂區移除。",
+            ),
+            Self::EditInEditor => ("Edit in editor: {}", "在編輯器中編輯：{}"),
+            Self::EditHint => (
+                "Open with `{}`; saved changes update the memo.",
+                "以 `{}` 開啟，存檔後更新備忘。",
+            ),
+            Self::HistoryOne => ("History: {} earlier version", "歷史：{} 個較早版本"),
+            Self::HistoryMany => ("History: {} earlier versions", "歷史：{} 個較早版本"),
+            Self::HistoryHint => (
+                "Press Enter to browse and restore earlier texts of {}",
+                "按 Enter 瀏覽並還原 {} 的較早內容",
+            ),
+            Self::LinkedOne => ("Linked: {} memo", "關聯：{} 則備忘"),
+            Self::LinkedMany => ("Linked: {} memos", "關聯：{} 則備忘"),
+            Self::LinkedHint => (
+                "Press Enter to list memos linked to {}",
+                "按 Enter 列出與 {} 關聯的備忘",
+            ),
+            Self::OpenAttachment => ("Open attachment: {}", "開啟附件：{}"),
+            Self::AttachmentPosition => ("Attachment {}/{} | {}", "附件 {}/{} | {}"),
+            Self::OpenUrl => ("Open URL: {}", "開啟網址：{}"),
+            Self::LinkPosition => ("Link {}/{} | {}", "連結 {}/{} | {}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_parse_accepts_locale_aliases() {
+        assert_eq!(Language::parse("EN"), Some(Language::En));
+        assert_eq!(Language::parse(" zh-TW "), Some(Language::Zh));
+        assert_eq!(Language::parse("chinese"), Some(Language::Zh));
+        assert_eq!(Language::parse("fr"), None);
+    }
+
+    #[test]
+    fn text_fills_placeholders_in_order() {
+        assert_eq!(
+            Language::En.text(Msg::AttachmentPosition, &[&2, &3, &"a.txt"]),
+            "Attachment 2/3 | a.txt"
+        );
+        assert_eq!(
+            Language::Zh.text(Msg::RecentRow, &[&"#7", &"buy milk"]),
+            "最近 #7：buy milk"
+        );
+        assert_eq!(Language::Zh.text(Msg::NoBackupsYet, &[]), "尚無備份");
+    }
+}
@@ -2,13 +2,38 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand, ValueEnum};
 use memo_workflow_cli::{
-    ADD_TOKEN_PREFIX, AppError, COPY_JSON_TOKEN_PREFIX, COPY_TOKEN_PREFIX, DELETE_TOKEN_PREFIX,
-    ListResult, RuntimeConfig, SearchMatchMode, SearchResult, UPDATE_TOKEN_PREFIX,
-    build_script_filter, execute_add, execute_db_init, execute_delete, execute_fetch_item,
-    execute_list, execute_search, execute_update, parse_add_token, parse_copy_json_token,
-    parse_copy_token, parse_delete_token, parse_update_token,
+    ADD_TOKEN_PREFIX, ATTACH_TOKEN_PREFIX, AppError, AttachResult, AttachmentResult,
+    BATCH_TOKEN_PREFIX, BatchAction, BatchResult, CLIP_TOKEN_PREFIX, CLIPBOARD_SOURCE,
+    COPY_HTML_TOKEN_PREFIX, COPY_JSON_TOKEN_PREFIX, COPY_MD_TOKEN_PREFIX, COPY_TOKEN_PREFIX,
+    DEFAULT_STATS_WEEKS, DELETE_TOKEN_PREFIX, DeleteResult, DeriveResult, DueResult,
+    EDIT_TOKEN_PREFIX, EXPORT_TOKEN_PREFIX, EditResult, ExportFilter, ExportFormat, ExportResult,
+    HISTORY_RESTORE_TOKEN_PREFIX, HistoryRestoreResult, ImportResult, LINK_TOKEN_PREFIX,
+    LinkResult, LinkedItemResult, LinkedMemoResult, ListResult, MERGE_TOKEN_PREFIX, MergeResult,
+    OPEN_ATTACHMENT_TOKEN_PREFIX, OPEN_LINK_TOKEN_PREFIX, PIN_TOKEN_PREFIX, PURGE_TOKEN_PREFIX,
+    PinResult, PurgeResult, REMIND_TOKEN_PREFIX, RESTORE_TOKEN_PREFIX, RemindResult, RestoreResult,
+    RuntimeConfig, SYNC_TOKEN, SearchMatchMode, SearchResult, StatsResult, SyncResult,
+    TEMPLATE_ADD_TOKEN_PREFIX, TagCountResult, TemplateResult, TemplateSaveResult, TrashResult,
+    UNDO_TOKEN_PREFIX, UNPIN_TOKEN_PREFIX, UPDATE_TOKEN_PREFIX, UndoResult, VersionResult,
+    build_script_filter, execute_add, execute_add_with_tags, execute_attach, execute_attachments,
+    execute_batch, execute_clip, execute_db_backup, execute_db_decrypt, execute_db_encrypt,
+    execute_db_init, execute_db_restore, execute_delete, execute_derive, execute_due_list,
+    execute_edit, execute_export, execute_fetch_item, execute_history, execute_history_restore,
+    execute_import, execute_link, execute_linked, execute_links, execute_list, execute_merge,
+    execute_open_attachment, execute_open_link, execute_pin, execute_purge, execute_remind,
+    execute_render_item, execute_restore, execute_search, execute_stats, execute_sync,
+    execute_tag_counts, execute_tag_items, execute_template_list, execute_template_save,
+    execute_template_use, execute_trash_list, execute_undo, execute_unpin, execute_update,
+    parse_add_token, parse_attach_token, parse_batch_token, parse_clip_token,
+    parse_copy_html_token, parse_copy_json_token, parse_copy_md_token, parse_copy_token,
+    parse_delete_token, parse_edit_token, parse_export_token, parse_history_restore_token,
+    parse_link_token, parse_merge_token, parse_open_attachment_token, parse_open_link_token,
+    parse_pin_token, parse_purge_token, parse_remind_token, parse_restore_token, parse_tag_list,
+    parse_template_add_token, parse_undo_token, parse_unpin_token, parse_update_token,
 };
 use serde::Serialize;
+use workflow_common::{
+    EnvelopePayloadKind, build_error_details_json, build_error_envelope, build_success_envelope,
+};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about = "Memo workflow adapter CLI")]
@@ -30,6 +55,9 @@ enum Command {
         /// Memo text to append.
         #[arg(long)]
         text: String,
+        /// Extra tags to attach (comma separated), in addition to `#tags` in the text.
+        #[arg(long)]
+        tags: Option<String>,
         /// Override sqlite DB path for this call.
         #[arg(long)]
         db: Option<PathBuf>,
@@ -37,7 +65,7 @@ enum Command {
         #[arg(long)]
         source: Option<String>,
         /// Output format.
-        #[arg(long, value_enum, default_value_t = ResultMode::Text)]
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
     },
     /// Update one memo item.
@@ -52,10 +80,10 @@ enum Command {
         #[arg(long)]
         db: Option<PathBuf>,
         /// Output format.
-        #[arg(long, value_enum, default_value_t = ResultMode::Text)]
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
     },
-    /// Hard-delete one memo item.
+    /// Move one memo item to trash.
     Delete {
         /// Memo item identifier (itm_XXXXXXXX or integer id).
         #[arg(long)]
@@ -64,16 +92,379 @@ enum Command {
         #[arg(long)]
         db: Option<PathBuf>,
         /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Restore one memo item from trash.
+    Restore {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Permanently delete one memo item.
+    Purge {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Add the current clipboard text as one memo (source `clipboard`).
+    Clip {
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Revert the latest add, update, delete, or merge inside MEMO_UNDO_WINDOW_MINUTES.
+    Undo {
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Edit one memo item in MEMO_EDITOR (or $VISUAL / $EDITOR).
+    Edit {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Enrich one memo item with MEMO_DERIVE_COMMAND and store the output as an accepted derivation.
+    Derive {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Enrichment command overriding MEMO_DERIVE_COMMAND (memo text on stdin, JSON on stdout).
+        #[arg(long)]
+        command: Option<String>,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Attach a file path or URL to one memo item.
+    Attach {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Existing file path (stored canonicalized) or URL.
+        #[arg(long)]
+        path: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List attachments of one memo item.
+    Attachments {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List earlier texts of one memo item, newest first.
+    History {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Restore one earlier version as the current text of its memo item.
+    HistoryRestore {
+        /// Version identifier from `history`.
+        #[arg(long)]
+        version_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Link two memo items to each other.
+    Link {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Identifier of the memo item to link to.
+        #[arg(long)]
+        other_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Merge one memo item into another and move it to trash.
+    Merge {
+        /// Identifier of the memo item to keep.
+        #[arg(long)]
+        item_id: String,
+        /// Identifier of the memo item to merge; it is moved to trash.
+        #[arg(long)]
+        merge_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List memo items linked to one memo item.
+    Linked {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Set or clear the due date of one memo item.
+    Remind {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Due phrase (`tomorrow 9am`, `2026-03-01 18:00`, RFC 3339) or `clear`.
+        #[arg(long)]
+        at: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List overdue and due-today memo records, soonest first.
+    Due {
+        /// Include memos due after today.
+        #[arg(long)]
+        all: bool,
+        /// Max rows to return.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Pin one memo item above recent rows.
+    Pin {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Unpin one memo item.
+    Unpin {
+        /// Memo item identifier (itm_XXXXXXXX or integer id).
+        #[arg(long)]
+        item_id: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List trashed memo records, most recently deleted first.
+    Trash {
+        /// Max rows to return.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Row offset for paging.
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Export memos to a JSON, Markdown, or CSV file.
+    Export {
+        /// Export file format.
+        #[arg(long, value_enum, default_value_t = ExportFormatArg::Json)]
+        format: ExportFormatArg,
+        /// Destination file (default: timestamped file under MEMO_EXPORT_DIR).
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Only export memos carrying this tag.
+        #[arg(long)]
+        tag: Option<String>,
+        /// Include trashed memos.
+        #[arg(long)]
+        include_trash: bool,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format (`--output` is the destination file here, so there is no alias).
         #[arg(long, value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
     },
+    /// Import memos from a JSON, Markdown, or CSV export, skipping duplicates.
+    Import {
+        /// Source file to import.
+        #[arg(long)]
+        input: PathBuf,
+        /// Import file format (default: inferred from the file extension).
+        #[arg(long, value_enum)]
+        format: Option<ExportFormatArg>,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
     /// Initialize memo sqlite schema.
     DbInit {
         /// Override sqlite DB path for this call.
         #[arg(long)]
         db: Option<PathBuf>,
         /// Output format.
-        #[arg(long, value_enum, default_value_t = ResultMode::Text)]
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Encrypt memo text at rest with the keychain passphrase.
+    DbEncrypt {
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Decrypt memo text back to plaintext storage.
+    DbDecrypt {
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Write a snapshot of the memo database and verify it with `PRAGMA integrity_check`.
+    DbBackup {
+        /// Snapshot directory; defaults to `backups/` next to the DB.
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Replace the memo database with a snapshot (the current DB is snapshotted first).
+    DbRestore {
+        /// Snapshot path, or a file name inside `backups/` next to the DB.
+        #[arg(long)]
+        snapshot: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Apply one action to several memos at once (explicit ids and/or every memo with a tag).
+    Batch {
+        /// Action to apply to every selected memo.
+        #[arg(long, value_enum)]
+        action: BatchActionArg,
+        /// Memo ids to include (repeatable or comma separated).
+        #[arg(long = "item-id", value_delimiter = ',')]
+        item_ids: Vec<String>,
+        /// Include every memo with this tag (repeatable).
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List live memos whose text contains links, newest first.
+    Links {
+        /// Max rows to return.
+        #[arg(long, default_value_t = 8)]
+        limit: usize,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Show memo totals, per-source and per-week counts, top tags, and DB size.
+    Stats {
+        /// Number of recent weeks to count (1..=52).
+        #[arg(long, default_value_t = DEFAULT_STATS_WEEKS)]
+        weeks: usize,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Sync memos through a git repository (export, commit, pull, re-import, push).
+    Sync {
+        /// Git work tree to sync through; defaults to `MEMO_SYNC_DIR`.
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
     },
     /// List memo records in newest-first order.
@@ -88,7 +479,7 @@ enum Command {
         #[arg(long)]
         db: Option<PathBuf>,
         /// Output format.
-        #[arg(long, value_enum, default_value_t = ResultMode::Text)]
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
     },
     /// Search memo records by query text.
@@ -109,16 +500,39 @@ enum Command {
         #[arg(long)]
         db: Option<PathBuf>,
         /// Output format.
-        #[arg(long, value_enum, default_value_t = ResultMode::Text)]
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List memos for one tag, or tag counts when no tag is given.
+    Tag {
+        /// Tag name (with or without leading `#`).
+        #[arg(long)]
+        name: Option<String>,
+        /// Max rows to return.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Row offset for paging (memo rows only).
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
     },
+    /// Save, list, or expand memo templates.
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommand,
+    },
     /// Execute one Alfred action token.
     Action {
         /// Action token generated by script-filter.
         #[arg(long)]
         token: String,
         /// Output format.
-        #[arg(long, value_enum, default_value_t = ResultMode::Text)]
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
         mode: ResultMode,
         /// Override sqlite DB path for this call.
         #[arg(long)]
@@ -129,6 +543,46 @@ enum Command {
     },
 }
 
+#[derive(Debug, Subcommand)]
+enum TemplateCommand {
+    /// Save one template, replacing any template with the same name.
+    Add {
+        /// Template name (letters, digits, `-`, `_`).
+        #[arg(long)]
+        name: String,
+        /// Template text; may contain `{date}`, `{time}`, `{datetime}`, `{weekday}`, `{clipboard}`.
+        #[arg(long)]
+        text: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// List saved templates by name.
+    List {
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+    /// Print one template with its placeholders expanded.
+    Use {
+        /// Template name.
+        #[arg(long)]
+        name: String,
+        /// Override sqlite DB path for this call.
+        #[arg(long)]
+        db: Option<PathBuf>,
+        /// Output format.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t = ResultMode::Text)]
+        mode: ResultMode,
+    },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 enum ResultMode {
@@ -144,6 +598,47 @@ enum SearchMatch {
     Contains,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+enum ExportFormatArg {
+    Json,
+    #[value(alias = "md")]
+    Markdown,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+enum BatchActionArg {
+    Delete,
+    Restore,
+    Purge,
+    Pin,
+    Unpin,
+}
+
+impl From<BatchActionArg> for BatchAction {
+    fn from(value: BatchActionArg) -> Self {
+        match value {
+            BatchActionArg::Delete => BatchAction::Delete,
+            BatchActionArg::Restore => BatchAction::Restore,
+            BatchActionArg::Purge => BatchAction::Purge,
+            BatchActionArg::Pin => BatchAction::Pin,
+            BatchActionArg::Unpin => BatchAction::Unpin,
+        }
+    }
+}
+
+impl From<ExportFormatArg> for ExportFormat {
+    fn from(value: ExportFormatArg) -> Self {
+        match value {
+            ExportFormatArg::Json => ExportFormat::Json,
+            ExportFormatArg::Markdown => ExportFormat::Markdown,
+            ExportFormatArg::Csv => ExportFormat::Csv,
+        }
+    }
+}
+
 impl From<SearchMatch> for SearchMatchMode {
     fn from(value: SearchMatch) -> Self {
         match value {
@@ -154,17 +649,6 @@ impl From<SearchMatch> for SearchMatchMode {
     }
 }
 
-#[derive(Debug, Serialize)]
-struct JsonEnvelope<T> {
-    schema_version: &'static str,
-    command: &'static str,
-    ok: bool,
-    result: Option<T>,
-    error: Option<String>,
-}
-
-const ENVELOPE_SCHEMA_VERSION: &str = "cli-envelope@v1";
-
 const ERROR_CODE_USER_INVALID_INPUT: &str = "NILS_MEMO_001";
 const ERROR_CODE_RUNTIME_FAILURE: &str = "NILS_MEMO_002";
 
@@ -175,10 +659,41 @@ fn error_code(error: &AppError) -> &'static str {
     }
 }
 
+fn error_kind(error: &AppError) -> &'static str {
+    match error {
+        AppError::User(_) => "user",
+        AppError::Runtime(_) => "runtime",
+    }
+}
+
+impl Command {
+    /// Envelope command name when this call reports failures as a JSON error envelope.
+    fn json_error_command(&self) -> Option<&'static str> {
+        let (command, mode) = match self {
+            Command::Add { mode, .. } => ("memo.add", mode),
+            Command::Update { mode, .. } => ("memo.update", mode),
+            Command::Delete { mode, .. } => ("memo.delete", mode),
+            Command::List { mode, .. } => ("memo.list", mode),
+            Command::Search { mode, .. } => ("memo.search", mode),
+            Command::DbInit { mode, .. } => ("memo.db_init", mode),
+            _ => return None,
+        };
+        (*mode == ResultMode::Json).then_some(command)
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
+    let json_error_command = cli.command.json_error_command();
 
     if let Err(error) = run(cli) {
+        if let Some(command) = json_error_command {
+            let details = build_error_details_json(error_kind(&error), error.exit_code());
+            println!(
+                "{}",
+                build_error_envelope(command, error_code(&error), error.message(), Some(&details))
+            );
+        }
         eprintln!("error[{}]: {}", error_code(&error), error.message());
         std::process::exit(error.exit_code());
     }
@@ -195,53 +710,248 @@ fn run(cli: Cli) -> Result<(), AppError> {
             })?;
             println!("{json}");
         }
-        Command::Add {
-            text,
+        Command::Add {
+            text,
+            tags,
+            db,
+            source,
+            mode,
+        } => {
+            let explicit_tags = match tags.as_deref() {
+                Some(raw) => parse_tag_list(raw)?,
+                None => Vec::new(),
+            };
+            let result =
+                execute_add_with_tags(&text, &explicit_tags, source.as_deref(), db, &config)?;
+            emit(mode, "memo.add", result, |res| {
+                format!("added {} at {}", res.item_id, res.created_at)
+            })?;
+        }
+        Command::Update {
+            item_id,
+            text,
+            db,
+            mode,
+        } => {
+            let result = execute_update(&item_id, &text, db, &config)?;
+            emit(mode, "memo.update", result, |res| {
+                format!(
+                    "updated {} at {} (state={}, cleared_derivations={}, cleared_workflows={})",
+                    res.item_id,
+                    res.updated_at,
+                    res.state,
+                    res.cleared_derivations,
+                    res.cleared_workflow_anchors
+                )
+            })?;
+        }
+        Command::Delete { item_id, db, mode } => {
+            let result = execute_delete(&item_id, db, &config)?;
+            emit(mode, "memo.delete", result, render_delete_text)?;
+        }
+        Command::Restore { item_id, db, mode } => {
+            let result = execute_restore(&item_id, db, &config)?;
+            emit(mode, "memo.restore", result, render_restore_text)?;
+        }
+        Command::Purge { item_id, db, mode } => {
+            let result = execute_purge(&item_id, db, &config)?;
+            emit(mode, "memo.purge", result, render_purge_text)?;
+        }
+        Command::Clip { db, mode } => {
+            let result = execute_clip(db, &config)?;
+            emit(mode, "memo.clip", result, |res| {
+                format!("added {} at {}", res.item_id, res.created_at)
+            })?;
+        }
+        Command::Undo { db, mode } => {
+            let result = execute_undo(None, db, &config)?;
+            emit(mode, "memo.undo", result, render_undo_text)?;
+        }
+        Command::Edit { item_id, db, mode } => {
+            let result = execute_edit(&item_id, db, &config)?;
+            emit(mode, "memo.edit", result, render_edit_text)?;
+        }
+        Command::Derive {
+            item_id,
+            command,
+            db,
+            mode,
+        } => {
+            let result = execute_derive(&item_id, command, db, &config)?;
+            emit(mode, "memo.derive", result, render_derive_text)?;
+        }
+        Command::Attach {
+            item_id,
+            path,
+            db,
+            mode,
+        } => {
+            let result = execute_attach(&item_id, &path, db, &config)?;
+            emit(mode, "memo.attach", result, render_attach_text)?;
+        }
+        Command::Attachments { item_id, db, mode } => {
+            let result = execute_attachments(&item_id, db, &config)?;
+            emit(mode, "memo.attachments", result, render_attachments_text)?;
+        }
+        Command::History { item_id, db, mode } => {
+            let result = execute_history(&item_id, db, &config)?;
+            emit(mode, "memo.history", result, render_history_text)?;
+        }
+        Command::HistoryRestore {
+            version_id,
+            db,
+            mode,
+        } => {
+            let result = execute_history_restore(&version_id, db, &config)?;
+            emit(
+                mode,
+                "memo.history-restore",
+                result,
+                render_history_restore_text,
+            )?;
+        }
+        Command::Link {
+            item_id,
+            other_id,
+            db,
+            mode,
+        } => {
+            let result = execute_link(&item_id, &other_id, db, &config)?;
+            emit(mode, "memo.link", result, render_link_text)?;
+        }
+        Command::Merge {
+            item_id,
+            merge_id,
+            db,
+            mode,
+        } => {
+            let result = execute_merge(&item_id, &merge_id, db, &config)?;
+            emit(mode, "memo.merge", result, render_merge_text)?;
+        }
+        Command::Linked { item_id, db, mode } => {
+            let result = execute_linked(&item_id, db, &config)?;
+            emit(mode, "memo.linked", result, render_linked_text)?;
+        }
+        Command::Remind {
+            item_id,
+            at,
+            db,
+            mode,
+        } => {
+            let result = execute_remind(&item_id, &at, db, &config)?;
+            emit(mode, "memo.remind", result, render_remind_text)?;
+        }
+        Command::Due {
+            all,
+            limit,
+            db,
+            mode,
+        } => {
+            let result = execute_due_list(db, all, limit, &config)?;
+            emit(mode, "memo.due", result, render_due_text)?;
+        }
+        Command::Pin { item_id, db, mode } => {
+            let result = execute_pin(&item_id, db, &config)?;
+            emit(mode, "memo.pin", result, render_pin_text)?;
+        }
+        Command::Unpin { item_id, db, mode } => {
+            let result = execute_unpin(&item_id, db, &config)?;
+            emit(mode, "memo.unpin", result, render_pin_text)?;
+        }
+        Command::Trash {
+            limit,
+            offset,
+            db,
+            mode,
+        } => {
+            let result = execute_trash_list(db, limit, offset, &config)?;
+            emit(mode, "memo.trash", result, render_trash_text)?;
+        }
+        Command::Export {
+            format,
+            output,
+            tag,
+            include_trash,
             db,
-            source,
             mode,
         } => {
-            let result = execute_add(&text, source.as_deref(), db, &config)?;
-            emit(mode, "memo.add", result, |res| {
-                format!("added {} at {}", res.item_id, res.created_at)
-            })?;
+            let filter = ExportFilter { tag, include_trash };
+            let result = execute_export(format.into(), output, &filter, db, &config)?;
+            emit(mode, "memo.export", result, render_export_text)?;
         }
-        Command::Update {
-            item_id,
-            text,
+        Command::Import {
+            input,
+            format,
             db,
             mode,
         } => {
-            let result = execute_update(&item_id, &text, db, &config)?;
-            emit(mode, "memo.update", result, |res| {
-                format!(
-                    "updated {} at {} (state={}, cleared_derivations={}, cleared_workflows={})",
-                    res.item_id,
-                    res.updated_at,
-                    res.state,
-                    res.cleared_derivations,
-                    res.cleared_workflow_anchors
-                )
+            let result = execute_import(&input, format.map(Into::into), db, &config)?;
+            emit(mode, "memo.import", result, render_import_text)?;
+        }
+        Command::DbInit { db, mode } => {
+            let result = execute_db_init(db, &config)?;
+            emit(mode, "memo.db_init", result, |res| {
+                format!("initialized {}", res.db_path)
             })?;
         }
-        Command::Delete { item_id, db, mode } => {
-            let result = execute_delete(&item_id, db, &config)?;
-            emit(mode, "memo.delete", result, |res| {
+        Command::DbEncrypt { db, mode } => {
+            let result = execute_db_encrypt(db, &config)?;
+            emit(mode, "memo.db_encrypt", result, |res| {
+                format!("encrypted {} memos in {}", res.items, res.db_path)
+            })?;
+        }
+        Command::DbDecrypt { db, mode } => {
+            let result = execute_db_decrypt(db, &config)?;
+            emit(mode, "memo.db_decrypt", result, |res| {
+                format!("decrypted {} memos in {}", res.items, res.db_path)
+            })?;
+        }
+        Command::DbBackup { dir, db, mode } => {
+            let result = execute_db_backup(db, dir, &config)?;
+            emit(mode, "memo.db_backup", result, |res| {
                 format!(
-                    "deleted {} at {} (removed_derivations={}, removed_workflows={})",
-                    res.item_id,
-                    res.deleted_at,
-                    res.removed_derivations,
-                    res.removed_workflow_anchors
+                    "backed up {} to {} ({} bytes)",
+                    res.db_path, res.snapshot, res.size_bytes
                 )
             })?;
         }
-        Command::DbInit { db, mode } => {
-            let result = execute_db_init(db, &config)?;
-            emit(mode, "memo.db_init", result, |res| {
-                format!("initialized {}", res.db_path)
+        Command::DbRestore { snapshot, db, mode } => {
+            let result = execute_db_restore(&snapshot, db, &config)?;
+            emit(mode, "memo.db_restore", result, |res| {
+                match &res.safety_backup {
+                    Some(safety_backup) => format!(
+                        "restored {} from {} ({} memos; previous DB saved to {})",
+                        res.db_path, res.snapshot, res.items, safety_backup
+                    ),
+                    None => format!(
+                        "restored {} from {} ({} memos)",
+                        res.db_path, res.snapshot, res.items
+                    ),
+                }
             })?;
         }
+        Command::Batch {
+            action,
+            item_ids,
+            tags,
+            db,
+            mode,
+        } => {
+            let result = execute_batch(action.into(), &item_ids, &tags, db, &config)?;
+            emit(mode, "memo.batch", result, render_batch_text)?;
+        }
+        Command::Links { limit, db, mode } => {
+            let result = execute_links(db, limit, &config)?;
+            emit(mode, "memo.links", result, render_links_text)?;
+        }
+        Command::Stats { weeks, db, mode } => {
+            let result = execute_stats(db, weeks, &config)?;
+            emit(mode, "memo.stats", result, render_stats_text)?;
+        }
+        Command::Sync { repo, db, mode } => {
+            let result = execute_sync(repo, db, &config)?;
+            emit(mode, "memo.sync", result, render_sync_text)?;
+        }
         Command::List {
             limit,
             offset,
@@ -262,6 +972,46 @@ fn run(cli: Cli) -> Result<(), AppError> {
             let result = execute_search(db, &query, match_mode.into(), limit, offset, &config)?;
             emit(mode, "memo.search", result, render_search_text)?;
         }
+        Command::Tag {
+            name,
+            limit,
+            offset,
+            db,
+            mode,
+        } => match name {
+            Some(name) => {
+                let result = execute_tag_items(db, &name, limit, offset, &config)?;
+                emit(mode, "memo.tag", result, render_list_text)?;
+            }
+            None => {
+                let result = execute_tag_counts(db, limit, &config)?;
+                emit(mode, "memo.tag", result, render_tag_counts_text)?;
+            }
+        },
+        Command::Template { command } => match command {
+            TemplateCommand::Add {
+                name,
+                text,
+                db,
+                mode,
+            } => {
+                let result = execute_template_save(&name, &text, db, &config)?;
+                emit(mode, "memo.template.add", result, render_template_save_text)?;
+            }
+            TemplateCommand::List { db, mode } => {
+                let result = execute_template_list(db, &config)?;
+                emit(
+                    mode,
+                    "memo.template.list",
+                    result,
+                    render_template_list_text,
+                )?;
+            }
+            TemplateCommand::Use { name, db, mode } => {
+                let result = execute_template_use(&name, db, &config)?;
+                emit(mode, "memo.template.use", result, |res| res.text.clone())?;
+            }
+        },
         Command::Action {
             token,
             mode,
@@ -276,6 +1026,20 @@ fn run(cli: Cli) -> Result<(), AppError> {
                 return Ok(());
             }
 
+            if token == SYNC_TOKEN {
+                let result = execute_sync(None, db, &config)?;
+                emit(mode, "memo.action", result, render_sync_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(BATCH_TOKEN_PREFIX) {
+                let (action, item_ids) = parse_batch_token(&token)
+                    .ok_or_else(|| AppError::User("invalid batch action token".to_string()))?;
+                let result = execute_batch(action, &item_ids, &[], db, &config)?;
+                emit(mode, "memo.action", result, render_batch_text)?;
+                return Ok(());
+            }
+
             if token.starts_with(COPY_JSON_TOKEN_PREFIX) {
                 let item_id = parse_copy_json_token(&token)
                     .ok_or_else(|| AppError::User("invalid copy-json action token".to_string()))?;
@@ -284,6 +1048,18 @@ fn run(cli: Cli) -> Result<(), AppError> {
                 return Ok(());
             }
 
+            if token.starts_with(COPY_MD_TOKEN_PREFIX) || token.starts_with(COPY_HTML_TOKEN_PREFIX)
+            {
+                let item_id = parse_copy_md_token(&token)
+                    .or_else(|| parse_copy_html_token(&token))
+                    .ok_or_else(|| {
+                        AppError::User("invalid copy-md/copy-html action token".to_string())
+                    })?;
+                let result = execute_render_item(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, |res| res.html.clone())?;
+                return Ok(());
+            }
+
             if token.starts_with(COPY_TOKEN_PREFIX) {
                 let item_id = parse_copy_token(&token)
                     .ok_or_else(|| AppError::User("invalid copy action token".to_string()))?;
@@ -313,15 +1089,143 @@ fn run(cli: Cli) -> Result<(), AppError> {
                 let item_id = parse_delete_token(&token)
                     .ok_or_else(|| AppError::User("invalid delete action token".to_string()))?;
                 let result = execute_delete(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, render_delete_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(CLIP_TOKEN_PREFIX) {
+                let text = parse_clip_token(&token).unwrap_or_default();
+                let result = execute_add(&text, Some(CLIPBOARD_SOURCE), db, &config)?;
                 emit(mode, "memo.action", result, |res| {
-                    format!(
-                        "deleted {} at {} (removed_derivations={}, removed_workflows={})",
-                        res.item_id,
-                        res.deleted_at,
-                        res.removed_derivations,
-                        res.removed_workflow_anchors
-                    )
+                    format!("added {} at {}", res.item_id, res.created_at)
+                })?;
+                return Ok(());
+            }
+
+            if token.starts_with(UNDO_TOKEN_PREFIX) {
+                let entry_id = parse_undo_token(&token)
+                    .ok_or_else(|| AppError::User("invalid undo action token".to_string()))?;
+                let result = execute_undo(Some(entry_id), db, &config)?;
+                emit(mode, "memo.action", result, render_undo_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(RESTORE_TOKEN_PREFIX) {
+                let item_id = parse_restore_token(&token)
+                    .ok_or_else(|| AppError::User("invalid restore action token".to_string()))?;
+                let result = execute_restore(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, render_restore_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(PURGE_TOKEN_PREFIX) {
+                let item_id = parse_purge_token(&token)
+                    .ok_or_else(|| AppError::User("invalid purge action token".to_string()))?;
+                let result = execute_purge(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, render_purge_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(EDIT_TOKEN_PREFIX) {
+                let item_id = parse_edit_token(&token)
+                    .ok_or_else(|| AppError::User("invalid edit action token".to_string()))?;
+                let result = execute_edit(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, render_edit_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(PIN_TOKEN_PREFIX) {
+                let item_id = parse_pin_token(&token)
+                    .ok_or_else(|| AppError::User("invalid pin action token".to_string()))?;
+                let result = execute_pin(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, render_pin_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(UNPIN_TOKEN_PREFIX) {
+                let item_id = parse_unpin_token(&token)
+                    .ok_or_else(|| AppError::User("invalid unpin action token".to_string()))?;
+                let result = execute_unpin(&item_id, db, &config)?;
+                emit(mode, "memo.action", result, render_pin_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(REMIND_TOKEN_PREFIX) {
+                let (item_id, due) = parse_remind_token(&token)
+                    .ok_or_else(|| AppError::User("invalid remind action token".to_string()))?;
+                let result = execute_remind(&item_id, &due, db, &config)?;
+                emit(mode, "memo.action", result, render_remind_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(ATTACH_TOKEN_PREFIX) {
+                let (item_id, path) = parse_attach_token(&token)
+                    .ok_or_else(|| AppError::User("invalid attach action token".to_string()))?;
+                let result = execute_attach(&item_id, &path, db, &config)?;
+                emit(mode, "memo.action", result, render_attach_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(HISTORY_RESTORE_TOKEN_PREFIX) {
+                let version_id = parse_history_restore_token(&token).ok_or_else(|| {
+                    AppError::User("invalid history-restore action token".to_string())
+                })?;
+                let result = execute_history_restore(&version_id, db, &config)?;
+                emit(mode, "memo.action", result, render_history_restore_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(LINK_TOKEN_PREFIX) {
+                let (item_id, other_id) = parse_link_token(&token)
+                    .ok_or_else(|| AppError::User("invalid link action token".to_string()))?;
+                let result = execute_link(&item_id, &other_id, db, &config)?;
+                emit(mode, "memo.action", result, render_link_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(TEMPLATE_ADD_TOKEN_PREFIX) {
+                let (name, text) = parse_template_add_token(&token).ok_or_else(|| {
+                    AppError::User("invalid template-add action token".to_string())
+                })?;
+                let result = execute_template_save(&name, &text, db, &config)?;
+                emit(mode, "memo.action", result, render_template_save_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(MERGE_TOKEN_PREFIX) {
+                let (item_id, merge_id) = parse_merge_token(&token)
+                    .ok_or_else(|| AppError::User("invalid merge action token".to_string()))?;
+                let result = execute_merge(&item_id, &merge_id, db, &config)?;
+                emit(mode, "memo.action", result, render_merge_text)?;
+                return Ok(());
+            }
+
+            if token.starts_with(OPEN_LINK_TOKEN_PREFIX) {
+                let (item_id, index) = parse_open_link_token(&token)
+                    .ok_or_else(|| AppError::User("invalid open-link action token".to_string()))?;
+                let result = execute_open_link(&item_id, index, db, &config)?;
+                emit(mode, "memo.action", result, |res| res.url.clone())?;
+                return Ok(());
+            }
+
+            if token.starts_with(OPEN_ATTACHMENT_TOKEN_PREFIX) {
+                let attachment_id = parse_open_attachment_token(&token).ok_or_else(|| {
+                    AppError::User("invalid open-attachment action token".to_string())
                 })?;
+                let result = execute_open_attachment(&attachment_id, db, &config)?;
+                emit(mode, "memo.action", result, |res| res.location.clone())?;
+                return Ok(());
+            }
+
+            if token.starts_with(EXPORT_TOKEN_PREFIX) {
+                let (format, tag) = parse_export_token(&token)
+                    .ok_or_else(|| AppError::User("invalid export action token".to_string()))?;
+                let filter = ExportFilter {
+                    tag,
+                    include_trash: false,
+                };
+                let result = execute_export(format, None, &filter, db, &config)?;
+                emit(mode, "memo.action", result, render_export_text)?;
                 return Ok(());
             }
 
@@ -341,6 +1245,212 @@ fn run(cli: Cli) -> Result<(), AppError> {
     Ok(())
 }
 
+fn render_delete_text(res: &DeleteResult) -> String {
+    match &res.purge_after {
+        Some(purge_after) => format!(
+            "deleted {} at {} (moved to trash, auto-purge after {})",
+            res.item_id, res.deleted_at, purge_after
+        ),
+        None => format!(
+            "deleted {} at {} (moved to trash)",
+            res.item_id, res.deleted_at
+        ),
+    }
+}
+
+fn render_restore_text(res: &RestoreResult) -> String {
+    format!("restored {} at {}", res.item_id, res.restored_at)
+}
+
+fn render_purge_text(res: &PurgeResult) -> String {
+    format!(
+        "purged {} at {} (removed_derivations={}, removed_workflows={})",
+        res.item_id, res.purged_at, res.removed_derivations, res.removed_workflow_anchors
+    )
+}
+
+fn render_undo_text(res: &UndoResult) -> String {
+    format!(
+        "undid {} of {} ({}) at {}",
+        res.operation, res.item_id, res.state, res.undone_at
+    )
+}
+
+fn render_edit_text(res: &EditResult) -> String {
+    match &res.updated_at {
+        Some(updated_at) => format!("edited {} at {}", res.item_id, updated_at),
+        None => format!("unchanged {}", res.item_id),
+    }
+}
+
+fn render_derive_text(res: &DeriveResult) -> String {
+    let verb = if res.changed { "derived" } else { "unchanged" };
+    format!(
+        "{verb} {} v{} ({})",
+        res.item_id, res.derivation_version, res.state
+    )
+}
+
+fn render_remind_text(res: &RemindResult) -> String {
+    match &res.due_at {
+        Some(due_at) => format!(
+            "reminder set {} at {} ({})",
+            res.item_id,
+            due_at,
+            res.status.as_deref().unwrap_or("upcoming")
+        ),
+        None if res.changed => format!("reminder cleared {}", res.item_id),
+        None => format!("no reminder {}", res.item_id),
+    }
+}
+
+fn render_due_text(rows: &Vec<DueResult>) -> String {
+    if rows.is_empty() {
+        return "no due memos".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!(
+            "{} due {} [{}] {}",
+            row.item_id, row.due_at, row.status, row.text_preview
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_attach_text(res: &AttachResult) -> String {
+    let verb = if res.changed {
+        "attached"
+    } else {
+        "already attached"
+    };
+    format!(
+        "{verb} {} to {} (attachments={})",
+        res.location, res.item_id, res.attachment_count
+    )
+}
+
+fn render_attachments_text(rows: &Vec<AttachmentResult>) -> String {
+    if rows.is_empty() {
+        return "no attachments".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!(
+            "{} [{}] {}",
+            row.attachment_id, row.kind, row.location
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_history_text(rows: &Vec<VersionResult>) -> String {
+    if rows.is_empty() {
+        return "no earlier versions".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!(
+            "{} v{} {} {}",
+            row.version_id, row.version, row.replaced_at, row.text
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_history_restore_text(res: &HistoryRestoreResult) -> String {
+    match &res.updated_at {
+        Some(updated_at) => format!(
+            "restored {} from version {} at {updated_at}",
+            res.item_id, res.version_id
+        ),
+        None => format!(
+            "unchanged {} (version {} matches current text)",
+            res.item_id, res.version_id
+        ),
+    }
+}
+
+fn render_link_text(res: &LinkResult) -> String {
+    let verb = if res.changed {
+        "linked"
+    } else {
+        "already linked"
+    };
+    format!(
+        "{verb} {} <-> {} (linked={})",
+        res.item_id, res.linked_item_id, res.linked_count
+    )
+}
+
+fn render_merge_text(res: &MergeResult) -> String {
+    format!(
+        "merged {} into {} at {} (attachments_added={}, links_added={}, moved to trash)",
+        res.merged_item_id, res.item_id, res.merged_at, res.attachments_added, res.links_added
+    )
+}
+
+fn render_linked_text(rows: &Vec<LinkedItemResult>) -> String {
+    if rows.is_empty() {
+        return "no linked memos".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!(
+            "{} {} {}",
+            row.item_id, row.linked_at, row.text_preview
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_pin_text(res: &PinResult) -> String {
+    let verb = if res.is_pinned { "pinned" } else { "unpinned" };
+    if res.changed {
+        format!("{verb} {}", res.item_id)
+    } else {
+        format!("already {verb} {}", res.item_id)
+    }
+}
+
+fn render_export_text(res: &ExportResult) -> String {
+    format!(
+        "exported {} memos to {} ({})",
+        res.exported, res.path, res.format
+    )
+}
+
+fn render_import_text(res: &ImportResult) -> String {
+    format!(
+        "imported {} memos from {} (skipped={}, invalid={})",
+        res.imported, res.path, res.skipped, res.invalid
+    )
+}
+
+fn render_trash_text(rows: &Vec<TrashResult>) -> String {
+    if rows.is_empty() {
+        return "trash is empty".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!(
+            "{} deleted {} {}",
+            row.item_id, row.deleted_at, row.text_preview
+        ));
+    }
+
+    lines.join("\n")
+}
+
 fn render_list_text(rows: &Vec<ListResult>) -> String {
     if rows.is_empty() {
         return "no memo records".to_string();
@@ -357,6 +1467,56 @@ fn render_list_text(rows: &Vec<ListResult>) -> String {
     lines.join("\n")
 }
 
+fn render_links_text(rows: &Vec<LinkedMemoResult>) -> String {
+    if rows.is_empty() {
+        return "no memos with links".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!(
+            "{} {} {}",
+            row.item_id,
+            row.created_at,
+            row.links.join(" ")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_template_save_text(res: &TemplateSaveResult) -> String {
+    let verb = if res.created { "saved" } else { "replaced" };
+    format!("{verb} template {}", res.name)
+}
+
+fn render_template_list_text(rows: &Vec<TemplateResult>) -> String {
+    if rows.is_empty() {
+        return "no templates".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        let preview = row.body.split_whitespace().collect::<Vec<_>>().join(" ");
+        lines.push(format!("{} {}", row.name, preview));
+    }
+
+    lines.join("\n")
+}
+
+fn render_tag_counts_text(rows: &Vec<TagCountResult>) -> String {
+    if rows.is_empty() {
+        return "no tags".to_string();
+    }
+
+    let mut lines = Vec::with_capacity(rows.len());
+    for row in rows {
+        lines.push(format!("#{} {}", row.tag, row.item_count));
+    }
+
+    lines.join("\n")
+}
+
 fn render_search_text(rows: &Vec<SearchResult>) -> String {
     if rows.is_empty() {
         return "no search matches".to_string();
@@ -364,8 +1524,13 @@ fn render_search_text(rows: &Vec<SearchResult>) -> String {
 
     let mut lines = Vec::with_capacity(rows.len());
     for row in rows {
+        let score_label = if row.approximate {
+            "approximate"
+        } else {
+            "score"
+        };
         lines.push(format!(
-            "{} {} [score={:.3}] {}",
+            "{} {} [{score_label}={:.3}] {}",
             row.item_id, row.created_at, row.score, row.text_preview
         ));
     }
@@ -380,6 +1545,80 @@ where
     serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string())
 }
 
+fn render_stats_text(result: &StatsResult) -> String {
+    let join = |rows: Vec<String>| {
+        if rows.is_empty() {
+            "none".to_string()
+        } else {
+            rows.join(", ")
+        }
+    };
+    [
+        format!(
+            "memos: {} live, {} trashed, {} pinned ({} total)",
+            result.live, result.trashed, result.pinned, result.total
+        ),
+        format!("db: {} ({} bytes)", result.db_path, result.db_size_bytes),
+        format!(
+            "sources: {}",
+            join(
+                result
+                    .sources
+                    .iter()
+                    .map(|row| format!("{} {}", row.source, row.item_count))
+                    .collect()
+            )
+        ),
+        format!(
+            "weeks: {}",
+            join(
+                result
+                    .weeks
+                    .iter()
+                    .map(|row| format!("{} {}", row.week_start, row.item_count))
+                    .collect()
+            )
+        ),
+        format!(
+            "tags: {}",
+            join(
+                result
+                    .tags
+                    .iter()
+                    .map(|row| format!("#{} {}", row.tag, row.item_count))
+                    .collect()
+            )
+        ),
+    ]
+    .join("\n")
+}
+
+fn render_batch_text(result: &BatchResult) -> String {
+    let mut lines = vec![format!(
+        "batch {}: {} succeeded, {} failed",
+        result.action, result.succeeded, result.failed
+    )];
+    lines.extend(result.items.iter().map(|item| match &item.error {
+        Some(error) => format!("{} skipped: {}", item.item_id, error),
+        None => format!("{} ok", item.item_id),
+    }));
+    lines.join("\n")
+}
+
+fn render_sync_text(result: &SyncResult) -> String {
+    format!(
+        "synced {}: wrote {}, removed {}, created {}, updated {}, purged {}, conflicts {}{}",
+        result.repo,
+        result.written,
+        result.removed,
+        result.created,
+        result.updated,
+        result.purged,
+        result.conflicts,
+        if result.pushed { ", pushed" } else { "" }
+    )
+}
+
 fn emit<T, F>(
     mode: ResultMode,
     command: &'static str,
@@ -393,16 +1632,12 @@ where
     match mode {
         ResultMode::Text => println!("{}", text_renderer(&result)),
         ResultMode::Json => {
-            let payload = JsonEnvelope {
-                schema_version: ENVELOPE_SCHEMA_VERSION,
-                command,
-                ok: true,
-                result: Some(result),
-                error: None,
-            };
-            let json = serde_json::to_string(&payload)
+            let json = serde_json::to_string(&result)
                 .map_err(|error| AppError::Runtime(format!("failed to serialize json: {error}")))?;
-            println!("{json}");
+            println!(
+                "{}",
+                build_success_envelope(command, EnvelopePayloadKind::Result, &json)
+            );
         }
     }
 
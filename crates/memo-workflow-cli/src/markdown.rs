@@ -0,0 +1,94 @@
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+
+/// Link and image URL schemes kept in rendered HTML; anything else (`javascript:`, `data:`, ...) is dropped.
+const SAFE_URL_SCHEMES: [&str; 4] = ["http", "https", "mailto", "tel"];
+
+/// Render memo Markdown (CommonMark plus tables, strikethrough, and task lists) to sanitized HTML.
+///
+/// Raw HTML blocks and inline tags are escaped into text, and link/image targets with a scheme outside
+/// `SAFE_URL_SCHEMES` are emptied, so the result is safe to paste into rich-text apps.
+pub fn render_markdown_html(text: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(text, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: sanitize_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: sanitize_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut rendered = String::with_capacity(text.len() * 3 / 2);
+    html::push_html(&mut rendered, events);
+    rendered
+}
+
+/// Keep relative URLs and URLs with a safe scheme; empty everything else.
+fn sanitize_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme = url
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+    match scheme {
+        Some(scheme)
+            if !SAFE_URL_SCHEMES
+                .iter()
+                .any(|safe| scheme.trim().eq_ignore_ascii_case(safe)) =>
+        {
+            CowStr::Borrowed("")
+        }
+        _ => url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_markdown_html_keeps_formatting_lists_and_tables() {
+        assert_eq!(
+            render_markdown_html(
+                "**Standup** notes\n\n- [x] ship `memo`\n- ~~old~~ [docs](https://docs.rs)"
+            ),
+            "<p><strong>Standup</strong> notes</p>\n<ul>\n<li><input disabled=\"\" type=\"checkbox\" checked=\"\"/>\nship <code>memo</code></li>\n<li><del>old</del> <a href=\"https://docs.rs\">docs</a></li>\n</ul>\n"
+        );
+        assert!(render_markdown_html("| a | b |\n|---|---|\n| 1 | 2 |").contains("<td>2</td>"));
+        assert_eq!(
+            render_markdown_html("#work buy milk"),
+            "<p>#work buy milk</p>\n"
+        );
+    }
+
+    #[test]
+    fn render_markdown_html_escapes_raw_html_and_drops_unsafe_urls() {
+        let rendered = render_markdown_html(
+            "<script>alert(1)</script>\n\nhi <b onclick=\"x()\">there</b> [x](javascript:alert(1)) ![i](data:image/png;base64,AA) [ok](/notes?a=b:c)",
+        );
+        assert!(!rendered.contains("<script>") && !rendered.contains("<b "));
+        assert!(rendered.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(rendered.contains("&lt;b onclick=\"x()\"&gt;there&lt;/b&gt;"));
+        assert!(rendered.contains("<a href=\"\">x</a>"));
+        assert!(rendered.contains("<img src=\"\" alt=\"i\" />"));
+        assert!(rendered.contains("<a href=\"/notes?a=b:c\">ok</a>"));
+    }
+}
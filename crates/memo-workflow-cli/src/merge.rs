@@ -0,0 +1,88 @@
+use memo::errors::AppError as MemoCliError;
+use rusqlite::Connection;
+
+use crate::relations;
+use crate::tags::TAG_ORIGIN_FLAG;
+
+/// Separator placed between the kept and the merged memo text.
+pub(crate) const MERGE_SEPARATOR: &str = "\n\n";
+
+/// Rows copied onto the kept memo by a merge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MergeUnion {
+    pub(crate) attachments_added: i64,
+    pub(crate) links_added: i64,
+}
+
+/// Join the kept and merged memo texts with `MERGE_SEPARATOR`.
+pub(crate) fn merged_text(keep_text: &str, merged_text: &str) -> String {
+    format!(
+        "{}{MERGE_SEPARATOR}{}",
+        keep_text.trim(),
+        merged_text.trim()
+    )
+}
+
+/// Copy the flag tags, attachments, and memo links of `merged_item_id` onto `item_id`.
+///
+/// Text tags are not copied: they follow the merged text. Rows the kept memo already has are skipped, and the link
+/// between the two memos (if any) is not carried over.
+pub(crate) fn union_item_rows(
+    conn: &Connection,
+    item_id: i64,
+    merged_item_id: i64,
+) -> Result<MergeUnion, MemoCliError> {
+    conn.execute(
+        "insert or ignore into workflow_item_tags(item_id, tag_name, origin)
+         select ?1, tag_name, origin from workflow_item_tags
+         where item_id = ?2 and origin = ?3",
+        (item_id, merged_item_id, TAG_ORIGIN_FLAG),
+    )
+    .map_err(MemoCliError::db_write)?;
+    let attachments_added = conn
+        .execute(
+            "insert or ignore into workflow_item_attachments(item_id, kind, location)
+             select ?1, kind, location from workflow_item_attachments
+             where item_id = ?2
+             order by attachment_id asc",
+            (item_id, merged_item_id),
+        )
+        .map_err(MemoCliError::db_write)?;
+
+    let mut stmt = conn
+        .prepare(
+            "select case when low_item_id = ?1 then high_item_id else low_item_id end
+            from workflow_item_links
+            where low_item_id = ?1 or high_item_id = ?1",
+        )
+        .map_err(MemoCliError::db_query)?;
+    let linked_ids = stmt
+        .query_map([merged_item_id], |row| row.get::<_, i64>(0))
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+    let mut links_added = 0;
+    for linked_id in linked_ids {
+        if linked_id != item_id && relations::add_link(conn, item_id, linked_id)? {
+            links_added += 1;
+        }
+    }
+
+    Ok(MergeUnion {
+        attachments_added: attachments_added as i64,
+        links_added,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_text_trims_both_sides_around_the_separator() {
+        assert_eq!(
+            merged_text("  buy milk\n", "\ncall mom "),
+            "buy milk\n\ncall mom"
+        );
+    }
+}
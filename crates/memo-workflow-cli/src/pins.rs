@@ -0,0 +1,80 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+
+use crate::ListResult;
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::trash::NOT_TRASHED_SQL;
+
+/// SQL expression yielding `1` when the `inbox_items` row aliased as `i` is pinned.
+pub(crate) const IS_PINNED_SQL: &str =
+    "exists (select 1 from workflow_item_pins p where p.item_id = i.item_id)";
+
+/// Pin one item, returning whether the pin state changed.
+pub(crate) fn pin_item(conn: &Connection, item_id: i64) -> Result<bool, MemoCliError> {
+    let inserted = conn
+        .execute(
+            "insert or ignore into workflow_item_pins(item_id) values (?1)",
+            [item_id],
+        )
+        .map_err(MemoCliError::db_write)?;
+    Ok(inserted > 0)
+}
+
+/// Unpin one item, returning whether the pin state changed.
+pub(crate) fn unpin_item(conn: &Connection, item_id: i64) -> Result<bool, MemoCliError> {
+    let removed = conn
+        .execute(
+            "delete from workflow_item_pins where item_id = ?1",
+            [item_id],
+        )
+        .map_err(MemoCliError::db_write)?;
+    Ok(removed > 0)
+}
+
+/// Pinned live memos, most recently pinned first.
+pub(crate) fn list_pinned(
+    conn: &Connection,
+    limit: usize,
+    codec: &TextCodec,
+) -> Result<Vec<ListResult>, MemoCliError> {
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            case
+                when exists (
+                    select 1
+                    from item_derivations d
+                    where d.item_id = i.item_id
+                      and d.is_active = 1
+                      and d.status = 'accepted'
+                ) then 'enriched'
+                else 'pending'
+            end as state,
+            {PREVIEW_TEXT_SQL} as text_preview
+        from workflow_item_pins p
+        join inbox_items i on i.item_id = p.item_id
+        where {NOT_TRASHED_SQL}
+        order by p.pinned_at desc, i.item_id desc
+        limit ?1"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([limit as i64], |row| {
+            Ok(ListResult {
+                item_id: format_item_id(row.get::<_, i64>(0)?),
+                created_at: row.get(1)?,
+                state: row.get(2)?,
+                text_preview: row.get(3)?,
+                is_pinned: true,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.map(|row| {
+        let mut row = row.map_err(MemoCliError::db_query)?;
+        row.text_preview = codec.preview(&row.text_preview)?;
+        Ok(row)
+    })
+    .collect()
+}
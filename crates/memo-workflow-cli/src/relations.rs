@@ -0,0 +1,67 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+
+use crate::LinkedItemResult;
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::trash::NOT_TRASHED_SQL;
+
+/// SQL expression counting live memos linked to the `inbox_items` row aliased as `i`.
+pub(crate) const LINKED_COUNT_SQL: &str = "(select count(*) from workflow_item_links l
+    join inbox_items o on o.item_id = case when l.low_item_id = i.item_id
+        then l.high_item_id else l.low_item_id end
+    where (l.low_item_id = i.item_id or l.high_item_id = i.item_id)
+      and not exists (select 1 from workflow_item_trash tr where tr.item_id = o.item_id))";
+
+/// Link two distinct items in both directions, returning whether a new link was stored.
+///
+/// Each pair is stored once with the lower item id first, so `a -> b` and `b -> a` are the same link.
+pub(crate) fn add_link(conn: &Connection, item_a: i64, item_b: i64) -> Result<bool, MemoCliError> {
+    let (low, high) = (item_a.min(item_b), item_a.max(item_b));
+    let inserted = conn
+        .execute(
+            "insert or ignore into workflow_item_links(low_item_id, high_item_id)
+             values (?1, ?2)",
+            (low, high),
+        )
+        .map_err(MemoCliError::db_write)?;
+    Ok(inserted > 0)
+}
+
+/// Live memos linked to one item, most recently linked first.
+pub(crate) fn list_linked_items(
+    conn: &Connection,
+    codec: &TextCodec,
+    item_id: i64,
+) -> Result<Vec<LinkedItemResult>, MemoCliError> {
+    let sql = format!(
+        "select i.item_id, i.created_at, {PREVIEW_TEXT_SQL} as text_preview, l.created_at
+        from workflow_item_links l
+        join inbox_items i on i.item_id = case when l.low_item_id = ?1
+            then l.high_item_id else l.low_item_id end
+        where (l.low_item_id = ?1 or l.high_item_id = ?1)
+          and {NOT_TRASHED_SQL}
+        order by l.created_at desc, i.item_id desc"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([item_id], |row| {
+            Ok(LinkedItemResult {
+                item_id: format_item_id(row.get::<_, i64>(0)?),
+                created_at: row.get(1)?,
+                text_preview: row.get(2)?,
+                linked_at: row.get(3)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(LinkedItemResult {
+                text_preview: codec.preview(&row.text_preview)?,
+                ..row
+            })
+        })
+        .collect()
+}
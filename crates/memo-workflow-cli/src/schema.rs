@@ -0,0 +1,363 @@
+use std::path::PathBuf;
+
+use memo::errors::AppError as MemoCliError;
+use memo::storage::Storage;
+use rusqlite::Connection;
+
+use crate::AppError;
+
+/// Workflow-owned tables layered on top of the upstream memo schema.
+///
+/// Versions are tracked in `workflow_schema_migrations` so they never collide with the
+/// upstream `schema_migrations` ledger owned by `nils-memo`.
+const WORKFLOW_MIGRATIONS: &[(i64, &str)] = &[
+    (
+        1,
+        "create table if not exists workflow_item_tags (
+        item_id integer not null references inbox_items(item_id) on delete cascade,
+        tag_name text not null check (length(trim(tag_name)) > 0 and tag_name = lower(tag_name)),
+        origin text not null check (origin in ('text', 'flag')),
+        created_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+        primary key (item_id, tag_name)
+    );
+    create index if not exists idx_workflow_item_tags_tag
+        on workflow_item_tags(tag_name, item_id);",
+    ),
+    (
+        2,
+        "create table if not exists workflow_item_trash (
+        item_id integer primary key references inbox_items(item_id) on delete cascade,
+        deleted_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );
+    create index if not exists idx_workflow_item_trash_deleted_at
+        on workflow_item_trash(deleted_at);",
+    ),
+    (
+        3,
+        "create table if not exists workflow_item_pins (
+        item_id integer primary key references inbox_items(item_id) on delete cascade,
+        pinned_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );",
+    ),
+    (
+        4,
+        "create table if not exists workflow_item_attachments (
+        attachment_id integer primary key autoincrement,
+        item_id integer not null references inbox_items(item_id) on delete cascade,
+        kind text not null check (kind in ('file', 'url')),
+        location text not null check (length(trim(location)) > 0),
+        created_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+        unique (item_id, location)
+    );",
+    ),
+    (
+        5,
+        "create table if not exists workflow_item_due (
+        item_id integer primary key references inbox_items(item_id) on delete cascade,
+        due_at text not null,
+        set_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );
+    create index if not exists idx_workflow_item_due_due_at
+        on workflow_item_due(due_at);",
+    ),
+    // Version 6 is the optional FTS5 search index applied by `fts::apply_search_index`.
+    (
+        7,
+        "create table if not exists workflow_templates (
+        name text primary key check (length(trim(name)) > 0 and name = lower(name)),
+        body text not null check (length(trim(body)) > 0),
+        created_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+        updated_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );",
+    ),
+    (
+        8,
+        "create table if not exists workflow_encryption (
+        id integer primary key check (id = 1),
+        salt text not null,
+        verifier text not null,
+        kdf_memory_kib integer not null,
+        kdf_iterations integer not null,
+        kdf_parallelism integer not null,
+        created_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );",
+    ),
+    // Sync identity and last-modified clock per memo; triggers bump `modified_at` on every synced field change and
+    // record a tombstone when a memo is purged.
+    (
+        9,
+        "create table if not exists workflow_item_sync (
+        item_id integer primary key references inbox_items(item_id) on delete cascade,
+        sync_id text not null unique,
+        modified_at text not null
+    );
+    create table if not exists workflow_sync_tombstones (
+        sync_id text primary key,
+        deleted_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );
+    create trigger if not exists trg_workflow_item_sync_ad
+        after delete on workflow_item_sync
+    begin
+        insert or replace into workflow_sync_tombstones(sync_id) values (old.sync_id);
+    end;
+    create trigger if not exists trg_workflow_sync_touch_text
+        after update of raw_text on inbox_items
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = new.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_tags_ai
+        after insert on workflow_item_tags
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = new.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_tags_ad
+        after delete on workflow_item_tags
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = old.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_pins_ai
+        after insert on workflow_item_pins
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = new.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_pins_ad
+        after delete on workflow_item_pins
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = old.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_trash_ai
+        after insert on workflow_item_trash
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = new.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_trash_ad
+        after delete on workflow_item_trash
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = old.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_due_ai
+        after insert on workflow_item_due
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = new.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_due_au
+        after update on workflow_item_due
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = new.item_id;
+    end;
+    create trigger if not exists trg_workflow_sync_touch_due_ad
+        after delete on workflow_item_due
+    begin
+        update workflow_item_sync set modified_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+        where item_id = old.item_id;
+    end;",
+    ),
+    // Content hash per memo for duplicate detection; rows are filled lazily and dropped whenever the text changes.
+    (
+        10,
+        "create table if not exists workflow_item_hashes (
+        item_id integer primary key references inbox_items(item_id) on delete cascade,
+        content_hash text not null
+    );
+    create index if not exists idx_workflow_item_hashes_hash on workflow_item_hashes(content_hash);
+    create trigger if not exists trg_workflow_item_hashes_au
+        after update of raw_text on inbox_items
+    begin
+        delete from workflow_item_hashes where item_id = new.item_id;
+    end;",
+    ),
+    // Undo journal: one row per add/update/delete, with the stored text around updates. Purging a memo drops its
+    // rows, so the journal never points at a missing memo.
+    (
+        11,
+        "create table if not exists workflow_operation_journal (
+        entry_id integer primary key autoincrement,
+        operation text not null check (operation in ('add', 'update', 'delete')),
+        item_id integer not null references inbox_items(item_id) on delete cascade,
+        before_text text,
+        after_text text,
+        recorded_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );
+    create index if not exists idx_workflow_operation_journal_recorded_at
+        on workflow_operation_journal(recorded_at);",
+    ),
+    // Item-to-item links; each bidirectional pair is stored once with the lower item id first.
+    (
+        12,
+        "create table if not exists workflow_item_links (
+        low_item_id integer not null references inbox_items(item_id) on delete cascade,
+        high_item_id integer not null references inbox_items(item_id) on delete cascade,
+        created_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+        primary key (low_item_id, high_item_id),
+        check (low_item_id < high_item_id)
+    );
+    create index if not exists idx_workflow_item_links_high
+        on workflow_item_links(high_item_id, low_item_id);",
+    ),
+    // Previous texts replaced by updates, stored in the same (possibly sealed) encoding as `raw_text`.
+    (
+        13,
+        "create table if not exists workflow_item_versions (
+        version_id integer primary key autoincrement,
+        item_id integer not null references inbox_items(item_id) on delete cascade,
+        text text not null,
+        replaced_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );
+    create index if not exists idx_workflow_item_versions_item
+        on workflow_item_versions(item_id, version_id);",
+    ),
+    // Journal rebuild allowing `merge` entries, which also reference the memo moved to trash by the merge. SQLite
+    // cannot alter a check constraint in place, so the table is copied.
+    (
+        14,
+        "create table workflow_operation_journal_v14 (
+        entry_id integer primary key autoincrement,
+        operation text not null check (operation in ('add', 'update', 'delete', 'merge')),
+        item_id integer not null references inbox_items(item_id) on delete cascade,
+        merged_item_id integer references inbox_items(item_id) on delete cascade,
+        before_text text,
+        after_text text,
+        recorded_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+    );
+    insert into workflow_operation_journal_v14(
+        entry_id, operation, item_id, before_text, after_text, recorded_at
+    )
+    select entry_id, operation, item_id, before_text, after_text, recorded_at
+    from workflow_operation_journal;
+    drop table workflow_operation_journal;
+    alter table workflow_operation_journal_v14 rename to workflow_operation_journal;
+    create index if not exists idx_workflow_operation_journal_recorded_at
+        on workflow_operation_journal(recorded_at);",
+    ),
+];
+
+/// Open memo storage at `db_path`, running upstream init plus workflow migrations.
+pub(crate) fn open_storage(db_path: PathBuf) -> Result<Storage, AppError> {
+    let storage = Storage::new(db_path);
+    storage
+        .init()
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    storage
+        .with_connection(|conn| {
+            apply_workflow_migrations(conn)?;
+            crate::fts::apply_search_index(conn).map(|_| ())
+        })
+        .map_err(|error| AppError::Runtime(error.message().to_string()))?;
+    Ok(storage)
+}
+
+fn apply_workflow_migrations(conn: &Connection) -> Result<(), MemoCliError> {
+    apply_migrations(conn, WORKFLOW_MIGRATIONS)
+}
+
+/// Each migration and its ledger row commit together, so an interrupted rebuild (such as v14's table copy) rolls
+/// back and reruns cleanly on the next open.
+fn apply_migrations(conn: &Connection, migrations: &[(i64, &str)]) -> Result<(), MemoCliError> {
+    conn.execute_batch(
+        "create table if not exists workflow_schema_migrations (
+            version integer primary key,
+            applied_at text not null default (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+        );",
+    )
+    .map_err(MemoCliError::db_write)?;
+
+    for (version, sql) in migrations {
+        let already_applied: i64 = conn
+            .query_row(
+                "select count(*) from workflow_schema_migrations where version = ?1",
+                [version],
+                |row| row.get(0),
+            )
+            .map_err(MemoCliError::db_query)?;
+
+        if already_applied == 0 {
+            let tx = conn
+                .unchecked_transaction()
+                .map_err(MemoCliError::db_write)?;
+            tx.execute_batch(sql).map_err(MemoCliError::db_write)?;
+            tx.execute(
+                "insert into workflow_schema_migrations(version) values(?1)",
+                [version],
+            )
+            .map_err(MemoCliError::db_write)?;
+            tx.commit().map_err(MemoCliError::db_write)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn open_storage_applies_workflow_migrations_once() {
+        let dir = tempdir().expect("temp dir");
+        let db_path = dir.path().join("memo.db");
+
+        open_storage(db_path.clone()).expect("first open");
+        let storage = open_storage(db_path).expect("second open");
+
+        let applied: i64 = storage
+            .with_connection(|conn| {
+                conn.query_row(
+                    "select count(*) from workflow_schema_migrations",
+                    [],
+                    |row| row.get(0),
+                )
+                .map_err(MemoCliError::db_query)
+            })
+            .expect("count migrations");
+        // Workflow migrations plus the FTS5 search index ledger entry.
+        assert_eq!(applied, WORKFLOW_MIGRATIONS.len() as i64 + 1);
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_and_reruns_cleanly() {
+        let conn = Connection::open_in_memory().expect("open db");
+        // Fails after its first statement, like a rebuild interrupted right after `create table ..._v14`.
+        let rebuild = "create table journal_rebuild (entry_id integer primary key);
+            insert into journal_rebuild(entry_id) select entry_id from journal_source;";
+        let migrations = [
+            (1, "create table journal_base (entry_id integer);"),
+            (2, rebuild),
+        ];
+
+        apply_migrations(&conn, &migrations).expect_err("missing source table");
+        let ledger = |conn: &Connection| -> Vec<i64> {
+            let mut statement = conn
+                .prepare("select version from workflow_schema_migrations order by version")
+                .expect("prepare");
+            statement
+                .query_map([], |row| row.get(0))
+                .expect("query")
+                .collect::<Result<_, _>>()
+                .expect("rows")
+        };
+        assert_eq!(ledger(&conn), vec![1]);
+        let partial: i64 = conn
+            .query_row(
+                "select count(*) from sqlite_master where name = 'journal_rebuild'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("lookup");
+        assert_eq!(partial, 0, "the half-applied rebuild must roll back");
+
+        conn.execute_batch("create table journal_source (entry_id integer primary key);")
+            .expect("create source");
+        apply_migrations(&conn, &migrations).expect("rerun after failure");
+        assert_eq!(ledger(&conn), vec![1, 2]);
+    }
+}
@@ -0,0 +1,350 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, TimeZone, Utc};
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::types::Value;
+use rusqlite::{Connection, params_from_iter};
+
+use crate::SearchResult;
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::trash::NOT_TRASHED_SQL;
+
+/// Operator keys recognised in search input as `key:value` (keys are case-insensitive).
+pub(crate) const SEARCH_OPERATORS: [&str; 4] = ["source", "before", "after", "state"];
+const STORED_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+/// Same enrichment test as the `state` column of list rows; expects `inbox_items` aliased as `i`.
+const ENRICHED_SQL: &str = "exists (
+    select 1 from item_derivations d
+    where d.item_id = i.item_id and d.is_active = 1 and d.status = 'accepted'
+)";
+
+/// `state:` operator value, matching the `state` reported by list rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StateFilter {
+    Enriched,
+    Pending,
+}
+
+/// Row filters parsed out of search input; `None` fields match every memo.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SearchFilters {
+    pub(crate) source: Option<String>,
+    /// Exclusive `created_at` upper bound: local midnight starting the `before:` day, as stored UTC text.
+    pub(crate) before: Option<String>,
+    /// Inclusive `created_at` lower bound: local midnight starting the `after:` day, as stored UTC text.
+    pub(crate) after: Option<String>,
+    pub(crate) state: Option<StateFilter>,
+}
+
+/// Search input split into free text (for the text matchers) and operator filters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ParsedSearch {
+    pub(crate) text: String,
+    pub(crate) filters: SearchFilters,
+}
+
+/// Split `source:`, `before:`, `after:`, and `state:` operators out of search input.
+///
+/// Operators may appear anywhere between text terms; quoted phrases and unknown `key:value` words stay text.
+/// Dates accept `YYYY-MM-DD`, `today`, and `yesterday` in the time zone of `now`. Empty, repeated, or
+/// invalid operators and an empty `after:`..`before:` range are reported as messages.
+pub(crate) fn parse_search<Tz: TimeZone>(
+    raw: &str,
+    now: &DateTime<Tz>,
+) -> Result<ParsedSearch, String> {
+    let mut filters = SearchFilters::default();
+    let mut text = Vec::new();
+
+    for token in split_tokens(raw) {
+        let operator = (!token.contains('"'))
+            .then(|| token.split_once(':'))
+            .flatten()
+            .map(|(key, value)| (key.to_ascii_lowercase(), value))
+            .filter(|(key, _)| SEARCH_OPERATORS.contains(&key.as_str()));
+        let Some((key, value)) = operator else {
+            text.push(token);
+            continue;
+        };
+        if value.is_empty() {
+            return Err(format!("search operator {key}: needs a value"));
+        }
+
+        let duplicate = match key.as_str() {
+            "source" => filters.source.replace(value.to_string()).is_some(),
+            "before" => filters
+                .before
+                .replace(parse_day(&key, value, now)?)
+                .is_some(),
+            "after" => filters
+                .after
+                .replace(parse_day(&key, value, now)?)
+                .is_some(),
+            _ => filters.state.replace(parse_state(value)?).is_some(),
+        };
+        if duplicate {
+            return Err(format!("duplicate search operator: {key}:"));
+        }
+    }
+
+    if let (Some(after), Some(before)) = (&filters.after, &filters.before)
+        && after >= before
+    {
+        return Err("invalid search range: after: must be earlier than before:".to_string());
+    }
+
+    Ok(ParsedSearch {
+        text: text.join(" "),
+        filters,
+    })
+}
+
+/// Whitespace-separated words, keeping `"quoted phrases"` (and anything glued to them) in one token.
+fn split_tokens(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in raw.chars() {
+        if ch.is_whitespace() && !quoted {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if ch == '"' {
+            quoted = !quoted;
+        }
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn parse_day<Tz: TimeZone>(key: &str, raw: &str, now: &DateTime<Tz>) -> Result<String, String> {
+    let today = now.date_naive();
+    let day = match raw.to_ascii_lowercase().as_str() {
+        "today" => Some(today),
+        "yesterday" => today.checked_sub_days(Days::new(1)),
+        other => NaiveDate::parse_from_str(other, "%Y-%m-%d").ok(),
+    };
+    day.and_then(|day| {
+        now.timezone()
+            .from_local_datetime(&day.and_time(NaiveTime::MIN))
+            .earliest()
+    })
+    .map(|start| start.with_timezone(&Utc).format(STORED_FORMAT).to_string())
+    .ok_or_else(|| {
+        format!("invalid search operator {key}:{raw} (expected YYYY-MM-DD, today, or yesterday)")
+    })
+}
+
+fn parse_state(raw: &str) -> Result<StateFilter, String> {
+    match raw.to_ascii_lowercase().as_str() {
+        "enriched" => Ok(StateFilter::Enriched),
+        "pending" => Ok(StateFilter::Pending),
+        _ => Err(format!(
+            "invalid search operator state:{raw} (expected enriched or pending)"
+        )),
+    }
+}
+
+impl SearchFilters {
+    pub(crate) fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// SQL predicate over `inbox_items` aliased as `i`, with parameters numbered from `?{first_param}`,
+    /// plus the values to bind to them. Without filters the predicate is `1`.
+    pub(crate) fn predicate_sql(&self, first_param: usize) -> (String, Vec<String>) {
+        let mut clauses = Vec::new();
+        let mut params = Vec::new();
+        let bound = [
+            ("lower(i.source) = lower(?N)", &self.source),
+            ("i.created_at < ?N", &self.before),
+            ("i.created_at >= ?N", &self.after),
+        ];
+        for (clause, value) in bound {
+            if let Some(value) = value {
+                let placeholder = format!("?{}", first_param + params.len());
+                clauses.push(clause.replace("?N", &placeholder));
+                params.push(value.clone());
+            }
+        }
+        match self.state {
+            Some(StateFilter::Enriched) => clauses.push(ENRICHED_SQL.to_string()),
+            Some(StateFilter::Pending) => clauses.push(format!("not {ENRICHED_SQL}")),
+            None => {}
+        }
+
+        if clauses.is_empty() {
+            ("1".to_string(), params)
+        } else {
+            (clauses.join(" and "), params)
+        }
+    }
+}
+
+/// Ids of memos (live or trashed) the filters reject; empty without filters.
+pub(crate) fn excluded_item_ids(
+    conn: &Connection,
+    filters: &SearchFilters,
+) -> Result<HashSet<i64>, MemoCliError> {
+    if filters.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let (predicate, params) = filters.predicate_sql(1);
+    let mut stmt = conn
+        .prepare(&format!(
+            "select i.item_id from inbox_items i where not ({predicate})"
+        ))
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map(params_from_iter(params), |row| row.get(0))
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<HashSet<i64>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+/// Live memos matching the filters alone (operator-only searches), newest first with no rank (`score` 0).
+pub(crate) fn search_filtered(
+    conn: &Connection,
+    codec: &TextCodec,
+    filters: &SearchFilters,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<SearchResult>, MemoCliError> {
+    let (predicate, params) = filters.predicate_sql(3);
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            {PREVIEW_TEXT_SQL} as preview,
+            json_extract(ad.payload_json, '$.content_type') as content_type,
+            json_extract(ad.payload_json, '$.validation_status') as validation_status
+        from inbox_items i
+        left join item_derivations ad
+          on ad.derivation_id = (
+            select d.derivation_id
+            from item_derivations d
+            where d.item_id = i.item_id
+              and d.is_active = 1
+              and d.status = 'accepted'
+            order by d.derivation_version desc, d.derivation_id desc
+            limit 1
+          )
+        where {NOT_TRASHED_SQL}
+          and {predicate}
+        order by i.created_at desc, i.item_id desc
+        limit ?1 offset ?2"
+    );
+    let mut values = vec![Value::Integer(limit as i64), Value::Integer(offset as i64)];
+    values.extend(params.into_iter().map(Value::Text));
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+    rows.into_iter()
+        .map(
+            |(item_id, created_at, preview, content_type, validation_status)| {
+                Ok(SearchResult {
+                    item_id: format_item_id(item_id),
+                    created_at,
+                    score: 0.0,
+                    matched_fields: Vec::new(),
+                    text_preview: codec.preview(&preview)?,
+                    content_type,
+                    validation_status,
+                    approximate: false,
+                })
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2026-03-10T15:30:00+08:00").expect("fixed now")
+    }
+
+    #[test]
+    fn parse_search_splits_operators_from_text_anywhere_in_the_query() {
+        let parsed = parse_search(
+            r#"buy Source:Clipboard "state:enriched" milk after:yesterday OR a:b state:PENDING"#,
+            &now(),
+        )
+        .expect("parsed");
+        assert_eq!(parsed.text, r#"buy "state:enriched" milk OR a:b"#);
+        assert_eq!(
+            parsed.filters,
+            SearchFilters {
+                source: Some("Clipboard".to_string()),
+                before: None,
+                after: Some("2026-03-08T16:00:00.000Z".to_string()),
+                state: Some(StateFilter::Pending),
+            }
+        );
+
+        let operators_only =
+            parse_search("before:2026-01-01 after:2025-12-01", &now()).expect("operators only");
+        assert_eq!(operators_only.text, "");
+        assert_eq!(
+            operators_only.filters.before.as_deref(),
+            Some("2025-12-31T16:00:00.000Z")
+        );
+        let (predicate, params) = operators_only.filters.predicate_sql(4);
+        assert_eq!(predicate, "i.created_at < ?4 and i.created_at >= ?5");
+        assert_eq!(params.len(), 2);
+
+        let plain = parse_search("meeting notes", &now()).expect("plain");
+        assert!(plain.filters.is_empty());
+        assert_eq!(
+            plain.filters.predicate_sql(1),
+            ("1".to_string(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn parse_search_rejects_invalid_empty_and_repeated_operators() {
+        let error = |raw: &str| parse_search(raw, &now()).expect_err(raw);
+        assert_eq!(
+            error("milk before:someday"),
+            "invalid search operator before:someday (expected YYYY-MM-DD, today, or yesterday)"
+        );
+        assert_eq!(
+            error("after:2026-02-30"),
+            "invalid search operator after:2026-02-30 (expected YYYY-MM-DD, today, or yesterday)"
+        );
+        assert_eq!(
+            error("state:done"),
+            "invalid search operator state:done (expected enriched or pending)"
+        );
+        assert_eq!(
+            error("source: milk"),
+            "search operator source: needs a value"
+        );
+        assert_eq!(
+            error("source:alfred SOURCE:clipboard"),
+            "duplicate search operator: source:"
+        );
+        assert_eq!(
+            error("after:today before:yesterday"),
+            "invalid search range: after: must be earlier than before:"
+        );
+    }
+}
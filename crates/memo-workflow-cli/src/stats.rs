@@ -0,0 +1,79 @@
+use memo::errors::AppError as MemoCliError;
+use rusqlite::Connection;
+
+use crate::trash::NOT_TRASHED_SQL;
+use crate::{SourceCountResult, WeekCountResult};
+
+/// Memo totals as `(total, live, trashed, pinned)`; pinned only counts live memos.
+pub(crate) fn memo_counts(conn: &Connection) -> Result<(i64, i64, i64, i64), MemoCliError> {
+    let sql = format!(
+        "select
+            count(*),
+            coalesce(sum({NOT_TRASHED_SQL}), 0),
+            (select count(*) from workflow_item_trash),
+            coalesce(sum({NOT_TRASHED_SQL}
+                and exists(select 1 from workflow_item_pins p where p.item_id = i.item_id)), 0)
+        from inbox_items i"
+    );
+    conn.query_row(&sql, [], |row| {
+        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+    })
+    .map_err(MemoCliError::db_query)
+}
+
+/// Live memos per source label, largest first.
+pub(crate) fn source_counts(
+    conn: &Connection,
+    limit: usize,
+) -> Result<Vec<SourceCountResult>, MemoCliError> {
+    let sql = format!(
+        "select i.source, count(*) as item_count
+        from inbox_items i
+        where {NOT_TRASHED_SQL}
+        group by i.source
+        order by item_count desc, i.source asc
+        limit ?1"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([limit as i64], |row| {
+            Ok(SourceCountResult {
+                source: row.get(0)?,
+                item_count: row.get(1)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+/// Live memos created in each of the last `weeks` UTC weeks (Monday start), newest week first, zeros included.
+pub(crate) fn weekly_counts(
+    conn: &Connection,
+    weeks: usize,
+) -> Result<Vec<WeekCountResult>, MemoCliError> {
+    let sql = format!(
+        "with recursive week(week_start, offset) as (
+            select date('now', 'weekday 0', '-6 days'), 1
+            union all
+            select date(week_start, '-7 days'), offset + 1 from week where offset < ?1
+        )
+        select w.week_start, (
+            select count(*) from inbox_items i
+            where date(i.created_at, 'weekday 0', '-6 days') = w.week_start and {NOT_TRASHED_SQL}
+        )
+        from week w
+        order by w.week_start desc"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([weeks as i64], |row| {
+            Ok(WeekCountResult {
+                week_start: row.get(0)?,
+                item_count: row.get(1)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
@@ -0,0 +1,630 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use memo::errors::AppError as MemoCliError;
+use memo::storage::repository;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+use crate::crypto::TextCodec;
+use crate::due;
+use crate::pins;
+use crate::tags::{self, extract_tags, normalize_tag};
+
+/// Directory inside the sync repo holding one `<sync_id>.json` file per memo.
+pub(crate) const SYNC_TREE_DIR: &str = "memos";
+const SYNC_FILE_EXTENSION: &str = "json";
+
+/// One memo as stored in the sync tree; field order is the on-disk key order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SyncRecord {
+    pub(crate) id: String,
+    pub(crate) created_at: String,
+    pub(crate) modified_at: String,
+    pub(crate) source: String,
+    pub(crate) text: String,
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) pinned: bool,
+    #[serde(default)]
+    pub(crate) due_at: Option<String>,
+    #[serde(default)]
+    pub(crate) deleted_at: Option<String>,
+}
+
+impl SyncRecord {
+    fn render(&self) -> String {
+        let mut output = serde_json::to_string_pretty(self).unwrap_or_default();
+        output.push('\n');
+        output
+    }
+}
+
+/// A purged memo whose file should leave the tree unless the remote edited it later.
+pub(crate) struct Tombstone {
+    sync_id: String,
+    deleted_at: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TreeChanges {
+    pub(crate) written: usize,
+    pub(crate) removed: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ApplyOutcome {
+    pub(crate) created: usize,
+    pub(crate) updated: usize,
+    pub(crate) purged: usize,
+}
+
+/// Give every memo without one a random sync id, stamped with its last text change.
+pub(crate) fn ensure_sync_rows(conn: &Connection) -> Result<(), MemoCliError> {
+    conn.execute(
+        "insert into workflow_item_sync(item_id, sync_id, modified_at)
+        select i.item_id, lower(hex(randomblob(16))), coalesce(
+            (select d.updated_at from item_search_documents d where d.item_id = i.item_id),
+            i.created_at
+        )
+        from inbox_items i
+        where not exists (select 1 from workflow_item_sync s where s.item_id = i.item_id)",
+        [],
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(())
+}
+
+/// Every memo with a sync id (trashed ones included), as it should appear in the tree.
+pub(crate) fn load_local_records(
+    conn: &Connection,
+    codec: &TextCodec,
+) -> Result<Vec<SyncRecord>, MemoCliError> {
+    let mut stmt = conn
+        .prepare(
+            "select
+                s.item_id,
+                s.sync_id,
+                i.created_at,
+                s.modified_at,
+                i.source,
+                i.raw_text,
+                exists(select 1 from workflow_item_pins p where p.item_id = i.item_id),
+                (select du.due_at from workflow_item_due du where du.item_id = i.item_id),
+                (select tr.deleted_at from workflow_item_trash tr where tr.item_id = i.item_id)
+            from workflow_item_sync s
+            join inbox_items i on i.item_id = s.item_id
+            order by s.sync_id",
+        )
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                SyncRecord {
+                    id: row.get(1)?,
+                    created_at: row.get(2)?,
+                    modified_at: row.get(3)?,
+                    source: row.get(4)?,
+                    text: row.get(5)?,
+                    tags: Vec::new(),
+                    pinned: row.get(6)?,
+                    due_at: row.get(7)?,
+                    deleted_at: row.get(8)?,
+                },
+            ))
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+
+    let mut records = Vec::with_capacity(rows.len());
+    for (item_id, mut record) in rows {
+        record.text = codec.open(&record.text)?;
+        record.tags = tags::list_item_tags(conn, item_id)?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+pub(crate) fn load_tombstones(conn: &Connection) -> Result<Vec<Tombstone>, MemoCliError> {
+    let mut stmt = conn
+        .prepare("select sync_id, deleted_at from workflow_sync_tombstones order by sync_id")
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(Tombstone {
+                sync_id: row.get(0)?,
+                deleted_at: row.get(1)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+fn tree_dir(repo: &Path) -> PathBuf {
+    repo.join(SYNC_TREE_DIR)
+}
+
+fn record_path(repo: &Path, sync_id: &str) -> PathBuf {
+    tree_dir(repo).join(format!("{sync_id}.{SYNC_FILE_EXTENSION}"))
+}
+
+fn read_record(path: &Path) -> Option<SyncRecord> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn io_error(action: &str, path: &Path, error: std::io::Error) -> AppError {
+    AppError::Runtime(format!("failed to {action} {}: {error}", path.display()))
+}
+
+/// Write local memos into the tree and drop files of purged memos.
+///
+/// Files whose `modified_at` is newer than the local memo (and tombstones older than the file) are left alone so
+/// the later import keeps the newer remote state.
+pub(crate) fn write_tree(
+    repo: &Path,
+    records: &[SyncRecord],
+    tombstones: &[Tombstone],
+) -> Result<TreeChanges, AppError> {
+    let dir = tree_dir(repo);
+    fs::create_dir_all(&dir).map_err(|error| io_error("create", &dir, error))?;
+    let mut changes = TreeChanges::default();
+
+    for record in records {
+        let path = record_path(repo, &record.id);
+        let rendered = record.render();
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == rendered => continue,
+            Ok(existing) => {
+                if serde_json::from_str::<SyncRecord>(&existing)
+                    .is_ok_and(|remote| remote.modified_at > record.modified_at)
+                {
+                    continue;
+                }
+            }
+            Err(_) => {}
+        }
+        fs::write(&path, rendered).map_err(|error| io_error("write", &path, error))?;
+        changes.written += 1;
+    }
+
+    for tombstone in tombstones {
+        let path = record_path(repo, &tombstone.sync_id);
+        let Some(remote) = read_record(&path) else {
+            continue;
+        };
+        if remote.modified_at <= tombstone.deleted_at {
+            fs::remove_file(&path).map_err(|error| io_error("remove", &path, error))?;
+            changes.removed += 1;
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Every parsable record of the tree, plus the ids (file stems) of files that could not be read.
+pub(crate) fn read_tree(repo: &Path) -> Result<(Vec<SyncRecord>, HashSet<String>), AppError> {
+    let dir = tree_dir(repo);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok((Vec::new(), HashSet::new()));
+        }
+        Err(error) => return Err(io_error("read", &dir, error)),
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == SYNC_FILE_EXTENSION)
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut records = Vec::with_capacity(paths.len());
+    let mut invalid = HashSet::new();
+    for path in paths {
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        match read_record(&path) {
+            Some(record) if record.id == stem && !record.text.trim().is_empty() => {
+                records.push(record);
+            }
+            _ => {
+                invalid.insert(stem.to_string());
+            }
+        }
+    }
+    Ok((records, invalid))
+}
+
+/// Bring local memos in line with the tree: newer records win, new ids are created, and missing ids are purged.
+///
+/// Ids in `unreadable` still have a file in the tree, so their memos are kept untouched.
+pub(crate) fn apply_tree(
+    conn: &Connection,
+    records: &[SyncRecord],
+    unreadable: &HashSet<String>,
+    codec: &TextCodec,
+) -> Result<ApplyOutcome, MemoCliError> {
+    let mut local: HashMap<String, (i64, String)> = HashMap::new();
+    {
+        let mut stmt = conn
+            .prepare("select sync_id, item_id, modified_at from workflow_item_sync")
+            .map_err(MemoCliError::db_query)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get(2)?))
+            })
+            .map_err(MemoCliError::db_query)?;
+        for row in rows {
+            let (sync_id, item_id, modified_at) = row.map_err(MemoCliError::db_query)?;
+            local.insert(sync_id, (item_id, modified_at));
+        }
+    }
+
+    let mut outcome = ApplyOutcome::default();
+    let mut seen = HashSet::new();
+    for record in records {
+        seen.insert(record.id.as_str());
+        let item_id = match local.get(&record.id) {
+            Some((_, modified_at)) if *modified_at >= record.modified_at => continue,
+            Some((item_id, _)) => {
+                let stored: String = conn
+                    .query_row(
+                        "select raw_text from inbox_items where item_id = ?1",
+                        [item_id],
+                        |row| row.get(0),
+                    )
+                    .map_err(MemoCliError::db_query)?;
+                if codec.open(&stored)? != record.text.trim() {
+                    repository::update_item(conn, *item_id, &codec.seal(record.text.trim())?)?;
+                }
+                outcome.updated += 1;
+                *item_id
+            }
+            None => {
+                let added = repository::add_item(
+                    conn,
+                    &codec.seal(record.text.trim())?,
+                    &record.source,
+                    Some(&record.created_at),
+                )?;
+                conn.execute(
+                    "insert into workflow_item_sync(item_id, sync_id, modified_at) values (?1, ?2, ?3)",
+                    (added.item_id, &record.id, &record.modified_at),
+                )
+                .map_err(MemoCliError::db_write)?;
+                outcome.created += 1;
+                added.item_id
+            }
+        };
+        apply_fields(conn, item_id, record)?;
+        conn.execute(
+            "update workflow_item_sync set modified_at = ?1 where item_id = ?2",
+            (&record.modified_at, item_id),
+        )
+        .map_err(MemoCliError::db_write)?;
+    }
+
+    for (sync_id, (item_id, _)) in &local {
+        if !seen.contains(sync_id.as_str()) && !unreadable.contains(sync_id) {
+            repository::delete_item_hard(conn, *item_id)?;
+            outcome.purged += 1;
+        }
+    }
+    // The tree no longer has these files, so their tombstones have nothing left to remove.
+    conn.execute("delete from workflow_sync_tombstones", [])
+        .map_err(MemoCliError::db_write)?;
+
+    Ok(outcome)
+}
+
+fn apply_fields(conn: &Connection, item_id: i64, record: &SyncRecord) -> Result<(), MemoCliError> {
+    let flag_tags = record
+        .tags
+        .iter()
+        .filter_map(|tag| normalize_tag(tag))
+        .collect::<Vec<_>>();
+    tags::replace_item_tags(
+        conn,
+        item_id,
+        tags::TAG_ORIGIN_TEXT,
+        &extract_tags(&record.text),
+    )?;
+    tags::replace_item_tags(conn, item_id, tags::TAG_ORIGIN_FLAG, &flag_tags)?;
+
+    if record.pinned {
+        pins::pin_item(conn, item_id)?;
+    } else {
+        pins::unpin_item(conn, item_id)?;
+    }
+    match &record.due_at {
+        Some(due_at) => due::set_due(conn, item_id, due_at)?,
+        None => {
+            due::clear_due(conn, item_id)?;
+        }
+    }
+    match &record.deleted_at {
+        Some(deleted_at) => conn.execute(
+            "insert into workflow_item_trash(item_id, deleted_at) values (?1, ?2)
+             on conflict(item_id) do update set deleted_at = excluded.deleted_at",
+            (item_id, deleted_at),
+        ),
+        None => conn.execute(
+            "delete from workflow_item_trash where item_id = ?1",
+            [item_id],
+        ),
+    }
+    .map_err(MemoCliError::db_write)?;
+    Ok(())
+}
+
+/// Last-write-wins pick between two conflicting file versions (`None` = deleted on that side).
+///
+/// The later `modified_at` wins; an edit beats a deletion; ties fall back to the larger rendering so every
+/// machine resolves the same conflict identically.
+pub(crate) fn resolve_conflict(ours: Option<&str>, theirs: Option<&str>) -> Option<String> {
+    fn parse(raw: Option<&str>) -> Option<(SyncRecord, &str)> {
+        raw.and_then(|raw| {
+            serde_json::from_str::<SyncRecord>(raw)
+                .ok()
+                .map(|record| (record, raw))
+        })
+    }
+    match (parse(ours), parse(theirs)) {
+        (Some((ours, ours_raw)), Some((theirs, theirs_raw))) => {
+            let ours_key = (&ours.modified_at, ours_raw);
+            let theirs_key = (&theirs.modified_at, theirs_raw);
+            Some(
+                if ours_key > theirs_key {
+                    ours_raw
+                } else {
+                    theirs_raw
+                }
+                .to_string(),
+            )
+        }
+        (Some((_, raw)), None) | (None, Some((_, raw))) => Some(raw.to_string()),
+        (None, None) => theirs.or(ours).map(str::to_string),
+    }
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<Output, AppError> {
+    Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|error| AppError::Runtime(format!("failed to run git: {error}")))
+}
+
+/// Run git and return trimmed stdout, surfacing git's stderr as a runtime error.
+fn git_ok(repo: &Path, args: &[&str]) -> Result<String, AppError> {
+    let output = git(repo, args)?;
+    if !output.status.success() {
+        return Err(AppError::Runtime(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub(crate) fn ensure_repo(repo: &Path) -> Result<(), AppError> {
+    if !repo.is_dir() {
+        return Err(AppError::User(format!(
+            "sync repo does not exist: {}",
+            repo.display()
+        )));
+    }
+    let inside = git(repo, &["rev-parse", "--is-inside-work-tree"])?;
+    if !inside.status.success() || String::from_utf8_lossy(&inside.stdout).trim() != "true" {
+        return Err(AppError::User(format!(
+            "sync repo is not a git work tree: {}",
+            repo.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Stage the tree and commit it when anything changed, returning whether a commit was made.
+pub(crate) fn commit_tree(repo: &Path, message: &str) -> Result<bool, AppError> {
+    git_ok(repo, &["add", "-A", "--", SYNC_TREE_DIR])?;
+    let staged = git_ok(repo, &["status", "--porcelain", "--", SYNC_TREE_DIR])?;
+    if staged.is_empty() {
+        return Ok(false);
+    }
+    git_ok(repo, &["commit", "-q", "-m", message, "--", SYNC_TREE_DIR])?;
+    Ok(true)
+}
+
+/// Fetch the first remote and report whether the current branch has a fetched upstream to merge.
+///
+/// A branch without tracking config adopts `<remote>/<branch>` when the remote already has it, so a fresh
+/// `git init` + `git remote add` work tree joins an existing sync history instead of diverging from it.
+pub(crate) fn fetch_upstream(repo: &Path) -> Result<bool, AppError> {
+    let remotes = git_ok(repo, &["remote"])?;
+    let Some(remote) = remotes.lines().next() else {
+        return Ok(false);
+    };
+    git_ok(repo, &["fetch", "-q", remote])?;
+    if upstream_exists(repo)? {
+        return Ok(true);
+    }
+
+    let Ok(branch) = git_ok(repo, &["symbolic-ref", "--short", "HEAD"]) else {
+        return Ok(false);
+    };
+    let tracked = git(
+        repo,
+        &["config", "--get", &format!("branch.{branch}.remote")],
+    )?;
+    let remote_ref = format!("refs/remotes/{remote}/{branch}");
+    if tracked.status.success()
+        || !git(repo, &["rev-parse", "--verify", "-q", &remote_ref])?
+            .status
+            .success()
+    {
+        return Ok(false);
+    }
+    git_ok(
+        repo,
+        &["config", &format!("branch.{branch}.remote"), remote],
+    )?;
+    git_ok(
+        repo,
+        &[
+            "config",
+            &format!("branch.{branch}.merge"),
+            &format!("refs/heads/{branch}"),
+        ],
+    )?;
+    upstream_exists(repo)
+}
+
+fn upstream_exists(repo: &Path) -> Result<bool, AppError> {
+    Ok(git(repo, &["rev-parse", "--verify", "-q", "@{u}"])?
+        .status
+        .success())
+}
+
+/// Merge the fetched upstream branch (unrelated first-sync histories included), resolving conflicting memo files
+/// last-write-wins; returns the conflict count.
+pub(crate) fn pull(repo: &Path) -> Result<usize, AppError> {
+    let output = git(
+        repo,
+        &[
+            "pull",
+            "-q",
+            "--no-rebase",
+            "--no-edit",
+            "--allow-unrelated-histories",
+        ],
+    )?;
+    if output.status.success() {
+        return Ok(0);
+    }
+
+    let conflicted = git_ok(repo, &["diff", "--name-only", "--diff-filter=U"])?;
+    let paths = conflicted.lines().collect::<Vec<_>>();
+    let prefix = format!("{SYNC_TREE_DIR}/");
+    if paths.is_empty() || paths.iter().any(|path| !path.starts_with(&prefix)) {
+        let _ = git(repo, &["merge", "--abort"]);
+        return Err(AppError::Runtime(format!(
+            "git pull failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    for path in &paths {
+        let stage = |number: u8| {
+            git(repo, &["show", &format!(":{number}:{path}")])
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        let ours = stage(2);
+        let theirs = stage(3);
+        let full_path = repo.join(path);
+        match resolve_conflict(ours.as_deref(), theirs.as_deref()) {
+            Some(contents) => {
+                fs::write(&full_path, contents)
+                    .map_err(|error| io_error("write", &full_path, error))?;
+                git_ok(repo, &["add", "--", path])?;
+            }
+            None => {
+                git_ok(repo, &["rm", "-q", "--", path])?;
+            }
+        }
+    }
+    git_ok(repo, &["commit", "-q", "--no-edit"])?;
+    Ok(paths.len())
+}
+
+/// Push commits the upstream lacks, or publish the branch to the first remote; returns whether anything was pushed.
+pub(crate) fn push(repo: &Path, upstream: bool) -> Result<bool, AppError> {
+    if !git(repo, &["rev-parse", "--verify", "-q", "HEAD"])?
+        .status
+        .success()
+    {
+        return Ok(false);
+    }
+    if upstream {
+        let ahead = git_ok(repo, &["rev-list", "--count", "@{u}..HEAD"])?;
+        if ahead == "0" {
+            return Ok(false);
+        }
+        git_ok(repo, &["push", "-q"])?;
+        return Ok(true);
+    }
+    let remotes = git_ok(repo, &["remote"])?;
+    let Some(remote) = remotes.lines().next() else {
+        return Ok(false);
+    };
+    git_ok(repo, &["push", "-q", "-u", remote, "HEAD"])?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(modified_at: &str, text: &str) -> String {
+        SyncRecord {
+            id: "0a1b".to_string(),
+            created_at: "2026-02-01T08:00:00.000Z".to_string(),
+            modified_at: modified_at.to_string(),
+            source: "alfred".to_string(),
+            text: text.to_string(),
+            tags: vec!["home".to_string()],
+            pinned: false,
+            due_at: None,
+            deleted_at: None,
+        }
+        .render()
+    }
+
+    #[test]
+    fn resolve_conflict_prefers_latest_edit_and_edits_over_deletes() {
+        let older = record("2026-02-01T09:00:00.000Z", "buy milk");
+        let newer = record("2026-02-01T10:00:00.000Z", "buy oat milk");
+
+        assert_eq!(
+            resolve_conflict(Some(&older), Some(&newer)).as_deref(),
+            Some(newer.as_str())
+        );
+        assert_eq!(
+            resolve_conflict(Some(&newer), Some(&older)).as_deref(),
+            Some(newer.as_str())
+        );
+        assert_eq!(
+            resolve_conflict(None, Some(&older)).as_deref(),
+            Some(older.as_str())
+        );
+        assert_eq!(
+            resolve_conflict(Some(&older), None).as_deref(),
+            Some(older.as_str())
+        );
+        assert_eq!(resolve_conflict(None, None), None);
+
+        let tie_a = record("2026-02-01T09:00:00.000Z", "a");
+        let tie_b = record("2026-02-01T09:00:00.000Z", "b");
+        assert_eq!(
+            resolve_conflict(Some(&tie_a), Some(&tie_b)),
+            resolve_conflict(Some(&tie_b), Some(&tie_a))
+        );
+        assert!(older.starts_with("{\n  \"id\": \"0a1b\",\n  \"created_at\""));
+    }
+}
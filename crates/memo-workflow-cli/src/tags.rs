@@ -0,0 +1,222 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use rusqlite::Connection;
+
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::pins::IS_PINNED_SQL;
+use crate::trash::NOT_TRASHED_SQL;
+use crate::{AppError, ListResult, TagCountResult};
+
+pub const TAG_ORIGIN_TEXT: &str = "text";
+pub const TAG_ORIGIN_FLAG: &str = "flag";
+const MAX_TAG_CHARS: usize = 64;
+
+/// Extract `#tag` markers from memo text in first-seen order.
+///
+/// A marker must start the text or follow whitespace, so `a#b` and URL fragments are ignored.
+/// Purely numeric markers (`#42`) are treated as references rather than tags.
+pub fn extract_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        let at_boundary = previous.is_none_or(char::is_whitespace);
+        previous = Some(ch);
+        if ch != '#' || !at_boundary {
+            continue;
+        }
+
+        let start = index + ch.len_utf8();
+        let mut end = start;
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !is_tag_char(next) {
+                break;
+            }
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        if let Some(tag) = normalize_tag(&text[start..end])
+            && !tags.contains(&tag)
+        {
+            tags.push(tag);
+        }
+    }
+
+    tags
+}
+
+/// Normalize one tag name (optional leading `#`), returning `None` when it is not a valid tag.
+pub fn normalize_tag(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    let trimmed = trimmed.strip_prefix('#').unwrap_or(trimmed);
+    let trimmed = trimmed.trim_end_matches(['-', '/']);
+    if trimmed.is_empty()
+        || trimmed.chars().count() > MAX_TAG_CHARS
+        || !trimmed.chars().all(is_tag_char)
+        || trimmed.chars().all(|ch| ch.is_ascii_digit())
+    {
+        return None;
+    }
+
+    Some(trimmed.to_lowercase())
+}
+
+/// Parse a comma/whitespace separated `--tags` value.
+pub fn parse_tag_list(raw: &str) -> Result<Vec<String>, AppError> {
+    let mut tags = Vec::new();
+    for part in raw
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|part| !part.is_empty())
+    {
+        let tag =
+            normalize_tag(part).ok_or_else(|| AppError::User(format!("invalid tag: {part}")))?;
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    Ok(tags)
+}
+
+fn is_tag_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '_' | '-' | '/')
+}
+
+/// Replace all tags of one `origin` for an item; tags already stored under the other origin are kept.
+pub(crate) fn replace_item_tags(
+    conn: &Connection,
+    item_id: i64,
+    origin: &str,
+    tags: &[String],
+) -> Result<(), MemoCliError> {
+    conn.execute(
+        "delete from workflow_item_tags where item_id = ?1 and origin = ?2",
+        (item_id, origin),
+    )
+    .map_err(MemoCliError::db_write)?;
+
+    for tag in tags {
+        conn.execute(
+            "insert or ignore into workflow_item_tags(item_id, tag_name, origin)
+             values (?1, ?2, ?3)",
+            (item_id, tag, origin),
+        )
+        .map_err(MemoCliError::db_write)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn list_item_tags(conn: &Connection, item_id: i64) -> Result<Vec<String>, MemoCliError> {
+    let mut stmt = conn
+        .prepare("select tag_name from workflow_item_tags where item_id = ?1 order by tag_name")
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([item_id], |row| row.get(0))
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<String>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+pub(crate) fn list_items_for_tag(
+    conn: &Connection,
+    tag: &str,
+    limit: usize,
+    offset: usize,
+    codec: &TextCodec,
+) -> Result<Vec<ListResult>, MemoCliError> {
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            case
+                when exists (
+                    select 1
+                    from item_derivations d
+                    where d.item_id = i.item_id
+                      and d.is_active = 1
+                      and d.status = 'accepted'
+                ) then 'enriched'
+                else 'pending'
+            end as state,
+            {PREVIEW_TEXT_SQL} as text_preview,
+            {IS_PINNED_SQL} as is_pinned
+        from workflow_item_tags t
+        join inbox_items i on i.item_id = t.item_id
+        where t.tag_name = ?1
+          and {NOT_TRASHED_SQL}
+        order by i.created_at desc, i.item_id desc
+        limit ?2 offset ?3"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map((tag, limit as i64, offset as i64), |row| {
+            Ok(ListResult {
+                item_id: format_item_id(row.get::<_, i64>(0)?),
+                created_at: row.get(1)?,
+                state: row.get(2)?,
+                text_preview: row.get(3)?,
+                is_pinned: row.get(4)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.map(|row| {
+        let mut row = row.map_err(MemoCliError::db_query)?;
+        row.text_preview = codec.preview(&row.text_preview)?;
+        Ok(row)
+    })
+    .collect()
+}
+
+pub(crate) fn list_tag_counts(
+    conn: &Connection,
+    limit: usize,
+) -> Result<Vec<TagCountResult>, MemoCliError> {
+    let sql = format!(
+        "select t.tag_name, count(*) as item_count
+        from workflow_item_tags t
+        join inbox_items i on i.item_id = t.item_id
+        where {NOT_TRASHED_SQL}
+        group by t.tag_name
+        order by item_count desc, t.tag_name asc
+        limit ?1"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([limit as i64], |row| {
+            Ok(TagCountResult {
+                tag: row.get(0)?,
+                item_count: row.get(1)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_tags_requires_word_boundary_and_skips_numeric_refs() {
+        assert_eq!(
+            extract_tags("#Work call bob re #42, see a#b and #home/garden #work"),
+            vec!["work".to_string(), "home/garden".to_string()]
+        );
+        assert_eq!(extract_tags("tail #todo-"), vec!["todo".to_string()]);
+        assert!(extract_tags("no tags # here").is_empty());
+    }
+
+    #[test]
+    fn parse_tag_list_normalizes_and_rejects_invalid_names() {
+        assert_eq!(
+            parse_tag_list("#Work, home  work").expect("tag list"),
+            vec!["work".to_string(), "home".to_string()]
+        );
+        assert!(parse_tag_list("ok,bad!tag").is_err());
+        assert!(parse_tag_list("123").is_err());
+    }
+}
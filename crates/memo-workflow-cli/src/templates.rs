@@ -0,0 +1,141 @@
+use chrono::{DateTime, TimeZone};
+use memo::errors::AppError as MemoCliError;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::TemplateResult;
+
+const MAX_TEMPLATE_NAME_CHARS: usize = 32;
+const CLIPBOARD_PLACEHOLDER: &str = "{clipboard}";
+
+/// Placeholders expanded by `expand_placeholders`, with the value each one renders.
+pub(crate) const TEMPLATE_PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{date}", "local date, YYYY-MM-DD"),
+    ("{time}", "local time, HH:MM"),
+    ("{datetime}", "local date and time, YYYY-MM-DD HH:MM"),
+    ("{weekday}", "local weekday name, e.g. Monday"),
+    (CLIPBOARD_PLACEHOLDER, "current clipboard text"),
+];
+
+/// Normalize a template name: lowercase ASCII letters, digits, `-`, `_`, at most 32 chars.
+pub fn normalize_template_name(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty()
+        || trimmed.chars().count() > MAX_TEMPLATE_NAME_CHARS
+        || !trimmed
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return None;
+    }
+    Some(trimmed.to_ascii_lowercase())
+}
+
+/// Replace known `{placeholder}` tokens in `body`; unknown braces are kept verbatim.
+///
+/// `clipboard` is only called when the body references `{clipboard}`.
+pub(crate) fn expand_placeholders<Tz, F>(body: &str, now: &DateTime<Tz>, clipboard: F) -> String
+where
+    Tz: TimeZone,
+    Tz::Offset: std::fmt::Display,
+    F: FnOnce() -> String,
+{
+    let mut expanded = body
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M").to_string())
+        .replace("{datetime}", &now.format("%Y-%m-%d %H:%M").to_string())
+        .replace("{weekday}", &now.format("%A").to_string());
+    if expanded.contains(CLIPBOARD_PLACEHOLDER) {
+        expanded = expanded.replace(CLIPBOARD_PLACEHOLDER, &clipboard());
+    }
+    expanded
+}
+
+/// Insert or replace one template body, returning whether a new template was created.
+pub(crate) fn save_template(
+    conn: &Connection,
+    name: &str,
+    body: &str,
+) -> Result<bool, MemoCliError> {
+    let created = find_template(conn, name)?.is_none();
+    conn.execute(
+        "insert into workflow_templates(name, body) values (?1, ?2)
+         on conflict(name) do update set
+            body = excluded.body,
+            updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+        (name, body),
+    )
+    .map_err(MemoCliError::db_write)?;
+    Ok(created)
+}
+
+pub(crate) fn list_templates(conn: &Connection) -> Result<Vec<TemplateResult>, MemoCliError> {
+    let mut stmt = conn
+        .prepare(
+            "select name, body, created_at, updated_at
+            from workflow_templates
+            order by name asc",
+        )
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([], map_template)
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+pub(crate) fn find_template(
+    conn: &Connection,
+    name: &str,
+) -> Result<Option<TemplateResult>, MemoCliError> {
+    conn.query_row(
+        "select name, body, created_at, updated_at
+        from workflow_templates
+        where name = ?1",
+        [name],
+        map_template,
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+fn map_template(row: &rusqlite::Row<'_>) -> rusqlite::Result<TemplateResult> {
+    Ok(TemplateResult {
+        name: row.get(0)?,
+        body: row.get(1)?,
+        created_at: row.get(2)?,
+        updated_at: row.get(3)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    #[test]
+    fn expand_placeholders_renders_known_tokens_and_reads_clipboard_lazily() {
+        let now = DateTime::parse_from_rfc3339("2026-02-12T09:05:00+08:00")
+            .expect("fixed now")
+            .with_timezone(&FixedOffset::east_opt(8 * 3600).expect("offset"));
+
+        assert_eq!(
+            expand_placeholders(
+                "## Standup {date} ({weekday}) {time}\n- {clipboard}\n- {unknown}",
+                &now,
+                || "fix login bug".to_string()
+            ),
+            "## Standup 2026-02-12 (Thursday) 09:05\n- fix login bug\n- {unknown}"
+        );
+        assert_eq!(
+            expand_placeholders("at {datetime}", &now, || panic!("clipboard not needed")),
+            "at 2026-02-12 09:05"
+        );
+
+        assert_eq!(
+            normalize_template_name(" Stand_Up-1 ").as_deref(),
+            Some("stand_up-1")
+        );
+        assert_eq!(normalize_template_name("two words"), None);
+        assert_eq!(normalize_template_name(&"x".repeat(33)), None);
+    }
+}
@@ -0,0 +1,236 @@
+use memo::errors::AppError as MemoCliError;
+use memo::output::format_item_id;
+use memo::storage::repository;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::crypto::{PREVIEW_TEXT_SQL, TextCodec};
+use crate::pins::IS_PINNED_SQL;
+use crate::{ListResult, TrashResult};
+
+/// SQL predicate excluding trashed rows; expects `inbox_items` aliased as `i`.
+pub(crate) const NOT_TRASHED_SQL: &str =
+    "not exists (select 1 from workflow_item_trash tr where tr.item_id = i.item_id)";
+
+/// Auto-purge timestamp expression for trash row `t`; `null` when retention is disabled.
+fn purge_after_sql(retention_days: u32) -> String {
+    if retention_days == 0 {
+        return "null".to_string();
+    }
+    format!("strftime('%Y-%m-%dT%H:%M:%fZ', t.deleted_at, '+{retention_days} days')")
+}
+
+pub(crate) fn trashed_at(conn: &Connection, item_id: i64) -> Result<Option<String>, MemoCliError> {
+    conn.query_row(
+        "select deleted_at from workflow_item_trash where item_id = ?1",
+        [item_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(MemoCliError::db_query)
+}
+
+/// Mark one item as trashed, returning `(deleted_at, purge_after)`.
+pub(crate) fn move_to_trash(
+    conn: &Connection,
+    item_id: i64,
+    retention_days: u32,
+) -> Result<(String, Option<String>), MemoCliError> {
+    conn.execute(
+        "insert into workflow_item_trash(item_id) values (?1)",
+        [item_id],
+    )
+    .map_err(MemoCliError::db_write)?;
+    let sql = format!(
+        "select t.deleted_at, {} from workflow_item_trash t where t.item_id = ?1",
+        purge_after_sql(retention_days)
+    );
+    conn.query_row(&sql, [item_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(MemoCliError::db_query)
+}
+
+/// Remove one item from trash, returning whether it was trashed.
+pub(crate) fn restore_from_trash(conn: &Connection, item_id: i64) -> Result<bool, MemoCliError> {
+    let removed = conn
+        .execute(
+            "delete from workflow_item_trash where item_id = ?1",
+            [item_id],
+        )
+        .map_err(MemoCliError::db_write)?;
+    Ok(removed > 0)
+}
+
+pub(crate) fn trashed_item_ids(conn: &Connection) -> Result<Vec<i64>, MemoCliError> {
+    let mut stmt = conn
+        .prepare("select item_id from workflow_item_trash")
+        .map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(MemoCliError::db_query)?;
+    rows.collect::<Result<Vec<i64>, _>>()
+        .map_err(MemoCliError::db_query)
+}
+
+/// Hard-delete every trashed item older than `retention_days`, returning the purge count.
+pub(crate) fn purge_expired(conn: &Connection, retention_days: u32) -> Result<i64, MemoCliError> {
+    let mut stmt = conn
+        .prepare(
+            "select item_id
+            from workflow_item_trash
+            where deleted_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now', ?1)",
+        )
+        .map_err(MemoCliError::db_query)?;
+    let expired = stmt
+        .query_map([format!("-{retention_days} days")], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+
+    for item_id in &expired {
+        repository::delete_item_hard(conn, *item_id)?;
+    }
+
+    Ok(expired.len() as i64)
+}
+
+/// Newest-first live (non-trashed) memo rows, mirroring upstream `list_items` output.
+pub(crate) fn list_live_items(
+    conn: &Connection,
+    limit: usize,
+    offset: usize,
+    codec: &TextCodec,
+) -> Result<Vec<ListResult>, MemoCliError> {
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            case
+                when exists (
+                    select 1
+                    from item_derivations d
+                    where d.item_id = i.item_id
+                      and d.is_active = 1
+                      and d.status = 'accepted'
+                ) then 'enriched'
+                else 'pending'
+            end as state,
+            {PREVIEW_TEXT_SQL} as text_preview,
+            {IS_PINNED_SQL} as is_pinned
+        from inbox_items i
+        where {NOT_TRASHED_SQL}
+        order by i.created_at desc, i.item_id desc
+        limit ?1 offset ?2"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map((limit as i64, offset as i64), |row| {
+            Ok(ListResult {
+                item_id: format_item_id(row.get::<_, i64>(0)?),
+                created_at: row.get(1)?,
+                state: row.get(2)?,
+                text_preview: row.get(3)?,
+                is_pinned: row.get(4)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.map(|row| {
+        let mut row = row.map_err(MemoCliError::db_query)?;
+        row.text_preview = codec.preview(&row.text_preview)?;
+        Ok(row)
+    })
+    .collect()
+}
+
+/// Plaintext and tags of one live memo, loaded for client-side matching.
+pub(crate) struct LiveText {
+    pub(crate) item_id: i64,
+    pub(crate) created_at: String,
+    pub(crate) text: String,
+    pub(crate) tags_text: String,
+}
+
+/// Newest-first live memos with decrypted text and space-separated workflow tags (all when `limit` is `None`).
+pub(crate) fn list_live_texts(
+    conn: &Connection,
+    codec: &TextCodec,
+    limit: Option<usize>,
+) -> Result<Vec<LiveText>, MemoCliError> {
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            i.raw_text,
+            coalesce((
+                select group_concat(t.tag_name, ' ') from workflow_item_tags t
+                where t.item_id = i.item_id
+            ), '') as tags_text
+        from inbox_items i
+        where {NOT_TRASHED_SQL}
+        order by i.created_at desc, i.item_id desc
+        limit ?1"
+    );
+    let limit = limit.map_or(-1, |limit| limit as i64);
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map([limit], |row| {
+            Ok(LiveText {
+                item_id: row.get(0)?,
+                created_at: row.get(1)?,
+                text: row.get(2)?,
+                tags_text: row.get(3)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(MemoCliError::db_query)?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(LiveText {
+                text: codec.open(&row.text)?,
+                ..row
+            })
+        })
+        .collect()
+}
+
+/// Trashed rows, most recently deleted first.
+pub(crate) fn list_trash(
+    conn: &Connection,
+    retention_days: u32,
+    limit: usize,
+    offset: usize,
+    codec: &TextCodec,
+) -> Result<Vec<TrashResult>, MemoCliError> {
+    let sql = format!(
+        "select
+            i.item_id,
+            i.created_at,
+            t.deleted_at,
+            {purge_after} as purge_after,
+            {PREVIEW_TEXT_SQL} as text_preview
+        from workflow_item_trash t
+        join inbox_items i on i.item_id = t.item_id
+        order by t.deleted_at desc, i.item_id desc
+        limit ?1 offset ?2",
+        purge_after = purge_after_sql(retention_days)
+    );
+    let mut stmt = conn.prepare(&sql).map_err(MemoCliError::db_query)?;
+    let rows = stmt
+        .query_map((limit as i64, offset as i64), |row| {
+            Ok(TrashResult {
+                item_id: format_item_id(row.get::<_, i64>(0)?),
+                created_at: row.get(1)?,
+                deleted_at: row.get(2)?,
+                purge_after: row.get(3)?,
+                text_preview: row.get(4)?,
+            })
+        })
+        .map_err(MemoCliError::db_query)?;
+    rows.map(|row| {
+        let mut row = row.map_err(MemoCliError::db_query)?;
+        row.text_preview = codec.preview(&row.text_preview)?;
+        Ok(row)
+    })
+    .collect()
+}
@@ -34,6 +34,10 @@ fn assert_json_success_envelope(payload: &Value, command: &str) {
         Some(command)
     );
     assert_eq!(payload.get("ok").and_then(Value::as_bool), Some(true));
+    assert!(
+        payload.get("error").is_none(),
+        "success envelope must not carry an error branch"
+    );
 }
 
 #[test]
@@ -127,7 +131,7 @@ fn script_filter_empty_query_with_existing_db_shows_db_path_row_without_db_init(
         "empty query with existing db should not include db-init action row"
     );
 
-    let db_path_subtitle = format!("Using SQLite at {db_path}");
+    let db_path_subtitle = format!("Using SQLite at {db_path} | No backups yet");
     let has_db_path_info = items.iter().any(|item| {
         item.get("title").and_then(Value::as_str) == Some("Memo database path")
             && item.get("subtitle").and_then(Value::as_str) == Some(db_path_subtitle.as_str())
@@ -238,8 +242,8 @@ fn script_filter_item_intent_shows_copy_update_delete_menu_and_update_guidance()
         .expect("items array");
     assert_eq!(
         menu_items.len(),
-        3,
-        "item intent should render three menu rows"
+        5,
+        "item intent should render five menu rows"
     );
     let expected_copy_title_prefix = format!("Copy memo: {} | menu seed", item_display_id(item_id));
     let expected_copy_arg = format!("copy::{item_id}");
@@ -503,6 +507,127 @@ fn update_mutates_existing_item() {
     );
 }
 
+#[test]
+fn output_json_wraps_results_and_failures_in_shared_envelope() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+
+    let init = Command::new(bin())
+        .args(["db-init", "--db", db_path, "--output", "json"])
+        .output()
+        .expect("db-init should run");
+    assert!(init.status.success(), "db-init should succeed");
+    let init_payload: Value = serde_json::from_slice(&init.stdout).expect("db-init json");
+    assert_json_success_envelope(&init_payload, "memo.db_init");
+
+    let add = Command::new(bin())
+        .args([
+            "add", "--db", db_path, "--text", "envelope", "--output", "json",
+        ])
+        .output()
+        .expect("add should run");
+    assert!(add.status.success(), "add should succeed");
+    let add_payload: Value = serde_json::from_slice(&add.stdout).expect("add json");
+    assert_json_success_envelope(&add_payload, "memo.add");
+
+    let search = Command::new(bin())
+        .args([
+            "search", "--db", db_path, "--query", "envelope", "--output", "json",
+        ])
+        .output()
+        .expect("search should run");
+    assert!(search.status.success(), "search should succeed");
+    let search_payload: Value = serde_json::from_slice(&search.stdout).expect("search json");
+    assert_json_success_envelope(&search_payload, "memo.search");
+    assert_eq!(
+        search_payload
+            .get("result")
+            .and_then(Value::as_array)
+            .map(Vec::len),
+        Some(1)
+    );
+
+    let update = Command::new(bin())
+        .args([
+            "update",
+            "--db",
+            db_path,
+            "--item-id",
+            "bad",
+            "--text",
+            "invalid id",
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("update should run");
+    assert_eq!(
+        update.status.code(),
+        Some(2),
+        "missing item is a user error"
+    );
+    assert!(
+        String::from_utf8_lossy(&update.stderr).contains("error[NILS_MEMO_001]"),
+        "stderr keeps the error diagnostic"
+    );
+    let error_payload: Value = serde_json::from_slice(&update.stdout).expect("error json");
+    assert_eq!(
+        error_payload.get("command").and_then(Value::as_str),
+        Some("memo.update")
+    );
+    assert_eq!(
+        error_payload.get("ok").and_then(Value::as_bool),
+        Some(false)
+    );
+    assert!(error_payload.get("result").is_none());
+    let error = error_payload.get("error").expect("error object");
+    assert_eq!(
+        error.get("code").and_then(Value::as_str),
+        Some("NILS_MEMO_001")
+    );
+    assert_eq!(
+        error
+            .get("details")
+            .and_then(|details| details.get("exit_code"))
+            .and_then(Value::as_i64),
+        Some(2)
+    );
+}
+
+#[test]
+fn output_alias_is_accepted_beyond_the_core_commands() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+
+    for (args, command) in [
+        (vec!["stats", "--db", db_path], "memo.stats"),
+        (vec!["trash", "--db", db_path], "memo.trash"),
+        (
+            vec!["template", "list", "--db", db_path],
+            "memo.template.list",
+        ),
+        (
+            vec!["action", "--token", "db-init", "--db", db_path],
+            "memo.action",
+        ),
+    ] {
+        let output = Command::new(bin())
+            .args(&args)
+            .args(["--output", "json"])
+            .output()
+            .expect("command should run");
+        assert!(
+            output.status.success(),
+            "{command} should accept --output json: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let payload: Value = serde_json::from_slice(&output.stdout).expect("envelope json");
+        assert_json_success_envelope(&payload, command);
+    }
+}
+
 #[test]
 fn delete_removes_existing_item() {
     let dir = tempdir().expect("temp dir");
@@ -803,6 +928,106 @@ fn search_command_supports_prefix_and_contains_match_modes() {
     );
 }
 
+#[test]
+fn search_command_ranks_fts_phrases_and_falls_back_when_disabled() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str], fts: &str| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_SEARCH_FTS", fts)
+            .output()
+            .expect("command should run")
+    };
+    let search_rows = |query: &str, fts: &str| {
+        let output = run(
+            &[
+                "search", "--db", db_path, "--query", query, "--mode", "json",
+            ],
+            fts,
+        );
+        assert!(output.status.success(), "search should succeed");
+        let payload: Value = serde_json::from_slice(&output.stdout).expect("search payload json");
+        assert_json_success_envelope(&payload, "memo.search");
+        payload
+            .get("result")
+            .and_then(Value::as_array)
+            .expect("search result rows")
+            .iter()
+            .filter_map(|row| row.get("text_preview").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    for text in ["milk then oat flakes", "buy oat milk"] {
+        let add = run(&["add", "--db", db_path, "--text", text], "1");
+        assert!(add.status.success(), "add should succeed");
+    }
+
+    assert_eq!(search_rows("\"oat milk\"", "1"), vec!["buy oat milk"]);
+    assert_eq!(search_rows("fla*", "1"), vec!["milk then oat flakes"]);
+    assert_eq!(
+        search_rows("milk:(", "1").len(),
+        2,
+        "fts syntax characters should be tokenized like text instead of failing"
+    );
+    assert_eq!(
+        search_rows("oat mil", "0").len(),
+        1,
+        "disabled fts should fall back to contains matching"
+    );
+}
+
+#[test]
+fn search_command_flags_fuzzy_fallback_rows_as_approximate() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let search = |query: &str, threshold: &str| {
+        let output = Command::new(bin())
+            .args([
+                "search", "--db", db_path, "--query", query, "--mode", "json",
+            ])
+            .env("MEMO_FUZZY_THRESHOLD", threshold)
+            .output()
+            .expect("search should run");
+        assert!(output.status.success(), "search should succeed");
+        let payload: Value = serde_json::from_slice(&output.stdout).expect("search payload json");
+        assert_json_success_envelope(&payload, "memo.search");
+        payload
+            .get("result")
+            .and_then(Value::as_array)
+            .expect("search result rows")
+            .clone()
+    };
+
+    let add = Command::new(bin())
+        .args(["add", "--db", db_path, "--text", "plumber appointment"])
+        .output()
+        .expect("add should run");
+    assert!(add.status.success(), "add should succeed");
+
+    let rows = search("plumbr", "60");
+    assert_eq!(rows.len(), 1, "typo should produce one approximate row");
+    assert_eq!(rows[0].get("approximate"), Some(&Value::Bool(true)));
+
+    let exact = search("plumber", "60");
+    assert_eq!(exact[0].get("approximate"), Some(&Value::Bool(false)));
+
+    assert!(
+        search("plumbr", "0").is_empty(),
+        "threshold 0 disables fuzzy"
+    );
+
+    let invalid = Command::new(bin())
+        .args(["search", "--db", db_path, "--query", "plumbr"])
+        .env("MEMO_FUZZY_THRESHOLD", "101")
+        .output()
+        .expect("search should run");
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
 #[test]
 fn script_filter_search_intent_uses_env_default_match_mode() {
     let dir = tempdir().expect("temp dir");
@@ -964,6 +1189,1447 @@ fn update_delete_invalid_item_id_returns_usage_error() {
     );
 }
 
+#[test]
+fn add_tags_flag_and_tag_command_list_tagged_memos() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+
+    let add_output = Command::new(bin())
+        .args([
+            "add",
+            "--db",
+            db_arg,
+            "--text",
+            "ship release #Work",
+            "--tags",
+            "urgent,#work",
+            "--mode",
+            "json",
+        ])
+        .output()
+        .expect("add should run");
+    assert!(add_output.status.success(), "add must exit 0");
+    let add_payload: Value =
+        serde_json::from_slice(&add_output.stdout).expect("add stdout must be JSON");
+    assert_json_success_envelope(&add_payload, "memo.add");
+    let tags = add_payload
+        .get("result")
+        .and_then(|result| result.get("tags"))
+        .and_then(Value::as_array)
+        .expect("add result should include tags");
+    assert_eq!(tags, &vec![Value::from("urgent"), Value::from("work")]);
+
+    let tag_output = Command::new(bin())
+        .args(["tag", "--db", db_arg, "--name", "#URGENT", "--mode", "json"])
+        .output()
+        .expect("tag should run");
+    assert!(tag_output.status.success(), "tag must exit 0");
+    let tag_payload: Value =
+        serde_json::from_slice(&tag_output.stdout).expect("tag stdout must be JSON");
+    assert_json_success_envelope(&tag_payload, "memo.tag");
+    let rows = tag_payload
+        .get("result")
+        .and_then(Value::as_array)
+        .expect("tag result should be an array");
+    assert_eq!(rows.len(), 1);
+
+    let counts_output = Command::new(bin())
+        .args(["tag", "--db", db_arg])
+        .output()
+        .expect("tag counts should run");
+    assert!(counts_output.status.success(), "tag counts must exit 0");
+    let counts_text = String::from_utf8_lossy(&counts_output.stdout);
+    assert_eq!(counts_text.trim(), "#urgent 1\n#work 1");
+
+    let invalid_output = Command::new(bin())
+        .args(["add", "--db", db_arg, "--text", "x", "--tags", "bad!tag"])
+        .output()
+        .expect("add should run");
+    assert_eq!(invalid_output.status.code(), Some(2));
+}
+
+#[test]
+fn delete_trash_restore_and_purge_roundtrip() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_TRASH_RETENTION_DAYS", "7")
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&[
+        "add",
+        "--db",
+        db_arg,
+        "--text",
+        "soft delete me",
+        "--mode",
+        "json",
+    ]);
+    assert!(add.status.success(), "add should succeed");
+    let add_payload: Value = serde_json::from_slice(&add.stdout).expect("add json");
+    let item_id = add_payload
+        .get("result")
+        .and_then(|result| result.get("item_id"))
+        .and_then(Value::as_str)
+        .expect("item id")
+        .to_string();
+
+    let delete = run(&[
+        "delete",
+        "--db",
+        db_arg,
+        "--item-id",
+        &item_id,
+        "--mode",
+        "json",
+    ]);
+    assert!(delete.status.success(), "delete should succeed");
+    let delete_payload: Value = serde_json::from_slice(&delete.stdout).expect("delete json");
+    assert_json_success_envelope(&delete_payload, "memo.delete");
+    let delete_result = delete_payload.get("result").expect("delete result");
+    assert_eq!(
+        delete_result.get("state").and_then(Value::as_str),
+        Some("deleted")
+    );
+    assert!(
+        delete_result
+            .get("purge_after")
+            .and_then(Value::as_str)
+            .is_some()
+    );
+
+    let trash = run(&["trash", "--db", db_arg, "--mode", "json"]);
+    assert!(trash.status.success(), "trash should succeed");
+    let trash_payload: Value = serde_json::from_slice(&trash.stdout).expect("trash json");
+    assert_json_success_envelope(&trash_payload, "memo.trash");
+    let trash_rows = trash_payload
+        .get("result")
+        .and_then(Value::as_array)
+        .expect("trash rows");
+    assert_eq!(trash_rows.len(), 1);
+
+    let restore_token = format!("restore::{item_id}");
+    let restore = run(&["action", "--db", db_arg, "--token", &restore_token]);
+    assert!(restore.status.success(), "restore action should succeed");
+    assert!(String::from_utf8_lossy(&restore.stdout).starts_with("restored "));
+
+    let purge = run(&["purge", "--db", db_arg, "--item-id", &item_id]);
+    assert!(purge.status.success(), "purge should succeed");
+    assert!(String::from_utf8_lossy(&purge.stdout).starts_with("purged "));
+
+    let purge_again = run(&["purge", "--db", db_arg, "--item-id", &item_id]);
+    assert_eq!(purge_again.status.code(), Some(2));
+}
+
+#[test]
+fn pin_command_and_action_tokens_toggle_pinned_state() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&["add", "--db", db_arg, "--text", "pin me", "--mode", "json"]);
+    assert!(add.status.success(), "add should succeed");
+    let add_payload: Value = serde_json::from_slice(&add.stdout).expect("add json");
+    let item_id = add_payload
+        .get("result")
+        .and_then(|result| result.get("item_id"))
+        .and_then(Value::as_str)
+        .expect("item id")
+        .to_string();
+
+    let pin = run(&[
+        "pin",
+        "--db",
+        db_arg,
+        "--item-id",
+        &item_id,
+        "--mode",
+        "json",
+    ]);
+    assert!(pin.status.success(), "pin should succeed");
+    let pin_payload: Value = serde_json::from_slice(&pin.stdout).expect("pin json");
+    assert_json_success_envelope(&pin_payload, "memo.pin");
+    let pin_result = pin_payload.get("result").expect("pin result");
+    assert_eq!(
+        pin_result.get("is_pinned").and_then(Value::as_bool),
+        Some(true)
+    );
+    assert_eq!(
+        pin_result.get("changed").and_then(Value::as_bool),
+        Some(true)
+    );
+
+    let pin_token = format!("pin::{item_id}");
+    let pin_again = run(&["action", "--db", db_arg, "--token", &pin_token]);
+    assert!(pin_again.status.success(), "pin action should succeed");
+    assert!(String::from_utf8_lossy(&pin_again.stdout).starts_with("already pinned "));
+
+    let unpin_token = format!("unpin::{item_id}");
+    let unpin = run(&["action", "--db", db_arg, "--token", &unpin_token]);
+    assert!(unpin.status.success(), "unpin action should succeed");
+    assert!(String::from_utf8_lossy(&unpin.stdout).starts_with("unpinned "));
+
+    let missing = run(&["pin", "--db", db_arg, "--item-id", "itm_99999999"]);
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn export_command_and_action_token_write_files() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+    let export_dir = dir.path().join("exports");
+
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_EXPORT_DIR", &export_dir)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&["add", "--db", db_arg, "--text", "export me #backup"]);
+    assert!(add.status.success(), "add should succeed");
+
+    let output = dir.path().join("memos.md");
+    let output_arg = output.to_str().expect("output path");
+    let export = run(&[
+        "export", "--db", db_arg, "--format", "md", "--output", output_arg, "--mode", "json",
+    ]);
+    assert!(export.status.success(), "export should succeed");
+    let payload: Value = serde_json::from_slice(&export.stdout).expect("export json");
+    assert_json_success_envelope(&payload, "memo.export");
+    assert_eq!(
+        payload
+            .get("result")
+            .and_then(|result| result.get("exported"))
+            .and_then(Value::as_u64),
+        Some(1)
+    );
+    let markdown = std::fs::read_to_string(&output).expect("read markdown export");
+    assert!(markdown.contains("- tags: #backup\n\nexport me #backup\n"));
+
+    let action = run(&["action", "--db", db_arg, "--token", "export::csv::backup"]);
+    assert!(action.status.success(), "export action should succeed");
+    assert!(String::from_utf8_lossy(&action.stdout).starts_with("exported 1 memos to "));
+    let exported = std::fs::read_dir(&export_dir)
+        .expect("export dir")
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(exported.len(), 1);
+    assert!(exported[0].starts_with("memo-export-") && exported[0].ends_with(".csv"));
+
+    let invalid = run(&["action", "--db", db_arg, "--token", "export::xml"]);
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
+#[test]
+fn import_command_reports_imported_and_skipped_counts() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+    let input = dir.path().join("drafts.json");
+    std::fs::write(
+        &input,
+        r#"[{"content":"draft one","created_at":"2025-05-01T08:00:00Z"},{"content":"draft one"}]"#,
+    )
+    .expect("write import file");
+    let input_arg = input.to_str().expect("input path");
+
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let import = run(&[
+        "import", "--db", db_arg, "--input", input_arg, "--mode", "json",
+    ]);
+    assert!(import.status.success(), "import should succeed");
+    let payload: Value = serde_json::from_slice(&import.stdout).expect("import json");
+    assert_json_success_envelope(&payload, "memo.import");
+    let result = payload.get("result").expect("import result");
+    assert_eq!(result.get("imported").and_then(Value::as_u64), Some(1));
+    assert_eq!(result.get("skipped").and_then(Value::as_u64), Some(1));
+
+    let again = run(&["import", "--db", db_arg, "--input", input_arg]);
+    assert!(again.status.success(), "re-import should succeed");
+    assert!(String::from_utf8_lossy(&again.stdout).starts_with("imported 0 memos from "));
+
+    let list = run(&["list", "--db", db_arg, "--mode", "json"]);
+    let list_payload: Value = serde_json::from_slice(&list.stdout).expect("list json");
+    let rows = list_payload
+        .get("result")
+        .and_then(Value::as_array)
+        .expect("list rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        rows[0].get("created_at").and_then(Value::as_str),
+        Some("2025-05-01T08:00:00.000Z")
+    );
+
+    let broken = dir.path().join("broken.json");
+    std::fs::write(&broken, "{not json").expect("write broken file");
+    let broken_import = run(&[
+        "import",
+        "--db",
+        db_arg,
+        "--input",
+        broken.to_str().expect("broken path"),
+    ]);
+    assert_eq!(broken_import.status.code(), Some(2));
+}
+
+#[test]
+fn edit_command_and_action_token_apply_editor_changes() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+    let script = dir.path().join("editor.sh");
+    std::fs::write(&script, "printf 'edited by script' > \"$1\"\n").expect("write editor");
+    let editor = format!("sh '{}'", script.display());
+
+    let run = |args: &[&str], editor: &str| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_EDITOR", editor)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(
+        &["add", "--db", db_arg, "--text", "edit me", "--mode", "json"],
+        &editor,
+    );
+    assert!(add.status.success(), "add should succeed");
+    let add_payload: Value = serde_json::from_slice(&add.stdout).expect("add json");
+    let item_id = add_payload
+        .get("result")
+        .and_then(|result| result.get("item_id"))
+        .and_then(Value::as_str)
+        .expect("item id")
+        .to_string();
+
+    let edit = run(
+        &[
+            "edit",
+            "--db",
+            db_arg,
+            "--item-id",
+            &item_id,
+            "--mode",
+            "json",
+        ],
+        &editor,
+    );
+    assert!(edit.status.success(), "edit should succeed");
+    let payload: Value = serde_json::from_slice(&edit.stdout).expect("edit json");
+    assert_json_success_envelope(&payload, "memo.edit");
+    assert_eq!(
+        payload
+            .get("result")
+            .and_then(|result| result.get("changed"))
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+
+    let edit_token = format!("edit::{item_id}");
+    let noop = run(&["action", "--db", db_arg, "--token", &edit_token], &editor);
+    assert!(noop.status.success(), "edit action should succeed");
+    assert!(String::from_utf8_lossy(&noop.stdout).starts_with("unchanged "));
+
+    let failing = run(&["edit", "--db", db_arg, "--item-id", &item_id], "false");
+    assert_eq!(failing.status.code(), Some(1));
+}
+
+#[test]
+fn attach_command_and_open_attachment_token_resolve_locations() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+    let file = dir.path().join("notes.txt");
+    std::fs::write(&file, "attached").expect("write attachment");
+    let canonical = std::fs::canonicalize(&file).expect("canonical path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&[
+        "add",
+        "--db",
+        db_arg,
+        "--text",
+        "with file",
+        "--mode",
+        "json",
+    ]);
+    assert!(add.status.success(), "add should succeed");
+    let add_payload: Value = serde_json::from_slice(&add.stdout).expect("add json");
+    let item_id = add_payload
+        .get("result")
+        .and_then(|result| result.get("item_id"))
+        .and_then(Value::as_str)
+        .expect("item id")
+        .to_string();
+
+    let attach_token = format!("attach::{item_id}::{}", file.display());
+    let attach = run(&[
+        "action",
+        "--db",
+        db_arg,
+        "--token",
+        &attach_token,
+        "--mode",
+        "json",
+    ]);
+    assert!(attach.status.success(), "attach action should succeed");
+    let payload: Value = serde_json::from_slice(&attach.stdout).expect("attach json");
+    assert_json_success_envelope(&payload, "memo.action");
+    let result = payload.get("result").expect("result");
+    assert_eq!(
+        result.get("location").and_then(Value::as_str),
+        canonical.to_str()
+    );
+    let attachment_id = result
+        .get("attachment_id")
+        .and_then(Value::as_i64)
+        .expect("attachment id");
+
+    let open_token = format!("open-attachment::{attachment_id}");
+    let open = run(&["action", "--db", db_arg, "--token", &open_token]);
+    assert!(open.status.success(), "open-attachment should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&open.stdout).trim(),
+        canonical.to_string_lossy()
+    );
+
+    let list = run(&[
+        "attachments",
+        "--db",
+        db_arg,
+        "--item-id",
+        &item_id,
+        "--mode",
+        "json",
+    ]);
+    let payload: Value = serde_json::from_slice(&list.stdout).expect("attachments json");
+    assert_json_success_envelope(&payload, "memo.attachments");
+    assert_eq!(
+        payload
+            .get("result")
+            .and_then(Value::as_array)
+            .map(Vec::len),
+        Some(1)
+    );
+
+    let missing = dir.path().join("missing.txt");
+    let missing_path = missing.to_str().expect("missing path");
+    let rejected = run(&[
+        "attach",
+        "--db",
+        db_arg,
+        "--item-id",
+        &item_id,
+        "--path",
+        missing_path,
+    ]);
+    assert_eq!(rejected.status.code(), Some(2));
+}
+
+#[test]
+fn remind_command_and_due_list_report_overdue_memos() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_arg = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&[
+        "add",
+        "--db",
+        db_arg,
+        "--text",
+        "call dentist",
+        "--mode",
+        "json",
+    ]);
+    assert!(add.status.success(), "add should succeed");
+    let add_payload: Value = serde_json::from_slice(&add.stdout).expect("add json");
+    let item_id = add_payload
+        .get("result")
+        .and_then(|result| result.get("item_id"))
+        .and_then(Value::as_str)
+        .expect("item id")
+        .to_string();
+
+    let remind = run(&[
+        "remind",
+        "--db",
+        db_arg,
+        "--item-id",
+        &item_id,
+        "--at",
+        "tomorrow 9am",
+        "--mode",
+        "json",
+    ]);
+    assert!(remind.status.success(), "remind should succeed");
+    let payload: Value = serde_json::from_slice(&remind.stdout).expect("remind json");
+    assert_json_success_envelope(&payload, "memo.remind");
+
+    let token = format!("remind::{item_id}::2001-02-03T04:05:06Z");
+    let action = run(&["action", "--db", db_arg, "--token", &token]);
+    assert!(action.status.success(), "remind action should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&action.stdout).trim(),
+        format!("reminder set {item_id} at 2001-02-03T04:05:06.000Z (overdue)")
+    );
+
+    let due = run(&["due", "--db", db_arg, "--mode", "json"]);
+    let payload: Value = serde_json::from_slice(&due.stdout).expect("due json");
+    assert_json_success_envelope(&payload, "memo.due");
+    let rows = payload
+        .get("result")
+        .and_then(Value::as_array)
+        .expect("due rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        rows[0].get("status").and_then(Value::as_str),
+        Some("overdue")
+    );
+
+    let invalid = run(&[
+        "remind",
+        "--db",
+        db_arg,
+        "--item-id",
+        &item_id,
+        "--at",
+        "whenever",
+    ]);
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
+#[test]
+fn template_commands_and_action_token_save_and_expand_templates() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&[
+        "template",
+        "add",
+        "--db",
+        db_path,
+        "--name",
+        "todo",
+        "--text",
+        "Todo {date}",
+        "--mode",
+        "json",
+    ]);
+    assert!(add.status.success(), "template add should succeed");
+    let payload: Value = serde_json::from_slice(&add.stdout).expect("template add json");
+    assert_json_success_envelope(&payload, "memo.template.add");
+    assert_eq!(payload["result"]["created"], Value::Bool(true));
+
+    let action = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "template-add::standup::Standup {weekday}",
+    ]);
+    assert!(action.status.success(), "template-add token should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&action.stdout).trim(),
+        "saved template standup"
+    );
+
+    let list = run(&["template", "list", "--db", db_path, "--mode", "json"]);
+    let payload: Value = serde_json::from_slice(&list.stdout).expect("template list json");
+    assert_json_success_envelope(&payload, "memo.template.list");
+    let names = payload["result"]
+        .as_array()
+        .expect("template rows")
+        .iter()
+        .filter_map(|row| row.get("name").and_then(Value::as_str))
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["standup", "todo"]);
+
+    let used = run(&["template", "use", "--db", db_path, "--name", "todo"]);
+    assert!(used.status.success(), "template use should succeed");
+    let text = String::from_utf8_lossy(&used.stdout).trim().to_string();
+    assert!(
+        text.starts_with("Todo 20"),
+        "date placeholder should expand: {text}"
+    );
+
+    let missing = run(&["template", "use", "--db", db_path, "--name", "nope"]);
+    assert_eq!(
+        missing.status.code(),
+        Some(2),
+        "unknown template is a user error"
+    );
+}
+
+#[test]
+fn db_encrypt_and_decrypt_commands_use_passphrase_env() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |passphrase: &str, args: &[&str]| {
+        Command::new(bin())
+            .env("MEMO_ENCRYPTION_PASSPHRASE", passphrase)
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(
+        "s3cret",
+        &["add", "--db", db_path, "--text", "pick up dry cleaning"],
+    );
+    assert!(add.status.success(), "add should succeed");
+
+    let encrypt = run("s3cret", &["db-encrypt", "--db", db_path, "--mode", "json"]);
+    assert!(encrypt.status.success(), "db-encrypt should succeed");
+    let payload: Value = serde_json::from_slice(&encrypt.stdout).expect("db-encrypt json");
+    assert_json_success_envelope(&payload, "memo.db_encrypt");
+    assert_eq!(payload["result"]["encrypted"], Value::Bool(true));
+    assert_eq!(payload["result"]["items"], Value::from(1));
+    let file = std::fs::read(&db).expect("read db file");
+    assert!(
+        !file
+            .windows("dry cleaning".len())
+            .any(|window| window == b"dry cleaning"),
+        "plaintext should not remain in the db file"
+    );
+
+    let search = run(
+        "s3cret",
+        &[
+            "search", "--db", db_path, "--query", "clean*", "--mode", "json",
+        ],
+    );
+    let payload: Value = serde_json::from_slice(&search.stdout).expect("search json");
+    assert_json_success_envelope(&payload, "memo.search");
+    assert_eq!(
+        payload["result"][0]["text_preview"],
+        Value::from("pick up dry cleaning")
+    );
+
+    let wrong = run("guess", &["list", "--db", db_path]);
+    assert_eq!(wrong.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&wrong.stderr).contains("passphrase does not match"));
+
+    let decrypt = run("s3cret", &["db-decrypt", "--db", db_path]);
+    assert!(decrypt.status.success(), "db-decrypt should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&decrypt.stdout).trim(),
+        format!("decrypted 1 memos in {db_path}")
+    );
+    let list = run("guess", &["list", "--db", db_path]);
+    assert!(list.status.success(), "plaintext db ignores the passphrase");
+    assert!(String::from_utf8_lossy(&list.stdout).contains("pick up dry cleaning"));
+}
+
+#[test]
+fn sync_command_exports_commits_and_reports_tree_changes() {
+    let dir = tempdir().expect("temp dir");
+    let repo = dir.path().join("repo");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let git = Command::new("git")
+        .args(["init", "-q"])
+        .arg(&repo)
+        .status()
+        .expect("git init should run");
+    assert!(git.success(), "git init should succeed");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .env("MEMO_SYNC_DIR", &repo)
+            .env("GIT_AUTHOR_NAME", "memo")
+            .env("GIT_AUTHOR_EMAIL", "memo@example.com")
+            .env("GIT_COMMITTER_NAME", "memo")
+            .env("GIT_COMMITTER_EMAIL", "memo@example.com")
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&["add", "--db", db_path, "--text", "sync me #travel"]);
+    assert!(add.status.success(), "add should succeed");
+
+    let sync = run(&["sync", "--db", db_path, "--mode", "json"]);
+    assert!(sync.status.success(), "sync should succeed");
+    let payload: Value = serde_json::from_slice(&sync.stdout).expect("sync json");
+    assert_json_success_envelope(&payload, "memo.sync");
+    assert_eq!(payload["result"]["written"], Value::from(1));
+    assert_eq!(payload["result"]["committed"], Value::Bool(true));
+    assert_eq!(payload["result"]["pulled"], Value::Bool(false));
+
+    let file = std::fs::read_dir(repo.join("memos"))
+        .expect("memo tree")
+        .next()
+        .expect("one memo file")
+        .expect("dir entry")
+        .path();
+    let record: Value =
+        serde_json::from_slice(&std::fs::read(file).expect("read memo file")).expect("memo json");
+    assert_eq!(record["text"], Value::from("sync me #travel"));
+    assert_eq!(record["tags"], serde_json::json!(["travel"]));
+
+    let action = run(&["action", "--token", "sync", "--db", db_path]);
+    assert!(action.status.success(), "sync action should succeed");
+    assert!(String::from_utf8_lossy(&action.stdout).contains("wrote 0"));
+
+    let missing = Command::new(bin())
+        .args(["sync", "--db", db_path])
+        .output()
+        .expect("command should run");
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn stats_command_emits_totals_weeks_and_tags_envelope() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&["add", "--db", db_path, "--text", "weekly review #work"]);
+    assert!(add.status.success(), "add should succeed");
+
+    let stats = run(&["stats", "--db", db_path, "--weeks", "2", "--mode", "json"]);
+    assert!(stats.status.success(), "stats should succeed");
+    let payload: Value = serde_json::from_slice(&stats.stdout).expect("stats json");
+    assert_json_success_envelope(&payload, "memo.stats");
+    assert_eq!(payload["result"]["live"], Value::from(1));
+    assert_eq!(
+        payload["result"]["sources"][0]["source"],
+        Value::from("alfred")
+    );
+    assert_eq!(payload["result"]["weeks"].as_array().map(Vec::len), Some(2));
+    assert_eq!(payload["result"]["weeks"][0]["item_count"], Value::from(1));
+    assert_eq!(payload["result"]["tags"][0]["tag"], Value::from("work"));
+    assert!(
+        payload["result"]["db_size_bytes"]
+            .as_u64()
+            .is_some_and(|size| size > 0)
+    );
+
+    let invalid = run(&["stats", "--db", db_path, "--weeks", "60"]);
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
+#[test]
+fn batch_command_reports_per_item_results_and_action_token_applies() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    for text in ["buy milk #home", "buy bread #home", "standup notes #work"] {
+        let add = run(&["add", "--db", db_path, "--text", text]);
+        assert!(add.status.success(), "add should succeed");
+    }
+
+    let pinned = run(&[
+        "batch",
+        "--db",
+        db_path,
+        "--action",
+        "pin",
+        "--tag",
+        "home",
+        "--item-id",
+        "1",
+        "--mode",
+        "json",
+    ]);
+    assert!(pinned.status.success(), "batch pin should succeed");
+    let payload: Value = serde_json::from_slice(&pinned.stdout).expect("batch json");
+    assert_json_success_envelope(&payload, "memo.batch");
+    assert_eq!(payload["result"]["action"], Value::from("pin"));
+    assert_eq!(payload["result"]["succeeded"], Value::from(2));
+    assert_eq!(payload["result"]["failed"], Value::from(0));
+    assert_eq!(
+        payload["result"]["items"][0]["item_id"],
+        Value::from("itm_00000001")
+    );
+
+    let deleted = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "batch::delete::itm_00000002,itm_00000003",
+        "--mode",
+        "json",
+    ]);
+    assert!(deleted.status.success(), "batch token should succeed");
+    let payload: Value = serde_json::from_slice(&deleted.stdout).expect("batch action json");
+    assert_json_success_envelope(&payload, "memo.action");
+    assert_eq!(payload["result"]["succeeded"], Value::from(2));
+
+    let skipped = run(&[
+        "batch",
+        "--db",
+        db_path,
+        "--action",
+        "unpin",
+        "--item-id",
+        "3",
+    ]);
+    assert!(skipped.status.success(), "skips are reported, not fatal");
+    let text = String::from_utf8_lossy(&skipped.stdout);
+    assert!(text.contains("batch unpin: 0 succeeded, 1 failed"));
+    assert!(text.contains("itm_00000003 skipped: item is in trash"));
+
+    let invalid = run(&[
+        "batch",
+        "--db",
+        db_path,
+        "--action",
+        "delete",
+        "--item-id",
+        "abc",
+    ]);
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
+#[test]
+fn links_command_lists_linked_memos_and_open_link_token_prints_url() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    for text in [
+        "plain memo",
+        "see <https://example.com/a>, then www.rust-lang.org.",
+    ] {
+        let add = run(&["add", "--db", db_path, "--text", text]);
+        assert!(add.status.success(), "add should succeed");
+    }
+
+    let links = run(&["links", "--db", db_path, "--mode", "json"]);
+    assert!(links.status.success(), "links should succeed");
+    let payload: Value = serde_json::from_slice(&links.stdout).expect("links json");
+    assert_json_success_envelope(&payload, "memo.links");
+    assert_eq!(payload["result"].as_array().map(Vec::len), Some(1));
+    assert_eq!(payload["result"][0]["item_id"], Value::from("itm_00000002"));
+    assert_eq!(
+        payload["result"][0]["links"],
+        serde_json::json!(["https://example.com/a", "https://www.rust-lang.org"])
+    );
+
+    let opened = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "open-link::itm_00000002::2",
+    ]);
+    assert!(opened.status.success(), "open-link should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&opened.stdout).trim(),
+        "https://www.rust-lang.org"
+    );
+
+    let missing = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "open-link::itm_00000001::1",
+    ]);
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn link_command_and_action_token_link_memos_both_ways() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    for text in ["spec draft", "review notes", "release plan"] {
+        let add = run(&["add", "--db", db_path, "--text", text]);
+        assert!(add.status.success(), "add should succeed");
+    }
+
+    let link = run(&[
+        "link",
+        "--db",
+        db_path,
+        "--item-id",
+        "1",
+        "--other-id",
+        "itm_00000002",
+        "--mode",
+        "json",
+    ]);
+    assert!(link.status.success(), "link should succeed");
+    let payload: Value = serde_json::from_slice(&link.stdout).expect("link json");
+    assert_json_success_envelope(&payload, "memo.link");
+    assert_eq!(
+        payload["result"]["linked_item_id"],
+        Value::from("itm_00000002")
+    );
+    assert_eq!(payload["result"]["changed"], Value::Bool(true));
+
+    let action = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "link::itm_00000003::itm_00000002",
+    ]);
+    assert!(action.status.success(), "link action should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&action.stdout).trim(),
+        "linked itm_00000003 <-> itm_00000002 (linked=1)"
+    );
+
+    let linked = run(&[
+        "linked",
+        "--db",
+        db_path,
+        "--item-id",
+        "2",
+        "--mode",
+        "json",
+    ]);
+    assert!(linked.status.success(), "linked should succeed");
+    let payload: Value = serde_json::from_slice(&linked.stdout).expect("linked json");
+    assert_json_success_envelope(&payload, "memo.linked");
+    let ids = payload["result"]
+        .as_array()
+        .expect("linked rows")
+        .iter()
+        .map(|row| row["item_id"].as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&"itm_00000001".to_string()));
+    assert!(ids.contains(&"itm_00000003".to_string()));
+
+    let itself = run(&["link", "--db", db_path, "--item-id", "1", "--other-id", "1"]);
+    assert_eq!(itself.status.code(), Some(2));
+}
+
+#[test]
+fn history_command_and_restore_token_bring_back_earlier_text() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&["add", "--db", db_path, "--text", "first draft"]);
+    assert!(add.status.success(), "add should succeed");
+    let update = run(&[
+        "update",
+        "--db",
+        db_path,
+        "--item-id",
+        "1",
+        "--text",
+        "second draft",
+    ]);
+    assert!(update.status.success(), "update should succeed");
+
+    let history = run(&[
+        "history",
+        "--db",
+        db_path,
+        "--item-id",
+        "itm_00000001",
+        "--mode",
+        "json",
+    ]);
+    assert!(history.status.success(), "history should succeed");
+    let payload: Value = serde_json::from_slice(&history.stdout).expect("history json");
+    assert_json_success_envelope(&payload, "memo.history");
+    assert_eq!(payload["result"].as_array().map(Vec::len), Some(1));
+    assert_eq!(payload["result"][0]["text"], Value::from("first draft"));
+    let version_id = payload["result"][0]["version_id"]
+        .as_i64()
+        .expect("version id");
+
+    let token = format!("history-restore::{version_id}");
+    let restore = run(&["action", "--db", db_path, "--token", &token]);
+    assert!(restore.status.success(), "history-restore should succeed");
+    assert!(String::from_utf8_lossy(&restore.stdout).starts_with("restored itm_00000001"));
+
+    let history = run(&["history", "--db", db_path, "--item-id", "1"]);
+    let text = String::from_utf8_lossy(&history.stdout);
+    assert!(
+        text.lines()
+            .next()
+            .is_some_and(|line| line.ends_with("second draft"))
+    );
+
+    let missing = run(&["history-restore", "--db", db_path, "--version-id", "42"]);
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn merge_command_and_action_token_fold_memos_and_trash_the_merged_one() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    for text in ["agenda", "action items", "open questions"] {
+        let add = run(&["add", "--db", db_path, "--text", text]);
+        assert!(add.status.success(), "add should succeed");
+    }
+
+    let merge = run(&[
+        "merge",
+        "--db",
+        db_path,
+        "--item-id",
+        "1",
+        "--merge-id",
+        "itm_00000002",
+        "--mode",
+        "json",
+    ]);
+    assert!(merge.status.success(), "merge should succeed");
+    let payload: Value = serde_json::from_slice(&merge.stdout).expect("merge json");
+    assert_json_success_envelope(&payload, "memo.merge");
+    assert_eq!(payload["result"]["item_id"], Value::from("itm_00000001"));
+    assert_eq!(
+        payload["result"]["merged_item_id"],
+        Value::from("itm_00000002")
+    );
+    assert_eq!(
+        payload["result"]["text"],
+        Value::from("agenda\n\naction items")
+    );
+
+    let action = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "merge::itm_00000001::itm_00000003",
+    ]);
+    assert!(action.status.success(), "merge action should succeed");
+    let stdout = String::from_utf8_lossy(&action.stdout);
+    assert!(stdout.starts_with("merged itm_00000003 into itm_00000001 at "));
+
+    let trash = run(&["trash", "--db", db_path, "--mode", "json"]);
+    assert!(trash.status.success(), "trash should succeed");
+    let payload: Value = serde_json::from_slice(&trash.stdout).expect("trash json");
+    assert_eq!(payload["result"].as_array().map(Vec::len), Some(2));
+
+    let trashed = run(&[
+        "merge",
+        "--db",
+        db_path,
+        "--item-id",
+        "1",
+        "--merge-id",
+        "2",
+    ]);
+    assert_eq!(trashed.status.code(), Some(2));
+    let itself = run(&[
+        "merge",
+        "--db",
+        db_path,
+        "--item-id",
+        "1",
+        "--merge-id",
+        "1",
+    ]);
+    assert_eq!(itself.status.code(), Some(2));
+}
+
+#[test]
+fn copy_md_and_copy_html_tokens_emit_sanitized_html() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&[
+        "add",
+        "--db",
+        db_path,
+        "--text",
+        "# Plan\n\n- [read](https://example.com)\n- [bad](javascript:alert(1))",
+    ]);
+    assert!(add.status.success(), "add should succeed");
+
+    let rich = run(&[
+        "action",
+        "--db",
+        db_path,
+        "--token",
+        "copy-md::itm_00000001",
+        "--mode",
+        "json",
+    ]);
+    assert!(rich.status.success(), "copy-md should succeed");
+    let payload: Value = serde_json::from_slice(&rich.stdout).expect("copy-md json");
+    assert_json_success_envelope(&payload, "memo.action");
+    let html = payload["result"]["html"].as_str().expect("html string");
+    assert!(html.starts_with("<h1>Plan</h1>"));
+    assert!(html.contains("<a href=\"https://example.com\">read</a>"));
+    assert!(html.contains("<a href=\"\">bad</a>"));
+
+    let source = run(&["action", "--db", db_path, "--token", "copy-html::1"]);
+    assert!(source.status.success(), "copy-html should succeed");
+    assert_eq!(
+        String::from_utf8_lossy(&source.stdout).trim_end(),
+        html.trim_end()
+    );
+
+    let invalid = run(&["action", "--db", db_path, "--token", "copy-md::abc"]);
+    assert_eq!(invalid.status.code(), Some(2));
+}
+
+#[test]
+fn db_backup_and_db_restore_commands_round_trip_snapshots() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let snapshots = dir.path().join("snapshots");
+    let snapshots_path = snapshots.to_str().expect("snapshots path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .output()
+            .expect("command should run")
+    };
+
+    let add = run(&["add", "--db", db_path, "--text", "before backup"]);
+    assert!(add.status.success(), "add should succeed");
+    let backup = run(&[
+        "db-backup",
+        "--db",
+        db_path,
+        "--dir",
+        snapshots_path,
+        "--mode",
+        "json",
+    ]);
+    assert!(backup.status.success(), "db-backup should succeed");
+    let payload: Value = serde_json::from_slice(&backup.stdout).expect("db-backup json");
+    assert_json_success_envelope(&payload, "memo.db_backup");
+    let snapshot = payload["result"]["snapshot"]
+        .as_str()
+        .expect("snapshot path")
+        .to_string();
+    assert!(snapshot.starts_with(snapshots_path) && snapshot.ends_with(".db"));
+
+    let add = run(&["add", "--db", db_path, "--text", "after backup"]);
+    assert!(add.status.success(), "add should succeed");
+    let restore = run(&[
+        "db-restore",
+        "--db",
+        db_path,
+        "--snapshot",
+        &snapshot,
+        "--mode",
+        "json",
+    ]);
+    assert!(restore.status.success(), "db-restore should succeed");
+    let payload: Value = serde_json::from_slice(&restore.stdout).expect("db-restore json");
+    assert_json_success_envelope(&payload, "memo.db_restore");
+    assert_eq!(payload["result"]["items"], Value::from(1));
+    assert!(
+        payload["result"]["safety_backup"]
+            .as_str()
+            .is_some_and(
+                |path| path.contains("/backups/memo-") && path.ends_with("-auto-restore.db")
+            )
+    );
+
+    let missing = run(&["db-restore", "--db", db_path, "--snapshot", "memo-none.db"]);
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn undo_command_and_action_token_revert_latest_operation() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_DB_PATH", db_path)
+            .output()
+            .expect("command should run")
+    };
+
+    assert!(run(&["add", "--text", "first"]).status.success());
+    assert!(
+        run(&["delete", "--item-id", "itm_00000001"])
+            .status
+            .success()
+    );
+
+    let filter = run(&["script-filter", "--query", "undo"]);
+    assert!(filter.status.success(), "undo intent should succeed");
+    let feedback: Value = serde_json::from_slice(&filter.stdout).expect("feedback json");
+    let token = feedback["items"][0]["arg"]
+        .as_str()
+        .expect("undo token")
+        .to_string();
+    assert!(token.starts_with("undo::"));
+
+    let action = run(&["action", "--token", &token, "--mode", "json"]);
+    assert!(action.status.success(), "undo action should succeed");
+    let payload: Value = serde_json::from_slice(&action.stdout).expect("action json");
+    assert_json_success_envelope(&payload, "memo.action");
+    assert_eq!(payload["result"]["state"], Value::from("restored"));
+
+    let undo = run(&["undo", "--mode", "json"]);
+    assert!(undo.status.success(), "undo should succeed");
+    let payload: Value = serde_json::from_slice(&undo.stdout).expect("undo json");
+    assert_json_success_envelope(&payload, "memo.undo");
+    assert_eq!(payload["result"]["operation"], Value::from("add"));
+    assert_eq!(payload["result"]["state"], Value::from("purged"));
+
+    assert_eq!(run(&["undo"]).status.code(), Some(2));
+    assert_eq!(run(&["action", "--token", &token]).status.code(), Some(2));
+}
+
+#[cfg(unix)]
+#[test]
+fn clip_intent_and_command_save_clipboard_text_with_clipboard_source() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let bin_dir = dir.path().join("bin");
+    std::fs::create_dir(&bin_dir).expect("bin dir");
+    let pbpaste = bin_dir.join("pbpaste");
+    let set_clipboard = |script: &str| {
+        std::fs::write(&pbpaste, format!("#!/bin/sh\n{script}\n")).expect("write pbpaste");
+        std::fs::set_permissions(&pbpaste, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod pbpaste");
+    };
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_DB_PATH", db_path)
+            .env("PATH", &path)
+            .output()
+            .expect("command should run")
+    };
+
+    set_clipboard("printf 'copied text #clip\\n'");
+    let filter = run(&["script-filter", "--query", "clip"]);
+    assert!(filter.status.success(), "clip intent should succeed");
+    let feedback: Value = serde_json::from_slice(&filter.stdout).expect("feedback json");
+    assert_eq!(
+        feedback["items"][0]["arg"],
+        Value::from("clip::copied text #clip")
+    );
+    let action = run(&[
+        "action",
+        "--token",
+        feedback["items"][0]["arg"].as_str().expect("clip token"),
+        "--mode",
+        "json",
+    ]);
+    assert!(action.status.success(), "clip action should succeed");
+    let payload: Value = serde_json::from_slice(&action.stdout).expect("action json");
+    assert_eq!(payload["result"]["source"], Value::from("clipboard"));
+
+    let clip = run(&["clip", "--mode", "json"]);
+    assert!(clip.status.success(), "clip should succeed");
+    let payload: Value = serde_json::from_slice(&clip.stdout).expect("clip json");
+    assert_json_success_envelope(&payload, "memo.clip");
+    assert_eq!(payload["result"]["tags"], serde_json::json!(["clip"]));
+
+    set_clipboard("printf 'a\\000b'");
+    let filter = run(&["script-filter", "--query", "clip"]);
+    let feedback: Value = serde_json::from_slice(&filter.stdout).expect("feedback json");
+    assert_eq!(
+        feedback["items"][0]["title"],
+        Value::from("Clipboard does not hold text")
+    );
+    assert_eq!(run(&["clip"]).status.code(), Some(2));
+
+    set_clipboard("printf '   '");
+    let filter = run(&["script-filter", "--query", "clip"]);
+    let feedback: Value = serde_json::from_slice(&filter.stdout).expect("feedback json");
+    assert_eq!(
+        feedback["items"][0]["title"],
+        Value::from("Clipboard is empty")
+    );
+    assert_eq!(feedback["items"][0]["valid"], Value::from(false));
+}
+
+#[test]
+fn search_operators_filter_by_source_and_date_from_the_cli() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_DB_PATH", db_path)
+            .output()
+            .expect("command should run")
+    };
+
+    assert!(run(&["add", "--text", "milk from alfred"]).status.success());
+    assert!(
+        run(&["add", "--text", "milk from sync", "--source", "sync"])
+            .status
+            .success()
+    );
+
+    let filtered = run(&["search", "--query", "milk source:sync", "--mode", "json"]);
+    assert!(filtered.status.success(), "filtered search should succeed");
+    let payload: Value = serde_json::from_slice(&filtered.stdout).expect("search json");
+    assert_json_success_envelope(&payload, "memo.search");
+    let rows = payload["result"].as_array().expect("search rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["item_id"], Value::from("itm_00000002"));
+
+    let operators_only = run(&["search", "--query", "after:today", "--mode", "json"]);
+    assert!(
+        operators_only.status.success(),
+        "operator-only search should succeed"
+    );
+    let payload: Value = serde_json::from_slice(&operators_only.stdout).expect("search json");
+    assert_eq!(payload["result"].as_array().map(Vec::len), Some(2));
+
+    let invalid = run(&["search", "--query", "milk before:soon", "--mode", "json"]);
+    assert_eq!(invalid.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&invalid.stderr);
+    assert!(
+        stderr.contains("invalid search operator before:soon"),
+        "stderr must name the invalid operator, got: {stderr}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn derive_command_stores_enrichment_and_flips_state_to_enriched() {
+    let dir = tempdir().expect("temp dir");
+    let db = dir.path().join("memo.db");
+    let db_path = db.to_str().expect("db path");
+    let script = dir.path().join("enrich.sh");
+    std::fs::write(
+        &script,
+        "cat >/dev/null\nprintf '{\"summary\":\"Milk run\",\"category\":\"errands\"}'\n",
+    )
+    .expect("write script");
+    let run = |args: &[&str]| {
+        Command::new(bin())
+            .args(args)
+            .env("MEMO_DB_PATH", db_path)
+            .env("MEMO_DERIVE_COMMAND", format!("sh {}", script.display()))
+            .output()
+            .expect("command should run")
+    };
+
+    assert!(run(&["add", "--text", "buy milk"]).status.success());
+    let derive = run(&["derive", "--item-id", "itm_00000001", "--mode", "json"]);
+    assert!(derive.status.success(), "derive should succeed");
+    let payload: Value = serde_json::from_slice(&derive.stdout).expect("derive json");
+    assert_json_success_envelope(&payload, "memo.derive");
+    assert_eq!(payload["result"]["changed"], Value::Bool(true));
+    assert_eq!(payload["result"]["summary"], Value::from("Milk run"));
+
+    let listed = run(&["list", "--mode", "json"]);
+    let payload: Value = serde_json::from_slice(&listed.stdout).expect("list json");
+    assert_eq!(payload["result"][0]["state"], Value::from("enriched"));
+
+    let invalid = run(&[
+        "derive",
+        "--item-id",
+        "itm_00000001",
+        "--command",
+        "echo [1]",
+    ]);
+    assert_eq!(invalid.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&invalid.stderr);
+    assert!(
+        stderr.contains("invalid derivation payload: output must be a JSON object"),
+        "stderr must explain the payload error, got: {stderr}"
+    );
+}
+
 fn resolve_cli_path() -> PathBuf {
     if let Some(path) = std::env::var_os("CARGO_BIN_EXE_memo-workflow-cli") {
         return PathBuf::from(path);
@@ -0,0 +1,164 @@
+use rand::{Rng, RngExt};
+
+use crate::locale::Locale;
+
+// `(city, state, ZIP prefix)`; the prefix keeps ZIP codes inside the real range for that city.
+const EN_US_CITIES: &[(&str, &str, &str)] = &[
+    ("Springfield", "IL", "627"),
+    ("Portland", "OR", "972"),
+    ("Austin", "TX", "787"),
+    ("Denver", "CO", "802"),
+    ("Columbus", "OH", "432"),
+    ("Madison", "WI", "537"),
+    ("Raleigh", "NC", "276"),
+    ("Sacramento", "CA", "958"),
+    ("Boise", "ID", "837"),
+    ("Albany", "NY", "122"),
+    ("Richmond", "VA", "232"),
+    ("Nashville", "TN", "372"),
+];
+
+const EN_US_STREETS: &[&str] = &[
+    "Maple",
+    "Oak",
+    "Pine",
+    "Cedar",
+    "Elm",
+    "Washington",
+    "Lake",
+    "Hill",
+    "Park",
+    "Main",
+    "Sunset",
+    "Lincoln",
+    "Jackson",
+    "River",
+    "Highland",
+    "Church",
+];
+
+const EN_US_STREET_SUFFIXES: &[&str] = &["St", "Ave", "Rd", "Blvd", "Ln", "Dr", "Ct", "Way"];
+
+// `(city, district, postal code)` with the 3-digit postal code of that district.
+const ZH_TW_DISTRICTS: &[(&str, &str, &str)] = &[
+    ("臺北市", "中正區", "100"),
+    ("臺北市", "大安區", "106"),
+    ("臺北市", "信義區", "110"),
+    ("臺北市", "內湖區", "114"),
+    ("新北市", "板橋區", "220"),
+    ("新北市", "新店區", "231"),
+    ("桃園市", "桃園區", "330"),
+    ("新竹市", "東區", "300"),
+    ("臺中市", "西屯區", "407"),
+    ("臺南市", "東區", "701"),
+    ("高雄市", "苓雅區", "802"),
+    ("花蓮縣", "花蓮市", "970"),
+];
+
+const ZH_TW_ROADS: &[&str] = &[
+    "中山路",
+    "中正路",
+    "民生路",
+    "民權路",
+    "忠孝東路",
+    "仁愛路",
+    "信義路",
+    "和平東路",
+    "復興南路",
+    "建國北路",
+    "光復路",
+    "文化路",
+    "成功路",
+    "自由路",
+];
+
+const ZH_TW_SECTIONS: &[&str] = &["一段", "二段", "三段", "四段"];
+
+/// A generated postal address split into the parts shipping forms ask for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Address {
+    pub(crate) street: String,
+    pub(crate) postal_code: String,
+    pub(crate) full: String,
+}
+
+pub(crate) fn random_address<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> Address {
+    match locale {
+        Locale::EnUs => {
+            let (city, state, zip_prefix) = EN_US_CITIES[rng.random_range(0..EN_US_CITIES.len())];
+            let street = format!(
+                "{} {} {}",
+                rng.random_range(1..10_000u32),
+                EN_US_STREETS[rng.random_range(0..EN_US_STREETS.len())],
+                EN_US_STREET_SUFFIXES[rng.random_range(0..EN_US_STREET_SUFFIXES.len())]
+            );
+            let postal_code = format!("{zip_prefix}{:02}", rng.random_range(0..100u8));
+            Address {
+                full: format!("{street}, {city}, {state} {postal_code}"),
+                street,
+                postal_code,
+            }
+        }
+        Locale::ZhTw => {
+            let (city, district, postal_code) =
+                ZH_TW_DISTRICTS[rng.random_range(0..ZH_TW_DISTRICTS.len())];
+            let section = if rng.random_bool(0.5) {
+                ZH_TW_SECTIONS[rng.random_range(0..ZH_TW_SECTIONS.len())]
+            } else {
+                ""
+            };
+            let street = format!(
+                "{}{section}{}號{}樓",
+                ZH_TW_ROADS[rng.random_range(0..ZH_TW_ROADS.len())],
+                rng.random_range(1..400u32),
+                rng.random_range(1..16u8)
+            );
+            Address {
+                full: format!("{postal_code}{city}{district}{street}"),
+                street,
+                postal_code: postal_code.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn en_us_address_has_street_city_state_and_city_zip() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let address = random_address(&mut rng, Locale::EnUs);
+            let parts: Vec<&str> = address.full.split(", ").collect();
+            assert_eq!(parts.len(), 3);
+            assert_eq!(parts[0], address.street);
+
+            let (state, zip) = parts[2].split_once(' ').expect("state and zip");
+            let (_, _, zip_prefix) = EN_US_CITIES
+                .iter()
+                .find(|(city, city_state, _)| *city == parts[1] && *city_state == state)
+                .expect("known city");
+            assert_eq!(zip, address.postal_code);
+            assert_eq!(zip.len(), 5);
+            assert!(zip.starts_with(zip_prefix));
+        }
+    }
+
+    #[test]
+    fn zh_tw_address_starts_with_district_postal_code() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let address = random_address(&mut rng, Locale::ZhTw);
+            assert!(ZH_TW_DISTRICTS.iter().any(|(city, district, code)| {
+                address.full == format!("{code}{city}{district}{}", address.street)
+                    && address.postal_code == *code
+            }));
+            assert!(address.street.contains('號') && address.street.ends_with('樓'));
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use chrono::{Datelike, NaiveDate};
+use rand::{Rng, RngExt};
+
+const ALL_BRANDS: [CardBrand; 4] = [
+    CardBrand::Visa,
+    CardBrand::Mastercard,
+    CardBrand::Amex,
+    CardBrand::Jcb,
+];
+
+/// Card network for the `card` format, selected with a query argument such as `card visa`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardBrand {
+    Visa,
+    Mastercard,
+    Amex,
+    Jcb,
+}
+
+impl CardBrand {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "visa" => Some(Self::Visa),
+            "mastercard" | "master" | "mc" => Some(Self::Mastercard),
+            "amex" | "americanexpress" => Some(Self::Amex),
+            "jcb" => Some(Self::Jcb),
+            _ => None,
+        }
+    }
+
+    /// Query argument for this brand, e.g. `visa`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Visa => "visa",
+            Self::Mastercard => "mastercard",
+            Self::Amex => "amex",
+            Self::Jcb => "jcb",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Visa => "Visa",
+            Self::Mastercard => "Mastercard",
+            Self::Amex => "Amex",
+            Self::Jcb => "JCB",
+        }
+    }
+
+    fn length(self) -> usize {
+        match self {
+            Self::Amex => 15,
+            Self::Visa | Self::Mastercard | Self::Jcb => 16,
+        }
+    }
+
+    fn groups(self) -> &'static [usize] {
+        match self {
+            Self::Amex => &[4, 6, 5],
+            Self::Visa | Self::Mastercard | Self::Jcb => &[4, 4, 4, 4],
+        }
+    }
+
+    fn cvc_length(self) -> usize {
+        match self {
+            Self::Amex => 4,
+            Self::Visa | Self::Mastercard | Self::Jcb => 3,
+        }
+    }
+
+    /// Issuer prefix inside the brand's test-friendly IIN range.
+    fn random_prefix<R: Rng + ?Sized>(self, rng: &mut R) -> u32 {
+        match self {
+            Self::Visa => 4,
+            Self::Mastercard => {
+                if rng.random_bool(0.5) {
+                    rng.random_range(51..=55)
+                } else {
+                    rng.random_range(2221..=2720)
+                }
+            }
+            Self::Amex => {
+                if rng.random_bool(0.5) {
+                    34
+                } else {
+                    37
+                }
+            }
+            Self::Jcb => rng.random_range(3528..=3589),
+        }
+    }
+}
+
+/// A generated card: grouped number, digits-only number, `MM/YY` expiry, and CVC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Card {
+    pub(crate) brand: CardBrand,
+    pub(crate) number: String,
+    pub(crate) digits: String,
+    pub(crate) expiry: String,
+    pub(crate) cvc: String,
+}
+
+pub(crate) fn random_card<R: Rng + ?Sized>(
+    rng: &mut R,
+    brand: Option<CardBrand>,
+    today: NaiveDate,
+) -> Card {
+    let brand = brand.unwrap_or_else(|| ALL_BRANDS[rng.random_range(0..ALL_BRANDS.len())]);
+
+    let mut body: Vec<u32> = brand
+        .random_prefix(rng)
+        .to_string()
+        .bytes()
+        .map(|byte| u32::from(byte - b'0'))
+        .collect();
+    while body.len() < brand.length() - 1 {
+        body.push(u32::from(rng.random_range(0..=9u8)));
+    }
+    let check = luhn_check_digit(&body);
+    body.push(check);
+
+    // Every entry is a single decimal digit, so the cast cannot truncate.
+    let digits: String = body
+        .iter()
+        .map(|digit| char::from(b'0' + *digit as u8))
+        .collect();
+    let mut number = String::with_capacity(digits.len() + brand.groups().len());
+    let mut start = 0;
+    for size in brand.groups() {
+        if start > 0 {
+            number.push(' ');
+        }
+        number.push_str(&digits[start..start + size]);
+        start += size;
+    }
+
+    let expiry_year = today.year() + rng.random_range(1..=5);
+    let expiry = format!(
+        "{:02}/{:02}",
+        rng.random_range(1..=12u8),
+        expiry_year.rem_euclid(100)
+    );
+    let cvc = (0..brand.cvc_length())
+        .map(|_| char::from(b'0' + rng.random_range(0..=9u8)))
+        .collect();
+
+    Card {
+        brand,
+        number,
+        digits,
+        expiry,
+        cvc,
+    }
+}
+
+/// Luhn check digit for `body` (all digits except the check digit).
+pub(crate) fn luhn_check_digit(body: &[u32]) -> u32 {
+    let sum: u32 = body
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| {
+            if index % 2 == 0 {
+                let doubled = digit * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                *digit
+            }
+        })
+        .sum();
+
+    (10 - sum % 10) % 10
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 15).expect("valid date")
+    }
+
+    fn luhn_is_valid(digits: &str) -> bool {
+        let values: Vec<u32> = digits
+            .chars()
+            .map(|ch| ch.to_digit(10).expect("digit"))
+            .collect();
+        let (body, check) = values.split_at(values.len() - 1);
+        luhn_check_digit(body) == check[0]
+    }
+
+    #[test]
+    fn luhn_check_digit_matches_known_test_numbers() {
+        assert!(luhn_is_valid("4111111111111111"));
+        assert!(luhn_is_valid("5555555555554444"));
+        assert!(luhn_is_valid("378282246310005"));
+        assert!(luhn_is_valid("3530111333300000"));
+        assert!(!luhn_is_valid("4111111111111112"));
+    }
+
+    #[test]
+    fn cards_have_brand_prefix_length_grouping_and_valid_checksum() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for brand in ALL_BRANDS {
+            for _ in 0..100 {
+                let card = random_card(&mut rng, Some(brand), today());
+                assert_eq!(card.brand, brand);
+                assert_eq!(card.digits.len(), brand.length());
+                assert!(luhn_is_valid(&card.digits));
+                assert_eq!(card.number.replace(' ', ""), card.digits);
+                let groups: Vec<usize> = card.number.split(' ').map(str::len).collect();
+                assert_eq!(groups, brand.groups());
+
+                let prefix2: u32 = card.digits[..2].parse().expect("prefix");
+                let prefix4: u32 = card.digits[..4].parse().expect("prefix");
+                match brand {
+                    CardBrand::Visa => assert!(card.digits.starts_with('4')),
+                    CardBrand::Mastercard => {
+                        assert!((51..=55).contains(&prefix2) || (2221..=2720).contains(&prefix4))
+                    }
+                    CardBrand::Amex => assert!(prefix2 == 34 || prefix2 == 37),
+                    CardBrand::Jcb => assert!((3528..=3589).contains(&prefix4)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn expiry_is_a_future_month_and_cvc_matches_brand() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let card = random_card(&mut rng, None, today());
+            let (month, year) = card.expiry.split_once('/').expect("MM/YY");
+            let month: u32 = month.parse().expect("month");
+            let year: i32 = year.parse().expect("year");
+            assert!((1..=12).contains(&month));
+            assert!((27..=31).contains(&year));
+            assert_eq!(card.cvc.len(), card.brand.cvc_length());
+            assert!(card.cvc.chars().all(|ch| ch.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn parse_accepts_brand_aliases() {
+        assert_eq!(CardBrand::parse("VISA"), Some(CardBrand::Visa));
+        assert_eq!(CardBrand::parse("mc"), Some(CardBrand::Mastercard));
+        assert_eq!(CardBrand::parse("amex"), Some(CardBrand::Amex));
+        assert_eq!(CardBrand::parse("jcb"), Some(CardBrand::Jcb));
+        assert_eq!(CardBrand::parse("discover"), None);
+    }
+}
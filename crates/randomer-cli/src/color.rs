@@ -0,0 +1,160 @@
+use rand::{Rng, RngExt};
+
+/// Lightness constraint for the `color` format, selected with a query argument such as `color pastel`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorTone {
+    /// Uniform over the RGB cube.
+    #[default]
+    Any,
+    /// Light, softly saturated colors.
+    Pastel,
+    /// Low-lightness colors that keep white text readable.
+    Dark,
+}
+
+impl ColorTone {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "pastel" | "light" => Some(Self::Pastel),
+            "dark" => Some(Self::Dark),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::Pastel => "pastel",
+            Self::Dark => "dark",
+        }
+    }
+}
+
+/// A generated color in the three notations the workflow offers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Color {
+    pub(crate) hex: String,
+    pub(crate) rgb: String,
+    pub(crate) hsl: String,
+}
+
+pub(crate) fn random_color<R: Rng + ?Sized>(rng: &mut R, tone: ColorTone) -> Color {
+    let rgb = match tone {
+        ColorTone::Any => [rng.random(), rng.random(), rng.random()],
+        ColorTone::Pastel => hsl_to_rgb(
+            rng.random_range(0.0..360.0),
+            rng.random_range(0.40..0.75),
+            rng.random_range(0.75..0.90),
+        ),
+        ColorTone::Dark => hsl_to_rgb(
+            rng.random_range(0.0..360.0),
+            rng.random_range(0.30..0.80),
+            rng.random_range(0.10..0.30),
+        ),
+    };
+    let [r, g, b] = rgb;
+    let (h, s, l) = rgb_to_hsl(rgb);
+
+    Color {
+        hex: format!("#{r:02X}{g:02X}{b:02X}"),
+        rgb: format!("rgb({r}, {g}, {b})"),
+        hsl: format!(
+            "hsl({:.0}, {:.0}%, {:.0}%)",
+            h.round() % 360.0,
+            s * 100.0,
+            l * 100.0
+        ),
+    }
+}
+
+/// `h` in degrees, `s` and `l` in `0.0..=1.0`.
+fn hsl_to_rgb(h: f64, s: f64, l: f64) -> [u8; 3] {
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let sector = h / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    // Each channel is within `0.0..=1.0`, so the scaled value fits in `u8`.
+    [r, g, b].map(|channel| ((channel + m) * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+/// `(h degrees, s, l)` with `s` and `l` in `0.0..=1.0`.
+fn rgb_to_hsl([r, g, b]: [u8; 3]) -> (f64, f64, f64) {
+    let [r, g, b] = [r, g, b].map(|channel| f64::from(channel) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, l);
+    }
+
+    let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (h, s, l)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn hex_channels(hex: &str) -> [u8; 3] {
+        let digits = hex.strip_prefix('#').expect("# prefix");
+        assert_eq!(digits.len(), 6);
+        [0, 2, 4].map(|start| u8::from_str_radix(&digits[start..start + 2], 16).expect("hex"))
+    }
+
+    #[test]
+    fn conversions_match_known_colors() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), [255, 0, 0]);
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.25), [0, 128, 0]);
+        assert_eq!(hsl_to_rgb(210.0, 0.5, 0.5), [64, 128, 191]);
+        let (h, s, l) = rgb_to_hsl([64, 128, 191]);
+        assert_eq!(
+            (h.round(), (s * 100.0).round(), (l * 100.0).round()),
+            (210.0, 50.0, 50.0)
+        );
+        assert_eq!(rgb_to_hsl([128, 128, 128]).1, 0.0);
+    }
+
+    #[test]
+    fn notations_describe_the_same_color() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..200 {
+            let color = random_color(&mut rng, ColorTone::Any);
+            let [r, g, b] = hex_channels(&color.hex);
+            assert_eq!(color.rgb, format!("rgb({r}, {g}, {b})"));
+            assert!(color.hsl.starts_with("hsl(") && color.hsl.ends_with("%)"));
+        }
+    }
+
+    #[test]
+    fn tones_constrain_lightness() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let pastel = hex_channels(&random_color(&mut rng, ColorTone::Pastel).hex);
+            let (_, _, lightness) = rgb_to_hsl(pastel);
+            assert!((0.74..=0.91).contains(&lightness), "{lightness}");
+
+            let dark = hex_channels(&random_color(&mut rng, ColorTone::Dark).hex);
+            let (_, _, lightness) = rgb_to_hsl(dark);
+            assert!((0.09..=0.31).contains(&lightness), "{lightness}");
+        }
+    }
+}
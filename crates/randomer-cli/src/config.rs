@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::card::CardBrand;
+use crate::color::ColorTone;
+use crate::date::DateRange;
+use crate::iban::IbanCountry;
+use crate::locale::Locale;
+use crate::lorem::LoremSpec;
+use crate::mac::MacStyle;
+use crate::net::{Ipv4Scope, Ipv6Scope};
+use crate::number::{CurrencySpec, DecimalSpec, NumberRange};
+use crate::password::{
+    DEFAULT_PASSWORD_LENGTH, PASSWORD_LENGTH_MAX, PASSWORD_LENGTH_MIN, PasswordPolicy,
+};
+use crate::phone::PhoneRegion;
+use crate::template::Template;
+use crate::token::DEFAULT_SECRET_BYTES;
+use crate::{Format, RandomerError};
+
+const MAX_COUNT_ENV: &str = "RANDOMER_MAX_COUNT";
+const SEED_ENV: &str = "RANDOMER_SEED";
+const LOCALE_ENV: &str = "RANDOMER_LOCALE";
+const PASSWORD_LENGTH_ENV: &str = "RANDOMER_PASSWORD_LENGTH";
+const PASSWORD_CLASSES_ENV: &str = "RANDOMER_PASSWORD_CLASSES";
+const IBAN_COUNTRIES_ENV: &str = "RANDOMER_IBAN_COUNTRIES";
+const TEMPLATE_ENV_PREFIX: &str = "RANDOMER_TEMPLATE_";
+const TEMPLATE_ENV_FIELD: &str = "RANDOMER_TEMPLATE_<NAME>";
+
+const MAX_COUNT_MIN: i64 = 1;
+const MAX_COUNT_MAX: i64 = 500;
+
+/// Reference time for seeded runs (2025-01-01T00:00:00Z), so time-based values stay reproducible.
+pub(crate) const SEEDED_NOW_TIMESTAMP: i64 = 1_735_689_600;
+
+/// Values generated when a list is expanded without an explicit count.
+pub const DEFAULT_EXPAND_COUNT: usize = 10;
+/// Upper bound for a query or `--count` count unless `RANDOMER_MAX_COUNT` overrides it.
+pub const DEFAULT_MAX_COUNT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    pub max_count: usize,
+    /// Seed for reproducible output; `None` draws from the thread-local RNG.
+    pub seed: Option<u64>,
+    pub password: PasswordPolicy,
+    pub locale: Locale,
+    /// `RANDOMER_LOCALE` was set; only an explicit locale moves the default `phone` region off Taiwan.
+    pub locale_explicit: bool,
+    /// `iban` countries; empty means every bundled country.
+    pub iban_countries: Vec<IbanCountry>,
+    /// `lorem` size; only set from the query (`lorem 3p`).
+    pub lorem: LoremSpec,
+    /// `card` brand; only set from the query (`card visa`). `None` picks a brand per value.
+    pub card_brand: Option<CardBrand>,
+    /// `mac` separator style; only set from the query (`mac dash`).
+    pub mac_style: MacStyle,
+    /// `ipv4` / `ipv6` scopes and CIDR output; only set from the query (`ipv4 public cidr`).
+    pub ipv4_scope: Ipv4Scope,
+    pub ipv6_scope: Ipv6Scope,
+    pub ip_cidr: bool,
+    /// `date` / `datetime` range; only set from the query (`date 2020..2024`).
+    pub date_range: DateRange,
+    /// `color` lightness constraint; only set from the query (`color pastel`).
+    pub color_tone: ColorTone,
+    /// `secret` byte length; only set from the query (`secret 48`).
+    pub secret_bytes: usize,
+    /// `int` / `decimal` / `currency` ranges, precision, and currency; only set from the query
+    /// (`int 1..1000`, `decimal 0..1 4dp`, `currency 10..500 eur`).
+    pub int_range: Option<NumberRange>,
+    pub decimal: DecimalSpec,
+    pub currency: CurrencySpec,
+    /// `phone` numbering plan from the query (`phone jp`); `None` follows the locale.
+    pub phone_region: Option<PhoneRegion>,
+    /// Reference time for time-based values; `None` reads the system clock.
+    pub now: Option<DateTime<Utc>>,
+    /// `RANDOMER_TEMPLATE_<NAME>` formats, sorted by name.
+    pub templates: Vec<&'static Template>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            max_count: DEFAULT_MAX_COUNT,
+            seed: None,
+            password: PasswordPolicy::default(),
+            locale: Locale::default(),
+            locale_explicit: false,
+            iban_countries: Vec::new(),
+            lorem: LoremSpec::default(),
+            card_brand: None,
+            mac_style: MacStyle::default(),
+            ipv4_scope: Ipv4Scope::default(),
+            ipv6_scope: Ipv6Scope::default(),
+            ip_cidr: false,
+            date_range: DateRange::default(),
+            color_tone: ColorTone::default(),
+            secret_bytes: DEFAULT_SECRET_BYTES,
+            int_range: None,
+            decimal: DecimalSpec::default(),
+            currency: CurrencySpec::default(),
+            phone_region: None,
+            now: None,
+            templates: Vec::new(),
+        }
+    }
+}
+
+impl RuntimeConfig {
+    /// `phone` numbering plan: the query region, else the one matching an explicit `RANDOMER_LOCALE`, else
+    /// Taiwan.
+    pub fn phone_region(&self) -> PhoneRegion {
+        self.phone_region.unwrap_or_else(|| {
+            if self.locale_explicit {
+                PhoneRegion::for_locale(self.locale)
+            } else {
+                PhoneRegion::Tw
+            }
+        })
+    }
+
+    /// Configured template named `name` (case-insensitive).
+    pub fn template(&self, name: &str) -> Option<&'static Template> {
+        let name = name.trim().to_ascii_lowercase();
+        self.templates
+            .iter()
+            .copied()
+            .find(|template| template.name() == name)
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now.unwrap_or_else(Utc::now)
+    }
+
+    /// This config with `now` pinned to `SEEDED_NOW_TIMESTAMP` unless already set, for seeded runs.
+    pub(crate) fn with_pinned_clock(&self) -> Self {
+        Self {
+            now: self
+                .now
+                .or_else(|| DateTime::from_timestamp(SEEDED_NOW_TIMESTAMP, 0)),
+            ..self.clone()
+        }
+    }
+
+    pub fn from_env() -> Result<Self, RandomerError> {
+        Self::from_pairs(std::env::vars())
+    }
+
+    pub(crate) fn from_pairs<I, K, V>(pairs: I) -> Result<Self, RandomerError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let env_map: HashMap<String, String> = pairs
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        let locale = parse_locale(env_map.get(LOCALE_ENV).map(String::as_str))?;
+
+        Ok(Self {
+            max_count: parse_clamped_count(
+                env_map.get(MAX_COUNT_ENV).map(String::as_str),
+                DEFAULT_MAX_COUNT,
+                MAX_COUNT_MIN,
+                MAX_COUNT_MAX,
+                MAX_COUNT_ENV,
+            )?,
+            seed: parse_seed(env_map.get(SEED_ENV).map(String::as_str))?,
+            password: parse_password_policy(
+                env_map.get(PASSWORD_LENGTH_ENV).map(String::as_str),
+                env_map.get(PASSWORD_CLASSES_ENV).map(String::as_str),
+            )?,
+            locale: locale.unwrap_or_default(),
+            locale_explicit: locale.is_some(),
+            iban_countries: parse_iban_countries(
+                env_map.get(IBAN_COUNTRIES_ENV).map(String::as_str),
+            )?,
+            lorem: LoremSpec::default(),
+            card_brand: None,
+            mac_style: MacStyle::default(),
+            ipv4_scope: Ipv4Scope::default(),
+            ipv6_scope: Ipv6Scope::default(),
+            ip_cidr: false,
+            date_range: DateRange::default(),
+            color_tone: ColorTone::default(),
+            secret_bytes: DEFAULT_SECRET_BYTES,
+            int_range: None,
+            decimal: DecimalSpec::default(),
+            currency: CurrencySpec::default(),
+            phone_region: None,
+            now: None,
+            templates: parse_templates(&env_map)?,
+        })
+    }
+}
+
+fn parse_clamped_count(
+    raw: Option<&str>,
+    default: usize,
+    min: i64,
+    max: i64,
+    field: &'static str,
+) -> Result<usize, RandomerError> {
+    let Some(value) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(default);
+    };
+
+    let parsed = value
+        .parse::<i64>()
+        .map_err(|_| RandomerError::InvalidConfig {
+            field,
+            value: value.to_string(),
+        })?;
+
+    // Clamped into `min..=max`, both positive, so the cast cannot wrap.
+    Ok(parsed.clamp(min, max) as usize)
+}
+
+fn parse_seed(raw: Option<&str>) -> Result<Option<u64>, RandomerError> {
+    let Some(value) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+
+    value
+        .parse::<u64>()
+        .map(Some)
+        .map_err(|_| RandomerError::InvalidConfig {
+            field: SEED_ENV,
+            value: value.to_string(),
+        })
+}
+
+/// `None` when `RANDOMER_LOCALE` is unset or blank.
+fn parse_locale(raw: Option<&str>) -> Result<Option<Locale>, RandomerError> {
+    let Some(value) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+
+    Locale::parse(value)
+        .map(Some)
+        .ok_or_else(|| RandomerError::InvalidConfig {
+            field: LOCALE_ENV,
+            value: value.to_string(),
+        })
+}
+
+fn parse_iban_countries(raw: Option<&str>) -> Result<Vec<IbanCountry>, RandomerError> {
+    let Some(value) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut countries = Vec::new();
+    for code in value
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|code| !code.is_empty())
+    {
+        let country = IbanCountry::parse(code).ok_or_else(|| RandomerError::InvalidConfig {
+            field: IBAN_COUNTRIES_ENV,
+            value: value.to_string(),
+        })?;
+        if !countries.contains(&country) {
+            countries.push(country);
+        }
+    }
+    Ok(countries)
+}
+
+/// Read every non-empty `RANDOMER_TEMPLATE_<NAME>` variable. Template definitions live for the whole process, so they
+/// are leaked to let `Format::Template` stay `Copy` with a `&'static str` key.
+fn parse_templates(
+    env_map: &HashMap<String, String>,
+) -> Result<Vec<&'static Template>, RandomerError> {
+    let mut templates = Vec::new();
+    for (key, pattern) in env_map {
+        let Some(name) = key.strip_prefix(TEMPLATE_ENV_PREFIX) else {
+            continue;
+        };
+        if pattern.trim().is_empty() {
+            continue;
+        }
+
+        let template = Template::parse(name, pattern)
+            .filter(|template| Format::parse(template.name()).is_none())
+            .ok_or_else(|| RandomerError::InvalidConfig {
+                field: TEMPLATE_ENV_FIELD,
+                value: format!("{key}={pattern}"),
+            })?;
+        templates.push(&*Box::leak(Box::new(template)));
+    }
+    templates.sort_by(|a, b| a.name().cmp(b.name()));
+    Ok(templates)
+}
+
+fn parse_password_policy(
+    length: Option<&str>,
+    classes: Option<&str>,
+) -> Result<PasswordPolicy, RandomerError> {
+    let mut policy = PasswordPolicy {
+        length: parse_clamped_count(
+            length,
+            DEFAULT_PASSWORD_LENGTH,
+            PASSWORD_LENGTH_MIN as i64,
+            PASSWORD_LENGTH_MAX as i64,
+            PASSWORD_LENGTH_ENV,
+        )?,
+        ..PasswordPolicy::default()
+    };
+
+    if let Some(value) = classes.map(str::trim).filter(|value| !value.is_empty()) {
+        let (classes, avoid_ambiguous) =
+            PasswordPolicy::parse_classes(value).ok_or_else(|| RandomerError::InvalidConfig {
+                field: PASSWORD_CLASSES_ENV,
+                value: value.to_string(),
+            })?;
+        policy.classes = classes;
+        policy.avoid_ambiguous = avoid_ambiguous;
+    }
+
+    Ok(policy)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::password::CharClass;
+
+    #[test]
+    fn max_count_defaults_and_clamps_into_range() {
+        let empty: [(&str, &str); 0] = [];
+        assert_eq!(
+            RuntimeConfig::from_pairs(empty).expect("default config"),
+            RuntimeConfig::default()
+        );
+        assert_eq!(
+            RuntimeConfig::from_pairs([(MAX_COUNT_ENV, " 120 ")])
+                .expect("config")
+                .max_count,
+            120
+        );
+        assert_eq!(
+            RuntimeConfig::from_pairs([(MAX_COUNT_ENV, "0")])
+                .expect("config")
+                .max_count,
+            1
+        );
+        assert_eq!(
+            RuntimeConfig::from_pairs([(MAX_COUNT_ENV, "99999")])
+                .expect("config")
+                .max_count,
+            500
+        );
+    }
+
+    #[test]
+    fn max_count_rejects_non_integer_values() {
+        let err = RuntimeConfig::from_pairs([(MAX_COUNT_ENV, "lots")]).expect_err("should fail");
+        assert_eq!(
+            err,
+            RandomerError::InvalidConfig {
+                field: MAX_COUNT_ENV,
+                value: String::from("lots"),
+            }
+        );
+        assert_eq!(err.to_string(), "invalid RANDOMER_MAX_COUNT: lots");
+    }
+
+    #[test]
+    fn seed_is_optional_and_must_be_an_unsigned_integer() {
+        let empty: [(&str, &str); 0] = [];
+        assert_eq!(RuntimeConfig::from_pairs(empty).expect("config").seed, None);
+        assert_eq!(
+            RuntimeConfig::from_pairs([(SEED_ENV, " 42 ")])
+                .expect("config")
+                .seed,
+            Some(42)
+        );
+        assert_eq!(
+            RuntimeConfig::from_pairs([(SEED_ENV, "")])
+                .expect("config")
+                .seed,
+            None
+        );
+
+        let err = RuntimeConfig::from_pairs([(SEED_ENV, "-1")]).expect_err("should fail");
+        assert_eq!(err.to_string(), "invalid RANDOMER_SEED: -1");
+    }
+
+    #[test]
+    fn password_policy_reads_length_and_classes() {
+        let config = RuntimeConfig::from_pairs([
+            (PASSWORD_LENGTH_ENV, "4"),
+            (PASSWORD_CLASSES_ENV, "upper,digits,unambiguous"),
+        ])
+        .expect("config");
+        assert_eq!(
+            config.password,
+            PasswordPolicy {
+                length: PASSWORD_LENGTH_MIN,
+                classes: vec![CharClass::Upper, CharClass::Digits],
+                avoid_ambiguous: true,
+            }
+        );
+
+        let err =
+            RuntimeConfig::from_pairs([(PASSWORD_CLASSES_ENV, "emoji")]).expect_err("should fail");
+        assert_eq!(err.to_string(), "invalid RANDOMER_PASSWORD_CLASSES: emoji");
+    }
+
+    #[test]
+    fn locale_defaults_to_en_us_and_rejects_unknown_values() {
+        let empty: [(&str, &str); 0] = [];
+        assert_eq!(
+            RuntimeConfig::from_pairs(empty).expect("config").locale,
+            Locale::EnUs
+        );
+        assert_eq!(
+            RuntimeConfig::from_pairs([(LOCALE_ENV, "zh_TW")])
+                .expect("config")
+                .locale,
+            Locale::ZhTw
+        );
+
+        let err = RuntimeConfig::from_pairs([(LOCALE_ENV, "klingon")]).expect_err("should fail");
+        assert_eq!(err.to_string(), "invalid RANDOMER_LOCALE: klingon");
+    }
+
+    #[test]
+    fn phone_region_stays_taiwan_unless_locale_is_set() {
+        let empty: [(&str, &str); 0] = [];
+        let config = RuntimeConfig::from_pairs(empty).expect("config");
+        assert_eq!(config.phone_region(), PhoneRegion::Tw);
+        let config = RuntimeConfig::from_pairs([(LOCALE_ENV, " ")]).expect("config");
+        assert_eq!(config.phone_region(), PhoneRegion::Tw);
+
+        let config = RuntimeConfig::from_pairs([(LOCALE_ENV, "en-US")]).expect("config");
+        assert_eq!(config.phone_region(), PhoneRegion::Us);
+
+        let config = RuntimeConfig {
+            phone_region: Some(PhoneRegion::Jp),
+            ..config
+        };
+        assert_eq!(config.phone_region(), PhoneRegion::Jp);
+    }
+
+    #[test]
+    fn templates_are_read_sorted_and_validated() {
+        let config = RuntimeConfig::from_pairs([
+            ("RANDOMER_TEMPLATE_SKU", "SKU-###"),
+            ("RANDOMER_TEMPLATE_ORDER", "ORD-####"),
+            ("RANDOMER_TEMPLATE_EMPTY", ""),
+        ])
+        .expect("config");
+        let names: Vec<&str> = config
+            .templates
+            .iter()
+            .map(|template| template.name())
+            .collect();
+        assert_eq!(names, ["order", "sku"]);
+        assert_eq!(
+            config.template("SKU").map(|template| template.pattern()),
+            Some("SKU-###")
+        );
+
+        let err = RuntimeConfig::from_pairs([("RANDOMER_TEMPLATE_UUID", "###")])
+            .expect_err("built-in name");
+        assert_eq!(
+            err.to_string(),
+            "invalid RANDOMER_TEMPLATE_<NAME>: RANDOMER_TEMPLATE_UUID=###"
+        );
+        assert!(RuntimeConfig::from_pairs([("RANDOMER_TEMPLATE_BAD", r"ORD-\")]).is_err());
+        assert!(RuntimeConfig::from_pairs([("RANDOMER_TEMPLATE_MY ORDER", "ORD-#")]).is_err());
+    }
+
+    #[test]
+    fn iban_countries_parse_codes_and_reject_unknown_ones() {
+        let config =
+            RuntimeConfig::from_pairs([(IBAN_COUNTRIES_ENV, "de, gb DE")]).expect("config");
+        let codes: Vec<&str> = config
+            .iban_countries
+            .iter()
+            .map(|country| country.code())
+            .collect();
+        assert_eq!(codes, ["DE", "GB"]);
+
+        let err =
+            RuntimeConfig::from_pairs([(IBAN_COUNTRIES_ENV, "DE,US")]).expect_err("should fail");
+        assert_eq!(err.to_string(), "invalid RANDOMER_IBAN_COUNTRIES: DE,US");
+    }
+}
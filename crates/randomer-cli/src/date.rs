@@ -0,0 +1,169 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use rand::{Rng, RngExt};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Inclusive day range for the `date` / `datetime` formats, e.g. `2020..2024` or `2024-03-01..2024-03-31`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Default for DateRange {
+    fn default() -> Self {
+        Self {
+            start: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or(NaiveDate::MIN),
+            end: NaiveDate::from_ymd_opt(2030, 12, 31).unwrap_or(NaiveDate::MAX),
+        }
+    }
+}
+
+impl DateRange {
+    /// Parse `<from>..<to>`, where each bound is a year (`2020`) or a date (`2020-06-15`). A year starts on
+    /// January 1 as a lower bound and ends on December 31 as an upper bound. Empty or reversed ranges are rejected.
+    pub fn parse(token: &str) -> Option<Self> {
+        let (start, end) = token.trim().split_once("..")?;
+        let range = Self {
+            start: parse_bound(start, false)?,
+            end: parse_bound(end, true)?,
+        };
+        (range.start <= range.end).then_some(range)
+    }
+
+    /// Query token for this range, using bare years when the range covers whole years.
+    pub fn token(self) -> String {
+        if self.start.ordinal() == 1 && self.end.month() == 12 && self.end.day() == 31 {
+            format!("{}..{}", self.start.year(), self.end.year())
+        } else {
+            format!("{}..{}", self.start, self.end)
+        }
+    }
+
+    fn random_date<R: Rng + ?Sized>(self, rng: &mut R) -> NaiveDate {
+        let span = (self.end - self.start).num_days();
+        self.start + chrono::Days::new(rng.random_range(0..=span).unsigned_abs())
+    }
+}
+
+fn parse_bound(raw: &str, upper: bool) -> Option<NaiveDate> {
+    let raw = raw.trim();
+    if raw.len() == 4 && raw.bytes().all(|byte| byte.is_ascii_digit()) {
+        let year = raw.parse().ok()?;
+        return if upper {
+            NaiveDate::from_ymd_opt(year, 12, 31)
+        } else {
+            NaiveDate::from_ymd_opt(year, 1, 1)
+        };
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok()
+}
+
+/// A generated date or UTC date-time in ISO 8601 form plus its Unix timestamp in seconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Moment {
+    pub(crate) iso: String,
+    pub(crate) epoch: i64,
+}
+
+pub(crate) fn random_date<R: Rng + ?Sized>(rng: &mut R, range: DateRange) -> Moment {
+    let date = range.random_date(rng);
+    Moment {
+        iso: date.format("%Y-%m-%d").to_string(),
+        epoch: date.and_time(NaiveTime::MIN).and_utc().timestamp(),
+    }
+}
+
+pub(crate) fn random_datetime<R: Rng + ?Sized>(rng: &mut R, range: DateRange) -> Moment {
+    let date = range.random_date(rng);
+    let seconds = rng.random_range(0..SECONDS_PER_DAY);
+    let datetime = NaiveDateTime::from(date)
+        .checked_add_signed(chrono::TimeDelta::seconds(seconds))
+        .unwrap_or(NaiveDateTime::from(date));
+    Moment {
+        iso: datetime.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        epoch: datetime.and_utc().timestamp(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use chrono::DateTime;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    #[test]
+    fn parse_reads_year_and_date_bounds() {
+        assert_eq!(
+            DateRange::parse("2020..2024"),
+            Some(DateRange {
+                start: ymd(2020, 1, 1),
+                end: ymd(2024, 12, 31),
+            })
+        );
+        assert_eq!(
+            DateRange::parse("2024-02-10..2024-03"),
+            None,
+            "partial dates are not bounds"
+        );
+        assert_eq!(
+            DateRange::parse("2024-02-10..2025"),
+            Some(DateRange {
+                start: ymd(2024, 2, 10),
+                end: ymd(2025, 12, 31),
+            })
+        );
+        assert_eq!(DateRange::parse("2024..2020"), None);
+        assert_eq!(DateRange::parse("2024-02-30..2025"), None);
+        assert_eq!(DateRange::parse("2024"), None);
+        assert_eq!(
+            DateRange::parse("2020..2024")
+                .map(DateRange::token)
+                .as_deref(),
+            Some("2020..2024")
+        );
+        assert_eq!(
+            DateRange::parse("2024-02-10..2024-02-10")
+                .map(DateRange::token)
+                .as_deref(),
+            Some("2024-02-10..2024-02-10")
+        );
+    }
+
+    #[test]
+    fn dates_stay_inside_range_and_epoch_matches() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let range = DateRange::parse("2020-02-27..2020-03-02").expect("range");
+        for _ in 0..200 {
+            let moment = random_date(&mut rng, range);
+            let date = NaiveDate::parse_from_str(&moment.iso, "%Y-%m-%d").expect("ISO date");
+            assert!(range.start <= date && date <= range.end);
+            assert_eq!(
+                DateTime::from_timestamp(moment.epoch, 0)
+                    .expect("timestamp")
+                    .date_naive(),
+                date
+            );
+        }
+    }
+
+    #[test]
+    fn datetimes_are_utc_iso_8601_inside_range() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let range = DateRange::default();
+        for _ in 0..200 {
+            let moment = random_datetime(&mut rng, range);
+            let parsed = DateTime::parse_from_rfc3339(&moment.iso).expect("RFC 3339 date-time");
+            assert!(moment.iso.ends_with('Z'));
+            assert_eq!(parsed.timestamp(), moment.epoch);
+            let date = parsed.date_naive();
+            assert!(range.start <= date && date <= range.end);
+        }
+    }
+}
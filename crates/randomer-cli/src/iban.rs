@@ -0,0 +1,240 @@
+use rand::{Rng, RngExt};
+
+use Bban::{Alnum, Digits, Upper};
+
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ALNUM: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// One character class run inside a BBAN, in ECBS registry notation (`n` digits, `a` letters, `c` alphanumerics).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bban {
+    Digits(usize),
+    Upper(usize),
+    Alnum(usize),
+}
+
+/// Country with its BBAN structure from the IBAN registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IbanCountry {
+    code: &'static str,
+    bban: &'static [Bban],
+}
+
+pub const IBAN_COUNTRIES: &[IbanCountry] = &[
+    IbanCountry {
+        code: "AT",
+        bban: &[Digits(16)],
+    },
+    IbanCountry {
+        code: "BE",
+        bban: &[Digits(12)],
+    },
+    IbanCountry {
+        code: "CH",
+        bban: &[Digits(5), Alnum(12)],
+    },
+    IbanCountry {
+        code: "DE",
+        bban: &[Digits(18)],
+    },
+    IbanCountry {
+        code: "DK",
+        bban: &[Digits(14)],
+    },
+    IbanCountry {
+        code: "ES",
+        bban: &[Digits(20)],
+    },
+    IbanCountry {
+        code: "FI",
+        bban: &[Digits(14)],
+    },
+    IbanCountry {
+        code: "FR",
+        bban: &[Digits(10), Alnum(11), Digits(2)],
+    },
+    IbanCountry {
+        code: "GB",
+        bban: &[Upper(4), Digits(14)],
+    },
+    IbanCountry {
+        code: "IE",
+        bban: &[Upper(4), Digits(14)],
+    },
+    IbanCountry {
+        code: "IT",
+        bban: &[Upper(1), Digits(10), Alnum(12)],
+    },
+    IbanCountry {
+        code: "LU",
+        bban: &[Digits(3), Alnum(13)],
+    },
+    IbanCountry {
+        code: "NL",
+        bban: &[Upper(4), Digits(10)],
+    },
+    IbanCountry {
+        code: "NO",
+        bban: &[Digits(11)],
+    },
+    IbanCountry {
+        code: "PL",
+        bban: &[Digits(24)],
+    },
+    IbanCountry {
+        code: "PT",
+        bban: &[Digits(21)],
+    },
+    IbanCountry {
+        code: "SE",
+        bban: &[Digits(20)],
+    },
+];
+
+impl IbanCountry {
+    /// Look up a bundled country by ISO 3166-1 alpha-2 code, case-insensitively.
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim();
+        IBAN_COUNTRIES
+            .iter()
+            .find(|country| country.code.eq_ignore_ascii_case(code))
+            .copied()
+    }
+
+    pub fn code(self) -> &'static str {
+        self.code
+    }
+
+    /// Full IBAN length: country code, two check digits, and the BBAN.
+    pub fn length(self) -> usize {
+        4 + self
+            .bban
+            .iter()
+            .map(|run| match run {
+                Digits(len) | Upper(len) | Alnum(len) => len,
+            })
+            .sum::<usize>()
+    }
+}
+
+/// A generated IBAN in electronic form (no spaces) and print form (groups of four).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Iban {
+    pub(crate) country: IbanCountry,
+    pub(crate) electronic: String,
+    pub(crate) print: String,
+}
+
+pub(crate) fn random_iban<R: Rng + ?Sized>(rng: &mut R, countries: &[IbanCountry]) -> Iban {
+    let country = if countries.is_empty() {
+        IBAN_COUNTRIES[rng.random_range(0..IBAN_COUNTRIES.len())]
+    } else {
+        countries[rng.random_range(0..countries.len())]
+    };
+
+    let mut bban = String::with_capacity(country.length() - 4);
+    for run in country.bban {
+        let (alphabet, len) = match *run {
+            Digits(len) => (&ALNUM[..10], len),
+            Upper(len) => (UPPER, len),
+            Alnum(len) => (ALNUM, len),
+        };
+        bban.extend((0..len).map(|_| char::from(alphabet[rng.random_range(0..alphabet.len())])));
+    }
+
+    let check = 98 - mod97(&format!("{bban}{}00", country.code));
+    let electronic = format!("{}{check:02}{bban}", country.code);
+    let print = electronic
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Iban {
+        country,
+        electronic,
+        print,
+    }
+}
+
+/// ISO 7064 MOD 97-10 remainder of an alphanumeric string, with letters expanded to `10..=35`.
+fn mod97(value: &str) -> u32 {
+    value.chars().fold(0, |remainder, ch| {
+        // Only ASCII digits and uppercase letters reach here, so `to_digit(36)` always succeeds.
+        let digit = ch.to_digit(36).unwrap_or(0);
+        if digit < 10 {
+            (remainder * 10 + digit) % 97
+        } else {
+            (remainder * 100 + digit) % 97
+        }
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn iban_is_valid(iban: &str) -> bool {
+        let (head, bban) = iban.split_at(4);
+        mod97(&format!("{bban}{head}")) == 1
+    }
+
+    #[test]
+    fn mod97_accepts_registry_examples() {
+        assert!(iban_is_valid("DE89370400440532013000"));
+        assert!(iban_is_valid("GB29NWBK60161331926819"));
+        assert!(iban_is_valid("FR1420041010050500013M02606"));
+        assert!(!iban_is_valid("DE89370400440532013001"));
+    }
+
+    #[test]
+    fn ibans_match_country_length_shape_and_checksum() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for country in IBAN_COUNTRIES {
+            for _ in 0..50 {
+                let iban = random_iban(&mut rng, &[*country]);
+                assert_eq!(iban.country, *country);
+                assert_eq!(iban.electronic.len(), country.length());
+                assert!(iban.electronic.starts_with(country.code));
+                assert!(
+                    iban.electronic[2..4]
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit())
+                );
+                assert!(
+                    iban.electronic
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit() || byte.is_ascii_uppercase())
+                );
+                assert!(iban_is_valid(&iban.electronic));
+                assert_eq!(iban.print.replace(' ', ""), iban.electronic);
+                assert!(iban.print.split(' ').all(|group| group.len() <= 4));
+            }
+        }
+    }
+
+    #[test]
+    fn registry_lengths_and_lookup() {
+        let length = |code| IbanCountry::parse(code).map(IbanCountry::length);
+        assert_eq!(length("de"), Some(22));
+        assert_eq!(length("GB"), Some(22));
+        assert_eq!(length("FR"), Some(27));
+        assert_eq!(length("NL"), Some(18));
+        assert_eq!(length("NO"), Some(15));
+        assert_eq!(length("PL"), Some(28));
+        assert_eq!(length("US"), None);
+    }
+
+    #[test]
+    fn empty_country_list_draws_from_all_bundled_countries() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let iban = random_iban(&mut rng, &[]);
+            assert!(IBAN_COUNTRIES.contains(&iban.country));
+        }
+    }
+}
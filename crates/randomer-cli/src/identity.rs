@@ -0,0 +1,124 @@
+use rand::{Rng, RngExt};
+
+const ADJECTIVES: &[&str] = &[
+    "amber", "bold", "brave", "bright", "calm", "clever", "cosmic", "crisp", "dapper", "eager",
+    "fancy", "fierce", "gentle", "glad", "golden", "happy", "hidden", "humble", "icy", "jolly",
+    "keen", "lively", "lucky", "merry", "misty", "nimble", "noble", "odd", "plucky", "proud",
+    "quick", "quiet", "rapid", "rosy", "rusty", "shiny", "silent", "silver", "sleepy", "sly",
+    "snowy", "solar", "sparkly", "spry", "steady", "stormy", "sunny", "swift", "tidy", "tiny",
+    "tranquil", "vivid", "warm", "wild", "windy", "witty", "zany", "zesty",
+];
+
+const NOUNS: &[&str] = &[
+    "acorn", "badger", "beacon", "birch", "bison", "breeze", "brook", "canyon", "cedar", "comet",
+    "coral", "crane", "dune", "eagle", "ember", "falcon", "fern", "fjord", "fox", "glacier",
+    "harbor", "hawk", "heron", "island", "lagoon", "lantern", "lark", "maple", "meadow", "meteor",
+    "moose", "nebula", "oak", "orbit", "otter", "owl", "panda", "pebble", "pine", "planet",
+    "prairie", "quartz", "raven", "reef", "river", "robin", "sparrow", "spruce", "summit",
+    "thunder", "tiger", "tundra", "valley", "walrus", "willow", "wolf",
+];
+
+const TLDS: &[&str] = &["com", "net", "org", "io", "dev", "app"];
+
+fn pick<R: Rng + ?Sized>(rng: &mut R, words: &[&'static str]) -> &'static str {
+    words[rng.random_range(0..words.len())]
+}
+
+/// Kebab-case adjective-noun pair, e.g. `quiet-river`.
+pub(crate) fn random_slug<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!("{}-{}", pick(rng, ADJECTIVES), pick(rng, NOUNS))
+}
+
+/// `<adjective>-<noun>.<noun>.<tld>`, e.g. `quiet-river.falcon.dev`. Labels use `[a-z0-9-]` and never start or end
+/// with `-`; the word lists keep every label far below the RFC 1035 limits of 63 characters per label and 253 overall.
+pub(crate) fn random_hostname<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!(
+        "{}.{}.{}",
+        random_slug(rng),
+        pick(rng, NOUNS),
+        pick(rng, TLDS)
+    )
+}
+
+/// `<adjective>_<noun><2 digits>`, e.g. `swift_falcon27`; only `[a-z0-9_]`.
+pub(crate) fn random_username<R: Rng + ?Sized>(rng: &mut R) -> String {
+    format!(
+        "{}_{}{:02}",
+        pick(rng, ADJECTIVES),
+        pick(rng, NOUNS),
+        rng.random_range(0..100u8)
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    const LABEL_LEN_MAX: usize = 63;
+    const HOSTNAME_LEN_MAX: usize = 253;
+
+    #[test]
+    fn word_lists_are_lowercase_ascii_letters() {
+        for word in ADJECTIVES.iter().chain(NOUNS).chain(TLDS) {
+            assert!(
+                !word.is_empty() && word.bytes().all(|byte| byte.is_ascii_lowercase()),
+                "{word}"
+            );
+        }
+    }
+
+    #[test]
+    fn slugs_and_usernames_use_their_character_sets() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..200 {
+            let slug = random_slug(&mut rng);
+            let (adjective, noun) = slug.split_once('-').expect("word pair");
+            assert!(
+                ADJECTIVES.contains(&adjective) && NOUNS.contains(&noun),
+                "{slug}"
+            );
+
+            let username = random_username(&mut rng);
+            assert!(
+                username
+                    .bytes()
+                    .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_'),
+                "{username}"
+            );
+            let digits = &username[username.len() - 2..];
+            assert!(
+                digits.bytes().all(|byte| byte.is_ascii_digit()),
+                "{username}"
+            );
+            assert_eq!(username.matches('_').count(), 1);
+        }
+    }
+
+    #[test]
+    fn hostnames_respect_label_rules_and_lengths() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let hostname = random_hostname(&mut rng);
+            assert!(hostname.len() <= HOSTNAME_LEN_MAX);
+            let labels: Vec<&str> = hostname.split('.').collect();
+            assert_eq!(labels.len(), 3, "{hostname}");
+            assert!(TLDS.contains(&labels[2]));
+            for label in labels {
+                assert!((1..=LABEL_LEN_MAX).contains(&label.len()), "{hostname}");
+                assert!(
+                    !label.starts_with('-') && !label.ends_with('-'),
+                    "{hostname}"
+                );
+                assert!(
+                    label.bytes().all(|byte| byte.is_ascii_lowercase()
+                        || byte.is_ascii_digit()
+                        || byte == b'-'),
+                    "{hostname}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use rand::{Rng, RngExt};
+
+// Crockford base32: no I, L, O, or U.
+const CROCKFORD: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const NANOID_ALPHABET: &[u8] = b"useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const ULID_LEN: usize = 26;
+const NANOID_LEN: usize = 21;
+const KSUID_LEN: usize = 27;
+/// KSUID timestamps count seconds from 2014-05-13T16:53:20Z.
+const KSUID_EPOCH: i64 = 1_400_000_000;
+
+/// ULID: 48-bit millisecond timestamp then 80 random bits, as 26 Crockford base32 characters. Lexical order
+/// follows creation time.
+pub(crate) fn random_ulid<R: Rng + ?Sized>(rng: &mut R, now_ms: i64) -> String {
+    let timestamp = u128::from(now_ms.clamp(0, (1 << 48) - 1).unsigned_abs());
+    let random = rng.random::<u128>() & ((1 << 80) - 1);
+    let value = (timestamp << 80) | random;
+
+    // 26 characters carry 130 bits; the top two are always zero.
+    (0..ULID_LEN)
+        .rev()
+        .map(|index| char::from(CROCKFORD[((value >> (index * 5)) & 0x1f) as usize]))
+        .collect()
+}
+
+/// Nano ID: 21 characters from the URL-safe 64-symbol alphabet (126 random bits).
+pub(crate) fn random_nanoid<R: Rng + ?Sized>(rng: &mut R) -> String {
+    (0..NANOID_LEN)
+        .map(|_| char::from(NANOID_ALPHABET[rng.random_range(0..NANOID_ALPHABET.len())]))
+        .collect()
+}
+
+/// KSUID: 32-bit timestamp (seconds since `KSUID_EPOCH`) then 128 random bits, as 27 zero-padded base62
+/// characters. Lexical order follows creation time.
+pub(crate) fn random_ksuid<R: Rng + ?Sized>(rng: &mut R, now_secs: i64) -> String {
+    let timestamp = u32::try_from((now_secs - KSUID_EPOCH).max(0)).unwrap_or(u32::MAX);
+    let mut bytes = [0u8; 20];
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    rng.fill_bytes(&mut bytes[4..]);
+    base62(&bytes)
+}
+
+/// Big-endian base62 of `bytes`, left-padded with `0` to `KSUID_LEN`.
+fn base62(bytes: &[u8]) -> String {
+    let mut digits: Vec<u32> = bytes.iter().map(|&byte| u32::from(byte)).collect();
+    let mut encoded = Vec::with_capacity(KSUID_LEN);
+    while digits.iter().any(|&digit| digit != 0) {
+        // Long division of the base-256 number by 62.
+        let mut remainder = 0;
+        for digit in &mut digits {
+            let accumulator = remainder * 256 + *digit;
+            *digit = accumulator / 62;
+            remainder = accumulator % 62;
+        }
+        encoded.push(BASE62[remainder as usize]);
+    }
+    encoded.resize(KSUID_LEN, b'0');
+    encoded.iter().rev().map(|&byte| char::from(byte)).collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    const NOW_MS: i64 = 1_735_689_600_000;
+
+    fn decode_ulid_timestamp(ulid: &str) -> u128 {
+        let value = ulid.bytes().fold(0u128, |value, byte| {
+            let digit = CROCKFORD
+                .iter()
+                .position(|&symbol| symbol == byte)
+                .expect("Crockford symbol");
+            (value << 5) | digit as u128
+        });
+        value >> 80
+    }
+
+    fn decode_base62(value: &str) -> u128 {
+        value.bytes().fold(0u128, |acc, byte| {
+            let digit = BASE62
+                .iter()
+                .position(|&symbol| symbol == byte)
+                .expect("base62 symbol");
+            acc * 62 + digit as u128
+        })
+    }
+
+    #[test]
+    fn ulid_is_26_crockford_chars_with_time_prefix() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let ulid = random_ulid(&mut rng, NOW_MS);
+        assert_eq!(ulid.len(), ULID_LEN);
+        assert!(ulid.bytes().all(|byte| CROCKFORD.contains(&byte)));
+        assert!(ulid.as_bytes()[0] <= b'7', "first char carries only 3 bits");
+        assert_eq!(decode_ulid_timestamp(&ulid), NOW_MS as u128);
+    }
+
+    #[test]
+    fn ulid_and_ksuid_sort_by_time() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for step in [1, 1_000, 86_400_000] {
+            assert!(random_ulid(&mut rng, NOW_MS) < random_ulid(&mut rng, NOW_MS + step));
+            assert!(
+                random_ksuid(&mut rng, NOW_MS / 1000)
+                    < random_ksuid(&mut rng, (NOW_MS + step.max(1000)) / 1000)
+            );
+        }
+    }
+
+    #[test]
+    fn nanoid_is_21_url_safe_chars() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let id = random_nanoid(&mut rng);
+            assert_eq!(id.len(), NANOID_LEN);
+            assert!(
+                id.chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+            );
+        }
+        assert_eq!(NANOID_ALPHABET.len(), 64);
+    }
+
+    #[test]
+    fn ksuid_is_27_zero_padded_base62_chars() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let ksuid = random_ksuid(&mut rng, NOW_MS / 1000);
+            assert_eq!(ksuid.len(), KSUID_LEN);
+            assert!(ksuid.bytes().all(|byte| BASE62.contains(&byte)));
+        }
+
+        // Boundary values from the KSUID reference implementation.
+        assert_eq!(base62(&[0xff; 20]), "aWgEPTl1tmebfsQzFP4bxwgy80V");
+        assert_eq!(
+            decode_base62(&base62(&[
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0
+            ])),
+            256
+        );
+        assert_eq!(base62(&[0; 20]), "0".repeat(KSUID_LEN));
+    }
+}
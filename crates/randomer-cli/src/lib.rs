@@ -6,18 +6,59 @@
 // `mod tests` below.
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
+mod address;
+mod card;
+mod color;
+mod config;
+mod date;
+mod iban;
+mod identity;
+mod ids;
+mod locale;
+mod lorem;
+mod mac;
+mod net;
+mod number;
+mod password;
+mod person;
+mod phone;
+mod template;
+mod token;
+
 use std::fmt;
 
 use alfred_core::{Feedback, Item, ItemIcon, ItemModifier};
-use rand::{Rng, RngExt};
-use uuid::Uuid;
+use rand::{Rng, RngExt, SeedableRng, rngs::StdRng};
+use uuid::Builder as UuidBuilder;
+
+use crate::number::NumberArgError;
+
+pub use card::CardBrand;
+pub use color::ColorTone;
+pub use config::{DEFAULT_EXPAND_COUNT, DEFAULT_MAX_COUNT, RuntimeConfig};
+pub use date::DateRange;
+pub use iban::{IBAN_COUNTRIES, IbanCountry};
+pub use locale::Locale;
+pub use lorem::{LOREM_WORDS_MAX, LoremSpec, LoremUnit};
+pub use mac::MacStyle;
+pub use net::{Ipv4Scope, Ipv6Scope};
+pub use number::{
+    CurrencyCode, CurrencySpec, DECIMAL_PLACES_MAX, DEFAULT_DECIMAL_PLACES, DecimalSpec,
+    NumberRange,
+};
+pub use password::{
+    CharClass, DEFAULT_PASSWORD_LENGTH, PASSWORD_LENGTH_MAX, PASSWORD_LENGTH_MIN, PasswordPolicy,
+};
+pub use phone::PhoneRegion;
+pub use template::Template;
+pub use token::{DEFAULT_SECRET_BYTES, SECRET_BYTES_MAX, SECRET_BYTES_MIN};
 
 const UNIT_LETTER_VALUES: [u32; 26] = [
     10, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 34, 35, 36,
     37, 38,
 ];
 
-const ALL_FORMATS: [Format; 11] = [
+const ALL_FORMATS: [Format; 33] = [
     Format::Email,
     Format::Imei,
     Format::Unit,
@@ -29,6 +70,28 @@ const ALL_FORMATS: [Format; 11] = [
     Format::Hex,
     Format::Otp,
     Format::Phone,
+    Format::Password,
+    Format::Name,
+    Format::FullProfile,
+    Format::Address,
+    Format::Lorem,
+    Format::Card,
+    Format::Iban,
+    Format::Mac,
+    Format::Ipv4,
+    Format::Ipv6,
+    Format::Date,
+    Format::DateTime,
+    Format::Color,
+    Format::Secret,
+    Format::Key256,
+    Format::Jwt,
+    Format::Ulid,
+    Format::Nanoid,
+    Format::Ksuid,
+    Format::Slug,
+    Format::Hostname,
+    Format::Username,
 ];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,13 +107,39 @@ pub enum Format {
     Hex,
     Otp,
     Phone,
+    Password,
+    Name,
+    FullProfile,
+    Address,
+    Lorem,
+    Card,
+    Iban,
+    Mac,
+    Ipv4,
+    Ipv6,
+    Date,
+    DateTime,
+    Color,
+    Secret,
+    Key256,
+    Jwt,
+    Ulid,
+    Nanoid,
+    Ksuid,
+    Slug,
+    Hostname,
+    Username,
+    /// User-defined `RANDOMER_TEMPLATE_<NAME>` pattern.
+    Template(&'static Template),
 }
 
 impl Format {
+    /// Built-in formats in canonical order; configured templates follow them in lists.
     pub fn all() -> &'static [Format] {
         &ALL_FORMATS
     }
 
+    /// Parse a built-in format key; see `RuntimeConfig::template` for user-defined templates.
     pub fn parse(input: &str) -> Option<Self> {
         let normalized = input.trim().to_ascii_lowercase();
         Self::all()
@@ -72,26 +161,381 @@ impl Format {
             Self::Hex => "hex",
             Self::Otp => "otp",
             Self::Phone => "phone",
+            Self::Password => "password",
+            Self::Name => "name",
+            Self::FullProfile => "fullprofile",
+            Self::Address => "address",
+            Self::Lorem => "lorem",
+            Self::Card => "card",
+            Self::Iban => "iban",
+            Self::Mac => "mac",
+            Self::Ipv4 => "ipv4",
+            Self::Ipv6 => "ipv6",
+            Self::Date => "date",
+            Self::DateTime => "datetime",
+            Self::Color => "color",
+            Self::Secret => "secret",
+            Self::Key256 => "key256",
+            Self::Jwt => "jwt",
+            Self::Ulid => "ulid",
+            Self::Nanoid => "nanoid",
+            Self::Ksuid => "ksuid",
+            Self::Slug => "slug",
+            Self::Hostname => "hostname",
+            Self::Username => "username",
+            Self::Template(template) => template.name(),
         }
     }
 
     fn icon_path(self) -> String {
-        format!("assets/icons/{}.png", self.key())
+        match self {
+            Self::Template(_) => String::from("assets/icons/template.png"),
+            _ => format!("assets/icons/{}.png", self.key()),
+        }
     }
 
-    fn generate_with_rng<R: Rng + ?Sized>(self, rng: &mut R) -> String {
-        match self {
+    fn generate_with_rng<R: Rng + ?Sized>(self, rng: &mut R, config: &RuntimeConfig) -> String {
+        self.generate_value(rng, config).value
+    }
+
+    fn generate_value<R: Rng + ?Sized>(self, rng: &mut R, config: &RuntimeConfig) -> Generated {
+        let value = match self {
             Self::Email => random_email(rng),
             Self::Imei => random_imei(rng),
             Self::Unit => random_unit_number(rng),
-            Self::Uuid => random_uuid(),
-            Self::Int => random_int(rng),
-            Self::Decimal => random_decimal(rng),
+            Self::Uuid => random_uuid(rng),
+            Self::Int => number::random_int(rng, config.int_range),
+            Self::Decimal => number::random_decimal(rng, config.decimal),
             Self::Percent => random_percent(rng),
-            Self::Currency => random_currency(rng),
+            Self::Currency => number::random_currency(rng, config.currency),
             Self::Hex => random_hex(rng),
             Self::Otp => random_otp(rng),
-            Self::Phone => random_phone(rng),
+            Self::Password => config.password.generate(rng),
+            Self::Name => person::random_name(rng, config.locale).display,
+            Self::Lorem => config.lorem.generate(rng),
+            Self::Mac => mac::random_mac(rng, config.mac_style),
+            Self::Secret => token::random_secret(rng, config.secret_bytes),
+            Self::Key256 => token::random_key256(rng),
+            Self::Jwt => token::random_jwt(rng, config.now().timestamp()),
+            Self::Ulid => ids::random_ulid(rng, config.now().timestamp_millis()),
+            Self::Nanoid => ids::random_nanoid(rng),
+            Self::Ksuid => ids::random_ksuid(rng, config.now().timestamp()),
+            Self::Slug => identity::random_slug(rng),
+            Self::Hostname => identity::random_hostname(rng),
+            Self::Username => identity::random_username(rng),
+            Self::Template(template) => template.generate(rng),
+            Self::Ipv4 => net::random_ipv4(rng, config.ipv4_scope, config.ip_cidr),
+            Self::Ipv6 => net::random_ipv6(rng, config.ipv6_scope, config.ip_cidr),
+            Self::FullProfile => return random_full_profile(rng, config),
+            Self::Address => return random_postal_address(rng, config),
+            Self::Card => return random_card_number(rng, config),
+            Self::Iban => return random_bank_account(rng, config),
+            Self::Color => return random_color_value(rng, config),
+            Self::Phone => return random_phone_number(rng, config),
+            Self::Date => return with_epoch(date::random_date(rng, config.date_range)),
+            Self::DateTime => return with_epoch(date::random_datetime(rng, config.date_range)),
+        };
+        Generated::plain(value)
+    }
+
+    /// Whether this format takes the query arguments between the format key and the trailing count.
+    fn accepts_args(self, args: &[String]) -> bool {
+        match (self, args) {
+            (_, []) => true,
+            (Self::Lorem, [size]) => LoremSpec::parse(size).is_some(),
+            (Self::Card, [brand]) => CardBrand::parse(brand).is_some(),
+            (Self::Mac, [style]) => MacStyle::parse(style).is_some(),
+            (Self::Color, [tone]) => ColorTone::parse(tone).is_some(),
+            (Self::Phone, [region]) => PhoneRegion::parse(region).is_some(),
+            // Range-shaped tokens are accepted here so that a bad range is reported by `apply_query`.
+            (Self::Int, args) => !matches!(
+                number::parse_int_args(args),
+                Err(NumberArgError::Unrecognized)
+            ),
+            (Self::Decimal, args) => !matches!(
+                DecimalSpec::from_args(args),
+                Err(NumberArgError::Unrecognized)
+            ),
+            (Self::Currency, args) => !matches!(
+                CurrencySpec::from_args(args),
+                Err(NumberArgError::Unrecognized)
+            ),
+            (Self::Date | Self::DateTime, [range]) => DateRange::parse(range).is_some(),
+            (Self::Ipv4, args) => net::parse_ip_args(args, Ipv4Scope::parse).is_some(),
+            (Self::Ipv6, args) => net::parse_ip_args(args, Ipv6Scope::parse).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Resolve the query arguments and count for this format into `(value count, config)`.
+    ///
+    /// `password` reads the trailing number as the password length (`password 24`) and `lorem` as the word count
+    /// (`lorem 40`), so neither changes the number of values. `lorem` also takes a size argument (`lorem 3p`), after
+    /// which a trailing number is the value count again.
+    fn apply_query(
+        self,
+        query: &CountedQuery,
+        raw_query: Option<&str>,
+        config: &RuntimeConfig,
+    ) -> Result<(Option<usize>, RuntimeConfig), RandomerError> {
+        if !self.accepts_args(&query.args) {
+            return Err(RandomerError::InvalidArgument {
+                format: self.key(),
+                argument: query.args.join(" "),
+            });
+        }
+
+        let mut config = config.clone();
+        match self {
+            Self::Password => {
+                if let Some(length) = CountedQuery::parse(raw_query, PASSWORD_LENGTH_MAX).count {
+                    config.password = config.password.with_length(length);
+                }
+                Ok((None, config))
+            }
+            Self::Secret => {
+                if let Some(bytes) = CountedQuery::parse(raw_query, SECRET_BYTES_MAX).count {
+                    config.secret_bytes = token::clamp_secret_bytes(bytes);
+                }
+                Ok((None, config))
+            }
+            Self::Lorem => match query.args.first() {
+                Some(size) => {
+                    config.lorem = LoremSpec::parse(size).unwrap_or(config.lorem);
+                    Ok((query.count, config))
+                }
+                None => {
+                    if let Some(words) = CountedQuery::parse(raw_query, LOREM_WORDS_MAX).count {
+                        config.lorem = LoremSpec {
+                            amount: words,
+                            unit: LoremUnit::Words,
+                        };
+                    }
+                    Ok((None, config))
+                }
+            },
+            Self::Card => {
+                config.card_brand = query.args.first().and_then(|brand| CardBrand::parse(brand));
+                Ok((query.count, config))
+            }
+            Self::Ipv4 => {
+                if let Some((scope, cidr)) = net::parse_ip_args(&query.args, Ipv4Scope::parse) {
+                    config.ipv4_scope = scope.unwrap_or(config.ipv4_scope);
+                    config.ip_cidr = cidr;
+                }
+                Ok((query.count, config))
+            }
+            Self::Ipv6 => {
+                if let Some((scope, cidr)) = net::parse_ip_args(&query.args, Ipv6Scope::parse) {
+                    config.ipv6_scope = scope.unwrap_or(config.ipv6_scope);
+                    config.ip_cidr = cidr;
+                }
+                Ok((query.count, config))
+            }
+            Self::Date | Self::DateTime => {
+                if let Some(range) = query.args.first().and_then(|range| DateRange::parse(range)) {
+                    config.date_range = range;
+                }
+                Ok((query.count, config))
+            }
+            Self::Color => {
+                if let Some(tone) = query.args.first().and_then(|tone| ColorTone::parse(tone)) {
+                    config.color_tone = tone;
+                }
+                Ok((query.count, config))
+            }
+            Self::Mac => {
+                if let Some(style) = query.args.first().and_then(|style| MacStyle::parse(style)) {
+                    config.mac_style = style;
+                }
+                Ok((query.count, config))
+            }
+            Self::Phone => {
+                if let Some(region) = query
+                    .args
+                    .first()
+                    .and_then(|region| PhoneRegion::parse(region))
+                {
+                    config.phone_region = Some(region);
+                }
+                Ok((query.count, config))
+            }
+            Self::Int => {
+                config.int_range = number::parse_int_args(&query.args)
+                    .map_err(|error| self.number_error(error, &query.args))?;
+                Ok((query.count, config))
+            }
+            Self::Decimal => {
+                config.decimal = DecimalSpec::from_args(&query.args)
+                    .map_err(|error| self.number_error(error, &query.args))?;
+                Ok((query.count, config))
+            }
+            Self::Currency => {
+                config.currency = CurrencySpec::from_args(&query.args)
+                    .map_err(|error| self.number_error(error, &query.args))?;
+                Ok((query.count, config))
+            }
+            _ => Ok((query.count, config)),
+        }
+    }
+
+    fn number_error(self, error: NumberArgError, args: &[String]) -> RandomerError {
+        match error {
+            NumberArgError::Range { token, reason } => RandomerError::InvalidRange {
+                format: self.key(),
+                range: token,
+                reason,
+            },
+            NumberArgError::Unrecognized => RandomerError::InvalidArgument {
+                format: self.key(),
+                argument: args.join(" "),
+            },
+        }
+    }
+
+    /// Argument handed to `rrvv` for this format, keeping a `password` length or `lorem` size from the query.
+    fn expand_arg(self, config: &RuntimeConfig, defaults: &RuntimeConfig) -> String {
+        match self {
+            Self::Password if config.password.length != defaults.password.length => {
+                format!("{} {}", self.key(), config.password.length)
+            }
+            Self::Lorem if config.lorem != defaults.lorem => {
+                format!("{} {}", self.key(), config.lorem.token())
+            }
+            Self::Mac if config.mac_style != defaults.mac_style => {
+                format!("{} {}", self.key(), config.mac_style.key())
+            }
+            Self::Ipv4 => {
+                let args = net::ip_args_token(
+                    config.ipv4_scope.key(),
+                    defaults.ipv4_scope.key(),
+                    config.ip_cidr,
+                );
+                [self.key(), args.as_str()].join(" ").trim_end().to_string()
+            }
+            Self::Ipv6 => {
+                let args = net::ip_args_token(
+                    config.ipv6_scope.key(),
+                    defaults.ipv6_scope.key(),
+                    config.ip_cidr,
+                );
+                [self.key(), args.as_str()].join(" ").trim_end().to_string()
+            }
+            Self::Date | Self::DateTime if config.date_range != defaults.date_range => {
+                format!("{} {}", self.key(), config.date_range.token())
+            }
+            Self::Secret if config.secret_bytes != defaults.secret_bytes => {
+                format!("{} {}", self.key(), config.secret_bytes)
+            }
+            Self::Color if config.color_tone != defaults.color_tone => {
+                format!("{} {}", self.key(), config.color_tone.key())
+            }
+            Self::Card => match config.card_brand {
+                Some(brand) => format!("{} {}", self.key(), brand.key()),
+                None => self.key().to_string(),
+            },
+            Self::Int => match config.int_range {
+                Some(range) => format!("{} {}", self.key(), range.token()),
+                None => self.key().to_string(),
+            },
+            Self::Decimal if config.decimal != defaults.decimal => {
+                format!("{} {}", self.key(), config.decimal.token())
+            }
+            Self::Currency if config.currency != defaults.currency => {
+                format!("{} {}", self.key(), config.currency.token())
+            }
+            Self::Phone => match config.phone_region {
+                Some(region) => format!("{} {}", self.key(), region.key()),
+                None => self.key().to_string(),
+            },
+            _ => self.key().to_string(),
+        }
+    }
+
+    /// Subtitle for a generated value; `password` rows add the length and entropy estimate.
+    fn value_subtitle(self, config: &RuntimeConfig) -> String {
+        match self {
+            Self::Password => format!(
+                "{} · {} chars · ~{:.0} bits entropy",
+                self.key(),
+                config.password.length,
+                config.password.entropy_bits()
+            ),
+            Self::Name => format!("{} · {}", self.key(), config.locale.as_str()),
+            Self::Lorem => format!("{} · {}", self.key(), config.lorem.describe()),
+            Self::Mac => format!("{} · {}", self.key(), config.mac_style.key()),
+            Self::Int => match config.int_range {
+                Some(range) => format!("{} · {}", self.key(), range.token()),
+                None => self.key().to_string(),
+            },
+            Self::Decimal if config.decimal != DecimalSpec::default() => {
+                format!("{} · {}", self.key(), config.decimal.describe())
+            }
+            Self::Currency if config.currency != CurrencySpec::default() => {
+                format!("{} · {}", self.key(), config.currency.describe())
+            }
+            Self::Phone => format!(
+                "{} · {} · Cmd+Enter: copy E.164",
+                self.key(),
+                config.phone_region().key().to_ascii_uppercase()
+            ),
+            Self::Secret => format!("{} · {} bytes · base64url", self.key(), config.secret_bytes),
+            Self::Key256 => format!("{} · 32 bytes · hex", self.key()),
+            Self::Template(template) => format!("{} · template {}", self.key(), template.pattern()),
+            Self::Jwt => format!("{} · HS256 · random signature, not verifiable", self.key()),
+            Self::Date | Self::DateTime => format!(
+                "{} · {} · Cmd+Enter: copy unix epoch",
+                self.key(),
+                config.date_range.token()
+            ),
+            Self::Ipv4 | Self::Ipv6 => {
+                let scope = match self {
+                    Self::Ipv4 => config.ipv4_scope.key(),
+                    _ => config.ipv6_scope.key(),
+                };
+                if config.ip_cidr {
+                    format!("{} · {scope} · CIDR", self.key())
+                } else {
+                    format!("{} · {scope}", self.key())
+                }
+            }
+            Self::Address => format!(
+                "{} · {} · Cmd+Enter: copy street · Alt+Enter: copy postal code",
+                self.key(),
+                config.locale.as_str()
+            ),
+            Self::FullProfile => format!(
+                "{} · {} · Cmd+Enter: copy email · Alt+Enter: copy phone",
+                self.key(),
+                config.locale.as_str()
+            ),
+            _ => self.key().to_string(),
+        }
+    }
+}
+
+/// A generated value plus alternate parts offered on modifier keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Generated {
+    value: String,
+    /// Per-value subtitle replacing the format-wide one, for details that differ between values.
+    subtitle: Option<String>,
+    alternates: Vec<Alternate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Alternate {
+    modifier: &'static str,
+    label: &'static str,
+    value: String,
+}
+
+impl Generated {
+    fn plain(value: String) -> Self {
+        Self {
+            value,
+            subtitle: None,
+            alternates: Vec::new(),
         }
     }
 }
@@ -100,6 +544,20 @@ impl Format {
 pub enum RandomerError {
     UnknownFormat(String),
     InvalidCount(usize),
+    InvalidArgument {
+        format: &'static str,
+        argument: String,
+    },
+    /// A range-shaped `int` / `decimal` / `currency` argument that cannot be used, with the reason.
+    InvalidRange {
+        format: &'static str,
+        range: String,
+        reason: &'static str,
+    },
+    InvalidConfig {
+        field: &'static str,
+        value: String,
+    },
 }
 
 impl fmt::Display for RandomerError {
@@ -107,44 +565,187 @@ impl fmt::Display for RandomerError {
         match self {
             Self::UnknownFormat(format) => write!(f, "unknown format: {format}"),
             Self::InvalidCount(count) => write!(f, "count must be at least 1 (got {count})"),
+            Self::InvalidArgument { format, argument } => {
+                write!(f, "invalid {format} argument: {argument}")
+            }
+            Self::InvalidRange {
+                format,
+                range,
+                reason,
+            } => write!(f, "invalid {format} range: {range} ({reason})"),
+            Self::InvalidConfig { field, value } => write!(f, "invalid {field}: {value}"),
         }
     }
 }
 
 impl std::error::Error for RandomerError {}
 
-pub fn filter_formats(query: Option<&str>) -> Vec<Format> {
-    let query = query.unwrap_or_default().trim().to_ascii_lowercase();
+/// Values produced by `generate_values`, keyed by the resolved format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedValues {
+    pub format: String,
+    pub values: Vec<String>,
+}
+
+/// Query split into the format filter, format arguments, and an optional trailing count, e.g. `lorem 3p 5` ->
+/// (`lorem`, [`3p`], `Some(5)`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountedQuery {
+    pub text: String,
+    pub args: Vec<String>,
+    pub count: Option<usize>,
+}
+
+impl CountedQuery {
+    /// Split a trailing all-digit token off `input` as the count, clamped to `1..=max_count`.
+    ///
+    /// The first remaining token is the format filter and the rest are format arguments, all trimmed and lowercased.
+    /// A lone number (`25`) is a count with empty text.
+    pub fn parse(input: Option<&str>, max_count: usize) -> Self {
+        let lowered = input.unwrap_or_default().trim().to_ascii_lowercase();
+        let mut tokens: Vec<&str> = lowered.split_whitespace().collect();
+
+        let mut count = None;
+        if let Some(last) = tokens
+            .last()
+            .filter(|last| last.bytes().all(|byte| byte.is_ascii_digit()))
+        {
+            // Overlong digit runs saturate and are clamped like any other large count.
+            let parsed = last.parse::<usize>().unwrap_or(usize::MAX);
+            count = Some(parsed.clamp(1, max_count.max(1)));
+            tokens.pop();
+        }
+
+        let text = if tokens.is_empty() {
+            String::new()
+        } else {
+            tokens.remove(0).to_string()
+        };
+        Self {
+            text,
+            args: tokens.into_iter().map(String::from).collect(),
+            count,
+        }
+    }
+}
+
+pub fn filter_formats(query: &CountedQuery, config: &RuntimeConfig) -> Vec<Format> {
     Format::all()
         .iter()
         .copied()
-        .filter(|format| query.is_empty() || format.key().contains(&query))
+        .chain(config.templates.iter().copied().map(Format::Template))
+        .filter(|format| query.text.is_empty() || format.key().contains(&query.text))
+        .filter(|format| format.accepts_args(&query.args))
         .collect()
 }
 
-pub fn list_formats_feedback(query: Option<&str>) -> Feedback {
+pub fn list_formats_feedback(query: Option<&str>, config: &RuntimeConfig) -> Feedback {
     let mut rng = rand::rng();
-    list_formats_feedback_with_rng(query, &mut rng)
+    list_formats_feedback_with_rng(query, config, &mut rng)
 }
 
-pub fn list_types_feedback(query: Option<&str>) -> Feedback {
+pub fn list_types_feedback(query: Option<&str>, config: &RuntimeConfig) -> Feedback {
     let mut rng = rand::rng();
-    list_types_feedback_with_rng(query, &mut rng)
+    list_types_feedback_with_rng(query, config, &mut rng)
 }
 
-pub fn generate_feedback(format_name: &str, count: usize) -> Result<Feedback, RandomerError> {
+/// Generate values for `format_query`; a trailing count in the query (`uuid 25`) overrides `count`.
+pub fn generate_feedback(
+    format_query: &str,
+    count: usize,
+    config: &RuntimeConfig,
+) -> Result<Feedback, RandomerError> {
     let mut rng = rand::rng();
-    generate_feedback_with_rng(format_name, count, &mut rng)
+    generate_feedback_with_rng(format_query, count, config, &mut rng)
 }
 
-fn list_formats_feedback_with_rng<R: Rng + ?Sized>(query: Option<&str>, rng: &mut R) -> Feedback {
-    let items = filter_formats(query)
+/// Raw values for `generate --output json`, resolved the same way as `generate_feedback`.
+pub fn generate_values(
+    format_query: &str,
+    count: usize,
+    config: &RuntimeConfig,
+) -> Result<GeneratedValues, RandomerError> {
+    let mut rng = rand::rng();
+    generate_values_with_rng(format_query, count, config, &mut rng)
+}
+
+/// Same as `list_formats_feedback`, but samples come from a `StdRng` seeded with `seed`.
+pub fn list_formats_feedback_seeded(
+    query: Option<&str>,
+    config: &RuntimeConfig,
+    seed: u64,
+) -> Feedback {
+    let mut rng = StdRng::seed_from_u64(seed);
+    list_formats_feedback_with_rng(query, &config.with_pinned_clock(), &mut rng)
+}
+
+/// Same as `list_types_feedback`, but samples come from a `StdRng` seeded with `seed`.
+pub fn list_types_feedback_seeded(
+    query: Option<&str>,
+    config: &RuntimeConfig,
+    seed: u64,
+) -> Feedback {
+    let mut rng = StdRng::seed_from_u64(seed);
+    list_types_feedback_with_rng(query, &config.with_pinned_clock(), &mut rng)
+}
+
+/// Same as `generate_feedback`, but values come from a `StdRng` seeded with `seed`, so equal inputs and seeds always
+/// produce the same values.
+pub fn generate_feedback_seeded(
+    format_query: &str,
+    count: usize,
+    config: &RuntimeConfig,
+    seed: u64,
+) -> Result<Feedback, RandomerError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    generate_feedback_with_rng(format_query, count, &config.with_pinned_clock(), &mut rng)
+}
+
+/// Same as `generate_values`, but values come from a `StdRng` seeded with `seed`; they match the rows of
+/// `generate_feedback_seeded` for the same inputs.
+pub fn generate_values_seeded(
+    format_query: &str,
+    count: usize,
+    config: &RuntimeConfig,
+    seed: u64,
+) -> Result<GeneratedValues, RandomerError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    generate_values_with_rng(format_query, count, &config.with_pinned_clock(), &mut rng)
+}
+
+fn list_formats_feedback_with_rng<R: Rng + ?Sized>(
+    raw_query: Option<&str>,
+    config: &RuntimeConfig,
+    rng: &mut R,
+) -> Feedback {
+    let query = CountedQuery::parse(raw_query, config.max_count);
+    let formats = filter_formats(&query, config);
+
+    // A count or argument that narrows to one format skips the expand step and lists the values directly.
+    if let [format] = formats.as_slice()
+        && (query.count.is_some() || !query.args.is_empty())
+    {
+        match format.apply_query(&query, raw_query, config) {
+            Ok((count, format_config)) => {
+                let count = count.unwrap_or(DEFAULT_EXPAND_COUNT);
+                return generated_values_feedback(*format, count, &format_config, rng);
+            }
+            Err(error @ RandomerError::InvalidRange { .. }) => {
+                return invalid_range_feedback(*format, &error);
+            }
+            Err(_) => {}
+        }
+    }
+
+    let items = formats
         .into_iter()
-        .map(|format| {
-            let sample = format.generate_with_rng(rng);
-            Item::new(sample.clone())
+        .filter_map(|format| {
+            let (count, format_config) = format.apply_query(&query, raw_query, config).ok()?;
+            let expand_count = count.unwrap_or(DEFAULT_EXPAND_COUNT);
+            let sample = format.generate_with_rng(rng, &format_config);
+            let item = Item::new(sample.clone())
                 .with_subtitle(format!(
-                    "{} · Enter: copy sample · Cmd+Enter: show 10 values",
+                    "{} · Enter: copy sample · Cmd+Enter: show {expand_count} values",
                     format.key()
                 ))
                 .with_arg(sample)
@@ -153,27 +754,48 @@ fn list_formats_feedback_with_rng<R: Rng + ?Sized>(query: Option<&str>, rng: &mu
                 .with_mod(
                     "cmd",
                     ItemModifier::new()
-                        .with_arg(format.key())
-                        .with_subtitle(format!("show 10 values for {}", format.key()))
-                        .with_variable("RANDOMER_FORMAT", format.key()),
-                )
+                        .with_arg(format.expand_arg(&format_config, config))
+                        .with_subtitle(format!("show {expand_count} values for {}", format.key()))
+                        .with_variable("RANDOMER_FORMAT", format.expand_arg(&format_config, config))
+                        .with_variable("RANDOMER_COUNT", expand_count.to_string()),
+                );
+            Some(item)
         })
         .collect();
 
     Feedback::new(items)
 }
 
-fn list_types_feedback_with_rng<R: Rng + ?Sized>(query: Option<&str>, rng: &mut R) -> Feedback {
-    let items = filter_formats(query)
+fn list_types_feedback_with_rng<R: Rng + ?Sized>(
+    raw_query: Option<&str>,
+    config: &RuntimeConfig,
+    rng: &mut R,
+) -> Feedback {
+    let query = CountedQuery::parse(raw_query, config.max_count);
+    let formats = filter_formats(&query, config);
+    if let [format] = formats.as_slice()
+        && let Err(error @ RandomerError::InvalidRange { .. }) =
+            format.apply_query(&query, raw_query, config)
+    {
+        return invalid_range_feedback(*format, &error);
+    }
+
+    let items = formats
         .into_iter()
-        .map(|format| {
-            let sample = format.generate_with_rng(rng);
-            Item::new(format.key())
-                .with_subtitle(format!("sample: {sample} · Enter: show 10 values"))
-                .with_arg(format.key())
+        .filter_map(|format| {
+            let (count, format_config) = format.apply_query(&query, raw_query, config).ok()?;
+            let expand_count = count.unwrap_or(DEFAULT_EXPAND_COUNT);
+            let sample = format.generate_with_rng(rng, &format_config);
+            let item = Item::new(format.key())
+                .with_subtitle(format!(
+                    "sample: {sample} · Enter: show {expand_count} values"
+                ))
+                .with_arg(format.expand_arg(&format_config, config))
                 .with_valid(true)
                 .with_icon(ItemIcon::new(format.icon_path()))
-                .with_variable("RANDOMER_FORMAT", format.key())
+                .with_variable("RANDOMER_FORMAT", format.expand_arg(&format_config, config))
+                .with_variable("RANDOMER_COUNT", expand_count.to_string());
+            Some(item)
         })
         .collect();
 
@@ -181,29 +803,266 @@ fn list_types_feedback_with_rng<R: Rng + ?Sized>(query: Option<&str>, rng: &mut
 }
 
 fn generate_feedback_with_rng<R: Rng + ?Sized>(
-    format_name: &str,
+    format_query: &str,
     count: usize,
+    config: &RuntimeConfig,
     rng: &mut R,
 ) -> Result<Feedback, RandomerError> {
+    let (format, count, format_config) = resolve_generate(format_query, count, config)?;
+    Ok(generated_values_feedback(
+        format,
+        count,
+        &format_config,
+        rng,
+    ))
+}
+
+fn generate_values_with_rng<R: Rng + ?Sized>(
+    format_query: &str,
+    count: usize,
+    config: &RuntimeConfig,
+    rng: &mut R,
+) -> Result<GeneratedValues, RandomerError> {
+    let (format, count, format_config) = resolve_generate(format_query, count, config)?;
+    Ok(GeneratedValues {
+        format: format.key().to_string(),
+        values: (0..count)
+            .map(|_| format.generate_with_rng(rng, &format_config))
+            .collect(),
+    })
+}
+
+/// Format, effective count, and query-adjusted config for a `generate` request.
+fn resolve_generate(
+    format_query: &str,
+    count: usize,
+    config: &RuntimeConfig,
+) -> Result<(Format, usize, RuntimeConfig), RandomerError> {
     if count == 0 {
         return Err(RandomerError::InvalidCount(count));
     }
 
-    let format = Format::parse(format_name)
-        .ok_or_else(|| RandomerError::UnknownFormat(format_name.trim().to_ascii_lowercase()))?;
+    let query = CountedQuery::parse(Some(format_query), config.max_count);
+    let format = Format::parse(&query.text)
+        .or_else(|| config.template(&query.text).map(Format::Template))
+        .ok_or_else(|| RandomerError::UnknownFormat(query.text.clone()))?;
+    let (query_count, format_config) = format.apply_query(&query, Some(format_query), config)?;
+    let count = query_count.unwrap_or(count).min(config.max_count.max(1));
+    Ok((format, count, format_config))
+}
 
-    let items = (0..count)
-        .map(|_| {
-            let value = format.generate_with_rng(rng);
-            Item::new(value.clone())
-                .with_subtitle(format.key())
-                .with_arg(value)
-                .with_valid(true)
-                .with_icon(ItemIcon::new(format.icon_path()))
-        })
+/// One non-actionable row explaining why a range argument was rejected, with a working example.
+fn invalid_range_feedback(format: Format, error: &RandomerError) -> Feedback {
+    let example = match format {
+        Format::Decimal => "decimal 0..1 4dp",
+        Format::Currency => "currency 10..500 eur",
+        _ => "int 1..1000",
+    };
+    Feedback::new(vec![
+        Item::new(format!("Invalid {} range", format.key()))
+            .with_subtitle(format!("{error} · e.g. {example}"))
+            .with_valid(false)
+            .with_icon(ItemIcon::new(format.icon_path())),
+    ])
+}
+
+fn generated_values_feedback<R: Rng + ?Sized>(
+    format: Format,
+    count: usize,
+    config: &RuntimeConfig,
+    rng: &mut R,
+) -> Feedback {
+    let subtitle = format.value_subtitle(config);
+    let generated: Vec<Generated> = (0..count)
+        .map(|_| format.generate_value(rng, config))
         .collect();
 
-    Ok(Feedback::new(items))
+    // With several values, a leading row copies them all at once, one per line.
+    let copy_all = (count > 1).then(|| {
+        let joined = generated
+            .iter()
+            .map(|generated| generated.value.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Item::new(format!("Copy all {count} values"))
+            .with_subtitle(format!(
+                "{} · Enter: copy {count} values, one per line",
+                format.key()
+            ))
+            .with_arg(joined)
+            .with_valid(true)
+            .with_icon(ItemIcon::new(format.icon_path()))
+    });
+
+    let items = copy_all
+        .into_iter()
+        .chain(generated.into_iter().map(|generated| {
+            generated.alternates.into_iter().fold(
+                Item::new(generated.value.clone())
+                    .with_subtitle(generated.subtitle.unwrap_or_else(|| subtitle.clone()))
+                    .with_arg(generated.value)
+                    .with_valid(true)
+                    .with_icon(ItemIcon::new(format.icon_path())),
+                |item, alternate| {
+                    item.with_mod(
+                        alternate.modifier,
+                        ItemModifier::new()
+                            .with_subtitle(format!("copy {}: {}", alternate.label, alternate.value))
+                            .with_arg(alternate.value),
+                    )
+                },
+            )
+        }))
+        .collect();
+
+    Feedback::new(items)
+}
+
+/// `name, email, phone` on one line; the email is derived from the name, and each part is also offered alone on a
+/// modifier key.
+fn random_full_profile<R: Rng + ?Sized>(rng: &mut R, config: &RuntimeConfig) -> Generated {
+    let name = person::random_name(rng, config.locale);
+    let email = name.email(rng);
+    let phone = phone::random_phone(rng, config.phone_region()).national;
+    Generated {
+        value: format!("{}, {email}, {phone}", name.display),
+        subtitle: None,
+        alternates: vec![
+            Alternate {
+                modifier: "cmd",
+                label: "email",
+                value: email,
+            },
+            Alternate {
+                modifier: "alt",
+                label: "phone",
+                value: phone,
+            },
+            Alternate {
+                modifier: "ctrl",
+                label: "name",
+                value: name.display,
+            },
+        ],
+    }
+}
+
+/// Full one-line address, with the street line and postal code offered alone on modifier keys.
+fn random_postal_address<R: Rng + ?Sized>(rng: &mut R, config: &RuntimeConfig) -> Generated {
+    let address = address::random_address(rng, config.locale);
+    Generated {
+        value: address.full,
+        subtitle: None,
+        alternates: vec![
+            Alternate {
+                modifier: "cmd",
+                label: "street",
+                value: address.street,
+            },
+            Alternate {
+                modifier: "alt",
+                label: "postal code",
+                value: address.postal_code,
+            },
+        ],
+    }
+}
+
+/// Grouped card number with brand, expiry, and CVC in the subtitle; digits, expiry, and CVC are offered alone on
+/// modifier keys.
+fn random_card_number<R: Rng + ?Sized>(rng: &mut R, config: &RuntimeConfig) -> Generated {
+    let card = card::random_card(rng, config.card_brand, config.now().date_naive());
+    Generated {
+        subtitle: Some(format!(
+            "card · {} · exp {} · CVC {} · Cmd+Enter: copy digits",
+            card.brand.label(),
+            card.expiry,
+            card.cvc
+        )),
+        value: card.number,
+        alternates: vec![
+            Alternate {
+                modifier: "cmd",
+                label: "digits",
+                value: card.digits,
+            },
+            Alternate {
+                modifier: "alt",
+                label: "expiry",
+                value: card.expiry,
+            },
+            Alternate {
+                modifier: "ctrl",
+                label: "CVC",
+                value: card.cvc,
+            },
+        ],
+    }
+}
+
+/// Print-form IBAN (groups of four) with the country in the subtitle; the electronic form is offered on `cmd`.
+fn random_bank_account<R: Rng + ?Sized>(rng: &mut R, config: &RuntimeConfig) -> Generated {
+    let iban = iban::random_iban(rng, &config.iban_countries);
+    Generated {
+        subtitle: Some(format!(
+            "iban · {} · Cmd+Enter: copy without spaces",
+            iban.country.code()
+        )),
+        value: iban.print,
+        alternates: vec![Alternate {
+            modifier: "cmd",
+            label: "without spaces",
+            value: iban.electronic,
+        }],
+    }
+}
+
+/// ISO 8601 value with its Unix timestamp offered on `cmd`.
+fn with_epoch(moment: date::Moment) -> Generated {
+    Generated {
+        value: moment.iso,
+        subtitle: None,
+        alternates: vec![Alternate {
+            modifier: "cmd",
+            label: "unix epoch",
+            value: moment.epoch.to_string(),
+        }],
+    }
+}
+
+/// National-format number with its E.164 form on `Cmd+Enter`.
+fn random_phone_number<R: Rng + ?Sized>(rng: &mut R, config: &RuntimeConfig) -> Generated {
+    let phone = phone::random_phone(rng, config.phone_region());
+    Generated {
+        value: phone.national,
+        subtitle: None,
+        alternates: vec![Alternate {
+            modifier: "cmd",
+            label: "E.164",
+            value: phone.e164,
+        }],
+    }
+}
+
+/// `#RRGGBB` with `rgb()` / `hsl()` in the subtitle, each also offered on a modifier key.
+fn random_color_value<R: Rng + ?Sized>(rng: &mut R, config: &RuntimeConfig) -> Generated {
+    let color = color::random_color(rng, config.color_tone);
+    Generated {
+        subtitle: Some(format!("color · {} · {}", color.rgb, color.hsl)),
+        value: color.hex,
+        alternates: vec![
+            Alternate {
+                modifier: "cmd",
+                label: "rgb",
+                value: color.rgb,
+            },
+            Alternate {
+                modifier: "alt",
+                label: "hsl",
+                value: color.hsl,
+            },
+        ],
+    }
 }
 
 fn random_alpha_string<R: Rng + ?Sized>(rng: &mut R, size: usize) -> String {
@@ -305,18 +1164,12 @@ fn unit_letter_value(ch: char) -> u32 {
     UNIT_LETTER_VALUES[index]
 }
 
-fn random_uuid() -> String {
-    Uuid::new_v4().to_string()
-}
-
-fn random_int<R: Rng + ?Sized>(rng: &mut R) -> String {
-    rng.random_range(0u64..=9_999_999_999u64).to_string()
-}
-
-fn random_decimal<R: Rng + ?Sized>(rng: &mut R) -> String {
-    let whole = rng.random_range(0u64..=999_999u64);
-    let fraction = rng.random_range(0u8..=99u8);
-    format!("{whole}.{fraction:02}")
+fn random_uuid<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let mut bytes = [0u8; 16];
+    rng.fill_bytes(&mut bytes);
+    UuidBuilder::from_random_bytes(bytes)
+        .into_uuid()
+        .to_string()
 }
 
 fn random_percent<R: Rng + ?Sized>(rng: &mut R) -> String {
@@ -324,24 +1177,6 @@ fn random_percent<R: Rng + ?Sized>(rng: &mut R) -> String {
     format!("{}.{:02}%", basis_points / 100, basis_points % 100)
 }
 
-fn random_currency<R: Rng + ?Sized>(rng: &mut R) -> String {
-    let dollars = rng.random_range(0u64..=99_999_999u64);
-    let cents = rng.random_range(0u8..=99u8);
-    format!("${}.{:02}", with_thousands_separators(dollars), cents)
-}
-
-fn with_thousands_separators(value: u64) -> String {
-    let digits = value.to_string();
-    let mut grouped_rev = String::with_capacity(digits.len() + digits.len() / 3);
-    for (index, ch) in digits.chars().rev().enumerate() {
-        if index > 0 && index % 3 == 0 {
-            grouped_rev.push(',');
-        }
-        grouped_rev.push(ch);
-    }
-    grouped_rev.chars().rev().collect()
-}
-
 fn random_hex<R: Rng + ?Sized>(rng: &mut R) -> String {
     let value = rng.random_range(0u32..=u32::MAX);
     format!("0x{value:08X}")
@@ -352,17 +1187,10 @@ fn random_otp<R: Rng + ?Sized>(rng: &mut R) -> String {
     format!("{value:06}")
 }
 
-fn random_phone<R: Rng + ?Sized>(rng: &mut R) -> String {
-    let suffix: String = (0..8)
-        .map(|_| char::from(b'0' + rng.random_range(0..=9u8)))
-        .collect();
-    format!("09{suffix}")
-}
-
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
-    use rand::{SeedableRng, rngs::StdRng};
+    use uuid::Uuid;
 
     use super::*;
 
@@ -370,14 +1198,60 @@ mod tests {
         StdRng::seed_from_u64(42)
     }
 
+    /// Generated value rows, without the leading "Copy all" row that multi-value lists carry.
+    fn value_rows(feedback: &Feedback) -> &[Item] {
+        match feedback.items.first() {
+            Some(first) if first.title.starts_with("Copy all ") => &feedback.items[1..],
+            _ => &feedback.items,
+        }
+    }
+
+    fn config_with_max(max_count: usize) -> RuntimeConfig {
+        RuntimeConfig {
+            max_count,
+            ..RuntimeConfig::default()
+        }
+    }
+
     #[test]
     fn supported_formats_match_contract_order() {
         let keys: Vec<_> = Format::all().iter().map(|format| format.key()).collect();
         assert_eq!(
             keys,
             vec![
-                "email", "imei", "unit", "uuid", "int", "decimal", "percent", "currency", "hex",
-                "otp", "phone"
+                "email",
+                "imei",
+                "unit",
+                "uuid",
+                "int",
+                "decimal",
+                "percent",
+                "currency",
+                "hex",
+                "otp",
+                "phone",
+                "password",
+                "name",
+                "fullprofile",
+                "address",
+                "lorem",
+                "card",
+                "iban",
+                "mac",
+                "ipv4",
+                "ipv6",
+                "date",
+                "datetime",
+                "color",
+                "secret",
+                "key256",
+                "jwt",
+                "ulid",
+                "nanoid",
+                "ksuid",
+                "slug",
+                "hostname",
+                "username"
             ]
         );
     }
@@ -394,22 +1268,579 @@ mod tests {
 
     #[test]
     fn query_filter_is_case_insensitive_contains() {
-        let all = filter_formats(None);
+        let all = filter_formats(
+            &CountedQuery::parse(None, DEFAULT_MAX_COUNT),
+            &RuntimeConfig::default(),
+        );
         assert_eq!(all, Format::all().to_vec());
 
-        let filtered = filter_formats(Some("IMEI"));
+        let filtered = filter_formats(
+            &CountedQuery::parse(Some("IMEI"), DEFAULT_MAX_COUNT),
+            &RuntimeConfig::default(),
+        );
         assert_eq!(filtered, vec![Format::Imei]);
 
-        let none = filter_formats(Some("not-found"));
+        let none = filter_formats(
+            &CountedQuery::parse(Some("not-found"), DEFAULT_MAX_COUNT),
+            &RuntimeConfig::default(),
+        );
         assert!(none.is_empty());
     }
 
+    #[test]
+    fn counted_query_splits_trailing_count_and_clamps_it() {
+        assert_eq!(
+            CountedQuery::parse(Some(" UUID 25 "), 50),
+            CountedQuery {
+                text: String::from("uuid"),
+                args: Vec::new(),
+                count: Some(25),
+            }
+        );
+        assert_eq!(CountedQuery::parse(Some("hex 100"), 50).count, Some(50));
+        assert_eq!(CountedQuery::parse(Some("hex 0"), 50).count, Some(1));
+        assert_eq!(
+            CountedQuery::parse(Some("otp 99999999999999999999999"), 50).count,
+            Some(50)
+        );
+        assert_eq!(
+            CountedQuery::parse(Some("12"), 50),
+            CountedQuery {
+                text: String::new(),
+                args: Vec::new(),
+                count: Some(12),
+            }
+        );
+        assert_eq!(
+            CountedQuery::parse(Some("int"), 50),
+            CountedQuery {
+                text: String::from("int"),
+                args: Vec::new(),
+                count: None,
+            }
+        );
+        assert_eq!(CountedQuery::parse(Some("hex -3"), 50).count, None);
+        assert_eq!(
+            CountedQuery::parse(Some(" Lorem  3P 4 "), 50),
+            CountedQuery {
+                text: String::from("lorem"),
+                args: vec![String::from("3p")],
+                count: Some(4),
+            }
+        );
+        assert_eq!(CountedQuery::parse(None, 50).count, None);
+    }
+
+    #[test]
+    fn list_formats_with_count_expands_single_match_and_forwards_count_otherwise() {
+        let mut rng = seeded_rng();
+        let expanded =
+            list_formats_feedback_with_rng(Some("uuid 25"), &config_with_max(50), &mut rng);
+        assert_eq!(value_rows(&expanded).len(), 25);
+        assert!(
+            value_rows(&expanded)
+                .iter()
+                .all(|item| item.subtitle.as_deref() == Some("uuid"))
+        );
+
+        let listed = list_formats_feedback_with_rng(Some("e 3"), &config_with_max(50), &mut rng);
+        assert!(value_rows(&listed).len() > 1);
+        let cmd_mod = listed.items[0]
+            .mods
+            .as_ref()
+            .and_then(|mods| mods.get("cmd"))
+            .expect("cmd modifier should be present");
+        assert_eq!(
+            cmd_mod
+                .variables
+                .as_ref()
+                .and_then(|vars| vars.get("RANDOMER_COUNT"))
+                .map(String::as_str),
+            Some("3")
+        );
+    }
+
+    #[test]
+    fn generate_feedback_prefers_query_count_and_clamps_to_max() {
+        let mut rng = seeded_rng();
+        let from_query = generate_feedback_with_rng("hex 7", 10, &config_with_max(50), &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&from_query).len(), 7);
+
+        let clamped = generate_feedback_with_rng("hex", 80, &config_with_max(20), &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&clamped).len(), 20);
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible_across_formats() {
+        for format in Format::all() {
+            let first = generate_feedback_seeded(format.key(), 5, &RuntimeConfig::default(), 7)
+                .expect("should generate");
+            let second = generate_feedback_seeded(format.key(), 5, &RuntimeConfig::default(), 7)
+                .expect("should generate");
+            assert_eq!(first, second, "{}", format.key());
+        }
+
+        let seven =
+            generate_feedback_seeded("uuid", 5, &RuntimeConfig::default(), 7).expect("seed 7");
+        let eight =
+            generate_feedback_seeded("uuid", 5, &RuntimeConfig::default(), 8).expect("seed 8");
+        assert_ne!(seven, eight);
+
+        assert_eq!(
+            list_formats_feedback_seeded(None, &RuntimeConfig::default(), 7),
+            list_formats_feedback_seeded(None, &RuntimeConfig::default(), 7)
+        );
+        assert_eq!(
+            list_types_feedback_seeded(None, &RuntimeConfig::default(), 7),
+            list_types_feedback_seeded(None, &RuntimeConfig::default(), 7)
+        );
+    }
+
+    #[test]
+    fn password_query_number_sets_length_and_subtitle_reports_entropy() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let generated = generate_feedback_with_rng("password 24", 3, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&generated).len(), 3);
+        let policy = config.password.with_length(24);
+        for item in value_rows(&generated) {
+            assert_eq!(item.title.chars().count(), 24);
+            assert_eq!(
+                item.subtitle.as_deref(),
+                Some(
+                    format!(
+                        "password · 24 chars · ~{:.0} bits entropy",
+                        policy.entropy_bits()
+                    )
+                    .as_str()
+                )
+            );
+        }
+
+        let expanded = list_formats_feedback_with_rng(Some("password 64"), &config, &mut rng);
+        assert_eq!(value_rows(&expanded).len(), DEFAULT_EXPAND_COUNT);
+        assert!(
+            value_rows(&expanded)
+                .iter()
+                .all(|item| item.title.chars().count() == 64)
+        );
+
+        let types = list_types_feedback_with_rng(Some("pass 30"), &config, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("password 30"));
+        assert_eq!(
+            types.items[0]
+                .variables
+                .as_ref()
+                .and_then(|vars| vars.get("RANDOMER_COUNT"))
+                .map(String::as_str),
+            Some("10")
+        );
+    }
+
+    #[test]
+    fn fullprofile_joins_name_email_phone_and_offers_each_on_modifiers() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig {
+            locale: Locale::ZhTw,
+            ..RuntimeConfig::default()
+        };
+        let feedback = generate_feedback_with_rng("fullprofile", 5, &config, &mut rng)
+            .expect("should generate");
+
+        for item in value_rows(&feedback) {
+            let parts: Vec<&str> = item.title.split(", ").collect();
+            assert_eq!(parts.len(), 3);
+            assert_eq!(parts[0].chars().count(), 3);
+            assert!(
+                parts[1].ends_with(".com")
+                    || parts[1].ends_with(".net")
+                    || parts[1].ends_with(".org")
+            );
+            assert!(parts[2].starts_with("09") && parts[2].len() == 10);
+
+            let mods = item.mods.as_ref().expect("modifiers should be present");
+            assert_eq!(
+                mods.get("cmd").and_then(|m| m.arg.as_deref()),
+                Some(parts[1])
+            );
+            assert_eq!(
+                mods.get("alt").and_then(|m| m.arg.as_deref()),
+                Some(parts[2])
+            );
+            assert_eq!(
+                mods.get("ctrl").and_then(|m| m.arg.as_deref()),
+                Some(parts[0])
+            );
+            assert!(
+                item.subtitle
+                    .as_deref()
+                    .is_some_and(|subtitle| subtitle.contains("zh-TW"))
+            );
+        }
+    }
+
+    #[test]
+    fn address_rows_offer_street_and_postal_code_on_modifiers() {
+        let mut rng = seeded_rng();
+        for locale in [Locale::EnUs, Locale::ZhTw] {
+            let config = RuntimeConfig {
+                locale,
+                ..RuntimeConfig::default()
+            };
+            let feedback = generate_feedback_with_rng("address", 3, &config, &mut rng)
+                .expect("should generate");
+            for item in value_rows(&feedback) {
+                let mods = item.mods.as_ref().expect("modifiers should be present");
+                let street = mods
+                    .get("cmd")
+                    .and_then(|m| m.arg.as_deref())
+                    .expect("street");
+                let postal = mods
+                    .get("alt")
+                    .and_then(|m| m.arg.as_deref())
+                    .expect("postal code");
+                assert!(item.title.contains(street));
+                assert!(item.title.contains(postal));
+                assert!(
+                    item.subtitle
+                        .as_deref()
+                        .is_some_and(|subtitle| subtitle.contains(locale.as_str()))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lorem_query_sets_size_and_rejects_unknown_arguments() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let paragraphs =
+            generate_feedback_with_rng("lorem 3p", 2, &config, &mut rng).expect("should generate");
+        assert_eq!(value_rows(&paragraphs).len(), 2);
+        for item in value_rows(&paragraphs) {
+            assert_eq!(item.title.split("\n\n").count(), 3);
+            assert_eq!(item.subtitle.as_deref(), Some("lorem · 3 paragraphs"));
+        }
+
+        let words =
+            generate_feedback_with_rng("lorem 40", 1, &config, &mut rng).expect("should generate");
+        assert_eq!(value_rows(&words).len(), 1);
+        assert_eq!(words.items[0].title.split_whitespace().count(), 40);
+
+        let counted = generate_feedback_with_rng("lorem 2s 4", 1, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&counted).len(), 4);
+
+        let err = generate_feedback_with_rng("lorem 3x", 1, &config, &mut rng)
+            .expect_err("should reject size");
+        assert_eq!(err.to_string(), "invalid lorem argument: 3x");
+        let err = generate_feedback_with_rng("uuid v7", 1, &config, &mut rng)
+            .expect_err("uuid takes no arguments");
+        assert_eq!(err.to_string(), "invalid uuid argument: v7");
+
+        let types = list_types_feedback_with_rng(Some("lor 2s"), &config, &mut rng);
+        assert_eq!(value_rows(&types).len(), 1);
+        assert_eq!(types.items[0].arg.as_deref(), Some("lorem 2s"));
+
+        let expanded = list_formats_feedback_with_rng(Some("lorem 5w"), &config, &mut rng);
+        assert_eq!(value_rows(&expanded).len(), DEFAULT_EXPAND_COUNT);
+        assert!(
+            value_rows(&expanded)
+                .iter()
+                .all(|item| item.title.split_whitespace().count() == 5)
+        );
+    }
+
+    #[test]
+    fn card_query_selects_brand_and_subtitle_carries_expiry_and_cvc() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let feedback = generate_feedback_with_rng("card amex 4", 1, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&feedback).len(), 4);
+        for item in value_rows(&feedback) {
+            assert!(item.title.starts_with("34") || item.title.starts_with("37"));
+            assert_eq!(item.title.len(), 17);
+            let subtitle = item.subtitle.as_deref().expect("subtitle");
+            assert!(subtitle.starts_with("card · Amex · exp "));
+            let mods = item.mods.as_ref().expect("modifiers should be present");
+            assert_eq!(
+                mods.get("cmd").and_then(|m| m.arg.clone()),
+                Some(item.title.replace(' ', ""))
+            );
+            let cvc = mods
+                .get("ctrl")
+                .and_then(|m| m.arg.as_deref())
+                .expect("cvc");
+            assert_eq!(cvc.len(), 4);
+            assert!(subtitle.contains(&format!("CVC {cvc}")));
+        }
+
+        let types = list_types_feedback_with_rng(Some("card mc"), &config, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("card mastercard"));
+
+        let err = generate_feedback_with_rng("card discover", 1, &config, &mut rng)
+            .expect_err("unknown brand");
+        assert_eq!(err.to_string(), "invalid card argument: discover");
+    }
+
+    #[test]
+    fn iban_rows_use_configured_countries_and_offer_electronic_form() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig {
+            iban_countries: vec![IbanCountry::parse("NL").expect("bundled country")],
+            ..RuntimeConfig::default()
+        };
+
+        let feedback =
+            generate_feedback_with_rng("iban", 5, &config, &mut rng).expect("should generate");
+        for item in value_rows(&feedback) {
+            assert!(item.title.starts_with("NL"));
+            assert_eq!(item.title.split(' ').count(), 5);
+            assert_eq!(
+                item.subtitle.as_deref(),
+                Some("iban · NL · Cmd+Enter: copy without spaces")
+            );
+            let mods = item.mods.as_ref().expect("modifiers should be present");
+            assert_eq!(
+                mods.get("cmd").and_then(|m| m.arg.clone()),
+                Some(item.title.replace(' ', ""))
+            );
+        }
+    }
+
+    #[test]
+    fn mac_query_selects_separator_style() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let colon =
+            generate_feedback_with_rng("mac", 1, &config, &mut rng).expect("should generate");
+        assert_eq!(colon.items[0].title.split(':').count(), 6);
+        assert_eq!(colon.items[0].subtitle.as_deref(), Some("mac · colon"));
+
+        let dotted = generate_feedback_with_rng("mac cisco 3", 1, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&dotted).len(), 3);
+        for item in value_rows(&dotted) {
+            assert_eq!(item.title.len(), 14);
+            assert_eq!(item.title.split('.').count(), 3);
+            assert_eq!(item.subtitle.as_deref(), Some("mac · dot"));
+        }
+
+        let types = list_types_feedback_with_rng(Some("mac -"), &config, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("mac dash"));
+    }
+
+    #[test]
+    fn ip_queries_select_scope_and_cidr() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let private =
+            generate_feedback_with_rng("ipv4", 5, &config, &mut rng).expect("should generate");
+        for item in value_rows(&private) {
+            let address: std::net::Ipv4Addr = item.title.parse().expect("ipv4");
+            assert!(address.is_private());
+            assert_eq!(item.subtitle.as_deref(), Some("ipv4 · private"));
+        }
+
+        let networks = generate_feedback_with_rng("ipv6 cidr public 3", 1, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&networks).len(), 3);
+        for item in value_rows(&networks) {
+            let (address, _) = item.title.split_once('/').expect("CIDR");
+            address.parse::<std::net::Ipv6Addr>().expect("ipv6");
+            assert_eq!(item.subtitle.as_deref(), Some("ipv6 · public · CIDR"));
+        }
+
+        let types = list_types_feedback_with_rng(Some("ip public"), &config, &mut rng);
+        let args: Vec<Option<&str>> = types.items.iter().map(|item| item.arg.as_deref()).collect();
+        assert_eq!(args, [Some("ipv4 public"), Some("ipv6 public")]);
+
+        let err = generate_feedback_with_rng("ipv4 ula", 1, &config, &mut rng)
+            .expect_err("v6-only scope");
+        assert_eq!(err.to_string(), "invalid ipv4 argument: ula");
+    }
+
+    #[test]
+    fn date_range_query_bounds_values_and_offers_epoch() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let feedback = generate_feedback_with_rng("datetime 2020..2021 4", 1, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&feedback).len(), 4);
+        for item in value_rows(&feedback) {
+            assert!(item.title.starts_with("2020-") || item.title.starts_with("2021-"));
+            assert_eq!(
+                item.subtitle.as_deref(),
+                Some("datetime · 2020..2021 · Cmd+Enter: copy unix epoch")
+            );
+            let epoch: i64 = item
+                .mods
+                .as_ref()
+                .and_then(|mods| mods.get("cmd"))
+                .and_then(|m| m.arg.as_deref())
+                .expect("epoch modifier")
+                .parse()
+                .expect("integer epoch");
+            assert!((1_577_836_800..1_640_995_200).contains(&epoch));
+        }
+
+        let types =
+            list_types_feedback_with_rng(Some("date 2024-01-01..2024-06-30"), &config, &mut rng);
+        let args: Vec<Option<&str>> = types.items.iter().map(|item| item.arg.as_deref()).collect();
+        assert_eq!(
+            args,
+            [
+                Some("date 2024-01-01..2024-06-30"),
+                Some("datetime 2024-01-01..2024-06-30")
+            ]
+        );
+
+        let err = generate_feedback_with_rng("date 2024..2020", 1, &config, &mut rng)
+            .expect_err("reversed range");
+        assert_eq!(err.to_string(), "invalid date argument: 2024..2020");
+    }
+
+    #[test]
+    fn color_rows_show_hex_title_with_rgb_and_hsl_modifiers() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let feedback = generate_feedback_with_rng("color dark 3", 1, &config, &mut rng)
+            .expect("should generate");
+        assert_eq!(value_rows(&feedback).len(), 3);
+        for item in value_rows(&feedback) {
+            assert_eq!(item.title.len(), 7);
+            assert!(item.title.starts_with('#'));
+            let mods = item.mods.as_ref().expect("modifiers should be present");
+            let rgb = mods.get("cmd").and_then(|m| m.arg.clone()).expect("rgb");
+            let hsl = mods.get("alt").and_then(|m| m.arg.clone()).expect("hsl");
+            assert!(rgb.starts_with("rgb(") && hsl.starts_with("hsl("));
+            assert_eq!(
+                item.subtitle.as_deref(),
+                Some(format!("color · {rgb} · {hsl}").as_str())
+            );
+        }
+
+        let types = list_types_feedback_with_rng(Some("color pastel"), &config, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("color pastel"));
+    }
+
+    #[test]
+    fn secret_query_number_sets_byte_length() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let feedback =
+            generate_feedback_with_rng("secret 48", 2, &config, &mut rng).expect("should generate");
+        assert_eq!(value_rows(&feedback).len(), 2);
+        for item in value_rows(&feedback) {
+            assert_eq!(item.title.len(), 64);
+            assert_eq!(
+                item.subtitle.as_deref(),
+                Some("secret · 48 bytes · base64url")
+            );
+        }
+
+        let types = list_types_feedback_with_rng(Some("secret 48"), &config, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("secret 48"));
+    }
+
+    #[test]
+    fn seeded_jwt_uses_the_pinned_clock() {
+        let feedback = generate_feedback_seeded("jwt", 1, &RuntimeConfig::default(), 7)
+            .expect("should generate");
+        let claims = feedback.items[0]
+            .title
+            .split('.')
+            .nth(1)
+            .expect("claims segment");
+        let claims = String::from_utf8(
+            base64::Engine::decode(&base64::engine::general_purpose::URL_SAFE_NO_PAD, claims)
+                .expect("base64url"),
+        )
+        .expect("utf-8");
+        assert!(claims.contains(&format!("\"iat\":{}", config::SEEDED_NOW_TIMESTAMP)));
+    }
+
+    #[test]
+    fn time_prefixed_ids_follow_the_configured_clock() {
+        let mut rng = seeded_rng();
+        let earlier = RuntimeConfig {
+            now: chrono::DateTime::from_timestamp(1_700_000_000, 0),
+            ..RuntimeConfig::default()
+        };
+        let later = RuntimeConfig {
+            now: chrono::DateTime::from_timestamp(1_700_000_001, 0),
+            ..RuntimeConfig::default()
+        };
+
+        for key in ["ulid", "ksuid"] {
+            let first =
+                generate_feedback_with_rng(key, 5, &earlier, &mut rng).expect("should generate");
+            let second =
+                generate_feedback_with_rng(key, 5, &later, &mut rng).expect("should generate");
+            let newest_earlier = value_rows(&first)
+                .iter()
+                .map(|item| &item.title)
+                .max()
+                .expect("values");
+            let oldest_later = value_rows(&second)
+                .iter()
+                .map(|item| &item.title)
+                .min()
+                .expect("values");
+            assert!(newest_earlier < oldest_later, "{key}");
+        }
+    }
+
+    #[test]
+    fn templates_are_listed_after_builtins_and_generate_by_name() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::from_pairs([("RANDOMER_TEMPLATE_ORDER", "ORD-####-AAAA")])
+            .expect("config");
+
+        let formats = list_formats_feedback_with_rng(None, &config, &mut rng);
+        assert_eq!(value_rows(&formats).len(), Format::all().len() + 1);
+        let last = formats.items.last().expect("template row");
+        assert!(last.title.starts_with("ORD-"));
+        assert_eq!(
+            last.icon.as_ref().map(|icon| icon.path.as_str()),
+            Some("assets/icons/template.png")
+        );
+
+        let generated =
+            generate_feedback_with_rng("ORDER 3", 1, &config, &mut rng).expect("should generate");
+        assert_eq!(value_rows(&generated).len(), 3);
+        for item in value_rows(&generated) {
+            assert_eq!(item.title.len(), "ORD-0000-AAAA".len());
+            assert_eq!(
+                item.subtitle.as_deref(),
+                Some("order · template ORD-####-AAAA")
+            );
+        }
+
+        let err = generate_feedback_with_rng("order", 1, &RuntimeConfig::default(), &mut rng)
+            .expect_err("template is not configured");
+        assert_eq!(err, RandomerError::UnknownFormat(String::from("order")));
+    }
+
     #[test]
     fn list_formats_feedback_contains_menu_contract_fields() {
         let mut rng = seeded_rng();
-        let feedback = list_formats_feedback_with_rng(Some("hex"), &mut rng);
+        let feedback =
+            list_formats_feedback_with_rng(Some("hex"), &RuntimeConfig::default(), &mut rng);
 
-        assert_eq!(feedback.items.len(), 1);
+        assert_eq!(value_rows(&feedback).len(), 1);
         let item = &feedback.items[0];
         assert_eq!(item.arg.as_deref(), Some(item.title.as_str()));
 
@@ -448,10 +1879,11 @@ mod tests {
     #[test]
     fn generate_feedback_emits_requested_count_and_fields() {
         let mut rng = seeded_rng();
-        let feedback = generate_feedback_with_rng("OtP", 3, &mut rng).expect("should generate");
+        let feedback = generate_feedback_with_rng("OtP", 3, &RuntimeConfig::default(), &mut rng)
+            .expect("should generate");
 
-        assert_eq!(feedback.items.len(), 3);
-        for item in feedback.items {
+        assert_eq!(value_rows(&feedback).len(), 3);
+        for item in value_rows(&feedback) {
             assert_eq!(item.arg.as_deref(), Some(item.title.as_str()));
             assert_eq!(item.subtitle.as_deref(), Some("otp"));
             assert_eq!(
@@ -461,27 +1893,126 @@ mod tests {
         }
     }
 
+    #[test]
+    fn number_ranges_bound_values_and_flow_into_expand_args() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let ints = generate_feedback_with_rng("int 1..1000 20", 1, &config, &mut rng)
+            .expect("should generate");
+        for item in value_rows(&ints) {
+            let value: u32 = item.title.parse().expect("int");
+            assert!((1..=1000).contains(&value));
+            assert_eq!(item.subtitle.as_deref(), Some("int · 1..1000"));
+        }
+
+        let decimals = generate_feedback_with_rng("decimal 0..1 4dp", 5, &config, &mut rng)
+            .expect("should generate");
+        for item in value_rows(&decimals) {
+            assert!(item.title.starts_with("0.") || item.title == "1.0000");
+            assert_eq!(item.title.len(), "0.1234".len());
+            assert_eq!(item.subtitle.as_deref(), Some("decimal · 0..1 · 4 dp"));
+        }
+
+        let euros = generate_feedback_with_rng("currency 10..500 EUR", 5, &config, &mut rng)
+            .expect("should generate");
+        for item in value_rows(&euros) {
+            let amount: f64 = item.title.trim_start_matches('€').parse().expect("amount");
+            assert!((10.0..=500.0).contains(&amount), "{}", item.title);
+            assert_eq!(item.subtitle.as_deref(), Some("currency · EUR · 10..500"));
+        }
+
+        let types = list_types_feedback_with_rng(Some("curr eur 10..500"), &config, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("currency 10..500 eur"));
+    }
+
+    #[test]
+    fn invalid_number_ranges_become_error_rows_and_errors() {
+        let mut rng = seeded_rng();
+        let config = RuntimeConfig::default();
+
+        let listed = list_formats_feedback_with_rng(Some("int 1000..1"), &config, &mut rng);
+        assert_eq!(listed.items.len(), 1);
+        assert_eq!(listed.items[0].title, "Invalid int range");
+        assert_eq!(listed.items[0].valid, Some(false));
+        assert_eq!(
+            listed.items[0].subtitle.as_deref(),
+            Some("invalid int range: 1000..1 (start is greater than end) · e.g. int 1..1000")
+        );
+
+        let types = list_types_feedback_with_rng(Some("decimal 0..x"), &config, &mut rng);
+        assert_eq!(types.items[0].title, "Invalid decimal range");
+
+        let err = generate_feedback_with_rng("currency -5..5", 1, &config, &mut rng)
+            .expect_err("negative currency");
+        assert_eq!(
+            err.to_string(),
+            "invalid currency range: -5..5 (currency amounts must not be negative)"
+        );
+        let err = generate_feedback_with_rng("int 1..5 eur", 1, &config, &mut rng)
+            .expect_err("unknown argument");
+        assert_eq!(
+            err,
+            RandomerError::InvalidArgument {
+                format: "int",
+                argument: String::from("1..5 eur"),
+            }
+        );
+    }
+
+    #[test]
+    fn multiple_values_lead_with_a_copy_all_row() {
+        let config = RuntimeConfig::default();
+        let feedback =
+            generate_feedback_seeded("lorem 2s 3", 1, &config, 7).expect("should generate");
+        let values = generate_values_seeded("lorem 2s 3", 1, &config, 7).expect("should generate");
+
+        assert_eq!(feedback.items.len(), 4);
+        let copy_all = &feedback.items[0];
+        assert_eq!(copy_all.title, "Copy all 3 values");
+        assert_eq!(
+            copy_all.subtitle.as_deref(),
+            Some("lorem · Enter: copy 3 values, one per line")
+        );
+        assert_eq!(
+            copy_all.arg.as_deref(),
+            Some(values.values.join("\n").as_str())
+        );
+        assert_eq!(values.format, "lorem");
+        let titles: Vec<&str> = value_rows(&feedback)
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, values.values);
+
+        let single = generate_feedback_seeded("uuid", 1, &config, 7).expect("should generate");
+        assert_eq!(single.items.len(), 1);
+        assert!(!single.items[0].title.starts_with("Copy all"));
+    }
+
     #[test]
     fn generate_feedback_rejects_unknown_format() {
         let mut rng = seeded_rng();
-        let err =
-            generate_feedback_with_rng("unknown", 1, &mut rng).expect_err("should reject format");
+        let err = generate_feedback_with_rng("unknown", 1, &RuntimeConfig::default(), &mut rng)
+            .expect_err("should reject format");
         assert_eq!(err, RandomerError::UnknownFormat(String::from("unknown")));
     }
 
     #[test]
     fn generate_feedback_rejects_zero_count() {
         let mut rng = seeded_rng();
-        let err = generate_feedback_with_rng("email", 0, &mut rng).expect_err("should reject 0");
+        let err = generate_feedback_with_rng("email", 0, &RuntimeConfig::default(), &mut rng)
+            .expect_err("should reject 0");
         assert_eq!(err, RandomerError::InvalidCount(0));
     }
 
     #[test]
     fn list_types_feedback_contains_type_selector_contract_fields() {
         let mut rng = seeded_rng();
-        let feedback = list_types_feedback_with_rng(Some("in"), &mut rng);
+        let feedback =
+            list_types_feedback_with_rng(Some("in"), &RuntimeConfig::default(), &mut rng);
 
-        assert_eq!(feedback.items.len(), 1);
+        assert_eq!(value_rows(&feedback).len(), 1);
         let item = &feedback.items[0];
         assert_eq!(item.title, "int");
         assert_eq!(item.arg.as_deref(), Some("int"));
@@ -507,7 +2038,7 @@ mod tests {
     fn format_email_shape_matches_legacy_contract() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Email.generate_with_rng(&mut rng);
+            let value = Format::Email.generate_with_rng(&mut rng, &RuntimeConfig::default());
             let (local, domain_tld) = value.split_once('@').expect("email should contain @");
             let domain = domain_tld
                 .strip_suffix(".com")
@@ -524,7 +2055,7 @@ mod tests {
     fn format_imei_has_15_digits_and_valid_checksum() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Imei.generate_with_rng(&mut rng);
+            let value = Format::Imei.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert_eq!(value.len(), 15);
             assert!(value.chars().all(|ch| ch.is_ascii_digit()));
             assert!(imei_has_valid_checksum(value.as_str()));
@@ -535,7 +2066,7 @@ mod tests {
     fn format_unit_has_legacy_shape_and_checksum() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Unit.generate_with_rng(&mut rng);
+            let value = Format::Unit.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert_eq!(value.len(), 11);
             assert!(value.chars().take(3).all(|ch| ch.is_ascii_uppercase()));
             assert!(matches!(value.chars().nth(3), Some('U' | 'J' | 'Z')));
@@ -552,8 +2083,9 @@ mod tests {
 
     #[test]
     fn format_uuid_is_rfc4122_v4() {
+        let mut rng = seeded_rng();
         for _ in 0..50 {
-            let value = Format::Uuid.generate_with_rng(&mut seeded_rng());
+            let value = Format::Uuid.generate_with_rng(&mut rng, &RuntimeConfig::default());
             let parsed = Uuid::parse_str(value.as_str()).expect("uuid should parse");
             assert_eq!(parsed.get_version_num(), 4);
         }
@@ -563,7 +2095,7 @@ mod tests {
     fn format_int_is_digits_only() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Int.generate_with_rng(&mut rng);
+            let value = Format::Int.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert!(!value.is_empty());
             assert!(value.chars().all(|ch| ch.is_ascii_digit()));
         }
@@ -573,7 +2105,7 @@ mod tests {
     fn format_decimal_has_fixed_two_decimals() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Decimal.generate_with_rng(&mut rng);
+            let value = Format::Decimal.generate_with_rng(&mut rng, &RuntimeConfig::default());
             let (whole, fraction) = value
                 .split_once('.')
                 .expect("decimal should contain decimal point");
@@ -587,7 +2119,7 @@ mod tests {
     fn format_percent_has_suffix_and_bounds() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Percent.generate_with_rng(&mut rng);
+            let value = Format::Percent.generate_with_rng(&mut rng, &RuntimeConfig::default());
             let number = value
                 .strip_suffix('%')
                 .expect("percent should end with % suffix");
@@ -610,7 +2142,7 @@ mod tests {
     fn format_currency_has_symbol_grouping_and_scale() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Currency.generate_with_rng(&mut rng);
+            let value = Format::Currency.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert!(is_currency_shape(value.as_str()));
         }
     }
@@ -619,7 +2151,7 @@ mod tests {
     fn format_hex_has_prefix_fixed_width_and_uppercase() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Hex.generate_with_rng(&mut rng);
+            let value = Format::Hex.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert_eq!(value.len(), 10);
             assert!(value.starts_with("0x"));
             assert!(
@@ -635,7 +2167,7 @@ mod tests {
     fn format_otp_is_six_digits_zero_padded() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Otp.generate_with_rng(&mut rng);
+            let value = Format::Otp.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert_eq!(value.len(), 6);
             assert!(value.chars().all(|ch| ch.is_ascii_digit()));
         }
@@ -645,13 +2177,60 @@ mod tests {
     fn format_phone_is_taiwan_mobile_shape() {
         let mut rng = seeded_rng();
         for _ in 0..100 {
-            let value = Format::Phone.generate_with_rng(&mut rng);
+            let value = Format::Phone.generate_with_rng(&mut rng, &RuntimeConfig::default());
             assert_eq!(value.len(), 10);
             assert!(value.starts_with("09"));
             assert!(value.chars().all(|ch| ch.is_ascii_digit()));
         }
     }
 
+    #[test]
+    fn format_phone_follows_explicit_locale_unless_the_query_picks_a_region() {
+        let mut rng = seeded_rng();
+        let en_us = RuntimeConfig {
+            locale: Locale::EnUs,
+            locale_explicit: true,
+            ..RuntimeConfig::default()
+        };
+        let zh_tw = RuntimeConfig {
+            locale: Locale::ZhTw,
+            locale_explicit: true,
+            ..RuntimeConfig::default()
+        };
+
+        let us = generate_feedback_with_rng("phone", 1, &en_us, &mut rng).expect("should generate");
+        assert!(us.items[0].title.starts_with('('));
+        assert_eq!(
+            us.items[0].subtitle.as_deref(),
+            Some("phone · US · Cmd+Enter: copy E.164")
+        );
+
+        let jp =
+            generate_feedback_with_rng("phone jp 2", 1, &zh_tw, &mut rng).expect("should generate");
+        for item in value_rows(&jp) {
+            assert!(item.title.starts_with('0') && item.title.matches('-').count() == 2);
+            let e164 = item
+                .mods
+                .as_ref()
+                .and_then(|mods| mods.get("cmd"))
+                .and_then(|m| m.arg.as_deref())
+                .expect("E.164 modifier");
+            assert_eq!(e164, format!("+81{}", item.title[1..].replace('-', "")));
+        }
+
+        let types = list_types_feedback_with_rng(Some("phone uk"), &zh_tw, &mut rng);
+        assert_eq!(types.items[0].arg.as_deref(), Some("phone uk"));
+        let err = generate_feedback_with_rng("phone fr", 1, &zh_tw, &mut rng)
+            .expect_err("unknown region");
+        assert_eq!(
+            err,
+            RandomerError::InvalidArgument {
+                format: "phone",
+                argument: String::from("fr"),
+            }
+        );
+    }
+
     fn imei_has_valid_checksum(value: &str) -> bool {
         if value.len() != 15 || !value.chars().all(|ch| ch.is_ascii_digit()) {
             return false;
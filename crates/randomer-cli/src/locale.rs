@@ -0,0 +1,39 @@
+/// Data locale for locale-aware formats, selected by `RANDOMER_LOCALE` (`en-US` default).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    EnUs,
+    ZhTw,
+}
+
+impl Locale {
+    /// Parse a `RANDOMER_LOCALE` value; case, `-`/`_`, and the bare language code are all accepted.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "en" | "en-us" => Some(Self::EnUs),
+            "zh" | "zh-tw" | "zh-hant" => Some(Self::ZhTw),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::EnUs => "en-US",
+            Self::ZhTw => "zh-TW",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_case_separator_and_language_aliases() {
+        assert_eq!(Locale::parse(" EN_us "), Some(Locale::EnUs));
+        assert_eq!(Locale::parse("zh-TW"), Some(Locale::ZhTw));
+        assert_eq!(Locale::parse("zh"), Some(Locale::ZhTw));
+        assert_eq!(Locale::parse("fr-FR"), None);
+        assert_eq!(Locale::ZhTw.as_str(), "zh-TW");
+    }
+}
@@ -0,0 +1,293 @@
+use rand::{Rng, RngExt};
+
+pub const LOREM_WORDS_MAX: usize = 1000;
+pub const LOREM_SENTENCES_MAX: usize = 100;
+pub const LOREM_PARAGRAPHS_MAX: usize = 20;
+
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "reprehenderit",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "eu",
+    "fugiat",
+    "nulla",
+    "pariatur",
+    "excepteur",
+    "sint",
+    "occaecat",
+    "cupidatat",
+    "non",
+    "proident",
+    "sunt",
+    "culpa",
+    "qui",
+    "officia",
+    "deserunt",
+    "mollit",
+    "anim",
+    "id",
+    "est",
+    "laborum",
+];
+
+const SENTENCE_WORDS: std::ops::RangeInclusive<usize> = 6..=14;
+const PARAGRAPH_SENTENCES: std::ops::RangeInclusive<usize> = 3..=6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoremUnit {
+    Words,
+    Sentences,
+    Paragraphs,
+}
+
+impl LoremUnit {
+    fn suffix(self) -> char {
+        match self {
+            Self::Words => 'w',
+            Self::Sentences => 's',
+            Self::Paragraphs => 'p',
+        }
+    }
+
+    fn max(self) -> usize {
+        match self {
+            Self::Words => LOREM_WORDS_MAX,
+            Self::Sentences => LOREM_SENTENCES_MAX,
+            Self::Paragraphs => LOREM_PARAGRAPHS_MAX,
+        }
+    }
+
+    fn label(self, amount: usize) -> &'static str {
+        match (self, amount) {
+            (Self::Words, 1) => "word",
+            (Self::Words, _) => "words",
+            (Self::Sentences, 1) => "sentence",
+            (Self::Sentences, _) => "sentences",
+            (Self::Paragraphs, 1) => "paragraph",
+            (Self::Paragraphs, _) => "paragraphs",
+        }
+    }
+}
+
+/// Amount and granularity of `lorem` filler text, e.g. `3p` for three paragraphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoremSpec {
+    pub amount: usize,
+    pub unit: LoremUnit,
+}
+
+impl Default for LoremSpec {
+    fn default() -> Self {
+        Self {
+            amount: 1,
+            unit: LoremUnit::Paragraphs,
+        }
+    }
+}
+
+impl LoremSpec {
+    /// Parse `<n>[w|s|p]` (bare numbers are words), clamping `n` to `1..=` the unit maximum.
+    pub fn parse(token: &str) -> Option<Self> {
+        let token = token.trim().to_ascii_lowercase();
+        let (digits, unit) = match token.char_indices().last()? {
+            (index, 'w') => (&token[..index], LoremUnit::Words),
+            (index, 's') => (&token[..index], LoremUnit::Sentences),
+            (index, 'p') => (&token[..index], LoremUnit::Paragraphs),
+            _ => (token.as_str(), LoremUnit::Words),
+        };
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let amount = digits.parse::<usize>().unwrap_or(usize::MAX);
+        Some(Self {
+            amount: amount.clamp(1, unit.max()),
+            unit,
+        })
+    }
+
+    /// Query token for this spec, e.g. `3p`.
+    pub fn token(self) -> String {
+        format!("{}{}", self.amount, self.unit.suffix())
+    }
+
+    /// Human label, e.g. `3 paragraphs`.
+    pub fn describe(self) -> String {
+        format!("{} {}", self.amount, self.unit.label(self.amount))
+    }
+
+    pub(crate) fn generate<R: Rng + ?Sized>(self, rng: &mut R) -> String {
+        match self.unit {
+            LoremUnit::Words => words(rng, self.amount).join(" "),
+            LoremUnit::Sentences => (0..self.amount)
+                .map(|_| sentence(rng))
+                .collect::<Vec<_>>()
+                .join(" "),
+            LoremUnit::Paragraphs => (0..self.amount)
+                .map(|_| {
+                    let sentences = rng.random_range(PARAGRAPH_SENTENCES);
+                    (0..sentences)
+                        .map(|_| sentence(rng))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        }
+    }
+}
+
+fn words<R: Rng + ?Sized>(rng: &mut R, count: usize) -> Vec<&'static str> {
+    (0..count)
+        .map(|_| WORDS[rng.random_range(0..WORDS.len())])
+        .collect()
+}
+
+/// Capitalized sentence ending in `.`, with a comma after an inner word about half the time.
+fn sentence<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let count = rng.random_range(SENTENCE_WORDS);
+    let mut words: Vec<String> = words(rng, count).into_iter().map(String::from).collect();
+    if rng.random_bool(0.5) {
+        let index = rng.random_range(1..count - 2);
+        words[index].push(',');
+    }
+
+    let mut sentence = words.join(" ");
+    if let Some(first) = sentence.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    sentence.push('.');
+    sentence
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn word_count(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn parse_reads_amount_and_unit_and_clamps() {
+        assert_eq!(
+            LoremSpec::parse("3P"),
+            Some(LoremSpec {
+                amount: 3,
+                unit: LoremUnit::Paragraphs,
+            })
+        );
+        assert_eq!(
+            LoremSpec::parse("40"),
+            Some(LoremSpec {
+                amount: 40,
+                unit: LoremUnit::Words,
+            })
+        );
+        assert_eq!(LoremSpec::parse("0s").map(|spec| spec.amount), Some(1));
+        assert_eq!(
+            LoremSpec::parse("999p").map(|spec| spec.amount),
+            Some(LOREM_PARAGRAPHS_MAX)
+        );
+        assert_eq!(LoremSpec::parse("p"), None);
+        assert_eq!(LoremSpec::parse("3x"), None);
+        assert_eq!(LoremSpec::parse("three"), None);
+        assert_eq!(
+            LoremSpec::parse("3p").map(LoremSpec::token).as_deref(),
+            Some("3p")
+        );
+    }
+
+    #[test]
+    fn words_mode_emits_exact_word_count() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for amount in [1, 7, 250] {
+            let text = LoremSpec {
+                amount,
+                unit: LoremUnit::Words,
+            }
+            .generate(&mut rng);
+            assert_eq!(word_count(&text), amount);
+            assert!(text.split(' ').all(|word| WORDS.contains(&word)));
+        }
+    }
+
+    #[test]
+    fn sentences_mode_emits_capitalized_sentences_within_word_bounds() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let text = LoremSpec {
+            amount: 5,
+            unit: LoremUnit::Sentences,
+        }
+        .generate(&mut rng);
+
+        let sentences: Vec<&str> = text.split_inclusive('.').map(str::trim).collect();
+        assert_eq!(sentences.len(), 5);
+        for sentence in sentences {
+            assert!(sentence.starts_with(|ch: char| ch.is_ascii_uppercase()));
+            assert!(SENTENCE_WORDS.contains(&word_count(sentence)));
+        }
+    }
+
+    #[test]
+    fn paragraphs_mode_separates_paragraphs_with_blank_lines() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let text = LoremSpec {
+            amount: 3,
+            unit: LoremUnit::Paragraphs,
+        }
+        .generate(&mut rng);
+
+        let paragraphs: Vec<&str> = text.split("\n\n").collect();
+        assert_eq!(paragraphs.len(), 3);
+        for paragraph in paragraphs {
+            let sentences = paragraph.matches('.').count();
+            assert!(PARAGRAPH_SENTENCES.contains(&sentences));
+            assert!(word_count(paragraph) >= sentences * SENTENCE_WORDS.start());
+            assert!(word_count(paragraph) <= sentences * SENTENCE_WORDS.end());
+        }
+    }
+}
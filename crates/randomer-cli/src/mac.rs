@@ -0,0 +1,104 @@
+use rand::Rng;
+
+/// Separator style for the `mac` format, selected with a query argument such as `mac dash`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MacStyle {
+    /// `02:1a:2b:3c:4d:5e`
+    #[default]
+    Colon,
+    /// `02-1a-2b-3c-4d-5e`
+    Dash,
+    /// Cisco dotted, `021a.2b3c.4d5e`
+    Dot,
+}
+
+impl MacStyle {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "colon" | ":" => Some(Self::Colon),
+            "dash" | "-" => Some(Self::Dash),
+            "dot" | "cisco" | "." => Some(Self::Dot),
+            _ => None,
+        }
+    }
+
+    /// Query argument for this style, e.g. `dash`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Colon => "colon",
+            Self::Dash => "dash",
+            Self::Dot => "dot",
+        }
+    }
+
+    fn format(self, octets: [u8; 6]) -> String {
+        let hex: Vec<String> = octets.iter().map(|octet| format!("{octet:02x}")).collect();
+        match self {
+            Self::Colon => hex.join(":"),
+            Self::Dash => hex.join("-"),
+            Self::Dot => hex
+                .chunks(2)
+                .map(|pair| pair.concat())
+                .collect::<Vec<_>>()
+                .join("."),
+        }
+    }
+}
+
+/// Random MAC address with the multicast bit cleared and the locally-administered bit set, so it never collides
+/// with a vendor-assigned address.
+pub(crate) fn random_mac<R: Rng + ?Sized>(rng: &mut R, style: MacStyle) -> String {
+    let mut octets = [0u8; 6];
+    rng.fill_bytes(&mut octets);
+    octets[0] = (octets[0] & 0b1111_1100) | 0b0000_0010;
+    style.format(octets)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn first_octet(mac: &str) -> u8 {
+        u8::from_str_radix(&mac[..2], 16).expect("hex octet")
+    }
+
+    #[test]
+    fn macs_are_unicast_and_locally_administered() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..200 {
+            let octet = first_octet(&random_mac(&mut rng, MacStyle::Colon));
+            assert_eq!(octet & 0b01, 0, "multicast bit must be clear");
+            assert_eq!(octet & 0b10, 0b10, "locally-administered bit must be set");
+        }
+    }
+
+    #[test]
+    fn styles_match_separator_shapes() {
+        let octets = [0x02, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e];
+        assert_eq!(MacStyle::Colon.format(octets), "02:1a:2b:3c:4d:5e");
+        assert_eq!(MacStyle::Dash.format(octets), "02-1a-2b-3c-4d-5e");
+        assert_eq!(MacStyle::Dot.format(octets), "021a.2b3c.4d5e");
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let dotted = random_mac(&mut rng, MacStyle::Dot);
+        let groups: Vec<&str> = dotted.split('.').collect();
+        assert_eq!(groups.len(), 3);
+        assert!(groups.iter().all(|group| {
+            group.len() == 4
+                && group
+                    .chars()
+                    .all(|ch| ch.is_ascii_digit() || ('a'..='f').contains(&ch))
+        }));
+    }
+
+    #[test]
+    fn parse_accepts_style_aliases() {
+        assert_eq!(MacStyle::parse("COLON"), Some(MacStyle::Colon));
+        assert_eq!(MacStyle::parse("-"), Some(MacStyle::Dash));
+        assert_eq!(MacStyle::parse("cisco"), Some(MacStyle::Dot));
+        assert_eq!(MacStyle::parse("space"), None);
+    }
+}
@@ -5,7 +5,11 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 use clap::{Parser, Subcommand, ValueEnum};
-use randomer_cli::{RandomerError, generate_feedback, list_formats_feedback, list_types_feedback};
+use randomer_cli::{
+    GeneratedValues, RandomerError, RuntimeConfig, generate_feedback, generate_feedback_seeded,
+    generate_values, generate_values_seeded, list_formats_feedback, list_formats_feedback_seeded,
+    list_types_feedback, list_types_feedback_seeded,
+};
 use workflow_common::{
     EnvelopePayloadKind, OutputMode, build_error_envelope, build_success_envelope,
 };
@@ -15,37 +19,43 @@ use workflow_common::{
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Seed for reproducible output; overrides RANDOMER_SEED.
+    #[arg(long, global = true)]
+    seed: Option<u64>,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
     /// List supported formats as Alfred menu items.
+    #[command(visible_alias = "list")]
     ListFormats {
-        /// Optional case-insensitive filter against format keys.
+        /// Optional case-insensitive filter against format keys, with an optional trailing count.
         #[arg(long)]
         query: Option<String>,
-        /// Output mode: workflow-compatible Alfred JSON or service envelope JSON.
+        /// Output mode: workflow-compatible Alfred JSON, service envelope JSON, or plain `title<TAB>subtitle` lines.
         #[arg(long, value_enum, default_value_t = OutputModeArg::AlfredJson)]
         output: OutputModeArg,
     },
     /// List type keys for selector flow in rrv mode.
+    #[command(visible_alias = "types")]
     ListTypes {
-        /// Optional case-insensitive filter against format keys.
+        /// Optional case-insensitive filter against format keys, with an optional trailing count.
         #[arg(long)]
         query: Option<String>,
-        /// Output mode: workflow-compatible Alfred JSON or service envelope JSON.
+        /// Output mode: workflow-compatible Alfred JSON, service envelope JSON, or plain `title<TAB>subtitle` lines.
         #[arg(long, value_enum, default_value_t = OutputModeArg::AlfredJson)]
         output: OutputModeArg,
     },
     /// Generate values for a specific format.
     Generate {
-        /// Target format key (case-insensitive).
+        /// Target format key (case-insensitive); a trailing count (`uuid 25`) overrides `--count`.
         #[arg(long)]
         format: String,
-        /// Number of values to generate.
+        /// Number of values to generate, clamped to RANDOMER_MAX_COUNT.
         #[arg(long, default_value_t = 1)]
         count: usize,
-        /// Output mode: workflow-compatible Alfred JSON or service envelope JSON.
+        /// Output mode: workflow-compatible Alfred JSON, a service envelope with the raw values array, or plain values
+        /// one per line.
         #[arg(long, value_enum, default_value_t = OutputModeArg::AlfredJson)]
         output: OutputModeArg,
     },
@@ -54,13 +64,17 @@ enum Commands {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 enum OutputModeArg {
+    #[value(alias = "plain")]
+    Human,
     Json,
+    #[value(alias = "alfred")]
     AlfredJson,
 }
 
 impl From<OutputModeArg> for OutputMode {
     fn from(value: OutputModeArg) -> Self {
         match value {
+            OutputModeArg::Human => OutputMode::Human,
             OutputModeArg::Json => OutputMode::Json,
             OutputModeArg::AlfredJson => OutputMode::AlfredJson,
         }
@@ -114,9 +128,11 @@ impl AppError {
 
     fn from_randomer(error: RandomerError) -> Self {
         match error {
-            RandomerError::UnknownFormat(_) | RandomerError::InvalidCount(_) => {
-                Self::user(error.to_string())
-            }
+            RandomerError::UnknownFormat(_)
+            | RandomerError::InvalidCount(_)
+            | RandomerError::InvalidArgument { .. }
+            | RandomerError::InvalidRange { .. }
+            | RandomerError::InvalidConfig { .. } => Self::user(error.to_string()),
         }
     }
 
@@ -153,7 +169,7 @@ fn main() {
                     eprintln!("error: {}", error.message);
                 }
                 OutputMode::Human => {
-                    unreachable!("only json and alfred-json output modes are supported")
+                    eprintln!("error[{}]: {}", error.code(), error.message);
                 }
             }
             std::process::exit(error.exit_code());
@@ -162,13 +178,21 @@ fn main() {
 }
 
 fn run(cli: Cli) -> Result<String, AppError> {
+    let config = RuntimeConfig::from_env().map_err(AppError::from_randomer)?;
+    let seed = cli.seed.or(config.seed);
     match cli.command {
         Commands::ListFormats { query, output } => {
-            let payload = list_formats_feedback(query.as_deref());
+            let payload = match seed {
+                Some(seed) => list_formats_feedback_seeded(query.as_deref(), &config, seed),
+                None => list_formats_feedback(query.as_deref(), &config),
+            };
             render_feedback(output.into(), "list-formats", payload)
         }
         Commands::ListTypes { query, output } => {
-            let payload = list_types_feedback(query.as_deref());
+            let payload = match seed {
+                Some(seed) => list_types_feedback_seeded(query.as_deref(), &config, seed),
+                None => list_types_feedback(query.as_deref(), &config),
+            };
             render_feedback(output.into(), "list-types", payload)
         }
         Commands::Generate {
@@ -176,13 +200,38 @@ fn run(cli: Cli) -> Result<String, AppError> {
             count,
             output,
         } => {
-            let payload =
-                generate_feedback(format.as_str(), count).map_err(AppError::from_randomer)?;
+            if output != OutputModeArg::AlfredJson {
+                let values = match seed {
+                    Some(seed) => generate_values_seeded(format.as_str(), count, &config, seed),
+                    None => generate_values(format.as_str(), count, &config),
+                }
+                .map_err(AppError::from_randomer)?;
+                return Ok(render_values(output.into(), "generate", &values));
+            }
+
+            let payload = match seed {
+                Some(seed) => generate_feedback_seeded(format.as_str(), count, &config, seed),
+                None => generate_feedback(format.as_str(), count, &config),
+            }
+            .map_err(AppError::from_randomer)?;
             render_feedback(output.into(), "generate", payload)
         }
     }
 }
 
+fn render_values(mode: OutputMode, command: &'static str, values: &GeneratedValues) -> String {
+    if mode == OutputMode::Human {
+        return values.values.join("\n");
+    }
+
+    let result = serde_json::json!({
+        "format": values.format,
+        "count": values.values.len(),
+        "values": values.values,
+    });
+    build_success_envelope(command, EnvelopePayloadKind::Result, &result.to_string())
+}
+
 fn render_feedback(
     mode: OutputMode,
     command: &'static str,
@@ -202,7 +251,15 @@ fn render_feedback(
                 &result,
             ))
         }
-        OutputMode::Human => unreachable!("only json and alfred-json output modes are supported"),
+        OutputMode::Human => Ok(payload
+            .items
+            .iter()
+            .map(|item| match &item.subtitle {
+                Some(subtitle) => format!("{}\t{subtitle}", item.title),
+                None => item.title.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")),
     }
 }
 
@@ -227,7 +284,7 @@ mod tests {
             .and_then(Value::as_array)
             .expect("items should be present");
 
-        assert_eq!(items.len(), 11);
+        assert_eq!(items.len(), 33);
         assert!(
             items
                 .first()
@@ -296,14 +353,156 @@ mod tests {
             .and_then(Value::as_array)
             .expect("items should be present");
 
-        assert_eq!(items.len(), 4);
-        assert!(items.iter().all(|item| {
+        assert_eq!(items.len(), 5);
+        assert_eq!(
+            items[0].get("title").and_then(Value::as_str),
+            Some("Copy all 4 values")
+        );
+        let values: Vec<&str> = items[1..]
+            .iter()
+            .filter_map(|item| item.get("arg").and_then(Value::as_str))
+            .collect();
+        assert_eq!(
+            items[0].get("arg").and_then(Value::as_str),
+            Some(values.join("\n").as_str())
+        );
+        assert!(items[1..].iter().all(|item| {
             item.get("subtitle").and_then(Value::as_str) == Some("otp")
                 && item.get("arg").and_then(Value::as_str)
                     == item.get("title").and_then(Value::as_str)
         }));
     }
 
+    #[test]
+    fn generate_service_json_mode_emits_values_array() {
+        let cli = Cli::parse_from([
+            "randomer-cli",
+            "generate",
+            "--format",
+            "uuid 3",
+            "--output",
+            "json",
+        ]);
+        let output = run(cli).expect("generate should succeed");
+        let json: Value = serde_json::from_str(&output).expect("output should be JSON");
+
+        assert_eq!(
+            json.get("schema_version").and_then(Value::as_str),
+            Some("cli-envelope@v1")
+        );
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("generate")
+        );
+        assert_eq!(json.get("ok").and_then(Value::as_bool), Some(true));
+        let result = json.get("result").expect("result should be present");
+        assert_eq!(result.get("format").and_then(Value::as_str), Some("uuid"));
+        assert_eq!(result.get("count").and_then(Value::as_u64), Some(3));
+        let values = result
+            .get("values")
+            .and_then(Value::as_array)
+            .expect("values should be present");
+        assert_eq!(values.len(), 3);
+        assert!(
+            values
+                .iter()
+                .all(|value| value.as_str().is_some_and(|value| value.len() == 36))
+        );
+    }
+
+    #[test]
+    fn generate_accepts_trailing_count_in_format_query() {
+        let cli = Cli::parse_from(["randomer-cli", "generate", "--format", "uuid 3"]);
+
+        let output = run(cli).expect("generate should succeed");
+        let json: Value = serde_json::from_str(&output).expect("output should be JSON");
+        let items = json
+            .get("items")
+            .and_then(Value::as_array)
+            .expect("items should be present");
+
+        assert_eq!(items.len(), 4, "copy-all row plus 3 values");
+    }
+
+    #[test]
+    fn seed_flag_makes_generate_output_reproducible() {
+        let generate = || {
+            let cli = Cli::parse_from([
+                "randomer-cli",
+                "generate",
+                "--format",
+                "uuid 3",
+                "--seed",
+                "2024",
+            ]);
+            run(cli).expect("generate should succeed")
+        };
+
+        assert_eq!(generate(), generate());
+    }
+
+    #[test]
+    fn short_command_aliases_and_output_aliases_are_accepted() {
+        let list = Cli::parse_from(["randomer-cli", "list", "--output", "alfred"]);
+        assert_eq!(list.command_name(), "list-formats");
+        assert_eq!(list.output_mode(), OutputMode::AlfredJson);
+
+        let types = Cli::parse_from(["randomer-cli", "types", "--output", "plain"]);
+        assert_eq!(types.command_name(), "list-types");
+        assert_eq!(types.output_mode(), OutputMode::Human);
+    }
+
+    #[test]
+    fn generate_plain_mode_prints_one_value_per_line() {
+        let generate = |output: &str| {
+            let cli = Cli::parse_from([
+                "randomer-cli",
+                "generate",
+                "--format",
+                "uuid",
+                "--count",
+                "3",
+                "--seed",
+                "7",
+                "--output",
+                output,
+            ]);
+            run(cli).expect("generate should succeed")
+        };
+
+        let plain = generate("plain");
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() == 36));
+
+        let json: Value = serde_json::from_str(&generate("json")).expect("output should be JSON");
+        let values: Vec<&str> = json["result"]["values"]
+            .as_array()
+            .expect("values should be present")
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert_eq!(values, lines);
+    }
+
+    #[test]
+    fn list_types_plain_mode_prints_title_and_subtitle_columns() {
+        let cli = Cli::parse_from([
+            "randomer-cli",
+            "types",
+            "--query",
+            "in",
+            "--output",
+            "plain",
+        ]);
+        let output = run(cli).expect("list-types should succeed");
+        let (title, subtitle) = output.split_once('\t').expect("tab-separated line");
+
+        assert_eq!(output.lines().count(), 1);
+        assert_eq!(title, "int");
+        assert!(subtitle.starts_with("sample: "));
+    }
+
     #[test]
     fn list_types_outputs_selector_items_with_format_args() {
         let cli = Cli::parse_from(["randomer-cli", "list-types", "--query", "in"]);
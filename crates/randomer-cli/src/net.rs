@@ -0,0 +1,322 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use rand::{Rng, RngExt};
+
+const CIDR_ARG: &str = "cidr";
+
+// RFC 1918 blocks as `(network, prefix length)`.
+const IPV4_PRIVATE: &[(u32, u32)] = &[(0x0A00_0000, 8), (0xAC10_0000, 12), (0xC0A8_0000, 16)];
+
+// Special-purpose and non-unicast IPv4 blocks that a public address must avoid (IANA registry, RFC 6890).
+const IPV4_RESERVED: &[(u32, u32)] = &[
+    (0x0000_0000, 8),
+    (0x0A00_0000, 8),
+    (0x6440_0000, 10),
+    (0x7F00_0000, 8),
+    (0xA9FE_0000, 16),
+    (0xAC10_0000, 12),
+    (0xC000_0000, 24),
+    (0xC000_0200, 24),
+    (0xC058_6300, 24),
+    (0xC0A8_0000, 16),
+    (0xC612_0000, 15),
+    (0xC633_6400, 24),
+    (0xCB00_7100, 24),
+    (0xE000_0000, 3),
+];
+
+// Special-purpose blocks inside global unicast `2000::/3`: IETF protocol assignments and documentation.
+const IPV6_RESERVED: &[(u128, u32)] = &[
+    (0x2001_0000 << 96, 23),
+    (0x2001_0db8 << 96, 32),
+    (0x2002 << 112, 16),
+    (0x3fff << 112, 20),
+];
+
+/// Address scope for the `ipv4` format, selected with a query argument such as `ipv4 public`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ipv4Scope {
+    /// RFC 1918: `10/8`, `172.16/12`, `192.168/16`.
+    #[default]
+    Private,
+    /// Globally routable unicast, outside every special-purpose block.
+    Public,
+}
+
+impl Ipv4Scope {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "private" => Some(Self::Private),
+            "public" | "global" => Some(Self::Public),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Private => "private",
+            Self::Public => "public",
+        }
+    }
+}
+
+/// Address scope for the `ipv6` format, selected with a query argument such as `ipv6 public`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ipv6Scope {
+    /// Unique local `fd00::/8` with a random 40-bit global ID (RFC 4193).
+    #[default]
+    Ula,
+    /// Global unicast `2000::/3`, outside the special-purpose blocks.
+    Public,
+    /// Link-local `fe80::/64`.
+    LinkLocal,
+}
+
+impl Ipv6Scope {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "ula" | "private" => Some(Self::Ula),
+            "public" | "global" => Some(Self::Public),
+            "link" | "linklocal" => Some(Self::LinkLocal),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Ula => "ula",
+            Self::Public => "public",
+            Self::LinkLocal => "link",
+        }
+    }
+}
+
+/// Parse `ipv4` / `ipv6` query arguments: at most one scope (via `parse_scope`) and an optional `cidr`, in any
+/// order. Returns `(scope, cidr)`.
+pub(crate) fn parse_ip_args<S>(
+    args: &[String],
+    parse_scope: impl Fn(&str) -> Option<S>,
+) -> Option<(Option<S>, bool)> {
+    let mut scope = None;
+    let mut cidr = false;
+    for arg in args {
+        if arg == CIDR_ARG && !cidr {
+            cidr = true;
+        } else if scope.is_none() {
+            scope = Some(parse_scope(arg)?);
+        } else {
+            return None;
+        }
+    }
+    Some((scope, cidr))
+}
+
+/// Query arguments that reproduce `scope` and `cidr`, leaving out the defaults.
+pub(crate) fn ip_args_token(
+    scope: &'static str,
+    default_scope: &'static str,
+    cidr: bool,
+) -> String {
+    let mut parts = Vec::new();
+    if scope != default_scope {
+        parts.push(scope);
+    }
+    if cidr {
+        parts.push(CIDR_ARG);
+    }
+    parts.join(" ")
+}
+
+pub(crate) fn random_ipv4<R: Rng + ?Sized>(rng: &mut R, scope: Ipv4Scope, cidr: bool) -> String {
+    let (address, min_prefix) = match scope {
+        Ipv4Scope::Private => {
+            let (network, prefix) = IPV4_PRIVATE[rng.random_range(0..IPV4_PRIVATE.len())];
+            (network | (rng.random::<u32>() & !mask32(prefix)), prefix)
+        }
+        Ipv4Scope::Public => loop {
+            let candidate = rng.random::<u32>();
+            if !IPV4_RESERVED
+                .iter()
+                .any(|&(network, prefix)| in_block32(candidate, network, prefix))
+            {
+                // A /24 or longer around an address outside every block stays outside too.
+                break (candidate, 24);
+            }
+        },
+    };
+
+    if cidr {
+        let prefix = rng.random_range(min_prefix.max(16)..=30);
+        format!("{}/{prefix}", Ipv4Addr::from(address & mask32(prefix)))
+    } else {
+        Ipv4Addr::from(address).to_string()
+    }
+}
+
+pub(crate) fn random_ipv6<R: Rng + ?Sized>(rng: &mut R, scope: Ipv6Scope, cidr: bool) -> String {
+    let (address, min_prefix) = match scope {
+        Ipv6Scope::Ula => (
+            (0xfd_u128 << 120) | (rng.random::<u128>() & !mask128(8)),
+            48,
+        ),
+        Ipv6Scope::Public => loop {
+            let candidate = (0x2_u128 << 124) | (rng.random::<u128>() & !mask128(3));
+            if !IPV6_RESERVED
+                .iter()
+                .any(|&(network, prefix)| in_block128(candidate, network, prefix))
+            {
+                break (candidate, 32);
+            }
+        },
+        Ipv6Scope::LinkLocal => (
+            (0xfe80_u128 << 112) | (rng.random::<u128>() & !mask128(64)),
+            64,
+        ),
+    };
+
+    if cidr {
+        let prefix = rng.random_range(min_prefix..=64);
+        format!("{}/{prefix}", Ipv6Addr::from(address & mask128(prefix)))
+    } else {
+        Ipv6Addr::from(address).to_string()
+    }
+}
+
+fn mask32(prefix: u32) -> u32 {
+    u32::MAX.checked_shl(32 - prefix).unwrap_or(0)
+}
+
+fn mask128(prefix: u32) -> u128 {
+    u128::MAX.checked_shl(128 - prefix).unwrap_or(0)
+}
+
+fn in_block32(address: u32, network: u32, prefix: u32) -> bool {
+    address & mask32(prefix) == network
+}
+
+fn in_block128(address: u128, network: u128, prefix: u32) -> bool {
+    address & mask128(prefix) == network
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn parse_cidr<T: std::str::FromStr>(value: &str) -> (T, u32)
+    where
+        T::Err: std::fmt::Debug,
+    {
+        let (address, prefix) = value.split_once('/').expect("CIDR should contain /");
+        (
+            address.parse().expect("address"),
+            prefix.parse().expect("prefix"),
+        )
+    }
+
+    #[test]
+    fn private_ipv4_stays_inside_rfc1918() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..500 {
+            let address: Ipv4Addr = random_ipv4(&mut rng, Ipv4Scope::Private, false)
+                .parse()
+                .expect("ipv4");
+            assert!(address.is_private(), "{address}");
+        }
+    }
+
+    #[test]
+    fn public_ipv4_avoids_reserved_blocks() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..500 {
+            let address: Ipv4Addr = random_ipv4(&mut rng, Ipv4Scope::Public, false)
+                .parse()
+                .expect("ipv4");
+            assert!(
+                !(address.is_private()
+                    || address.is_loopback()
+                    || address.is_link_local()
+                    || address.is_multicast()
+                    || address.is_documentation()
+                    || address.is_unspecified()
+                    || address.is_broadcast()),
+                "{address}"
+            );
+            let octets = address.octets();
+            assert!(!(octets[0] == 100 && (64..128).contains(&octets[1])));
+            assert!(octets[0] < 240);
+        }
+    }
+
+    #[test]
+    fn ipv4_cidr_is_a_network_address_within_scope() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let (network, prefix): (Ipv4Addr, u32) =
+                parse_cidr(&random_ipv4(&mut rng, Ipv4Scope::Private, true));
+            assert!((16..=30).contains(&prefix));
+            assert_eq!(u32::from(network) & !mask32(prefix), 0);
+            assert!(network.is_private());
+        }
+    }
+
+    #[test]
+    fn ipv6_scopes_land_in_their_ranges() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..500 {
+            let ula: Ipv6Addr = random_ipv6(&mut rng, Ipv6Scope::Ula, false)
+                .parse()
+                .expect("ipv6");
+            assert!(ula.is_unique_local() && ula.segments()[0] >> 8 == 0xfd);
+
+            let link: Ipv6Addr = random_ipv6(&mut rng, Ipv6Scope::LinkLocal, false)
+                .parse()
+                .expect("ipv6");
+            assert!(link.is_unicast_link_local());
+            assert_eq!(&link.segments()[..4], &[0xfe80, 0, 0, 0]);
+
+            let public: Ipv6Addr = random_ipv6(&mut rng, Ipv6Scope::Public, false)
+                .parse()
+                .expect("ipv6");
+            let segments = public.segments();
+            assert_eq!(segments[0] >> 13, 0b001);
+            assert!(!(segments[0] == 0x2001 && segments[1] == 0x0db8));
+            assert_ne!(segments[0], 0x2002);
+        }
+    }
+
+    #[test]
+    fn ipv6_cidr_is_a_network_address_within_scope() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let (network, prefix): (Ipv6Addr, u32) =
+                parse_cidr(&random_ipv6(&mut rng, Ipv6Scope::Ula, true));
+            assert!((48..=64).contains(&prefix));
+            assert_eq!(u128::from(network) & !mask128(prefix), 0);
+            assert!(network.is_unique_local());
+        }
+    }
+
+    #[test]
+    fn ip_args_take_one_scope_and_an_optional_cidr() {
+        let args = |raw: &str| -> Vec<String> { raw.split(' ').map(String::from).collect() };
+        assert_eq!(
+            parse_ip_args(&args("public cidr"), Ipv4Scope::parse),
+            Some((Some(Ipv4Scope::Public), true))
+        );
+        assert_eq!(
+            parse_ip_args(&args("cidr"), Ipv6Scope::parse),
+            Some((None, true))
+        );
+        assert_eq!(parse_ip_args(&args("ula"), Ipv4Scope::parse), None);
+        assert_eq!(
+            parse_ip_args(&args("public private"), Ipv4Scope::parse),
+            None
+        );
+        assert_eq!(parse_ip_args(&args("cidr cidr"), Ipv4Scope::parse), None);
+        assert_eq!(ip_args_token("public", "private", true), "public cidr");
+        assert_eq!(ip_args_token("private", "private", false), "");
+    }
+}
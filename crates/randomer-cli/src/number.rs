@@ -0,0 +1,496 @@
+use rand::{Rng, RngExt};
+
+pub const DEFAULT_DECIMAL_PLACES: u32 = 2;
+pub const DECIMAL_PLACES_MAX: u32 = 8;
+
+const BOUND_DIGITS_MAX: usize = 15;
+const DEFAULT_INT_MAX: i128 = 9_999_999_999;
+/// Exclusive whole-number ceilings for the default `decimal` and `currency` ranges.
+const DEFAULT_DECIMAL_WHOLE_LIMIT: i128 = 1_000_000;
+const DEFAULT_CURRENCY_WHOLE_LIMIT: i128 = 100_000_000;
+
+const MALFORMED: &str = "expected <min>..<max>, for example 1..1000";
+const TOO_LARGE: &str = "bounds take at most 15 whole digits";
+const TOO_PRECISE: &str = "bounds take at most 8 decimal places";
+const REVERSED: &str = "start is greater than end";
+const NOT_WHOLE: &str = "int bounds must be whole numbers";
+const NEGATIVE: &str = "currency amounts must not be negative";
+const EMPTY: &str = "no value at this precision falls inside the range";
+
+/// Exact decimal from query text: `units / 10^scale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fixed {
+    units: i128,
+    scale: u32,
+}
+
+impl Fixed {
+    fn parse(raw: &str) -> Result<Self, &'static str> {
+        let (negative, digits) = match raw.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, raw),
+        };
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((_, "")) => return Err(MALFORMED),
+            Some(parts) => parts,
+            None => (digits, ""),
+        };
+        if whole.is_empty()
+            || !whole
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(MALFORMED);
+        }
+        if whole.len() > BOUND_DIGITS_MAX {
+            return Err(TOO_LARGE);
+        }
+        if fraction.len() > DECIMAL_PLACES_MAX as usize {
+            return Err(TOO_PRECISE);
+        }
+
+        let units: i128 = format!("{whole}{fraction}")
+            .parse()
+            .map_err(|_| MALFORMED)?;
+        Ok(Self {
+            units: if negative { -units } else { units },
+            scale: fraction.len() as u32,
+        })
+    }
+
+    /// Value in units of `10^-scale`, rounded up for lower bounds and down for upper bounds.
+    fn at_scale(self, scale: u32, round_up: bool) -> i128 {
+        if scale >= self.scale {
+            return self.units * 10i128.pow(scale - self.scale);
+        }
+        let divisor = 10i128.pow(self.scale - scale);
+        let floor = self.units.div_euclid(divisor);
+        if round_up && self.units.rem_euclid(divisor) != 0 {
+            floor + 1
+        } else {
+            floor
+        }
+    }
+}
+
+/// Inclusive numeric range from a query argument such as `1..1000` or `-0.5..0.5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberRange {
+    start: Fixed,
+    end: Fixed,
+}
+
+impl NumberRange {
+    /// Whether `token` is written as a range, valid or not; such tokens get a range error instead of being
+    /// treated as unknown arguments.
+    pub fn is_range_token(token: &str) -> bool {
+        token.contains("..")
+    }
+
+    /// Parse `<min>..<max>`; the error is a short reason suitable for an error row.
+    pub fn parse(token: &str) -> Result<Self, &'static str> {
+        let (start, end) = token.trim().split_once("..").ok_or(MALFORMED)?;
+        let range = Self {
+            start: Fixed::parse(start)?,
+            end: Fixed::parse(end)?,
+        };
+        if range.start.at_scale(DECIMAL_PLACES_MAX, false)
+            > range.end.at_scale(DECIMAL_PLACES_MAX, false)
+        {
+            return Err(REVERSED);
+        }
+        Ok(range)
+    }
+
+    /// Query token for this range, keeping the bounds as written (`0.50..2`).
+    pub fn token(self) -> String {
+        format!(
+            "{}..{}",
+            format_units(self.start.units, self.start.scale),
+            format_units(self.end.units, self.end.scale)
+        )
+    }
+
+    /// Bounds in units of `10^-places`, or `None` when no value with that many places fits.
+    fn scaled(self, places: u32) -> Option<(i128, i128)> {
+        let low = self.start.at_scale(places, true);
+        let high = self.end.at_scale(places, false);
+        (low <= high).then_some((low, high))
+    }
+}
+
+/// Currency for the `currency` format, selected with a query argument such as `currency eur`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CurrencyCode {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Jpy,
+    Twd,
+}
+
+impl CurrencyCode {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "usd" => Some(Self::Usd),
+            "eur" => Some(Self::Eur),
+            "gbp" => Some(Self::Gbp),
+            "jpy" => Some(Self::Jpy),
+            "twd" => Some(Self::Twd),
+            _ => None,
+        }
+    }
+
+    /// ISO 4217 code, e.g. `EUR`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Usd => "USD",
+            Self::Eur => "EUR",
+            Self::Gbp => "GBP",
+            Self::Jpy => "JPY",
+            Self::Twd => "TWD",
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Usd => "$",
+            Self::Eur => "€",
+            Self::Gbp => "£",
+            Self::Jpy => "¥",
+            Self::Twd => "NT$",
+        }
+    }
+
+    /// Minor-unit digits; yen has none.
+    fn places(self) -> u32 {
+        match self {
+            Self::Jpy => 0,
+            _ => 2,
+        }
+    }
+}
+
+/// Why `int` / `decimal` / `currency` arguments were rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NumberArgError {
+    /// A token that is neither a range nor a known option for the format.
+    Unrecognized,
+    /// A range-shaped token that cannot be used, with the reason.
+    Range { token: String, reason: &'static str },
+}
+
+fn parse_range_arg(token: &str) -> Result<NumberRange, NumberArgError> {
+    NumberRange::parse(token).map_err(|reason| range_error(token, reason))
+}
+
+fn range_error(token: &str, reason: &'static str) -> NumberArgError {
+    NumberArgError::Range {
+        token: token.to_string(),
+        reason,
+    }
+}
+
+/// Optional whole-number range for `int` (`int -50..50`).
+pub(crate) fn parse_int_args(args: &[String]) -> Result<Option<NumberRange>, NumberArgError> {
+    match args {
+        [] => Ok(None),
+        [token] if NumberRange::is_range_token(token) => {
+            let range = parse_range_arg(token)?;
+            if range.start.scale > 0 || range.end.scale > 0 {
+                return Err(range_error(token, NOT_WHOLE));
+            }
+            Ok(Some(range))
+        }
+        _ => Err(NumberArgError::Unrecognized),
+    }
+}
+
+/// Range and precision for the `decimal` format, from arguments such as `0..1 4dp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalSpec {
+    pub range: Option<NumberRange>,
+    pub places: u32,
+}
+
+impl Default for DecimalSpec {
+    fn default() -> Self {
+        Self {
+            range: None,
+            places: DEFAULT_DECIMAL_PLACES,
+        }
+    }
+}
+
+impl DecimalSpec {
+    /// Read a range and an `<n>dp` precision (`0..=8`), each at most once and in either order.
+    pub(crate) fn from_args(args: &[String]) -> Result<Self, NumberArgError> {
+        let mut spec = Self::default();
+        let mut places = None;
+        for arg in args {
+            if NumberRange::is_range_token(arg) && spec.range.is_none() {
+                spec.range = Some(parse_range_arg(arg)?);
+            } else if let Some(parsed) = parse_places(arg)
+                && places.is_none()
+            {
+                places = Some(parsed);
+            } else {
+                return Err(NumberArgError::Unrecognized);
+            }
+        }
+        spec.places = places.unwrap_or(DEFAULT_DECIMAL_PLACES);
+
+        if let Some(range) = spec.range
+            && range.scaled(spec.places).is_none()
+        {
+            return Err(range_error(&range.token(), EMPTY));
+        }
+        Ok(spec)
+    }
+
+    /// Query arguments for this spec, e.g. `0..1 4dp`; empty for the default.
+    pub fn token(self) -> String {
+        let mut parts = Vec::new();
+        if let Some(range) = self.range {
+            parts.push(range.token());
+        }
+        if self.places != DEFAULT_DECIMAL_PLACES {
+            parts.push(format!("{}dp", self.places));
+        }
+        parts.join(" ")
+    }
+
+    /// Subtitle detail, e.g. `0..1 · 4 dp`.
+    pub(crate) fn describe(self) -> String {
+        match self.range {
+            Some(range) => format!("{} · {} dp", range.token(), self.places),
+            None => format!("{} dp", self.places),
+        }
+    }
+}
+
+fn parse_places(token: &str) -> Option<u32> {
+    token
+        .strip_suffix("dp")
+        .and_then(|places| places.parse().ok())
+        .filter(|places| *places <= DECIMAL_PLACES_MAX)
+}
+
+/// Range and currency for the `currency` format, from arguments such as `10..500 eur`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CurrencySpec {
+    pub range: Option<NumberRange>,
+    pub code: CurrencyCode,
+}
+
+impl CurrencySpec {
+    /// Read a non-negative range and a currency code, each at most once and in either order.
+    pub(crate) fn from_args(args: &[String]) -> Result<Self, NumberArgError> {
+        let mut spec = Self::default();
+        let mut code = None;
+        for arg in args {
+            if NumberRange::is_range_token(arg) && spec.range.is_none() {
+                spec.range = Some(parse_range_arg(arg)?);
+            } else if let Some(parsed) = CurrencyCode::parse(arg)
+                && code.is_none()
+            {
+                code = Some(parsed);
+            } else {
+                return Err(NumberArgError::Unrecognized);
+            }
+        }
+        spec.code = code.unwrap_or_default();
+
+        if let Some(range) = spec.range {
+            if range.start.units < 0 {
+                return Err(range_error(&range.token(), NEGATIVE));
+            }
+            if range.scaled(spec.code.places()).is_none() {
+                return Err(range_error(&range.token(), EMPTY));
+            }
+        }
+        Ok(spec)
+    }
+
+    /// Query arguments for this spec, e.g. `10..500 eur`; empty for the default.
+    pub fn token(self) -> String {
+        let mut parts = Vec::new();
+        if let Some(range) = self.range {
+            parts.push(range.token());
+        }
+        if self.code != CurrencyCode::default() {
+            parts.push(self.code.code().to_ascii_lowercase());
+        }
+        parts.join(" ")
+    }
+
+    /// Subtitle detail, e.g. `EUR · 10..500`.
+    pub(crate) fn describe(self) -> String {
+        match self.range {
+            Some(range) => format!("{} · {}", self.code.code(), range.token()),
+            None => self.code.code().to_string(),
+        }
+    }
+}
+
+/// Integer in the range, or `0..=9_999_999_999` without one.
+pub(crate) fn random_int<R: Rng + ?Sized>(rng: &mut R, range: Option<NumberRange>) -> String {
+    let (low, high) = range
+        .and_then(|range| range.scaled(0))
+        .unwrap_or((0, DEFAULT_INT_MAX));
+    rng.random_range(low..=high).to_string()
+}
+
+/// Fixed-precision decimal in the range, or below one million without one.
+pub(crate) fn random_decimal<R: Rng + ?Sized>(rng: &mut R, spec: DecimalSpec) -> String {
+    let (low, high) = bounds(spec.range, spec.places, DEFAULT_DECIMAL_WHOLE_LIMIT);
+    format_units(rng.random_range(low..=high), spec.places)
+}
+
+/// Symbol, thousands separators, and minor units, e.g. `€1,234.56`; below one hundred million without a range.
+pub(crate) fn random_currency<R: Rng + ?Sized>(rng: &mut R, spec: CurrencySpec) -> String {
+    let places = spec.code.places();
+    let (low, high) = bounds(spec.range, places, DEFAULT_CURRENCY_WHOLE_LIMIT);
+    let units = rng.random_range(low..=high).unsigned_abs();
+    let divisor = 10u128.pow(places);
+    let whole = with_thousands_separators(units / divisor);
+    if places == 0 {
+        format!("{}{whole}", spec.code.symbol())
+    } else {
+        format!(
+            "{}{whole}.{:0width$}",
+            spec.code.symbol(),
+            units % divisor,
+            width = places as usize
+        )
+    }
+}
+
+fn bounds(range: Option<NumberRange>, places: u32, default_whole_limit: i128) -> (i128, i128) {
+    range
+        .and_then(|range| range.scaled(places))
+        .unwrap_or((0, default_whole_limit * 10i128.pow(places) - 1))
+}
+
+fn format_units(units: i128, places: u32) -> String {
+    let sign = if units < 0 { "-" } else { "" };
+    let magnitude = units.unsigned_abs();
+    if places == 0 {
+        return format!("{sign}{magnitude}");
+    }
+    let divisor = 10u128.pow(places);
+    format!(
+        "{sign}{}.{:0width$}",
+        magnitude / divisor,
+        magnitude % divisor,
+        width = places as usize
+    )
+}
+
+fn with_thousands_separators(value: u128) -> String {
+    let digits = value.to_string();
+    let mut grouped_rev = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, ch) in digits.chars().rev().enumerate() {
+        if index > 0 && index % 3 == 0 {
+            grouped_rev.push(',');
+        }
+        grouped_rev.push(ch);
+    }
+    grouped_rev.chars().rev().collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(String::from).collect()
+    }
+
+    fn range_reason(result: Result<impl std::fmt::Debug, NumberArgError>) -> &'static str {
+        match result {
+            Err(NumberArgError::Range { reason, .. }) => reason,
+            other => panic!("expected a range error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ranges_parse_bounds_and_explain_rejections() {
+        let range = NumberRange::parse("-0.50..2").expect("range");
+        assert_eq!(range.token(), "-0.50..2");
+        assert_eq!(range.scaled(1), Some((-5, 20)));
+        assert_eq!(
+            NumberRange::parse("0.125..0.125").expect("range").scaled(2),
+            None
+        );
+
+        assert_eq!(NumberRange::parse("5..1"), Err(REVERSED));
+        assert_eq!(NumberRange::parse("1..x"), Err(MALFORMED));
+        assert_eq!(NumberRange::parse("1...5"), Err(MALFORMED));
+        assert_eq!(NumberRange::parse("1.."), Err(MALFORMED));
+        assert_eq!(NumberRange::parse("0..1234567890123456"), Err(TOO_LARGE));
+        assert_eq!(NumberRange::parse("0..0.123456789"), Err(TOO_PRECISE));
+    }
+
+    #[test]
+    fn format_arguments_accept_either_order_and_report_range_errors() {
+        assert_eq!(parse_int_args(&args("")), Ok(None));
+        assert_eq!(range_reason(parse_int_args(&args("1.5..3"))), NOT_WHOLE);
+        assert_eq!(
+            parse_int_args(&args("eur")),
+            Err(NumberArgError::Unrecognized)
+        );
+
+        let decimal = DecimalSpec::from_args(&args("4dp 0..1")).expect("decimal");
+        assert_eq!(
+            (decimal.places, decimal.token()),
+            (4, String::from("0..1 4dp"))
+        );
+        assert_eq!(
+            range_reason(DecimalSpec::from_args(&args("0.001..0.009 2dp"))),
+            EMPTY
+        );
+        assert_eq!(
+            DecimalSpec::from_args(&args("9dp")),
+            Err(NumberArgError::Unrecognized)
+        );
+
+        let currency = CurrencySpec::from_args(&args("eur 10..500")).expect("currency");
+        assert_eq!(currency.token(), "10..500 eur");
+        assert_eq!(currency.describe(), "EUR · 10..500");
+        assert_eq!(
+            range_reason(CurrencySpec::from_args(&args("-5..5"))),
+            NEGATIVE
+        );
+        assert_eq!(
+            CurrencySpec::from_args(&args("eur usd")),
+            Err(NumberArgError::Unrecognized)
+        );
+    }
+
+    #[test]
+    fn values_stay_inside_range_at_the_requested_precision() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let int_range = NumberRange::parse("-3..3").expect("range");
+        let decimal = DecimalSpec::from_args(&args("0..1 4dp")).expect("decimal");
+        let currency = CurrencySpec::from_args(&args("1000..1000.5 jpy")).expect("currency");
+        for _ in 0..200 {
+            let int: i64 = random_int(&mut rng, Some(int_range)).parse().expect("int");
+            assert!((-3..=3).contains(&int));
+
+            let value = random_decimal(&mut rng, decimal);
+            let (whole, fraction) = value.split_once('.').expect("decimal point");
+            assert_eq!(fraction.len(), 4);
+            assert!(whole == "0" || value == "1.0000", "{value}");
+
+            assert_eq!(random_currency(&mut rng, currency), "¥1,000");
+        }
+        assert_eq!(with_thousands_separators(1_234_567), "1,234,567");
+        assert_eq!(format_units(-5, 2), "-0.05");
+    }
+}
@@ -0,0 +1,209 @@
+use rand::{Rng, RngExt};
+
+pub const DEFAULT_PASSWORD_LENGTH: usize = 20;
+pub const PASSWORD_LENGTH_MIN: usize = 8;
+pub const PASSWORD_LENGTH_MAX: usize = 128;
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+// Quotes, backslash, and backtick are left out so values paste cleanly into shells and config files.
+const SYMBOLS: &str = "!#$%&*+-=?@^_~";
+const AMBIGUOUS: &str = "Il1O0o";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digits,
+    Symbols,
+}
+
+impl CharClass {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            "lower" => Some(Self::Lower),
+            "upper" => Some(Self::Upper),
+            "digits" => Some(Self::Digits),
+            "symbols" => Some(Self::Symbols),
+            _ => None,
+        }
+    }
+
+    fn charset(self, avoid_ambiguous: bool) -> Vec<char> {
+        let all = match self {
+            Self::Lower => LOWER,
+            Self::Upper => UPPER,
+            Self::Digits => DIGITS,
+            Self::Symbols => SYMBOLS,
+        };
+        all.chars()
+            .filter(|ch| !avoid_ambiguous || !AMBIGUOUS.contains(*ch))
+            .collect()
+    }
+}
+
+/// Length and character rules for the `password` format.
+///
+/// Every enabled class appears at least once in a generated password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub length: usize,
+    pub classes: Vec<CharClass>,
+    pub avoid_ambiguous: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            length: DEFAULT_PASSWORD_LENGTH,
+            classes: vec![
+                CharClass::Lower,
+                CharClass::Upper,
+                CharClass::Digits,
+                CharClass::Symbols,
+            ],
+            avoid_ambiguous: false,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Parse a `RANDOMER_PASSWORD_CLASSES` list such as `lower,upper,digits,unambiguous`.
+    ///
+    /// Tokens are separated by commas or whitespace. `unambiguous` drops look-alike characters (`Il1O0o`) and is not a
+    /// class itself, so at least one real class is required. Returns `None` for unknown tokens or no class.
+    pub fn parse_classes(raw: &str) -> Option<(Vec<CharClass>, bool)> {
+        let mut classes = Vec::new();
+        let mut avoid_ambiguous = false;
+        for token in raw
+            .split(|ch: char| ch == ',' || ch.is_whitespace())
+            .filter(|token| !token.is_empty())
+        {
+            let token = token.to_ascii_lowercase();
+            if token == "unambiguous" {
+                avoid_ambiguous = true;
+                continue;
+            }
+            let class = CharClass::parse(&token)?;
+            if !classes.contains(&class) {
+                classes.push(class);
+            }
+        }
+
+        (!classes.is_empty()).then_some((classes, avoid_ambiguous))
+    }
+
+    /// Copy of this policy with `length` clamped to `PASSWORD_LENGTH_MIN..=PASSWORD_LENGTH_MAX`.
+    pub fn with_length(&self, length: usize) -> Self {
+        Self {
+            length: length.clamp(PASSWORD_LENGTH_MIN, PASSWORD_LENGTH_MAX),
+            ..self.clone()
+        }
+    }
+
+    fn charsets(&self) -> Vec<Vec<char>> {
+        self.classes
+            .iter()
+            .map(|class| class.charset(self.avoid_ambiguous))
+            .collect()
+    }
+
+    /// Estimated entropy in bits: `length * log2(alphabet size)`.
+    pub fn entropy_bits(&self) -> f64 {
+        let alphabet: usize = self.charsets().iter().map(Vec::len).sum();
+        self.length as f64 * (alphabet as f64).log2()
+    }
+
+    pub(crate) fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let charsets = self.charsets();
+        let alphabet: Vec<char> = charsets.iter().flatten().copied().collect();
+        if alphabet.is_empty() {
+            return String::new();
+        }
+
+        // One character from each class first, then fill and shuffle so the required ones land anywhere.
+        let mut chars: Vec<char> = charsets
+            .iter()
+            .filter(|charset| !charset.is_empty())
+            .map(|charset| charset[rng.random_range(0..charset.len())])
+            .take(self.length)
+            .collect();
+        while chars.len() < self.length {
+            chars.push(alphabet[rng.random_range(0..alphabet.len())]);
+        }
+        for index in (1..chars.len()).rev() {
+            chars.swap(index, rng.random_range(0..=index));
+        }
+
+        chars.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn parse_classes_accepts_lists_and_rejects_unknown_or_empty() {
+        assert_eq!(
+            PasswordPolicy::parse_classes("Lower, digits unambiguous,lower"),
+            Some((vec![CharClass::Lower, CharClass::Digits], true))
+        );
+        assert_eq!(PasswordPolicy::parse_classes("lower,emoji"), None);
+        assert_eq!(PasswordPolicy::parse_classes("unambiguous"), None);
+        assert_eq!(PasswordPolicy::parse_classes(" , "), None);
+    }
+
+    #[test]
+    fn generated_passwords_follow_length_and_every_class() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let policy = PasswordPolicy::default().with_length(12);
+        for _ in 0..200 {
+            let value = policy.generate(&mut rng);
+            assert_eq!(value.chars().count(), 12);
+            assert!(value.chars().any(|ch| ch.is_ascii_lowercase()));
+            assert!(value.chars().any(|ch| ch.is_ascii_uppercase()));
+            assert!(value.chars().any(|ch| ch.is_ascii_digit()));
+            assert!(value.chars().any(|ch| SYMBOLS.contains(ch)));
+        }
+    }
+
+    #[test]
+    fn restricted_policy_only_uses_allowed_unambiguous_characters() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (classes, avoid_ambiguous) =
+            PasswordPolicy::parse_classes("lower,digits,unambiguous").expect("classes");
+        let policy = PasswordPolicy {
+            length: 64,
+            classes,
+            avoid_ambiguous,
+        };
+        for _ in 0..50 {
+            let value = policy.generate(&mut rng);
+            assert!(
+                value
+                    .chars()
+                    .all(|ch| (ch.is_ascii_lowercase() || ch.is_ascii_digit())
+                        && !AMBIGUOUS.contains(ch))
+            );
+        }
+    }
+
+    #[test]
+    fn with_length_clamps_and_entropy_scales_with_alphabet() {
+        assert_eq!(PasswordPolicy::default().with_length(2).length, 8);
+        assert_eq!(PasswordPolicy::default().with_length(999).length, 128);
+
+        let digits_only = PasswordPolicy {
+            length: 10,
+            classes: vec![CharClass::Digits],
+            avoid_ambiguous: false,
+        };
+        assert!((digits_only.entropy_bits() - 10.0 * 10f64.log2()).abs() < 1e-9);
+        assert!(PasswordPolicy::default().entropy_bits() > digits_only.entropy_bits());
+    }
+}
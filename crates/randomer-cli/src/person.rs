@@ -0,0 +1,226 @@
+use rand::{Rng, RngExt};
+
+use crate::locale::Locale;
+
+const EN_US_GIVEN_NAMES: &[&str] = &[
+    "James",
+    "Mary",
+    "John",
+    "Patricia",
+    "Robert",
+    "Jennifer",
+    "Michael",
+    "Linda",
+    "William",
+    "Elizabeth",
+    "David",
+    "Barbara",
+    "Richard",
+    "Susan",
+    "Joseph",
+    "Jessica",
+    "Thomas",
+    "Sarah",
+    "Charles",
+    "Karen",
+    "Daniel",
+    "Emily",
+    "Matthew",
+    "Olivia",
+    "Anthony",
+    "Emma",
+    "Mark",
+    "Ava",
+    "Steven",
+    "Sophia",
+    "Andrew",
+    "Grace",
+    "Joshua",
+    "Chloe",
+    "Ryan",
+    "Hannah",
+];
+
+const EN_US_FAMILY_NAMES: &[&str] = &[
+    "Smith",
+    "Johnson",
+    "Williams",
+    "Brown",
+    "Jones",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Rodriguez",
+    "Martinez",
+    "Hernandez",
+    "Lopez",
+    "Wilson",
+    "Anderson",
+    "Thomas",
+    "Taylor",
+    "Moore",
+    "Jackson",
+    "Martin",
+    "Lee",
+    "Thompson",
+    "White",
+    "Harris",
+    "Clark",
+    "Lewis",
+    "Walker",
+    "Young",
+    "Allen",
+    "King",
+    "Wright",
+    "Scott",
+    "Green",
+    "Baker",
+    "Adams",
+    "Nelson",
+    "Carter",
+];
+
+// `(display, romanized)` pairs; the romanized form feeds profile email addresses.
+const ZH_TW_FAMILY_NAMES: &[(&str, &str)] = &[
+    ("陳", "chen"),
+    ("林", "lin"),
+    ("黃", "huang"),
+    ("張", "chang"),
+    ("李", "lee"),
+    ("王", "wang"),
+    ("吳", "wu"),
+    ("劉", "liu"),
+    ("蔡", "tsai"),
+    ("楊", "yang"),
+    ("許", "hsu"),
+    ("鄭", "cheng"),
+    ("謝", "hsieh"),
+    ("郭", "kuo"),
+    ("洪", "hung"),
+    ("曾", "tseng"),
+    ("邱", "chiu"),
+    ("廖", "liao"),
+];
+
+const ZH_TW_GIVEN_NAMES: &[(&str, &str)] = &[
+    ("怡君", "yichun"),
+    ("雅婷", "yating"),
+    ("淑芬", "shufen"),
+    ("美玲", "meiling"),
+    ("佳穎", "chiaying"),
+    ("詩涵", "shihhan"),
+    ("宜庭", "yiting"),
+    ("心怡", "hsinyi"),
+    ("欣妤", "hsinyu"),
+    ("思妤", "ssuyu"),
+    ("志明", "chihming"),
+    ("家豪", "chiahao"),
+    ("俊傑", "chunchieh"),
+    ("建宏", "chienhung"),
+    ("承恩", "chengen"),
+    ("冠宇", "kuanyu"),
+    ("宇翔", "yuhsiang"),
+    ("柏翰", "pohan"),
+    ("品睿", "pinjui"),
+    ("彥廷", "yenting"),
+];
+
+// Reserved example domains (RFC 2606), so generated profiles never hit a real mailbox.
+const PROFILE_EMAIL_DOMAINS: &[&str] = &["example.com", "example.net", "example.org"];
+
+/// A generated person name plus the ASCII parts used to derive an email address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PersonName {
+    pub(crate) display: String,
+    given_ascii: String,
+    family_ascii: String,
+}
+
+impl PersonName {
+    /// `given.family<2 digits>@<example domain>`, lowercase.
+    pub(crate) fn email<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let domain = PROFILE_EMAIL_DOMAINS[rng.random_range(0..PROFILE_EMAIL_DOMAINS.len())];
+        format!(
+            "{}.{}{:02}@{domain}",
+            self.given_ascii,
+            self.family_ascii,
+            rng.random_range(0..100u8)
+        )
+        .to_ascii_lowercase()
+    }
+}
+
+pub(crate) fn random_name<R: Rng + ?Sized>(rng: &mut R, locale: Locale) -> PersonName {
+    match locale {
+        Locale::EnUs => {
+            let given = EN_US_GIVEN_NAMES[rng.random_range(0..EN_US_GIVEN_NAMES.len())];
+            let family = EN_US_FAMILY_NAMES[rng.random_range(0..EN_US_FAMILY_NAMES.len())];
+            PersonName {
+                display: format!("{given} {family}"),
+                given_ascii: given.to_string(),
+                family_ascii: family.to_string(),
+            }
+        }
+        Locale::ZhTw => {
+            let (family, family_ascii) =
+                ZH_TW_FAMILY_NAMES[rng.random_range(0..ZH_TW_FAMILY_NAMES.len())];
+            let (given, given_ascii) =
+                ZH_TW_GIVEN_NAMES[rng.random_range(0..ZH_TW_GIVEN_NAMES.len())];
+            PersonName {
+                display: format!("{family}{given}"),
+                given_ascii: given_ascii.to_string(),
+                family_ascii: family_ascii.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn en_us_names_are_given_then_family_from_bundled_lists() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let name = random_name(&mut rng, Locale::EnUs);
+            let (given, family) = name.display.split_once(' ').expect("two parts");
+            assert!(EN_US_GIVEN_NAMES.contains(&given));
+            assert!(EN_US_FAMILY_NAMES.contains(&family));
+        }
+    }
+
+    #[test]
+    fn zh_tw_names_are_family_then_given_without_space() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let name = random_name(&mut rng, Locale::ZhTw);
+            assert_eq!(name.display.chars().count(), 3);
+            assert!(
+                ZH_TW_FAMILY_NAMES
+                    .iter()
+                    .any(|(family, _)| name.display.starts_with(family))
+            );
+        }
+    }
+
+    #[test]
+    fn profile_email_is_ascii_lowercase_on_example_domain() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for locale in [Locale::EnUs, Locale::ZhTw] {
+            for _ in 0..50 {
+                let email = random_name(&mut rng, locale).email(&mut rng);
+                let (local, domain) = email.split_once('@').expect("email should contain @");
+                assert!(PROFILE_EMAIL_DOMAINS.contains(&domain));
+                assert!(
+                    local
+                        .chars()
+                        .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '.')
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use rand::{Rng, RngExt};
+
+use crate::locale::Locale;
+
+/// Numbering plan for the `phone` format. Defaults to the `RANDOMER_LOCALE` region and can be picked with a query
+/// argument such as `phone jp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhoneRegion {
+    /// Taiwan mobile, `0912345678`.
+    Tw,
+    /// North American Numbering Plan, `(415) 555-0132`.
+    Us,
+    /// Japan mobile, `090-1234-5678`.
+    Jp,
+    /// United Kingdom mobile, `07700 900123`.
+    Uk,
+}
+
+impl PhoneRegion {
+    pub fn parse(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "tw" | "taiwan" => Some(Self::Tw),
+            "us" | "usa" => Some(Self::Us),
+            "jp" | "japan" => Some(Self::Jp),
+            "uk" | "gb" => Some(Self::Uk),
+            _ => None,
+        }
+    }
+
+    /// Query argument for this region, e.g. `jp`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Tw => "tw",
+            Self::Us => "us",
+            Self::Jp => "jp",
+            Self::Uk => "uk",
+        }
+    }
+
+    pub fn for_locale(locale: Locale) -> Self {
+        match locale {
+            Locale::EnUs => Self::Us,
+            Locale::ZhTw => Self::Tw,
+        }
+    }
+}
+
+/// A generated number in national notation plus its E.164 form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Phone {
+    pub(crate) national: String,
+    pub(crate) e164: String,
+}
+
+pub(crate) fn random_phone<R: Rng + ?Sized>(rng: &mut R, region: PhoneRegion) -> Phone {
+    match region {
+        PhoneRegion::Tw => {
+            let subscriber = digits(rng, 8);
+            Phone {
+                national: format!("09{subscriber}"),
+                e164: format!("+8869{subscriber}"),
+            }
+        }
+        PhoneRegion::Us => {
+            let area = nanp_code(rng, 0..=8);
+            let exchange = nanp_code(rng, 0..=9);
+            let line = digits(rng, 4);
+            Phone {
+                national: format!("({area}) {exchange}-{line}"),
+                e164: format!("+1{area}{exchange}{line}"),
+            }
+        }
+        PhoneRegion::Jp => {
+            let prefix = ['7', '8', '9'][rng.random_range(0..3)];
+            let (head, tail) = (digits(rng, 4), digits(rng, 4));
+            Phone {
+                national: format!("0{prefix}0-{head}-{tail}"),
+                e164: format!("+81{prefix}0{head}{tail}"),
+            }
+        }
+        PhoneRegion::Uk => {
+            // 076 is reserved for pagers.
+            let range = ['1', '2', '3', '4', '5', '7', '8', '9'][rng.random_range(0..8)];
+            let (head, tail) = (digits(rng, 2), digits(rng, 6));
+            Phone {
+                national: format!("07{range}{head} {tail}"),
+                e164: format!("+447{range}{head}{tail}"),
+            }
+        }
+    }
+}
+
+/// NANP area code or exchange: `[2-9]` then a digit from `second`, never an `N11` service code.
+fn nanp_code<R: Rng + ?Sized>(rng: &mut R, second: std::ops::RangeInclusive<u8>) -> String {
+    loop {
+        let code = [
+            rng.random_range(2..=9u8),
+            rng.random_range(second.clone()),
+            rng.random_range(0..=9u8),
+        ];
+        if code[1..] != [1, 1] {
+            return code.iter().map(|&digit| char::from(b'0' + digit)).collect();
+        }
+    }
+}
+
+fn digits<R: Rng + ?Sized>(rng: &mut R, len: usize) -> String {
+    (0..len)
+        .map(|_| char::from(b'0' + rng.random_range(0..=9u8)))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn national_digits(national: &str) -> String {
+        national.chars().filter(char::is_ascii_digit).collect()
+    }
+
+    #[test]
+    fn national_forms_follow_each_numbering_plan() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..200 {
+            let tw = random_phone(&mut rng, PhoneRegion::Tw).national;
+            assert!(tw.len() == 10 && tw.starts_with("09"), "{tw}");
+
+            let us = random_phone(&mut rng, PhoneRegion::Us).national;
+            let bytes = us.as_bytes();
+            assert_eq!(us.len(), "(415) 555-0132".len(), "{us}");
+            assert_eq!(
+                (bytes[0], bytes[4], bytes[5], bytes[9]),
+                (b'(', b')', b' ', b'-')
+            );
+            assert!(
+                bytes[1] >= b'2' && bytes[2] != b'9' && bytes[6] >= b'2',
+                "{us}"
+            );
+            assert!(&us[2..4] != "11" && &us[7..9] != "11", "{us}");
+
+            let jp = random_phone(&mut rng, PhoneRegion::Jp).national;
+            let parts: Vec<&str> = jp.split('-').collect();
+            assert!(["070", "080", "090"].contains(&parts[0]), "{jp}");
+            assert_eq!((parts[1].len(), parts[2].len()), (4, 4));
+
+            let uk = random_phone(&mut rng, PhoneRegion::Uk).national;
+            assert!(uk.starts_with("07") && !uk.starts_with("076"), "{uk}");
+            assert_eq!(uk.find(' '), Some(5));
+            assert_eq!(national_digits(&uk).len(), 11);
+        }
+    }
+
+    #[test]
+    fn e164_drops_the_trunk_prefix_and_adds_the_country_code() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for (region, country) in [
+            (PhoneRegion::Tw, "886"),
+            (PhoneRegion::Us, "1"),
+            (PhoneRegion::Jp, "81"),
+            (PhoneRegion::Uk, "44"),
+        ] {
+            let phone = random_phone(&mut rng, region);
+            let national = national_digits(&phone.national);
+            let subscriber = national.strip_prefix('0').unwrap_or(&national);
+            assert_eq!(phone.e164, format!("+{country}{subscriber}"), "{region:?}");
+            assert!(phone.e164.len() <= 16);
+        }
+    }
+
+    #[test]
+    fn regions_parse_aliases_and_follow_the_locale() {
+        assert_eq!(PhoneRegion::parse(" JP "), Some(PhoneRegion::Jp));
+        assert_eq!(PhoneRegion::parse("gb"), Some(PhoneRegion::Uk));
+        assert_eq!(PhoneRegion::parse("fr"), None);
+        assert_eq!(PhoneRegion::for_locale(Locale::ZhTw), PhoneRegion::Tw);
+        assert_eq!(PhoneRegion::for_locale(Locale::EnUs), PhoneRegion::Us);
+    }
+}
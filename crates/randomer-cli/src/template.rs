@@ -0,0 +1,134 @@
+use rand::{Rng, RngExt};
+
+const DIGITS: &[u8] = b"0123456789";
+const UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const HEX: &[u8] = b"0123456789abcdef";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    Random(&'static [u8]),
+}
+
+/// User-defined format from `RANDOMER_TEMPLATE_<NAME>=<pattern>`.
+///
+/// In the pattern `#` is a digit, `A` an uppercase letter, `a` a lowercase letter, and `x` a lowercase hex digit;
+/// `\` makes the next character literal and every other character is copied as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    name: String,
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+impl Template {
+    /// Parse a template; the name is lowercased. Returns `None` for an empty name or pattern, a name that is not
+    /// `[a-z0-9_-]`, or a pattern ending in a lone `\`.
+    pub fn parse(name: &str, pattern: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+        {
+            return None;
+        }
+
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(ch) = chars.next() {
+            tokens.push(match ch {
+                '\\' => Token::Literal(chars.next()?),
+                '#' => Token::Random(DIGITS),
+                'A' => Token::Random(UPPER),
+                'a' => Token::Random(LOWER),
+                'x' => Token::Random(HEX),
+                other => Token::Literal(other),
+            });
+        }
+        if tokens.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name,
+            pattern: pattern.to_string(),
+            tokens,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub(crate) fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.tokens
+            .iter()
+            .map(|token| match token {
+                Token::Literal(ch) => *ch,
+                Token::Random(alphabet) => {
+                    char::from(alphabet[rng.random_range(0..alphabet.len())])
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn placeholders_expand_to_their_character_classes() {
+        let template = Template::parse("ORDER", "ORD-####-AAAA-aa-xx").expect("valid template");
+        assert_eq!(template.name(), "order");
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let value = template.generate(&mut rng);
+            let parts: Vec<&str> = value.split('-').collect();
+            assert_eq!(parts.len(), 5);
+            assert_eq!(parts[0], "ORD");
+            assert!(parts[1].len() == 4 && parts[1].chars().all(|ch| ch.is_ascii_digit()));
+            assert!(parts[2].len() == 4 && parts[2].chars().all(|ch| ch.is_ascii_uppercase()));
+            assert!(parts[3].len() == 2 && parts[3].chars().all(|ch| ch.is_ascii_lowercase()));
+            assert!(
+                parts[4].len() == 2
+                    && parts[4]
+                        .chars()
+                        .all(|ch| ch.is_ascii_digit() || ('a'..='f').contains(&ch))
+            );
+        }
+    }
+
+    #[test]
+    fn backslash_escapes_placeholders_and_itself() {
+        let template = Template::parse("batch", r"B\atch\#\\-#").expect("valid template");
+        let mut rng = StdRng::seed_from_u64(7);
+        let value = template.generate(&mut rng);
+        assert!(value.starts_with(r"Batch#\-"), "{value}");
+        assert!(
+            value[value.len() - 1..]
+                .chars()
+                .all(|ch| ch.is_ascii_digit())
+        );
+        assert_eq!(value.len(), r"Batch#\-0".len());
+    }
+
+    #[test]
+    fn parse_rejects_bad_names_and_patterns() {
+        assert_eq!(Template::parse("", "###"), None);
+        assert_eq!(Template::parse("my order", "###"), None);
+        assert_eq!(Template::parse("order", ""), None);
+        assert_eq!(Template::parse("order", r"ORD-#\"), None);
+        assert!(Template::parse("sku_v2", "SKU-###").is_some());
+    }
+}
@@ -0,0 +1,117 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use rand::Rng;
+
+pub const DEFAULT_SECRET_BYTES: usize = 32;
+pub const SECRET_BYTES_MIN: usize = 8;
+pub const SECRET_BYTES_MAX: usize = 512;
+
+const KEY256_BYTES: usize = 32;
+// HS256 signatures are one SHA-256 output.
+const JWT_SIGNATURE_BYTES: usize = 32;
+const JWT_LIFETIME_SECONDS: i64 = 3600;
+const JWT_HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+fn random_bytes<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// `bytes` random bytes, base64url without padding.
+pub(crate) fn random_secret<R: Rng + ?Sized>(rng: &mut R, bytes: usize) -> String {
+    URL_SAFE_NO_PAD.encode(random_bytes(rng, bytes))
+}
+
+/// 256-bit key as 64 lowercase hex characters.
+pub(crate) fn random_key256<R: Rng + ?Sized>(rng: &mut R) -> String {
+    hex(&random_bytes(rng, KEY256_BYTES))
+}
+
+/// JWT-shaped `header.payload.signature`: an HS256 header, a claims object issued at `now` and valid for an hour,
+/// and a random (not computed) signature. Every segment decodes as base64url.
+pub(crate) fn random_jwt<R: Rng + ?Sized>(rng: &mut R, now: i64) -> String {
+    let payload = format!(
+        r#"{{"sub":"user_{}","iat":{now},"exp":{},"jti":"{}"}}"#,
+        hex(&random_bytes(rng, 4)),
+        now + JWT_LIFETIME_SECONDS,
+        hex(&random_bytes(rng, 8)),
+    );
+    format!(
+        "{}.{}.{}",
+        URL_SAFE_NO_PAD.encode(JWT_HEADER),
+        URL_SAFE_NO_PAD.encode(payload),
+        URL_SAFE_NO_PAD.encode(random_bytes(rng, JWT_SIGNATURE_BYTES)),
+    )
+}
+
+/// Byte length for `secret` from a query number, clamped to `SECRET_BYTES_MIN..=SECRET_BYTES_MAX`.
+pub(crate) fn clamp_secret_bytes(bytes: usize) -> usize {
+    bytes.clamp(SECRET_BYTES_MIN, SECRET_BYTES_MAX)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn decode(segment: &str) -> Vec<u8> {
+        URL_SAFE_NO_PAD.decode(segment).expect("base64url segment")
+    }
+
+    #[test]
+    fn secret_decodes_to_requested_byte_length() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for bytes in [SECRET_BYTES_MIN, DEFAULT_SECRET_BYTES, 33, SECRET_BYTES_MAX] {
+            let secret = random_secret(&mut rng, bytes);
+            assert!(
+                secret
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+            );
+            assert_eq!(decode(&secret).len(), bytes);
+        }
+        assert_eq!(clamp_secret_bytes(1), SECRET_BYTES_MIN);
+        assert_eq!(clamp_secret_bytes(4096), SECRET_BYTES_MAX);
+    }
+
+    #[test]
+    fn key256_is_64_lowercase_hex_chars() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let key = random_key256(&mut rng);
+        assert_eq!(key.len(), 64);
+        assert!(
+            key.chars()
+                .all(|ch| ch.is_ascii_digit() || ('a'..='f').contains(&ch))
+        );
+    }
+
+    #[test]
+    fn jwt_segments_decode_to_header_claims_and_signature() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let now = 1_735_689_600;
+        let jwt = random_jwt(&mut rng, now);
+        let segments: Vec<&str> = jwt.split('.').collect();
+        assert_eq!(segments.len(), 3);
+
+        let header: serde_json::Value =
+            serde_json::from_slice(&decode(segments[0])).expect("header JSON");
+        assert_eq!(header["alg"], "HS256");
+        assert_eq!(header["typ"], "JWT");
+
+        let claims: serde_json::Value =
+            serde_json::from_slice(&decode(segments[1])).expect("claims JSON");
+        assert_eq!(claims["iat"], now);
+        assert_eq!(claims["exp"], now + JWT_LIFETIME_SECONDS);
+        assert!(claims["sub"].as_str().expect("sub").starts_with("user_"));
+        assert_eq!(claims["jti"].as_str().expect("jti").len(), 16);
+
+        assert_eq!(decode(segments[2]).len(), JWT_SIGNATURE_BYTES);
+    }
+}
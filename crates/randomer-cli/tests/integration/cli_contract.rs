@@ -68,6 +68,70 @@ fn service_json_error_envelope_has_required_keys_and_no_secret_leak() {
     assert!(!stderr.contains(secret));
 }
 
+#[test]
+fn generate_plain_output_is_one_value_per_line_and_reproducible_with_seed() {
+    let args = [
+        "generate", "--format", "slug", "--count", "5", "--seed", "42", "--output", "plain",
+    ];
+    let first = run_cli(&args, &[]);
+    let second = run_cli(&args, &[]);
+    assert_eq!(first.status.code(), Some(0));
+    assert_eq!(first.stdout, second.stdout);
+
+    let stdout = String::from_utf8(first.stdout).expect("stdout should be utf-8");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines.iter().all(|line| line.contains('-')));
+}
+
+#[test]
+fn short_aliases_produce_the_same_alfred_payload() {
+    let full = run_cli(
+        &[
+            "list-formats",
+            "--query",
+            "uuid",
+            "--seed",
+            "1",
+            "--output",
+            "alfred-json",
+        ],
+        &[],
+    );
+    let short = run_cli(
+        &[
+            "list", "--query", "uuid", "--seed", "1", "--output", "alfred",
+        ],
+        &[],
+    );
+    assert_eq!(short.status.code(), Some(0));
+    assert_eq!(full.stdout, short.stdout);
+
+    let types = run_cli(&["types", "--query", "uuid"], &[]);
+    let json: Value = serde_json::from_slice(&types.stdout).expect("stdout should be json");
+    assert_eq!(json["items"][0]["title"].as_str(), Some("uuid"));
+}
+
+#[test]
+fn plain_mode_errors_go_to_stderr_with_code() {
+    let output = run_cli(
+        &[
+            "generate",
+            "--format",
+            "unknown-format",
+            "--output",
+            "plain",
+        ],
+        &[],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr).trim(),
+        "error[NILS_RANDOMER_001]: unknown format: unknown-format"
+    );
+}
+
 fn resolve_cli_path() -> PathBuf {
     if let Some(path) = std::env::var_os("CARGO_BIN_EXE_randomer-cli") {
         return PathBuf::from(path);
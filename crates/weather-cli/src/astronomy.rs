@@ -0,0 +1,239 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{ForecastDay, ForecastOutput, SunDay, SunOutput, TimeWindow};
+
+const SYNODIC_MONTH_DAYS: f64 = 29.530_588_853;
+/// Reference new moon: 2000-01-06 18:14 UTC.
+const REFERENCE_NEW_MOON_UNIX_SECS: f64 = 947_182_440.0;
+/// Golden hour is approximated as the first hour after sunrise and the last hour before sunset.
+const GOLDEN_HOUR_MINUTES: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonPhase {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    pub fn label_en(self) -> &'static str {
+        match self {
+            Self::NewMoon => "New moon",
+            Self::WaxingCrescent => "Waxing crescent",
+            Self::FirstQuarter => "First quarter",
+            Self::WaxingGibbous => "Waxing gibbous",
+            Self::FullMoon => "Full moon",
+            Self::WaningGibbous => "Waning gibbous",
+            Self::LastQuarter => "Last quarter",
+            Self::WaningCrescent => "Waning crescent",
+        }
+    }
+
+    /// Key of the phase label in the `locales/` tables.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::NewMoon => "moon.new_moon",
+            Self::WaxingCrescent => "moon.waxing_crescent",
+            Self::FirstQuarter => "moon.first_quarter",
+            Self::WaxingGibbous => "moon.waxing_gibbous",
+            Self::FullMoon => "moon.full_moon",
+            Self::WaningGibbous => "moon.waning_gibbous",
+            Self::LastQuarter => "moon.last_quarter",
+            Self::WaningCrescent => "moon.waning_crescent",
+        }
+    }
+
+    pub fn label_zh(self) -> &'static str {
+        match self {
+            Self::NewMoon => "新月",
+            Self::WaxingCrescent => "眉月",
+            Self::FirstQuarter => "上弦月",
+            Self::WaxingGibbous => "盈凸月",
+            Self::FullMoon => "滿月",
+            Self::WaningGibbous => "虧凸月",
+            Self::LastQuarter => "下弦月",
+            Self::WaningCrescent => "殘月",
+        }
+    }
+}
+
+/// Moon phase and illuminated fraction (percent) at noon UTC on `date`, from the mean synodic month.
+/// Accurate to within about a day, which is enough for a phase label.
+pub fn moon_on(date: NaiveDate) -> (MoonPhase, u8) {
+    let noon = date
+        .and_hms_opt(12, 0, 0)
+        .map(|value| value.and_utc().timestamp())
+        .unwrap_or_default() as f64;
+    let age_days =
+        ((noon - REFERENCE_NEW_MOON_UNIX_SECS) / 86_400.0).rem_euclid(SYNODIC_MONTH_DAYS);
+    let fraction = age_days / SYNODIC_MONTH_DAYS;
+
+    // Eight equal buckets centred on the principal phases.
+    let phase = match ((fraction * 8.0 + 0.5).floor() as usize) % 8 {
+        0 => MoonPhase::NewMoon,
+        1 => MoonPhase::WaxingCrescent,
+        2 => MoonPhase::FirstQuarter,
+        3 => MoonPhase::WaxingGibbous,
+        4 => MoonPhase::FullMoon,
+        5 => MoonPhase::WaningGibbous,
+        6 => MoonPhase::LastQuarter,
+        _ => MoonPhase::WaningCrescent,
+    };
+    let illumination = (1.0 - (fraction * std::f64::consts::TAU).cos()) / 2.0;
+
+    (phase, (illumination * 100.0).round() as u8)
+}
+
+/// `moon_on` for a `YYYY-MM-DD` forecast date.
+pub fn moon_for_date(date: &str) -> Option<(MoonPhase, u8)> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(moon_on)
+}
+
+/// Sun times for one forecast day; `None` when the provider did not report sunrise and sunset.
+pub fn sun_day(day: &ForecastDay) -> Option<SunDay> {
+    let sunrise = parse_local_time(day.sunrise.as_deref()?)?;
+    let sunset = parse_local_time(day.sunset.as_deref()?)?;
+    if sunset <= sunrise {
+        return None;
+    }
+
+    let golden = chrono::Duration::minutes(GOLDEN_HOUR_MINUTES);
+    let (moon_phase, moon_illumination_pct) = match (day.moon_phase, day.moon_illumination_pct) {
+        (Some(phase), Some(pct)) => (phase, pct),
+        _ => moon_for_date(&day.date)?,
+    };
+
+    Some(SunDay {
+        date: day.date.clone(),
+        sunrise: format_local_time(sunrise),
+        sunset: format_local_time(sunset),
+        day_length_minutes: (sunset - sunrise).num_minutes(),
+        golden_hour_morning: TimeWindow {
+            start: format_local_time(sunrise),
+            end: format_local_time(sunrise + golden),
+        },
+        golden_hour_evening: TimeWindow {
+            start: format_local_time(sunset - golden),
+            end: format_local_time(sunset),
+        },
+        moon_phase,
+        moon_illumination_pct,
+    })
+}
+
+/// Sun and moon view of a daily forecast; days without provider sun times are dropped.
+pub fn sun_output(forecast: &ForecastOutput) -> SunOutput {
+    SunOutput {
+        location: forecast.location.clone(),
+        timezone: forecast.timezone.clone(),
+        days: forecast.forecast.iter().filter_map(sun_day).collect(),
+        source: forecast.source.clone(),
+        source_trace: forecast.source_trace.clone(),
+        fetched_at: forecast.fetched_at.clone(),
+        freshness: forecast.freshness.clone(),
+    }
+}
+
+/// `HH:MM` part of a provider `YYYY-MM-DDTHH:MM` local time.
+pub fn clock_label(local_time: &str) -> &str {
+    local_time
+        .split_once('T')
+        .map(|(_, time)| time)
+        .unwrap_or(local_time)
+}
+
+fn parse_local_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").ok()
+}
+
+fn format_local_time(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").expect("date")
+    }
+
+    fn day_with_sun(sunrise: Option<&str>, sunset: Option<&str>) -> ForecastDay {
+        ForecastDay {
+            date: "2026-02-11".to_string(),
+            weather_code: 0,
+            summary_zh: "晴朗".to_string(),
+            temp_min_c: 14.0,
+            temp_max_c: 20.0,
+            precip_prob_max_pct: 0,
+            precip_sum_mm: None,
+            sunrise: sunrise.map(str::to_string),
+            sunset: sunset.map(str::to_string),
+            moon_phase: None,
+            moon_illumination_pct: None,
+            wind_speed_max_kmh: None,
+            wind_direction_deg: None,
+            humidity_mean_pct: None,
+            uv_index_max: None,
+        }
+    }
+
+    #[test]
+    fn moon_phase_matches_known_new_and_full_moons() {
+        // Published new moons: 2024-01-11, 2025-01-29; full moons: 2024-01-25, 2026-03-03.
+        for raw in ["2024-01-11", "2025-01-29"] {
+            let (phase, illumination) = moon_on(date(raw));
+            assert_eq!(phase, MoonPhase::NewMoon, "{raw}");
+            assert!(illumination <= 3, "{raw}: {illumination}");
+        }
+        for raw in ["2024-01-25", "2026-03-03"] {
+            let (phase, illumination) = moon_on(date(raw));
+            assert_eq!(phase, MoonPhase::FullMoon, "{raw}");
+            assert!(illumination >= 97, "{raw}: {illumination}");
+        }
+    }
+
+    #[test]
+    fn moon_phase_waxes_between_new_and_full() {
+        assert_eq!(moon_on(date("2024-01-15")).0, MoonPhase::WaxingCrescent);
+        assert_eq!(moon_on(date("2024-01-18")).0, MoonPhase::FirstQuarter);
+        assert_eq!(moon_on(date("2024-02-02")).0, MoonPhase::LastQuarter);
+        assert_eq!(moon_for_date("not-a-date"), None);
+    }
+
+    #[test]
+    fn sun_day_derives_day_length_and_golden_hours() {
+        let sun = sun_day(&day_with_sun(
+            Some("2026-02-11T06:32"),
+            Some("2026-02-11T17:48"),
+        ))
+        .expect("sun day");
+
+        assert_eq!(sun.day_length_minutes, 11 * 60 + 16);
+        assert_eq!(sun.golden_hour_morning.end, "2026-02-11T07:32");
+        assert_eq!(sun.golden_hour_evening.start, "2026-02-11T16:48");
+        assert_eq!(clock_label(&sun.sunset), "17:48");
+        assert_eq!(sun.moon_phase, moon_for_date("2026-02-11").expect("moon").0);
+    }
+
+    #[test]
+    fn sun_day_requires_provider_sun_times() {
+        assert_eq!(sun_day(&day_with_sun(None, Some("2026-02-11T17:48"))), None);
+        assert_eq!(
+            sun_day(&day_with_sun(
+                Some("2026-02-11T17:48"),
+                Some("2026-02-11T06:32")
+            )),
+            None
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::geocoding::{ResolvedLocation, read_cached_city_location, write_cached_city_location};
+use crate::model::LocationQuery;
+use crate::providers::ProviderApi;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct AutoLocateRecord {
+    location: ResolvedLocation,
+    fetched_at: String,
+}
+
+pub fn auto_locate_cache_path(config_cache_dir: &Path) -> PathBuf {
+    config_cache_dir
+        .join("weather-cli")
+        .join("auto-locate.json")
+}
+
+/// Location to use when no `--city` or coordinates were given: `None` unless `WEATHER_AUTO_LOCATE` opted in.
+///
+/// The IP lookup is cached for `auto_locate_ttl_secs`; a stale entry is reused when the provider fails. A located
+/// city seeds the geocoding cache (without overwriting an existing entry), so forecasts resolve it by name without
+/// another geocoding request.
+pub fn resolve_auto_location<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+) -> Result<Option<LocationQuery>, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let Some(ttl_secs) = config.auto_locate_ttl_secs else {
+        return Ok(None);
+    };
+
+    let path = auto_locate_cache_path(&config.cache_dir);
+    let cached = read_record(&path).ok().flatten();
+    let now = now_fn();
+
+    let location = match cached {
+        Some(record) if is_fresh(&record, now, ttl_secs) => record.location,
+        cached => match providers.locate_by_ip() {
+            Ok(location) => {
+                let _ = write_record(
+                    &path,
+                    &AutoLocateRecord {
+                        location: location.clone(),
+                        fetched_at: now.to_rfc3339(),
+                    },
+                );
+                location
+            }
+            Err(error) => match cached {
+                Some(record) => record.location,
+                None => {
+                    return Err(AppError::runtime(format!(
+                        "failed to detect location from IP address: {error}"
+                    )));
+                }
+            },
+        },
+    };
+
+    if location.name.trim().is_empty() {
+        return Ok(Some(LocationQuery::Coordinates {
+            lat: location.latitude,
+            lon: location.longitude,
+        }));
+    }
+
+    if !matches!(
+        read_cached_city_location(&config.cache_dir, &location.name),
+        Ok(Some(_))
+    ) {
+        let _ = write_cached_city_location(&config.cache_dir, &location.name, &location);
+    }
+    Ok(Some(LocationQuery::City(location.name)))
+}
+
+fn is_fresh(record: &AutoLocateRecord, now: DateTime<Utc>, ttl_secs: u64) -> bool {
+    DateTime::parse_from_rfc3339(&record.fetched_at)
+        .ok()
+        .and_then(|fetched_at| {
+            u64::try_from(now.signed_duration_since(fetched_at).num_seconds()).ok()
+        })
+        .is_some_and(|age_secs| age_secs <= ttl_secs)
+}
+
+fn read_record(path: &Path) -> io::Result<Option<AutoLocateRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let payload = fs::read_to_string(path)?;
+    Ok(serde_json::from_str::<AutoLocateRecord>(&payload).ok())
+}
+
+fn write_record(path: &Path, record: &AutoLocateRecord) -> io::Result<()> {
+    let parent = path.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cache path must have a parent directory",
+        )
+    })?;
+    fs::create_dir_all(parent)?;
+
+    let payload = serde_json::to_vec(record)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, payload)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::providers::{
+        ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderHistory,
+        ProviderHourlyForecast, ProviderNowcast,
+    };
+    use crate::units::UnitSystem;
+
+    struct IpProviders {
+        result: Result<ResolvedLocation, ProviderError>,
+        calls: Cell<usize>,
+    }
+
+    impl IpProviders {
+        fn new(result: Result<ResolvedLocation, ProviderError>) -> Self {
+            Self {
+                result,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl ProviderApi for IpProviders {
+        fn geocode_city(&self, _city: &str) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_hourly_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_hours: usize,
+        ) -> Result<ProviderHourlyForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_met_no_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            self.calls.set(self.calls.get() + 1);
+            self.result.clone()
+        }
+    }
+
+    fn taipei() -> ResolvedLocation {
+        ResolvedLocation {
+            name: "Taipei".to_string(),
+            latitude: 25.0478,
+            longitude: 121.5319,
+            timezone: "Asia/Taipei".to_string(),
+        }
+    }
+
+    fn auto_locate_config(cache_dir: &Path, auto_locate_ttl_secs: Option<u64>) -> RuntimeConfig {
+        RuntimeConfig {
+            cache_dir: cache_dir.to_path_buf(),
+            cache_ttl_secs: 1800,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
+        }
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 11, hour, 0, 0)
+            .single()
+            .expect("time")
+    }
+
+    #[test]
+    fn auto_locate_is_disabled_without_opt_in() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = IpProviders::new(Ok(taipei()));
+
+        let location =
+            resolve_auto_location(&auto_locate_config(dir.path(), None), &providers, || at(0))
+                .expect("ok");
+        assert_eq!(location, None);
+        assert_eq!(providers.calls.get(), 0);
+    }
+
+    #[test]
+    fn auto_locate_caches_lookup_and_seeds_geocode_cache() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = auto_locate_config(dir.path(), Some(3600));
+        let providers = IpProviders::new(Ok(taipei()));
+
+        for _ in 0..2 {
+            let location = resolve_auto_location(&config, &providers, || at(0)).expect("ok");
+            assert_eq!(location, Some(LocationQuery::City("Taipei".to_string())));
+        }
+        assert_eq!(providers.calls.get(), 1);
+        assert_eq!(
+            read_cached_city_location(dir.path(), "Taipei").expect("read"),
+            Some(taipei())
+        );
+
+        resolve_auto_location(&config, &providers, || at(2)).expect("ok");
+        assert_eq!(providers.calls.get(), 2);
+    }
+
+    #[test]
+    fn auto_locate_reuses_stale_entry_when_provider_fails() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = auto_locate_config(dir.path(), Some(3600));
+        resolve_auto_location(&config, &IpProviders::new(Ok(taipei())), || at(0)).expect("ok");
+
+        let failing = IpProviders::new(Err(ProviderError::Transport("timeout".to_string())));
+        let location = resolve_auto_location(&config, &failing, || at(5)).expect("stale");
+        assert_eq!(location, Some(LocationQuery::City("Taipei".to_string())));
+
+        let empty_dir = tempfile::tempdir().expect("tempdir");
+        let error = resolve_auto_location(
+            &auto_locate_config(empty_dir.path(), Some(3600)),
+            &failing,
+            || at(5),
+        )
+        .expect_err("must fail");
+        assert!(error.message.contains("timeout"));
+    }
+
+    #[test]
+    fn auto_locate_without_city_uses_coordinates() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = IpProviders::new(Ok(ResolvedLocation {
+            name: String::new(),
+            ..taipei()
+        }));
+
+        let location = resolve_auto_location(
+            &auto_locate_config(dir.path(), Some(3600)),
+            &providers,
+            || at(0),
+        )
+        .expect("ok");
+        assert_eq!(
+            location,
+            Some(LocationQuery::Coordinates {
+                lat: 25.0478,
+                lon: 121.5319
+            })
+        );
+    }
+}
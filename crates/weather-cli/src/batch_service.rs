@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, SecondsFormat, Utc};
 
+use crate::astronomy;
 use crate::cache::{
     CacheRecord, cache_path, evaluate_freshness, parse_fetched_at, read_cache, write_cache,
 };
@@ -10,7 +11,7 @@ use crate::error::AppError;
 use crate::geocoding::{ResolvedLocation, read_cached_city_location, write_cached_city_location};
 use crate::model::{
     CacheMetadata, ForecastBatchEntry, ForecastBatchOutput, ForecastDay, ForecastOutput,
-    ForecastPeriod, FreshnessStatus, normalize_cities,
+    ForecastPeriod, FreshnessStatus, ProviderAttempt, normalize_cities,
 };
 use crate::providers::{ProviderApi, ProviderForecast, ProviderForecastDay};
 use crate::weather_code;
@@ -72,6 +73,7 @@ where
                     &location,
                     FreshnessStatus::CacheFresh,
                     *age_secs,
+                    Vec::new(),
                     &output_context,
                 ),
             ));
@@ -89,7 +91,7 @@ where
     }
 
     if !pending.is_empty() {
-        resolve_pending_forecasts(providers, &mut entries, pending)?;
+        resolve_pending_forecasts(config, providers, &mut entries, pending)?;
     }
 
     Ok(ForecastBatchOutput {
@@ -154,126 +156,70 @@ fn resolve_locations<P: ProviderApi>(
 }
 
 fn resolve_pending_forecasts<P: ProviderApi>(
+    config: &RuntimeConfig,
     providers: &P,
     entries: &mut [Option<ForecastBatchEntry>],
     pending: Vec<PendingForecast>,
 ) -> Result<(), AppError> {
-    let locations = pending
-        .iter()
-        .map(|item| item.location.clone())
-        .collect::<Vec<_>>();
     let forecast_days = pending[0].output_context.period.forecast_days();
+    let mut remaining = pending
+        .into_iter()
+        .map(|item| (item, Vec::new()))
+        .collect::<Vec<(PendingForecast, Vec<String>)>>();
 
-    match providers.fetch_open_meteo_forecasts_batch(&locations, forecast_days) {
-        Ok(forecasts) => {
-            if forecasts.len() != pending.len() {
-                let error = format!(
-                    "open_meteo batch result length mismatch: expected {}, got {}",
-                    pending.len(),
-                    forecasts.len()
-                );
-                resolve_met_no_fallback(providers, entries, pending, &error, forecast_days);
-                return Ok(());
-            }
+    for provider in config.forecast_provider_order() {
+        if remaining.is_empty() {
+            break;
+        }
 
-            for (item, forecast) in pending.into_iter().zip(forecasts) {
-                let entry = match build_live_output(
-                    &item.path,
-                    &item.location,
-                    forecast,
-                    "open_meteo",
-                    Vec::new(),
-                    &item.output_context,
-                ) {
-                    Ok(output) => success_entry(&item.city, output),
-                    Err(error) => error_entry(&item.city, error.message),
-                };
-                entries[item.index] = Some(entry);
+        let locations = remaining
+            .iter()
+            .map(|(item, _)| item.location.clone())
+            .collect::<Vec<_>>();
+        let mut results = providers
+            .fetch_forecasts_batch_from(provider, &locations, forecast_days)
+            .into_iter();
+        let mut unresolved = Vec::new();
+
+        for (item, mut trace) in remaining {
+            match results.next() {
+                Some(Ok(forecast)) => {
+                    let entry = match build_live_output(
+                        &item.path,
+                        &item.location,
+                        forecast,
+                        provider.as_str(),
+                        trace,
+                        &item.output_context,
+                    ) {
+                        Ok(output) => success_entry(&item.city, output),
+                        Err(error) => error_entry(&item.city, error.message),
+                    };
+                    entries[item.index] = Some(entry);
+                }
+                Some(Err(error)) => {
+                    trace.push(format!("{}: {error}", provider.as_str()));
+                    unresolved.push((item, trace));
+                }
+                None => {
+                    trace.push(format!("{}: missing batch result", provider.as_str()));
+                    unresolved.push((item, trace));
+                }
             }
         }
-        Err(error) => {
-            resolve_met_no_fallback(
-                providers,
-                entries,
-                pending,
-                &format!("open_meteo: {error}"),
-                forecast_days,
-            );
-        }
-    }
 
-    Ok(())
-}
-
-fn resolve_met_no_fallback<P: ProviderApi>(
-    providers: &P,
-    entries: &mut [Option<ForecastBatchEntry>],
-    pending: Vec<PendingForecast>,
-    open_meteo_error: &str,
-    forecast_days: usize,
-) {
-    let locations = pending
-        .iter()
-        .map(|item| item.location.clone())
-        .collect::<Vec<_>>();
-    let fallback_results = providers.fetch_met_no_forecasts_batch(&locations, forecast_days);
-
-    for (index, item) in pending.into_iter().enumerate() {
-        let entry_index = item.index;
-        let source_trace = vec![open_meteo_error.to_string()];
-        let entry = match fallback_results.get(index) {
-            Some(Ok(forecast)) => match build_live_output(
-                &item.path,
-                &item.location,
-                forecast.clone(),
-                "met_no",
-                source_trace,
-                &item.output_context,
-            ) {
-                Ok(output) => success_entry(&item.city, output),
-                Err(error) => error_entry(&item.city, error.message),
-            },
-            Some(Err(error)) => fallback_or_error(item, open_meteo_error, error),
-            None => fallback_or_error_missing(item, open_meteo_error),
-        };
-        entries[entry_index] = Some(entry);
+        remaining = unresolved;
     }
-}
 
-fn fallback_or_error(
-    item: PendingForecast,
-    open_meteo_error: &str,
-    met_no_error: &crate::providers::ProviderError,
-) -> ForecastBatchEntry {
-    let trace = vec![
-        open_meteo_error.to_string(),
-        format!("met_no: {met_no_error}"),
-    ];
-
-    match item.cached_state {
-        Some((record, age_secs, false)) => success_entry(
-            &item.city,
-            build_output_from_record(
-                &record,
-                &item.location,
-                FreshnessStatus::CacheStaleFallback,
-                age_secs,
-                &item.output_context,
-            ),
-        ),
-        _ => error_entry(
-            &item.city,
-            AppError::runtime_with_trace("failed to fetch forecast from providers", &trace).message,
-        ),
+    for (item, trace) in remaining {
+        let index = item.index;
+        entries[index] = Some(fallback_or_error(item, &trace));
     }
-}
 
-fn fallback_or_error_missing(item: PendingForecast, open_meteo_error: &str) -> ForecastBatchEntry {
-    let trace = vec![
-        open_meteo_error.to_string(),
-        "met_no: missing batch result".to_string(),
-    ];
+    Ok(())
+}
 
+fn fallback_or_error(item: PendingForecast, trace: &[String]) -> ForecastBatchEntry {
     match item.cached_state {
         Some((record, age_secs, false)) => success_entry(
             &item.city,
@@ -282,12 +228,13 @@ fn fallback_or_error_missing(item: PendingForecast, open_meteo_error: &str) -> F
                 &item.location,
                 FreshnessStatus::CacheStaleFallback,
                 age_secs,
+                ProviderAttempt::from_trace(trace, None),
                 &item.output_context,
             ),
         ),
         _ => error_entry(
             &item.city,
-            AppError::runtime_with_trace("failed to fetch forecast from providers", &trace).message,
+            AppError::runtime_with_trace("failed to fetch forecast from providers", trace).message,
         ),
     }
 }
@@ -324,11 +271,13 @@ fn build_live_output(
 
     write_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
 
+    let attempts = ProviderAttempt::from_trace(&record.source_trace, Some(source));
     Ok(build_output_from_record(
         &record,
         location,
         FreshnessStatus::Live,
         0,
+        attempts,
         output_context,
     ))
 }
@@ -338,6 +287,7 @@ fn build_output_from_record(
     location: &ResolvedLocation,
     freshness_status: FreshnessStatus,
     age_secs: u64,
+    attempts: Vec<ProviderAttempt>,
     output_context: &OutputContext,
 ) -> ForecastOutput {
     let fetched_at = parse_fetched_at(record)
@@ -357,19 +307,33 @@ fn build_output_from_record(
             key: output_context.cache_key.clone(),
             ttl_secs: output_context.ttl_secs,
             age_secs,
+            provider: Some(record.source.clone()),
+            attempts,
         },
     }
 }
 
 fn normalize_days(days: Vec<ProviderForecastDay>) -> Vec<ForecastDay> {
     days.into_iter()
-        .map(|item| ForecastDay {
-            date: item.date,
-            weather_code: item.weather_code,
-            summary_zh: weather_code::summary_zh(item.weather_code).to_string(),
-            temp_min_c: round1(item.temp_min_c),
-            temp_max_c: round1(item.temp_max_c),
-            precip_prob_max_pct: item.precip_prob_max_pct.min(100),
+        .map(|item| {
+            let moon = astronomy::moon_for_date(&item.date);
+            ForecastDay {
+                date: item.date,
+                weather_code: item.weather_code,
+                summary_zh: weather_code::summary_zh(item.weather_code).to_string(),
+                temp_min_c: round1(item.temp_min_c),
+                temp_max_c: round1(item.temp_max_c),
+                precip_prob_max_pct: item.precip_prob_max_pct.min(100),
+                precip_sum_mm: item.precip_sum_mm.map(round1),
+                sunrise: item.sunrise,
+                sunset: item.sunset,
+                moon_phase: moon.map(|(phase, _)| phase),
+                moon_illumination_pct: moon.map(|(_, pct)| pct),
+                wind_speed_max_kmh: item.wind_speed_max_kmh.map(round1),
+                wind_direction_deg: item.wind_direction_deg,
+                humidity_mean_pct: item.humidity_mean_pct.map(|pct| pct.min(100)),
+                uv_index_max: item.uv_index_max.map(round1),
+            }
         })
         .collect()
 }
@@ -419,7 +383,10 @@ mod tests {
     use chrono::TimeZone;
 
     use super::*;
-    use crate::providers::{ProviderError, ProviderForecast};
+    use crate::providers::{
+        ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderHistory, ProviderNowcast,
+    };
+    use crate::units::UnitSystem;
 
     struct BatchProviders {
         geocode_calls: RefCell<Vec<String>>,
@@ -460,6 +427,13 @@ mod tests {
                     temp_min_c: min,
                     temp_max_c: max,
                     precip_prob_max_pct: precip,
+                    precip_sum_mm: None,
+                    sunrise: None,
+                    sunset: None,
+                    wind_speed_max_kmh: None,
+                    wind_direction_deg: None,
+                    humidity_mean_pct: None,
+                    uv_index_max: None,
                 }],
             };
 
@@ -536,6 +510,32 @@ mod tests {
             Err(ProviderError::Transport("unused".to_string()))
         }
 
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
         fn fetch_met_no_forecast(
             &self,
             _lat: f64,
@@ -563,12 +563,35 @@ mod tests {
                 })
                 .collect()
         }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
     }
 
     fn config_in_tempdir() -> RuntimeConfig {
         RuntimeConfig {
             cache_dir: tempfile::tempdir().expect("tempdir").path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         }
     }
 
@@ -641,6 +664,15 @@ mod tests {
                 temp_min_c: 9.9,
                 temp_max_c: 15.2,
                 precip_prob_max_pct: 30,
+                precip_sum_mm: None,
+                sunrise: None,
+                sunset: None,
+                moon_phase: None,
+                moon_illumination_pct: None,
+                wind_speed_max_kmh: None,
+                wind_direction_deg: None,
+                humidity_mean_pct: None,
+                uv_index_max: None,
             }],
             source: "open_meteo".to_string(),
             source_trace: Vec::new(),
@@ -110,6 +110,15 @@ mod tests {
                 temp_min_c: 14.0,
                 temp_max_c: 20.0,
                 precip_prob_max_pct: 20,
+                precip_sum_mm: None,
+                sunrise: None,
+                sunset: None,
+                moon_phase: None,
+                moon_illumination_pct: None,
+                wind_speed_max_kmh: None,
+                wind_direction_deg: None,
+                humidity_mean_pct: None,
+                uv_index_max: None,
             }],
             source: "open_meteo".to_string(),
             source_trace: Vec::new(),
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+
+use crate::batch_service;
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::model::{
+    CompareDay, CompareOutput, ForecastBatchEntry, ForecastDay, ForecastOutput, ForecastPeriod,
+};
+use crate::providers::ProviderApi;
+
+/// Fetch today's forecast for both cities as one batch, so geocoding and provider requests run concurrently, then
+/// pair the days by position (each city's own local date). Either city failing fails the comparison.
+pub fn resolve_comparison<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    left: &str,
+    right: &str,
+) -> Result<CompareOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let cities = [left.to_string(), right.to_string()];
+    let batch = batch_service::resolve_forecast_batch(
+        config,
+        providers,
+        now_fn,
+        ForecastPeriod::Today,
+        &cities,
+    )?;
+
+    let mut entries = batch.entries.into_iter();
+    let (Some(left), Some(right), None) = (entries.next(), entries.next(), entries.next()) else {
+        return Err(AppError::user("compare needs two different cities"));
+    };
+    let left = entry_output(left)?;
+    let right = entry_output(right)?;
+    let days = compare_days(&left.forecast, &right.forecast);
+
+    Ok(CompareOutput { left, right, days })
+}
+
+fn entry_output(entry: ForecastBatchEntry) -> Result<ForecastOutput, AppError> {
+    match entry.result {
+        Some(output) => Ok(output),
+        None => Err(AppError::runtime(format!(
+            "{}: {}",
+            entry.city,
+            entry.error.as_deref().unwrap_or("no forecast")
+        ))),
+    }
+}
+
+fn compare_days(left: &[ForecastDay], right: &[ForecastDay]) -> Vec<CompareDay> {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| CompareDay {
+            date: left.date.clone(),
+            temp_min_delta_c: round1(right.temp_min_c - left.temp_min_c),
+            temp_max_delta_c: round1(right.temp_max_c - left.temp_max_c),
+            precip_prob_delta_pct: i16::from(right.precip_prob_max_pct)
+                - i16::from(left.precip_prob_max_pct),
+        })
+        .collect()
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, min: f64, max: f64, precip: u8) -> ForecastDay {
+        ForecastDay {
+            date: date.to_string(),
+            weather_code: 3,
+            summary_zh: "陰天".to_string(),
+            temp_min_c: min,
+            temp_max_c: max,
+            precip_prob_max_pct: precip,
+            precip_sum_mm: None,
+            sunrise: None,
+            sunset: None,
+            moon_phase: None,
+            moon_illumination_pct: None,
+            wind_speed_max_kmh: None,
+            wind_direction_deg: None,
+            humidity_mean_pct: None,
+            uv_index_max: None,
+        }
+    }
+
+    #[test]
+    fn compare_days_subtracts_left_from_right_by_position() {
+        let left = [day("2026-02-11", 14.5, 20.1, 20)];
+        let right = [
+            day("2026-02-12", 5.2, 12.6, 10),
+            day("2026-02-13", 1.0, 2.0, 90),
+        ];
+
+        assert_eq!(
+            compare_days(&left, &right),
+            vec![CompareDay {
+                date: "2026-02-11".to_string(),
+                temp_min_delta_c: -9.3,
+                temp_max_delta_c: -7.5,
+                precip_prob_delta_pct: -10,
+            }]
+        );
+    }
+}
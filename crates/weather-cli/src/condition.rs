@@ -0,0 +1,439 @@
+use crate::model::{ForecastDay, ValidationError};
+use crate::units::UnitSystem;
+
+const METRIC_NAMES: &str = "rain, precip, tmax, tmin, wind, humidity, uv";
+
+/// Forecast field named in a `notify --when` condition. Temperature, wind, and precipitation compare in the active
+/// unit system, so `tmax>95` means °F under `WEATHER_UNITS=imperial`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Maximum precipitation probability, percent.
+    Rain,
+    /// Precipitation sum, mm or inches.
+    Precip,
+    Tmax,
+    Tmin,
+    /// Maximum wind speed, km/h or mph.
+    Wind,
+    /// Mean relative humidity, percent.
+    Humidity,
+    Uv,
+}
+
+impl Metric {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "rain" => Some(Self::Rain),
+            "precip" => Some(Self::Precip),
+            "tmax" => Some(Self::Tmax),
+            "tmin" => Some(Self::Tmin),
+            "wind" => Some(Self::Wind),
+            "humidity" => Some(Self::Humidity),
+            "uv" => Some(Self::Uv),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rain => "rain",
+            Self::Precip => "precip",
+            Self::Tmax => "tmax",
+            Self::Tmin => "tmin",
+            Self::Wind => "wind",
+            Self::Humidity => "humidity",
+            Self::Uv => "uv",
+        }
+    }
+
+    /// The day's value in `units`; `None` when the provider did not report the field.
+    pub fn value(self, day: &ForecastDay, units: UnitSystem) -> Option<f64> {
+        match self {
+            Self::Rain => Some(f64::from(day.precip_prob_max_pct)),
+            Self::Precip => day.precip_sum_mm.map(|mm| units.length(mm)),
+            Self::Tmax => Some(units.temperature(day.temp_max_c)),
+            Self::Tmin => Some(units.temperature(day.temp_min_c)),
+            Self::Wind => day.wind_speed_max_kmh.map(|kmh| units.speed(kmh)),
+            Self::Humidity => day.humidity_mean_pct.map(f64::from),
+            Self::Uv => day.uv_index_max,
+        }
+    }
+
+    /// `name value` with its unit, e.g. `rain 80%` or `tmax 36.2°C`.
+    pub fn label(self, day: &ForecastDay, units: UnitSystem) -> Option<String> {
+        let value = match self {
+            Self::Rain => format!("{}%", day.precip_prob_max_pct),
+            Self::Precip => units.length_label(day.precip_sum_mm?),
+            Self::Tmax => format!(
+                "{}{}",
+                units.temperature_label(day.temp_max_c),
+                units.temperature_unit()
+            ),
+            Self::Tmin => format!(
+                "{}{}",
+                units.temperature_label(day.temp_min_c),
+                units.temperature_unit()
+            ),
+            Self::Wind => units.speed_label(day.wind_speed_max_kmh?),
+            Self::Humidity => format!("{}%", day.humidity_mean_pct?),
+            Self::Uv => format!("{:.1}", day.uv_index_max?),
+        };
+        Some(format!("{} {value}", self.name()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::Greater => value > threshold,
+            Self::GreaterOrEqual => value >= threshold,
+            Self::Less => value < threshold,
+            Self::LessOrEqual => value <= threshold,
+            Self::Equal => value == threshold,
+            Self::NotEqual => value != threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare {
+        metric: Metric,
+        comparison: Comparison,
+        threshold: f64,
+    },
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// A parsed `notify --when` expression such as `rain>60 or tmax>35`: comparisons of a metric against a number,
+/// combined with `and` / `or` (`and` binds tighter) and parentheses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+impl Condition {
+    pub fn parse(raw: &str) -> Result<Self, ValidationError> {
+        let source = raw.trim();
+        let invalid = |reason: String| ValidationError::InvalidCondition {
+            condition: source.to_string(),
+            reason,
+        };
+        let tokens = tokenize(source).map_err(invalid)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let expr = parser.or_expr().map_err(invalid)?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected {}", token.describe())));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            expr,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// A comparison on a field the provider did not report is false.
+    pub fn evaluate(&self, day: &ForecastDay, units: UnitSystem) -> bool {
+        evaluate(&self.expr, day, units)
+    }
+
+    /// Metrics named in the condition, in first-mention order without repeats.
+    pub fn metrics(&self) -> Vec<Metric> {
+        let mut metrics = Vec::new();
+        collect_metrics(&self.expr, &mut metrics);
+        metrics
+    }
+}
+
+fn evaluate(expr: &Expr, day: &ForecastDay, units: UnitSystem) -> bool {
+    match expr {
+        Expr::Compare {
+            metric,
+            comparison,
+            threshold,
+        } => metric
+            .value(day, units)
+            .is_some_and(|value| comparison.holds(value, *threshold)),
+        Expr::And(left, right) => evaluate(left, day, units) && evaluate(right, day, units),
+        Expr::Or(left, right) => evaluate(left, day, units) || evaluate(right, day, units),
+    }
+}
+
+fn collect_metrics(expr: &Expr, metrics: &mut Vec<Metric>) {
+    match expr {
+        Expr::Compare { metric, .. } => {
+            if !metrics.contains(metric) {
+                metrics.push(*metric);
+            }
+        }
+        Expr::And(left, right) | Expr::Or(left, right) => {
+            collect_metrics(left, metrics);
+            collect_metrics(right, metrics);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Name(String),
+    Number(f64),
+    Comparison(Comparison),
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Name(name) => format!("'{name}'"),
+            Self::Number(number) => format!("number {number}"),
+            Self::Comparison(_) => "comparison operator".to_string(),
+            Self::And => "'and'".to_string(),
+            Self::Or => "'or'".to_string(),
+            Self::Open => "'('".to_string(),
+            Self::Close => "')'".to_string(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while let Some(&ch) = chars.get(index) {
+        let next = chars.get(index + 1).copied();
+        let (token, width) = match (ch, next) {
+            (ch, _) if ch.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('>', Some('=')) => (Token::Comparison(Comparison::GreaterOrEqual), 2),
+            ('>', _) => (Token::Comparison(Comparison::Greater), 1),
+            ('<', Some('=')) => (Token::Comparison(Comparison::LessOrEqual), 2),
+            ('<', _) => (Token::Comparison(Comparison::Less), 1),
+            ('=', Some('=')) => (Token::Comparison(Comparison::Equal), 2),
+            ('=', _) => (Token::Comparison(Comparison::Equal), 1),
+            ('!', Some('=')) => (Token::Comparison(Comparison::NotEqual), 2),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            (ch, _) if ch.is_ascii_digit() || ch == '-' || ch == '.' => {
+                let width = 1 + chars[index + 1..]
+                    .iter()
+                    .take_while(|ch| ch.is_ascii_digit() || **ch == '.')
+                    .count();
+                let raw = chars[index..index + width].iter().collect::<String>();
+                let number = raw
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number '{raw}'"))?;
+                (Token::Number(number), width)
+            }
+            (ch, _) if ch.is_ascii_alphabetic() => {
+                let width = chars[index..]
+                    .iter()
+                    .take_while(|ch| ch.is_ascii_alphanumeric() || **ch == '_')
+                    .count();
+                let name = chars[index..index + width]
+                    .iter()
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+                let token = match name.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    _ => Token::Name(name),
+                };
+                (token, width)
+            }
+            (ch, _) => return Err(format!("unexpected character '{ch}'")),
+        };
+        tokens.push(token);
+        index += width;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and_expr()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and_expr()?));
+        }
+        Ok(expr)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.primary()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.primary()?));
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Open) => {
+                let expr = self.or_expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    Some(token) => Err(format!("expected ')' but found {}", token.describe())),
+                    None => Err("missing ')'".to_string()),
+                }
+            }
+            Some(Token::Name(name)) => {
+                let metric = Metric::parse(&name)
+                    .ok_or_else(|| format!("unknown field '{name}' (use {METRIC_NAMES})"))?;
+                let comparison = match self.next() {
+                    Some(Token::Comparison(comparison)) => comparison,
+                    _ => return Err(format!("expected a comparison after '{name}'")),
+                };
+                let threshold = match self.next() {
+                    Some(Token::Number(number)) => number,
+                    _ => return Err(format!("expected a number after '{name}' comparison")),
+                };
+                Ok(Expr::Compare {
+                    metric,
+                    comparison,
+                    threshold,
+                })
+            }
+            Some(token) => Err(format!(
+                "expected a field such as rain or tmax but found {}",
+                token.describe()
+            )),
+            None => Err("expected a field such as rain or tmax".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day() -> ForecastDay {
+        ForecastDay {
+            date: "2026-02-11".to_string(),
+            weather_code: 61,
+            summary_zh: "小雨".to_string(),
+            temp_min_c: 24.0,
+            temp_max_c: 36.2,
+            precip_prob_max_pct: 80,
+            precip_sum_mm: Some(12.5),
+            sunrise: None,
+            sunset: None,
+            moon_phase: None,
+            moon_illumination_pct: None,
+            wind_speed_max_kmh: None,
+            wind_direction_deg: None,
+            humidity_mean_pct: Some(70),
+            uv_index_max: Some(9.1),
+        }
+    }
+
+    fn holds(raw: &str) -> bool {
+        Condition::parse(raw)
+            .expect("condition")
+            .evaluate(&day(), UnitSystem::Metric)
+    }
+
+    #[test]
+    fn condition_evaluates_comparisons_with_and_binding_tighter_than_or() {
+        assert!(holds("rain>60 or tmax>35"));
+        assert!(holds("rain >= 80"));
+        assert!(!holds("rain>80"));
+        assert!(holds("tmin<30 && humidity==70"));
+        assert!(!holds("tmin != 24"));
+        assert!(holds("uv>9 AND precip>10.5"));
+        // `a or b and c` is `a or (b and c)`.
+        assert!(holds("rain>60 or tmax>40 and tmin<0"));
+        assert!(!holds("(rain>60 or tmax>40) and tmin<0"));
+        assert!(holds("tmin>-5"));
+    }
+
+    #[test]
+    fn condition_fields_the_provider_omitted_never_match() {
+        assert!(!holds("wind>10"));
+        assert!(!holds("wind<10"));
+        assert!(holds("wind>10 or rain>50"));
+    }
+
+    #[test]
+    fn condition_compares_in_the_active_unit_system() {
+        let condition = Condition::parse("tmax>95 and precip>0.4").expect("condition");
+        assert!(condition.evaluate(&day(), UnitSystem::Imperial));
+        assert!(!condition.evaluate(&day(), UnitSystem::Metric));
+        assert_eq!(condition.metrics(), vec![Metric::Tmax, Metric::Precip]);
+        assert_eq!(
+            Metric::Tmax.label(&day(), UnitSystem::Imperial).as_deref(),
+            Some("tmax 97.2°F")
+        );
+        assert_eq!(Metric::Wind.label(&day(), UnitSystem::Metric), None);
+    }
+
+    #[test]
+    fn condition_parse_reports_what_is_wrong() {
+        let reason = |raw: &str| match Condition::parse(raw) {
+            Err(ValidationError::InvalidCondition { reason, .. }) => reason,
+            other => panic!("expected invalid condition for {raw:?}, got {other:?}"),
+        };
+
+        assert_eq!(
+            reason("temp>30"),
+            "unknown field 'temp' (use rain, precip, tmax, tmin, wind, humidity, uv)"
+        );
+        assert_eq!(reason("rain 60"), "expected a comparison after 'rain'");
+        assert_eq!(reason("rain>"), "expected a number after 'rain' comparison");
+        assert_eq!(reason("(rain>60"), "missing ')'");
+        assert_eq!(reason("rain>60 tmax>35"), "unexpected 'tmax'");
+        assert_eq!(
+            reason("rain>60 or"),
+            "expected a field such as rain or tmax"
+        );
+        assert_eq!(reason("rain>6%"), "unexpected character '%'");
+        assert_eq!(reason(""), "expected a field such as rain or tmax");
+        assert_eq!(
+            Condition::parse(" rain>60 ").expect("condition").source(),
+            "rain>60"
+        );
+    }
+}
@@ -1,10 +1,35 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::favorites::{FavoriteLocation, parse_favorite_locations};
+use crate::locale::Locale;
+use crate::providers::ForecastProvider;
+use crate::units::UnitSystem;
+
 pub const WEATHER_CACHE_TTL_SECS: u64 = 30 * 60;
+/// Upper bound for the `now` cache; current conditions go stale faster than forecasts.
+pub const WEATHER_CURRENT_CACHE_TTL_SECS: u64 = 10 * 60;
+/// IP geolocation rarely changes within a session, so it is cached much longer than forecasts.
+pub const WEATHER_AUTO_LOCATE_TTL_SECS: u64 = 6 * 60 * 60;
+/// Past-date observations only change while the reanalysis catches up, so they are kept for a week.
+pub const WEATHER_HISTORY_CACHE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
 
 pub const WEATHER_CACHE_DIR_ENV: &str = "WEATHER_CACHE_DIR";
 pub const WEATHER_CACHE_TTL_SECS_ENV: &str = "WEATHER_CACHE_TTL_SECS";
+pub const WEATHER_UNITS_ENV: &str = "WEATHER_UNITS";
+pub const WEATHER_DETAIL_ENV: &str = "WEATHER_DETAIL";
+pub const WEATHER_LANG_ENV: &str = "WEATHER_LANG";
+pub const WEATHER_OFFLINE_ENV: &str = "WEATHER_OFFLINE";
+pub const WEATHER_LOCATIONS_ENV: &str = "WEATHER_LOCATIONS";
+pub const WEATHER_AUTO_LOCATE_ENV: &str = "WEATHER_AUTO_LOCATE";
+pub const WEATHER_AUTO_LOCATE_TTL_SECS_ENV: &str = "WEATHER_AUTO_LOCATE_TTL_SECS";
+pub const WEATHER_PROVIDER_ORDER_ENV: &str = "WEATHER_PROVIDER_ORDER";
+pub const WEATHER_PROVIDER_ENV: &str = "WEATHER_PROVIDER";
+pub const WEATHER_PROVIDER_TIMEOUT_SECS_ENV: &str = "WEATHER_PROVIDER_TIMEOUT_SECS";
+pub const WEATHER_OPEN_METEO_TIMEOUT_SECS_ENV: &str = "WEATHER_OPEN_METEO_TIMEOUT_SECS";
+pub const WEATHER_MET_NO_TIMEOUT_SECS_ENV: &str = "WEATHER_MET_NO_TIMEOUT_SECS";
+pub const WEATHER_WTTR_TIMEOUT_SECS_ENV: &str = "WEATHER_WTTR_TIMEOUT_SECS";
+pub const WEATHER_PROVIDER_MAX_ATTEMPTS_ENV: &str = "WEATHER_PROVIDER_MAX_ATTEMPTS";
 const ALFRED_WORKFLOW_CACHE_ENV: &str = "ALFRED_WORKFLOW_CACHE";
 const ALFRED_WORKFLOW_DATA_ENV: &str = "ALFRED_WORKFLOW_DATA";
 const HOME_ENV: &str = "HOME";
@@ -12,6 +37,8 @@ const HOME_ENV: &str = "HOME";
 pub const PROVIDER_TIMEOUT_SECS: u64 = 3;
 pub const PROVIDER_RETRY_MAX_ATTEMPTS: usize = 2;
 pub const PROVIDER_RETRY_BASE_BACKOFF_MS: u64 = 200;
+/// Upper bound for `WEATHER_PROVIDER_MAX_ATTEMPTS`, so a misconfigured value cannot stall Alfred.
+pub const PROVIDER_RETRY_MAX_ATTEMPTS_LIMIT: usize = 5;
 pub const MET_NO_USER_AGENT: &str =
     "nils-alfredworkflow/1.0 (+https://github.com/sympoies/nils-alfredworkflow)";
 
@@ -19,6 +46,53 @@ pub const MET_NO_USER_AGENT: &str =
 pub struct RuntimeConfig {
     pub cache_dir: PathBuf,
     pub cache_ttl_secs: u64,
+    pub units: UnitSystem,
+    /// `WEATHER_DETAIL=extended`: daily human and Alfred rows also show wind, humidity and UV index.
+    pub extended_detail: bool,
+    /// Label language from `WEATHER_LANG`; `--lang` overrides it per invocation.
+    pub language: Locale,
+    /// `WEATHER_OFFLINE=1` (or `--offline`): serve cached data only and never touch the network.
+    pub offline: bool,
+    pub favorite_locations: Vec<FavoriteLocation>,
+    /// Cache TTL for IP geolocation; `Some` only when `WEATHER_AUTO_LOCATE` opts in.
+    pub auto_locate_ttl_secs: Option<u64>,
+    /// Daily forecast fallback chain from `WEATHER_PROVIDER_ORDER`; empty means the default order.
+    pub provider_order: Vec<ForecastProvider>,
+    /// `WEATHER_PROVIDER`: use only this daily provider, ignoring `provider_order`.
+    pub forced_provider: Option<ForecastProvider>,
+    pub provider_timeouts: ProviderTimeouts,
+    pub retry_policy: RetryPolicy,
+}
+
+/// Request timeouts in seconds. `WEATHER_PROVIDER_TIMEOUT_SECS` sets all of them and `WEATHER_<PROVIDER>_TIMEOUT_SECS`
+/// overrides one provider; geocoding shares the Open-Meteo timeout and IP geolocation uses the base value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderTimeouts {
+    pub base_secs: u64,
+    pub open_meteo_secs: u64,
+    pub met_no_secs: u64,
+    pub wttr_secs: u64,
+}
+
+impl Default for ProviderTimeouts {
+    fn default() -> Self {
+        Self {
+            base_secs: PROVIDER_TIMEOUT_SECS,
+            open_meteo_secs: PROVIDER_TIMEOUT_SECS,
+            met_no_secs: PROVIDER_TIMEOUT_SECS,
+            wttr_secs: PROVIDER_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl ProviderTimeouts {
+    pub fn for_provider(self, provider: ForecastProvider) -> u64 {
+        match provider {
+            ForecastProvider::OpenMeteo => self.open_meteo_secs,
+            ForecastProvider::MetNo => self.met_no_secs,
+            ForecastProvider::Wttr => self.wttr_secs,
+        }
+    }
 }
 
 impl RuntimeConfig {
@@ -39,7 +113,46 @@ impl RuntimeConfig {
         Self {
             cache_dir: resolve_cache_dir(&map),
             cache_ttl_secs: resolve_cache_ttl_secs(&map),
+            units: resolve_units(&map),
+            extended_detail: map
+                .get(WEATHER_DETAIL_ENV)
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("extended")),
+            language: map
+                .get(WEATHER_LANG_ENV)
+                .and_then(|value| Locale::parse(value))
+                .unwrap_or_default(),
+            offline: env_flag(&map, WEATHER_OFFLINE_ENV),
+            favorite_locations: map
+                .get(WEATHER_LOCATIONS_ENV)
+                .map(|value| parse_favorite_locations(value))
+                .unwrap_or_default(),
+            auto_locate_ttl_secs: resolve_auto_locate_ttl_secs(&map),
+            provider_order: map
+                .get(WEATHER_PROVIDER_ORDER_ENV)
+                .map(|value| ForecastProvider::parse_order(value))
+                .unwrap_or_default(),
+            forced_provider: map
+                .get(WEATHER_PROVIDER_ENV)
+                .and_then(|value| ForecastProvider::parse(value)),
+            provider_timeouts: resolve_provider_timeouts(&map),
+            retry_policy: resolve_retry_policy(&map),
+        }
+    }
+
+    /// TTL for current-conditions cache entries: the forecast TTL, capped at `WEATHER_CURRENT_CACHE_TTL_SECS`.
+    pub fn current_cache_ttl_secs(&self) -> u64 {
+        self.cache_ttl_secs.min(WEATHER_CURRENT_CACHE_TTL_SECS)
+    }
+
+    /// Providers tried in order for daily forecasts.
+    pub fn forecast_provider_order(&self) -> Vec<ForecastProvider> {
+        if let Some(provider) = self.forced_provider {
+            return vec![provider];
+        }
+        if self.provider_order.is_empty() {
+            return ForecastProvider::DEFAULT_ORDER.to_vec();
         }
+        self.provider_order.clone()
     }
 }
 
@@ -86,6 +199,68 @@ fn resolve_cache_ttl_secs(env_map: &HashMap<String, String>) -> u64 {
         .unwrap_or(WEATHER_CACHE_TTL_SECS)
 }
 
+fn resolve_units(env_map: &HashMap<String, String>) -> UnitSystem {
+    env_map
+        .get(WEATHER_UNITS_ENV)
+        .and_then(|value| UnitSystem::parse(value))
+        .unwrap_or_default()
+}
+
+fn resolve_auto_locate_ttl_secs(env_map: &HashMap<String, String>) -> Option<u64> {
+    if !env_flag(env_map, WEATHER_AUTO_LOCATE_ENV) {
+        return None;
+    }
+
+    let ttl_secs = env_map
+        .get(WEATHER_AUTO_LOCATE_TTL_SECS_ENV)
+        .map(String::as_str)
+        .map(str::trim)
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(WEATHER_AUTO_LOCATE_TTL_SECS);
+    Some(ttl_secs)
+}
+
+/// Opt-in switches accept `1`, `true`, `yes`, or `on` (case-insensitive).
+fn env_flag(env_map: &HashMap<String, String>, key: &str) -> bool {
+    env_map
+        .get(key)
+        .map(|value| value.trim().to_ascii_lowercase())
+        .is_some_and(|value| matches!(value.as_str(), "1" | "true" | "yes" | "on"))
+}
+
+fn positive_u64(env_map: &HashMap<String, String>, key: &str) -> Option<u64> {
+    env_map
+        .get(key)
+        .map(String::as_str)
+        .map(str::trim)
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|value| *value > 0)
+}
+
+fn resolve_provider_timeouts(env_map: &HashMap<String, String>) -> ProviderTimeouts {
+    let base_secs =
+        positive_u64(env_map, WEATHER_PROVIDER_TIMEOUT_SECS_ENV).unwrap_or(PROVIDER_TIMEOUT_SECS);
+    ProviderTimeouts {
+        base_secs,
+        open_meteo_secs: positive_u64(env_map, WEATHER_OPEN_METEO_TIMEOUT_SECS_ENV)
+            .unwrap_or(base_secs),
+        met_no_secs: positive_u64(env_map, WEATHER_MET_NO_TIMEOUT_SECS_ENV).unwrap_or(base_secs),
+        wttr_secs: positive_u64(env_map, WEATHER_WTTR_TIMEOUT_SECS_ENV).unwrap_or(base_secs),
+    }
+}
+
+fn resolve_retry_policy(env_map: &HashMap<String, String>) -> RetryPolicy {
+    let max_attempts = positive_u64(env_map, WEATHER_PROVIDER_MAX_ATTEMPTS_ENV)
+        .and_then(|value| usize::try_from(value).ok())
+        .map(|value| value.min(PROVIDER_RETRY_MAX_ATTEMPTS_LIMIT))
+        .unwrap_or(PROVIDER_RETRY_MAX_ATTEMPTS);
+    RetryPolicy {
+        max_attempts,
+        ..RetryPolicy::default()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RetryPolicy {
     pub max_attempts: usize,
@@ -121,6 +296,144 @@ mod tests {
         let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
         assert!(config.cache_dir.ends_with("nils-weather-cli"));
         assert_eq!(config.cache_ttl_secs, WEATHER_CACHE_TTL_SECS);
+        assert_eq!(config.units, UnitSystem::Metric);
+    }
+
+    #[test]
+    fn config_reads_units_and_ignores_unknown_values() {
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_UNITS_ENV, "Imperial")]);
+        assert_eq!(config.units, UnitSystem::Imperial);
+
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_UNITS_ENV, "kelvin")]);
+        assert_eq!(config.units, UnitSystem::Metric);
+    }
+
+    #[test]
+    fn config_language_reads_weather_lang_and_defaults_to_english() {
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_LANG_ENV, "de-CH")]);
+        assert_eq!(config.language.language(), "de");
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_LANG_ENV, "klingon")]);
+        assert_eq!(config.language, Locale::default());
+    }
+
+    #[test]
+    fn config_offline_is_opt_in() {
+        assert!(!RuntimeConfig::from_pairs(Vec::<(String, String)>::new()).offline);
+        assert!(RuntimeConfig::from_pairs(vec![(WEATHER_OFFLINE_ENV, " On ")]).offline);
+        assert!(!RuntimeConfig::from_pairs(vec![(WEATHER_OFFLINE_ENV, "0")]).offline);
+    }
+
+    #[test]
+    fn config_extended_detail_is_opt_in() {
+        assert!(!RuntimeConfig::from_pairs(Vec::<(String, String)>::new()).extended_detail);
+        assert!(
+            RuntimeConfig::from_pairs(vec![(WEATHER_DETAIL_ENV, " Extended ")]).extended_detail
+        );
+        assert!(!RuntimeConfig::from_pairs(vec![(WEATHER_DETAIL_ENV, "basic")]).extended_detail);
+    }
+
+    #[test]
+    fn config_reads_favorite_locations() {
+        let config =
+            RuntimeConfig::from_pairs(vec![(WEATHER_LOCATIONS_ENV, "home=Taipei;office=Hsinchu")]);
+        assert_eq!(
+            config
+                .favorite_locations
+                .iter()
+                .map(|favorite| favorite.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["home", "office"]
+        );
+
+        let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
+        assert!(config.favorite_locations.is_empty());
+    }
+
+    #[test]
+    fn config_auto_locate_is_opt_in_with_ttl_override() {
+        let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
+        assert_eq!(config.auto_locate_ttl_secs, None);
+
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_AUTO_LOCATE_ENV, "0")]);
+        assert_eq!(config.auto_locate_ttl_secs, None);
+
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_AUTO_LOCATE_ENV, "1")]);
+        assert_eq!(
+            config.auto_locate_ttl_secs,
+            Some(WEATHER_AUTO_LOCATE_TTL_SECS)
+        );
+
+        let config = RuntimeConfig::from_pairs(vec![
+            (WEATHER_AUTO_LOCATE_ENV, "true"),
+            (WEATHER_AUTO_LOCATE_TTL_SECS_ENV, "3600"),
+        ]);
+        assert_eq!(config.auto_locate_ttl_secs, Some(3600));
+    }
+
+    #[test]
+    fn config_reads_provider_order_and_defaults_when_empty() {
+        let config =
+            RuntimeConfig::from_pairs(vec![(WEATHER_PROVIDER_ORDER_ENV, "wttr,open_meteo")]);
+        assert_eq!(
+            config.forecast_provider_order(),
+            vec![ForecastProvider::Wttr, ForecastProvider::OpenMeteo]
+        );
+
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_PROVIDER_ORDER_ENV, "nws")]);
+        assert_eq!(
+            config.forecast_provider_order(),
+            ForecastProvider::DEFAULT_ORDER.to_vec()
+        );
+    }
+
+    #[test]
+    fn config_forced_provider_overrides_order() {
+        let config = RuntimeConfig::from_pairs(vec![
+            (WEATHER_PROVIDER_ORDER_ENV, "wttr,open_meteo"),
+            (WEATHER_PROVIDER_ENV, "met_no"),
+        ]);
+        assert_eq!(
+            config.forecast_provider_order(),
+            vec![ForecastProvider::MetNo]
+        );
+    }
+
+    #[test]
+    fn config_reads_provider_timeouts_and_attempts() {
+        let config = RuntimeConfig::from_pairs(vec![
+            (WEATHER_PROVIDER_TIMEOUT_SECS_ENV, "5"),
+            (WEATHER_MET_NO_TIMEOUT_SECS_ENV, "8"),
+            (WEATHER_WTTR_TIMEOUT_SECS_ENV, "0"),
+            (WEATHER_PROVIDER_MAX_ATTEMPTS_ENV, "9"),
+        ]);
+
+        assert_eq!(config.provider_timeouts.base_secs, 5);
+        assert_eq!(
+            config
+                .provider_timeouts
+                .for_provider(ForecastProvider::OpenMeteo),
+            5
+        );
+        assert_eq!(
+            config
+                .provider_timeouts
+                .for_provider(ForecastProvider::MetNo),
+            8
+        );
+        assert_eq!(
+            config
+                .provider_timeouts
+                .for_provider(ForecastProvider::Wttr),
+            5
+        );
+        assert_eq!(
+            config.retry_policy.max_attempts,
+            PROVIDER_RETRY_MAX_ATTEMPTS_LIMIT
+        );
+
+        let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
+        assert_eq!(config.provider_timeouts, ProviderTimeouts::default());
+        assert_eq!(config.retry_policy, RetryPolicy::default());
     }
 
     #[test]
@@ -150,6 +463,18 @@ mod tests {
         assert_eq!(config.cache_ttl_secs, 900);
     }
 
+    #[test]
+    fn config_caps_current_cache_ttl_below_forecast_ttl() {
+        let config = RuntimeConfig::from_pairs(Vec::<(String, String)>::new());
+        assert_eq!(
+            config.current_cache_ttl_secs(),
+            WEATHER_CURRENT_CACHE_TTL_SECS
+        );
+
+        let config = RuntimeConfig::from_pairs(vec![(WEATHER_CACHE_TTL_SECS_ENV, "120")]);
+        assert_eq!(config.current_cache_ttl_secs(), 120);
+    }
+
     #[test]
     fn config_falls_back_when_cache_ttl_override_invalid() {
         let config = RuntimeConfig::from_pairs(vec![(WEATHER_CACHE_TTL_SECS_ENV, "abc")]);
@@ -0,0 +1,481 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::geocoding::{
+    ResolvedLocation, city_query_cache_key, location_from_coordinates, read_cached_city_location,
+    write_cached_city_location,
+};
+use crate::model::{
+    CacheMetadata, CurrentConditions, CurrentWeatherOutput, ForecastLocation, ForecastPeriod,
+    FreshnessStatus, LocationQuery, ProviderAttempt,
+};
+use crate::providers::{ProviderApi, ProviderCurrentWeather};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CurrentCacheRecord {
+    location: ForecastLocation,
+    timezone: String,
+    current: CurrentConditions,
+    source: String,
+    #[serde(default)]
+    source_trace: Vec<String>,
+    fetched_at: String,
+}
+
+#[derive(Debug, Clone)]
+struct OutputContext {
+    cache_key: String,
+    ttl_secs: u64,
+}
+
+pub fn resolve_current_weather<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    location_query: &LocationQuery,
+) -> Result<CurrentWeatherOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let now = now_fn();
+    let ttl_secs = config.current_cache_ttl_secs();
+
+    let (cache_key, mut resolved_location) = match location_query {
+        LocationQuery::City(city) => (city_query_cache_key(city), None),
+        _ => {
+            let location = resolve_location(config, providers, location_query)?;
+            let key = location.cache_key();
+            (key, Some(location))
+        }
+    };
+    let path = crate::cache::cache_path(&config.cache_dir, ForecastPeriod::Now, &cache_key);
+    let output_context = OutputContext {
+        cache_key: cache_key.clone(),
+        ttl_secs,
+    };
+
+    let cached = read_current_cache(&path).map_err(|error| AppError::runtime(error.to_string()))?;
+    let cached_state = cached.map(|record| {
+        let age_secs = record_age_secs(&record, now, ttl_secs);
+        (record, age_secs)
+    });
+
+    if let Some((record, age_secs)) = &cached_state
+        && *age_secs <= ttl_secs
+    {
+        return Ok(build_output_from_record(
+            record,
+            FreshnessStatus::CacheFresh,
+            *age_secs,
+            Vec::new(),
+            &output_context,
+        ));
+    }
+
+    let location = match resolved_location.take() {
+        Some(location) => location,
+        None => match cached_state.as_ref() {
+            Some((record, _)) => resolved_location_from_record(record),
+            None => resolve_location(config, providers, location_query)?,
+        },
+    };
+
+    match providers.fetch_open_meteo_current_weather(location.latitude, location.longitude) {
+        Ok(current) => build_live_output(&path, &location, current, &output_context),
+        Err(error) => {
+            let trace = vec![format!("open_meteo: {error}")];
+            match cached_state {
+                Some((mut record, age_secs)) => {
+                    let attempts = ProviderAttempt::from_trace(&trace, None);
+                    record.source_trace = trace;
+                    Ok(build_output_from_record(
+                        &record,
+                        FreshnessStatus::CacheStaleFallback,
+                        age_secs,
+                        attempts,
+                        &output_context,
+                    ))
+                }
+                None => Err(AppError::runtime_with_trace(
+                    "failed to fetch current weather from providers",
+                    &trace,
+                )),
+            }
+        }
+    }
+}
+
+fn resolve_location<P: ProviderApi>(
+    config: &RuntimeConfig,
+    providers: &P,
+    location: &LocationQuery,
+) -> Result<ResolvedLocation, AppError> {
+    match location {
+        LocationQuery::City(city) => {
+            if let Ok(Some(cached)) = read_cached_city_location(&config.cache_dir, city) {
+                return Ok(cached);
+            }
+
+            let resolved = providers.geocode_city(city).map_err(|error| {
+                AppError::runtime(format!("failed to resolve city '{city}': {error}"))
+            })?;
+
+            let _ = write_cached_city_location(&config.cache_dir, city, &resolved);
+            Ok(resolved)
+        }
+        LocationQuery::Coordinates { lat, lon } => Ok(location_from_coordinates(*lat, *lon)),
+    }
+}
+
+fn build_live_output(
+    path: &Path,
+    location: &ResolvedLocation,
+    current: ProviderCurrentWeather,
+    output_context: &OutputContext,
+) -> Result<CurrentWeatherOutput, AppError> {
+    let timezone = if current.timezone.trim().is_empty() {
+        location.timezone.clone()
+    } else {
+        current.timezone
+    };
+
+    let record = CurrentCacheRecord {
+        location: location.to_output_location(),
+        timezone,
+        current: CurrentConditions {
+            observed_at: current.observed_at,
+            weather_code: current.weather_code,
+            temp_c: round1(current.temp_c),
+            apparent_temp_c: round1(current.apparent_temp_c),
+            humidity_pct: current.humidity_pct.min(100),
+            wind_speed_kmh: round1(current.wind_speed_kmh),
+            wind_direction_deg: current.wind_direction_deg % 360,
+        },
+        source: "open_meteo".to_string(),
+        source_trace: Vec::new(),
+        fetched_at: current
+            .fetched_at
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+    };
+
+    write_current_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
+
+    let attempts = ProviderAttempt::from_trace(&record.source_trace, Some(&record.source));
+    Ok(build_output_from_record(
+        &record,
+        FreshnessStatus::Live,
+        0,
+        attempts,
+        output_context,
+    ))
+}
+
+fn build_output_from_record(
+    record: &CurrentCacheRecord,
+    status: FreshnessStatus,
+    age_secs: u64,
+    attempts: Vec<ProviderAttempt>,
+    output_context: &OutputContext,
+) -> CurrentWeatherOutput {
+    CurrentWeatherOutput {
+        location: record.location.clone(),
+        timezone: record.timezone.clone(),
+        current: record.current.clone(),
+        source: record.source.clone(),
+        source_trace: record.source_trace.clone(),
+        fetched_at: record.fetched_at.clone(),
+        freshness: CacheMetadata {
+            status,
+            key: output_context.cache_key.clone(),
+            ttl_secs: output_context.ttl_secs,
+            age_secs,
+            provider: Some(record.source.clone()),
+            attempts,
+        },
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn resolved_location_from_record(record: &CurrentCacheRecord) -> ResolvedLocation {
+    ResolvedLocation {
+        name: record.location.name.clone(),
+        latitude: record.location.latitude,
+        longitude: record.location.longitude,
+        timezone: record.timezone.clone(),
+    }
+}
+
+/// Seconds since the record was fetched; an unparsable timestamp counts as just past the TTL.
+fn record_age_secs(record: &CurrentCacheRecord, now: DateTime<Utc>, ttl_secs: u64) -> u64 {
+    DateTime::parse_from_rfc3339(&record.fetched_at)
+        .ok()
+        .map(|fetched_at| {
+            now.signed_duration_since(fetched_at.with_timezone(&Utc))
+                .num_seconds()
+                .max(0)
+                .try_into()
+                .unwrap_or(u64::MAX)
+        })
+        .unwrap_or(ttl_secs.saturating_add(1))
+}
+
+fn read_current_cache(path: &Path) -> io::Result<Option<CurrentCacheRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let payload = fs::read_to_string(path)?;
+    Ok(serde_json::from_str::<CurrentCacheRecord>(&payload).ok())
+}
+
+fn write_current_cache(path: &Path, record: &CurrentCacheRecord) -> io::Result<()> {
+    let payload = serde_json::to_vec(record)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    let parent = path.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cache path must have a parent directory",
+        )
+    })?;
+    fs::create_dir_all(parent)?;
+
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, payload)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::providers::{
+        ProviderError, ProviderForecast, ProviderHistory, ProviderHourlyForecast, ProviderNowcast,
+    };
+    use crate::units::UnitSystem;
+
+    struct FakeProviders {
+        current_result: Result<ProviderCurrentWeather, ProviderError>,
+        geocode_calls: Cell<usize>,
+        current_calls: Cell<usize>,
+    }
+
+    impl FakeProviders {
+        fn with_current(current_result: Result<ProviderCurrentWeather, ProviderError>) -> Self {
+            Self {
+                current_result,
+                geocode_calls: Cell::new(0),
+                current_calls: Cell::new(0),
+            }
+        }
+
+        fn ok() -> Self {
+            Self::with_current(Ok(ProviderCurrentWeather {
+                timezone: "Asia/Tokyo".to_string(),
+                fetched_at: fixed_now(),
+                observed_at: "2026-02-12T09:00".to_string(),
+                weather_code: 3,
+                temp_c: 4.44,
+                apparent_temp_c: 1.16,
+                humidity_pct: 65,
+                wind_speed_kmh: 12.34,
+                wind_direction_deg: 270,
+            }))
+        }
+    }
+
+    impl ProviderApi for FakeProviders {
+        fn geocode_city(&self, _city: &str) -> Result<ResolvedLocation, ProviderError> {
+            self.geocode_calls.set(self.geocode_calls.get() + 1);
+            Ok(ResolvedLocation {
+                name: "Tokyo".to_string(),
+                latitude: 35.6762,
+                longitude: 139.6503,
+                timezone: "Asia/Tokyo".to_string(),
+            })
+        }
+
+        fn fetch_open_meteo_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_hourly_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_hours: usize,
+        ) -> Result<ProviderHourlyForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            self.current_calls.set(self.current_calls.get() + 1);
+            self.current_result.clone()
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_met_no_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+    }
+
+    fn config_in(dir: &tempfile::TempDir) -> RuntimeConfig {
+        RuntimeConfig {
+            cache_dir: dir.path().to_path_buf(),
+            cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
+        }
+    }
+
+    fn fixed_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 12, 0, 5, 0)
+            .single()
+            .expect("time")
+    }
+
+    fn tokyo() -> LocationQuery {
+        LocationQuery::City("Tokyo".to_string())
+    }
+
+    #[test]
+    fn current_service_returns_rounded_live_conditions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = FakeProviders::ok();
+
+        let output = resolve_current_weather(&config_in(&dir), &providers, fixed_now, &tokyo())
+            .expect("must pass");
+
+        assert_eq!(output.freshness.status, FreshnessStatus::Live);
+        assert_eq!(output.freshness.key, "city-tokyo");
+        assert_eq!(
+            output.freshness.ttl_secs,
+            crate::config::WEATHER_CURRENT_CACHE_TTL_SECS
+        );
+        assert_eq!(output.current.temp_c, 4.4);
+        assert_eq!(output.current.apparent_temp_c, 1.2);
+        assert_eq!(output.current.wind_speed_kmh, 12.3);
+        assert_eq!(providers.current_calls.get(), 1);
+    }
+
+    #[test]
+    fn current_service_serves_cache_within_the_short_ttl_only() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = config_in(&dir);
+        let providers = FakeProviders::ok();
+        resolve_current_weather(&config, &providers, fixed_now, &tokyo()).expect("prime cache");
+
+        let within_ttl = || fixed_now() + chrono::Duration::minutes(5);
+        let output =
+            resolve_current_weather(&config, &providers, within_ttl, &tokyo()).expect("must pass");
+        assert_eq!(output.freshness.status, FreshnessStatus::CacheFresh);
+        assert_eq!(output.freshness.age_secs, 300);
+        assert_eq!(providers.current_calls.get(), 1);
+
+        // Still fresh for daily forecasts (30 minutes), but past the current-conditions TTL.
+        let past_ttl = || fixed_now() + chrono::Duration::minutes(15);
+        let output =
+            resolve_current_weather(&config, &providers, past_ttl, &tokyo()).expect("must pass");
+        assert_eq!(output.freshness.status, FreshnessStatus::Live);
+        assert_eq!(providers.current_calls.get(), 2);
+        assert_eq!(providers.geocode_calls.get(), 1);
+    }
+
+    #[test]
+    fn current_service_falls_back_to_stale_cache_when_provider_fails() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = config_in(&dir);
+        resolve_current_weather(&config, &FakeProviders::ok(), fixed_now, &tokyo())
+            .expect("prime cache");
+
+        let failing =
+            FakeProviders::with_current(Err(ProviderError::Transport("timeout".to_string())));
+        let later = || fixed_now() + chrono::Duration::hours(1);
+        let output = resolve_current_weather(&config, &failing, later, &tokyo()).expect("stale");
+
+        assert_eq!(output.freshness.status, FreshnessStatus::CacheStaleFallback);
+        assert_eq!(output.freshness.age_secs, 3600);
+        assert!(output.source_trace[0].contains("timeout"));
+    }
+
+    #[test]
+    fn current_service_reports_provider_trace_without_cache() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let failing =
+            FakeProviders::with_current(Err(ProviderError::Transport("timeout".to_string())));
+
+        let error = resolve_current_weather(&config_in(&dir), &failing, fixed_now, &tokyo())
+            .expect_err("must fail");
+
+        assert_eq!(error.exit_code(), 1);
+        assert!(
+            error
+                .message
+                .contains("open_meteo: transport error: timeout")
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::model::{ForecastDay, ValidationError};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Days kept from a fetched week: a set of weekdays and/or an inclusive `--from`/`--to` range. Filtering happens
+/// after the fetch so the cached week stays shared with unfiltered queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayWindow {
+    /// Bit `n` keeps the weekday `n` days from Monday; zero keeps every weekday.
+    weekdays: u8,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+impl DayWindow {
+    /// Build a window from the raw `--days`, `--from`, and `--to` values; `None` when none of them is set.
+    pub fn parse(
+        days: Option<&str>,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Option<Self>, ValidationError> {
+        if days.is_none() && from.is_none() && to.is_none() {
+            return Ok(None);
+        }
+
+        let weekdays = days.map(parse_weekdays).transpose()?.unwrap_or(0);
+        let from = from.map(|raw| parse_date("--from", raw)).transpose()?;
+        let to = to.map(|raw| parse_date("--to", raw)).transpose()?;
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
+        {
+            return Err(ValidationError::InvertedDateWindow { from, to });
+        }
+
+        Ok(Some(Self { weekdays, from, to }))
+    }
+
+    /// Keep the matching days. A `--from`/`--to` date outside the fetched days is rejected instead of quietly
+    /// returning a shorter list, and so is a window that keeps nothing.
+    pub fn apply(&self, days: &[ForecastDay]) -> Result<Vec<ForecastDay>, ValidationError> {
+        let dates = days
+            .iter()
+            .filter_map(|day| NaiveDate::parse_from_str(&day.date, DATE_FORMAT).ok())
+            .collect::<Vec<_>>();
+        if let (Some(first), Some(last)) = (dates.iter().min(), dates.iter().max()) {
+            for (flag, date) in [("--from", self.from), ("--to", self.to)] {
+                if let Some(date) = date
+                    && (date < *first || date > *last)
+                {
+                    return Err(ValidationError::DateOutsideHorizon {
+                        flag,
+                        date,
+                        first: *first,
+                        last: *last,
+                    });
+                }
+            }
+        }
+
+        let kept = days
+            .iter()
+            .filter(|day| {
+                NaiveDate::parse_from_str(&day.date, DATE_FORMAT).is_ok_and(|date| self.keeps(date))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if kept.is_empty() {
+            return Err(ValidationError::EmptyDateWindow);
+        }
+        Ok(kept)
+    }
+
+    fn keeps(&self, date: NaiveDate) -> bool {
+        (self.weekdays == 0 || self.weekdays & weekday_bit(date.weekday()) != 0)
+            && self.from.is_none_or(|from| date >= from)
+            && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+fn weekday_bit(weekday: Weekday) -> u8 {
+    1 << weekday.num_days_from_monday()
+}
+
+fn parse_weekdays(raw: &str) -> Result<u8, ValidationError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .try_fold(0, |mask, entry| {
+            let bits = match entry.to_ascii_lowercase().as_str() {
+                "weekend" => weekday_bit(Weekday::Sat) | weekday_bit(Weekday::Sun),
+                "weekdays" => 0b0001_1111,
+                lowered => lowered
+                    .parse::<Weekday>()
+                    .map(weekday_bit)
+                    .map_err(|_| ValidationError::InvalidWeekday(entry.to_string()))?,
+            };
+            Ok(mask | bits)
+        })
+        .and_then(|mask| {
+            if mask == 0 {
+                Err(ValidationError::InvalidWeekday(raw.to_string()))
+            } else {
+                Ok(mask)
+            }
+        })
+}
+
+fn parse_date(flag: &'static str, raw: &str) -> Result<NaiveDate, ValidationError> {
+    NaiveDate::parse_from_str(raw.trim(), DATE_FORMAT).map_err(|_| ValidationError::InvalidDate {
+        flag,
+        value: raw.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn week() -> Vec<ForecastDay> {
+        // 2026-02-11 is a Wednesday; the week runs through Tuesday 2026-02-17.
+        (11..=17)
+            .map(|day| ForecastDay {
+                date: format!("2026-02-{day}"),
+                weather_code: 3,
+                summary_zh: "陰天".to_string(),
+                temp_min_c: 14.0,
+                temp_max_c: 20.0,
+                precip_prob_max_pct: 10,
+                precip_sum_mm: None,
+                sunrise: None,
+                sunset: None,
+                moon_phase: None,
+                moon_illumination_pct: None,
+                wind_speed_max_kmh: None,
+                wind_direction_deg: None,
+                humidity_mean_pct: None,
+                uv_index_max: None,
+            })
+            .collect()
+    }
+
+    fn dates(days: &[ForecastDay]) -> Vec<&str> {
+        days.iter().map(|day| day.date.as_str()).collect()
+    }
+
+    #[test]
+    fn day_window_keeps_listed_weekdays_and_aliases() {
+        let window = DayWindow::parse(Some("sat, Sunday"), None, None)
+            .expect("parse")
+            .expect("window");
+        assert_eq!(
+            dates(&window.apply(&week()).expect("apply")),
+            vec!["2026-02-14", "2026-02-15"]
+        );
+
+        let weekend = DayWindow::parse(Some("weekend"), None, None).expect("parse");
+        assert_eq!(weekend, Some(window));
+
+        let weekdays = DayWindow::parse(Some("weekdays"), None, None)
+            .expect("parse")
+            .expect("window");
+        assert_eq!(weekdays.apply(&week()).expect("apply").len(), 5);
+
+        assert_eq!(DayWindow::parse(None, None, None), Ok(None));
+        assert_eq!(
+            DayWindow::parse(Some("sat,funday"), None, None),
+            Err(ValidationError::InvalidWeekday("funday".to_string()))
+        );
+        assert!(DayWindow::parse(Some(" , "), None, None).is_err());
+    }
+
+    #[test]
+    fn day_window_applies_inclusive_date_range_with_weekdays() {
+        let window = DayWindow::parse(Some("mon,fri"), Some("2026-02-12"), Some("2026-02-16"))
+            .expect("parse")
+            .expect("window");
+        assert_eq!(
+            dates(&window.apply(&week()).expect("apply")),
+            vec!["2026-02-13", "2026-02-16"]
+        );
+
+        let open_ended = DayWindow::parse(None, Some("2026-02-16"), None)
+            .expect("parse")
+            .expect("window");
+        assert_eq!(
+            dates(&open_ended.apply(&week()).expect("apply")),
+            vec!["2026-02-16", "2026-02-17"]
+        );
+    }
+
+    #[test]
+    fn day_window_rejects_bad_dates_and_windows_beyond_the_horizon() {
+        assert!(matches!(
+            DayWindow::parse(None, Some("2026/02/12"), None),
+            Err(ValidationError::InvalidDate { flag: "--from", .. })
+        ));
+        assert!(matches!(
+            DayWindow::parse(None, Some("2026-02-15"), Some("2026-02-12")),
+            Err(ValidationError::InvertedDateWindow { .. })
+        ));
+
+        let beyond = DayWindow::parse(None, None, Some("2026-02-20"))
+            .expect("parse")
+            .expect("window");
+        let error = beyond.apply(&week()).expect_err("beyond horizon");
+        assert_eq!(
+            error.to_string(),
+            "--to 2026-02-20 is outside the forecast horizon (2026-02-11 to 2026-02-17)"
+        );
+
+        let nothing = DayWindow::parse(Some("sat"), Some("2026-02-16"), None)
+            .expect("parse")
+            .expect("window");
+        assert_eq!(
+            nothing.apply(&week()),
+            Err(ValidationError::EmptyDateWindow)
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{LocationQuery, validate_coordinates};
+
+/// A named location from `WEATHER_LOCATIONS`, e.g. `home=Taipei;office=Hsinchu;cabin=24.15,121.28`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FavoriteLocation {
+    pub name: String,
+    /// City name or `lat,lon` pair, as written in the config.
+    pub query: String,
+}
+
+impl FavoriteLocation {
+    pub fn location(&self) -> LocationQuery {
+        match parse_coordinates(&self.query) {
+            Some((lat, lon)) => LocationQuery::Coordinates { lat, lon },
+            None => LocationQuery::City(self.query.clone()),
+        }
+    }
+}
+
+/// Parse `name=query` entries separated by `;`. Entries without a name or query are skipped, and the first entry
+/// wins when a name repeats (names compare case-insensitively).
+pub fn parse_favorite_locations(raw: &str) -> Vec<FavoriteLocation> {
+    let mut favorites: Vec<FavoriteLocation> = Vec::new();
+
+    for entry in raw.split(';') {
+        let Some((name, query)) = entry.split_once('=') else {
+            continue;
+        };
+        let (name, query) = (name.trim(), query.trim());
+        if name.is_empty() || query.is_empty() || find_favorite(&favorites, name).is_some() {
+            continue;
+        }
+
+        favorites.push(FavoriteLocation {
+            name: name.to_string(),
+            query: query.to_string(),
+        });
+    }
+
+    favorites
+}
+
+pub fn find_favorite<'a>(
+    favorites: &'a [FavoriteLocation],
+    name: &str,
+) -> Option<&'a FavoriteLocation> {
+    let name = name.trim();
+    favorites
+        .iter()
+        .find(|favorite| favorite.name.eq_ignore_ascii_case(name))
+}
+
+fn parse_coordinates(raw: &str) -> Option<(f64, f64)> {
+    let (lat, lon) = raw.split_once(',')?;
+    let lat = lat.trim().parse::<f64>().ok()?;
+    let lon = lon.trim().parse::<f64>().ok()?;
+    validate_coordinates(lat, lon).ok()?;
+    Some((lat, lon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favorites_parse_named_cities_and_coordinates() {
+        let favorites =
+            parse_favorite_locations(" home = Taipei ; office=Hsinchu;cabin=24.15, 121.28");
+
+        assert_eq!(
+            favorites
+                .iter()
+                .map(|favorite| (favorite.name.as_str(), favorite.query.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("home", "Taipei"),
+                ("office", "Hsinchu"),
+                ("cabin", "24.15, 121.28")
+            ]
+        );
+        assert_eq!(
+            favorites[0].location(),
+            LocationQuery::City("Taipei".to_string())
+        );
+        assert_eq!(
+            favorites[2].location(),
+            LocationQuery::Coordinates {
+                lat: 24.15,
+                lon: 121.28
+            }
+        );
+    }
+
+    #[test]
+    fn favorites_skip_malformed_and_duplicate_entries() {
+        let favorites = parse_favorite_locations("home=Taipei;;Tokyo;=Osaka;work=;HOME=Kyoto");
+
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(
+            find_favorite(&favorites, "Home").map(|favorite| favorite.query.as_str()),
+            Some("Taipei")
+        );
+        assert_eq!(find_favorite(&favorites, "Tokyo"), None);
+    }
+
+    #[test]
+    fn favorites_with_out_of_range_coordinates_are_cities() {
+        let favorites = parse_favorite_locations("odd=95,10");
+        assert_eq!(
+            favorites[0].location(),
+            LocationQuery::City("95,10".to_string())
+        );
+    }
+}
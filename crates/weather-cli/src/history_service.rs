@@ -0,0 +1,509 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{RuntimeConfig, WEATHER_HISTORY_CACHE_TTL_SECS};
+use crate::error::AppError;
+use crate::geocoding::{
+    ResolvedLocation, city_query_cache_key, location_from_coordinates, read_cached_city_location,
+    write_cached_city_location,
+};
+use crate::model::{
+    CacheMetadata, ForecastLocation, ForecastPeriod, FreshnessStatus, HistoryDay, HistoryOutput,
+    LocationQuery, ProviderAttempt,
+};
+use crate::providers::{ProviderApi, ProviderHistory};
+use crate::weather_code;
+
+/// First day covered by the Open-Meteo archive.
+pub const HISTORY_EARLIEST_DATE: &str = "1940-01-01";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct HistoryCacheRecord {
+    location: ForecastLocation,
+    timezone: String,
+    observed: HistoryDay,
+    source: String,
+    #[serde(default)]
+    source_trace: Vec<String>,
+    fetched_at: String,
+}
+
+#[derive(Debug, Clone)]
+struct OutputContext {
+    cache_key: String,
+    ttl_secs: u64,
+}
+
+/// Observed weather for a past `date`. Dates before the archive starts or after today (UTC) are user errors.
+pub fn resolve_history<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    location_query: &LocationQuery,
+    date: NaiveDate,
+) -> Result<HistoryOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let now = now_fn();
+    validate_date(date, now)?;
+    let date_label = date.format("%Y-%m-%d").to_string();
+    let ttl_secs = WEATHER_HISTORY_CACHE_TTL_SECS;
+
+    let (location_key, mut resolved_location) = match location_query {
+        LocationQuery::City(city) => (city_query_cache_key(city), None),
+        _ => {
+            let location = resolve_location(config, providers, location_query)?;
+            let key = location.cache_key();
+            (key, Some(location))
+        }
+    };
+    let cache_key = format!("{location_key}-{date_label}");
+    let path = crate::cache::cache_path(&config.cache_dir, ForecastPeriod::History, &cache_key);
+    let output_context = OutputContext {
+        cache_key: cache_key.clone(),
+        ttl_secs,
+    };
+
+    let cached = read_history_cache(&path).map_err(|error| AppError::runtime(error.to_string()))?;
+    let cached_state = cached.map(|record| {
+        let age_secs = record_age_secs(&record, now, ttl_secs);
+        (record, age_secs)
+    });
+
+    if let Some((record, age_secs)) = &cached_state
+        && *age_secs <= ttl_secs
+    {
+        return Ok(build_output_from_record(
+            record,
+            FreshnessStatus::CacheFresh,
+            *age_secs,
+            Vec::new(),
+            &output_context,
+        ));
+    }
+
+    let location = match resolved_location.take() {
+        Some(location) => location,
+        None => match cached_state.as_ref() {
+            Some((record, _)) => resolved_location_from_record(record),
+            None => resolve_location(config, providers, location_query)?,
+        },
+    };
+
+    match providers.fetch_open_meteo_history(location.latitude, location.longitude, &date_label) {
+        Ok(history) => build_live_output(&path, &location, history, &output_context),
+        Err(error) => {
+            let trace = vec![format!("open_meteo: {error}")];
+            match cached_state {
+                Some((mut record, age_secs)) => {
+                    let attempts = ProviderAttempt::from_trace(&trace, None);
+                    record.source_trace = trace;
+                    Ok(build_output_from_record(
+                        &record,
+                        FreshnessStatus::CacheStaleFallback,
+                        age_secs,
+                        attempts,
+                        &output_context,
+                    ))
+                }
+                None => Err(AppError::runtime_with_trace(
+                    &format!("failed to fetch observed weather for {date_label}"),
+                    &trace,
+                )),
+            }
+        }
+    }
+}
+
+fn validate_date(date: NaiveDate, now: DateTime<Utc>) -> Result<(), AppError> {
+    let earliest = NaiveDate::parse_from_str(HISTORY_EARLIEST_DATE, "%Y-%m-%d")
+        .map_err(|error| AppError::runtime(error.to_string()))?;
+    if date < earliest {
+        return Err(AppError::user(format!(
+            "--date must be on or after {HISTORY_EARLIEST_DATE}"
+        )));
+    }
+    if date > now.date_naive() {
+        return Err(AppError::user(
+            "--date must not be in the future; use today or week for forecasts",
+        ));
+    }
+    Ok(())
+}
+
+fn resolve_location<P: ProviderApi>(
+    config: &RuntimeConfig,
+    providers: &P,
+    location: &LocationQuery,
+) -> Result<ResolvedLocation, AppError> {
+    match location {
+        LocationQuery::City(city) => {
+            if let Ok(Some(cached)) = read_cached_city_location(&config.cache_dir, city) {
+                return Ok(cached);
+            }
+
+            let resolved = providers.geocode_city(city).map_err(|error| {
+                AppError::runtime(format!("failed to resolve city '{city}': {error}"))
+            })?;
+
+            let _ = write_cached_city_location(&config.cache_dir, city, &resolved);
+            Ok(resolved)
+        }
+        LocationQuery::Coordinates { lat, lon } => Ok(location_from_coordinates(*lat, *lon)),
+    }
+}
+
+fn build_live_output(
+    path: &Path,
+    location: &ResolvedLocation,
+    history: ProviderHistory,
+    output_context: &OutputContext,
+) -> Result<HistoryOutput, AppError> {
+    let timezone = if history.timezone.trim().is_empty() {
+        location.timezone.clone()
+    } else {
+        history.timezone
+    };
+    let day = history.day;
+
+    let record = HistoryCacheRecord {
+        location: location.to_output_location(),
+        timezone,
+        observed: HistoryDay {
+            date: day.date,
+            weather_code: day.weather_code,
+            summary_zh: weather_code::summary_zh(day.weather_code).to_string(),
+            temp_min_c: round1(day.temp_min_c),
+            temp_max_c: round1(day.temp_max_c),
+            temp_mean_c: day.temp_mean_c.map(round1),
+            precip_sum_mm: day.precip_sum_mm.map(round1),
+            precip_hours: day.precip_hours.map(round1),
+            wind_speed_max_kmh: day.wind_speed_max_kmh.map(round1),
+        },
+        source: "open_meteo".to_string(),
+        source_trace: Vec::new(),
+        fetched_at: history
+            .fetched_at
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+    };
+
+    write_history_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
+
+    let attempts = ProviderAttempt::from_trace(&record.source_trace, Some(&record.source));
+    Ok(build_output_from_record(
+        &record,
+        FreshnessStatus::Live,
+        0,
+        attempts,
+        output_context,
+    ))
+}
+
+fn build_output_from_record(
+    record: &HistoryCacheRecord,
+    status: FreshnessStatus,
+    age_secs: u64,
+    attempts: Vec<ProviderAttempt>,
+    output_context: &OutputContext,
+) -> HistoryOutput {
+    HistoryOutput {
+        location: record.location.clone(),
+        timezone: record.timezone.clone(),
+        observed: record.observed.clone(),
+        source: record.source.clone(),
+        source_trace: record.source_trace.clone(),
+        fetched_at: record.fetched_at.clone(),
+        freshness: CacheMetadata {
+            status,
+            key: output_context.cache_key.clone(),
+            ttl_secs: output_context.ttl_secs,
+            age_secs,
+            provider: Some(record.source.clone()),
+            attempts,
+        },
+    }
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn resolved_location_from_record(record: &HistoryCacheRecord) -> ResolvedLocation {
+    ResolvedLocation {
+        name: record.location.name.clone(),
+        latitude: record.location.latitude,
+        longitude: record.location.longitude,
+        timezone: record.timezone.clone(),
+    }
+}
+
+/// Seconds since the record was fetched; an unparsable timestamp counts as just past the TTL.
+fn record_age_secs(record: &HistoryCacheRecord, now: DateTime<Utc>, ttl_secs: u64) -> u64 {
+    DateTime::parse_from_rfc3339(&record.fetched_at)
+        .ok()
+        .map(|fetched_at| {
+            now.signed_duration_since(fetched_at.with_timezone(&Utc))
+                .num_seconds()
+                .max(0)
+                .try_into()
+                .unwrap_or(u64::MAX)
+        })
+        .unwrap_or(ttl_secs.saturating_add(1))
+}
+
+fn read_history_cache(path: &Path) -> io::Result<Option<HistoryCacheRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let payload = fs::read_to_string(path)?;
+    Ok(serde_json::from_str::<HistoryCacheRecord>(&payload).ok())
+}
+
+fn write_history_cache(path: &Path, record: &HistoryCacheRecord) -> io::Result<()> {
+    let payload = serde_json::to_vec(record)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    let parent = path.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cache path must have a parent directory",
+        )
+    })?;
+    fs::create_dir_all(parent)?;
+
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, payload)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+    use crate::providers::{
+        ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderHistoryDay,
+        ProviderHourlyForecast, ProviderNowcast,
+    };
+    use crate::units::UnitSystem;
+
+    struct FakeProviders {
+        history_result: Result<ProviderHistory, ProviderError>,
+        history_calls: Cell<usize>,
+        requested_dates: RefCell<Vec<String>>,
+    }
+
+    impl FakeProviders {
+        fn with_history(history_result: Result<ProviderHistory, ProviderError>) -> Self {
+            Self {
+                history_result,
+                history_calls: Cell::new(0),
+                requested_dates: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn ok() -> Self {
+            Self::with_history(Ok(ProviderHistory {
+                timezone: "Asia/Tokyo".to_string(),
+                fetched_at: fixed_now(),
+                day: ProviderHistoryDay {
+                    date: "2025-08-01".to_string(),
+                    weather_code: 63,
+                    temp_min_c: 26.14,
+                    temp_max_c: 33.36,
+                    temp_mean_c: Some(29.22),
+                    precip_sum_mm: Some(12.64),
+                    precip_hours: Some(5.0),
+                    wind_speed_max_kmh: None,
+                },
+            }))
+        }
+    }
+
+    impl ProviderApi for FakeProviders {
+        fn geocode_city(&self, _city: &str) -> Result<ResolvedLocation, ProviderError> {
+            Ok(ResolvedLocation {
+                name: "Tokyo".to_string(),
+                latitude: 35.6762,
+                longitude: 139.6503,
+                timezone: "Asia/Tokyo".to_string(),
+            })
+        }
+
+        fn fetch_open_meteo_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_hourly_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_hours: usize,
+        ) -> Result<ProviderHourlyForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            self.history_calls.set(self.history_calls.get() + 1);
+            self.requested_dates.borrow_mut().push(date.to_string());
+            self.history_result.clone()
+        }
+
+        fn fetch_met_no_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+    }
+
+    fn config_in(dir: &tempfile::TempDir) -> RuntimeConfig {
+        RuntimeConfig {
+            cache_dir: dir.path().to_path_buf(),
+            cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
+        }
+    }
+
+    fn fixed_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 12, 0, 5, 0)
+            .single()
+            .expect("time")
+    }
+
+    fn tokyo() -> LocationQuery {
+        LocationQuery::City("Tokyo".to_string())
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").expect("date")
+    }
+
+    #[test]
+    fn history_service_returns_rounded_observations_and_caches_them() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = config_in(&dir);
+        let providers = FakeProviders::ok();
+
+        let output = resolve_history(&config, &providers, fixed_now, &tokyo(), date("2025-08-01"))
+            .expect("must pass");
+
+        assert_eq!(output.freshness.status, FreshnessStatus::Live);
+        assert_eq!(output.freshness.key, "city-tokyo-2025-08-01");
+        assert_eq!(output.observed.temp_max_c, 33.4);
+        assert_eq!(output.observed.temp_mean_c, Some(29.2));
+        assert_eq!(output.observed.precip_sum_mm, Some(12.6));
+        assert_eq!(output.observed.summary_zh, weather_code::summary_zh(63));
+        assert_eq!(*providers.requested_dates.borrow(), vec!["2025-08-01"]);
+
+        let next_day = || fixed_now() + chrono::Duration::days(1);
+        let output = resolve_history(&config, &providers, next_day, &tokyo(), date("2025-08-01"))
+            .expect("must pass");
+        assert_eq!(output.freshness.status, FreshnessStatus::CacheFresh);
+        assert_eq!(providers.history_calls.get(), 1);
+    }
+
+    #[test]
+    fn history_service_rejects_future_and_pre_archive_dates() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = FakeProviders::ok();
+
+        for raw in ["2026-02-13", "1939-12-31"] {
+            let error =
+                resolve_history(&config_in(&dir), &providers, fixed_now, &tokyo(), date(raw))
+                    .expect_err("must fail");
+            assert_eq!(error.exit_code(), 2);
+        }
+        assert_eq!(providers.history_calls.get(), 0);
+    }
+
+    #[test]
+    fn history_service_falls_back_to_stale_cache_when_provider_fails() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = config_in(&dir);
+        resolve_history(
+            &config,
+            &FakeProviders::ok(),
+            fixed_now,
+            &tokyo(),
+            date("2025-08-01"),
+        )
+        .expect("prime cache");
+
+        let failing =
+            FakeProviders::with_history(Err(ProviderError::Transport("timeout".to_string())));
+        let later = || fixed_now() + chrono::Duration::days(8);
+        let output =
+            resolve_history(&config, &failing, later, &tokyo(), date("2025-08-01")).expect("stale");
+        assert_eq!(output.freshness.status, FreshnessStatus::CacheStaleFallback);
+        assert!(output.source_trace[0].contains("timeout"));
+
+        let error = resolve_history(&config, &failing, later, &tokyo(), date("2025-07-31"))
+            .expect_err("must fail");
+        assert!(error.message.contains("2025-07-31"));
+        assert!(
+            error
+                .message
+                .contains("open_meteo: transport error: timeout")
+        );
+    }
+}
@@ -13,7 +13,7 @@ use crate::geocoding::{
 };
 use crate::model::{
     CacheMetadata, ForecastLocation, ForecastPeriod, HourlyForecastOutput, HourlyForecastPoint,
-    LocationQuery,
+    LocationQuery, ProviderAttempt,
 };
 use crate::providers::{ProviderApi, ProviderHourlyForecast};
 
@@ -85,6 +85,7 @@ where
             &location,
             FreshnessStatus::CacheFresh,
             *age_secs,
+            Vec::new(),
             &output_context,
         ));
     }
@@ -172,11 +173,13 @@ fn build_live_output(
 
     write_hourly_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
 
+    let attempts = ProviderAttempt::from_trace(&record.source_trace, Some(&record.source));
     Ok(build_output_from_record(
         &record,
         location,
         FreshnessStatus::Live,
         0,
+        attempts,
         output_context,
     ))
 }
@@ -193,6 +196,7 @@ fn fallback_or_error(
             location,
             FreshnessStatus::CacheStaleFallback,
             age_secs,
+            ProviderAttempt::from_trace(&trace, None),
             output_context,
         ));
     }
@@ -208,6 +212,7 @@ fn build_output_from_record(
     location: &ResolvedLocation,
     freshness_status: FreshnessStatus,
     age_secs: u64,
+    attempts: Vec<ProviderAttempt>,
     output_context: &OutputContext,
 ) -> HourlyForecastOutput {
     let fetched_at = parse_fetched_at(record)
@@ -237,6 +242,8 @@ fn build_output_from_record(
             key: output_context.cache_key.clone(),
             ttl_secs: output_context.ttl_secs,
             age_secs,
+            provider: Some(record.source.clone()),
+            attempts,
         },
     }
 }
@@ -376,7 +383,11 @@ mod tests {
     use std::cell::Cell;
 
     use super::*;
-    use crate::providers::{ProviderError, ProviderForecast, ProviderForecastHour};
+    use crate::providers::{
+        ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderForecastHour,
+        ProviderHistory, ProviderNowcast,
+    };
+    use crate::units::UnitSystem;
 
     struct FakeProviders {
         geocode_result: Result<ResolvedLocation, ProviderError>,
@@ -448,6 +459,32 @@ mod tests {
             self.hourly_result.clone()
         }
 
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
         fn fetch_met_no_forecast(
             &self,
             _lat: f64,
@@ -456,12 +493,35 @@ mod tests {
         ) -> Result<ProviderForecast, ProviderError> {
             Err(ProviderError::Transport("unused".to_string()))
         }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
     }
 
     fn config_in_tempdir() -> RuntimeConfig {
         RuntimeConfig {
             cache_dir: tempfile::tempdir().expect("tempdir").path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         }
     }
 
@@ -490,6 +550,16 @@ mod tests {
         let config = RuntimeConfig {
             cache_dir: dir.path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         };
         let providers = FakeProviders::ok();
         let query = LocationQuery::City("Tokyo".to_string());
@@ -1,11 +1,24 @@
+pub mod astronomy;
+pub mod auto_locate;
 pub mod batch_service;
 pub mod cache;
+pub mod compare_service;
+pub mod condition;
 pub mod config;
+pub mod current_service;
+pub mod day_window;
 pub mod error;
+pub mod favorites;
 pub mod geocoding;
+pub mod history_service;
 pub mod hourly_service;
+pub mod locale;
 pub mod model;
+pub mod notify_service;
+pub mod nowcast_service;
 pub mod providers;
 pub mod service;
+pub mod units;
+pub mod warm_service;
 pub mod weather_code;
 pub mod weather_icon;
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use chrono::Weekday;
+
+/// Languages with a bundled label table under `locales/`. English is also the fallback for keys another table lacks.
+pub const SUPPORTED_LANGUAGES: [&str; 4] = ["en", "zh", "ja", "de"];
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+const TABLE_SOURCES: [(&str, &str); 4] = [
+    ("en", include_str!("../locales/en.json")),
+    ("zh", include_str!("../locales/zh.json")),
+    ("ja", include_str!("../locales/ja.json")),
+    ("de", include_str!("../locales/de.json")),
+];
+
+type Table = HashMap<String, String>;
+
+fn tables() -> &'static HashMap<&'static str, Table> {
+    static TABLES: OnceLock<HashMap<&'static str, Table>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        // The tables are compiled in and checked by tests; a malformed one degrades to the fallback chain.
+        TABLE_SOURCES
+            .iter()
+            .map(|(language, source)| (*language, serde_json::from_str(source).unwrap_or_default()))
+            .collect()
+    })
+}
+
+/// Output language for human and Alfred labels. Machine JSON fields are never localized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    language: &'static str,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            language: FALLBACK_LANGUAGE,
+        }
+    }
+}
+
+impl Locale {
+    /// Parse a language tag such as `ja`, `de-AT`, or `zh_TW`. Regional variants use their base language's table;
+    /// a base language without a table is `None`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let lowered = raw.trim().to_ascii_lowercase();
+        let base = lowered.split(['-', '_']).next().unwrap_or_default();
+        SUPPORTED_LANGUAGES
+            .iter()
+            .find(|language| **language == base)
+            .map(|language| Self { language })
+    }
+
+    pub fn language(self) -> &'static str {
+        self.language
+    }
+
+    /// Label for `key` from this language's table, then English, then the key itself so a gap shows up in output
+    /// instead of an empty label.
+    pub fn text(self, key: &str) -> &str {
+        let tables = tables();
+        lookup(
+            [self.language, FALLBACK_LANGUAGE]
+                .iter()
+                .filter_map(|language| tables.get(language)),
+            key,
+        )
+    }
+
+    /// `text` with `{name}` placeholders replaced, e.g. `format("rain_outlook.starts", &[("minutes", "40")])`.
+    pub fn format(self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.text(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+
+    pub fn weekday(self, weekday: Weekday) -> &'static str {
+        self.text(match weekday {
+            Weekday::Mon => "weekday.mon",
+            Weekday::Tue => "weekday.tue",
+            Weekday::Wed => "weekday.wed",
+            Weekday::Thu => "weekday.thu",
+            Weekday::Fri => "weekday.fri",
+            Weekday::Sat => "weekday.sat",
+            Weekday::Sun => "weekday.sun",
+        })
+    }
+}
+
+fn lookup<'a, 'k>(chain: impl IntoIterator<Item = &'a Table>, key: &'k str) -> &'k str
+where
+    'a: 'k,
+{
+    chain
+        .into_iter()
+        .find_map(|table| table.get(key))
+        .map_or(key, String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astronomy::MoonPhase;
+    use crate::weather_code::{summary_en, summary_key, summary_zh};
+
+    #[test]
+    fn locale_parse_accepts_regional_tags_and_rejects_unknown_languages() {
+        assert_eq!(Locale::parse(" JA ").map(Locale::language), Some("ja"));
+        assert_eq!(Locale::parse("de-AT").map(Locale::language), Some("de"));
+        assert_eq!(Locale::parse("zh_TW").map(Locale::language), Some("zh"));
+        assert_eq!(Locale::parse("fr"), None);
+        assert_eq!(Locale::parse(""), None);
+        assert_eq!(Locale::default().language(), "en");
+    }
+
+    #[test]
+    fn locale_tables_parse_and_only_use_english_keys() {
+        let tables = tables();
+        let english = &tables[FALLBACK_LANGUAGE];
+        for language in SUPPORTED_LANGUAGES {
+            let table = &tables[language];
+            assert!(!table.is_empty(), "{language} table is empty or malformed");
+            let unknown = table
+                .keys()
+                .filter(|key| !english.contains_key(*key))
+                .collect::<Vec<_>>();
+            assert!(
+                unknown.is_empty(),
+                "{language} has keys English lacks: {unknown:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn locale_missing_keys_fall_back_to_english_then_the_key() {
+        let english = Table::from([
+            ("label.rain".to_string(), "rain".to_string()),
+            ("label.wind".to_string(), "wind".to_string()),
+        ]);
+        let partial = Table::from([("label.rain".to_string(), "Regen".to_string())]);
+
+        assert_eq!(lookup([&partial, &english], "label.rain"), "Regen");
+        assert_eq!(lookup([&partial, &english], "label.wind"), "wind");
+        assert_eq!(lookup([&partial, &english], "label.uv"), "label.uv");
+        assert_eq!(Locale::default().text("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn locale_tables_match_the_model_summaries() {
+        let en = Locale::default();
+        let zh = Locale::parse("zh").expect("zh");
+        for code in -1..=100 {
+            assert_eq!(en.text(summary_key(code)), summary_en(code), "code {code}");
+            assert_eq!(zh.text(summary_key(code)), summary_zh(code), "code {code}");
+        }
+        assert_eq!(
+            en.text(MoonPhase::FullMoon.label_key()),
+            MoonPhase::FullMoon.label_en()
+        );
+        assert_eq!(
+            zh.text(MoonPhase::NewMoon.label_key()),
+            MoonPhase::NewMoon.label_zh()
+        );
+    }
+
+    #[test]
+    fn locale_formats_placeholders_per_language() {
+        let ja = Locale::parse("ja").expect("ja");
+        assert_eq!(
+            ja.format("rain_outlook.starts", &[("minutes", "40")]),
+            "40 分後に雨が降り始める"
+        );
+        assert_eq!(
+            Locale::default().format("favorites.subtitle", &[("query", "Taipei")]),
+            "Weather for Taipei"
+        );
+        assert_eq!(Locale::parse("de").expect("de").weekday(Weekday::Wed), "Mi");
+    }
+}
@@ -10,24 +10,37 @@ use workflow_common::{
 };
 
 use weather_cli::{
-    batch_service,
+    astronomy, auto_locate, batch_service, compare_service,
+    condition::Condition,
     config::RuntimeConfig,
+    current_service,
+    day_window::DayWindow,
     error::AppError,
+    favorites::{FavoriteLocation, find_favorite},
+    history_service,
     hourly_service::{self, DEFAULT_HOURLY_COUNT},
+    locale::{Locale, SUPPORTED_LANGUAGES},
     model::{
-        ForecastBatchOutput, ForecastOutput, ForecastPeriod, ForecastRequest, HourlyForecastOutput,
-        LocationQuery, OutputMode as RequestOutputMode,
+        CompareDay, CompareOutput, CurrentWeatherOutput, ForecastBatchOutput, ForecastDay,
+        ForecastOutput, ForecastPeriod, ForecastRequest, HistoryOutput, HourlyForecastOutput,
+        LocationQuery, NotifyOutput, NowcastOutput, OutputMode as RequestOutputMode, RainOutlook,
+        SunOutput, WarmOutput,
     },
-    providers::{HttpProviders, ProviderApi},
+    notify_service, nowcast_service,
+    providers::{HttpProviders, OfflineProviders, ProviderApi},
     service,
+    units::UnitSystem,
+    warm_service,
+    weather_icon::FALLBACK_ICON_KEY,
 };
 
 #[cfg(test)]
 use weather_cli::{
     geocoding::ResolvedLocation,
     providers::{
-        ProviderError, ProviderForecast, ProviderForecastDay, ProviderForecastHour,
-        ProviderHourlyForecast,
+        ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderForecastDay,
+        ProviderForecastHour, ProviderHistory, ProviderHistoryDay, ProviderHourlyForecast,
+        ProviderNowcast, ProviderNowcastPoint,
     },
 };
 
@@ -36,6 +49,9 @@ use weather_cli::{
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Serve cached data only and never touch the network (also `WEATHER_OFFLINE=1`).
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -50,8 +66,10 @@ enum Commands {
         lon: Option<f64>,
         #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
         output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
         #[arg(long, value_enum)]
-        lang: Option<LanguageArg>,
+        units: Option<UnitsArg>,
     },
     /// 7-day weather forecast.
     Week {
@@ -63,10 +81,22 @@ enum Commands {
         lon: Option<f64>,
         #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
         output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
         #[arg(long, value_enum)]
-        lang: Option<LanguageArg>,
+        units: Option<UnitsArg>,
+        /// Keep only these weekdays, comma-separated: `sat,sun`, `weekend`, or `weekdays`.
+        #[arg(long)]
+        days: Option<String>,
+        /// First date to keep (YYYY-MM-DD), within the forecast horizon.
+        #[arg(long)]
+        from: Option<String>,
+        /// Last date to keep (YYYY-MM-DD), within the forecast horizon.
+        #[arg(long)]
+        to: Option<String>,
     },
     /// Hourly weather forecast (next 24h by default).
+    #[command(visible_alias = "hours")]
     Hourly {
         #[arg(long)]
         city: Option<String>,
@@ -76,16 +106,131 @@ enum Commands {
         lon: Option<f64>,
         #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
         output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
         #[arg(long, value_enum)]
-        lang: Option<LanguageArg>,
+        units: Option<UnitsArg>,
         #[arg(long, default_value_t = DEFAULT_HOURLY_COUNT)]
         hours: usize,
     },
+    /// Current conditions (temperature, feels-like, wind, humidity).
+    Now {
+        #[arg(long)]
+        city: Option<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        lat: Option<f64>,
+        #[arg(long, allow_hyphen_values = true)]
+        lon: Option<f64>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
+        #[arg(long, value_enum)]
+        units: Option<UnitsArg>,
+    },
+    /// Precipitation for the next 2 hours in 15-minute steps, with when rain starts or stops.
+    Rain {
+        #[arg(long)]
+        city: Option<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        lat: Option<f64>,
+        #[arg(long, allow_hyphen_values = true)]
+        lon: Option<f64>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
+        #[arg(long, value_enum)]
+        units: Option<UnitsArg>,
+    },
+    /// Observed weather on a past date (Open-Meteo archive).
+    History {
+        /// Past date as YYYY-MM-DD.
+        #[arg(long)]
+        date: String,
+        #[arg(long)]
+        city: Option<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        lat: Option<f64>,
+        #[arg(long, allow_hyphen_values = true)]
+        lon: Option<f64>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
+        #[arg(long, value_enum)]
+        units: Option<UnitsArg>,
+    },
+    /// Today's forecast for two cities side by side, with the temperature and rain-chance differences.
+    Compare {
+        /// Exactly two cities (or favorite names); differences are the second minus the first.
+        #[arg(long = "city", required = true)]
+        city: Vec<String>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
+        #[arg(long, value_enum)]
+        units: Option<UnitsArg>,
+    },
+    /// Saved favorite locations from `WEATHER_LOCATIONS`; each name also works as `--city <name>`.
+    Locations {
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
+    },
+    /// Refresh the today, week, hourly, now and rain cache entries, for a scheduled trigger. Prints nothing on
+    /// success; without a location, all `WEATHER_LOCATIONS` favorites are warmed.
+    Warm {
+        #[arg(long = "city")]
+        city: Vec<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        lat: Option<f64>,
+        #[arg(long, allow_hyphen_values = true)]
+        lon: Option<f64>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+    },
+    /// Post a macOS notification when a condition holds for today's forecast, for a scheduled trigger. Prints the
+    /// alert when it fires and nothing otherwise.
+    Notify {
+        #[arg(long)]
+        city: Option<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        lat: Option<f64>,
+        #[arg(long, allow_hyphen_values = true)]
+        lon: Option<f64>,
+        /// Condition such as `rain>60 or tmax>35` (fields: rain, precip, tmax, tmin, wind, humidity, uv).
+        #[arg(long)]
+        when: String,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_enum)]
+        units: Option<UnitsArg>,
+        /// Evaluate the condition without posting a notification.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Sunrise, sunset, golden hours and moon phase for the next 7 days.
+    Sun {
+        #[arg(long)]
+        city: Option<String>,
+        #[arg(long, allow_hyphen_values = true)]
+        lat: Option<f64>,
+        #[arg(long, allow_hyphen_values = true)]
+        lon: Option<f64>,
+        #[arg(long, value_enum, default_value_t = OutputModeArg::Human)]
+        output: OutputModeArg,
+        #[arg(long, value_parser = parse_lang_arg)]
+        lang: Option<Locale>,
+    },
 }
 
 const ERROR_CODE_USER_INVALID_INPUT: &str = "NILS_WEATHER_001";
 const ERROR_CODE_RUNTIME_PROVIDER_INIT: &str = "NILS_WEATHER_002";
 const ERROR_CODE_RUNTIME_PROVIDER_FAILED: &str = "NILS_WEATHER_002";
+const ERROR_CODE_RUNTIME_NOTIFY: &str = "NILS_WEATHER_004";
 const ERROR_CODE_RUNTIME_SERIALIZE: &str = "NILS_COMMON_005";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -95,16 +240,22 @@ enum OutputModeArg {
     AlfredJson,
 }
 
+/// Overrides `WEATHER_UNITS` for one invocation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
-enum LanguageArg {
-    En,
-    Zh,
+enum UnitsArg {
+    Metric,
+    Imperial,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum OutputLanguage {
-    En,
-    Zh,
+/// Overrides `WEATHER_LANG` for one invocation. Any tag whose base language has a label table is accepted, so
+/// `zh-TW` and `de-AT` work too.
+fn parse_lang_arg(raw: &str) -> Result<Locale, String> {
+    Locale::parse(raw).ok_or_else(|| {
+        format!(
+            "unsupported language (expected one of: {})",
+            SUPPORTED_LANGUAGES.join(", ")
+        )
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -149,11 +300,11 @@ impl From<OutputModeArg> for OutputMode {
     }
 }
 
-impl From<LanguageArg> for OutputLanguage {
-    fn from(value: LanguageArg) -> Self {
+impl From<UnitsArg> for UnitSystem {
+    fn from(value: UnitsArg) -> Self {
         match value {
-            LanguageArg::En => OutputLanguage::En,
-            LanguageArg::Zh => OutputLanguage::Zh,
+            UnitsArg::Metric => UnitSystem::Metric,
+            UnitsArg::Imperial => UnitSystem::Imperial,
         }
     }
 }
@@ -164,6 +315,14 @@ impl Cli {
             Commands::Today { .. } => "weather.today",
             Commands::Week { .. } => "weather.week",
             Commands::Hourly { .. } => "weather.hourly",
+            Commands::Now { .. } => "weather.now",
+            Commands::Rain { .. } => "weather.rain",
+            Commands::Sun { .. } => "weather.sun",
+            Commands::History { .. } => "weather.history",
+            Commands::Compare { .. } => "weather.compare",
+            Commands::Locations { .. } => "weather.locations",
+            Commands::Warm { .. } => "weather.warm",
+            Commands::Notify { .. } => "weather.notify",
         }
     }
 
@@ -171,7 +330,15 @@ impl Cli {
         match &self.command {
             Commands::Today { output, .. }
             | Commands::Week { output, .. }
-            | Commands::Hourly { output, .. } => (*output).into(),
+            | Commands::Hourly { output, .. }
+            | Commands::Now { output, .. }
+            | Commands::Rain { output, .. }
+            | Commands::Sun { output, .. }
+            | Commands::History { output, .. }
+            | Commands::Compare { output, .. }
+            | Commands::Locations { output, .. }
+            | Commands::Warm { output, .. }
+            | Commands::Notify { output, .. } => (*output).into(),
         }
     }
 }
@@ -181,6 +348,7 @@ fn main() {
     let command = cli.command_name();
     let output_mode = cli.output_mode_hint();
     match run(cli) {
+        Ok(output) if output.is_empty() => {}
         Ok(output) => println!("{output}"),
         Err(error) => {
             emit_error(command, output_mode, &error);
@@ -191,11 +359,13 @@ fn main() {
 
 fn run(cli: Cli) -> Result<String, CliError> {
     let config = RuntimeConfig::from_env();
-    let providers = HttpProviders::new()
+    let providers = HttpProviders::from_config(&config)
         .map_err(|error| runtime_error(ERROR_CODE_RUNTIME_PROVIDER_INIT, error.to_string()))?;
     run_with(cli, &config, &providers, Utc::now)
 }
 
+/// In offline mode the given providers are swapped for `OfflineProviders`, so every command is answered from the cache
+/// (stale entries as `cache_stale_fallback`) and a cache miss becomes a user error.
 fn run_with<P, N>(
     cli: Cli,
     config: &RuntimeConfig,
@@ -206,13 +376,46 @@ where
     P: ProviderApi,
     N: Fn() -> DateTime<Utc> + Copy,
 {
-    match cli.command {
+    if !cli.offline && !config.offline {
+        return run_command_with(cli.command, config, providers, now_fn);
+    }
+
+    let config = RuntimeConfig {
+        offline: true,
+        ..config.clone()
+    };
+    run_command_with(cli.command, &config, &OfflineProviders, now_fn).map_err(|error| {
+        if error.kind == weather_cli::error::ErrorKind::Runtime
+            && error.code == ERROR_CODE_RUNTIME_PROVIDER_FAILED
+        {
+            user_error(
+                ERROR_CODE_USER_INVALID_INPUT,
+                "offline mode: nothing cached for this request; run it once online (or `weather-cli warm`) first",
+            )
+        } else {
+            error
+        }
+    })
+}
+
+fn run_command_with<P, N>(
+    command: Commands,
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    match command {
         Commands::Today {
             city,
             lat,
             lon,
             output,
             lang,
+            units,
         } => run_command(
             config,
             providers,
@@ -225,6 +428,8 @@ where
                 lon,
                 output,
                 lang,
+                units,
+                window: None,
             },
         ),
         Commands::Week {
@@ -233,6 +438,10 @@ where
             lon,
             output,
             lang,
+            units,
+            days,
+            from,
+            to,
         } => run_command(
             config,
             providers,
@@ -245,6 +454,9 @@ where
                 lon,
                 output,
                 lang,
+                units,
+                window: DayWindow::parse(days.as_deref(), from.as_deref(), to.as_deref())
+                    .map_err(user_invalid_input)?,
             },
         ),
         Commands::Hourly {
@@ -253,6 +465,7 @@ where
             lon,
             output,
             lang,
+            units,
             hours,
         } => run_hourly_command(
             config,
@@ -265,9 +478,155 @@ where
                 lon,
                 output,
                 lang,
+                units,
                 hours,
             },
         ),
+        Commands::Now {
+            city,
+            lat,
+            lon,
+            output,
+            lang,
+            units,
+        } => run_now_command(
+            config,
+            providers,
+            now_fn,
+            LocationCommandArgs {
+                command: "weather.now",
+                city: city.as_deref(),
+                lat,
+                lon,
+                output,
+                lang,
+                units,
+            },
+        ),
+        Commands::Sun {
+            city,
+            lat,
+            lon,
+            output,
+            lang,
+        } => run_sun_command(
+            config,
+            providers,
+            now_fn,
+            LocationCommandArgs {
+                command: "weather.sun",
+                city: city.as_deref(),
+                lat,
+                lon,
+                output,
+                lang,
+                units: None,
+            },
+        ),
+        Commands::Rain {
+            city,
+            lat,
+            lon,
+            output,
+            lang,
+            units,
+        } => run_rain_command(
+            config,
+            providers,
+            now_fn,
+            LocationCommandArgs {
+                command: "weather.rain",
+                city: city.as_deref(),
+                lat,
+                lon,
+                output,
+                lang,
+                units,
+            },
+        ),
+        Commands::History {
+            date,
+            city,
+            lat,
+            lon,
+            output,
+            lang,
+            units,
+        } => run_history_command(
+            config,
+            providers,
+            now_fn,
+            HistoryCommandArgs {
+                command: "weather.history",
+                date: &date,
+                city: city.as_deref(),
+                lat,
+                lon,
+                output,
+                lang,
+                units,
+            },
+        ),
+        Commands::Locations { output, lang } => {
+            run_locations_command(config, "weather.locations", output, lang)
+        }
+        Commands::Compare {
+            city,
+            output,
+            lang,
+            units,
+        } => run_compare_command(
+            config,
+            providers,
+            now_fn,
+            CompareCommandArgs {
+                command: "weather.compare",
+                cities: &city,
+                output,
+                lang,
+                units,
+            },
+        ),
+        Commands::Warm {
+            city,
+            lat,
+            lon,
+            output,
+        } => run_warm_command(
+            config,
+            providers,
+            now_fn,
+            WarmCommandArgs {
+                command: "weather.warm",
+                cities: &city,
+                lat,
+                lon,
+                output,
+            },
+        ),
+        Commands::Notify {
+            city,
+            lat,
+            lon,
+            when,
+            output,
+            units,
+            dry_run,
+        } => run_notify_command(
+            config,
+            providers,
+            now_fn,
+            NotifyCommandArgs {
+                command: "weather.notify",
+                city: city.as_deref(),
+                lat,
+                lon,
+                when: &when,
+                output,
+                units,
+                dry_run,
+            },
+        ),
     }
 }
 
@@ -279,7 +638,9 @@ struct CommandArgs<'a> {
     lat: Option<f64>,
     lon: Option<f64>,
     output: OutputModeArg,
-    lang: Option<LanguageArg>,
+    lang: Option<Locale>,
+    units: Option<UnitsArg>,
+    window: Option<DayWindow>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -289,10 +650,64 @@ struct HourlyCommandArgs<'a> {
     lat: Option<f64>,
     lon: Option<f64>,
     output: OutputModeArg,
-    lang: Option<LanguageArg>,
+    lang: Option<Locale>,
+    units: Option<UnitsArg>,
     hours: usize,
 }
 
+#[derive(Debug, Clone, Copy)]
+struct HistoryCommandArgs<'a> {
+    command: &'static str,
+    date: &'a str,
+    city: Option<&'a str>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    output: OutputModeArg,
+    lang: Option<Locale>,
+    units: Option<UnitsArg>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CompareCommandArgs<'a> {
+    command: &'static str,
+    cities: &'a [String],
+    output: OutputModeArg,
+    lang: Option<Locale>,
+    units: Option<UnitsArg>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WarmCommandArgs<'a> {
+    command: &'static str,
+    cities: &'a [String],
+    lat: Option<f64>,
+    lon: Option<f64>,
+    output: OutputModeArg,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NotifyCommandArgs<'a> {
+    command: &'static str,
+    city: Option<&'a str>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    when: &'a str,
+    output: OutputModeArg,
+    units: Option<UnitsArg>,
+    dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LocationCommandArgs<'a> {
+    command: &'static str,
+    city: Option<&'a str>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    output: OutputModeArg,
+    lang: Option<Locale>,
+    units: Option<UnitsArg>,
+}
+
 fn run_command<P, N>(
     config: &RuntimeConfig,
     providers: &P,
@@ -304,7 +719,8 @@ where
     N: Fn() -> DateTime<Utc> + Copy,
 {
     let output_mode: OutputMode = args.output.into();
-    let output_language = args.lang.map(Into::into).unwrap_or(OutputLanguage::En);
+    let output_language = args.lang.unwrap_or(config.language);
+    let units = args.units.map(Into::into).unwrap_or(config.units);
 
     if args.cities.len() > 1 {
         if args.lat.is_some() || args.lon.is_some() {
@@ -314,19 +730,29 @@ where
             ));
         }
 
-        let output = batch_service::resolve_forecast_batch(
-            config,
-            providers,
-            now_fn,
-            args.period,
-            args.cities,
-        )
-        .map_err(map_app_error)?;
+        let cities = expand_favorite_cities(config, args.cities)?;
+        let mut output =
+            batch_service::resolve_forecast_batch(config, providers, now_fn, args.period, &cities)
+                .map_err(map_app_error)?;
+        if let Some(window) = args.window {
+            apply_day_window_to_batch(&mut output, window);
+        }
 
         return match output_mode {
-            OutputMode::Json => render_batch_json_envelope(args.command, &output),
-            OutputMode::Human => Ok(format_batch_text_output(&output, output_language)),
-            OutputMode::AlfredJson => render_batch_alfred_json(&output, output_language, now_fn()),
+            OutputMode::Json => render_batch_json_envelope(args.command, &output, units),
+            OutputMode::Human => Ok(format_batch_text_output(
+                &output,
+                output_language,
+                units,
+                config.extended_detail,
+            )),
+            OutputMode::AlfredJson => render_batch_alfred_json(
+                &output,
+                output_language,
+                units,
+                now_fn(),
+                config.extended_detail,
+            ),
         };
     }
 
@@ -334,21 +760,59 @@ where
         OutputMode::Json => RequestOutputMode::Json,
         OutputMode::Human | OutputMode::AlfredJson => RequestOutputMode::Text,
     };
-    let request = ForecastRequest::new(
-        args.period,
+    let input = resolve_location_input(
+        config,
+        providers,
+        now_fn,
         args.cities.first().map(String::as_str),
         args.lat,
         args.lon,
+    )?;
+    let request = ForecastRequest::new(
+        args.period,
+        input.city.as_deref(),
+        input.lat,
+        input.lon,
         request_mode,
     )
     .map_err(user_invalid_input)?;
-    let output =
+    let mut output =
         service::resolve_forecast(config, providers, now_fn, &request).map_err(map_app_error)?;
+    if let Some(window) = args.window {
+        output.forecast = window.apply(&output.forecast).map_err(user_invalid_input)?;
+    }
 
     match output_mode {
-        OutputMode::Json => render_service_json_envelope(args.command, &output),
-        OutputMode::Human => Ok(format_text_output(&output, output_language)),
-        OutputMode::AlfredJson => render_alfred_json(&output, output_language, now_fn()),
+        OutputMode::Json => render_service_json_envelope(args.command, &output, units),
+        OutputMode::Human => Ok(format_text_output(
+            &output,
+            output_language,
+            units,
+            config.extended_detail,
+        )),
+        OutputMode::AlfredJson => render_alfred_json(
+            &output,
+            output_language,
+            units,
+            now_fn(),
+            config.extended_detail,
+        ),
+    }
+}
+
+/// A city whose week has no days in the window becomes an error entry, like any other per-city failure.
+fn apply_day_window_to_batch(output: &mut ForecastBatchOutput, window: DayWindow) {
+    for entry in &mut output.entries {
+        let Some(result) = entry.result.as_mut() else {
+            continue;
+        };
+        match window.apply(&result.forecast) {
+            Ok(days) => result.forecast = days,
+            Err(error) => {
+                entry.result = None;
+                entry.error = Some(error.to_string());
+            }
+        }
     }
 }
 
@@ -363,40 +827,475 @@ where
     N: Fn() -> DateTime<Utc> + Copy,
 {
     let output_mode: OutputMode = args.output.into();
-    let output_language = args.lang.map(Into::into).unwrap_or(OutputLanguage::En);
-    let location = resolve_location_query(args.city, args.lat, args.lon)?;
+    let output_language = args.lang.unwrap_or(config.language);
+    let units = args.units.map(Into::into).unwrap_or(config.units);
+    let location =
+        resolve_location_query(config, providers, now_fn, args.city, args.lat, args.lon)?;
     let output =
         hourly_service::resolve_hourly_forecast(config, providers, now_fn, &location, args.hours)
             .map_err(map_app_error)?;
 
     match output_mode {
-        OutputMode::Json => render_hourly_json_envelope(args.command, &output),
-        OutputMode::Human => Ok(format_hourly_text_output(&output, output_language)),
-        OutputMode::AlfredJson => render_hourly_alfred_json(&output, output_language),
+        OutputMode::Json => render_hourly_json_envelope(args.command, &output, units),
+        OutputMode::Human => Ok(format_hourly_text_output(&output, output_language, units)),
+        OutputMode::AlfredJson => render_hourly_alfred_json(&output, output_language, units),
     }
 }
 
-fn resolve_location_query(
-    city: Option<&str>,
-    lat: Option<f64>,
-    lon: Option<f64>,
-) -> Result<LocationQuery, CliError> {
-    let request = ForecastRequest::new(
-        ForecastPeriod::Hourly,
-        city,
-        lat,
-        lon,
-        RequestOutputMode::Json,
+fn run_now_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: LocationCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let output_mode: OutputMode = args.output.into();
+    let output_language = args.lang.unwrap_or(config.language);
+    let units = args.units.map(Into::into).unwrap_or(config.units);
+    let location =
+        resolve_location_query(config, providers, now_fn, args.city, args.lat, args.lon)?;
+    let output = current_service::resolve_current_weather(config, providers, now_fn, &location)
+        .map_err(map_app_error)?;
+
+    match output_mode {
+        OutputMode::Json => render_current_json_envelope(args.command, &output, units),
+        OutputMode::Human => Ok(format_current_text_output(&output, output_language, units)),
+        OutputMode::AlfredJson => {
+            render_current_alfred_json(&output, output_language, units, now_fn())
+        }
+    }
+}
+
+fn run_sun_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: LocationCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let output_mode: OutputMode = args.output.into();
+    let output_language = args.lang.unwrap_or(config.language);
+    let input = resolve_location_input(config, providers, now_fn, args.city, args.lat, args.lon)?;
+    let request = ForecastRequest::new(
+        ForecastPeriod::Week,
+        input.city.as_deref(),
+        input.lat,
+        input.lon,
+        RequestOutputMode::Json,
+    )
+    .map_err(user_invalid_input)?;
+    let forecast =
+        service::resolve_forecast(config, providers, now_fn, &request).map_err(map_app_error)?;
+    let output = astronomy::sun_output(&forecast);
+    if output.days.is_empty() {
+        return Err(runtime_error(
+            ERROR_CODE_RUNTIME_PROVIDER_FAILED,
+            format!(
+                "sunrise/sunset times are not available from {}",
+                output.source
+            ),
+        ));
+    }
+
+    match output_mode {
+        OutputMode::Json => render_sun_json_envelope(args.command, &output),
+        OutputMode::Human => Ok(format_sun_text_output(&output, output_language)),
+        OutputMode::AlfredJson => render_sun_alfred_json(&output, output_language),
+    }
+}
+
+fn run_rain_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: LocationCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let output_mode: OutputMode = args.output.into();
+    let output_language = args.lang.unwrap_or(config.language);
+    let units = args.units.map(Into::into).unwrap_or(config.units);
+    let location =
+        resolve_location_query(config, providers, now_fn, args.city, args.lat, args.lon)?;
+    let output = nowcast_service::resolve_nowcast(config, providers, now_fn, &location)
+        .map_err(map_app_error)?;
+    if output.points.is_empty() {
+        return Err(runtime_error(
+            ERROR_CODE_RUNTIME_PROVIDER_FAILED,
+            format!(
+                "no precipitation steps left in the {} nowcast",
+                output.source
+            ),
+        ));
+    }
+
+    match output_mode {
+        OutputMode::Json => render_rain_json_envelope(args.command, &output, units),
+        OutputMode::Human => Ok(format_rain_text_output(&output, output_language, units)),
+        OutputMode::AlfredJson => render_rain_alfred_json(&output, output_language, units),
+    }
+}
+
+fn run_compare_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: CompareCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let output_mode: OutputMode = args.output.into();
+    let output_language = args.lang.unwrap_or(config.language);
+    let units = args.units.map(Into::into).unwrap_or(config.units);
+    let [left, right] = expand_favorite_cities(config, args.cities)?
+        .try_into()
+        .map_err(|_| {
+            user_error(
+                ERROR_CODE_USER_INVALID_INPUT,
+                "compare needs exactly two --city values",
+            )
+        })?;
+    let output = compare_service::resolve_comparison(config, providers, now_fn, &left, &right)
+        .map_err(map_app_error)?;
+
+    match output_mode {
+        OutputMode::Json => render_compare_json_envelope(args.command, &output, units),
+        OutputMode::Human => Ok(format_compare_text_output(&output, output_language, units)),
+        OutputMode::AlfredJson => render_compare_alfred_json(&output, output_language, units),
+    }
+}
+
+fn run_history_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: HistoryCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let output_mode: OutputMode = args.output.into();
+    let output_language = args.lang.unwrap_or(config.language);
+    let units = args.units.map(Into::into).unwrap_or(config.units);
+    let date = NaiveDate::parse_from_str(args.date.trim(), "%Y-%m-%d").map_err(|_| {
+        user_error(
+            ERROR_CODE_USER_INVALID_INPUT,
+            format!("invalid --date '{}': expected YYYY-MM-DD", args.date),
+        )
+    })?;
+    let location =
+        resolve_location_query(config, providers, now_fn, args.city, args.lat, args.lon)?;
+    let output = history_service::resolve_history(config, providers, now_fn, &location, date)
+        .map_err(map_app_error)?;
+
+    match output_mode {
+        OutputMode::Json => render_history_json_envelope(args.command, &output, units),
+        OutputMode::Human => Ok(format_history_text_output(&output, output_language, units)),
+        OutputMode::AlfredJson => render_history_alfred_json(&output, output_language, units),
+    }
+}
+
+fn run_locations_command(
+    config: &RuntimeConfig,
+    command: &str,
+    output: OutputModeArg,
+    lang: Option<Locale>,
+) -> Result<String, CliError> {
+    let output_language = lang.unwrap_or(config.language);
+    let favorites = &config.favorite_locations;
+
+    match output.into() {
+        OutputMode::Json => render_locations_json_envelope(command, favorites),
+        OutputMode::Human => Ok(format_locations_text_output(favorites, output_language)),
+        OutputMode::AlfredJson => render_locations_alfred_json(favorites, output_language),
+    }
+}
+
+fn run_warm_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: WarmCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    if config.offline {
+        return Err(user_error(
+            ERROR_CODE_USER_INVALID_INPUT,
+            "warm refreshes caches from the network and cannot run in offline mode",
+        ));
+    }
+    let locations = warm_locations(config, providers, now_fn, args.cities, args.lat, args.lon)?;
+    let warmed = warm_service::warm_caches(config, providers, now_fn, &locations);
+
+    match args.output.into() {
+        OutputMode::Json => render_warm_json_envelope(args.command, &warmed),
+        OutputMode::Human => match warm_failure_summary(&warmed) {
+            Some(summary) => Err(runtime_error(ERROR_CODE_RUNTIME_PROVIDER_FAILED, summary)),
+            None => Ok(String::new()),
+        },
+        OutputMode::AlfredJson => render_warm_alfred_json(&warmed),
+    }
+}
+
+fn run_notify_command<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    args: NotifyCommandArgs<'_>,
+) -> Result<String, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc> + Copy,
+{
+    let units = args.units.map(Into::into).unwrap_or(config.units);
+    let condition = Condition::parse(args.when).map_err(user_invalid_input)?;
+    let input = resolve_location_input(config, providers, now_fn, args.city, args.lat, args.lon)?;
+    let request = ForecastRequest::new(
+        ForecastPeriod::Today,
+        input.city.as_deref(),
+        input.lat,
+        input.lon,
+        RequestOutputMode::Json,
+    )
+    .map_err(user_invalid_input)?;
+    let mut output =
+        notify_service::resolve_alert(config, providers, now_fn, &request, &condition, units)
+            .map_err(map_app_error)?;
+
+    if output.triggered && !args.dry_run {
+        notify_service::post_notification(&output.title, &output.message)
+            .map_err(|error| runtime_error(ERROR_CODE_RUNTIME_NOTIFY, error.message))?;
+        output.notified = true;
+    }
+
+    match args.output.into() {
+        OutputMode::Json => render_notify_json_envelope(args.command, &output),
+        OutputMode::Human if output.triggered => {
+            Ok(format!("{}: {}", output.title, output.message))
+        }
+        OutputMode::Human => Ok(String::new()),
+        OutputMode::AlfredJson => render_notify_alfred_json(&output),
+    }
+}
+
+/// `--city` (repeatable, favorites expanded) or one coordinate pair; with neither, every saved favorite, and
+/// without favorites the automatic location.
+fn warm_locations<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    cities: &[String],
+    lat: Option<f64>,
+    lon: Option<f64>,
+) -> Result<Vec<LocationQuery>, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    if cities.is_empty() {
+        if lat.is_none() && lon.is_none() && !config.favorite_locations.is_empty() {
+            return Ok(config
+                .favorite_locations
+                .iter()
+                .map(FavoriteLocation::location)
+                .collect());
+        }
+        return resolve_location_query(config, providers, now_fn, None, lat, lon)
+            .map(|location| vec![location]);
+    }
+
+    let mut locations: Vec<LocationQuery> = Vec::with_capacity(cities.len());
+    for city in cities {
+        let input = expand_favorite(config, Some(city), lat, lon);
+        let location = ForecastRequest::new(
+            ForecastPeriod::Today,
+            input.city.as_deref(),
+            input.lat,
+            input.lon,
+            RequestOutputMode::Json,
+        )
+        .map_err(user_invalid_input)?
+        .location;
+        let duplicate = locations.iter().any(|seen| match (seen, &location) {
+            (LocationQuery::City(seen), LocationQuery::City(city)) => {
+                seen.eq_ignore_ascii_case(city)
+            }
+            (seen, location) => seen == location,
+        });
+        if !duplicate {
+            locations.push(location);
+        }
+    }
+    Ok(locations)
+}
+
+/// `failed to refresh 2 of 5 cache entries: Taipei/now: ...; Taipei/rain: ...`, or `None` when all succeeded.
+fn warm_failure_summary(output: &WarmOutput) -> Option<String> {
+    let failures = output
+        .failures()
+        .map(|entry| {
+            format!(
+                "{}/{}: {}",
+                entry.location,
+                entry.period.as_str(),
+                entry.error.as_deref().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    if failures.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "failed to refresh {} of {} cache entries: {}",
+        failures.len(),
+        output.entries.len(),
+        failures.join("; ")
+    ))
+}
+
+fn resolve_location_query<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    city: Option<&str>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+) -> Result<LocationQuery, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let input = resolve_location_input(config, providers, now_fn, city, lat, lon)?;
+    let request = ForecastRequest::new(
+        ForecastPeriod::Hourly,
+        input.city.as_deref(),
+        input.lat,
+        input.lon,
+        RequestOutputMode::Json,
     )
     .map_err(user_invalid_input)?;
     Ok(request.location)
 }
 
+/// Location flags after expanding a `--city` that names a `WEATHER_LOCATIONS` favorite.
+#[derive(Debug, Clone, PartialEq)]
+struct LocationInput {
+    city: Option<String>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+}
+
+/// Without any location flag, fall back to IP geolocation when `WEATHER_AUTO_LOCATE` opted in.
+fn resolve_location_input<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    city: Option<&str>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+) -> Result<LocationInput, CliError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    if city.is_none()
+        && lat.is_none()
+        && lon.is_none()
+        && let Some(location) =
+            auto_locate::resolve_auto_location(config, providers, now_fn).map_err(map_app_error)?
+    {
+        return Ok(match location {
+            LocationQuery::City(city) => LocationInput {
+                city: Some(city),
+                lat: None,
+                lon: None,
+            },
+            LocationQuery::Coordinates { lat, lon } => LocationInput {
+                city: None,
+                lat: Some(lat),
+                lon: Some(lon),
+            },
+        });
+    }
+
+    Ok(expand_favorite(config, city, lat, lon))
+}
+
+fn expand_favorite(
+    config: &RuntimeConfig,
+    city: Option<&str>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+) -> LocationInput {
+    let favorite = city.and_then(|name| find_favorite(&config.favorite_locations, name));
+    match favorite.map(FavoriteLocation::location) {
+        Some(LocationQuery::City(city)) => LocationInput {
+            city: Some(city),
+            lat,
+            lon,
+        },
+        Some(LocationQuery::Coordinates {
+            lat: saved_lat,
+            lon: saved_lon,
+        }) if lat.is_none() && lon.is_none() => LocationInput {
+            city: None,
+            lat: Some(saved_lat),
+            lon: Some(saved_lon),
+        },
+        _ => LocationInput {
+            city: city.map(str::to_string),
+            lat,
+            lon,
+        },
+    }
+}
+
+/// Batch mode is city-only, so favorites saved as coordinates cannot join it.
+fn expand_favorite_cities(
+    config: &RuntimeConfig,
+    cities: &[String],
+) -> Result<Vec<String>, CliError> {
+    cities
+        .iter()
+        .map(|city| {
+            let Some(favorite) = find_favorite(&config.favorite_locations, city) else {
+                return Ok(city.clone());
+            };
+            match favorite.location() {
+                LocationQuery::City(saved_city) => Ok(saved_city),
+                LocationQuery::Coordinates { .. } => Err(user_error(
+                    ERROR_CODE_USER_INVALID_INPUT,
+                    format!(
+                        "favorite location `{}` is saved as coordinates; query it on its own",
+                        favorite.name
+                    ),
+                )),
+            }
+        })
+        .collect()
+}
+
 fn render_service_json_envelope(
     command: &str,
     output: &ForecastOutput,
+    units: UnitSystem,
 ) -> Result<String, CliError> {
-    let result = serde_json::to_string(output).map_err(|error| {
+    let result = serialize_result(output, units).map_err(|error| {
         runtime_error(
             ERROR_CODE_RUNTIME_SERIALIZE,
             format!("failed to serialize output: {error}"),
@@ -412,8 +1311,9 @@ fn render_service_json_envelope(
 fn render_hourly_json_envelope(
     command: &str,
     output: &HourlyForecastOutput,
+    units: UnitSystem,
 ) -> Result<String, CliError> {
-    let result = serde_json::to_string(output).map_err(|error| {
+    let result = serialize_result(output, units).map_err(|error| {
         runtime_error(
             ERROR_CODE_RUNTIME_SERIALIZE,
             format!("failed to serialize output: {error}"),
@@ -426,14 +1326,15 @@ fn render_hourly_json_envelope(
     ))
 }
 
-fn render_batch_json_envelope(
+fn render_current_json_envelope(
     command: &str,
-    output: &ForecastBatchOutput,
+    output: &CurrentWeatherOutput,
+    units: UnitSystem,
 ) -> Result<String, CliError> {
-    let result = serde_json::to_string(output).map_err(|error| {
+    let result = serialize_result(output, units).map_err(|error| {
         runtime_error(
             ERROR_CODE_RUNTIME_SERIALIZE,
-            format!("failed to serialize batch output: {error}"),
+            format!("failed to serialize output: {error}"),
         )
     })?;
     Ok(build_success_envelope(
@@ -443,51 +1344,265 @@ fn render_batch_json_envelope(
     ))
 }
 
-fn render_alfred_json(
-    output: &ForecastOutput,
-    language: OutputLanguage,
-    now: DateTime<Utc>,
-) -> Result<String, CliError> {
-    let mut items = Vec::with_capacity(output.forecast.len() + 1);
-    items.push(alfred_header_item(
-        &output.location.name,
-        &output.timezone,
-        output.location.latitude,
-        output.location.longitude,
-        &output.source,
-        output.freshness.status,
-    ));
-    for day in &output.forecast {
-        let summary = localized_summary(day, language);
-        let date_with_weekday = format_date_with_weekday(&day.date, language);
-        let weekday_label = weekday_label_for_date(&day.date, language);
-        let utc_offset_label = timezone_offset_label_for_date(&output.timezone, &day.date);
-        let timezone_display =
-            timezone_display_label(&output.timezone, utc_offset_label.as_deref());
-        let icon_key = if output.period == ForecastPeriod::Today {
-            weather_cli::weather_icon::current_conditions_icon_key(
-                day.weather_code,
-                &output.timezone,
-                now,
-            )
-        } else {
-            weather_cli::weather_icon::daily_forecast_icon_key(day.weather_code)
-        };
+/// Imperial results replace metric fields with converted ones (see `UnitSystem::convert_json`) and add `units`.
+fn serialize_result<T: serde::Serialize>(
+    output: &T,
+    units: UnitSystem,
+) -> Result<String, serde_json::Error> {
+    if units == UnitSystem::Metric {
+        return serde_json::to_string(output);
+    }
 
-        items.push(json!({
-            "title": format!(
-                "{} {} {:.1}~{:.1}°C",
-                date_with_weekday, summary, day.temp_min_c, day.temp_max_c
-            ),
-            "subtitle": format!("{}:{}%", precip_label(language), day.precip_prob_max_pct),
-            "arg": day.date,
-            "valid": false,
-            "icon": {
-                "path": icon_path(icon_key),
-            },
-            "weather_meta": {
-                "item_kind": "daily",
-                "date": day.date,
+    let mut value = serde_json::to_value(output)?;
+    units.convert_json(&mut value);
+    if let Some(result) = value.as_object_mut() {
+        result.insert("units".to_string(), json!(units.as_str()));
+    }
+    serde_json::to_string(&value)
+}
+
+fn render_rain_json_envelope(
+    command: &str,
+    output: &NowcastOutput,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let result = serialize_result(output, units).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+fn render_compare_json_envelope(
+    command: &str,
+    output: &CompareOutput,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let result = serialize_result(output, units).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+fn render_history_json_envelope(
+    command: &str,
+    output: &HistoryOutput,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let result = serialize_result(output, units).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+fn render_sun_json_envelope(command: &str, output: &SunOutput) -> Result<String, CliError> {
+    let result = serde_json::to_string(output).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+fn render_locations_json_envelope(
+    command: &str,
+    favorites: &[FavoriteLocation],
+) -> Result<String, CliError> {
+    let result = serde_json::to_string(&json!({ "locations": favorites })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+fn render_warm_json_envelope(command: &str, output: &WarmOutput) -> Result<String, CliError> {
+    let result = serde_json::to_string(output).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+/// One informational row summarizing the refresh, with failures (if any) in the subtitle.
+fn render_warm_alfred_json(output: &WarmOutput) -> Result<String, CliError> {
+    let failed = output.failures().count();
+    let refreshed = output.entries.len() - failed;
+    let item = json!({
+        "title": format!("Refreshed {refreshed} of {} weather cache entries", output.entries.len()),
+        "subtitle": warm_failure_summary(output).unwrap_or_else(|| "all cache entries are fresh".to_string()),
+        "valid": false,
+        "icon": {
+            "path": icon_path(FALLBACK_ICON_KEY),
+        },
+        "weather_meta": {
+            "item_kind": "warm",
+            "refreshed": refreshed,
+            "failed": failed,
+        },
+    });
+
+    serde_json::to_string(&json!({ "items": [item] })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+fn render_notify_json_envelope(command: &str, output: &NotifyOutput) -> Result<String, CliError> {
+    let result = serde_json::to_string(output).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+/// One informational row: the alert when the condition holds, otherwise the checked values.
+fn render_notify_alfred_json(output: &NotifyOutput) -> Result<String, CliError> {
+    let title = if output.triggered {
+        output.title.clone()
+    } else {
+        format!("No weather alert: {}", output.location.name)
+    };
+    let item = json!({
+        "title": title,
+        "subtitle": format!("{} ({})", output.message, output.condition),
+        "valid": false,
+        "icon": {
+            "path": icon_path(FALLBACK_ICON_KEY),
+        },
+        "weather_meta": {
+            "item_kind": "notify",
+            "triggered": output.triggered,
+            "notified": output.notified,
+        },
+    });
+
+    serde_json::to_string(&json!({ "items": [item] })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+fn render_batch_json_envelope(
+    command: &str,
+    output: &ForecastBatchOutput,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let result = serialize_result(output, units).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize batch output: {error}"),
+        )
+    })?;
+    Ok(build_success_envelope(
+        command,
+        EnvelopePayloadKind::Result,
+        &result,
+    ))
+}
+
+fn render_alfred_json(
+    output: &ForecastOutput,
+    language: Locale,
+    units: UnitSystem,
+    now: DateTime<Utc>,
+    extended_detail: bool,
+) -> Result<String, CliError> {
+    let daily_icon_key = |day: &ForecastDay| {
+        if output.period == ForecastPeriod::Today {
+            weather_cli::weather_icon::current_conditions_icon_key(
+                day.weather_code,
+                &output.timezone,
+                now,
+            )
+        } else {
+            weather_cli::weather_icon::daily_forecast_icon_key(day.weather_code)
+        }
+    };
+    let mut items = Vec::with_capacity(output.forecast.len() + 1);
+    items.push(alfred_header_item(
+        &output.location.name,
+        &output.timezone,
+        output.location.latitude,
+        output.location.longitude,
+        &output.source,
+        output.freshness.status,
+        output
+            .forecast
+            .first()
+            .map_or(FALLBACK_ICON_KEY, daily_icon_key),
+    ));
+    for day in &output.forecast {
+        let summary = localized_summary(day, language);
+        let date_with_weekday = format_date_with_weekday(&day.date, language);
+        let weekday_label = weekday_label_for_date(&day.date, language);
+        let utc_offset_label = timezone_offset_label_for_date(&output.timezone, &day.date);
+        let timezone_display =
+            timezone_display_label(&output.timezone, utc_offset_label.as_deref());
+        let icon_key = daily_icon_key(day);
+
+        items.push(json!({
+            "title": format!(
+                "{} {} {}",
+                date_with_weekday,
+                summary,
+                temp_range_label(day, units)
+            ),
+            "subtitle": daily_subtitle(output.period, day, language, units, extended_detail),
+            "arg": day.date,
+            "valid": false,
+            "icon": {
+                "path": icon_path(icon_key),
+            },
+            "weather_meta": {
+                "item_kind": "daily",
+                "date": day.date,
                 "date_with_weekday": date_with_weekday,
                 "weekday_label": weekday_label,
                 "utc_offset_label": utc_offset_label,
@@ -502,6 +1617,16 @@ fn render_alfred_json(
                 "temp_max_c_label": format!("{:.1}", day.temp_max_c),
                 "precip_prob_max_pct": day.precip_prob_max_pct,
                 "precip_prob_max_pct_label": day.precip_prob_max_pct.to_string(),
+                "precip_sum_mm": day.precip_sum_mm,
+                "precip_sum_label": day.precip_sum_mm.map(|mm| units.length_label(mm)),
+                "units": units.as_str(),
+                "temp_unit": units.temperature_unit(),
+                "temp_min_label": units.temperature_label(day.temp_min_c),
+                "temp_max_label": units.temperature_label(day.temp_max_c),
+                "sunrise": day.sunrise,
+                "sunset": day.sunset,
+                "moon_phase": day.moon_phase,
+                "moon_illumination_pct": day.moon_illumination_pct,
             },
         }));
     }
@@ -516,8 +1641,10 @@ fn render_alfred_json(
 
 fn render_batch_alfred_json(
     output: &ForecastBatchOutput,
-    language: OutputLanguage,
+    language: Locale,
+    units: UnitSystem,
     now: DateTime<Utc>,
+    extended_detail: bool,
 ) -> Result<String, CliError> {
     let mut items = Vec::new();
 
@@ -528,8 +1655,10 @@ fn render_batch_alfred_json(
                     result,
                     day,
                     language,
+                    units,
                     now,
                     output.period == ForecastPeriod::Today,
+                    extended_detail,
                 ));
             }
             continue;
@@ -539,6 +1668,9 @@ fn render_batch_alfred_json(
             "title": format!("{}: forecast error", entry.city),
             "subtitle": entry.error.as_deref().unwrap_or("failed to fetch forecast"),
             "valid": false,
+            "icon": {
+                "path": icon_path(FALLBACK_ICON_KEY),
+            },
         }));
     }
 
@@ -552,7 +1684,8 @@ fn render_batch_alfred_json(
 
 fn render_hourly_alfred_json(
     output: &HourlyForecastOutput,
-    language: OutputLanguage,
+    language: Locale,
+    units: UnitSystem,
 ) -> Result<String, CliError> {
     let mut items = Vec::with_capacity(output.hourly.len() + 1);
     items.push(alfred_header_item(
@@ -562,6 +1695,9 @@ fn render_hourly_alfred_json(
         output.location.longitude,
         &output.source,
         output.freshness.status,
+        output.hourly.first().map_or(FALLBACK_ICON_KEY, |hour| {
+            weather_cli::weather_icon::hourly_forecast_icon_key(hour.weather_code, &hour.datetime)
+        }),
     ));
 
     for hour in &output.hourly {
@@ -576,10 +1712,11 @@ fn render_hourly_alfred_json(
             timezone_display_label(&output.timezone, utc_offset_label.as_deref());
         items.push(json!({
             "title": format!(
-                "{} {} {:.1}°C",
+                "{} {} {}{}",
                 display_hour_label(&hour.datetime, language),
                 summary,
-                hour.temp_c
+                units.temperature_label(hour.temp_c),
+                units.temperature_unit()
             ),
             "subtitle": format!("{}:{}%", precip_label(language), hour.precip_prob_pct),
             "arg": hour.datetime,
@@ -602,6 +1739,9 @@ fn render_hourly_alfred_json(
                 "is_night": weather_cli::weather_icon::is_night_icon_key(icon_key),
                 "temp_c": hour.temp_c,
                 "temp_c_label": format!("{:.1}", hour.temp_c),
+                "units": units.as_str(),
+                "temp_unit": units.temperature_unit(),
+                "temp_label": units.temperature_label(hour.temp_c),
                 "precip_prob_pct": hour.precip_prob_pct,
                 "precip_prob_pct_label": hour.precip_prob_pct.to_string(),
             },
@@ -616,48 +1756,419 @@ fn render_hourly_alfred_json(
     })
 }
 
-fn emit_error(command: &str, output_mode: OutputMode, error: &CliError) {
-    match output_mode {
-        OutputMode::Json => {
-            let details = build_error_details_json(error_kind_label(error.kind), error.exit_code());
-            println!(
-                "{}",
-                build_error_envelope(command, error.code, &error.message, Some(&details))
-            );
-        }
-        OutputMode::AlfredJson => {
-            println!(
-                "{}",
-                build_alfred_error_feedback(error.code, &error.message)
-            );
-        }
-        OutputMode::Human => {
-            eprintln!(
-                "error[{}]: {}",
-                error.code,
-                redact_sensitive(&error.message)
-            );
-        }
-    }
-}
-
-fn user_invalid_input(error: weather_cli::model::ValidationError) -> CliError {
-    user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string())
-}
+/// One row for hotkey use: the whole reading in the title, details in the subtitle.
+fn render_current_alfred_json(
+    output: &CurrentWeatherOutput,
+    language: Locale,
+    units: UnitSystem,
+    now: DateTime<Utc>,
+) -> Result<String, CliError> {
+    let current = &output.current;
+    let summary = localized_summary_by_code(current.weather_code, language);
+    let icon_key = weather_cli::weather_icon::current_conditions_icon_key(
+        current.weather_code,
+        &output.timezone,
+        now,
+    );
+    let title = format!(
+        "{} {}{} {}",
+        output.location.name,
+        units.temperature_label(current.temp_c),
+        units.temperature_unit(),
+        summary
+    );
+    let item = json!({
+        "title": title,
+        "subtitle": format!(
+            "{} · {} · {}",
+            current_details_label(output, language, units),
+            current.observed_at.replace('T', " "),
+            freshness_label(output.freshness.status)
+        ),
+        "arg": title,
+        "valid": true,
+        "icon": {
+            "path": icon_path(icon_key),
+        },
+        "weather_meta": {
+            "item_kind": "current",
+            "location_name": output.location.name,
+            "timezone": output.timezone,
+            "observed_at": current.observed_at,
+            "summary": summary,
+            "weather_code": current.weather_code,
+            "icon_key": icon_key,
+            "is_night": weather_cli::weather_icon::is_night_icon_key(icon_key),
+            "temp_c": current.temp_c,
+            "temp_c_label": format!("{:.1}", current.temp_c),
+            "apparent_temp_c": current.apparent_temp_c,
+            "apparent_temp_c_label": format!("{:.1}", current.apparent_temp_c),
+            "units": units.as_str(),
+            "temp_unit": units.temperature_unit(),
+            "temp_label": units.temperature_label(current.temp_c),
+            "apparent_temp_label": units.temperature_label(current.apparent_temp_c),
+            "humidity_pct": current.humidity_pct,
+            "wind_speed_kmh": current.wind_speed_kmh,
+            "wind_speed_label": units.speed_label(current.wind_speed_kmh),
+            "wind_direction_deg": current.wind_direction_deg,
+            "wind_direction_label": compass_label(current.wind_direction_deg),
+        },
+    });
 
-fn user_error(code: &'static str, message: impl Into<String>) -> CliError {
-    CliError::user(code, message)
+    serde_json::to_string(&json!({ "items": [item] })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
 }
 
-fn runtime_error(code: &'static str, message: impl Into<String>) -> CliError {
-    CliError::runtime(code, message)
-}
+/// A summary row (outlook and sparkline) followed by one informational row per 15-minute step.
+fn render_rain_alfred_json(
+    output: &NowcastOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let outlook = rain_outlook_label(&output.outlook, language);
+    let mut items = Vec::with_capacity(output.points.len() + 1);
+    items.push(json!({
+        "title": format!("{} {}", output.location.name, outlook),
+        "subtitle": format!(
+            "{} {} · {}",
+            output.sparkline,
+            rain_peak_label(output, language, units),
+            freshness_label(output.freshness.status)
+        ),
+        "arg": outlook,
+        "valid": true,
+        "icon": {
+            "path": icon_path(rain_icon_key(&output.outlook)),
+        },
+        "weather_meta": {
+            "item_kind": "rain_summary",
+            "location_name": output.location.name,
+            "timezone": output.timezone,
+            "outlook": output.outlook,
+            "outlook_label": outlook,
+            "sparkline": output.sparkline,
+            "interval_minutes": output.interval_minutes,
+        },
+    }));
 
-fn map_app_error(error: AppError) -> CliError {
-    match error.kind {
-        weather_cli::error::ErrorKind::User => {
-            user_error(ERROR_CODE_USER_INVALID_INPUT, error.message)
-        }
+    for point in &output.points {
+        let (date, time) = split_datetime_label(&point.datetime);
+        let wet = point.precip_mm >= nowcast_service::RAIN_THRESHOLD_MM;
+        items.push(json!({
+            "title": format!(
+                "{} {}:{}",
+                time,
+                precip_label(language),
+                units.length_label(point.precip_mm)
+            ),
+            "subtitle": format_date_with_weekday(date, language),
+            "arg": point.datetime,
+            "valid": false,
+            "icon": {
+                "path": icon_path(if wet { "rain" } else { "cloudy" }),
+            },
+            "weather_meta": {
+                "item_kind": "rain",
+                "date": date,
+                "time": time,
+                "datetime": point.datetime,
+                "precip_mm": point.precip_mm,
+                "units": units.as_str(),
+                "precip_label": units.length_label(point.precip_mm),
+            },
+        }));
+    }
+
+    serde_json::to_string(&json!({ "items": items })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+/// One row per city, then one difference row per paired day.
+fn render_compare_alfred_json(
+    output: &CompareOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let mut items = Vec::with_capacity(output.days.len() * 3);
+    for (index, compared) in output.days.iter().enumerate() {
+        for city in [&output.left, &output.right] {
+            let Some(day) = city.forecast.get(index) else {
+                continue;
+            };
+            let icon_key = weather_cli::weather_icon::daily_forecast_icon_key(day.weather_code);
+            items.push(json!({
+                "title": format!(
+                    "{} {} {} {}%",
+                    city.location.name,
+                    temp_range_label(day, units),
+                    localized_summary(day, language),
+                    day.precip_prob_max_pct
+                ),
+                "subtitle": format!(
+                    "{} {} · source={} freshness={}",
+                    format_date_with_weekday(&day.date, language),
+                    city.timezone,
+                    city.source,
+                    freshness_label(city.freshness.status)
+                ),
+                "arg": city.location.name,
+                "valid": true,
+                "icon": {
+                    "path": icon_path(icon_key),
+                },
+                "weather_meta": {
+                    "item_kind": "compare_city",
+                    "icon_key": icon_key,
+                    "location_name": city.location.name,
+                    "timezone": city.timezone,
+                    "date": day.date,
+                    "units": units.as_str(),
+                    "temp_min": units.temperature(day.temp_min_c),
+                    "temp_max": units.temperature(day.temp_max_c),
+                    "precip_prob_max_pct": day.precip_prob_max_pct,
+                },
+            }));
+        }
+
+        let icon_key = output
+            .right
+            .forecast
+            .get(index)
+            .map(|day| weather_cli::weather_icon::daily_forecast_icon_key(day.weather_code))
+            .unwrap_or(FALLBACK_ICON_KEY);
+        items.push(json!({
+            "title": compare_delta_label(output, compared, language, units),
+            "subtitle": format_date_with_weekday(&compared.date, language),
+            "arg": compared.date,
+            "valid": false,
+            "icon": {
+                "path": icon_path(icon_key),
+            },
+            "weather_meta": {
+                "item_kind": "compare_delta",
+                "icon_key": icon_key,
+                "date": compared.date,
+                "units": units.as_str(),
+                "temp_min_delta": units.temperature_delta(compared.temp_min_delta_c),
+                "temp_max_delta": units.temperature_delta(compared.temp_max_delta_c),
+                "precip_prob_delta_pct": compared.precip_prob_delta_pct,
+            },
+        }));
+    }
+
+    serde_json::to_string(&json!({ "items": items })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+/// One row: the observed range and condition in the title, totals in the subtitle.
+fn render_history_alfred_json(
+    output: &HistoryOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> Result<String, CliError> {
+    let day = &output.observed;
+    let summary = localized_summary_by_code(day.weather_code, language);
+    let date_with_weekday = format_date_with_weekday(&day.date, language);
+    let icon_key = weather_cli::weather_icon::daily_forecast_icon_key(day.weather_code);
+    let title = format!(
+        "{} {} {} {}",
+        output.location.name,
+        date_with_weekday,
+        history_temp_range_label(day, units),
+        summary
+    );
+    let item = json!({
+        "title": title,
+        "subtitle": format!(
+            "{} · {}",
+            history_details_label(day, language, units),
+            freshness_label(output.freshness.status)
+        ),
+        "arg": title,
+        "valid": true,
+        "icon": {
+            "path": icon_path(icon_key),
+        },
+        "weather_meta": {
+            "item_kind": "history",
+            "location_name": output.location.name,
+            "timezone": output.timezone,
+            "date": day.date,
+            "date_with_weekday": date_with_weekday,
+            "summary": summary,
+            "weather_code": day.weather_code,
+            "icon_key": icon_key,
+            "temp_min_c": day.temp_min_c,
+            "temp_max_c": day.temp_max_c,
+            "units": units.as_str(),
+            "temp_unit": units.temperature_unit(),
+            "temp_min_label": units.temperature_label(day.temp_min_c),
+            "temp_max_label": units.temperature_label(day.temp_max_c),
+            "precip_sum_mm": day.precip_sum_mm,
+            "precip_sum_label": day.precip_sum_mm.map(|mm| units.length_label(mm)),
+        },
+    });
+
+    serde_json::to_string(&json!({ "items": [item] })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+fn render_sun_alfred_json(output: &SunOutput, language: Locale) -> Result<String, CliError> {
+    let mut items = Vec::with_capacity(output.days.len() + 1);
+    items.push(alfred_header_item(
+        &output.location.name,
+        &output.timezone,
+        output.location.latitude,
+        output.location.longitude,
+        &output.source,
+        output.freshness.status,
+        "clear-day",
+    ));
+
+    for day in &output.days {
+        let date_with_weekday = format_date_with_weekday(&day.date, language);
+        items.push(json!({
+            "title": format!("{} {}", date_with_weekday, sun_times_label(&day.sunrise, &day.sunset, language)),
+            "subtitle": format!(
+                "{} · {}",
+                golden_hours_label(day, language),
+                moon_label(day.moon_phase, day.moon_illumination_pct, language)
+            ),
+            "arg": day.date,
+            "valid": false,
+            "icon": {
+                "path": icon_path("clear-day"),
+            },
+            "weather_meta": {
+                "item_kind": "sun",
+                "date": day.date,
+                "date_with_weekday": date_with_weekday,
+                "sunrise": day.sunrise,
+                "sunset": day.sunset,
+                "sunrise_label": astronomy::clock_label(&day.sunrise),
+                "sunset_label": astronomy::clock_label(&day.sunset),
+                "day_length_minutes": day.day_length_minutes,
+                "golden_hour_morning": day.golden_hour_morning,
+                "golden_hour_evening": day.golden_hour_evening,
+                "moon_phase": day.moon_phase,
+                "moon_phase_label": moon_phase_label(day.moon_phase, language),
+                "moon_illumination_pct": day.moon_illumination_pct,
+            },
+        }));
+    }
+
+    serde_json::to_string(&json!({ "items": items })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+/// One row per favorite; `autocomplete` fills the favorite name into the query so the script filter can
+/// offer favorites on an empty query.
+fn render_locations_alfred_json(
+    favorites: &[FavoriteLocation],
+    language: Locale,
+) -> Result<String, CliError> {
+    let items: Vec<serde_json::Value> = if favorites.is_empty() {
+        vec![json!({
+            "title": language.text("favorites.empty"),
+            "subtitle": "WEATHER_LOCATIONS=\"home=Taipei;office=Hsinchu\"",
+            "valid": false,
+            "icon": {
+                "path": icon_path(FALLBACK_ICON_KEY),
+            },
+        })]
+    } else {
+        favorites
+            .iter()
+            .map(|favorite| {
+                json!({
+                    "title": favorite.name,
+                    "subtitle": language.format("favorites.subtitle", &[("query", &favorite.query)]),
+                    "arg": favorite.query,
+                    "autocomplete": favorite.name,
+                    "valid": false,
+                    "icon": {
+                        "path": icon_path(FALLBACK_ICON_KEY),
+                    },
+                    "weather_meta": {
+                        "item_kind": "favorite",
+                        "favorite_name": favorite.name,
+                        "query": favorite.query,
+                    },
+                })
+            })
+            .collect()
+    };
+
+    serde_json::to_string(&json!({ "items": items })).map_err(|error| {
+        runtime_error(
+            ERROR_CODE_RUNTIME_SERIALIZE,
+            format!("failed to serialize Alfred output: {error}"),
+        )
+    })
+}
+
+fn emit_error(command: &str, output_mode: OutputMode, error: &CliError) {
+    match output_mode {
+        OutputMode::Json => {
+            let details = build_error_details_json(error_kind_label(error.kind), error.exit_code());
+            println!(
+                "{}",
+                build_error_envelope(command, error.code, &error.message, Some(&details))
+            );
+        }
+        OutputMode::AlfredJson => {
+            println!(
+                "{}",
+                build_alfred_error_feedback(error.code, &error.message)
+            );
+        }
+        OutputMode::Human => {
+            eprintln!(
+                "error[{}]: {}",
+                error.code,
+                redact_sensitive(&error.message)
+            );
+        }
+    }
+}
+
+fn user_invalid_input(error: weather_cli::model::ValidationError) -> CliError {
+    user_error(ERROR_CODE_USER_INVALID_INPUT, error.to_string())
+}
+
+fn user_error(code: &'static str, message: impl Into<String>) -> CliError {
+    CliError::user(code, message)
+}
+
+fn runtime_error(code: &'static str, message: impl Into<String>) -> CliError {
+    CliError::runtime(code, message)
+}
+
+fn map_app_error(error: AppError) -> CliError {
+    match error.kind {
+        weather_cli::error::ErrorKind::User => {
+            user_error(ERROR_CODE_USER_INVALID_INPUT, error.message)
+        }
         weather_cli::error::ErrorKind::Runtime => {
             runtime_error(ERROR_CODE_RUNTIME_PROVIDER_FAILED, error.message)
         }
@@ -671,7 +2182,12 @@ fn error_kind_label(kind: weather_cli::error::ErrorKind) -> &'static str {
     }
 }
 
-fn format_text_output(output: &ForecastOutput, language: OutputLanguage) -> String {
+fn format_text_output(
+    output: &ForecastOutput,
+    language: Locale,
+    units: UnitSystem,
+    extended_detail: bool,
+) -> String {
     let mut lines = vec![format!(
         "{} ({}) | source={} | freshness={}",
         output.location.name,
@@ -682,26 +2198,40 @@ fn format_text_output(output: &ForecastOutput, language: OutputLanguage) -> Stri
 
     for day in &output.forecast {
         let summary = localized_summary(day, language);
-        lines.push(format!(
-            "{} {} {:.1}~{:.1}°C {}:{}%",
+        let mut line = format!(
+            "{} {} {} {}",
             format_date_with_weekday(&day.date, language),
             summary,
-            day.temp_min_c,
-            day.temp_max_c,
-            precip_label(language),
-            day.precip_prob_max_pct
-        ));
+            temp_range_label(day, units),
+            precip_details_label(day, language, units)
+        );
+        if extended_detail && let Some(details) = extended_details_label(day, language, units) {
+            line.push(' ');
+            line.push_str(&details);
+        }
+        if output.period == ForecastPeriod::Today
+            && let Some(details) = sun_moon_details(day, language)
+        {
+            line.push(' ');
+            line.push_str(&details);
+        }
+        lines.push(line);
     }
 
     lines.join("\n")
 }
 
-fn format_batch_text_output(output: &ForecastBatchOutput, language: OutputLanguage) -> String {
+fn format_batch_text_output(
+    output: &ForecastBatchOutput,
+    language: Locale,
+    units: UnitSystem,
+    extended_detail: bool,
+) -> String {
     let mut sections = Vec::new();
 
     for entry in &output.entries {
         if let Some(result) = &entry.result {
-            sections.push(format_text_output(result, language));
+            sections.push(format_text_output(result, language, units, extended_detail));
         } else {
             sections.push(format!(
                 "{} | error={}",
@@ -714,7 +2244,54 @@ fn format_batch_text_output(output: &ForecastBatchOutput, language: OutputLangua
     sections.join("\n\n")
 }
 
-fn format_hourly_text_output(output: &HourlyForecastOutput, language: OutputLanguage) -> String {
+/// Both forecasts as in `today`, then the differences (second city minus first) for each paired day.
+fn format_compare_text_output(
+    output: &CompareOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
+    let deltas = output
+        .days
+        .iter()
+        .map(|day| compare_delta_label(output, day, language, units))
+        .collect::<Vec<_>>();
+
+    [
+        format_text_output(&output.left, language, units, false),
+        format_text_output(&output.right, language, units, false),
+        deltas.join("\n"),
+    ]
+    .join("\n\n")
+}
+
+/// E.g. `Tokyo vs Taipei: high -7.5°C low -9.3°C rain -10%`.
+fn compare_delta_label(
+    output: &CompareOutput,
+    day: &CompareDay,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
+    let (versus, high, low) = (
+        language.text("label.vs"),
+        language.text("label.high"),
+        language.text("label.low"),
+    );
+    format!(
+        "{} {versus} {}: {high} {} {low} {} {} {:+}%",
+        output.right.location.name,
+        output.left.location.name,
+        units.temperature_delta_label(day.temp_max_delta_c),
+        units.temperature_delta_label(day.temp_min_delta_c),
+        precip_label(language),
+        day.precip_prob_delta_pct
+    )
+}
+
+fn format_hourly_text_output(
+    output: &HourlyForecastOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
     let mut lines = vec![format!(
         "{} ({}) | source={} | freshness={}",
         output.location.name,
@@ -726,10 +2303,11 @@ fn format_hourly_text_output(output: &HourlyForecastOutput, language: OutputLang
     for hour in &output.hourly {
         let summary = localized_summary_by_code(hour.weather_code, language);
         lines.push(format!(
-            "{} {} {:.1}°C {}:{}%",
+            "{} {} {}{} {}:{}%",
             display_hour_label(&hour.datetime, language),
             summary,
-            hour.temp_c,
+            units.temperature_label(hour.temp_c),
+            units.temperature_unit(),
             precip_label(language),
             hour.precip_prob_pct
         ));
@@ -738,101 +2316,412 @@ fn format_hourly_text_output(output: &HourlyForecastOutput, language: OutputLang
     lines.join("\n")
 }
 
-fn localized_summary(day: &weather_cli::model::ForecastDay, language: OutputLanguage) -> String {
-    localized_summary_by_code(day.weather_code, language)
-}
+fn format_sun_text_output(output: &SunOutput, language: Locale) -> String {
+    let mut lines = vec![format!(
+        "{} ({}) | source={} | freshness={}",
+        output.location.name,
+        output.timezone,
+        output.source,
+        freshness_label(output.freshness.status)
+    )];
 
-fn localized_summary_by_code(weather_code: i32, language: OutputLanguage) -> String {
-    match language {
-        OutputLanguage::En => weather_cli::weather_code::summary_en(weather_code).to_string(),
-        OutputLanguage::Zh => weather_cli::weather_code::summary_zh(weather_code).to_string(),
+    for day in &output.days {
+        lines.push(format!(
+            "{} {} ({}) {} {}",
+            format_date_with_weekday(&day.date, language),
+            sun_times_label(&day.sunrise, &day.sunset, language),
+            day_length_label(day.day_length_minutes),
+            golden_hours_label(day, language),
+            moon_label(day.moon_phase, day.moon_illumination_pct, language)
+        ));
     }
+
+    lines.join("\n")
 }
 
-fn localized_weekday_label(weekday: Weekday, language: OutputLanguage) -> &'static str {
-    match language {
-        OutputLanguage::En => match weekday {
-            Weekday::Mon => "Mon",
-            Weekday::Tue => "Tue",
-            Weekday::Wed => "Wed",
-            Weekday::Thu => "Thu",
-            Weekday::Fri => "Fri",
-            Weekday::Sat => "Sat",
-            Weekday::Sun => "Sun",
-        },
-        OutputLanguage::Zh => match weekday {
-            Weekday::Mon => "週一",
-            Weekday::Tue => "週二",
-            Weekday::Wed => "週三",
-            Weekday::Thu => "週四",
-            Weekday::Fri => "週五",
-            Weekday::Sat => "週六",
-            Weekday::Sun => "週日",
-        },
+fn format_locations_text_output(favorites: &[FavoriteLocation], language: Locale) -> String {
+    if favorites.is_empty() {
+        return language.text("favorites.empty_hint").to_string();
     }
-}
 
-fn weekday_label_for_date(date: &str, language: OutputLanguage) -> Option<&'static str> {
-    NaiveDate::parse_from_str(date, "%Y-%m-%d")
-        .ok()
-        .map(|parsed| localized_weekday_label(parsed.weekday(), language))
+    favorites
+        .iter()
+        .map(|favorite| format!("{}\t{}", favorite.name, favorite.query))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-fn parse_local_datetime(datetime: &str) -> Option<NaiveDateTime> {
-    NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M")
-        .or_else(|_| NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M"))
-        .ok()
+fn format_current_text_output(
+    output: &CurrentWeatherOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
+    let current = &output.current;
+    [
+        format!(
+            "{} ({}) | source={} | freshness={}",
+            output.location.name,
+            output.timezone,
+            output.source,
+            freshness_label(output.freshness.status)
+        ),
+        format!(
+            "{} {} {}{} {}",
+            current.observed_at.replace('T', " "),
+            localized_summary_by_code(current.weather_code, language),
+            units.temperature_label(current.temp_c),
+            units.temperature_unit(),
+            current_details_label(output, language, units)
+        ),
+    ]
+    .join("\n")
 }
 
-fn parse_timezone(timezone: &str) -> Option<Tz> {
-    timezone.parse::<Tz>().ok()
-}
+fn format_rain_text_output(output: &NowcastOutput, language: Locale, units: UnitSystem) -> String {
+    let mut lines = vec![
+        format!(
+            "{} ({}) | source={} | freshness={}",
+            output.location.name,
+            output.timezone,
+            output.source,
+            freshness_label(output.freshness.status)
+        ),
+        rain_outlook_label(&output.outlook, language),
+        format!(
+            "[{}] {}",
+            output.sparkline,
+            rain_peak_label(output, language, units)
+        ),
+    ];
 
-fn resolve_localized_datetime(
-    timezone: &str,
-    local_datetime: NaiveDateTime,
-) -> Option<DateTime<Tz>> {
-    let tz = parse_timezone(timezone)?;
-    match tz.from_local_datetime(&local_datetime) {
-        LocalResult::Single(datetime) => Some(datetime),
-        LocalResult::Ambiguous(datetime, _) => Some(datetime),
-        LocalResult::None => None,
+    for point in &output.points {
+        lines.push(format!(
+            "{} {}:{}",
+            split_datetime_label(&point.datetime).1,
+            precip_label(language),
+            units.length_label(point.precip_mm)
+        ));
     }
-}
-
-fn timezone_offset_seconds_for_date(timezone: &str, date: &str) -> Option<i32> {
-    let local_date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
-    let local_datetime = local_date.and_hms_opt(12, 0, 0)?;
-    resolve_localized_datetime(timezone, local_datetime)
-        .map(|datetime| datetime.offset().fix().local_minus_utc())
-}
 
-fn timezone_offset_seconds_for_datetime(timezone: &str, datetime: &str) -> Option<i32> {
-    let local_datetime = parse_local_datetime(datetime)?;
-    resolve_localized_datetime(timezone, local_datetime)
-        .map(|datetime| datetime.offset().fix().local_minus_utc())
+    lines.join("\n")
 }
 
-fn format_utc_offset_label(offset_seconds: i32) -> String {
-    let sign = if offset_seconds >= 0 { '+' } else { '-' };
-    let absolute_seconds = offset_seconds.unsigned_abs();
-    let hours = absolute_seconds / 3600;
-    let minutes = (absolute_seconds % 3600) / 60;
-    let seconds = absolute_seconds % 60;
-
-    if seconds > 0 {
-        return format!("UTC{sign}{hours}:{minutes:02}:{seconds:02}");
-    }
-
-    if minutes > 0 {
-        return format!("UTC{sign}{hours}:{minutes:02}");
+fn rain_outlook_label(outlook: &RainOutlook, language: Locale) -> String {
+    match outlook {
+        RainOutlook::Dry => language.text("rain_outlook.dry").to_string(),
+        RainOutlook::Raining => language.text("rain_outlook.raining").to_string(),
+        RainOutlook::Starts { in_minutes } => language.format(
+            "rain_outlook.starts",
+            &[("minutes", &in_minutes.to_string())],
+        ),
+        RainOutlook::Stops { in_minutes } => language.format(
+            "rain_outlook.stops",
+            &[("minutes", &in_minutes.to_string())],
+        ),
     }
+}
 
-    format!("UTC{sign}{hours}")
+/// Largest 15-minute amount in the window, e.g. `max 3.5mm/15min`.
+fn rain_peak_label(output: &NowcastOutput, language: Locale, units: UnitSystem) -> String {
+    let peak = output
+        .points
+        .iter()
+        .map(|point| point.precip_mm)
+        .fold(0.0_f64, f64::max);
+    let max = language.text("label.max");
+    format!(
+        "{max} {}/{}min",
+        units.length_label(peak),
+        output.interval_minutes
+    )
 }
 
-fn timezone_offset_label_for_date(timezone: &str, date: &str) -> Option<String> {
-    timezone_offset_seconds_for_date(timezone, date).map(format_utc_offset_label)
+fn rain_icon_key(outlook: &RainOutlook) -> &'static str {
+    match outlook {
+        RainOutlook::Dry => "cloudy",
+        _ => "rain",
+    }
+}
+
+fn format_history_text_output(
+    output: &HistoryOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
+    let day = &output.observed;
+    [
+        format!(
+            "{} ({}) | source={} | freshness={}",
+            output.location.name,
+            output.timezone,
+            output.source,
+            freshness_label(output.freshness.status)
+        ),
+        format!(
+            "{} {} {} {}",
+            format_date_with_weekday(&day.date, language),
+            localized_summary_by_code(day.weather_code, language),
+            history_temp_range_label(day, units),
+            history_details_label(day, language, units)
+        ),
+    ]
+    .join("\n")
+}
+
+fn history_temp_range_label(day: &weather_cli::model::HistoryDay, units: UnitSystem) -> String {
+    format!(
+        "{}~{}{}",
+        units.temperature_label(day.temp_min_c),
+        units.temperature_label(day.temp_max_c),
+        units.temperature_unit()
+    )
+}
+
+/// Mean temperature, precipitation total (with wet hours) and peak wind, skipping values the archive left empty.
+fn history_details_label(
+    day: &weather_cli::model::HistoryDay,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
+    let (mean, wind) = (language.text("label.mean"), language.text("label.max_wind"));
+    let mut parts = Vec::new();
+    if let Some(temp) = day.temp_mean_c {
+        parts.push(format!(
+            "{mean} {}{}",
+            units.temperature_label(temp),
+            units.temperature_unit()
+        ));
+    }
+    if let Some(mm) = day.precip_sum_mm {
+        let mut precip = format!("{}:{}", precip_label(language), units.length_label(mm));
+        if let Some(hours) = day.precip_hours.filter(|hours| *hours > 0.0) {
+            precip.push_str(&format!(" ({hours:.0}h)"));
+        }
+        parts.push(precip);
+    }
+    if let Some(kmh) = day.wind_speed_max_kmh {
+        parts.push(format!("{wind} {}", units.speed_label(kmh)));
+    }
+    parts.join(" ")
+}
+
+fn current_details_label(
+    output: &CurrentWeatherOutput,
+    language: Locale,
+    units: UnitSystem,
+) -> String {
+    let current = &output.current;
+    let (feels_like, humidity, wind) = (
+        language.text("label.feels"),
+        language.text("label.humidity"),
+        language.text("label.wind"),
+    );
+    format!(
+        "{feels_like} {}{} {humidity}:{}% {wind}:{} {}",
+        units.temperature_label(current.apparent_temp_c),
+        units.temperature_unit(),
+        current.humidity_pct,
+        units.speed_label(current.wind_speed_kmh),
+        compass_label(current.wind_direction_deg)
+    )
+}
+
+/// Eight-point compass label for the direction the wind blows from.
+fn compass_label(degrees: u16) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[(usize::from(degrees % 360) * 2 + 45) / 90 % 8]
+}
+
+fn temp_range_label(day: &ForecastDay, units: UnitSystem) -> String {
+    format!(
+        "{}~{}{}",
+        units.temperature_label(day.temp_min_c),
+        units.temperature_label(day.temp_max_c),
+        units.temperature_unit()
+    )
+}
+
+/// Rain chance, followed by the expected amount when the provider reports one.
+fn precip_details_label(day: &ForecastDay, language: Locale, units: UnitSystem) -> String {
+    let chance = format!("{}:{}%", precip_label(language), day.precip_prob_max_pct);
+    match day.precip_sum_mm {
+        Some(mm) => format!("{chance} {}", units.length_label(mm)),
+        None => chance,
+    }
+}
+
+/// Wind, humidity and UV for `WEATHER_DETAIL=extended`, e.g. `wind:21.6km/h NE humidity:78% UV:6.4`; `None` when
+/// the provider reported none of them.
+fn extended_details_label(
+    day: &ForecastDay,
+    language: Locale,
+    units: UnitSystem,
+) -> Option<String> {
+    let (humidity, wind) = (language.text("label.humidity"), language.text("label.wind"));
+    let mut parts = Vec::new();
+    if let Some(kmh) = day.wind_speed_max_kmh {
+        let mut label = format!("{wind}:{}", units.speed_label(kmh));
+        if let Some(degrees) = day.wind_direction_deg {
+            label.push(' ');
+            label.push_str(compass_label(degrees));
+        }
+        parts.push(label);
+    }
+    if let Some(pct) = day.humidity_mean_pct {
+        parts.push(format!("{humidity}:{pct}%"));
+    }
+    if let Some(uv) = day.uv_index_max {
+        parts.push(format!("UV:{uv:.1}"));
+    }
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Extended wind/humidity/UV details when enabled, then today-only sunrise/sunset and moon phase.
+fn daily_subtitle(
+    period: ForecastPeriod,
+    day: &ForecastDay,
+    language: Locale,
+    units: UnitSystem,
+    extended_detail: bool,
+) -> String {
+    let mut precip = precip_details_label(day, language, units);
+    if extended_detail && let Some(details) = extended_details_label(day, language, units) {
+        precip.push(' ');
+        precip.push_str(&details);
+    }
+    match sun_moon_details(day, language) {
+        Some(details) if period == ForecastPeriod::Today => format!("{precip} · {details}"),
+        _ => precip,
+    }
+}
+
+fn sun_moon_details(day: &ForecastDay, language: Locale) -> Option<String> {
+    let sun = day
+        .sunrise
+        .as_deref()
+        .zip(day.sunset.as_deref())
+        .map(|(sunrise, sunset)| sun_times_label(sunrise, sunset, language));
+    let moon = day
+        .moon_phase
+        .zip(day.moon_illumination_pct)
+        .map(|(phase, pct)| moon_label(phase, pct, language));
+
+    match (sun, moon) {
+        (Some(sun), Some(moon)) => Some(format!("{sun} {moon}")),
+        (sun, moon) => sun.or(moon),
+    }
+}
+
+fn sun_times_label(sunrise: &str, sunset: &str, language: Locale) -> String {
+    let (rise, set) = (
+        language.text("label.sunrise"),
+        language.text("label.sunset"),
+    );
+    format!(
+        "{rise} {} {set} {}",
+        astronomy::clock_label(sunrise),
+        astronomy::clock_label(sunset)
+    )
+}
+
+fn golden_hours_label(day: &weather_cli::model::SunDay, language: Locale) -> String {
+    let golden = language.text("label.golden");
+    format!(
+        "{golden} {}-{} {}-{}",
+        astronomy::clock_label(&day.golden_hour_morning.start),
+        astronomy::clock_label(&day.golden_hour_morning.end),
+        astronomy::clock_label(&day.golden_hour_evening.start),
+        astronomy::clock_label(&day.golden_hour_evening.end)
+    )
+}
+
+fn day_length_label(minutes: i64) -> String {
+    format!("{}h{:02}m", minutes / 60, minutes % 60)
+}
+
+fn moon_label(phase: astronomy::MoonPhase, illumination_pct: u8, language: Locale) -> String {
+    format!("{} {illumination_pct}%", moon_phase_label(phase, language))
+}
+
+fn moon_phase_label(phase: astronomy::MoonPhase, language: Locale) -> &'static str {
+    language.text(phase.label_key())
+}
+
+fn localized_summary(day: &weather_cli::model::ForecastDay, language: Locale) -> String {
+    localized_summary_by_code(day.weather_code, language)
+}
+
+fn localized_summary_by_code(weather_code: i32, language: Locale) -> String {
+    language
+        .text(weather_cli::weather_code::summary_key(weather_code))
+        .to_string()
+}
+
+fn localized_weekday_label(weekday: Weekday, language: Locale) -> &'static str {
+    language.weekday(weekday)
+}
+
+fn weekday_label_for_date(date: &str, language: Locale) -> Option<&'static str> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(|parsed| localized_weekday_label(parsed.weekday(), language))
+}
+
+fn parse_local_datetime(datetime: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M"))
+        .ok()
+}
+
+fn parse_timezone(timezone: &str) -> Option<Tz> {
+    timezone.parse::<Tz>().ok()
+}
+
+fn resolve_localized_datetime(
+    timezone: &str,
+    local_datetime: NaiveDateTime,
+) -> Option<DateTime<Tz>> {
+    let tz = parse_timezone(timezone)?;
+    match tz.from_local_datetime(&local_datetime) {
+        LocalResult::Single(datetime) => Some(datetime),
+        LocalResult::Ambiguous(datetime, _) => Some(datetime),
+        LocalResult::None => None,
+    }
+}
+
+fn timezone_offset_seconds_for_date(timezone: &str, date: &str) -> Option<i32> {
+    let local_date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let local_datetime = local_date.and_hms_opt(12, 0, 0)?;
+    resolve_localized_datetime(timezone, local_datetime)
+        .map(|datetime| datetime.offset().fix().local_minus_utc())
+}
+
+fn timezone_offset_seconds_for_datetime(timezone: &str, datetime: &str) -> Option<i32> {
+    let local_datetime = parse_local_datetime(datetime)?;
+    resolve_localized_datetime(timezone, local_datetime)
+        .map(|datetime| datetime.offset().fix().local_minus_utc())
+}
+
+fn format_utc_offset_label(offset_seconds: i32) -> String {
+    let sign = if offset_seconds >= 0 { '+' } else { '-' };
+    let absolute_seconds = offset_seconds.unsigned_abs();
+    let hours = absolute_seconds / 3600;
+    let minutes = (absolute_seconds % 3600) / 60;
+    let seconds = absolute_seconds % 60;
+
+    if seconds > 0 {
+        return format!("UTC{sign}{hours}:{minutes:02}:{seconds:02}");
+    }
+
+    if minutes > 0 {
+        return format!("UTC{sign}{hours}:{minutes:02}");
+    }
+
+    format!("UTC{sign}{hours}")
+}
+
+fn timezone_offset_label_for_date(timezone: &str, date: &str) -> Option<String> {
+    timezone_offset_seconds_for_date(timezone, date).map(format_utc_offset_label)
 }
 
 fn timezone_offset_label_for_datetime(timezone: &str, datetime: &str) -> Option<String> {
@@ -846,18 +2735,18 @@ fn timezone_display_label(timezone: &str, offset_label: Option<&str>) -> String
     }
 }
 
-fn weekday_label_for_datetime(datetime: &str, language: OutputLanguage) -> Option<&'static str> {
+fn weekday_label_for_datetime(datetime: &str, language: Locale) -> Option<&'static str> {
     parse_local_datetime(datetime).map(|parsed| localized_weekday_label(parsed.weekday(), language))
 }
 
-fn format_date_with_weekday(date: &str, language: OutputLanguage) -> String {
+fn format_date_with_weekday(date: &str, language: Locale) -> String {
     match weekday_label_for_date(date, language) {
         Some(label) => format!("{date} {label}"),
         None => date.to_string(),
     }
 }
 
-fn display_hour_label(datetime: &str, language: OutputLanguage) -> String {
+fn display_hour_label(datetime: &str, language: Locale) -> String {
     if let Some(parsed) = parse_local_datetime(datetime) {
         return format!(
             "{} {} {}",
@@ -884,9 +2773,11 @@ fn icon_path(icon_key: &str) -> String {
 fn alfred_daily_city_item(
     output: &ForecastOutput,
     day: &weather_cli::model::ForecastDay,
-    language: OutputLanguage,
+    language: Locale,
+    units: UnitSystem,
     now: DateTime<Utc>,
     use_current_conditions_icon: bool,
+    extended_detail: bool,
 ) -> serde_json::Value {
     let summary = localized_summary(day, language);
     let rendered_summary = if summary
@@ -913,24 +2804,25 @@ fn alfred_daily_city_item(
     } else {
         weather_cli::weather_icon::daily_forecast_icon_key(day.weather_code)
     };
+    let mut subtitle = format!(
+        "{} {} {:.4},{:.4}",
+        date_with_weekday, output.timezone, output.location.latitude, output.location.longitude
+    );
+    if extended_detail && let Some(details) = extended_details_label(day, language, units) {
+        subtitle.push(' ');
+        subtitle.push_str(&details);
+    }
 
     json!({
         "title": format!(
-            "{}{} {:.1}~{:.1}°C {} {}%",
+            "{}{} {} {} {}%",
             output.location.name,
             weekday_segment,
-            day.temp_min_c,
-            day.temp_max_c,
+            temp_range_label(day, units),
             rendered_summary,
             day.precip_prob_max_pct
         ),
-        "subtitle": format!(
-            "{} {} {:.4},{:.4}",
-            date_with_weekday,
-            output.timezone,
-            output.location.latitude,
-            output.location.longitude
-        ),
+        "subtitle": subtitle,
         "arg": day.date,
         "valid": true,
         "icon": {
@@ -953,6 +2845,12 @@ fn alfred_daily_city_item(
             "temp_max_c_label": format!("{:.1}", day.temp_max_c),
             "precip_prob_max_pct": day.precip_prob_max_pct,
             "precip_prob_max_pct_label": day.precip_prob_max_pct.to_string(),
+            "precip_sum_mm": day.precip_sum_mm,
+            "precip_sum_label": day.precip_sum_mm.map(|mm| units.length_label(mm)),
+            "units": units.as_str(),
+            "temp_unit": units.temperature_unit(),
+            "temp_min_label": units.temperature_label(day.temp_min_c),
+            "temp_max_label": units.temperature_label(day.temp_max_c),
             "location_name": output.location.name,
             "timezone": output.timezone,
             "latitude_label": format!("{:.4}", output.location.latitude),
@@ -961,6 +2859,7 @@ fn alfred_daily_city_item(
     })
 }
 
+/// The header shows the icon of the first row below it, or the fallback glyph when there are no rows.
 fn alfred_header_item(
     location_name: &str,
     timezone: &str,
@@ -968,6 +2867,7 @@ fn alfred_header_item(
     longitude: f64,
     source: &str,
     freshness_status: weather_cli::model::FreshnessStatus,
+    icon_key: &str,
 ) -> serde_json::Value {
     json!({
         "title": format!("{location_name} ({timezone})"),
@@ -980,8 +2880,12 @@ fn alfred_header_item(
         ),
         "arg": location_name,
         "valid": false,
+        "icon": {
+            "path": icon_path(icon_key),
+        },
         "weather_meta": {
             "item_kind": "header",
+            "icon_key": icon_key,
             "location_name": location_name,
             "timezone": timezone,
             "latitude": latitude,
@@ -992,11 +2896,8 @@ fn alfred_header_item(
     })
 }
 
-fn precip_label(language: OutputLanguage) -> &'static str {
-    match language {
-        OutputLanguage::En => "rain",
-        OutputLanguage::Zh => "降雨",
-    }
+fn precip_label(language: Locale) -> &'static str {
+    language.text("label.rain")
 }
 
 fn freshness_label(status: weather_cli::model::FreshnessStatus) -> &'static str {
@@ -1018,7 +2919,11 @@ mod tests {
         geocode_result: Result<ResolvedLocation, ProviderError>,
         open_meteo_result: Result<ProviderForecast, ProviderError>,
         open_meteo_hourly_result: Result<ProviderHourlyForecast, ProviderError>,
+        open_meteo_current_result: Result<ProviderCurrentWeather, ProviderError>,
+        open_meteo_nowcast_result: Result<ProviderNowcast, ProviderError>,
+        open_meteo_history_result: Result<ProviderHistory, ProviderError>,
         met_no_result: Result<ProviderForecast, ProviderError>,
+        ip_location_result: Result<ResolvedLocation, ProviderError>,
     }
 
     impl FakeProviders {
@@ -1044,6 +2949,13 @@ mod tests {
                         temp_min_c: 14.5,
                         temp_max_c: 20.1,
                         precip_prob_max_pct: 20,
+                        precip_sum_mm: Some(3.0),
+                        sunrise: Some("2026-02-11T06:32".to_string()),
+                        sunset: Some("2026-02-11T17:48".to_string()),
+                        wind_speed_max_kmh: Some(21.6),
+                        wind_direction_deg: Some(45),
+                        humidity_mean_pct: Some(78),
+                        uv_index_max: Some(6.4),
                     }],
                 }),
                 open_meteo_hourly_result: Ok(ProviderHourlyForecast {
@@ -1057,6 +2969,48 @@ mod tests {
                         precip_prob_pct: 20,
                     }],
                 }),
+                open_meteo_current_result: Ok(ProviderCurrentWeather {
+                    timezone: "Asia/Taipei".to_string(),
+                    fetched_at: now,
+                    observed_at: "2026-02-11T08:00".to_string(),
+                    weather_code: 3,
+                    temp_c: 17.2,
+                    apparent_temp_c: 16.4,
+                    humidity_pct: 78,
+                    wind_speed_kmh: 9.4,
+                    wind_direction_deg: 60,
+                }),
+                open_meteo_nowcast_result: Ok(ProviderNowcast {
+                    timezone: "Asia/Taipei".to_string(),
+                    utc_offset_seconds: 8 * 3600,
+                    fetched_at: now,
+                    points: [0.0, 0.0, 0.0, 0.6, 1.8, 0.2, 0.0, 0.0]
+                        .iter()
+                        .enumerate()
+                        .map(|(index, mm)| ProviderNowcastPoint {
+                            datetime: format!(
+                                "2026-02-11T{:02}:{:02}",
+                                8 + index / 4,
+                                index % 4 * 15
+                            ),
+                            precip_mm: *mm,
+                        })
+                        .collect(),
+                }),
+                open_meteo_history_result: Ok(ProviderHistory {
+                    timezone: "Asia/Taipei".to_string(),
+                    fetched_at: now,
+                    day: ProviderHistoryDay {
+                        date: "2025-08-01".to_string(),
+                        weather_code: 63,
+                        temp_min_c: 26.1,
+                        temp_max_c: 33.4,
+                        temp_mean_c: Some(29.2),
+                        precip_sum_mm: Some(12.6),
+                        precip_hours: Some(5.0),
+                        wind_speed_max_kmh: Some(18.7),
+                    },
+                }),
                 met_no_result: Ok(ProviderForecast {
                     timezone: "UTC".to_string(),
                     fetched_at: now,
@@ -1066,8 +3020,21 @@ mod tests {
                         temp_min_c: 11.0,
                         temp_max_c: 15.0,
                         precip_prob_max_pct: 70,
+                        precip_sum_mm: None,
+                        sunrise: None,
+                        sunset: None,
+                        wind_speed_max_kmh: None,
+                        wind_direction_deg: None,
+                        humidity_mean_pct: None,
+                        uv_index_max: None,
                     }],
                 }),
+                ip_location_result: Ok(ResolvedLocation {
+                    name: "Banqiao".to_string(),
+                    latitude: 25.0143,
+                    longitude: 121.4672,
+                    timezone: "Asia/Taipei".to_string(),
+                }),
             }
         }
     }
@@ -1117,6 +3084,13 @@ mod tests {
                         temp_min_c: 14.5,
                         temp_max_c: 20.1,
                         precip_prob_max_pct: 20,
+                        precip_sum_mm: None,
+                        sunrise: None,
+                        sunset: None,
+                        wind_speed_max_kmh: None,
+                        wind_direction_deg: None,
+                        humidity_mean_pct: None,
+                        uv_index_max: None,
                     }],
                 }),
                 "Tokyo" => Ok(ProviderForecast {
@@ -1128,6 +3102,13 @@ mod tests {
                         temp_min_c: 5.2,
                         temp_max_c: 12.6,
                         precip_prob_max_pct: 10,
+                        precip_sum_mm: None,
+                        sunrise: None,
+                        sunset: None,
+                        wind_speed_max_kmh: None,
+                        wind_direction_deg: None,
+                        humidity_mean_pct: None,
+                        uv_index_max: None,
                     }],
                 }),
                 _ => Err(ProviderError::NotFound(city.to_string())),
@@ -1158,6 +3139,32 @@ mod tests {
             self.open_meteo_hourly_result.clone()
         }
 
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            self.open_meteo_current_result.clone()
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            self.open_meteo_nowcast_result.clone()
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            self.open_meteo_history_result.clone()
+        }
+
         fn fetch_met_no_forecast(
             &self,
             _lat: f64,
@@ -1166,6 +3173,19 @@ mod tests {
         ) -> Result<ProviderForecast, ProviderError> {
             self.met_no_result.clone()
         }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            self.ip_location_result.clone()
+        }
     }
 
     impl ProviderApi for MultiCityProviders {
@@ -1203,6 +3223,32 @@ mod tests {
             Err(ProviderError::Transport("unused".to_string()))
         }
 
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
         fn fetch_met_no_forecast(
             &self,
             _lat: f64,
@@ -1211,12 +3257,35 @@ mod tests {
         ) -> Result<ProviderForecast, ProviderError> {
             Err(ProviderError::Transport("unused".to_string()))
         }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
     }
 
     fn config_in_tempdir() -> RuntimeConfig {
         RuntimeConfig {
             cache_dir: tempfile::tempdir().expect("tempdir").path().to_path_buf(),
             cache_ttl_secs: weather_cli::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         }
     }
 
@@ -1286,75 +3355,686 @@ mod tests {
                     temp_min_c: 14.0 + i as f64,
                     temp_max_c: 20.0 + i as f64,
                     precip_prob_max_pct: 10 + i as u8,
+                    precip_sum_mm: None,
+                    sunrise: None,
+                    sunset: None,
+                    wind_speed_max_kmh: None,
+                    wind_direction_deg: None,
+                    humidity_mean_pct: None,
+                    uv_index_max: None,
                 })
                 .collect(),
         });
 
         let cli = Cli::parse_from([
             "weather-cli",
-            "week",
+            "week",
+            "--city",
+            "Taipei",
+            "--output",
+            "json",
+        ]);
+
+        let output =
+            run_with(cli, &config_in_tempdir(), &providers, fixed_now).expect("week should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+
+        assert_eq!(
+            json.get("schema_version").and_then(Value::as_str),
+            Some("cli-envelope@v1")
+        );
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.week")
+        );
+        assert_eq!(
+            json.get("result")
+                .and_then(|result| result.get("period"))
+                .and_then(Value::as_str),
+            Some("week")
+        );
+        assert_eq!(
+            json.get("result")
+                .and_then(|result| result.get("forecast"))
+                .and_then(Value::as_array)
+                .map(Vec::len),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn main_outputs_hourly_json_contract() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "hourly",
+            "--city",
+            "Tokyo",
+            "--output",
+            "json",
+        ]);
+
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("hourly should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+
+        assert_eq!(
+            json.get("schema_version").and_then(Value::as_str),
+            Some("cli-envelope@v1")
+        );
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.hourly")
+        );
+        assert_eq!(
+            json.get("result")
+                .and_then(|result| result.get("hourly"))
+                .and_then(Value::as_array)
+                .map(Vec::len),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn main_outputs_now_json_contract() {
+        let cli = Cli::parse_from(["weather-cli", "now", "--city", "Taipei", "--output", "json"]);
+
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("now should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.now")
+        );
+        let current = json
+            .get("result")
+            .and_then(|result| result.get("current"))
+            .expect("current");
+        assert_eq!(current.get("temp_c").and_then(Value::as_f64), Some(17.2));
+        assert_eq!(
+            current.get("apparent_temp_c").and_then(Value::as_f64),
+            Some(16.4)
+        );
+        assert_eq!(
+            current.get("humidity_pct").and_then(Value::as_u64),
+            Some(78)
+        );
+        assert_eq!(
+            json.get("result")
+                .and_then(|result| result.get("freshness"))
+                .and_then(|freshness| freshness.get("ttl_secs"))
+                .and_then(Value::as_u64),
+            Some(weather_cli::config::WEATHER_CURRENT_CACHE_TTL_SECS)
+        );
+    }
+
+    #[test]
+    fn main_outputs_now_as_single_alfred_row() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "now",
+            "--city",
+            "Taipei",
+            "--output",
+            "alfred-json",
+        ]);
+
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("now should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let items = json.get("items").and_then(Value::as_array).expect("items");
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].get("title").and_then(Value::as_str),
+            Some("Taipei City 17.2°C Cloudy")
+        );
+        assert_eq!(items[0].get("valid").and_then(Value::as_bool), Some(true));
+        assert_eq!(
+            items[0].get("subtitle").and_then(Value::as_str),
+            Some("feels 16.4°C humidity:78% wind:9.4km/h NE · 2026-02-11 08:00 · live")
+        );
+        assert_eq!(
+            items[0]
+                .get("weather_meta")
+                .and_then(|meta| meta.get("item_kind"))
+                .and_then(Value::as_str),
+            Some("current")
+        );
+    }
+
+    #[test]
+    fn main_outputs_now_text_in_chinese() {
+        let cli = Cli::parse_from(["weather-cli", "now", "--city", "Taipei", "--lang", "zh"]);
+
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("now should pass");
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Taipei City (Asia/Taipei) | source=open_meteo"));
+        assert!(lines[1].contains("體感 16.4°C 濕度:78% 風:9.4km/h NE"));
+    }
+
+    #[test]
+    fn main_outputs_rain_nowcast_json_text_and_alfred_rows() {
+        let config = config_in_tempdir();
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "rain",
+            "--city",
+            "Taipei",
+            "--output",
+            "json",
+        ]);
+
+        let output =
+            run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("rain should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.rain")
+        );
+        let result = json.get("result").expect("result");
+        assert_eq!(
+            result.pointer("/outlook/status").and_then(Value::as_str),
+            Some("starts")
+        );
+        assert_eq!(
+            result
+                .pointer("/outlook/in_minutes")
+                .and_then(Value::as_u64),
+            Some(40)
+        );
+        assert_eq!(
+            result.get("sparkline").and_then(Value::as_str),
+            Some("___:=.__")
+        );
+        assert_eq!(
+            result
+                .pointer("/points/4/precip_mm")
+                .and_then(Value::as_f64),
+            Some(1.8)
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "rain", "--city", "Taipei", "--lang", "zh"]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("rain text");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[1], "40 分鐘後開始下雨");
+        assert_eq!(lines[2], "[___:=.__] 最大 1.8mm/15min");
+        assert_eq!(lines[3], "08:00 降雨:0.0mm");
+
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "rain",
+            "--city",
+            "Taipei",
+            "--output",
+            "alfred-json",
+        ]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("rain alfred");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let items = json.get("items").and_then(Value::as_array).expect("items");
+        assert_eq!(items.len(), 9);
+        assert_eq!(
+            items[0].get("title").and_then(Value::as_str),
+            Some("Taipei City Rain starts in 40 min")
+        );
+        assert_eq!(
+            items[0].get("subtitle").and_then(Value::as_str),
+            Some("___:=.__ max 1.8mm/15min · cache_fresh")
+        );
+        assert_eq!(
+            items[4].get("title").and_then(Value::as_str),
+            Some("08:45 rain:0.6mm")
+        );
+        assert_eq!(items[4].get("valid").and_then(Value::as_bool), Some(false));
+    }
+
+    #[test]
+    fn main_rain_converts_amounts_for_imperial_units() {
+        let config = config_in_tempdir();
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "rain",
+            "--city",
+            "Taipei",
+            "--units",
+            "imperial",
+            "--output",
+            "json",
+        ]);
+
+        let output =
+            run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("rain should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let point = json.pointer("/result/points/4").expect("point");
+        assert!(point.get("precip_mm").is_none());
+        assert_eq!(point.get("precip_in").and_then(Value::as_f64), Some(0.07));
+    }
+
+    #[test]
+    fn main_warm_refreshes_every_period_even_when_cached() {
+        let config = config_in_tempdir();
+        let cli = Cli::parse_from(["weather-cli", "today", "--city", "Taipei"]);
+        run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("prime today cache");
+
+        let later = || fixed_now() + chrono::Duration::seconds(60);
+        let cli = Cli::parse_from(["weather-cli", "warm", "--city", "Taipei"]);
+        assert_eq!(cli.command_name(), "weather.warm");
+        let output = run_with(cli, &config, &FakeProviders::ok(), later).expect("warm");
+        assert_eq!(output, "");
+
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "warm",
+            "--city",
+            "Taipei",
+            "--city",
+            "taipei",
+            "--output",
+            "json",
+        ]);
+        let later = || fixed_now() + chrono::Duration::seconds(120);
+        let output = run_with(cli, &config, &FakeProviders::ok(), later).expect("warm json");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let entries = json
+            .pointer("/result/entries")
+            .and_then(Value::as_array)
+            .expect("entries");
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.get("period").and_then(Value::as_str).unwrap_or(""))
+                .collect::<Vec<_>>(),
+            vec!["today", "week", "hourly", "now", "rain"]
+        );
+        assert!(
+            entries
+                .iter()
+                .all(|entry| entry.get("status").and_then(Value::as_str) == Some("live"))
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "now", "--city", "Taipei", "--output", "json"]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), later).expect("now");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(
+            json.pointer("/result/freshness/status")
+                .and_then(Value::as_str),
+            Some("cache_fresh")
+        );
+    }
+
+    #[test]
+    fn main_warm_reports_failed_refreshes() {
+        let config = config_in_tempdir();
+        let mut providers = FakeProviders::ok();
+        providers.open_meteo_nowcast_result = Err(ProviderError::Transport("timeout".to_string()));
+
+        let cli = Cli::parse_from(["weather-cli", "warm", "--city", "Taipei"]);
+        let error = run_with(cli, &config, &providers, fixed_now).expect_err("warm should fail");
+        assert_eq!(error.exit_code(), 1);
+        assert!(
+            error
+                .message
+                .starts_with("failed to refresh 1 of 5 cache entries: Taipei/rain:")
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "warm", "--output", "json"]);
+        let error = run_with(cli, &config, &providers, fixed_now).expect_err("no location");
+        assert_eq!(error.exit_code(), 2);
+    }
+
+    #[test]
+    fn main_outputs_history_json_and_alfred_row() {
+        let config = config_in_tempdir();
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "history",
+            "--date",
+            "2025-08-01",
+            "--city",
+            "Taipei",
+            "--output",
+            "json",
+        ]);
+
+        let output =
+            run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("history should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.history")
+        );
+        let observed = json
+            .get("result")
+            .and_then(|result| result.get("observed"))
+            .expect("observed");
+        assert_eq!(
+            observed.get("date").and_then(Value::as_str),
+            Some("2025-08-01")
+        );
+        assert_eq!(
+            observed.get("temp_max_c").and_then(Value::as_f64),
+            Some(33.4)
+        );
+        assert_eq!(
+            observed.get("precip_sum_mm").and_then(Value::as_f64),
+            Some(12.6)
+        );
+
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "history",
+            "--date",
+            "2025-08-01",
+            "--city",
+            "Taipei",
+            "--output",
+            "alfred-json",
+        ]);
+        let output =
+            run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("history should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let items = json.get("items").and_then(Value::as_array).expect("items");
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].get("title").and_then(Value::as_str),
+            Some("Taipei City 2025-08-01 Fri 26.1~33.4°C Rain")
+        );
+        assert_eq!(
+            items[0].get("subtitle").and_then(Value::as_str),
+            Some("mean 29.2°C rain:12.6mm (5h) max wind 18.7km/h · cache_fresh")
+        );
+    }
+
+    #[test]
+    fn main_history_rejects_malformed_and_future_dates() {
+        for date in ["2025/08/01", "2026-02-12"] {
+            let cli =
+                Cli::parse_from(["weather-cli", "history", "--date", date, "--city", "Taipei"]);
+
+            let err = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+                .expect_err("history should fail");
+            assert_eq!(err.exit_code(), 2);
+            assert!(err.message.contains("--date"));
+        }
+    }
+
+    #[test]
+    fn main_outputs_sun_json_contract() {
+        let cli = Cli::parse_from(["weather-cli", "sun", "--city", "Taipei", "--output", "json"]);
+
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("sun should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.sun")
+        );
+        let day = json
+            .get("result")
+            .and_then(|result| result.get("days"))
+            .and_then(|days| days.get(0))
+            .expect("first day");
+        assert_eq!(
+            day.get("sunrise").and_then(Value::as_str),
+            Some("2026-02-11T06:32")
+        );
+        assert_eq!(
+            day.get("day_length_minutes").and_then(Value::as_i64),
+            Some(676)
+        );
+        assert_eq!(
+            day.get("golden_hour_evening")
+                .and_then(|window| window.get("start"))
+                .and_then(Value::as_str),
+            Some("2026-02-11T16:48")
+        );
+        assert_eq!(
+            day.get("moon_phase").and_then(Value::as_str),
+            Some("last_quarter")
+        );
+    }
+
+    #[test]
+    fn main_outputs_sun_alfred_rows_and_text() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "sun",
+            "--city",
+            "Taipei",
+            "--output",
+            "alfred-json",
+        ]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("sun alfred should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let items = json.get("items").and_then(Value::as_array).expect("items");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[1].get("title").and_then(Value::as_str),
+            Some("2026-02-11 Wed sunrise 06:32 sunset 17:48")
+        );
+        assert!(
+            items[1]
+                .get("subtitle")
+                .and_then(Value::as_str)
+                .is_some_and(|subtitle| subtitle.starts_with("golden 06:32-07:32 16:48-17:48 · "))
+        );
+        assert_eq!(
+            items[1]
+                .get("weather_meta")
+                .and_then(|meta| meta.get("item_kind"))
+                .and_then(Value::as_str),
+            Some("sun")
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "sun", "--city", "Taipei", "--lang", "zh"]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("sun text should pass");
+        assert!(output.contains("日出 06:32 日落 17:48 (11h16m) 黃金時刻"));
+        assert!(output.contains("下弦月"));
+    }
+
+    #[test]
+    fn main_sun_fails_when_provider_has_no_sun_times() {
+        let cli = Cli::parse_from(["weather-cli", "sun", "--city", "Taipei"]);
+        let providers = FakeProviders {
+            open_meteo_result: Err(ProviderError::Transport("timeout".to_string())),
+            ..FakeProviders::ok()
+        };
+
+        let err =
+            run_with(cli, &config_in_tempdir(), &providers, fixed_now).expect_err("must fail");
+        assert_eq!(err.kind, weather_cli::error::ErrorKind::Runtime);
+        assert!(err.message.contains("met_no"), "{}", err.message);
+    }
+
+    #[test]
+    fn main_today_shows_sun_and_moon_details() {
+        let cli = Cli::parse_from(["weather-cli", "today", "--city", "Taipei"]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("today should pass");
+        assert!(output.contains("rain:20% 3.0mm sunrise 06:32 sunset 17:48 Last quarter"));
+
+        let cli = Cli::parse_from(["weather-cli", "week", "--city", "Taipei"]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("week should pass");
+        assert!(!output.contains("sunrise"));
+    }
+
+    #[test]
+    fn main_formats_daily_text_in_metric_and_imperial() {
+        let cli = Cli::parse_from(["weather-cli", "today", "--city", "Taipei"]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("metric text");
+        assert!(
+            output.contains("Cloudy 14.5~20.1°C rain:20% 3.0mm"),
+            "{output}"
+        );
+
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "today",
+            "--city",
+            "Taipei",
+            "--units",
+            "imperial",
+        ]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("imperial text");
+        assert!(
+            output.contains("Cloudy 58.1~68.2°F rain:20% 0.12in"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn main_formats_current_conditions_in_imperial() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "now",
+            "--city",
+            "Taipei",
+            "--units",
+            "imperial",
+        ]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("imperial text");
+        assert!(
+            output.contains("Cloudy 63.0°F feels 61.5°F humidity:78% wind:5.8mph NE"),
+            "{output}"
+        );
+
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "now",
+            "--city",
+            "Taipei",
+            "--units",
+            "imperial",
+            "--output",
+            "alfred-json",
+        ]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("imperial alfred");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let item = &json["items"][0];
+        assert_eq!(
+            item.get("title").and_then(Value::as_str),
+            Some("Taipei City 63.0°F Cloudy")
+        );
+        assert_eq!(item["weather_meta"]["temp_unit"], "°F");
+        assert_eq!(item["weather_meta"]["wind_speed_label"], "5.8mph");
+        assert_eq!(item["weather_meta"]["temp_c"].as_f64(), Some(17.2));
+    }
+
+    #[test]
+    fn main_imperial_json_renames_metric_fields() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "today",
             "--city",
             "Taipei",
             "--output",
             "json",
+            "--units",
+            "imperial",
         ]);
-
-        let output =
-            run_with(cli, &config_in_tempdir(), &providers, fixed_now).expect("week should pass");
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("imperial json");
         let json: Value = serde_json::from_str(&output).expect("json");
+        let result = json.get("result").expect("result");
+        let day = &result["forecast"][0];
 
+        assert_eq!(result["units"], "imperial");
+        assert_eq!(day["temp_min_f"].as_f64(), Some(58.1));
+        assert_eq!(day["temp_max_f"].as_f64(), Some(68.2));
+        assert_eq!(day["precip_sum_in"].as_f64(), Some(0.12));
+        assert!(day.get("temp_min_c").is_none());
+        assert_eq!(result["freshness"]["ttl_secs"].as_u64(), Some(1800));
+
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "today",
+            "--city",
+            "Taipei",
+            "--output",
+            "json",
+        ]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("metric json");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert!(json["result"].get("units").is_none());
         assert_eq!(
-            json.get("schema_version").and_then(Value::as_str),
-            Some("cli-envelope@v1")
-        );
-        assert_eq!(
-            json.get("command").and_then(Value::as_str),
-            Some("weather.week")
-        );
-        assert_eq!(
-            json.get("result")
-                .and_then(|result| result.get("period"))
-                .and_then(Value::as_str),
-            Some("week")
-        );
-        assert_eq!(
-            json.get("result")
-                .and_then(|result| result.get("forecast"))
-                .and_then(Value::as_array)
-                .map(Vec::len),
-            Some(7)
+            json["result"]["forecast"][0]["temp_min_c"].as_f64(),
+            Some(14.5)
         );
     }
 
     #[test]
-    fn main_outputs_hourly_json_contract() {
+    fn main_units_flag_overrides_configured_units() {
+        let config = RuntimeConfig {
+            units: UnitSystem::Imperial,
+            ..config_in_tempdir()
+        };
+
+        let cli = Cli::parse_from(["weather-cli", "hourly", "--city", "Taipei"]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("hourly");
+        assert!(output.contains("61.0°F"), "{output}");
+
         let cli = Cli::parse_from([
             "weather-cli",
             "hourly",
             "--city",
+            "Taipei",
+            "--units",
+            "metric",
+        ]);
+        let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("hourly");
+        assert!(output.contains("16.1°C"), "{output}");
+    }
+
+    #[test]
+    fn compass_label_rounds_to_eight_points() {
+        assert_eq!(compass_label(0), "N");
+        assert_eq!(compass_label(22), "N");
+        assert_eq!(compass_label(23), "NE");
+        assert_eq!(compass_label(180), "S");
+        assert_eq!(compass_label(338), "N");
+        assert_eq!(compass_label(300), "NW");
+    }
+
+    #[test]
+    fn main_accepts_hours_alias_for_hourly_command() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "hours",
+            "--city",
             "Tokyo",
+            "--hours",
+            "12",
             "--output",
             "json",
         ]);
+        assert_eq!(cli.command_name(), "weather.hourly");
 
         let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
-            .expect("hourly should pass");
+            .expect("hours alias should pass");
         let json: Value = serde_json::from_str(&output).expect("json");
 
-        assert_eq!(
-            json.get("schema_version").and_then(Value::as_str),
-            Some("cli-envelope@v1")
-        );
         assert_eq!(
             json.get("command").and_then(Value::as_str),
             Some("weather.hourly")
         );
-        assert_eq!(
+        assert!(
             json.get("result")
                 .and_then(|result| result.get("hourly"))
                 .and_then(Value::as_array)
-                .map(Vec::len),
-            Some(1)
+                .is_some()
         );
     }
 
@@ -1396,6 +4076,166 @@ mod tests {
         assert_eq!(providers.batch_calls.get(), 1);
     }
 
+    fn config_with_favorites() -> RuntimeConfig {
+        RuntimeConfig {
+            favorite_locations: weather_cli::favorites::parse_favorite_locations(
+                "home=Taipei;cabin=24.15,121.28",
+            ),
+            ..config_in_tempdir()
+        }
+    }
+
+    #[test]
+    fn main_outputs_locations_json_and_text() {
+        let cli = Cli::parse_from(["weather-cli", "locations", "--output", "json"]);
+        let output = run_with(
+            cli,
+            &config_with_favorites(),
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("locations json should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+
+        assert_eq!(
+            json.get("command").and_then(Value::as_str),
+            Some("weather.locations")
+        );
+        assert_eq!(
+            json.pointer("/result/locations/1/query")
+                .and_then(Value::as_str),
+            Some("24.15,121.28")
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "locations"]);
+        let output = run_with(
+            cli,
+            &config_with_favorites(),
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("locations text should pass");
+        assert_eq!(output, "home\tTaipei\ncabin\t24.15,121.28");
+    }
+
+    #[test]
+    fn main_outputs_locations_alfred_rows() {
+        let cli = Cli::parse_from(["weather-cli", "locations", "--output", "alfred-json"]);
+        let output = run_with(
+            cli,
+            &config_with_favorites(),
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("locations alfred should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        let first = json.pointer("/items/0").expect("first row");
+
+        assert_eq!(first.get("title").and_then(Value::as_str), Some("home"));
+        assert_eq!(
+            first.get("autocomplete").and_then(Value::as_str),
+            Some("home")
+        );
+        assert_eq!(
+            first
+                .pointer("/weather_meta/item_kind")
+                .and_then(Value::as_str),
+            Some("favorite")
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "locations", "--output", "alfred-json"]);
+        let output = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect("empty locations should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(
+            json.pointer("/items/0/title").and_then(Value::as_str),
+            Some("No favorite locations")
+        );
+    }
+
+    #[test]
+    fn main_expands_favorite_names_passed_as_city() {
+        let cli = Cli::parse_from([
+            "weather-cli",
+            "today",
+            "--city",
+            "Home",
+            "--city",
+            "Tokyo",
+            "--output",
+            "json",
+        ]);
+        let output = run_with(
+            cli,
+            &config_with_favorites(),
+            &MultiCityProviders::new(),
+            fixed_now,
+        )
+        .expect("favorite batch should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(
+            json.pointer("/result/entries/0/city")
+                .and_then(Value::as_str),
+            Some("Taipei")
+        );
+
+        let cli = Cli::parse_from(["weather-cli", "now", "--city", "cabin", "--output", "json"]);
+        let output = run_with(
+            cli,
+            &config_with_favorites(),
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("coordinate favorite should pass");
+        let json: Value = serde_json::from_str(&output).expect("json");
+        assert_eq!(
+            json.pointer("/result/location/latitude")
+                .and_then(Value::as_f64),
+            Some(24.15)
+        );
+    }
+
+    #[test]
+    fn main_rejects_coordinate_favorites_in_batch_mode() {
+        let cli = Cli::parse_from(["weather-cli", "today", "--city", "cabin", "--city", "Tokyo"]);
+        let err = run_with(
+            cli,
+            &config_with_favorites(),
+            &MultiCityProviders::new(),
+            fixed_now,
+        )
+        .expect_err("must fail");
+
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_INPUT);
+        assert!(err.message.contains("cabin"));
+    }
+
+    #[test]
+    fn main_auto_locates_when_no_location_is_given() {
+        let config = RuntimeConfig {
+            auto_locate_ttl_secs: Some(3600),
+            ..config_in_tempdir()
+        };
+
+        for command in ["today", "now", "sun"] {
+            let cli = Cli::parse_from(["weather-cli", command, "--output", "json"]);
+            let output = run_with(cli, &config, &FakeProviders::ok(), fixed_now)
+                .unwrap_or_else(|error| panic!("{command} should pass: {error:?}"));
+            let json: Value = serde_json::from_str(&output).expect("json");
+            assert_eq!(
+                json.pointer("/result/location/name")
+                    .and_then(Value::as_str),
+                Some("Banqiao"),
+                "{command}"
+            );
+        }
+
+        let cli = Cli::parse_from(["weather-cli", "today", "--output", "json"]);
+        let err = run_with(cli, &config_in_tempdir(), &FakeProviders::ok(), fixed_now)
+            .expect_err("must fail without opt-in");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_INPUT);
+    }
+
     #[test]
     fn main_accepts_negative_longitude_values() {
         let cli = Cli::try_parse_from([
@@ -1498,6 +4338,414 @@ mod tests {
         assert!(output.contains("降雨:20%"));
     }
 
+    #[test]
+    fn main_week_keeps_only_days_in_the_window() {
+        let config = config_in_tempdir();
+        let base = FakeProviders::ok();
+        let Ok(forecast) = &base.open_meteo_result else {
+            panic!("fake forecast");
+        };
+        let template = forecast.days[0].clone();
+        // Wednesday 2026-02-11 through Tuesday 2026-02-17.
+        let days = (11..=17)
+            .map(|day| ProviderForecastDay {
+                date: format!("2026-02-{day}"),
+                ..template.clone()
+            })
+            .collect();
+        let providers = FakeProviders {
+            open_meteo_result: Ok(ProviderForecast {
+                days,
+                ..forecast.clone()
+            }),
+            ..FakeProviders::ok()
+        };
+        let run = |args: &[&str]| {
+            let mut argv = vec!["weather-cli", "week", "--city", "Taipei"];
+            argv.extend_from_slice(args);
+            run_with(Cli::parse_from(argv), &config, &providers, fixed_now)
+        };
+        let dates = |output: String| {
+            let json: Value = serde_json::from_str(&output).expect("json");
+            json.pointer("/result/forecast")
+                .and_then(Value::as_array)
+                .expect("forecast")
+                .iter()
+                .map(|day| day["date"].as_str().unwrap_or_default().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dates(run(&["--days", "weekend", "--output", "json"]).expect("weekend")),
+            vec!["2026-02-14", "2026-02-15"]
+        );
+        assert_eq!(
+            dates(
+                run(&[
+                    "--days",
+                    "sun,mon",
+                    "--from",
+                    "2026-02-15",
+                    "--output",
+                    "json"
+                ])
+                .expect("window")
+            ),
+            vec!["2026-02-15", "2026-02-16"]
+        );
+
+        let beyond = run(&["--to", "2026-02-25"]).expect_err("beyond horizon");
+        assert_eq!(beyond.code, ERROR_CODE_USER_INVALID_INPUT);
+        assert!(beyond.message.contains("outside the forecast horizon"));
+        let invalid = run(&["--days", "funday"]).expect_err("bad weekday");
+        assert_eq!(invalid.code, ERROR_CODE_USER_INVALID_INPUT);
+    }
+
+    #[test]
+    fn main_notify_dry_run_reports_triggered_conditions_only() {
+        let config = config_in_tempdir();
+        let run = |args: &[&str]| {
+            let mut argv = vec!["weather-cli", "notify", "--city", "Taipei", "--dry-run"];
+            argv.extend_from_slice(args);
+            run_with(
+                Cli::parse_from(argv),
+                &config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+        };
+
+        assert_eq!(
+            run(&["--when", "rain>10 or tmax>35"]).expect("triggered"),
+            "Weather alert: Taipei City: 2026-02-11 rain 20%, tmax 20.1°C"
+        );
+        assert_eq!(run(&["--when", "rain>50"]).expect("quiet"), "");
+
+        let json: Value = serde_json::from_str(
+            &run(&[
+                "--when", "tmax>=68", "--units", "imperial", "--output", "json",
+            ])
+            .expect("json"),
+        )
+        .expect("json");
+        assert_eq!(json["command"], "weather.notify");
+        assert_eq!(json.pointer("/result/triggered"), Some(&json!(true)));
+        assert_eq!(json.pointer("/result/notified"), Some(&json!(false)));
+        assert_eq!(
+            json.pointer("/result/message"),
+            Some(&json!("2026-02-11 tmax 68.2°F"))
+        );
+
+        let alfred: Value = serde_json::from_str(
+            &run(&["--when", "uv>8", "--output", "alfred-json"]).expect("alfred"),
+        )
+        .expect("json");
+        assert_eq!(
+            alfred.pointer("/items/0/title"),
+            Some(&json!("No weather alert: Taipei City"))
+        );
+        assert!(alfred.pointer("/items/0/icon/path").is_some());
+
+        let invalid = run(&["--when", "temp>30"]).expect_err("unknown field");
+        assert_eq!(invalid.code, ERROR_CODE_USER_INVALID_INPUT);
+        assert!(invalid.message.contains("unknown field 'temp'"));
+    }
+
+    #[test]
+    fn main_offline_serves_only_cached_entries() {
+        let config = config_in_tempdir();
+        let later = || fixed_now() + chrono::Duration::hours(2);
+        let json_status = |output: &str| -> String {
+            let json: Value = serde_json::from_str(output).expect("json");
+            json.pointer("/result/freshness/status")
+                .and_then(Value::as_str)
+                .expect("status")
+                .to_string()
+        };
+        let today = |args: &[&str]| {
+            let mut argv = vec!["weather-cli", "today", "--output", "json"];
+            argv.extend_from_slice(args);
+            Cli::parse_from(argv)
+        };
+
+        run_with(
+            today(&["--city", "Taipei"]),
+            &config,
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("online run fills the cache");
+
+        // The working providers are ignored offline, so the expired entry is served instead of refetched.
+        let output = run_with(
+            today(&["--city", "Taipei", "--offline"]),
+            &config,
+            &FakeProviders::ok(),
+            later,
+        )
+        .expect("offline serves the cache");
+        assert_eq!(json_status(&output), "cache_stale_fallback");
+
+        let offline_config = RuntimeConfig {
+            offline: true,
+            ..config.clone()
+        };
+        let output = run_with(
+            today(&["--city", "Taipei"]),
+            &offline_config,
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect("WEATHER_OFFLINE serves the cache");
+        assert_eq!(json_status(&output), "cache_fresh");
+
+        let err = run_with(
+            today(&["--city", "Osaka"]),
+            &offline_config,
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect_err("nothing cached");
+        assert_eq!(err.kind, weather_cli::error::ErrorKind::User);
+        assert!(err.message.starts_with("offline mode:"), "{}", err.message);
+
+        let err = run_with(
+            Cli::parse_from(["weather-cli", "warm", "--city", "Taipei", "--offline"]),
+            &config,
+            &FakeProviders::ok(),
+            fixed_now,
+        )
+        .expect_err("warm needs the network");
+        assert_eq!(err.kind, weather_cli::error::ErrorKind::User);
+    }
+
+    #[test]
+    fn main_localizes_labels_from_lang_flag_or_weather_lang() {
+        let config = RuntimeConfig {
+            language: Locale::parse("de").expect("de"),
+            ..config_in_tempdir()
+        };
+        let run = |args: &[&str]| {
+            let mut argv = vec!["weather-cli", "today", "--city", "Taipei"];
+            argv.extend_from_slice(args);
+            run_with(
+                Cli::parse_from(argv),
+                &config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+            .expect("today")
+        };
+
+        let german = run(&[]);
+        assert!(german.contains("2026-02-11 Mi Bewölkt"), "{german}");
+        assert!(german.contains("Regen:20%"), "{german}");
+
+        let japanese = run(&["--lang", "ja-JP"]);
+        assert!(japanese.contains("2026-02-11 水曜 曇り"), "{japanese}");
+        assert!(japanese.contains("降水:20%"), "{japanese}");
+
+        let error = Cli::try_parse_from(["weather-cli", "today", "--lang", "fr"])
+            .expect_err("unsupported language");
+        assert!(error.to_string().contains("en, zh, ja, de"), "{error}");
+    }
+
+    #[test]
+    fn main_shows_wind_humidity_and_uv_only_with_extended_detail() {
+        let base = config_in_tempdir();
+        let extended = RuntimeConfig {
+            extended_detail: true,
+            ..base.clone()
+        };
+        let run = |config: &RuntimeConfig, args: &[&str]| {
+            let mut argv = vec!["weather-cli"];
+            argv.extend_from_slice(args);
+            run_with(
+                Cli::parse_from(argv),
+                config,
+                &FakeProviders::ok(),
+                fixed_now,
+            )
+            .expect("command should pass")
+        };
+
+        let text = run(&extended, &["today", "--city", "Taipei"]);
+        assert!(
+            text.contains("wind:21.6km/h NE humidity:78% UV:6.4"),
+            "{text}"
+        );
+        assert!(!run(&base, &["today", "--city", "Taipei"]).contains("humidity"));
+
+        let alfred = run(
+            &extended,
+            &["week", "--city", "Taipei", "--output", "alfred-json"],
+        );
+        assert!(
+            alfred.contains("wind:21.6km/h NE humidity:78% UV:6.4"),
+            "{alfred}"
+        );
+        let alfred = run(
+            &base,
+            &["week", "--city", "Taipei", "--output", "alfred-json"],
+        );
+        assert!(!alfred.contains("humidity"), "{alfred}");
+
+        let json: Value = serde_json::from_str(&run(
+            &base,
+            &["today", "--city", "Taipei", "--output", "json"],
+        ))
+        .expect("json");
+        let day = json.pointer("/result/forecast/0").expect("day");
+        assert_eq!(day["wind_speed_max_kmh"], 21.6);
+        assert_eq!(day["wind_direction_deg"], 45);
+        assert_eq!(day["humidity_mean_pct"], 78);
+        assert_eq!(day["uv_index_max"], 6.4);
+    }
+
+    #[test]
+    fn main_attaches_an_icon_to_every_alfred_item() {
+        let config = config_in_tempdir();
+        let icon_paths = |args: &[&str], run: &dyn Fn(Cli) -> String| -> Vec<String> {
+            let mut argv = vec!["weather-cli"];
+            argv.extend_from_slice(args);
+            argv.extend_from_slice(&["--output", "alfred-json"]);
+            let json: Value = serde_json::from_str(&run(Cli::parse_from(argv))).expect("json");
+            json.get("items")
+                .and_then(Value::as_array)
+                .expect("items")
+                .iter()
+                .map(|item| {
+                    item.pointer("/icon/path")
+                        .and_then(Value::as_str)
+                        .unwrap_or_else(|| panic!("item without icon: {item}"))
+                        .to_string()
+                })
+                .collect()
+        };
+        let single = |cli: Cli| {
+            run_with(cli, &config, &FakeProviders::ok(), fixed_now).expect("command should pass")
+        };
+        let multi = |cli: Cli| {
+            run_with(cli, &config, &MultiCityProviders::new(), fixed_now)
+                .expect("batch should pass")
+        };
+
+        for args in [
+            &["today", "--city", "Taipei"][..],
+            &["week", "--city", "Taipei"],
+            &["hourly", "--city", "Taipei"],
+            &["now", "--city", "Taipei"],
+            &["sun", "--city", "Taipei"],
+            &["rain", "--city", "Taipei"],
+            &["history", "--date", "2025-08-01", "--city", "Taipei"],
+            &["locations"],
+        ] {
+            let paths = icon_paths(args, &single);
+            assert!(
+                paths
+                    .iter()
+                    .all(|path| path.starts_with("assets/icons/weather/") && path.ends_with(".png")),
+                "{args:?}: {paths:?}"
+            );
+        }
+
+        assert_eq!(
+            icon_paths(&["week", "--city", "Taipei"], &single)[0],
+            "assets/icons/weather/cloudy.png"
+        );
+        assert_eq!(
+            icon_paths(&["locations"], &single),
+            vec!["assets/icons/weather/unknown.png"]
+        );
+        let batch = icon_paths(&["today", "--city", "Taipei", "--city", "Atlantis"], &multi);
+        assert_eq!(
+            batch.last().map(String::as_str),
+            Some("assets/icons/weather/unknown.png")
+        );
+    }
+
+    #[test]
+    fn main_compares_two_cities_second_minus_first() {
+        let config = config_in_tempdir();
+        let run = |args: &[&str]| {
+            let mut argv = vec!["weather-cli", "compare"];
+            argv.extend_from_slice(args);
+            run_with(
+                Cli::parse_from(argv),
+                &config,
+                &MultiCityProviders::new(),
+                fixed_now,
+            )
+        };
+
+        let text = run(&["--city", "Taipei", "--city", "Tokyo"]).expect("compare");
+        assert!(text.starts_with("Taipei (Asia/Taipei)"), "{text}");
+        assert!(
+            text.ends_with("Tokyo vs Taipei: high -7.5°C low -9.3°C rain -10%"),
+            "{text}"
+        );
+
+        let json: Value = serde_json::from_str(
+            &run(&[
+                "--city", "Taipei", "--city", "Tokyo", "--output", "json", "--units", "imperial",
+            ])
+            .expect("compare json"),
+        )
+        .expect("json");
+        assert_eq!(json["command"], "weather.compare");
+        assert_eq!(
+            json.pointer("/result/days/0/temp_max_delta_f"),
+            Some(&json!(-13.5))
+        );
+        assert_eq!(
+            json.pointer("/result/days/0/precip_prob_delta_pct"),
+            Some(&json!(-10))
+        );
+        assert_eq!(
+            json.pointer("/result/right/location/name"),
+            Some(&json!("Tokyo"))
+        );
+
+        let alfred: Value = serde_json::from_str(
+            &run(&[
+                "--city",
+                "Taipei",
+                "--city",
+                "Tokyo",
+                "--output",
+                "alfred-json",
+            ])
+            .expect("compare alfred"),
+        )
+        .expect("json");
+        let items = alfred["items"].as_array().expect("items");
+        assert!(
+            items
+                .iter()
+                .all(|item| item.pointer("/icon/path").is_some())
+        );
+        let kinds = items
+            .iter()
+            .map(|item| {
+                item["weather_meta"]["item_kind"]
+                    .as_str()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, ["compare_city", "compare_city", "compare_delta"]);
+
+        for args in [
+            &["--city", "Taipei"][..],
+            &["--city", "Taipei", "--city", "taipei"],
+            &["--city", "Taipei", "--city", "Tokyo", "--city", "Taipei "],
+        ] {
+            let err = run(args).expect_err("needs two different cities");
+            assert_eq!(err.kind, weather_cli::error::ErrorKind::User, "{args:?}");
+        }
+        let err = run(&["--city", "Taipei", "--city", "Atlantis"]).expect_err("unknown city");
+        assert_eq!(err.kind, weather_cli::error::ErrorKind::Runtime);
+    }
+
     #[test]
     fn main_outputs_alfred_json_mode_when_requested() {
         let cli = Cli::parse_from([
@@ -1,14 +1,20 @@
 use std::collections::HashSet;
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::astronomy::MoonPhase;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ForecastPeriod {
     Today,
     Week,
     Hourly,
+    Now,
+    History,
+    Rain,
 }
 
 impl ForecastPeriod {
@@ -17,6 +23,9 @@ impl ForecastPeriod {
             Self::Today => "today",
             Self::Week => "week",
             Self::Hourly => "hourly",
+            Self::Now => "now",
+            Self::History => "history",
+            Self::Rain => "rain",
         }
     }
 
@@ -25,6 +34,9 @@ impl ForecastPeriod {
             Self::Today => 1,
             Self::Week => 7,
             Self::Hourly => 1,
+            Self::Now => 1,
+            Self::History => 1,
+            Self::Rain => 1,
         }
     }
 }
@@ -43,6 +55,49 @@ pub struct CacheMetadata {
     pub key: String,
     pub ttl_secs: u64,
     pub age_secs: u64,
+    /// Provider that produced the served forecast.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Providers tried for this response, in order; empty when it was served from a fresh cache.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<ProviderAttempt>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderAttempt {
+    pub provider: String,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ProviderAttempt {
+    /// Rebuild attempts from `<provider>: <error>` trace lines, followed by the provider that succeeded, if any.
+    pub fn from_trace(trace: &[String], succeeded: Option<&str>) -> Vec<Self> {
+        let mut attempts = trace
+            .iter()
+            .map(|line| match line.split_once(": ") {
+                Some((provider, error)) => Self {
+                    provider: provider.to_string(),
+                    ok: false,
+                    error: Some(error.to_string()),
+                },
+                None => Self {
+                    provider: line.clone(),
+                    ok: false,
+                    error: None,
+                },
+            })
+            .collect::<Vec<_>>();
+        if let Some(provider) = succeeded {
+            attempts.push(Self {
+                provider: provider.to_string(),
+                ok: true,
+                error: None,
+            });
+        }
+        attempts
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -60,6 +115,24 @@ pub struct ForecastDay {
     pub temp_min_c: f64,
     pub temp_max_c: f64,
     pub precip_prob_max_pct: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precip_sum_mm: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunrise: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moon_phase: Option<MoonPhase>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moon_illumination_pct: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind_speed_max_kmh: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind_direction_deg: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub humidity_mean_pct: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_index_max: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -90,6 +163,52 @@ pub struct ForecastBatchOutput {
     pub entries: Vec<ForecastBatchEntry>,
 }
 
+/// Differences between two cities' forecasts for the same day offset, as `right - left`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompareDay {
+    pub date: String,
+    pub temp_min_delta_c: f64,
+    pub temp_max_delta_c: f64,
+    pub precip_prob_delta_pct: i16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompareOutput {
+    pub left: ForecastOutput,
+    pub right: ForecastOutput,
+    pub days: Vec<CompareDay>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SunDay {
+    pub date: String,
+    pub sunrise: String,
+    pub sunset: String,
+    pub day_length_minutes: i64,
+    pub golden_hour_morning: TimeWindow,
+    pub golden_hour_evening: TimeWindow,
+    pub moon_phase: MoonPhase,
+    pub moon_illumination_pct: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SunOutput {
+    pub location: ForecastLocation,
+    pub timezone: String,
+    pub days: Vec<SunDay>,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_trace: Vec<String>,
+    pub fetched_at: String,
+    pub freshness: CacheMetadata,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HourlyForecastPoint {
     pub datetime: String,
@@ -110,6 +229,123 @@ pub struct HourlyForecastOutput {
     pub freshness: CacheMetadata,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurrentConditions {
+    pub observed_at: String,
+    pub weather_code: i32,
+    pub temp_c: f64,
+    pub apparent_temp_c: f64,
+    pub humidity_pct: u8,
+    pub wind_speed_kmh: f64,
+    pub wind_direction_deg: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurrentWeatherOutput {
+    pub location: ForecastLocation,
+    pub timezone: String,
+    pub current: CurrentConditions,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_trace: Vec<String>,
+    pub fetched_at: String,
+    pub freshness: CacheMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NowcastPoint {
+    pub datetime: String,
+    pub precip_mm: f64,
+}
+
+/// Rain outlook over the nowcast horizon; minutes count from now to the start of the step where it changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RainOutlook {
+    Dry,
+    Raining,
+    Starts { in_minutes: u32 },
+    Stops { in_minutes: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NowcastOutput {
+    pub location: ForecastLocation,
+    pub timezone: String,
+    pub interval_minutes: u32,
+    pub outlook: RainOutlook,
+    pub sparkline: String,
+    pub points: Vec<NowcastPoint>,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_trace: Vec<String>,
+    pub fetched_at: String,
+    pub freshness: CacheMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryDay {
+    pub date: String,
+    pub weather_code: i32,
+    pub summary_zh: String,
+    pub temp_min_c: f64,
+    pub temp_max_c: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_mean_c: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precip_sum_mm: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precip_hours: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wind_speed_max_kmh: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryOutput {
+    pub location: ForecastLocation,
+    pub timezone: String,
+    pub observed: HistoryDay,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_trace: Vec<String>,
+    pub fetched_at: String,
+    pub freshness: CacheMetadata,
+}
+
+/// One refreshed cache entry; `error` is set when the refresh failed (`status` is then a stale fallback or absent).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarmEntry {
+    pub location: String,
+    pub period: ForecastPeriod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<FreshnessStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarmOutput {
+    pub entries: Vec<WarmEntry>,
+}
+
+impl WarmOutput {
+    pub fn failures(&self) -> impl Iterator<Item = &WarmEntry> {
+        self.entries.iter().filter(|entry| entry.error.is_some())
+    }
+}
+
+/// One `notify` check against today's forecast; `notified` is set once the notification was posted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotifyOutput {
+    pub location: ForecastLocation,
+    pub date: String,
+    pub condition: String,
+    pub triggered: bool,
+    pub title: String,
+    pub message: String,
+    pub notified: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputMode {
     Json,
@@ -219,6 +455,23 @@ pub enum ValidationError {
     InvalidLatitude(f64),
     #[error("invalid longitude: {0}")]
     InvalidLongitude(f64),
+    #[error("invalid --days entry '{0}': use mon..sun, weekend, or weekdays")]
+    InvalidWeekday(String),
+    #[error("invalid {flag} '{value}': expected YYYY-MM-DD")]
+    InvalidDate { flag: &'static str, value: String },
+    #[error("--from {from} is after --to {to}")]
+    InvertedDateWindow { from: NaiveDate, to: NaiveDate },
+    #[error("{flag} {date} is outside the forecast horizon ({first} to {last})")]
+    DateOutsideHorizon {
+        flag: &'static str,
+        date: NaiveDate,
+        first: NaiveDate,
+        last: NaiveDate,
+    },
+    #[error("no forecast days match the selected window")]
+    EmptyDateWindow,
+    #[error("invalid --when condition '{condition}': {reason}")]
+    InvalidCondition { condition: String, reason: String },
 }
 
 #[cfg(test)]
@@ -0,0 +1,147 @@
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+
+use crate::condition::Condition;
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::model::{ForecastDay, ForecastLocation, ForecastRequest, NotifyOutput};
+use crate::providers::ProviderApi;
+use crate::service;
+use crate::units::UnitSystem;
+
+/// Evaluate `condition` against the first forecast day (today in the location's timezone). Posting is left to the
+/// caller so a dry run shares the same result.
+pub fn resolve_alert<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    request: &ForecastRequest,
+    condition: &Condition,
+    units: UnitSystem,
+) -> Result<NotifyOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let output = service::resolve_forecast(config, providers, now_fn, request)?;
+    let day = output
+        .forecast
+        .first()
+        .ok_or_else(|| AppError::runtime("forecast returned no days"))?;
+    Ok(alert_for(&output.location, day, condition, units))
+}
+
+fn alert_for(
+    location: &ForecastLocation,
+    day: &ForecastDay,
+    condition: &Condition,
+    units: UnitSystem,
+) -> NotifyOutput {
+    let values = condition
+        .metrics()
+        .into_iter()
+        .filter_map(|metric| metric.label(day, units))
+        .collect::<Vec<_>>();
+    let detail = if values.is_empty() {
+        condition.source().to_string()
+    } else {
+        values.join(", ")
+    };
+
+    NotifyOutput {
+        location: location.clone(),
+        date: day.date.clone(),
+        condition: condition.source().to_string(),
+        triggered: condition.evaluate(day, units),
+        title: format!("Weather alert: {}", location.name),
+        message: format!("{} {detail}", day.date),
+        notified: false,
+    }
+}
+
+/// Post a notification through `osascript` (macOS). A non-zero exit is an error so scheduled runs surface it.
+pub fn post_notification(title: &str, message: &str) -> Result<(), AppError> {
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(notification_script(title, message))
+        .output()
+        .map_err(|error| AppError::runtime(format!("failed to launch osascript: {error}")))?;
+    if !output.status.success() {
+        return Err(AppError::runtime(format!(
+            "osascript exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+fn notification_script(title: &str, message: &str) -> String {
+    format!(
+        "display notification {} with title {}",
+        applescript_string(message),
+        applescript_string(title)
+    )
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day() -> ForecastDay {
+        ForecastDay {
+            date: "2026-02-11".to_string(),
+            weather_code: 61,
+            summary_zh: "小雨".to_string(),
+            temp_min_c: 24.0,
+            temp_max_c: 36.2,
+            precip_prob_max_pct: 80,
+            precip_sum_mm: Some(12.5),
+            sunrise: None,
+            sunset: None,
+            moon_phase: None,
+            moon_illumination_pct: None,
+            wind_speed_max_kmh: None,
+            wind_direction_deg: None,
+            humidity_mean_pct: None,
+            uv_index_max: None,
+        }
+    }
+
+    fn location() -> ForecastLocation {
+        ForecastLocation {
+            name: "Taipei City".to_string(),
+            latitude: 25.05,
+            longitude: 121.52,
+        }
+    }
+
+    #[test]
+    fn notify_alert_lists_the_values_the_condition_names() {
+        let condition = Condition::parse("rain>60 or tmax>35 or wind>50").expect("condition");
+        let alert = alert_for(&location(), &day(), &condition, UnitSystem::Metric);
+
+        assert!(alert.triggered);
+        assert!(!alert.notified);
+        assert_eq!(alert.title, "Weather alert: Taipei City");
+        assert_eq!(alert.message, "2026-02-11 rain 80%, tmax 36.2°C");
+
+        let quiet = Condition::parse("humidity>90").expect("condition");
+        let alert = alert_for(&location(), &day(), &quiet, UnitSystem::Metric);
+        assert!(!alert.triggered);
+        assert_eq!(alert.message, "2026-02-11 humidity>90");
+    }
+
+    #[test]
+    fn notify_script_escapes_applescript_strings() {
+        assert_eq!(
+            notification_script(r#"Say "hi""#, r"C:\rain"),
+            r#"display notification "C:\\rain" with title "Say \"hi\"""#
+        );
+    }
+}
@@ -0,0 +1,573 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, Duration, NaiveDateTime, SecondsFormat, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::error::AppError;
+use crate::geocoding::{
+    ResolvedLocation, city_query_cache_key, location_from_coordinates, read_cached_city_location,
+    write_cached_city_location,
+};
+use crate::model::{
+    CacheMetadata, ForecastLocation, ForecastPeriod, FreshnessStatus, LocationQuery, NowcastOutput,
+    NowcastPoint, ProviderAttempt, RainOutlook,
+};
+use crate::providers::{ProviderApi, ProviderNowcast};
+
+pub const NOWCAST_INTERVAL_MINUTES: u32 = 15;
+/// Two hours of 15-minute steps.
+pub const NOWCAST_STEPS: usize = 8;
+/// Steps fetched and cached, so a cached entry still covers the full horizon as time moves on.
+const NOWCAST_FETCH_STEPS: usize = 16;
+/// Precipitation per step (mm) below which a step counts as dry.
+pub const RAIN_THRESHOLD_MM: f64 = 0.1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct NowcastCacheRecord {
+    location: ForecastLocation,
+    timezone: String,
+    utc_offset_seconds: i32,
+    points: Vec<NowcastPoint>,
+    source: String,
+    #[serde(default)]
+    source_trace: Vec<String>,
+    fetched_at: String,
+}
+
+#[derive(Debug, Clone)]
+struct OutputContext {
+    cache_key: String,
+    ttl_secs: u64,
+    now: DateTime<Utc>,
+}
+
+/// Precipitation for the next two hours from the current 15-minute step. Shares the short `now` cache TTL.
+pub fn resolve_nowcast<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    location_query: &LocationQuery,
+) -> Result<NowcastOutput, AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    let now = now_fn();
+    let ttl_secs = config.current_cache_ttl_secs();
+
+    let (cache_key, mut resolved_location) = match location_query {
+        LocationQuery::City(city) => (city_query_cache_key(city), None),
+        _ => {
+            let location = resolve_location(config, providers, location_query)?;
+            let key = location.cache_key();
+            (key, Some(location))
+        }
+    };
+    let path = crate::cache::cache_path(&config.cache_dir, ForecastPeriod::Rain, &cache_key);
+    let output_context = OutputContext {
+        cache_key: cache_key.clone(),
+        ttl_secs,
+        now,
+    };
+
+    let cached = read_nowcast_cache(&path).map_err(|error| AppError::runtime(error.to_string()))?;
+    let cached_state = cached.map(|record| {
+        let age_secs = record_age_secs(&record, now, ttl_secs);
+        (record, age_secs)
+    });
+
+    if let Some((record, age_secs)) = &cached_state
+        && *age_secs <= ttl_secs
+    {
+        return Ok(build_output_from_record(
+            record,
+            FreshnessStatus::CacheFresh,
+            *age_secs,
+            Vec::new(),
+            &output_context,
+        ));
+    }
+
+    let location = match resolved_location.take() {
+        Some(location) => location,
+        None => match cached_state.as_ref() {
+            Some((record, _)) => resolved_location_from_record(record),
+            None => resolve_location(config, providers, location_query)?,
+        },
+    };
+
+    match providers.fetch_open_meteo_nowcast(
+        location.latitude,
+        location.longitude,
+        NOWCAST_FETCH_STEPS,
+    ) {
+        Ok(nowcast) => build_live_output(&path, &location, nowcast, &output_context),
+        Err(error) => {
+            let trace = vec![format!("open_meteo: {error}")];
+            match cached_state {
+                Some((mut record, age_secs)) => {
+                    let attempts = ProviderAttempt::from_trace(&trace, None);
+                    record.source_trace = trace;
+                    Ok(build_output_from_record(
+                        &record,
+                        FreshnessStatus::CacheStaleFallback,
+                        age_secs,
+                        attempts,
+                        &output_context,
+                    ))
+                }
+                None => Err(AppError::runtime_with_trace(
+                    "failed to fetch precipitation nowcast from providers",
+                    &trace,
+                )),
+            }
+        }
+    }
+}
+
+/// Whether it is raining in the first step, and when that changes within `points`.
+pub fn rain_outlook(
+    points: &[NowcastPoint],
+    now: DateTime<Utc>,
+    utc_offset_seconds: i32,
+) -> RainOutlook {
+    let Some(first) = points.first() else {
+        return RainOutlook::Dry;
+    };
+    let raining_now = is_wet(first);
+    let change = points.iter().find(|point| is_wet(point) != raining_now);
+    let in_minutes = |point: &NowcastPoint| minutes_until(point, now, utc_offset_seconds);
+
+    match (raining_now, change) {
+        (false, None) => RainOutlook::Dry,
+        (true, None) => RainOutlook::Raining,
+        (false, Some(point)) => RainOutlook::Starts {
+            in_minutes: in_minutes(point),
+        },
+        (true, Some(point)) => RainOutlook::Stops {
+            in_minutes: in_minutes(point),
+        },
+    }
+}
+
+/// One ASCII character per step on a fixed scale, so drizzle never looks like a downpour:
+/// `_` dry, then `.` `:` `-` `=` `#` up to 3 mm or more per step.
+pub fn sparkline(points: &[NowcastPoint]) -> String {
+    points
+        .iter()
+        .map(|point| match point.precip_mm {
+            mm if mm < RAIN_THRESHOLD_MM => '_',
+            mm if mm < 0.3 => '.',
+            mm if mm < 0.7 => ':',
+            mm if mm < 1.5 => '-',
+            mm if mm < 3.0 => '=',
+            _ => '#',
+        })
+        .collect()
+}
+
+fn is_wet(point: &NowcastPoint) -> bool {
+    point.precip_mm >= RAIN_THRESHOLD_MM
+}
+
+fn minutes_until(point: &NowcastPoint, now: DateTime<Utc>, utc_offset_seconds: i32) -> u32 {
+    let local_now = (now + Duration::seconds(i64::from(utc_offset_seconds))).naive_utc();
+    NaiveDateTime::parse_from_str(&point.datetime, "%Y-%m-%dT%H:%M")
+        .ok()
+        .and_then(|start| u32::try_from(start.signed_duration_since(local_now).num_minutes()).ok())
+        .unwrap_or(0)
+}
+
+fn resolve_location<P: ProviderApi>(
+    config: &RuntimeConfig,
+    providers: &P,
+    location: &LocationQuery,
+) -> Result<ResolvedLocation, AppError> {
+    match location {
+        LocationQuery::City(city) => {
+            if let Ok(Some(cached)) = read_cached_city_location(&config.cache_dir, city) {
+                return Ok(cached);
+            }
+
+            let resolved = providers.geocode_city(city).map_err(|error| {
+                AppError::runtime(format!("failed to resolve city '{city}': {error}"))
+            })?;
+
+            let _ = write_cached_city_location(&config.cache_dir, city, &resolved);
+            Ok(resolved)
+        }
+        LocationQuery::Coordinates { lat, lon } => Ok(location_from_coordinates(*lat, *lon)),
+    }
+}
+
+fn build_live_output(
+    path: &Path,
+    location: &ResolvedLocation,
+    nowcast: ProviderNowcast,
+    output_context: &OutputContext,
+) -> Result<NowcastOutput, AppError> {
+    let timezone = if nowcast.timezone.trim().is_empty() {
+        location.timezone.clone()
+    } else {
+        nowcast.timezone
+    };
+
+    let record = NowcastCacheRecord {
+        location: location.to_output_location(),
+        timezone,
+        utc_offset_seconds: nowcast.utc_offset_seconds,
+        points: nowcast
+            .points
+            .into_iter()
+            .map(|point| NowcastPoint {
+                datetime: point.datetime,
+                precip_mm: round1(point.precip_mm),
+            })
+            .collect(),
+        source: "open_meteo".to_string(),
+        source_trace: Vec::new(),
+        fetched_at: nowcast
+            .fetched_at
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+    };
+
+    write_nowcast_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
+
+    let attempts = ProviderAttempt::from_trace(&record.source_trace, Some(&record.source));
+    Ok(build_output_from_record(
+        &record,
+        FreshnessStatus::Live,
+        0,
+        attempts,
+        output_context,
+    ))
+}
+
+fn build_output_from_record(
+    record: &NowcastCacheRecord,
+    status: FreshnessStatus,
+    age_secs: u64,
+    attempts: Vec<ProviderAttempt>,
+    output_context: &OutputContext,
+) -> NowcastOutput {
+    let points = take_current_steps(
+        &record.points,
+        output_context.now,
+        record.utc_offset_seconds,
+    );
+
+    NowcastOutput {
+        location: record.location.clone(),
+        timezone: record.timezone.clone(),
+        interval_minutes: NOWCAST_INTERVAL_MINUTES,
+        outlook: rain_outlook(&points, output_context.now, record.utc_offset_seconds),
+        sparkline: sparkline(&points),
+        points,
+        source: record.source.clone(),
+        source_trace: record.source_trace.clone(),
+        fetched_at: record.fetched_at.clone(),
+        freshness: CacheMetadata {
+            status,
+            key: output_context.cache_key.clone(),
+            ttl_secs: output_context.ttl_secs,
+            age_secs,
+            provider: Some(record.source.clone()),
+            attempts,
+        },
+    }
+}
+
+/// Steps from the one containing `now`, up to the two-hour horizon.
+fn take_current_steps(
+    points: &[NowcastPoint],
+    now: DateTime<Utc>,
+    utc_offset_seconds: i32,
+) -> Vec<NowcastPoint> {
+    let start = current_step_start_label(now, utc_offset_seconds);
+    points
+        .iter()
+        .filter(|point| point.datetime.as_str() >= start.as_str())
+        .take(NOWCAST_STEPS)
+        .cloned()
+        .collect()
+}
+
+fn current_step_start_label(now: DateTime<Utc>, utc_offset_seconds: i32) -> String {
+    let local = now + Duration::seconds(i64::from(utc_offset_seconds));
+    let step = NOWCAST_INTERVAL_MINUTES;
+    let floored = local
+        .with_minute(local.minute() / step * step)
+        .and_then(|value| value.with_second(0))
+        .and_then(|value| value.with_nanosecond(0))
+        .unwrap_or(local);
+    floored.format("%Y-%m-%dT%H:%M").to_string()
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn resolved_location_from_record(record: &NowcastCacheRecord) -> ResolvedLocation {
+    ResolvedLocation {
+        name: record.location.name.clone(),
+        latitude: record.location.latitude,
+        longitude: record.location.longitude,
+        timezone: record.timezone.clone(),
+    }
+}
+
+/// Seconds since the record was fetched; an unparsable timestamp counts as just past the TTL.
+fn record_age_secs(record: &NowcastCacheRecord, now: DateTime<Utc>, ttl_secs: u64) -> u64 {
+    DateTime::parse_from_rfc3339(&record.fetched_at)
+        .ok()
+        .map(|fetched_at| {
+            now.signed_duration_since(fetched_at.with_timezone(&Utc))
+                .num_seconds()
+                .max(0)
+                .try_into()
+                .unwrap_or(u64::MAX)
+        })
+        .unwrap_or(ttl_secs.saturating_add(1))
+}
+
+fn read_nowcast_cache(path: &Path) -> io::Result<Option<NowcastCacheRecord>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let payload = fs::read_to_string(path)?;
+    Ok(serde_json::from_str::<NowcastCacheRecord>(&payload).ok())
+}
+
+fn write_nowcast_cache(path: &Path, record: &NowcastCacheRecord) -> io::Result<()> {
+    let payload = serde_json::to_vec(record)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    let parent = path.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cache path must have a parent directory",
+        )
+    })?;
+    fs::create_dir_all(parent)?;
+
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, payload)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::providers::{
+        ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderHistory,
+        ProviderHourlyForecast, ProviderNowcastPoint,
+    };
+    use crate::units::UnitSystem;
+
+    struct FakeProviders {
+        nowcast_result: Result<ProviderNowcast, ProviderError>,
+        nowcast_calls: Cell<usize>,
+    }
+
+    impl FakeProviders {
+        fn with_nowcast(nowcast_result: Result<ProviderNowcast, ProviderError>) -> Self {
+            Self {
+                nowcast_result,
+                nowcast_calls: Cell::new(0),
+            }
+        }
+
+        /// Dry until 08:30 local, then raining for 45 minutes.
+        fn ok() -> Self {
+            let precip = [0.0, 0.0, 0.4, 1.2, 3.5, 0.0, 0.0, 0.0, 0.0, 0.0];
+            Self::with_nowcast(Ok(ProviderNowcast {
+                timezone: "Asia/Taipei".to_string(),
+                utc_offset_seconds: 8 * 3600,
+                fetched_at: fixed_now(),
+                points: precip
+                    .iter()
+                    .enumerate()
+                    .map(|(index, mm)| ProviderNowcastPoint {
+                        datetime: format!("2026-02-11T{:02}:{:02}", 8 + index / 4, index % 4 * 15),
+                        precip_mm: *mm,
+                    })
+                    .collect(),
+            }))
+        }
+    }
+
+    impl ProviderApi for FakeProviders {
+        fn geocode_city(&self, _city: &str) -> Result<ResolvedLocation, ProviderError> {
+            Ok(ResolvedLocation {
+                name: "Taipei".to_string(),
+                latitude: 25.0478,
+                longitude: 121.5319,
+                timezone: "Asia/Taipei".to_string(),
+            })
+        }
+
+        fn fetch_open_meteo_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_hourly_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_hours: usize,
+        ) -> Result<ProviderHourlyForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            self.nowcast_calls.set(self.nowcast_calls.get() + 1);
+            self.nowcast_result.clone()
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_met_no_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+    }
+
+    fn config_in(dir: &tempfile::TempDir) -> RuntimeConfig {
+        RuntimeConfig {
+            cache_dir: dir.path().to_path_buf(),
+            cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
+        }
+    }
+
+    /// 08:05 in Taipei.
+    fn fixed_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 2, 11, 0, 5, 0)
+            .single()
+            .expect("time")
+    }
+
+    fn taipei() -> LocationQuery {
+        LocationQuery::City("Taipei".to_string())
+    }
+
+    fn points(precip: &[f64]) -> Vec<NowcastPoint> {
+        precip
+            .iter()
+            .enumerate()
+            .map(|(index, mm)| NowcastPoint {
+                datetime: format!("2026-02-11T{:02}:{:02}", 8 + index / 4, index % 4 * 15),
+                precip_mm: *mm,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nowcast_service_reports_rain_start_from_current_step() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let providers = FakeProviders::ok();
+
+        let output =
+            resolve_nowcast(&config_in(&dir), &providers, fixed_now, &taipei()).expect("must pass");
+
+        assert_eq!(output.freshness.status, FreshnessStatus::Live);
+        assert_eq!(output.points.len(), NOWCAST_STEPS);
+        assert_eq!(output.points[0].datetime, "2026-02-11T08:00");
+        assert_eq!(output.outlook, RainOutlook::Starts { in_minutes: 25 });
+        assert_eq!(output.sparkline, "__:-#___");
+    }
+
+    #[test]
+    fn nowcast_service_slides_cached_steps_and_falls_back_when_stale() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = config_in(&dir);
+        resolve_nowcast(&config, &FakeProviders::ok(), fixed_now, &taipei()).expect("prime");
+
+        let failing =
+            FakeProviders::with_nowcast(Err(ProviderError::Transport("timeout".to_string())));
+        let later = || fixed_now() + Duration::minutes(35);
+        let output = resolve_nowcast(&config, &failing, later, &taipei()).expect("stale");
+
+        assert_eq!(output.freshness.status, FreshnessStatus::CacheStaleFallback);
+        assert_eq!(output.points[0].datetime, "2026-02-11T08:30");
+        assert_eq!(output.points.len(), 8);
+        assert_eq!(output.outlook, RainOutlook::Stops { in_minutes: 35 });
+        assert!(output.source_trace[0].contains("timeout"));
+    }
+
+    #[test]
+    fn nowcast_outlook_covers_dry_and_continuous_rain() {
+        let now = fixed_now();
+        assert_eq!(
+            rain_outlook(&points(&[0.0; 8]), now, 8 * 3600),
+            RainOutlook::Dry
+        );
+        assert_eq!(
+            rain_outlook(&points(&[0.5; 8]), now, 8 * 3600),
+            RainOutlook::Raining
+        );
+        assert_eq!(rain_outlook(&[], now, 0), RainOutlook::Dry);
+        assert_eq!(
+            sparkline(&points(&[0.05, 0.2, 0.5, 1.0, 2.0, 5.0])),
+            "_.:-=#"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::config::RetryPolicy;
+use crate::geocoding::ResolvedLocation;
+use crate::model::validate_coordinates;
+
+use super::{ProviderError, execute_with_retry};
+
+const PROVIDER_NAME: &str = "ipapi";
+const IP_LOCATION_ENDPOINT: &str = "https://ipapi.co/json/";
+
+#[derive(Debug, Deserialize)]
+struct IpLocationResponse {
+    #[serde(default)]
+    error: bool,
+    reason: Option<String>,
+    city: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    timezone: Option<String>,
+}
+
+/// Approximate location of the caller's public IP address.
+pub fn fetch_ip_location(
+    client: &Client,
+    retry_policy: RetryPolicy,
+) -> Result<ResolvedLocation, ProviderError> {
+    execute_with_retry(
+        PROVIDER_NAME,
+        retry_policy,
+        || fetch_ip_location_once(client),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_ip_location_once(client: &Client) -> Result<ResolvedLocation, ProviderError> {
+    let response = client
+        .get(IP_LOCATION_ENDPOINT)
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    if !status.is_success() {
+        let message = parse_error_reason(&body).unwrap_or_else(|| {
+            status
+                .canonical_reason()
+                .unwrap_or("request failed")
+                .to_string()
+        });
+        return Err(ProviderError::Http {
+            status: status.as_u16(),
+            message,
+        });
+    }
+
+    parse_ip_location_response(&body)
+}
+
+fn parse_error_reason(body: &str) -> Option<String> {
+    serde_json::from_str::<IpLocationResponse>(body)
+        .ok()
+        .and_then(|payload| payload.reason)
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty())
+}
+
+fn parse_ip_location_response(body: &str) -> Result<ResolvedLocation, ProviderError> {
+    let payload: IpLocationResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(format!("ip location payload: {error}")))?;
+
+    // ipapi.co reports rate limits and reserved addresses as `200` with `"error": true`.
+    if payload.error {
+        return Err(ProviderError::NotFound(
+            payload
+                .reason
+                .unwrap_or_else(|| "ip location unavailable".to_string()),
+        ));
+    }
+
+    let (Some(latitude), Some(longitude)) = (payload.latitude, payload.longitude) else {
+        return Err(ProviderError::InvalidResponse(
+            "ip location payload: missing coordinates".to_string(),
+        ));
+    };
+    validate_coordinates(latitude, longitude)
+        .map_err(|error| ProviderError::InvalidResponse(format!("ip location payload: {error}")))?;
+
+    Ok(ResolvedLocation {
+        name: payload
+            .city
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default(),
+        latitude,
+        longitude,
+        timezone: payload
+            .timezone
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "UTC".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipapi_parses_city_coordinates_and_timezone() {
+        let body = r#"{
+            "ip": "203.0.113.7",
+            "city": "Taipei",
+            "country_name": "Taiwan",
+            "latitude": 25.0478,
+            "longitude": 121.5319,
+            "timezone": "Asia/Taipei"
+        }"#;
+
+        let location = parse_ip_location_response(body).expect("location");
+        assert_eq!(location.name, "Taipei");
+        assert_eq!(location.latitude, 25.0478);
+        assert_eq!(location.timezone, "Asia/Taipei");
+    }
+
+    #[test]
+    fn ipapi_maps_error_payloads() {
+        let error = parse_ip_location_response(r#"{"error":true,"reason":"RateLimited"}"#)
+            .expect_err("must fail");
+        assert_eq!(error, ProviderError::NotFound("RateLimited".to_string()));
+
+        let error = parse_ip_location_response(r#"{"city":"Nowhere"}"#).expect_err("must fail");
+        assert!(matches!(error, ProviderError::InvalidResponse(_)));
+    }
+}
@@ -9,7 +9,10 @@ use serde_json::Value;
 
 use crate::config::{MET_NO_USER_AGENT, RetryPolicy};
 
-use super::{ProviderError, ProviderForecast, ProviderForecastDay, execute_with_retry};
+use super::{
+    ProviderError, ProviderForecast, ProviderForecastDay, dominant_wind_direction,
+    execute_with_retry,
+};
 
 const MET_NO_COMPACT_ENDPOINT: &str = "https://api.met.no/weatherapi/locationforecast/2.0/compact";
 
@@ -123,7 +126,10 @@ fn parse_provider_forecast(
         let day_key = datetime.format("%Y-%m-%d").to_string();
         let entry = daily.entry(day_key).or_default();
 
-        entry.observe_temperature(point.data.instant.details.air_temperature);
+        let details = &point.data.instant.details;
+        entry.observe_temperature(details.air_temperature);
+        entry.observe_wind(details.wind_speed, details.wind_from_direction);
+        entry.observe_humidity(details.relative_humidity);
 
         if let Some(code) = point.data.primary_weather_code() {
             entry.observe_weather_code(code);
@@ -151,6 +157,14 @@ fn parse_provider_forecast(
                 )
             })?,
             precip_prob_max_pct: stats.precip_prob_max_pct,
+            precip_sum_mm: None,
+            sunrise: None,
+            sunset: None,
+            wind_speed_max_kmh: stats.wind_speed_max_ms.map(|ms| ms * 3.6),
+            wind_direction_deg: dominant_wind_direction(stats.wind_samples.iter().copied()),
+            humidity_mean_pct: stats.humidity_mean_pct(),
+            // The compact endpoint has no UV index.
+            uv_index_max: None,
         });
     }
 
@@ -306,6 +320,11 @@ struct DailyAccumulator {
     temp_max_c: Option<f64>,
     precip_prob_max_pct: u8,
     weather_counts: HashMap<i32, usize>,
+    wind_speed_max_ms: Option<f64>,
+    /// `(speed m/s, from-direction degrees)` per timeseries point.
+    wind_samples: Vec<(f64, f64)>,
+    humidity_sum_pct: f64,
+    humidity_samples: usize,
 }
 
 impl DailyAccumulator {
@@ -320,6 +339,31 @@ impl DailyAccumulator {
         );
     }
 
+    fn observe_wind(&mut self, speed_ms: Option<f64>, from_direction_deg: Option<f64>) {
+        let Some(speed_ms) = speed_ms.filter(|speed| speed.is_finite()) else {
+            return;
+        };
+        self.wind_speed_max_ms = Some(
+            self.wind_speed_max_ms
+                .map_or(speed_ms, |current| current.max(speed_ms)),
+        );
+        if let Some(degrees) = from_direction_deg {
+            self.wind_samples.push((speed_ms, degrees));
+        }
+    }
+
+    fn observe_humidity(&mut self, humidity_pct: Option<f64>) {
+        if let Some(humidity_pct) = humidity_pct.filter(|value| value.is_finite()) {
+            self.humidity_sum_pct += humidity_pct.clamp(0.0, 100.0);
+            self.humidity_samples += 1;
+        }
+    }
+
+    fn humidity_mean_pct(&self) -> Option<u8> {
+        (self.humidity_samples > 0)
+            .then(|| (self.humidity_sum_pct / self.humidity_samples as f64).round() as u8)
+    }
+
     fn observe_weather_code(&mut self, code: i32) {
         *self.weather_counts.entry(code).or_insert(0) += 1;
     }
@@ -421,6 +465,12 @@ struct MetNoInstant {
 #[derive(Debug, Deserialize)]
 struct MetNoInstantDetails {
     air_temperature: f64,
+    #[serde(default)]
+    wind_speed: Option<f64>,
+    #[serde(default)]
+    wind_from_direction: Option<f64>,
+    #[serde(default)]
+    relative_humidity: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -477,6 +527,10 @@ mod tests {
         assert_eq!(day1.temp_min_c, 10.0);
         assert_eq!(day1.temp_max_c, 15.0);
         assert_eq!(day1.precip_prob_max_pct, 60);
+        assert_eq!(day1.wind_speed_max_kmh, Some(18.0));
+        assert_eq!(day1.wind_direction_deg, Some(94));
+        assert_eq!(day1.humidity_mean_pct, Some(76));
+        assert_eq!(day1.uv_index_max, None);
 
         let day2 = &forecast.days[1];
         assert_eq!(day2.date, "2026-02-12");
@@ -484,6 +538,8 @@ mod tests {
         assert_eq!(day2.temp_min_c, 8.0);
         assert_eq!(day2.temp_max_c, 14.0);
         assert_eq!(day2.precip_prob_max_pct, 45);
+        assert_eq!(day2.wind_speed_max_kmh, None);
+        assert_eq!(day2.humidity_mean_pct, None);
     }
 
     #[test]
@@ -580,7 +636,7 @@ mod tests {
                     {
                         "time": "2026-02-11T00:00:00Z",
                         "data": {
-                            "instant": { "details": { "air_temperature": 10.0 } },
+                            "instant": { "details": { "air_temperature": 10.0, "wind_speed": 2.0, "wind_from_direction": 80.0, "relative_humidity": 70.0 } },
                             "next_1_hours": {
                                 "summary": { "symbol_code": "rainshowers_day" },
                                 "details": { "probability_of_precipitation": 40 }
@@ -590,7 +646,7 @@ mod tests {
                     {
                         "time": "2026-02-11T06:00:00Z",
                         "data": {
-                            "instant": { "details": { "air_temperature": 15.0 } },
+                            "instant": { "details": { "air_temperature": 15.0, "wind_speed": 5.0, "wind_from_direction": 100.0, "relative_humidity": 81.0 } },
                             "next_6_hours": {
                                 "summary": { "symbol_code": "rainshowers_day" },
                                 "details": { "probability_of_precipitation": 60 }
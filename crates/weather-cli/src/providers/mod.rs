@@ -6,11 +6,53 @@ use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
 use thiserror::Error;
 
-use crate::config::{PROVIDER_TIMEOUT_SECS, RetryPolicy};
+use crate::config::{ProviderTimeouts, RetryPolicy, RuntimeConfig};
 use crate::geocoding::ResolvedLocation;
 
+pub mod ipapi;
 pub mod met_no;
 pub mod open_meteo;
+pub mod wttr;
+
+/// Daily forecast providers that can take part in the fallback chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastProvider {
+    OpenMeteo,
+    MetNo,
+    Wttr,
+}
+
+impl ForecastProvider {
+    pub const DEFAULT_ORDER: [Self; 3] = [Self::OpenMeteo, Self::MetNo, Self::Wttr];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenMeteo => "open_meteo",
+            Self::MetNo => "met_no",
+            Self::Wttr => "wttr",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "open_meteo" | "openmeteo" => Some(Self::OpenMeteo),
+            "met_no" | "metno" => Some(Self::MetNo),
+            "wttr" | "wttr.in" | "wttr_in" => Some(Self::Wttr),
+            _ => None,
+        }
+    }
+
+    /// Parse a comma-separated provider list; unknown names and repeats are dropped.
+    pub fn parse_order(raw: &str) -> Vec<Self> {
+        let mut order = Vec::new();
+        for provider in raw.split(',').filter_map(Self::parse) {
+            if !order.contains(&provider) {
+                order.push(provider);
+            }
+        }
+        order
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProviderForecastDay {
@@ -19,6 +61,14 @@ pub struct ProviderForecastDay {
     pub temp_min_c: f64,
     pub temp_max_c: f64,
     pub precip_prob_max_pct: u8,
+    pub precip_sum_mm: Option<f64>,
+    pub sunrise: Option<String>,
+    pub sunset: Option<String>,
+    pub wind_speed_max_kmh: Option<f64>,
+    /// Dominant direction the wind blows from.
+    pub wind_direction_deg: Option<u16>,
+    pub humidity_mean_pct: Option<u8>,
+    pub uv_index_max: Option<f64>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -44,6 +94,54 @@ pub struct ProviderHourlyForecast {
     pub hours: Vec<ProviderForecastHour>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderCurrentWeather {
+    pub timezone: String,
+    pub fetched_at: DateTime<Utc>,
+    pub observed_at: String,
+    pub weather_code: i32,
+    pub temp_c: f64,
+    pub apparent_temp_c: f64,
+    pub humidity_pct: u8,
+    pub wind_speed_kmh: f64,
+    pub wind_direction_deg: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderNowcastPoint {
+    pub datetime: String,
+    pub precip_mm: f64,
+}
+
+/// Precipitation in 15-minute steps; `datetime` is the local start of each step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderNowcast {
+    pub timezone: String,
+    pub utc_offset_seconds: i32,
+    pub fetched_at: DateTime<Utc>,
+    pub points: Vec<ProviderNowcastPoint>,
+}
+
+/// Observed daily values for one past date; the archive leaves mean, sum and wind fields empty when unknown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderHistoryDay {
+    pub date: String,
+    pub weather_code: i32,
+    pub temp_min_c: f64,
+    pub temp_max_c: f64,
+    pub temp_mean_c: Option<f64>,
+    pub precip_sum_mm: Option<f64>,
+    pub precip_hours: Option<f64>,
+    pub wind_speed_max_kmh: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderHistory {
+    pub timezone: String,
+    pub fetched_at: DateTime<Utc>,
+    pub day: ProviderHistoryDay,
+}
+
 pub trait ProviderApi {
     fn geocode_city(&self, city: &str) -> Result<ResolvedLocation, ProviderError>;
     fn geocode_cities(&self, cities: &[String]) -> Vec<Result<ResolvedLocation, ProviderError>> {
@@ -73,6 +171,24 @@ pub trait ProviderApi {
         lon: f64,
         forecast_hours: usize,
     ) -> Result<ProviderHourlyForecast, ProviderError>;
+    fn fetch_open_meteo_current_weather(
+        &self,
+        lat: f64,
+        lon: f64,
+    ) -> Result<ProviderCurrentWeather, ProviderError>;
+    fn fetch_open_meteo_nowcast(
+        &self,
+        lat: f64,
+        lon: f64,
+        steps: usize,
+    ) -> Result<ProviderNowcast, ProviderError>;
+    /// Observed weather for `date` (`YYYY-MM-DD`, location-local) from the Open-Meteo archive.
+    fn fetch_open_meteo_history(
+        &self,
+        lat: f64,
+        lon: f64,
+        date: &str,
+    ) -> Result<ProviderHistory, ProviderError>;
     fn fetch_met_no_forecast(
         &self,
         lat: f64,
@@ -91,37 +207,110 @@ pub trait ProviderApi {
             })
             .collect()
     }
+    fn fetch_wttr_forecast(
+        &self,
+        lat: f64,
+        lon: f64,
+        forecast_days: usize,
+    ) -> Result<ProviderForecast, ProviderError>;
+    fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError>;
+
+    fn fetch_forecast_from(
+        &self,
+        provider: ForecastProvider,
+        lat: f64,
+        lon: f64,
+        forecast_days: usize,
+    ) -> Result<ProviderForecast, ProviderError> {
+        match provider {
+            ForecastProvider::OpenMeteo => self.fetch_open_meteo_forecast(lat, lon, forecast_days),
+            ForecastProvider::MetNo => self.fetch_met_no_forecast(lat, lon, forecast_days),
+            ForecastProvider::Wttr => self.fetch_wttr_forecast(lat, lon, forecast_days),
+        }
+    }
+
+    /// One result per location. Open-Meteo answers in a single request, so its failure applies to every location.
+    fn fetch_forecasts_batch_from(
+        &self,
+        provider: ForecastProvider,
+        locations: &[ResolvedLocation],
+        forecast_days: usize,
+    ) -> Vec<Result<ProviderForecast, ProviderError>> {
+        match provider {
+            ForecastProvider::OpenMeteo => {
+                match self.fetch_open_meteo_forecasts_batch(locations, forecast_days) {
+                    Ok(forecasts) if forecasts.len() == locations.len() => {
+                        forecasts.into_iter().map(Ok).collect()
+                    }
+                    Ok(forecasts) => {
+                        let error = ProviderError::InvalidResponse(format!(
+                            "batch result length mismatch: expected {}, got {}",
+                            locations.len(),
+                            forecasts.len()
+                        ));
+                        vec![Err(error); locations.len()]
+                    }
+                    Err(error) => vec![Err(error); locations.len()],
+                }
+            }
+            ForecastProvider::MetNo => self.fetch_met_no_forecasts_batch(locations, forecast_days),
+            ForecastProvider::Wttr => locations
+                .iter()
+                .map(|location| {
+                    self.fetch_wttr_forecast(location.latitude, location.longitude, forecast_days)
+                })
+                .collect(),
+        }
+    }
 }
 
+/// HTTP clients per provider, so each provider gets its own request timeout.
 #[derive(Debug, Clone)]
 pub struct HttpProviders {
     client: Client,
+    open_meteo_client: Client,
+    met_no_client: Client,
+    wttr_client: Client,
     retry_policy: RetryPolicy,
 }
 
 impl HttpProviders {
     pub fn new() -> Result<Self, ProviderError> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(PROVIDER_TIMEOUT_SECS))
-            .build()
-            .map_err(|error| ProviderError::Transport(error.to_string()))?;
+        Self::with_settings(ProviderTimeouts::default(), RetryPolicy::default())
+    }
+
+    pub fn with_retry_policy(retry_policy: RetryPolicy) -> Result<Self, ProviderError> {
+        Self::with_settings(ProviderTimeouts::default(), retry_policy)
+    }
+
+    pub fn from_config(config: &RuntimeConfig) -> Result<Self, ProviderError> {
+        Self::with_settings(config.provider_timeouts, config.retry_policy)
+    }
 
+    pub fn with_settings(
+        timeouts: ProviderTimeouts,
+        retry_policy: RetryPolicy,
+    ) -> Result<Self, ProviderError> {
         Ok(Self {
-            client,
-            retry_policy: RetryPolicy::default(),
+            client: build_client(timeouts.base_secs)?,
+            open_meteo_client: build_client(timeouts.for_provider(ForecastProvider::OpenMeteo))?,
+            met_no_client: build_client(timeouts.for_provider(ForecastProvider::MetNo))?,
+            wttr_client: build_client(timeouts.for_provider(ForecastProvider::Wttr))?,
+            retry_policy,
         })
     }
+}
 
-    pub fn with_retry_policy(retry_policy: RetryPolicy) -> Result<Self, ProviderError> {
-        let mut providers = Self::new()?;
-        providers.retry_policy = retry_policy;
-        Ok(providers)
-    }
+fn build_client(timeout_secs: u64) -> Result<Client, ProviderError> {
+    Client::builder()
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .map_err(|error| ProviderError::Transport(error.to_string()))
 }
 
 impl ProviderApi for HttpProviders {
     fn geocode_city(&self, city: &str) -> Result<ResolvedLocation, ProviderError> {
-        open_meteo::fetch_geocode(&self.client, city, self.retry_policy)
+        open_meteo::fetch_geocode(&self.open_meteo_client, city, self.retry_policy)
     }
 
     fn geocode_cities(&self, cities: &[String]) -> Vec<Result<ResolvedLocation, ProviderError>> {
@@ -132,8 +321,11 @@ impl ProviderApi for HttpProviders {
                 let tx = tx.clone();
                 let providers = self.clone();
                 scope.spawn(move || {
-                    let result =
-                        open_meteo::fetch_geocode(&providers.client, &city, providers.retry_policy);
+                    let result = open_meteo::fetch_geocode(
+                        &providers.open_meteo_client,
+                        &city,
+                        providers.retry_policy,
+                    );
                     let _ = tx.send((index, result));
                 });
             }
@@ -165,7 +357,13 @@ impl ProviderApi for HttpProviders {
         lon: f64,
         forecast_days: usize,
     ) -> Result<ProviderForecast, ProviderError> {
-        open_meteo::fetch_forecast(&self.client, lat, lon, forecast_days, self.retry_policy)
+        open_meteo::fetch_forecast(
+            &self.open_meteo_client,
+            lat,
+            lon,
+            forecast_days,
+            self.retry_policy,
+        )
     }
 
     fn fetch_open_meteo_forecasts_batch(
@@ -173,7 +371,12 @@ impl ProviderApi for HttpProviders {
         locations: &[ResolvedLocation],
         forecast_days: usize,
     ) -> Result<Vec<ProviderForecast>, ProviderError> {
-        open_meteo::fetch_forecasts_batch(&self.client, locations, forecast_days, self.retry_policy)
+        open_meteo::fetch_forecasts_batch(
+            &self.open_meteo_client,
+            locations,
+            forecast_days,
+            self.retry_policy,
+        )
     }
 
     fn fetch_open_meteo_hourly_forecast(
@@ -182,7 +385,39 @@ impl ProviderApi for HttpProviders {
         lon: f64,
         forecast_hours: usize,
     ) -> Result<ProviderHourlyForecast, ProviderError> {
-        open_meteo::fetch_hourly_forecast(&self.client, lat, lon, forecast_hours, self.retry_policy)
+        open_meteo::fetch_hourly_forecast(
+            &self.open_meteo_client,
+            lat,
+            lon,
+            forecast_hours,
+            self.retry_policy,
+        )
+    }
+
+    fn fetch_open_meteo_current_weather(
+        &self,
+        lat: f64,
+        lon: f64,
+    ) -> Result<ProviderCurrentWeather, ProviderError> {
+        open_meteo::fetch_current_weather(&self.open_meteo_client, lat, lon, self.retry_policy)
+    }
+
+    fn fetch_open_meteo_nowcast(
+        &self,
+        lat: f64,
+        lon: f64,
+        steps: usize,
+    ) -> Result<ProviderNowcast, ProviderError> {
+        open_meteo::fetch_nowcast(&self.open_meteo_client, lat, lon, steps, self.retry_policy)
+    }
+
+    fn fetch_open_meteo_history(
+        &self,
+        lat: f64,
+        lon: f64,
+        date: &str,
+    ) -> Result<ProviderHistory, ProviderError> {
+        open_meteo::fetch_history(&self.open_meteo_client, lat, lon, date, self.retry_policy)
     }
 
     fn fetch_met_no_forecast(
@@ -191,7 +426,13 @@ impl ProviderApi for HttpProviders {
         lon: f64,
         forecast_days: usize,
     ) -> Result<ProviderForecast, ProviderError> {
-        met_no::fetch_forecast(&self.client, lat, lon, forecast_days, self.retry_policy)
+        met_no::fetch_forecast(
+            &self.met_no_client,
+            lat,
+            lon,
+            forecast_days,
+            self.retry_policy,
+        )
     }
 
     fn fetch_met_no_forecasts_batch(
@@ -207,7 +448,7 @@ impl ProviderApi for HttpProviders {
                 let providers = self.clone();
                 scope.spawn(move || {
                     let result = met_no::fetch_forecast(
-                        &providers.client,
+                        &providers.met_no_client,
                         location.latitude,
                         location.longitude,
                         forecast_days,
@@ -237,6 +478,124 @@ impl ProviderApi for HttpProviders {
                 .collect()
         })
     }
+
+    fn fetch_wttr_forecast(
+        &self,
+        lat: f64,
+        lon: f64,
+        forecast_days: usize,
+    ) -> Result<ProviderForecast, ProviderError> {
+        wttr::fetch_forecast(
+            &self.wttr_client,
+            lat,
+            lon,
+            forecast_days,
+            self.retry_policy,
+        )
+    }
+
+    fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+        ipapi::fetch_ip_location(&self.client, self.retry_policy)
+    }
+}
+
+/// Stand-in for `HttpProviders` in offline mode: every request fails with `ProviderError::Offline` before touching
+/// the network, so the services fall back to whatever is cached.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineProviders;
+
+impl ProviderApi for OfflineProviders {
+    fn geocode_city(&self, _city: &str) -> Result<ResolvedLocation, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_open_meteo_forecast(
+        &self,
+        _lat: f64,
+        _lon: f64,
+        _forecast_days: usize,
+    ) -> Result<ProviderForecast, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_open_meteo_hourly_forecast(
+        &self,
+        _lat: f64,
+        _lon: f64,
+        _forecast_hours: usize,
+    ) -> Result<ProviderHourlyForecast, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_open_meteo_current_weather(
+        &self,
+        _lat: f64,
+        _lon: f64,
+    ) -> Result<ProviderCurrentWeather, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_open_meteo_nowcast(
+        &self,
+        _lat: f64,
+        _lon: f64,
+        _steps: usize,
+    ) -> Result<ProviderNowcast, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_open_meteo_history(
+        &self,
+        _lat: f64,
+        _lon: f64,
+        _date: &str,
+    ) -> Result<ProviderHistory, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_met_no_forecast(
+        &self,
+        _lat: f64,
+        _lon: f64,
+        _forecast_days: usize,
+    ) -> Result<ProviderForecast, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn fetch_wttr_forecast(
+        &self,
+        _lat: f64,
+        _lon: f64,
+        _forecast_days: usize,
+    ) -> Result<ProviderForecast, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+
+    fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+        Err(ProviderError::Offline)
+    }
+}
+
+/// Speed-weighted circular mean of `(speed, from-direction in degrees)` samples, so calm hours barely move the
+/// result. `None` without samples or when the winds cancel out.
+pub(crate) fn dominant_wind_direction(
+    samples: impl IntoIterator<Item = (f64, f64)>,
+) -> Option<u16> {
+    let (east, north) = samples
+        .into_iter()
+        .filter(|(speed, degrees)| speed.is_finite() && degrees.is_finite())
+        .fold((0.0_f64, 0.0_f64), |(east, north), (speed, degrees)| {
+            let radians = degrees.to_radians();
+            (
+                east + speed.max(0.0) * radians.sin(),
+                north + speed.max(0.0) * radians.cos(),
+            )
+        });
+    if east.hypot(north) < 1e-9 {
+        return None;
+    }
+
+    Some((east.atan2(north).to_degrees().rem_euclid(360.0).round() as u16) % 360)
 }
 
 pub fn execute_with_retry<T, F, S>(
@@ -280,6 +639,8 @@ pub enum ProviderError {
     InvalidResponse(String),
     #[error("location not found: {0}")]
     NotFound(String),
+    #[error("offline mode, no network request made")]
+    Offline,
 }
 
 impl ProviderError {
@@ -289,6 +650,7 @@ impl ProviderError {
             ProviderError::Http { status, .. } => *status == 429 || (500..=599).contains(status),
             ProviderError::InvalidResponse(_) => false,
             ProviderError::NotFound(_) => false,
+            ProviderError::Offline => false,
         }
     }
 
@@ -307,6 +669,7 @@ impl ProviderError {
             ProviderError::NotFound(message) => {
                 ProviderError::NotFound(format!("{provider}: {message}"))
             }
+            ProviderError::Offline => ProviderError::Offline,
         }
     }
 }
@@ -348,6 +711,30 @@ mod tests {
         assert_eq!(*observed_sleep.borrow(), vec![25]);
     }
 
+    #[test]
+    fn dominant_wind_direction_weights_by_speed_and_wraps_north() {
+        assert_eq!(
+            dominant_wind_direction([(10.0, 350.0), (10.0, 30.0)]),
+            Some(10)
+        );
+        assert_eq!(
+            dominant_wind_direction([(20.0, 90.0), (1.0, 270.0)]),
+            Some(90)
+        );
+        assert_eq!(dominant_wind_direction([(5.0, 0.0), (5.0, 180.0)]), None);
+        assert_eq!(dominant_wind_direction(Vec::new()), None);
+    }
+
+    #[test]
+    fn forecast_provider_order_parses_names_and_drops_unknown_entries() {
+        assert_eq!(
+            ForecastProvider::parse_order(" wttr , Met-No,bogus,wttr.in"),
+            vec![ForecastProvider::Wttr, ForecastProvider::MetNo]
+        );
+        assert_eq!(ForecastProvider::parse_order(""), Vec::new());
+        assert_eq!(ForecastProvider::OpenMeteo.as_str(), "open_meteo");
+    }
+
     #[test]
     fn provider_error_mapping_marks_retryable_http_statuses() {
         assert!(
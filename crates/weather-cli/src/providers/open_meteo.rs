@@ -7,16 +7,20 @@ use crate::config::RetryPolicy;
 use crate::geocoding::ResolvedLocation;
 
 use super::{
-    ProviderError, ProviderForecast, ProviderForecastDay, ProviderForecastHour,
-    ProviderHourlyForecast, execute_with_retry,
+    ProviderCurrentWeather, ProviderError, ProviderForecast, ProviderForecastDay,
+    ProviderForecastHour, ProviderHistory, ProviderHistoryDay, ProviderHourlyForecast,
+    ProviderNowcast, ProviderNowcastPoint, execute_with_retry,
 };
 
 const PROVIDER_NAME: &str = "open_meteo";
 const GEOCODE_ENDPOINT: &str = "https://geocoding-api.open-meteo.com/v1/search";
 const FORECAST_ENDPOINT: &str = "https://api.open-meteo.com/v1/forecast";
-const FORECAST_DAILY_FIELDS: &str =
-    "weather_code,temperature_2m_max,temperature_2m_min,precipitation_probability_max";
+const ARCHIVE_ENDPOINT: &str = "https://archive-api.open-meteo.com/v1/archive";
+const FORECAST_DAILY_FIELDS: &str = "weather_code,temperature_2m_max,temperature_2m_min,precipitation_probability_max,precipitation_sum,sunrise,sunset,wind_speed_10m_max,wind_direction_10m_dominant,relative_humidity_2m_mean,uv_index_max";
 const FORECAST_HOURLY_FIELDS: &str = "weather_code,temperature_2m,precipitation_probability";
+const FORECAST_CURRENT_FIELDS: &str = "weather_code,temperature_2m,apparent_temperature,relative_humidity_2m,wind_speed_10m,wind_direction_10m";
+const FORECAST_MINUTELY_15_FIELDS: &str = "precipitation";
+const ARCHIVE_DAILY_FIELDS: &str = "weather_code,temperature_2m_max,temperature_2m_min,temperature_2m_mean,precipitation_sum,precipitation_hours,wind_speed_10m_max";
 
 #[derive(Debug, Serialize)]
 struct GeocodeQuery<'a> {
@@ -67,6 +71,33 @@ struct ForecastHourlyQuery<'a> {
     hourly: &'a str,
 }
 
+#[derive(Debug, Serialize)]
+struct ForecastCurrentQuery<'a> {
+    latitude: f64,
+    longitude: f64,
+    timezone: &'a str,
+    current: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ForecastMinutelyQuery<'a> {
+    latitude: f64,
+    longitude: f64,
+    timezone: &'a str,
+    forecast_minutely_15: usize,
+    minutely_15: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct ArchiveQuery<'a> {
+    latitude: f64,
+    longitude: f64,
+    timezone: &'a str,
+    start_date: &'a str,
+    end_date: &'a str,
+    daily: &'a str,
+}
+
 #[derive(Debug, Deserialize)]
 struct ForecastResponse {
     timezone: Option<String>,
@@ -80,6 +111,23 @@ struct ForecastHourlyResponse {
     hourly: Option<ForecastHourly>,
 }
 
+#[derive(Debug, Deserialize)]
+struct ForecastCurrentResponse {
+    timezone: Option<String>,
+    current: Option<ForecastCurrent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastCurrent {
+    time: String,
+    weather_code: i32,
+    temperature_2m: f64,
+    apparent_temperature: f64,
+    relative_humidity_2m: Option<f64>,
+    wind_speed_10m: f64,
+    wind_direction_10m: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ForecastDaily {
     #[serde(default)]
@@ -92,6 +140,63 @@ struct ForecastDaily {
     temperature_2m_min: Vec<f64>,
     #[serde(default)]
     precipitation_probability_max: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_sum: Vec<Option<f64>>,
+    #[serde(default)]
+    sunrise: Vec<Option<String>>,
+    #[serde(default)]
+    sunset: Vec<Option<String>>,
+    #[serde(default)]
+    wind_speed_10m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_direction_10m_dominant: Vec<Option<f64>>,
+    #[serde(default)]
+    relative_humidity_2m_mean: Vec<Option<f64>>,
+    #[serde(default)]
+    uv_index_max: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastMinutelyResponse {
+    timezone: Option<String>,
+    utc_offset_seconds: Option<i32>,
+    minutely_15: Option<ForecastMinutely>,
+}
+
+/// Outside regions with 15-minute models the API interpolates hourly data; gaps come back as `null`.
+#[derive(Debug, Deserialize)]
+struct ForecastMinutely {
+    #[serde(default)]
+    time: Vec<String>,
+    #[serde(default)]
+    precipitation: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveResponse {
+    timezone: Option<String>,
+    daily: Option<ArchiveDaily>,
+}
+
+/// Archive series are nullable: days the reanalysis has not reached yet come back as `null`.
+#[derive(Debug, Deserialize)]
+struct ArchiveDaily {
+    #[serde(default)]
+    time: Vec<String>,
+    #[serde(default)]
+    weather_code: Vec<Option<i32>>,
+    #[serde(default)]
+    temperature_2m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    temperature_2m_min: Vec<Option<f64>>,
+    #[serde(default)]
+    temperature_2m_mean: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_sum: Vec<Option<f64>>,
+    #[serde(default)]
+    precipitation_hours: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_speed_10m_max: Vec<Option<f64>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -167,6 +272,50 @@ pub fn fetch_hourly_forecast(
     )
 }
 
+pub fn fetch_current_weather(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    retry_policy: RetryPolicy,
+) -> Result<ProviderCurrentWeather, ProviderError> {
+    execute_with_retry(
+        PROVIDER_NAME,
+        retry_policy,
+        || fetch_current_weather_once(client, lat, lon),
+        std::thread::sleep,
+    )
+}
+
+pub fn fetch_nowcast(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    steps: usize,
+    retry_policy: RetryPolicy,
+) -> Result<ProviderNowcast, ProviderError> {
+    execute_with_retry(
+        PROVIDER_NAME,
+        retry_policy,
+        || fetch_nowcast_once(client, lat, lon, steps),
+        std::thread::sleep,
+    )
+}
+
+pub fn fetch_history(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    date: &str,
+    retry_policy: RetryPolicy,
+) -> Result<ProviderHistory, ProviderError> {
+    execute_with_retry(
+        PROVIDER_NAME,
+        retry_policy,
+        || fetch_history_once(client, lat, lon, date),
+        std::thread::sleep,
+    )
+}
+
 fn fetch_geocode_once(client: &Client, city: &str) -> Result<ResolvedLocation, ProviderError> {
     let query = GeocodeQuery {
         name: city,
@@ -246,6 +395,59 @@ fn fetch_hourly_forecast_once(
     parse_hourly_response(&body)
 }
 
+fn fetch_current_weather_once(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+) -> Result<ProviderCurrentWeather, ProviderError> {
+    let query = ForecastCurrentQuery {
+        latitude: lat,
+        longitude: lon,
+        timezone: "auto",
+        current: FORECAST_CURRENT_FIELDS,
+    };
+
+    let body = execute_request(client.get(FORECAST_ENDPOINT).query(&query))?;
+    parse_current_response(&body)
+}
+
+fn fetch_nowcast_once(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    steps: usize,
+) -> Result<ProviderNowcast, ProviderError> {
+    let query = ForecastMinutelyQuery {
+        latitude: lat,
+        longitude: lon,
+        timezone: "auto",
+        forecast_minutely_15: steps,
+        minutely_15: FORECAST_MINUTELY_15_FIELDS,
+    };
+
+    let body = execute_request(client.get(FORECAST_ENDPOINT).query(&query))?;
+    parse_nowcast_response(&body)
+}
+
+fn fetch_history_once(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    date: &str,
+) -> Result<ProviderHistory, ProviderError> {
+    let query = ArchiveQuery {
+        latitude: lat,
+        longitude: lon,
+        timezone: "auto",
+        start_date: date,
+        end_date: date,
+        daily: ARCHIVE_DAILY_FIELDS,
+    };
+
+    let body = execute_request(client.get(ARCHIVE_ENDPOINT).query(&query))?;
+    parse_history_response(&body, date)
+}
+
 fn execute_request(request: RequestBuilder) -> Result<String, ProviderError> {
     let response = request
         .send()
@@ -383,6 +585,143 @@ fn parse_hourly_response(body: &str) -> Result<ProviderHourlyForecast, ProviderE
     })
 }
 
+fn parse_current_response(body: &str) -> Result<ProviderCurrentWeather, ProviderError> {
+    let payload: ForecastCurrentResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(format!("current payload: {error}")))?;
+
+    let timezone = payload
+        .timezone
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            ProviderError::InvalidResponse("current payload: missing timezone".to_string())
+        })?;
+
+    let current = payload
+        .current
+        .ok_or_else(|| ProviderError::InvalidResponse("current payload: missing current".into()))?;
+
+    let observed_at = current.time.trim().to_string();
+    if observed_at.is_empty() {
+        return Err(ProviderError::InvalidResponse(
+            "current payload: empty current.time".to_string(),
+        ));
+    }
+
+    Ok(ProviderCurrentWeather {
+        timezone,
+        fetched_at: Utc::now(),
+        observed_at,
+        weather_code: current.weather_code,
+        temp_c: current.temperature_2m,
+        apparent_temp_c: current.apparent_temperature,
+        humidity_pct: clamp_percentage(current.relative_humidity_2m.unwrap_or(0.0)),
+        wind_speed_kmh: current.wind_speed_10m.max(0.0),
+        wind_direction_deg: normalize_direction(current.wind_direction_10m.unwrap_or(0.0)),
+    })
+}
+
+fn parse_nowcast_response(body: &str) -> Result<ProviderNowcast, ProviderError> {
+    let payload: ForecastMinutelyResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(format!("minutely payload: {error}")))?;
+
+    let timezone = payload
+        .timezone
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            ProviderError::InvalidResponse("minutely payload: missing timezone".to_string())
+        })?;
+
+    let minutely = payload.minutely_15.ok_or_else(|| {
+        ProviderError::InvalidResponse("minutely payload: missing minutely_15".into())
+    })?;
+
+    if minutely.precipitation.len() != minutely.time.len() {
+        return Err(ProviderError::InvalidResponse(
+            "minutely payload: minutely_15 arrays length mismatch".to_string(),
+        ));
+    }
+
+    let points = minutely
+        .time
+        .into_iter()
+        .zip(minutely.precipitation)
+        .filter_map(|(datetime, precip)| {
+            let datetime = datetime.trim().to_string();
+            (!datetime.is_empty()).then_some(ProviderNowcastPoint {
+                datetime,
+                precip_mm: precip?.max(0.0),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if points.is_empty() {
+        return Err(ProviderError::InvalidResponse(
+            "minutely payload: no precipitation steps".to_string(),
+        ));
+    }
+
+    Ok(ProviderNowcast {
+        timezone,
+        utc_offset_seconds: payload.utc_offset_seconds.unwrap_or(0),
+        fetched_at: Utc::now(),
+        points,
+    })
+}
+
+fn parse_history_response(body: &str, date: &str) -> Result<ProviderHistory, ProviderError> {
+    let payload: ArchiveResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(format!("archive payload: {error}")))?;
+
+    let timezone = payload
+        .timezone
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| {
+            ProviderError::InvalidResponse("archive payload: missing timezone".to_string())
+        })?;
+
+    let daily = payload
+        .daily
+        .ok_or_else(|| ProviderError::InvalidResponse("archive payload: missing daily".into()))?;
+
+    let index = daily
+        .time
+        .iter()
+        .position(|value| value.trim() == date)
+        .ok_or_else(|| {
+            ProviderError::InvalidResponse(format!("archive payload: no entry for {date}"))
+        })?;
+    let value = |series: &[Option<f64>]| series.get(index).copied().flatten();
+
+    // The reanalysis trails real time by several days; recent dates are listed with null values.
+    let (Some(weather_code), Some(temp_max_c), Some(temp_min_c)) = (
+        daily.weather_code.get(index).copied().flatten(),
+        value(&daily.temperature_2m_max),
+        value(&daily.temperature_2m_min),
+    ) else {
+        return Err(ProviderError::InvalidResponse(format!(
+            "archive payload: no observations for {date} yet"
+        )));
+    };
+
+    Ok(ProviderHistory {
+        timezone,
+        fetched_at: Utc::now(),
+        day: ProviderHistoryDay {
+            date: date.to_string(),
+            weather_code,
+            temp_min_c,
+            temp_max_c,
+            temp_mean_c: value(&daily.temperature_2m_mean),
+            precip_sum_mm: value(&daily.precipitation_sum).map(|mm| mm.max(0.0)),
+            precip_hours: value(&daily.precipitation_hours).map(|hours| hours.max(0.0)),
+            wind_speed_max_kmh: value(&daily.wind_speed_10m_max).map(|kmh| kmh.max(0.0)),
+        },
+    })
+}
+
 fn build_forecast_days(daily: ForecastDaily) -> Result<Vec<ProviderForecastDay>, ProviderError> {
     let length = daily.time.len();
 
@@ -390,12 +729,26 @@ fn build_forecast_days(daily: ForecastDaily) -> Result<Vec<ProviderForecastDay>,
         || daily.temperature_2m_max.len() != length
         || daily.temperature_2m_min.len() != length
         || daily.precipitation_probability_max.len() != length
+        || !optional_series_fits(&daily.precipitation_sum, length)
+        || !optional_series_fits(&daily.sunrise, length)
+        || !optional_series_fits(&daily.sunset, length)
+        || !optional_series_fits(&daily.wind_speed_10m_max, length)
+        || !optional_series_fits(&daily.wind_direction_10m_dominant, length)
+        || !optional_series_fits(&daily.relative_humidity_2m_mean, length)
+        || !optional_series_fits(&daily.uv_index_max, length)
     {
         return Err(ProviderError::InvalidResponse(
             "forecast payload: daily arrays length mismatch".to_string(),
         ));
     }
 
+    let number = |series: &[Option<f64>], index: usize| {
+        series
+            .get(index)
+            .copied()
+            .flatten()
+            .filter(|value| value.is_finite())
+    };
     let mut days = Vec::with_capacity(length);
     for index in 0..length {
         let date = daily.time[index].trim().to_string();
@@ -412,12 +765,41 @@ fn build_forecast_days(daily: ForecastDaily) -> Result<Vec<ProviderForecastDay>,
             temp_max_c: daily.temperature_2m_max[index],
             temp_min_c: daily.temperature_2m_min[index],
             precip_prob_max_pct: clamp_percentage(precip),
+            precip_sum_mm: daily
+                .precipitation_sum
+                .get(index)
+                .copied()
+                .flatten()
+                .map(|value| value.max(0.0)),
+            sunrise: optional_series_value(&daily.sunrise, index),
+            sunset: optional_series_value(&daily.sunset, index),
+            wind_speed_max_kmh: number(&daily.wind_speed_10m_max, index).map(|kmh| kmh.max(0.0)),
+            wind_direction_deg: number(&daily.wind_direction_10m_dominant, index)
+                .map(normalize_direction),
+            humidity_mean_pct: number(&daily.relative_humidity_2m_mean, index)
+                .map(clamp_percentage),
+            uv_index_max: number(&daily.uv_index_max, index).map(|uv| uv.max(0.0)),
         });
     }
 
     Ok(days)
 }
 
+/// Precipitation sum, sun times, wind, humidity and UV are optional series: an absent array is accepted, a present
+/// one must match `daily.time`.
+fn optional_series_fits<T>(series: &[Option<T>], length: usize) -> bool {
+    series.is_empty() || series.len() == length
+}
+
+fn optional_series_value(series: &[Option<String>], index: usize) -> Option<String> {
+    series
+        .get(index)
+        .and_then(|value| value.as_deref())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
 fn build_forecast_hours(
     hourly: ForecastHourly,
 ) -> Result<Vec<ProviderForecastHour>, ProviderError> {
@@ -460,6 +842,13 @@ fn clamp_percentage(value: f64) -> u8 {
     value.clamp(0.0, 100.0).round() as u8
 }
 
+fn normalize_direction(degrees: f64) -> u16 {
+    if !degrees.is_finite() {
+        return 0;
+    }
+    (degrees.rem_euclid(360.0).round() as u16) % 360
+}
+
 fn extract_error_message(body: &str) -> Option<String> {
     let trimmed = body.trim();
     if trimmed.is_empty() {
@@ -530,7 +919,8 @@ mod tests {
                 "weather_code": [2, 61],
                 "temperature_2m_max": [26.4, 24.1],
                 "temperature_2m_min": [18.2, 17.0],
-                "precipitation_probability_max": [120, -3]
+                "precipitation_probability_max": [120, -3],
+                "precipitation_sum": [4.2, -0.1]
             }
         }"#;
 
@@ -539,6 +929,72 @@ mod tests {
         assert_eq!(forecast.days.len(), 2);
         assert_eq!(forecast.days[0].precip_prob_max_pct, 100);
         assert_eq!(forecast.days[1].precip_prob_max_pct, 0);
+        assert_eq!(forecast.days[0].precip_sum_mm, Some(4.2));
+        assert_eq!(forecast.days[1].precip_sum_mm, Some(0.0));
+    }
+
+    #[test]
+    fn open_meteo_forecast_reads_optional_sun_times() {
+        let body_with = |sun_fields: &str| {
+            format!(
+                r#"{{
+                    "timezone": "Asia/Taipei",
+                    "daily": {{
+                        "time": ["2025-02-10", "2025-02-11"],
+                        "weather_code": [2, 61],
+                        "temperature_2m_max": [26.4, 24.1],
+                        "temperature_2m_min": [18.2, 17.0],
+                        "precipitation_probability_max": [30, 50]{sun_fields}
+                    }}
+                }}"#
+            )
+        };
+
+        let forecast = parse_forecast_response(&body_with(
+            r#", "sunrise": ["2025-02-10T06:33", null], "sunset": ["2025-02-10T17:47", "2025-02-11T17:48"]"#,
+        ))
+        .expect("forecast");
+        assert_eq!(
+            forecast.days[0].sunrise.as_deref(),
+            Some("2025-02-10T06:33")
+        );
+        assert_eq!(forecast.days[0].sunset.as_deref(), Some("2025-02-10T17:47"));
+        assert_eq!(forecast.days[1].sunrise, None);
+
+        let forecast = parse_forecast_response(&body_with("")).expect("forecast");
+        assert!(forecast.days.iter().all(|day| day.sunset.is_none()));
+
+        let error = parse_forecast_response(&body_with(r#", "sunrise": ["2025-02-10T06:33"]"#))
+            .expect_err("must fail");
+        assert!(
+            matches!(error, ProviderError::InvalidResponse(message) if message.contains("length mismatch"))
+        );
+    }
+
+    #[test]
+    fn open_meteo_forecast_reads_wind_humidity_and_uv() {
+        let body = r#"{
+            "timezone": "Asia/Taipei",
+            "daily": {
+                "time": ["2025-02-10", "2025-02-11"],
+                "weather_code": [2, 61],
+                "temperature_2m_max": [26.4, 24.1],
+                "temperature_2m_min": [18.2, 17.0],
+                "precipitation_probability_max": [30, 50],
+                "wind_speed_10m_max": [21.6, null],
+                "wind_direction_10m_dominant": [405.0, 180.0],
+                "relative_humidity_2m_mean": [78.4, 104.0],
+                "uv_index_max": [6.35, 1.2]
+            }
+        }"#;
+
+        let forecast = parse_forecast_response(body).expect("forecast");
+        assert_eq!(forecast.days[0].wind_speed_max_kmh, Some(21.6));
+        assert_eq!(forecast.days[1].wind_speed_max_kmh, None);
+        assert_eq!(forecast.days[0].wind_direction_deg, Some(45));
+        assert_eq!(forecast.days[0].humidity_mean_pct, Some(78));
+        assert_eq!(forecast.days[1].humidity_mean_pct, Some(100));
+        assert_eq!(forecast.days[0].uv_index_max, Some(6.35));
     }
 
     #[test]
@@ -599,6 +1055,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn open_meteo_current_parses_conditions_and_clamps_ranges() {
+        let body = r#"{
+            "timezone": "Asia/Tokyo",
+            "utc_offset_seconds": 32400,
+            "current": {
+                "time": "2026-02-12T09:15",
+                "interval": 900,
+                "weather_code": 3,
+                "temperature_2m": 4.4,
+                "apparent_temperature": 1.2,
+                "relative_humidity_2m": 104,
+                "wind_speed_10m": 12.3,
+                "wind_direction_10m": 359.6
+            }
+        }"#;
+
+        let current = parse_current_response(body).expect("current");
+        assert_eq!(current.timezone, "Asia/Tokyo");
+        assert_eq!(current.observed_at, "2026-02-12T09:15");
+        assert_eq!(current.weather_code, 3);
+        assert_eq!(current.apparent_temp_c, 1.2);
+        assert_eq!(current.humidity_pct, 100);
+        assert_eq!(current.wind_direction_deg, 0);
+    }
+
+    #[test]
+    fn open_meteo_current_rejects_missing_block() {
+        let body = r#"{"timezone": "Asia/Tokyo"}"#;
+
+        let error = parse_current_response(body).expect_err("must fail");
+        assert!(
+            matches!(error, ProviderError::InvalidResponse(message) if message.contains("missing current"))
+        );
+    }
+
+    #[test]
+    fn open_meteo_nowcast_reads_steps_and_skips_gaps() {
+        let body = r#"{
+            "timezone": "Asia/Taipei",
+            "utc_offset_seconds": 28800,
+            "minutely_15": {
+                "time": ["2026-02-11T08:00", "2026-02-11T08:15", "2026-02-11T08:30"],
+                "precipitation": [0.0, null, -0.1]
+            }
+        }"#;
+
+        let nowcast = parse_nowcast_response(body).expect("nowcast");
+        assert_eq!(nowcast.utc_offset_seconds, 28800);
+        assert_eq!(
+            nowcast.points,
+            vec![
+                ProviderNowcastPoint {
+                    datetime: "2026-02-11T08:00".to_string(),
+                    precip_mm: 0.0,
+                },
+                ProviderNowcastPoint {
+                    datetime: "2026-02-11T08:30".to_string(),
+                    precip_mm: 0.0,
+                },
+            ]
+        );
+
+        let error = parse_nowcast_response(r#"{"timezone": "Asia/Taipei"}"#).expect_err("fail");
+        assert!(
+            matches!(error, ProviderError::InvalidResponse(message) if message.contains("missing minutely_15"))
+        );
+    }
+
+    #[test]
+    fn open_meteo_history_reads_observed_day() {
+        let body = r#"{
+            "timezone": "Asia/Taipei",
+            "daily": {
+                "time": ["2025-08-01"],
+                "weather_code": [63],
+                "temperature_2m_max": [33.4],
+                "temperature_2m_min": [26.1],
+                "temperature_2m_mean": [29.2],
+                "precipitation_sum": [12.6],
+                "precipitation_hours": [5.0],
+                "wind_speed_10m_max": [18.7]
+            }
+        }"#;
+
+        let history = parse_history_response(body, "2025-08-01").expect("history");
+        assert_eq!(history.timezone, "Asia/Taipei");
+        assert_eq!(history.day.weather_code, 63);
+        assert_eq!(history.day.temp_max_c, 33.4);
+        assert_eq!(history.day.temp_min_c, 26.1);
+        assert_eq!(history.day.temp_mean_c, Some(29.2));
+        assert_eq!(history.day.precip_sum_mm, Some(12.6));
+        assert_eq!(history.day.precip_hours, Some(5.0));
+        assert_eq!(history.day.wind_speed_max_kmh, Some(18.7));
+    }
+
+    #[test]
+    fn open_meteo_history_rejects_days_without_observations() {
+        let body = r#"{
+            "timezone": "Asia/Taipei",
+            "daily": {
+                "time": ["2026-02-10"],
+                "weather_code": [null],
+                "temperature_2m_max": [null],
+                "temperature_2m_min": [null]
+            }
+        }"#;
+
+        let error = parse_history_response(body, "2026-02-10").expect_err("must fail");
+        assert!(
+            matches!(error, ProviderError::InvalidResponse(message) if message.contains("no observations for 2026-02-10"))
+        );
+    }
+
     #[test]
     fn open_meteo_extract_error_message_prefers_reason() {
         let body = r#"{"error": true, "reason": "rate limit exceeded"}"#;
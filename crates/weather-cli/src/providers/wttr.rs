@@ -0,0 +1,314 @@
+use chrono::{NaiveDate, NaiveTime, Utc};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use crate::config::RetryPolicy;
+
+use super::{
+    ProviderError, ProviderForecast, ProviderForecastDay, dominant_wind_direction,
+    execute_with_retry,
+};
+
+const PROVIDER_NAME: &str = "wttr";
+const WTTR_ENDPOINT: &str = "https://wttr.in";
+
+#[derive(Debug, Deserialize)]
+struct WttrResponse {
+    #[serde(default)]
+    weather: Vec<WttrDay>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WttrDay {
+    date: String,
+    #[serde(rename = "mintempC")]
+    min_temp_c: String,
+    #[serde(rename = "maxtempC")]
+    max_temp_c: String,
+    #[serde(rename = "uvIndex", default)]
+    uv_index: Option<String>,
+    #[serde(default)]
+    astronomy: Vec<WttrAstronomy>,
+    #[serde(default)]
+    hourly: Vec<WttrHour>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WttrAstronomy {
+    sunrise: Option<String>,
+    sunset: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WttrHour {
+    weather_code: String,
+    #[serde(default)]
+    chanceofrain: Option<String>,
+    #[serde(rename = "precipMM", default)]
+    precip_mm: Option<String>,
+    #[serde(rename = "windspeedKmph", default)]
+    wind_speed_kmph: Option<String>,
+    #[serde(rename = "winddirDegree", default)]
+    wind_direction_degree: Option<String>,
+    #[serde(default)]
+    humidity: Option<String>,
+}
+
+pub fn fetch_forecast(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    forecast_days: usize,
+    retry_policy: RetryPolicy,
+) -> Result<ProviderForecast, ProviderError> {
+    execute_with_retry(
+        PROVIDER_NAME,
+        retry_policy,
+        || fetch_forecast_once(client, lat, lon, forecast_days),
+        std::thread::sleep,
+    )
+}
+
+fn fetch_forecast_once(
+    client: &Client,
+    lat: f64,
+    lon: f64,
+    forecast_days: usize,
+) -> Result<ProviderForecast, ProviderError> {
+    let response = client
+        .get(format!("{WTTR_ENDPOINT}/{lat:.4},{lon:.4}"))
+        .query(&[("format", "j1")])
+        .send()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|error| ProviderError::Transport(error.to_string()))?;
+
+    if !status.is_success() {
+        let message = body.trim();
+        return Err(ProviderError::Http {
+            status: status.as_u16(),
+            message: if message.is_empty() || message.len() > 240 {
+                status
+                    .canonical_reason()
+                    .unwrap_or("request failed")
+                    .to_string()
+            } else {
+                message.to_string()
+            },
+        });
+    }
+
+    parse_forecast_response(&body, forecast_days)
+}
+
+fn parse_forecast_response(
+    body: &str,
+    forecast_days: usize,
+) -> Result<ProviderForecast, ProviderError> {
+    let payload: WttrResponse = serde_json::from_str(body)
+        .map_err(|error| ProviderError::InvalidResponse(format!("wttr payload: {error}")))?;
+
+    // wttr.in only publishes today plus two days ahead, so `week` always falls through to the next provider.
+    if payload.weather.len() < forecast_days {
+        return Err(ProviderError::InvalidResponse(format!(
+            "wttr response does not include {forecast_days} forecast days"
+        )));
+    }
+
+    let days = payload
+        .weather
+        .into_iter()
+        .take(forecast_days)
+        .map(build_day)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // wttr.in reports local dates without a zone name; the caller falls back to the location timezone.
+    Ok(ProviderForecast {
+        timezone: String::new(),
+        fetched_at: Utc::now(),
+        days,
+    })
+}
+
+fn build_day(day: WttrDay) -> Result<ProviderForecastDay, ProviderError> {
+    let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").map_err(|_| {
+        ProviderError::InvalidResponse(format!("wttr payload: invalid date '{}'", day.date))
+    })?;
+    let weather_code = day
+        .hourly
+        .iter()
+        .filter_map(|hour| hour.weather_code.trim().parse::<i32>().ok())
+        .map(wwo_code_to_open_meteo_code)
+        .max()
+        .ok_or_else(|| {
+            ProviderError::InvalidResponse(format!(
+                "wttr payload: no weather codes for {}",
+                day.date
+            ))
+        })?;
+    let precip_prob_max_pct = day
+        .hourly
+        .iter()
+        .filter_map(|hour| parse_number(hour.chanceofrain.as_deref()))
+        .fold(0.0_f64, f64::max)
+        .clamp(0.0, 100.0)
+        .round() as u8;
+    let precip_sum_mm = day
+        .hourly
+        .iter()
+        .map(|hour| parse_number(hour.precip_mm.as_deref()))
+        .sum::<Option<f64>>();
+    let wind_samples = day
+        .hourly
+        .iter()
+        .filter_map(|hour| {
+            parse_number(hour.wind_speed_kmph.as_deref())
+                .zip(parse_number(hour.wind_direction_degree.as_deref()))
+        })
+        .collect::<Vec<_>>();
+    let humidity = day
+        .hourly
+        .iter()
+        .filter_map(|hour| parse_number(hour.humidity.as_deref()))
+        .collect::<Vec<_>>();
+    let astronomy = day.astronomy.first();
+
+    Ok(ProviderForecastDay {
+        date: day.date.clone(),
+        weather_code,
+        temp_min_c: parse_temperature(&day.min_temp_c, "mintempC")?,
+        temp_max_c: parse_temperature(&day.max_temp_c, "maxtempC")?,
+        precip_prob_max_pct,
+        precip_sum_mm,
+        sunrise: astronomy.and_then(|value| local_time(date, value.sunrise.as_deref())),
+        sunset: astronomy.and_then(|value| local_time(date, value.sunset.as_deref())),
+        wind_speed_max_kmh: wind_samples
+            .iter()
+            .map(|(speed, _)| speed.max(0.0))
+            .reduce(f64::max),
+        wind_direction_deg: dominant_wind_direction(wind_samples.iter().copied()),
+        humidity_mean_pct: (!humidity.is_empty()).then(|| {
+            (humidity.iter().sum::<f64>() / humidity.len() as f64)
+                .clamp(0.0, 100.0)
+                .round() as u8
+        }),
+        uv_index_max: parse_number(day.uv_index.as_deref()).map(|uv| uv.max(0.0)),
+    })
+}
+
+fn parse_number(raw: Option<&str>) -> Option<f64> {
+    raw?.trim().parse::<f64>().ok()
+}
+
+fn parse_temperature(raw: &str, field: &str) -> Result<f64, ProviderError> {
+    parse_number(Some(raw))
+        .ok_or_else(|| ProviderError::InvalidResponse(format!("wttr payload: invalid {field}")))
+}
+
+/// `06:32 AM` on `date` as `YYYY-MM-DDTHH:MM`; polar days report `No sunrise`, which is dropped.
+fn local_time(date: NaiveDate, raw: Option<&str>) -> Option<String> {
+    let time = NaiveTime::parse_from_str(raw?.trim(), "%I:%M %p").ok()?;
+    Some(date.and_time(time).format("%Y-%m-%dT%H:%M").to_string())
+}
+
+/// Map World Weather Online condition codes (used by wttr.in) to the Open-Meteo WMO codes used everywhere else.
+fn wwo_code_to_open_meteo_code(code: i32) -> i32 {
+    match code {
+        113 => 0,
+        116 => 2,
+        119 | 122 => 3,
+        143 | 248 => 45,
+        260 => 48,
+        263 | 266 => 51,
+        281 | 284 | 185 => 56,
+        176 | 293 | 296 => 61,
+        299 | 302 => 63,
+        305 | 308 => 65,
+        311 => 66,
+        314 => 67,
+        179 | 323 | 326 => 71,
+        329 | 332 => 73,
+        227 | 230 | 335 | 338 => 75,
+        350 | 374 | 377 => 77,
+        353 => 80,
+        356 => 81,
+        359 => 82,
+        182 | 317 | 320 | 362 | 365 | 368 => 85,
+        371 => 86,
+        200 | 386 | 389 | 392 => 95,
+        395 => 99,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_body(days: usize) -> String {
+        let day = |date: &str| {
+            format!(
+                r#"{{
+                    "date": "{date}",
+                    "maxtempC": "20",
+                    "mintempC": "14",
+                    "uvIndex": "5",
+                    "astronomy": [{{ "sunrise": "06:32 AM", "sunset": "05:48 PM" }}],
+                    "hourly": [
+                        {{ "time": "0", "weatherCode": "116", "chanceofrain": "0", "precipMM": "0.0", "windspeedKmph": "6", "winddirDegree": "200", "humidity": "70" }},
+                        {{ "time": "1200", "weatherCode": "353", "chanceofrain": "72", "precipMM": "1.4", "windspeedKmph": "19", "winddirDegree": "220", "humidity": "88" }},
+                        {{ "time": "2100", "weatherCode": "122", "chanceofrain": "30", "precipMM": "0.3" }}
+                    ]
+                }}"#
+            )
+        };
+        let weather = ["2026-02-11", "2026-02-12", "2026-02-13"]
+            .iter()
+            .take(days)
+            .map(|date| day(date))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(r#"{{ "weather": [{weather}] }}"#)
+    }
+
+    #[test]
+    fn wttr_builds_daily_summary_from_hourly_entries() {
+        let forecast = parse_forecast_response(&sample_body(3), 1).expect("forecast");
+
+        assert_eq!(forecast.timezone, "");
+        assert_eq!(forecast.days.len(), 1);
+        let day = &forecast.days[0];
+        assert_eq!(day.weather_code, 80);
+        assert_eq!(day.temp_min_c, 14.0);
+        assert_eq!(day.temp_max_c, 20.0);
+        assert_eq!(day.precip_prob_max_pct, 72);
+        assert_eq!(day.precip_sum_mm.map(|mm| (mm * 10.0).round()), Some(17.0));
+        assert_eq!(day.sunrise.as_deref(), Some("2026-02-11T06:32"));
+        assert_eq!(day.sunset.as_deref(), Some("2026-02-11T17:48"));
+        assert_eq!(day.wind_speed_max_kmh, Some(19.0));
+        assert_eq!(day.wind_direction_deg, Some(215));
+        assert_eq!(day.humidity_mean_pct, Some(79));
+        assert_eq!(day.uv_index_max, Some(5.0));
+    }
+
+    #[test]
+    fn wttr_rejects_requests_beyond_published_days() {
+        let error = parse_forecast_response(&sample_body(3), 7).expect_err("must fail");
+        assert!(
+            matches!(error, ProviderError::InvalidResponse(message) if message.contains("7 forecast days"))
+        );
+    }
+
+    #[test]
+    fn wttr_maps_condition_codes_to_open_meteo_codes() {
+        assert_eq!(wwo_code_to_open_meteo_code(113), 0);
+        assert_eq!(wwo_code_to_open_meteo_code(302), 63);
+        assert_eq!(wwo_code_to_open_meteo_code(338), 75);
+        assert_eq!(wwo_code_to_open_meteo_code(389), 95);
+        assert_eq!(wwo_code_to_open_meteo_code(1), 3);
+    }
+}
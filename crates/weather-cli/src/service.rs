@@ -1,5 +1,6 @@
 use chrono::{DateTime, SecondsFormat, Utc};
 
+use crate::astronomy;
 use crate::cache::{
     CacheRecord, cache_path, evaluate_freshness, parse_fetched_at, read_cache, write_cache,
 };
@@ -11,6 +12,7 @@ use crate::geocoding::{
 };
 use crate::model::{
     CacheMetadata, ForecastDay, ForecastOutput, ForecastRequest, FreshnessStatus, LocationQuery,
+    ProviderAttempt,
 };
 use crate::providers::{ProviderApi, ProviderForecast};
 use crate::weather_code;
@@ -57,6 +59,7 @@ where
             request,
             FreshnessStatus::CacheFresh,
             *age_secs,
+            Vec::new(),
             &output_context,
         ));
     }
@@ -71,44 +74,29 @@ where
 
     let mut trace = Vec::new();
 
-    match providers.fetch_open_meteo_forecast(
-        location.latitude,
-        location.longitude,
-        request.period.forecast_days(),
-    ) {
-        Ok(forecast) => {
-            return build_live_output(
-                &path,
-                &location,
-                request,
-                forecast,
-                "open_meteo",
-                trace,
-                &output_context,
-            );
+    for provider in config.forecast_provider_order() {
+        match providers.fetch_forecast_from(
+            provider,
+            location.latitude,
+            location.longitude,
+            request.period.forecast_days(),
+        ) {
+            Ok(forecast) => {
+                return build_live_output(
+                    &path,
+                    &location,
+                    request,
+                    forecast,
+                    provider.as_str(),
+                    trace,
+                    &output_context,
+                );
+            }
+            Err(error) => trace.push(format!("{}: {error}", provider.as_str())),
         }
-        Err(error) => trace.push(format!("open_meteo: {error}")),
     }
 
-    match providers.fetch_met_no_forecast(
-        location.latitude,
-        location.longitude,
-        request.period.forecast_days(),
-    ) {
-        Ok(forecast) => build_live_output(
-            &path,
-            &location,
-            request,
-            forecast,
-            "met_no",
-            trace,
-            &output_context,
-        ),
-        Err(error) => {
-            trace.push(format!("met_no: {error}"));
-            fallback_or_error(cached_state, &location, request, trace, &output_context)
-        }
-    }
+    fallback_or_error(cached_state, &location, request, trace, &output_context)
 }
 
 fn resolve_location<P: ProviderApi>(
@@ -166,12 +154,14 @@ fn build_live_output(
 
     write_cache(path, &record).map_err(|error| AppError::runtime(error.to_string()))?;
 
+    let attempts = ProviderAttempt::from_trace(&record.source_trace, Some(source));
     Ok(build_output_from_record(
         &record,
         location,
         request,
         FreshnessStatus::Live,
         0,
+        attempts,
         output_context,
     ))
 }
@@ -190,6 +180,7 @@ fn fallback_or_error(
             request,
             FreshnessStatus::CacheStaleFallback,
             age_secs,
+            ProviderAttempt::from_trace(&trace, None),
             output_context,
         ));
     }
@@ -206,6 +197,7 @@ fn build_output_from_record(
     request: &ForecastRequest,
     freshness_status: FreshnessStatus,
     age_secs: u64,
+    attempts: Vec<ProviderAttempt>,
     output_context: &OutputContext,
 ) -> ForecastOutput {
     let fetched_at = parse_fetched_at(record)
@@ -225,6 +217,8 @@ fn build_output_from_record(
             key: output_context.cache_key.clone(),
             ttl_secs: output_context.ttl_secs,
             age_secs,
+            provider: Some(record.source.clone()),
+            attempts,
         },
     }
 }
@@ -246,13 +240,25 @@ fn resolved_location_from_record(record: &CacheRecord) -> ResolvedLocation {
 
 fn normalize_days(days: Vec<crate::providers::ProviderForecastDay>) -> Vec<ForecastDay> {
     days.into_iter()
-        .map(|item| ForecastDay {
-            date: item.date,
-            weather_code: item.weather_code,
-            summary_zh: weather_code::summary_zh(item.weather_code).to_string(),
-            temp_min_c: round1(item.temp_min_c),
-            temp_max_c: round1(item.temp_max_c),
-            precip_prob_max_pct: item.precip_prob_max_pct.min(100),
+        .map(|item| {
+            let moon = astronomy::moon_for_date(&item.date);
+            ForecastDay {
+                date: item.date,
+                weather_code: item.weather_code,
+                summary_zh: weather_code::summary_zh(item.weather_code).to_string(),
+                temp_min_c: round1(item.temp_min_c),
+                temp_max_c: round1(item.temp_max_c),
+                precip_prob_max_pct: item.precip_prob_max_pct.min(100),
+                precip_sum_mm: item.precip_sum_mm.map(round1),
+                sunrise: item.sunrise,
+                sunset: item.sunset,
+                moon_phase: moon.map(|(phase, _)| phase),
+                moon_illumination_pct: moon.map(|(_, pct)| pct),
+                wind_speed_max_kmh: item.wind_speed_max_kmh.map(round1),
+                wind_direction_deg: item.wind_direction_deg,
+                humidity_mean_pct: item.humidity_mean_pct.map(|pct| pct.min(100)),
+                uv_index_max: item.uv_index_max.map(round1),
+            }
         })
         .collect()
 }
@@ -268,7 +274,11 @@ mod tests {
 
     use super::*;
     use crate::model::{ForecastPeriod, OutputMode};
-    use crate::providers::{ProviderError, ProviderForecastDay};
+    use crate::providers::{
+        ForecastProvider, ProviderCurrentWeather, ProviderError, ProviderForecastDay,
+        ProviderHistory, ProviderNowcast,
+    };
+    use crate::units::UnitSystem;
 
     struct FakeProviders {
         geocode_result: Result<ResolvedLocation, ProviderError>,
@@ -302,6 +312,13 @@ mod tests {
                         temp_min_c: 14.4,
                         temp_max_c: 20.2,
                         precip_prob_max_pct: 22,
+                        precip_sum_mm: None,
+                        sunrise: None,
+                        sunset: None,
+                        wind_speed_max_kmh: None,
+                        wind_direction_deg: None,
+                        humidity_mean_pct: None,
+                        uv_index_max: None,
                     }],
                 }),
                 met_no_result: Ok(ProviderForecast {
@@ -313,6 +330,13 @@ mod tests {
                         temp_min_c: 10.2,
                         temp_max_c: 12.7,
                         precip_prob_max_pct: 70,
+                        precip_sum_mm: None,
+                        sunrise: None,
+                        sunset: None,
+                        wind_speed_max_kmh: None,
+                        wind_direction_deg: None,
+                        humidity_mean_pct: None,
+                        uv_index_max: None,
                     }],
                 }),
                 geocode_calls: Cell::new(0),
@@ -347,6 +371,32 @@ mod tests {
             Err(ProviderError::Transport("unused".to_string()))
         }
 
+        fn fetch_open_meteo_current_weather(
+            &self,
+            _lat: f64,
+            _lon: f64,
+        ) -> Result<ProviderCurrentWeather, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_nowcast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _steps: usize,
+        ) -> Result<ProviderNowcast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn fetch_open_meteo_history(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _date: &str,
+        ) -> Result<ProviderHistory, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
         fn fetch_met_no_forecast(
             &self,
             _lat: f64,
@@ -356,12 +406,35 @@ mod tests {
             self.met_no_calls.set(self.met_no_calls.get() + 1);
             self.met_no_result.clone()
         }
+
+        fn fetch_wttr_forecast(
+            &self,
+            _lat: f64,
+            _lon: f64,
+            _forecast_days: usize,
+        ) -> Result<ProviderForecast, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
+
+        fn locate_by_ip(&self) -> Result<ResolvedLocation, ProviderError> {
+            Err(ProviderError::Transport("unused".to_string()))
+        }
     }
 
     fn config_in_tempdir() -> RuntimeConfig {
         RuntimeConfig {
             cache_dir: tempfile::tempdir().expect("tempdir").path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         }
     }
 
@@ -410,6 +483,16 @@ mod tests {
         let config = RuntimeConfig {
             cache_dir: dir.path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         };
         let providers = FakeProviders::ok();
         let request = city_request(ForecastPeriod::Today);
@@ -446,12 +529,87 @@ mod tests {
         assert_eq!(output.source_trace.len(), 1);
     }
 
+    #[test]
+    fn service_follows_configured_provider_order() {
+        let providers = FakeProviders::ok();
+        let config = RuntimeConfig {
+            provider_order: vec![ForecastProvider::Wttr, ForecastProvider::MetNo],
+            ..config_in_tempdir()
+        };
+        let request = city_request(ForecastPeriod::Today);
+
+        let output = resolve_forecast(&config, &providers, fixed_now, &request).expect("must pass");
+
+        assert_eq!(output.source, "met_no");
+        assert_eq!(providers.open_meteo_calls.get(), 0);
+        assert_eq!(output.source_trace.len(), 1);
+        assert!(output.source_trace[0].starts_with("wttr: "));
+    }
+
+    #[test]
+    fn service_reports_provider_attempts_in_freshness() {
+        let providers = FakeProviders {
+            open_meteo_result: Err(ProviderError::Transport("timeout".to_string())),
+            ..FakeProviders::ok()
+        };
+        let request = city_request(ForecastPeriod::Today);
+
+        let output = resolve_forecast(&config_in_tempdir(), &providers, fixed_now, &request)
+            .expect("must pass");
+
+        assert_eq!(output.freshness.provider.as_deref(), Some("met_no"));
+        assert_eq!(
+            output.freshness.attempts,
+            vec![
+                ProviderAttempt {
+                    provider: "open_meteo".to_string(),
+                    ok: false,
+                    error: Some("transport error: timeout".to_string()),
+                },
+                ProviderAttempt {
+                    provider: "met_no".to_string(),
+                    ok: true,
+                    error: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn service_forced_provider_skips_the_rest_of_the_order() {
+        let providers = FakeProviders {
+            open_meteo_result: Err(ProviderError::Transport("timeout".to_string())),
+            ..FakeProviders::ok()
+        };
+        let config = RuntimeConfig {
+            forced_provider: Some(ForecastProvider::OpenMeteo),
+            ..config_in_tempdir()
+        };
+        let request = city_request(ForecastPeriod::Today);
+
+        let error =
+            resolve_forecast(&config, &providers, fixed_now, &request).expect_err("must fail");
+
+        assert_eq!(providers.met_no_calls.get(), 0);
+        assert!(error.message.contains("open_meteo: "));
+    }
+
     #[test]
     fn service_short_circuits_on_fresh_cache() {
         let dir = tempfile::tempdir().expect("tempdir");
         let config = RuntimeConfig {
             cache_dir: dir.path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         };
         let request = city_request(ForecastPeriod::Today);
         let location = ResolvedLocation {
@@ -478,6 +636,15 @@ mod tests {
                     temp_min_c: 14.0,
                     temp_max_c: 20.0,
                     precip_prob_max_pct: 20,
+                    precip_sum_mm: None,
+                    sunrise: None,
+                    sunset: None,
+                    moon_phase: None,
+                    moon_illumination_pct: None,
+                    wind_speed_max_kmh: None,
+                    wind_direction_deg: None,
+                    humidity_mean_pct: None,
+                    uv_index_max: None,
                 }],
                 source: "open_meteo".to_string(),
                 source_trace: Vec::new(),
@@ -500,6 +667,16 @@ mod tests {
         let config = RuntimeConfig {
             cache_dir: dir.path().to_path_buf(),
             cache_ttl_secs: crate::config::WEATHER_CACHE_TTL_SECS,
+            units: UnitSystem::Metric,
+            extended_detail: false,
+            language: Default::default(),
+            offline: false,
+            favorite_locations: Vec::new(),
+            auto_locate_ttl_secs: None,
+            provider_order: Vec::new(),
+            forced_provider: None,
+            provider_timeouts: Default::default(),
+            retry_policy: Default::default(),
         };
         let request = city_request(ForecastPeriod::Today);
         let location = ResolvedLocation {
@@ -526,6 +703,15 @@ mod tests {
                     temp_min_c: 12.0,
                     temp_max_c: 17.0,
                     precip_prob_max_pct: 80,
+                    precip_sum_mm: None,
+                    sunrise: None,
+                    sunset: None,
+                    moon_phase: None,
+                    moon_illumination_pct: None,
+                    wind_speed_max_kmh: None,
+                    wind_direction_deg: None,
+                    humidity_mean_pct: None,
+                    uv_index_max: None,
                 }],
                 source: "open_meteo".to_string(),
                 source_trace: Vec::new(),
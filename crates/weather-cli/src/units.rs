@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Measurement system for rendered output. Forecast data is always fetched, cached, and modelled in metric; the
+/// selected system is applied when rendering.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    /// Parse a `WEATHER_UNITS` value (`metric`/`imperial`, also `si`/`us`).
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "metric" | "si" => Some(Self::Metric),
+            "imperial" | "us" => Some(Self::Imperial),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Metric => "metric",
+            Self::Imperial => "imperial",
+        }
+    }
+
+    pub fn temperature(self, celsius: f64) -> f64 {
+        match self {
+            Self::Metric => celsius,
+            Self::Imperial => round_to(celsius * 9.0 / 5.0 + 32.0, 1),
+        }
+    }
+
+    /// A temperature difference: scaled like a temperature but without the offset.
+    pub fn temperature_delta(self, celsius: f64) -> f64 {
+        match self {
+            Self::Metric => celsius,
+            Self::Imperial => round_to(celsius * 9.0 / 5.0, 1),
+        }
+    }
+
+    pub fn speed(self, kmh: f64) -> f64 {
+        match self {
+            Self::Metric => kmh,
+            Self::Imperial => round_to(kmh / KM_PER_MILE, 1),
+        }
+    }
+
+    pub fn length(self, mm: f64) -> f64 {
+        match self {
+            Self::Metric => mm,
+            Self::Imperial => round_to(mm / MM_PER_INCH, 2),
+        }
+    }
+
+    pub fn temperature_unit(self) -> &'static str {
+        match self {
+            Self::Metric => "°C",
+            Self::Imperial => "°F",
+        }
+    }
+
+    pub fn speed_unit(self) -> &'static str {
+        match self {
+            Self::Metric => "km/h",
+            Self::Imperial => "mph",
+        }
+    }
+
+    pub fn length_unit(self) -> &'static str {
+        match self {
+            Self::Metric => "mm",
+            Self::Imperial => "in",
+        }
+    }
+
+    /// Signed temperature difference with unit, e.g. `+1.2°C` or `-3.4°F`.
+    pub fn temperature_delta_label(self, celsius: f64) -> String {
+        format!(
+            "{:+.1}{}",
+            self.temperature_delta(celsius),
+            self.temperature_unit()
+        )
+    }
+
+    /// Temperature with one decimal, e.g. `20.1` or `68.2`.
+    pub fn temperature_label(self, celsius: f64) -> String {
+        format!("{:.1}", self.temperature(celsius))
+    }
+
+    pub fn speed_label(self, kmh: f64) -> String {
+        format!("{:.1}{}", self.speed(kmh), self.speed_unit())
+    }
+
+    /// Precipitation amount: one decimal in millimetres, two in inches.
+    pub fn length_label(self, mm: f64) -> String {
+        match self {
+            Self::Metric => format!("{:.1}{}", mm, self.length_unit()),
+            Self::Imperial => format!("{:.2}{}", self.length(mm), self.length_unit()),
+        }
+    }
+
+    /// Convert a serialized result in place: metric fields (`*_c`, `*_kmh`, `*_mm`) are replaced by their
+    /// imperial counterparts (`*_f`, `*_mph`, `*_in`). Metric values are left untouched.
+    pub fn convert_json(self, value: &mut Value) {
+        if self == Self::Metric {
+            return;
+        }
+
+        match value {
+            Value::Object(map) => {
+                let converted = std::mem::take(map)
+                    .into_iter()
+                    .map(|(key, mut field)| {
+                        self.convert_json(&mut field);
+                        self.convert_json_field(key, field)
+                    })
+                    .collect::<Map<String, Value>>();
+                *map = converted;
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.convert_json(item)),
+            _ => {}
+        }
+    }
+
+    fn convert_json_field(self, key: String, field: Value) -> (String, Value) {
+        let Some(number) = field.as_f64() else {
+            return (key, field);
+        };
+
+        if let Some(stem) = key.strip_suffix("_delta_c") {
+            return (
+                format!("{stem}_delta_f"),
+                Value::from(self.temperature_delta(number)),
+            );
+        }
+        if let Some(stem) = key.strip_suffix("_c") {
+            return (format!("{stem}_f"), Value::from(self.temperature(number)));
+        }
+        if let Some(stem) = key.strip_suffix("_kmh") {
+            return (format!("{stem}_mph"), Value::from(self.speed(number)));
+        }
+        if let Some(stem) = key.strip_suffix("_mm") {
+            return (format!("{stem}_in"), Value::from(self.length(number)));
+        }
+
+        (key, field)
+    }
+}
+
+const KM_PER_MILE: f64 = 1.609_344;
+const MM_PER_INCH: f64 = 25.4;
+
+fn round_to(value: f64, decimals: i32) -> f64 {
+    let factor = 10_f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn units_parse_names_and_aliases() {
+        assert_eq!(UnitSystem::parse(" Imperial "), Some(UnitSystem::Imperial));
+        assert_eq!(UnitSystem::parse("us"), Some(UnitSystem::Imperial));
+        assert_eq!(UnitSystem::parse("metric"), Some(UnitSystem::Metric));
+        assert_eq!(UnitSystem::parse("kelvin"), None);
+    }
+
+    #[test]
+    fn imperial_conversions_round_for_display() {
+        let units = UnitSystem::Imperial;
+        assert_eq!(units.temperature(20.1), 68.2);
+        assert_eq!(units.temperature(-40.0), -40.0);
+        assert_eq!(units.speed(9.4), 5.8);
+        assert_eq!(units.length(12.7), 0.5);
+
+        assert_eq!(units.temperature_label(0.0), "32.0");
+        assert_eq!(units.speed_label(100.0), "62.1mph");
+        assert_eq!(units.length_label(3.0), "0.12in");
+        assert_eq!(UnitSystem::Metric.length_label(3.04), "3.0mm");
+        assert_eq!(UnitSystem::Metric.speed_label(9.4), "9.4km/h");
+        assert_eq!(units.temperature_delta(-5.0), -9.0);
+        assert_eq!(units.temperature_delta_label(2.5), "+4.5°F");
+        assert_eq!(UnitSystem::Metric.temperature_delta_label(-1.24), "-1.2°C");
+    }
+
+    #[test]
+    fn imperial_json_renames_metric_fields() {
+        let mut value = json!({
+            "forecast": [{ "temp_min_c": 10.0, "precip_sum_mm": 25.4, "precip_prob_max_pct": 20 }],
+            "current": { "wind_speed_kmh": 16.09344, "summary_zh": "晴" },
+            "days": [{ "temp_max_delta_c": 10.0 }],
+        });
+        UnitSystem::Imperial.convert_json(&mut value);
+
+        assert_eq!(
+            value,
+            json!({
+                "forecast": [{ "temp_min_f": 50.0, "precip_sum_in": 1.0, "precip_prob_max_pct": 20 }],
+                "current": { "wind_speed_mph": 10.0, "summary_zh": "晴" },
+                "days": [{ "temp_max_delta_f": 18.0 }],
+            })
+        );
+    }
+
+    #[test]
+    fn metric_json_is_unchanged() {
+        let original = json!({ "temp_c": 20.5, "wind_speed_kmh": 3.0 });
+        let mut value = original.clone();
+        UnitSystem::Metric.convert_json(&mut value);
+        assert_eq!(value, original);
+    }
+}
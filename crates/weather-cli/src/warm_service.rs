@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+
+use crate::config::RuntimeConfig;
+use crate::current_service;
+use crate::error::AppError;
+use crate::geocoding::coordinate_label;
+use crate::hourly_service::{self, DEFAULT_HOURLY_COUNT};
+use crate::model::{
+    ForecastPeriod, ForecastRequest, FreshnessStatus, LocationQuery, OutputMode, WarmEntry,
+    WarmOutput,
+};
+use crate::nowcast_service;
+use crate::providers::ProviderApi;
+use crate::service;
+
+/// Cache entries refreshed per location, in order. `history` is date-specific and never warmed.
+pub const WARM_PERIODS: [ForecastPeriod; 5] = [
+    ForecastPeriod::Today,
+    ForecastPeriod::Week,
+    ForecastPeriod::Hourly,
+    ForecastPeriod::Now,
+    ForecastPeriod::Rain,
+];
+
+/// Refetch every `WARM_PERIODS` entry for each location, even when the cached copy is still fresh, so interactive
+/// queries keep hitting `cache_fresh`. Failures are recorded per entry; an existing entry is left in place.
+pub fn warm_caches<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    locations: &[LocationQuery],
+) -> WarmOutput
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    // A zero TTL turns every cached entry older than a second into a miss, so each service refetches it.
+    let forced = RuntimeConfig {
+        cache_ttl_secs: 0,
+        ..config.clone()
+    };
+
+    let mut entries = Vec::with_capacity(locations.len() * WARM_PERIODS.len());
+    for location in locations {
+        for period in WARM_PERIODS {
+            let result = refresh(&forced, providers, &now_fn, location, period);
+            entries.push(warm_entry(location_label(location), period, result));
+        }
+    }
+
+    WarmOutput { entries }
+}
+
+fn refresh<P, N>(
+    config: &RuntimeConfig,
+    providers: &P,
+    now_fn: N,
+    location: &LocationQuery,
+    period: ForecastPeriod,
+) -> Result<(FreshnessStatus, Vec<String>), AppError>
+where
+    P: ProviderApi,
+    N: Fn() -> DateTime<Utc>,
+{
+    match period {
+        ForecastPeriod::Today | ForecastPeriod::Week => {
+            let request = ForecastRequest {
+                period,
+                location: location.clone(),
+                output_mode: OutputMode::Json,
+            };
+            service::resolve_forecast(config, providers, now_fn, &request)
+                .map(|output| (output.freshness.status, output.source_trace))
+        }
+        ForecastPeriod::Hourly => hourly_service::resolve_hourly_forecast(
+            config,
+            providers,
+            now_fn,
+            location,
+            DEFAULT_HOURLY_COUNT,
+        )
+        .map(|output| (output.freshness.status, output.source_trace)),
+        ForecastPeriod::Now => {
+            current_service::resolve_current_weather(config, providers, now_fn, location)
+                .map(|output| (output.freshness.status, output.source_trace))
+        }
+        ForecastPeriod::Rain => {
+            nowcast_service::resolve_nowcast(config, providers, now_fn, location)
+                .map(|output| (output.freshness.status, output.source_trace))
+        }
+        ForecastPeriod::History => Err(AppError::user("history cache entries are not warmed")),
+    }
+}
+
+/// A stale fallback still counts as a failed refresh: the entry kept its old data.
+fn warm_entry(
+    location: String,
+    period: ForecastPeriod,
+    result: Result<(FreshnessStatus, Vec<String>), AppError>,
+) -> WarmEntry {
+    let (status, error) = match result {
+        Ok((FreshnessStatus::CacheStaleFallback, trace)) => (
+            Some(FreshnessStatus::CacheStaleFallback),
+            Some(trace.join("; ")),
+        ),
+        Ok((status, _)) => (Some(status), None),
+        Err(error) => (None, Some(error.message)),
+    };
+
+    WarmEntry {
+        location,
+        period,
+        status,
+        error,
+    }
+}
+
+fn location_label(location: &LocationQuery) -> String {
+    match location {
+        LocationQuery::City(city) => city.clone(),
+        LocationQuery::Coordinates { lat, lon } => coordinate_label(*lat, *lon),
+    }
+}
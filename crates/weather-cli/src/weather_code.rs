@@ -32,6 +32,24 @@ pub fn summary_en(code: i32) -> &'static str {
     }
 }
 
+/// Key of the summary label in the `locales/` tables.
+pub fn summary_key(code: i32) -> &'static str {
+    match code {
+        0 => "weather.clear_sky",
+        1 => "weather.mainly_clear",
+        2 => "weather.partly_cloudy",
+        3 => "weather.cloudy",
+        45 | 48 => "weather.fog",
+        51 | 53 | 55 | 56 | 57 => "weather.drizzle",
+        61 | 63 | 65 | 66 | 67 => "weather.rain",
+        71 | 73 | 75 | 77 => "weather.snow",
+        80..=82 => "weather.rain_showers",
+        85 | 86 => "weather.snow_showers",
+        95 | 96 | 99 => "weather.thunderstorm",
+        _ => "weather.unknown",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 use chrono::{DateTime, Timelike, Utc};
 use chrono_tz::Tz;
 
+/// Bundled glyph for unmapped weather codes and rows without a weather condition.
+pub const FALLBACK_ICON_KEY: &str = "unknown";
+
 const NIGHT_START_HOUR: u32 = 18;
 const DAY_START_HOUR: u32 = 6;
 
@@ -79,7 +82,7 @@ fn day_variant_icon_key(weather_code: i32) -> &'static str {
         80..=82 => "rain-showers",
         85 | 86 => "snow-showers",
         95 | 96 | 99 => "thunderstorm",
-        _ => "unknown",
+        _ => FALLBACK_ICON_KEY,
     }
 }
 
@@ -176,6 +179,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn representative_codes_map_to_condition_icons() {
+        let cases = [
+            (45, "fog"),
+            (53, "drizzle"),
+            (63, "rain"),
+            (75, "snow"),
+            (81, "rain-showers"),
+            (86, "snow-showers"),
+            (99, "thunderstorm"),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(daily_forecast_icon_key(code), expected, "code {code}");
+        }
+    }
+
+    #[test]
+    fn unmapped_codes_use_fallback_glyph() {
+        assert_eq!(daily_forecast_icon_key(4), FALLBACK_ICON_KEY);
+        assert_eq!(daily_forecast_icon_key(-1), FALLBACK_ICON_KEY);
+        assert_eq!(
+            hourly_forecast_icon_key(42, "2026-02-11T22:00"),
+            "unknown-night"
+        );
+    }
+
+    #[test]
+    fn every_icon_key_has_a_bundled_asset() {
+        let assets = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../workflows/weather/src/assets/icons/weather");
+        for code in -1..=100 {
+            for key in [day_variant_icon_key(code), night_variant_icon_key(code)] {
+                assert!(
+                    assets.join(format!("{key}.png")).is_file(),
+                    "missing icon asset for {key}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn icon_key_reports_night_variant_status() {
         assert!(is_night_icon_key("clear-night"));
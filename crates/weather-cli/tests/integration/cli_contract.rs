@@ -33,6 +33,15 @@ fn cli_contract_output_contains_required_fields_for_today() {
             temp_min_c: 14.5,
             temp_max_c: 19.9,
             precip_prob_max_pct: 13,
+            precip_sum_mm: None,
+            sunrise: None,
+            sunset: None,
+            moon_phase: None,
+            moon_illumination_pct: None,
+            wind_speed_max_kmh: None,
+            wind_direction_deg: None,
+            humidity_mean_pct: None,
+            uv_index_max: None,
         }],
         source: "open_meteo".to_string(),
         source_trace: vec![],
@@ -42,6 +51,8 @@ fn cli_contract_output_contains_required_fields_for_today() {
             key: "today-taipei-city-25.0531-121.5264".to_string(),
             ttl_secs: 1800,
             age_secs: 0,
+            provider: None,
+            attempts: Vec::new(),
         },
     };
 
@@ -79,6 +90,8 @@ fn cli_contract_freshness_status_serializes_in_snake_case() {
             key: "week-taipei-city-25.0531-121.5264".to_string(),
             ttl_secs: 1800,
             age_secs: 7200,
+            provider: None,
+            attempts: Vec::new(),
         },
     };
 
@@ -113,6 +126,15 @@ fn cli_contract_batch_output_contains_entries_and_city_key() {
                     temp_min_c: 14.5,
                     temp_max_c: 19.9,
                     precip_prob_max_pct: 13,
+                    precip_sum_mm: None,
+                    sunrise: None,
+                    sunset: None,
+                    moon_phase: None,
+                    moon_illumination_pct: None,
+                    wind_speed_max_kmh: None,
+                    wind_direction_deg: None,
+                    humidity_mean_pct: None,
+                    uv_index_max: None,
                 }],
                 source: "open_meteo".to_string(),
                 source_trace: vec![],
@@ -122,6 +144,8 @@ fn cli_contract_batch_output_contains_entries_and_city_key() {
                     key: "today-taipei-25.0330-121.5654".to_string(),
                     ttl_secs: 1800,
                     age_secs: 0,
+                    provider: None,
+                    attempts: Vec::new(),
                 },
             }),
             error: None,
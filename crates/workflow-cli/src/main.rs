@@ -1,11 +1,16 @@
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 use clap::{Parser, Subcommand, ValueEnum};
 use workflow_common::{
-    EnvelopePayloadKind, OutputMode, RuntimeConfig, ScriptFilterMode, WorkflowError,
+    DEFAULT_PRUNE_HALF_LIFE_DAYS, EnvelopePayloadKind, ForgePage, OutputMode, ProjectListing,
+    RefreshPolicy, RuntimeConfig, ScriptFilterMode, WorkflowError, archive_project,
     build_alfred_error_feedback, build_error_details_json, build_error_envelope,
-    build_script_filter_feedback_with_mode, build_success_envelope, record_usage,
-    web_url_for_project,
+    build_script_filter_feedback_for_projects, build_success_envelope, claim_background_rescan,
+    export_usage, forge_page_url_for_project, is_code_workspace_file, list_projects, load_projects,
+    normalize_alias, open_terminal, parse_remote_spec, pin_project, prune_usage,
+    record_usage_with_action, release_background_rescan, rescan, set_project_alias,
+    unarchive_project, unpin_project, usage_now, web_url_for_project_with,
 };
 
 #[derive(Debug, Parser)]
@@ -34,15 +39,101 @@ enum Commands {
         /// Selected project path.
         #[arg(long)]
         path: PathBuf,
+        /// Action stored as the record's `last_action` (e.g. `open`, `github`).
+        #[arg(long, default_value = "open")]
+        action: String,
     },
-    /// Resolve project origin URL to its canonical web URL (`https://<host>/<path>`).
-    GithubUrl {
+    /// Inspect or maintain the usage log.
+    Usage {
+        #[command(subcommand)]
+        command: UsageCommands,
+    },
+    /// Rebuild the persistent project scan cache.
+    Rescan,
+    /// List discovered projects in rank order (path, name, remote, last_used, score).
+    ListProjects {
+        /// Optional filter query (same matching as `script-filter`).
+        #[arg(long, short, default_value = "")]
+        query: String,
+        /// Output mode (`human` lines or the `json` service envelope).
+        #[arg(long, value_enum, default_value_t = ListOutputArg::Human)]
+        output: ListOutputArg,
+    },
+    /// Pin a project to the top of results (stored in the config file).
+    Pin {
+        /// Project path to pin.
+        #[arg(long)]
+        path: PathBuf,
+    },
+    /// Remove a project from the pin list.
+    Unpin {
+        /// Pinned project path.
+        #[arg(long)]
+        path: PathBuf,
+    },
+    /// Hide a project from default results; it still matches queries starting with `all `.
+    Archive {
+        /// Project path to archive.
+        #[arg(long)]
+        path: PathBuf,
+    },
+    /// Show an archived project in default results again.
+    Unarchive {
+        /// Archived project path.
+        #[arg(long)]
+        path: PathBuf,
+    },
+    /// Set or remove a query alias that jumps straight to a project.
+    Alias {
+        /// Alias typed as the whole query (one word, case-insensitive).
+        name: String,
+        /// Project path the alias opens.
+        #[arg(long, required_unless_present = "remove")]
+        path: Option<PathBuf>,
+        /// Remove the alias instead of setting it.
+        #[arg(long, conflicts_with = "path")]
+        remove: bool,
+    },
+    /// Open the configured terminal app (`OPEN_PROJECT_TERMINAL_APP`) at a project path.
+    OpenTerminal {
+        /// Selected project path.
+        #[arg(long)]
+        path: PathBuf,
+    },
+    /// Resolve project origin URL to its canonical web URL (forge-aware; alias: `github-url`).
+    #[command(alias = "github-url")]
+    RemoteUrl {
+        /// Selected project path.
+        #[arg(long)]
+        path: PathBuf,
+    },
+    /// Resolve a repository sub-page (pull requests, issues, CI) on the origin's forge.
+    ForgeUrl {
+        /// Page to resolve.
+        #[arg(long, value_enum)]
+        kind: ForgePageArg,
         /// Selected project path.
         #[arg(long)]
         path: PathBuf,
     },
 }
 
+#[derive(Debug, Subcommand)]
+enum UsageCommands {
+    /// Drop entries for deleted projects and decay stored hit counts.
+    Prune {
+        /// Half-life (days) applied to stored counts; entries decayed to zero are removed.
+        #[arg(long, default_value_t = DEFAULT_PRUNE_HALF_LIFE_DAYS)]
+        half_life_days: u32,
+    },
+    /// Print usage records (`path | last_used | count | last_action`).
+    Export {
+        /// Emit the JSON service envelope with one record per entry.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum ScriptFilterModeArg {
     Open,
@@ -58,6 +149,29 @@ impl From<ScriptFilterModeArg> for ScriptFilterMode {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ListOutputArg {
+    Human,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ForgePageArg {
+    Pr,
+    Issues,
+    Ci,
+}
+
+impl From<ForgePageArg> for ForgePage {
+    fn from(value: ForgePageArg) -> Self {
+        match value {
+            ForgePageArg::Pr => ForgePage::PullRequests,
+            ForgePageArg::Issues => ForgePage::Issues,
+            ForgePageArg::Ci => ForgePage::Ci,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum OutputModeArg {
     Human,
@@ -114,8 +228,16 @@ impl AppError {
 }
 
 const ERROR_CODE_USER_INVALID_PATH: &str = "NILS_WORKFLOW_001";
+const ERROR_CODE_USER_CACHE_DISABLED: &str = "NILS_WORKFLOW_005";
+const ERROR_CODE_USER_FORGE_PAGE: &str = "NILS_WORKFLOW_007";
+const ERROR_CODE_USER_CONFIG_PARSE: &str = "NILS_WORKFLOW_008";
+const ERROR_CODE_USER_INVALID_INPUT: &str = "NILS_COMMON_001";
 const ERROR_CODE_RUNTIME_GIT: &str = "NILS_WORKFLOW_002";
 const ERROR_CODE_RUNTIME_USAGE_WRITE: &str = "NILS_WORKFLOW_003";
+const ERROR_CODE_RUNTIME_CACHE_WRITE: &str = "NILS_WORKFLOW_004";
+const ERROR_CODE_RUNTIME_TERMINAL_LAUNCH: &str = "NILS_WORKFLOW_006";
+const ERROR_CODE_RUNTIME_CONFIG_WRITE: &str = "NILS_WORKFLOW_009";
+const ERROR_CODE_RUNTIME_SEARCH_LAUNCH: &str = "NILS_WORKFLOW_010";
 const ERROR_CODE_RUNTIME_SERIALIZE: &str = "NILS_COMMON_005";
 
 impl Cli {
@@ -123,14 +245,47 @@ impl Cli {
         match &self.command {
             Commands::ScriptFilter { .. } => "workflow.script-filter",
             Commands::RecordUsage { .. } => "workflow.record-usage",
-            Commands::GithubUrl { .. } => "workflow.github-url",
+            Commands::Usage {
+                command: UsageCommands::Prune { .. },
+            } => "workflow.usage.prune",
+            Commands::Usage {
+                command: UsageCommands::Export { .. },
+            } => "workflow.usage.export",
+            Commands::Rescan => "workflow.rescan",
+            Commands::ListProjects { .. } => "workflow.list-projects",
+            Commands::Pin { .. } => "workflow.pin",
+            Commands::Unpin { .. } => "workflow.unpin",
+            Commands::Archive { .. } => "workflow.archive",
+            Commands::Unarchive { .. } => "workflow.unarchive",
+            Commands::Alias { .. } => "workflow.alias",
+            Commands::OpenTerminal { .. } => "workflow.open-terminal",
+            Commands::RemoteUrl { .. } => "workflow.remote-url",
+            Commands::ForgeUrl { .. } => "workflow.forge-url",
         }
     }
 
     fn output_mode_hint(&self) -> OutputMode {
         match &self.command {
             Commands::ScriptFilter { output, .. } => (*output).into(),
-            Commands::RecordUsage { .. } | Commands::GithubUrl { .. } => OutputMode::Human,
+            Commands::Usage {
+                command: UsageCommands::Export { json: true },
+            } => OutputMode::Json,
+            Commands::ListProjects {
+                output: ListOutputArg::Json,
+                ..
+            } => OutputMode::Json,
+            Commands::RecordUsage { .. }
+            | Commands::ListProjects { .. }
+            | Commands::Pin { .. }
+            | Commands::Unpin { .. }
+            | Commands::Archive { .. }
+            | Commands::Unarchive { .. }
+            | Commands::Alias { .. }
+            | Commands::Usage { .. }
+            | Commands::Rescan
+            | Commands::OpenTerminal { .. }
+            | Commands::RemoteUrl { .. }
+            | Commands::ForgeUrl { .. } => OutputMode::Human,
         }
     }
 }
@@ -164,7 +319,16 @@ fn run_with_config(cli: Cli, config: &RuntimeConfig) -> Result<String, AppError>
             output,
         } => {
             let output_mode: OutputMode = output.into();
-            let feedback = build_script_filter_feedback_with_mode(&query, config, mode.into());
+            let snapshot = load_projects(config, RefreshPolicy::Deferred);
+            if snapshot.needs_refresh() {
+                spawn_background_rescan(config);
+            }
+            let feedback = build_script_filter_feedback_for_projects(
+                &query,
+                config,
+                mode.into(),
+                &snapshot.projects,
+            );
             let alfred_json = feedback.to_json().map_err(|error| {
                 AppError::runtime(
                     ERROR_CODE_RUNTIME_SERIALIZE,
@@ -182,16 +346,206 @@ fn run_with_config(cli: Cli, config: &RuntimeConfig) -> Result<String, AppError>
                 OutputMode::Human => Ok(render_script_filter_human(&feedback)),
             }
         }
-        Commands::RecordUsage { path } => {
+        Commands::RecordUsage { path, action } => {
+            validate_entry_path(&path)?;
+            record_usage_with_action(&path, &config.usage_file, &action)
+                .map_err(map_workflow_error)?;
+            Ok(path.to_string_lossy().to_string())
+        }
+        Commands::Usage {
+            command: UsageCommands::Prune { half_life_days },
+        } => {
+            let report = prune_usage(&config.usage_file, half_life_days, usage_now())
+                .map_err(map_workflow_error)?;
+            Ok(format!(
+                "removed {} missing, {} decayed; kept {}",
+                report.removed_missing, report.removed_decayed, report.kept
+            ))
+        }
+        Commands::Usage {
+            command: UsageCommands::Export { json },
+        } => {
+            let records = export_usage(&config.usage_file);
+            if json {
+                let payload = serde_json::to_string(&records).map_err(|error| {
+                    AppError::runtime(
+                        ERROR_CODE_RUNTIME_SERIALIZE,
+                        format!("failed to serialize usage records: {error}"),
+                    )
+                })?;
+                return Ok(build_success_envelope(
+                    "workflow.usage.export",
+                    EnvelopePayloadKind::Results,
+                    &payload,
+                ));
+            }
+            Ok(render_usage_human(&records))
+        }
+        Commands::Rescan => {
+            if config.cache_dir.is_none() {
+                return Err(AppError::user(
+                    ERROR_CODE_USER_CACHE_DISABLED,
+                    "scan cache is disabled: ALFRED_WORKFLOW_CACHE is not set",
+                ));
+            }
+            let projects = rescan(config).map_err(map_workflow_error)?;
+            Ok(format!("{} projects cached", projects.len()))
+        }
+        Commands::ListProjects { query, output } => {
+            let snapshot = load_projects(config, RefreshPolicy::Inline);
+            let listing = list_projects(&query, config, &snapshot.projects);
+            match output {
+                ListOutputArg::Json => {
+                    let payload = serde_json::to_string(&listing).map_err(|error| {
+                        AppError::runtime(
+                            ERROR_CODE_RUNTIME_SERIALIZE,
+                            format!("failed to serialize project listing: {error}"),
+                        )
+                    })?;
+                    Ok(build_success_envelope(
+                        "workflow.list-projects",
+                        EnvelopePayloadKind::Results,
+                        &payload,
+                    ))
+                }
+                ListOutputArg::Human => Ok(render_listing_human(&listing)),
+            }
+        }
+        Commands::Pin { path } => {
+            validate_entry_path(&path)?;
+            let changed = pin_project(&config.config_file, &path).map_err(map_workflow_error)?;
+            let status = if changed { "pinned" } else { "already pinned" };
+            Ok(format!("{status}: {}", path.to_string_lossy()))
+        }
+        Commands::Unpin { path } => {
+            let changed = unpin_project(&config.config_file, &path).map_err(map_workflow_error)?;
+            let status = if changed { "unpinned" } else { "not pinned" };
+            Ok(format!("{status}: {}", path.to_string_lossy()))
+        }
+        Commands::Archive { path } => {
+            validate_entry_path(&path)?;
+            let changed = archive_project(&config.usage_file, &path).map_err(map_workflow_error)?;
+            let status = if changed {
+                "archived"
+            } else {
+                "already archived"
+            };
+            Ok(format!("{status}: {}", path.to_string_lossy()))
+        }
+        Commands::Unarchive { path } => {
+            let changed =
+                unarchive_project(&config.usage_file, &path).map_err(map_workflow_error)?;
+            let status = if changed {
+                "unarchived"
+            } else {
+                "not archived"
+            };
+            Ok(format!("{status}: {}", path.to_string_lossy()))
+        }
+        Commands::Alias { name, path, remove } => {
+            let alias = normalize_alias(&name).ok_or_else(|| {
+                AppError::user(
+                    ERROR_CODE_USER_INVALID_INPUT,
+                    format!("alias must be a single word: {name:?}"),
+                )
+            })?;
+            if remove {
+                let changed = set_project_alias(&config.config_file, &alias, None)
+                    .map_err(map_workflow_error)?;
+                return Ok(if changed {
+                    format!("removed alias {alias}")
+                } else {
+                    format!("alias {alias} is not set")
+                });
+            }
+
+            let path = path.unwrap_or_default();
+            validate_entry_path(&path)?;
+            set_project_alias(&config.config_file, &alias, Some(&path))
+                .map_err(map_workflow_error)?;
+            Ok(format!("alias {alias} -> {}", path.to_string_lossy()))
+        }
+        Commands::OpenTerminal { path } => {
             validate_project_path(&path)?;
-            record_usage(&path, &config.usage_file).map_err(map_workflow_error)?;
+            open_terminal(config.terminal_app, &path).map_err(map_workflow_error)?;
             Ok(path.to_string_lossy().to_string())
         }
-        Commands::GithubUrl { path } => {
+        Commands::RemoteUrl { path } => {
             validate_project_path(&path)?;
-            web_url_for_project(&path).map_err(map_workflow_error)
+            web_url_for_project_with(&path, &config.forge_hosts).map_err(map_workflow_error)
         }
+        Commands::ForgeUrl { kind, path } => {
+            validate_project_path(&path)?;
+            forge_page_url_for_project(&path, &config.forge_hosts, kind.into())
+                .map_err(map_workflow_error)
+        }
+    }
+}
+
+/// Refresh a stale scan cache in a detached child so the script filter returns immediately.
+///
+/// At most one rescan runs at a time: keystrokes that find the marker claimed skip the spawn.
+fn spawn_background_rescan(config: &RuntimeConfig) {
+    let Some(cache_dir) = config.cache_dir.as_deref() else {
+        return;
+    };
+    if !claim_background_rescan(cache_dir) {
+        return;
+    }
+
+    // Best effort: the stale snapshot is still served and the next call retries.
+    let spawned = std::env::current_exe().and_then(|current_exe| {
+        Command::new(current_exe)
+            .arg("rescan")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    });
+    if spawned.is_err() {
+        release_background_rescan(cache_dir);
+    }
+}
+
+fn render_usage_human(records: &[serde_json::Value]) -> String {
+    if records.is_empty() {
+        return "No usage recorded".to_string();
+    }
+
+    records
+        .iter()
+        .map(|record| {
+            format!(
+                "{} | {} | {} | {}",
+                record["path"].as_str().unwrap_or_default(),
+                record["last_used"].as_str().unwrap_or_default(),
+                record["use_count"].as_u64().unwrap_or_default(),
+                record["last_action"].as_str().unwrap_or("-"),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_listing_human(listing: &[ProjectListing]) -> String {
+    if listing.is_empty() {
+        return "No projects matched".to_string();
     }
+
+    listing
+        .iter()
+        .map(|entry| {
+            format!(
+                "{} | {} | {} | {} | {:.2}",
+                entry.name,
+                entry.path,
+                entry.remote.as_deref().unwrap_or("-"),
+                entry.last_used.as_deref().unwrap_or("N/A"),
+                entry.score,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 fn render_script_filter_human(feedback: &workflow_common::Feedback) -> String {
@@ -242,6 +596,18 @@ fn error_kind_label(kind: ErrorKind) -> &'static str {
     }
 }
 
+/// Like [`validate_project_path`], but also accepts `host:/path` Remote-SSH entries and
+/// existing `*.code-workspace` files.
+fn validate_entry_path(path: &Path) -> Result<(), AppError> {
+    if parse_remote_spec(&path.to_string_lossy()).is_some()
+        || (is_code_workspace_file(path) && path.is_file())
+    {
+        return Ok(());
+    }
+
+    validate_project_path(path)
+}
+
 fn validate_project_path(path: &Path) -> Result<(), AppError> {
     if !path.exists() {
         return Err(AppError::user(
@@ -278,6 +644,10 @@ fn map_workflow_error(error: WorkflowError) -> AppError {
             ERROR_CODE_RUNTIME_GIT,
             format!("unsupported remote URL format: {remote}"),
         ),
+        WorkflowError::UnsupportedForgePage { forge, page } => AppError::user(
+            ERROR_CODE_USER_FORGE_PAGE,
+            format!("{} has no {} page", forge.label(), page.as_str()),
+        ),
         WorkflowError::GitCommand { path, message } => AppError::runtime(
             ERROR_CODE_RUNTIME_GIT,
             format!(
@@ -285,6 +655,35 @@ fn map_workflow_error(error: WorkflowError) -> AppError {
                 path.to_string_lossy()
             ),
         ),
+        WorkflowError::CacheWrite { path, source } => AppError::runtime(
+            ERROR_CODE_RUNTIME_CACHE_WRITE,
+            format!(
+                "failed to persist scan cache at {}: {source}",
+                path.to_string_lossy()
+            ),
+        ),
+        WorkflowError::TerminalLaunch { app, message } => AppError::runtime(
+            ERROR_CODE_RUNTIME_TERMINAL_LAUNCH,
+            format!("failed to launch {app}: {message}"),
+        ),
+        WorkflowError::SearchLaunch { program, message } => AppError::runtime(
+            ERROR_CODE_RUNTIME_SEARCH_LAUNCH,
+            format!("failed to launch {program}: {message}"),
+        ),
+        WorkflowError::ConfigParse { path, message } => AppError::user(
+            ERROR_CODE_USER_CONFIG_PARSE,
+            format!(
+                "config file {} is invalid: {message}",
+                path.to_string_lossy()
+            ),
+        ),
+        WorkflowError::ConfigWrite { path, source } => AppError::runtime(
+            ERROR_CODE_RUNTIME_CONFIG_WRITE,
+            format!(
+                "failed to persist config file at {}: {source}",
+                path.to_string_lossy()
+            ),
+        ),
         WorkflowError::UsageWrite { path, source } => AppError::runtime(
             ERROR_CODE_RUNTIME_USAGE_WRITE,
             format!(
@@ -317,6 +716,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let output = run_with_config(
@@ -368,11 +768,15 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let recorded = run_with_config(
             Cli {
-                command: Commands::RecordUsage { path: repo.clone() },
+                command: Commands::RecordUsage {
+                    path: repo.clone(),
+                    action: "open".to_string(),
+                },
             },
             &config,
         )
@@ -385,7 +789,7 @@ mod tests {
 
         let github_url = run_with_config(
             Cli {
-                command: Commands::GithubUrl { path: repo.clone() },
+                command: Commands::RemoteUrl { path: repo.clone() },
             },
             &config,
         )
@@ -396,6 +800,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn record_usage_accepts_remote_ssh_entries() {
+        let temp = tempdir().expect("create temp dir");
+        let config = RuntimeConfig {
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+
+        let recorded = run_with_config(
+            Cli {
+                command: Commands::RecordUsage {
+                    path: PathBuf::from("devbox:/srv/api"),
+                    action: "open".to_string(),
+                },
+            },
+            &config,
+        )
+        .expect("remote entries skip the local directory check");
+        assert_eq!(recorded, "devbox:/srv/api");
+
+        let err = run_with_config(
+            Cli {
+                command: Commands::RecordUsage {
+                    path: PathBuf::from("devbox:relative"),
+                    action: "open".to_string(),
+                },
+            },
+            &config,
+        )
+        .expect_err("non-absolute remote path is not an entry");
+        assert_eq!(err.code, "NILS_WORKFLOW_001");
+    }
+
+    #[test]
+    fn record_usage_accepts_code_workspace_files() {
+        let temp = tempdir().expect("create temp dir");
+        let workspace_file = temp.path().join("stack.code-workspace");
+        fs::write(&workspace_file, "{}").expect("write workspace file");
+        let config = RuntimeConfig {
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+        let record = |path: PathBuf| {
+            run_with_config(
+                Cli {
+                    command: Commands::RecordUsage {
+                        path,
+                        action: "open".to_string(),
+                    },
+                },
+                &config,
+            )
+        };
+
+        let recorded = record(workspace_file.clone()).expect("workspace files are entries");
+        assert_eq!(recorded, workspace_file.to_string_lossy());
+        let usage = fs::read_to_string(&config.usage_file).expect("usage written");
+        assert!(
+            usage.contains("stack.code-workspace"),
+            "keyed by file: {usage}"
+        );
+
+        let plain = temp.path().join("notes.txt");
+        fs::write(&plain, "").expect("write plain file");
+        let err = record(plain).expect_err("other files are not entries");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_PATH);
+    }
+
     #[test]
     fn github_url_accepts_ssh_url_remote_format() {
         let temp = tempdir().expect("create temp dir");
@@ -421,11 +893,12 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let github_url = run_with_config(
             Cli {
-                command: Commands::GithubUrl { path: repo.clone() },
+                command: Commands::RemoteUrl { path: repo.clone() },
             },
             &config,
         )
@@ -444,6 +917,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let missing = temp.path().join("missing-project");
@@ -451,6 +925,7 @@ mod tests {
             Cli {
                 command: Commands::RecordUsage {
                     path: missing.clone(),
+                    action: "open".to_string(),
                 },
             },
             &config,
@@ -481,6 +956,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let output = run_with_config(
@@ -533,6 +1009,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let output = run_with_config(
@@ -572,6 +1049,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let output = run_with_config(
@@ -616,11 +1094,12 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let url = run_with_config(
             Cli {
-                command: Commands::GithubUrl { path: repo.clone() },
+                command: Commands::RemoteUrl { path: repo.clone() },
             },
             &config,
         )
@@ -628,6 +1107,402 @@ mod tests {
         assert_eq!(url, "https://gitlab.com/gitlab-org/gitlab-foss/scripts");
     }
 
+    #[test]
+    fn github_url_remains_an_alias_of_remote_url() {
+        let cli = Cli::try_parse_from(["workflow-cli", "github-url", "--path", "/tmp/repo"])
+            .expect("legacy subcommand name should parse");
+        assert!(matches!(cli.command, Commands::RemoteUrl { .. }));
+        assert_eq!(cli.command_name(), "workflow.remote-url");
+    }
+
+    #[test]
+    fn remote_url_applies_configured_forge_hosts() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("projects/billing");
+        init_repo(&repo);
+
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args([
+                "remote",
+                "add",
+                "origin",
+                "ssh://git@code.corp.example:7999/plat/billing.git",
+            ])
+            .status()
+            .expect("set git remote");
+        assert!(status.success(), "git remote add should succeed");
+
+        let config = RuntimeConfig {
+            forge_hosts: workflow_common::parse_forge_hosts("code.corp.example=bitbucket-server"),
+            ..RuntimeConfig::default()
+        };
+
+        let url = run_with_config(
+            Cli {
+                command: Commands::RemoteUrl { path: repo },
+            },
+            &config,
+        )
+        .expect("remote-url should resolve bitbucket server origin");
+        assert_eq!(url, "https://code.corp.example/projects/PLAT/repos/billing");
+    }
+
+    #[test]
+    fn forge_url_derives_pages_for_origin_forge() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("projects/alpha");
+        init_repo(&repo);
+
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args([
+                "remote",
+                "add",
+                "origin",
+                "git@gitlab.com:group/sub/alpha.git",
+            ])
+            .status()
+            .expect("set git remote");
+        assert!(status.success(), "git remote add should succeed");
+
+        let config = RuntimeConfig::default();
+        let resolve = |kind| {
+            run_with_config(
+                Cli {
+                    command: Commands::ForgeUrl {
+                        kind,
+                        path: repo.clone(),
+                    },
+                },
+                &config,
+            )
+        };
+
+        assert_eq!(
+            resolve(ForgePageArg::Pr).expect("merge requests url"),
+            "https://gitlab.com/group/sub/alpha/-/merge_requests"
+        );
+        assert_eq!(
+            resolve(ForgePageArg::Issues).expect("issues url"),
+            "https://gitlab.com/group/sub/alpha/-/issues"
+        );
+        assert_eq!(
+            resolve(ForgePageArg::Ci).expect("pipelines url"),
+            "https://gitlab.com/group/sub/alpha/-/pipelines"
+        );
+    }
+
+    #[test]
+    fn forge_url_reports_missing_page_as_user_error() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("projects/billing");
+        init_repo(&repo);
+
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args([
+                "remote",
+                "add",
+                "origin",
+                "https://code.corp.example/scm/plat/billing.git",
+            ])
+            .status()
+            .expect("set git remote");
+        assert!(status.success(), "git remote add should succeed");
+
+        let config = RuntimeConfig {
+            forge_hosts: workflow_common::parse_forge_hosts("code.corp.example=bitbucket-server"),
+            ..RuntimeConfig::default()
+        };
+
+        let err = run_with_config(
+            Cli {
+                command: Commands::ForgeUrl {
+                    kind: ForgePageArg::Issues,
+                    path: repo,
+                },
+            },
+            &config,
+        )
+        .expect_err("bitbucket server has no issues page");
+        assert_eq!(err.code, ERROR_CODE_USER_FORGE_PAGE);
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn list_projects_json_wraps_listing_in_results_envelope() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("projects");
+        let repo = root.join("alpha");
+        init_repo(&repo);
+
+        let config = RuntimeConfig {
+            project_roots: vec![root],
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+
+        let cli = Cli::try_parse_from(["workflow-cli", "list-projects", "--output", "json"])
+            .expect("list-projects should parse");
+        assert_eq!(cli.output_mode_hint(), OutputMode::Json);
+        let output = run_with_config(cli, &config).expect("list-projects should succeed");
+
+        let envelope: serde_json::Value =
+            serde_json::from_str(&output).expect("envelope should be valid JSON");
+        assert_eq!(envelope["ok"], true);
+        assert_eq!(envelope["command"], "workflow.list-projects");
+        let results = envelope["results"].as_array().expect("results array");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["name"], "alpha");
+        assert_eq!(results[0]["path"], repo.to_string_lossy().as_ref());
+        assert_eq!(results[0]["remote"], serde_json::Value::Null);
+        assert_eq!(results[0]["last_used"], serde_json::Value::Null);
+        assert_eq!(results[0]["score"], 0.0);
+
+        let human = run_with_config(
+            Cli {
+                command: Commands::ListProjects {
+                    query: "missing".to_string(),
+                    output: ListOutputArg::Human,
+                },
+            },
+            &config,
+        )
+        .expect("human listing should succeed");
+        assert_eq!(human, "No projects matched");
+    }
+
+    #[test]
+    fn pin_unpin_and_alias_edit_the_config_file() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("projects/alpha");
+        fs::create_dir_all(&repo).expect("create project dir");
+        let config = RuntimeConfig {
+            config_file: temp.path().join("nils/open-project.toml"),
+            ..RuntimeConfig::default()
+        };
+        let run = |args: &[&str]| {
+            let cli =
+                Cli::try_parse_from(std::iter::once("workflow-cli").chain(args.iter().copied()))
+                    .expect("args should parse");
+            run_with_config(cli, &config)
+        };
+        let repo_arg = repo.to_string_lossy().to_string();
+
+        let pinned = run(&["pin", "--path", &repo_arg]).expect("pin should succeed");
+        assert_eq!(pinned, format!("pinned: {repo_arg}"));
+        let again = run(&["pin", "--path", &repo_arg]).expect("re-pin should succeed");
+        assert_eq!(again, format!("already pinned: {repo_arg}"));
+
+        let aliased = run(&["alias", "Wk", "--path", &repo_arg]).expect("alias should succeed");
+        assert_eq!(aliased, format!("alias wk -> {repo_arg}"));
+
+        let written = fs::read_to_string(&config.config_file).expect("config file written");
+        assert!(written.contains("pins = ["), "pins persisted: {written}");
+        assert!(
+            written.contains("[aliases]"),
+            "aliases persisted: {written}"
+        );
+
+        let unpinned = run(&["unpin", "--path", &repo_arg]).expect("unpin should succeed");
+        assert_eq!(unpinned, format!("unpinned: {repo_arg}"));
+        let removed = run(&["alias", "wk", "--remove"]).expect("alias removal should succeed");
+        assert_eq!(removed, "removed alias wk");
+
+        let err = run(&["alias", "two words", "--path", &repo_arg])
+            .expect_err("multi-word alias should fail");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_INPUT);
+        assert!(
+            Cli::try_parse_from(["workflow-cli", "alias", "wk"]).is_err(),
+            "alias needs --path or --remove"
+        );
+    }
+
+    #[test]
+    fn archive_and_unarchive_edit_the_usage_sidecar() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("projects/alpha");
+        fs::create_dir_all(&repo).expect("create project dir");
+        let config = RuntimeConfig {
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+        let run = |args: &[&str]| {
+            let cli =
+                Cli::try_parse_from(std::iter::once("workflow-cli").chain(args.iter().copied()))
+                    .expect("args should parse");
+            run_with_config(cli, &config)
+        };
+        let repo_arg = repo.to_string_lossy().to_string();
+
+        let archived = run(&["archive", "--path", &repo_arg]).expect("archive should succeed");
+        assert_eq!(archived, format!("archived: {repo_arg}"));
+        let again = run(&["archive", "--path", &repo_arg]).expect("re-archive should succeed");
+        assert_eq!(again, format!("already archived: {repo_arg}"));
+        let sidecar = fs::read_to_string(temp.path().join("usage.log.archived"))
+            .expect("archive sidecar written");
+        assert_eq!(sidecar.trim(), repo_arg);
+
+        let unarchived =
+            run(&["unarchive", "--path", &repo_arg]).expect("unarchive should succeed");
+        assert_eq!(unarchived, format!("unarchived: {repo_arg}"));
+
+        let missing = temp.path().join("missing").to_string_lossy().to_string();
+        let err = run(&["archive", "--path", &missing]).expect_err("missing path should fail");
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_PATH);
+    }
+
+    #[test]
+    fn config_edits_report_malformed_config_file() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("projects/alpha");
+        fs::create_dir_all(&repo).expect("create project dir");
+        let config_file = temp.path().join("open-project.toml");
+        fs::write(&config_file, "pins = [").expect("write malformed config");
+        let config = RuntimeConfig {
+            config_file,
+            ..RuntimeConfig::default()
+        };
+
+        let err = run_with_config(
+            Cli {
+                command: Commands::Pin { path: repo },
+            },
+            &config,
+        )
+        .expect_err("malformed config should fail");
+        assert_eq!(err.code, ERROR_CODE_USER_CONFIG_PARSE);
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn rescan_command_writes_cache_and_reports_count() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("projects");
+        init_repo(&root.join("alpha"));
+        init_repo(&root.join("beta"));
+        let cache_dir = temp.path().join("cache");
+
+        let config = RuntimeConfig {
+            project_roots: vec![root],
+            usage_file: temp.path().join("usage.log"),
+            cache_dir: Some(cache_dir.clone()),
+            ..RuntimeConfig::default()
+        };
+
+        let output = run_with_config(
+            Cli {
+                command: Commands::Rescan,
+            },
+            &config,
+        )
+        .expect("rescan should succeed");
+
+        assert_eq!(output, "2 projects cached");
+        assert!(
+            workflow_common::scan_cache::scan_cache_path(&cache_dir).is_file(),
+            "rescan should persist the cache file"
+        );
+    }
+
+    #[test]
+    fn rescan_command_requires_cache_dir() {
+        let config = RuntimeConfig::default();
+        let err = run_with_config(
+            Cli {
+                command: Commands::Rescan,
+            },
+            &config,
+        )
+        .expect_err("rescan without cache dir should fail");
+
+        assert_eq!(err.kind, ErrorKind::User);
+        assert_eq!(err.code, ERROR_CODE_USER_CACHE_DISABLED);
+    }
+
+    #[test]
+    fn usage_prune_and_export_report_entries() {
+        let temp = tempdir().expect("create temp dir");
+        let project = temp.path().join("alpha");
+        fs::create_dir_all(&project).expect("create project dir");
+        let usage_file = temp.path().join("usage.log");
+        fs::write(
+            &usage_file,
+            format!(
+                "{} | 2999-01-01 00:00:00 | 2\n{} | 2999-01-01 00:00:00 | 5\n",
+                project.display(),
+                temp.path().join("gone").display()
+            ),
+        )
+        .expect("seed usage");
+
+        let config = RuntimeConfig {
+            usage_file,
+            ..RuntimeConfig::default()
+        };
+
+        let pruned = run_with_config(
+            Cli {
+                command: Commands::Usage {
+                    command: UsageCommands::Prune { half_life_days: 30 },
+                },
+            },
+            &config,
+        )
+        .expect("prune should succeed");
+        assert_eq!(pruned, "removed 1 missing, 0 decayed; kept 1");
+
+        let human = run_with_config(
+            Cli {
+                command: Commands::Usage {
+                    command: UsageCommands::Export { json: false },
+                },
+            },
+            &config,
+        )
+        .expect("export should succeed");
+        assert_eq!(
+            human,
+            format!("{} | 2999-01-01 00:00:00 | 2 | -", project.display())
+        );
+
+        let json = run_with_config(
+            Cli {
+                command: Commands::Usage {
+                    command: UsageCommands::Export { json: true },
+                },
+            },
+            &config,
+        )
+        .expect("json export should succeed");
+        let envelope: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+        assert_eq!(envelope["command"], "workflow.usage.export");
+        assert_eq!(envelope["results"][0]["use_count"], 2);
+    }
+
+    #[test]
+    fn open_terminal_reports_user_error_for_invalid_path() {
+        let temp = tempdir().expect("create temp dir");
+        let file = temp.path().join("not-a-dir.txt");
+        fs::write(&file, "x").expect("write file");
+
+        let err = run_with_config(
+            Cli {
+                command: Commands::OpenTerminal { path: file },
+            },
+            &RuntimeConfig::default(),
+        )
+        .expect_err("file path should be rejected before launching");
+
+        assert_eq!(err.kind, ErrorKind::User);
+        assert_eq!(err.code, ERROR_CODE_USER_INVALID_PATH);
+        assert!(err.message.contains("not a directory"));
+    }
+
     #[test]
     fn script_filter_error_redaction_masks_sensitive_tokens() {
         let redacted = workflow_common::redact_sensitive(
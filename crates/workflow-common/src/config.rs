@@ -1,50 +1,306 @@
+use std::collections::BTreeMap;
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use serde::Deserialize;
+
+use crate::discovery::{
+    DEFAULT_PROJECT_MARKERS, DEFAULT_SCAN_WORKERS, MAX_SCAN_DEPTH, ScanOptions, parse_remote_spec,
+};
+use crate::error::WorkflowError;
+use crate::git::{ForgeHost, GIT_STATUS_TIMEOUT, parse_forge_hosts};
+use crate::glob::parse_ignore_globs;
+use crate::list_parser::split_ordered_list;
 use crate::output_contract::OutputMode;
+use crate::search::{SEARCH_TIMEOUT, SearchOptions};
+use crate::terminal::TerminalApp;
 
 pub const DEFAULT_PROJECT_DIRS: &str = "$HOME/Project,$HOME/.config";
 pub const DEFAULT_USAGE_FILE: &str = "$HOME/.config/zsh/cache/.alfred_project_usage.log";
 pub const DEFAULT_VSCODE_PATH: &str =
     "/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code";
 pub const DEFAULT_OPEN_PROJECT_MAX_RESULTS: usize = 30;
+pub const DEFAULT_OPEN_PROJECT_RECENT_COUNT: usize = 5;
+pub const DEFAULT_OPEN_PROJECT_SCAN_WORKERS: usize = DEFAULT_SCAN_WORKERS;
+pub const DEFAULT_OPEN_PROJECT_SCAN_DEPTH: usize = MAX_SCAN_DEPTH;
+pub const DEFAULT_CONFIG_FILE: &str = "$HOME/.config/nils/open-project.toml";
 pub const DEFAULT_OUTPUT_MODE: OutputMode = OutputMode::AlfredJson;
 pub const OUTPUT_MODE_ENV: &str = "WORKFLOW_OUTPUT_MODE";
 
 const PROJECT_DIRS_ENV: &str = "PROJECT_DIRS";
+const ALFRED_WORKFLOW_CACHE_ENV: &str = "ALFRED_WORKFLOW_CACHE";
 const USAGE_FILE_ENV: &str = "USAGE_FILE";
 const VSCODE_PATH_ENV: &str = "VSCODE_PATH";
+const OPEN_PROJECT_EDITOR_2_ENV: &str = "OPEN_PROJECT_EDITOR_2";
+const OPEN_PROJECT_EDITOR_3_ENV: &str = "OPEN_PROJECT_EDITOR_3";
 const OPEN_PROJECT_MAX_RESULTS_ENV: &str = "OPEN_PROJECT_MAX_RESULTS";
 const OPEN_PROJECT_MAX_RESULTS_MIN: usize = 1;
 const OPEN_PROJECT_MAX_RESULTS_MAX: usize = 200;
+const OPEN_PROJECT_RECENT_COUNT_ENV: &str = "OPEN_PROJECT_RECENT_COUNT";
+const OPEN_PROJECT_RECENT_COUNT_MAX: usize = 20;
+const OPEN_PROJECT_SCAN_WORKERS_ENV: &str = "OPEN_PROJECT_SCAN_WORKERS";
+const OPEN_PROJECT_SCAN_WORKERS_MIN: usize = 1;
+const OPEN_PROJECT_SCAN_WORKERS_MAX: usize = 32;
+const OPEN_PROJECT_SCAN_DEPTH_ENV: &str = "OPEN_PROJECT_SCAN_DEPTH";
+const OPEN_PROJECT_SCAN_DEPTH_MIN: usize = 1;
+const OPEN_PROJECT_SCAN_DEPTH_MAX: usize = 10;
+const OPEN_PROJECT_IGNORE_GLOBS_ENV: &str = "OPEN_PROJECT_IGNORE_GLOBS";
+const OPEN_PROJECT_MARKERS_ENV: &str = "OPEN_PROJECT_MARKERS";
+const OPEN_PROJECT_TERMINAL_APP_ENV: &str = "OPEN_PROJECT_TERMINAL_APP";
+const OPEN_PROJECT_SHOW_GIT_STATUS_ENV: &str = "OPEN_PROJECT_SHOW_GIT_STATUS";
+const OPEN_PROJECT_WORKSPACE_MEMBERS_ENV: &str = "OPEN_PROJECT_WORKSPACE_MEMBERS";
+const OPEN_PROJECT_RG_PATH_ENV: &str = "OPEN_PROJECT_RG_PATH";
+const OPEN_PROJECT_REMOTE_PROJECTS_ENV: &str = "OPEN_PROJECT_REMOTE_PROJECTS";
+const OPEN_PROJECT_FORGE_HOSTS_ENV: &str = "OPEN_PROJECT_FORGE_HOSTS";
+const OPEN_PROJECT_CONFIG_FILE_ENV: &str = "OPEN_PROJECT_CONFIG_FILE";
+
+/// Settings read from the TOML config file (`DEFAULT_CONFIG_FILE`).
+///
+/// Every key is optional; a non-empty env var for the same setting wins. Unknown keys are
+/// ignored so newer files keep working with older binaries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct FileConfig {
+    pub project_dirs: Option<Vec<String>>,
+    pub vscode_path: Option<String>,
+    /// `Label=command` or bare `command` entries, bound to `alt` then `ctrl`.
+    pub editors: Option<Vec<String>>,
+    pub ignore_globs: Option<Vec<String>>,
+    pub max_results: Option<usize>,
+    /// Projects listed first, in this order (`workflow-cli pin` / `unpin`).
+    pub pins: Option<Vec<String>>,
+    /// `alias = "path"` shortcuts; typing an alias exactly jumps to its project (`workflow-cli alias`).
+    pub aliases: Option<BTreeMap<String, String>>,
+    /// Remote-SSH entries as `host:/absolute/path`.
+    pub remote_projects: Option<Vec<String>>,
+}
+
+impl FileConfig {
+    pub fn parse(raw: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(raw)
+    }
+
+    /// Missing or malformed files yield the empty config, leaving env vars and defaults.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| Self::parse(&raw).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Additional editor launcher bound to a modifier key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorTarget {
+    pub label: String,
+    pub command: String,
+}
+
+impl EditorTarget {
+    /// Parse `Label=command` or a bare `command` (label derived from the executable name).
+    pub fn parse(raw: &str, home: &str) -> Option<Self> {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        let (label, command) = match trimmed.split_once('=') {
+            Some((label, command)) if !label.contains('/') && !label.trim().is_empty() => {
+                (label.trim().to_string(), command)
+            }
+            _ => (String::new(), trimmed),
+        };
+
+        let command = expand_home_tokens(command, home);
+        if command.is_empty() {
+            return None;
+        }
+
+        let label = if label.is_empty() {
+            Path::new(&command)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| command.clone())
+        } else {
+            label
+        };
+
+        Some(Self { label, command })
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeConfig {
     pub project_roots: Vec<PathBuf>,
     pub usage_file: PathBuf,
     pub vscode_path: String,
+    /// Editor slots opened with `alt` (`OPEN_PROJECT_EDITOR_2`) and `ctrl` (`OPEN_PROJECT_EDITOR_3`) on a project
+    /// row. Slots are positional: an unset or invalid editor 2 leaves editor 3 on `ctrl`.
+    pub extra_editors: [Option<EditorTarget>; 2],
     pub max_results: usize,
+    /// Size of the empty-query "Recently used" section; `0` keeps one flat list.
+    pub recent_count: usize,
+    pub scan_workers: usize,
+    pub scan_depth: usize,
+    /// Raw ignore globs (`node_modules/**`, `.Trash`, ...) applied during discovery.
+    pub ignore_globs: Vec<String>,
+    /// Marker file names that surface non-git folders; empty means git-only discovery.
+    pub markers: Vec<String>,
+    /// Directory for the persistent scan cache; `None` disables caching.
+    pub cache_dir: Option<PathBuf>,
+    /// Terminal opened with `cmd` on a project row.
+    pub terminal_app: TerminalApp,
+    /// Prefix git subtitles with branch/dirty/ahead-behind state (probed per shown row).
+    pub show_git_status: bool,
+    /// Budget for each git status probe; a row whose probe overruns renders without the prefix.
+    pub git_status_timeout: Duration,
+    /// Also list monorepo workspace members (Cargo, npm/pnpm, `go.work`) as `repo › package` rows.
+    pub workspace_members: bool,
+    /// `rg` executable for the `grep` intent; `None` probes `PATH` and Homebrew locations.
+    pub rg_path: Option<String>,
+    /// Valid `host:/path` Remote-SSH specs, listed next to discovered projects.
+    pub remote_projects: Vec<String>,
+    /// Self-hosted forges (`host=gitlab|bitbucket|bitbucket-server|github|generic`).
+    pub forge_hosts: Vec<ForgeHost>,
+    /// Resolved TOML config file; `pin` / `alias` edits are written here.
+    pub config_file: PathBuf,
+    /// Pinned project paths, shown above ranked results in this order.
+    pub pins: Vec<PathBuf>,
+    /// Alias → project path; matched case-insensitively against the whole query.
+    pub aliases: BTreeMap<String, PathBuf>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            project_roots: Vec::new(),
+            usage_file: PathBuf::new(),
+            vscode_path: DEFAULT_VSCODE_PATH.to_string(),
+            extra_editors: [None, None],
+            max_results: DEFAULT_OPEN_PROJECT_MAX_RESULTS,
+            recent_count: DEFAULT_OPEN_PROJECT_RECENT_COUNT,
+            scan_workers: DEFAULT_OPEN_PROJECT_SCAN_WORKERS,
+            scan_depth: DEFAULT_OPEN_PROJECT_SCAN_DEPTH,
+            ignore_globs: Vec::new(),
+            markers: default_markers(),
+            cache_dir: None,
+            terminal_app: TerminalApp::default(),
+            show_git_status: false,
+            git_status_timeout: GIT_STATUS_TIMEOUT,
+            workspace_members: false,
+            rg_path: None,
+            remote_projects: Vec::new(),
+            forge_hosts: Vec::new(),
+            config_file: PathBuf::new(),
+            pins: Vec::new(),
+            aliases: BTreeMap::new(),
+        }
+    }
 }
 
 impl RuntimeConfig {
     pub fn from_env() -> Self {
-        let home = env::var("HOME").unwrap_or_default();
-        let project_dirs =
-            env::var(PROJECT_DIRS_ENV).unwrap_or_else(|_| DEFAULT_PROJECT_DIRS.to_string());
-        let usage_file =
-            env::var(USAGE_FILE_ENV).unwrap_or_else(|_| DEFAULT_USAGE_FILE.to_string());
-        let vscode_path =
-            env::var(VSCODE_PATH_ENV).unwrap_or_else(|_| DEFAULT_VSCODE_PATH.to_string());
-        let max_results = env::var(OPEN_PROJECT_MAX_RESULTS_ENV)
-            .unwrap_or_else(|_| DEFAULT_OPEN_PROJECT_MAX_RESULTS.to_string());
-
-        Self::from_values(
+        Self::from_lookup(|key| env::var(key).ok())
+    }
+
+    /// Resolve config from an env lookup, layered over the TOML config file.
+    ///
+    /// Precedence per setting: non-empty env var, then `OPEN_PROJECT_CONFIG_FILE` (default
+    /// `DEFAULT_CONFIG_FILE`), then the built-in default.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let home = lookup("HOME").unwrap_or_default();
+        let layered = |key: &str| lookup(key).filter(|value| !value.trim().is_empty());
+        let config_file = layered(OPEN_PROJECT_CONFIG_FILE_ENV)
+            .unwrap_or_else(|| DEFAULT_CONFIG_FILE.to_string());
+        let config_file = PathBuf::from(expand_home_tokens(&config_file, &home));
+        let file = FileConfig::load(&config_file);
+
+        let project_dirs = layered(PROJECT_DIRS_ENV)
+            .or_else(|| file.project_dirs.as_ref().map(|dirs| dirs.join(",")))
+            .unwrap_or_else(|| DEFAULT_PROJECT_DIRS.to_string());
+        let usage_file = lookup(USAGE_FILE_ENV).unwrap_or_else(|| DEFAULT_USAGE_FILE.to_string());
+        let vscode_path = layered(VSCODE_PATH_ENV)
+            .or_else(|| file.vscode_path.clone())
+            .unwrap_or_else(|| DEFAULT_VSCODE_PATH.to_string());
+        let max_results = layered(OPEN_PROJECT_MAX_RESULTS_ENV)
+            .or_else(|| file.max_results.map(|value| value.to_string()))
+            .unwrap_or_else(|| DEFAULT_OPEN_PROJECT_MAX_RESULTS.to_string());
+
+        let mut config = Self::from_values(
             &home,
             &project_dirs,
             &usage_file,
             &vscode_path,
             &max_results,
-        )
+        );
+        config.scan_workers = parse_scan_workers(lookup(OPEN_PROJECT_SCAN_WORKERS_ENV).as_deref());
+        let env_editors = [OPEN_PROJECT_EDITOR_2_ENV, OPEN_PROJECT_EDITOR_3_ENV].map(layered);
+        let editors = if env_editors.iter().all(Option::is_none) {
+            let file_editors = file.editors.clone().unwrap_or_default();
+            [0, 1].map(|slot| file_editors.get(slot).cloned())
+        } else {
+            env_editors
+        };
+        config.extra_editors =
+            editors.map(|raw| raw.and_then(|raw| EditorTarget::parse(&raw, &home)));
+        config.recent_count = parse_recent_count(lookup(OPEN_PROJECT_RECENT_COUNT_ENV).as_deref());
+        config.scan_depth = parse_scan_depth(lookup(OPEN_PROJECT_SCAN_DEPTH_ENV).as_deref());
+        config.ignore_globs = layered(OPEN_PROJECT_IGNORE_GLOBS_ENV)
+            .map(|raw| split_ordered_list(&raw))
+            .or_else(|| file.ignore_globs.clone())
+            .unwrap_or_default();
+        config.markers = parse_markers(lookup(OPEN_PROJECT_MARKERS_ENV).as_deref());
+        config.cache_dir = parse_cache_dir(lookup(ALFRED_WORKFLOW_CACHE_ENV).as_deref(), &home);
+        config.terminal_app = parse_terminal_app(lookup(OPEN_PROJECT_TERMINAL_APP_ENV).as_deref());
+        config.show_git_status = parse_toggle(lookup(OPEN_PROJECT_SHOW_GIT_STATUS_ENV).as_deref());
+        config.workspace_members =
+            parse_toggle(lookup(OPEN_PROJECT_WORKSPACE_MEMBERS_ENV).as_deref());
+        config.rg_path = lookup(OPEN_PROJECT_RG_PATH_ENV)
+            .map(|raw| expand_home_tokens(&raw, &home))
+            .filter(|path| !path.is_empty());
+        config.remote_projects = layered(OPEN_PROJECT_REMOTE_PROJECTS_ENV)
+            .map(|raw| split_ordered_list(&raw))
+            .or_else(|| file.remote_projects.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|spec| parse_remote_spec(spec).is_some())
+            .map(|spec| spec.trim().to_string())
+            .collect();
+        config.forge_hosts = lookup(OPEN_PROJECT_FORGE_HOSTS_ENV)
+            .map(|raw| parse_forge_hosts(&raw))
+            .unwrap_or_default();
+        config.pins = file
+            .pins
+            .unwrap_or_default()
+            .iter()
+            .map(|path| expand_home_tokens(path, &home))
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        config.aliases = file
+            .aliases
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(alias, path)| {
+                let alias = normalize_alias(&alias)?;
+                let path = expand_home_tokens(&path, &home);
+                (!path.is_empty()).then(|| (alias, PathBuf::from(path)))
+            })
+            .collect();
+        config.config_file = config_file;
+        config
+    }
+
+    pub fn alias_target(&self, query: &str) -> Option<&Path> {
+        normalize_alias(query)
+            .and_then(|alias| self.aliases.get(&alias))
+            .map(PathBuf::as_path)
+    }
+
+    pub fn is_pinned(&self, path: &Path) -> bool {
+        self.pins.iter().any(|pin| pin == path)
     }
 
     pub fn from_values(
@@ -64,8 +320,144 @@ impl RuntimeConfig {
             usage_file,
             vscode_path,
             max_results,
+            ..Self::default()
+        }
+    }
+
+    pub fn search_options(&self) -> SearchOptions {
+        SearchOptions {
+            rg_path: self.rg_path.clone(),
+            timeout: SEARCH_TIMEOUT,
+            max_results: self.max_results,
+            ignore_globs: self.ignore_globs.clone(),
         }
     }
+
+    pub fn scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            workers: self.scan_workers,
+            max_depth: self.scan_depth,
+            ignore_globs: parse_ignore_globs(&self.ignore_globs),
+            markers: self.markers.clone(),
+            workspace_members: self.workspace_members,
+        }
+    }
+}
+
+/// Aliases are single case-insensitive words; anything else yields `None`.
+pub fn normalize_alias(raw: &str) -> Option<String> {
+    let alias = raw.trim().to_lowercase();
+    (!alias.is_empty() && !alias.contains(char::is_whitespace)).then_some(alias)
+}
+
+/// Add `path` to the config file's `pins`; returns `false` when it was already pinned.
+pub fn pin_project(config_file: &Path, path: &Path) -> Result<bool, WorkflowError> {
+    let path = path.to_string_lossy().to_string();
+    edit_config_file(config_file, |table| {
+        let Some(pins) = array_entry(table, "pins") else {
+            return false;
+        };
+        if pins.iter().any(|pin| pin.as_str() == Some(path.as_str())) {
+            return false;
+        }
+        pins.push(toml::Value::String(path));
+        true
+    })
+}
+
+/// Remove `path` from the config file's `pins`; returns `false` when it was not pinned.
+pub fn unpin_project(config_file: &Path, path: &Path) -> Result<bool, WorkflowError> {
+    let path = path.to_string_lossy().to_string();
+    edit_config_file(config_file, |table| {
+        let Some(pins) = array_entry(table, "pins") else {
+            return false;
+        };
+        let before = pins.len();
+        pins.retain(|pin| pin.as_str() != Some(path.as_str()));
+        pins.len() != before
+    })
+}
+
+/// Point `alias` at `path`, or remove it when `path` is `None`; returns whether the file changed.
+///
+/// `alias` must already be normalized with [`normalize_alias`].
+pub fn set_project_alias(
+    config_file: &Path,
+    alias: &str,
+    path: Option<&Path>,
+) -> Result<bool, WorkflowError> {
+    edit_config_file(config_file, |table| {
+        let aliases = table
+            .entry("aliases")
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !aliases.is_table() {
+            *aliases = toml::Value::Table(toml::Table::new());
+        }
+        let Some(aliases) = aliases.as_table_mut() else {
+            return false;
+        };
+
+        match path {
+            Some(path) => {
+                let value = toml::Value::String(path.to_string_lossy().to_string());
+                aliases.insert(alias.to_string(), value.clone()) != Some(value)
+            }
+            None => aliases.remove(alias).is_some(),
+        }
+    })
+}
+
+/// `table[key]` as an array, replacing a missing or mistyped value with an empty one.
+fn array_entry<'a>(table: &'a mut toml::Table, key: &str) -> Option<&'a mut Vec<toml::Value>> {
+    let entry = table
+        .entry(key)
+        .or_insert_with(|| toml::Value::Array(Vec::new()));
+    if !entry.is_array() {
+        *entry = toml::Value::Array(Vec::new());
+    }
+    entry.as_array_mut()
+}
+
+/// Read-modify-write the config file as a TOML table; comments are not preserved.
+///
+/// A malformed file is reported instead of being overwritten. Unchanged edits skip the write.
+fn edit_config_file(
+    config_file: &Path,
+    edit: impl FnOnce(&mut toml::Table) -> bool,
+) -> Result<bool, WorkflowError> {
+    let mut table = match fs::read_to_string(config_file) {
+        Ok(raw) => raw
+            .parse::<toml::Table>()
+            .map_err(|error| WorkflowError::ConfigParse {
+                path: config_file.to_path_buf(),
+                message: error.message().to_string(),
+            })?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+        Err(source) => {
+            return Err(WorkflowError::ConfigWrite {
+                path: config_file.to_path_buf(),
+                source,
+            });
+        }
+    };
+
+    if !edit(&mut table) {
+        return Ok(false);
+    }
+
+    let write_error = |source| WorkflowError::ConfigWrite {
+        path: config_file.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = config_file.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let rendered = toml::to_string(&table)
+        .map_err(|error| write_error(std::io::Error::other(error.to_string())))?;
+    let temp_file = config_file.with_extension("toml.tmp");
+    fs::write(&temp_file, rendered).map_err(write_error)?;
+    fs::rename(&temp_file, config_file).map_err(write_error)?;
+    Ok(true)
 }
 
 fn parse_max_results(raw: &str) -> usize {
@@ -76,6 +468,57 @@ fn parse_max_results(raw: &str) -> usize {
         .unwrap_or(DEFAULT_OPEN_PROJECT_MAX_RESULTS)
 }
 
+fn parse_scan_workers(raw: Option<&str>) -> usize {
+    raw.map(str::trim)
+        .and_then(|value| value.parse::<usize>().ok())
+        .map(|value| value.clamp(OPEN_PROJECT_SCAN_WORKERS_MIN, OPEN_PROJECT_SCAN_WORKERS_MAX))
+        .unwrap_or(DEFAULT_OPEN_PROJECT_SCAN_WORKERS)
+}
+
+fn parse_recent_count(raw: Option<&str>) -> usize {
+    raw.map(str::trim)
+        .and_then(|value| value.parse::<usize>().ok())
+        .map(|value| value.min(OPEN_PROJECT_RECENT_COUNT_MAX))
+        .unwrap_or(DEFAULT_OPEN_PROJECT_RECENT_COUNT)
+}
+
+fn parse_scan_depth(raw: Option<&str>) -> usize {
+    raw.map(str::trim)
+        .and_then(|value| value.parse::<usize>().ok())
+        .map(|value| value.clamp(OPEN_PROJECT_SCAN_DEPTH_MIN, OPEN_PROJECT_SCAN_DEPTH_MAX))
+        .unwrap_or(DEFAULT_OPEN_PROJECT_SCAN_DEPTH)
+}
+
+fn default_markers() -> Vec<String> {
+    DEFAULT_PROJECT_MARKERS
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Unset keeps the default marker list; an explicitly empty value disables marker detection.
+fn parse_markers(raw: Option<&str>) -> Vec<String> {
+    match raw {
+        Some(raw) => split_ordered_list(raw),
+        None => default_markers(),
+    }
+}
+
+fn parse_terminal_app(raw: Option<&str>) -> TerminalApp {
+    raw.and_then(TerminalApp::parse).unwrap_or_default()
+}
+
+fn parse_toggle(raw: Option<&str>) -> bool {
+    raw.map(|value| value.trim().to_ascii_lowercase())
+        .is_some_and(|value| matches!(value.as_str(), "1" | "true" | "yes" | "on"))
+}
+
+fn parse_cache_dir(raw: Option<&str>, home: &str) -> Option<PathBuf> {
+    raw.map(|value| expand_home_tokens(value, home))
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
 pub fn parse_project_dirs(raw: &str, home: &str) -> Vec<PathBuf> {
     raw.split(',')
         .map(str::trim)
@@ -144,6 +587,22 @@ mod tests {
         assert_eq!(config.vscode_path, "/Users/tester/.local/bin/code");
     }
 
+    #[test]
+    fn editor_target_parses_labeled_and_bare_commands() {
+        let labeled = EditorTarget::parse("Cursor=~/bin/cursor", "/Users/tester")
+            .expect("labeled editor should parse");
+        assert_eq!(labeled.label, "Cursor");
+        assert_eq!(labeled.command, "/Users/tester/bin/cursor");
+
+        let bare = EditorTarget::parse("/usr/local/bin/zed", "/Users/tester")
+            .expect("bare editor should parse");
+        assert_eq!(bare.label, "zed");
+        assert_eq!(bare.command, "/usr/local/bin/zed");
+
+        assert_eq!(EditorTarget::parse("  ", "/Users/tester"), None);
+        assert_eq!(EditorTarget::parse("Zed=", "/Users/tester"), None);
+    }
+
     #[test]
     fn open_project_max_results_uses_default_and_clamps() {
         let default_config = RuntimeConfig::from_values(
@@ -174,6 +633,283 @@ mod tests {
         assert_eq!(clamped_high.max_results, OPEN_PROJECT_MAX_RESULTS_MAX);
     }
 
+    #[test]
+    fn open_project_scan_workers_uses_default_and_clamps() {
+        assert_eq!(parse_scan_workers(None), DEFAULT_OPEN_PROJECT_SCAN_WORKERS);
+        assert_eq!(
+            parse_scan_workers(Some("abc")),
+            DEFAULT_OPEN_PROJECT_SCAN_WORKERS
+        );
+        assert_eq!(parse_scan_workers(Some(" 8 ")), 8);
+        assert_eq!(parse_scan_workers(Some("0")), OPEN_PROJECT_SCAN_WORKERS_MIN);
+        assert_eq!(
+            parse_scan_workers(Some("500")),
+            OPEN_PROJECT_SCAN_WORKERS_MAX
+        );
+    }
+
+    #[test]
+    fn open_project_scan_depth_uses_default_and_clamps() {
+        assert_eq!(parse_scan_depth(None), DEFAULT_OPEN_PROJECT_SCAN_DEPTH);
+        assert_eq!(parse_scan_depth(Some("5")), 5);
+        assert_eq!(parse_scan_depth(Some("0")), OPEN_PROJECT_SCAN_DEPTH_MIN);
+        assert_eq!(parse_scan_depth(Some("99")), OPEN_PROJECT_SCAN_DEPTH_MAX);
+        assert_eq!(
+            parse_scan_depth(Some("deep")),
+            DEFAULT_OPEN_PROJECT_SCAN_DEPTH
+        );
+    }
+
+    #[test]
+    fn scan_options_carry_depth_and_parsed_ignore_globs() {
+        let config = RuntimeConfig {
+            scan_depth: 6,
+            ignore_globs: vec!["node_modules/**".to_string(), " ".to_string()],
+            ..RuntimeConfig::default()
+        };
+
+        let options = config.scan_options();
+        assert_eq!(options.max_depth, 6);
+        assert_eq!(options.ignore_globs.len(), 1, "blank globs are dropped");
+    }
+
+    #[test]
+    fn project_markers_default_and_can_be_disabled() {
+        assert_eq!(
+            parse_markers(None),
+            vec!["Cargo.toml", "package.json", "go.mod", ".project"]
+        );
+        assert_eq!(
+            parse_markers(Some("pyproject.toml, Makefile")),
+            vec!["pyproject.toml", "Makefile"]
+        );
+        assert!(parse_markers(Some("")).is_empty());
+    }
+
+    #[test]
+    fn terminal_app_falls_back_to_terminal() {
+        assert_eq!(parse_terminal_app(None), TerminalApp::Terminal);
+        assert_eq!(parse_terminal_app(Some("")), TerminalApp::Terminal);
+        assert_eq!(parse_terminal_app(Some("hyper")), TerminalApp::Terminal);
+        assert_eq!(parse_terminal_app(Some("kitty")), TerminalApp::Kitty);
+    }
+
+    #[test]
+    fn toggles_accept_common_truthy_values() {
+        assert!(!parse_toggle(None));
+        assert!(!parse_toggle(Some("")));
+        assert!(!parse_toggle(Some("0")));
+        assert!(!parse_toggle(Some("off")));
+        assert!(parse_toggle(Some("1")));
+        assert!(parse_toggle(Some(" TRUE ")));
+        assert!(parse_toggle(Some("yes")));
+    }
+
+    #[test]
+    fn cache_dir_is_disabled_when_unset_or_blank() {
+        assert_eq!(parse_cache_dir(None, "/Users/tester"), None);
+        assert_eq!(parse_cache_dir(Some("  "), "/Users/tester"), None);
+        assert_eq!(
+            parse_cache_dir(Some("~/Library/Caches/open-project"), "/Users/tester"),
+            Some(PathBuf::from("/Users/tester/Library/Caches/open-project"))
+        );
+    }
+
+    fn lookup_from<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| {
+            vars.iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn config_file_supplies_values_when_env_is_unset() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let config_file = temp.path().join("open-project.toml");
+        fs::write(
+            &config_file,
+            r#"
+project_dirs = ["~/Work", "$HOME/oss"]
+vscode_path = "~/bin/code"
+editors = ["Cursor=cursor", "zed"]
+ignore_globs = ["node_modules/**"]
+max_results = 12
+future_key = true
+"#,
+        )
+        .expect("write config file");
+        let config_path = config_file.to_string_lossy().to_string();
+
+        let config = RuntimeConfig::from_lookup(lookup_from(&[
+            ("HOME", "/Users/tester"),
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+            ("PROJECT_DIRS", ""),
+        ]));
+        assert_eq!(
+            config.project_roots,
+            vec![
+                PathBuf::from("/Users/tester/Work"),
+                PathBuf::from("/Users/tester/oss")
+            ]
+        );
+        assert_eq!(config.vscode_path, "/Users/tester/bin/code");
+        let labels: Vec<Option<&str>> = config
+            .extra_editors
+            .iter()
+            .map(|editor| editor.as_ref().map(|editor| editor.label.as_str()))
+            .collect();
+        assert_eq!(labels, vec![Some("Cursor"), Some("zed")]);
+        assert_eq!(config.ignore_globs, vec!["node_modules/**"]);
+        assert_eq!(config.max_results, 12);
+    }
+
+    #[test]
+    fn env_vars_take_precedence_over_config_file() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let config_file = temp.path().join("open-project.toml");
+        fs::write(
+            &config_file,
+            "project_dirs = [\"/from/file\"]\neditors = [\"zed\"]\nmax_results = 12\n",
+        )
+        .expect("write config file");
+        let config_path = config_file.to_string_lossy().to_string();
+
+        let config = RuntimeConfig::from_lookup(lookup_from(&[
+            ("HOME", "/Users/tester"),
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+            ("PROJECT_DIRS", "/from/env"),
+            ("OPEN_PROJECT_EDITOR_3", "Nova=nova"),
+            ("OPEN_PROJECT_MAX_RESULTS", "40"),
+        ]));
+        assert_eq!(config.project_roots, vec![PathBuf::from("/from/env")]);
+        assert_eq!(
+            config.extra_editors[1]
+                .as_ref()
+                .map(|editor| editor.label.as_str()),
+            Some("Nova")
+        );
+        assert!(
+            config.extra_editors[0].is_none(),
+            "editor 3 should stay on ctrl without editor 2"
+        );
+        assert_eq!(config.max_results, 40);
+    }
+
+    #[test]
+    fn remote_projects_layer_env_over_file_and_drop_invalid_specs() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let config_file = temp.path().join("open-project.toml");
+        fs::write(
+            &config_file,
+            "remote_projects = [\"devbox:/srv/api\", \"not-a-spec\"]\n",
+        )
+        .expect("write config file");
+        let config_path = config_file.to_string_lossy().to_string();
+
+        let from_file = RuntimeConfig::from_lookup(lookup_from(&[
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+            ("OPEN_PROJECT_REMOTE_PROJECTS", ""),
+        ]));
+        assert_eq!(from_file.remote_projects, vec!["devbox:/srv/api"]);
+
+        let from_env = RuntimeConfig::from_lookup(lookup_from(&[
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+            (
+                "OPEN_PROJECT_REMOTE_PROJECTS",
+                "me@gpu:/home/me/train, ~/local, gpu:/opt/svc",
+            ),
+        ]));
+        assert_eq!(
+            from_env.remote_projects,
+            vec!["me@gpu:/home/me/train", "gpu:/opt/svc"]
+        );
+    }
+
+    #[test]
+    fn malformed_or_missing_config_file_falls_back_to_defaults() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let config_file = temp.path().join("open-project.toml");
+        fs::write(&config_file, "project_dirs = [unterminated").expect("write config file");
+
+        assert!(FileConfig::parse("project_dirs = [unterminated").is_err());
+        assert_eq!(FileConfig::load(&config_file), FileConfig::default());
+        assert_eq!(
+            FileConfig::load(&temp.path().join("missing.toml")),
+            FileConfig::default()
+        );
+
+        let config_path = config_file.to_string_lossy().to_string();
+        let config = RuntimeConfig::from_lookup(lookup_from(&[
+            ("HOME", "/Users/tester"),
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+        ]));
+        assert_eq!(
+            config.project_roots,
+            parse_project_dirs(DEFAULT_PROJECT_DIRS, "/Users/tester")
+        );
+        assert_eq!(config.max_results, DEFAULT_OPEN_PROJECT_MAX_RESULTS);
+    }
+
+    #[test]
+    fn pins_and_aliases_round_trip_through_config_file() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let config_file = temp.path().join("nils/open-project.toml");
+        fs::create_dir_all(temp.path().join("nils")).expect("create config dir");
+        fs::write(&config_file, "max_results = 12\n").expect("seed config file");
+
+        let alpha = Path::new("/Users/tester/Project/alpha");
+        let beta = Path::new("/Users/tester/Project/beta");
+        assert!(pin_project(&config_file, alpha).expect("pin alpha"));
+        assert!(pin_project(&config_file, beta).expect("pin beta"));
+        assert!(!pin_project(&config_file, alpha).expect("pin alpha twice"));
+        assert!(unpin_project(&config_file, beta).expect("unpin beta"));
+        assert!(!unpin_project(&config_file, beta).expect("unpin beta twice"));
+        assert!(set_project_alias(&config_file, "wk", Some(beta)).expect("set alias"));
+        assert!(!set_project_alias(&config_file, "wk", Some(beta)).expect("same alias"));
+
+        let config_path = config_file.to_string_lossy().to_string();
+        let config = RuntimeConfig::from_lookup(lookup_from(&[
+            ("HOME", "/Users/tester"),
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+        ]));
+        assert_eq!(config.config_file, config_file);
+        assert_eq!(config.max_results, 12, "existing keys survive edits");
+        assert_eq!(config.pins, vec![alpha.to_path_buf()]);
+        assert!(config.is_pinned(alpha));
+        assert_eq!(config.alias_target(" WK "), Some(beta));
+        assert_eq!(config.alias_target("w"), None);
+
+        assert!(set_project_alias(&config_file, "wk", None).expect("remove alias"));
+        let config = RuntimeConfig::from_lookup(lookup_from(&[
+            ("HOME", "/Users/tester"),
+            ("OPEN_PROJECT_CONFIG_FILE", &config_path),
+        ]));
+        assert!(config.aliases.is_empty());
+    }
+
+    #[test]
+    fn config_edits_refuse_to_overwrite_malformed_file() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let config_file = temp.path().join("open-project.toml");
+        fs::write(&config_file, "pins = [unterminated").expect("write config file");
+
+        let err = pin_project(&config_file, Path::new("/tmp/alpha"))
+            .expect_err("malformed file should not be rewritten");
+        assert!(matches!(err, WorkflowError::ConfigParse { .. }));
+        assert_eq!(
+            fs::read_to_string(&config_file).expect("read config file"),
+            "pins = [unterminated"
+        );
+    }
+
+    #[test]
+    fn alias_names_are_single_lowercase_words() {
+        assert_eq!(normalize_alias(" Work "), Some("work".to_string()));
+        assert_eq!(normalize_alias("two words"), None);
+        assert_eq!(normalize_alias("  "), None);
+    }
+
     #[test]
     fn output_mode_defaults_to_alfred_json() {
         assert_eq!(parse_output_mode_env(None), OutputMode::AlfredJson);
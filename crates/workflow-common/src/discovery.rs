@@ -1,64 +1,530 @@
-use std::collections::BTreeMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
 
+use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+use crate::glob::IgnoreGlob;
+use crate::workspace::{WorkspaceKind, workspace_members};
+
 pub const MAX_SCAN_DEPTH: usize = 3;
+pub const DEFAULT_SCAN_WORKERS: usize = 4;
+pub const DEFAULT_PROJECT_MARKERS: &[&str] = &["Cargo.toml", "package.json", "go.mod", ".project"];
+/// Extension of VS Code multi-root workspace files, reported as [`ProjectKind::CodeWorkspace`].
+pub const CODE_WORKSPACE_EXTENSION: &str = "code-workspace";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectKind {
+    /// Directory with a `.git` directory.
+    #[default]
+    Git,
+    /// Linked worktree (`.git` file pointing at `<primary>/.git/worktrees/<name>`).
+    Worktree {
+        primary: PathBuf,
+        branch: Option<String>,
+    },
+    /// Plain folder recognized by a marker file such as `Cargo.toml`.
+    Marker { marker: String },
+    /// Workspace member folder below `repo` (only with `ScanOptions::workspace_members`).
+    Member {
+        repo: PathBuf,
+        workspace: WorkspaceKind,
+    },
+    /// Configured `host:/path` entry opened over VS Code Remote-SSH; never scanned or probed.
+    Remote { host: String, remote_path: String },
+    /// `*.code-workspace` file; the row path is the file itself, opened as a multi-root workspace.
+    CodeWorkspace,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Project {
     pub name: String,
     pub path: PathBuf,
+    pub kind: ProjectKind,
 }
 
 impl Project {
     pub fn new(path: PathBuf) -> Option<Self> {
+        Self::with_kind(path, ProjectKind::Git)
+    }
+
+    pub fn with_kind(path: PathBuf, kind: ProjectKind) -> Option<Self> {
         let name = path.file_name()?.to_string_lossy().trim().to_string();
         if name.is_empty() {
             return None;
         }
 
-        Some(Self { name, path })
+        Some(Self { name, path, kind })
+    }
+
+    /// VS Code workspace file row, named after the file stem (`acme.code-workspace` → `acme`).
+    pub fn code_workspace(path: PathBuf) -> Option<Self> {
+        let name = path.file_stem()?.to_string_lossy().trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            name,
+            path,
+            kind: ProjectKind::CodeWorkspace,
+        })
+    }
+
+    /// Remote-SSH entry from a `host:/absolute/path` spec (`user@host:/path` works too).
+    ///
+    /// The spec itself becomes the row path (and usage-log key); the name is `host:<dir>`.
+    pub fn remote(spec: &str) -> Option<Self> {
+        let (host, remote_path) = parse_remote_spec(spec)?;
+        let dir = remote_path
+            .rsplit('/')
+            .find(|component| !component.is_empty())
+            .unwrap_or("/");
+
+        Some(Self {
+            name: format!("{host}:{dir}"),
+            path: PathBuf::from(format!("{host}:{remote_path}")),
+            kind: ProjectKind::Remote { host, remote_path },
+        })
+    }
+
+    /// `vscode-remote://ssh-remote+<host><path>` for remote entries.
+    pub fn remote_uri(&self) -> Option<String> {
+        match &self.kind {
+            ProjectKind::Remote { host, remote_path } => Some(vscode_remote_uri(host, remote_path)),
+            _ => None,
+        }
+    }
+
+    /// Git repositories, including linked worktrees.
+    pub fn is_git(&self) -> bool {
+        matches!(self.kind, ProjectKind::Git | ProjectKind::Worktree { .. })
+    }
+
+    /// Folder a terminal should open in: the project itself, or a workspace file's directory.
+    pub fn working_dir(&self) -> &Path {
+        match self.kind {
+            ProjectKind::CodeWorkspace => self.path.parent().unwrap_or(&self.path),
+            _ => &self.path,
+        }
+    }
+}
+
+/// `path` names a `*.code-workspace` file (by extension; existence is not checked).
+pub fn is_code_workspace_file(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new(CODE_WORKSPACE_EXTENSION))
+}
+
+/// Knobs that shape a discovery pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanOptions {
+    /// Upper bound on concurrent walker threads; clamped to at least one.
+    pub workers: usize,
+    /// Deepest repo root to report, counted from the scan root (root itself = 0).
+    pub max_depth: usize,
+    /// Directories matching any glob are pruned together with their subtree.
+    pub ignore_globs: Vec<IgnoreGlob>,
+    /// File names that mark a non-git folder as a project; empty disables marker detection.
+    pub markers: Vec<String>,
+    /// Expand each discovered project into its workspace members (see [`crate::workspace`]).
+    pub workspace_members: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            workers: DEFAULT_SCAN_WORKERS,
+            max_depth: MAX_SCAN_DEPTH,
+            ignore_globs: Vec::new(),
+            markers: Vec::new(),
+            workspace_members: false,
+        }
     }
 }
 
+impl ScanOptions {
+    fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        if self.ignore_globs.is_empty() {
+            return false;
+        }
+
+        let Ok(relative) = path.strip_prefix(root) else {
+            return false;
+        };
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        !relative.is_empty() && self.ignore_globs.iter().any(|glob| glob.matches(&relative))
+    }
+}
+
+/// One independently walkable slice of a root.
+///
+/// Each root is split into a shallow unit for the root itself plus one unit per top-level
+/// child directory, so a single large root still fans out across workers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScanUnit {
+    root: PathBuf,
+    path: PathBuf,
+    max_depth: usize,
+    /// The root itself is a project, so marker-only folders below it are not reported.
+    inside_project: bool,
+}
+
 pub fn discover_projects(roots: &[PathBuf]) -> Vec<Project> {
-    let mut projects = BTreeMap::<String, Project>::new();
+    discover_projects_with(roots, &ScanOptions::default())
+}
+
+pub fn discover_projects_with(roots: &[PathBuf], options: &ScanOptions) -> Vec<Project> {
+    let units = scan_units(roots, options);
+    if units.is_empty() {
+        return Vec::new();
+    }
+
+    let workers = options.workers.clamp(1, units.len());
+    let queue = Mutex::new(units.into_iter());
+
+    let found = thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut local = Vec::new();
+                    loop {
+                        let next = match queue.lock() {
+                            Ok(mut pending) => pending.next(),
+                            Err(_) => None,
+                        };
+                        let Some(unit) = next else {
+                            break;
+                        };
+                        scan_unit(&unit, options, &mut local);
+                    }
+                    local
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect::<Vec<_>>()
+    });
+
+    let projects = dedup_by_canonical_path(found, roots);
+    if options.workspace_members {
+        with_workspace_members(projects)
+    } else {
+        projects
+    }
+}
+
+/// Append `repo › package` rows for workspace members of every discovered project.
+///
+/// Members that are projects in their own right (nested repos, marker folders) keep their
+/// existing row. Output stays sorted by path.
+fn with_workspace_members(projects: Vec<Project>) -> Vec<Project> {
+    let known = projects
+        .iter()
+        .map(|project| normalize_path_key(&project.path))
+        .collect::<HashSet<_>>();
+    let members = projects
+        .iter()
+        .filter(|project| project.kind != ProjectKind::CodeWorkspace)
+        .flat_map(|project| {
+            workspace_members(&project.path)
+                .into_iter()
+                .filter(|(path, _)| !known.contains(&normalize_path_key(path)))
+                .filter_map(|(path, workspace)| member_project(project, path, workspace))
+        })
+        .collect::<Vec<_>>();
+
+    let mut projects = projects;
+    projects.extend(members);
+    projects.sort_by_key(|project| normalize_path_key(&project.path));
+    projects.dedup_by(|left, right| left.path == right.path);
+    projects
+}
+
+fn member_project(repo: &Project, path: PathBuf, workspace: WorkspaceKind) -> Option<Project> {
+    let package = path.file_name()?.to_string_lossy().trim().to_string();
+    if package.is_empty() {
+        return None;
+    }
+
+    Some(Project {
+        name: format!("{} › {package}", repo.name),
+        path,
+        kind: ProjectKind::Member {
+            repo: repo.path.clone(),
+            workspace,
+        },
+    })
+}
+
+/// Collapse projects reachable through several paths (overlapping roots, symlinks).
+///
+/// The kept display path is the one under the earliest root in `roots`, so a symlinked root
+/// keeps the paths the usage log already knows; ties prefer a path with no symlink below its
+/// root, then the shorter one. Output is sorted by display path.
+fn dedup_by_canonical_path(found: Vec<Project>, roots: &[PathBuf]) -> Vec<Project> {
+    let canonical_roots = roots
+        .iter()
+        .map(|root| fs::canonicalize(root).ok())
+        .collect::<Vec<_>>();
+    let display_rank = |path: &Path, canonical: &Path| {
+        let root_index = roots
+            .iter()
+            .position(|root| path.starts_with(root))
+            .unwrap_or(roots.len());
+        let via_symlink = roots
+            .get(root_index)
+            .zip(canonical_roots.get(root_index).and_then(Option::as_ref))
+            .and_then(|(root, canonical_root)| {
+                path.strip_prefix(root)
+                    .ok()
+                    .map(|relative| canonical_root.join(relative) != canonical)
+            })
+            .unwrap_or(path != canonical);
+        (
+            root_index,
+            via_symlink,
+            path.as_os_str().len(),
+            normalize_path_key(path),
+        )
+    };
+
+    let mut by_canonical = HashMap::<PathBuf, Project>::new();
+    for project in found {
+        let canonical = fs::canonicalize(&project.path).unwrap_or_else(|_| project.path.clone());
+        match by_canonical.entry(canonical) {
+            Entry::Vacant(slot) => {
+                slot.insert(project);
+            }
+            Entry::Occupied(mut slot) => {
+                if display_rank(&project.path, slot.key())
+                    < display_rank(&slot.get().path, slot.key())
+                {
+                    slot.insert(project);
+                }
+            }
+        }
+    }
+
+    let mut projects = by_canonical.into_values().collect::<Vec<_>>();
+    projects.sort_by_key(|project| normalize_path_key(&project.path));
+    projects
+}
+
+fn scan_units(roots: &[PathBuf], options: &ScanOptions) -> Vec<ScanUnit> {
+    let mut units = Vec::new();
 
     for root in roots {
         if !root.is_dir() {
             continue;
         }
 
-        // Git repo root depth = 3 means `.git` appears at depth 4 from base root.
-        let walker = WalkDir::new(root)
-            .follow_links(true)
-            .max_depth(MAX_SCAN_DEPTH + 1)
-            .into_iter();
+        // The root itself is a candidate at depth 0.
+        units.push(ScanUnit {
+            root: root.clone(),
+            path: root.clone(),
+            max_depth: 0,
+            inside_project: false,
+        });
+
+        if options.max_depth == 0 {
+            continue;
+        }
 
-        for entry in walker {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(_) => continue,
-            };
+        let Ok(children) = fs::read_dir(root) else {
+            continue;
+        };
+        let inside_project = classify_dir(root, options, false).is_some();
 
-            if !entry.file_type().is_dir() || entry.file_name() != OsStr::new(".git") {
+        for child in children.flatten() {
+            let path = child.path();
+            // Workspace files directly in the root sit at depth 1; the root unit stops at 0.
+            if is_code_workspace_file(&path) && path.is_file() && !options.is_ignored(root, &path) {
+                units.push(ScanUnit {
+                    root: root.clone(),
+                    path,
+                    max_depth: 0,
+                    inside_project,
+                });
                 continue;
             }
-
-            let Some(project_path) = entry.path().parent() else {
+            if child.file_name() == OsStr::new(".git")
+                || !path.is_dir()
+                || options.is_ignored(root, &path)
+            {
                 continue;
-            };
+            }
+
+            // A top-level child sits at depth 1, so it may descend `max_depth - 1` more levels.
+            units.push(ScanUnit {
+                root: root.clone(),
+                path,
+                max_depth: options.max_depth - 1,
+                inside_project,
+            });
+        }
+    }
+
+    units
+}
+
+fn scan_unit(unit: &ScanUnit, options: &ScanOptions, found: &mut Vec<Project>) {
+    // Cycle guard: walkdir rejects links back to an ancestor; this also stops two links
+    // into the same directory from walking it twice.
+    let mut visited_links = HashSet::<PathBuf>::new();
+    let walker = WalkDir::new(&unit.path)
+        .follow_links(true)
+        .max_depth(unit.max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.file_name() == OsStr::new(".git")
+                || (entry.depth() > 0 && options.is_ignored(&unit.root, entry.path()))
+            {
+                return false;
+            }
+            if !entry.path_is_symlink() {
+                return true;
+            }
+            fs::canonicalize(entry.path()).is_ok_and(|target| visited_links.insert(target))
+        });
 
-            if let Some(project) = Project::new(project_path.to_path_buf()) {
-                let key = normalize_path_key(&project.path);
-                projects.insert(key, project);
+    // Ancestor chain of already-claimed project dirs along the current DFS path.
+    let mut claimed: Vec<PathBuf> = Vec::new();
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+
+        if !entry.file_type().is_dir() {
+            // Workspace files are reported even inside another project.
+            if is_code_workspace_file(entry.path())
+                && let Some(project) = Project::code_workspace(entry.path().to_path_buf())
+            {
+                found.push(project);
             }
+            continue;
+        }
+
+        let path = entry.path();
+        while claimed.last().is_some_and(|last| !path.starts_with(last)) {
+            claimed.pop();
+        }
+
+        let inside_project = unit.inside_project || !claimed.is_empty();
+        let Some(kind) = classify_dir(path, options, inside_project) else {
+            continue;
+        };
+
+        claimed.push(path.to_path_buf());
+        if let Some(project) = Project::with_kind(path.to_path_buf(), kind) {
+            found.push(project);
         }
     }
+}
+
+/// Decide whether `path` is a project root.
+///
+/// Git repos are always reported (nested repos included). Marker-only folders are reported
+/// only when they are not already inside another project, so a repo's `package.json`
+/// sub-folders do not flood the results.
+fn classify_dir(path: &Path, options: &ScanOptions, inside_project: bool) -> Option<ProjectKind> {
+    let dot_git = path.join(".git");
+    if dot_git.is_dir() {
+        return Some(ProjectKind::Git);
+    }
+    if dot_git.is_file() {
+        return classify_git_file(path, &dot_git);
+    }
+
+    if inside_project {
+        return None;
+    }
+
+    options
+        .markers
+        .iter()
+        .find(|marker| path.join(marker).exists())
+        .map(|marker| ProjectKind::Marker {
+            marker: marker.clone(),
+        })
+}
+
+/// Resolve a `.git` file (`gitdir: <dir>`).
+///
+/// A gitdir with a `commondir` file belongs to a linked worktree whose primary checkout owns
+/// the common dir; anything else (e.g. a submodule under `.git/modules/`) is a regular repo.
+fn classify_git_file(path: &Path, dot_git: &Path) -> Option<ProjectKind> {
+    let contents = fs::read_to_string(dot_git).ok()?;
+    let gitdir = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())?;
+    let gitdir = path.join(gitdir);
+
+    let Ok(commondir) = fs::read_to_string(gitdir.join("commondir")) else {
+        return Some(ProjectKind::Git);
+    };
+    let common = fs::canonicalize(gitdir.join(commondir.trim())).ok()?;
+    let primary = if common.file_name() == Some(OsStr::new(".git")) {
+        common.parent()?.to_path_buf()
+    } else {
+        // Bare primary repository: the common dir is the repository itself.
+        common
+    };
 
-    projects.into_values().collect()
+    let branch = fs::read_to_string(gitdir.join("HEAD"))
+        .ok()
+        .and_then(|head| {
+            head.trim()
+                .strip_prefix("ref: refs/heads/")
+                .map(ToString::to_string)
+        });
+
+    Some(ProjectKind::Worktree { primary, branch })
+}
+
+/// Split `host:/path` into host and absolute path (trailing `/` trimmed).
+///
+/// Local paths (`/...`, `~/...`) and specs without an absolute remote path are rejected.
+pub fn parse_remote_spec(raw: &str) -> Option<(String, String)> {
+    let (host, path) = raw.trim().split_once(':')?;
+    if host.is_empty() || host.contains(['/', ' ']) || !path.starts_with('/') {
+        return None;
+    }
+
+    let path = match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    };
+    Some((host.to_string(), path.to_string()))
+}
+
+fn vscode_remote_uri(host: &str, remote_path: &str) -> String {
+    let mut uri = format!("vscode-remote://ssh-remote+{host}");
+    for byte in remote_path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    uri
 }
 
 pub fn filter_projects(projects: &[Project], query: &str) -> Vec<Project> {
@@ -131,14 +597,8 @@ mod tests {
     #[test]
     fn query_filter_handles_empty_and_non_empty_queries() {
         let projects = vec![
-            Project {
-                name: "alpha-api".to_string(),
-                path: PathBuf::from("/tmp/alpha-api"),
-            },
-            Project {
-                name: "beta-service".to_string(),
-                path: PathBuf::from("/tmp/beta-service"),
-            },
+            Project::new(PathBuf::from("/tmp/alpha-api")).expect("valid project"),
+            Project::new(PathBuf::from("/tmp/beta-service")).expect("valid project"),
         ];
 
         let all = filter_projects(&projects, "   ");
@@ -153,6 +613,415 @@ mod tests {
         assert_eq!(filtered[0].name, "alpha-api");
     }
 
+    #[test]
+    fn project_scan_honors_depth_limit_for_parallel_units() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("a/b/c/.git")).expect("create depth-3 repo");
+        fs::create_dir_all(root.join("a/b/c/d/.git")).expect("create depth-4 repo");
+        fs::create_dir_all(root.join(".git")).expect("create root repo");
+
+        let projects = discover_projects_with(
+            std::slice::from_ref(&root),
+            &ScanOptions {
+                workers: 3,
+                ..ScanOptions::default()
+            },
+        );
+        let paths: Vec<&Path> = projects
+            .iter()
+            .map(|project| project.path.as_path())
+            .collect();
+
+        assert!(paths.contains(&root.as_path()), "root repo should be found");
+        assert!(
+            paths.contains(&root.join("a/b/c").as_path()),
+            "repo at max depth should be found"
+        );
+        assert!(
+            !paths.contains(&root.join("a/b/c/d").as_path()),
+            "repo deeper than max depth should be skipped"
+        );
+    }
+
+    #[test]
+    fn project_scan_is_identical_across_worker_counts() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        for group in 0..4 {
+            for repo in 0..5 {
+                fs::create_dir_all(root.join(format!("group-{group}/repo-{repo}/.git")))
+                    .expect("create synthetic repo");
+            }
+        }
+
+        let serial = discover_projects_with(
+            std::slice::from_ref(&root),
+            &ScanOptions {
+                workers: 1,
+                ..ScanOptions::default()
+            },
+        );
+        let parallel = discover_projects_with(
+            &[root],
+            &ScanOptions {
+                workers: 8,
+                ..ScanOptions::default()
+            },
+        );
+
+        assert_eq!(serial.len(), 20);
+        assert_eq!(serial, parallel, "worker count must not change results");
+    }
+
+    #[test]
+    fn project_scan_respects_configured_depth() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("a/.git")).expect("create depth-1 repo");
+        fs::create_dir_all(root.join("a/b/c/d/e/.git")).expect("create depth-5 repo");
+
+        let shallow = discover_projects_with(
+            std::slice::from_ref(&root),
+            &ScanOptions {
+                max_depth: 1,
+                ..ScanOptions::default()
+            },
+        );
+        assert_eq!(
+            shallow.len(),
+            1,
+            "depth 1 should only report top-level repos"
+        );
+
+        let deep = discover_projects_with(
+            std::slice::from_ref(&root),
+            &ScanOptions {
+                max_depth: 5,
+                ..ScanOptions::default()
+            },
+        );
+        assert_eq!(deep.len(), 2, "depth 5 should reach the nested repo");
+    }
+
+    #[test]
+    fn project_scan_prunes_ignored_directories() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("app/.git")).expect("create app repo");
+        fs::create_dir_all(root.join("app/node_modules/dep/.git")).expect("create dep repo");
+        fs::create_dir_all(root.join(".Trash/old/.git")).expect("create trashed repo");
+        fs::create_dir_all(root.join("vendor/lib/.git")).expect("create vendored repo");
+
+        let options = ScanOptions {
+            ignore_globs: crate::glob::parse_ignore_globs(&[
+                "node_modules/**",
+                "vendor/**",
+                ".Trash",
+            ]),
+            ..ScanOptions::default()
+        };
+        let projects = discover_projects_with(&[root], &options);
+        let names: Vec<&str> = projects
+            .iter()
+            .map(|project| project.name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["app"], "ignored subtrees should be pruned");
+    }
+
+    #[test]
+    fn project_scan_reports_marker_folders_outside_git_repos() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("repo/.git")).expect("create git repo");
+        fs::create_dir_all(root.join("repo/packages/web")).expect("create repo package");
+        fs::write(root.join("repo/packages/web/package.json"), "{}").expect("write marker");
+        fs::create_dir_all(root.join("scratch/tool")).expect("create plain folder");
+        fs::write(root.join("scratch/tool/Cargo.toml"), "").expect("write marker");
+        fs::create_dir_all(root.join("scratch/tool/sub")).expect("create nested folder");
+        fs::write(root.join("scratch/tool/sub/go.mod"), "").expect("write nested marker");
+
+        let options = ScanOptions {
+            markers: DEFAULT_PROJECT_MARKERS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            ..ScanOptions::default()
+        };
+        let projects = discover_projects_with(&[root], &options);
+        let summary: Vec<(&str, &ProjectKind)> = projects
+            .iter()
+            .map(|project| (project.name.as_str(), &project.kind))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("repo", &ProjectKind::Git),
+                (
+                    "tool",
+                    &ProjectKind::Marker {
+                        marker: "Cargo.toml".to_string()
+                    }
+                ),
+            ],
+            "marker folders inside other projects should not be reported"
+        );
+    }
+
+    #[test]
+    fn workspace_member_mode_adds_member_rows_below_repos() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let repo = root.join("mono");
+        fs::create_dir_all(repo.join(".git")).expect("create git repo");
+        fs::write(
+            repo.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .expect("write workspace manifest");
+        fs::create_dir_all(repo.join("crates/core")).expect("create member");
+        fs::write(repo.join("crates/core/Cargo.toml"), "[package]").expect("write member");
+
+        let projects = discover_projects_with(std::slice::from_ref(&root), &ScanOptions::default());
+        assert_eq!(projects.len(), 1, "member mode is opt-in");
+
+        let options = ScanOptions {
+            workspace_members: true,
+            ..ScanOptions::default()
+        };
+        let projects = discover_projects_with(&[root], &options);
+        let summary: Vec<(&str, &ProjectKind)> = projects
+            .iter()
+            .map(|project| (project.name.as_str(), &project.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("mono", &ProjectKind::Git),
+                (
+                    "mono › core",
+                    &ProjectKind::Member {
+                        repo: repo.clone(),
+                        workspace: WorkspaceKind::Cargo,
+                    }
+                ),
+            ]
+        );
+        assert_eq!(projects[1].path, repo.join("crates/core"));
+    }
+
+    #[test]
+    fn remote_specs_build_remote_projects_with_vscode_uri() {
+        let project = Project::remote(" dev@box:/srv/my app/ ").expect("valid remote spec");
+        assert_eq!(project.name, "dev@box:my app");
+        assert_eq!(project.path, PathBuf::from("dev@box:/srv/my app"));
+        assert_eq!(
+            project.remote_uri().as_deref(),
+            Some("vscode-remote://ssh-remote+dev@box/srv/my%20app")
+        );
+        assert!(!project.is_git());
+
+        assert_eq!(
+            parse_remote_spec("box:/"),
+            Some(("box".to_string(), "/".to_string()))
+        );
+        assert_eq!(parse_remote_spec("/Users/me/repo"), None);
+        assert_eq!(parse_remote_spec("box:relative/path"), None);
+        assert_eq!(parse_remote_spec(":/srv"), None);
+    }
+
+    #[test]
+    fn project_scan_skips_markers_when_disabled() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("tool")).expect("create plain folder");
+        fs::write(root.join("tool/Cargo.toml"), "").expect("write marker");
+
+        let projects = discover_projects_with(&[root], &ScanOptions::default());
+        assert!(
+            projects.is_empty(),
+            "no markers configured means git-only scan"
+        );
+    }
+
+    #[test]
+    fn project_scan_of_2k_repo_tree_stays_within_latency_budget() {
+        const REPO_GROUPS: usize = 40;
+        const REPOS_PER_GROUP: usize = 50;
+        const LATENCY_BUDGET: std::time::Duration = std::time::Duration::from_secs(5);
+
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        for group in 0..REPO_GROUPS {
+            for repo in 0..REPOS_PER_GROUP {
+                let repo_dir = root.join(format!("group-{group}/nested/repo-{repo}"));
+                fs::create_dir_all(repo_dir.join(".git/objects")).expect("create synthetic repo");
+                fs::create_dir_all(repo_dir.join("src")).expect("create synthetic sources");
+            }
+        }
+
+        let started = std::time::Instant::now();
+        let projects = discover_projects_with(&[root], &ScanOptions::default());
+        let elapsed = started.elapsed();
+
+        assert_eq!(projects.len(), REPO_GROUPS * REPOS_PER_GROUP);
+        assert!(
+            elapsed < LATENCY_BUDGET,
+            "scan of synthetic tree took {elapsed:?}, budget {LATENCY_BUDGET:?}"
+        );
+    }
+
+    #[test]
+    fn linked_worktrees_reference_primary_repo_and_branch() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let primary = root.join("alpha");
+        init_git_repo(&primary);
+        git(&primary, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        let worktree = root.join("alpha-feature");
+        git(
+            &primary,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                "feature/login",
+                worktree.to_str().expect("utf-8 path"),
+            ],
+        );
+
+        let projects = discover_projects(std::slice::from_ref(&root));
+        assert_eq!(
+            projects.len(),
+            2,
+            "worktree is neither missed nor duplicated"
+        );
+
+        let alpha = projects.iter().find(|p| p.name == "alpha").expect("alpha");
+        assert_eq!(alpha.kind, ProjectKind::Git);
+
+        let linked = projects
+            .iter()
+            .find(|p| p.name == "alpha-feature")
+            .expect("worktree row");
+        assert!(linked.is_git());
+        assert_eq!(
+            linked.kind,
+            ProjectKind::Worktree {
+                primary: fs::canonicalize(&primary).expect("canonical primary"),
+                branch: Some("feature/login".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn submodule_style_git_file_is_a_regular_repo() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let module_gitdir = temp.path().join("modules/lib");
+        fs::create_dir_all(&module_gitdir).expect("create module gitdir");
+        fs::create_dir_all(root.join("lib")).expect("create checkout");
+        fs::write(
+            root.join("lib/.git"),
+            format!("gitdir: {}\n", module_gitdir.display()),
+        )
+        .expect("write .git file");
+
+        let projects = discover_projects(std::slice::from_ref(&root));
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].kind, ProjectKind::Git);
+    }
+
+    #[test]
+    fn code_workspace_files_are_reported_at_root_and_inside_projects() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        init_git_repo(&root.join("alpha"));
+        fs::write(root.join("fullstack.code-workspace"), "{}").expect("write root workspace");
+        fs::write(root.join("alpha/alpha.code-workspace"), "{}").expect("write repo workspace");
+        fs::write(root.join("alpha/notes.txt"), "").expect("write plain file");
+        fs::create_dir_all(root.join("skip.code-workspace")).expect("create look-alike dir");
+
+        let projects = discover_projects(std::slice::from_ref(&root));
+        let rows: Vec<(&str, &ProjectKind)> = projects
+            .iter()
+            .map(|project| (project.name.as_str(), &project.kind))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("alpha", &ProjectKind::Git),
+                ("alpha", &ProjectKind::CodeWorkspace),
+                ("fullstack", &ProjectKind::CodeWorkspace),
+            ]
+        );
+        assert_eq!(projects[1].path, root.join("alpha/alpha.code-workspace"));
+        assert_eq!(projects[2].working_dir(), root.as_path());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_roots_collapse_to_the_earliest_root_path() {
+        let temp = tempdir().expect("create temp dir");
+        let real = temp.path().join("real");
+        init_git_repo(&real.join("alpha"));
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).expect("create root symlink");
+
+        let projects = discover_projects(&[link.clone(), real.clone()]);
+        assert_eq!(projects.len(), 1, "one row per canonical repo");
+        assert_eq!(projects[0].path, link.join("alpha"), "first root wins");
+
+        let projects = discover_projects(&[real.clone(), link]);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].path, real.join("alpha"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_inside_a_root_prefer_the_real_path_and_loops_terminate() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        init_git_repo(&root.join("group/alpha"));
+        std::os::unix::fs::symlink(root.join("group/alpha"), root.join("alias-alpha"))
+            .expect("create repo symlink");
+        // Loops through an ancestor and through a sibling must not hang or duplicate rows.
+        std::os::unix::fs::symlink(&root, root.join("group/back-to-root"))
+            .expect("create ancestor loop");
+        std::os::unix::fs::symlink(root.join("group"), root.join("group/alpha/up"))
+            .expect("create nested loop");
+
+        let options = ScanOptions {
+            max_depth: 10,
+            ..ScanOptions::default()
+        };
+        let projects = discover_projects_with(std::slice::from_ref(&root), &options);
+        let paths: Vec<&Path> = projects
+            .iter()
+            .map(|project| project.path.as_path())
+            .collect();
+        assert_eq!(paths, vec![root.join("group/alpha").as_path()]);
+    }
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args([
+                "-c",
+                "user.name=tester",
+                "-c",
+                "user.email=tester@example.com",
+            ])
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} should succeed");
+    }
+
     fn init_git_repo(path: &Path) {
         fs::create_dir_all(path).expect("create repo dir");
 
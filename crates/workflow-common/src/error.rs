@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
+use crate::git::{Forge, ForgePage};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CliErrorKind {
     User,
@@ -34,8 +36,28 @@ pub enum WorkflowError {
     MissingOrigin(PathBuf),
     #[error("unsupported remote URL format: {0}")]
     UnsupportedRemote(String),
+    #[error("{} has no {} page", forge.label(), page.as_str())]
+    UnsupportedForgePage { forge: Forge, page: ForgePage },
     #[error("failed to execute git in {path}: {message}")]
     GitCommand { path: PathBuf, message: String },
+    #[error("failed to persist scan cache at {path}: {source}")]
+    CacheWrite {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to launch {app}: {message}")]
+    TerminalLaunch { app: String, message: String },
+    #[error("failed to launch {program}: {message}")]
+    SearchLaunch { program: String, message: String },
+    #[error("config file {path} is invalid: {message}")]
+    ConfigParse { path: PathBuf, message: String },
+    #[error("failed to persist config file at {path}: {source}")]
+    ConfigWrite {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
     #[error("failed to persist usage log at {path}: {source}")]
     UsageWrite {
         path: PathBuf,
@@ -1,11 +1,18 @@
+use std::collections::HashMap;
+use std::fs;
 use std::path::Path;
 
 use alfred_core::{Feedback, Item, ItemIcon, ItemModifier};
 
 use crate::config::RuntimeConfig;
-use crate::discovery::{discover_projects, filter_projects};
-use crate::git::{last_commit_summary, remote_host_for_project};
-use crate::usage_log::{UsageLog, parse_usage_timestamp};
+use crate::discovery::{Project, ProjectKind, filter_projects};
+use crate::git::{Forge, ForgePage, forge_for_project, git_statuses, last_commit_summary};
+use crate::scan_cache::{RefreshPolicy, load_projects};
+use crate::search::{
+    SEARCH_KEYWORD, SEARCH_MIN_PATTERN_CHARS, SEARCH_TIMEOUT, SearchQuery, search,
+};
+use crate::usage_log::{UsageLog, parse_usage_timestamp, usage_now};
+use crate::workspace::WorkspaceKind;
 
 const NO_PROJECTS_TITLE: &str = "No Git projects found";
 const NO_PROJECTS_SUBTITLE: &str = "No matching or initialized Git repos found";
@@ -15,6 +22,39 @@ const GITHUB_SHIFT_SUBTITLE: &str = "Open Project on GitHub";
 const GITHUB_ICON_PATH: &str = "assets/icon-github.png";
 const GITLAB_SHIFT_SUBTITLE: &str = "Open Project on GitLab";
 const GITLAB_ICON_PATH: &str = "assets/icon-gitlab.png";
+const MARKER_SHIFT_SUBTITLE: &str = "Not a Git repository (no remote to open)";
+const REMOTE_SHIFT_SUBTITLE: &str = "Remote-SSH project (no local git metadata)";
+const REMOTE_LOCAL_ONLY_SUBTITLE: &str = "Not available for Remote-SSH projects";
+const MEMBER_SHIFT_SUBTITLE: &str = "Workspace member (open the repo row for its remote)";
+const CODE_WORKSPACE_SHIFT_SUBTITLE: &str = "VS Code workspace file (no remote to open)";
+const FILE_ICON_TYPE: &str = "fileicon";
+const PINNED_SUBTITLE_PREFIX: &str = "Pinned • ";
+const ARCHIVED_SUBTITLE_PREFIX: &str = "Archived • ";
+/// Query prefix that brings archived projects back into the results (`all <text>`).
+pub const ARCHIVE_SCOPE_KEYWORD: &str = "all";
+const PINNED_SECTION_TITLE: &str = "Pinned";
+const RECENT_SECTION_TITLE: &str = "Recently used";
+const ALL_SECTION_TITLE: &str = "All projects";
+/// Modifier keys for `RuntimeConfig::extra_editors`, in order.
+const EDITOR_MODIFIERS: [&str; 2] = ["alt", "ctrl"];
+/// Modifier key for the open-in-terminal action (`RuntimeConfig::terminal_app`).
+const TERMINAL_MODIFIER: &str = "cmd";
+/// Alfred variable read by `action_open.sh` as the editor command: the resolved `vscode_path` on rows,
+/// overridden by the extra editors on `alt` / `ctrl`.
+pub const EDITOR_VARIABLE: &str = "open_project_editor";
+/// Modifier keys for forge sub-pages in GitHub mode, in order.
+const FORGE_PAGE_MODIFIERS: [(&str, ForgePage); 3] = [
+    ("shift", ForgePage::PullRequests),
+    ("alt", ForgePage::Issues),
+    ("ctrl", ForgePage::Ci),
+];
+/// Alfred variable read by `action_open_github.sh` to open a forge sub-page (`forge-url --kind`).
+pub const FORGE_PAGE_VARIABLE: &str = "open_project_forge_page";
+/// Set on Remote-SSH rows: `vscode-remote://ssh-remote+<host><path>` for `code --folder-uri`.
+pub const REMOTE_URI_VARIABLE: &str = "open_project_remote_uri";
+/// Set on `grep` result rows: `file:line:column` for `code -g`.
+pub const SEARCH_GOTO_VARIABLE: &str = "open_project_goto";
+const SEARCH_TITLE_MAX_CHARS: usize = 120;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct RemotePresentation {
@@ -32,6 +72,13 @@ const GITLAB_PRESENTATION: RemotePresentation = RemotePresentation {
     icon_path: GITLAB_ICON_PATH,
 };
 
+/// Ranking key: decayed usage count first, raw last-used timestamp as the tie-breaker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RankKey {
+    pub(crate) frecency: f64,
+    pub(crate) last_used: i64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScriptFilterMode {
     Open,
@@ -47,79 +94,610 @@ pub fn build_script_filter_feedback_with_mode(
     config: &RuntimeConfig,
     mode: ScriptFilterMode,
 ) -> Feedback {
-    let trimmed_query = query.trim();
-    let discovered = discover_projects(&config.project_roots);
-    let filtered = filter_projects(&discovered, trimmed_query);
+    let snapshot = load_projects(config, RefreshPolicy::Inline);
+    build_script_filter_feedback_for_projects(query, config, mode, &snapshot.projects)
+}
 
-    if filtered.is_empty() {
-        return no_projects_feedback();
+/// Render feedback for an already-resolved project list (e.g. a cached scan snapshot).
+pub fn build_script_filter_feedback_for_projects(
+    query: &str,
+    config: &RuntimeConfig,
+    mode: ScriptFilterMode,
+    discovered: &[Project],
+) -> Feedback {
+    if mode == ScriptFilterMode::Open
+        && let Some(search_query) = SearchQuery::parse(query)
+    {
+        return build_search_feedback(&search_query, config, discovered);
     }
 
+    let (trimmed_query, include_archived) = split_archive_scope(query);
     let usage_log = UsageLog::load(&config.usage_file);
+    // Remote rows only make sense for git repositories.
+    let git_only = mode == ScriptFilterMode::Github;
+    let mut ranked = ranked_matches(
+        trimmed_query,
+        config,
+        discovered,
+        MatchScope {
+            git_only,
+            include_archived,
+        },
+        &usage_log,
+        usage_now(),
+    );
+
+    if ranked.is_empty() {
+        return no_projects_feedback();
+    }
+
+    let max_items = if trimmed_query.is_empty() {
+        config.max_results
+    } else {
+        usize::MAX
+    };
+    let (pinned_len, recent_len) = if trimmed_query.is_empty() {
+        split_recent(&mut ranked, config, config.recent_count)
+    } else {
+        (0, 0)
+    };
+    ranked.truncate(max_items);
+    let pinned_len = pinned_len.min(ranked.len());
+    let recent_len = recent_len.min(ranked.len() - pinned_len);
 
-    let mut ranked_items = filtered
+    // Probe only the rows that will be shown, in parallel, each under its own deadline.
+    let statuses = if config.show_git_status {
+        let git_paths = ranked
+            .iter()
+            .filter(|(_, project)| project.is_git())
+            .map(|(_, project)| project.path.as_path())
+            .collect::<Vec<_>>();
+        let mut probed = git_statuses(&git_paths, config.git_status_timeout).into_iter();
+        ranked
+            .iter()
+            .map(|(_, project)| {
+                if project.is_git() {
+                    probed.next().flatten()
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+    } else {
+        vec![None; ranked.len()]
+    };
+
+    let mut items = ranked
         .into_iter()
-        .map(|project| {
-            let commit = last_commit_summary(&project.path);
+        .zip(statuses)
+        .map(|((_, project), git_status)| {
             let last_used = usage_log.timestamp_for(&project.path, &project.name);
-            let subtitle = subtitle_format(commit.as_deref(), last_used);
-            let sort_key = parse_usage_timestamp(last_used);
+            let subtitle = match &project.kind {
+                ProjectKind::Git | ProjectKind::Worktree { .. } => {
+                    let commit = last_commit_summary(&project.path);
+                    let mut subtitle = subtitle_format(commit.as_deref(), last_used);
+                    if let ProjectKind::Worktree { primary, branch } = &project.kind {
+                        subtitle = format!(
+                            "{} • {subtitle}",
+                            worktree_label(primary, branch.as_deref())
+                        );
+                    }
+                    match git_status {
+                        Some(status) => format!("{} • {subtitle}", status.label()),
+                        None => subtitle,
+                    }
+                }
+                ProjectKind::Marker { marker } => marker_subtitle_format(marker, last_used),
+                ProjectKind::Member { repo, workspace } => {
+                    member_subtitle_format(repo, *workspace, last_used)
+                }
+                ProjectKind::Remote { host, .. } => remote_subtitle_format(host, last_used),
+                ProjectKind::CodeWorkspace => {
+                    code_workspace_subtitle_format(project.working_dir(), last_used)
+                }
+            };
+            let subtitle = if config.is_pinned(&project.path) {
+                format!("{PINNED_SUBTITLE_PREFIX}{subtitle}")
+            } else if usage_log.is_archived(&project.path) {
+                format!("{ARCHIVED_SUBTITLE_PREFIX}{subtitle}")
+            } else {
+                subtitle
+            };
             let path = project.path.to_string_lossy().to_string();
-            let remote_presentation = remote_presentation_for_project(&project.path);
 
             let mut item = Item::new(&project.name)
                 .with_arg(path.clone())
                 .with_autocomplete(project.name.clone())
-                .with_subtitle(subtitle)
-                .with_mod(
+                .with_subtitle(subtitle);
+
+            if let Some(uri) = project.remote_uri() {
+                let local_only = || {
+                    ItemModifier::new()
+                        .with_valid(false)
+                        .with_subtitle(REMOTE_LOCAL_ONLY_SUBTITLE)
+                };
+                item = item.with_variable(REMOTE_URI_VARIABLE, uri).with_mod(
                     "shift",
                     ItemModifier::new()
-                        .with_arg(path.clone())
-                        .with_valid(true)
-                        .with_icon(ItemIcon::new(remote_presentation.icon_path))
-                        .with_subtitle(remote_presentation.shift_subtitle),
-                )
-                .with_variable("project_path", path);
+                        .with_valid(false)
+                        .with_subtitle(REMOTE_SHIFT_SUBTITLE),
+                );
+                for modifier in EDITOR_MODIFIERS.iter().chain([&TERMINAL_MODIFIER]) {
+                    item = item.with_mod(*modifier, local_only());
+                }
+                return item
+                    .with_variable(EDITOR_VARIABLE, config.vscode_path.clone())
+                    .with_variable("project_path", path);
+            }
 
-            if mode == ScriptFilterMode::Github {
-                item = item.with_icon(ItemIcon::new(remote_presentation.icon_path));
+            if project.is_git() {
+                let forge = forge_for_project(&project.path, &config.forge_hosts);
+                let remote_presentation = remote_presentation_for_forge(forge);
+                if mode == ScriptFilterMode::Github {
+                    item = item.with_icon(ItemIcon::new(remote_presentation.icon_path));
+                    // Without a parseable origin the primary action fails anyway; label the
+                    // pages the GitHub way, matching the fallback presentation.
+                    let forge = forge.unwrap_or(Forge::GitHub);
+                    for (modifier, page) in FORGE_PAGE_MODIFIERS {
+                        item = item.with_mod(modifier, forge_page_modifier(forge, page, &path));
+                    }
+                } else {
+                    item = item.with_mod(
+                        "shift",
+                        ItemModifier::new()
+                            .with_arg(path.clone())
+                            .with_valid(true)
+                            .with_icon(ItemIcon::new(remote_presentation.icon_path))
+                            .with_subtitle(remote_presentation.shift_subtitle),
+                    );
+                }
+            } else {
+                let shift_subtitle = match project.kind {
+                    ProjectKind::Member { .. } => MEMBER_SHIFT_SUBTITLE,
+                    ProjectKind::CodeWorkspace => CODE_WORKSPACE_SHIFT_SUBTITLE,
+                    _ => MARKER_SHIFT_SUBTITLE,
+                };
+                item = item
+                    .with_icon(ItemIcon::new(path.clone()).with_type(FILE_ICON_TYPE))
+                    .with_mod(
+                        "shift",
+                        ItemModifier::new()
+                            .with_valid(false)
+                            .with_subtitle(shift_subtitle),
+                    );
             }
 
-            (sort_key, project.name, item)
+            item = item.with_mod(
+                TERMINAL_MODIFIER,
+                ItemModifier::new()
+                    .with_arg(project.working_dir().to_string_lossy().to_string())
+                    .with_valid(true)
+                    .with_subtitle(format!("Open in {}", config.terminal_app.label())),
+            );
+
+            if mode == ScriptFilterMode::Open {
+                for (modifier, editor) in EDITOR_MODIFIERS.iter().zip(&config.extra_editors) {
+                    let Some(editor) = editor else {
+                        continue;
+                    };
+                    item = item.with_mod(
+                        *modifier,
+                        ItemModifier::new()
+                            .with_arg(path.clone())
+                            .with_valid(true)
+                            .with_subtitle(format!("Open in {}", editor.label))
+                            .with_variable(EDITOR_VARIABLE, editor.command.clone()),
+                    );
+                }
+            }
+
+            item.with_variable(EDITOR_VARIABLE, config.vscode_path.clone())
+                .with_variable("project_path", path)
         })
         .collect::<Vec<_>>();
 
-    ranked_items.sort_by(|(left_sort, left_name, _), (right_sort, right_name, _)| {
-        right_sort
-            .cmp(left_sort)
-            .then_with(|| left_name.cmp(right_name))
+    if recent_len > 0 {
+        let rest_start = pinned_len + recent_len;
+        if rest_start < items.len() {
+            items.insert(rest_start, Item::section_header(ALL_SECTION_TITLE));
+        }
+        items.insert(pinned_len, Item::section_header(RECENT_SECTION_TITLE));
+        if pinned_len > 0 {
+            items.insert(0, Item::section_header(PINNED_SECTION_TITLE));
+        }
+    }
+
+    Feedback::new(items)
+}
+
+/// Order an empty-query list as pinned rows, then the `count` most recently used rows (newest
+/// first), then everything else in rank order; returns `(pinned, recent)` section lengths.
+fn split_recent(
+    ranked: &mut Vec<(RankKey, Project)>,
+    config: &RuntimeConfig,
+    count: usize,
+) -> (usize, usize) {
+    // `ranked_matches` already sorted pinned rows to the front.
+    let pinned_len = ranked
+        .iter()
+        .take_while(|(_, project)| config.is_pinned(&project.path))
+        .count();
+    let mut recent = ranked
+        .iter()
+        .enumerate()
+        .skip(pinned_len)
+        .filter(|(_, (rank, _))| rank.last_used > 0)
+        .map(|(index, (rank, _))| (rank.last_used, index))
+        .collect::<Vec<_>>();
+    recent.sort_by(|left, right| right.0.cmp(&left.0).then(left.1.cmp(&right.1)));
+    recent.truncate(count);
+    if recent.is_empty() {
+        return (pinned_len, 0);
+    }
+
+    let mut slots = ranked.drain(..).map(Some).collect::<Vec<_>>();
+    let mut ordered = slots[..pinned_len]
+        .iter_mut()
+        .filter_map(Option::take)
+        .collect::<Vec<_>>();
+    ordered.extend(recent.iter().filter_map(|(_, index)| slots[*index].take()));
+    ordered.extend(slots.into_iter().flatten());
+    *ranked = ordered;
+    (pinned_len, recent.len())
+}
+
+/// Render `grep [@project] <text>` as file:line rows that open the match in the editor.
+///
+/// `@project` resolves like a regular query (alias first, then rank); without it every
+/// configured root is searched. Rows keep the owning project as `arg` so usage is recorded
+/// against the project, not the file.
+fn build_search_feedback(
+    search_query: &SearchQuery,
+    config: &RuntimeConfig,
+    discovered: &[Project],
+) -> Feedback {
+    let pattern = search_query.pattern.as_str();
+    if pattern.chars().count() < SEARCH_MIN_PATTERN_CHARS {
+        return info_feedback(
+            "Search projects with ripgrep",
+            &format!(
+                "Type at least {SEARCH_MIN_PATTERN_CHARS} characters; `{SEARCH_KEYWORD} @project text` searches one project"
+            ),
+        );
+    }
+
+    let scoped = match search_query.scope.as_deref() {
+        Some(scope) => {
+            let usage_log = UsageLog::load(&config.usage_file);
+            let best = ranked_matches(
+                scope,
+                config,
+                discovered,
+                MatchScope::default(),
+                &usage_log,
+                usage_now(),
+            )
+            .into_iter()
+            .next()
+            .map(|(_, project)| project);
+            match best {
+                Some(project) => Some(project),
+                None => {
+                    return info_feedback(
+                        &format!("No project matches @{scope}"),
+                        "Use a project name or alias after @",
+                    );
+                }
+            }
+        }
+        None => None,
+    };
+    let paths = match &scoped {
+        Some(project) => vec![project.path.clone()],
+        None => config
+            .project_roots
+            .iter()
+            .filter(|root| root.is_dir())
+            .cloned()
+            .collect(),
+    };
+
+    let outcome = match search(pattern, &paths, &config.search_options()) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            return info_feedback(
+                "ripgrep (rg) is not available",
+                &format!("{error}; install ripgrep or set OPEN_PROJECT_RG_PATH"),
+            );
+        }
+    };
+    if outcome.matches.is_empty() {
+        let subtitle = if outcome.timed_out {
+            "Search timed out before any match; narrow it with @project"
+        } else {
+            "Search is literal and smart-case; .gitignore'd files are skipped"
+        };
+        return info_feedback(&format!("No matches for \"{pattern}\""), subtitle);
+    }
+
+    let mut items = outcome
+        .matches
+        .iter()
+        .map(|found| {
+            let owner = scoped
+                .as_ref()
+                .or_else(|| {
+                    discovered
+                        .iter()
+                        .filter(|project| found.path.starts_with(&project.path))
+                        .max_by_key(|project| project.path.as_os_str().len())
+                })
+                .map(|project| (project.name.as_str(), project.path.as_path()));
+            let owner_path = owner
+                .map(|(_, path)| path)
+                .or_else(|| found.path.parent())
+                .unwrap_or(&found.path);
+            let relative = found
+                .path
+                .strip_prefix(owner_path)
+                .unwrap_or(&found.path)
+                .to_string_lossy();
+            let location = match owner {
+                Some((name, _)) => format!("{name}/{relative}:{}", found.line),
+                None => format!("{}:{}", found.path.to_string_lossy(), found.line),
+            };
+            let title = found.text.trim();
+            let title = match title.char_indices().nth(SEARCH_TITLE_MAX_CHARS) {
+                Some((cut, _)) => format!("{}…", &title[..cut]),
+                None => title.to_string(),
+            };
+            let project_path = owner_path.to_string_lossy().to_string();
+
+            Item::new(title)
+                .with_arg(project_path.clone())
+                .with_subtitle(location)
+                .with_icon(
+                    ItemIcon::new(found.path.to_string_lossy().to_string())
+                        .with_type(FILE_ICON_TYPE),
+                )
+                .with_variable(EDITOR_VARIABLE, config.vscode_path.clone())
+                .with_variable("project_path", project_path)
+                .with_variable(SEARCH_GOTO_VARIABLE, found.goto_target())
+        })
+        .collect::<Vec<_>>();
+
+    if outcome.timed_out {
+        items.push(
+            Item::new("Search stopped early; results are partial")
+                .with_subtitle(format!(
+                    "ripgrep exceeded {} ms; narrow it with @project",
+                    SEARCH_TIMEOUT.as_millis()
+                ))
+                .with_valid(false),
+        );
+    } else if outcome.truncated {
+        items.push(
+            Item::new(format!("Showing the first {} matches", items.len()))
+                .with_subtitle("Refine the text or scope it with @project")
+                .with_valid(false),
+        );
+    }
+
+    Feedback::new(items)
+}
+
+fn info_feedback(title: &str, subtitle: &str) -> Feedback {
+    Feedback::new(vec![
+        Item::new(title).with_subtitle(subtitle).with_valid(false),
+    ])
+}
+
+/// Which projects [`ranked_matches`] may return besides name matches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MatchScope {
+    pub(crate) git_only: bool,
+    /// Keep projects archived with `workflow-cli archive` (the `all <text>` query form).
+    pub(crate) include_archived: bool,
+}
+
+/// Strip a leading `all ` scope from `query`; returns the trimmed rest and whether archived
+/// projects are included.
+pub(crate) fn split_archive_scope(query: &str) -> (&str, bool) {
+    let trimmed = query.trim();
+    match trimmed.strip_prefix(ARCHIVE_SCOPE_KEYWORD) {
+        Some(rest) if rest.is_empty() && query.trim_start().len() > trimmed.len() => ("", true),
+        Some(rest) if rest.starts_with(char::is_whitespace) => (rest.trim(), true),
+        _ => (trimmed, false),
+    }
+}
+
+/// Filter and rank `discovered` for `query`, applying aliases, pins, and archive state.
+///
+/// An exact alias match comes first (even when its name does not match the query, it sits
+/// outside the scanned roots, or it is archived), then pinned projects in pin order, then
+/// everything else. Archived projects are dropped unless `scope.include_archived` is set.
+pub(crate) fn ranked_matches(
+    query: &str,
+    config: &RuntimeConfig,
+    discovered: &[Project],
+    scope: MatchScope,
+    usage_log: &UsageLog,
+    now: i64,
+) -> Vec<(RankKey, Project)> {
+    let remote = config
+        .remote_projects
+        .iter()
+        .filter_map(|spec| Project::remote(spec))
+        .collect::<Vec<_>>();
+    let alias_target = config.alias_target(query).and_then(|target| {
+        discovered
+            .iter()
+            .chain(&remote)
+            .find(|project| project.path == target)
+            .cloned()
+            .or_else(|| {
+                target
+                    .is_dir()
+                    .then(|| Project::new(target.to_path_buf()))
+                    .flatten()
+            })
     });
 
-    let max_items = if trimmed_query.is_empty() {
-        config.max_results
-    } else {
-        usize::MAX
+    let mut filtered = filter_projects(discovered, query);
+    filtered.extend(filter_projects(&remote, query));
+    if !scope.include_archived {
+        filtered.retain(|project| !usage_log.is_archived(&project.path));
+    }
+    if let Some(target) = &alias_target {
+        filtered.retain(|project| project.path != target.path);
+        filtered.insert(0, target.clone());
+    }
+    if scope.git_only {
+        filtered.retain(Project::is_git);
+    }
+
+    let mut ranked = rank_projects(filtered, usage_log, now);
+    let pin_rank = |project: &Project| {
+        if alias_target
+            .as_ref()
+            .is_some_and(|target| target.path == project.path)
+        {
+            return 0;
+        }
+        config
+            .pins
+            .iter()
+            .position(|pin| *pin == project.path)
+            .map_or(usize::MAX, |position| position + 1)
     };
+    // Stable sort keeps rank order among unpinned rows.
+    ranked.sort_by_key(|(_, project)| pin_rank(project));
+    ranked
+}
 
-    let items = ranked_items
+/// Sort by frecency, then last use, then name; worktrees follow their primary repo.
+fn rank_projects(
+    projects: Vec<Project>,
+    usage_log: &UsageLog,
+    now: i64,
+) -> Vec<(RankKey, Project)> {
+    let mut ranked = projects
         .into_iter()
-        .take(max_items)
-        .map(|(_, _, item)| item)
-        .collect();
+        .map(|project| {
+            let sort_key = RankKey {
+                frecency: usage_log.frecency_for(&project.path, &project.name, now),
+                last_used: parse_usage_timestamp(
+                    usage_log.timestamp_for(&project.path, &project.name),
+                ),
+            };
+            (sort_key, project)
+        })
+        .collect::<Vec<_>>();
 
-    Feedback::new(items)
+    ranked.sort_by(|(left_sort, left), (right_sort, right)| {
+        right_sort
+            .frecency
+            .total_cmp(&left_sort.frecency)
+            .then_with(|| right_sort.last_used.cmp(&left_sort.last_used))
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    group_under_owners(ranked)
 }
 
-fn remote_presentation_for_project(project_path: &Path) -> RemotePresentation {
-    match remote_host_for_project(project_path) {
-        Some(host) if is_gitlab_host(&host) => GITLAB_PRESENTATION,
+/// Move each linked worktree and workspace member directly below its repo when both are
+/// listed.
+///
+/// Children keep their relative rank order inside a group; children whose repo is not
+/// listed stay where ranking put them.
+fn group_under_owners(ranked: Vec<(RankKey, Project)>) -> Vec<(RankKey, Project)> {
+    if !ranked.iter().any(|(_, project)| {
+        matches!(
+            project.kind,
+            ProjectKind::Worktree { .. } | ProjectKind::Member { .. }
+        )
+    }) {
+        return ranked;
+    }
+
+    // Worktree primaries are canonical paths; compare listed owners the same way.
+    let owner_index = ranked
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, project))| !matches!(project.kind, ProjectKind::Member { .. }))
+        .filter_map(|(index, (_, project))| {
+            fs::canonicalize(&project.path)
+                .ok()
+                .map(|path| (path, index))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let total = ranked.len();
+    let mut rows = Vec::with_capacity(total);
+    let mut children_by_owner = HashMap::<usize, Vec<(RankKey, Project)>>::new();
+    for (index, entry) in ranked.into_iter().enumerate() {
+        let owner = match &entry.1.kind {
+            ProjectKind::Worktree { primary, .. } => owner_index.get(primary).copied(),
+            ProjectKind::Member { repo, .. } => fs::canonicalize(repo)
+                .ok()
+                .and_then(|repo| owner_index.get(&repo).copied()),
+            _ => None,
+        };
+        match owner {
+            Some(owner) => children_by_owner.entry(owner).or_default().push(entry),
+            None => rows.push((index, entry)),
+        }
+    }
+
+    let mut grouped = Vec::with_capacity(total);
+    for (index, entry) in rows {
+        grouped.push(entry);
+        if let Some(children) = children_by_owner.remove(&index) {
+            grouped.extend(children);
+        }
+    }
+    grouped
+}
+
+fn worktree_label(primary: &Path, branch: Option<&str>) -> String {
+    let primary_name = primary
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| primary.to_string_lossy().to_string());
+    format!(
+        "⎇ {} (worktree of {primary_name})",
+        branch.unwrap_or("detached")
+    )
+}
+
+fn remote_presentation_for_forge(forge: Option<Forge>) -> RemotePresentation {
+    match forge {
+        Some(Forge::GitLab) => GITLAB_PRESENTATION,
         _ => GITHUB_PRESENTATION,
     }
 }
 
-fn is_gitlab_host(host: &str) -> bool {
-    host.split('.')
-        .any(|segment| segment.eq_ignore_ascii_case("gitlab"))
+fn forge_page_modifier(forge: Forge, page: ForgePage, path: &str) -> ItemModifier {
+    if forge.page_path(page).is_none() {
+        return ItemModifier::new().with_valid(false).with_subtitle(format!(
+            "{} has no {} page",
+            forge.label(),
+            page.as_str()
+        ));
+    }
+
+    let label = match (page, forge) {
+        (ForgePage::PullRequests, Forge::GitLab) => "Merge Requests",
+        (ForgePage::PullRequests, _) => "Pull Requests",
+        (ForgePage::Issues, _) => "Issues",
+        (ForgePage::Ci, Forge::GitHub | Forge::Generic) => "Actions",
+        (ForgePage::Ci, Forge::BitbucketServer) => "Builds",
+        (ForgePage::Ci, _) => "Pipelines",
+    };
+    ItemModifier::new()
+        .with_arg(path.to_string())
+        .with_valid(true)
+        .with_subtitle(format!("Open {label} on {}", forge.label()))
+        .with_variable(FORGE_PAGE_VARIABLE, page.as_str())
 }
 
 pub fn subtitle_format(commit_summary: Option<&str>, usage_timestamp: Option<&str>) -> String {
@@ -136,6 +714,58 @@ pub fn subtitle_format(commit_summary: Option<&str>, usage_timestamp: Option<&st
     format!("{commit_text} • {usage_text}")
 }
 
+/// Subtitle for marker-detected (non-git) folders: `<marker> project • <last used>`.
+pub fn marker_subtitle_format(marker: &str, usage_timestamp: Option<&str>) -> String {
+    let usage_text = usage_timestamp
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(NO_USAGE_TEXT);
+
+    format!("{marker} project • {usage_text}")
+}
+
+/// Subtitle for `*.code-workspace` rows: `VS Code workspace in <dir> • <last used>`.
+fn code_workspace_subtitle_format(dir: &Path, usage_timestamp: Option<&str>) -> String {
+    let usage_text = usage_timestamp
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(NO_USAGE_TEXT);
+    let dir = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| dir.to_string_lossy().to_string());
+
+    format!("VS Code workspace in {dir} • {usage_text}")
+}
+
+/// Subtitle for Remote-SSH entries: `SSH <host> • <last used>`.
+fn remote_subtitle_format(host: &str, usage_timestamp: Option<&str>) -> String {
+    let usage_text = usage_timestamp
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(NO_USAGE_TEXT);
+
+    format!("SSH {host} • {usage_text}")
+}
+
+/// Subtitle for workspace members: `<workspace> member of <repo> • <last used>`.
+fn member_subtitle_format(
+    repo: &Path,
+    workspace: WorkspaceKind,
+    usage_timestamp: Option<&str>,
+) -> String {
+    let repo_name = repo
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo.to_string_lossy().to_string());
+    let usage_text = usage_timestamp
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(NO_USAGE_TEXT);
+
+    format!("{} member of {repo_name} • {usage_text}", workspace.label())
+}
+
 pub fn no_projects_feedback() -> Feedback {
     Feedback::new(vec![
         Item::new(NO_PROJECTS_TITLE)
@@ -161,6 +791,7 @@ mod tests {
     use std::fs;
     use std::path::PathBuf;
     use std::process::Command;
+    use std::time::Duration;
 
     use tempfile::tempdir;
 
@@ -209,6 +840,8 @@ mod tests {
             usage_file,
             vscode_path: "code".to_string(),
             max_results: 10,
+            recent_count: 0,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("", &config);
@@ -226,6 +859,55 @@ mod tests {
         assert_eq!(titles.get(1), Some(&"alpha"), "older usage should be later");
     }
 
+    #[test]
+    fn sort_order_prefers_frequent_usage_over_single_recent_hit() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        let frequent = roots.join("frequent");
+        let once = roots.join("once");
+
+        init_repo(&frequent);
+        init_repo(&once);
+
+        let now = chrono::Local::now().naive_local();
+        let format =
+            |offset: chrono::Duration| (now - offset).format("%Y-%m-%d %H:%M:%S").to_string();
+        let usage_file = temp.path().join("usage.log");
+        fs::write(
+            &usage_file,
+            format!(
+                "{} | {} | 40\n{} | {} | 1\n",
+                frequent.to_string_lossy(),
+                format(chrono::Duration::days(3)),
+                once.to_string_lossy(),
+                format(chrono::Duration::days(1)),
+            ),
+        )
+        .expect("write usage file");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file,
+            vscode_path: "code".to_string(),
+            max_results: 10,
+            recent_count: 0,
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("", &config);
+        let titles: Vec<&str> = feedback
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+
+        assert_eq!(
+            titles,
+            vec!["frequent", "once"],
+            "frequently opened repo should outrank a single recent hit"
+        );
+    }
+
     #[test]
     fn no_projects_feedback_is_invalid_item() {
         let config = RuntimeConfig {
@@ -233,6 +915,7 @@ mod tests {
             usage_file: PathBuf::from("/tmp/non-existent-usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("", &config);
@@ -262,6 +945,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 2,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("", &config);
@@ -285,6 +969,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 1,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("a", &config);
@@ -307,6 +992,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("", &config);
@@ -340,6 +1026,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("", &config);
@@ -377,6 +1064,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let feedback =
@@ -403,6 +1091,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 10,
+            ..RuntimeConfig::default()
         };
 
         let feedback =
@@ -416,6 +1105,680 @@ mod tests {
         );
     }
 
+    #[test]
+    fn github_mode_maps_forge_pages_to_modifiers() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        let repo = roots.join("alpha");
+        init_repo(&repo);
+        set_origin(&repo, "git@gitlab.com:group/alpha.git");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+
+        let feedback =
+            build_script_filter_feedback_with_mode("", &config, ScriptFilterMode::Github);
+        let mods = feedback
+            .items
+            .first()
+            .and_then(|item| item.mods.as_ref())
+            .expect("github rows carry modifiers");
+
+        let expected = [
+            ("shift", "Open Merge Requests on GitLab", "pr"),
+            ("alt", "Open Issues on GitLab", "issues"),
+            ("ctrl", "Open Pipelines on GitLab", "ci"),
+        ];
+        for (modifier, subtitle, page) in expected {
+            let entry = mods.get(modifier).expect("forge page modifier");
+            assert_eq!(entry.subtitle.as_deref(), Some(subtitle));
+            assert_eq!(entry.valid, Some(true));
+            let variables = entry.variables.as_ref().expect("modifier variables");
+            assert_eq!(
+                variables.get(FORGE_PAGE_VARIABLE).map(String::as_str),
+                Some(page)
+            );
+        }
+    }
+
+    #[test]
+    fn github_mode_disables_pages_the_forge_lacks() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        let repo = roots.join("billing");
+        init_repo(&repo);
+        set_origin(&repo, "ssh://git@code.corp.example:7999/plat/billing.git");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            forge_hosts: crate::git::parse_forge_hosts("code.corp.example=bitbucket-server"),
+            ..RuntimeConfig::default()
+        };
+
+        let feedback =
+            build_script_filter_feedback_with_mode("", &config, ScriptFilterMode::Github);
+        let mods = feedback
+            .items
+            .first()
+            .and_then(|item| item.mods.as_ref())
+            .expect("github rows carry modifiers");
+        let issues = mods.get("alt").expect("issues modifier");
+        assert_eq!(issues.valid, Some(false));
+        assert_eq!(
+            mods.get("ctrl").and_then(|entry| entry.subtitle.as_deref()),
+            Some("Open Builds on Bitbucket Server")
+        );
+    }
+
+    #[test]
+    fn marker_projects_use_folder_icon_and_skip_github_mode() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        let plain = roots.join("plain");
+        fs::create_dir_all(&plain).expect("create plain folder");
+        fs::write(plain.join("go.mod"), "module plain").expect("write marker");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("plain", &config);
+        let item = feedback.items.first().expect("marker project row");
+        assert_eq!(item.title, "plain");
+        assert_eq!(item.subtitle.as_deref(), Some("go.mod project • N/A"));
+        let icon = item.icon.as_ref().expect("marker row icon");
+        assert_eq!(icon.r#type.as_deref(), Some("fileicon"));
+        let shift = item
+            .mods
+            .as_ref()
+            .and_then(|mods| mods.get("shift"))
+            .expect("shift modifier should be present");
+        assert_eq!(
+            shift.valid,
+            Some(false),
+            "no remote action for plain folders"
+        );
+
+        let github = build_script_filter_feedback_with_mode("", &config, ScriptFilterMode::Github);
+        let titles: Vec<&str> = github
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["alpha"], "github mode only lists git repos");
+    }
+
+    #[test]
+    fn code_workspace_rows_open_the_file_and_track_usage_by_its_path() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        let workspace_file = roots.join("alpha/stack.code-workspace");
+        fs::write(&workspace_file, "{\"folders\": []}").expect("write workspace file");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots.clone()],
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+        crate::usage_log::record_usage(&workspace_file, &config.usage_file).expect("record usage");
+
+        let feedback = build_script_filter_feedback("stack", &config);
+        let item = feedback.items.first().expect("workspace row");
+        assert_eq!(item.title, "stack");
+        assert_eq!(
+            item.arg.as_deref(),
+            Some(workspace_file.to_string_lossy().as_ref())
+        );
+        assert!(
+            item.subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.starts_with("VS Code workspace in alpha • 20")),
+            "usage keyed to the workspace file: {:?}",
+            item.subtitle
+        );
+        let icon = item.icon.as_ref().expect("workspace row icon");
+        assert_eq!(icon.r#type.as_deref(), Some("fileicon"));
+        let mods = item.mods.as_ref().expect("modifiers");
+        assert_eq!(mods["shift"].valid, Some(false));
+        let alpha = roots.join("alpha");
+        assert_eq!(
+            mods["cmd"].arg.as_deref(),
+            Some(alpha.to_string_lossy().as_ref()),
+            "terminal opens the workspace file's folder"
+        );
+    }
+
+    #[test]
+    fn extra_editors_map_to_alt_and_ctrl_modifiers() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            extra_editors: [
+                Some(crate::config::EditorTarget {
+                    label: "Cursor".to_string(),
+                    command: "cursor".to_string(),
+                }),
+                Some(crate::config::EditorTarget {
+                    label: "Zed".to_string(),
+                    command: "zed".to_string(),
+                }),
+            ],
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("", &config);
+        assert_eq!(
+            feedback.items[0]
+                .variables
+                .as_ref()
+                .and_then(|vars| vars.get(EDITOR_VARIABLE))
+                .map(String::as_str),
+            Some(config.vscode_path.as_str()),
+            "rows should carry the resolved default editor"
+        );
+        let mods = feedback.items[0].mods.as_ref().expect("mods should exist");
+        let alt = mods.get("alt").expect("alt editor modifier");
+        assert_eq!(alt.subtitle.as_deref(), Some("Open in Cursor"));
+        assert_eq!(
+            alt.variables
+                .as_ref()
+                .and_then(|vars| vars.get(EDITOR_VARIABLE))
+                .map(String::as_str),
+            Some("cursor")
+        );
+        let ctrl = mods.get("ctrl").expect("ctrl editor modifier");
+        assert_eq!(ctrl.subtitle.as_deref(), Some("Open in Zed"));
+
+        let github = build_script_filter_feedback_with_mode("", &config, ScriptFilterMode::Github);
+        let github_mods = github.items[0].mods.as_ref().expect("mods should exist");
+        assert!(
+            github_mods
+                .get("alt")
+                .and_then(|entry| entry.variables.as_ref())
+                .is_none_or(|vars| !vars.contains_key(EDITOR_VARIABLE)),
+            "github mode should not offer editor modifiers"
+        );
+    }
+
+    #[test]
+    fn editor_3_keeps_ctrl_when_editor_2_is_unset_or_invalid() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        let roots = roots.to_string_lossy().to_string();
+        let missing_file = temp.path().join("missing.toml");
+        let missing_file = missing_file.to_string_lossy().to_string();
+
+        for editor_2 in [None, Some(""), Some("  "), Some("Cursor=")] {
+            let config = RuntimeConfig::from_lookup(|key| match key {
+                "PROJECT_DIRS" => Some(roots.clone()),
+                "OPEN_PROJECT_CONFIG_FILE" => Some(missing_file.clone()),
+                "OPEN_PROJECT_EDITOR_2" => editor_2.map(ToOwned::to_owned),
+                "OPEN_PROJECT_EDITOR_3" => Some("Nova=nova".to_string()),
+                _ => None,
+            });
+
+            let feedback = build_script_filter_feedback("", &config);
+            let mods = feedback.items[0].mods.as_ref().expect("mods should exist");
+            let ctrl = mods.get("ctrl").expect("editor 3 stays on ctrl");
+            assert_eq!(ctrl.subtitle.as_deref(), Some("Open in Nova"));
+            assert!(mods.get("alt").is_none(), "editor 2 slot is empty");
+        }
+    }
+
+    #[test]
+    fn empty_query_groups_recent_projects_under_section_headers() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        for name in ["alpha", "beta", "gamma", "delta", "pinned"] {
+            init_repo(&roots.join(name));
+        }
+
+        let now = chrono::Local::now().naive_local();
+        let at = |hours: i64| {
+            (now - chrono::Duration::hours(hours))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        };
+        let usage_file = temp.path().join("usage.log");
+        fs::write(
+            &usage_file,
+            format!(
+                "{} | {} | 50\n{} | {} | 1\n{} | {} | 2\n{} | {} | 9\n",
+                roots.join("alpha").to_string_lossy(),
+                at(48),
+                roots.join("beta").to_string_lossy(),
+                at(1),
+                roots.join("gamma").to_string_lossy(),
+                at(5),
+                roots.join("pinned").to_string_lossy(),
+                at(0),
+            ),
+        )
+        .expect("write usage file");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots.clone()],
+            usage_file,
+            recent_count: 2,
+            pins: vec![roots.join("pinned")],
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("", &config);
+        let titles: Vec<&str> = feedback
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Pinned",
+                "pinned",
+                "Recently used",
+                "beta",
+                "gamma",
+                "All projects",
+                "alpha",
+                "delta"
+            ]
+        );
+        for header in [0, 2, 5] {
+            assert_eq!(feedback.items[header].valid, Some(false));
+            assert!(feedback.items[header].arg.is_none());
+        }
+
+        let capped = build_script_filter_feedback(
+            "",
+            &RuntimeConfig {
+                max_results: 2,
+                pins: Vec::new(),
+                ..config.clone()
+            },
+        );
+        let titles: Vec<&str> = capped
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec!["Recently used", "pinned", "beta"],
+            "headers do not count toward max_results"
+        );
+
+        let filtered = build_script_filter_feedback("a", &config);
+        assert!(
+            filtered.items.iter().all(|item| item.valid != Some(false)),
+            "typed queries stay one flat ranked list"
+        );
+    }
+
+    #[test]
+    fn pinned_projects_lead_in_pin_order() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        for name in ["alpha", "beta", "gamma"] {
+            init_repo(&roots.join(name));
+        }
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots.clone()],
+            usage_file: temp.path().join("usage.log"),
+            pins: vec![roots.join("gamma"), roots.join("beta")],
+            recent_count: 0,
+            ..RuntimeConfig::default()
+        };
+        crate::usage_log::record_usage(&roots.join("alpha"), &config.usage_file)
+            .expect("record usage");
+
+        let feedback = build_script_filter_feedback("", &config);
+        let titles: Vec<&str> = feedback
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["gamma", "beta", "alpha"]);
+        assert!(
+            feedback.items[0]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.starts_with("Pinned • "))
+        );
+
+        let filtered = build_script_filter_feedback("a", &config);
+        assert_eq!(filtered.items[0].title, "gamma", "pins lead among matches");
+    }
+
+    #[test]
+    fn archived_projects_only_appear_under_all_scope() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        for name in ["alpha", "beta", "legacy"] {
+            init_repo(&roots.join(name));
+        }
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots.clone()],
+            usage_file: temp.path().join("usage.log"),
+            recent_count: 0,
+            ..RuntimeConfig::default()
+        };
+        crate::usage_log::archive_project(&config.usage_file, &roots.join("legacy"))
+            .expect("archive legacy");
+        let titles = |query: &str| {
+            build_script_filter_feedback(query, &config)
+                .items
+                .into_iter()
+                .map(|item| item.title)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(titles(""), vec!["alpha", "beta"]);
+        assert_eq!(titles("legacy"), vec![NO_PROJECTS_TITLE]);
+        assert_eq!(titles("all "), vec!["alpha", "beta", "legacy"]);
+        assert_eq!(titles("all leg"), vec!["legacy"]);
+        assert_eq!(split_archive_scope("all"), ("all", false));
+        assert_eq!(split_archive_scope("allegro"), ("allegro", false));
+
+        let archived = build_script_filter_feedback("all leg", &config);
+        assert!(
+            archived.items[0]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.starts_with(ARCHIVED_SUBTITLE_PREFIX))
+        );
+    }
+
+    #[test]
+    fn exact_alias_jumps_to_its_project() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        init_repo(&roots.join("wkbench"));
+        let outside = temp.path().join("elsewhere/notes");
+        init_repo(&outside);
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots.clone()],
+            usage_file: temp.path().join("usage.log"),
+            aliases: [
+                ("wk".to_string(), roots.join("alpha")),
+                ("nt".to_string(), outside.clone()),
+            ]
+            .into_iter()
+            .collect(),
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("wk", &config);
+        let titles: Vec<&str> = feedback
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(
+            titles,
+            vec!["alpha", "wkbench"],
+            "alias row first, then matches"
+        );
+
+        let upper = build_script_filter_feedback("WK", &config);
+        assert_eq!(upper.items[0].title, "alpha", "aliases ignore case");
+
+        let outside_feedback = build_script_filter_feedback("nt", &config);
+        assert_eq!(outside_feedback.items[0].title, "notes");
+        assert_eq!(
+            outside_feedback.items[0].arg.as_deref(),
+            Some(outside.to_string_lossy().as_ref())
+        );
+    }
+
+    #[test]
+    fn git_status_prefix_is_rendered_only_when_enabled() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        let alpha = roots.join("alpha");
+        init_repo(&alpha);
+        fs::write(alpha.join("scratch.txt"), "wip").expect("write untracked file");
+
+        let mut config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            // Generous budget: the production 250 ms cap is too tight for a loaded CI runner.
+            git_status_timeout: Duration::from_secs(10),
+            ..RuntimeConfig::default()
+        };
+
+        let plain = build_script_filter_feedback("", &config);
+        assert_eq!(
+            plain.items[0].subtitle.as_deref(),
+            Some("No recent commits • N/A")
+        );
+
+        config.show_git_status = true;
+        let enriched = build_script_filter_feedback("", &config);
+        let subtitle = enriched.items[0].subtitle.as_deref().expect("subtitle");
+        assert!(
+            subtitle.ends_with("* • No recent commits • N/A"),
+            "dirty branch label should prefix the subtitle: {subtitle}"
+        );
+    }
+
+    #[test]
+    fn worktrees_are_grouped_under_primary_with_branch_label() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        let alpha = roots.join("alpha");
+        init_repo(&alpha);
+        init_repo(&roots.join("beta"));
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&alpha)
+            .args([
+                "-c",
+                "user.name=tester",
+                "-c",
+                "user.email=tester@example.com",
+            ])
+            .args(["commit", "-q", "--allow-empty", "-m", "init"])
+            .status()
+            .expect("commit");
+        assert!(status.success(), "git commit should succeed");
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&alpha)
+            .args(["worktree", "add", "-q", "-b", "feature"])
+            .arg(roots.join("zeta-wt"))
+            .status()
+            .expect("add worktree");
+        assert!(status.success(), "git worktree add should succeed");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("", &config);
+        let titles: Vec<&str> = feedback
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["alpha", "zeta-wt", "beta"]);
+
+        let subtitle = feedback.items[1].subtitle.as_deref().expect("subtitle");
+        assert!(
+            subtitle.starts_with("⎇ feature (worktree of alpha) • init (by tester, "),
+            "unexpected worktree subtitle: {subtitle}"
+        );
+    }
+
+    #[test]
+    fn workspace_members_are_grouped_under_their_repo() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        let mono = roots.join("mono");
+        init_repo(&mono);
+        init_repo(&roots.join("beta"));
+        fs::write(
+            mono.join("package.json"),
+            r#"{"workspaces":["packages/*"]}"#,
+        )
+        .expect("write workspace manifest");
+        fs::create_dir_all(mono.join("packages/web")).expect("create member");
+        fs::write(mono.join("packages/web/package.json"), "{}").expect("write member");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            workspace_members: true,
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("", &config);
+        let titles: Vec<&str> = feedback
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["beta", "mono", "mono › web"]);
+
+        let member = build_script_filter_feedback("web", &config);
+        let item = member.items.first().expect("member row");
+        assert_eq!(item.title, "mono › web");
+        assert_eq!(
+            item.subtitle.as_deref(),
+            Some("npm workspace member of mono • N/A")
+        );
+        assert!(
+            item.arg
+                .as_deref()
+                .is_some_and(|arg| arg.ends_with("mono/packages/web"))
+        );
+
+        let github =
+            build_script_filter_feedback_with_mode("web", &config, ScriptFilterMode::Github);
+        assert!(is_no_projects_feedback(&github), "members are not repos");
+    }
+
+    #[test]
+    fn grep_intent_renders_hint_rows_before_searching() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            rg_path: Some(temp.path().join("missing-rg").to_string_lossy().to_string()),
+            ..RuntimeConfig::default()
+        };
+
+        let short = build_script_filter_feedback("grep x", &config);
+        assert_eq!(short.items[0].title, "Search projects with ripgrep");
+        assert_eq!(short.items[0].valid, Some(false));
+
+        let unknown = build_script_filter_feedback("grep @nope needle", &config);
+        assert_eq!(unknown.items[0].title, "No project matches @nope");
+
+        let missing = build_script_filter_feedback("grep @alpha needle", &config);
+        assert_eq!(missing.items[0].title, "ripgrep (rg) is not available");
+        assert!(
+            missing.items[0]
+                .subtitle
+                .as_deref()
+                .is_some_and(|subtitle| subtitle.ends_with("set OPEN_PROJECT_RG_PATH"))
+        );
+
+        let github =
+            build_script_filter_feedback_with_mode("grep alpha", &config, ScriptFilterMode::Github);
+        assert!(
+            is_no_projects_feedback(&github),
+            "github mode treats grep as a plain filter"
+        );
+    }
+
+    #[test]
+    fn remote_ssh_entries_render_with_remote_uri_and_no_local_actions() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            remote_projects: vec!["devbox:/srv/api".to_string()],
+            ..RuntimeConfig::default()
+        };
+
+        let feedback = build_script_filter_feedback("devbox", &config);
+        let item = feedback.items.first().expect("remote row");
+        assert_eq!(item.title, "devbox:api");
+        assert_eq!(item.arg.as_deref(), Some("devbox:/srv/api"));
+        assert_eq!(item.subtitle.as_deref(), Some("SSH devbox • N/A"));
+        assert!(item.icon.is_none(), "no local file icon for remote rows");
+        let variables = item.variables.as_ref().expect("row variables");
+        assert_eq!(
+            variables.get(REMOTE_URI_VARIABLE).map(String::as_str),
+            Some("vscode-remote://ssh-remote+devbox/srv/api")
+        );
+        let mods = item.mods.as_ref().expect("remote row modifiers");
+        for modifier in ["shift", "cmd", "alt", "ctrl"] {
+            assert_eq!(
+                mods.get(modifier).and_then(|entry| entry.valid),
+                Some(false)
+            );
+        }
+
+        let all = build_script_filter_feedback("", &config);
+        assert_eq!(all.items.len(), 2, "remote rows list next to local repos");
+        let github = build_script_filter_feedback_with_mode("", &config, ScriptFilterMode::Github);
+        assert_eq!(github.items.len(), 1, "github mode skips remote rows");
+    }
+
+    #[test]
+    fn every_row_offers_terminal_on_cmd_modifier() {
+        let temp = tempdir().expect("create temp dir");
+        let roots = temp.path().join("roots");
+        init_repo(&roots.join("alpha"));
+        fs::create_dir_all(roots.join("tool")).expect("create marker dir");
+        fs::write(roots.join("tool/go.mod"), "module tool\n").expect("write marker");
+
+        let config = RuntimeConfig {
+            project_roots: vec![roots],
+            usage_file: temp.path().join("usage.log"),
+            terminal_app: crate::terminal::TerminalApp::WezTerm,
+            ..RuntimeConfig::default()
+        };
+
+        for mode in [ScriptFilterMode::Open, ScriptFilterMode::Github] {
+            let feedback = build_script_filter_feedback_with_mode("", &config, mode);
+            for item in &feedback.items {
+                let cmd = item
+                    .mods
+                    .as_ref()
+                    .and_then(|mods| mods.get("cmd"))
+                    .expect("cmd terminal modifier");
+                assert_eq!(cmd.subtitle.as_deref(), Some("Open in WezTerm"));
+                assert_eq!(cmd.arg, item.arg);
+                assert_eq!(cmd.valid, Some(true));
+            }
+        }
+    }
+
     fn init_repo(path: &Path) {
         fs::create_dir_all(path).expect("create repo dir");
         let status = Command::new("git")
@@ -454,6 +1817,7 @@ mod tests {
             usage_file: temp.path().join("usage.log"),
             vscode_path: "code".to_string(),
             max_results: 2,
+            ..RuntimeConfig::default()
         };
 
         let feedback = build_script_filter_feedback("", &config);
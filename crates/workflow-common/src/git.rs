@@ -1,7 +1,151 @@
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::error::WorkflowError;
+use crate::list_parser::split_ordered_list;
+
+/// Upper bound for a single `git status` probe; slower repos render without status.
+pub const GIT_STATUS_TIMEOUT: Duration = Duration::from_millis(250);
+const GIT_STATUS_WORKERS: usize = 8;
+const GIT_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Working-tree snapshot parsed from `git status --porcelain=v2 --branch`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitStatus {
+    /// Checked-out branch; `None` for a detached HEAD.
+    pub branch: Option<String>,
+    pub dirty: bool,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+impl GitStatus {
+    /// Compact label such as `main* ↑2 ↓1` (`*` marks uncommitted changes).
+    pub fn label(&self) -> String {
+        let mut label = self.branch.as_deref().unwrap_or("detached").to_string();
+        if self.dirty {
+            label.push('*');
+        }
+        if self.ahead > 0 {
+            label.push_str(&format!(" ↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            label.push_str(&format!(" ↓{}", self.behind));
+        }
+        label
+    }
+}
+
+/// Probe branch/dirty/ahead-behind state, giving up after `timeout`.
+pub fn git_status(project_path: &Path, timeout: Duration) -> Option<GitStatus> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(project_path)
+        .args([
+            "--no-optional-locks",
+            "status",
+            "--porcelain=v2",
+            "--branch",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Drain stdout on a side thread so a large status listing cannot fill the pipe
+    // and stall the child past the deadline.
+    let mut stdout = child.stdout.take()?;
+    let reader = thread::spawn(move || {
+        let mut buffer = String::new();
+        stdout.read_to_string(&mut buffer).map(|_| buffer)
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(GIT_STATUS_POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    let output = reader.join().ok()?.ok()?;
+    if !status.success() {
+        return None;
+    }
+
+    Some(parse_porcelain_v2_status(&output))
+}
+
+/// Probe several repositories concurrently; results keep the input order.
+pub fn git_statuses(project_paths: &[&Path], timeout: Duration) -> Vec<Option<GitStatus>> {
+    let workers = GIT_STATUS_WORKERS.min(project_paths.len());
+    let queue = Mutex::new(project_paths.iter().enumerate());
+    let mut statuses = vec![None; project_paths.len()];
+
+    let probed = thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut local = Vec::new();
+                    loop {
+                        let next = match queue.lock() {
+                            Ok(mut pending) => pending.next(),
+                            Err(_) => None,
+                        };
+                        let Some((index, path)) = next else {
+                            break;
+                        };
+                        local.push((index, git_status(path, timeout)));
+                    }
+                    local
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect::<Vec<_>>()
+    });
+
+    for (index, status) in probed {
+        statuses[index] = status;
+    }
+    statuses
+}
+
+fn parse_porcelain_v2_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            let head = head.trim();
+            if head != "(detached)" {
+                status.branch = Some(head.to_string());
+            }
+        } else if let Some(counts) = line.strip_prefix("# branch.ab ") {
+            for count in counts.split_whitespace() {
+                if let Some(ahead) = count.strip_prefix('+') {
+                    status.ahead = ahead.parse().unwrap_or(0);
+                } else if let Some(behind) = count.strip_prefix('-') {
+                    status.behind = behind.parse().unwrap_or(0);
+                }
+            }
+        } else if !line.starts_with('#') && !line.trim().is_empty() {
+            status.dirty = true;
+        }
+    }
+    status
+}
 
 pub fn last_commit_summary(project_path: &Path) -> Option<String> {
     let output = Command::new("git")
@@ -26,9 +170,173 @@ pub fn last_commit_summary(project_path: &Path) -> Option<String> {
     }
 }
 
+/// Hosting flavor that decides how a remote path maps to a web URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    /// `github.com` or GitHub Enterprise: path must be exactly `owner/repo`.
+    GitHub,
+    /// GitLab (including subgroups): two or more path segments.
+    GitLab,
+    /// Bitbucket Cloud: exactly `workspace/repo`.
+    Bitbucket,
+    /// Bitbucket Server / Data Center: `PROJECT/repo` maps to `/projects/PROJECT/repos/repo`.
+    BitbucketServer,
+    /// Any other host whose web URL mirrors the clone path (Gitea, Codeberg, Gogs, ...).
+    Generic,
+}
+
+impl Forge {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "github" => Some(Self::GitHub),
+            "gitlab" => Some(Self::GitLab),
+            "bitbucket" => Some(Self::Bitbucket),
+            "bitbucket-server" | "bitbucket_server" => Some(Self::BitbucketServer),
+            "generic" | "gitea" | "gogs" | "forgejo" => Some(Self::Generic),
+            _ => None,
+        }
+    }
+}
+
+impl Forge {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::GitHub => "GitHub",
+            Self::GitLab => "GitLab",
+            Self::Bitbucket => "Bitbucket",
+            Self::BitbucketServer => "Bitbucket Server",
+            Self::Generic => "Git host",
+        }
+    }
+
+    /// Path below the repository web URL for `page`; `None` when the forge has no such page.
+    pub const fn page_path(self, page: ForgePage) -> Option<&'static str> {
+        match (self, page) {
+            (Self::GitHub | Self::Generic, ForgePage::PullRequests) => Some("pulls"),
+            (Self::GitHub | Self::Generic, ForgePage::Issues) => Some("issues"),
+            (Self::GitHub | Self::Generic, ForgePage::Ci) => Some("actions"),
+            (Self::GitLab, ForgePage::PullRequests) => Some("-/merge_requests"),
+            (Self::GitLab, ForgePage::Issues) => Some("-/issues"),
+            (Self::GitLab, ForgePage::Ci) => Some("-/pipelines"),
+            (Self::Bitbucket | Self::BitbucketServer, ForgePage::PullRequests) => {
+                Some("pull-requests")
+            }
+            (Self::Bitbucket, ForgePage::Issues) => Some("issues"),
+            (Self::Bitbucket, ForgePage::Ci) => Some("pipelines"),
+            // Bitbucket Server delegates issue tracking to Jira.
+            (Self::BitbucketServer, ForgePage::Issues) => None,
+            (Self::BitbucketServer, ForgePage::Ci) => Some("builds"),
+        }
+    }
+}
+
+/// Repository sub-page derived from the canonical web URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgePage {
+    PullRequests,
+    Issues,
+    Ci,
+}
+
+impl ForgePage {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::PullRequests => "pr",
+            Self::Issues => "issues",
+            Self::Ci => "ci",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "pr" | "prs" | "pulls" | "mr" | "mrs" => Some(Self::PullRequests),
+            "issues" => Some(Self::Issues),
+            "ci" | "actions" | "pipelines" | "builds" => Some(Self::Ci),
+            _ => None,
+        }
+    }
+}
+
+/// Self-hosted host pinned to a forge via `OPEN_PROJECT_FORGE_HOSTS` (`host=forge`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForgeHost {
+    pub host: String,
+    pub forge: Forge,
+}
+
+/// Parse a comma/newline separated `host=forge` list; malformed tokens are skipped.
+pub fn parse_forge_hosts(raw: &str) -> Vec<ForgeHost> {
+    split_ordered_list(raw)
+        .iter()
+        .filter_map(|token| {
+            let (host, forge) = token.split_once('=')?;
+            let host = host.trim().to_ascii_lowercase();
+            if host.is_empty() {
+                return None;
+            }
+            Some(ForgeHost {
+                host,
+                forge: Forge::parse(forge)?,
+            })
+        })
+        .collect()
+}
+
+/// Configured hosts win; otherwise well-known hosts are recognized by name.
+pub fn detect_forge(host: &str, forge_hosts: &[ForgeHost]) -> Forge {
+    let host = host.to_ascii_lowercase();
+    if let Some(configured) = forge_hosts.iter().find(|entry| entry.host == host) {
+        return configured.forge;
+    }
+
+    match host.as_str() {
+        "github.com" => Forge::GitHub,
+        "bitbucket.org" => Forge::Bitbucket,
+        _ if host
+            .split('.')
+            .any(|segment| segment.eq_ignore_ascii_case("gitlab")) =>
+        {
+            Forge::GitLab
+        }
+        _ => Forge::Generic,
+    }
+}
+
 pub fn web_url_for_project(project_path: &Path) -> Result<String, WorkflowError> {
+    web_url_for_project_with(project_path, &[])
+}
+
+pub fn web_url_for_project_with(
+    project_path: &Path,
+    forge_hosts: &[ForgeHost],
+) -> Result<String, WorkflowError> {
     let remote_url = origin_remote_url(project_path)?;
-    normalize_remote(&remote_url)
+    normalize_remote_with(&remote_url, forge_hosts)
+}
+
+/// Web URL of a repository sub-page (pull requests, issues, CI) for the project's origin.
+pub fn forge_page_url_for_project(
+    project_path: &Path,
+    forge_hosts: &[ForgeHost],
+    page: ForgePage,
+) -> Result<String, WorkflowError> {
+    let remote_url = origin_remote_url(project_path)?;
+    let parsed = parse_remote_url(&remote_url)
+        .ok_or_else(|| WorkflowError::UnsupportedRemote(remote_url.clone()))?;
+    let forge = detect_forge(&parsed.host, forge_hosts);
+    let web_url = build_web_url(&parsed, forge, &remote_url)?;
+    forge_page_url(&web_url, forge, page)
+}
+
+pub fn forge_page_url(
+    web_url: &str,
+    forge: Forge,
+    page: ForgePage,
+) -> Result<String, WorkflowError> {
+    let suffix = forge
+        .page_path(page)
+        .ok_or(WorkflowError::UnsupportedForgePage { forge, page })?;
+    Ok(format!("{}/{suffix}", web_url.trim_end_matches('/')))
 }
 
 pub fn remote_host_for_project(project_path: &Path) -> Option<String> {
@@ -37,6 +345,11 @@ pub fn remote_host_for_project(project_path: &Path) -> Option<String> {
         .and_then(|remote_url| remote_host_from_url(&remote_url))
 }
 
+/// Forge of the project's `origin`, or `None` without a parseable origin.
+pub fn forge_for_project(project_path: &Path, forge_hosts: &[ForgeHost]) -> Option<Forge> {
+    remote_host_for_project(project_path).map(|host| detect_forge(&host, forge_hosts))
+}
+
 pub fn remote_host_from_url(remote_url: &str) -> Option<String> {
     parse_remote_url(remote_url).map(|parsed| parsed.host)
 }
@@ -75,19 +388,30 @@ fn origin_remote_url(project_path: &Path) -> Result<String, WorkflowError> {
 
 /// Normalize a git remote URL to its canonical web URL.
 ///
-/// Assumes `https://<host>/<path>` mirrors the clone URL — the standard layout for
-/// GitHub, GitLab (including subgroups), Gitea, Bitbucket, Codeberg, and Gogs.
-/// `github.com` is the single strict case (path must be exactly `owner/repo`);
-/// every other host accepts two or more path segments to allow GitLab-style subgroups.
+/// Accepts scp-style (`user@host:path`), `ssh://`, `https://`, and `http://` remotes, with
+/// optional user info and ports. The forge (see [`detect_forge`]) decides the path rules:
+/// GitHub and Bitbucket Cloud need exactly two segments, Bitbucket Server rewrites
+/// `PROJECT/repo` to its `/projects/.../repos/...` page, and everything else accepts two or
+/// more segments (GitLab subgroups, Gitea organizations, ...).
 pub fn normalize_remote(remote_url: &str) -> Result<String, WorkflowError> {
+    normalize_remote_with(remote_url, &[])
+}
+
+pub fn normalize_remote_with(
+    remote_url: &str,
+    forge_hosts: &[ForgeHost],
+) -> Result<String, WorkflowError> {
     let parsed = parse_remote_url(remote_url)
         .ok_or_else(|| WorkflowError::UnsupportedRemote(remote_url.to_string()))?;
-    build_web_url(&parsed, remote_url)
+    let forge = detect_forge(&parsed.host, forge_hosts);
+    build_web_url(&parsed, forge, remote_url)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ParsedRemote {
     host: String,
+    /// Explicit port of an `http(s)` remote; SSH ports never apply to the web UI.
+    web_port: Option<String>,
     path: String,
 }
 
@@ -97,26 +421,47 @@ fn parse_remote_url(remote_url: &str) -> Option<ParsedRemote> {
         return None;
     }
 
-    if let Some(rest) = trimmed.strip_prefix("git@") {
-        let (host, path) = rest.split_once(':')?;
-        return build_parsed(host, path);
+    for scheme in ["https://", "http://"] {
+        if let Some(rest) = trimmed.strip_prefix(scheme) {
+            let (authority, path) = rest.split_once('/')?;
+            let (host, port) = split_authority(authority);
+            return build_parsed(host, port, path);
+        }
     }
 
-    if let Some(rest) = trimmed.strip_prefix("ssh://git@") {
-        let (host_part, path) = rest.split_once('/')?;
-        let host = host_part.split(':').next().unwrap_or(host_part);
-        return build_parsed(host, path);
+    if let Some(rest) = trimmed.strip_prefix("ssh://") {
+        let (authority, path) = rest.split_once('/')?;
+        let (host, _) = split_authority(authority);
+        return build_parsed(host, None, path);
     }
 
-    if let Some(rest) = trimmed.strip_prefix("https://") {
-        let (host, path) = rest.split_once('/')?;
-        return build_parsed(host, path);
+    // scp-like syntax: `[user@]host:path`.
+    if trimmed.contains("://") {
+        return None;
+    }
+    let (authority, path) = trimmed.split_once(':')?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    if host.contains(char::is_whitespace) || host.contains('/') {
+        return None;
     }
+    build_parsed(host, None, path)
+}
 
-    None
+/// Split `[user[:pass]@]host[:port]` into host and port.
+fn split_authority(authority: &str) -> (&str, Option<&str>) {
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    match host_port.split_once(':') {
+        Some((host, port)) if !port.is_empty() => (host, Some(port)),
+        Some((host, _)) => (host, None),
+        None => (host_port, None),
+    }
 }
 
-fn build_parsed(host: &str, path: &str) -> Option<ParsedRemote> {
+fn build_parsed(host: &str, port: Option<&str>, path: &str) -> Option<ParsedRemote> {
     let host = host.trim();
     let path = trim_repo_suffix(path);
     if host.is_empty() || path.is_empty() {
@@ -124,33 +469,58 @@ fn build_parsed(host: &str, path: &str) -> Option<ParsedRemote> {
     }
     Some(ParsedRemote {
         host: host.to_ascii_lowercase(),
+        web_port: port.map(ToString::to_string),
         path,
     })
 }
 
 fn trim_repo_suffix(raw: &str) -> String {
     raw.trim()
+        .trim_start_matches('/')
         .trim_end_matches('/')
         .trim_end_matches(".git")
         .trim_end_matches('/')
         .to_string()
 }
 
-fn build_web_url(parsed: &ParsedRemote, raw_url: &str) -> Result<String, WorkflowError> {
-    let segment_count = parsed
+fn build_web_url(
+    parsed: &ParsedRemote,
+    forge: Forge,
+    raw_url: &str,
+) -> Result<String, WorkflowError> {
+    let unsupported = || WorkflowError::UnsupportedRemote(raw_url.to_string());
+    let mut segments = parsed
         .path
         .split('/')
         .filter(|segment| !segment.is_empty())
-        .count();
-
-    if segment_count < 2 {
-        return Err(WorkflowError::UnsupportedRemote(raw_url.to_string()));
+        .collect::<Vec<_>>();
+
+    let origin = match &parsed.web_port {
+        Some(port) => format!("https://{}:{port}", parsed.host),
+        None => format!("https://{}", parsed.host),
+    };
+
+    match forge {
+        Forge::GitHub | Forge::Bitbucket if segments.len() != 2 => Err(unsupported()),
+        Forge::BitbucketServer => {
+            // HTTPS clone URLs carry a `/scm/` prefix that the web UI does not use.
+            if segments.first() == Some(&"scm") {
+                segments.remove(0);
+            }
+            let [owner, repo] = segments.as_slice() else {
+                return Err(unsupported());
+            };
+            Ok(match owner.strip_prefix('~') {
+                Some(user) => format!("{origin}/users/{user}/repos/{repo}"),
+                None => format!(
+                    "{origin}/projects/{}/repos/{repo}",
+                    owner.to_ascii_uppercase()
+                ),
+            })
+        }
+        _ if segments.len() < 2 => Err(unsupported()),
+        _ => Ok(format!("{origin}/{}", segments.join("/"))),
     }
-    if parsed.host == "github.com" && segment_count != 2 {
-        return Err(WorkflowError::UnsupportedRemote(raw_url.to_string()));
-    }
-
-    Ok(format!("https://{}/{}", parsed.host, parsed.path))
 }
 
 #[cfg(test)]
@@ -162,6 +532,56 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn porcelain_v2_parses_branch_dirty_and_ahead_behind() {
+        let clean = parse_porcelain_v2_status(
+            "# branch.oid abc123\n# branch.head main\n# branch.upstream origin/main\n# branch.ab +2 -1\n",
+        );
+        assert_eq!(
+            clean,
+            GitStatus {
+                branch: Some("main".to_string()),
+                dirty: false,
+                ahead: 2,
+                behind: 1,
+            }
+        );
+        assert_eq!(clean.label(), "main ↑2 ↓1");
+
+        let dirty = parse_porcelain_v2_status(
+            "# branch.oid abc123\n# branch.head (detached)\n1 .M N... 100644 100644 100644 a b src/lib.rs\n? notes.txt\n",
+        );
+        assert_eq!(dirty.branch, None);
+        assert!(dirty.dirty);
+        assert_eq!(dirty.label(), "detached*");
+    }
+
+    #[test]
+    fn git_status_probes_real_repository() {
+        let temp = tempdir().expect("create temp dir");
+        let repo = temp.path().join("repo");
+        fs::create_dir_all(&repo).expect("create repo dir");
+        let status = Command::new("git")
+            .args(["init", "-q", "-b", "trunk"])
+            .arg(&repo)
+            .status()
+            .expect("run git init");
+        assert!(status.success(), "git init should succeed");
+
+        let clean = git_status(&repo, Duration::from_secs(5)).expect("status should be probed");
+        assert_eq!(clean.branch.as_deref(), Some("trunk"));
+        assert!(!clean.dirty);
+
+        fs::write(repo.join("new.txt"), "x").expect("write untracked file");
+        let statuses = git_statuses(
+            &[repo.as_path(), temp.path().join("missing").as_path()],
+            Duration::from_secs(5),
+        );
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses[0].as_ref().is_some_and(|status| status.dirty));
+        assert_eq!(statuses[1], None, "non-repos render without status");
+    }
+
     #[test]
     fn github_remote_normalizes_ssh_and_https_formats() {
         let ssh =
@@ -237,6 +657,106 @@ mod tests {
         assert!(matches!(err, WorkflowError::UnsupportedRemote(_)));
     }
 
+    #[test]
+    fn bitbucket_cloud_remotes_normalize() {
+        let ssh = normalize_remote("git@bitbucket.org:workspace/service.git")
+            .expect("bitbucket ssh should resolve");
+        assert_eq!(ssh, "https://bitbucket.org/workspace/service");
+
+        let https = normalize_remote("https://dev@bitbucket.org/workspace/service.git")
+            .expect("bitbucket https with user info should resolve");
+        assert_eq!(https, "https://bitbucket.org/workspace/service");
+
+        let err = normalize_remote("git@bitbucket.org:workspace/group/service.git")
+            .expect_err("bitbucket cloud has no subgroups");
+        assert!(matches!(err, WorkflowError::UnsupportedRemote(_)));
+    }
+
+    #[test]
+    fn configured_forge_hosts_drive_normalization() {
+        let hosts = parse_forge_hosts(
+            "git.corp.example=gitlab, code.corp.example=bitbucket-server,\nghe.corp.example=github, bad, x=unknown",
+        );
+        assert_eq!(hosts.len(), 3, "malformed and unknown entries are skipped");
+        assert_eq!(detect_forge("GIT.corp.example", &hosts), Forge::GitLab);
+
+        let server =
+            normalize_remote_with("ssh://git@code.corp.example:7999/plat/billing.git", &hosts)
+                .expect("bitbucket server ssh should resolve");
+        assert_eq!(
+            server,
+            "https://code.corp.example/projects/PLAT/repos/billing"
+        );
+
+        let server_https = normalize_remote_with(
+            "https://alice@code.corp.example:8443/scm/~alice/dotfiles.git",
+            &hosts,
+        )
+        .expect("bitbucket server https personal repo should resolve");
+        assert_eq!(
+            server_https,
+            "https://code.corp.example:8443/users/alice/repos/dotfiles"
+        );
+
+        let err = normalize_remote_with("git@ghe.corp.example:org/team/repo.git", &hosts)
+            .expect_err("github enterprise keeps the owner/repo rule");
+        assert!(matches!(err, WorkflowError::UnsupportedRemote(_)));
+    }
+
+    #[test]
+    fn forge_pages_follow_each_forge_layout() {
+        let github = "https://github.com/owner/repo";
+        assert_eq!(
+            forge_page_url(github, Forge::GitHub, ForgePage::PullRequests).expect("pulls"),
+            "https://github.com/owner/repo/pulls"
+        );
+        assert_eq!(
+            forge_page_url(github, Forge::GitHub, ForgePage::Ci).expect("actions"),
+            "https://github.com/owner/repo/actions"
+        );
+
+        let gitlab = "https://gitlab.com/group/sub/repo";
+        assert_eq!(
+            forge_page_url(gitlab, Forge::GitLab, ForgePage::PullRequests).expect("mrs"),
+            "https://gitlab.com/group/sub/repo/-/merge_requests"
+        );
+        assert_eq!(
+            forge_page_url(gitlab, Forge::GitLab, ForgePage::Issues).expect("issues"),
+            "https://gitlab.com/group/sub/repo/-/issues"
+        );
+
+        let bitbucket = "https://bitbucket.org/ws/repo";
+        assert_eq!(
+            forge_page_url(bitbucket, Forge::Bitbucket, ForgePage::Ci).expect("pipelines"),
+            "https://bitbucket.org/ws/repo/pipelines"
+        );
+
+        let err = forge_page_url(
+            "https://code.corp/projects/P/repos/r",
+            Forge::BitbucketServer,
+            ForgePage::Issues,
+        )
+        .expect_err("bitbucket server has no issues page");
+        assert!(matches!(err, WorkflowError::UnsupportedForgePage { .. }));
+    }
+
+    #[test]
+    fn forge_page_parse_accepts_aliases() {
+        assert_eq!(ForgePage::parse("pr"), Some(ForgePage::PullRequests));
+        assert_eq!(ForgePage::parse("MR"), Some(ForgePage::PullRequests));
+        assert_eq!(ForgePage::parse("issues"), Some(ForgePage::Issues));
+        assert_eq!(ForgePage::parse("pipelines"), Some(ForgePage::Ci));
+        assert_eq!(ForgePage::parse("wiki"), None);
+    }
+
+    #[test]
+    fn well_known_hosts_are_detected_without_configuration() {
+        assert_eq!(detect_forge("github.com", &[]), Forge::GitHub);
+        assert_eq!(detect_forge("gitlab.example.com", &[]), Forge::GitLab);
+        assert_eq!(detect_forge("bitbucket.org", &[]), Forge::Bitbucket);
+        assert_eq!(detect_forge("codeberg.org", &[]), Forge::Generic);
+    }
+
     #[test]
     fn malformed_remote_returns_unsupported_remote() {
         let err = normalize_remote("not a remote").expect_err("garbage input should fail");
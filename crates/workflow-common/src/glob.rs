@@ -0,0 +1,123 @@
+//! Minimal glob matching for discovery ignore rules.
+//!
+//! Supported syntax: `*` (any run within one path component), `**` (any run across
+//! components), and `?` (one character). A trailing `/**` marks a directory whose whole
+//! subtree is pruned. Patterns without `/` match a single path component anywhere in the
+//! tree (gitignore-style); patterns with `/` match the path relative to the scan root.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IgnoreGlob {
+    pattern: String,
+    anchored: bool,
+}
+
+impl IgnoreGlob {
+    pub fn new(raw: &str) -> Option<Self> {
+        let trimmed = raw.trim().trim_start_matches("./");
+        let pattern = trimmed
+            .strip_suffix("/**")
+            .unwrap_or(trimmed)
+            .trim_end_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            anchored: pattern.contains('/'),
+            pattern: pattern.to_string(),
+        })
+    }
+
+    /// Test a root-relative path (components joined with `/`).
+    pub fn matches(&self, relative_path: &str) -> bool {
+        if self.anchored {
+            return wildcard_match(self.pattern.as_bytes(), relative_path.as_bytes());
+        }
+
+        relative_path
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| wildcard_match(self.pattern.as_bytes(), name.as_bytes()))
+    }
+}
+
+/// Match a `/`-joined relative path against `pattern` as a whole (always anchored).
+pub fn glob_match_path(pattern: &str, relative_path: &str) -> bool {
+    wildcard_match(pattern.as_bytes(), relative_path.as_bytes())
+}
+
+pub fn parse_ignore_globs<S: AsRef<str>>(raw: &[S]) -> Vec<IgnoreGlob> {
+    raw.iter()
+        .filter_map(|pattern| IgnoreGlob::new(pattern.as_ref()))
+        .collect()
+}
+
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) if rest.first() == Some(&b'*') => {
+            let rest = rest[1..].strip_prefix(b"/").unwrap_or(&rest[1..]);
+            (0..=text.len()).any(|skip| wildcard_match(rest, &text[skip..]))
+        }
+        Some((b'*', rest)) => {
+            let limit = text
+                .iter()
+                .position(|byte| *byte == b'/')
+                .unwrap_or(text.len());
+            (0..=limit).any(|skip| wildcard_match(rest, &text[skip..]))
+        }
+        Some((b'?', rest)) => text
+            .split_first()
+            .is_some_and(|(byte, tail)| *byte != b'/' && wildcard_match(rest, tail)),
+        Some((expected, rest)) => text
+            .split_first()
+            .is_some_and(|(byte, tail)| byte == expected && wildcard_match(rest, tail)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unanchored_pattern_matches_component_at_any_depth() {
+        let glob = IgnoreGlob::new("node_modules/**").expect("valid glob");
+        assert!(glob.matches("node_modules"));
+        assert!(glob.matches("web/app/node_modules"));
+        assert!(!glob.matches("web/node_modules_backup"));
+
+        let trash = IgnoreGlob::new(".Trash").expect("valid glob");
+        assert!(trash.matches(".Trash"));
+        assert!(!trash.matches("Trash"));
+    }
+
+    #[test]
+    fn anchored_pattern_matches_relative_path_only() {
+        let glob = IgnoreGlob::new("archive/*/old").expect("valid glob");
+        assert!(glob.matches("archive/2023/old"));
+        assert!(!glob.matches("archive/2023/deep/old"));
+        assert!(!glob.matches("nested/archive/2023/old"));
+
+        let deep = IgnoreGlob::new("archive/**/old").expect("valid glob");
+        assert!(deep.matches("archive/2023/deep/old"));
+        assert!(deep.matches("archive/old"));
+    }
+
+    #[test]
+    fn wildcards_respect_component_boundaries() {
+        let glob = IgnoreGlob::new("*.bak").expect("valid glob");
+        assert!(glob.matches("projects/site.bak"));
+        assert!(!glob.matches("projects/site.bak2"));
+
+        let single = IgnoreGlob::new("tmp?").expect("valid glob");
+        assert!(single.matches("tmp1"));
+        assert!(!single.matches("tmp12"));
+    }
+
+    #[test]
+    fn blank_patterns_are_skipped() {
+        let globs = parse_ignore_globs(&["", "  ", "/**", "vendor/**"]);
+        assert_eq!(globs.len(), 1);
+        assert!(globs[0].matches("vendor"));
+    }
+}
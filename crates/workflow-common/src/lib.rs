@@ -1,10 +1,16 @@
 //! Shared open-project domain modules.
 //!
 //! - `config`: environment/default parsing and path expansion.
-//! - `discovery`: git repository scan + query filtering.
-//! - `usage_log`: usage file read/write + timestamp sort keys.
-//! - `git`: git metadata helpers and remote URL normalization for GitHub + generic `host/path` hosts.
+//! - `discovery`: parallel git repository (linked worktree, `.code-workspace` file) scan + query filtering.
+//! - `scan_cache`: persistent per-root scan cache with directory mtime invalidation.
+//! - `usage_log`: versioned JSONL usage file (legacy `path | timestamp` lines still read) + frecency (decayed hit count) sort keys + the archived-projects sidecar.
+//! - `workspace`: monorepo workspace member detection (Cargo, npm/pnpm, `go.work`).
+//! - `glob`: minimal ignore-glob matcher used by discovery.
+//! - `git`: git metadata + bounded-time status probes and forge-aware remote URL normalization (GitHub, GitLab, Bitbucket, self-hosted).
 //! - `feedback`: Alfred item assembly.
+//! - `listing`: machine-readable project listing in script-filter rank order.
+//! - `search`: bounded ripgrep search behind the `grep <text>` script-filter intent.
+//! - `terminal`: terminal app selection + launch for the open-in-terminal action.
 //! - `output_contract`: shared output modes + JSON envelope helpers.
 //! - `list_parser`: ordered comma/newline list parsing utilities.
 
@@ -13,28 +19,53 @@ pub mod discovery;
 pub mod error;
 pub mod feedback;
 pub mod git;
+pub mod glob;
 pub mod list_parser;
+pub mod listing;
 pub mod output_contract;
+pub mod scan_cache;
+pub mod search;
+pub mod terminal;
 pub mod usage_log;
+pub mod workspace;
 
 pub use alfred_core::Feedback;
 pub use config::{
-    DEFAULT_OPEN_PROJECT_MAX_RESULTS, DEFAULT_PROJECT_DIRS, DEFAULT_USAGE_FILE,
-    DEFAULT_VSCODE_PATH, RuntimeConfig, expand_home_tokens, parse_project_dirs,
+    DEFAULT_CONFIG_FILE, DEFAULT_OPEN_PROJECT_MAX_RESULTS, DEFAULT_OPEN_PROJECT_SCAN_DEPTH,
+    DEFAULT_OPEN_PROJECT_SCAN_WORKERS, DEFAULT_PROJECT_DIRS, DEFAULT_USAGE_FILE,
+    DEFAULT_VSCODE_PATH, EditorTarget, FileConfig, RuntimeConfig, expand_home_tokens,
+    normalize_alias, parse_project_dirs, pin_project, set_project_alias, unpin_project,
 };
+pub use discovery::{is_code_workspace_file, parse_remote_spec};
 pub use error::{CliErrorKind, WorkflowError};
 pub use feedback::{
-    ScriptFilterMode, build_script_filter_feedback, build_script_filter_feedback_with_mode,
-    no_projects_feedback, subtitle_format,
+    ScriptFilterMode, build_script_filter_feedback, build_script_filter_feedback_for_projects,
+    build_script_filter_feedback_with_mode, marker_subtitle_format, no_projects_feedback,
+    subtitle_format,
+};
+pub use git::{
+    Forge, ForgeHost, ForgePage, GitStatus, detect_forge, forge_page_url,
+    forge_page_url_for_project, git_status, git_statuses, normalize_remote, normalize_remote_with,
+    parse_forge_hosts, web_url_for_project, web_url_for_project_with,
 };
-pub use git::{normalize_remote, web_url_for_project};
 pub use list_parser::{parse_ordered_list_with, split_ordered_list};
+pub use listing::{ProjectListing, list_projects};
 pub use output_contract::{
     ENVELOPE_SCHEMA_VERSION, EnvelopePayloadKind, OutputMode, build_alfred_error_feedback,
     build_error_details_json, build_error_envelope, build_feedback_result_envelope,
     build_success_envelope, redact_sensitive,
 };
-pub use usage_log::{parse_usage_timestamp, record_usage};
+pub use scan_cache::{
+    CacheFreshness, ProjectSnapshot, RefreshPolicy, claim_background_rescan, load_projects,
+    release_background_rescan, rescan,
+};
+pub use search::{SearchMatch, SearchOptions, SearchOutcome, SearchQuery, search};
+pub use terminal::{TerminalApp, open_terminal};
+pub use usage_log::{
+    DEFAULT_PRUNE_HALF_LIFE_DAYS, PruneReport, archive_project, export_usage, load_archived,
+    parse_usage_timestamp, prune_usage, record_usage, record_usage_with_action, unarchive_project,
+    usage_now,
+};
 
 pub fn build_feedback(query: &str) -> Feedback {
     let config = RuntimeConfig::from_env();
@@ -0,0 +1,140 @@
+//! Machine-readable project listing (`workflow-cli list-projects`).
+
+use serde::Serialize;
+
+use crate::config::RuntimeConfig;
+use crate::discovery::{Project, ProjectKind};
+use crate::feedback::{MatchScope, ranked_matches, split_archive_scope};
+use crate::git::web_url_for_project_with;
+use crate::usage_log::{UsageLog, usage_now};
+
+/// One discovered project, in script-filter rank order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectListing {
+    pub path: String,
+    pub name: String,
+    /// `git`, `worktree`, `marker`, `member`, `remote`, or `code-workspace`.
+    pub kind: &'static str,
+    /// Canonical web URL of `origin`; `None` without a resolvable remote.
+    pub remote: Option<String>,
+    pub last_used: Option<String>,
+    /// Frecency score used for ranking (decayed hit count).
+    pub score: f64,
+}
+
+/// Filter `discovered` by `query` and rank it exactly like the script filter (aliases and
+/// pins included, archived projects only under an `all ` query), without the `max_results` cap.
+pub fn list_projects(
+    query: &str,
+    config: &RuntimeConfig,
+    discovered: &[Project],
+) -> Vec<ProjectListing> {
+    let usage_log = UsageLog::load(&config.usage_file);
+    let (query, include_archived) = split_archive_scope(query);
+    ranked_matches(
+        query,
+        config,
+        discovered,
+        MatchScope {
+            git_only: false,
+            include_archived,
+        },
+        &usage_log,
+        usage_now(),
+    )
+    .into_iter()
+    .map(|(rank, project)| ProjectListing {
+        path: project.path.to_string_lossy().to_string(),
+        kind: kind_label(&project.kind),
+        remote: project
+            .is_git()
+            .then(|| web_url_for_project_with(&project.path, &config.forge_hosts).ok())
+            .flatten(),
+        last_used: usage_log
+            .timestamp_for(&project.path, &project.name)
+            .map(ToString::to_string),
+        score: rank.frecency,
+        name: project.name,
+    })
+    .collect()
+}
+
+fn kind_label(kind: &ProjectKind) -> &'static str {
+    match kind {
+        ProjectKind::Git => "git",
+        ProjectKind::Worktree { .. } => "worktree",
+        ProjectKind::Marker { .. } => "marker",
+        ProjectKind::Member { .. } => "member",
+        ProjectKind::Remote { .. } => "remote",
+        ProjectKind::CodeWorkspace => "code-workspace",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::usage_log::record_usage;
+
+    fn init_repo(path: &Path, origin: Option<&str>) {
+        fs::create_dir_all(path).expect("create repo dir");
+        let status = Command::new("git")
+            .arg("init")
+            .arg("-q")
+            .arg(path)
+            .status()
+            .expect("run git init");
+        assert!(status.success(), "git init should succeed");
+        if let Some(origin) = origin {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(["remote", "add", "origin", origin])
+                .status()
+                .expect("set git remote");
+            assert!(status.success(), "git remote add should succeed");
+        }
+    }
+
+    #[test]
+    fn listing_ranks_projects_and_resolves_remotes() {
+        let temp = tempdir().expect("create temp dir");
+        let alpha = temp.path().join("alpha");
+        let beta = temp.path().join("beta");
+        init_repo(&alpha, None);
+        init_repo(&beta, Some("git@github.com:owner/beta.git"));
+
+        let config = RuntimeConfig {
+            usage_file: temp.path().join("usage.log"),
+            ..RuntimeConfig::default()
+        };
+        record_usage(&beta, &config.usage_file).expect("record usage");
+
+        let discovered = [
+            Project::new(alpha.clone()).expect("alpha project"),
+            Project::new(beta.clone()).expect("beta project"),
+        ];
+        let listing = list_projects("", &config, &discovered);
+
+        let names: Vec<&str> = listing.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["beta", "alpha"], "used projects rank first");
+        assert_eq!(
+            listing[0].remote.as_deref(),
+            Some("https://github.com/owner/beta")
+        );
+        assert!(listing[0].last_used.is_some());
+        assert!(listing[0].score > 0.0);
+        assert_eq!(listing[1].remote, None);
+        assert_eq!(listing[1].last_used, None);
+        assert_eq!(listing[1].kind, "git");
+
+        let filtered = list_projects("alp", &config, &discovered);
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].path, alpha.to_string_lossy());
+    }
+}
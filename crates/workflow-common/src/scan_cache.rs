@@ -0,0 +1,463 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::discovery::{Project, ProjectKind, discover_projects_with};
+use crate::error::WorkflowError;
+
+pub const SCAN_CACHE_FILE_NAME: &str = "open-project-scan.json";
+pub const SCAN_CACHE_VERSION: u32 = 3;
+/// Cached scans older than this are served once more while a refresh is requested.
+pub const SCAN_CACHE_MAX_AGE_SECS: u64 = 5 * 60;
+pub const RESCAN_MARKER_FILE_NAME: &str = "open-project-scan.rescan";
+/// A rescan marker older than this is treated as left behind by a crashed rescan and reclaimed.
+pub const RESCAN_MARKER_TTL_SECS: u64 = 60;
+
+/// How `load_projects` should react to a stale cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshPolicy {
+    /// Rescan synchronously and rewrite the cache before returning.
+    Inline,
+    /// Return the stale snapshot and let the caller schedule a background rescan.
+    Deferred,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheFreshness {
+    /// Caching is disabled (no cache directory configured).
+    Uncached,
+    /// Snapshot came from a live scan in this call.
+    Scanned,
+    /// Snapshot came from the cache and every recorded mtime still matches.
+    Fresh,
+    /// Snapshot came from the cache but a directory changed or the entry aged out.
+    Stale,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSnapshot {
+    pub projects: Vec<Project>,
+    pub freshness: CacheFreshness,
+}
+
+impl ProjectSnapshot {
+    pub fn needs_refresh(&self) -> bool {
+        self.freshness == CacheFreshness::Stale
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ScanCacheFile {
+    version: u32,
+    written_at: u64,
+    /// Depth + ignore globs the scan ran with; a mismatch invalidates the cache.
+    #[serde(default)]
+    options_key: String,
+    roots: Vec<CachedRoot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedRoot {
+    path: PathBuf,
+    /// The root plus its top-level child directories, with their mtimes at scan time.
+    dirs: Vec<CachedDir>,
+    projects: Vec<CachedProject>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedDir {
+    path: PathBuf,
+    mtime_ns: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedProject {
+    name: String,
+    path: PathBuf,
+    #[serde(default)]
+    kind: ProjectKind,
+}
+
+pub fn scan_cache_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join(SCAN_CACHE_FILE_NAME)
+}
+
+pub fn rescan_marker_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join(RESCAN_MARKER_FILE_NAME)
+}
+
+/// Claim the "rescan in progress" marker before spawning a background rescan.
+///
+/// Returns `false` while another claim younger than [`RESCAN_MARKER_TTL_SECS`] is outstanding, so
+/// stale keystrokes do not pile up rescans. [`rescan`] clears the marker once the cache is written. Reclaiming an
+/// expired marker is best effort and may let more than one rescan through.
+pub fn claim_background_rescan(cache_dir: &Path) -> bool {
+    if fs::create_dir_all(cache_dir).is_err() {
+        return false;
+    }
+
+    let marker = rescan_marker_path(cache_dir);
+    if create_marker(&marker) {
+        return true;
+    }
+
+    let expired = fs::metadata(&marker)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_none_or(|age| age.as_secs() >= RESCAN_MARKER_TTL_SECS);
+    if !expired {
+        return false;
+    }
+
+    // Not atomic: reclaimers racing on the same expired marker can each remove a freshly created one and
+    // claim it, so an occasional duplicate rescan is possible. That is harmless, since every rescan writes
+    // the cache through its own per-process temp file and a rename.
+    let _ = fs::remove_file(&marker);
+    create_marker(&marker)
+}
+
+/// Drop a claim taken with [`claim_background_rescan`], e.g. when the rescan could not be spawned.
+pub fn release_background_rescan(cache_dir: &Path) {
+    let _ = fs::remove_file(rescan_marker_path(cache_dir));
+}
+
+fn create_marker(marker: &Path) -> bool {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(marker)
+        .is_ok()
+}
+
+/// Resolve the project list, preferring the persistent scan cache when one is configured.
+pub fn load_projects(config: &RuntimeConfig, policy: RefreshPolicy) -> ProjectSnapshot {
+    let Some(cache_dir) = config.cache_dir.as_deref() else {
+        return ProjectSnapshot {
+            projects: discover_projects_with(&config.project_roots, &config.scan_options()),
+            freshness: CacheFreshness::Uncached,
+        };
+    };
+
+    let cache_file = scan_cache_path(cache_dir);
+    let cached = read_cache(&cache_file).filter(|cache| covers_roots(cache, config));
+
+    match cached {
+        Some(cache) if is_fresh(&cache, now_secs()) => ProjectSnapshot {
+            projects: cached_projects(&cache),
+            freshness: CacheFreshness::Fresh,
+        },
+        Some(cache) if policy == RefreshPolicy::Deferred => ProjectSnapshot {
+            projects: cached_projects(&cache),
+            freshness: CacheFreshness::Stale,
+        },
+        _ => {
+            // A failed cache write only costs the next call a rescan.
+            let projects = rescan(config).unwrap_or_else(|_| {
+                discover_projects_with(&config.project_roots, &config.scan_options())
+            });
+            ProjectSnapshot {
+                projects,
+                freshness: CacheFreshness::Scanned,
+            }
+        }
+    }
+}
+
+/// Scan every configured root and persist the result, returning the discovered projects.
+///
+/// Without a configured cache directory this is a plain scan.
+pub fn rescan(config: &RuntimeConfig) -> Result<Vec<Project>, WorkflowError> {
+    let projects = discover_projects_with(&config.project_roots, &config.scan_options());
+    let Some(cache_dir) = config.cache_dir.as_deref() else {
+        return Ok(projects);
+    };
+
+    let roots = config
+        .project_roots
+        .iter()
+        .map(|root| CachedRoot {
+            path: root.clone(),
+            dirs: snapshot_dirs(root),
+            projects: projects
+                .iter()
+                .filter(|project| project.path.starts_with(root))
+                .map(|project| CachedProject {
+                    name: project.name.clone(),
+                    path: project.path.clone(),
+                    kind: project.kind.clone(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let cache = ScanCacheFile {
+        version: SCAN_CACHE_VERSION,
+        written_at: now_secs(),
+        options_key: options_key(config),
+        roots,
+    };
+    let written = write_cache(&scan_cache_path(cache_dir), &cache);
+    release_background_rescan(cache_dir);
+    written?;
+
+    Ok(projects)
+}
+
+fn read_cache(path: &Path) -> Option<ScanCacheFile> {
+    let content = fs::read_to_string(path).ok()?;
+    let cache = serde_json::from_str::<ScanCacheFile>(&content).ok()?;
+    (cache.version == SCAN_CACHE_VERSION).then_some(cache)
+}
+
+fn write_cache(path: &Path, cache: &ScanCacheFile) -> Result<(), WorkflowError> {
+    let to_error = |source: std::io::Error| WorkflowError::CacheWrite {
+        path: path.to_path_buf(),
+        source,
+    };
+
+    let payload = serde_json::to_string(cache)
+        .map_err(|error| to_error(std::io::Error::other(error.to_string())))?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(to_error)?;
+    }
+
+    // Per-process name so concurrent rescans cannot clobber each other's temp file.
+    let temp_file = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_file, payload).map_err(to_error)?;
+    fs::rename(&temp_file, path).map_err(to_error)
+}
+
+fn options_key(config: &RuntimeConfig) -> String {
+    format!(
+        "{}|{}|{}|{}",
+        config.scan_depth,
+        config.ignore_globs.join(","),
+        config.markers.join(","),
+        config.workspace_members
+    )
+}
+
+fn covers_roots(cache: &ScanCacheFile, config: &RuntimeConfig) -> bool {
+    cache.options_key == options_key(config)
+        && cache.roots.len() == config.project_roots.len()
+        && cache
+            .roots
+            .iter()
+            .zip(&config.project_roots)
+            .all(|(cached, root)| &cached.path == root)
+}
+
+fn is_fresh(cache: &ScanCacheFile, now: u64) -> bool {
+    if now.saturating_sub(cache.written_at) >= SCAN_CACHE_MAX_AGE_SECS {
+        return false;
+    }
+
+    cache.roots.iter().all(|root| {
+        root.dirs
+            .iter()
+            .all(|dir| dir_mtime_ns(&dir.path) == Some(dir.mtime_ns))
+    })
+}
+
+fn cached_projects(cache: &ScanCacheFile) -> Vec<Project> {
+    let mut projects = BTreeMap::<String, Project>::new();
+    for project in cache.roots.iter().flat_map(|root| &root.projects) {
+        projects.insert(
+            project.path.to_string_lossy().to_string(),
+            Project {
+                name: project.name.clone(),
+                path: project.path.clone(),
+                kind: project.kind.clone(),
+            },
+        );
+    }
+
+    projects.into_values().collect()
+}
+
+fn snapshot_dirs(root: &Path) -> Vec<CachedDir> {
+    let mut dirs = Vec::new();
+    let Some(mtime_ns) = dir_mtime_ns(root) else {
+        return dirs;
+    };
+    dirs.push(CachedDir {
+        path: root.to_path_buf(),
+        mtime_ns,
+    });
+
+    if let Ok(children) = fs::read_dir(root) {
+        for child in children.flatten() {
+            let path = child.path();
+            if let Some(mtime_ns) = dir_mtime_ns(&path) {
+                dirs.push(CachedDir { path, mtime_ns });
+            }
+        }
+    }
+
+    dirs.sort_by(|left, right| left.path.cmp(&right.path));
+    dirs
+}
+
+fn dir_mtime_ns(path: &Path) -> Option<u64> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return None;
+    }
+
+    let modified = metadata.modified().ok()?;
+    let elapsed = modified.duration_since(UNIX_EPOCH).ok()?;
+    u64::try_from(elapsed.as_nanos()).ok()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn cached_config(root: &Path, cache_dir: &Path) -> RuntimeConfig {
+        RuntimeConfig {
+            project_roots: vec![root.to_path_buf()],
+            cache_dir: Some(cache_dir.to_path_buf()),
+            ..RuntimeConfig::default()
+        }
+    }
+
+    #[test]
+    fn background_rescan_claim_is_exclusive_until_rescan_or_expiry() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let cache_dir = temp.path().join("cache");
+        fs::create_dir_all(root.join("alpha/.git")).expect("create repo");
+
+        assert!(claim_background_rescan(&cache_dir));
+        assert!(
+            !claim_background_rescan(&cache_dir),
+            "a rescan in progress should block another spawn"
+        );
+
+        rescan(&cached_config(&root, &cache_dir)).expect("rescan");
+        assert!(!rescan_marker_path(&cache_dir).exists());
+        assert!(claim_background_rescan(&cache_dir));
+
+        let expired =
+            SystemTime::now() - std::time::Duration::from_secs(RESCAN_MARKER_TTL_SECS + 1);
+        fs::File::options()
+            .write(true)
+            .open(rescan_marker_path(&cache_dir))
+            .and_then(|marker| marker.set_modified(expired))
+            .expect("age rescan marker");
+        assert!(
+            claim_background_rescan(&cache_dir),
+            "an expired marker should be reclaimed"
+        );
+    }
+
+    #[test]
+    fn load_projects_without_cache_dir_scans_directly() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        fs::create_dir_all(root.join("alpha/.git")).expect("create repo");
+
+        let config = RuntimeConfig {
+            project_roots: vec![root],
+            ..RuntimeConfig::default()
+        };
+
+        let snapshot = load_projects(&config, RefreshPolicy::Deferred);
+        assert_eq!(snapshot.freshness, CacheFreshness::Uncached);
+        assert_eq!(snapshot.projects.len(), 1);
+    }
+
+    #[test]
+    fn load_projects_writes_then_reuses_fresh_cache() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let cache_dir = temp.path().join("cache");
+        fs::create_dir_all(root.join("alpha/.git")).expect("create repo");
+        let config = cached_config(&root, &cache_dir);
+
+        let first = load_projects(&config, RefreshPolicy::Deferred);
+        assert_eq!(first.freshness, CacheFreshness::Scanned);
+        assert!(
+            scan_cache_path(&cache_dir).is_file(),
+            "cache should be written"
+        );
+
+        let second = load_projects(&config, RefreshPolicy::Deferred);
+        assert_eq!(second.freshness, CacheFreshness::Fresh);
+        assert_eq!(second.projects, first.projects);
+    }
+
+    #[test]
+    fn load_projects_marks_cache_stale_when_top_level_dir_changes() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let cache_dir = temp.path().join("cache");
+        fs::create_dir_all(root.join("alpha/.git")).expect("create repo");
+        let config = cached_config(&root, &cache_dir);
+        rescan(&config).expect("rescan should write cache");
+
+        fs::create_dir_all(root.join("beta/.git")).expect("create second repo");
+
+        let deferred = load_projects(&config, RefreshPolicy::Deferred);
+        assert_eq!(deferred.freshness, CacheFreshness::Stale);
+        assert!(deferred.needs_refresh());
+        assert_eq!(deferred.projects.len(), 1, "stale snapshot is served as-is");
+
+        let inline = load_projects(&config, RefreshPolicy::Inline);
+        assert_eq!(inline.freshness, CacheFreshness::Scanned);
+        assert_eq!(inline.projects.len(), 2, "inline refresh picks up new repo");
+    }
+
+    #[test]
+    fn load_projects_ignores_cache_written_with_other_scan_options() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path().join("root");
+        let cache_dir = temp.path().join("cache");
+        fs::create_dir_all(root.join("alpha/.git")).expect("create repo");
+        fs::create_dir_all(root.join("vendor/dep/.git")).expect("create vendored repo");
+
+        let config = cached_config(&root, &cache_dir);
+        rescan(&config).expect("seed cache");
+
+        let narrowed = RuntimeConfig {
+            ignore_globs: vec!["vendor/**".to_string()],
+            ..config
+        };
+        let snapshot = load_projects(&narrowed, RefreshPolicy::Deferred);
+        assert_eq!(snapshot.freshness, CacheFreshness::Scanned);
+        assert_eq!(snapshot.projects.len(), 1, "new ignore glob should apply");
+    }
+
+    #[test]
+    fn load_projects_ignores_cache_written_for_other_roots() {
+        let temp = tempdir().expect("create temp dir");
+        let root_a = temp.path().join("root-a");
+        let root_b = temp.path().join("root-b");
+        let cache_dir = temp.path().join("cache");
+        fs::create_dir_all(root_a.join("alpha/.git")).expect("create repo a");
+        fs::create_dir_all(root_b.join("bravo/.git")).expect("create repo b");
+
+        rescan(&cached_config(&root_a, &cache_dir)).expect("seed cache for root a");
+
+        let snapshot = load_projects(&cached_config(&root_b, &cache_dir), RefreshPolicy::Deferred);
+        assert_eq!(snapshot.freshness, CacheFreshness::Scanned);
+        assert_eq!(snapshot.projects[0].name, "bravo");
+    }
+}
@@ -0,0 +1,429 @@
+//! `grep <text>` intent: bounded ripgrep search across projects.
+//!
+//! `rg --json` runs with fixed-string, smart-case matching so half-typed queries never fail
+//! as regexes. Output is consumed incrementally and the child is killed once the result cap
+//! is reached or the deadline passes, so the script filter stays responsive on large trees.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::error::WorkflowError;
+
+pub const SEARCH_KEYWORD: &str = "grep";
+pub const SEARCH_TIMEOUT: Duration = Duration::from_millis(1500);
+pub const SEARCH_MIN_PATTERN_CHARS: usize = 2;
+/// Matches reported per file, so one noisy file cannot fill the whole result list.
+pub const SEARCH_MAX_MATCHES_PER_FILE: usize = 5;
+const SEARCH_MAX_COLUMNS: usize = 300;
+const SEARCH_MAX_FILESIZE: &str = "1M";
+const SEARCH_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Checked in order when `OPEN_PROJECT_RG_PATH` is unset; Alfred's `PATH` lacks Homebrew.
+const RG_CANDIDATES: &[&str] = &["/opt/homebrew/bin/rg", "/usr/local/bin/rg"];
+
+/// Parsed `grep [@project] <text>` query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Project name / alias after `@`; `None` searches every configured root.
+    pub scope: Option<String>,
+    pub pattern: String,
+}
+
+impl SearchQuery {
+    /// Recognize the search intent; the keyword must be followed by whitespace.
+    pub fn parse(query: &str) -> Option<Self> {
+        let rest = query.trim_start().strip_prefix(SEARCH_KEYWORD)?;
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let rest = rest.trim();
+        let (scope, pattern) = match rest.strip_prefix('@') {
+            Some(scoped) => match scoped.split_once(char::is_whitespace) {
+                Some((scope, pattern)) => (Some(scope.to_string()), pattern.trim()),
+                None => (Some(scoped.to_string()), ""),
+            },
+            None => (None, rest),
+        };
+
+        Some(Self {
+            scope: scope.filter(|scope| !scope.is_empty()),
+            pattern: pattern.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Explicit `rg` executable; `None` probes `PATH` and the Homebrew locations.
+    pub rg_path: Option<String>,
+    pub timeout: Duration,
+    pub max_results: usize,
+    /// Discovery ignore globs, forwarded as `--glob !<pattern>`.
+    pub ignore_globs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub path: PathBuf,
+    /// 1-based line number.
+    pub line: u64,
+    /// 1-based character column of the first submatch.
+    pub column: usize,
+    /// Matched line without its trailing newline.
+    pub text: String,
+}
+
+impl SearchMatch {
+    /// `file:line:column`, the form accepted by `code -g`.
+    pub fn goto_target(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.path.to_string_lossy(),
+            self.line,
+            self.column
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOutcome {
+    pub matches: Vec<SearchMatch>,
+    /// The result cap was hit; more matches may exist.
+    pub truncated: bool,
+    /// The deadline passed before `rg` finished; `matches` is partial.
+    pub timed_out: bool,
+}
+
+/// Search `paths` for `pattern`, returning at most `options.max_results` matches.
+///
+/// Only a failure to start `rg` is an error; `rg` exiting non-zero (no match, unreadable
+/// files) yields whatever matches were printed.
+pub fn search(
+    pattern: &str,
+    paths: &[PathBuf],
+    options: &SearchOptions,
+) -> Result<SearchOutcome, WorkflowError> {
+    if pattern.is_empty() || paths.is_empty() || options.max_results == 0 {
+        return Ok(SearchOutcome::default());
+    }
+
+    let program = resolve_rg(options.rg_path.as_deref());
+    let mut command = Command::new(&program);
+    command.args([
+        "--json",
+        "--fixed-strings",
+        "--smart-case",
+        "--no-messages",
+        "--max-count",
+        &SEARCH_MAX_MATCHES_PER_FILE.to_string(),
+        "--max-columns",
+        &SEARCH_MAX_COLUMNS.to_string(),
+        "--max-filesize",
+        SEARCH_MAX_FILESIZE,
+    ]);
+    for glob in &options.ignore_globs {
+        command.arg("--glob").arg(format!("!{}", glob.trim()));
+    }
+    let mut child = command
+        .arg("--")
+        .arg(pattern)
+        .args(paths)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| WorkflowError::SearchLaunch {
+            program: program.clone(),
+            message: error.to_string(),
+        })?;
+
+    let Some(stdout) = child.stdout.take() else {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Ok(SearchOutcome::default());
+    };
+    let max_results = options.max_results;
+    // Returning early drops the pipe, so `rg` stops on its next write even before the kill.
+    let reader = thread::spawn(move || {
+        let mut matches = Vec::new();
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(found) = parse_match_line(&line) {
+                matches.push(found);
+                if matches.len() > max_results {
+                    break;
+                }
+            }
+        }
+        matches
+    });
+
+    let deadline = Instant::now() + options.timeout;
+    let mut timed_out = false;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if reader.is_finished() => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break;
+            }
+            Ok(None) if Instant::now() < deadline => thread::sleep(SEARCH_POLL_INTERVAL),
+            _ => {
+                timed_out = true;
+                let _ = child.kill();
+                let _ = child.wait();
+                break;
+            }
+        }
+    }
+
+    let mut matches = reader.join().unwrap_or_default();
+    let truncated = matches.len() > max_results;
+    matches.truncate(max_results);
+    Ok(SearchOutcome {
+        matches,
+        truncated,
+        timed_out,
+    })
+}
+
+fn resolve_rg(configured: Option<&str>) -> String {
+    if let Some(path) = configured.map(str::trim).filter(|path| !path.is_empty()) {
+        return path.to_string();
+    }
+
+    RG_CANDIDATES
+        .iter()
+        .find(|candidate| Path::new(candidate).is_file())
+        .map_or_else(|| "rg".to_string(), ToString::to_string)
+}
+
+#[derive(Deserialize)]
+struct RgEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    data: Option<RgMatch>,
+}
+
+#[derive(Deserialize)]
+struct RgMatch {
+    path: RgText,
+    lines: RgText,
+    line_number: Option<u64>,
+    #[serde(default)]
+    submatches: Vec<RgSubmatch>,
+}
+
+/// `rg` emits `{"text": ...}` for UTF-8 data and `{"bytes": <base64>}` otherwise.
+#[derive(Deserialize)]
+struct RgText {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RgSubmatch {
+    start: usize,
+}
+
+fn parse_match_line(line: &str) -> Option<SearchMatch> {
+    let event = serde_json::from_str::<RgEvent>(line).ok()?;
+    if event.kind != "match" {
+        return None;
+    }
+
+    let data = event.data?;
+    let text = data.lines.text?;
+    let start = data.submatches.first().map_or(0, |submatch| submatch.start);
+    let column = text.get(..start).map_or(0, |prefix| prefix.chars().count()) + 1;
+
+    Some(SearchMatch {
+        path: PathBuf::from(data.path.text?),
+        line: data.line_number?,
+        column,
+        text: text.trim_end_matches(['\n', '\r']).to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn rg_available() -> bool {
+        Command::new(resolve_rg(None))
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+
+    fn options(max_results: usize) -> SearchOptions {
+        SearchOptions {
+            rg_path: None,
+            timeout: Duration::from_secs(5),
+            max_results,
+            ignore_globs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse_recognizes_keyword_scope_and_pattern() {
+        assert_eq!(
+            SearchQuery::parse("grep fn main"),
+            Some(SearchQuery {
+                scope: None,
+                pattern: "fn main".to_string(),
+            })
+        );
+        assert_eq!(
+            SearchQuery::parse("grep @alpha  TODO(x) "),
+            Some(SearchQuery {
+                scope: Some("alpha".to_string()),
+                pattern: "TODO(x)".to_string(),
+            })
+        );
+        assert_eq!(
+            SearchQuery::parse("grep @alpha"),
+            Some(SearchQuery {
+                scope: Some("alpha".to_string()),
+                pattern: String::new(),
+            })
+        );
+        assert_eq!(SearchQuery::parse("grep"), None, "bare keyword filters");
+        assert_eq!(SearchQuery::parse("grepper"), None);
+        assert_eq!(SearchQuery::parse("alpha grep x"), None);
+    }
+
+    #[test]
+    fn parse_match_line_reads_rg_json_and_skips_other_events() {
+        let line = r#"{"type":"match","data":{"path":{"text":"/p/src/main.rs"},"lines":{"text":"    let héllo = 1;\n"},"line_number":12,"absolute_offset":40,"submatches":[{"match":{"text":"1"},"start":17,"end":18}]}}"#;
+        assert_eq!(
+            parse_match_line(line),
+            Some(SearchMatch {
+                path: PathBuf::from("/p/src/main.rs"),
+                line: 12,
+                column: 17,
+                text: "    let héllo = 1;".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_match_line(r#"{"type":"begin","data":{"path":{"text":"/p/a"}}}"#),
+            None
+        );
+        let binary_path = r#"{"type":"match","data":{"path":{"bytes":"L3A="},"lines":{"text":"x"},"line_number":1,"submatches":[]}}"#;
+        assert_eq!(parse_match_line(binary_path), None);
+    }
+
+    #[test]
+    fn search_caps_results_and_reports_truncation() {
+        if !rg_available() {
+            return;
+        }
+        let temp = tempdir().expect("create temp dir");
+        for index in 0..4 {
+            fs::write(
+                temp.path().join(format!("file-{index}.txt")),
+                "needle one\nhay\nNeedle two\n",
+            )
+            .expect("write fixture");
+        }
+
+        let all = search("needle", &[temp.path().to_path_buf()], &options(50)).expect("search");
+        assert_eq!(all.matches.len(), 8, "smart case matches both spellings");
+        assert!(!all.truncated && !all.timed_out);
+        let first = all
+            .matches
+            .iter()
+            .find(|found| found.line == 3)
+            .expect("second match in a file");
+        assert_eq!(first.column, 1);
+        assert!(first.goto_target().ends_with(":3:1"));
+
+        let capped = search("needle", &[temp.path().to_path_buf()], &options(3)).expect("search");
+        assert_eq!(capped.matches.len(), 3);
+        assert!(capped.truncated);
+
+        let literal = search("ne(", &[temp.path().to_path_buf()], &options(3)).expect("search");
+        assert!(literal.matches.is_empty(), "patterns are fixed strings");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn search_kills_slow_rg_at_deadline_and_keeps_partial_matches() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempdir().expect("create temp dir");
+        let stub = temp.path().join("rg");
+        fs::write(
+            &stub,
+            "#!/bin/sh\n\
+             for n in 1 2 3; do\n\
+             printf '{\"type\":\"match\",\"data\":{\"path\":{\"text\":\"/p/a.rs\"},\"lines\":{\"text\":\"hit\\\\n\"},\"line_number\":%s,\"submatches\":[{\"start\":0}]}}\\n' \"$n\"\n\
+             done\n\
+             exec sleep 10\n",
+        )
+        .expect("write rg stub");
+        fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).expect("chmod rg stub");
+
+        let options = SearchOptions {
+            rg_path: Some(stub.to_string_lossy().to_string()),
+            timeout: Duration::from_millis(300),
+            ..options(10)
+        };
+        let started = Instant::now();
+        let slow = search("hit", &[temp.path().to_path_buf()], &options).expect("search");
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "child is killed"
+        );
+        assert!(slow.timed_out);
+        assert_eq!(
+            slow.matches
+                .iter()
+                .map(|found| found.line)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        let capped = search(
+            "hit",
+            &[temp.path().to_path_buf()],
+            &SearchOptions {
+                timeout: Duration::from_secs(5),
+                max_results: 2,
+                ..options
+            },
+        )
+        .expect("search");
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "cap stops early"
+        );
+        assert!(capped.truncated && !capped.timed_out);
+        assert_eq!(capped.matches.len(), 2);
+    }
+
+    #[test]
+    fn search_reports_missing_rg_binary() {
+        let temp = tempdir().expect("create temp dir");
+        let options = SearchOptions {
+            rg_path: Some(temp.path().join("no-rg").to_string_lossy().to_string()),
+            ..options(5)
+        };
+        let err = search("x", &[temp.path().to_path_buf()], &options)
+            .expect_err("missing binary should fail to launch");
+        assert!(matches!(err, WorkflowError::SearchLaunch { .. }));
+    }
+}
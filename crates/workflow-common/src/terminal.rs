@@ -0,0 +1,157 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::WorkflowError;
+
+/// Terminal application used by the `cmd` modifier on project rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TerminalApp {
+    #[default]
+    Terminal,
+    ITerm2,
+    WezTerm,
+    Kitty,
+}
+
+impl TerminalApp {
+    /// Parse a config value case-insensitively; unknown values yield `None`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "terminal" | "terminal.app" => Some(Self::Terminal),
+            "iterm" | "iterm2" => Some(Self::ITerm2),
+            "wezterm" => Some(Self::WezTerm),
+            "kitty" => Some(Self::Kitty),
+            _ => None,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Terminal => "Terminal",
+            Self::ITerm2 => "iTerm2",
+            Self::WezTerm => "WezTerm",
+            Self::Kitty => "kitty",
+        }
+    }
+
+    /// Program + argv that opens a new window rooted at `path`.
+    ///
+    /// Terminal and iTerm2 accept a folder through `open -a`; WezTerm and kitty need their
+    /// own working-directory flags, passed through a fresh app instance (`open -na`).
+    pub fn launch_command(self, path: &Path) -> (&'static str, Vec<String>) {
+        let path = path.to_string_lossy().to_string();
+        let args = match self {
+            Self::Terminal => vec!["-a".to_string(), "Terminal".to_string(), path],
+            Self::ITerm2 => vec!["-a".to_string(), "iTerm".to_string(), path],
+            Self::WezTerm => vec![
+                "-na".to_string(),
+                "WezTerm".to_string(),
+                "--args".to_string(),
+                "start".to_string(),
+                "--cwd".to_string(),
+                path,
+            ],
+            Self::Kitty => vec![
+                "-na".to_string(),
+                "kitty".to_string(),
+                "--args".to_string(),
+                "--directory".to_string(),
+                path,
+            ],
+        };
+
+        ("open", args)
+    }
+}
+
+pub fn open_terminal(app: TerminalApp, path: &Path) -> Result<(), WorkflowError> {
+    if !path.exists() {
+        return Err(WorkflowError::MissingPath(path.to_path_buf()));
+    }
+    if !path.is_dir() {
+        return Err(WorkflowError::NotDirectory(path.to_path_buf()));
+    }
+
+    let (program, args) = app.launch_command(path);
+    let output = Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|error| WorkflowError::TerminalLaunch {
+            app: app.label().to_string(),
+            message: error.to_string(),
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(WorkflowError::TerminalLaunch {
+            app: app.label().to_string(),
+            message: if stderr.is_empty() {
+                format!("{program} exited with {}", output.status)
+            } else {
+                stderr
+            },
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_supported_apps_case_insensitively() {
+        assert_eq!(TerminalApp::parse("Terminal"), Some(TerminalApp::Terminal));
+        assert_eq!(TerminalApp::parse(" iTerm2 "), Some(TerminalApp::ITerm2));
+        assert_eq!(TerminalApp::parse("iterm"), Some(TerminalApp::ITerm2));
+        assert_eq!(TerminalApp::parse("WEZTERM"), Some(TerminalApp::WezTerm));
+        assert_eq!(TerminalApp::parse("kitty"), Some(TerminalApp::Kitty));
+        assert_eq!(TerminalApp::parse("alacritty"), None);
+    }
+
+    #[test]
+    fn launch_command_passes_project_path_per_app() {
+        let path = Path::new("/Users/tester/Project/alpha");
+
+        let (program, args) = TerminalApp::Terminal.launch_command(path);
+        assert_eq!(program, "open");
+        assert_eq!(args, ["-a", "Terminal", "/Users/tester/Project/alpha"]);
+
+        let (_, args) = TerminalApp::ITerm2.launch_command(path);
+        assert_eq!(args, ["-a", "iTerm", "/Users/tester/Project/alpha"]);
+
+        let (_, args) = TerminalApp::WezTerm.launch_command(path);
+        assert_eq!(
+            args,
+            [
+                "-na",
+                "WezTerm",
+                "--args",
+                "start",
+                "--cwd",
+                "/Users/tester/Project/alpha"
+            ]
+        );
+
+        let (_, args) = TerminalApp::Kitty.launch_command(path);
+        assert_eq!(
+            args,
+            [
+                "-na",
+                "kitty",
+                "--args",
+                "--directory",
+                "/Users/tester/Project/alpha"
+            ]
+        );
+    }
+
+    #[test]
+    fn open_terminal_rejects_missing_directory() {
+        let err = open_terminal(TerminalApp::Terminal, Path::new("/definitely/missing/dir"))
+            .expect_err("missing path should fail before launching");
+        assert!(matches!(err, WorkflowError::MissingPath(_)));
+    }
+}
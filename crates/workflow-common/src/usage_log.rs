@@ -1,51 +1,206 @@
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
 use std::path::{Path, PathBuf};
 
 use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 
 use crate::error::WorkflowError;
 
 const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
 
+/// Schema version written into every JSONL usage record.
+pub const USAGE_SCHEMA_VERSION: u32 = 1;
+/// `last_action` recorded when the caller does not name one.
+pub const DEFAULT_USAGE_ACTION: &str = "open";
+/// Half-life applied to stored counts by `usage prune`.
+pub const DEFAULT_PRUNE_HALF_LIFE_DAYS: u32 = 30;
+
+/// Suffix of the sidecar file (next to the usage log) listing archived project paths.
+pub const ARCHIVE_FILE_SUFFIX: &str = ".archived";
+
+/// Half-life used to decay hit counts: a hit this many seconds old counts half as much as a
+/// hit recorded right now.
+pub const FRECENCY_HALF_LIFE_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageEntry {
+    pub timestamp: String,
+    pub count: u32,
+    /// Action that produced the latest hit (`open`, `github`, ...); `None` for legacy lines.
+    pub last_action: Option<String>,
+    /// When `usage prune` last decayed `count`; decay resumes from here instead of `timestamp`.
+    pub decayed_at: Option<String>,
+}
+
+/// Outcome of [`prune_usage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// Entries whose absolute path no longer exists.
+    pub removed_missing: usize,
+    /// Entries whose decayed count reached zero.
+    pub removed_decayed: usize,
+    pub kept: usize,
+}
+
+/// One JSONL usage line.
+///
+/// Readers ignore unknown fields so newer writers can extend the record without breaking
+/// older binaries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct UsageRecord {
+    #[serde(default = "default_schema_version")]
+    schema_version: u32,
+    path: String,
+    last_used: String,
+    #[serde(default = "default_use_count")]
+    use_count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_action: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    decayed_at: Option<String>,
+}
+
+fn default_schema_version() -> u32 {
+    USAGE_SCHEMA_VERSION
+}
+
+fn default_use_count() -> u32 {
+    1
+}
+
+impl UsageRecord {
+    fn from_entry(key: &str, entry: UsageEntry) -> Self {
+        Self {
+            schema_version: USAGE_SCHEMA_VERSION,
+            path: key.to_string(),
+            last_used: entry.timestamp,
+            use_count: entry.count,
+            last_action: entry.last_action,
+            decayed_at: entry.decayed_at,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        // Serializing plain strings and integers cannot fail.
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct UsageLog {
-    entries: HashMap<String, String>,
+    entries: HashMap<String, UsageEntry>,
+    /// Paths from the `<usage_file>.archived` sidecar (`workflow-cli archive`).
+    archived: HashSet<String>,
 }
 
 impl UsageLog {
     pub fn load(path: &Path) -> Self {
+        let archived = load_archived(path)
+            .into_iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
         let content = match fs::read_to_string(path) {
             Ok(content) => content,
-            Err(_) => return Self::default(),
+            Err(_) => {
+                return Self {
+                    archived,
+                    ..Self::default()
+                };
+            }
         };
 
         let mut entries = HashMap::new();
         for line in content.lines() {
-            let Some((key, timestamp)) = line.split_once('|') else {
+            let Some((key, entry)) = parse_usage_line(line) else {
                 continue;
             };
 
-            let key = key.trim();
-            let timestamp = timestamp.trim();
-            if key.is_empty() || timestamp.is_empty() {
-                continue;
-            }
-
             // Keep the most recent occurrence in the file for each key.
-            entries.insert(key.to_string(), timestamp.to_string());
+            entries.insert(key, entry);
         }
 
-        Self { entries }
+        Self { entries, archived }
     }
 
-    pub fn timestamp_for(&self, project_path: &Path, project_name: &str) -> Option<&str> {
+    pub fn is_archived(&self, project_path: &Path) -> bool {
+        self.archived
+            .contains(project_path.to_string_lossy().as_ref())
+    }
+
+    pub fn entry_for(&self, project_path: &Path, project_name: &str) -> Option<&UsageEntry> {
         let path_key = project_path.to_string_lossy();
         self.entries
             .get(path_key.as_ref())
-            .map(String::as_str)
-            .or_else(|| self.entries.get(project_name).map(String::as_str))
+            .or_else(|| self.entries.get(project_name))
     }
+
+    pub fn timestamp_for(&self, project_path: &Path, project_name: &str) -> Option<&str> {
+        self.entry_for(project_path, project_name)
+            .map(|entry| entry.timestamp.as_str())
+    }
+
+    /// Decayed hit count for a project, evaluated at `now` (seconds, same clock as
+    /// [`parse_usage_timestamp`]). Projects without usage score `0.0`.
+    pub fn frecency_for(&self, project_path: &Path, project_name: &str, now: i64) -> f64 {
+        self.entry_for(project_path, project_name)
+            .map(|entry| {
+                frecency_score(
+                    entry.count,
+                    parse_usage_timestamp(Some(&entry.timestamp)),
+                    now,
+                )
+            })
+            .unwrap_or(0.0)
+    }
+}
+
+/// Parse one usage line: a JSONL record, or the legacy `key | timestamp [| count]` form.
+///
+/// Legacy two-field lines carry no count and are treated as a single hit.
+fn parse_usage_line(line: &str) -> Option<(String, UsageEntry)> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('{') {
+        let record = serde_json::from_str::<UsageRecord>(trimmed).ok()?;
+        let path = record.path.trim();
+        let timestamp = record.last_used.trim();
+        if path.is_empty() || timestamp.is_empty() {
+            return None;
+        }
+        return Some((
+            path.to_string(),
+            UsageEntry {
+                timestamp: timestamp.to_string(),
+                count: record.use_count.max(1),
+                last_action: record.last_action,
+                decayed_at: record.decayed_at,
+            },
+        ));
+    }
+
+    parse_legacy_usage_line(trimmed)
+}
+
+fn parse_legacy_usage_line(line: &str) -> Option<(String, UsageEntry)> {
+    let mut fields = line.split('|').map(str::trim);
+    let key = fields.next().filter(|key| !key.is_empty())?;
+    let timestamp = fields.next().filter(|timestamp| !timestamp.is_empty())?;
+    let count = fields
+        .next()
+        .and_then(|raw| raw.parse::<u32>().ok())
+        .filter(|count| *count > 0)
+        .unwrap_or(1);
+
+    Some((
+        key.to_string(),
+        UsageEntry {
+            timestamp: timestamp.to_string(),
+            count,
+            last_action: None,
+            decayed_at: None,
+        },
+    ))
 }
 
 pub fn parse_usage_timestamp(raw: Option<&str>) -> i64 {
@@ -54,32 +209,207 @@ pub fn parse_usage_timestamp(raw: Option<&str>) -> i64 {
         .unwrap_or(0)
 }
 
+/// Current time on the same clock as [`parse_usage_timestamp`] (local wall time read as UTC).
+pub fn usage_now() -> i64 {
+    Local::now().naive_local().and_utc().timestamp()
+}
+
+/// Exponentially decayed hit count: `count * 0.5^(age / FRECENCY_HALF_LIFE_SECS)`.
+///
+/// Unparseable timestamps (`last_used <= 0`) score `0.0`; future timestamps are not boosted.
+pub fn frecency_score(count: u32, last_used: i64, now: i64) -> f64 {
+    if count == 0 || last_used <= 0 {
+        return 0.0;
+    }
+
+    let age = (now - last_used).max(0) as f64;
+    f64::from(count) * 0.5_f64.powf(age / FRECENCY_HALF_LIFE_SECS as f64)
+}
+
 pub fn record_usage(project_path: &Path, usage_file: &Path) -> Result<(), WorkflowError> {
+    record_usage_with_action(project_path, usage_file, DEFAULT_USAGE_ACTION)
+}
+
+/// Record a hit for `project_path`, rewriting the file as JSONL.
+///
+/// Legacy lines for other projects are migrated in place; their timestamp and count are kept.
+pub fn record_usage_with_action(
+    project_path: &Path,
+    usage_file: &Path,
+    action: &str,
+) -> Result<(), WorkflowError> {
     let project_path_string = project_path.to_string_lossy().to_string();
     let project_name = project_path
         .file_name()
         .map(|value| value.to_string_lossy().to_string())
         .unwrap_or_default();
 
+    let _lock = lock_usage_file(usage_file)?;
     let existing = fs::read_to_string(usage_file).unwrap_or_default();
     let mut lines = Vec::new();
+    let mut previous_count = 0;
 
     for line in existing.lines() {
-        let Some((key, _)) = line.split_once('|') else {
+        let Some((key, entry)) = parse_usage_line(line) else {
             continue;
         };
 
-        let key = key.trim();
-        if key != project_path_string && key != project_name {
-            lines.push(line.to_string());
+        if key == project_path_string || key == project_name {
+            previous_count = previous_count.max(entry.count);
+        } else {
+            lines.push(UsageRecord::from_entry(&key, entry).to_line());
+        }
+    }
+
+    let action = action.trim();
+    let record = UsageRecord {
+        schema_version: USAGE_SCHEMA_VERSION,
+        path: project_path_string,
+        last_used: Local::now().format(TIMESTAMP_FORMAT).to_string(),
+        use_count: previous_count.saturating_add(1),
+        last_action: (!action.is_empty()).then(|| action.to_string()),
+        decayed_at: None,
+    };
+    lines.push(record.to_line());
+
+    write_usage_lines(usage_file, &lines)
+}
+
+/// Every entry in file order (latest occurrence per key), in the JSONL record shape.
+pub fn export_usage(usage_file: &Path) -> Vec<serde_json::Value> {
+    read_usage_entries(usage_file)
+        .into_iter()
+        .filter_map(|(key, entry)| serde_json::to_value(UsageRecord::from_entry(&key, entry)).ok())
+        .collect()
+}
+
+/// Drop entries for deleted projects and decay stored counts.
+///
+/// Counts shrink by `0.5^(elapsed / half_life)`, where `elapsed` runs from the later of the
+/// last hit and the previous prune, so repeated prunes do not compound. Entries that round
+/// down to zero are removed. Basename-only legacy keys cannot be checked for existence and
+/// are only subject to decay.
+pub fn prune_usage(
+    usage_file: &Path,
+    half_life_days: u32,
+    now: i64,
+) -> Result<PruneReport, WorkflowError> {
+    let half_life_secs = i64::from(half_life_days.max(1)) * 24 * 60 * 60;
+    let decayed_at = chrono::DateTime::from_timestamp(now, 0)
+        .map(|value| value.naive_utc().format(TIMESTAMP_FORMAT).to_string());
+
+    let _lock = lock_usage_file(usage_file)?;
+    let mut report = PruneReport::default();
+    let mut lines = Vec::new();
+    for (key, mut entry) in read_usage_entries(usage_file) {
+        let path = Path::new(&key);
+        if path.is_absolute() && !path.exists() {
+            report.removed_missing += 1;
+            continue;
+        }
+
+        let since = parse_usage_timestamp(Some(&entry.timestamp))
+            .max(parse_usage_timestamp(entry.decayed_at.as_deref()));
+        if since > 0 {
+            let elapsed = (now - since).max(0) as f64;
+            let decayed = f64::from(entry.count) * 0.5_f64.powf(elapsed / half_life_secs as f64);
+            entry.count = decayed.round() as u32;
+            entry.decayed_at.clone_from(&decayed_at);
+        }
+
+        if entry.count == 0 {
+            report.removed_decayed += 1;
+            continue;
         }
+
+        report.kept += 1;
+        lines.push(UsageRecord::from_entry(&key, entry).to_line());
+    }
+
+    if report.removed_missing + report.removed_decayed + report.kept == 0 {
+        return Ok(report);
     }
 
-    let timestamp = Local::now().format(TIMESTAMP_FORMAT).to_string();
-    lines.push(format!("{project_path_string} | {timestamp}"));
+    write_usage_lines(usage_file, &lines)?;
+    Ok(report)
+}
+
+/// Archived project paths, in the order they were archived.
+pub fn load_archived(usage_file: &Path) -> Vec<PathBuf> {
+    fs::read_to_string(sidecar_path(usage_file, ARCHIVE_FILE_SUFFIX))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Hide `project_path` from default results; returns `false` when it was already archived.
+pub fn archive_project(usage_file: &Path, project_path: &Path) -> Result<bool, WorkflowError> {
+    edit_archived(usage_file, |archived| {
+        if archived.iter().any(|path| path == project_path) {
+            return false;
+        }
+        archived.push(project_path.to_path_buf());
+        true
+    })
+}
+
+/// Show `project_path` again; returns `false` when it was not archived.
+pub fn unarchive_project(usage_file: &Path, project_path: &Path) -> Result<bool, WorkflowError> {
+    edit_archived(usage_file, |archived| {
+        let before = archived.len();
+        archived.retain(|path| path != project_path);
+        archived.len() != before
+    })
+}
+
+/// Read-modify-write the archive sidecar under the usage-file lock; unchanged lists are not
+/// rewritten.
+fn edit_archived(
+    usage_file: &Path,
+    edit: impl FnOnce(&mut Vec<PathBuf>) -> bool,
+) -> Result<bool, WorkflowError> {
+    let _lock = lock_usage_file(usage_file)?;
+    let mut archived = load_archived(usage_file);
+    if !edit(&mut archived) {
+        return Ok(false);
+    }
 
-    let output = format!("{}\n", lines.join("\n"));
+    let lines = archived
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    write_usage_lines(&sidecar_path(usage_file, ARCHIVE_FILE_SUFFIX), &lines)?;
+    Ok(true)
+}
 
+/// Parse the file keeping the latest occurrence of each key, in first-seen order.
+fn read_usage_entries(usage_file: &Path) -> Vec<(String, UsageEntry)> {
+    let content = fs::read_to_string(usage_file).unwrap_or_default();
+    let mut order = Vec::new();
+    let mut entries = HashMap::new();
+    for line in content.lines() {
+        let Some((key, entry)) = parse_usage_line(line) else {
+            continue;
+        };
+        if entries.insert(key.clone(), entry).is_none() {
+            order.push(key);
+        }
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| entries.remove(&key).map(|entry| (key, entry)))
+        .collect()
+}
+
+/// Take the exclusive advisory lock guarding read-modify-write cycles on `usage_file`.
+///
+/// The lock lives on a `<usage_file>.lock` sidecar because writes replace the usage file by
+/// rename, which would orphan a lock held on the old inode. Released when the handle drops.
+fn lock_usage_file(usage_file: &Path) -> Result<File, WorkflowError> {
     let parent = usage_file
         .parent()
         .map(Path::to_path_buf)
@@ -89,7 +419,40 @@ pub fn record_usage(project_path: &Path, usage_file: &Path) -> Result<(), Workfl
         source,
     })?;
 
-    let temp_file = usage_file.with_extension("tmp");
+    let lock_path = sidecar_path(usage_file, ".lock");
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|source| WorkflowError::UsageWrite {
+            path: lock_path.clone(),
+            source,
+        })?;
+    lock.lock().map_err(|source| WorkflowError::UsageWrite {
+        path: lock_path,
+        source,
+    })?;
+
+    Ok(lock)
+}
+
+fn sidecar_path(usage_file: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(usage_file.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Replace `usage_file` atomically (temp file + rename); callers hold [`lock_usage_file`].
+fn write_usage_lines(usage_file: &Path, lines: &[String]) -> Result<(), WorkflowError> {
+    let output = if lines.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", lines.join("\n"))
+    };
+
+    // Per-process name so a writer that bypasses the lock cannot clobber our temp file.
+    let temp_file = sidecar_path(usage_file, &format!(".{}.tmp", std::process::id()));
     fs::write(&temp_file, output).map_err(|source| WorkflowError::UsageWrite {
         path: temp_file.clone(),
         source,
@@ -197,4 +560,285 @@ mod tests {
             "valid timestamp should map to positive sort key"
         );
     }
+
+    #[test]
+    fn usage_log_legacy_lines_count_as_single_hit() {
+        let temp = tempdir().expect("create temp dir");
+        let log_path = temp.path().join("usage.log");
+        let project_path = temp.path().join("projects/delta");
+
+        fs::write(
+            &log_path,
+            format!(
+                "{} | 2025-01-02 03:04:05\nepsilon | 2025-01-02 03:04:05 | 7\n",
+                project_path.to_string_lossy()
+            ),
+        )
+        .expect("write usage log");
+
+        let usage = UsageLog::load(&log_path);
+        let delta = usage
+            .entry_for(&project_path, "delta")
+            .expect("delta entry should load");
+        assert_eq!(delta.count, 1, "legacy two-field line should count once");
+
+        let epsilon = usage
+            .entry_for(Path::new("/elsewhere/epsilon"), "epsilon")
+            .expect("epsilon entry should load");
+        assert_eq!(
+            epsilon.count, 7,
+            "third field should be parsed as hit count"
+        );
+    }
+
+    #[test]
+    fn usage_log_record_usage_increments_hit_count() {
+        let temp = tempdir().expect("create temp dir");
+        let usage_file = temp.path().join("usage.log");
+        let project_path = temp.path().join("workspace/zeta");
+        fs::create_dir_all(&project_path).expect("create project dir");
+
+        fs::write(&usage_file, "zeta | 2024-01-01 00:00:00 | 4\n").expect("seed usage");
+
+        record_usage(&project_path, &usage_file).expect("first record should succeed");
+        record_usage(&project_path, &usage_file).expect("second record should succeed");
+
+        let usage = UsageLog::load(&usage_file);
+        let entry = usage
+            .entry_for(&project_path, "zeta")
+            .expect("entry should exist after recording");
+        assert_eq!(
+            entry.count, 6,
+            "legacy count should carry over and increment"
+        );
+
+        let content = fs::read_to_string(&usage_file).expect("read usage file");
+        assert_eq!(content.lines().count(), 1, "one line per project");
+    }
+
+    #[test]
+    fn record_usage_migrates_legacy_lines_to_jsonl() {
+        let temp = tempdir().expect("create temp dir");
+        let usage_file = temp.path().join("usage.log");
+        let project_path = temp.path().join("workspace/eta");
+        fs::create_dir_all(&project_path).expect("create project dir");
+
+        fs::write(
+            &usage_file,
+            "/legacy/theta | 2024-02-03 04:05:06 | 3\n/legacy/iota | 2024-02-04 00:00:00\n",
+        )
+        .expect("seed legacy usage");
+
+        record_usage_with_action(&project_path, &usage_file, "github")
+            .expect("record should succeed");
+
+        let content = fs::read_to_string(&usage_file).expect("read usage file");
+        let records = content
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("jsonl line"))
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 3);
+        assert!(
+            records
+                .iter()
+                .all(|record| record["schema_version"] == USAGE_SCHEMA_VERSION)
+        );
+        assert_eq!(records[0]["path"], "/legacy/theta");
+        assert_eq!(records[0]["last_used"], "2024-02-03 04:05:06");
+        assert_eq!(records[0]["use_count"], 3);
+        assert_eq!(records[2]["use_count"], 1);
+        assert_eq!(records[2]["last_action"], "github");
+
+        let usage = UsageLog::load(&usage_file);
+        let theta = usage
+            .entry_for(Path::new("/legacy/theta"), "theta")
+            .expect("migrated entry keeps history");
+        assert_eq!(theta.count, 3);
+        assert_eq!(theta.timestamp, "2024-02-03 04:05:06");
+        let eta = usage
+            .entry_for(&project_path, "eta")
+            .expect("recorded entry");
+        assert_eq!(eta.last_action.as_deref(), Some("github"));
+    }
+
+    #[test]
+    fn usage_log_reads_mixed_legacy_and_jsonl_lines() {
+        let temp = tempdir().expect("create temp dir");
+        let log_path = temp.path().join("usage.log");
+        fs::write(
+            &log_path,
+            concat!(
+                "/p/kappa | 2024-01-01 00:00:00 | 2\n",
+                "{\"schema_version\":1,\"path\":\"/p/lambda\",\"last_used\":\"2025-01-01 00:00:00\",\"use_count\":5,\"future_field\":true}\n",
+                "{\"path\":\"/p/kappa\",\"last_used\":\"2025-02-02 00:00:00\",\"use_count\":4}\n",
+                "{not json\n",
+            ),
+        )
+        .expect("write usage log");
+
+        let usage = UsageLog::load(&log_path);
+        let lambda = usage
+            .entry_for(Path::new("/p/lambda"), "lambda")
+            .expect("jsonl entry with unknown fields loads");
+        assert_eq!(lambda.count, 5);
+        let kappa = usage
+            .entry_for(Path::new("/p/kappa"), "kappa")
+            .expect("later jsonl line wins over legacy line");
+        assert_eq!(kappa.timestamp, "2025-02-02 00:00:00");
+        assert_eq!(kappa.count, 4);
+    }
+
+    #[test]
+    fn prune_removes_missing_paths_and_decays_counts_once() {
+        let temp = tempdir().expect("create temp dir");
+        let usage_file = temp.path().join("usage.log");
+        let alive = temp.path().join("alive");
+        let faded = temp.path().join("faded");
+        fs::create_dir_all(&alive).expect("create alive dir");
+        fs::create_dir_all(&faded).expect("create faded dir");
+
+        fs::write(
+            &usage_file,
+            format!(
+                "{} | 2025-01-01 00:00:00 | 8\n{} | 2024-01-01 00:00:00 | 1\n{} | 2025-01-01 00:00:00 | 9\nomega | 2025-01-01 00:00:00 | 2\n",
+                alive.display(),
+                faded.display(),
+                temp.path().join("deleted").display(),
+            ),
+        )
+        .expect("seed usage");
+
+        // 30 days after the alive hit: one half-life with the default setting.
+        let now = parse_usage_timestamp(Some("2025-01-31 00:00:00"));
+        let report = prune_usage(&usage_file, DEFAULT_PRUNE_HALF_LIFE_DAYS, now)
+            .expect("prune should succeed");
+        assert_eq!(
+            report,
+            PruneReport {
+                removed_missing: 1,
+                removed_decayed: 1,
+                kept: 2,
+            }
+        );
+
+        let usage = UsageLog::load(&usage_file);
+        let entry = usage.entry_for(&alive, "alive").expect("alive kept");
+        assert_eq!(entry.count, 4, "one half-life halves the count");
+        assert_eq!(entry.timestamp, "2025-01-01 00:00:00", "last hit untouched");
+        assert_eq!(entry.decayed_at.as_deref(), Some("2025-01-31 00:00:00"));
+        assert!(
+            usage.entry_for(Path::new("/x/omega"), "omega").is_some(),
+            "basename keys are not existence-checked"
+        );
+
+        // Pruning again at the same instant must not decay a second time.
+        prune_usage(&usage_file, DEFAULT_PRUNE_HALF_LIFE_DAYS, now).expect("second prune");
+        let usage = UsageLog::load(&usage_file);
+        assert_eq!(usage.entry_for(&alive, "alive").map(|e| e.count), Some(4));
+    }
+
+    #[test]
+    fn export_usage_emits_records_in_file_order() {
+        let temp = tempdir().expect("create temp dir");
+        let usage_file = temp.path().join("usage.log");
+        fs::write(
+            &usage_file,
+            "/p/one | 2025-01-01 00:00:00 | 2\n/p/two | 2025-01-02 00:00:00\n/p/one | 2025-01-03 00:00:00 | 3\n",
+        )
+        .expect("seed usage");
+
+        let exported = export_usage(&usage_file);
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[0]["path"], "/p/one");
+        assert_eq!(exported[0]["use_count"], 3);
+        assert_eq!(exported[0]["last_used"], "2025-01-03 00:00:00");
+        assert_eq!(exported[1]["path"], "/p/two");
+        assert_eq!(exported[1]["schema_version"], USAGE_SCHEMA_VERSION);
+
+        assert!(export_usage(&temp.path().join("missing.log")).is_empty());
+    }
+
+    #[test]
+    fn archive_sidecar_round_trips_without_touching_usage() {
+        let temp = tempdir().expect("create temp dir");
+        let usage_file = temp.path().join("usage.log");
+        let alpha = Path::new("/p/alpha");
+        let beta = Path::new("/p/beta");
+
+        assert!(archive_project(&usage_file, alpha).expect("archive alpha"));
+        assert!(archive_project(&usage_file, beta).expect("archive beta"));
+        assert!(!archive_project(&usage_file, alpha).expect("archive alpha twice"));
+        assert!(unarchive_project(&usage_file, beta).expect("unarchive beta"));
+        assert!(!unarchive_project(&usage_file, beta).expect("unarchive beta twice"));
+
+        assert_eq!(load_archived(&usage_file), vec![alpha.to_path_buf()]);
+        assert!(!usage_file.exists(), "usage log itself is not created");
+        let log = UsageLog::load(&usage_file);
+        assert!(log.is_archived(alpha));
+        assert!(!log.is_archived(beta));
+    }
+
+    #[test]
+    fn concurrent_record_usage_loses_no_hits() {
+        let temp = tempdir().expect("create temp dir");
+        let usage_file = temp.path().join("usage.log");
+        let shared = temp.path().join("shared");
+        let writers = 8;
+        let hits_per_writer = 15;
+
+        std::thread::scope(|scope| {
+            for writer in 0..writers {
+                let usage_file = &usage_file;
+                let shared = &shared;
+                let own = temp.path().join(format!("own-{writer}"));
+                scope.spawn(move || {
+                    for _ in 0..hits_per_writer {
+                        record_usage(shared, usage_file).expect("record shared usage");
+                        record_usage(&own, usage_file).expect("record own usage");
+                    }
+                });
+            }
+        });
+
+        let log = UsageLog::load(&usage_file);
+        let count_for = |path: &Path| {
+            log.entry_for(path, "")
+                .map(|entry| entry.count)
+                .expect("entry recorded")
+        };
+        assert_eq!(count_for(&shared), writers * hits_per_writer);
+        for writer in 0..writers {
+            assert_eq!(
+                count_for(&temp.path().join(format!("own-{writer}"))),
+                hits_per_writer
+            );
+        }
+
+        let content = fs::read_to_string(&usage_file).expect("read usage file");
+        assert_eq!(content.lines().count(), writers as usize + 1);
+        assert!(
+            content.lines().all(|line| parse_usage_line(line).is_some()),
+            "every line stays a valid record"
+        );
+    }
+
+    #[test]
+    fn frecency_score_decays_with_age() {
+        let now = parse_usage_timestamp(Some("2025-03-01 00:00:00"));
+        let fresh = frecency_score(1, now, now);
+        let one_half_life = frecency_score(4, now - FRECENCY_HALF_LIFE_SECS, now);
+        let stale = frecency_score(4, now - 4 * FRECENCY_HALF_LIFE_SECS, now);
+
+        assert!((fresh - 1.0).abs() < f64::EPSILON);
+        assert!((one_half_life - 2.0).abs() < 1e-9);
+        assert!(
+            stale < fresh,
+            "old hits should decay below a fresh single hit"
+        );
+        assert_eq!(
+            frecency_score(3, 0, now),
+            0.0,
+            "invalid timestamp scores zero"
+        );
+    }
 }
@@ -0,0 +1,344 @@
+//! Monorepo workspace member detection (Cargo, npm/yarn, pnpm, `go.work`).
+//!
+//! Member patterns are expanded with the discovery glob syntax (`*`, `**`, `?`) relative to
+//! the workspace root. `**` descends at most [`MAX_MEMBER_DEPTH`] levels, and dependency or
+//! build folders (`node_modules`, `target`, `.git`) are never entered.
+
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::glob::glob_match_path;
+
+pub const MAX_MEMBER_DEPTH: usize = 4;
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules", "target"];
+
+/// Workspace manifest a member was declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceKind {
+    Cargo,
+    Npm,
+    Pnpm,
+    Go,
+}
+
+impl WorkspaceKind {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo workspace",
+            Self::Npm => "npm workspace",
+            Self::Pnpm => "pnpm workspace",
+            Self::Go => "go.work",
+        }
+    }
+
+    /// File every member directory must contain.
+    const fn member_manifest(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.toml",
+            Self::Npm | Self::Pnpm => "package.json",
+            Self::Go => "go.mod",
+        }
+    }
+}
+
+/// Member directories declared by `root`'s workspace manifests, sorted by path.
+///
+/// The root itself is never reported; a directory listed by several manifests keeps the
+/// first kind found (Cargo, pnpm, npm, go).
+pub fn workspace_members(root: &Path) -> Vec<(PathBuf, WorkspaceKind)> {
+    let mut members = BTreeMap::new();
+    for (kind, patterns) in workspace_patterns(root) {
+        let (include, exclude): (Vec<_>, Vec<_>) = patterns
+            .iter()
+            .map(|pattern| {
+                pattern
+                    .trim()
+                    .trim_start_matches("./")
+                    .trim_end_matches('/')
+            })
+            .filter(|pattern| !pattern.is_empty())
+            .partition(|pattern| !pattern.starts_with('!'));
+        let exclude = exclude
+            .iter()
+            .map(|pattern| pattern.trim_start_matches('!').trim_start_matches("./"))
+            .collect::<Vec<_>>();
+
+        for pattern in include {
+            for member in expand_pattern(root, pattern) {
+                let relative = relative_key(root, &member);
+                if relative.is_empty()
+                    || exclude
+                        .iter()
+                        .any(|excluded| glob_match_path(excluded, &relative))
+                    || !member.join(kind.member_manifest()).is_file()
+                {
+                    continue;
+                }
+                members.entry(member).or_insert(kind);
+            }
+        }
+    }
+
+    members.into_iter().collect()
+}
+
+/// Include patterns (and `!`-prefixed excludes) per manifest found at `root`.
+fn workspace_patterns(root: &Path) -> Vec<(WorkspaceKind, Vec<String>)> {
+    let mut found = Vec::new();
+    if let Some(patterns) = read(root, "Cargo.toml").and_then(|raw| cargo_patterns(&raw)) {
+        found.push((WorkspaceKind::Cargo, patterns));
+    }
+    if let Some(patterns) = read(root, "pnpm-workspace.yaml").map(|raw| pnpm_patterns(&raw)) {
+        found.push((WorkspaceKind::Pnpm, patterns));
+    }
+    if let Some(patterns) = read(root, "package.json").and_then(|raw| npm_patterns(&raw)) {
+        found.push((WorkspaceKind::Npm, patterns));
+    }
+    if let Some(patterns) = read(root, "go.work").map(|raw| go_work_patterns(&raw)) {
+        found.push((WorkspaceKind::Go, patterns));
+    }
+    found
+}
+
+fn read(root: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(root.join(file)).ok()
+}
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    workspace: Option<CargoWorkspace>,
+}
+
+#[derive(Deserialize)]
+struct CargoWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+fn cargo_patterns(raw: &str) -> Option<Vec<String>> {
+    let workspace = toml::from_str::<CargoManifest>(raw).ok()?.workspace?;
+    Some(
+        workspace
+            .members
+            .into_iter()
+            .chain(workspace.exclude.into_iter().map(|path| format!("!{path}")))
+            .collect(),
+    )
+}
+
+/// `workspaces` as an array or as `{ "packages": [...] }` (yarn classic).
+fn npm_patterns(raw: &str) -> Option<Vec<String>> {
+    let manifest = serde_json::from_str::<serde_json::Value>(raw).ok()?;
+    let workspaces = manifest.get("workspaces")?;
+    let list = workspaces
+        .as_array()
+        .or_else(|| workspaces.get("packages")?.as_array())?;
+    Some(
+        list.iter()
+            .filter_map(|value| value.as_str().map(ToString::to_string))
+            .collect(),
+    )
+}
+
+/// The `packages:` block list of `pnpm-workspace.yaml`; other keys are ignored.
+fn pnpm_patterns(raw: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let item = item.split(" #").next().unwrap_or_default().trim();
+            patterns.push(item.trim_matches(['"', '\'']).to_string());
+        }
+    }
+    patterns
+}
+
+/// `use ./dir` lines and `use ( ... )` blocks of a `go.work` file.
+fn go_work_patterns(raw: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_block = false;
+    for line in raw.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                patterns.push(line.trim_matches('"').to_string());
+            }
+            continue;
+        }
+        match line.strip_prefix("use").map(str::trim) {
+            Some("(") => in_block = true,
+            Some(path) if !path.is_empty() => patterns.push(path.trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    patterns
+}
+
+fn expand_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    if !pattern.contains(['*', '?']) {
+        let path = root.join(pattern);
+        return if path.is_dir() {
+            vec![path]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let max_depth = if pattern.contains("**") {
+        MAX_MEMBER_DEPTH
+    } else {
+        pattern.split('/').count()
+    };
+    WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.file_type().is_dir()
+                && !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+                && entry.file_name() != OsStr::new("")
+        })
+        .flatten()
+        .map(|entry| entry.into_path())
+        .filter(|path| glob_match_path(pattern, &relative_key(root, path)))
+        .collect()
+}
+
+fn relative_key(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map(|relative| {
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn touch(path: &Path, contents: &str) {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("create parent dir");
+        }
+        fs::write(path, contents).expect("write file");
+    }
+
+    fn relative_members(root: &Path) -> Vec<(String, WorkspaceKind)> {
+        workspace_members(root)
+            .into_iter()
+            .map(|(path, kind)| (relative_key(root, &path), kind))
+            .collect()
+    }
+
+    #[test]
+    fn cargo_members_expand_globs_and_honor_exclude() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path();
+        touch(
+            &root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\", \"tools/cli\"]\nexclude = [\"crates/legacy\"]\n",
+        );
+        touch(&root.join("crates/core/Cargo.toml"), "[package]");
+        touch(&root.join("crates/legacy/Cargo.toml"), "[package]");
+        touch(&root.join("crates/notes/README.md"), "no manifest");
+        touch(&root.join("tools/cli/Cargo.toml"), "[package]");
+
+        assert_eq!(
+            relative_members(root),
+            vec![
+                ("crates/core".to_string(), WorkspaceKind::Cargo),
+                ("tools/cli".to_string(), WorkspaceKind::Cargo),
+            ]
+        );
+    }
+
+    #[test]
+    fn npm_and_pnpm_workspaces_support_negation() {
+        let temp = tempdir().expect("create temp dir");
+        let npm = temp.path().join("npm");
+        touch(
+            &npm.join("package.json"),
+            r#"{"name":"root","workspaces":{"packages":["packages/*","!packages/private"]}}"#,
+        );
+        touch(&npm.join("packages/web/package.json"), "{}");
+        touch(&npm.join("packages/private/package.json"), "{}");
+        touch(&npm.join("node_modules/dep/package.json"), "{}");
+        assert_eq!(
+            relative_members(&npm),
+            vec![("packages/web".to_string(), WorkspaceKind::Npm)]
+        );
+
+        let pnpm = temp.path().join("pnpm");
+        touch(
+            &pnpm.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/**'\n  - \"!apps/legacy\" # retired\ncatalog:\n  - ignored\n",
+        );
+        touch(&pnpm.join("package.json"), r#"{"name":"root"}"#);
+        touch(&pnpm.join("apps/site/package.json"), "{}");
+        touch(&pnpm.join("apps/group/admin/package.json"), "{}");
+        touch(&pnpm.join("apps/legacy/package.json"), "{}");
+        assert_eq!(
+            relative_members(&pnpm),
+            vec![
+                ("apps/group/admin".to_string(), WorkspaceKind::Pnpm),
+                ("apps/site".to_string(), WorkspaceKind::Pnpm),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_work_reads_single_and_block_use_directives() {
+        let temp = tempdir().expect("create temp dir");
+        let root = temp.path();
+        touch(
+            &root.join("go.work"),
+            "go 1.22\n\nuse ./tools\nuse (\n\t./svc/api // main service\n\t./svc/missing\n\t.\n)\n",
+        );
+        touch(&root.join("go.mod"), "module root");
+        touch(&root.join("tools/go.mod"), "module tools");
+        touch(&root.join("svc/api/go.mod"), "module api");
+
+        assert_eq!(
+            relative_members(root),
+            vec![
+                ("svc/api".to_string(), WorkspaceKind::Go),
+                ("tools".to_string(), WorkspaceKind::Go),
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_projects_have_no_members() {
+        let temp = tempdir().expect("create temp dir");
+        touch(
+            &temp.path().join("Cargo.toml"),
+            "[package]\nname = \"solo\"\n",
+        );
+        touch(&temp.path().join("package.json"), r#"{"name":"solo"}"#);
+        assert!(workspace_members(temp.path()).is_empty());
+    }
+}
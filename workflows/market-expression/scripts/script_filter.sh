@@ -56,16 +56,16 @@ print_error_item() {
     subtitle="Package workflow or set MARKET_CLI_BIN to an executable market-cli path."
   elif [[ "$lower" == *"unsupported operator"* || "$lower" == *"operator '*'"* || "$lower" == *"operator '/'"* || "$lower" == *"operator *"* || "$lower" == *"operator /"* || "$lower" == *"unsupported *"* || "$lower" == *"unsupported /"* ]]; then
     title="Unsupported operator"
-    subtitle="Asset expressions support +/-. Numeric-only expressions support + - * /."
+    subtitle="Amounts can be added or subtracted, and multiplied or divided by plain numbers (for example: * 0.9)."
   elif [[ "$lower" == *"mixed asset and numeric"* || "$lower" == *"mixed numeric and asset"* || "$lower" == *"cannot mix asset and numeric"* || "$lower" == *"cannot mix numeric and asset"* || "$lower" == *"asset and numeric terms"* || "$lower" == *"numeric and asset terms"* ]]; then
     title="Invalid expression terms"
-    subtitle="Do not mix asset symbols and raw numeric-only terms in the same side of expression."
+    subtitle="Plain numbers can scale amounts (* 0.9) or apply percentages (+ 5%), but cannot be added to them."
   elif [[ "$lower" == *"invalid to clause"* || "$lower" == *"incomplete to clause"* || "$lower" == *"invalid to-clause"* || "$lower" == *"incomplete to-clause"* || "$lower" == *"missing target after to"* || "$lower" == *"expected target after to"* ]]; then
     title="Invalid to-clause"
     subtitle="Use a complete target clause, for example: 1 BTC + 2 ETH to USD."
   elif [[ "$lower" == *"invalid expression"* || "$lower" == *"parse error"* || "$lower" == *"syntax error"* || "$lower" == *"expected expression"* || "$lower" == *"unexpected token"* || "$lower" == *"invalid token"* ]]; then
     title="Invalid expression"
-    subtitle="Use market terms with + or -, for example: 1 BTC + 2 ETH to USD."
+    subtitle="Use market terms with + - * / % and parentheses, for example: (1 BTC + 2 ETH) * 0.9 to USD."
   elif [[ "$lower" == *"provider"* || "$lower" == *"upstream"* || "$lower" == *"rate limit"* || "$lower" == *"429"* ]]; then
     title="Market Expression provider failure"
     subtitle="Failed to fetch market data from provider. Retry shortly."
@@ -54,6 +54,9 @@ fi
 if ! rg -n '^MARKET_CRYPTO_CACHE_TTL[[:space:]]*=[[:space:]]*""' "$manifest" >/dev/null; then
   fail "MARKET_CRYPTO_CACHE_TTL default must be empty"
 fi
+if ! rg -n '^MARKET_FEE_PCT[[:space:]]*=[[:space:]]*""' "$manifest" >/dev/null; then
+  fail "MARKET_FEE_PCT default must be empty"
+fi
 if ! rg -n '^MARKET_FAVORITES_ENABLED[[:space:]]*=[[:space:]]*"1"' "$manifest" >/dev/null; then
   fail "MARKET_FAVORITES_ENABLED default must be 1"
 fi
@@ -270,6 +273,14 @@ exit 2
 EOS
 chmod +x "$tmp_dir/stubs/market-cli-invalid-expression"
 
+cat >"$tmp_dir/stubs/market-cli-unbalanced" <<'EOS'
+#!/usr/bin/env bash
+set -euo pipefail
+echo "invalid expression: missing closing \`)\`" >&2
+exit 2
+EOS
+chmod +x "$tmp_dir/stubs/market-cli-unbalanced"
+
 cat >"$tmp_dir/stubs/market-cli-provider" <<'EOS'
 #!/usr/bin/env bash
 set -euo pipefail
@@ -341,6 +352,10 @@ assert_jq_json "$invalid_to_json" '.items[0].title == "Invalid to-clause"' "inva
 invalid_expression_json="$({ MARKET_CLI_BIN="$tmp_dir/stubs/market-cli-invalid-expression" "$workflow_dir/scripts/script_filter.sh" "BTC to"; })"
 assert_jq_json "$invalid_expression_json" '.items[0].title == "Invalid expression"' "invalid expression title mapping mismatch"
 
+unbalanced_json="$({ MARKET_CLI_BIN="$tmp_dir/stubs/market-cli-unbalanced" "$workflow_dir/scripts/script_filter.sh" "(1 BTC + 2 ETH * 0.9"; })"
+assert_jq_json "$unbalanced_json" '.items[0].title == "Invalid expression"' "unbalanced parentheses title mapping mismatch"
+assert_jq_json "$unbalanced_json" '.items[0].subtitle | contains("parentheses")' "unbalanced parentheses subtitle mismatch"
+
 provider_json="$({ MARKET_CLI_BIN="$tmp_dir/stubs/market-cli-provider" "$workflow_dir/scripts/script_filter.sh" "1 BTC to USD"; })"
 assert_jq_json "$provider_json" '.items[0].title == "Market Expression provider failure"' "provider failure title mapping mismatch"
 
@@ -513,11 +528,12 @@ assert_jq_file "$packaged_json_file" '.objects[] | select(.uid=="D7E624DB-D4AB-4
 assert_jq_file "$packaged_json_file" '.objects[] | select(.uid=="D7E624DB-D4AB-4D53-8C03-D051A1A97A4A") | .config.type == 8' "action node must be external script type=8"
 assert_jq_file "$packaged_json_file" '.connections["96AC3342-84A9-449E-B0AB-114E2068FC34"] | any(.destinationuid == "70EEA820-E77B-42F3-A8D2-1A4D9E8E4A10" and .modifiers == 0)' "missing hotkey to script-filter connection"
 assert_jq_file "$packaged_json_file" '.connections["70EEA820-E77B-42F3-A8D2-1A4D9E8E4A10"] | any(.destinationuid == "D7E624DB-D4AB-4D53-8C03-D051A1A97A4A" and .modifiers == 0)' "missing script-filter to action connection"
-assert_jq_file "$packaged_json_file" '[.userconfigurationconfig[] | .variable] | sort == ["MARKET_CLI_BIN","MARKET_CRYPTO_CACHE_TTL","MARKET_DEFAULT_FIAT","MARKET_FAVORITES_ENABLED","MARKET_FAVORITE_LIST","MARKET_FX_CACHE_TTL"]' "user configuration variables mismatch"
+assert_jq_file "$packaged_json_file" '[.userconfigurationconfig[] | .variable] | sort == ["MARKET_CLI_BIN","MARKET_CRYPTO_CACHE_TTL","MARKET_DEFAULT_FIAT","MARKET_FAVORITES_ENABLED","MARKET_FAVORITE_LIST","MARKET_FEE_PCT","MARKET_FX_CACHE_TTL"]' "user configuration variables mismatch"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_CLI_BIN") | .config.default == ""' "MARKET_CLI_BIN default must be empty string"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_DEFAULT_FIAT") | .config.default == "USD"' "MARKET_DEFAULT_FIAT default must be USD"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_FX_CACHE_TTL") | .config.default == ""' "MARKET_FX_CACHE_TTL default must be empty string"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_CRYPTO_CACHE_TTL") | .config.default == ""' "MARKET_CRYPTO_CACHE_TTL default must be empty string"
+assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_FEE_PCT") | .config.default == ""' "MARKET_FEE_PCT default must be empty string"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_FAVORITES_ENABLED") | .config.default == "1"' "MARKET_FAVORITES_ENABLED default must be 1"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="MARKET_FAVORITE_LIST") | .config.default == "BTC,ETH,EUR,JPY"' "MARKET_FAVORITE_LIST default must be BTC,ETH,EUR,JPY"
 
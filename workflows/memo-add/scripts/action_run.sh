@@ -72,16 +72,109 @@ if [[ "$rc" -eq 0 ]]; then
     exit 0
   fi
 
+  if [[ "$action_token" == copy-md::* || "$action_token" == copy-html::* ]]; then
+    if ! command -v pbcopy >/dev/null 2>&1; then
+      notify "Memo action failed"
+      echo "pbcopy not found for copy action" >&2
+      exit 1
+    fi
+
+    if [[ "$action_token" == copy-html::* ]]; then
+      printf '%s' "$output" | pbcopy
+      notify "Memo HTML copied"
+      exit 0
+    fi
+
+    if ! command -v textutil >/dev/null 2>&1; then
+      notify "Memo action failed"
+      echo "textutil not found for rich-text copy action" >&2
+      exit 1
+    fi
+
+    # pbcopy stores RTF input as rich text, so rich-text apps paste the rendered formatting.
+    printf '%s' "$output" | textutil -stdin -format html -inputencoding UTF-8 -convert rtf -stdout | pbcopy
+    notify "Memo copied as rich text"
+    exit 0
+  fi
+
+  if [[ "$action_token" == open-attachment::* ]]; then
+    if ! command -v open >/dev/null 2>&1; then
+      notify "Memo action failed"
+      echo "open not found for attachment action" >&2
+      exit 1
+    fi
+
+    open "$output"
+    notify "Memo attachment opened"
+    exit 0
+  fi
+
+  if [[ "$action_token" == open-link::* ]]; then
+    if ! command -v open >/dev/null 2>&1; then
+      notify "Memo action failed"
+      echo "open not found for link action" >&2
+      exit 1
+    fi
+
+    open "$output"
+    notify "Memo link opened"
+    exit 0
+  fi
+
   [[ -n "$output" ]] && printf '%s\n' "$output"
 
   if [[ "$action_token" == "db-init" ]]; then
     notify "Memo DB initialized"
   elif [[ "$action_token" == add::* ]]; then
     notify "Memo added"
+  elif [[ "$action_token" == clip::* ]]; then
+    notify "Memo added from clipboard"
   elif [[ "$action_token" == update::* ]]; then
     notify "Memo updated"
   elif [[ "$action_token" == delete::* ]]; then
     notify "Memo deleted"
+  elif [[ "$action_token" == restore::* ]]; then
+    notify "Memo restored"
+  elif [[ "$action_token" == purge::* ]]; then
+    notify "Memo purged"
+  elif [[ "$action_token" == pin::* ]]; then
+    notify "Memo pinned"
+  elif [[ "$action_token" == unpin::* ]]; then
+    notify "Memo unpinned"
+  elif [[ "$action_token" == export::* ]]; then
+    notify "Memo export saved"
+  elif [[ "$action_token" == remind::* ]]; then
+    if [[ "$output" == "reminder set"* ]]; then
+      notify "Memo reminder set"
+    else
+      notify "Memo reminder cleared"
+    fi
+  elif [[ "$action_token" == attach::* ]]; then
+    notify "Memo attachment added"
+  elif [[ "$action_token" == history-restore::* ]]; then
+    if [[ "$output" == unchanged* ]]; then
+      notify "Memo unchanged"
+    else
+      notify "Memo version restored"
+    fi
+  elif [[ "$action_token" == link::* ]]; then
+    notify "Memos linked"
+  elif [[ "$action_token" == merge::* ]]; then
+    notify "Memos merged"
+  elif [[ "$action_token" == template-add::* ]]; then
+    notify "Memo template saved"
+  elif [[ "$action_token" == "sync" ]]; then
+    notify "Memo sync complete"
+  elif [[ "$action_token" == batch::* ]]; then
+    notify "Memo batch applied"
+  elif [[ "$action_token" == undo::* ]]; then
+    notify "Memo undo applied"
+  elif [[ "$action_token" == edit::* ]]; then
+    if [[ "$output" == unchanged* ]]; then
+      notify "Memo unchanged"
+    else
+      notify "Memo edited"
+    fi
   else
     notify "Memo added"
   fi
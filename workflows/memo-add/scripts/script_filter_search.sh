@@ -30,7 +30,7 @@ first_token="${query%%[[:space:]]*}"
 first_token_lower="$(printf '%s' "$first_token" | tr '[:upper:]' '[:lower:]')"
 
 case "$first_token_lower" in
-item | update | delete | copy | search)
+item | update | delete | copy | search | tag | trash | export | edit | attach | remind | tpl | sync | stats | batch | history | link | linked | links | merge | undo | clip)
   exec "$script_dir/script_filter.sh" "$query"
   ;;
 *)
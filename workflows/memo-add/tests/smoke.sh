@@ -55,7 +55,7 @@ manifest="$workflow_dir/workflow.toml"
 [[ "$(toml_string "$manifest" script_filter)" == "script_filter_entry.sh" ]] || fail "script_filter mismatch"
 [[ "$(toml_string "$manifest" action)" == "action_run.sh" ]] || fail "action mismatch"
 
-for variable in MEMO_DB_PATH MEMO_SOURCE MEMO_REQUIRE_CONFIRM MEMO_MAX_INPUT_BYTES MEMO_RECENT_LIMIT MEMO_SEARCH_MATCH MEMO_WORKFLOW_CLI_BIN; do
+for variable in MEMO_DB_PATH MEMO_SOURCE MEMO_REQUIRE_CONFIRM MEMO_MAX_INPUT_BYTES MEMO_RECENT_LIMIT MEMO_SEARCH_MATCH MEMO_SEARCH_FTS MEMO_FUZZY_THRESHOLD MEMO_TRASH_RETENTION_DAYS MEMO_UNDO_WINDOW_MINUTES MEMO_PREVIEW_CHARS MEMO_LANG MEMO_EXPORT_DIR MEMO_EDITOR MEMO_SYNC_DIR MEMO_WORKFLOW_CLI_BIN; do
   rg -n "^${variable}[[:space:]]*=" "$manifest" >/dev/null || fail "missing env var: $variable"
 done
 
@@ -64,6 +64,12 @@ rg -n '^MEMO_REQUIRE_CONFIRM[[:space:]]*=[[:space:]]*"0"' "$manifest" >/dev/null
 rg -n '^MEMO_MAX_INPUT_BYTES[[:space:]]*=[[:space:]]*"4096"' "$manifest" >/dev/null || fail "MEMO_MAX_INPUT_BYTES default mismatch"
 rg -n '^MEMO_RECENT_LIMIT[[:space:]]*=[[:space:]]*"8"' "$manifest" >/dev/null || fail "MEMO_RECENT_LIMIT default mismatch"
 rg -n '^MEMO_SEARCH_MATCH[[:space:]]*=[[:space:]]*"fts"' "$manifest" >/dev/null || fail "MEMO_SEARCH_MATCH default mismatch"
+rg -n '^MEMO_SEARCH_FTS[[:space:]]*=[[:space:]]*"1"' "$manifest" >/dev/null || fail "MEMO_SEARCH_FTS default mismatch"
+rg -n '^MEMO_FUZZY_THRESHOLD[[:space:]]*=[[:space:]]*"60"' "$manifest" >/dev/null || fail "MEMO_FUZZY_THRESHOLD default mismatch"
+rg -n '^MEMO_TRASH_RETENTION_DAYS[[:space:]]*=[[:space:]]*"30"' "$manifest" >/dev/null || fail "MEMO_TRASH_RETENTION_DAYS default mismatch"
+rg -n '^MEMO_UNDO_WINDOW_MINUTES[[:space:]]*=[[:space:]]*"10"' "$manifest" >/dev/null || fail "MEMO_UNDO_WINDOW_MINUTES default mismatch"
+rg -n '^MEMO_PREVIEW_CHARS[[:space:]]*=[[:space:]]*"56"' "$manifest" >/dev/null || fail "MEMO_PREVIEW_CHARS default mismatch"
+rg -n '^MEMO_LANG[[:space:]]*=[[:space:]]*"en"' "$manifest" >/dev/null || fail "MEMO_LANG default mismatch"
 
 workflow_smoke_assert_action_requires_arg "$workflow_dir/scripts/action_run.sh"
 
@@ -134,7 +140,7 @@ emit_item_menu() {
   local item_display item_route
   item_display="$(item_display_id "$item_id")"
   item_route="$(item_route_id "$item_id")"
-  printf '{"items":[{"title":"Copy memo: %s | %s","arg":"copy::%s","valid":true,"mods":{"cmd":{"subtitle":"raw json","arg":"copy-json::%s","valid":true}}},{"title":"Update memo: %s | %s","autocomplete":"update %s ","valid":false},{"title":"Delete memo: %s | %s","arg":"delete::%s","valid":true}]}\n' "$item_display" "$memo_text" "$item_id" "$item_id" "$item_display" "$memo_text" "$item_route" "$item_display" "$memo_text" "$item_id"
+  printf '{"items":[{"title":"Copy memo: %s | %s","arg":"copy::%s","valid":true,"mods":{"cmd":{"subtitle":"raw json","arg":"copy-json::%s","valid":true}}},{"title":"Update memo: %s | %s","autocomplete":"update %s ","valid":false},{"title":"Delete memo: %s | %s","arg":"delete::%s","valid":true},{"title":"Pin memo: %s","arg":"pin::%s","valid":true},{"title":"Edit in editor: %s","arg":"edit::%s","valid":true}]}\n' "$item_display" "$memo_text" "$item_id" "$item_id" "$item_display" "$memo_text" "$item_route" "$item_display" "$memo_text" "$item_id" "$item_display" "$item_id" "$item_display" "$item_id"
 }
 
 if [[ "${1:-}" == "script-filter" && "${2:-}" == "--query" ]]; then
@@ -167,6 +173,39 @@ if [[ "${1:-}" == "script-filter" && "${2:-}" == "--query" ]]; then
     "search milk")
       printf '{"items":[{"title":"Search #1: buy milk","subtitle":"search","autocomplete":"item 1","valid":false}]}\n'
       ;;
+    "trash")
+      printf '{"items":[{"title":"Trash #1: buy milk","subtitle":"trash","arg":"restore::itm_00000001","valid":true,"mods":{"cmd":{"subtitle":"purge","arg":"purge::itm_00000001","valid":true}}}]}\n'
+      ;;
+    "export csv")
+      printf '{"items":[{"title":"Export memos as CSV","subtitle":"export","arg":"export::csv","valid":true}]}\n'
+      ;;
+    "sync")
+      printf '{"items":[{"title":"Sync memos with git","subtitle":"sync","arg":"sync","valid":true}]}\n'
+      ;;
+    "stats")
+      printf '{"items":[{"title":"2 memos (1 pinned, 0 in trash)","subtitle":"stats","valid":false}]}\n'
+      ;;
+    "links")
+      printf '{"items":[{"title":"#1: read https://example.com/docs","subtitle":"1 link | https://example.com/docs","autocomplete":"item 1","valid":false}]}\n'
+      ;;
+    "link 1 2")
+      printf '{"items":[{"title":"Link memos #1 <-> #2","subtitle":"buy milk <-> call mom | Press Enter to link","arg":"link::itm_00000001::itm_00000002","valid":true}]}\n'
+      ;;
+    "merge 1 2")
+      printf '{"items":[{"title":"Merge #2 into #1","subtitle":"Press Enter to merge","arg":"merge::itm_00000001::itm_00000002","valid":true},{"title":"Result: buy milk call mom","subtitle":"18 bytes","valid":false}]}\n'
+      ;;
+    "clip")
+      printf '{"items":[{"title":"Add clipboard: copied text","subtitle":"Source: clipboard","arg":"clip::copied text","valid":true}]}\n'
+      ;;
+    "undo")
+      printf '{"items":[{"title":"Undo delete #1: buy milk","subtitle":"Restores the memo from trash","arg":"undo::3","valid":true}]}\n'
+      ;;
+    "batch delete #work")
+      printf '{"items":[{"title":"Confirm batch delete: 1 memo","subtitle":"#1","arg":"batch::delete::itm_00000001","valid":true}]}\n'
+      ;;
+    "tag work")
+      printf '{"items":[{"title":"#work #1: buy milk","subtitle":"tag","autocomplete":"item 1","valid":false}]}\n'
+      ;;
     "item itm_00000001")
       emit_item_menu "itm_00000001" "buy milk"
       ;;
@@ -253,6 +292,63 @@ if [[ "${1:-}" == "action" && "${2:-}" == "--token" ]]; then
       printf 'deleted %s at 2026-02-12T12:10:00Z\n' "$item_id"
       exit 0
       ;;
+    pin::*)
+      printf 'pinned %s\n' "${token#pin::}"
+      exit 0
+      ;;
+    edit::*)
+      printf 'edited %s at 2026-02-12T12:20:00Z\n' "${token#edit::}"
+      exit 0
+      ;;
+    remind::*)
+      printf 'reminder set itm_00000001 at 2026-02-13T01:00:00.000Z (upcoming)\n'
+      exit 0
+      ;;
+    attach::*)
+      printf 'attached /tmp/receipt.pdf to itm_00000001 (attachments=1)\n'
+      exit 0
+      ;;
+    history-restore::*)
+      printf 'restored itm_00000001 from version 1 at 2026-02-12T12:30:00Z\n'
+      exit 0
+      ;;
+    link::*)
+      printf 'linked itm_00000001 <-> itm_00000002 (linked=1)\n'
+      exit 0
+      ;;
+    merge::*)
+      printf 'merged itm_00000002 into itm_00000001 at 2026-02-12T10:00:00Z (attachments_added=0, links_added=0, moved to trash)\n'
+      exit 0
+      ;;
+    open-attachment::*)
+      printf '/tmp/receipt.pdf'
+      exit 0
+      ;;
+    open-link::*)
+      printf 'https://example.com/docs'
+      exit 0
+      ;;
+    template-add::*)
+      template_name="${token#template-add::}"
+      printf 'saved template %s\n' "${template_name%%::*}"
+      exit 0
+      ;;
+    sync)
+      printf 'synced /tmp/memo-sync: wrote 1, removed 0, created 0, updated 0, purged 0, conflicts 0, pushed\n'
+      exit 0
+      ;;
+    batch::*)
+      printf 'batch delete: 1 succeeded, 0 failed\nitm_00000001 ok\n'
+      exit 0
+      ;;
+    clip::*)
+      printf 'added itm_00000002 at 2026-01-01T00:00:00.000Z\n'
+      exit 0
+      ;;
+    undo::*)
+      printf 'undid delete of itm_00000001 (restored) at 2026-01-01T00:00:00.000Z\n'
+      exit 0
+      ;;
     copy::*)
       item_id="${token#copy::}"
       if [[ -f "$state_file" ]]; then
@@ -266,6 +362,10 @@ if [[ "${1:-}" == "action" && "${2:-}" == "--token" ]]; then
       echo "item not found: $item_id" >&2
       exit 4
       ;;
+    copy-md::* | copy-html::*)
+      printf '<p><strong>buy</strong> oat milk</p>\n'
+      exit 0
+      ;;
     copy-json::*)
       item_id="${token#copy-json::}"
       if [[ -f "$state_file" ]]; then
@@ -314,6 +414,22 @@ fi
 EOS
 chmod +x "$tmp_dir/stubs/pbcopy"
 
+cat >"$tmp_dir/stubs/textutil" <<'EOS'
+#!/usr/bin/env bash
+set -euo pipefail
+printf '{\\rtf1 %s}' "$(cat)"
+EOS
+chmod +x "$tmp_dir/stubs/textutil"
+
+cat >"$tmp_dir/stubs/open" <<'EOS'
+#!/usr/bin/env bash
+set -euo pipefail
+if [[ -n "${MEMO_OPEN_LOG:-}" ]]; then
+  printf '%s\n' "$*" >>"$MEMO_OPEN_LOG"
+fi
+EOS
+chmod +x "$tmp_dir/stubs/open"
+
 success_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter.sh" "buy milk"; })"
 assert_jq_json "$success_json" '.items | type == "array" and length == 1' "script_filter success must return one item"
 assert_jq_json "$success_json" '.items[0].arg == "add::buy milk"' "script_filter add arg mismatch"
@@ -370,9 +486,44 @@ assert_jq_json "$keyword_search_json" '.items | length == 1' "mmq query should r
 assert_jq_json "$keyword_search_json" '.items[0].autocomplete == "item 1"' "mmq should route to item autocomplete"
 
 keyword_search_item_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "item 1"; })"
-assert_jq_json "$keyword_search_item_json" '.items | length == 3' "mmq item intent should keep full item menu"
+assert_jq_json "$keyword_search_item_json" '.items | length == 5' "mmq item intent should keep full item menu"
 assert_jq_json "$keyword_search_item_json" '.items[0].arg == "copy::itm_00000001"' "mmq item intent should include copy action"
 
+keyword_search_tag_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "tag work"; })"
+assert_jq_json "$keyword_search_tag_json" '.items[0].autocomplete == "item 1"' "mmq tag intent should pass through to tag rows"
+
+keyword_recent_trash_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_recent.sh" "trash"; })"
+assert_jq_json "$keyword_recent_trash_json" '.items[0].arg == "restore::itm_00000001"' "mmr trash intent should expose restore action"
+assert_jq_json "$keyword_recent_trash_json" '.items[0].mods.cmd.arg == "purge::itm_00000001"' "mmr trash intent should expose purge modifier"
+
+keyword_search_export_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "export csv"; })"
+assert_jq_json "$keyword_search_export_json" '.items[0].arg == "export::csv"' "mmq export intent should pass through to export rows"
+
+keyword_recent_sync_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_recent.sh" "sync"; })"
+assert_jq_json "$keyword_recent_sync_json" '.items[0].arg == "sync"' "mmr sync intent should pass through to sync row"
+
+keyword_search_stats_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "stats"; })"
+assert_jq_json "$keyword_search_stats_json" '.items[0].subtitle == "stats"' "mmq stats intent should pass through to stats rows"
+
+keyword_recent_batch_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_recent.sh" "batch delete #work"; })"
+assert_jq_json "$keyword_recent_batch_json" '.items[0].arg == "batch::delete::itm_00000001"' "mmr batch intent should pass through to batch confirm row"
+
+keyword_recent_undo_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_recent.sh" "undo"; })"
+assert_jq_json "$keyword_recent_undo_json" '.items[0].arg == "undo::3"' "mmr undo intent should pass through to undo row"
+
+keyword_search_clip_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "clip"; })"
+assert_jq_json "$keyword_search_clip_json" '.items[0].arg == "clip::copied text"' "mmq clip intent should pass through to clipboard add row"
+
+keyword_search_links_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "links"; })"
+assert_jq_json "$keyword_search_links_json" '.items[0].autocomplete == "item 1"' "mmq links intent should pass through to linked memo rows"
+
+keyword_search_link_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "link 1 2"; })"
+assert_jq_json "$keyword_search_link_json" '.items[0].arg == "link::itm_00000001::itm_00000002"' "mmq link intent should pass through to link row"
+
+keyword_search_merge_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" "merge 1 2"; })"
+assert_jq_json "$keyword_search_merge_json" '.items[0].arg == "merge::itm_00000001::itm_00000002"' "mmq merge intent should pass through to merge confirm row"
+assert_jq_json "$keyword_search_merge_json" '.items[1].valid == false' "mmq merge preview rows should not be actionable"
+
 keyword_search_empty_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_search.sh" ""; })"
 assert_jq_json "$keyword_search_empty_json" '.items[0].valid == false' "mmq empty query should show guidance row"
 assert_jq_json "$keyword_search_empty_json" '([.items[].arg // ""] | all(startswith("add::") | not))' "mmq empty query should not return add token"
@@ -382,10 +533,10 @@ assert_jq_json "$keyword_recent_json" '.items[0].arg == "add::"' "mmr should for
 
 keyword_recent_id_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_recent.sh" "1"; })"
 assert_jq_json "$keyword_recent_id_json" '.items[0].arg == "copy::itm_00000001"' "mmr numeric query should map to item lookup"
-assert_jq_json "$keyword_recent_id_json" '.items | length == 3' "mmr numeric query should keep full item menu"
+assert_jq_json "$keyword_recent_id_json" '.items | length == 5' "mmr numeric query should keep full item menu"
 keyword_recent_item_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter_recent.sh" "item 1"; })"
 assert_jq_json "$keyword_recent_item_json" '.items[0].arg == "copy::itm_00000001"' "mmr item intent should passthrough to item lookup"
-assert_jq_json "$keyword_recent_item_json" '.items | length == 3' "mmr item intent should keep full item menu"
+assert_jq_json "$keyword_recent_item_json" '.items | length == 5' "mmr item intent should keep full item menu"
 
 success_env_query_json="$({ alfred_workflow_query="buy milk" MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter.sh"; })"
 assert_jq_json "$success_env_query_json" '.items[0].arg == "add::buy milk"' "script_filter alfred_workflow_query fallback mismatch"
@@ -403,13 +554,15 @@ delete_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$w
 assert_jq_json "$delete_json" '.items[0].arg == "delete::itm_00000001"' "script_filter delete arg mismatch"
 
 item_menu_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter.sh" "item itm_00000001"; })"
-assert_jq_json "$item_menu_json" '.items | type == "array" and length == 3' "script_filter item menu length mismatch"
+assert_jq_json "$item_menu_json" '.items | type == "array" and length == 5' "script_filter item menu length mismatch"
 assert_jq_json "$item_menu_json" '.items[0].arg == "copy::itm_00000001"' "script_filter copy arg mismatch"
 assert_jq_json "$item_menu_json" '(.items[0].title | startswith("Copy memo: #1 |"))' "script_filter item copy title should include memo preview"
 assert_jq_json "$item_menu_json" '.items[0].mods.cmd.arg == "copy-json::itm_00000001"' "script_filter copy-json cmd arg mismatch"
 assert_jq_json "$item_menu_json" '(.items[1].title | startswith("Update memo: #1 |"))' "script_filter item update title should include memo preview"
 assert_jq_json "$item_menu_json" '.items[1].autocomplete == "update 1 "' "script_filter item update autocomplete mismatch"
 assert_jq_json "$item_menu_json" '.items[2].arg == "delete::itm_00000001"' "script_filter item delete arg mismatch"
+assert_jq_json "$item_menu_json" '.items[3].arg == "pin::itm_00000001"' "script_filter item pin arg mismatch"
+assert_jq_json "$item_menu_json" '.items[4].arg == "edit::itm_00000001"' "script_filter item edit arg mismatch"
 
 invalid_json="$({ MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-invalid" "$workflow_dir/scripts/script_filter.sh" "buy milk"; })"
 assert_jq_json "$invalid_json" '.items[0].title == "Invalid Memo workflow config"' "invalid config title mismatch"
@@ -493,6 +646,32 @@ crud_copy_json_token="$(jq -r '.items[0].mods.cmd.arg' <<<"$crud_copy_json")"
 rg -n --fixed-strings 'Memo JSON copied' "$notify_log" >/dev/null || fail "copy-json notification mismatch"
 assert_jq_json "$(cat "$clipboard_log")" '.item_id == "itm_00000001" and .text == "buy oat milk"' "copy-json clipboard payload mismatch"
 
+: >"$notify_log"
+: >"$clipboard_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_CLIPBOARD_LOG="$clipboard_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "copy-md::itm_00000001"
+} >/dev/null
+rg -n --fixed-strings 'Memo copied as rich text' "$notify_log" >/dev/null || fail "copy-md notification mismatch"
+[[ "$(cat "$clipboard_log")" == '{\rtf1 <p><strong>buy</strong> oat milk</p>}' ]] || fail "copy-md clipboard should hold RTF"
+
+: >"$notify_log"
+: >"$clipboard_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_CLIPBOARD_LOG="$clipboard_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "copy-html::itm_00000001"
+} >/dev/null
+rg -n --fixed-strings 'Memo HTML copied' "$notify_log" >/dev/null || fail "copy-html notification mismatch"
+[[ "$(cat "$clipboard_log")" == "<p><strong>buy</strong> oat milk</p>" ]] || fail "copy-html clipboard mismatch"
+
 crud_delete_json="$({ MEMO_DB_PATH="$crud_db_path" MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" "$workflow_dir/scripts/script_filter.sh" "delete itm_00000001"; })"
 crud_delete_token="$(jq -r '.items[0].arg' <<<"$crud_delete_json")"
 [[ "$crud_delete_token" == "delete::itm_00000001" ]] || fail "crud delete token mismatch"
@@ -508,6 +687,161 @@ crud_delete_output="$({
 rg -n --fixed-strings 'Memo deleted' "$notify_log" >/dev/null || fail "delete notification mismatch"
 [[ ! -s "$crud_state_path" ]] || fail "crud delete should clear state"
 
+: >"$notify_log"
+pin_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "pin::itm_00000001"
+})"
+[[ "$pin_output" == *"pinned itm_00000001"* ]] || fail "pin output mismatch"
+rg -n --fixed-strings 'Memo pinned' "$notify_log" >/dev/null || fail "pin notification mismatch"
+
+: >"$notify_log"
+edit_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "edit::itm_00000001"
+})"
+[[ "$edit_output" == *"edited itm_00000001"* ]] || fail "edit output mismatch"
+rg -n --fixed-strings 'Memo edited' "$notify_log" >/dev/null || fail "edit notification mismatch"
+
+: >"$notify_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "attach::itm_00000001::/tmp/receipt.pdf"
+} >/dev/null
+rg -n --fixed-strings 'Memo attachment added' "$notify_log" >/dev/null || fail "attach notification mismatch"
+
+: >"$notify_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "link::itm_00000001::itm_00000002"
+} >/dev/null
+rg -n --fixed-strings 'Memos linked' "$notify_log" >/dev/null || fail "link notification mismatch"
+
+: >"$notify_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "merge::itm_00000001::itm_00000002"
+} >/dev/null
+rg -n --fixed-strings 'Memos merged' "$notify_log" >/dev/null || fail "merge notification mismatch"
+
+: >"$notify_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "history-restore::1"
+} >/dev/null
+rg -n --fixed-strings 'Memo version restored' "$notify_log" >/dev/null || fail "history-restore notification mismatch"
+
+: >"$notify_log"
+template_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "template-add::standup::Standup {date}"
+})"
+[[ "$template_output" == *"saved template standup"* ]] || fail "template-add output mismatch"
+rg -n --fixed-strings 'Memo template saved' "$notify_log" >/dev/null || fail "template-add notification mismatch"
+
+: >"$notify_log"
+sync_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "sync"
+})"
+[[ "$sync_output" == *"synced /tmp/memo-sync"* ]] || fail "sync output mismatch"
+rg -n --fixed-strings 'Memo sync complete' "$notify_log" >/dev/null || fail "sync notification mismatch"
+
+: >"$notify_log"
+batch_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "batch::delete::itm_00000001"
+})"
+[[ "$batch_output" == *"batch delete: 1 succeeded"* ]] || fail "batch output mismatch"
+rg -n --fixed-strings 'Memo batch applied' "$notify_log" >/dev/null || fail "batch notification mismatch"
+
+: >"$notify_log"
+clip_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "clip::copied text"
+})"
+[[ "$clip_output" == *"added itm_00000002"* ]] || fail "clip output mismatch"
+rg -n --fixed-strings 'Memo added from clipboard' "$notify_log" >/dev/null || fail "clip notification mismatch"
+
+: >"$notify_log"
+undo_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "undo::3"
+})"
+[[ "$undo_output" == *"undid delete of itm_00000001"* ]] || fail "undo output mismatch"
+rg -n --fixed-strings 'Memo undo applied' "$notify_log" >/dev/null || fail "undo notification mismatch"
+
+: >"$notify_log"
+remind_output="$({
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "remind::itm_00000001::2026-02-13T01:00:00.000Z"
+})"
+[[ "$remind_output" == *"reminder set itm_00000001"* ]] || fail "remind output mismatch"
+rg -n --fixed-strings 'Memo reminder set' "$notify_log" >/dev/null || fail "remind notification mismatch"
+
+: >"$notify_log"
+open_log="$tmp_dir/open.log"
+: >"$open_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_OPEN_LOG="$open_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "open-attachment::1"
+} >/dev/null
+[[ "$(cat "$open_log")" == "/tmp/receipt.pdf" ]] || fail "open-attachment location mismatch"
+rg -n --fixed-strings 'Memo attachment opened' "$notify_log" >/dev/null || fail "open-attachment notification mismatch"
+
+: >"$notify_log"
+: >"$open_log"
+{
+  PATH="$tmp_dir/stubs:$PATH" \
+    MEMO_NOTIFY_LOG="$notify_log" \
+    MEMO_OPEN_LOG="$open_log" \
+    MEMO_DB_PATH="$crud_db_path" \
+    MEMO_WORKFLOW_CLI_BIN="$tmp_dir/stubs/memo-workflow-cli-ok" \
+    "$workflow_dir/scripts/action_run.sh" "open-link::itm_00000001::1"
+} >/dev/null
+[[ "$(cat "$open_log")" == "https://example.com/docs" ]] || fail "open-link url mismatch"
+rg -n --fixed-strings 'Memo link opened' "$notify_log" >/dev/null || fail "open-link notification mismatch"
+
 cat >"$tmp_dir/stubs/cargo" <<EOS
 #!/usr/bin/env bash
 set -euo pipefail
@@ -559,9 +893,15 @@ assert_jq_json "$packaged_json" '.objects[] | select(.type == "alfred.workflow.i
 assert_jq_json "$packaged_json" '.objects[] | select(.type == "alfred.workflow.input.scriptfilter" and .config.keyword == "mmq") | .config.scriptfile == "./scripts/script_filter_search.sh"' "mmq keyword should use search script"
 assert_jq_json "$packaged_json" '.connections | length == 14' "connection wiring mismatch"
 assert_jq_json "$packaged_json" '[.objects[] | select(.type == "alfred.workflow.trigger.hotkey")] | length == 7' "hotkey trigger count mismatch"
-assert_jq_json "$packaged_json" '[.userconfigurationconfig[].variable] | sort == ["MEMO_DB_PATH","MEMO_MAX_INPUT_BYTES","MEMO_RECENT_LIMIT","MEMO_REQUIRE_CONFIRM","MEMO_SEARCH_MATCH","MEMO_SOURCE","MEMO_WORKFLOW_CLI_BIN"]' "plist variable list mismatch"
+assert_jq_json "$packaged_json" '[.userconfigurationconfig[].variable] | sort == ["MEMO_DB_PATH","MEMO_EDITOR","MEMO_EXPORT_DIR","MEMO_FUZZY_THRESHOLD","MEMO_LANG","MEMO_MAX_INPUT_BYTES","MEMO_PREVIEW_CHARS","MEMO_RECENT_LIMIT","MEMO_REQUIRE_CONFIRM","MEMO_SEARCH_FTS","MEMO_SEARCH_MATCH","MEMO_SOURCE","MEMO_SYNC_DIR","MEMO_TRASH_RETENTION_DAYS","MEMO_UNDO_WINDOW_MINUTES","MEMO_WORKFLOW_CLI_BIN"]' "plist variable list mismatch"
 assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_MAX_INPUT_BYTES") | .config.default == "4096"' "plist default mismatch"
 assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_RECENT_LIMIT") | .config.default == "8"' "plist recent limit default mismatch"
 assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_SEARCH_MATCH") | .config.default == "fts"' "plist search match default mismatch"
+assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_SEARCH_FTS") | .config.default == "1"' "plist search fts default mismatch"
+assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_FUZZY_THRESHOLD") | .config.default == "60"' "plist fuzzy threshold default mismatch"
+assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_TRASH_RETENTION_DAYS") | .config.default == "30"' "plist trash retention default mismatch"
+assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_UNDO_WINDOW_MINUTES") | .config.default == "10"' "plist undo window default mismatch"
+assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_PREVIEW_CHARS") | .config.default == "56"' "plist preview chars default mismatch"
+assert_jq_json "$packaged_json" '.userconfigurationconfig[] | select(.variable == "MEMO_LANG") | .config.default == "en"' "plist language default mismatch"
 
 echo "ok: memo-add smoke test"
@@ -21,7 +21,14 @@ if [ "$#" -lt 1 ] || [ -z "$1" ]; then
 fi
 
 project_path="$(printf '%s' "$1")"
-if [ -z "$project_path" ] || [ ! -d "$project_path" ]; then
+# `open_project_remote_uri` is set by Remote-SSH rows, whose `host:/path` arg is not local.
+remote_uri="${open_project_remote_uri:-}"
+# `*.code-workspace` rows pass the workspace file itself; the editor opens it as a multi-root workspace.
+is_workspace_file=0
+case "$project_path" in
+*.code-workspace) [ -f "$project_path" ] && is_workspace_file=1 ;;
+esac
+if [ -z "$project_path" ] || { [ -z "$remote_uri" ] && [ "$is_workspace_file" -eq 0 ] && [ ! -d "$project_path" ]; }; then
   echo "error: project path is not a directory: $project_path" >&2
   exit 2
 fi
@@ -38,17 +45,27 @@ if ! wfhl_source_helper "$script_dir" "workflow_cli_resolver.sh" off; then
   exit 1
 fi
 
-vscode_bin_raw="${VSCODE_PATH:-/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code}"
+# `open_project_editor` carries the resolved VSCODE_PATH (env, config file, default) on rows, and
+# OPEN_PROJECT_EDITOR_2/3 on the alt/ctrl modifiers.
+vscode_bin_raw="${open_project_editor:-${VSCODE_PATH:-/Applications/Visual Studio Code.app/Contents/Resources/app/bin/code}}"
 vscode_bin="$(wfcr_expand_home_path "$vscode_bin_raw")"
 
+# `open_project_goto` (`file:line:column`) is set by `grep` search result rows.
+open_args=("$project_path")
+if [ -n "$remote_uri" ]; then
+  open_args=(--folder-uri "$remote_uri")
+elif [ -n "${open_project_goto:-}" ]; then
+  open_args=(-g "$open_project_goto")
+fi
+
 if [ -x "$vscode_bin" ]; then
-  exec "$vscode_bin" "$project_path"
+  exec "$vscode_bin" "${open_args[@]}"
 fi
 
 resolved_bin="$(command -v "$vscode_bin" 2>/dev/null || true)"
 if [ -n "$resolved_bin" ] && [ -x "$resolved_bin" ]; then
-  exec "$resolved_bin" "$project_path"
+  exec "$resolved_bin" "${open_args[@]}"
 fi
 
-echo "error: unable to execute VSCODE_PATH: $vscode_bin" >&2
+echo "error: unable to execute editor command: $vscode_bin" >&2
 exit 1
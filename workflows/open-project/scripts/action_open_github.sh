@@ -53,5 +53,11 @@ if [ -z "$project_path" ]; then
 fi
 
 workflow_cli="$(resolve_workflow_cli)"
-url="$("$workflow_cli" github-url --path "$project_path")"
+# Set by GitHub-mode modifiers (shift/alt/ctrl): pr | issues | ci.
+forge_page="${open_project_forge_page:-}"
+if [ -n "$forge_page" ]; then
+  url="$("$workflow_cli" forge-url --kind "$forge_page" --path "$project_path")"
+else
+  url="$("$workflow_cli" remote-url --path "$project_path")"
+fi
 exec open "$url"
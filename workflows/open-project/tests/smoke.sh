@@ -21,7 +21,8 @@ for required in \
   scripts/script_filter_github.sh \
   scripts/action_open.sh \
   scripts/action_record_usage.sh \
-  scripts/action_open_github.sh; do
+  scripts/action_open_github.sh \
+  scripts/action_open_terminal.sh; do
   assert_file "$workflow_dir/$required"
 done
 
@@ -30,7 +31,8 @@ for executable in \
   scripts/script_filter_github.sh \
   scripts/action_open.sh \
   scripts/action_record_usage.sh \
-  scripts/action_open_github.sh; do
+  scripts/action_open_github.sh \
+  scripts/action_open_terminal.sh; do
   assert_exec "$workflow_dir/$executable"
 done
 
@@ -62,9 +64,11 @@ script_filter_output="$({
 })"
 
 echo "$script_filter_output" | jq -e '.items | length > 0' >/dev/null
-echo "$script_filter_output" | jq -e '.items[0].title == "alpha-repo"' >/dev/null
-echo "$script_filter_output" | jq -e '.items[0].arg == $path' --arg path "$repo_path" >/dev/null
-echo "$script_filter_output" | jq -e '.items[0].mods.shift.icon.path == "assets/icon-github.png"' >/dev/null
+echo "$script_filter_output" | jq -e '[.items[].title] == ["Recently used", "alpha-repo", "All projects", "gitlab-repo"]' >/dev/null
+echo "$script_filter_output" | jq -e '.items[0].valid == false and (.items[0] | has("arg") | not)' >/dev/null
+echo "$script_filter_output" | jq -e '.items[1].arg == $path' --arg path "$repo_path" >/dev/null
+echo "$script_filter_output" | jq -e '.items[1].mods.shift.icon.path == "assets/icon-github.png"' >/dev/null
+echo "$script_filter_output" | jq -e '.items[1].mods.cmd.subtitle == "Open in Terminal"' >/dev/null
 
 gitlab_script_filter_output="$({
   PROJECT_DIRS="$project_root" \
@@ -86,7 +90,7 @@ script_filter_tilde_bin_output="$({
     WORKFLOW_CLI_BIN=\~/.local/bin/workflow-cli \
     "$workflow_dir/scripts/script_filter.sh" ""
 })"
-echo "$script_filter_tilde_bin_output" | jq -e '.items[0].title == "alpha-repo"' >/dev/null
+echo "$script_filter_tilde_bin_output" | jq -e '.items[1].title == "alpha-repo"' >/dev/null
 
 github_filter_output="$({
   PROJECT_DIRS="$project_root" \
@@ -94,7 +98,11 @@ github_filter_output="$({
     WORKFLOW_CLI_BIN="$repo_root/target/debug/workflow-cli" \
     "$workflow_dir/scripts/script_filter_github.sh" ""
 })"
-echo "$github_filter_output" | jq -e '.items[0].icon.path == "assets/icon-github.png"' >/dev/null
+echo "$github_filter_output" | jq -e '.items[0].title == "Recently used"' >/dev/null
+echo "$github_filter_output" | jq -e '.items[1].icon.path == "assets/icon-github.png"' >/dev/null
+echo "$github_filter_output" | jq -e '.items[1].mods.shift.variables.open_project_forge_page == "pr"' >/dev/null
+echo "$github_filter_output" | jq -e '.items[1].mods.alt.variables.open_project_forge_page == "issues"' >/dev/null
+echo "$github_filter_output" | jq -e '.items[1].mods.ctrl.variables.open_project_forge_page == "ci"' >/dev/null
 
 gitlab_github_filter_output="$({
   PROJECT_DIRS="$project_root" \
@@ -114,6 +122,35 @@ EOS
 chmod +x "$vscode_home/.local/bin/code"
 env HOME="$vscode_home" VSCODE_PATH=\~/.local/bin/code \
   "$workflow_dir/scripts/action_open.sh" "$repo_path" >/dev/null
+goto_args="$(env VSCODE_PATH=/bin/echo open_project_goto="$repo_path/README.md:3:7" \
+  "$workflow_dir/scripts/action_open.sh" "$repo_path")"
+[[ "$goto_args" == "-g $repo_path/README.md:3:7" ]]
+workspace_file="$tmp_dir/stack.code-workspace"
+printf '{"folders":[]}\n' >"$workspace_file"
+workspace_args="$(env VSCODE_PATH=/bin/echo "$workflow_dir/scripts/action_open.sh" "$workspace_file")"
+[[ "$workspace_args" == "$workspace_file" ]]
+remote_args="$(env VSCODE_PATH=/bin/echo open_project_remote_uri="vscode-remote://ssh-remote+devbox/srv/api" \
+  "$workflow_dir/scripts/action_open.sh" "devbox:/srv/api")"
+[[ "$remote_args" == "--folder-uri vscode-remote://ssh-remote+devbox/srv/api" ]]
+if env VSCODE_PATH=/bin/echo "$workflow_dir/scripts/action_open.sh" "devbox:/srv/api" >/dev/null 2>&1; then
+  echo "error: remote spec without remote uri should be rejected" >&2
+  exit 1
+fi
+remote_filter_output="$({
+  PROJECT_DIRS="$project_root" \
+    USAGE_FILE="$usage_file" \
+    OPEN_PROJECT_REMOTE_PROJECTS="devbox:/srv/api" \
+    WORKFLOW_CLI_BIN="$repo_root/target/debug/workflow-cli" \
+    "$workflow_dir/scripts/script_filter.sh" "devbox"
+})"
+echo "$remote_filter_output" | jq -e '.items[0].arg == "devbox:/srv/api" and .items[0].variables.open_project_remote_uri == "vscode-remote://ssh-remote+devbox/srv/api"' >/dev/null
+grep_hint_output="$({
+  PROJECT_DIRS="$project_root" \
+    USAGE_FILE="$usage_file" \
+    WORKFLOW_CLI_BIN="$repo_root/target/debug/workflow-cli" \
+    "$workflow_dir/scripts/script_filter.sh" "grep x"
+})"
+echo "$grep_hint_output" | jq -e '.items[0].title == "Search projects with ripgrep" and .items[0].valid == false' >/dev/null
 recorded_path="$("$workflow_dir/scripts/action_record_usage.sh" "$repo_path")"
 [[ "$recorded_path" == "$repo_path" ]]
 
@@ -166,11 +203,23 @@ echo "$packaged_json" | jq -e '.objects[] | select(.uid=="FD59A9AB-0760-49CC-98D
 echo "$packaged_json" | jq -e '.objects[] | select(.uid=="FD59A9AB-0760-49CC-98D9-8B6A7CE43210") | .config.type == 8' >/dev/null
 echo "$packaged_json" | jq -e '.objects[] | select(.uid=="C74C273E-BE92-4960-9054-3577DC7878B4") | .config.scriptfile == "./scripts/action_open_github.sh"' >/dev/null
 echo "$packaged_json" | jq -e '.objects[] | select(.uid=="C74C273E-BE92-4960-9054-3577DC7878B4") | .config.type == 8' >/dev/null
+echo "$packaged_json" | jq -e '.objects[] | select(.uid=="422648CB-27AF-436E-87A6-F716EBCF1AB1") | .config.scriptfile == "./scripts/action_open_terminal.sh"' >/dev/null
+echo "$packaged_json" | jq -e '.objects[] | select(.uid=="422648CB-27AF-436E-87A6-F716EBCF1AB1") | .config.type == 8' >/dev/null
 echo "$packaged_json" | jq -e '[.objects[] | select(.type=="alfred.workflow.input.scriptfilter") | .config.keyword] | map(select(. != null)) | index("c") != null' >/dev/null
 echo "$packaged_json" | jq -e '[.objects[] | select(.type=="alfred.workflow.input.scriptfilter") | .config.keyword] | map(select(. != null)) | index("code") != null' >/dev/null
 echo "$packaged_json" | jq -e '[.objects[] | select(.type=="alfred.workflow.input.scriptfilter") | .config.keyword] | map(select(. != null)) | index("github") != null' >/dev/null
-echo "$packaged_json" | jq -e '.connections["6F5EB7A5-CDCD-4FDD-A04B-5FACC38B2F94"] | any(.modifiers == 1048576 and .destinationuid == "FD59A9AB-0760-49CC-98D9-8B6A7CE43210")' >/dev/null
-echo "$packaged_json" | jq -e '[.userconfigurationconfig[] | .variable] | sort == ["OPEN_PROJECT_MAX_RESULTS", "PROJECT_DIRS", "USAGE_FILE", "VSCODE_PATH"]' >/dev/null
-echo "$packaged_json" | jq -e '.userconfigurationconfig[] | select(.variable=="OPEN_PROJECT_MAX_RESULTS") | .config.default == "30"' >/dev/null
+echo "$packaged_json" | jq -e '.connections["6F5EB7A5-CDCD-4FDD-A04B-5FACC38B2F94"] | any(.modifiers == 131072 and .destinationuid == "FD59A9AB-0760-49CC-98D9-8B6A7CE43210")' >/dev/null
+for forge_page_modifier in 131072 524288 262144; do
+  echo "$packaged_json" | jq -e --argjson mods "$forge_page_modifier" '.connections["8F3399E3-951A-4DC0-BC7D-CFA83C1E1F76"] | any(.modifiers == $mods and .destinationuid == "FD59A9AB-0760-49CC-98D9-8B6A7CE43210")' >/dev/null
+done
+for terminal_source in 6F5EB7A5-CDCD-4FDD-A04B-5FACC38B2F94 E6B67FD5-5462-46F2-BB39-75F015526AA6 8F3399E3-951A-4DC0-BC7D-CFA83C1E1F76; do
+  echo "$packaged_json" | jq -e --arg src "$terminal_source" '.connections[$src] | any(.modifiers == 1048576 and .destinationuid == "422648CB-27AF-436E-87A6-F716EBCF1AB1")' >/dev/null
+done
+echo "$packaged_json" | jq -e '.connections["6F5EB7A5-CDCD-4FDD-A04B-5FACC38B2F94"] | any(.modifiers == 524288 and .destinationuid == "05AA5EAC-4638-4A25-B975-FE35FBEA8FA0")' >/dev/null
+echo "$packaged_json" | jq -e '.connections["E6B67FD5-5462-46F2-BB39-75F015526AA6"] | any(.modifiers == 262144 and .destinationuid == "05AA5EAC-4638-4A25-B975-FE35FBEA8FA0")' >/dev/null
+echo "$packaged_json" | jq -e '[.userconfigurationconfig[] | .variable] | sort == ["OPEN_PROJECT_EDITOR_2", "OPEN_PROJECT_EDITOR_3", "OPEN_PROJECT_FORGE_HOSTS", "OPEN_PROJECT_IGNORE_GLOBS", "OPEN_PROJECT_MARKERS", "OPEN_PROJECT_MAX_RESULTS", "OPEN_PROJECT_RECENT_COUNT", "OPEN_PROJECT_REMOTE_PROJECTS", "OPEN_PROJECT_RG_PATH", "OPEN_PROJECT_SCAN_DEPTH", "OPEN_PROJECT_SHOW_GIT_STATUS", "OPEN_PROJECT_TERMINAL_APP", "OPEN_PROJECT_WORKSPACE_MEMBERS", "PROJECT_DIRS", "USAGE_FILE", "VSCODE_PATH"]' >/dev/null
+for layered_var in PROJECT_DIRS VSCODE_PATH OPEN_PROJECT_MAX_RESULTS; do
+  echo "$packaged_json" | jq -e --arg var "$layered_var" '.userconfigurationconfig[] | select(.variable==$var) | .config.default == ""' >/dev/null
+done
 
 echo "ok: open-project smoke test"
@@ -64,6 +64,12 @@ print_error_item() {
   elif [[ "$lower" == *"unknown format"* || "$lower" == *"unsupported format"* ]]; then
     title="Unknown format"
     subtitle="$message"
+  elif [[ "$lower" == *" range: "* ]]; then
+    title="Invalid range"
+    subtitle="$message"
+  elif [[ "$lower" == *" argument: "* ]]; then
+    title="Invalid format argument"
+    subtitle="$message"
   elif [[ "$lower" == *"malformed alfred json"* ]]; then
     title="Randomer output format error"
     subtitle="randomer-cli returned malformed Alfred JSON."
@@ -96,11 +102,21 @@ resolve_randomer_cli() {
     "randomer-cli binary not found (checked RANDOMER_CLI_BIN/package/release/debug paths)"
 }
 
+resolve_count() {
+  local count="${RANDOMER_COUNT:-${randomer_count:-}}"
+  if [[ "$count" =~ ^[0-9]+$ && "$count" -gt 0 ]]; then
+    printf '%s' "$count"
+    return 0
+  fi
+
+  printf '10'
+}
+
 execute_generate() {
   local query="${1:-}"
   local randomer_cli
   randomer_cli="$(resolve_randomer_cli)"
-  "$randomer_cli" generate --format "$query" --count 10 --output alfred-json
+  "$randomer_cli" generate --format "$query" --count "$(resolve_count)" --output alfred-json
 }
 
 resolve_query() {
@@ -33,7 +33,7 @@ for required in \
   assert_file "$workflow_dir/$required"
 done
 
-for format_icon in email imei unit uuid int decimal percent currency hex otp phone; do
+for format_icon in email imei unit uuid int decimal percent currency hex otp phone password name fullprofile address lorem card iban mac ipv4 ipv6 date datetime color secret key256 jwt ulid nanoid ksuid slug hostname username template; do
   assert_file "$workflow_dir/src/assets/icons/${format_icon}.png"
 done
 
@@ -54,6 +54,18 @@ manifest="$workflow_dir/workflow.toml"
 [[ "$(toml_string "$manifest" rust_binary)" == "randomer-cli" ]] || fail "rust_binary must be randomer-cli"
 [[ "$(toml_string "$manifest" script_filter)" == "script_filter.sh" ]] || fail "script_filter mismatch"
 [[ "$(toml_string "$manifest" action)" == "action_open.sh" ]] || fail "action mismatch"
+if ! rg -n '^RANDOMER_MAX_COUNT[[:space:]]*=[[:space:]]*"50"' "$manifest" >/dev/null; then
+  fail "RANDOMER_MAX_COUNT default must be 50"
+fi
+if ! rg -n '^RANDOMER_PASSWORD_LENGTH[[:space:]]*=[[:space:]]*"20"' "$manifest" >/dev/null; then
+  fail "RANDOMER_PASSWORD_LENGTH default must be 20"
+fi
+if ! rg -n '^RANDOMER_IBAN_COUNTRIES[[:space:]]*=[[:space:]]*""' "$manifest" >/dev/null; then
+  fail "RANDOMER_IBAN_COUNTRIES default must be empty"
+fi
+if ! rg -n '^RANDOMER_SEED[[:space:]]*=[[:space:]]*""' "$manifest" >/dev/null; then
+  fail "RANDOMER_SEED default must be empty"
+fi
 
 tmp_dir="$(mktemp -d)"
 artifact_id="$(toml_string "$manifest" id)"
@@ -110,11 +122,11 @@ generate)
   format="${3:-}"
   [[ "${4:-}" == "--count" ]] || exit 9
   count="${5:-}"
-  [[ "$count" == "10" ]] || exit 9
+  [[ "$count" =~ ^[0-9]+$ ]] || exit 9
   printf '{"items":['
-  for ((i = 1; i <= 10; i++)); do
+  for ((i = 1; i <= count; i++)); do
     printf '{"title":"%s-%02d","subtitle":"%s","arg":"%s-%02d","valid":true}' "$format" "$i" "$format" "$format" "$i"
-    if [[ "$i" -lt 10 ]]; then
+    if [[ "$i" -lt "$count" ]]; then
       printf ','
     fi
   done
@@ -135,6 +147,14 @@ exit 7
 EOS
 chmod +x "$tmp_dir/stubs/randomer-cli-fail"
 
+cat >"$tmp_dir/stubs/randomer-cli-range-fail" <<'EOS'
+#!/usr/bin/env bash
+set -euo pipefail
+echo "error: invalid int range: 1000..1 (start is greater than end)" >&2
+exit 2
+EOS
+chmod +x "$tmp_dir/stubs/randomer-cli-range-fail"
+
 primary_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-ok" "$workflow_dir/scripts/script_filter.sh" "uuid"; })"
 assert_jq_json "$primary_json" '.items | type == "array" and length == 1' "primary script output must be Alfred items array"
 assert_jq_json "$primary_json" '.items[0].mods.cmd.arg == "uuid"' "primary script item must provide cmd modifier arg for expand"
@@ -153,6 +173,12 @@ expanded_env_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-ok" RANDOME
 assert_jq_json "$expanded_env_json" '.items | type == "array" and length == 10' "expanded script must support RANDOMER_FORMAT env fallback"
 assert_jq_json "$expanded_env_json" 'all(.items[]; .subtitle == "uuid")' "RANDOMER_FORMAT fallback subtitles must match format key"
 
+expanded_count_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-ok" RANDOMER_COUNT="25" "$workflow_dir/scripts/script_filter_expand.sh" "uuid"; })"
+assert_jq_json "$expanded_count_json" '.items | type == "array" and length == 25' "expanded script must pass RANDOMER_COUNT through to generate"
+
+expanded_bad_count_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-ok" RANDOMER_COUNT="lots" "$workflow_dir/scripts/script_filter_expand.sh" "uuid"; })"
+assert_jq_json "$expanded_bad_count_json" '.items | type == "array" and length == 10' "expanded script must fall back to 10 values for invalid RANDOMER_COUNT"
+
 expanded_query_env_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-ok" alfred_workflow_query="uuid" "$workflow_dir/scripts/script_filter_expand.sh"; })"
 assert_jq_json "$expanded_query_env_json" '.items | type == "array" and length == 10' "expanded script must support alfred_workflow_query fallback"
 assert_jq_json "$expanded_query_env_json" 'all(.items[]; .subtitle == "uuid")' "alfred_workflow_query fallback subtitles must match format key"
@@ -176,6 +202,9 @@ expand_failure_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-fail" "$w
 assert_jq_json "$expand_failure_json" '.items | type == "array" and length == 1' "expand failure fallback must output one item"
 assert_jq_json "$expand_failure_json" '.items[0].valid == false' "expand failure fallback item must be invalid"
 
+expand_range_json="$({ RANDOMER_CLI_BIN="$tmp_dir/stubs/randomer-cli-range-fail" "$workflow_dir/scripts/script_filter_expand.sh" "int 1000..1"; })"
+assert_jq_json "$expand_range_json" '.items[0].title == "Invalid range" and .items[0].valid == false' "expand range error must map to Invalid range item"
+
 cat >"$tmp_dir/bin/cargo" <<EOS
 #!/usr/bin/env bash
 set -euo pipefail
@@ -210,7 +239,7 @@ assert_file "$packaged_dir/bin/randomer-cli"
 assert_file "$artifact_path"
 assert_file "$artifact_sha_path"
 
-for format_icon in email imei unit uuid int decimal percent currency hex otp phone; do
+for format_icon in email imei unit uuid int decimal percent currency hex otp phone password name fullprofile address lorem card iban mac ipv4 ipv6 date datetime color secret key256 jwt ulid nanoid ksuid slug hostname username template; do
   assert_file "$packaged_dir/assets/icons/${format_icon}.png"
 done
 
@@ -225,6 +254,13 @@ assert_jq_file "$packaged_json_file" '.objects | length >= 4' "packaged plist mi
 assert_jq_file "$packaged_json_file" '.connections | length >= 3' "packaged plist missing connections"
 assert_jq_file "$packaged_json_file" '[.objects[] | select(.type=="alfred.workflow.trigger.hotkey")] | length == 3' "packaged plist must include exactly three hotkey triggers"
 
+assert_jq_file "$packaged_json_file" '[.userconfigurationconfig[] | .variable] == ["RANDOMER_MAX_COUNT", "RANDOMER_PASSWORD_LENGTH", "RANDOMER_PASSWORD_CLASSES", "RANDOMER_LOCALE", "RANDOMER_IBAN_COUNTRIES", "RANDOMER_SEED"]' "user configuration variables mismatch"
+assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="RANDOMER_MAX_COUNT") | .config.default == "50"' "RANDOMER_MAX_COUNT default must be 50"
+assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="RANDOMER_PASSWORD_LENGTH") | .config.default == "20"' "RANDOMER_PASSWORD_LENGTH default must be 20"
+assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="RANDOMER_LOCALE") | .config.default == ""' "RANDOMER_LOCALE default must be empty"
+assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="RANDOMER_IBAN_COUNTRIES") | .config.default == ""' "RANDOMER_IBAN_COUNTRIES default must be empty"
+assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="RANDOMER_SEED") | .config.default == ""' "RANDOMER_SEED default must be empty"
+
 assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$PRIMARY_UID\") | .config.scriptfile == \"./scripts/script_filter.sh\"" "primary script filter scriptfile mismatch"
 assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$PRIMARY_UID\") | .config.scriptargtype == 1" "primary script filter must pass query via argv"
 assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$TYPE_UID\") | .config.scriptfile == \"./scripts/script_filter_types.sh\"" "type selector scriptfile mismatch"
@@ -55,6 +55,12 @@ period_title() {
   hourly)
     printf 'Hourly'
     ;;
+  now)
+    printf 'Current'
+    ;;
+  history)
+    printf 'History'
+    ;;
   *)
     printf 'Weather'
     ;;
@@ -141,6 +147,12 @@ resolve_locale() {
   zh | zh-tw | zh-cn | zh-hant | zh-hans | chinese)
     printf 'zh'
     ;;
+  ja | ja-jp | japanese)
+    printf 'ja'
+    ;;
+  de | de-de | de-at | de-ch | german)
+    printf 'de'
+    ;;
   *)
     printf 'en'
     ;;
@@ -215,7 +227,7 @@ normalize_alfred_items() {
           (($meta.summary // "unknown weather") | if test("^[A-Za-z ]+$") then ascii_downcase else . end) as $summary
           |
           {
-              "title": ($display_location + " " + ($meta.temp_min_c_label // "?") + "~" + ($meta.temp_max_c_label // "?") + "°C " + $summary + " " + (($meta.precip_prob_max_pct_label // "?") + "%")),
+              "title": ($display_location + " " + ($meta.temp_min_label // $meta.temp_min_c_label // "?") + "~" + ($meta.temp_max_label // $meta.temp_max_c_label // "?") + ($meta.temp_unit // "°C") + " " + $summary + " " + (($meta.precip_prob_max_pct_label // "?") + "%")),
               "subtitle": (subtitle_date($meta) + subtitle_separator + $timezone_display + subtitle_separator + $lat + "," + $lon),
               "arg": (if (($item.arg // "") | length) == 0 then ($meta.date // ($item.title // "")) else $item.arg end),
               "valid": true,
@@ -232,7 +244,7 @@ normalize_alfred_items() {
           (($meta.summary // "unknown weather") | if test("^[A-Za-z ]+$") then ascii_downcase else . end) as $summary
           |
           {
-              "title": ($display_location + " " + ($meta.time // "") + " " + ($meta.temp_c_label // "?") + "°C " + $summary + " " + (($meta.precip_prob_pct_label // "?") + "%")),
+              "title": ($display_location + " " + ($meta.time // "") + " " + ($meta.temp_label // $meta.temp_c_label // "?") + ($meta.temp_unit // "°C") + " " + $summary + " " + (($meta.precip_prob_pct_label // "?") + "%")),
               "subtitle": (subtitle_date($meta) + subtitle_separator + $timezone_display + subtitle_separator + $lat + "," + $lon),
               "arg": (if (($item.arg // "") | length) == 0 then (($meta.datetime // "") | gsub("T"; " ")) else $item.arg end),
               "valid": true,
@@ -279,6 +291,8 @@ normalize_alfred_items() {
             .items[1:]
             | map(normalize_item(.; $display_location; $timezone; $lat; $lon))
           )
+      elif ((.items[0].weather_meta.item_kind // "") == "current" or (.items[0].weather_meta.item_kind // "") == "history") then
+        .
       elif ((.items[0].weather_meta.item_kind // "") == "daily" or (.items[0].weather_meta.item_kind // "") == "hourly") then
         .items |= map(
           (.weather_meta // {}) as $meta
@@ -304,7 +318,7 @@ period="${1:-}"
 query="${2:-}"
 
 case "$period" in
-today | week | hourly) ;;
+today | week | hourly | now | history) ;;
 *)
   emit_single_item "Weather workflow error" "Invalid period: $period" false
   exit 0
@@ -312,6 +326,18 @@ today | week | hourly) ;;
 esac
 
 trimmed_query="$(trim_query "$query")"
+period_args=("$period")
+
+# `history` queries lead with the date (`2024-05-01 Tokyo`); the rest resolves like any other location.
+if [[ "$period" == "history" ]]; then
+  if [[ ! "$trimmed_query" =~ ^([0-9]{4}-[0-9]{2}-[0-9]{2})([[:space:]]+(.*))?$ ]]; then
+    emit_single_item "History query needs a date" "Start with YYYY-MM-DD, then a city or lat,lon (for example 2024-05-01 Tokyo)." false
+    exit 0
+  fi
+  period_args+=(--date "${BASH_REMATCH[1]}")
+  trimmed_query="$(trim_query "${BASH_REMATCH[3]}")"
+fi
+
 output_locale="$(resolve_locale "${WEATHER_LOCALE:-$DEFAULT_LOCALE_FALLBACK}")"
 
 err_file="${TMPDIR:-/tmp}/weather-script-filter.err.$$"
@@ -328,7 +354,7 @@ if [[ -n "$trimmed_query" ]] && lat_lon="$(parse_lat_lon "$trimmed_query")"; the
   lat="$(printf '%s\n' "$lat_lon" | sed -n '1p')"
   lon="$(printf '%s\n' "$lat_lon" | sed -n '2p')"
 
-  if json_output="$("$weather_cli" "$period" --output alfred-json --lang "$output_locale" --lat="$lat" --lon="$lon" 2>"$err_file")"; then
+  if json_output="$("$weather_cli" "${period_args[@]}" --output alfred-json --lang "$output_locale" --lat="$lat" --lon="$lon" 2>"$err_file")"; then
     if [[ -z "$json_output" ]]; then
       print_error_item "$period" "weather-cli returned empty response"
       exit 0
@@ -348,6 +374,32 @@ if [[ -n "$trimmed_query" ]] && lat_lon="$(parse_lat_lon "$trimmed_query")"; the
   exit 0
 fi
 
+# `now` skips the favorites picker so a hotkey lands directly on current conditions; `history` skips it
+# because picking a favorite would drop the date prefix.
+favorite_locations="$(trim_query "${WEATHER_LOCATIONS:-}")"
+if [[ -z "$trimmed_query" && -n "$favorite_locations" && "$period" != "now" && "$period" != "history" ]]; then
+  if json_output="$("$weather_cli" locations --output alfred-json --lang "$output_locale" 2>"$err_file")"; then
+    printf '%s\n' "$json_output"
+    exit 0
+  fi
+
+  err_msg="$(cat "$err_file")"
+  print_error_item "$period" "$err_msg"
+  exit 0
+fi
+
+# Opt-in IP geolocation: weather-cli locates the caller when no location flag is given; on failure the
+# default cities below still apply.
+auto_locate="$(trim_query "${WEATHER_AUTO_LOCATE:-}")"
+if [[ -z "$trimmed_query" && "$auto_locate" =~ ^(1|true|yes|on)$ ]]; then
+  if json_output="$("$weather_cli" "${period_args[@]}" --output alfred-json --lang "$output_locale" 2>"$err_file")" &&
+    [[ -n "$json_output" ]] &&
+    normalized_output="$(normalize_alfred_items "$json_output" 2>/dev/null)"; then
+    printf '%s\n' "$normalized_output"
+    exit 0
+  fi
+fi
+
 city_csv="$trimmed_query"
 if [[ -z "$city_csv" ]]; then
   city_csv="$(trim_query "${WEATHER_DEFAULT_CITIES:-$DEFAULT_CITY_FALLBACK}")"
@@ -359,14 +411,14 @@ if [[ ${#city_targets[@]} -eq 0 ]]; then
   city_targets=("$DEFAULT_CITY_FALLBACK")
 fi
 
-if [[ ${#city_targets[@]} -gt 1 ]] && [[ "$period" == "hourly" ]] && ! command -v jq >/dev/null 2>&1; then
+if [[ ${#city_targets[@]} -gt 1 ]] && [[ "$period" == "hourly" || "$period" == "now" || "$period" == "history" ]] && ! command -v jq >/dev/null 2>&1; then
   emit_single_item "Missing jq for multi-city mode" "Install jq or query a single city." false
   exit 0
 fi
 
 if [[ ${#city_targets[@]} -eq 1 ]]; then
   city="${city_targets[0]}"
-  if json_output="$("$weather_cli" "$period" --output alfred-json --lang "$output_locale" --city "$city" 2>"$err_file")"; then
+  if json_output="$("$weather_cli" "${period_args[@]}" --output alfred-json --lang "$output_locale" --city "$city" 2>"$err_file")"; then
     if [[ -z "$json_output" ]]; then
       print_error_item "$period" "weather-cli returned empty response"
       exit 0
@@ -386,7 +438,7 @@ if [[ ${#city_targets[@]} -eq 1 ]]; then
   exit 0
 fi
 
-if [[ "$period" != "hourly" ]]; then
+if [[ "$period" == "today" || "$period" == "week" ]]; then
   cli_args=("$period" --output alfred-json --lang "$output_locale")
   for city in "${city_targets[@]}"; do
     cli_args+=(--city "$city")
@@ -419,7 +471,7 @@ fi
 
 item_arrays=()
 for city in "${city_targets[@]}"; do
-  if json_output="$("$weather_cli" "$period" --output alfred-json --lang "$output_locale" --city "$city" 2>"$err_file")"; then
+  if json_output="$("$weather_cli" "${period_args[@]}" --output alfred-json --lang "$output_locale" --city "$city" 2>"$err_file")"; then
     if [[ -z "$json_output" ]]; then
       message="weather-cli returned empty response"
       error_item="$(jq -nc --arg city "$city" --arg message "$message" '{title: ($city + ": forecast error"), subtitle: $message, valid: false}')"
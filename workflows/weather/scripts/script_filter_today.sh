@@ -45,8 +45,8 @@ present_today_with_hourly_token() {
         | (.subtitle // "") as $subtitle
         | (
             if (($meta.location_name // "") | length) > 0 then $meta.location_name
-            elif ($title | test("^(?<location>.+) -?[0-9]+(?:\\.[0-9]+)?~-?[0-9]+(?:\\.[0-9]+)?°C .+ [0-9?]+%$"))
-            then ($title | capture("^(?<location>.+) -?[0-9]+(?:\\.[0-9]+)?~-?[0-9]+(?:\\.[0-9]+)?°C .+ [0-9?]+%$")).location
+            elif ($title | test("^(?<location>.+) -?[0-9]+(?:\\.[0-9]+)?~-?[0-9]+(?:\\.[0-9]+)?°[CF] .+ [0-9?]+%$"))
+            then ($title | capture("^(?<location>.+) -?[0-9]+(?:\\.[0-9]+)?~-?[0-9]+(?:\\.[0-9]+)?°[CF] .+ [0-9?]+%$")).location
             else null
             end
           ) as $location
@@ -168,5 +168,11 @@ if [[ "$trimmed_query" == "${COORD_TOKEN_PREFIX}"* ]]; then
   exit 0
 fi
 
+# A leading YYYY-MM-DD switches to the historical view; its single row is already actionable.
+if [[ "$trimmed_query" =~ ^[0-9]{4}-[0-9]{2}-[0-9]{2}([[:space:]]|$) ]]; then
+  "$script_dir/script_filter_common.sh" history "$trimmed_query"
+  exit 0
+fi
+
 today_json="$("$script_dir/script_filter_common.sh" today "$trimmed_query")"
 present_today_with_hourly_token "$today_json"
@@ -16,6 +16,8 @@ source "$smoke_helper"
 
 TODAY_UID="B1A11A4C-5F5D-4E8D-8E68-2AD5A95E95E1"
 WEEK_UID="8A72E2AF-189E-4A13-9A0E-B30FEAF37F9A"
+NOW_UID="C9A9E5A1-5298-46B7-A554-DA4E122BF812"
+NOW_HOTKEY_UID="D4E636D5-C84B-4C77-AA8A-9C96041CEB86"
 ACTION_UID="E7A2F2B8-9BB0-4F7A-A2A9-9074CBF90AA0"
 
 for required in \
@@ -28,6 +30,7 @@ for required in \
   scripts/generate_weather_icons.sh \
   scripts/script_filter_today.sh \
   scripts/script_filter_week.sh \
+  scripts/script_filter_now.sh \
   scripts/action_copy.sh \
   tests/smoke.sh; do
   assert_file "$workflow_dir/$required"
@@ -77,6 +80,7 @@ for executable in \
   scripts/script_filter_common.sh \
   scripts/script_filter_today.sh \
   scripts/script_filter_week.sh \
+  scripts/script_filter_now.sh \
   scripts/action_copy.sh \
   tests/smoke.sh; do
   assert_exec "$workflow_dir/$executable"
@@ -121,6 +125,18 @@ if ! rg -n '^WEATHER_CACHE_TTL_SECS[[:space:]]*=[[:space:]]*"900"' "$manifest" >
   fail "WEATHER_CACHE_TTL_SECS default must be 900"
 fi
 
+if ! rg -n '^WEATHER_UNITS[[:space:]]*=[[:space:]]*"metric"' "$manifest" >/dev/null; then
+  fail "WEATHER_UNITS default must be metric"
+fi
+
+if ! rg -n '^WEATHER_LOCATIONS[[:space:]]*=[[:space:]]*""' "$manifest" >/dev/null; then
+  fail "WEATHER_LOCATIONS default must be empty"
+fi
+
+if ! rg -n '^WEATHER_AUTO_LOCATE[[:space:]]*=[[:space:]]*"0"' "$manifest" >/dev/null; then
+  fail "WEATHER_AUTO_LOCATE default must be 0 (opt-in)"
+fi
+
 tmp_dir="$(mktemp -d)"
 artifact_id="$(toml_string "$manifest" id)"
 artifact_version="$(toml_string "$manifest" version)"
@@ -162,6 +178,7 @@ lang=""
 cities=()
 lat=""
 lon=""
+date=""
 
 while [[ $# -gt 0 ]]; do
   case "$1" in
@@ -169,6 +186,10 @@ while [[ $# -gt 0 ]]; do
     mode="${2:-}"
     shift 2
     ;;
+  --date)
+    date="${2:-}"
+    shift 2
+    ;;
   --city)
     cities+=("${2:-}")
     shift 2
@@ -697,6 +718,58 @@ if [[ "$period" == "today" ]]; then
   exit 0
 fi
 
+if [[ "$period" == "now" ]]; then
+  current_icon_key="$(current_icon_key_for_summary "$summary_en")"
+  jq -nc \
+    --arg location "$location" \
+    --arg summary "$summary" \
+    --arg icon_key "$current_icon_key" \
+    '{
+      items: [
+        {
+          title: ($location + " 14.0°C " + $summary),
+          subtitle: "feels 13.0°C • humidity 70% • wind 12.0 km/h NE • 2026-02-12 10:00 • source=open_meteo freshness=live",
+          arg: ($location + " 14.0°C " + $summary),
+          valid: true,
+          icon: {
+            path: ("assets/icons/weather/" + $icon_key + ".png")
+          },
+          weather_meta: {
+            item_kind: "current",
+            location_name: $location,
+            summary: $summary,
+            icon_key: $icon_key
+          }
+        }
+      ]
+    }'
+  exit 0
+fi
+
+if [[ "$period" == "history" ]]; then
+  [[ "$date" =~ ^[0-9]{4}-[0-9]{2}-[0-9]{2}$ ]] || exit 9
+  jq -nc \
+    --arg location "$location" \
+    --arg date "$date" \
+    --arg summary "$summary" \
+    '{
+      items: [
+        {
+          title: ($location + " " + $date + " Wed 11.0~19.0°C " + $summary),
+          subtitle: "mean 15.0°C • precip 2.4 mm over 3 h • wind 20.0 km/h • source=open_meteo_archive freshness=live",
+          arg: ($location + " " + $date + " Wed 11.0~19.0°C " + $summary),
+          valid: true,
+          weather_meta: {
+            item_kind: "history",
+            location_name: $location,
+            date: $date
+          }
+        }
+      ]
+    }'
+  exit 0
+fi
+
 exit 9
 EOS
 chmod +x "$tmp_dir/stubs/weather-cli-ok"
@@ -936,6 +1009,31 @@ assert_jq_json "$today_coordinate_stage_two_json" '.items | type == "array" and
 assert_jq_json "$today_coordinate_stage_two_json" '.items[0].title == "25.03,121.56 00:00 12.0°C cloudy 10%"' "today coordinate stage two should include coordinate location without weekday in title"
 assert_jq_json "$today_coordinate_stage_two_json" '.items[0].subtitle == "Thu, Feb 12 • Asia/Taipei (UTC+8) • 25.03,121.56"' "today coordinate stage two subtitle should show timezone with UTC offset"
 
+now_city_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" "$workflow_dir/scripts/script_filter_now.sh" "Taipei"; })"
+assert_jq_json "$now_city_json" '.items | type == "array" and length == 1' "now query should return one current-conditions row"
+assert_jq_json "$now_city_json" '.items[0].title == "Taipei 14.0°C Cloudy"' "now row title should pass through weather-cli current title"
+assert_jq_json "$now_city_json" '.items[0].valid == true and .items[0].arg == "Taipei 14.0°C Cloudy"' "now row must stay actionable for copy"
+
+now_hotkey_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" WEATHER_LOCATIONS="home=Taipei" "$workflow_dir/scripts/script_filter_now.sh" "  "; })"
+assert_jq_json "$now_hotkey_json" '(.items | length == 1) and .items[0].title == "Tokyo 14.0°C Cloudy"' "empty now query should skip favorites picker and use default city"
+
+now_multi_city_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" "$workflow_dir/scripts/script_filter_now.sh" "Taipei,Tokyo"; })"
+assert_jq_json "$now_multi_city_json" '[.items[].title] == ["Taipei 14.0°C Cloudy", "Tokyo 14.0°C Cloudy"]' "multi-city now query should emit one row per city"
+
+history_city_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" "$workflow_dir/scripts/script_filter_today.sh" "2024-05-01 Taipei"; })"
+assert_jq_json "$history_city_json" '.items | type == "array" and length == 1' "date-prefixed today query should return one history row"
+assert_jq_json "$history_city_json" '.items[0].title == "Taipei 2024-05-01 Wed 11.0~19.0°C Cloudy"' "history row should pass date and city through to weather-cli history"
+assert_jq_json "$history_city_json" '.items[0].valid == true and .items[0].weather_meta.item_kind == "history"' "history row must stay actionable for copy"
+
+history_coordinate_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" "$workflow_dir/scripts/script_filter_today.sh" "2024-05-01 25.03,121.56"; })"
+assert_jq_json "$history_coordinate_json" '.items[0].title == "25.03,121.56 2024-05-01 Wed 11.0~19.0°C Cloudy"' "history query should accept lat,lon after the date"
+
+history_default_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" WEATHER_LOCATIONS="home=Taipei" "$workflow_dir/scripts/script_filter_today.sh" "2024-05-01"; })"
+assert_jq_json "$history_default_json" '(.items | length == 1) and .items[0].title == "Tokyo 2024-05-01 Wed 11.0~19.0°C Cloudy"' "date-only history query should use default city instead of favorites picker"
+
+history_missing_date_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-ok" "$workflow_dir/scripts/script_filter_common.sh" history "Taipei"; })"
+assert_jq_json "$history_missing_date_json" '.items[0].title == "History query needs a date" and .items[0].valid == false' "history without leading date should explain the query format"
+
 invalid_json="$({ WEATHER_CLI_BIN="$tmp_dir/stubs/weather-cli-invalid" "$workflow_dir/scripts/script_filter_today.sh" "city::Taipei"; })"
 assert_jq_json "$invalid_json" '.items[0].title == "Invalid location input"' "invalid input title mapping mismatch"
 assert_jq_json "$invalid_json" '.items[0].valid == false' "invalid fallback item must be invalid"
@@ -1063,10 +1161,17 @@ assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$WEEK_UID\") |
 assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$WEEK_UID\") | .config.keyword == \"ww\"" "week keyword must be ww"
 assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$WEEK_UID\") | .config.scriptargtype == 1" "week script filter must pass query via argv"
 
+assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$NOW_UID\") | .config.scriptfile == \"./scripts/script_filter_now.sh\"" "now script filter scriptfile mismatch"
+assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$NOW_UID\") | .config.keyword == \"wn\"" "now keyword must be wn"
+assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$NOW_UID\") | .config.scriptargtype == 1" "now script filter must pass query via argv"
+assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$NOW_HOTKEY_UID\") | .type == \"alfred.workflow.trigger.hotkey\"" "now hotkey trigger missing"
+
 assert_jq_file "$packaged_json_file" ".objects[] | select(.uid==\"$ACTION_UID\") | .config.scriptfile == \"./scripts/action_copy.sh\"" "copy action scriptfile mismatch"
 
 assert_jq_file "$packaged_json_file" ".connections[\"$TODAY_UID\"] | any(.destinationuid == \"$ACTION_UID\" and .modifiers == 0)" "missing today->copy enter connection"
 assert_jq_file "$packaged_json_file" ".connections[\"$WEEK_UID\"] | any(.destinationuid == \"$ACTION_UID\" and .modifiers == 0)" "missing week->copy enter connection"
+assert_jq_file "$packaged_json_file" ".connections[\"$NOW_UID\"] | any(.destinationuid == \"$ACTION_UID\" and .modifiers == 0)" "missing now->copy enter connection"
+assert_jq_file "$packaged_json_file" ".connections[\"$NOW_HOTKEY_UID\"] | any(.destinationuid == \"$NOW_UID\")" "missing now hotkey->now script filter connection"
 
 assert_jq_file "$packaged_json_file" '[.userconfigurationconfig[] | .variable] | sort == ["WEATHER_CACHE_TTL_SECS", "WEATHER_CLI_BIN", "WEATHER_DEFAULT_CITIES", "WEATHER_LOCALE"]' "user configuration variables mismatch"
 assert_jq_file "$packaged_json_file" '.userconfigurationconfig[] | select(.variable=="WEATHER_CLI_BIN") | .config.required == false' "WEATHER_CLI_BIN must be optional"